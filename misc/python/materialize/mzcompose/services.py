@@ -626,6 +626,108 @@ class MinioMc(Service):
         )
 
 
+class Elasticsearch(Service):
+    def __init__(
+        self,
+        name: str = "elasticsearch",
+        image: str = "docker.elastic.co/elasticsearch/elasticsearch:7.17.7",
+        port: int = 9200,
+        environment: List[str] = [
+            "discovery.type=single-node",
+            "xpack.security.enabled=false",
+            "ES_JAVA_OPTS=-Xms512m -Xmx512m",
+        ],
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "environment": environment,
+            },
+        )
+
+
+class Mosquitto(Service):
+    def __init__(
+        self,
+        name: str = "mosquitto",
+        image: str = "eclipse-mosquitto:2.0.15",
+        port: int = 1883,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "command": "mosquitto -c /mosquitto-no-auth.conf",
+            },
+        )
+
+
+class Nats(Service):
+    def __init__(
+        self,
+        name: str = "nats",
+        image: str = "nats:2.9.8",
+        port: int = 4222,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "command": "--jetstream",
+            },
+        )
+
+
+class NatsBox(Service):
+    def __init__(
+        self,
+        name: str = "nats-box",
+        image: str = "natsio/nats-box:0.13.2",
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={"image": image, "environment": ["NATS_URL=nats://nats:4222"]},
+        )
+
+
+class Redis(Service):
+    def __init__(
+        self,
+        name: str = "redis",
+        image: str = "redis:7.0.5",
+        port: int = 6379,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+            },
+        )
+
+
+class Sftp(Service):
+    def __init__(
+        self,
+        name: str = "sftp",
+        image: str = "atmoz/sftp:alpine",
+        port: int = 22,
+        users: List[str] = ["feeds:password:::inbox"],
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "command": " ".join(users),
+            },
+        )
+
+
 class Testdrive(Service):
     def __init__(
         self,
@@ -37,6 +37,7 @@ use mz_compute_client::controller::{
     ComputeReplicaLocation, ComputeReplicaLogging,
 };
 use mz_compute_client::logging::{LogVariant, LogView, DEFAULT_LOG_VARIANTS, DEFAULT_LOG_VIEWS};
+use mz_compute_client::sinks::PersistSinkBatching;
 use mz_expr::{MirScalarExpr, OptimizedMirRelationExpr};
 use mz_ore::collections::CollectionExt;
 use mz_ore::metrics::MetricsRegistry;
@@ -49,8 +50,8 @@ use mz_sql::ast::Expr;
 use mz_sql::catalog::{
     CatalogComputeInstance, CatalogDatabase, CatalogError as SqlCatalogError,
     CatalogItem as SqlCatalogItem, CatalogItemType as SqlCatalogItemType, CatalogItemType,
-    CatalogSchema, CatalogType, CatalogTypeDetails, IdReference, NameReference, SessionCatalog,
-    TypeReference,
+    CatalogSchema, CatalogType, CatalogTypeDetails, IdReference, NameReference, Privilege,
+    SessionCatalog, TypeReference,
 };
 use mz_sql::names::{
     Aug, DatabaseId, FullObjectName, ObjectQualifiers, PartialObjectName, QualifiedObjectName,
@@ -58,16 +59,22 @@ use mz_sql::names::{
     SchemaSpecifier,
 };
 use mz_sql::plan::{
-    AlterOptionParameter, CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
-    StorageHostConfig as PlanStorageHostConfig,
+    AlterOptionParameter, AlterSinkRuntimeOptions, CreateConnectionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
+    CreateTablePlan, CreateTypePlan, CreateViewPlan, ObjectId, Params, Plan, PlanContext,
+    StatementDesc, StorageHostConfig as PlanStorageHostConfig,
 };
 use mz_sql::{plan, DEFAULT_SCHEMA};
-use mz_sql_parser::ast::{CreateSinkOption, CreateSourceOption, Statement, WithOptionValue};
+use mz_sql_parser::ast::{
+    CreateSinkConnection, CreateSinkOption, CreateSinkStatement, CreateSourceOption,
+    KafkaConfigOption, KafkaConfigOptionName, KafkaConnection, Raw, Statement, WithOptionValue,
+};
 use mz_stash::{Append, Postgres, Sqlite};
 use mz_storage::types::hosts::{StorageHostConfig, StorageHostResourceAllocation};
-use mz_storage::types::sinks::{SinkEnvelope, StorageSinkConnection, StorageSinkConnectionBuilder};
+use mz_storage::types::sinks::{
+    KafkaSinkCompressionType, SinkColumnMask, SinkEnvelope, SinkRateLimit, StorageSinkConnection,
+    StorageSinkConnectionBuilder,
+};
 use mz_storage::types::sources::{SourceDesc, Timeline};
 use mz_transform::Optimizer;
 
@@ -80,6 +87,7 @@ pub use crate::catalog::config::{ClusterReplicaSizeMap, Config, StorageHostSizeM
 pub use crate::catalog::error::{AmbiguousRename, Error, ErrorKind};
 use crate::catalog::storage::{BootstrapArgs, Transaction};
 use crate::client::ConnectionId;
+use crate::coord::DEFAULT_LOGICAL_COMPACTION_WINDOW_MS;
 use crate::session::vars::SystemVars;
 use crate::session::{PreparedStatement, Session, User, DEFAULT_DATABASE_NAME};
 use crate::util::index_sql;
@@ -97,6 +105,9 @@ pub mod storage;
 
 pub const SYSTEM_CONN_ID: ConnectionId = 0;
 
+/// The ID of the `mz_system` role, which owns all system objects.
+pub const MZ_SYSTEM_ROLE_ID: RoleId = RoleId::System(1);
+
 pub static SYSTEM_USER: Lazy<User> = Lazy::new(|| User {
     name: "mz_system".into(),
     external_metadata: None,
@@ -572,6 +583,8 @@ impl CatalogState {
             id,
             oid,
             used_by: Vec::new(),
+            privileges: BTreeMap::new(),
+            owner_id: MZ_SYSTEM_ROLE_ID,
         };
         for u in entry.uses() {
             match self.entry_by_id.get_mut(u) {
@@ -1286,6 +1299,7 @@ pub struct Database {
     pub oid: u32,
     pub schemas_by_id: BTreeMap<SchemaId, Schema>,
     pub schemas_by_name: BTreeMap<String, SchemaId>,
+    pub owner_id: RoleId,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -1296,6 +1310,7 @@ pub struct Schema {
     pub oid: u32,
     pub items: BTreeMap<String, GlobalId>,
     pub functions: BTreeMap<String, GlobalId>,
+    pub owner_id: RoleId,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -1304,12 +1319,23 @@ pub struct Role {
     pub id: RoleId,
     #[serde(skip)]
     pub oid: u32,
+    pub superuser: bool,
+    /// The privileges granted to this role on schemas.
+    pub schema_privileges: BTreeMap<SchemaId, BTreeSet<Privilege>>,
 }
 
 impl Role {
     pub fn is_user(&self) -> bool {
         self.id.is_user()
     }
+
+    /// Reports whether this role has been granted `privilege` on the schema
+    /// identified by `schema_id`.
+    pub fn has_schema_privilege(&self, schema_id: &SchemaId, privilege: Privilege) -> bool {
+        self.schema_privileges
+            .get(schema_id)
+            .map_or(false, |privileges| privileges.contains(&privilege))
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -1337,6 +1363,8 @@ pub struct CatalogEntry {
     id: GlobalId,
     oid: u32,
     name: QualifiedObjectName,
+    privileges: BTreeMap<RoleId, BTreeSet<Privilege>>,
+    owner_id: RoleId,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1380,6 +1408,13 @@ pub enum DataSourceDesc {
     Source,
     /// Receives introspection data from an internal system
     Introspection(IntrospectionType),
+    /// Receives data from requests to the source's webhook endpoint
+    Webhook {
+        /// The secret with which each request must be signed, if any
+        secret: Option<GlobalId>,
+        /// The predicate that the body of each request must satisfy, if any
+        check: Option<MirScalarExpr>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1395,7 +1430,9 @@ impl Source {
     pub fn size(&self) -> Option<&str> {
         match &self.data_source {
             DataSourceDesc::Ingestion(Ingestion { host_config, .. }) => host_config.size(),
-            DataSourceDesc::Introspection(_) | DataSourceDesc::Source => None,
+            DataSourceDesc::Introspection(_)
+            | DataSourceDesc::Source
+            | DataSourceDesc::Webhook { .. } => None,
         }
     }
 }
@@ -1433,6 +1470,13 @@ pub struct Sink {
     pub with_snapshot: bool,
     pub depends_on: Vec<GlobalId>,
     pub host_config: StorageHostConfig,
+    pub catch_up_limit: Option<SinkRateLimit>,
+    pub rate_limit: Option<SinkRateLimit>,
+    pub projection: Vec<usize>,
+    pub column_masks: Vec<SinkColumnMask>,
+    pub include_timestamp: bool,
+    pub include_diff: bool,
+    pub filter: Option<MirScalarExpr>,
 }
 
 impl Sink {
@@ -1466,6 +1510,10 @@ pub struct MaterializedView {
     pub desc: RelationDesc,
     pub depends_on: Vec<GlobalId>,
     pub compute_instance: ComputeInstanceId,
+    pub batching: PersistSinkBatching,
+    /// The logical compaction window of the materialized view's storage
+    /// collection. `None` disables logical compaction entirely.
+    pub compaction_window_ms: Option<mz_repr::Timestamp>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1568,7 +1616,9 @@ impl CatalogItem {
         match &self {
             CatalogItem::Source(source) => match &source.data_source {
                 DataSourceDesc::Ingestion(ingestion) => Ok(Some(&ingestion.desc)),
-                DataSourceDesc::Source | DataSourceDesc::Introspection(_) => Ok(None),
+                DataSourceDesc::Source
+                | DataSourceDesc::Introspection(_)
+                | DataSourceDesc::Webhook { .. } => Ok(None),
             },
             _ => Err(SqlCatalogError::UnexpectedType(
                 name.item.clone(),
@@ -1639,6 +1689,22 @@ impl CatalogItem {
         self.conn_id().is_some()
     }
 
+    /// Returns the privileges that the owner of this item is implicitly
+    /// granted, unless it is a superuser.
+    pub(crate) fn owner_privileges(&self) -> BTreeSet<Privilege> {
+        if self.is_temporary() {
+            return BTreeSet::new();
+        }
+        let privileges = match self {
+            CatalogItem::Source(Source {
+                data_source: DataSourceDesc::Webhook { .. },
+                ..
+            }) => Privilege::for_webhook_source(),
+            _ => Privilege::for_item_type(self.typ()),
+        };
+        privileges.iter().copied().collect()
+    }
+
     /// Returns a clone of `self` with all instances of `from` renamed to `to`
     /// (with the option of including the item's own name) or errors if request
     /// is ambiguous.
@@ -1789,6 +1855,18 @@ impl CatalogEntry {
         matches!(self.item(), CatalogItem::Source(_))
     }
 
+    /// Reports whether this catalog entry is a source that receives its data
+    /// from requests to its webhook endpoint.
+    pub fn is_webhook_source(&self) -> bool {
+        matches!(
+            self.item(),
+            CatalogItem::Source(Source {
+                data_source: DataSourceDesc::Webhook { .. },
+                ..
+            })
+        )
+    }
+
     /// Reports whether this catalog entry is a sink.
     pub fn is_sink(&self) -> bool {
         matches!(self.item(), CatalogItem::Sink(_))
@@ -1835,6 +1913,23 @@ impl CatalogEntry {
         &self.used_by
     }
 
+    /// Returns the privileges granted on this item, by grantee.
+    pub fn privileges(&self) -> &BTreeMap<RoleId, BTreeSet<Privilege>> {
+        &self.privileges
+    }
+
+    /// Returns the ID of the role that owns this item.
+    pub fn owner_id(&self) -> RoleId {
+        self.owner_id
+    }
+
+    /// Reports whether `role_id` has been granted `privilege` on this item.
+    pub fn has_privilege(&self, role_id: &RoleId, privilege: Privilege) -> bool {
+        self.privileges
+            .get(role_id)
+            .map_or(false, |privileges| privileges.contains(&privilege))
+    }
+
     /// Returns the connection ID that this item belongs to, if this item is
     /// temporary.
     pub fn conn_id(&self) -> Option<ConnectionId> {
@@ -1906,6 +2001,10 @@ pub struct BuiltinMigrationMetadata {
     pub migrated_system_table_mappings: HashMap<GlobalId, SystemObjectMapping>,
     pub user_drop_ops: Vec<GlobalId>,
     pub user_create_ops: Vec<(GlobalId, SchemaId, String)>,
+    // Used to carry over the privileges of recreated user objects
+    pub user_item_privileges: HashMap<GlobalId, BTreeMap<RoleId, BTreeSet<Privilege>>>,
+    // Used to carry over the owners of recreated user objects
+    pub user_item_owners: HashMap<GlobalId, RoleId>,
 }
 
 impl BuiltinMigrationMetadata {
@@ -1920,6 +2019,8 @@ impl BuiltinMigrationMetadata {
             migrated_system_table_mappings: HashMap::new(),
             user_drop_ops: Vec::new(),
             user_create_ops: Vec::new(),
+            user_item_privileges: HashMap::new(),
+            user_item_owners: HashMap::new(),
         }
     }
 }
@@ -2005,7 +2106,7 @@ impl<S: Append> Catalog<S> {
         catalog.create_temporary_schema(SYSTEM_CONN_ID)?;
 
         let databases = catalog.storage().await.load_databases().await?;
-        for (id, name) in databases {
+        for (id, name, owner_id) in databases {
             let oid = catalog.allocate_oid()?;
             catalog.state.database_by_id.insert(
                 id.clone(),
@@ -2015,6 +2116,7 @@ impl<S: Append> Catalog<S> {
                     oid,
                     schemas_by_id: BTreeMap::new(),
                     schemas_by_name: BTreeMap::new(),
+                    owner_id,
                 },
             );
             catalog
@@ -2024,7 +2126,7 @@ impl<S: Append> Catalog<S> {
         }
 
         let schemas = catalog.storage().await.load_schemas().await?;
-        for (schema_id, schema_name, database_id) in schemas {
+        for (schema_id, schema_name, database_id, owner_id) in schemas {
             let oid = catalog.allocate_oid()?;
             let (schemas_by_id, schemas_by_name, database_spec) = match &database_id {
                 Some(database_id) => {
@@ -2056,13 +2158,14 @@ impl<S: Append> Catalog<S> {
                     oid,
                     items: BTreeMap::new(),
                     functions: BTreeMap::new(),
+                    owner_id,
                 },
             );
             schemas_by_name.insert(schema_name.clone(), schema_id);
         }
 
         let roles = catalog.storage().await.load_roles().await?;
-        for (id, name) in roles {
+        for (id, name, superuser, schema_privileges) in roles {
             let oid = catalog.allocate_oid()?;
             catalog.state.roles.insert(
                 name.clone(),
@@ -2070,6 +2173,8 @@ impl<S: Append> Catalog<S> {
                     name: name.clone(),
                     id,
                     oid,
+                    superuser,
+                    schema_privileges,
                 },
             );
         }
@@ -2418,6 +2523,7 @@ impl<S: Append> Catalog<S> {
         }
         for (_name, role) in &catalog.state.roles {
             builtin_table_updates.push(catalog.state.pack_role_update(role, 1));
+            builtin_table_updates.extend(catalog.state.pack_role_privileges_update(role, 1));
         }
         for (name, id) in &catalog.state.compute_instances_by_name {
             builtin_table_updates.push(catalog.state.pack_compute_instance_update(name, 1));
@@ -2723,6 +2829,14 @@ impl<S: Append> Catalog<S> {
                 migration_metadata
                     .user_create_ops
                     .push((new_id, schema_id, name.item.clone()));
+                if !entry.privileges.is_empty() {
+                    migration_metadata
+                        .user_item_privileges
+                        .insert(new_id, entry.privileges.clone());
+                }
+                migration_metadata
+                    .user_item_owners
+                    .insert(new_id, entry.owner_id);
             }
             let item_rebuilder = CatalogItemRebuilder::new(entry, new_id, &ancestor_ids);
             migration_metadata
@@ -2754,6 +2868,13 @@ impl<S: Append> Catalog<S> {
         for (id, oid, name, item_rebuilder) in migration_metadata.all_create_ops.drain(..) {
             let item = item_rebuilder.build(self);
             self.state.insert_item(id, oid, name, item);
+            let entry = self.state.entry_by_id.get_mut(&id).unwrap();
+            if let Some(privileges) = migration_metadata.user_item_privileges.get(&id) {
+                entry.privileges = privileges.clone();
+            }
+            if let Some(owner_id) = migration_metadata.user_item_owners.get(&id) {
+                entry.owner_id = *owner_id;
+            }
         }
         for (compute_instance, updates) in migration_metadata
             .introspection_source_index_updates
@@ -2786,7 +2907,11 @@ impl<S: Append> Catalog<S> {
         for (id, schema_id, name) in migration_metadata.user_create_ops.drain(..) {
             let item = self.get_entry(&id).item();
             let serialized_item = Self::serialize_item(item);
-            tx.insert_item(id, schema_id, &name, serialized_item)?;
+            let owner_id = migration_metadata.user_item_owners[&id];
+            tx.insert_item(id, schema_id, &name, serialized_item, owner_id)?;
+            if let Some(privileges) = migration_metadata.user_item_privileges.get(&id) {
+                tx.update_item_privileges(id, privileges)?;
+            }
         }
         tx.update_system_object_mappings(
             migration_metadata
@@ -2821,7 +2946,7 @@ impl<S: Append> Catalog<S> {
     ) -> Result<Catalog<S>, Error> {
         let mut c = c.clone();
         let items = tx.loaded_items();
-        for (id, name, def) in items {
+        for (id, name, def, privileges, owner_id) in items {
             // TODO(benesch): a better way of detecting when a view has depended
             // upon a non-existent logging view. This is fine for now because
             // the only goal is to produce a nicer error message; we'll bail out
@@ -2843,6 +2968,9 @@ impl<S: Append> Catalog<S> {
             };
             let oid = c.allocate_oid()?;
             c.state.insert_item(id, oid, name, item);
+            let entry = c.state.entry_by_id.get_mut(&id).unwrap();
+            entry.privileges = privileges;
+            entry.owner_id = owner_id;
         }
         c.transient_revision = 1;
         Ok(c)
@@ -3207,6 +3335,7 @@ impl<S: Append> Catalog<S> {
                 oid,
                 items: BTreeMap::new(),
                 functions: BTreeMap::new(),
+                owner_id: MZ_SYSTEM_ROLE_ID,
             },
         );
         Ok(())
@@ -3500,17 +3629,20 @@ impl<S: Append> Catalog<S> {
                 id: DatabaseId,
                 oid: u32,
                 name: String,
+                owner_id: RoleId,
             },
             CreateSchema {
                 id: SchemaId,
                 oid: u32,
                 database_id: DatabaseId,
                 schema_name: String,
+                owner_id: RoleId,
             },
             CreateRole {
                 id: RoleId,
                 oid: u32,
                 name: String,
+                superuser: bool,
             },
             CreateComputeInstance {
                 id: ComputeInstanceId,
@@ -3529,6 +3661,8 @@ impl<S: Append> Catalog<S> {
                 oid: u32,
                 name: QualifiedObjectName,
                 item: CatalogItem,
+                privileges: BTreeMap<RoleId, BTreeSet<Privilege>>,
+                owner_id: RoleId,
             },
 
             DropDatabase {
@@ -3555,6 +3689,18 @@ impl<S: Append> Catalog<S> {
                 to_name: QualifiedObjectName,
                 to_item: CatalogItem,
             },
+            UpdateItemPrivileges {
+                id: GlobalId,
+                privileges: BTreeMap<RoleId, BTreeSet<Privilege>>,
+            },
+            UpdateRolePrivileges {
+                name: String,
+                schema_privileges: BTreeMap<SchemaId, BTreeSet<Privilege>>,
+            },
+            UpdateOwner {
+                id: ObjectId,
+                new_owner: RoleId,
+            },
             UpdateComputeInstanceStatus {
                 event: ComputeInstanceEvent,
             },
@@ -3587,9 +3733,20 @@ impl<S: Append> Catalog<S> {
             }
         }
 
+        // Objects are owned by the role of the session that creates them, or by
+        // the system role if there is no session.
+        let owner_id = session
+            .and_then(|session| state.roles.get(&session.user().name))
+            .map_or(MZ_SYSTEM_ROLE_ID, |role| role.id);
+
         for op in ops {
             match op {
-                Op::AlterSink { id, size, remote } => {
+                Op::AlterSink {
+                    id,
+                    size,
+                    remote,
+                    runtime_options,
+                } => {
                     use mz_sql::ast::Value;
                     use mz_sql_parser::ast::CreateSinkOptionName::*;
 
@@ -3625,7 +3782,11 @@ impl<S: Append> Catalog<S> {
                     };
 
                     let new_config = alter_host_config(&old_sink.host_config, size, remote)?;
+                    if new_config.is_none() && !runtime_options.any_changed() {
+                        continue;
+                    }
 
+                    let mut sink = old_sink.clone();
                     if let Some(config) = new_config {
                         create_stmt
                             .with_options
@@ -3649,50 +3810,51 @@ impl<S: Append> Catalog<S> {
                         // Undefined size sinks only allowed in unsafe mode.
                         let allow_undefined_size = state.config().unsafe_mode;
 
-                        let host_config =
+                        sink.host_config =
                             state.resolve_storage_host_config(config, allow_undefined_size)?;
-                        let create_sql = stmt.to_ast_string_stable();
-                        let sink = CatalogItem::Sink(Sink {
-                            create_sql,
-                            host_config: host_config.clone(),
-                            ..old_sink
-                        });
+                    }
+                    if runtime_options.any_changed() {
+                        alter_sink_runtime_options(create_stmt, &mut sink, runtime_options)?;
+                    }
+                    sink.create_sql = stmt.to_ast_string_stable();
 
-                        let ser = Self::serialize_item(&sink);
-                        tx.update_item(id, &name.item, &ser)?;
+                    let new_size = sink.host_config.size().map(|x| x.to_string());
+                    let sink = CatalogItem::Sink(sink);
 
-                        // NB: this will be re-incremented by the action below.
-                        builtin_table_updates.extend(state.pack_item_update(id, -1));
+                    let ser = Self::serialize_item(&sink);
+                    tx.update_item(id, &name.item, &ser)?;
 
-                        state.add_to_audit_log(
-                            session,
-                            tx,
-                            builtin_table_updates,
-                            audit_events,
-                            EventType::Alter,
-                            ObjectType::Sink,
-                            EventDetails::AlterSourceSinkV1(mz_audit_log::AlterSourceSinkV1 {
-                                id: id.to_string(),
-                                name: Self::full_name_detail(&state.resolve_full_name(
-                                    &name,
-                                    session.map(|session| session.conn_id()),
-                                )),
-                                old_size: old_sink.host_config.size().map(|x| x.to_string()),
-                                new_size: host_config.size().map(|x| x.to_string()),
-                            }),
-                        )?;
+                    // NB: this will be re-incremented by the action below.
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
 
-                        let to_name = entry.name().clone();
-                        catalog_action(
-                            state,
-                            builtin_table_updates,
-                            Action::UpdateItem {
-                                id,
-                                to_name,
-                                to_item: sink,
-                            },
-                        )?;
-                    }
+                    state.add_to_audit_log(
+                        session,
+                        tx,
+                        builtin_table_updates,
+                        audit_events,
+                        EventType::Alter,
+                        ObjectType::Sink,
+                        EventDetails::AlterSourceSinkV1(mz_audit_log::AlterSourceSinkV1 {
+                            id: id.to_string(),
+                            name: Self::full_name_detail(&state.resolve_full_name(
+                                &name,
+                                session.map(|session| session.conn_id()),
+                            )),
+                            old_size: old_sink.host_config.size().map(|x| x.to_string()),
+                            new_size,
+                        }),
+                    )?;
+
+                    let to_name = entry.name().clone();
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateItem {
+                            id,
+                            to_name,
+                            to_item: sink,
+                        },
+                    )?;
                 }
                 Op::AlterSource { id, size, remote } => {
                     use mz_sql::ast::Value;
@@ -3735,7 +3897,9 @@ impl<S: Append> Catalog<S> {
                         DataSourceDesc::Ingestion(ingestion) => {
                             alter_host_config(&ingestion.host_config, size, remote)?
                         }
-                        DataSourceDesc::Introspection(_) | DataSourceDesc::Source => None,
+                        DataSourceDesc::Introspection(_)
+                        | DataSourceDesc::Source
+                        | DataSourceDesc::Webhook { .. } => None,
                     };
 
                     if let Some(config) = new_config {
@@ -3762,7 +3926,9 @@ impl<S: Append> Catalog<S> {
                         // unsafe mode.
                         let allow_undefined_size = state.config().unsafe_mode
                             || match old_source.data_source {
-                                DataSourceDesc::Introspection(_) | DataSourceDesc::Source => true,
+                                DataSourceDesc::Introspection(_)
+                                | DataSourceDesc::Source
+                                | DataSourceDesc::Webhook { .. } => true,
                                 DataSourceDesc::Ingestion(_) => false,
                             };
 
@@ -3828,8 +3994,8 @@ impl<S: Append> Catalog<S> {
                     oid,
                     public_schema_oid,
                 } => {
-                    let database_id = tx.insert_database(&name)?;
-                    let schema_id = tx.insert_schema(database_id, DEFAULT_SCHEMA)?;
+                    let database_id = tx.insert_database(&name, owner_id)?;
+                    let schema_id = tx.insert_schema(database_id, DEFAULT_SCHEMA, owner_id)?;
                     state.add_to_audit_log(
                         session,
                         tx,
@@ -3849,6 +4015,7 @@ impl<S: Append> Catalog<S> {
                             id: database_id,
                             oid,
                             name: name.clone(),
+                            owner_id,
                         },
                     )?;
                     state.add_to_audit_log(
@@ -3872,6 +4039,7 @@ impl<S: Append> Catalog<S> {
                             oid: public_schema_oid,
                             database_id,
                             schema_name: DEFAULT_SCHEMA.to_string(),
+                            owner_id,
                         },
                     )?;
                 }
@@ -3893,7 +4061,7 @@ impl<S: Append> Catalog<S> {
                             )));
                         }
                     };
-                    let schema_id = tx.insert_schema(database_id, &schema_name)?;
+                    let schema_id = tx.insert_schema(database_id, &schema_name, owner_id)?;
                     state.add_to_audit_log(
                         session,
                         tx,
@@ -3915,16 +4083,21 @@ impl<S: Append> Catalog<S> {
                             oid,
                             database_id,
                             schema_name,
+                            owner_id,
                         },
                     )?;
                 }
-                Op::CreateRole { name, oid } => {
+                Op::CreateRole {
+                    name,
+                    oid,
+                    superuser,
+                } => {
                     if is_reserved_name(&name) {
                         return Err(AdapterError::Catalog(Error::new(
                             ErrorKind::ReservedRoleName(name),
                         )));
                     }
-                    let role_id = tx.insert_user_role(&name)?;
+                    let role_id = tx.insert_user_role(&name, superuser)?;
                     state.add_to_audit_log(
                         session,
                         tx,
@@ -3944,6 +4117,7 @@ impl<S: Append> Catalog<S> {
                             id: role_id,
                             oid,
                             name,
+                            superuser,
                         },
                     )?;
                 }
@@ -4040,6 +4214,18 @@ impl<S: Append> Catalog<S> {
                 } => {
                     state.ensure_no_unstable_uses(&item)?;
 
+                    // Roles that are not superusers are granted every
+                    // applicable privilege on the items they create.
+                    let mut privileges = BTreeMap::new();
+                    if let Some(role) =
+                        session.and_then(|session| state.roles.get(&session.user().name))
+                    {
+                        let creator_privileges = item.owner_privileges();
+                        if !role.superuser && !creator_privileges.is_empty() {
+                            privileges.insert(role.id, creator_privileges);
+                        }
+                    }
+
                     if let Some(id @ ComputeInstanceId::System(_)) = item.compute_instance_id() {
                         let compute_instance_name = state.compute_instances_by_id[&id].name.clone();
                         return Err(AdapterError::Catalog(Error::new(
@@ -4088,7 +4274,10 @@ impl<S: Append> Catalog<S> {
                         }
                         let schema_id = name.qualifiers.schema_spec.clone().into();
                         let serialized_item = Self::serialize_item(&item);
-                        tx.insert_item(id, schema_id, &name.item, serialized_item)?;
+                        tx.insert_item(id, schema_id, &name.item, serialized_item, owner_id)?;
+                        if !privileges.is_empty() {
+                            tx.update_item_privileges(id, &privileges)?;
+                        }
                     }
 
                     if Self::should_audit_log_item(&item) {
@@ -4133,6 +4322,8 @@ impl<S: Append> Catalog<S> {
                             oid,
                             name,
                             item,
+                            privileges,
+                            owner_id,
                         },
                     )?;
                 }
@@ -4178,6 +4369,24 @@ impl<S: Append> Catalog<S> {
                             database_name: state.database_by_id[&database_id].name.clone(),
                         }),
                     )?;
+                    let grantees: Vec<_> = state
+                        .roles
+                        .values()
+                        .filter(|role| role.schema_privileges.contains_key(&schema_id))
+                        .map(|role| (role.id, role.name.clone(), role.schema_privileges.clone()))
+                        .collect();
+                    for (role_id, name, mut schema_privileges) in grantees {
+                        schema_privileges.remove(&schema_id);
+                        tx.update_role_privileges(role_id, &schema_privileges)?;
+                        catalog_action(
+                            state,
+                            builtin_table_updates,
+                            Action::UpdateRolePrivileges {
+                                name,
+                                schema_privileges,
+                            },
+                        )?;
+                    }
                     catalog_action(
                         state,
                         builtin_table_updates,
@@ -4193,8 +4402,33 @@ impl<S: Append> Catalog<S> {
                             ErrorKind::ReservedRoleName(name),
                         )));
                     }
-                    tx.remove_role(&name)?;
                     let role = &state.roles[&name];
+                    if !role.schema_privileges.is_empty()
+                        || state
+                            .entry_by_id
+                            .values()
+                            .any(|entry| entry.privileges.contains_key(&role.id))
+                    {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::RoleHasPrivileges(name),
+                        )));
+                    }
+                    if state.database_by_id.values().any(|database| {
+                        database.owner_id == role.id
+                            || database
+                                .schemas_by_id
+                                .values()
+                                .any(|schema| schema.owner_id == role.id)
+                    }) || state
+                        .entry_by_id
+                        .values()
+                        .any(|entry| entry.owner_id == role.id)
+                    {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::RoleOwnsObjects(name),
+                        )));
+                    }
+                    tx.remove_role(&name)?;
                     builtin_table_updates.push(state.pack_role_update(role, -1));
                     state.add_to_audit_log(
                         session,
@@ -4490,6 +4724,77 @@ impl<S: Append> Catalog<S> {
                         },
                     )?;
                 }
+                Op::UpdateOwner { id, new_owner } => {
+                    match id {
+                        ObjectId::Database(id) => tx.update_database_owner(id, new_owner)?,
+                        ObjectId::Schema(database_id, schema_id) => {
+                            tx.update_schema_owner(database_id, schema_id, new_owner)?
+                        }
+                        ObjectId::Item(id) => tx.update_item_owner(id, new_owner)?,
+                    }
+                    // The privileges that owners are implicitly granted on
+                    // items move along with the ownership.
+                    let privileges = match id {
+                        ObjectId::Item(id) => {
+                            let entry = state.get_entry(&id);
+                            let owner_privileges = entry.item().owner_privileges();
+                            let mut privileges = entry.privileges().clone();
+                            if let Some(old) = privileges.get_mut(&entry.owner_id()) {
+                                old.retain(|privilege| !owner_privileges.contains(privilege));
+                                if old.is_empty() {
+                                    privileges.remove(&entry.owner_id());
+                                }
+                            }
+                            let new_owner_is_superuser = state
+                                .roles
+                                .values()
+                                .any(|role| role.id == new_owner && role.superuser);
+                            if !new_owner_is_superuser && !owner_privileges.is_empty() {
+                                privileges
+                                    .entry(new_owner)
+                                    .or_default()
+                                    .extend(owner_privileges);
+                            }
+                            Some((id, privileges))
+                        }
+                        ObjectId::Database(_) | ObjectId::Schema(..) => None,
+                    };
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateOwner { id, new_owner },
+                    )?;
+                    if let Some((id, privileges)) = privileges {
+                        tx.update_item_privileges(id, &privileges)?;
+                        catalog_action(
+                            state,
+                            builtin_table_updates,
+                            Action::UpdateItemPrivileges { id, privileges },
+                        )?;
+                    }
+                }
+                Op::UpdateItemPrivileges { id, privileges } => {
+                    tx.update_item_privileges(id, &privileges)?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateItemPrivileges { id, privileges },
+                    )?;
+                }
+                Op::UpdateRolePrivileges {
+                    name,
+                    schema_privileges,
+                } => {
+                    tx.update_role_privileges(state.roles[&name].id, &schema_privileges)?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateRolePrivileges {
+                            name,
+                            schema_privileges,
+                        },
+                    )?;
+                }
                 Op::UpdateStorageUsage {
                     shard_id,
                     size_bytes,
@@ -4569,7 +4874,12 @@ impl<S: Append> Catalog<S> {
             action: Action,
         ) -> Result<(), AdapterError> {
             match action {
-                Action::CreateDatabase { id, oid, name } => {
+                Action::CreateDatabase {
+                    id,
+                    oid,
+                    name,
+                    owner_id,
+                } => {
                     info!("create database {}", name);
                     state.database_by_id.insert(
                         id.clone(),
@@ -4579,6 +4889,7 @@ impl<S: Append> Catalog<S> {
                             oid,
                             schemas_by_id: BTreeMap::new(),
                             schemas_by_name: BTreeMap::new(),
+                            owner_id,
                         },
                     );
                     state.database_by_name.insert(name, id.clone());
@@ -4591,6 +4902,7 @@ impl<S: Append> Catalog<S> {
                     oid,
                     database_id,
                     schema_name,
+                    owner_id,
                 } => {
                     info!(
                         "create schema {}.{}",
@@ -4609,6 +4921,7 @@ impl<S: Append> Catalog<S> {
                             oid,
                             items: BTreeMap::new(),
                             functions: BTreeMap::new(),
+                            owner_id,
                         },
                     );
                     db.schemas_by_name.insert(schema_name, id.clone());
@@ -4619,7 +4932,12 @@ impl<S: Append> Catalog<S> {
                     ));
                 }
 
-                Action::CreateRole { id, oid, name } => {
+                Action::CreateRole {
+                    id,
+                    oid,
+                    name,
+                    superuser,
+                } => {
                     info!("create role {}", name);
                     state.roles.insert(
                         name.clone(),
@@ -4627,6 +4945,8 @@ impl<S: Append> Catalog<S> {
                             name: name.clone(),
                             id,
                             oid,
+                            superuser,
+                            schema_privileges: BTreeMap::new(),
                         },
                     );
                     let role = &state.roles[&name];
@@ -4674,8 +4994,13 @@ impl<S: Append> Catalog<S> {
                     oid,
                     name,
                     item,
+                    privileges,
+                    owner_id,
                 } => {
                     state.insert_item(id, oid, name, item);
+                    let entry = state.entry_by_id.get_mut(&id).unwrap();
+                    entry.privileges = privileges;
+                    entry.owner_id = owner_id;
                     builtin_table_updates.extend(state.pack_item_update(id, 1));
                 }
 
@@ -4773,6 +5098,69 @@ impl<S: Append> Catalog<S> {
                     builtin_table_updates.extend(state.pack_item_update(id, 1));
                 }
 
+                Action::UpdateOwner {
+                    id: ObjectId::Database(id),
+                    new_owner,
+                } => {
+                    builtin_table_updates
+                        .push(state.pack_database_update(&state.database_by_id[&id], -1));
+                    state.database_by_id.get_mut(&id).unwrap().owner_id = new_owner;
+                    builtin_table_updates
+                        .push(state.pack_database_update(&state.database_by_id[&id], 1));
+                }
+
+                Action::UpdateOwner {
+                    id: ObjectId::Schema(database_id, schema_id),
+                    new_owner,
+                } => {
+                    let database_spec = ResolvedDatabaseSpecifier::Id(database_id);
+                    builtin_table_updates.push(state.pack_schema_update(
+                        &database_spec,
+                        &schema_id,
+                        -1,
+                    ));
+                    state
+                        .database_by_id
+                        .get_mut(&database_id)
+                        .unwrap()
+                        .schemas_by_id
+                        .get_mut(&schema_id)
+                        .unwrap()
+                        .owner_id = new_owner;
+                    builtin_table_updates.push(state.pack_schema_update(
+                        &database_spec,
+                        &schema_id,
+                        1,
+                    ));
+                }
+
+                Action::UpdateOwner {
+                    id: ObjectId::Item(id),
+                    new_owner,
+                } => {
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
+                    state.entry_by_id.get_mut(&id).unwrap().owner_id = new_owner;
+                    builtin_table_updates.extend(state.pack_item_update(id, 1));
+                }
+
+                Action::UpdateItemPrivileges { id, privileges } => {
+                    builtin_table_updates.extend(state.pack_item_privileges_update(id, -1));
+                    state.entry_by_id.get_mut(&id).unwrap().privileges = privileges;
+                    builtin_table_updates.extend(state.pack_item_privileges_update(id, 1));
+                }
+
+                Action::UpdateRolePrivileges {
+                    name,
+                    schema_privileges,
+                } => {
+                    let role = &state.roles[&name];
+                    builtin_table_updates.extend(state.pack_role_privileges_update(role, -1));
+                    let role = state.roles.get_mut(&name).unwrap();
+                    role.schema_privileges = schema_privileges;
+                    let role = &state.roles[&name];
+                    builtin_table_updates.extend(state.pack_role_privileges_update(role, 1));
+                }
+
                 Action::UpdateComputeInstanceStatus { event } => {
                     // It is possible that we receive a status update for a
                     // replica that has already been dropped from the catalog.
@@ -4901,15 +5289,22 @@ impl<S: Append> Catalog<S> {
                 let allow_undefined_size = true;
                 CatalogItem::Source(Source {
                     create_sql: source.create_sql,
-                    data_source: match source.ingestion {
-                        Some(ingestion) => DataSourceDesc::Ingestion(Ingestion {
-                            desc: ingestion.desc,
-                            source_imports: ingestion.source_imports,
-                            subsource_exports: ingestion.subsource_exports,
-                            host_config: self
-                                .resolve_storage_host_config(host_config, allow_undefined_size)?,
-                        }),
-                        None => DataSourceDesc::Source,
+                    data_source: match source.data_source {
+                        plan::DataSourceDesc::Ingestion(ingestion) => {
+                            DataSourceDesc::Ingestion(Ingestion {
+                                desc: ingestion.desc,
+                                source_imports: ingestion.source_imports,
+                                subsource_exports: ingestion.subsource_exports,
+                                host_config: self.resolve_storage_host_config(
+                                    host_config,
+                                    allow_undefined_size,
+                                )?,
+                            })
+                        }
+                        plan::DataSourceDesc::Source => DataSourceDesc::Source,
+                        plan::DataSourceDesc::Webhook { secret, check } => {
+                            DataSourceDesc::Webhook { secret, check }
+                        }
                     },
                     desc: source.desc,
                     timeline,
@@ -4940,6 +5335,10 @@ impl<S: Append> Catalog<S> {
                     desc,
                     depends_on,
                     compute_instance: materialized_view.compute_instance,
+                    batching: materialized_view.batching,
+                    compaction_window_ms: materialized_view
+                        .compaction_window
+                        .unwrap_or(DEFAULT_LOGICAL_COMPACTION_WINDOW_MS),
                 })
             }
             Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
@@ -4966,6 +5365,13 @@ impl<S: Append> Catalog<S> {
                     depends_on,
                     host_config: self
                         .resolve_storage_host_config(host_config, allow_undefined_size)?,
+                    catch_up_limit: sink.catch_up_limit,
+                    rate_limit: sink.rate_limit,
+                    projection: sink.projection,
+                    column_masks: sink.column_masks,
+                    include_timestamp: sink.include_timestamp,
+                    include_diff: sink.include_diff,
+                    filter: sink.filter,
                 })
             }
             Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
@@ -5058,6 +5464,22 @@ impl<S: Append> Catalog<S> {
         self.state.roles.values().filter(|role| role.is_user())
     }
 
+    /// Returns the role with the given name, if it exists.
+    pub fn try_get_role(&self, name: &str) -> Option<&Role> {
+        self.state.roles.get(name)
+    }
+
+    /// Returns the role with the given ID.
+    ///
+    /// Panics if no such role exists.
+    pub fn get_role_by_id(&self, id: &RoleId) -> &Role {
+        self.state
+            .roles
+            .values()
+            .find(|role| &role.id == id)
+            .unwrap_or_else(|| panic!("catalog out of sync, missing role {id}"))
+    }
+
     /// Allocate ids for legacy, active logs. Called once per compute instance creation
     pub async fn allocate_arranged_introspection_sources(
         &mut self,
@@ -5177,12 +5599,152 @@ fn alter_host_config(
     }
 }
 
+/// Applies a set of altered runtime options to a sink, and to the `CREATE SINK`
+/// statement from which the sink is recreated when the catalog is reloaded.
+fn alter_sink_runtime_options(
+    stmt: &mut CreateSinkStatement<Raw>,
+    sink: &mut Sink,
+    options: AlterSinkRuntimeOptions,
+) -> Result<(), AdapterError> {
+    use mz_sql::ast::Value;
+    use mz_sql_parser::ast::CreateSinkOptionName::*;
+    use plan::AlterOptionParameter::*;
+
+    fn number<T: ToString>(param: &AlterOptionParameter<T>) -> Option<Option<Value>> {
+        match param {
+            Set(v) => Some(Some(Value::Number(v.to_string()))),
+            Reset => Some(None),
+            Unchanged => None,
+        }
+    }
+
+    fn apply<T: Copy>(param: &AlterOptionParameter<T>, field: &mut Option<T>) {
+        match param {
+            Set(v) => *field = Some(*v),
+            Reset => *field = None,
+            Unchanged => (),
+        }
+    }
+
+    let kafka = match &mut sink.connection {
+        StorageSinkConnectionState::Ready(StorageSinkConnection::Kafka(kafka)) => Some(kafka),
+        StorageSinkConnectionState::Ready(_) => None,
+        StorageSinkConnectionState::Pending(_) => {
+            coord_bail!("cannot alter a sink whose connection is still being established")
+        }
+    };
+
+    for (name, value) in [
+        (MaxBytesPerSecond, number(&options.max_bytes_per_second)),
+        (
+            MaxMessagesPerSecond,
+            number(&options.max_messages_per_second),
+        ),
+    ] {
+        if let Some(value) = value {
+            stmt.with_options.retain(|o| o.name != name);
+            if let Some(value) = value {
+                stmt.with_options.push(CreateSinkOption {
+                    name,
+                    value: Some(WithOptionValue::Value(value)),
+                });
+            }
+        }
+    }
+
+    fn apply_rate_limit(
+        limit: Option<SinkRateLimit>,
+        bytes_per_second: &AlterOptionParameter<u64>,
+        records_per_second: &AlterOptionParameter<u64>,
+    ) -> Option<SinkRateLimit> {
+        let mut limit = limit.unwrap_or(SinkRateLimit {
+            records_per_second: None,
+            bytes_per_second: None,
+        });
+        apply(bytes_per_second, &mut limit.bytes_per_second);
+        apply(records_per_second, &mut limit.records_per_second);
+        if limit.bytes_per_second.is_some() || limit.records_per_second.is_some() {
+            Some(limit)
+        } else {
+            None
+        }
+    }
+
+    sink.rate_limit = apply_rate_limit(
+        sink.rate_limit,
+        &options.max_bytes_per_second,
+        &options.max_messages_per_second,
+    );
+
+    if !options.kafka_connection_changed() {
+        return Ok(());
+    }
+    let (kafka, kafka_options) = match (kafka, &mut stmt.connection) {
+        (
+            Some(kafka),
+            CreateSinkConnection::Kafka {
+                connection: KafkaConnection { options, .. },
+                ..
+            },
+        ) => (kafka, options),
+        _ => coord_bail!("KAFKA CONNECTION options can only be altered on Kafka sinks"),
+    };
+
+    let compression_type = match options.compression_type {
+        Set(compression_type) => {
+            kafka.compression_type = compression_type;
+            Some(Some(Value::String(
+                compression_type.as_librdkafka_str().into(),
+            )))
+        }
+        Reset => {
+            kafka.compression_type = KafkaSinkCompressionType::None;
+            Some(None)
+        }
+        Unchanged => None,
+    };
+    apply(&options.linger_ms, &mut kafka.linger_ms);
+    apply(&options.batch_num_messages, &mut kafka.batch_num_messages);
+    apply(&options.retention_ms, &mut kafka.retention.duration);
+    apply(&options.retention_bytes, &mut kafka.retention.bytes);
+
+    for (name, value) in [
+        (KafkaConfigOptionName::CompressionType, compression_type),
+        (KafkaConfigOptionName::LingerMs, number(&options.linger_ms)),
+        (
+            KafkaConfigOptionName::BatchNumMessages,
+            number(&options.batch_num_messages),
+        ),
+        (
+            KafkaConfigOptionName::RetentionMs,
+            number(&options.retention_ms),
+        ),
+        (
+            KafkaConfigOptionName::RetentionBytes,
+            number(&options.retention_bytes),
+        ),
+    ] {
+        if let Some(value) = value {
+            kafka_options.retain(|o| o.name != name);
+            if let Some(value) = value {
+                kafka_options.push(KafkaConfigOption {
+                    name,
+                    value: Some(WithOptionValue::Value(value)),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub enum Op {
     AlterSink {
         id: GlobalId,
         size: AlterOptionParameter,
         remote: AlterOptionParameter,
+        runtime_options: AlterSinkRuntimeOptions,
     },
     AlterSource {
         id: GlobalId,
@@ -5202,6 +5764,7 @@ pub enum Op {
     CreateRole {
         name: String,
         oid: u32,
+        superuser: bool,
     },
     CreateComputeInstance {
         name: String,
@@ -5253,6 +5816,21 @@ pub enum Op {
         name: QualifiedObjectName,
         to_item: CatalogItem,
     },
+    /// Replaces the privileges granted on the identified item.
+    UpdateItemPrivileges {
+        id: GlobalId,
+        privileges: BTreeMap<RoleId, BTreeSet<Privilege>>,
+    },
+    /// Replaces the privileges granted to the named role on schemas.
+    UpdateRolePrivileges {
+        name: String,
+        schema_privileges: BTreeMap<SchemaId, BTreeSet<Privilege>>,
+    },
+    /// Transfers ownership of the identified object to `new_owner`.
+    UpdateOwner {
+        id: ObjectId,
+        new_owner: RoleId,
+    },
     UpdateStorageUsage {
         shard_id: Option<String>,
         size_bytes: u64,
@@ -5765,6 +6343,10 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.item().typ()
     }
 
+    fn is_webhook_source(&self) -> bool {
+        self.is_webhook_source()
+    }
+
     fn index_details(&self) -> Option<(&[MirScalarExpr], GlobalId)> {
         if let CatalogItem::Index(Index { keys, on, .. }) = self.item() {
             Some((keys, *on))
@@ -5803,7 +6385,9 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
                 DataSourceDesc::Ingestion(ingestion) => {
                     ingestion.subsource_exports.keys().copied().collect()
                 }
-                DataSourceDesc::Source | DataSourceDesc::Introspection(_) => vec![],
+                DataSourceDesc::Source
+                | DataSourceDesc::Introspection(_)
+                | DataSourceDesc::Webhook { .. } => vec![],
             },
             CatalogItem::Table(_)
             | CatalogItem::Log(_)
@@ -5823,6 +6407,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
 mod tests {
     use itertools::Itertools;
     use mz_compute_client::controller::ComputeInstanceId;
+    use mz_compute_client::sinks::PersistSinkBatching;
     use std::collections::HashMap;
     use std::error::Error;
 
@@ -5839,6 +6424,7 @@ mod tests {
     use mz_stash::Sqlite;
 
     use crate::catalog::{Catalog, CatalogItem, MaterializedView, Op, Table, SYSTEM_CONN_ID};
+    use crate::coord::DEFAULT_LOGICAL_COMPACTION_WINDOW_MS;
     use crate::session::{Session, DEFAULT_DATABASE_NAME};
 
     /// System sessions have an empty `search_path` so it's necessary to
@@ -6119,6 +6705,8 @@ mod tests {
                                 .with_key(vec![0]),
                             depends_on,
                             compute_instance: ComputeInstanceId::User(1),
+                            batching: PersistSinkBatching::default(),
+                            compaction_window_ms: DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
                         })
                     }
                 };
@@ -1094,6 +1094,12 @@ pub const MZ_RAW_PEEK_DURATIONS: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::PeekDuration),
 };
 
+pub const MZ_WORKER_COMPUTE_LAST_PEEKS: BuiltinLog = BuiltinLog {
+    name: "mz_worker_compute_last_peeks",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::PeekLast),
+};
+
 pub const MZ_MESSAGE_COUNTS_RECEIVED_INTERNAL: BuiltinLog = BuiltinLog {
     name: "mz_message_counts_received_internal",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1106,6 +1112,12 @@ pub const MZ_MESSAGE_COUNTS_SENT_INTERNAL: BuiltinLog = BuiltinLog {
     variant: LogVariant::Timely(TimelyLog::MessagesSent),
 };
 
+pub const MZ_WORKER_NETWORK_BYTES: BuiltinLog = BuiltinLog {
+    name: "mz_worker_network_bytes",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Timely(TimelyLog::NetworkBytes),
+};
+
 pub const MZ_DATAFLOW_OPERATOR_REACHABILITY_INTERNAL: BuiltinLog = BuiltinLog {
     name: "mz_dataflow_operator_reachability_internal",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1164,7 +1176,8 @@ pub static MZ_DATABASES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     desc: RelationDesc::empty()
         .with_column("id", ScalarType::UInt64.nullable(false))
         .with_column("oid", ScalarType::Oid.nullable(false))
-        .with_column("name", ScalarType::String.nullable(false)),
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_SCHEMAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_schemas",
@@ -1173,7 +1186,8 @@ pub static MZ_SCHEMAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("id", ScalarType::UInt64.nullable(false))
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("database_id", ScalarType::UInt64.nullable(true))
-        .with_column("name", ScalarType::String.nullable(false)),
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_COLUMNS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_columns",
@@ -1195,7 +1209,8 @@ pub static MZ_INDEXES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("name", ScalarType::String.nullable(false))
         .with_column("on_id", ScalarType::String.nullable(false))
-        .with_column("cluster_id", ScalarType::String.nullable(false)),
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_INDEX_COLUMNS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_index_columns",
@@ -1214,7 +1229,8 @@ pub static MZ_TABLES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("id", ScalarType::String.nullable(false))
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
-        .with_column("name", ScalarType::String.nullable(false)),
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_CONNECTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_connections",
@@ -1224,7 +1240,8 @@ pub static MZ_CONNECTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
         .with_column("name", ScalarType::String.nullable(false))
-        .with_column("type", ScalarType::String.nullable(false)),
+        .with_column("type", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_SSH_TUNNEL_CONNECTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_ssh_tunnel_connections",
@@ -1244,7 +1261,8 @@ pub static MZ_SOURCES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("name", ScalarType::String.nullable(false))
         .with_column("type", ScalarType::String.nullable(false))
         .with_column("connection_id", ScalarType::String.nullable(true))
-        .with_column("size", ScalarType::String.nullable(true)),
+        .with_column("size", ScalarType::String.nullable(true))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_SINKS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_sinks",
@@ -1256,7 +1274,8 @@ pub static MZ_SINKS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("name", ScalarType::String.nullable(false))
         .with_column("type", ScalarType::String.nullable(false))
         .with_column("connection_id", ScalarType::String.nullable(true))
-        .with_column("size", ScalarType::String.nullable(true)),
+        .with_column("size", ScalarType::String.nullable(true))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_views",
@@ -1266,7 +1285,8 @@ pub static MZ_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
         .with_column("name", ScalarType::String.nullable(false))
-        .with_column("definition", ScalarType::String.nullable(false)),
+        .with_column("definition", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_materialized_views",
@@ -1277,7 +1297,8 @@ pub static MZ_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
         .with_column("name", ScalarType::String.nullable(false))
         .with_column("cluster_id", ScalarType::String.nullable(false))
-        .with_column("definition", ScalarType::String.nullable(false)),
+        .with_column("definition", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_types",
@@ -1287,7 +1308,8 @@ pub static MZ_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("oid", ScalarType::Oid.nullable(false))
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
         .with_column("name", ScalarType::String.nullable(false))
-        .with_column("category", ScalarType::String.nullable(false)),
+        .with_column("category", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_ARRAY_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_array_types",
@@ -1361,7 +1383,8 @@ pub static MZ_SECRETS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     desc: RelationDesc::empty()
         .with_column("id", ScalarType::String.nullable(false))
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
-        .with_column("name", ScalarType::String.nullable(false)),
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false)),
 });
 pub static MZ_CLUSTER_REPLICAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_cluster_replicas",
@@ -1392,6 +1415,76 @@ pub static MZ_CLUSTER_REPLICA_HEARTBEATS: Lazy<BuiltinTable> = Lazy::new(|| Buil
         .with_column("last_heartbeat", ScalarType::TimestampTz.nullable(false)),
 });
 
+pub static MZ_CLUSTER_REPLICA_LIVENESS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_cluster_replica_liveness",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("replica_id", ScalarType::UInt64.nullable(false))
+        .with_column("last_heartbeat", ScalarType::TimestampTz.nullable(false))
+        .with_column("build_version", ScalarType::String.nullable(false))
+        .with_column("uptime", ScalarType::Interval.nullable(false)),
+});
+
+pub static MZ_CLUSTER_REPLICA_PENDING_DDL: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_cluster_replica_pending_ddl",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::UInt64.nullable(false))
+        .with_column("issued_at", ScalarType::TimestampTz.nullable(false)),
+});
+
+pub static MZ_PENDING_SOURCES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_pending_sources",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("connection_id", ScalarType::UInt32.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("status", ScalarType::String.nullable(false))
+        .with_column("started_at", ScalarType::TimestampTz.nullable(false)),
+});
+
+pub static MZ_PRIVILEGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_privileges",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("grantee_id", ScalarType::String.nullable(false))
+        .with_column("object_id", ScalarType::String.nullable(true))
+        .with_column("schema_id", ScalarType::UInt64.nullable(true))
+        .with_column("privilege", ScalarType::String.nullable(false)),
+});
+
+pub static MZ_SOURCE_DETAILS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_source_details",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("type", ScalarType::String.nullable(false))
+        .with_column("options", ScalarType::Jsonb.nullable(false))
+        .with_column("key_format", ScalarType::String.nullable(true))
+        .with_column("value_format", ScalarType::String.nullable(false))
+        .with_column("envelope", ScalarType::String.nullable(false))
+        .with_column("columns", ScalarType::Jsonb.nullable(false))
+        .with_key(vec![0]),
+});
+
+pub static MZ_COORDINATOR_QUEUE_DEPTH_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_coordinator_queue_depth_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("sampled_at", ScalarType::TimestampTz.nullable(false))
+        .with_column("command_queue_depth", ScalarType::UInt64.nullable(false))
+        .with_column("messages_processed", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "max_processing_duration_ns",
+            ScalarType::UInt64.nullable(false),
+        )
+        .with_column(
+            "total_processing_duration_ns",
+            ScalarType::UInt64.nullable(false),
+        ),
+});
+
 pub static MZ_AUDIT_EVENTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_audit_events",
     schema: MZ_CATALOG_SCHEMA,
@@ -1456,6 +1549,57 @@ pub static MZ_STORAGE_SHARDS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
         .with_column("shard_id", ScalarType::String.nullable(false)),
 });
 
+pub static MZ_SOURCE_MESSAGE_SIZES_INTERNAL: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_source_message_sizes_internal",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::SourceMessageSizes),
+    desc: RelationDesc::empty()
+        .with_column("source_id", ScalarType::String.nullable(false))
+        .with_column("size_bytes", ScalarType::UInt64.nullable(false)),
+});
+
+pub static MZ_SINK_PROGRESS_PUBLISHES_INTERNAL: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_sink_progress_publishes_internal",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::SinkProgressPublishes),
+    desc: RelationDesc::empty()
+        .with_column("sink_id", ScalarType::String.nullable(false))
+        .with_column("duration_ns", ScalarType::UInt64.nullable(false))
+        .with_column("failed", ScalarType::Bool.nullable(false)),
+});
+
+pub static MZ_SINK_DEAD_LETTERS_INTERNAL: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_sink_dead_letters_internal",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::SinkDeadLetters),
+    desc: RelationDesc::empty()
+        .with_column("sink_id", ScalarType::String.nullable(false))
+        .with_column("error", ScalarType::String.nullable(false)),
+});
+
+pub static MZ_SOURCE_NUMERIC_OVERFLOWS_INTERNAL: Lazy<BuiltinSource> =
+    Lazy::new(|| BuiltinSource {
+        name: "mz_source_numeric_overflows_internal",
+        schema: MZ_INTERNAL_SCHEMA,
+        data_source: Some(IntrospectionType::SourceNumericOverflows),
+        desc: RelationDesc::empty()
+            .with_column("source_id", ScalarType::String.nullable(false))
+            .with_column("policy", ScalarType::String.nullable(false)),
+    });
+
+pub static MZ_SINK_METRICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_sink_metrics",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::SinkMetrics),
+    desc: RelationDesc::empty()
+        .with_column("sink_id", ScalarType::String.nullable(false))
+        .with_column("messages_emitted", ScalarType::UInt64.nullable(false))
+        .with_column("bytes_emitted", ScalarType::UInt64.nullable(false))
+        .with_column("committed_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("input_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("lag_ms", ScalarType::UInt64.nullable(true)),
+});
+
 pub static MZ_STORAGE_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_storage_usage",
     schema: MZ_CATALOG_SCHEMA,
@@ -1922,6 +2066,63 @@ GROUP BY
     id, worker_id",
 };
 
+pub const MZ_SOURCE_MESSAGE_SIZES: BuiltinView = BuiltinView {
+    name: "mz_source_message_sizes",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_source_message_sizes AS SELECT
+    source_id, size_bytes, pg_catalog.count(*) AS count
+FROM
+    mz_internal.mz_source_message_sizes_internal
+GROUP BY
+    source_id, size_bytes",
+};
+
+pub const MZ_SINK_PROGRESS_PUBLISHES: BuiltinView = BuiltinView {
+    name: "mz_sink_progress_publishes",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_sink_progress_publishes AS SELECT
+    sink_id, duration_ns,
+    pg_catalog.count(*) FILTER (WHERE NOT failed) AS count,
+    pg_catalog.count(*) FILTER (WHERE failed) AS failures
+FROM
+    mz_internal.mz_sink_progress_publishes_internal
+GROUP BY
+    sink_id, duration_ns",
+};
+
+pub const MZ_SINK_DEAD_LETTERS: BuiltinView = BuiltinView {
+    name: "mz_sink_dead_letters",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_sink_dead_letters AS SELECT
+    sink_id, error, pg_catalog.count(*) AS count
+FROM
+    mz_internal.mz_sink_dead_letters_internal
+GROUP BY
+    sink_id, error",
+};
+
+pub const MZ_SOURCE_NUMERIC_OVERFLOWS: BuiltinView = BuiltinView {
+    name: "mz_source_numeric_overflows",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_source_numeric_overflows AS SELECT
+    source_id, policy, pg_catalog.count(*) AS count
+FROM
+    mz_internal.mz_source_numeric_overflows_internal
+GROUP BY
+    source_id, policy",
+};
+
+pub const MZ_SOURCE_STATUS: BuiltinView = BuiltinView {
+    name: "mz_source_status",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_source_status AS SELECT DISTINCT ON (source_id)
+    source_id, occurred_at AS last_status_change_at, status, error, details
+FROM
+    mz_internal.mz_source_status_history
+ORDER BY
+    source_id, occurred_at DESC",
+};
+
 pub const MZ_RAW_COMPUTE_OPERATOR_DURATIONS: BuiltinView = BuiltinView {
     name: "mz_raw_compute_operator_durations",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1966,6 +2167,30 @@ pub const MZ_PEEK_DURATIONS: BuiltinView = BuiltinView {
 FROM mz_internal.mz_raw_peek_durations",
 };
 
+pub const MZ_ZOMBIE_DATAFLOWS: BuiltinView = BuiltinView {
+    name: "mz_zombie_dataflows",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_zombie_dataflows AS
+WITH last_peeks AS (
+    SELECT export_id, pg_catalog.max(time) AS last_peek
+    FROM mz_internal.mz_worker_compute_last_peeks
+    GROUP BY export_id
+)
+SELECT DISTINCT
+    mz_compute_exports.export_id,
+    last_peeks.last_peek
+FROM
+    mz_internal.mz_compute_exports
+    JOIN mz_catalog.mz_indexes ON mz_compute_exports.export_id = mz_indexes.id
+    LEFT JOIN last_peeks ON mz_compute_exports.export_id = last_peeks.export_id
+WHERE
+    NOT EXISTS (
+        SELECT 1 FROM mz_internal.mz_worker_compute_dependencies
+        WHERE mz_worker_compute_dependencies.import_id = mz_compute_exports.export_id
+    ) AND
+    (last_peeks.last_peek IS NULL OR last_peeks.last_peek < pg_catalog.now() - INTERVAL '1 hour')",
+};
+
 pub const MZ_SCHEDULING_PARKS: BuiltinView = BuiltinView {
     name: "mz_scheduling_parks",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2610,12 +2835,14 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_MESSAGE_COUNTS_SENT_INTERNAL),
         Builtin::Log(&MZ_ACTIVE_PEEKS),
         Builtin::Log(&MZ_RAW_PEEK_DURATIONS),
+        Builtin::Log(&MZ_WORKER_COMPUTE_LAST_PEEKS),
         Builtin::Log(&MZ_SCHEDULING_ELAPSED_INTERNAL),
         Builtin::Log(&MZ_RAW_COMPUTE_OPERATOR_DURATIONS_INTERNAL),
         Builtin::Log(&MZ_SCHEDULING_PARKS_INTERNAL),
         Builtin::Log(&MZ_WORKER_COMPUTE_FRONTIERS),
         Builtin::Log(&MZ_WORKER_COMPUTE_IMPORT_FRONTIERS),
         Builtin::Log(&MZ_RAW_WORKER_COMPUTE_DELAYS),
+        Builtin::Log(&MZ_WORKER_NETWORK_BYTES),
         Builtin::Table(&MZ_VIEW_KEYS),
         Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
         Builtin::Table(&MZ_KAFKA_SINKS),
@@ -2645,6 +2872,12 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_CLUSTER_REPLICAS),
         Builtin::Table(&MZ_CLUSTER_REPLICA_STATUSES),
         Builtin::Table(&MZ_CLUSTER_REPLICA_HEARTBEATS),
+        Builtin::Table(&MZ_CLUSTER_REPLICA_LIVENESS),
+        Builtin::Table(&MZ_CLUSTER_REPLICA_PENDING_DDL),
+        Builtin::Table(&MZ_SOURCE_DETAILS),
+        Builtin::Table(&MZ_COORDINATOR_QUEUE_DEPTH_HISTORY),
+        Builtin::Table(&MZ_PENDING_SOURCES),
+        Builtin::Table(&MZ_PRIVILEGES),
         Builtin::Table(&MZ_AUDIT_EVENTS),
         Builtin::Table(&MZ_STORAGE_USAGE_BY_SHARD),
         Builtin::Table(&MZ_EGRESS_IPS),
@@ -2662,6 +2895,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_COMPUTE_OPERATOR_DURATIONS),
         Builtin::View(&MZ_WORKER_COMPUTE_DELAYS),
         Builtin::View(&MZ_PEEK_DURATIONS),
+        Builtin::View(&MZ_ZOMBIE_DATAFLOWS),
         Builtin::View(&MZ_RECORDS_PER_DATAFLOW_OPERATOR),
         Builtin::View(&MZ_RECORDS_PER_DATAFLOW),
         Builtin::View(&MZ_RECORDS_PER_DATAFLOW_GLOBAL),
@@ -2696,8 +2930,18 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&INFORMATION_SCHEMA_TABLES),
         Builtin::Source(&MZ_SINK_STATUS_HISTORY),
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
+        Builtin::View(&MZ_SOURCE_STATUS),
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::View(&MZ_STORAGE_USAGE),
+        Builtin::Source(&MZ_SOURCE_MESSAGE_SIZES_INTERNAL),
+        Builtin::View(&MZ_SOURCE_MESSAGE_SIZES),
+        Builtin::Source(&MZ_SINK_PROGRESS_PUBLISHES_INTERNAL),
+        Builtin::View(&MZ_SINK_PROGRESS_PUBLISHES),
+        Builtin::Source(&MZ_SINK_DEAD_LETTERS_INTERNAL),
+        Builtin::View(&MZ_SINK_DEAD_LETTERS),
+        Builtin::Source(&MZ_SOURCE_NUMERIC_OVERFLOWS_INTERNAL),
+        Builtin::View(&MZ_SOURCE_NUMERIC_OVERFLOWS),
+        Builtin::Source(&MZ_SINK_METRICS),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
         Builtin::Index(&MZ_SHOW_SCHEMAS_IND),
         Builtin::Index(&MZ_SHOW_CONNECTIONS_IND),
@@ -14,35 +14,42 @@ use chrono::{DateTime, Utc};
 use mz_audit_log::{EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage};
 use mz_compute_client::command::{ProcessId, ReplicaId};
 use mz_compute_client::controller::{
-    ComputeInstanceId, ComputeInstanceStatus, ComputeReplicaLocation,
+    ComputeInstanceId, ComputeInstanceStatus, ComputeReplicaLocation, PendingAck,
 };
 use mz_expr::MirScalarExpr;
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
-use mz_repr::{Datum, Diff, GlobalId, Row};
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row};
 use mz_sql::ast::{CreateIndexStatement, Statement};
-use mz_sql::catalog::{CatalogDatabase, CatalogType, TypeCategory};
-use mz_sql::names::{ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier};
+use mz_sql::catalog::{CatalogDatabase, CatalogType, Privilege, TypeCategory};
+use mz_sql::names::{ResolvedDatabaseSpecifier, RoleId, SchemaId, SchemaSpecifier};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_storage::types::connections::KafkaConnection;
 use mz_storage::types::hosts::StorageHostConfig;
 use mz_storage::types::sinks::{KafkaSinkConnection, StorageSinkConnection};
+use mz_storage::types::sources::encoding::DataEncoding;
+use mz_storage::types::sources::SourceDesc;
 
 use crate::catalog::builtin::{
     MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
-    MZ_CLUSTER_REPLICA_HEARTBEATS, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS, MZ_CONNECTIONS,
+    MZ_CLUSTER_REPLICA_HEARTBEATS, MZ_CLUSTER_REPLICA_LIVENESS, MZ_CLUSTER_REPLICA_PENDING_DDL,
+    MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS, MZ_CONNECTIONS, MZ_COORDINATOR_QUEUE_DEPTH_HISTORY,
     MZ_DATABASES, MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_CONNECTIONS,
-    MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_SCHEMAS, MZ_SECRETS, MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS,
-    MZ_STORAGE_USAGE_BY_SHARD, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
+    MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS, MZ_PENDING_SOURCES,
+    MZ_PRIVILEGES, MZ_PSEUDO_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SECRETS, MZ_SINKS, MZ_SOURCES,
+    MZ_SOURCE_DETAILS, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_TABLES, MZ_TYPES,
+    MZ_VIEWS,
 };
 use crate::catalog::{
     CatalogItem, CatalogState, Connection, Database, Error, ErrorKind, Func, Index,
     MaterializedView, Role, Sink, StorageSinkConnectionState, Type, View, SYSTEM_CONN_ID,
 };
-use crate::coord::ReplicaMetadata;
+use crate::client::ConnectionId;
+use crate::coord::queue_depth::QueueDepthSample;
+use crate::coord::{PendingPurification, ReplicaLiveness, ReplicaMetadata};
 
 use super::{DataSourceDesc, Ingestion};
 
@@ -69,6 +76,7 @@ impl CatalogState {
                 Datum::UInt64(database.id.0),
                 Datum::UInt32(database.oid),
                 Datum::String(database.name()),
+                Datum::String(&database.owner_id.to_string()),
             ]),
             diff,
         }
@@ -94,6 +102,7 @@ impl CatalogState {
                 Datum::UInt32(schema.oid),
                 Datum::from(database_id),
                 Datum::String(&schema.name.schema),
+                Datum::String(&schema.owner_id.to_string()),
             ]),
             diff,
         }
@@ -111,6 +120,58 @@ impl CatalogState {
         }
     }
 
+    pub(super) fn pack_role_privileges_update(
+        &self,
+        role: &Role,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        role.schema_privileges
+            .iter()
+            .flat_map(|(schema_id, privileges)| {
+                privileges.iter().map(move |privilege| {
+                    self.pack_privilege_update(role.id, None, Some(*schema_id), *privilege, diff)
+                })
+            })
+            .collect()
+    }
+
+    pub(super) fn pack_item_privileges_update(
+        &self,
+        id: GlobalId,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        self.get_entry(&id)
+            .privileges()
+            .iter()
+            .flat_map(|(role_id, privileges)| {
+                privileges.iter().map(move |privilege| {
+                    self.pack_privilege_update(*role_id, Some(id), None, *privilege, diff)
+                })
+            })
+            .collect()
+    }
+
+    fn pack_privilege_update(
+        &self,
+        grantee_id: RoleId,
+        object_id: Option<GlobalId>,
+        schema_id: Option<SchemaId>,
+        privilege: Privilege,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let object_id = object_id.map(|id| id.to_string());
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_PRIVILEGES),
+            row: Row::pack_slice(&[
+                Datum::String(&grantee_id.to_string()),
+                Datum::from(object_id.as_deref()),
+                Datum::from(schema_id.map(|id| id.0)),
+                Datum::String(&privilege.to_string()),
+            ]),
+            diff,
+        }
+    }
+
     pub(super) fn pack_compute_instance_update(
         &self,
         name: &str,
@@ -198,12 +259,17 @@ impl CatalogState {
             )
             .id;
         let name = &entry.name().item;
+        let owner_id = &entry.owner_id();
         let mut updates = match entry.item() {
             CatalogItem::Log(_) => {
-                self.pack_source_update(id, oid, schema_id, name, "log", None, None, diff)
+                self.pack_source_update(id, oid, schema_id, name, "log", None, None, owner_id, diff)
+            }
+            CatalogItem::Index(index) => {
+                self.pack_index_update(id, oid, name, index, owner_id, diff)
+            }
+            CatalogItem::Table(_) => {
+                self.pack_table_update(id, oid, schema_id, name, owner_id, diff)
             }
-            CatalogItem::Index(index) => self.pack_index_update(id, oid, name, index, diff),
-            CatalogItem::Table(_) => self.pack_table_update(id, oid, schema_id, name, diff),
             CatalogItem::Source(source) => {
                 let (source_type, connection_id) = match &source.data_source {
                     DataSourceDesc::Ingestion(ingestion) => (
@@ -212,9 +278,10 @@ impl CatalogState {
                     ),
                     DataSourceDesc::Source => ("subsource", None),
                     DataSourceDesc::Introspection(_) => ("source", None),
+                    DataSourceDesc::Webhook { .. } => ("webhook", None),
                 };
 
-                self.pack_source_update(
+                let mut updates = self.pack_source_update(
                     id,
                     oid,
                     schema_id,
@@ -228,19 +295,35 @@ impl CatalogState {
                         }) => Some(size.as_str()),
                         _ => None,
                     },
+                    owner_id,
                     diff,
-                )
+                );
+                if let DataSourceDesc::Ingestion(ingestion) = &source.data_source {
+                    updates.push(self.pack_source_details_update(
+                        id,
+                        &ingestion.desc,
+                        &source.desc,
+                        diff,
+                    ));
+                }
+                updates
+            }
+            CatalogItem::View(view) => {
+                self.pack_view_update(id, oid, schema_id, name, view, owner_id, diff)
             }
-            CatalogItem::View(view) => self.pack_view_update(id, oid, schema_id, name, view, diff),
             CatalogItem::MaterializedView(mview) => {
-                self.pack_materialized_view_update(id, oid, schema_id, name, mview, diff)
+                self.pack_materialized_view_update(id, oid, schema_id, name, mview, owner_id, diff)
+            }
+            CatalogItem::Sink(sink) => {
+                self.pack_sink_update(id, oid, schema_id, name, sink, owner_id, diff)
+            }
+            CatalogItem::Type(ty) => {
+                self.pack_type_update(id, oid, schema_id, name, ty, owner_id, diff)
             }
-            CatalogItem::Sink(sink) => self.pack_sink_update(id, oid, schema_id, name, sink, diff),
-            CatalogItem::Type(ty) => self.pack_type_update(id, oid, schema_id, name, ty, diff),
             CatalogItem::Func(func) => self.pack_func_update(id, schema_id, name, func, diff),
-            CatalogItem::Secret(_) => self.pack_secret_update(id, schema_id, name, diff),
+            CatalogItem::Secret(_) => self.pack_secret_update(id, schema_id, name, owner_id, diff),
             CatalogItem::Connection(connection) => {
-                self.pack_connection_update(id, oid, schema_id, name, connection, diff)
+                self.pack_connection_update(id, oid, schema_id, name, connection, owner_id, diff)
             }
         };
 
@@ -272,6 +355,8 @@ impl CatalogState {
             }
         }
 
+        updates.extend(self.pack_item_privileges_update(id, diff));
+
         updates
     }
 
@@ -281,6 +366,7 @@ impl CatalogState {
         oid: u32,
         schema_id: &SchemaSpecifier,
         name: &str,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         vec![BuiltinTableUpdate {
@@ -290,6 +376,7 @@ impl CatalogState {
                 Datum::UInt32(oid),
                 Datum::UInt64(schema_id.into()),
                 Datum::String(name),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }]
@@ -304,6 +391,7 @@ impl CatalogState {
         source_desc_name: &str,
         connection_id: Option<GlobalId>,
         size: Option<&str>,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         vec![BuiltinTableUpdate {
@@ -316,11 +404,63 @@ impl CatalogState {
                 Datum::String(source_desc_name),
                 Datum::from(connection_id.map(|id| id.to_string()).as_deref()),
                 Datum::from(size),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }]
     }
 
+    fn pack_source_details_update(
+        &self,
+        id: GlobalId,
+        source_desc: &SourceDesc,
+        desc: &RelationDesc,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let options = serde_json::Value::Object(
+            source_desc
+                .connection
+                .options()
+                .into_iter()
+                .map(|(k, v)| (k, serde_json::Value::String(v)))
+                .collect(),
+        );
+        let columns = serde_json::Value::Array(
+            desc.iter()
+                .map(|(name, typ)| {
+                    let pgtype = mz_pgrepr::Type::from(&typ.scalar_type);
+                    serde_json::json!({
+                        "name": name.as_str(),
+                        "type": pgtype.name(),
+                        "nullable": typ.nullable,
+                    })
+                })
+                .collect(),
+        );
+        let options = Jsonb::from_serde_json(options)
+            .expect("options are valid JSON")
+            .into_row();
+        let columns = Jsonb::from_serde_json(columns)
+            .expect("columns are valid JSON")
+            .into_row();
+        let format_name = |encoding: &DataEncoding| encoding.op_name().to_lowercase();
+        let key_format = source_desc.encoding.key_ref().map(format_name);
+        let value_format = format_name(source_desc.encoding.value_ref());
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_SOURCE_DETAILS),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::String(source_desc.name()),
+                options.unpack_first(),
+                Datum::from(key_format.as_deref()),
+                Datum::String(&value_format),
+                Datum::String(source_desc.envelope.name()),
+                columns.unpack_first(),
+            ]),
+            diff,
+        }
+    }
+
     fn pack_connection_update(
         &self,
         id: GlobalId,
@@ -328,6 +468,7 @@ impl CatalogState {
         schema_id: &SchemaSpecifier,
         name: &str,
         connection: &Connection,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let mut updates = vec![BuiltinTableUpdate {
@@ -343,9 +484,15 @@ impl CatalogState {
                         "confluent-schema-registry"
                     }
                     mz_storage::types::connections::Connection::Postgres { .. } => "postgres",
+                    mz_storage::types::connections::Connection::MySql { .. } => "mysql",
+                    mz_storage::types::connections::Connection::SqlServer { .. } => "sql-server",
                     mz_storage::types::connections::Connection::Aws(..) => "aws",
+                    mz_storage::types::connections::Connection::AwsPrivatelink(..) => {
+                        "aws-privatelink"
+                    }
                     mz_storage::types::connections::Connection::Ssh { .. } => "ssh-tunnel",
                 }),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }];
@@ -366,7 +513,10 @@ impl CatalogState {
             }
             mz_storage::types::connections::Connection::Csr(_)
             | mz_storage::types::connections::Connection::Postgres(_)
-            | mz_storage::types::connections::Connection::Aws(_) => {}
+            | mz_storage::types::connections::Connection::MySql(_)
+            | mz_storage::types::connections::Connection::SqlServer(_)
+            | mz_storage::types::connections::Connection::Aws(_)
+            | mz_storage::types::connections::Connection::AwsPrivatelink(_) => {}
         };
         updates
     }
@@ -427,6 +577,7 @@ impl CatalogState {
         schema_id: &SchemaSpecifier,
         name: &str,
         view: &View,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let create_sql = mz_sql::parse::parse(&view.create_sql)
@@ -450,6 +601,7 @@ impl CatalogState {
                 Datum::UInt64(schema_id.into()),
                 Datum::String(name),
                 Datum::String(&query_string),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }]
@@ -462,6 +614,7 @@ impl CatalogState {
         schema_id: &SchemaSpecifier,
         name: &str,
         mview: &MaterializedView,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let create_sql = mz_sql::parse::parse(&mview.create_sql)
@@ -486,6 +639,7 @@ impl CatalogState {
                 Datum::String(name),
                 Datum::String(&mview.compute_instance.to_string()),
                 Datum::String(&query_string),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }]
@@ -498,6 +652,7 @@ impl CatalogState {
         schema_id: &SchemaSpecifier,
         name: &str,
         sink: &Sink,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let mut updates = vec![];
@@ -517,6 +672,15 @@ impl CatalogState {
                         diff,
                     });
                 }
+                StorageSinkConnection::S3(_)
+                | StorageSinkConnection::Postgres(_)
+                | StorageSinkConnection::File(_)
+                | StorageSinkConnection::Elasticsearch(_)
+                | StorageSinkConnection::Redis(_)
+                | StorageSinkConnection::Nats(_)
+                | StorageSinkConnection::Mqtt(_)
+                | StorageSinkConnection::Grpc(_)
+                | StorageSinkConnection::Sqs(_) => {}
             };
             updates.push(BuiltinTableUpdate {
                 id: self.resolve_builtin_table(&MZ_SINKS),
@@ -528,6 +692,7 @@ impl CatalogState {
                     Datum::String(connection.name()),
                     Datum::from(sink.connection_id().map(|id| id.to_string()).as_deref()),
                     Datum::from(sink.host_config.size()),
+                    Datum::String(&owner_id.to_string()),
                 ]),
                 diff,
             });
@@ -541,6 +706,7 @@ impl CatalogState {
         oid: u32,
         name: &str,
         index: &Index,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let mut updates = vec![];
@@ -561,6 +727,7 @@ impl CatalogState {
                 Datum::String(name),
                 Datum::String(&index.on.to_string()),
                 Datum::String(&index.compute_instance.to_string()),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         });
@@ -610,6 +777,7 @@ impl CatalogState {
         schema_id: &SchemaSpecifier,
         name: &str,
         typ: &Type,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         let generic_update = BuiltinTableUpdate {
@@ -620,6 +788,7 @@ impl CatalogState {
                 Datum::UInt64(schema_id.into()),
                 Datum::String(name),
                 Datum::String(&TypeCategory::from_catalog_type(&typ.details.typ).to_string()),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         };
@@ -723,6 +892,7 @@ impl CatalogState {
         id: GlobalId,
         schema_id: &SchemaSpecifier,
         name: &str,
+        owner_id: &RoleId,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
         vec![BuiltinTableUpdate {
@@ -731,6 +901,7 @@ impl CatalogState {
                 Datum::String(&id.to_string()),
                 Datum::UInt64(schema_id.into()),
                 Datum::String(name),
+                Datum::String(&owner_id.to_string()),
             ]),
             diff,
         }]
@@ -802,6 +973,75 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_replica_liveness_update(
+        &self,
+        id: ReplicaId,
+        liveness: ReplicaLiveness,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let ReplicaLiveness {
+            last_heartbeat,
+            build_version,
+            uptime,
+        } = liveness;
+        let uptime_micros = i64::try_from(uptime.as_micros()).expect("must fit");
+        let table = self.resolve_builtin_table(&MZ_CLUSTER_REPLICA_LIVENESS);
+        let row = Row::pack_slice(&[
+            Datum::UInt64(id),
+            Datum::TimestampTz(last_heartbeat.try_into().expect("must fit")),
+            Datum::String(&build_version),
+            Datum::Interval(Interval::new(0, 0, uptime_micros)),
+        ]);
+        BuiltinTableUpdate {
+            id: table,
+            row,
+            diff,
+        }
+    }
+
+    pub fn pack_replica_pending_ddl_update(
+        &self,
+        ack: PendingAck,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let PendingAck {
+            collection_id,
+            replica_id,
+            issued_at,
+        } = ack;
+        let table = self.resolve_builtin_table(&MZ_CLUSTER_REPLICA_PENDING_DDL);
+        let row = Row::pack_slice(&[
+            Datum::String(&collection_id.to_string()),
+            Datum::UInt64(replica_id),
+            Datum::TimestampTz(issued_at.try_into().expect("must fit")),
+        ]);
+        BuiltinTableUpdate {
+            id: table,
+            row,
+            diff,
+        }
+    }
+
+    pub fn pack_pending_source_update(
+        &self,
+        conn_id: ConnectionId,
+        purification: &PendingPurification,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let table = self.resolve_builtin_table(&MZ_PENDING_SOURCES);
+        let row = Row::pack_slice(&[
+            Datum::UInt32(conn_id),
+            Datum::String(&purification.name),
+            Datum::String("validating"),
+            Datum::TimestampTz(purification.started_at.try_into().expect("must fit")),
+        ]);
+        BuiltinTableUpdate {
+            id: table,
+            row,
+            diff,
+        }
+    }
+
     pub fn pack_storage_usage_update(
         &self,
         VersionedStorageUsage::V1(event): &VersionedStorageUsage,
@@ -820,6 +1060,30 @@ impl CatalogState {
         Ok(BuiltinTableUpdate { id, row, diff: 1 })
     }
 
+    pub fn pack_queue_depth_update(
+        &self,
+        sample: &QueueDepthSample,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let id = self.resolve_builtin_table(&MZ_COORDINATOR_QUEUE_DEPTH_HISTORY);
+        let row = Row::pack_slice(&[
+            Datum::TimestampTz(
+                mz_ore::now::to_datetime(sample.sampled_at)
+                    .try_into()
+                    .expect("must fit"),
+            ),
+            Datum::UInt64(sample.command_queue_depth),
+            Datum::UInt64(sample.messages_processed),
+            Datum::UInt64(
+                u64::try_from(sample.max_processing_duration.as_nanos()).unwrap_or(u64::MAX),
+            ),
+            Datum::UInt64(
+                u64::try_from(sample.total_processing_duration.as_nanos()).unwrap_or(u64::MAX),
+            ),
+        ]);
+        BuiltinTableUpdate { id, row, diff }
+    }
+
     pub fn pack_egress_ip_update(&self, ip: &Ipv4Addr) -> Result<BuiltinTableUpdate, Error> {
         let id = self.resolve_builtin_table(&MZ_EGRESS_IPS);
         let row = Row::pack_slice(&[Datum::String(&ip.to_string())]);
@@ -58,6 +58,10 @@ pub enum ErrorKind {
     ReadOnlyItem(String),
     #[error("cannot drop non-empty schema '{0}'")]
     SchemaNotEmpty(String),
+    #[error("role '{0}' cannot be dropped because it has been granted privileges")]
+    RoleHasPrivileges(String),
+    #[error("role '{0}' cannot be dropped because it owns objects")]
+    RoleOwnsObjects(String),
     #[error("non-temporary items cannot depend on temporary item '{0}'")]
     InvalidTemporaryDependency(String),
     #[error("cannot create temporary item in non-temporary schema")]
@@ -113,7 +117,16 @@ impl Error {
 
     /// Reports a hint for the user about how the error could be fixed.
     pub fn hint(&self) -> Option<String> {
-        None
+        match &self.kind {
+            ErrorKind::RoleHasPrivileges(_) => {
+                Some("Revoke the role's privileges with REVOKE first.".into())
+            }
+            ErrorKind::RoleOwnsObjects(_) => Some(
+                "Drop the objects or transfer their ownership with ALTER ... OWNER TO first."
+                    .into(),
+            ),
+            _ => None,
+        }
     }
 }
 
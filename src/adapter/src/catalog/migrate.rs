@@ -23,7 +23,7 @@ where
     F: FnMut(&mut mz_sql::ast::Statement<Raw>) -> Result<(), anyhow::Error>,
 {
     let items = tx.loaded_items();
-    for (id, name, SerializedCatalogItem::V1 { create_sql }) in items {
+    for (id, name, SerializedCatalogItem::V1 { create_sql }, _privileges, _owner_id) in items {
         let mut stmt = mz_sql::parse::parse(&create_sql)?.into_element();
 
         f(&mut stmt)?;
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
 use std::iter::once;
 use std::time::Duration;
@@ -24,7 +24,7 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::now::EpochMillis;
 use mz_repr::GlobalId;
-use mz_sql::catalog::{CatalogError as SqlCatalogError, CatalogItemType};
+use mz_sql::catalog::{CatalogError as SqlCatalogError, CatalogItemType, Privilege};
 use mz_sql::names::{
     DatabaseId, ObjectQualifiers, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId, SchemaId,
     SchemaSpecifier,
@@ -35,10 +35,10 @@ use mz_storage::types::sources::Timeline;
 use crate::catalog;
 use crate::catalog::builtin::{
     BuiltinLog, BUILTIN_COMPUTE_INSTANCES, BUILTIN_COMPUTE_REPLICAS, BUILTIN_PREFIXES,
-    BUILTIN_ROLES,
+    BUILTIN_ROLES, MZ_SYSTEM_ROLE,
 };
 use crate::catalog::error::{Error, ErrorKind};
-use crate::catalog::{is_reserved_name, SystemObjectMapping};
+use crate::catalog::{is_reserved_name, SystemObjectMapping, MZ_SYSTEM_ROLE_ID};
 use crate::catalog::{SerializedComputeReplicaConfig, DEFAULT_CLUSTER_REPLICA_NAME};
 
 use super::{
@@ -168,6 +168,7 @@ async fn migrate<S: Append>(
                 },
                 DatabaseValue {
                     name: "materialize".into(),
+                    owner_id: None,
                 },
             )?;
             let id = txn.get_and_increment_id(AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
@@ -195,6 +196,7 @@ async fn migrate<S: Append>(
                 SchemaValue {
                     database_id: None,
                     name: "mz_catalog".into(),
+                    owner_id: None,
                 },
             )?;
             txn.schemas.insert(
@@ -204,6 +206,7 @@ async fn migrate<S: Append>(
                 SchemaValue {
                     database_id: None,
                     name: "pg_catalog".into(),
+                    owner_id: None,
                 },
             )?;
             txn.schemas.insert(
@@ -213,6 +216,7 @@ async fn migrate<S: Append>(
                 SchemaValue {
                     database_id: Some(1),
                     name: "public".into(),
+                    owner_id: None,
                 },
             )?;
             let id = txn.get_and_increment_id(AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
@@ -241,6 +245,7 @@ async fn migrate<S: Append>(
                 SchemaValue {
                     database_id: None,
                     name: "mz_internal".into(),
+                    owner_id: None,
                 },
             )?;
             txn.schemas.insert(
@@ -250,6 +255,7 @@ async fn migrate<S: Append>(
                 SchemaValue {
                     database_id: None,
                     name: "information_schema".into(),
+                    owner_id: None,
                 },
             )?;
             txn.roles.insert(
@@ -258,6 +264,8 @@ async fn migrate<S: Append>(
                 },
                 RoleValue {
                     name: "materialize".into(),
+                    superuser: true,
+                    schema_privileges: vec![],
                 },
             )?;
             let id = txn.get_and_increment_id(AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
@@ -356,6 +364,48 @@ async fn migrate<S: Append>(
         |_, _| Ok(()),
         |_, _| Ok(()),
         |_, _| Ok(()),
+        // > Record an owner for every database, schema, and item.
+        // >
+        // > Introduced in v0.29.0.
+        // >
+        // > Objects created before owners were recorded are assigned to the
+        // > `materialize` role if it still exists, and otherwise to the
+        // > `mz_system` role, which always has the first system role ID.
+        // > Schemas in the ambient database are owned by `mz_system`.
+        |txn: &mut Transaction<'_, S>, _bootstrap_args| {
+            let default_owner_id = if txn.roles.items().contains_key(&RoleKey {
+                id: RoleId::User(MATERIALIZE_ROLE_ID),
+            }) {
+                RoleId::User(MATERIALIZE_ROLE_ID)
+            } else {
+                MZ_SYSTEM_ROLE_ID
+            };
+            txn.databases.update(|_k, v| match v.owner_id {
+                Some(_) => None,
+                None => Some(DatabaseValue {
+                    owner_id: Some(default_owner_id),
+                    ..v.clone()
+                }),
+            })?;
+            txn.schemas.update(|_k, v| match v.owner_id {
+                Some(_) => None,
+                None => Some(SchemaValue {
+                    owner_id: Some(match v.database_id {
+                        Some(_) => default_owner_id,
+                        None => MZ_SYSTEM_ROLE_ID,
+                    }),
+                    ..v.clone()
+                }),
+            })?;
+            txn.items.update(|_k, v| match v.owner_id {
+                Some(_) => None,
+                None => Some(ItemValue {
+                    owner_id: Some(default_owner_id),
+                    ..v.clone()
+                }),
+            })?;
+            Ok(())
+        },
         // Add new migrations above.
         //
         // Migrations should be preceded with a comment of the following form:
@@ -407,7 +457,13 @@ fn add_new_builtin_roles_migration<S: Append>(
             BUILTIN_PREFIXES.join(", ")
         );
         if !role_names.contains(builtin_role.name) {
-            txn.insert_system_role(builtin_role.name)?;
+            let id = txn.insert_system_role(builtin_role.name)?;
+            if builtin_role.name == MZ_SYSTEM_ROLE.name {
+                assert_eq!(
+                    id, MZ_SYSTEM_ROLE_ID,
+                    "objects without an owner are assigned to {MZ_SYSTEM_ROLE_ID}"
+                );
+            }
         }
     }
     Ok(())
@@ -607,38 +663,57 @@ impl<S: Append> Connection<S> {
             .await
     }
 
-    pub async fn load_databases(&mut self) -> Result<Vec<(DatabaseId, String)>, Error> {
+    pub async fn load_databases(&mut self) -> Result<Vec<(DatabaseId, String, RoleId)>, Error> {
         Ok(COLLECTION_DATABASE
             .peek_one(&mut self.stash)
             .await?
             .into_iter()
-            .map(|(k, v)| (DatabaseId::new(k.id), v.name))
+            .map(|(k, v)| (DatabaseId::new(k.id), v.name, v.owner_id()))
             .collect())
     }
 
     pub async fn load_schemas(
         &mut self,
-    ) -> Result<Vec<(SchemaId, String, Option<DatabaseId>)>, Error> {
+    ) -> Result<Vec<(SchemaId, String, Option<DatabaseId>, RoleId)>, Error> {
         Ok(COLLECTION_SCHEMA
             .peek_one(&mut self.stash)
             .await?
             .into_iter()
             .map(|(k, v)| {
+                let owner_id = v.owner_id();
                 (
                     SchemaId::new(k.id),
                     v.name,
                     v.database_id.map(DatabaseId::new),
+                    owner_id,
                 )
             })
             .collect())
     }
 
-    pub async fn load_roles(&mut self) -> Result<Vec<(RoleId, String)>, Error> {
+    pub async fn load_roles(
+        &mut self,
+    ) -> Result<
+        Vec<(
+            RoleId,
+            String,
+            bool,
+            BTreeMap<SchemaId, BTreeSet<Privilege>>,
+        )>,
+        Error,
+    > {
         Ok(COLLECTION_ROLE
             .peek_one(&mut self.stash)
             .await?
             .into_iter()
-            .map(|(k, v)| (k.id, v.name))
+            .map(|(k, v)| {
+                let schema_privileges = v
+                    .schema_privileges
+                    .into_iter()
+                    .map(|(schema_id, privileges)| (SchemaId::new(schema_id), privileges))
+                    .collect();
+                (k.id, v.name, v.superuser, schema_privileges)
+            })
             .collect())
     }
 
@@ -990,7 +1065,15 @@ pub struct Transaction<'a, S> {
 }
 
 impl<'a, S: Append> Transaction<'a, S> {
-    pub fn loaded_items(&self) -> Vec<(GlobalId, QualifiedObjectName, SerializedCatalogItem)> {
+    pub fn loaded_items(
+        &self,
+    ) -> Vec<(
+        GlobalId,
+        QualifiedObjectName,
+        SerializedCatalogItem,
+        BTreeMap<RoleId, BTreeSet<Privilege>>,
+        RoleId,
+    )> {
         let databases = self.databases.items();
         let schemas = self.schemas.items();
         let mut items = Vec::new();
@@ -1017,9 +1100,11 @@ impl<'a, S: Append> Transaction<'a, S> {
                     item: v.name.clone(),
                 },
                 v.definition.clone(),
+                v.privileges.iter().cloned().collect(),
+                v.owner_id(),
             ));
         });
-        items.sort_by_key(|(id, _, _)| *id);
+        items.sort_by_key(|(id, _, _, _, _)| *id);
         items
     }
 
@@ -1032,12 +1117,17 @@ impl<'a, S: Append> Transaction<'a, S> {
             .push((StorageUsageKey { metric }, (), 1));
     }
 
-    pub fn insert_database(&mut self, database_name: &str) -> Result<DatabaseId, Error> {
+    pub fn insert_database(
+        &mut self,
+        database_name: &str,
+        owner_id: RoleId,
+    ) -> Result<DatabaseId, Error> {
         let id = self.get_and_increment_id(DATABASE_ID_ALLOC_KEY.to_string())?;
         match self.databases.insert(
             DatabaseKey { id },
             DatabaseValue {
                 name: database_name.to_string(),
+                owner_id: Some(owner_id),
             },
         ) {
             Ok(_) => Ok(DatabaseId::new(id)),
@@ -1051,6 +1141,7 @@ impl<'a, S: Append> Transaction<'a, S> {
         &mut self,
         database_id: DatabaseId,
         schema_name: &str,
+        owner_id: RoleId,
     ) -> Result<SchemaId, Error> {
         let id = self.get_and_increment_id(SCHEMA_ID_ALLOC_KEY.to_string())?;
         match self.schemas.insert(
@@ -1058,6 +1149,7 @@ impl<'a, S: Append> Transaction<'a, S> {
             SchemaValue {
                 database_id: Some(database_id.0),
                 name: schema_name.to_string(),
+                owner_id: Some(owner_id),
             },
         ) {
             Ok(_) => Ok(SchemaId::new(id)),
@@ -1067,17 +1159,18 @@ impl<'a, S: Append> Transaction<'a, S> {
         }
     }
 
-    pub fn insert_user_role(&mut self, role_name: &str) -> Result<RoleId, Error> {
-        self.insert_role(role_name, USER_ROLE_ID_ALLOC_KEY, RoleId::User)
+    pub fn insert_user_role(&mut self, role_name: &str, superuser: bool) -> Result<RoleId, Error> {
+        self.insert_role(role_name, superuser, USER_ROLE_ID_ALLOC_KEY, RoleId::User)
     }
 
     fn insert_system_role(&mut self, role_name: &str) -> Result<RoleId, Error> {
-        self.insert_role(role_name, SYSTEM_ROLE_ID_ALLOC_KEY, RoleId::System)
+        self.insert_role(role_name, true, SYSTEM_ROLE_ID_ALLOC_KEY, RoleId::System)
     }
 
     fn insert_role<F>(
         &mut self,
         role_name: &str,
+        superuser: bool,
         id_alloc_key: &str,
         role_id_variant: F,
     ) -> Result<RoleId, Error>
@@ -1090,6 +1183,8 @@ impl<'a, S: Append> Transaction<'a, S> {
             RoleKey { id },
             RoleValue {
                 name: role_name.to_string(),
+                superuser,
+                schema_privileges: vec![],
             },
         ) {
             Ok(_) => Ok(id),
@@ -1209,6 +1304,7 @@ impl<'a, S: Append> Transaction<'a, S> {
         schema_id: SchemaId,
         item_name: &str,
         item: SerializedCatalogItem,
+        owner_id: RoleId,
     ) -> Result<(), Error> {
         match self.items.insert(
             ItemKey { gid: id },
@@ -1216,6 +1312,8 @@ impl<'a, S: Append> Transaction<'a, S> {
                 schema_id: schema_id.0,
                 name: item_name.to_string(),
                 definition: item,
+                privileges: vec![],
+                owner_id: Some(owner_id),
             },
         ) {
             Ok(_) => Ok(()),
@@ -1345,6 +1443,34 @@ impl<'a, S: Append> Transaction<'a, S> {
                     schema_id: v.schema_id,
                     name: item_name.to_string(),
                     definition: item.clone(),
+                    privileges: v.privileges.clone(),
+                    owner_id: v.owner_id,
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownItem(id.to_string()).into())
+        }
+    }
+
+    pub fn update_item_privileges(
+        &mut self,
+        id: GlobalId,
+        privileges: &BTreeMap<RoleId, BTreeSet<Privilege>>,
+    ) -> Result<(), Error> {
+        let n = self.items.update(|k, v| {
+            if k.gid == id {
+                Some(ItemValue {
+                    privileges: privileges
+                        .iter()
+                        .map(|(role_id, privileges)| (*role_id, privileges.clone()))
+                        .collect(),
+                    ..v.clone()
                 })
             } else {
                 None
@@ -1358,6 +1484,94 @@ impl<'a, S: Append> Transaction<'a, S> {
         }
     }
 
+    pub fn update_database_owner(&mut self, id: DatabaseId, owner_id: RoleId) -> Result<(), Error> {
+        let n = self.databases.update(|k, v| {
+            if k.id == id.0 {
+                Some(DatabaseValue {
+                    owner_id: Some(owner_id),
+                    ..v.clone()
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownDatabase(id.to_string()).into())
+        }
+    }
+
+    pub fn update_schema_owner(
+        &mut self,
+        database_id: DatabaseId,
+        schema_id: SchemaId,
+        owner_id: RoleId,
+    ) -> Result<(), Error> {
+        let n = self.schemas.update(|k, v| {
+            if k.id == schema_id.0 {
+                Some(SchemaValue {
+                    owner_id: Some(owner_id),
+                    ..v.clone()
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownSchema(format!("{}.{}", database_id.0, schema_id.0)).into())
+        }
+    }
+
+    pub fn update_item_owner(&mut self, id: GlobalId, owner_id: RoleId) -> Result<(), Error> {
+        let n = self.items.update(|k, v| {
+            if k.gid == id {
+                Some(ItemValue {
+                    owner_id: Some(owner_id),
+                    ..v.clone()
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownItem(id.to_string()).into())
+        }
+    }
+
+    pub fn update_role_privileges(
+        &mut self,
+        id: RoleId,
+        schema_privileges: &BTreeMap<SchemaId, BTreeSet<Privilege>>,
+    ) -> Result<(), Error> {
+        let n = self.roles.update(|k, v| {
+            if k.id == id {
+                Some(RoleValue {
+                    schema_privileges: schema_privileges
+                        .iter()
+                        .map(|(schema_id, privileges)| (schema_id.0, privileges.clone()))
+                        .collect(),
+                    ..v.clone()
+                })
+            } else {
+                None
+            }
+        })?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownRole(id.to_string()).into())
+        }
+    }
+
     pub fn update_user_version(&mut self, version: u64) -> Result<(), Error> {
         let n = self.configs.update(|k, _v| {
             if k == USER_VERSION {
@@ -1720,6 +1934,16 @@ pub struct ComputeReplicaValue {
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
 pub struct DatabaseValue {
     name: String,
+    /// The role that owns the database. Only absent in catalogs written
+    /// before owners were recorded.
+    #[serde(default)]
+    owner_id: Option<RoleId>,
+}
+
+impl DatabaseValue {
+    fn owner_id(&self) -> RoleId {
+        self.owner_id.unwrap_or(MZ_SYSTEM_ROLE_ID)
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
@@ -1731,6 +1955,16 @@ pub struct SchemaKey {
 pub struct SchemaValue {
     database_id: Option<u64>,
     name: String,
+    /// The role that owns the schema. Only absent in catalogs written before
+    /// owners were recorded.
+    #[serde(default)]
+    owner_id: Option<RoleId>,
+}
+
+impl SchemaValue {
+    fn owner_id(&self) -> RoleId {
+        self.owner_id.unwrap_or(MZ_SYSTEM_ROLE_ID)
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
@@ -1743,6 +1977,19 @@ pub struct ItemValue {
     schema_id: u64,
     name: String,
     definition: SerializedCatalogItem,
+    /// The privileges granted on the item, by grantee.
+    #[serde(default)]
+    privileges: Vec<(RoleId, BTreeSet<Privilege>)>,
+    /// The role that owns the item. Only absent in catalogs written before
+    /// owners were recorded.
+    #[serde(default)]
+    owner_id: Option<RoleId>,
+}
+
+impl ItemValue {
+    fn owner_id(&self) -> RoleId {
+        self.owner_id.unwrap_or(MZ_SYSTEM_ROLE_ID)
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
@@ -1753,6 +2000,17 @@ pub struct RoleKey {
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
 pub struct RoleValue {
     name: String,
+    /// Roles created before superuser status was recorded could only be
+    /// superusers.
+    #[serde(default = "default_superuser")]
+    superuser: bool,
+    /// The privileges granted to the role on schemas, by schema ID.
+    #[serde(default)]
+    schema_privileges: Vec<(u64, BTreeSet<Privilege>)>,
+}
+
+fn default_superuser() -> bool {
+    true
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
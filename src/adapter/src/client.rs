@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -18,6 +19,7 @@ use mz_ore::id_gen::IdAllocator;
 use mz_ore::thread::JoinOnDropHandle;
 use mz_repr::{GlobalId, Row, ScalarType};
 use mz_sql::ast::{Raw, Statement};
+use mz_sql::names::PartialObjectName;
 
 use crate::command::{Canceled, Command, ExecuteResponse, Response, StartupResponse};
 use crate::error::AdapterError;
@@ -63,17 +65,32 @@ impl Handle {
 #[derive(Debug, Clone)]
 pub struct Client {
     cmd_tx: mpsc::UnboundedSender<Command>,
+    /// The number of commands sent to the coordinator that it has not yet
+    /// received.
+    cmd_queue_depth: Arc<AtomicUsize>,
     id_alloc: Arc<IdAllocator<ConnectionId>>,
 }
 
 impl Client {
-    pub(crate) fn new(cmd_tx: mpsc::UnboundedSender<Command>) -> Client {
+    pub(crate) fn new(
+        cmd_tx: mpsc::UnboundedSender<Command>,
+        cmd_queue_depth: Arc<AtomicUsize>,
+    ) -> Client {
         Client {
             cmd_tx,
+            cmd_queue_depth,
             id_alloc: Arc::new(IdAllocator::new(1, 1 << 16)),
         }
     }
 
+    /// Sends a command to the coordinator.
+    fn send_command(&self, cmd: Command) {
+        self.cmd_queue_depth.fetch_add(1, Ordering::Relaxed);
+        self.cmd_tx
+            .send(cmd)
+            .expect("coordinator unexpectedly gone");
+    }
+
     /// Allocates a client for an incoming connection.
     pub fn new_conn(&self) -> Result<ConnClient, AdapterError> {
         Ok(ConnClient {
@@ -154,13 +171,10 @@ impl ConnClient {
 
     /// Cancels the query currently running on another connection.
     pub fn cancel_request(&mut self, conn_id: ConnectionId, secret_key: u32) {
-        self.inner
-            .cmd_tx
-            .send(Command::CancelRequest {
-                conn_id,
-                secret_key,
-            })
-            .expect("coordinator unexpectedly gone");
+        self.inner.send_command(Command::CancelRequest {
+            conn_id,
+            secret_key,
+        });
     }
 
     async fn send<T, F>(&mut self, f: F) -> T
@@ -168,10 +182,7 @@ impl ConnClient {
         F: FnOnce(oneshot::Sender<T>) -> Command,
     {
         let (tx, rx) = oneshot::channel();
-        self.inner
-            .cmd_tx
-            .send(f(tx))
-            .expect("coordinator unexpectedly gone");
+        self.inner.send_command(f(tx));
         rx.await.expect("coordinator unexpectedly canceled request")
     }
 }
@@ -349,6 +360,26 @@ impl SessionClient {
         .await
     }
 
+    /// Appends the body of a request to the named webhook source.
+    ///
+    /// The `signature` is the hex-encoded HMAC-SHA256 of the body, which is
+    /// required if the source validates requests with a secret.
+    pub async fn append_webhook(
+        &mut self,
+        name: PartialObjectName,
+        body: String,
+        signature: Option<String>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        self.send(|tx, session| Command::AppendWebhook {
+            name,
+            body,
+            signature,
+            session,
+            tx,
+        })
+        .await
+    }
+
     /// Returns a mutable reference to the session bound to this client.
     pub fn session(&mut self) -> &mut Session {
         self.session.as_mut().unwrap()
@@ -373,9 +404,7 @@ impl Drop for SessionClient {
         if let Some(session) = self.session.take() {
             self.inner
                 .inner
-                .cmd_tx
-                .send(Command::Terminate { session })
-                .expect("coordinator unexpectedly gone");
+                .send_command(Command::Terminate { session });
         }
     }
 }
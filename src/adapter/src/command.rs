@@ -26,6 +26,7 @@ use mz_ore::str::StrExt;
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::{GlobalId, Row, ScalarType};
 use mz_sql::ast::{FetchDirection, ObjectType, Raw, Statement};
+use mz_sql::names::PartialObjectName;
 use mz_sql::plan::ExecuteTimeout;
 
 use crate::client::ConnectionId;
@@ -102,6 +103,14 @@ pub enum Command {
         tx: oneshot::Sender<Response<ExecuteResponse>>,
     },
 
+    AppendWebhook {
+        name: PartialObjectName,
+        body: String,
+        signature: Option<String>,
+        session: Session,
+        tx: oneshot::Sender<Response<ExecuteResponse>>,
+    },
+
     Terminate {
         session: Session,
     },
@@ -274,12 +283,16 @@ pub enum ExecuteResponse {
         /// How long to wait for results to arrive.
         timeout: ExecuteTimeout,
     },
+    /// The requested privileges were granted.
+    GrantedPrivilege,
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
     /// The specified prepared statement was created.
     Prepare,
     /// A user-requested warning was raised.
     Raised,
+    /// The requested privileges were revoked.
+    RevokedPrivilege,
     /// Rows will be delivered via the specified future.
     SendingRows {
         #[derivative(Debug = "ignore")]
@@ -354,6 +367,7 @@ impl ExecuteResponse {
             DroppedSecret => Some("DROP SECRET".into()),
             EmptyQuery => None,
             Fetch { .. } => None,
+            GrantedPrivilege => Some("GRANT".into()),
             Inserted(n) => {
                 // "On successful completion, an INSERT command returns a
                 // command tag of the form `INSERT <oid> <count>`."
@@ -366,6 +380,7 @@ impl ExecuteResponse {
             }
             Prepare => Some("PREPARE".into()),
             Raised => Some("RAISE".into()),
+            RevokedPrivilege => Some("REVOKE".into()),
             SendingRows { .. } => None,
             SetVariable { reset: true, .. } => Some("RESET".into()),
             SetVariable { reset: false, .. } => Some("SET".into()),
@@ -385,9 +400,14 @@ impl ExecuteResponse {
 
         match plan {
             AbortTransaction => vec![TransactionRolledBack],
-            AlterItemRename | AlterNoop | AlterSecret | AlterSink | AlterSource | RotateKeys => {
-                vec![AlteredObject]
-            }
+            AlterItemRename
+            | AlterNoop
+            | AlterOwner
+            | AlterSecret
+            | AlterSink
+            | AlterSource
+            | AlterSourceRefreshSchema
+            | RotateKeys => vec![AlteredObject],
             AlterIndexSetOptions | AlterIndexResetOptions => {
                 vec![AlteredObject, AlteredIndexLogicalCompaction]
             }
@@ -437,9 +457,11 @@ impl ExecuteResponse {
             }
             Execute | ReadThenWrite | SendDiffs => vec![Deleted, Inserted, SendingRows, Updated],
             PlanKind::Fetch => vec![ExecuteResponseKind::Fetch],
+            GrantPrivileges => vec![GrantedPrivilege],
             Insert => vec![Inserted, SendingRows],
             PlanKind::Prepare => vec![ExecuteResponseKind::Prepare],
             PlanKind::Raise => vec![ExecuteResponseKind::Raised],
+            RevokePrivileges => vec![RevokedPrivilege],
             PlanKind::SetVariable | ResetVariable => vec![ExecuteResponseKind::SetVariable],
             PlanKind::Subscribe => vec![Subscribing, CopyTo],
             StartTransaction => vec![StartedTransaction],
@@ -70,9 +70,10 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::net::Ipv4Addr;
 use std::num::NonZeroUsize;
 use std::ops::Neg;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
 use derivative::Derivative;
@@ -106,7 +107,7 @@ use mz_sql::plan::{MutationKind, Params};
 use mz_stash::Append;
 use mz_storage::controller::{CollectionDescription, CreateExportToken, DataSource, StorageError};
 use mz_storage::types::connections::ConnectionContext;
-use mz_storage::types::sinks::StorageSinkConnection;
+use mz_storage::types::sinks::{SinkAsOf, StorageSinkConnection};
 use mz_storage::types::sources::{IngestionDescription, SourceExport, Timeline};
 use mz_transform::Optimizer;
 
@@ -121,16 +122,18 @@ use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, PendingWriteTxn}
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::metrics::Metrics;
 use crate::coord::peek::PendingPeek;
+use crate::coord::queue_depth::QueueDepthTracker;
 use crate::coord::read_policy::{ReadCapability, ReadHolds};
 use crate::coord::timeline::{TimelineState, WriteTimestamp};
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, Session};
-use crate::subscribe::PendingSubscribe;
+use crate::subscribe::{self, PendingSubscribe};
 use crate::util::{ClientTransmitter, CompletedClientTransmitter};
 use crate::AdapterNotice;
 
 pub(crate) mod id_bundle;
 pub(crate) mod peek;
+pub(crate) mod queue_depth;
 
 mod appends;
 mod command_handler;
@@ -158,6 +161,7 @@ pub enum Message<T = mz_repr::Timestamp> {
     Command(Command),
     ControllerReady,
     CreateSourceStatementReady(CreateSourceStatementReady),
+    SourceSchemaRefreshed(SourceSchemaRefreshed),
     SinkConnectionReady(SinkConnectionReady),
     SendDiffs(SendDiffs),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
@@ -181,6 +185,9 @@ pub enum Message<T = mz_repr::Timestamp> {
     StorageUsageFetch,
     StorageUsageUpdate(HashMap<Option<ShardId>, u64>),
     Consolidate(Vec<mz_stash::Id>),
+    SampleQueueDepth,
+    /// Emits heartbeats for, and reaps idle, pending subscribes.
+    SubscribeHeartbeat,
 }
 
 #[derive(Derivative)]
@@ -214,6 +221,26 @@ pub struct CreateSourceStatementReady {
     pub otel_ctx: OpenTelemetryContext,
 }
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct SourceSchemaRefreshed {
+    pub session: Session,
+    #[derivative(Debug = "ignore")]
+    pub tx: ClientTransmitter<ExecuteResponse>,
+    /// The source whose schema was refreshed.
+    pub id: GlobalId,
+    /// The `create_sql` of the source when the refresh started.
+    pub create_sql: String,
+    pub result: Result<
+        (
+            Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
+            CreateSourceStatement<Aug>,
+        ),
+        AdapterError,
+    >,
+    pub otel_ctx: OpenTelemetryContext,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct SinkConnectionReady {
@@ -222,6 +249,8 @@ pub struct SinkConnectionReady {
     pub id: GlobalId,
     pub oid: u32,
     pub create_export_token: CreateExportToken,
+    /// The as-of of the sink, determined when the sink was created.
+    pub as_of: SinkAsOf<Timestamp>,
     pub result: Result<StorageSinkConnection, AdapterError>,
 }
 
@@ -257,6 +286,30 @@ pub struct ReplicaMetadata {
     pub last_heartbeat: DateTime<Utc>,
 }
 
+/// Soft-state liveness information about a compute replica, as reported by
+/// the replica itself
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReplicaLiveness {
+    /// The last time the replica sent a heartbeat (possibly rounded)
+    pub last_heartbeat: DateTime<Utc>,
+    /// The version of the build the replica is running
+    pub build_version: String,
+    /// The uptime of the replica process, as of its last heartbeat
+    pub uptime: Duration,
+}
+
+/// A `CREATE SOURCE` statement that is being purified in the background,
+/// intended for inclusion in system tables.
+#[derive(Debug)]
+pub struct PendingPurification {
+    /// The name of the source, as written in the statement.
+    pub name: String,
+    /// When purification started.
+    pub started_at: DateTime<Utc>,
+    /// Cancels purification when sent to or dropped.
+    cancel_tx: oneshot::Sender<()>,
+}
+
 /// Metadata about an active connection.
 struct ConnMeta {
     /// A watch channel shared with the client to inform the client of
@@ -350,6 +403,10 @@ pub struct Coordinator<S> {
     /// A map from pending subscribes to the subscribe description.
     pending_subscribes: HashMap<GlobalId, PendingSubscribe>,
 
+    /// A map from client connection ids to the `CREATE SOURCE` statement being
+    /// purified on behalf of that client, if any.
+    pending_purifications: HashMap<ConnectionId, PendingPurification>,
+
     /// Serializes accesses to write critical sections.
     write_lock: Arc<tokio::sync::Mutex<()>>,
     /// Holds plans deferred due to write lock.
@@ -370,6 +427,11 @@ pub struct Coordinator<S> {
     /// `None` is used as a tombstone value for replicas that have been
     /// dropped and for which no further updates should be recorded.
     transient_replica_metadata: HashMap<ReplicaId, Option<ReplicaMetadata>>,
+    /// Liveness information reported by replicas, intended for inclusion in
+    /// system tables.
+    ///
+    /// `None` is a tombstone, as in `transient_replica_metadata`.
+    transient_replica_liveness: HashMap<ReplicaId, Option<ReplicaLiveness>>,
 
     /// Persist client for fetching storage metadata such as size metrics.
     storage_usage_client: StorageUsageClient,
@@ -381,6 +443,10 @@ pub struct Coordinator<S> {
 
     /// Coordinator metrics.
     metrics: Metrics,
+
+    /// Tracks the depth of the command queue and the time spent processing
+    /// messages, for `mz_internal.mz_coordinator_queue_depth_history`.
+    queue_depth: QueueDepthTracker,
 }
 
 impl<S: Append + 'static> Coordinator<S> {
@@ -395,6 +461,9 @@ impl<S: Append + 'static> Coordinator<S> {
     ) -> Result<(), AdapterError> {
         // Capture identifiers that need to have their read holds relaxed once the bootstrap completes.
         let mut policies_to_set: CollectionIdBundle = Default::default();
+        // Materialized views with a non-default compaction window have their
+        // read policies relaxed separately.
+        let mut mview_policies_to_set = Vec::new();
 
         for instance in self.catalog.compute_instances() {
             self.controller.compute.create_instance(
@@ -517,7 +586,9 @@ impl<S: Append + 'static> Coordinator<S> {
                                 host_config: ingestion.host_config.clone(),
                             })
                         }
-                        DataSourceDesc::Source => DataSource::Other,
+                        DataSourceDesc::Source | DataSourceDesc::Webhook { .. } => {
+                            DataSource::Other
+                        }
                         DataSourceDesc::Introspection(introspection) => {
                             DataSource::Introspection(*introspection)
                         }
@@ -586,7 +657,11 @@ impl<S: Append + 'static> Coordinator<S> {
                         .await
                         .unwrap();
 
-                    policies_to_set.storage_ids.insert(entry.id());
+                    if mview.compaction_window_ms == DEFAULT_LOGICAL_COMPACTION_WINDOW_MS {
+                        policies_to_set.storage_ids.insert(entry.id());
+                    } else {
+                        mview_policies_to_set.push((entry.id(), mview.compaction_window_ms));
+                    }
 
                     // Re-create the sink on the compute instance.
                     let id_bundle = self
@@ -620,6 +695,9 @@ impl<S: Append + 'static> Coordinator<S> {
                         .prepare_export(id, sink.from)
                         .await
                         .unwrap();
+                    // If the sink was already exported before the restart,
+                    // the storage controller keeps its original as-of.
+                    let as_of = self.storage_sink_as_of(sink).await;
 
                     task::spawn(
                         || format!("sink_connection_ready:{}", sink.from),
@@ -646,6 +724,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                     id,
                                     oid,
                                     create_export_token,
+                                    as_of,
                                     result: conn_result,
                                 },
                             ));
@@ -667,6 +746,10 @@ impl<S: Append + 'static> Coordinator<S> {
         // Having installed all entries, creating all constraints, we can now relax read policies.
         self.initialize_read_policies(policies_to_set, DEFAULT_LOGICAL_COMPACTION_WINDOW_MS)
             .await;
+        for (id, compaction_window_ms) in mview_policies_to_set {
+            self.initialize_storage_read_policies(vec![id], compaction_window_ms)
+                .await;
+        }
 
         // Announce the completion of initialization.
         self.controller.initialization_complete();
@@ -724,14 +807,14 @@ impl<S: Append + 'static> Coordinator<S> {
             )
         }
 
-        // Advance all tables to the current timestamp
+        // Advance all tables and webhook sources to the current timestamp
         let WriteTimestamp {
             timestamp: _,
             advance_to,
         } = self.get_local_write_ts().await;
         let appends = entries
             .iter()
-            .filter(|entry| entry.is_table())
+            .filter(|entry| entry.is_table() || entry.is_webhook_source())
             .map(|entry| (entry.id(), Vec::new(), advance_to))
             .collect();
         self.controller
@@ -795,6 +878,14 @@ impl<S: Append + 'static> Coordinator<S> {
         // Watcher that listens for and reports compute service status changes.
         let mut compute_events = self.controller.compute.watch_services();
 
+        // Interval at which the depth of the message queues is sampled.
+        let mut queue_depth_interval = tokio::time::interval(queue_depth::SAMPLE_INTERVAL);
+
+        // Interval at which pending subscribes are checked for heartbeats and
+        // idle timeouts.
+        let mut subscribe_heartbeat_interval =
+            tokio::time::interval(subscribe::HEARTBEAT_CHECK_INTERVAL);
+
         self.schedule_storage_usage_collection().await;
 
         loop {
@@ -821,7 +912,10 @@ impl<S: Append + 'static> Coordinator<S> {
                 // https://docs.rs/tokio/1.8.0/tokio/sync/mpsc/struct.UnboundedReceiver.html#cancel-safety
                 m = cmd_rx.recv() => match m {
                     None => break,
-                    Some(m) => Message::Command(m),
+                    Some(m) => {
+                        self.queue_depth.command_received();
+                        Message::Command(m)
+                    }
                 },
                 // `recv()` on `UnboundedReceiver` is cancellation safe:
                 // https://docs.rs/tokio/1.8.0/tokio/sync/mpsc/struct.UnboundedReceiver.html#cancel-safety
@@ -844,6 +938,12 @@ impl<S: Append + 'static> Coordinator<S> {
                     }
                     Message::Consolidate(ids.into_iter().collect())
                 }
+                // `tick()` on `Interval` is cancel-safe:
+                // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                _ = queue_depth_interval.tick() => Message::SampleQueueDepth,
+                // `tick()` on `Interval` is cancel-safe:
+                // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                _ = subscribe_heartbeat_interval.tick() => Message::SubscribeHeartbeat,
             };
 
             // All message processing functions trace. Start a parent span for them to make
@@ -851,7 +951,9 @@ impl<S: Append + 'static> Coordinator<S> {
             let span = span!(Level::DEBUG, "coordinator message processing");
             let _enter = span.enter();
 
+            let start = Instant::now();
             self.handle_message(msg).await;
+            self.queue_depth.message_processed(start.elapsed());
         }
     }
 }
@@ -889,6 +991,7 @@ pub async fn serve<S: Append + 'static>(
     }: Config<S>,
 ) -> Result<(Handle, Client), AdapterError> {
     let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+    let command_queue_depth = Arc::new(AtomicUsize::new(0));
     let (internal_cmd_tx, internal_cmd_rx) = mpsc::unbounded_channel();
     let (strict_serializable_reads_tx, strict_serializable_reads_rx) = mpsc::unbounded_channel();
 
@@ -987,16 +1090,19 @@ pub async fn serve<S: Append + 'static>(
                 pending_peeks: HashMap::new(),
                 client_pending_peeks: HashMap::new(),
                 pending_subscribes: HashMap::new(),
+                pending_purifications: HashMap::new(),
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
                 pending_writes: Vec::new(),
                 secrets_controller,
                 connection_context,
                 transient_replica_metadata: HashMap::new(),
+                transient_replica_liveness: HashMap::new(),
                 storage_usage_client,
                 storage_usage_collection_interval,
                 segment_client,
                 metrics: Metrics::register_with(&metrics_registry),
+                queue_depth: QueueDepthTracker::new(Arc::clone(&command_queue_depth)),
             };
             let bootstrap =
                 handle.block_on(coord.bootstrap(builtin_migration_metadata, builtin_table_updates));
@@ -1019,7 +1125,7 @@ pub async fn serve<S: Append + 'static>(
                 start_instant,
                 _thread: thread.join_on_drop(),
             };
-            let client = Client::new(cmd_tx.clone());
+            let client = Client::new(cmd_tx.clone(), command_queue_depth);
             Ok((handle, client))
         }
         Err(e) => Err(e),
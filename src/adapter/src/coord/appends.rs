@@ -282,8 +282,13 @@ impl<S: Append + 'static> Coordinator<S> {
         for (_, updates) in &mut appends {
             differential_dataflow::consolidation::consolidate(updates);
         }
-        // Add table advancements for all tables.
-        for table in self.catalog.entries().filter(|entry| entry.is_table()) {
+        // Add table advancements for all tables, and for all webhook sources, which requests
+        // write to like tables.
+        for table in self
+            .catalog
+            .entries()
+            .filter(|entry| entry.is_table() || entry.is_webhook_source())
+        {
             appends.entry(table.id()).or_default();
         }
         let appends = appends
@@ -18,8 +18,10 @@ use tokio::sync::{oneshot, watch};
 use tracing::Instrument;
 
 use mz_compute_client::response::PeekResponse;
+use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::ScalarType;
+use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{InsertSource, Query, Raw, SetExpr, Statement};
 use mz_sql::catalog::SessionCatalog as _;
 use mz_sql::plan::{CreateRolePlan, Params};
@@ -29,11 +31,14 @@ use crate::client::ConnectionId;
 use crate::command::{
     Canceled, Command, ExecuteResponse, Response, StartupMessage, StartupResponse,
 };
-use crate::coord::appends::{Deferred, PendingWriteTxn};
+use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, PendingWriteTxn};
 use crate::coord::metrics;
 use crate::coord::peek::PendingPeek;
-use crate::coord::{ConnMeta, Coordinator, CreateSourceStatementReady, Message, PendingTxn};
+use crate::coord::{
+    ConnMeta, Coordinator, CreateSourceStatementReady, Message, PendingPurification, PendingTxn,
+};
 use crate::error::AdapterError;
+use crate::rbac;
 use crate::session::{PreparedStatement, Session, TransactionStatus};
 use crate::util::ClientTransmitter;
 
@@ -94,13 +99,15 @@ impl<S: Append + 'static> Coordinator<S> {
             }
 
             Command::DumpCatalog { session, tx } => {
-                // TODO(benesch): when we have RBAC, dumping the catalog should
-                // require superuser permissions.
-
-                let _ = tx.send(Response {
-                    result: Ok(self.catalog.dump()),
-                    session,
-                });
+                let result = if rbac::is_superuser(&self.catalog, &session) {
+                    Ok(self.catalog.dump())
+                } else {
+                    Err(AdapterError::Unauthorized(format!(
+                        "role {} must be a superuser to dump the catalog",
+                        session.user().name.quoted(),
+                    )))
+                };
+                let _ = tx.send(Response { result, session });
             }
 
             Command::CopyRows {
@@ -114,6 +121,19 @@ impl<S: Append + 'static> Coordinator<S> {
                 let _ = tx.send(Response { result, session });
             }
 
+            Command::AppendWebhook {
+                name,
+                body,
+                signature,
+                mut session,
+                tx,
+            } => {
+                let result = self
+                    .sequence_append_webhook(&mut session, name, body, signature)
+                    .await;
+                let _ = tx.send(Response { result, session });
+            }
+
             Command::Terminate { mut session } => {
                 self.handle_terminate(&mut session).await;
             }
@@ -181,6 +201,7 @@ impl<S: Append + 'static> Coordinator<S> {
             }
             let plan = CreateRolePlan {
                 name: session.user().name.to_string(),
+                superuser: true,
             };
             if let Err(err) = self.sequence_create_role(&session, plan).await {
                 let _ = tx.send(Response {
@@ -354,6 +375,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::AlterSink(_)
                     | Statement::AlterSource(_)
                     | Statement::AlterObjectRename(_)
+                    | Statement::AlterOwner(_)
                     | Statement::AlterSystemSet(_)
                     | Statement::AlterSystemReset(_)
                     | Statement::AlterSystemResetAll(_)
@@ -379,7 +401,9 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::DropRoles(_)
                     | Statement::DropClusters(_)
                     | Statement::DropClusterReplicas(_)
+                    | Statement::GrantPrivileges(_)
                     | Statement::Insert(_)
+                    | Statement::RevokePrivileges(_)
                     | Statement::Update(_) => {
                         return tx.send(
                             Err(AdapterError::OperationProhibitsTransaction(
@@ -411,15 +435,39 @@ impl<S: Append + 'static> Coordinator<S> {
             Statement::CreateSource(stmt) => {
                 let internal_cmd_tx = self.internal_cmd_tx.clone();
                 let conn_id = session.conn_id();
+                let (cancel_tx, cancel_rx) = oneshot::channel();
+                let purification = PendingPurification {
+                    name: stmt.name.to_ast_string(),
+                    started_at: self.now_datetime(),
+                    cancel_tx,
+                };
                 let purify_fut = mz_sql::pure::purify_create_source(
                     Box::new(catalog.into_owned()),
                     self.now(),
                     stmt,
                     self.connection_context.clone(),
                 );
+                let update =
+                    self.catalog
+                        .state()
+                        .pack_pending_source_update(conn_id, &purification, 1);
+                self.send_builtin_table_updates(vec![update], BuiltinTableUpdateSource::Background)
+                    .await;
+                self.pending_purifications.insert(conn_id, purification);
                 let otel_ctx = OpenTelemetryContext::obtain();
                 task::spawn(|| format!("purify:{conn_id}"), async move {
-                    let result = purify_fut.await.map_err(|e| e.into());
+                    // Purification may wait on unresponsive external systems,
+                    // so it must be abandoned if the client cancels the
+                    // statement. The coordinator cleans up after itself when
+                    // it cancels, so there is nothing to report back to it.
+                    let result = tokio::select! {
+                        biased;
+                        _ = cancel_rx => {
+                            tx.send(Ok(ExecuteResponse::Canceled), session);
+                            return;
+                        }
+                        result = purify_fut => result.map_err(|e| e.into()),
+                    };
                     // It is not an error for purification to complete after `internal_cmd_rx` is dropped.
                     let result = internal_cmd_tx.send(Message::CreateSourceStatementReady(
                         CreateSourceStatementReady {
@@ -515,6 +563,18 @@ impl<S: Append + 'static> Coordinator<S> {
             // Inform the target session (if it asks) about the cancellation.
             let _ = conn_meta.cancel_tx.send(Canceled::Canceled);
 
+            // Cancel purification of a `CREATE SOURCE` statement. There is at
+            // most one per session.
+            if let Some(purification) = self.pending_purifications.remove(&conn_id) {
+                let update =
+                    self.catalog
+                        .state()
+                        .pack_pending_source_update(conn_id, &purification, -1);
+                self.send_builtin_table_updates(vec![update], BuiltinTableUpdateSource::Background)
+                    .await;
+                let _ = purification.cancel_tx.send(());
+            }
+
             for PendingPeek {
                 sender: rows_tx,
                 conn_id: _,
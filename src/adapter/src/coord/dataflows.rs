@@ -398,6 +398,7 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
             connection: ComputeSinkConnection::Persist(PersistSinkConnection {
                 value_desc: mview.desc.clone(),
                 storage_metadata: (),
+                batching: mview.batching,
             }),
             as_of: SinkAsOf {
                 frontier: as_of,
@@ -415,6 +416,8 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         // we can retrieve monotonicity information from the parent source.
         match &source.data_source {
             DataSourceDesc::Ingestion(ingestion) => ingestion.desc.monotonic(),
+            // Requests only ever append to webhook sources.
+            DataSourceDesc::Webhook { .. } => true,
             DataSourceDesc::Introspection(_) | DataSourceDesc::Source => false,
         }
     }
@@ -11,6 +11,7 @@
 //! and altering objects.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use itertools::Itertools;
@@ -27,7 +28,10 @@ use mz_repr::{GlobalId, Timestamp};
 use mz_sql::names::ResolvedDatabaseSpecifier;
 use mz_stash::Append;
 use mz_storage::controller::{CreateExportToken, ExportDescription};
-use mz_storage::types::sinks::{SinkAsOf, StorageSinkConnection};
+use mz_storage::types::connections::CsrConnection;
+use mz_storage::types::sinks::{
+    KafkaSinkConnection, PublishedSchemaInfo, SinkAsOf, StorageSinkConnection,
+};
 use mz_storage::types::sources::{PostgresSourceConnection, SourceConnection, Timeline};
 
 use crate::catalog::{
@@ -78,6 +82,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let mut indexes_to_drop = vec![];
         let mut materialized_views_to_drop = vec![];
         let mut replication_slots_to_drop: Vec<(mz_postgres_util::Config, String)> = vec![];
+        let mut schema_subjects_to_drop: Vec<(CsrConnection, Vec<String>)> = vec![];
         let mut secrets_to_drop = vec![];
         let mut timelines_to_drop = vec![];
 
@@ -109,8 +114,24 @@ impl<S: Append + 'static> Coordinator<S> {
                         }
                     }
                     CatalogItem::Sink(catalog::Sink { connection, .. }) => match connection {
-                        StorageSinkConnectionState::Ready(_) => {
+                        StorageSinkConnectionState::Ready(connection) => {
                             storage_sinks_to_drop.push(*id);
+                            if let StorageSinkConnection::Kafka(KafkaSinkConnection {
+                                published_schema_info:
+                                    Some(
+                                        info @ PublishedSchemaInfo {
+                                            delete_subjects_on_drop: Some(csr_connection),
+                                            ..
+                                        },
+                                    ),
+                                ..
+                            }) = connection
+                            {
+                                schema_subjects_to_drop.push((
+                                    csr_connection.clone(),
+                                    info.subjects().map(String::from).collect(),
+                                ));
+                            }
                         }
                         StorageSinkConnectionState::Pending(_) => (),
                     },
@@ -263,6 +284,41 @@ impl<S: Append + 'static> Coordinator<S> {
                     }
                 });
             }
+
+            // Likewise, we don't want to block the coordinator on an external
+            // schema registry, so delete the subjects of dropped sinks in a
+            // separate task. The subjects are only soft-deleted, so the schemas
+            // remain available to consumers that reference them by ID.
+            if !schema_subjects_to_drop.is_empty() {
+                let secrets_reader = Arc::clone(&self.connection_context.secrets_reader);
+                task::spawn(|| "drop_schema_subjects", async move {
+                    for (csr_connection, subjects) in schema_subjects_to_drop {
+                        let ccsr = match csr_connection.connect(&*secrets_reader).await {
+                            Ok(ccsr) => ccsr,
+                            Err(e) => {
+                                warn!("unable to connect to schema registry to delete subjects {subjects:?}: {e:#}");
+                                continue;
+                            }
+                        };
+                        for subject in subjects {
+                            // Try to delete the subject, but give up after a while.
+                            // A subject that is already gone needs no deleting.
+                            let result = Retry::default()
+                                .max_duration(Duration::from_secs(30))
+                                .retry_async(|_state| async {
+                                    match ccsr.delete_subject(&subject).await {
+                                        Err(mz_ccsr::DeleteError::SubjectNotFound) => Ok(()),
+                                        result => result,
+                                    }
+                                })
+                                .await;
+                            if let Err(e) = result {
+                                warn!("unable to delete schema registry subject {subject}: {e}");
+                            }
+                        }
+                    }
+                });
+            }
         }
         .await;
 
@@ -410,28 +466,35 @@ impl<S: Append + 'static> Coordinator<S> {
             .expect("unable to drop temporary items for conn_id");
     }
 
+    /// Determines the as-of of a new storage sink.
+    ///
+    /// The as-of is used to determine at what time to snapshot reading from
+    /// the persist collection. This is primarily relevant when we do _not_
+    /// want to include the snapshot in the sink. Choosing now will mean that
+    /// only things going forward are exported, so this should be called when
+    /// the sink is created rather than once its connection is ready, which
+    /// can take arbitrarily long.
+    pub(crate) async fn storage_sink_as_of(&mut self, sink: &Sink) -> SinkAsOf<Timestamp> {
+        let timeline = self
+            .get_timeline(sink.from)
+            .unwrap_or(Timeline::EpochMilliseconds);
+        let now = self.ensure_timeline_state(timeline).await.oracle.read_ts();
+        SinkAsOf {
+            frontier: Antichain::from_elem(now),
+            strict: !sink.with_snapshot,
+        }
+    }
+
     async fn create_storage_export(
         &mut self,
         create_export_token: CreateExportToken,
         sink: &Sink,
         connection: StorageSinkConnection,
+        as_of: SinkAsOf<Timestamp>,
     ) -> Result<(), AdapterError> {
         // Validate `sink.from` is in fact a storage collection
         self.controller.storage.collection(sink.from)?;
 
-        // The AsOf is used to determine at what time to snapshot reading from the persist collection.  This is
-        // primarily relevant when we do _not_ want to include the snapshot in the sink.  Choosing now will mean
-        // that only things going forward are exported.
-        let timeline = self
-            .get_timeline(sink.from)
-            .unwrap_or(Timeline::EpochMilliseconds);
-        let now = self.ensure_timeline_state(timeline).await.oracle.read_ts();
-        let frontier = Antichain::from_elem(now);
-        let as_of = SinkAsOf {
-            frontier,
-            strict: !sink.with_snapshot,
-        };
-
         let storage_sink_from_entry = self.catalog.get_entry(&sink.from);
         let storage_sink_desc = mz_storage::types::sinks::StorageSinkDesc {
             from: sink.from,
@@ -446,6 +509,14 @@ impl<S: Append + 'static> Coordinator<S> {
             envelope: Some(sink.envelope),
             as_of,
             from_storage_metadata: (),
+            catch_up_limit: sink.catch_up_limit,
+            rate_limit: sink.rate_limit,
+            projection: sink.projection.clone(),
+            column_masks: sink.column_masks.clone(),
+            include_timestamp: sink.include_timestamp,
+            include_diff: sink.include_diff,
+            filter: sink.filter.clone(),
+            progress_shard: None,
         };
 
         Ok(self
@@ -467,6 +538,7 @@ impl<S: Append + 'static> Coordinator<S> {
         oid: u32,
         connection: StorageSinkConnection,
         create_export_token: CreateExportToken,
+        as_of: SinkAsOf<Timestamp>,
         session: Option<&Session>,
     ) -> Result<(), AdapterError> {
         // Update catalog entry with sink connection.
@@ -491,7 +563,7 @@ impl<S: Append + 'static> Coordinator<S> {
         // - Dropping the sink will either succeed (or panic) so it's easier to reason about rolling that change back
         //   than it is rolling back a catalog change.
         match self
-            .create_storage_export(create_export_token, &sink, connection)
+            .create_storage_export(create_export_token, &sink, connection, as_of)
             .await
         {
             Ok(()) => {
@@ -574,7 +646,9 @@ impl<S: Append + 'static> Coordinator<S> {
                             match source.data_source {
                                 // Only sources that ingest data from an external system count
                                 // towards resource limits.
-                                DataSourceDesc::Ingestion(_) => new_sources += 1,
+                                DataSourceDesc::Ingestion(_) | DataSourceDesc::Webhook { .. } => {
+                                    new_sources += 1
+                                }
                                 DataSourceDesc::Source | DataSourceDesc::Introspection(_) => {}
                             }
                         }
@@ -624,7 +698,9 @@ impl<S: Append + 'static> Coordinator<S> {
                             match source.data_source {
                                 // Only sources that ingest data from an external system count
                                 // towards resource limits.
-                                DataSourceDesc::Ingestion(_) => new_sources -= 1,
+                                DataSourceDesc::Ingestion(_) | DataSourceDesc::Webhook { .. } => {
+                                    new_sources -= 1
+                                }
                                 DataSourceDesc::Source | DataSourceDesc::Introspection(_) => {}
                             }
                         }
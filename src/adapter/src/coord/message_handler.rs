@@ -11,7 +11,7 @@
 //! messages from various sources (ex: controller, clients, background tasks, etc).
 
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::DurationRound;
 use tracing::{event, warn, Level};
@@ -22,7 +22,7 @@ use mz_ore::now::EpochMillis;
 use mz_ore::task;
 use mz_persist_client::ShardId;
 use mz_sql::ast::Statement;
-use mz_sql::plan::{Plan, SendDiffsPlan};
+use mz_sql::plan::{Params, Plan, SendDiffsPlan};
 use mz_stash::Append;
 
 use crate::command::{Command, ExecuteResponse};
@@ -30,8 +30,8 @@ use crate::coord::appends::{BuiltinTableUpdateSource, Deferred};
 use crate::{catalog, AdapterNotice};
 
 use crate::coord::{
-    Coordinator, CreateSourceStatementReady, Message, PendingTxn, ReplicaMetadata, SendDiffs,
-    SinkConnectionReady,
+    Coordinator, CreateSourceStatementReady, Message, PendingTxn, ReplicaLiveness, ReplicaMetadata,
+    SendDiffs, SinkConnectionReady, SourceSchemaRefreshed,
 };
 
 impl<S: Append + 'static> Coordinator<S> {
@@ -46,6 +46,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Message::CreateSourceStatementReady(ready) => {
                 self.message_create_source_statement_ready(ready).await
             }
+            Message::SourceSchemaRefreshed(refreshed) => {
+                self.message_source_schema_refreshed(refreshed).await
+            }
             Message::SinkConnectionReady(ready) => self.message_sink_connection_ready(ready).await,
             Message::WriteLockGrant(write_lock_guard) => {
                 self.message_write_lock_grant(write_lock_guard).await;
@@ -82,9 +85,38 @@ impl<S: Append + 'static> Coordinator<S> {
             Message::Consolidate(collections) => {
                 self.consolidate(&collections).await;
             }
+            Message::SampleQueueDepth => {
+                self.sample_queue_depth().await;
+            }
+            Message::SubscribeHeartbeat => {
+                self.subscribe_heartbeat().await;
+            }
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn subscribe_heartbeat(&mut self) {
+        let now = Instant::now();
+        let mut to_drop = Vec::new();
+        for (id, pending_subscribe) in self.pending_subscribes.iter_mut() {
+            if pending_subscribe.needs_heartbeat() && pending_subscribe.heartbeat(now) {
+                to_drop.push(*id);
+            }
+        }
+        if to_drop.is_empty() {
+            return;
+        }
+
+        let mut sinks = Vec::with_capacity(to_drop.len());
+        for id in to_drop {
+            if let Some(pending_subscribe) = self.pending_subscribes.remove(&id) {
+                self.metrics.active_subscribes.dec();
+                sinks.push(pending_subscribe.sink_id);
+            }
+        }
+        self.drop_compute_sinks(sinks).await;
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn consolidate(&mut self, collections: &[mz_stash::Id]) {
         if let Err(err) = self.catalog.consolidate(collections).await {
@@ -207,6 +239,59 @@ impl<S: Append + 'static> Coordinator<S> {
                         .await;
                 }
             }
+            ControllerResponse::ComputePendingAck(ack, diff) => {
+                let update = self
+                    .catalog
+                    .state()
+                    .pack_replica_pending_ddl_update(ack, diff);
+                self.send_builtin_table_updates(vec![update], BuiltinTableUpdateSource::Background)
+                    .await;
+            }
+            ControllerResponse::ComputeReplicaLiveness(replica_id, when, heartbeat) => {
+                let replica_status_interval = chrono::Duration::seconds(60);
+                let when_coarsened = when
+                    .duration_trunc(replica_status_interval)
+                    .expect("Time coarsening should not fail");
+                let new = ReplicaLiveness {
+                    last_heartbeat: when_coarsened,
+                    build_version: heartbeat.build_version,
+                    uptime: heartbeat.uptime,
+                };
+                let old = match self.transient_replica_liveness.get(&replica_id) {
+                    None => None,
+                    // `None` is the tombstone for a removed replica
+                    Some(None) => return,
+                    Some(Some(old)) => Some(old.clone()),
+                };
+
+                // The uptime changes with every heartbeat, so only record a new
+                // liveness row once the coarsened heartbeat time or the build
+                // changes.
+                let changed = old.as_ref().map_or(true, |old| {
+                    old.last_heartbeat != new.last_heartbeat
+                        || old.build_version != new.build_version
+                });
+                if changed {
+                    self.transient_replica_liveness
+                        .insert(replica_id, Some(new.clone()));
+                    let retraction = old.map(|old| {
+                        self.catalog
+                            .state()
+                            .pack_replica_liveness_update(replica_id, old, -1)
+                    });
+                    let insertion = self
+                        .catalog
+                        .state()
+                        .pack_replica_liveness_update(replica_id, new, 1);
+                    let updates = if let Some(retraction) = retraction {
+                        vec![retraction, insertion]
+                    } else {
+                        vec![insertion]
+                    };
+                    self.send_builtin_table_updates(updates, BuiltinTableUpdateSource::Background)
+                        .await;
+                }
+            }
         }
     }
 
@@ -225,6 +310,16 @@ impl<S: Append + 'static> Coordinator<S> {
     ) {
         otel_ctx.attach_as_parent();
 
+        let conn_id = session.conn_id();
+        if let Some(purification) = self.pending_purifications.remove(&conn_id) {
+            let update =
+                self.catalog
+                    .state()
+                    .pack_pending_source_update(conn_id, &purification, -1);
+            self.send_builtin_table_updates(vec![update], BuiltinTableUpdateSource::Background)
+                .await;
+        }
+
         // Ensure that all dependencies still exist after purification, as a
         // `DROP CONNECTION` may have sneaked in. If any have gone missing, we
         // repurify the original statement. This will either produce a nice
@@ -297,6 +392,43 @@ impl<S: Append + 'static> Coordinator<S> {
         tx.send(result, session);
     }
 
+    #[tracing::instrument(level = "debug", skip(self, tx, session))]
+    async fn message_source_schema_refreshed(
+        &mut self,
+        SourceSchemaRefreshed {
+            mut session,
+            tx,
+            id,
+            create_sql,
+            result,
+            otel_ctx,
+        }: SourceSchemaRefreshed,
+    ) {
+        otel_ctx.attach_as_parent();
+
+        // Sources that use a schema registry never have subsources.
+        let (_, stmt) = match result {
+            Ok(ok) => ok,
+            Err(e) => return tx.send(Err(e), session),
+        };
+        let plan = match self.plan_statement(
+            &mut session,
+            Statement::CreateSource(stmt),
+            &Params::empty(),
+        ) {
+            Ok(Plan::CreateSource(plan)) => plan,
+            Ok(_) => {
+                unreachable!("planning CREATE SOURCE must result in a Plan::CreateSource")
+            }
+            Err(e) => return tx.send(Err(e), session),
+        };
+
+        let result = self
+            .sequence_alter_source_schema(&session, id, &create_sql, plan)
+            .await;
+        tx.send(result, session);
+    }
+
     #[tracing::instrument(level = "debug", skip(self, session_and_tx))]
     async fn message_sink_connection_ready(
         &mut self,
@@ -305,6 +437,7 @@ impl<S: Append + 'static> Coordinator<S> {
             id,
             oid,
             create_export_token,
+            as_of,
             result,
         }: SinkConnectionReady,
     ) {
@@ -324,6 +457,7 @@ impl<S: Append + 'static> Coordinator<S> {
                         oid,
                         connection,
                         create_export_token,
+                        as_of,
                         session_and_tx.as_ref().map(|(ref session, _tx)| session),
                     )
                     .await
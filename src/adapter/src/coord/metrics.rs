@@ -66,6 +66,7 @@ where
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
         StatementKind::AlterObjectRename => "alter_object_rename",
+        StatementKind::AlterOwner => "alter_owner",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSink => "alter_sink",
@@ -81,6 +82,8 @@ where
         StatementKind::DropRoles => "drop_roles",
         StatementKind::DropClusters => "drop_clusters",
         StatementKind::DropClusterReplicas => "drop_cluster_replicas",
+        StatementKind::GrantPrivileges => "grant_privileges",
+        StatementKind::RevokePrivileges => "revoke_privileges",
         StatementKind::SetVariable => "set_variable",
         StatementKind::ResetVariable => "reset_variable",
         StatementKind::Show => "show",
@@ -0,0 +1,175 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sampling of the depth of the [`Coordinator`]'s command queue and of the
+//! time it spends processing messages.
+//!
+//! The most recent samples are recorded in the
+//! `mz_internal.mz_coordinator_queue_depth_history` table, so that a
+//! backed-up coordinator loop can be told apart from slowness elsewhere.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use mz_ore::cast::CastFrom;
+use mz_ore::now::EpochMillis;
+use mz_stash::Append;
+
+use crate::coord::appends::BuiltinTableUpdateSource;
+use crate::coord::Coordinator;
+
+/// The interval at which the queue depth is sampled.
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of samples to retain, which covers the last ten minutes.
+const MAX_SAMPLES: usize = 600;
+
+/// A sample of the state of the coordinator's message queues.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueueDepthSample {
+    /// The time at which the sample was taken.
+    pub sampled_at: EpochMillis,
+    /// The number of client commands waiting to be processed.
+    pub command_queue_depth: u64,
+    /// The number of messages processed since the previous sample.
+    pub messages_processed: u64,
+    /// The longest time spent processing a single message since the previous
+    /// sample.
+    pub max_processing_duration: Duration,
+    /// The total time spent processing messages since the previous sample.
+    pub total_processing_duration: Duration,
+}
+
+/// Tracks the coordinator's message queues between samples.
+#[derive(Debug)]
+pub(crate) struct QueueDepthTracker {
+    /// The number of commands sent by clients that the coordinator has not
+    /// yet received. Shared with every [`crate::Client`].
+    command_queue_depth: Arc<AtomicUsize>,
+    messages_processed: u64,
+    max_processing_duration: Duration,
+    total_processing_duration: Duration,
+    /// The retained samples, oldest first.
+    samples: VecDeque<QueueDepthSample>,
+}
+
+impl QueueDepthTracker {
+    pub(crate) fn new(command_queue_depth: Arc<AtomicUsize>) -> QueueDepthTracker {
+        QueueDepthTracker {
+            command_queue_depth,
+            messages_processed: 0,
+            max_processing_duration: Duration::ZERO,
+            total_processing_duration: Duration::ZERO,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records that a command was taken off the command queue.
+    pub(crate) fn command_received(&self) {
+        self.command_queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Records that a message took `duration` to process.
+    pub(crate) fn message_processed(&mut self, duration: Duration) {
+        self.messages_processed += 1;
+        self.max_processing_duration = self.max_processing_duration.max(duration);
+        self.total_processing_duration += duration;
+    }
+
+    /// Takes a sample at time `now` and resets the per-sample statistics.
+    ///
+    /// Returns the new sample and the sample it evicted from the trace, if
+    /// any.
+    fn sample(&mut self, now: EpochMillis) -> (QueueDepthSample, Option<QueueDepthSample>) {
+        let sample = QueueDepthSample {
+            sampled_at: now,
+            command_queue_depth: u64::cast_from(self.command_queue_depth.load(Ordering::Relaxed)),
+            messages_processed: self.messages_processed,
+            max_processing_duration: self.max_processing_duration,
+            total_processing_duration: self.total_processing_duration,
+        };
+        self.messages_processed = 0;
+        self.max_processing_duration = Duration::ZERO;
+        self.total_processing_duration = Duration::ZERO;
+
+        self.samples.push_back(sample.clone());
+        let evicted = if self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front()
+        } else {
+            None
+        };
+        (sample, evicted)
+    }
+}
+
+impl<S: Append + 'static> Coordinator<S> {
+    /// Samples the coordinator's message queues into
+    /// `mz_internal.mz_coordinator_queue_depth_history`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn sample_queue_depth(&mut self) {
+        let now = self.now();
+        let (sample, evicted) = self.queue_depth.sample(now);
+        let mut updates = vec![self.catalog.state().pack_queue_depth_update(&sample, 1)];
+        if let Some(evicted) = evicted {
+            updates.push(self.catalog.state().pack_queue_depth_update(&evicted, -1));
+        }
+        self.send_builtin_table_updates(updates, BuiltinTableUpdateSource::Background)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        let depth = Arc::new(AtomicUsize::new(3));
+        let mut tracker = QueueDepthTracker::new(Arc::clone(&depth));
+        tracker.command_received();
+        tracker.message_processed(Duration::from_millis(5));
+        tracker.message_processed(Duration::from_millis(20));
+
+        let (sample, evicted) = tracker.sample(1000);
+        assert_eq!(
+            sample,
+            QueueDepthSample {
+                sampled_at: 1000,
+                command_queue_depth: 2,
+                messages_processed: 2,
+                max_processing_duration: Duration::from_millis(20),
+                total_processing_duration: Duration::from_millis(25),
+            }
+        );
+        assert_eq!(evicted, None);
+
+        // The per-sample statistics reset, but the queue depth does not.
+        let (sample, _) = tracker.sample(2000);
+        assert_eq!(
+            sample,
+            QueueDepthSample {
+                sampled_at: 2000,
+                command_queue_depth: 2,
+                messages_processed: 0,
+                max_processing_duration: Duration::ZERO,
+                total_processing_duration: Duration::ZERO,
+            }
+        );
+
+        // Only the most recent samples are retained.
+        for now in 3..=u64::cast_from(MAX_SAMPLES) {
+            assert_eq!(tracker.sample(now * 1000).1, None);
+        }
+        let (_, evicted) = tracker.sample(u64::cast_from(MAX_SAMPLES + 1) * 1000);
+        assert_eq!(evicted.map(|sample| sample.sampled_at), Some(1000));
+        assert_eq!(tracker.samples.len(), MAX_SAMPLES);
+    }
+}
@@ -14,7 +14,9 @@ use std::num::{NonZeroI64, NonZeroUsize};
 use std::time::Duration;
 
 use anyhow::anyhow;
+use hmac::{Hmac, Mac};
 use maplit::btreeset;
+use sha2::Sha256;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
 use tokio::sync::{mpsc, OwnedMutexGuard};
 use tracing::{event, warn, Level};
@@ -32,28 +34,34 @@ use mz_expr::{
     OptimizedMirRelationExpr, RowSetFinishing,
 };
 use mz_ore::ssh_key::SshKeyset;
+use mz_ore::str::StrExt;
 use mz_ore::task;
+use mz_ore::tracing::OpenTelemetryContext;
+use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::explain_new::Explainee;
 use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, RowArena, ScalarType, Timestamp};
-use mz_sql::ast::{ExplainStage, IndexOptionName, ObjectType};
-use mz_sql::catalog::{CatalogComputeInstance, CatalogError, CatalogItemType, CatalogTypeDetails};
-use mz_sql::names::QualifiedObjectName;
+use mz_sql::ast::{ExplainStage, IndexOptionName, ObjectType, Statement};
+use mz_sql::catalog::{
+    CatalogComputeInstance, CatalogError, CatalogItemType, CatalogTypeDetails, Privilege,
+};
+use mz_sql::names::{PartialObjectName, QualifiedObjectName, RoleId};
 use mz_sql::plan::{
-    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterSecretPlan,
-    AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan, AlterSystemResetPlan,
-    AlterSystemSetPlan, CreateComputeInstancePlan, CreateComputeReplicaPlan, CreateConnectionPlan,
-    CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan,
-    CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
-    CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan, DropComputeReplicasPlan,
-    DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
-    FetchPlan, HirRelationExpr, IndexOption, InsertPlan, MaterializedView, MutationKind,
-    OptimizerConfig, PeekPlan, Plan, PlanKind, QueryWhen, RaisePlan, ReadThenWritePlan,
-    ResetVariablePlan, RotateKeysPlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
+    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterOwnerPlan,
+    AlterSecretPlan, AlterSinkPlan, AlterSourcePlan, AlterSourceRefreshSchemaPlan,
+    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, CreateComputeInstancePlan,
+    CreateComputeReplicaPlan, CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan,
+    DropComputeReplicasPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
+    ExecutePlan, ExplainPlan, FetchPlan, GrantPrivilegesPlan, HirRelationExpr, IndexOption,
+    InsertPlan, MaterializedView, MutationKind, OptimizerConfig, PeekPlan, Plan, PlanKind,
+    PrivilegeTarget, QueryWhen, RaisePlan, ReadThenWritePlan, ResetVariablePlan,
+    RevokePrivilegesPlan, RotateKeysPlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
     SubscribeFrom, SubscribePlan, View,
 };
 use mz_stash::Append;
 use mz_storage::controller::{CollectionDescription, DataSource, ReadPolicy, StorageError};
-use mz_storage::types::sinks::StorageSinkConnectionBuilder;
+use mz_storage::types::sinks::StorageSinkConnection;
 use mz_storage::types::sources::{IngestionDescription, SourceExport};
 
 use crate::catalog::{
@@ -64,8 +72,8 @@ use crate::command::{Command, ExecuteResponse};
 use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, DeferredPlan, PendingWriteTxn};
 use crate::coord::dataflows::{prep_relation_expr, prep_scalar_expr, ExprPrepStyle};
 use crate::coord::{
-    peek, read_policy, Coordinator, Message, PendingTxn, SendDiffs, SinkConnectionReady, TxnReads,
-    DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
+    peek, read_policy, Coordinator, Message, PendingTxn, SendDiffs, SinkConnectionReady,
+    SourceSchemaRefreshed, TxnReads, DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
 };
 use crate::error::AdapterError;
 use crate::explain_new::optimizer_trace::OptimizerTrace;
@@ -77,7 +85,7 @@ use crate::session::{
 };
 use crate::subscribe::PendingSubscribe;
 use crate::util::{send_immediate_rows, ClientTransmitter, ComputeSinkId};
-use crate::{guard_write_critical_section, session, PeekResponseUnary};
+use crate::{guard_write_critical_section, rbac, session, PeekResponseUnary};
 
 impl<S: Append + 'static> Coordinator<S> {
     #[tracing::instrument(level = "debug", skip_all)]
@@ -92,6 +100,10 @@ impl<S: Append + 'static> Coordinator<S> {
         let responses = ExecuteResponse::generated_from(PlanKind::from(&plan));
         tx.set_allowed(responses);
 
+        if let Err(e) = rbac::check_plan(&self.catalog, &session, &plan, &depends_on) {
+            return tx.send(Err(e), session);
+        }
+
         match plan {
             Plan::CreateSource(plan) => {
                 let source_id = match self.catalog.allocate_user_id().await {
@@ -191,6 +203,18 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::DropRoles(plan) => {
                 tx.send(self.sequence_drop_roles(&session, plan).await, session);
             }
+            Plan::GrantPrivileges(plan) => {
+                tx.send(
+                    self.sequence_grant_privileges(&session, plan).await,
+                    session,
+                );
+            }
+            Plan::RevokePrivileges(plan) => {
+                tx.send(
+                    self.sequence_revoke_privileges(&session, plan).await,
+                    session,
+                );
+            }
             Plan::DropComputeInstances(plan) => {
                 tx.send(
                     self.sequence_drop_compute_instances(&session, plan).await,
@@ -295,6 +319,9 @@ impl<S: Append + 'static> Coordinator<S> {
                     session,
                 );
             }
+            Plan::AlterOwner(plan) => {
+                tx.send(self.sequence_alter_owner(&session, plan).await, session);
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 tx.send(self.sequence_alter_index_set_options(plan).await, session);
             }
@@ -310,6 +337,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::AlterSource(plan) => {
                 tx.send(self.sequence_alter_source(&session, plan).await, session);
             }
+            Plan::AlterSourceRefreshSchema(plan) => {
+                self.sequence_alter_source_refresh_schema(tx, session, plan);
+            }
             Plan::AlterSystemSet(plan) => {
                 tx.send(
                     self.sequence_alter_system_set(&session, plan).await,
@@ -455,8 +485,8 @@ impl<S: Append + 'static> Coordinator<S> {
             let source_oid = self.catalog.allocate_oid()?;
             let source = catalog::Source {
                 create_sql: plan.source.create_sql,
-                data_source: match plan.source.ingestion {
-                    Some(ingestion) => {
+                data_source: match plan.source.data_source {
+                    mz_sql::plan::DataSourceDesc::Ingestion(ingestion) => {
                         let host_config = self.catalog.resolve_storage_host_config(
                             plan.host_config,
                             // Undefined sizes permitted in unsafe mode
@@ -469,13 +499,20 @@ impl<S: Append + 'static> Coordinator<S> {
                             host_config,
                         })
                     }
-                    None => {
+                    mz_sql::plan::DataSourceDesc::Source => {
                         assert!(
                             matches!(plan.host_config, mz_sql::plan::StorageHostConfig::Undefined),
                             "subsources must not have a host config defined"
                         );
                         DataSourceDesc::Source
                     }
+                    mz_sql::plan::DataSourceDesc::Webhook { secret, check } => {
+                        assert!(
+                            matches!(plan.host_config, mz_sql::plan::StorageHostConfig::Undefined),
+                            "webhook sources must not have a host config defined"
+                        );
+                        DataSourceDesc::Webhook { secret, check }
+                    }
                 },
                 desc: plan.source.desc,
                 timeline: plan.timeline,
@@ -536,7 +573,9 @@ impl<S: Append + 'static> Coordinator<S> {
                                 host_config: ingestion.host_config,
                             })
                         }
-                        DataSourceDesc::Source => DataSource::Other,
+                        DataSourceDesc::Source | DataSourceDesc::Webhook { .. } => {
+                            DataSource::Other
+                        }
                         DataSourceDesc::Introspection(_) => {
                             unreachable!("cannot create sources with introspection data sources")
                         }
@@ -705,6 +744,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let op = catalog::Op::CreateRole {
             name: plan.name,
             oid,
+            superuser: plan.superuser,
         };
         self.catalog_transact(Some(session), vec![op], |_| Ok(()))
             .await
@@ -1234,10 +1274,6 @@ impl<S: Append + 'static> Coordinator<S> {
             }
         };
 
-        // Knowing that we're only handling kafka sinks here helps us simplify.
-        let StorageSinkConnectionBuilder::Kafka(connection_builder) =
-            sink.connection_builder.clone();
-
         // Then try to create a placeholder catalog item with an unknown
         // connection. If that fails, we're done, though if the client specified
         // `if_not_exists` we'll tell the client we succeeded.
@@ -1248,13 +1284,18 @@ impl<S: Append + 'static> Coordinator<S> {
         let catalog_sink = catalog::Sink {
             create_sql: sink.create_sql,
             from: sink.from,
-            connection: StorageSinkConnectionState::Pending(StorageSinkConnectionBuilder::Kafka(
-                connection_builder,
-            )),
+            connection: StorageSinkConnectionState::Pending(sink.connection_builder.clone()),
             envelope: sink.envelope,
             with_snapshot,
             depends_on,
             host_config,
+            catch_up_limit: sink.catch_up_limit,
+            rate_limit: sink.rate_limit,
+            projection: sink.projection,
+            column_masks: sink.column_masks,
+            include_timestamp: sink.include_timestamp,
+            include_diff: sink.include_diff,
+            filter: sink.filter,
         };
 
         let ops = vec![catalog::Op::CreateItem {
@@ -1314,6 +1355,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 return;
             }
         };
+        let as_of = self.storage_sink_as_of(&catalog_sink).await;
 
         // Now we're ready to create the sink connection. Arrange to notify the
         // main coordinator thread when the future completes.
@@ -1330,6 +1372,7 @@ impl<S: Append + 'static> Coordinator<S> {
                         id,
                         oid,
                         create_export_token,
+                        as_of,
                         result: mz_storage::sink::build_sink_connection(
                             connection_builder,
                             connection_context,
@@ -1430,10 +1473,14 @@ impl<S: Append + 'static> Coordinator<S> {
                     expr: view_expr,
                     column_names,
                     compute_instance,
+                    batching,
+                    compaction_window,
                 },
             replace,
             if_not_exists,
         } = plan;
+        let compaction_window_ms =
+            compaction_window.unwrap_or(DEFAULT_LOGICAL_COMPACTION_WINDOW_MS);
 
         self.validate_timeline(depends_on.clone())?;
 
@@ -1485,6 +1532,8 @@ impl<S: Append + 'static> Coordinator<S> {
                 desc: desc.clone(),
                 depends_on,
                 compute_instance,
+                batching,
+                compaction_window_ms,
             }),
         });
 
@@ -1515,11 +1564,8 @@ impl<S: Append + 'static> Coordinator<S> {
                     .await
                     .unwrap();
 
-                self.initialize_storage_read_policies(
-                    vec![id],
-                    DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
-                )
-                .await;
+                self.initialize_storage_read_policies(vec![id], compaction_window_ms)
+                    .await;
 
                 self.ship_dataflow(df, compute_instance).await;
 
@@ -1668,6 +1714,85 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::DroppedRole)
     }
 
+    async fn sequence_grant_privileges(
+        &mut self,
+        session: &Session,
+        GrantPrivilegesPlan {
+            privileges,
+            target,
+            role_ids,
+        }: GrantPrivilegesPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let ops = self.privilege_ops(target, &role_ids, |granted| {
+            granted.extend(privileges.iter().copied())
+        });
+        self.catalog_transact(Some(session), ops, |_| Ok(()))
+            .await?;
+        Ok(ExecuteResponse::GrantedPrivilege)
+    }
+
+    async fn sequence_revoke_privileges(
+        &mut self,
+        session: &Session,
+        RevokePrivilegesPlan {
+            privileges,
+            target,
+            role_ids,
+        }: RevokePrivilegesPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let ops = self.privilege_ops(target, &role_ids, |granted| {
+            granted.retain(|privilege| !privileges.contains(privilege))
+        });
+        self.catalog_transact(Some(session), ops, |_| Ok(()))
+            .await?;
+        Ok(ExecuteResponse::RevokedPrivilege)
+    }
+
+    /// Computes the catalog operations that apply `update` to the privileges
+    /// that each role in `role_ids` holds on `target`.
+    fn privilege_ops<F>(
+        &self,
+        target: PrivilegeTarget,
+        role_ids: &[RoleId],
+        update: F,
+    ) -> Vec<catalog::Op>
+    where
+        F: Fn(&mut BTreeSet<Privilege>),
+    {
+        fn apply<K: Ord>(
+            all: &mut BTreeMap<K, BTreeSet<Privilege>>,
+            key: K,
+            update: &impl Fn(&mut BTreeSet<Privilege>),
+        ) {
+            let mut granted = all.remove(&key).unwrap_or_default();
+            update(&mut granted);
+            if !granted.is_empty() {
+                all.insert(key, granted);
+            }
+        }
+        match target {
+            PrivilegeTarget::Item(id) => {
+                let mut privileges = self.catalog.get_entry(&id).privileges().clone();
+                for role_id in role_ids {
+                    apply(&mut privileges, *role_id, &update);
+                }
+                vec![catalog::Op::UpdateItemPrivileges { id, privileges }]
+            }
+            PrivilegeTarget::Schema(schema_id) => role_ids
+                .iter()
+                .map(|role_id| {
+                    let role = self.catalog.get_role_by_id(role_id);
+                    let mut schema_privileges = role.schema_privileges.clone();
+                    apply(&mut schema_privileges, schema_id, &update);
+                    catalog::Op::UpdateRolePrivileges {
+                        name: role.name.clone(),
+                        schema_privileges,
+                    }
+                })
+                .collect(),
+        }
+    }
+
     async fn sequence_drop_compute_instances(
         &mut self,
         session: &Session,
@@ -1808,6 +1933,14 @@ impl<S: Append + 'static> Coordinator<S> {
             self.send_builtin_table_updates(vec![retraction], BuiltinTableUpdateSource::Background)
                 .await;
         }
+        if let Some(Some(liveness)) = self.transient_replica_liveness.insert(replica_id, None) {
+            let retraction = self
+                .catalog
+                .state()
+                .pack_replica_liveness_update(replica_id, liveness, -1);
+            self.send_builtin_table_updates(vec![retraction], BuiltinTableUpdateSource::Background)
+                .await;
+        }
         self.controller
             .active_compute()
             .drop_replica(instance_id, replica_id, replica_config)
@@ -1833,7 +1966,11 @@ impl<S: Append + 'static> Coordinator<S> {
             ObjectType::Type => ExecuteResponse::DroppedType,
             ObjectType::Secret => ExecuteResponse::DroppedSecret,
             ObjectType::Connection => ExecuteResponse::DroppedConnection,
-            ObjectType::Role | ObjectType::Cluster | ObjectType::ClusterReplica => {
+            ObjectType::Role
+            | ObjectType::Cluster
+            | ObjectType::ClusterReplica
+            | ObjectType::Database
+            | ObjectType::Schema => {
                 unreachable!("handled through their respective sequence_drop functions")
             }
             ObjectType::Object => unreachable!("generic OBJECT cannot be dropped"),
@@ -2268,6 +2405,8 @@ impl<S: Append + 'static> Coordinator<S> {
             when,
             copy_to,
             emit_progress,
+            progress_interval,
+            idle_timeout,
         } = plan;
 
         let compute_instance = self.catalog.active_compute_instance(session)?;
@@ -2345,15 +2484,25 @@ impl<S: Append + 'static> Coordinator<S> {
         };
 
         let (sink_id, sink_desc) = dataflow.sink_exports.iter().next().unwrap();
-        session.add_drop_sink(ComputeSinkId {
+        let compute_sink_id = ComputeSinkId {
             compute_instance: compute_instance_id,
             global_id: *sink_id,
-        });
+        };
+        session.add_drop_sink(compute_sink_id);
         let arity = sink_desc.from_desc.arity();
         let (tx, rx) = mpsc::unbounded_channel();
         self.metrics.active_subscribes.inc();
-        self.pending_subscribes
-            .insert(*sink_id, PendingSubscribe::new(tx, emit_progress, arity));
+        self.pending_subscribes.insert(
+            *sink_id,
+            PendingSubscribe::new(
+                compute_sink_id,
+                tx,
+                emit_progress,
+                arity,
+                progress_interval,
+                idle_timeout,
+            ),
+        );
         self.ship_dataflow(dataflow, compute_instance_id).await;
 
         let resp = ExecuteResponse::Subscribing { rx };
@@ -2846,6 +2995,72 @@ impl<S: Append + 'static> Coordinator<S> {
         self.sequence_insert_constant(session, id, values.into_inner())
     }
 
+    /// Appends the body of a request to a webhook source, if it satisfies the
+    /// source's `CHECK` clause.
+    pub(crate) async fn sequence_append_webhook(
+        &mut self,
+        session: &mut Session,
+        name: PartialObjectName,
+        body: String,
+        signature: Option<String>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let entry = self
+            .catalog
+            .resolve_entry(None, &vec![], &name, session.conn_id())?;
+        let (secret, check) = match entry.item() {
+            CatalogItem::Source(catalog::Source {
+                data_source: DataSourceDesc::Webhook { secret, check },
+                ..
+            }) => (*secret, check.clone()),
+            _ => {
+                return Err(AdapterError::Unstructured(anyhow!(
+                    "{name} is not a webhook source"
+                )))
+            }
+        };
+        let id = entry.id();
+        rbac::check_webhook_append(&self.catalog, session, &id)?;
+
+        // Requests to a source that validates them must carry the HMAC-SHA256
+        // of their body, keyed by the contents of the secret.
+        if let Some(secret) = secret {
+            let key = self
+                .connection_context
+                .secrets_reader
+                .read(secret)
+                .await
+                .map_err(AdapterError::Unstructured)?;
+            let signature = signature.and_then(|signature| hex::decode(signature).ok());
+            let mut mac =
+                Hmac::<Sha256>::new_from_slice(&key).expect("HMAC accepts keys of any length");
+            mac.update(body.as_bytes());
+            if signature.map_or(true, |signature| mac.verify_slice(&signature).is_err()) {
+                return Err(AdapterError::Unauthorized(format!(
+                    "request to {name} has a missing or invalid signature"
+                )));
+            }
+        }
+
+        let row = body
+            .parse::<Jsonb>()
+            .map_err(|e| AdapterError::Unstructured(anyhow!("invalid request body: {e}")))?
+            .into_row();
+        if let Some(check) = check {
+            let arena = RowArena::new();
+            if check.eval(&row.unpack(), &arena)? != Datum::True {
+                return Err(AdapterError::Unstructured(anyhow!(
+                    "request body does not satisfy the CHECK clause of {name}"
+                )));
+            }
+        }
+
+        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+            id,
+            rows: vec![(row, 1)],
+        }]))?;
+        Ok(ExecuteResponse::Inserted(1))
+    }
+
     // ReadThenWrite is a plan whose writes depend on the results of a
     // read. This works by doing a Peek then queuing a SendDiffs. No writes
     // or read-then-writes can occur between the Peek and SendDiff otherwise a
@@ -3113,6 +3328,20 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    async fn sequence_alter_owner(
+        &mut self,
+        session: &Session,
+        plan: AlterOwnerPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::UpdateOwner {
+            id: plan.id,
+            new_owner: plan.new_owner,
+        };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+        Ok(ExecuteResponse::AlteredObject(plan.object_type))
+    }
+
     async fn sequence_alter_index_set_options(
         &mut self,
         plan: AlterIndexSetOptionsPlan,
@@ -3183,9 +3412,22 @@ impl<S: Append + 'static> Coordinator<S> {
     async fn sequence_alter_sink(
         &mut self,
         session: &Session,
-        AlterSinkPlan { id, size, remote }: AlterSinkPlan,
+        AlterSinkPlan {
+            id,
+            size,
+            remote,
+            runtime_options,
+        }: AlterSinkPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let op = catalog::Op::AlterSink { id, size, remote };
+        let runtime_options_changed = runtime_options.any_changed();
+        let retention_changed = !runtime_options.retention_ms.is_unchanged()
+            || !runtime_options.retention_bytes.is_unchanged();
+        let op = catalog::Op::AlterSink {
+            id,
+            size,
+            remote,
+            runtime_options,
+        };
         self.catalog_transact(Some(session), vec![op], |_| Ok(()))
             .await?;
 
@@ -3195,6 +3437,30 @@ impl<S: Append + 'static> Coordinator<S> {
             CatalogError::UnexpectedType(entry.name().to_string(), CatalogItemType::Sink)
         })?;
 
+        if runtime_options_changed {
+            let connection = match &updated_sink.connection {
+                StorageSinkConnectionState::Ready(connection) => connection.clone(),
+                StorageSinkConnectionState::Pending(_) => {
+                    coord_bail!("sink {id} does not have a ready connection")
+                }
+            };
+            if retention_changed {
+                if let StorageSinkConnection::Kafka(kafka) = &connection {
+                    mz_storage::sink::alter_kafka_sink_retention(
+                        kafka,
+                        kafka.retention,
+                        &self.connection_context,
+                    )
+                    .await
+                    .map_err(AdapterError::Unstructured)?;
+                }
+            }
+            self.controller
+                .storage
+                .alter_export(id, connection, updated_sink.rate_limit)
+                .await?;
+        }
+
         self.controller
             .storage
             .alter_collections(vec![(id, updated_sink.host_config.clone())])
@@ -3229,6 +3495,154 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
     }
 
+    /// Re-purifies the statement of the source, fetching the latest schemas
+    /// from its schema registry off the main coordinator thread. The source is
+    /// altered once purification completes, in
+    /// [`Coordinator::sequence_alter_source_schema`].
+    fn sequence_alter_source_refresh_schema(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        AlterSourceRefreshSchemaPlan { id }: AlterSourceRefreshSchemaPlan,
+    ) {
+        let entry = self.catalog.get_entry(&id);
+        let create_sql = match entry.source() {
+            Some(source) => source.create_sql.clone(),
+            None => {
+                let err =
+                    CatalogError::UnexpectedType(entry.name().to_string(), CatalogItemType::Source);
+                return tx.send(Err(err.into()), session);
+            }
+        };
+
+        let stmt = mz_sql::parse::parse(&create_sql)
+            .expect("create_sql cannot be invalid")
+            .into_element();
+        let catalog = self.catalog.for_session(&session);
+        let stmt = match mz_sql::names::resolve(&catalog, stmt) {
+            Ok((Statement::CreateSource(stmt), _)) => stmt,
+            Ok(_) => unreachable!("sources are created by CREATE SOURCE statements"),
+            Err(e) => return tx.send(Err(e.into()), session),
+        };
+        let stmt = match mz_sql::pure::clear_csr_seeds(stmt) {
+            Ok(stmt) => stmt,
+            Err(e) => return tx.send(Err(e.into()), session),
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let purify_fut = mz_sql::pure::purify_create_source(
+            Box::new(catalog.into_owned()),
+            self.now(),
+            stmt,
+            self.connection_context.clone(),
+        );
+        let otel_ctx = OpenTelemetryContext::obtain();
+        task::spawn(|| format!("refresh_schema:{id}"), async move {
+            let result = purify_fut.await.map_err(|e| e.into());
+            // It is not an error for purification to complete after `internal_cmd_rx` is dropped.
+            let result =
+                internal_cmd_tx.send(Message::SourceSchemaRefreshed(SourceSchemaRefreshed {
+                    session,
+                    tx,
+                    id,
+                    create_sql,
+                    result,
+                    otel_ctx,
+                }));
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Replaces the relation and source description of the source `id` with
+    /// those planned from its re-purified statement.
+    ///
+    /// The new relation must extend the old one with nullable columns only, so
+    /// that rows ingested before the refresh remain valid once padded with
+    /// `NULL`s.
+    pub(crate) async fn sequence_alter_source_schema(
+        &mut self,
+        session: &Session,
+        id: GlobalId,
+        original_create_sql: &str,
+        CreateSourcePlan { source, .. }: CreateSourcePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        // The catalog may have changed while purification was in progress.
+        let entry = match self.catalog.try_get_entry(&id) {
+            Some(entry) => entry,
+            None => coord_bail!("source was dropped while its schema was being refreshed"),
+        };
+        let name = entry.name().clone();
+        let old_source = match entry.source() {
+            Some(source) if source.create_sql == original_create_sql => source.clone(),
+            _ => coord_bail!(
+                "source {} was altered while its schema was being refreshed",
+                name.item
+            ),
+        };
+        if !entry.used_by().is_empty() {
+            coord_bail!(
+                "cannot refresh the schema of source {}: other objects depend on it",
+                name.item
+            );
+        }
+
+        let old_arity = old_source.desc.arity();
+        if source.desc.arity() < old_arity
+            || old_source
+                .desc
+                .iter()
+                .zip(source.desc.iter())
+                .any(|(old, new)| old != new)
+        {
+            coord_bail!(
+                "cannot refresh the schema of source {}: the new schema must only add columns",
+                name.item
+            );
+        }
+        if let Some((column, _)) = source
+            .desc
+            .iter()
+            .skip(old_arity)
+            .find(|(_, typ)| !typ.nullable)
+        {
+            coord_bail!(
+                "cannot refresh the schema of source {}: new column {} is not nullable",
+                name.item,
+                column.as_str().quoted()
+            );
+        }
+
+        let source_desc = match source.data_source {
+            mz_sql::plan::DataSourceDesc::Ingestion(ingestion) => ingestion.desc,
+            _ => coord_bail!("source {} does not ingest data", name.item),
+        };
+        let data_source = match old_source.data_source {
+            DataSourceDesc::Ingestion(ingestion) => DataSourceDesc::Ingestion(Ingestion {
+                desc: source_desc.clone(),
+                ..ingestion
+            }),
+            _ => coord_bail!("source {} does not ingest data", name.item),
+        };
+        let to_item = CatalogItem::Source(catalog::Source {
+            create_sql: source.create_sql,
+            data_source,
+            desc: source.desc.clone(),
+            ..old_source
+        });
+        let op = catalog::Op::UpdateItem { id, name, to_item };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+
+        self.controller
+            .storage
+            .alter_ingestion(id, source.desc, source_desc)
+            .await?;
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
     fn extract_secret(
         &mut self,
         session: &Session,
@@ -37,6 +37,7 @@ mod coord;
 mod error;
 mod explain_new;
 mod notice;
+mod rbac;
 mod subscribe;
 mod util;
 
@@ -0,0 +1,327 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Privilege checks for planned SQL statements.
+//!
+//! Superusers and the internal system users may execute any plan. Other roles
+//! may only read from, write to, and create objects in the relations and
+//! schemas on which they have been granted the corresponding privilege, may
+//! only use the secrets and connections on which they have been granted USAGE,
+//! may only drop and alter the objects they own, and may not execute any other
+//! DDL.
+
+use mz_ore::str::StrExt;
+use mz_repr::GlobalId;
+use mz_sql::catalog::Privilege;
+use mz_sql::names::{QualifiedObjectName, ResolvedDatabaseSpecifier, SchemaSpecifier};
+use mz_sql::plan::{
+    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterSecretPlan,
+    AlterSinkPlan, AlterSourcePlan, AlterSourceRefreshSchemaPlan, MutationKind, ObjectId, Plan,
+    RotateKeysPlan,
+};
+
+use crate::catalog::{Catalog, Role, INTERNAL_USER_NAMES};
+use crate::error::AdapterError;
+use crate::session::Session;
+
+/// Reports whether the role of the current session is a superuser.
+///
+/// Sessions of internal users are always considered superusers. Sessions whose
+/// role no longer exists are not.
+pub fn is_superuser(catalog: &Catalog, session: &Session) -> bool {
+    let name = &session.user().name;
+    INTERNAL_USER_NAMES.contains(name)
+        || catalog
+            .try_get_role(name)
+            .map_or(false, |role| role.superuser)
+}
+
+/// Checks that the role of the current session holds the privileges required
+/// to execute `plan`, which depends on the items in `depends_on`.
+pub fn check_plan(
+    catalog: &Catalog,
+    session: &Session,
+    plan: &Plan,
+    depends_on: &[GlobalId],
+) -> Result<(), AdapterError> {
+    if is_superuser(catalog, session) {
+        return Ok(());
+    }
+    let checker = Checker { catalog, session };
+    match plan {
+        Plan::Peek(_)
+        | Plan::Subscribe(_)
+        | Plan::Explain(_)
+        | Plan::CreateView(_)
+        | Plan::CreateMaterializedView(_)
+        | Plan::CreateIndex(_)
+        | Plan::CreateSink(_) => {
+            checker.check_items(depends_on, Privilege::Select)?;
+        }
+        Plan::Insert(plan) => {
+            checker.check_item(&plan.id, Privilege::Insert)?;
+            let reads: Vec<_> = depends_on
+                .iter()
+                .filter(|id| **id != plan.id)
+                .copied()
+                .collect();
+            checker.check_items(&reads, Privilege::Select)?;
+        }
+        Plan::ReadThenWrite(plan) => {
+            let privilege = match plan.kind {
+                MutationKind::Insert => Privilege::Insert,
+                MutationKind::Update => Privilege::Update,
+                MutationKind::Delete => Privilege::Delete,
+            };
+            checker.check_item(&plan.id, privilege)?;
+            let reads: Vec<_> = depends_on
+                .iter()
+                .filter(|id| **id != plan.id)
+                .copied()
+                .collect();
+            checker.check_items(&reads, Privilege::Select)?;
+        }
+        Plan::CopyFrom(plan) => checker.check_item(&plan.id, Privilege::Insert)?,
+        _ => {}
+    }
+
+    if let Plan::CreateConnection(_) | Plan::CreateSource(_) | Plan::CreateSink(_) = plan {
+        checker.check_items(depends_on, Privilege::Usage)?;
+    }
+
+    let create_in = match plan {
+        Plan::CreateConnection(plan) => Some(&plan.name),
+        Plan::CreateSource(plan) => Some(&plan.name),
+        Plan::CreateSecret(plan) => Some(&plan.name),
+        Plan::CreateSink(plan) => Some(&plan.name),
+        Plan::CreateTable(plan) => Some(&plan.name),
+        Plan::CreateView(plan) => Some(&plan.name),
+        Plan::CreateMaterializedView(plan) => Some(&plan.name),
+        Plan::CreateIndex(plan) => Some(&plan.name),
+        Plan::CreateType(plan) => Some(&plan.name),
+        _ => None,
+    };
+    if let Some(name) = create_in {
+        return checker.check_schema(name);
+    }
+
+    match plan {
+        Plan::DropDatabase(plan) => match plan.id {
+            Some(id) => checker.check_owner(ObjectId::Database(id)),
+            None => Ok(()),
+        },
+        Plan::DropSchema(plan) => match plan.id {
+            Some((database_id, schema_id)) => {
+                checker.check_owner(ObjectId::Schema(database_id, schema_id))
+            }
+            None => Ok(()),
+        },
+        Plan::DropItems(plan) => plan
+            .items
+            .iter()
+            .try_for_each(|id| checker.check_owner(ObjectId::Item(*id))),
+        Plan::AlterIndexSetOptions(AlterIndexSetOptionsPlan { id, .. })
+        | Plan::AlterIndexResetOptions(AlterIndexResetOptionsPlan { id, .. })
+        | Plan::AlterSink(AlterSinkPlan { id, .. })
+        | Plan::AlterSource(AlterSourcePlan { id, .. })
+        | Plan::AlterSourceRefreshSchema(AlterSourceRefreshSchemaPlan { id })
+        | Plan::AlterItemRename(AlterItemRenamePlan { id, .. })
+        | Plan::AlterSecret(AlterSecretPlan { id, .. })
+        | Plan::RotateKeys(RotateKeysPlan { id }) => checker.check_owner(ObjectId::Item(*id)),
+        Plan::CreateDatabase(_)
+        | Plan::CreateSchema(_)
+        | Plan::CreateRole(_)
+        | Plan::CreateComputeInstance(_)
+        | Plan::CreateComputeReplica(_)
+        | Plan::DropRoles(_)
+        | Plan::DropComputeInstances(_)
+        | Plan::DropComputeReplicas(_)
+        | Plan::AlterOwner(_)
+        | Plan::AlterSystemSet(_)
+        | Plan::AlterSystemReset(_)
+        | Plan::AlterSystemResetAll(_)
+        | Plan::GrantPrivileges(_)
+        | Plan::RevokePrivileges(_) => Err(AdapterError::Unauthorized(format!(
+            "role {} must be a superuser to execute this statement",
+            session.user().name.quoted(),
+        ))),
+        Plan::CreateConnection(_)
+        | Plan::CreateSource(_)
+        | Plan::CreateSecret(_)
+        | Plan::CreateSink(_)
+        | Plan::CreateTable(_)
+        | Plan::CreateView(_)
+        | Plan::CreateMaterializedView(_)
+        | Plan::CreateIndex(_)
+        | Plan::CreateType(_)
+        | Plan::DiscardTemp
+        | Plan::DiscardAll
+        | Plan::EmptyQuery
+        | Plan::ShowAllVariables
+        | Plan::ShowVariable(_)
+        | Plan::SetVariable(_)
+        | Plan::ResetVariable(_)
+        | Plan::StartTransaction(_)
+        | Plan::CommitTransaction
+        | Plan::AbortTransaction
+        | Plan::Peek(_)
+        | Plan::Subscribe(_)
+        | Plan::SendRows(_)
+        | Plan::CopyFrom(_)
+        | Plan::Explain(_)
+        | Plan::SendDiffs(_)
+        | Plan::Insert(_)
+        | Plan::AlterNoop(_)
+        | Plan::Declare(_)
+        | Plan::Fetch(_)
+        | Plan::Close(_)
+        | Plan::ReadThenWrite(_)
+        | Plan::Prepare(_)
+        | Plan::Execute(_)
+        | Plan::Deallocate(_)
+        | Plan::Raise(_) => Ok(()),
+    }
+}
+
+/// Checks that the role of the current session may append requests to the
+/// webhook source `id`, which requires that it owns the source or has been
+/// granted INSERT on it.
+pub fn check_webhook_append(
+    catalog: &Catalog,
+    session: &Session,
+    id: &GlobalId,
+) -> Result<(), AdapterError> {
+    if is_superuser(catalog, session) {
+        return Ok(());
+    }
+    let checker = Checker { catalog, session };
+    if catalog.get_entry(id).owner_id() == checker.role()?.id {
+        return Ok(());
+    }
+    checker.check_item(id, Privilege::Insert)
+}
+
+struct Checker<'a> {
+    catalog: &'a Catalog,
+    session: &'a Session,
+}
+
+impl Checker<'_> {
+    /// Checks `privilege` on each user item in `ids`.
+    ///
+    /// System items and temporary items are readable by every role.
+    fn check_items(&self, ids: &[GlobalId], privilege: Privilege) -> Result<(), AdapterError> {
+        for id in ids {
+            let entry = match self.catalog.try_get_entry(id) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            if id.is_system() || entry.conn_id().is_some() {
+                continue;
+            }
+            if Privilege::for_item_type(entry.item().typ()).contains(&privilege) {
+                self.check_item(id, privilege)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_item(&self, id: &GlobalId, privilege: Privilege) -> Result<(), AdapterError> {
+        let entry = self.catalog.get_entry(id);
+        if id.is_system() || entry.conn_id().is_some() {
+            return Ok(());
+        }
+        let role = self.role()?;
+        if entry.has_privilege(&role.id, privilege) {
+            return Ok(());
+        }
+        let name = self
+            .catalog
+            .resolve_full_name(entry.name(), entry.conn_id());
+        Err(AdapterError::Unauthorized(format!(
+            "role {} does not have {privilege} privilege on {} {}",
+            role.name.quoted(),
+            entry.item().typ(),
+            name.to_string().quoted(),
+        )))
+    }
+
+    /// Checks that the role of the current session owns the object identified
+    /// by `id`.
+    fn check_owner(&self, id: ObjectId) -> Result<(), AdapterError> {
+        let role = self.role()?;
+        let (owner_id, object) = match id {
+            ObjectId::Database(id) => {
+                let database = self.catalog.get_database(&id);
+                (
+                    database.owner_id,
+                    format!("database {}", database.name.quoted()),
+                )
+            }
+            ObjectId::Schema(database_id, schema_id) => {
+                let schema = self.catalog.get_schema(
+                    &ResolvedDatabaseSpecifier::Id(database_id),
+                    &SchemaSpecifier::Id(schema_id),
+                    self.session.conn_id(),
+                );
+                (
+                    schema.owner_id,
+                    format!("schema {}", schema.name.schema.quoted()),
+                )
+            }
+            ObjectId::Item(id) => {
+                let entry = self.catalog.get_entry(&id);
+                let name = self
+                    .catalog
+                    .resolve_full_name(entry.name(), entry.conn_id());
+                (
+                    entry.owner_id(),
+                    format!("{} {}", entry.item().typ(), name.to_string().quoted()),
+                )
+            }
+        };
+        if owner_id == role.id {
+            return Ok(());
+        }
+        Err(AdapterError::Unauthorized(format!(
+            "role {} must be the owner of {object}",
+            role.name.quoted(),
+        )))
+    }
+
+    /// Checks the CREATE privilege on the schema in which `name` would be
+    /// created.
+    fn check_schema(&self, name: &QualifiedObjectName) -> Result<(), AdapterError> {
+        let schema_id = match &name.qualifiers.schema_spec {
+            SchemaSpecifier::Temporary => return Ok(()),
+            SchemaSpecifier::Id(id) => id,
+        };
+        let role = self.role()?;
+        if role.has_schema_privilege(schema_id, Privilege::Create) {
+            return Ok(());
+        }
+        let schema = self.catalog.get_schema(
+            &name.qualifiers.database_spec,
+            &name.qualifiers.schema_spec,
+            self.session.conn_id(),
+        );
+        Err(AdapterError::Unauthorized(format!(
+            "role {} does not have CREATE privilege on schema {}",
+            role.name.quoted(),
+            schema.name.schema.quoted(),
+        )))
+    }
+
+    fn role(&self) -> Result<&Role, AdapterError> {
+        let name = &self.session.user().name;
+        self.catalog
+            .try_get_role(name)
+            .ok_or_else(|| AdapterError::UnknownLoginRole(name.clone()))
+    }
+}
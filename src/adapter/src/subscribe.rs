@@ -9,16 +9,25 @@
 
 //! Implementations around supporting the SUBSCRIBE protocol with the dataflow layer
 
+use std::time::{Duration, Instant};
+
 use tokio::sync::mpsc;
 
 use mz_compute_client::response::{SubscribeBatch, SubscribeResponse};
 use mz_repr::adt::numeric;
-use mz_repr::{Datum, Row};
+use mz_repr::{Datum, Row, Timestamp};
 
 use crate::coord::peek::PeekResponseUnary;
+use crate::util::ComputeSinkId;
+
+/// How often the coordinator checks pending subscribes that have a progress
+/// interval or an idle timeout.
+pub(crate) const HEARTBEAT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
 /// A description of a pending subscribe from coord's perspective
 pub(crate) struct PendingSubscribe {
+    /// The compute sink that maintains the subscribe
+    pub(crate) sink_id: ComputeSinkId,
     /// Channel to send responses to the client
     ///
     /// The responses have the form `PeekResponseUnary` but should perhaps become `TailResponse`.
@@ -27,6 +36,15 @@ pub(crate) struct PendingSubscribe {
     emit_progress: bool,
     /// Number of columns in the output
     arity: usize,
+    /// The interval at which progress information is repeated if the upper
+    /// has not advanced
+    progress_interval: Option<Duration>,
+    /// How long the subscribe may go without emitting updates
+    idle_timeout: Option<Duration>,
+    /// The last upper reported to the client, and when it was reported
+    last_progress: Option<(Timestamp, Instant)>,
+    /// When the last update was sent to the client
+    last_update: Instant,
 }
 
 impl PendingSubscribe {
@@ -34,18 +52,82 @@ impl PendingSubscribe {
     /// * The `channel` receives batches of finalized PeekResponses.
     /// * If `emit_progress` is true, the finalized rows are either data or progress updates
     /// * `arity` is the arity of the sink relation.
+    /// * If `progress_interval` is set, progress updates are repeated at least
+    ///   that often, even if the sink's upper does not advance.
+    /// * If `idle_timeout` is set, the subscribe ends once it has not emitted
+    ///   any updates for that long.
     pub(crate) fn new(
+        sink_id: ComputeSinkId,
         channel: mpsc::UnboundedSender<PeekResponseUnary>,
         emit_progress: bool,
         arity: usize,
+        progress_interval: Option<Duration>,
+        idle_timeout: Option<Duration>,
     ) -> Self {
         Self {
+            sink_id,
             channel,
             emit_progress,
             arity,
+            progress_interval,
+            idle_timeout,
+            last_progress: None,
+            last_update: Instant::now(),
         }
     }
 
+    /// Whether the subscribe needs to be checked periodically with
+    /// [`PendingSubscribe::heartbeat`].
+    pub(crate) fn needs_heartbeat(&self) -> bool {
+        self.progress_interval.is_some() || self.idle_timeout.is_some()
+    }
+
+    /// Repeats the last progress update if `progress_interval` has elapsed
+    /// since it was sent, and checks whether the subscribe has become idle.
+    ///
+    /// Returns `true` if the sink should be removed, either because the client
+    /// has gone away or because the subscribe exceeded its idle timeout.
+    pub(crate) fn heartbeat(&mut self, now: Instant) -> bool {
+        if self.channel.is_closed() {
+            return true;
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            if now.duration_since(self.last_update) >= idle_timeout {
+                let _ = self.channel.send(PeekResponseUnary::Error(format!(
+                    "SUBSCRIBE emitted no updates for {:?}, exceeding its IDLE TIMEOUT",
+                    idle_timeout
+                )));
+                return true;
+            }
+        }
+        if let (Some(interval), Some((upper, sent))) = (self.progress_interval, self.last_progress)
+        {
+            if now.duration_since(sent) >= interval {
+                self.send_progress(upper, now);
+            }
+        }
+        false
+    }
+
+    /// Sends a progress update for `upper` to the client.
+    fn send_progress(&mut self, upper: Timestamp, now: Instant) {
+        let mut row_buf = Row::default();
+        let mut packer = row_buf.packer();
+        packer.push(Datum::from(numeric::Numeric::from(upper)));
+        packer.push(Datum::True);
+        // Fill in the diff column and all table columns with NULL.
+        for _ in 0..(self.arity + 1) {
+            packer.push(Datum::Null);
+        }
+
+        let result = self.channel.send(PeekResponseUnary::Rows(vec![row_buf]));
+        if result.is_err() {
+            // TODO(benesch): we should actually drop the sink if the
+            // receiver has gone away. E.g. form a DROP SINK command?
+        }
+        self.last_progress = Some((upper, now));
+    }
+
     /// Process a subscribe response
     ///
     /// Returns `true` if the sink should be removed.
@@ -84,6 +166,10 @@ impl PendingSubscribe {
                         row_buf.clone()
                     })
                     .collect();
+                let now = Instant::now();
+                if !rows.is_empty() {
+                    self.last_update = now;
+                }
                 // TODO(benesch): the lack of backpressure here can result in
                 // unbounded memory usage.
                 let result = self.channel.send(PeekResponseUnary::Rows(rows));
@@ -98,19 +184,7 @@ impl PendingSubscribe {
                         1,
                         "SUBSCRIBE only supports single-dimensional timestamps"
                     );
-                    let mut packer = row_buf.packer();
-                    packer.push(Datum::from(numeric::Numeric::from(upper[0])));
-                    packer.push(Datum::True);
-                    // Fill in the diff column and all table columns with NULL.
-                    for _ in 0..(self.arity + 1) {
-                        packer.push(Datum::Null);
-                    }
-
-                    let result = self.channel.send(PeekResponseUnary::Rows(vec![row_buf]));
-                    if result.is_err() {
-                        // TODO(benesch): we should actually drop the sink if the
-                        // receiver has gone away. E.g. form a DROP SINK command?
-                    }
+                    self.send_progress(upper[0], now);
                 }
                 upper.is_empty()
             }
@@ -187,10 +187,16 @@ pub enum SchemaPiece {
     Date,
     /// An `Int64` Avro schema with a semantic type being milliseconds since the unix epoch.
     ///
+    /// Also used for the `local-timestamp-millis` logical type, as timestamps
+    /// carry no time zone once decoded.
+    ///
     /// <https://avro.apache.org/docs/current/spec.html#Timestamp+%28millisecond+precision%29>
     TimestampMilli,
     /// An `Int64` Avro schema with a semantic type being microseconds since the unix epoch.
     ///
+    /// Also used for the `local-timestamp-micros` logical type, as timestamps
+    /// carry no time zone once decoded.
+    ///
     /// <https://avro.apache.org/docs/current/spec.html#Timestamp+%28microsecond+precision%29>
     TimestampMicro,
     /// A `bytes` or `fixed` Avro schema with a logical type of `decimal` and
@@ -1205,8 +1211,8 @@ impl SchemaParser {
     /// [1]: https://debezium.io/docs/connectors/mysql/#temporal-values
     /// [2]: https://avro.apache.org/docs/1.9.0/spec.html
     fn parse_long(complex: &Map<String, Value>) -> Result<SchemaPiece, AvroError> {
-        const AVRO_MILLI_TS: &str = "timestamp-millis";
-        const AVRO_MICRO_TS: &str = "timestamp-micros";
+        const AVRO_MILLI_TS: &[&str] = &["timestamp-millis", "local-timestamp-millis"];
+        const AVRO_MICRO_TS: &[&str] = &["timestamp-micros", "local-timestamp-micros"];
 
         const CONNECT_MILLI_TS: &[&str] = &[
             "io.debezium.time.Timestamp",
@@ -1222,11 +1228,11 @@ impl SchemaParser {
                 return Ok(SchemaPiece::TimestampMicro);
             }
         }
-        if let Some(name) = complex.get("logicalType") {
-            if name == AVRO_MILLI_TS {
+        if let Some(serde_json::Value::String(name)) = complex.get("logicalType") {
+            if AVRO_MILLI_TS.contains(&&**name) {
                 return Ok(SchemaPiece::TimestampMilli);
             }
-            if name == AVRO_MICRO_TS {
+            if AVRO_MICRO_TS.contains(&&**name) {
                 return Ok(SchemaPiece::TimestampMicro);
             }
         }
@@ -1279,7 +1285,10 @@ impl SchemaParser {
         if let Some("decimal") = logical_type {
             match Self::parse_decimal(complex) {
                 Ok((precision, scale)) => {
-                    let max = ((2_usize.pow((8 * size - 1) as u32) - 1) as f64).log10() as usize;
+                    // The largest precision that fits in `size` bytes is
+                    // floor(log10(2^(8 * size - 1) - 1)), computed in floating
+                    // point so that sizes over 8 bytes do not overflow.
+                    let max = ((8 * size - 1) as f64 * 2_f64.log10()).floor() as usize;
                     if precision > max {
                         warn!("Decimal precision {} requires more than {} bytes of space, parsing as fixed", precision, size);
                     } else {
@@ -2630,6 +2639,34 @@ mod tests {
             reader_null_variant: Some(0),
         };
         assert_eq!(resolved.top_node().inner, &expected);
+
+        let schema = r#"{
+                "type": "fixed",
+                "name": "dec",
+                "size": 16,
+                "logicalType": "decimal",
+                "precision": 38,
+                "scale": 10
+            }"#;
+        let expected = SchemaPiece::Decimal {
+            precision: 38,
+            scale: 10,
+            fixed_size: Some(16),
+        };
+        check_schema(schema, expected);
+    }
+
+    #[test]
+    fn test_timestamp_schemas() {
+        for (logical_type, expected) in [
+            ("timestamp-millis", SchemaPiece::TimestampMilli),
+            ("timestamp-micros", SchemaPiece::TimestampMicro),
+            ("local-timestamp-millis", SchemaPiece::TimestampMilli),
+            ("local-timestamp-micros", SchemaPiece::TimestampMicro),
+        ] {
+            let schema = format!(r#"{{"type": "long", "logicalType": "{}"}}"#, logical_type);
+            check_schema(&schema, expected);
+        }
     }
 
     #[test]
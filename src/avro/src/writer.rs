@@ -71,6 +71,8 @@ pub struct Writer<W> {
     codec: Option<Codec>,
     marker: [u8; 16],
     has_header: bool,
+    schema_json: Option<String>,
+    user_metadata: Vec<(String, Vec<u8>)>,
 }
 
 impl<W: Write> Writer<W> {
@@ -106,9 +108,24 @@ impl<W: Write> Writer<W> {
             codec,
             marker,
             has_header: false,
+            schema_json: None,
+            user_metadata: vec![],
         }
     }
 
+    /// Creates a `Writer` for the schema that `json` describes.
+    ///
+    /// Unlike the other constructors, which embed the canonical form of the
+    /// parsed `Schema` in the header, this embeds `json` verbatim. This
+    /// preserves attributes that `Schema` does not model, like the field IDs
+    /// that Apache Iceberg relies on.
+    pub fn with_json_schema(json: &str, writer: W, codec: Codec) -> Result<Writer<W>, Error> {
+        let schema = json.parse()?;
+        let mut writer = Writer::with_codec(schema, writer, codec);
+        writer.schema_json = Some(json.into());
+        Ok(writer)
+    }
+
     /// Creates a `Writer` that appends to an existing OCF file.
     pub fn append_to(mut file: W) -> Result<Writer<W>, Error>
     where
@@ -125,9 +142,27 @@ impl<W: Write> Writer<W> {
             codec: Some(codec),
             marker,
             has_header: true,
+            schema_json: None,
+            user_metadata: vec![],
         })
     }
 
+    /// Adds the pair of `key` and `value` to the metadata in the header.
+    ///
+    /// This must be called before appending any values, and `key` must not
+    /// use the `avro.` prefix that the specification reserves.
+    pub fn add_user_metadata<V: AsRef<[u8]>>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        if self.has_header {
+            return Err(ValidationError::new("header has already been written").into());
+        }
+        if key.starts_with("avro.") {
+            return Err(ValidationError::new(format!("reserved metadata key {}", key)).into());
+        }
+        self.user_metadata
+            .push((key.into(), value.as_ref().to_vec()));
+        Ok(())
+    }
+
     /// Get a reference to the `Schema` associated to a `Writer`.
     pub fn schema(&self) -> &Schema {
         &self.schema
@@ -300,9 +335,15 @@ impl<W: Write> Writer<W> {
 
     /// Create an Avro header based on schema, codec and sync marker.
     fn header(&self) -> Result<Vec<u8>, Error> {
-        let schema_bytes = serde_json::to_string(&self.schema)?.into_bytes();
+        let schema_bytes = match &self.schema_json {
+            Some(json) => json.clone().into_bytes(),
+            None => serde_json::to_string(&self.schema)?.into_bytes(),
+        };
 
-        let mut metadata = HashMap::with_capacity(2);
+        let mut metadata = HashMap::with_capacity(2 + self.user_metadata.len());
+        for (key, value) in &self.user_metadata {
+            metadata.insert(key.as_str(), Value::Bytes(value.clone()));
+        }
         metadata.insert("avro.schema", Value::Bytes(schema_bytes));
         if let Some(codec) = self.codec {
             metadata.insert("avro.codec", codec.avro());
@@ -631,4 +672,27 @@ mod tests {
             actual
         );
     }
+
+    #[test]
+    fn test_writer_json_schema_and_metadata() {
+        let json = r#"{"type": "record", "name": "test", "fields": [{"name": "a", "type": "long", "field-id": 1}]}"#;
+        let mut writer = Writer::with_json_schema(json, Vec::new(), Codec::Null).unwrap();
+        writer.add_user_metadata("format-version", "1").unwrap();
+        assert!(writer.add_user_metadata("avro.codec", "deflate").is_err());
+
+        let record = Value::Record(vec![("a".into(), Value::Long(27))]);
+        writer.append_value_ref(&record).unwrap();
+        assert!(writer.add_user_metadata("snapshot-id", "1").is_err());
+        writer.flush().unwrap();
+        let buf = writer.into_inner();
+
+        // The header embeds the schema verbatim, and the user metadata.
+        let contains = |needle: &[u8]| buf.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(json.as_bytes()));
+        assert!(contains(b"format-version"));
+
+        let reader = Reader::new(&buf[..]).unwrap();
+        let actual: Result<Vec<_>, _> = reader.collect();
+        assert_eq!(vec![record], actual.unwrap());
+    }
 }
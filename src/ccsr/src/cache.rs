@@ -0,0 +1,196 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A cache of schema registry responses that is shared between clients.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use url::Url;
+
+use crate::client::{Schema, Subject};
+
+/// The durations for which a [`ClientCache`] retains responses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CacheTtls {
+    /// How long to retain schemas looked up by ID.
+    ///
+    /// The schema with a given ID never changes, so this can be long.
+    pub schema: Duration,
+    /// How long to retain the latest version of a subject.
+    pub subject: Duration,
+    /// How long to retain the absence of a schema or subject.
+    pub negative: Duration,
+}
+
+impl Default for CacheTtls {
+    fn default() -> CacheTtls {
+        CacheTtls {
+            schema: Duration::from_secs(60 * 60),
+            subject: Duration::from_secs(60),
+            negative: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Counts of the lookups served by a [`ClientCache`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    /// The number of lookups answered from the cache.
+    pub hits: u64,
+    /// The number of lookups that were sent to the schema registry.
+    pub misses: u64,
+}
+
+/// Identifies the registry, and the credentials used to access it, that a
+/// cached response came from.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RegistryKey {
+    pub(crate) url: Url,
+    pub(crate) username: Option<String>,
+}
+
+#[derive(Debug)]
+struct CacheEntry<T> {
+    /// The cached response, or `None` if the schema or subject does not exist.
+    value: Option<T>,
+    expires_at: Instant,
+}
+
+/// A cache of schema registry responses.
+///
+/// A single cache is meant to be shared by every [`Client`](crate::Client) in
+/// a process, via [`Client::with_cache`](crate::Client::with_cache), so that
+/// many sources reading from the same registry do not each look up the same
+/// schemas. Only successful lookups and lookups of schemas or subjects that do
+/// not exist are cached; transport and server errors are not.
+#[derive(Debug)]
+pub struct ClientCache {
+    ttls: CacheTtls,
+    schemas: Mutex<HashMap<(RegistryKey, i32), CacheEntry<Schema>>>,
+    subjects: Mutex<HashMap<(RegistryKey, String), CacheEntry<Subject>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ClientCache {
+    /// Constructs an empty cache that retains responses for `ttls`.
+    pub fn new(ttls: CacheTtls) -> ClientCache {
+        ClientCache {
+            ttls,
+            schemas: Mutex::new(HashMap::new()),
+            subjects: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the counts of the lookups served by the cache so far.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Looks up the schema with ID `id`.
+    ///
+    /// Returns `None` if the cache has no live entry for the schema, and
+    /// `Some(None)` if the schema is known not to exist.
+    pub(crate) fn get_schema(&self, registry: &RegistryKey, id: i32) -> Option<Option<Schema>> {
+        let schemas = self.schemas.lock().expect("lock poisoned");
+        self.record_lookup(lookup(&schemas, &(registry.clone(), id)))
+    }
+
+    /// Records the schema with ID `id`, or that it does not exist.
+    pub(crate) fn insert_schema(&self, registry: &RegistryKey, id: i32, schema: Option<&Schema>) {
+        let ttl = self.ttl(schema.is_some(), self.ttls.schema);
+        let mut schemas = self.schemas.lock().expect("lock poisoned");
+        insert(&mut schemas, (registry.clone(), id), schema, ttl);
+    }
+
+    /// Looks up the latest version of `subject`.
+    ///
+    /// Returns `None` if the cache has no live entry for the subject, and
+    /// `Some(None)` if the subject is known not to exist.
+    pub(crate) fn get_subject(
+        &self,
+        registry: &RegistryKey,
+        subject: &str,
+    ) -> Option<Option<Subject>> {
+        let subjects = self.subjects.lock().expect("lock poisoned");
+        self.record_lookup(lookup(&subjects, &(registry.clone(), subject.to_owned())))
+    }
+
+    /// Records the latest version of `subject`, or that it does not exist.
+    pub(crate) fn insert_subject(
+        &self,
+        registry: &RegistryKey,
+        subject: &str,
+        latest: Option<&Subject>,
+    ) {
+        let ttl = self.ttl(latest.is_some(), self.ttls.subject);
+        let mut subjects = self.subjects.lock().expect("lock poisoned");
+        insert(
+            &mut subjects,
+            (registry.clone(), subject.to_owned()),
+            latest,
+            ttl,
+        );
+    }
+
+    fn ttl(&self, found: bool, positive: Duration) -> Duration {
+        if found {
+            positive
+        } else {
+            self.ttls.negative
+        }
+    }
+
+    fn record_lookup<T>(&self, result: Option<T>) -> Option<T> {
+        let counter = match result {
+            Some(_) => &self.hits,
+            None => &self.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+}
+
+fn lookup<K, T>(entries: &HashMap<K, CacheEntry<T>>, key: &K) -> Option<Option<T>>
+where
+    K: Eq + Hash,
+    T: Clone,
+{
+    match entries.get(key) {
+        Some(entry) if entry.expires_at > Instant::now() => Some(entry.value.clone()),
+        _ => None,
+    }
+}
+
+fn insert<K, T>(entries: &mut HashMap<K, CacheEntry<T>>, key: K, value: Option<&T>, ttl: Duration)
+where
+    K: Eq + Hash,
+    T: Clone,
+{
+    let now = Instant::now();
+    // Evict expired entries while we hold the lock, so that the cache does not
+    // grow without bound as schemas come and go.
+    entries.retain(|_, entry| entry.expires_at > now);
+    entries.insert(
+        key,
+        CacheEntry {
+            value: value.cloned(),
+            expires_at: now + ttl,
+        },
+    );
+}
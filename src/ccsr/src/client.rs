@@ -10,12 +10,15 @@
 use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::bail;
 use reqwest::{Method, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::cache::{ClientCache, RegistryKey};
 use crate::config::Auth;
 
 /// An API client for a Confluent-compatible schema registry.
@@ -24,6 +27,7 @@ pub struct Client {
     inner: reqwest::Client,
     url: Url,
     auth: Option<Auth>,
+    cache: Option<Arc<ClientCache>>,
 }
 
 impl Client {
@@ -35,7 +39,26 @@ impl Client {
         if url.cannot_be_a_base() {
             bail!("cannot construct a CCSR client with a cannot-be-a-base URL");
         }
-        Ok(Client { inner, url, auth })
+        Ok(Client {
+            inner,
+            url,
+            auth,
+            cache: None,
+        })
+    }
+
+    /// Serves lookups of schemas by ID and of the latest versions of subjects
+    /// from `cache` where possible.
+    pub fn with_cache(mut self, cache: Arc<ClientCache>) -> Client {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn registry_key(&self) -> RegistryKey {
+        RegistryKey {
+            url: self.url.clone(),
+            username: self.auth.as_ref().map(|auth| auth.username.clone()),
+        }
     }
 
     fn make_request<P>(&self, method: Method, path: P) -> reqwest::RequestBuilder
@@ -58,6 +81,26 @@ impl Client {
 
     /// Gets the schema with the associated ID.
     pub async fn get_schema_by_id(&self, id: i32) -> Result<Schema, GetByIdError> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.fetch_schema_by_id(id).await,
+        };
+        let registry = self.registry_key();
+        match cache.get_schema(&registry, id) {
+            Some(Some(schema)) => return Ok(schema),
+            Some(None) => return Err(GetByIdError::SchemaNotFound),
+            None => (),
+        }
+        let res = self.fetch_schema_by_id(id).await;
+        match &res {
+            Ok(schema) => cache.insert_schema(&registry, id, Some(schema)),
+            Err(GetByIdError::SchemaNotFound) => cache.insert_schema(&registry, id, None),
+            Err(_) => (),
+        }
+        res
+    }
+
+    async fn fetch_schema_by_id(&self, id: i32) -> Result<Schema, GetByIdError> {
         let req = self.make_request(Method::GET, &["schemas", "ids", &id.to_string()]);
         let res: GetByIdResponse = send_request(req).await?;
         Ok(Schema {
@@ -73,6 +116,28 @@ impl Client {
 
     /// Gets the latest version of the specified subject.
     pub async fn get_subject(&self, subject: &str) -> Result<Subject, GetBySubjectError> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.fetch_subject(subject).await,
+        };
+        let registry = self.registry_key();
+        match cache.get_subject(&registry, subject) {
+            Some(Some(latest)) => return Ok(latest),
+            Some(None) => return Err(GetBySubjectError::SubjectNotFound),
+            None => (),
+        }
+        let res = self.fetch_subject(subject).await;
+        match &res {
+            Ok(latest) => cache.insert_subject(&registry, subject, Some(latest)),
+            Err(GetBySubjectError::SubjectNotFound) => {
+                cache.insert_subject(&registry, subject, None)
+            }
+            Err(_) => (),
+        }
+        res
+    }
+
+    async fn fetch_subject(&self, subject: &str) -> Result<Subject, GetBySubjectError> {
         let req = self.make_request(Method::GET, &["subjects", subject, "versions", "latest"]);
         let res: GetBySubjectResponse = send_request(req).await?;
         Ok(Subject {
@@ -157,6 +222,23 @@ impl Client {
         Ok(res.id)
     }
 
+    /// Sets the compatibility level of the specified subject, which determines
+    /// which schemas may be published under it.
+    ///
+    /// The subject need not yet have any schemas.
+    pub async fn set_subject_compatibility_level(
+        &self,
+        subject: &str,
+        compatibility_level: CompatibilityLevel,
+    ) -> Result<(), SetCompatibilityLevelError> {
+        let req = self.make_request(Method::PUT, &["config", subject]);
+        let req = req.json(&CompatibilityLevelRequest {
+            compatibility: compatibility_level,
+        });
+        let _res: CompatibilityLevelRequest = send_request(req).await?;
+        Ok(())
+    }
+
     /// Lists the names of all subjects that the schema registry is aware of.
     pub async fn list_subjects(&self) -> Result<Vec<String>, ListError> {
         let req = self.make_request(Method::GET, &["subjects"]);
@@ -253,8 +335,68 @@ impl SchemaType {
     }
 }
 
+/// The compatibility level of a subject, which determines the schemas that
+/// may be published under it with respect to those already published.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompatibilityLevel {
+    /// Consumers of the new schema can read data of the latest schema.
+    Backward,
+    /// Consumers of the new schema can read data of all earlier schemas.
+    BackwardTransitive,
+    /// Consumers of the latest schema can read data of the new schema.
+    Forward,
+    /// Consumers of all earlier schemas can read data of the new schema.
+    ForwardTransitive,
+    /// Both `Backward` and `Forward`.
+    Full,
+    /// Both `BackwardTransitive` and `ForwardTransitive`.
+    FullTransitive,
+    /// Any schema may be published.
+    None,
+}
+
+impl CompatibilityLevel {
+    /// Returns the name of the compatibility level, as the schema registry
+    /// spells it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompatibilityLevel::Backward => "BACKWARD",
+            CompatibilityLevel::BackwardTransitive => "BACKWARD_TRANSITIVE",
+            CompatibilityLevel::Forward => "FORWARD",
+            CompatibilityLevel::ForwardTransitive => "FORWARD_TRANSITIVE",
+            CompatibilityLevel::Full => "FULL",
+            CompatibilityLevel::FullTransitive => "FULL_TRANSITIVE",
+            CompatibilityLevel::None => "NONE",
+        }
+    }
+}
+
+impl FromStr for CompatibilityLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "BACKWARD" => Ok(CompatibilityLevel::Backward),
+            "BACKWARD_TRANSITIVE" => Ok(CompatibilityLevel::BackwardTransitive),
+            "FORWARD" => Ok(CompatibilityLevel::Forward),
+            "FORWARD_TRANSITIVE" => Ok(CompatibilityLevel::ForwardTransitive),
+            "FULL" => Ok(CompatibilityLevel::Full),
+            "FULL_TRANSITIVE" => Ok(CompatibilityLevel::FullTransitive),
+            "NONE" => Ok(CompatibilityLevel::None),
+            _ => bail!("unknown compatibility level: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for CompatibilityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// A schema stored by a schema registry.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Schema {
     /// The ID of the schema.
     pub id: i32,
@@ -263,7 +405,7 @@ pub struct Schema {
 }
 
 /// A subject stored by a schema registry.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Subject {
     /// The version of the schema.
     pub version: i32,
@@ -473,6 +615,58 @@ impl fmt::Display for PublishError {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct CompatibilityLevelRequest {
+    compatibility: CompatibilityLevel,
+}
+
+/// Errors for compatibility level operations.
+#[derive(Debug)]
+pub enum SetCompatibilityLevelError {
+    /// The schema registry rejected the compatibility level.
+    InvalidCompatibilityLevel { message: String },
+    /// The underlying HTTP transport failed.
+    Transport(reqwest::Error),
+    /// An internal server error occurred.
+    Server { code: i32, message: String },
+}
+
+impl From<UnhandledError> for SetCompatibilityLevelError {
+    fn from(err: UnhandledError) -> SetCompatibilityLevelError {
+        match err {
+            UnhandledError::Transport(err) => SetCompatibilityLevelError::Transport(err),
+            UnhandledError::Api { code, message } => match code {
+                42203 => SetCompatibilityLevelError::InvalidCompatibilityLevel { message },
+                _ => SetCompatibilityLevelError::Server { code, message },
+            },
+        }
+    }
+}
+
+impl Error for SetCompatibilityLevelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SetCompatibilityLevelError::InvalidCompatibilityLevel { .. }
+            | SetCompatibilityLevelError::Server { .. } => None,
+            SetCompatibilityLevelError::Transport(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for SetCompatibilityLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetCompatibilityLevelError::InvalidCompatibilityLevel { message } => {
+                write!(f, "{}", message)
+            }
+            SetCompatibilityLevelError::Transport(err) => write!(f, "transport: {}", err),
+            SetCompatibilityLevelError::Server { code, message } => {
+                write!(f, "server error {}: {}", code, message)
+            }
+        }
+    }
+}
+
 /// Errors for list operations.
 #[derive(Debug)]
 pub enum ListError {
@@ -37,10 +37,12 @@
 //! [API specification]: https://docs.confluent.io/current/schema-registry/develop/api.html
 //! [Confluent Schema Registry]: https://docs.confluent.io/current/schema-registry/index.html
 
+mod cache;
 mod client;
 mod config;
 
 pub mod tls;
 
+pub use cache::{CacheStats, CacheTtls, ClientCache};
 pub use client::*;
 pub use config::ClientConfig;
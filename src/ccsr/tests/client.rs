@@ -8,6 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 use hyper::server::conn::AddrIncoming;
 use hyper::service;
@@ -17,7 +20,10 @@ use hyper::{Body, Response};
 use mz_ccsr::SchemaReference;
 use once_cell::sync::Lazy;
 
-use mz_ccsr::{Client, DeleteError, GetByIdError, GetBySubjectError, PublishError, SchemaType};
+use mz_ccsr::{
+    CacheStats, CacheTtls, Client, ClientCache, CompatibilityLevel, DeleteError, GetByIdError,
+    GetBySubjectError, PublishError, SchemaType,
+};
 
 pub static SCHEMA_REGISTRY_URL: Lazy<reqwest::Url> =
     Lazy::new(|| match env::var("SCHEMA_REGISTRY_URL") {
@@ -131,6 +137,64 @@ async fn test_client() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_client_compatibility_level() -> Result<(), anyhow::Error> {
+    let client = mz_ccsr::ClientConfig::new(SCHEMA_REGISTRY_URL.clone()).build()?;
+
+    let existing_subjects = client.list_subjects().await?;
+    for s in existing_subjects {
+        if s.starts_with("ccsr-test-compat-") {
+            client.delete_subject(&s).await?;
+        }
+    }
+
+    let schema_v1 = r#"{ "type": "record", "name": "na", "fields": [
+        { "name": "a", "type": "long" }
+    ]}"#;
+
+    let schema_v2_incompat = r#"{ "type": "record", "name": "na", "fields": [
+        { "name": "a", "type": "string" }
+    ]}"#;
+
+    // The compatibility level can be set before the subject has any schemas.
+    client
+        .set_subject_compatibility_level("ccsr-test-compat-schema", CompatibilityLevel::None)
+        .await?;
+    client
+        .publish_schema("ccsr-test-compat-schema", schema_v1, SchemaType::Avro, &[])
+        .await?;
+    client
+        .publish_schema(
+            "ccsr-test-compat-schema",
+            schema_v2_incompat,
+            SchemaType::Avro,
+            &[],
+        )
+        .await?;
+
+    let schema_v3_incompat = r#"{ "type": "record", "name": "na", "fields": [
+        { "name": "a", "type": "boolean" }
+    ]}"#;
+
+    client
+        .set_subject_compatibility_level("ccsr-test-compat-schema", CompatibilityLevel::Full)
+        .await?;
+    match client
+        .publish_schema(
+            "ccsr-test-compat-schema",
+            schema_v3_incompat,
+            SchemaType::Avro,
+            &[],
+        )
+        .await
+    {
+        Err(PublishError::IncompatibleSchema) => (),
+        res => panic!("expected IncompatibleSchema error, got {:?}", res),
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_client_subject_and_references() -> Result<(), anyhow::Error> {
     let client = mz_ccsr::ClientConfig::new(SCHEMA_REGISTRY_URL.clone()).build()?;
@@ -371,18 +435,86 @@ async fn test_server_errors() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_client_cache() -> Result<(), anyhow::Error> {
+    let ttls = CacheTtls {
+        schema: Duration::from_secs(3600),
+        subject: Duration::from_secs(3600),
+        negative: Duration::from_secs(3600),
+    };
+
+    // Successful lookups are answered from the cache after the first one.
+    let cache = Arc::new(ClientCache::new(ttls));
+    let (client, requests) = start_counting_server(StatusCode::OK, r#"{ "schema": "\"int\"" }"#);
+    let client = client?.with_cache(Arc::clone(&cache));
+    for _ in 0..3 {
+        let schema = client.get_schema_by_id(1).await?;
+        assert_eq!(schema.raw, r#""int""#);
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 1 });
+
+    // So are lookups of schemas that do not exist.
+    let cache = Arc::new(ClientCache::new(ttls));
+    let (client, requests) = start_counting_server(
+        StatusCode::NOT_FOUND,
+        r#"{ "error_code": 40403, "message": "schema not found" }"#,
+    );
+    let client = client?.with_cache(Arc::clone(&cache));
+    for _ in 0..3 {
+        match client.get_schema_by_id(1).await {
+            Err(GetByIdError::SchemaNotFound) => (),
+            res => panic!("expected GetByIdError::SchemaNotFound, got {:?}", res),
+        }
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // But server errors are not cached.
+    let cache = Arc::new(ClientCache::new(ttls));
+    let (client, requests) = start_counting_server(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        r#"{ "error_code": 50001, "message": "overloaded; try again later" }"#,
+    );
+    let client = client?.with_cache(Arc::clone(&cache));
+    for _ in 0..3 {
+        match client.get_schema_by_subject("foo").await {
+            Err(GetBySubjectError::Server { code: 50001, .. }) => (),
+            res => panic!("expected GetBySubjectError::Server, got {:?}", res),
+        }
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
 fn start_server(status_code: StatusCode, body: &'static str) -> Result<Client, anyhow::Error> {
+    start_counting_server(status_code, body).0
+}
+
+/// Like `start_server`, but also returns the number of requests that the
+/// server has received.
+fn start_counting_server(
+    status_code: StatusCode,
+    body: &'static str,
+) -> (Result<Client, anyhow::Error>, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
     let addr = {
         let incoming = AddrIncoming::bind(&([127, 0, 0, 1], 0).into()).unwrap();
         let addr = incoming.local_addr();
-        let server =
-            Server::builder(incoming).serve(service::make_service_fn(move |_conn| async move {
-                Ok::<_, hyper::Error>(service::service_fn(move |_req| async move {
-                    Response::builder()
-                        .status(status_code)
-                        .body(Body::from(body))
+        let server_requests = Arc::clone(&requests);
+        let server = Server::builder(incoming).serve(service::make_service_fn(move |_conn| {
+            let requests = Arc::clone(&server_requests);
+            async move {
+                Ok::<_, hyper::Error>(service::service_fn(move |_req| {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        Response::builder()
+                            .status(status_code)
+                            .body(Body::from(body))
+                    }
                 }))
-            }));
+            }
+        }));
         mz_ore::task::spawn(|| "start_server", async {
             match server.await {
                 Ok(()) => (),
@@ -393,7 +525,7 @@ fn start_server(status_code: StatusCode, body: &'static str) -> Result<Client, a
     };
 
     let url: reqwest::Url = format!("http://{}", addr).parse().unwrap();
-    mz_ccsr::ClientConfig::new(url).build()
+    (mz_ccsr::ClientConfig::new(url).build(), requests)
 }
 
 fn assert_raw_schemas_eq(schema1: &str, schema2: &str) {
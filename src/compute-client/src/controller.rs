@@ -51,12 +51,12 @@ use mz_build_info::BuildInfo;
 use mz_expr::RowSetFinishing;
 use mz_orchestrator::{CpuLimit, MemoryLimit, NamespacedOrchestrator};
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_repr::{GlobalId, Row};
+use mz_repr::{Diff, GlobalId, Row};
 use mz_storage::controller::{ReadPolicy, StorageController, StorageError};
 
 use crate::command::{CommunicationConfig, DataflowDescription, ProcessId, ReplicaId};
 use crate::logging::{LogVariant, LogView};
-use crate::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use crate::response::{ComputeResponse, PeekResponse, ReplicaHeartbeat, SubscribeResponse};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 
 use self::instance::{ActiveInstance, Instance};
@@ -136,6 +136,26 @@ pub enum ComputeControllerResponse<T> {
     /// A notification that we heard a response from the given replica at the
     /// given time.
     ReplicaHeartbeat(ReplicaId, DateTime<Utc>),
+    /// See [`ComputeResponse::Heartbeat`](crate::response::ComputeResponse::Heartbeat),
+    /// along with the time at which the heartbeat was received.
+    ReplicaLiveness(ReplicaId, DateTime<Utc>, ReplicaHeartbeat),
+    /// A change in the set of dataflow exports that are waiting for a replica
+    /// to acknowledge them.
+    PendingAck(PendingAck, Diff),
+}
+
+/// A dataflow export that a replica has not yet acknowledged.
+///
+/// A replica acknowledges an export by reporting a write frontier beyond the
+/// `as_of` of its dataflow, i.e., once it has hydrated the export.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAck {
+    /// The ID of the index or sink.
+    pub collection_id: GlobalId,
+    /// The replica the acknowledgment is awaited from.
+    pub replica_id: ReplicaId,
+    /// The time at which the dataflow was created.
+    pub issued_at: DateTime<Utc>,
 }
 
 /// Errors arising from compute commands.
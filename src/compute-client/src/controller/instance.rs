@@ -11,6 +11,7 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
+use chrono::{DateTime, Utc};
 use differential_dataflow::lattice::Lattice;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt};
@@ -21,7 +22,7 @@ use uuid::Uuid;
 use mz_build_info::BuildInfo;
 use mz_expr::RowSetFinishing;
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_repr::{GlobalId, Row};
+use mz_repr::{Diff, GlobalId, Row};
 use mz_storage::controller::{ReadPolicy, StorageController};
 
 use crate::command::{
@@ -34,7 +35,9 @@ use crate::service::{ComputeClient, ComputeGrpcClient};
 use crate::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection};
 
 use super::replica::Replica;
-use super::{CollectionState, ComputeControllerResponse, ComputeError, ComputeReplicaLogging};
+use super::{
+    CollectionState, ComputeControllerResponse, ComputeError, ComputeReplicaLogging, PendingAck,
+};
 
 /// The state we keep for a compute instance.
 #[derive(Debug)]
@@ -61,6 +64,8 @@ pub(super) struct Instance<T> {
     ///
     /// Persisted log collections are special in that they are replica-specific.
     sink_log_uppers: HashMap<GlobalId, Antichain<T>>,
+    /// Dataflow exports that are waiting to be acknowledged by replicas.
+    pending_acks: BTreeMap<GlobalId, PendingAcks<T>>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<T>,
     /// IDs of replicas that have failed and require rehydration.
@@ -136,6 +141,7 @@ where
             uppers: Default::default(),
             index_log_uppers: Default::default(),
             sink_log_uppers: Default::default(),
+            pending_acks: Default::default(),
             history: Default::default(),
             failed_replicas: Default::default(),
             ready_responses: Default::default(),
@@ -227,6 +233,25 @@ where
         for peek in self.peeks.values_mut() {
             peek.unfinished.insert(id);
         }
+        for (collection_id, acks) in self.pending_acks.iter_mut() {
+            acks.replicas.insert(id);
+            self.ready_responses
+                .push_back(acks.response(*collection_id, id, 1));
+        }
+    }
+
+    /// Starts waiting for all replicas to acknowledge the given dataflow export.
+    fn start_ack_tracking(&mut self, id: GlobalId, as_of: Antichain<T>) {
+        let acks = PendingAcks {
+            as_of,
+            issued_at: Utc::now(),
+            replicas: self.replicas.keys().copied().collect(),
+        };
+        for replica_id in acks.replicas.iter() {
+            self.ready_responses
+                .push_back(acks.response(id, *replica_id, 1));
+        }
+        self.pending_acks.insert(id, acks);
     }
 
     /// Sends a command to all replicas of this instance.
@@ -297,6 +322,14 @@ where
     fn cease_frontier_tracking(&mut self, id: GlobalId) {
         let previous = self.uppers.remove(&id).expect("untracked frontier");
 
+        // A dropped collection is no longer waiting on any replica.
+        if let Some(acks) = self.pending_acks.remove(&id) {
+            for replica_id in acks.replicas.iter() {
+                self.ready_responses
+                    .push_back(acks.response(id, *replica_id, -1));
+            }
+        }
+
         // If we cease tracking an in-progress subscribe, we should emit a `DroppedAt` response.
         if self.subscribes.remove(&id) {
             self.ready_responses
@@ -399,6 +432,15 @@ where
             .remove(&id)
             .expect("replica not found");
 
+        // The removed replica no longer owes any acknowledgments.
+        for (collection_id, acks) in self.compute.pending_acks.iter_mut() {
+            if acks.replicas.remove(&id) {
+                self.compute
+                    .ready_responses
+                    .push_back(acks.response(*collection_id, id, -1));
+            }
+        }
+
         // Cease tracking frontiers of persisted log collections.
         if let Some(logging) = replica.logging_config {
             for (collection_id, _) in logging.sink_logs.values() {
@@ -527,6 +569,19 @@ where
 
             // Initialize tracking of subscribes.
             self.compute.subscribes.extend(dataflow.subscribe_ids());
+
+            // Wait for replicas to acknowledge the exports. Subscribes are not
+            // DDL and are not tracked.
+            let subscribe_ids: BTreeSet<_> = dataflow.subscribe_ids().collect();
+            for id in dataflow
+                .index_exports
+                .keys()
+                .chain(dataflow.sink_exports.keys())
+            {
+                if !subscribe_ids.contains(id) {
+                    self.compute.start_ack_tracking(*id, as_of.clone());
+                }
+            }
         }
 
         // Here we augment all imported sources and all exported sinks with with the appropriate
@@ -556,6 +611,7 @@ where
                         let conn = PersistSinkConnection {
                             value_desc: conn.value_desc,
                             storage_metadata: metadata,
+                            batching: conn.batching,
                         };
                         ComputeSinkConnection::Persist(conn)
                     }
@@ -919,6 +975,9 @@ where
                 self.handle_subscribe_response(id, response, replica_id)
                     .await
             }
+            ComputeResponse::Heartbeat(heartbeat) => Ok(Some(
+                ComputeControllerResponse::ReplicaLiveness(replica_id, Utc::now(), heartbeat),
+            )),
         }
     }
 
@@ -930,6 +989,20 @@ where
         let mut new_uppers = Vec::new();
 
         for (id, new_upper) in list {
+            // Reporting a frontier beyond the `as_of` acknowledges an export.
+            if let Some(acks) = self.compute.pending_acks.get_mut(&id) {
+                if !PartialOrder::less_equal(&new_upper, &acks.as_of)
+                    && acks.replicas.remove(&replica_id)
+                {
+                    self.compute
+                        .ready_responses
+                        .push_back(acks.response(id, replica_id, -1));
+                    if acks.replicas.is_empty() {
+                        self.compute.pending_acks.remove(&id);
+                    }
+                }
+            }
+
             if let Some(reported) = self.compute.uppers.get_mut(&id) {
                 if reported.update(replica_id, new_upper) {
                     new_uppers.push((id, reported.bounds.clone()));
@@ -1096,6 +1169,33 @@ impl<T> PendingPeek<T> {
     }
 }
 
+/// Replicas that have yet to acknowledge a single dataflow export.
+#[derive(Debug)]
+struct PendingAcks<T> {
+    /// The `as_of` of the export's dataflow.
+    as_of: Antichain<T>,
+    /// The time at which the dataflow was created.
+    issued_at: DateTime<Utc>,
+    /// The replicas the acknowledgment is still awaited from.
+    replicas: BTreeSet<ReplicaId>,
+}
+
+impl<T> PendingAcks<T> {
+    fn response(
+        &self,
+        collection_id: GlobalId,
+        replica_id: ReplicaId,
+        diff: Diff,
+    ) -> ComputeControllerResponse<T> {
+        let ack = PendingAck {
+            collection_id,
+            replica_id,
+            issued_at: self.issued_at,
+        };
+        ComputeControllerResponse::PendingAck(ack, diff)
+    }
+}
+
 /// Reported upper frontiers for a single compute collection.
 ///
 /// The type maintains the following invariants:
@@ -146,6 +146,7 @@ pub enum TimelyLog {
     MessagesSent,
     MessagesReceived,
     Reachability,
+    NetworkBytes,
 }
 
 impl RustType<ProtoTimelyLog> for TimelyLog {
@@ -162,6 +163,7 @@ impl RustType<ProtoTimelyLog> for TimelyLog {
                 TimelyLog::MessagesSent => MessagesSent(()),
                 TimelyLog::MessagesReceived => MessagesReceived(()),
                 TimelyLog::Reachability => Reachability(()),
+                TimelyLog::NetworkBytes => NetworkBytes(()),
             }),
         }
     }
@@ -178,6 +180,7 @@ impl RustType<ProtoTimelyLog> for TimelyLog {
             Some(MessagesSent(())) => Ok(TimelyLog::MessagesSent),
             Some(MessagesReceived(())) => Ok(TimelyLog::MessagesReceived),
             Some(Reachability(())) => Ok(TimelyLog::Reachability),
+            Some(NetworkBytes(())) => Ok(TimelyLog::NetworkBytes),
             None => Err(TryFromProtoError::missing_field("ProtoTimelyLog::kind")),
         }
     }
@@ -222,6 +225,7 @@ pub enum ComputeLog {
     FrontierCurrent,
     PeekCurrent,
     PeekDuration,
+    PeekLast,
     FrontierDelay,
     SourceFrontierCurrent,
 }
@@ -236,6 +240,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::FrontierCurrent => FrontierCurrent(()),
                 ComputeLog::PeekCurrent => PeekCurrent(()),
                 ComputeLog::PeekDuration => PeekDuration(()),
+                ComputeLog::PeekLast => PeekLast(()),
                 ComputeLog::FrontierDelay => FrontierDelay(()),
                 ComputeLog::SourceFrontierCurrent => SourceFrontierCurrent(()),
             }),
@@ -250,6 +255,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(FrontierCurrent(())) => Ok(ComputeLog::FrontierCurrent),
             Some(PeekCurrent(())) => Ok(ComputeLog::PeekCurrent),
             Some(PeekDuration(())) => Ok(ComputeLog::PeekDuration),
+            Some(PeekLast(())) => Ok(ComputeLog::PeekLast),
             Some(FrontierDelay(())) => Ok(ComputeLog::FrontierDelay),
             Some(SourceFrontierCurrent(())) => Ok(ComputeLog::SourceFrontierCurrent),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
@@ -268,6 +274,7 @@ pub static DEFAULT_LOG_VARIANTS: Lazy<Vec<LogVariant>> = Lazy::new(|| {
         LogVariant::Timely(TimelyLog::MessagesSent),
         LogVariant::Timely(TimelyLog::MessagesReceived),
         LogVariant::Timely(TimelyLog::Reachability),
+        LogVariant::Timely(TimelyLog::NetworkBytes),
         LogVariant::Differential(DifferentialLog::ArrangementBatches),
         LogVariant::Differential(DifferentialLog::ArrangementRecords),
         LogVariant::Differential(DifferentialLog::Sharing),
@@ -278,6 +285,7 @@ pub static DEFAULT_LOG_VARIANTS: Lazy<Vec<LogVariant>> = Lazy::new(|| {
         LogVariant::Compute(ComputeLog::FrontierDelay),
         LogVariant::Compute(ComputeLog::PeekCurrent),
         LogVariant::Compute(ComputeLog::PeekDuration),
+        LogVariant::Compute(ComputeLog::PeekLast),
     ];
 
     default_logs
@@ -677,6 +685,13 @@ impl LogVariant {
                 .with_column("update_type", ScalarType::String.nullable(false))
                 .with_column("timestamp", ScalarType::UInt64.nullable(true)),
 
+            LogVariant::Timely(TimelyLog::NetworkBytes) => RelationDesc::empty()
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("remote_process_id", ScalarType::UInt64.nullable(false))
+                .with_column("sent_bytes", ScalarType::UInt64.nullable(false))
+                .with_column("received_bytes", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1]),
+
             LogVariant::Differential(DifferentialLog::ArrangementBatches)
             | LogVariant::Differential(DifferentialLog::ArrangementRecords)
             | LogVariant::Differential(DifferentialLog::Sharing) => RelationDesc::empty()
@@ -724,6 +739,12 @@ impl LogVariant {
                 .with_column("duration_ns", ScalarType::UInt64.nullable(false))
                 .with_column("count", ScalarType::UInt64.nullable(false))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Compute(ComputeLog::PeekLast) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("time", ScalarType::TimestampTz.nullable(false))
+                .with_key(vec![0, 1]),
         }
     }
 
@@ -760,6 +781,7 @@ impl LogVariant {
                 ),
             ],
             LogVariant::Timely(TimelyLog::Reachability) => vec![],
+            LogVariant::Timely(TimelyLog::NetworkBytes) => vec![],
             LogVariant::Differential(DifferentialLog::ArrangementBatches)
             | LogVariant::Differential(DifferentialLog::ArrangementRecords)
             | LogVariant::Differential(DifferentialLog::Sharing) => vec![(
@@ -773,6 +795,7 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::FrontierDelay) => vec![],
             LogVariant::Compute(ComputeLog::PeekCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekDuration) => vec![],
+            LogVariant::Compute(ComputeLog::PeekLast) => vec![],
         }
     }
 }
@@ -14,6 +14,7 @@
 //! Compute layer responses.
 
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use proptest::prelude::{any, Arbitrary, Just};
 use proptest::prop_oneof;
@@ -23,7 +24,7 @@ use timely::progress::frontier::Antichain;
 use uuid::Uuid;
 
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_proto::{any_uuid, IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_proto::{any_duration, any_uuid, IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Diff, GlobalId, Row};
 use mz_timely_util::progress::any_antichain;
 
@@ -41,6 +42,8 @@ pub enum ComputeResponse<T = mz_repr::Timestamp> {
     PeekResponse(Uuid, PeekResponse, OpenTelemetryContext),
     /// The worker's next response to a specified subscribe.
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+    /// A periodic report that the replica is alive, and what it is running.
+    Heartbeat(ReplicaHeartbeat),
 }
 
 impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
@@ -63,6 +66,7 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                         resp: Some(resp.into_proto()),
                     })
                 }
+                ComputeResponse::Heartbeat(heartbeat) => Heartbeat(heartbeat.into_proto()),
             }),
         }
     }
@@ -84,6 +88,7 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                 resp.resp
                     .into_rust_if_some("ProtoSubscribeResponseKind::resp")?,
             )),
+            Some(Heartbeat(heartbeat)) => Ok(ComputeResponse::Heartbeat(heartbeat.into_rust()?)),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeResponse::kind",
             )),
@@ -104,11 +109,56 @@ impl Arbitrary for ComputeResponse<mz_repr::Timestamp> {
             }),
             (any::<GlobalId>(), any::<SubscribeResponse>())
                 .prop_map(|(id, resp)| ComputeResponse::SubscribeResponse(id, resp)),
+            any::<ReplicaHeartbeat>().prop_map(ComputeResponse::Heartbeat),
         ]
         .boxed()
     }
 }
 
+/// A report of the build and process backing a replica.
+///
+/// Every replica sends one of these periodically, which lets the controller
+/// tell live replicas apart from unresponsive ones.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaHeartbeat {
+    /// The version of the build the replica is running.
+    pub build_version: String,
+    /// The time elapsed since the replica process started.
+    pub uptime: Duration,
+}
+
+impl RustType<ProtoReplicaHeartbeat> for ReplicaHeartbeat {
+    fn into_proto(&self) -> ProtoReplicaHeartbeat {
+        ProtoReplicaHeartbeat {
+            build_version: self.build_version.clone(),
+            uptime: Some(self.uptime.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoReplicaHeartbeat) -> Result<Self, TryFromProtoError> {
+        Ok(ReplicaHeartbeat {
+            build_version: proto.build_version,
+            uptime: proto
+                .uptime
+                .into_rust_if_some("ProtoReplicaHeartbeat::uptime")?,
+        })
+    }
+}
+
+impl Arbitrary for ReplicaHeartbeat {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any_duration())
+            .prop_map(|(build_version, uptime)| ReplicaHeartbeat {
+                build_version,
+                uptime,
+            })
+            .boxed()
+    }
+}
+
 /// The response from a `Peek`.
 ///
 /// Note that each `Peek` expects to generate exactly one `PeekResponse`, i.e.
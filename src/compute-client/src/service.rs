@@ -331,6 +331,15 @@ where
                     }
                 }
             }
+            ComputeResponse::Heartbeat(heartbeat) => {
+                // Heartbeats describe the replica as a whole, and are only
+                // sent by its first worker.
+                if shard_id == 0 {
+                    Some(Ok(ComputeResponse::Heartbeat(heartbeat)))
+                } else {
+                    None
+                }
+            }
         }
     }
 }
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -129,6 +131,7 @@ pub struct SubscribeSinkConnection {}
 pub struct PersistSinkConnection<S> {
     pub value_desc: RelationDesc,
     pub storage_metadata: S,
+    pub batching: PersistSinkBatching,
 }
 
 impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMetadata> {
@@ -136,6 +139,7 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
         ProtoPersistSinkConnection {
             value_desc: Some(self.value_desc.into_proto()),
             storage_metadata: Some(self.storage_metadata.into_proto()),
+            batching: Some(self.batching.into_proto()),
         }
     }
 
@@ -147,6 +151,49 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
             storage_metadata: proto
                 .storage_metadata
                 .into_rust_if_some("ProtoPersistSinkConnection::storage_metadata")?,
+            batching: proto
+                .batching
+                .into_rust_if_some("ProtoPersistSinkConnection::batching")?,
+        })
+    }
+}
+
+/// Controls how often a persist sink writes the updates it has accumulated.
+///
+/// The default writes a batch whenever the frontier of the sink's input
+/// advances, which keeps the output as fresh as possible but can produce many
+/// small batches for collections that change frequently.
+#[derive(Arbitrary, Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PersistSinkBatching {
+    /// The estimated size of the accumulated updates, in bytes, at which the
+    /// sink writes a batch without waiting for `max_staleness` to elapse.
+    pub target_bytes: Option<u64>,
+    /// The longest the sink holds back updates before writing them, even if
+    /// fewer than `target_bytes` have accumulated.
+    pub max_staleness: Duration,
+}
+
+impl PersistSinkBatching {
+    /// Reports whether the sink should hold back updates at all.
+    pub fn is_enabled(&self) -> bool {
+        self.max_staleness > Duration::ZERO
+    }
+}
+
+impl RustType<ProtoPersistSinkBatching> for PersistSinkBatching {
+    fn into_proto(&self) -> ProtoPersistSinkBatching {
+        ProtoPersistSinkBatching {
+            target_bytes: self.target_bytes,
+            max_staleness: Some(self.max_staleness.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPersistSinkBatching) -> Result<Self, TryFromProtoError> {
+        Ok(PersistSinkBatching {
+            target_bytes: proto.target_bytes,
+            max_staleness: proto
+                .max_staleness
+                .into_rust_if_some("ProtoPersistSinkBatching::max_staleness")?,
         })
     }
 }
@@ -17,33 +17,127 @@
 use std::any::Any;
 use std::io::{self, Read, Write};
 use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use timely::communication::allocator::zero_copy::initialize::initialize_networking_from_sockets;
 use timely::communication::allocator::GenericBuilder;
+use timely::communication::logging::{CommunicationEvent, CommunicationSetup, MessageEvent};
+use timely::logging_core::Logger;
 use tracing::{info, trace, warn};
 
+use mz_ore::cast::CastFrom;
+
 use mz_compute_client::command::CommunicationConfig;
 
+/// Counts the bytes that each worker of this process has exchanged with each
+/// remote process over the cluster interconnect.
+///
+/// The counters are maintained by the network threads of the communication
+/// layer, which report the messages they send and receive in batches, so the
+/// counters can lag behind the actual traffic by a few messages.
+#[derive(Debug)]
+pub struct NetworkCounters {
+    processes: usize,
+    /// Bytes sent, indexed by `worker * processes + remote_process`.
+    sent: Vec<AtomicU64>,
+    /// Bytes received, indexed by `worker * processes + remote_process`.
+    received: Vec<AtomicU64>,
+}
+
+impl NetworkCounters {
+    fn new(peers: usize, processes: usize) -> Self {
+        let counters = || (0..peers * processes).map(|_| AtomicU64::new(0)).collect();
+        NetworkCounters {
+            processes,
+            sent: counters(),
+            received: counters(),
+        }
+    }
+
+    /// Records a message exchanged with the process `remote`.
+    fn record(&self, remote: usize, event: &MessageEvent) {
+        let header = &event.header;
+        let bytes = u64::cast_from(header.required_bytes());
+        let (counters, worker) = if event.is_send {
+            (&self.sent, header.source)
+        } else {
+            (&self.received, header.target)
+        };
+        if let Some(counter) = counters.get(worker * self.processes + remote) {
+            counter.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the number of bytes that `worker` has sent to and received
+    /// from each remote process, as `(remote_process, sent, received)`.
+    pub fn sample(&self, worker: usize) -> Vec<(usize, u64, u64)> {
+        let offset = worker * self.processes;
+        (0..self.processes)
+            .filter_map(|remote| {
+                let sent = self.sent.get(offset + remote)?.load(Ordering::Relaxed);
+                let received = self.received.get(offset + remote)?.load(Ordering::Relaxed);
+                Some((remote, sent, received))
+            })
+            .collect()
+    }
+}
+
 /// Creates communication mesh from cluster config
+///
+/// Also returns counters of the bytes that the workers of this process
+/// exchange with remote processes.
 pub fn initialize_networking(
     config: &CommunicationConfig,
-) -> Result<(Vec<GenericBuilder>, Box<dyn Any + Send>), String> {
+) -> Result<
+    (
+        Vec<GenericBuilder>,
+        Box<dyn Any + Send>,
+        Arc<NetworkCounters>,
+    ),
+    String,
+> {
     let CommunicationConfig {
         workers,
         process,
         addresses,
     } = config;
+    let counters = Arc::new(NetworkCounters::new(
+        workers * addresses.len(),
+        addresses.len(),
+    ));
+    let log_sender = {
+        let counters = Arc::clone(&counters);
+        Box::new(move |setup: CommunicationSetup| {
+            // Only the network threads, which talk to a remote process, report
+            // messages.
+            let remote = setup.remote?;
+            let counters = Arc::clone(&counters);
+            Some(Logger::new(
+                Instant::now(),
+                Duration::default(),
+                setup,
+                move |_time, events: &mut Vec<(Duration, CommunicationSetup, CommunicationEvent)>| {
+                    for (_time, _setup, event) in events.drain(..) {
+                        if let CommunicationEvent::Message(message) = event {
+                            counters.record(remote, &message);
+                        }
+                    }
+                },
+            ))
+        })
+    };
     let sockets_result = create_sockets(addresses.clone(), *process);
     match sockets_result.and_then(|sockets| {
-        initialize_networking_from_sockets(sockets, *process, *workers, Box::new(|_| None))
+        initialize_networking_from_sockets(sockets, *process, *workers, log_sender)
     }) {
         Ok((stuff, guard)) => Ok((
             stuff.into_iter().map(GenericBuilder::ZeroCopy).collect(),
             Box::new(guard),
+            counters,
         )),
         Err(err) => Err(format!("failed to initialize networking: {err}")),
     }
@@ -26,12 +26,15 @@ use timely::progress::reachability::logging::TrackerEvent;
 use timely::worker::Worker as TimelyWorker;
 use tokio::sync::{mpsc, Mutex};
 
+use mz_build_info::BuildInfo;
 use mz_compute_client::command::{
     ComputeCommand, ComputeCommandHistory, DataflowDescription, InstanceConfig, Peek, ReplicaId,
 };
 use mz_compute_client::logging::LoggingConfig;
 use mz_compute_client::plan::Plan;
-use mz_compute_client::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use mz_compute_client::response::{
+    ComputeResponse, PeekResponse, ReplicaHeartbeat, SubscribeResponse,
+};
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::cache::PersistClientCache;
@@ -43,8 +46,13 @@ use mz_timely_util::operator::CollectionExt;
 use tracing::{span, Level};
 
 use crate::arrangement::manager::{TraceBundle, TraceManager};
+use crate::communication::NetworkCounters;
 use crate::logging;
 use crate::logging::compute::ComputeEvent;
+use crate::logging::LoggingMetrics;
+
+/// The interval at which a replica reports a heartbeat to the controller.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Worker-local state that is maintained across dataflows.
 ///
@@ -75,10 +83,22 @@ pub struct ComputeState {
     /// A process-global cache of (blob_uri, consensus_uri) -> PersistClient.
     /// This is intentionally shared between workers.
     pub persist_clients: Arc<Mutex<PersistClientCache>>,
+    /// Counters of the bytes this process has exchanged with remote processes
+    /// over the cluster interconnect.
+    pub network_counters: Arc<NetworkCounters>,
     /// History of commands received by this workers and all its peers.
     pub command_history: ComputeCommandHistory,
     /// Max size in bytes of any result.
     pub max_result_size: u32,
+    /// Build information, reported in heartbeats.
+    pub build_info: &'static BuildInfo,
+    /// The time at which this process started serving, from which heartbeats
+    /// derive the process uptime.
+    pub process_start: Instant,
+    /// The time at which the last heartbeat was sent, if any.
+    pub last_heartbeat: Option<Instant>,
+    /// Metrics for the logging dataflows.
+    pub logging_metrics: LoggingMetrics,
 }
 
 /// A wrapper around [ComputeState] with a live timely worker and response channel.
@@ -291,7 +311,7 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
             panic!("dataflow server has already initialized logging");
         }
 
-        use crate::logging::BatchLogger;
+        use crate::logging::{BatchLogger, DroppedEvents, PendingEventLink};
         use timely::dataflow::operators::capture::event::link::EventLink;
 
         let interval = std::cmp::max(1, logging.interval_ns / 1_000_000)
@@ -307,14 +327,56 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
             .expect("Failed to get duration since Unix epoch");
 
         // Establish loggers first, so we can either log the logging or not, as we like.
-        let t_linked = std::rc::Rc::new(EventLink::new());
-        let mut t_logger = BatchLogger::new(Rc::clone(&t_linked), interval);
-        let r_linked = std::rc::Rc::new(EventLink::new());
-        let mut r_logger = BatchLogger::new(Rc::clone(&r_linked), interval);
-        let d_linked = std::rc::Rc::new(EventLink::new());
-        let mut d_logger = BatchLogger::new(Rc::clone(&d_linked), interval);
-        let c_linked = std::rc::Rc::new(EventLink::new());
-        let mut c_logger = BatchLogger::new(Rc::clone(&c_linked), interval);
+        // Each logger drops events once too many of them await replay by its logging
+        // dataflow, and counts the events it drops.
+        let worker_id = self.timely_worker.index();
+        let metrics = &self.compute_state.logging_metrics;
+        let t_link = std::rc::Rc::new(EventLink::new());
+        let (t_linked, t_pending) = PendingEventLink::new(Rc::clone(&t_link));
+        let mut t_logger = BatchLogger::new(
+            t_link,
+            interval,
+            t_pending,
+            DroppedEvents::new(
+                metrics.clone(),
+                "timely",
+                worker_id,
+                logging::timely::droppable_event_type,
+            ),
+        );
+        let r_link = std::rc::Rc::new(EventLink::new());
+        let (r_linked, r_pending) = PendingEventLink::new(Rc::clone(&r_link));
+        let mut r_logger = BatchLogger::new(
+            r_link,
+            interval,
+            r_pending,
+            DroppedEvents::new(metrics.clone(), "reachability", worker_id, |_| {
+                Some("update")
+            }),
+        );
+        let d_link = std::rc::Rc::new(EventLink::new());
+        let (d_linked, d_pending) = PendingEventLink::new(Rc::clone(&d_link));
+        let mut d_logger = BatchLogger::new(
+            d_link,
+            interval,
+            d_pending,
+            DroppedEvents::new(
+                metrics.clone(),
+                "differential",
+                worker_id,
+                logging::differential::droppable_event_type,
+            ),
+        );
+        // Compute events are never dropped, as the compute logging dataflow relies on
+        // all of them to retract what it has reported.
+        let c_link = std::rc::Rc::new(EventLink::new());
+        let (c_linked, c_pending) = PendingEventLink::new(Rc::clone(&c_link));
+        let mut c_logger = BatchLogger::new(
+            c_link,
+            interval,
+            c_pending,
+            DroppedEvents::new(metrics.clone(), "compute", worker_id, |_| None),
+        );
 
         let mut t_traces = HashMap::new();
         let mut r_traces = HashMap::new();
@@ -334,28 +396,28 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 self.timely_worker,
                 logging,
                 self.compute_state,
-                Rc::clone(&t_linked),
+                t_linked.clone(),
                 t_activator.clone(),
             ));
             r_traces.extend(logging::reachability::construct(
                 self.timely_worker,
                 logging,
                 self.compute_state,
-                Rc::clone(&r_linked),
+                r_linked.clone(),
                 r_activator.clone(),
             ));
             d_traces.extend(logging::differential::construct(
                 self.timely_worker,
                 logging,
                 self.compute_state,
-                Rc::clone(&d_linked),
+                d_linked.clone(),
                 d_activator.clone(),
             ));
             c_traces.extend(logging::compute::construct(
                 self.timely_worker,
                 logging,
                 self.compute_state,
-                Rc::clone(&c_linked),
+                c_linked.clone(),
                 c_activator.clone(),
             ));
         }
@@ -604,6 +666,29 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Send a heartbeat to the coordinator, if one is due.
+    ///
+    /// Heartbeats describe the replica as a whole, so only its first worker
+    /// sends them.
+    pub fn report_heartbeat(&mut self) {
+        if self.timely_worker.index() != 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        if let Some(last_heartbeat) = self.compute_state.last_heartbeat {
+            if now.duration_since(last_heartbeat) < HEARTBEAT_INTERVAL {
+                return;
+            }
+        }
+        self.compute_state.last_heartbeat = Some(now);
+
+        self.send_compute_response(ComputeResponse::Heartbeat(ReplicaHeartbeat {
+            build_version: self.compute_state.build_info.version.to_string(),
+            uptime: now.duration_since(self.compute_state.process_start),
+        }));
+    }
+
     /// Scan pending peeks and attempt to retire each.
     pub fn process_peeks(&mut self) {
         let mut upper = Antichain::new();
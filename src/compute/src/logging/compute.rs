@@ -14,11 +14,11 @@ use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
 use std::time::Duration;
 
+use chrono::{DateTime, NaiveDateTime, Utc};
 use differential_dataflow::collection::AsCollection;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use differential_dataflow::operators::count::CountTotal;
 use timely::communication::Allocate;
-use timely::dataflow::operators::capture::EventLink;
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use timely::logging::WorkerIdentifier;
 use tracing::error;
@@ -26,13 +26,14 @@ use uuid::Uuid;
 
 use mz_expr::{permutation_for_arrangement, MirScalarExpr};
 use mz_ore::cast::CastFrom;
+use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::{Datum, DatumVec, GlobalId, Row, Timestamp};
 use mz_timely_util::activator::RcActivator;
 use mz_timely_util::replay::MzReplay;
 
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
-use crate::logging::{ComputeLog, LogVariant};
+use crate::logging::{ComputeLog, LogVariant, PendingEventLink};
 use crate::typedefs::{KeysValsHandle, RowSpine};
 
 /// Type alias for logging of compute events.
@@ -92,7 +93,7 @@ pub fn construct<A: Allocate>(
     worker: &mut timely::worker::Worker<A>,
     config: &mz_compute_client::logging::LoggingConfig,
     compute_state: &mut ComputeState,
-    compute: std::rc::Rc<EventLink<Timestamp, (Duration, WorkerIdentifier, ComputeEvent)>>,
+    compute: PendingEventLink<Timestamp, (Duration, WorkerIdentifier, ComputeEvent)>,
     activator: RcActivator,
 ) -> HashMap<LogVariant, (KeysValsHandle, Rc<dyn Any>)> {
     let interval_ms = std::cmp::max(1, config.interval_ns / 1_000_000);
@@ -115,11 +116,15 @@ pub fn construct<A: Allocate>(
         let (mut frontier_delay_out, frontier_delay) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut peek_last_out, peek_last) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
         demux.build(move |_capability| {
             let mut active_dataflows = HashMap::new();
             let mut peek_stash = HashMap::new();
+            // The time, in milliseconds since the Unix epoch, of the latest
+            // peek targeting each export on each worker.
+            let mut peek_last_stash = HashMap::<(GlobalId, usize), u64>::new();
             let mut storage_sources = HashMap::<
                 (GlobalId, usize),
                 HashMap<GlobalId, (VecDeque<(mz_repr::Timestamp, u128)>, HashMap<u128, i32>)>,
@@ -132,6 +137,7 @@ pub fn construct<A: Allocate>(
                 let mut frontier_delay = frontier_delay_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut peek_last = peek_last_out.activate();
 
                 input.for_each(|time, data| {
                     data.swap(&mut demux_buffer);
@@ -143,6 +149,7 @@ pub fn construct<A: Allocate>(
                     let mut frontier_delay_session = frontier_delay.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
+                    let mut peek_last_session = peek_last.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
                         let time_ms = (((time.as_millis() / interval_ms) + 1) * interval_ms)
@@ -180,6 +187,9 @@ pub fn construct<A: Allocate>(
                                             key.0, worker
                                         ),
                                     }
+                                    if let Some(last) = peek_last_stash.remove(key) {
+                                        peek_last_session.give(((id, worker, last), time_ms, -1));
+                                    }
                                     // dataflow may or may not be associated to a storage
                                     // source instantiation. Report removal if so.
                                     if let Some(source_map) = storage_sources.remove(key) {
@@ -285,6 +295,22 @@ pub fn construct<A: Allocate>(
                             ComputeEvent::Peek(peek, is_install) => {
                                 let key = (worker, peek.uuid);
                                 if is_install {
+                                    let peek_time = u64::try_from(time.as_millis())
+                                        .expect("peek time must fit");
+                                    let last_key = (peek.id, worker);
+                                    if let Some(last) = peek_last_stash.insert(last_key, peek_time)
+                                    {
+                                        peek_last_session.give((
+                                            (peek.id, worker, last),
+                                            time_ms,
+                                            -1,
+                                        ));
+                                    }
+                                    peek_last_session.give((
+                                        (peek.id, worker, peek_time),
+                                        time_ms,
+                                        1,
+                                    ));
                                     peek_session.give(((peek, worker), time_ms, 1));
                                     if peek_stash.contains_key(&key) {
                                         error!(
@@ -378,6 +404,16 @@ pub fn construct<A: Allocate>(
             }
         });
 
+        let peek_last = peek_last.as_collection().map({
+            move |(id, worker, time)| {
+                Row::pack_slice(&[
+                    Datum::String(&id.to_string()),
+                    Datum::UInt64(u64::cast_from(worker)),
+                    Datum::TimestampTz(epoch_millis_to_timestamp(time)),
+                ])
+            }
+        });
+
         let logs = vec![
             (
                 LogVariant::Compute(ComputeLog::DataflowCurrent),
@@ -401,6 +437,7 @@ pub fn construct<A: Allocate>(
             ),
             (LogVariant::Compute(ComputeLog::PeekCurrent), peek_current),
             (LogVariant::Compute(ComputeLog::PeekDuration), peek_duration),
+            (LogVariant::Compute(ComputeLog::PeekLast), peek_last),
         ];
 
         let mut result = std::collections::HashMap::new();
@@ -442,3 +479,12 @@ pub fn construct<A: Allocate>(
 
     traces
 }
+
+/// Converts a number of milliseconds since the Unix epoch into a timestamp.
+fn epoch_millis_to_timestamp(millis: u64) -> CheckedTimestamp<DateTime<Utc>> {
+    let secs = i64::try_from(millis / 1000).expect("timestamp seconds must fit");
+    let nanos = u32::try_from(millis % 1000 * 1_000_000).expect("timestamp nanos must fit");
+    DateTime::from_utc(NaiveDateTime::from_timestamp(secs, nanos), Utc)
+        .try_into()
+        .expect("timestamp must fit")
+}
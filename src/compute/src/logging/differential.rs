@@ -19,7 +19,6 @@ use differential_dataflow::logging::DifferentialEvent;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use timely::communication::Allocate;
 use timely::dataflow::channels::pact::Pipeline;
-use timely::dataflow::operators::capture::EventLink;
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use timely::logging::WorkerIdentifier;
 
@@ -31,9 +30,22 @@ use mz_timely_util::replay::MzReplay;
 
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
-use crate::logging::{ConsolidateBuffer, DifferentialLog, LogVariant};
+use crate::logging::{ConsolidateBuffer, DifferentialLog, LogVariant, PendingEventLink};
 use crate::typedefs::{KeysValsHandle, RowSpine};
 
+/// Names the type of `event` for the purpose of counting dropped events, or
+/// returns `None` if the event must not be dropped.
+pub fn droppable_event_type(event: &DifferentialEvent) -> Option<&'static str> {
+    match event {
+        DifferentialEvent::Batch(_) => Some("batch"),
+        DifferentialEvent::Merge(_) => Some("merge"),
+        DifferentialEvent::Drop(_) => Some("drop"),
+        DifferentialEvent::MergeShortfall(_) => Some("merge_shortfall"),
+        // Trace sharing is retracted when the trace is dropped.
+        DifferentialEvent::TraceShare(_) => None,
+    }
+}
+
 /// Constructs the logging dataflow for differential logs.
 ///
 /// Params
@@ -48,7 +60,7 @@ pub fn construct<A: Allocate>(
     worker: &mut timely::worker::Worker<A>,
     config: &mz_compute_client::logging::LoggingConfig,
     compute_state: &mut ComputeState,
-    linked: std::rc::Rc<EventLink<Timestamp, (Duration, WorkerIdentifier, DifferentialEvent)>>,
+    linked: PendingEventLink<Timestamp, (Duration, WorkerIdentifier, DifferentialEvent)>,
     activator: RcActivator,
 ) -> HashMap<LogVariant, (KeysValsHandle, Rc<dyn Any>)> {
     let interval_ms = std::cmp::max(1, config.interval_ns / 1_000_000);
@@ -15,11 +15,15 @@ pub mod persist;
 pub mod reachability;
 pub mod timely;
 
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::time::Duration;
 
 use ::timely::communication::Push;
 use ::timely::dataflow::channels::Bundle;
-use ::timely::dataflow::operators::capture::{Event, EventPusher};
+use ::timely::dataflow::operators::capture::event::link::EventLink;
+use ::timely::dataflow::operators::capture::{Event, EventIterator, EventPusher};
 use ::timely::dataflow::operators::generic::OutputHandle;
 use ::timely::dataflow::operators::Capability;
 use ::timely::dataflow::operators::CapabilityRef;
@@ -28,11 +32,139 @@ use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::ExchangeData;
+use prometheus::core::AtomicU64;
 
 use mz_compute_client::logging::{ComputeLog, DifferentialLog, LogVariant, TimelyLog};
+use mz_ore::metric;
+use mz_ore::metrics::{CounterVecExt, DeleteOnDropCounter, IntCounterVec, MetricsRegistry};
 use mz_repr::Timestamp;
 
+/// Metrics for the logging dataflows.
+#[derive(Clone, Debug)]
+pub struct LoggingMetrics {
+    dropped_events: IntCounterVec,
+}
+
+impl LoggingMetrics {
+    /// Registers the logging metrics with `registry`.
+    pub fn register_with(registry: &MetricsRegistry) -> Self {
+        Self {
+            dropped_events: registry.register(metric!(
+                name: "mz_compute_logging_dropped_events_total",
+                help: "The number of logged events dropped because the logging dataflow fell behind",
+                var_labels: ["log", "event_type", "worker_id"],
+            )),
+        }
+    }
+}
+
+/// Counts the events a [`BatchLogger`] drops, by event type.
+pub struct DroppedEvents<T> {
+    metrics: LoggingMetrics,
+    log: &'static str,
+    worker_id: String,
+    /// Names the type of an event, or returns `None` if the event must never
+    /// be dropped.
+    ///
+    /// Events that announce or retire objects must not be dropped, as the
+    /// logging dataflows rely on them to retract what they have reported.
+    event_type: fn(&T) -> Option<&'static str>,
+    counters: HashMap<&'static str, DeleteOnDropCounter<'static, AtomicU64, Vec<String>>>,
+}
+
+impl<T> DroppedEvents<T> {
+    /// Creates a counter of the events of the log named `log` that are
+    /// dropped on worker `worker_id`.
+    pub fn new(
+        metrics: LoggingMetrics,
+        log: &'static str,
+        worker_id: usize,
+        event_type: fn(&T) -> Option<&'static str>,
+    ) -> Self {
+        DroppedEvents {
+            metrics,
+            log,
+            worker_id: worker_id.to_string(),
+            event_type,
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Returns whether `event` may be dropped.
+    fn droppable(&self, event: &T) -> bool {
+        (self.event_type)(event).is_some()
+    }
+
+    /// Records that `event` was dropped.
+    fn record(&mut self, event: &T) {
+        if let Some(event_type) = (self.event_type)(event) {
+            let DroppedEvents {
+                metrics,
+                log,
+                worker_id,
+                counters,
+                ..
+            } = self;
+            counters
+                .entry(event_type)
+                .or_insert_with(|| {
+                    metrics.dropped_events.get_delete_on_drop_counter(vec![
+                        log.to_string(),
+                        event_type.to_string(),
+                        worker_id.clone(),
+                    ])
+                })
+                .inc();
+        }
+    }
+}
+
+/// Replays the events pushed to an [`EventLink`], keeping track of how many
+/// of the events pushed by a [`BatchLogger`] have yet to be replayed.
+pub struct PendingEventLink<T, D> {
+    link: Rc<EventLink<T, D>>,
+    pending: Rc<Cell<usize>>,
+}
+
+impl<T, D> PendingEventLink<T, D> {
+    /// Creates a new link, along with the count of its pending events that
+    /// is to be shared with the [`BatchLogger`] pushing to it.
+    pub fn new(link: Rc<EventLink<T, D>>) -> (Self, Rc<Cell<usize>>) {
+        let pending = Rc::new(Cell::new(0));
+        let link = PendingEventLink {
+            link,
+            pending: Rc::clone(&pending),
+        };
+        (link, pending)
+    }
+}
+
+impl<T, D> Clone for PendingEventLink<T, D> {
+    fn clone(&self) -> Self {
+        PendingEventLink {
+            link: Rc::clone(&self.link),
+            pending: Rc::clone(&self.pending),
+        }
+    }
+}
+
+impl<T, D> EventIterator<T, D> for PendingEventLink<T, D> {
+    fn next(&mut self) -> Option<&Event<T, D>> {
+        let event = self.link.next();
+        if let Some(Event::Messages(_, data)) = &event {
+            self.pending
+                .set(self.pending.get().saturating_sub(data.len()));
+        }
+        event
+    }
+}
+
 /// Logs events as a timely stream, with progress statements.
+///
+/// If the logging dataflow replaying the stream falls too far behind, the
+/// logger drops events rather than buffering them without bound, and counts
+/// the dropped events so that gaps in the logs can be told apart from
+/// periods of inactivity.
 pub struct BatchLogger<T, E, P>
 where
     P: EventPusher<Timestamp, (Duration, E, T)>,
@@ -47,6 +179,10 @@ where
     interval_ms: u64,
     /// A stash for data that does not yet need to be sent.
     buffer: Vec<(Duration, E, T)>,
+    /// The number of events pushed to `event_pusher` that have yet to be replayed.
+    pending: Rc<Cell<usize>>,
+    /// The events dropped because too many were pending.
+    dropped: DroppedEvents<T>,
 }
 
 impl<T, E, P> BatchLogger<T, E, P>
@@ -56,6 +192,16 @@ where
     /// Batch size in bytes for batches
     const BATCH_SIZE_BYTES: usize = 1 << 13;
 
+    /// The size in bytes of the events that may be pending replay before
+    /// further events are dropped.
+    const MAX_PENDING_BYTES: usize = 1 << 28;
+
+    /// Calculate the maximum number of pending events based on `(Duration, E, T)` tuples.
+    fn max_pending() -> usize {
+        let size = ::std::mem::size_of::<(Duration, E, T)>();
+        std::cmp::max(1, Self::MAX_PENDING_BYTES / std::cmp::max(1, size))
+    }
+
     /// Calculate the default buffer size based on `(Duration, E, T)` tuples.
     fn buffer_capacity() -> usize {
         let size = ::std::mem::size_of::<(Duration, E, T)>();
@@ -69,16 +215,55 @@ where
     }
 
     /// Creates a new batch logger.
-    pub fn new(event_pusher: P, interval_ms: u64) -> Self {
+    ///
+    /// `pending` is the count of pushed events that have yet to be replayed,
+    /// as maintained by a [`PendingEventLink`].
+    pub fn new(
+        event_pusher: P,
+        interval_ms: u64,
+        pending: Rc<Cell<usize>>,
+        dropped: DroppedEvents<T>,
+    ) -> Self {
         BatchLogger {
             time_ms: Timestamp::minimum(),
             event_pusher,
             _phantom: ::std::marker::PhantomData,
             interval_ms,
             buffer: Vec::with_capacity(Self::buffer_capacity()),
+            pending,
+            dropped,
         }
     }
 
+    /// Pushes the buffered events at the current time.
+    fn push_buffer(&mut self) {
+        self.pending.set(self.pending.get() + self.buffer.len());
+        self.event_pusher.push(Event::Messages(
+            self.time_ms as Timestamp,
+            self.buffer.drain(..).collect(),
+        ));
+    }
+
+    /// Drops droppable events from `data` that exceed the room left for
+    /// pending events.
+    fn shed(&mut self, data: &mut Vec<(Duration, E, T)>) {
+        let max_pending = Self::max_pending();
+        let mut occupied = self.pending.get() + self.buffer.len();
+        if occupied + data.len() <= max_pending {
+            return;
+        }
+        let dropped = &mut self.dropped;
+        data.retain(|(_, _, event)| {
+            if occupied < max_pending || !dropped.droppable(event) {
+                occupied += 1;
+                true
+            } else {
+                dropped.record(event);
+                false
+            }
+        });
+    }
+
     /// Publishes a batch of logged events and advances the capability.
     pub fn publish_batch(&mut self, time: &Duration, data: &mut Vec<(Duration, E, T)>) {
         let new_time_ms = Timestamp::try_from(
@@ -86,22 +271,18 @@ where
         )
         .expect("must fit");
         if !data.is_empty() {
+            self.shed(data);
+
             // If we don't need to grow our buffer, move
             if data.len() > self.buffer.capacity() - self.buffer.len() {
-                self.event_pusher.push(Event::Messages(
-                    self.time_ms as Timestamp,
-                    self.buffer.drain(..).collect(),
-                ));
+                self.push_buffer();
             }
 
             self.buffer.append(data);
         }
         if self.time_ms < new_time_ms {
             // Flush buffered events that may need to advance.
-            self.event_pusher.push(Event::Messages(
-                self.time_ms as Timestamp,
-                self.buffer.drain(..).collect(),
-            ));
+            self.push_buffer();
             if self.buffer.capacity() > Self::buffer_capacity() {
                 self.buffer = Vec::with_capacity(Self::buffer_capacity())
             }
@@ -11,6 +11,7 @@ use differential_dataflow::Collection;
 use timely::dataflow::Scope;
 use timely::progress::{Antichain, Timestamp as _};
 
+use mz_compute_client::sinks::PersistSinkBatching;
 use mz_repr::GlobalId;
 use mz_repr::{Diff, Row, Timestamp};
 use mz_storage::controller::CollectionMetadata;
@@ -28,8 +29,14 @@ pub(crate) fn persist_sink<G>(
     let desired_collection = log_collection.map(Ok);
     let as_of = Antichain::from_elem(Timestamp::minimum());
 
-    let token =
-        crate::sink::persist_sink(target_id, target, desired_collection, as_of, compute_state);
+    let token = crate::sink::persist_sink(
+        target_id,
+        target,
+        desired_collection,
+        as_of,
+        PersistSinkBatching::default(),
+        compute_state,
+    );
 
     compute_state.sink_tokens.insert(
         target_id,
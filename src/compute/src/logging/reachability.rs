@@ -19,7 +19,6 @@ use differential_dataflow::AsCollection;
 use mz_expr::{permutation_for_arrangement, MirScalarExpr};
 use timely::communication::Allocate;
 use timely::dataflow::channels::pact::Exchange;
-use timely::dataflow::operators::capture::EventLink;
 use timely::logging::WorkerIdentifier;
 
 use mz_compute_client::logging::LoggingConfig;
@@ -31,7 +30,7 @@ use mz_timely_util::replay::MzReplay;
 
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
-use crate::logging::{ConsolidateBuffer, LogVariant, TimelyLog};
+use crate::logging::{ConsolidateBuffer, LogVariant, PendingEventLink, TimelyLog};
 use crate::typedefs::{KeysValsHandle, RowSpine};
 
 /// Constructs the logging dataflow for reachability logs.
@@ -48,18 +47,16 @@ pub fn construct<A: Allocate>(
     worker: &mut timely::worker::Worker<A>,
     config: &LoggingConfig,
     compute_state: &mut ComputeState,
-    linked: std::rc::Rc<
-        EventLink<
-            Timestamp,
+    linked: PendingEventLink<
+        Timestamp,
+        (
+            Duration,
+            WorkerIdentifier,
             (
-                Duration,
-                WorkerIdentifier,
-                (
-                    Vec<usize>,
-                    Vec<(usize, usize, bool, Option<Timestamp>, Diff)>,
-                ),
+                Vec<usize>,
+                Vec<(usize, usize, bool, Option<Timestamp>, Diff)>,
             ),
-        >,
+        ),
     >,
     activator: RcActivator,
 ) -> HashMap<LogVariant, (KeysValsHandle, Rc<dyn Any>)> {
@@ -12,14 +12,15 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::rc::Rc;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use differential_dataflow::collection::AsCollection;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use mz_expr::{permutation_for_arrangement, MirScalarExpr};
 use timely::communication::Allocate;
 use timely::dataflow::channels::pact::Exchange;
-use timely::dataflow::operators::capture::EventLink;
+use timely::dataflow::operators::generic::operator::source;
 use timely::logging::{ParkEvent, TimelyEvent, WorkerIdentifier};
 
 use mz_compute_client::logging::LoggingConfig;
@@ -30,9 +31,27 @@ use mz_timely_util::replay::MzReplay;
 
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
-use crate::logging::{ConsolidateBuffer, LogVariant, TimelyLog};
+use crate::logging::{ConsolidateBuffer, LogVariant, PendingEventLink, TimelyLog};
 use crate::typedefs::{KeysValsHandle, RowSpine};
 
+/// Names the type of `event` for the purpose of counting dropped events, or
+/// returns `None` if the event must not be dropped.
+pub fn droppable_event_type(event: &TimelyEvent) -> Option<&'static str> {
+    match event {
+        // The demux relies on these to retract what it has reported, or to
+        // pair parks with unparks.
+        TimelyEvent::Operates(_)
+        | TimelyEvent::Channels(_)
+        | TimelyEvent::Shutdown(_)
+        | TimelyEvent::Park(_) => None,
+        TimelyEvent::Messages(_) => Some("messages"),
+        TimelyEvent::Schedule(_) => Some("schedule"),
+        TimelyEvent::Progress(_) => Some("progress"),
+        TimelyEvent::PushProgress(_) => Some("push_progress"),
+        _ => Some("other"),
+    }
+}
+
 /// Constructs the logging dataflow for timely logs.
 ///
 /// Params
@@ -47,11 +66,13 @@ pub fn construct<A: Allocate>(
     worker: &mut timely::worker::Worker<A>,
     config: &LoggingConfig,
     compute_state: &mut ComputeState,
-    linked: std::rc::Rc<EventLink<Timestamp, (Duration, WorkerIdentifier, TimelyEvent)>>,
+    linked: PendingEventLink<Timestamp, (Duration, WorkerIdentifier, TimelyEvent)>,
     activator: RcActivator,
 ) -> HashMap<LogVariant, (KeysValsHandle, Rc<dyn Any>)> {
     let interval_ms = std::cmp::max(1, config.interval_ns / 1_000_000);
     let peers = worker.peers();
+    let worker_index = worker.index();
+    let network_counters = Arc::clone(&compute_state.network_counters);
 
     // A dataflow for multiple log-derived arrangements.
     let traces = worker.dataflow_named("Dataflow: timely logging", move |scope| {
@@ -283,15 +304,16 @@ pub fn construct<A: Allocate>(
                                 // Pair of operator ID and worker
                                 let key = (event.id, worker);
                                 match event.start_stop {
+                                    // Either event of a pair may have been dropped by the
+                                    // logger, in which case the schedule goes unrecorded.
                                     timely::logging::StartStop::Start => {
-                                        debug_assert!(!schedules_stash.contains_key(&key));
                                         schedules_stash.insert(key, time_ns);
                                     }
                                     timely::logging::StartStop::Stop => {
-                                        debug_assert!(schedules_stash.contains_key(&key));
-                                        let start = schedules_stash
-                                            .remove(&key)
-                                            .expect("start event absent");
+                                        let start = match schedules_stash.remove(&key) {
+                                            Some(start) => start,
+                                            None => continue,
+                                        };
                                         let elapsed_ns = time_ns - start;
 
                                         // Record count and elapsed for retraction
@@ -422,6 +444,65 @@ pub fn construct<A: Allocate>(
                 ])
             });
 
+        // Sample the bytes this worker has exchanged with each remote process
+        // once per logging interval. The counters live outside of timely, so
+        // rather than being replayed from an event log, they are read by a
+        // source that retracts the previous sample whenever a counter changes.
+        let network_bytes = {
+            let token = Rc::downgrade(&token);
+            let interval = Duration::from_nanos(config.interval_ns as u64);
+            let activator_scope = scope.clone();
+            source(scope, "Timely network bytes", move |capability, info| {
+                let activator = activator_scope.activator_for(&info.address[..]);
+                let mut capability = Some(capability);
+                let mut previous: HashMap<usize, (u64, u64)> = HashMap::new();
+                move |output| {
+                    if token.upgrade().is_none() {
+                        // The logging dataflow is shutting down.
+                        capability = None;
+                        return;
+                    }
+                    let capability = capability.as_mut().expect("known to exist");
+
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("Failed to get duration since Unix epoch");
+                    let time_ms: Timestamp = (((now.as_millis() / interval_ms) + 1) * interval_ms)
+                        .try_into()
+                        .expect("must fit");
+                    if *capability.time() < time_ms {
+                        capability.downgrade(&time_ms);
+                    }
+
+                    let time = *capability.time();
+                    let mut session = output.session(&*capability);
+                    for (remote, sent, received) in network_counters.sample(worker_index) {
+                        if previous.get(&remote) == Some(&(sent, received)) {
+                            continue;
+                        }
+                        if let Some((sent, received)) = previous.insert(remote, (sent, received)) {
+                            session.give(((worker_index, remote, sent, received), time, -1));
+                        }
+                        session.give(((worker_index, remote, sent, received), time, 1));
+                    }
+
+                    activator.activate_after(interval);
+                }
+            })
+        };
+
+        let network_bytes =
+            network_bytes
+                .as_collection()
+                .map(|(worker, remote, sent, received)| {
+                    Row::pack_slice(&[
+                        Datum::UInt64(u64::cast_from(worker)),
+                        Datum::UInt64(u64::cast_from(remote)),
+                        Datum::UInt64(sent),
+                        Datum::UInt64(received),
+                    ])
+                });
+
         let channels = channels
             .as_collection()
             .arrange_core::<_, RowSpine<_, _, _, _>>(
@@ -454,6 +535,7 @@ pub fn construct<A: Allocate>(
                 LogVariant::Timely(TimelyLog::MessagesReceived),
                 messages_received,
             ),
+            (LogVariant::Timely(TimelyLog::NetworkBytes), network_bytes),
         ];
 
         let mut result = std::collections::HashMap::new();
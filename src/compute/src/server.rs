@@ -14,6 +14,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
@@ -45,9 +46,10 @@ use mz_persist_client::PersistConfig;
 use mz_service::client::{GenericClient, Partitioned};
 use mz_service::local::LocalClient;
 
-use crate::communication::initialize_networking;
+use crate::communication::{initialize_networking, NetworkCounters};
 use crate::compute_state::ActiveComputeState;
-use crate::compute_state::ComputeState;
+use crate::compute_state::{ComputeState, HEARTBEAT_INTERVAL};
+use crate::logging::LoggingMetrics;
 use crate::{TraceManager, TraceMetrics};
 
 /// Configures a dataflow server.
@@ -69,10 +71,16 @@ struct ClusterClient<C> {
     timely_container: TimelyContainerRef,
     /// The dataflow trace metrics.
     trace_metrics: TraceMetrics,
+    /// The logging dataflow metrics.
+    logging_metrics: LoggingMetrics,
     /// Handle to the persist infrastructure.
     persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
     /// The handle to the Tokio runtime.
     tokio_handle: tokio::runtime::Handle,
+    /// Build information.
+    build_info: &'static BuildInfo,
+    /// The time at which this process started serving.
+    process_start: Instant,
 }
 
 /// Metadata about timely workers in this process.
@@ -100,6 +108,7 @@ pub fn serve(
 ) -> Result<(TimelyContainerRef, impl Fn() -> Box<dyn ComputeClient>), Error> {
     // Various metrics related things.
     let trace_metrics = TraceMetrics::register_with(&config.metrics_registry);
+    let logging_metrics = LoggingMetrics::register_with(&config.metrics_registry);
 
     let persist_clients = PersistClientCache::new(
         PersistConfig::new(config.build_info, config.now.clone()),
@@ -107,6 +116,8 @@ pub fn serve(
     );
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
     let tokio_executor = tokio::runtime::Handle::current();
+    let build_info = config.build_info;
+    let process_start = Instant::now();
     let timely_container = Arc::new(Mutex::new(None));
     let client_builder = {
         let timely_container = Arc::clone(&timely_container);
@@ -114,8 +125,11 @@ pub fn serve(
             let client = ClusterClient::new(
                 Arc::clone(&timely_container),
                 trace_metrics.clone(),
+                logging_metrics.clone(),
                 Arc::clone(&persist_clients),
                 tokio_executor.clone(),
+                build_info,
+                process_start,
             );
             Box::new(client) as Box<dyn ComputeClient>
         }
@@ -128,15 +142,21 @@ impl ClusterClient<PartitionedClient> {
     fn new(
         timely_container: TimelyContainerRef,
         trace_metrics: TraceMetrics,
+        logging_metrics: LoggingMetrics,
         persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
         tokio_handle: tokio::runtime::Handle,
+        build_info: &'static BuildInfo,
+        process_start: Instant,
     ) -> Self {
         Self {
             timely_container,
             inner: None,
             trace_metrics,
+            logging_metrics,
             persist_clients,
             tokio_handle,
+            build_info,
+            process_start,
         }
     }
 
@@ -146,12 +166,16 @@ impl ClusterClient<PartitionedClient> {
             .unzip();
         let client_rxs: Mutex<Vec<_>> = Mutex::new(client_rxs.into_iter().map(Some).collect());
 
-        let (builders, other) = initialize_networking(&comm_config).map_err(|e| anyhow!("{e}"))?;
+        let (builders, other, network_counters) =
+            initialize_networking(&comm_config).map_err(|e| anyhow!("{e}"))?;
 
         let workers = comm_config.workers;
         let trace_metrics = self.trace_metrics.clone();
+        let logging_metrics = self.logging_metrics.clone();
         let persist_clients = Arc::clone(&self.persist_clients);
         let tokio_executor = self.tokio_handle.clone();
+        let build_info = self.build_info;
+        let process_start = self.process_start;
         let worker_guards = execute_from(
             builders,
             other,
@@ -164,12 +188,17 @@ impl ClusterClient<PartitionedClient> {
                     .unwrap();
                 let _trace_metrics = trace_metrics.clone();
                 let persist_clients = Arc::clone(&persist_clients);
+                let network_counters = Arc::clone(&network_counters);
                 Worker {
                     timely_worker,
                     client_rx,
                     compute_state: None,
                     trace_metrics: trace_metrics.clone(),
+                    logging_metrics: logging_metrics.clone(),
                     persist_clients,
+                    network_counters,
+                    build_info,
+                    process_start,
                 }
                 .run()
             },
@@ -334,9 +363,18 @@ struct Worker<'w, A: Allocate> {
     compute_state: Option<ComputeState>,
     /// Trace metrics.
     trace_metrics: TraceMetrics,
+    /// Logging dataflow metrics.
+    logging_metrics: LoggingMetrics,
     /// A process-global cache of (blob_uri, consensus_uri) -> PersistClient.
     /// This is intentionally shared between workers
     persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
+    /// Counters of the bytes exchanged with remote processes, shared between
+    /// all workers of this process.
+    network_counters: Arc<NetworkCounters>,
+    /// Build information.
+    build_info: &'static BuildInfo,
+    /// The time at which this process started serving.
+    process_start: Instant,
 }
 
 impl<'w, A: Allocate> Worker<'w, A> {
@@ -507,11 +545,14 @@ impl<'w, A: Allocate> Worker<'w, A> {
                 compute_state.traces.maintenance();
             }
 
-            self.timely_worker.step_or_park(None);
+            // Wake up at least once per heartbeat interval, so that an idle
+            // replica still reports that it is alive.
+            self.timely_worker.step_or_park(Some(HEARTBEAT_INTERVAL));
 
             // Report frontier information back the coordinator.
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
                 compute_state.report_compute_frontiers();
+                compute_state.report_heartbeat();
             }
 
             // Handle any received commands.
@@ -565,8 +606,13 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     reported_frontiers: HashMap::new(),
                     compute_logger: None,
                     persist_clients: Arc::clone(&self.persist_clients),
+                    network_counters: Arc::clone(&self.network_counters),
                     command_history: ComputeCommandHistory::default(),
                     max_result_size: config.max_result_size,
+                    build_info: self.build_info,
+                    process_start: self.process_start,
+                    last_heartbeat: None,
+                    logging_metrics: self.logging_metrics.clone(),
                 });
             }
             ComputeCommand::DropInstance => {
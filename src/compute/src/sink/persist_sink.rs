@@ -13,7 +13,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::lattice::Lattice;
@@ -32,7 +32,8 @@ use timely::PartialOrder;
 use tokio::sync::Mutex;
 use tracing::trace;
 
-use mz_compute_client::sinks::{ComputeSinkDesc, PersistSinkConnection};
+use mz_compute_client::sinks::{ComputeSinkDesc, PersistSinkBatching, PersistSinkConnection};
+use mz_ore::cast::CastFrom;
 use mz_persist_client::cache::PersistClientCache;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage::controller::CollectionMetadata;
@@ -65,6 +66,7 @@ where
             &self.storage_metadata,
             desired_collection,
             sink.as_of.frontier.clone(),
+            self.batching,
             compute_state,
         )
     }
@@ -75,6 +77,7 @@ pub(crate) fn persist_sink<G>(
     target: &CollectionMetadata,
     desired_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     as_of: Antichain<Timestamp>,
+    batching: PersistSinkBatching,
     compute_state: &mut ComputeState,
 ) -> Option<Rc<dyn Any>>
 where
@@ -109,6 +112,7 @@ where
             desired_collection,
             persist_collection,
             as_of,
+            batching,
             compute_state,
         ),
         token,
@@ -125,7 +129,10 @@ where
 ///    than `desired_frontier`. A batch description is a pair of `(lower,
 ///    upper)` that tells write operators which updates to write and in the end
 ///    tells the append operator what frontiers to use when calling
-///    `append`/`compare_and_append`. This is a single-worker operator.
+///    `append`/`compare_and_append`. This is a single-worker operator. If the
+///    sink is configured with [`PersistSinkBatching`], minting is additionally
+///    held back until enough updates have accumulated or until the maximum
+///    staleness has elapsed.
 /// 2. `write_batches` writes the difference between `desired_stream` and
 ///    `persist_stream` to persist as batches and sends those batches along.
 ///    This does not yet append the batches to the persist shard, the update are
@@ -142,6 +149,7 @@ fn install_desired_into_persist<G>(
     desired_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     persist_collection: Collection<G, Result<Row, DataflowError>, Diff>,
     as_of: Antichain<Timestamp>,
+    batching: PersistSinkBatching,
     compute_state: &mut crate::compute_state::ComputeState,
 ) -> Option<Rc<dyn Any>>
 where
@@ -179,6 +187,7 @@ where
         &desired_collection.inner,
         &persist_feedback_stream,
         as_of,
+        batching,
         Arc::clone(&persist_clients),
         compute_state,
     );
@@ -218,6 +227,12 @@ where
 /// `broadcast()` to, ahem, broadcast, the one description to all downstream
 /// write operators/workers.
 ///
+/// With a non-zero `batching.max_staleness`, a new batch description is only
+/// minted once the updates that arrived since the last one are estimated to
+/// reach `batching.target_bytes`, or once `batching.max_staleness` has elapsed
+/// since the last one, whichever comes first. The estimate extrapolates the
+/// updates seen by the active worker to all workers.
+///
 /// This also keeps the shared frontier that is stored in `compute_state` in
 /// sync with the upper of the persist shard.
 fn mint_batch_descriptions<G>(
@@ -227,6 +242,7 @@ fn mint_batch_descriptions<G>(
     desired_stream: &Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>,
     persist_feedback_stream: &Stream<G, ()>,
     as_of: Antichain<Timestamp>,
+    batching: PersistSinkBatching,
     persist_clients: Arc<Mutex<PersistClientCache>>,
     compute_state: &mut crate::compute_state::ComputeState,
 ) -> (
@@ -250,6 +266,7 @@ where
     // can be combined into one batch that gets appended to Consensus state.
     let hashed_id = sink_id.hashed();
     let active_worker = (hashed_id as usize) % scope.peers() == scope.index();
+    let peers = u64::cast_from(scope.peers());
 
     // Only the "active" operator will mint batches. All other workers have an
     // empty frontier. It's necessary to insert all of these into
@@ -276,6 +293,9 @@ where
     let mut persist_feedback_input =
         mint_op.new_input_connection(persist_feedback_stream, Pipeline, vec![Antichain::new()]);
 
+    // Used to wake the operator once the maximum batch staleness has elapsed.
+    let activator = scope.activator_for(&mint_op.operator_info().address[..]);
+
     // Dropping this token signals that the operator should shut down cleanly.
     let token = Rc::new(());
     let token_weak = Rc::downgrade(&token);
@@ -352,14 +372,28 @@ where
             // do this, we would be stuck at `[minimum]`.
             let mut current_persist_frontier = None;
 
+            // When we last minted a batch description, and the estimated size
+            // of the updates this worker has seen since then.
+            let mut last_mint = Instant::now();
+            let mut pending_bytes: u64 = 0;
+
             while scheduler.notified().await {
                 if token_weak.upgrade().is_none() {
                     return;
                 }
 
-                desired_input.for_each(|_cap, _data| {
-                    // Just read away data.
-                    // WIP: Is this idiomatic timely?
+                desired_input.for_each(|_cap, data| {
+                    if batching.target_bytes.is_some() {
+                        for (update, _time, _diff) in data.iter() {
+                            let row_bytes = match update {
+                                Ok(row) => row.byte_len(),
+                                Err(_) => 0,
+                            };
+                            pending_bytes += u64::cast_from(
+                                row_bytes + std::mem::size_of::<(Row, Timestamp, Diff)>(),
+                            );
+                        }
+                    }
                 });
                 persist_feedback_input.for_each(|_cap, _data| {
                     // Just read away data.
@@ -418,13 +452,28 @@ where
                 // persist frontier got moved by someone else, in which case
                 // we also won't mint a new batch description for the same
                 // frontier.
-                if PartialOrder::less_than(persist_frontier, desired_frontier)
+                //
+                // When batching is configured, we additionally hold back new
+                // batch descriptions until the batch is large or stale enough.
+                // The desired frontier becoming empty always flushes.
+                let staleness = last_mint.elapsed();
+                let batch_ready = !batching.is_enabled()
+                    || desired_frontier.is_empty()
+                    || staleness >= batching.max_staleness
+                    || batching
+                        .target_bytes
+                        .map_or(false, |target| pending_bytes * peers >= target);
+
+                let can_mint = PartialOrder::less_than(persist_frontier, desired_frontier)
                     && (current_persist_frontier.is_none()
                         || PartialOrder::less_than(
                             current_persist_frontier.as_ref().unwrap(),
                             persist_frontier,
-                        ))
-                {
+                        ));
+
+                if can_mint && !batch_ready {
+                    activator.activate_after(batching.max_staleness - staleness);
+                } else if can_mint {
                     let batch_description =
                         (persist_frontier.to_owned(), desired_frontier.to_owned());
 
@@ -472,6 +521,8 @@ where
 
                     current_desired_frontier.clone_from(desired_frontier);
                     current_persist_frontier.replace(persist_frontier.clone());
+                    last_mint = Instant::now();
+                    pending_bytes = 0;
                 } else {
                     // WIP: Remove this!
                     if sink_id.is_user() {
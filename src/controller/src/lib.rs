@@ -35,9 +35,9 @@ use uuid::Uuid;
 use mz_build_info::BuildInfo;
 use mz_compute_client::command::ReplicaId;
 use mz_compute_client::controller::{
-    ActiveComputeController, ComputeController, ComputeControllerResponse,
+    ActiveComputeController, ComputeController, ComputeControllerResponse, PendingAck,
 };
-use mz_compute_client::response::{PeekResponse, SubscribeResponse};
+use mz_compute_client::response::{PeekResponse, ReplicaHeartbeat, SubscribeResponse};
 use mz_compute_client::service::{ComputeClient, ComputeGrpcClient};
 use mz_orchestrator::Orchestrator;
 use mz_ore::now::{EpochMillis, NowFn};
@@ -46,7 +46,7 @@ use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::PersistLocation;
 use mz_persist_types::Codec64;
 use mz_proto::RustType;
-use mz_repr::{GlobalId, TimestampManipulation};
+use mz_repr::{Diff, GlobalId, TimestampManipulation};
 use mz_storage::controller::StorageController;
 use mz_storage::protocol::client::{
     ProtoStorageCommand, ProtoStorageResponse, StorageCommand, StorageResponse,
@@ -89,6 +89,12 @@ pub enum ControllerResponse<T = mz_repr::Timestamp> {
     /// Notification that we have received a message from the given compute replica
     /// at the given time.
     ComputeReplicaHeartbeat(ReplicaId, DateTime<Utc>),
+    /// A heartbeat from the given compute replica, received at the given time,
+    /// describing what the replica is running.
+    ComputeReplicaLiveness(ReplicaId, DateTime<Utc>, ReplicaHeartbeat),
+    /// A change in the set of dataflow exports awaiting acknowledgment from a
+    /// compute replica.
+    ComputePendingAck(PendingAck, Diff),
 }
 
 impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
@@ -103,6 +109,12 @@ impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
             ComputeControllerResponse::ReplicaHeartbeat(id, when) => {
                 ControllerResponse::ComputeReplicaHeartbeat(id, when)
             }
+            ComputeControllerResponse::ReplicaLiveness(id, when, heartbeat) => {
+                ControllerResponse::ComputeReplicaLiveness(id, when, heartbeat)
+            }
+            ComputeControllerResponse::PendingAck(ack, diff) => {
+                ControllerResponse::ComputePendingAck(ack, diff)
+            }
         }
     }
 }
@@ -55,6 +55,7 @@ use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistConfig, PersistLocation};
+use mz_secrets::keyring::{EncryptedSecretsController, KeyringConfig, SecretsEncryptionConfig};
 use mz_secrets::SecretsController;
 use mz_storage::types::connections::ConnectionContext;
 
@@ -303,6 +304,49 @@ pub struct Args {
     )]
     orchestrator_process_data_directory: PathBuf,
 
+    // === Secrets options. ===
+    /// The keyring with which to encrypt secrets at rest.
+    ///
+    /// If unspecified, secrets are stored unencrypted. Secrets stored before a
+    /// keyring is specified cannot be read unless
+    /// `--secrets-keyring-allow-plaintext` is specified.
+    #[clap(long, env = "SECRETS_KEYRING", arg_enum, value_name = "KIND")]
+    secrets_keyring: Option<SecretsKeyringKind>,
+    /// When using the local file keyring, the file that contains the master
+    /// key, encoded as 64 hexadecimal digits.
+    ///
+    /// The file must be readable at the same path by every service that reads
+    /// secrets. As services orchestrated by Kubernetes do not share a
+    /// filesystem with environmentd, the local file keyring can only be used
+    /// with the process orchestrator.
+    #[clap(
+        long,
+        env = "SECRETS_KEYRING_LOCAL_FILE",
+        value_name = "PATH",
+        required_if_eq("secrets-keyring", "local-file")
+    )]
+    secrets_keyring_local_file: Option<PathBuf>,
+    /// When using the AWS KMS keyring, the ID, ARN, or alias of the KMS key
+    /// that wraps the keys with which secrets are encrypted.
+    #[clap(
+        long,
+        env = "SECRETS_KEYRING_AWS_KMS_KEY_ID",
+        value_name = "ID",
+        required_if_eq("secrets-keyring", "aws-kms")
+    )]
+    secrets_keyring_aws_kms_key_id: Option<String>,
+    /// Whether to read secrets that were stored unencrypted, before a keyring
+    /// was specified.
+    ///
+    /// Such secrets are encrypted the next time they are altered. Only specify
+    /// this while migrating an existing deployment to encrypted secrets.
+    #[clap(
+        long,
+        env = "SECRETS_KEYRING_ALLOW_PLAINTEXT",
+        requires = "secrets-keyring"
+    )]
+    secrets_keyring_allow_plaintext: bool,
+
     // === Storage options. ===
     /// Where the persist library should store its blob data.
     #[clap(long, env = "PERSIST_BLOB_URL")]
@@ -415,6 +459,12 @@ enum OrchestratorKind {
     Process,
 }
 
+#[derive(ArgEnum, Debug, Clone)]
+enum SecretsKeyringKind {
+    LocalFile,
+    AwsKms,
+}
+
 fn main() {
     let args = cli::parse_args(CliConfig {
         env_prefix: Some("MZ_"),
@@ -543,9 +593,33 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         ])
     };
 
+    // Configure the encryption of secrets at rest.
+    let secrets_encryption = args.secrets_keyring.map(|kind| SecretsEncryptionConfig {
+        keyring: match kind {
+            SecretsKeyringKind::LocalFile => {
+                KeyringConfig::LocalFile(args.secrets_keyring_local_file.expect("clap enforced"))
+            }
+            SecretsKeyringKind::AwsKms => KeyringConfig::AwsKms {
+                key_id: args.secrets_keyring_aws_kms_key_id.expect("clap enforced"),
+            },
+        },
+        allow_plaintext: args.secrets_keyring_allow_plaintext,
+    });
+
     // Configure controller.
     let (orchestrator, secrets_controller) = match args.orchestrator {
         OrchestratorKind::Kubernetes => {
+            if let Some(SecretsEncryptionConfig {
+                keyring: KeyringConfig::LocalFile(_),
+                ..
+            }) = &secrets_encryption
+            {
+                bail!(
+                    "cannot specify --secrets-keyring=local-file with \
+                     --orchestrator=kubernetes, as the key file is not \
+                     available to the services that read secrets"
+                );
+            }
             let orchestrator = Arc::new(
                 runtime
                     .block_on(KubernetesOrchestrator::new(KubernetesOrchestratorConfig {
@@ -562,6 +636,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                             .collect(),
                         service_account: args.orchestrator_kubernetes_service_account,
                         image_pull_policy: args.orchestrator_kubernetes_image_pull_policy,
+                        secrets_encryption: secrets_encryption.clone(),
                     }))
                     .context("creating kubernetes orchestrator")?,
             );
@@ -591,6 +666,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                         command_wrapper: args
                             .orchestrator_process_wrapper
                             .map_or(Ok(vec![]), |s| shell_words::split(&s))?,
+                        secrets_encryption: secrets_encryption.clone(),
                     }))
                     .context("creating process orchestrator")?,
             );
@@ -600,6 +676,19 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
             )
         }
     };
+    let secrets_controller = match &secrets_encryption {
+        None => secrets_controller,
+        Some(encryption) => {
+            let keyring = runtime
+                .block_on(encryption.keyring.load())
+                .context("loading secrets keyring")?;
+            Arc::new(EncryptedSecretsController::new(
+                secrets_controller,
+                keyring,
+                encryption.allow_plaintext,
+            )) as Arc<dyn SecretsController>
+        }
+    };
     let secrets_reader = secrets_controller.reader();
     let now = SYSTEM_TIME.clone();
     let persist_clients = PersistClientCache::new(
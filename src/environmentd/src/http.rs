@@ -11,7 +11,8 @@
 //!
 //! environmentd embeds an HTTP server for introspection into the running
 //! process. At the moment, its primary exports are Prometheus metrics, heap
-//! profiles, and catalog dumps.
+//! profiles, and catalog dumps. It also accepts the requests that append data
+//! to webhook sources.
 
 // Axum handlers must use async, but often don't actually use `await`.
 #![allow(clippy::unused_async)]
@@ -56,6 +57,7 @@ mod catalog;
 mod memory;
 mod root;
 mod sql;
+mod webhook;
 
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
@@ -425,6 +427,10 @@ fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
             routing::get(move || async move { root::handle_home(profiling).await }),
         )
         .route("/api/sql", routing::post(sql::handle_sql))
+        .route(
+            "/api/webhook/:database/:schema/:name",
+            routing::post(webhook::handle_webhook),
+        )
         .route("/memory", routing::get(memory::handle_memory))
         .route(
             "/hierarchical-memory",
@@ -364,8 +364,10 @@ async fn execute_stmt(
         | ExecuteResponse::DroppedSecret
         | ExecuteResponse::DroppedConnection
         | ExecuteResponse::EmptyQuery
+        | ExecuteResponse::GrantedPrivilege
         | ExecuteResponse::Inserted(_)
         | ExecuteResponse::Raised
+        | ExecuteResponse::RevokedPrivilege
         | ExecuteResponse::SetVariable { .. }
         | ExecuteResponse::StartedTransaction { .. }
         | ExecuteResponse::TransactionCommitted
@@ -0,0 +1,68 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Webhook source HTTP endpoints.
+
+use axum::extract::Path;
+use axum::response::IntoResponse;
+use http::{HeaderMap, StatusCode};
+
+use mz_adapter::session::EndTransactionAction;
+use mz_adapter::{AdapterError, SessionClient};
+use mz_sql::catalog::CatalogError;
+use mz_sql::names::PartialObjectName;
+
+use crate::http::AuthedClient;
+
+/// The header that carries the hex-encoded HMAC-SHA256 of the request body.
+const SIGNATURE_HEADER: &str = "x-mz-signature";
+
+/// Appends the body of the request to the webhook source named by the path.
+pub async fn handle_webhook(
+    AuthedClient(mut client): AuthedClient,
+    Path((database, schema, name)): Path<(String, String, String)>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    let name = PartialObjectName {
+        database: Some(database),
+        schema: Some(schema),
+        item: name,
+    };
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|signature| signature.to_str().ok())
+        .map(|signature| signature.to_string());
+    match append_webhook(&mut client, name, body, signature).await {
+        Ok(()) => Ok(StatusCode::OK),
+        Err(e @ AdapterError::SqlCatalog(CatalogError::UnknownItem(_))) => {
+            Err((StatusCode::NOT_FOUND, e.to_string()))
+        }
+        Err(e @ AdapterError::Unauthorized(_)) => Err((StatusCode::UNAUTHORIZED, e.to_string())),
+        Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
+    }
+}
+
+async fn append_webhook(
+    client: &mut SessionClient,
+    name: PartialObjectName,
+    body: String,
+    signature: Option<String>,
+) -> Result<(), AdapterError> {
+    client.start_transaction(Some(1)).await?;
+    if let Err(e) = client.append_webhook(name, body, signature).await {
+        client
+            .end_transaction(EndTransactionAction::Rollback)
+            .await?;
+        return Err(e);
+    }
+    // Responds only once the body has been durably written.
+    client.end_transaction(EndTransactionAction::Commit).await?;
+    Ok(())
+}
@@ -161,6 +161,40 @@ fn test_source_sink_size_required() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Test that requests to the endpoint of a webhook source need the INSERT
+// privilege on the source.
+#[test]
+fn test_http_webhook_privileges() -> Result<(), Box<dyn Error>> {
+    mz_ore::test::init_logging();
+    let server = util::start_server(util::Config::default().unsafe_mode())?;
+    let mut client = server.connect(postgres::NoTls)?;
+    let url = Url::parse(&format!(
+        "http://{}/api/webhook/materialize/public/events",
+        server.inner.http_local_addr()
+    ))?;
+
+    // Requests without credentials are made by the default HTTP user, which
+    // is not a superuser if it already exists.
+    client.batch_execute("CREATE ROLE anonymous_http_user LOGIN NOSUPERUSER")?;
+    client.batch_execute("CREATE SOURCE events FROM WEBHOOK")?;
+
+    let res = Client::new().post(url.clone()).body(r#"{"a": 1}"#).send()?;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        res.text()?,
+        "unauthorized: role \"anonymous_http_user\" does not have INSERT privilege on source \"materialize.public.events\""
+    );
+
+    client.batch_execute("GRANT INSERT ON events TO anonymous_http_user")?;
+    let res = Client::new().post(url).body(r#"{"a": 2}"#).send()?;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let count: i64 = client.query_one("SELECT count(*) FROM events", &[])?.get(0);
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
 // Test the /sql POST endpoint of the HTTP server.
 #[test]
 fn test_http_sql() -> Result<(), Box<dyn Error>> {
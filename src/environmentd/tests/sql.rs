@@ -1027,6 +1027,58 @@ fn test_temporary_views() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Test that a session whose role has been dropped is not a superuser.
+#[test]
+fn test_dropped_role_is_not_superuser() -> Result<(), Box<dyn Error>> {
+    mz_ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut admin = server.connect(postgres::NoTls)?;
+    let mut client = server
+        .pg_config()
+        .user("ephemeral")
+        .connect(postgres::NoTls)?;
+
+    // Roles created on login are superusers.
+    client.batch_execute("CREATE TABLE t (a int)")?;
+    admin.batch_execute("DROP TABLE t")?;
+
+    admin.batch_execute("DROP ROLE ephemeral")?;
+    let err = client
+        .batch_execute("CREATE TABLE t (a int)")
+        .unwrap_db_error();
+    assert_eq!(err.message(), "role \"ephemeral\" does not exist");
+
+    Ok(())
+}
+
+// Test that roles that are not superusers need the USAGE privilege to use
+// secrets in connections.
+#[test]
+fn test_usage_privilege() -> Result<(), Box<dyn Error>> {
+    mz_ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut admin = server.connect(postgres::NoTls)?;
+    admin.batch_execute("CREATE ROLE joe LOGIN NOSUPERUSER")?;
+    admin.batch_execute("GRANT CREATE ON SCHEMA public TO joe")?;
+    admin.batch_execute("CREATE SECRET pg_password AS 'password'")?;
+    let mut joe = server.pg_config().user("joe").connect(postgres::NoTls)?;
+
+    let create_connection = "CREATE CONNECTION pg TO POSTGRES (HOST 'localhost', \
+        DATABASE 'materialize', USER 'materialize', PASSWORD SECRET pg_password)";
+    let err = joe.batch_execute(create_connection).unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "unauthorized: role \"joe\" does not have USAGE privilege on secret \"materialize.public.pg_password\""
+    );
+
+    admin.batch_execute("GRANT USAGE ON pg_password TO joe")?;
+    joe.batch_execute(create_connection)?;
+
+    Ok(())
+}
+
 // Test EXPLAIN TIMESTAMP with tables. Mock time to verify initial table since
 // is now(), not 0.
 #[test]
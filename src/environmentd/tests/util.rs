@@ -189,6 +189,7 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
             suppress_output: true,
             data_dir: data_directory.clone(),
             command_wrapper: vec![],
+            secrets_encryption: None,
         }))?,
     );
     // Messing with the clock causes persist to expire leases, causing hangs and
@@ -139,10 +139,20 @@ impl fmt::Display for SourceInstanceId {
 
 /// Unique identifier for each part of a whole source.
 ///     Kafka -> partition
+///     Kafka, reading several topics -> topic and partition
+///     Kinesis -> shard
+///     NATS -> subject
+///     Postgres, initial snapshot -> upstream table OID
+///     MySQL -> UUID of the server on which transactions originate
 ///     None -> sources that have no notion of partitioning (e.g file sources)
 #[derive(Arbitrary, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum PartitionId {
     Kafka(i32),
+    KafkaTopic(String, i32),
+    Kinesis(String),
+    Nats(String),
+    PostgresSnapshot(u32),
+    MySqlServer(String),
     None,
 }
 
@@ -150,6 +160,11 @@ impl fmt::Display for PartitionId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             PartitionId::Kafka(id) => write!(f, "{}", id),
+            PartitionId::KafkaTopic(topic, id) => write!(f, "kafka:{}:{}", topic, id),
+            PartitionId::Kinesis(shard_id) => write!(f, "{}", shard_id),
+            PartitionId::Nats(subject) => write!(f, "nats:{}", subject),
+            PartitionId::PostgresSnapshot(oid) => write!(f, "pg-snapshot:{}", oid),
+            PartitionId::MySqlServer(uuid) => write!(f, "mysql:{}", uuid),
             PartitionId::None => write!(f, "none"),
         }
     }
@@ -170,6 +185,21 @@ impl FromStr for PartitionId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "none" => Ok(PartitionId::None),
+            s if s.starts_with("shardId-") => Ok(PartitionId::Kinesis(s.to_string())),
+            s if s.starts_with("nats:") => Ok(PartitionId::Nats(s["nats:".len()..].to_string())),
+            s if s.starts_with("pg-snapshot:") => Ok(PartitionId::PostgresSnapshot(
+                s["pg-snapshot:".len()..].parse()?,
+            )),
+            s if s.starts_with("mysql:") => {
+                Ok(PartitionId::MySqlServer(s["mysql:".len()..].to_string()))
+            }
+            s if s.starts_with("kafka:") => {
+                // Kafka topic names cannot contain colons.
+                let (topic, id) = s["kafka:".len()..]
+                    .rsplit_once(':')
+                    .ok_or_else(|| anyhow::anyhow!("invalid Kafka topic partition: {}", s))?;
+                Ok(PartitionId::KafkaTopic(topic.to_string(), id.parse()?))
+            }
             s => {
                 let val: i32 = s.parse()?;
                 Ok(PartitionId::Kafka(val))
@@ -184,6 +214,14 @@ impl RustType<ProtoPartitionId> for PartitionId {
         ProtoPartitionId {
             kind: Some(match self {
                 PartitionId::Kafka(x) => Kafka(*x),
+                PartitionId::KafkaTopic(topic, partition) => KafkaTopic(ProtoKafkaTopicPartition {
+                    topic: topic.clone(),
+                    partition: *partition,
+                }),
+                PartitionId::Kinesis(x) => Kinesis(x.clone()),
+                PartitionId::Nats(x) => Nats(x.clone()),
+                PartitionId::PostgresSnapshot(x) => PostgresSnapshot(*x),
+                PartitionId::MySqlServer(x) => MySqlServer(x.clone()),
                 PartitionId::None => None(()),
             }),
         }
@@ -193,6 +231,11 @@ impl RustType<ProtoPartitionId> for PartitionId {
         use proto_partition_id::Kind::*;
         match proto.kind {
             Option::Some(Kafka(x)) => Ok(PartitionId::Kafka(x)),
+            Option::Some(KafkaTopic(x)) => Ok(PartitionId::KafkaTopic(x.topic, x.partition)),
+            Option::Some(Kinesis(x)) => Ok(PartitionId::Kinesis(x)),
+            Option::Some(Nats(x)) => Ok(PartitionId::Nats(x)),
+            Option::Some(PostgresSnapshot(x)) => Ok(PartitionId::PostgresSnapshot(x)),
+            Option::Some(MySqlServer(x)) => Ok(PartitionId::MySqlServer(x)),
             Option::Some(None(_)) => Ok(PartitionId::None),
             Option::None => Err(TryFromProtoError::missing_field("ProtoPartitionId::kind")),
         }
@@ -10,7 +10,7 @@ use byteorder::{NetworkEndian, WriteBytesExt};
 use criterion::{black_box, Criterion, Throughput};
 
 use mz_avro::types::Value as AvroValue;
-use mz_interchange::avro::{parse_schema, Decoder};
+use mz_interchange::avro::{parse_schema, Decoder, OverflowPolicy};
 use mz_repr::adt::date::Date;
 use tokio::runtime::Runtime;
 
@@ -392,7 +392,14 @@ pub fn bench_avro(c: &mut Criterion) {
     buf.extend(mz_avro::to_avro_datum(&schema, record).unwrap());
     let len = buf.len() as u64;
 
-    let mut decoder = Decoder::new(schema_str, None, "avro_bench".to_string(), false).unwrap();
+    let mut decoder = Decoder::new(
+        schema_str,
+        None,
+        "avro_bench".to_string(),
+        false,
+        OverflowPolicy::Error,
+    )
+    .unwrap();
 
     let mut bg = c.benchmark_group("avro");
     bg.throughput(Throughput::Bytes(len));
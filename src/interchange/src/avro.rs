@@ -9,6 +9,7 @@
 
 use mz_avro::schema::{SchemaPiece, SchemaPieceOrNamed};
 
+mod conform;
 mod decode;
 mod encode;
 pub mod envelope_cdc_v2;
@@ -16,7 +17,8 @@ mod schema;
 
 pub use envelope_cdc_v2 as cdc_v2;
 
-pub use self::decode::{Decoder, DiffPair};
+pub use self::conform::Conformance;
+pub use self::decode::{Decoder, DiffPair, OverflowPolicy};
 pub use self::encode::{
     encode_datums_as_avro, encode_debezium_transaction_unchecked, get_debezium_transaction_schema,
     AvroEncoder, AvroSchemaGenerator,
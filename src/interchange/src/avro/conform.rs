@@ -0,0 +1,459 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Conformance of generated Avro values to user-supplied schemas.
+//!
+//! Sinks generate an Avro schema from the relation they write. Users may
+//! instead supply a schema agreed upon with their consumers, which must be
+//! able to represent every value of the generated schema without loss. Such a
+//! schema may order record fields and union variants differently, make
+//! non-nullable values nullable, and add nullable fields that are always
+//! written as `null`, but must otherwise agree with the generated schema,
+//! including on logical types.
+
+use anyhow::bail;
+
+use mz_avro::schema::{SchemaNode, SchemaPiece, SchemaPieceOrNamed, UnionSchema};
+use mz_avro::types::Value;
+use mz_avro::Schema;
+
+/// Describes how to rewrite values of a generated schema into values of a
+/// compatible user-supplied schema.
+#[derive(Clone, Debug)]
+pub struct Conformance(Node);
+
+#[derive(Clone, Debug)]
+enum Node {
+    /// Values are encoded identically by both schemas.
+    Identity,
+    /// A concrete value that the target schema wraps in a union.
+    Wrap {
+        variant: TargetVariant,
+        inner: Box<Node>,
+    },
+    /// A union value whose variants are at different positions in the target
+    /// union. The `i`th element describes the `i`th variant of the generated
+    /// union.
+    Union(Vec<(TargetVariant, Node)>),
+    /// A record whose fields are in a different order in the target record.
+    Record(Vec<TargetField>),
+    Array(Box<Node>),
+    Map(Box<Node>),
+}
+
+/// A variant of a union in the target schema.
+#[derive(Clone, Copy, Debug)]
+struct TargetVariant {
+    index: usize,
+    n_variants: usize,
+    null_variant: Option<usize>,
+}
+
+impl TargetVariant {
+    fn new(union: &UnionSchema, index: usize) -> Self {
+        TargetVariant {
+            index,
+            n_variants: union.variants().len(),
+            null_variant: null_variant(union),
+        }
+    }
+
+    fn wrap(self, inner: Value) -> Value {
+        Value::Union {
+            index: self.index,
+            inner: Box::new(inner),
+            n_variants: self.n_variants,
+            null_variant: self.null_variant,
+        }
+    }
+}
+
+/// A field of a record in the target schema.
+#[derive(Clone, Debug)]
+enum TargetField {
+    /// The field is written from the `index`th field of the generated record.
+    Generated { index: usize, node: Node },
+    /// The field has no counterpart in the generated record and is always
+    /// written as `null`.
+    Null {
+        name: String,
+        variant: TargetVariant,
+    },
+}
+
+impl Conformance {
+    /// Validates that every value of the `generated` schema can be faithfully
+    /// encoded with the `target` schema, and describes how to do so.
+    pub fn new(generated: &Schema, target: &Schema) -> Result<Self, anyhow::Error> {
+        let node = conform(generated.top_node(), target.top_node(), "value")?;
+        Ok(Conformance(node))
+    }
+
+    /// Rewrites a value of the generated schema into a value of the target
+    /// schema.
+    pub fn apply(&self, value: Value) -> Value {
+        apply(&self.0, value)
+    }
+}
+
+fn conform(generated: SchemaNode, target: SchemaNode, path: &str) -> Result<Node, anyhow::Error> {
+    match (generated.inner, target.inner) {
+        (SchemaPiece::Union(g), SchemaPiece::Union(t)) => {
+            let mut variants = Vec::with_capacity(g.variants().len());
+            let mut identity = g.variants().len() == t.variants().len();
+            for (i, variant) in g.variants().iter().enumerate() {
+                let variant = generated.step(variant);
+                let (index, node) = conform_variant(variant, target, t, path)?;
+                identity &= index == i && matches!(node, Node::Identity);
+                variants.push((TargetVariant::new(t, index), node));
+            }
+            Ok(if identity {
+                Node::Identity
+            } else {
+                Node::Union(variants)
+            })
+        }
+        (SchemaPiece::Union(_), _) => {
+            bail!("{path} is nullable, but its schema does not admit null")
+        }
+        (_, SchemaPiece::Union(t)) => {
+            let (index, node) = conform_variant(generated, target, t, path)?;
+            Ok(Node::Wrap {
+                variant: TargetVariant::new(t, index),
+                inner: Box::new(node),
+            })
+        }
+        (
+            SchemaPiece::Record { fields: g, .. },
+            SchemaPiece::Record {
+                fields: t,
+                lookup: t_lookup,
+                ..
+            },
+        ) => {
+            for field in g {
+                if !t_lookup.contains_key(&field.name) {
+                    bail!(
+                        "{path} has field {} with no counterpart in the schema",
+                        field.name
+                    );
+                }
+            }
+            let mut fields = Vec::with_capacity(t.len());
+            let mut identity = g.len() == t.len();
+            for (i, t_field) in t.iter().enumerate() {
+                let field_path = format!("{path}.{}", t_field.name);
+                let target_field = target.step(&t_field.schema);
+                match g.iter().position(|f| f.name == t_field.name) {
+                    Some(index) => {
+                        let node =
+                            conform(generated.step(&g[index].schema), target_field, &field_path)?;
+                        identity &= index == i && matches!(node, Node::Identity);
+                        fields.push(TargetField::Generated { index, node });
+                    }
+                    None => match target_field.inner {
+                        SchemaPiece::Union(t) => match null_variant(t) {
+                            Some(index) => {
+                                identity = false;
+                                fields.push(TargetField::Null {
+                                    name: t_field.name.clone(),
+                                    variant: TargetVariant::new(t, index),
+                                });
+                            }
+                            None => bail!("{field_path} does not correspond to any column"),
+                        },
+                        _ => bail!("{field_path} does not correspond to any column"),
+                    },
+                }
+            }
+            Ok(if identity {
+                Node::Identity
+            } else {
+                Node::Record(fields)
+            })
+        }
+        (SchemaPiece::Array(g), SchemaPiece::Array(t)) => {
+            let node = conform(
+                generated.step(g),
+                target.step(t),
+                &format!("{path} element"),
+            )?;
+            Ok(match node {
+                Node::Identity => Node::Identity,
+                node => Node::Array(Box::new(node)),
+            })
+        }
+        (SchemaPiece::Map(g), SchemaPiece::Map(t)) => {
+            let node = conform(generated.step(g), target.step(t), &format!("{path} value"))?;
+            Ok(match node {
+                Node::Identity => Node::Identity,
+                node => Node::Map(Box::new(node)),
+            })
+        }
+        (SchemaPiece::Fixed { size: g }, SchemaPiece::Fixed { size: t }) if g == t => {
+            Ok(Node::Identity)
+        }
+        (g, t) if is_scalar(g) && g == t => Ok(Node::Identity),
+        (g, t) => bail!(
+            "{path} has type {} in the schema, but must have type {}",
+            describe(t),
+            describe(g)
+        ),
+    }
+}
+
+/// Finds the variant of the target union `t` that values of `generated` are
+/// written as.
+fn conform_variant(
+    generated: SchemaNode,
+    target: SchemaNode,
+    t: &UnionSchema,
+    path: &str,
+) -> Result<(usize, Node), anyhow::Error> {
+    if matches!(generated.inner, SchemaPiece::Null) {
+        return match null_variant(t) {
+            Some(index) => Ok((index, Node::Identity)),
+            None => bail!("{path} is nullable, but its schema does not admit null"),
+        };
+    }
+    let mut errors = vec![];
+    for (index, variant) in t.variants().iter().enumerate() {
+        let variant = target.step(variant);
+        if matches!(variant.inner, SchemaPiece::Null) {
+            continue;
+        }
+        match conform(generated, variant, path) {
+            Ok(node) => return Ok((index, node)),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    match errors.len() {
+        1 => bail!("{}", errors.remove(0)),
+        _ => bail!(
+            "{path} must have type {}, but no variant of its union does",
+            describe(generated.inner)
+        ),
+    }
+}
+
+fn null_variant(union: &UnionSchema) -> Option<usize> {
+    union
+        .variants()
+        .iter()
+        .position(|v| matches!(v, SchemaPieceOrNamed::Piece(SchemaPiece::Null)))
+}
+
+/// Reports whether `piece` is encoded without reference to any other schema.
+fn is_scalar(piece: &SchemaPiece) -> bool {
+    matches!(
+        piece,
+        SchemaPiece::Null
+            | SchemaPiece::Boolean
+            | SchemaPiece::Int
+            | SchemaPiece::Long
+            | SchemaPiece::Float
+            | SchemaPiece::Double
+            | SchemaPiece::Date
+            | SchemaPiece::TimestampMilli
+            | SchemaPiece::TimestampMicro
+            | SchemaPiece::Decimal { .. }
+            | SchemaPiece::Bytes
+            | SchemaPiece::String
+            | SchemaPiece::Json
+            | SchemaPiece::Uuid
+    )
+}
+
+fn describe(piece: &SchemaPiece) -> String {
+    match piece {
+        SchemaPiece::Null => "null".into(),
+        SchemaPiece::Boolean => "boolean".into(),
+        SchemaPiece::Int => "int".into(),
+        SchemaPiece::Long => "long".into(),
+        SchemaPiece::Float => "float".into(),
+        SchemaPiece::Double => "double".into(),
+        SchemaPiece::Date => "int (logical type date)".into(),
+        SchemaPiece::TimestampMilli => "long (logical type timestamp-millis)".into(),
+        SchemaPiece::TimestampMicro => "long (logical type timestamp-micros)".into(),
+        SchemaPiece::Decimal {
+            precision,
+            scale,
+            fixed_size: None,
+        } => format!("bytes (logical type decimal({precision}, {scale}))"),
+        SchemaPiece::Decimal {
+            precision,
+            scale,
+            fixed_size: Some(size),
+        } => format!("fixed({size}) (logical type decimal({precision}, {scale}))"),
+        SchemaPiece::Bytes => "bytes".into(),
+        SchemaPiece::String => "string".into(),
+        SchemaPiece::Json => "string (connect.name io.debezium.data.Json)".into(),
+        SchemaPiece::Uuid => "string (logical type uuid)".into(),
+        SchemaPiece::Array(_) => "array".into(),
+        SchemaPiece::Map(_) => "map".into(),
+        SchemaPiece::Union(_) => "union".into(),
+        SchemaPiece::Record { .. } => "record".into(),
+        SchemaPiece::Enum { .. } => "enum".into(),
+        SchemaPiece::Fixed { size } => format!("fixed({size})"),
+        _ => format!("{:?}", piece),
+    }
+}
+
+fn apply(node: &Node, value: Value) -> Value {
+    match (node, value) {
+        (Node::Identity, value) => value,
+        (Node::Wrap { variant, inner }, value) => variant.wrap(apply(inner, value)),
+        (Node::Union(variants), Value::Union { index, inner, .. }) => {
+            let (variant, node) = &variants[index];
+            variant.wrap(apply(node, *inner))
+        }
+        (Node::Record(fields), Value::Record(generated)) => {
+            let mut generated: Vec<_> = generated.into_iter().map(Some).collect();
+            let fields = fields
+                .iter()
+                .map(|field| match field {
+                    TargetField::Generated { index, node } => {
+                        let (name, value) = generated[*index]
+                            .take()
+                            .expect("each generated field is written once");
+                        (name, apply(node, value))
+                    }
+                    TargetField::Null { name, variant } => {
+                        (name.clone(), variant.wrap(Value::Null))
+                    }
+                })
+                .collect();
+            Value::Record(fields)
+        }
+        (Node::Array(node), Value::Array(elements)) => {
+            Value::Array(elements.into_iter().map(|e| apply(node, e)).collect())
+        }
+        (Node::Map(node), Value::Map(mut map)) => {
+            for value in map.0.values_mut() {
+                let v = std::mem::replace(value, Value::Null);
+                *value = apply(node, v);
+            }
+            Value::Map(map)
+        }
+        (node, value) => panic!("value {:?} does not match conformance {:?}", value, node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_avro::types::Value;
+
+    use super::*;
+    use crate::avro::parse_schema;
+
+    fn generated() -> Schema {
+        parse_schema(
+            r#"{
+                "type": "record",
+                "name": "envelope",
+                "fields": [
+                    {"name": "a", "type": "long"},
+                    {"name": "b", "type": ["null", "string"]}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn identical_schemas_conform() {
+        let conformance = Conformance::new(&generated(), &generated()).unwrap();
+        assert!(matches!(conformance.0, Node::Identity));
+    }
+
+    #[test]
+    fn reordered_and_widened_schema_conforms() {
+        let target = parse_schema(
+            r#"{
+                "type": "record",
+                "name": "com.example.Row",
+                "fields": [
+                    {"name": "extra", "type": ["null", "int"], "default": null},
+                    {"name": "b", "type": ["string", "null"]},
+                    {"name": "a", "type": ["null", "long"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let conformance = Conformance::new(&generated(), &target).unwrap();
+        let value = Value::Record(vec![
+            ("a".into(), Value::Long(1)),
+            (
+                "b".into(),
+                Value::Union {
+                    index: 1,
+                    inner: Box::new(Value::String("x".into())),
+                    n_variants: 2,
+                    null_variant: Some(0),
+                },
+            ),
+        ]);
+        let value = conformance.apply(value);
+        let nullable = |index, inner, null_variant| Value::Union {
+            index,
+            inner: Box::new(inner),
+            n_variants: 2,
+            null_variant: Some(null_variant),
+        };
+        assert_eq!(
+            value,
+            Value::Record(vec![
+                ("extra".into(), nullable(0, Value::Null, 0)),
+                ("b".into(), nullable(0, Value::String("x".into()), 1)),
+                ("a".into(), nullable(1, Value::Long(1), 0)),
+            ])
+        );
+        let mut buf = vec![];
+        mz_avro::encode_unchecked(&value, &target, &mut buf);
+        assert!(!buf.is_empty());
+    }
+
+    #[test]
+    fn incompatible_schemas_are_rejected() {
+        for (target, err) in [
+            (
+                r#"{"type": "record", "name": "r", "fields": [
+                    {"name": "a", "type": "long"},
+                    {"name": "b", "type": "string"}
+                ]}"#,
+                "value.b is nullable, but its schema does not admit null",
+            ),
+            (
+                r#"{"type": "record", "name": "r", "fields": [
+                    {"name": "a", "type": "int"},
+                    {"name": "b", "type": ["null", "string"]}
+                ]}"#,
+                "value.a has type int in the schema, but must have type long",
+            ),
+            (
+                r#"{"type": "record", "name": "r", "fields": [
+                    {"name": "b", "type": ["null", "string"]}
+                ]}"#,
+                "value has field a with no counterpart in the schema",
+            ),
+            (
+                r#"{"type": "record", "name": "r", "fields": [
+                    {"name": "a", "type": "long"},
+                    {"name": "b", "type": ["null", "string"]},
+                    {"name": "c", "type": "long"}
+                ]}"#,
+                "value.c does not correspond to any column",
+            ),
+        ] {
+            let target = parse_schema(target).unwrap();
+            let e = Conformance::new(&generated(), &target).unwrap_err();
+            assert_eq!(e.to_string(), err);
+        }
+    }
+}
@@ -10,7 +10,7 @@
 use anyhow::Context;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::timestamp::CheckedTimestamp;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::BTreeMap;
 use std::io::Read;
 use std::rc::Rc;
@@ -32,6 +32,35 @@ use mz_repr::{Datum, Row, RowPacker};
 
 use crate::avro::ConfluentAvroResolver;
 
+/// How to decode values that do not fit the type of the column they are
+/// decoded into, e.g. decimals that exceed the maximum precision of `numeric`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Fail to decode the record.
+    Error,
+    /// Decode the value as `NULL`.
+    Null,
+    /// Decode the value as the closest value that fits the column.
+    Saturate,
+}
+
+/// The overflow policy of a decoder, along with the number of values to which
+/// it has been applied.
+#[derive(Debug)]
+pub struct Overflow {
+    policy: OverflowPolicy,
+    count: Cell<u64>,
+}
+
+impl Overflow {
+    pub fn new(policy: OverflowPolicy) -> Overflow {
+        Overflow {
+            policy,
+            count: Cell::new(0),
+        }
+    }
+}
+
 /// Manages decoding of Avro-encoded bytes.
 #[derive(Debug)]
 pub struct Decoder {
@@ -39,13 +68,15 @@ pub struct Decoder {
     debug_name: String,
     buf1: Vec<u8>,
     row_buf: Row,
+    overflow: Overflow,
 }
 
 #[cfg(test)]
 mod tests {
+    use mz_repr::adt::date::Date;
     use mz_repr::{Datum, Row};
 
-    use crate::avro::Decoder;
+    use crate::avro::{Decoder, OverflowPolicy};
 
     #[tokio::test]
     async fn test_error_followed_by_success() {
@@ -54,7 +85,14 @@ mod tests {
 "name": "test",
 "fields": [{"name": "f1", "type": "int"}, {"name": "f2", "type": "int"}]
 }"#;
-        let mut decoder = Decoder::new(schema, None, "Test".to_string(), false).unwrap();
+        let mut decoder = Decoder::new(
+            schema,
+            None,
+            "Test".to_string(),
+            false,
+            OverflowPolicy::Error,
+        )
+        .unwrap();
         // This is not a valid Avro blob for the given schema
         let mut bad_bytes: &[u8] = &[0];
         assert!(decoder.decode(&mut bad_bytes).await.is_err());
@@ -66,6 +104,58 @@ mod tests {
             Row::pack([Datum::Int32(0), Datum::Int32(0)])
         );
     }
+
+    #[tokio::test]
+    async fn test_date_overflow() {
+        let schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [{"name": "f1", "type": {"type": "int", "logicalType": "date"}}]
+}"#;
+        // The zig-zag encoding of `i32::MAX` days since the Unix epoch, which
+        // is after the largest supported date.
+        let bytes: &[u8] = &[0xfe, 0xff, 0xff, 0xff, 0x0f];
+
+        let mut decoder = Decoder::new(
+            schema,
+            None,
+            "Test".to_string(),
+            false,
+            OverflowPolicy::Error,
+        )
+        .unwrap();
+        assert!(decoder.decode(&mut &bytes[..]).await.is_err());
+        assert_eq!(decoder.take_overflows(), 0);
+
+        let mut decoder = Decoder::new(
+            schema,
+            None,
+            "Test".to_string(),
+            false,
+            OverflowPolicy::Null,
+        )
+        .unwrap();
+        assert_eq!(
+            decoder.decode(&mut &bytes[..]).await.unwrap(),
+            Row::pack([Datum::Null])
+        );
+        assert_eq!(decoder.take_overflows(), 1);
+        assert_eq!(decoder.take_overflows(), 0);
+
+        let mut decoder = Decoder::new(
+            schema,
+            None,
+            "Test".to_string(),
+            false,
+            OverflowPolicy::Saturate,
+        )
+        .unwrap();
+        assert_eq!(
+            decoder.decode(&mut &bytes[..]).await.unwrap(),
+            Row::pack([Datum::Date(Date::from_pg_epoch(Date::HIGH_DAYS).unwrap())])
+        );
+        assert_eq!(decoder.take_overflows(), 1);
+    }
 }
 
 impl Decoder {
@@ -79,6 +169,7 @@ impl Decoder {
         ccsr_client: Option<mz_ccsr::Client>,
         debug_name: String,
         confluent_wire_format: bool,
+        overflow_policy: OverflowPolicy,
     ) -> anyhow::Result<Decoder> {
         let csr_avro =
             ConfluentAvroResolver::new(reader_schema, ccsr_client, confluent_wire_format)?;
@@ -88,9 +179,16 @@ impl Decoder {
             debug_name,
             buf1: vec![],
             row_buf: Row::default(),
+            overflow: Overflow::new(overflow_policy),
         })
     }
 
+    /// Returns the number of values to which the overflow policy has been
+    /// applied since the last call, and resets it.
+    pub fn take_overflows(&mut self) -> u64 {
+        self.overflow.count.take()
+    }
+
     /// Decodes Avro-encoded `bytes` into a `Row`.
     pub async fn decode(&mut self, bytes: &mut &[u8]) -> anyhow::Result<Row> {
         // Clear out any bytes that might be left over from
@@ -104,11 +202,18 @@ impl Decoder {
             packer: &mut packer,
             buf: &mut self.buf1,
             is_top: true,
+            overflow: &self.overflow,
         };
         let dsr = GeneralDeserializer {
             schema: resolved_schema.top_node(),
         };
-        dsr.deserialize(bytes, dec).with_context(|| {
+        // Values in records that fail to decode are not counted.
+        let overflows = self.overflow.count.get();
+        let result = dsr.deserialize(bytes, dec);
+        if result.is_err() {
+            self.overflow.count.set(overflows);
+        }
+        result.with_context(|| {
             format!(
                 "unable to decode row {}",
                 match csr_schema_id {
@@ -156,6 +261,7 @@ impl<'a> AvroDecode for AvroStringDecoder<'a> {
 pub(super) struct OptionalRecordDecoder<'a, 'row> {
     pub packer: &'a mut RowPacker<'row>,
     pub buf: &'a mut Vec<u8>,
+    pub overflow: &'a Overflow,
 }
 
 impl<'a, 'row> AvroDecode for OptionalRecordDecoder<'a, 'row> {
@@ -176,6 +282,7 @@ impl<'a, 'row> AvroDecode for OptionalRecordDecoder<'a, 'row> {
                 packer: self.packer,
                 buf: self.buf,
                 is_top: false,
+                overflow: self.overflow,
             };
             deserializer.deserialize(reader, d)?;
             Ok(true)
@@ -199,10 +306,12 @@ impl AvroDecode for RowDecoder {
         let mut row_borrow = self.state.0.borrow_mut();
         let mut buf_borrow = self.state.1.borrow_mut();
         let mut packer = row_borrow.packer();
+        let overflow = Overflow::new(OverflowPolicy::Error);
         let inner = AvroFlatDecoder {
             packer: &mut packer,
             buf: &mut buf_borrow,
             is_top: true,
+            overflow: &overflow,
         };
         inner.record(a)?;
         Ok(RowWrapper(row_borrow.clone()))
@@ -232,6 +341,21 @@ pub struct AvroFlatDecoder<'a, 'row> {
     pub packer: &'a mut RowPacker<'row>,
     pub buf: &'a mut Vec<u8>,
     pub is_top: bool,
+    pub overflow: &'a Overflow,
+}
+
+impl<'a, 'row> AvroFlatDecoder<'a, 'row> {
+    /// Handles a value that does not fit the type of its column according to
+    /// the overflow policy, where `saturated` is the closest value that does.
+    fn push_overflowed(self, saturated: Datum, err: DecodeError) -> Result<(), AvroError> {
+        match self.overflow.policy {
+            OverflowPolicy::Error => return Err(AvroError::Decode(err)),
+            OverflowPolicy::Null => self.packer.push(Datum::Null),
+            OverflowPolicy::Saturate => self.packer.push(saturated),
+        }
+        self.overflow.count.set(self.overflow.count.get() + 1);
+        Ok(())
+    }
 }
 
 impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
@@ -261,6 +385,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                         packer: rp,
                         buf: &mut str_buf,
                         is_top: false,
+                        overflow: self.overflow,
                     })?;
                 } else {
                     let val = f.decode_field(ValueDecoder)?;
@@ -275,6 +400,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                     packer: rp,
                     buf: &mut str_buf,
                     is_top: false,
+                    overflow: self.overflow,
                 };
                 give_value(dec, &val)?;
             }
@@ -308,6 +434,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                     packer: self.packer,
                     buf: self.buf,
                     is_top: false,
+                    overflow: self.overflow,
                 };
                 if null_variant != Some(i) {
                     if i == idx {
@@ -345,13 +472,35 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
             mz_avro::types::Scalar::Double(val) => {
                 self.packer.push(Datum::Float64(OrderedFloat(val)))
             }
-            mz_avro::types::Scalar::Date(val) => self.packer.push(Datum::Date(
-                Date::from_unix_epoch(val).map_err(|_| DecodeError::DateOutOfRange(val))?,
-            )),
-            mz_avro::types::Scalar::Timestamp(val) => self.packer.push(Datum::Timestamp(
-                CheckedTimestamp::from_timestamplike(val)
-                    .map_err(|_| DecodeError::TimestampOutOfRange(val))?,
-            )),
+            mz_avro::types::Scalar::Date(val) => match Date::from_unix_epoch(val) {
+                Ok(date) => self.packer.push(Datum::Date(date)),
+                Err(_) => {
+                    let days = if val < 0 {
+                        Date::LOW_DAYS
+                    } else {
+                        Date::HIGH_DAYS
+                    };
+                    let saturated = Date::from_pg_epoch(days).expect("days are in range");
+                    return self
+                        .push_overflowed(Datum::Date(saturated), DecodeError::DateOutOfRange(val));
+                }
+            },
+            mz_avro::types::Scalar::Timestamp(val) => {
+                match CheckedTimestamp::from_timestamplike(val) {
+                    Ok(ts) => self.packer.push(Datum::Timestamp(ts)),
+                    Err(_) => {
+                        let saturated = if val.timestamp() < 0 {
+                            CheckedTimestamp::min()
+                        } else {
+                            CheckedTimestamp::max()
+                        };
+                        return self.push_overflowed(
+                            Datum::Timestamp(saturated),
+                            DecodeError::TimestampOutOfRange(val),
+                        );
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -380,20 +529,44 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
             ))
         })?;
 
-        let n = numeric::twos_complement_be_to_numeric(&mut buf, scale)
-            .map_err_to_string()
-            .map_err(DecodeError::Custom)?;
-
-        if n.is_special()
-            || numeric::get_precision(&n) > u32::from(numeric::NUMERIC_DATUM_MAX_PRECISION)
-        {
-            return Err(AvroError::Decode(DecodeError::Custom(format!(
-                "Error decoding numeric: exceeds maximum precision {}",
-                numeric::NUMERIC_DATUM_MAX_PRECISION
-            ))));
-        }
+        // The sign must be determined before the conversion, which negates
+        // negative values in place.
+        let is_negative = buf.first().map_or(false, |b| b & 0x80 != 0);
+        let n = match numeric::twos_complement_be_to_numeric(&mut buf, scale) {
+            Ok(n) => Some(n),
+            Err(e) if self.overflow.policy == OverflowPolicy::Error => {
+                return Err(AvroError::Decode(DecodeError::Custom(format!("{:#}", e))));
+            }
+            Err(_) => None,
+        };
 
-        self.packer.push(Datum::from(n));
+        match n {
+            Some(n)
+                if !n.is_special()
+                    && numeric::get_precision(&n)
+                        <= u32::from(numeric::NUMERIC_DATUM_MAX_PRECISION) =>
+            {
+                self.packer.push(Datum::from(n))
+            }
+            _ => {
+                // The largest magnitude that fits is all nines at the
+                // decimal's scale.
+                let mut cx = numeric::cx_datum();
+                let nines = "9".repeat(usize::from(numeric::NUMERIC_DATUM_MAX_PRECISION));
+                let mut saturated = cx.parse(nines.as_str()).expect("valid numeric");
+                saturated.set_exponent(-i32::from(scale));
+                if is_negative {
+                    cx.neg(&mut saturated);
+                }
+                return self.push_overflowed(
+                    Datum::from(saturated),
+                    DecodeError::Custom(format!(
+                        "Error decoding numeric: exceeds maximum precision {}",
+                        numeric::NUMERIC_DATUM_MAX_PRECISION
+                    )),
+                );
+            }
+        }
 
         Ok(())
     }
@@ -493,6 +666,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                     packer: rp,
                     buf: &mut str_buf,
                     is_top: false,
+                    overflow: self.overflow,
                 };
                 if a.decode_next(next)?.is_none() {
                     break;
@@ -519,6 +693,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                             packer,
                             buf: &mut vec![],
                             is_top: false,
+                            overflow: self.overflow,
                         },
                         &val,
                     )?;
@@ -23,6 +23,7 @@ use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::numeric::{self, NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 
+use crate::avro::Conformance;
 use crate::encode::{column_names_and_types, Encode, TypedDatum};
 use crate::envelopes::{self, ENVELOPE_CUSTOM_NAMES};
 use crate::json::build_row_schema_json;
@@ -82,7 +83,7 @@ static DEBEZIUM_TRANSACTION_SCHEMA: Lazy<Schema> = Lazy::new(|| {
     .expect("valid schema constructed")
 });
 
-fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
+pub(super) fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
     // The first byte is a magic byte (0) that indicates the Confluent
     // serialization format version, and the next four bytes are a
     // 32-bit schema ID.
@@ -126,6 +127,20 @@ impl AvroSchemaGenerator {
         value_desc: RelationDesc,
         debezium: bool,
     ) -> Self {
+        Self::try_new(key_fullname, value_fullname, key_desc, value_desc, debezium)
+            .expect("valid schema constructed")
+    }
+
+    /// Like [`AvroSchemaGenerator::new`], but returns an error rather than
+    /// panicking if the fullnames do not produce valid schemas, e.g. because
+    /// they are malformed or collide with the names of nested records.
+    pub fn try_new(
+        key_fullname: Option<&str>,
+        value_fullname: Option<&str>,
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        debezium: bool,
+    ) -> Result<Self, anyhow::Error> {
         let mut value_columns = column_names_and_types(value_desc);
         if debezium {
             value_columns = envelopes::dbz_envelope(value_columns);
@@ -135,21 +150,24 @@ impl AvroSchemaGenerator {
             value_fullname.unwrap_or("envelope"),
             &ENVELOPE_CUSTOM_NAMES,
         );
-        let writer_schema = Schema::parse(&row_schema).expect("valid schema constructed");
-        let key_info = key_desc.map(|key_desc| {
-            let columns = column_names_and_types(key_desc);
-            let row_schema =
-                build_row_schema_json(&columns, key_fullname.unwrap_or("row"), &HashMap::new());
-            KeyInfo {
-                schema: Schema::parse(&row_schema).expect("valid schema constructed"),
-                columns,
+        let writer_schema = Schema::parse(&row_schema)?;
+        let key_info = match key_desc {
+            Some(key_desc) => {
+                let columns = column_names_and_types(key_desc);
+                let row_schema =
+                    build_row_schema_json(&columns, key_fullname.unwrap_or("row"), &HashMap::new());
+                Some(KeyInfo {
+                    schema: Schema::parse(&row_schema)?,
+                    columns,
+                })
             }
-        });
-        AvroSchemaGenerator {
+            None => None,
+        };
+        Ok(AvroSchemaGenerator {
             value_columns,
             key_info,
             writer_schema,
-        }
+        })
     }
 
     pub fn value_writer_schema(&self) -> &Schema {
@@ -184,6 +202,9 @@ pub struct AvroEncoder {
     schema_generator: AvroSchemaGenerator,
     key_schema_id: Option<i32>,
     value_schema_id: i32,
+    /// The user-supplied value schema, if any, and how to conform the values
+    /// of the generated value schema to it.
+    value_schema: Option<(Schema, Conformance)>,
 }
 
 impl fmt::Debug for AvroEncoder {
@@ -204,9 +225,22 @@ impl AvroEncoder {
             schema_generator,
             key_schema_id,
             value_schema_id,
+            value_schema: None,
         }
     }
 
+    /// Encodes values with `value_schema` rather than the generated value
+    /// schema.
+    ///
+    /// Returns an error if `value_schema` cannot faithfully represent every
+    /// value of the generated value schema.
+    pub fn with_value_schema(mut self, value_schema: Schema) -> Result<Self, anyhow::Error> {
+        let conformance =
+            Conformance::new(self.schema_generator.value_writer_schema(), &value_schema)?;
+        self.value_schema = Some((value_schema, conformance));
+        Ok(self)
+    }
+
     pub fn encode_key_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
         let schema = self.schema_generator.key_writer_schema().unwrap();
         let columns = self.schema_generator.key_columns().unwrap();
@@ -214,9 +248,20 @@ impl AvroEncoder {
     }
 
     pub fn encode_value_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
-        let schema = self.schema_generator.value_writer_schema();
         let columns = self.schema_generator.value_columns();
-        encode_message_unchecked(schema_id, row, schema, columns)
+        match &self.value_schema {
+            None => {
+                let schema = self.schema_generator.value_writer_schema();
+                encode_message_unchecked(schema_id, row, schema, columns)
+            }
+            Some((schema, conformance)) => {
+                let mut buf = vec![];
+                encode_avro_header(&mut buf, schema_id);
+                let value = conformance.apply(encode_datums_as_avro(row.iter(), columns));
+                mz_avro::encode_unchecked(&value, schema, &mut buf);
+                buf
+            }
+        }
     }
 }
 
@@ -134,7 +134,7 @@ pub static ENVELOPE_CUSTOM_NAMES: Lazy<HashMap<GlobalId, String>> = Lazy::new(||
     }
 });
 
-pub(crate) fn dbz_envelope(
+pub fn dbz_envelope(
     names_and_types: Vec<(ColumnName, ColumnType)>,
 ) -> Vec<(ColumnName, ColumnType)> {
     let row = ColumnType {
@@ -161,10 +161,39 @@ pub fn dbz_format(rp: &mut RowPacker, dp: DiffPair<Row>) {
 }
 
 pub fn upsert_format(dps: Vec<DiffPair<Row>>, sink_id: GlobalId, from: GlobalId) -> Option<Row> {
-    let dp = dps.expect_element(format!(
+    upsert_diff_pair(dps, sink_id, from).after
+}
+
+/// Like [`upsert_format`], but renders a delete as the deleted row instead of
+/// as `None`, and appends to every row a boolean that is true if and only if
+/// the row was deleted.
+pub fn upsert_delete_record_format(
+    rp: &mut RowPacker,
+    dps: Vec<DiffPair<Row>>,
+    sink_id: GlobalId,
+    from: GlobalId,
+) {
+    let (row, deleted) = match upsert_diff_pair(dps, sink_id, from) {
+        DiffPair {
+            after: Some(after), ..
+        } => (after, false),
+        DiffPair {
+            before: Some(before),
+            after: None,
+        } => (before, true),
+        DiffPair {
+            before: None,
+            after: None,
+        } => unreachable!("diff pairs have a before or an after record"),
+    };
+    rp.extend_by_row(&row);
+    rp.push(Datum::from(deleted));
+}
+
+fn upsert_diff_pair(dps: Vec<DiffPair<Row>>, sink_id: GlobalId, from: GlobalId) -> DiffPair<Row> {
+    dps.expect_element(format!(
         "primary key error: expected at most one update per key and timestamp \
           This can happen when the configured sink key is not a primary key of \
           the sinked relation: sink {sink_id} created from {from}."
-    ));
-    dp.after
+    ))
 }
@@ -10,6 +10,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use byteorder::{NetworkEndian, WriteBytesExt};
 use serde_json::{json, Map};
 
 use mz_repr::adt::char;
@@ -24,6 +25,8 @@ use crate::envelopes;
 pub struct JsonEncoder {
     key_columns: Option<Vec<(ColumnName, ColumnType)>>,
     value_columns: Vec<(ColumnName, ColumnType)>,
+    key_schema_id: Option<i32>,
+    value_schema_id: Option<i32>,
 }
 
 impl JsonEncoder {
@@ -39,9 +42,31 @@ impl JsonEncoder {
                 None
             },
             value_columns,
+            key_schema_id: None,
+            value_schema_id: None,
         }
     }
 
+    /// Prefixes the encoded keys and values with the IDs of their schemas in
+    /// a schema registry, in the Confluent wire format.
+    pub fn with_schema_ids(mut self, key_schema_id: Option<i32>, value_schema_id: i32) -> Self {
+        self.key_schema_id = key_schema_id;
+        self.value_schema_id = Some(value_schema_id);
+        self
+    }
+
+    /// Returns the JSON Schema of the encoded keys, if any.
+    pub fn key_json_schema(&self) -> Option<String> {
+        self.key_columns
+            .as_ref()
+            .map(|columns| build_row_json_schema(columns).to_string())
+    }
+
+    /// Returns the JSON Schema of the encoded values.
+    pub fn value_json_schema(&self) -> String {
+        build_row_json_schema(&self.value_columns).to_string()
+    }
+
     pub fn encode_row(
         &self,
         row: mz_repr::Row,
@@ -50,6 +75,25 @@ impl JsonEncoder {
         let value = encode_datums_as_json(row.iter(), names_types);
         value.to_string().into_bytes()
     }
+
+    fn encode_row_with_schema_id(
+        &self,
+        schema_id: Option<i32>,
+        row: mz_repr::Row,
+        names_types: &[(ColumnName, ColumnType)],
+    ) -> Vec<u8> {
+        let mut buf = vec![];
+        if let Some(schema_id) = schema_id {
+            // The Confluent wire format for JSON Schema is a magic byte (0)
+            // followed by the 32-bit schema ID.
+            buf.write_u8(0).expect("writing to vec cannot fail");
+            buf.write_i32::<NetworkEndian>(schema_id)
+                .expect("writing to vec cannot fail");
+        }
+        let value = encode_datums_as_json(row.iter(), names_types);
+        serde_json::to_writer(&mut buf, &value).expect("writing to vec cannot fail");
+        buf
+    }
 }
 
 impl Encode for JsonEncoder {
@@ -58,14 +102,15 @@ impl Encode for JsonEncoder {
     }
 
     fn encode_key_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(
+        self.encode_row_with_schema_id(
+            self.key_schema_id,
             row,
             self.key_columns.as_ref().expect("key schema must exist"),
         )
     }
 
     fn encode_value_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(row, &self.value_columns)
+        self.encode_row_with_schema_id(self.value_schema_id, row, &self.value_columns)
     }
 }
 
@@ -213,6 +258,82 @@ impl ToJson for TypedDatum<'_> {
     }
 }
 
+/// Builds the JSON Schema of rows with the given columns, as encoded by
+/// [`encode_datums_as_json`].
+pub fn build_row_json_schema(columns: &[(ColumnName, ColumnType)]) -> serde_json::Value {
+    let mut schema = build_record_json_schema(columns);
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    schema
+}
+
+fn build_record_json_schema(columns: &[(ColumnName, ColumnType)]) -> serde_json::Value {
+    let properties: Map<String, serde_json::Value> = columns
+        .iter()
+        .map(|(name, typ)| (name.to_string(), build_json_schema_field(typ)))
+        .collect();
+    let required: Vec<_> = columns.iter().map(|(name, _typ)| name.as_str()).collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn build_json_schema_field(typ: &ColumnType) -> serde_json::Value {
+    let schema = match &typ.scalar_type {
+        ScalarType::Bool => json!({"type": "boolean"}),
+        ScalarType::PgLegacyChar
+        | ScalarType::Int16
+        | ScalarType::Int32
+        | ScalarType::Int64
+        | ScalarType::UInt16
+        | ScalarType::UInt32
+        | ScalarType::UInt64
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => json!({"type": "integer"}),
+        ScalarType::Float32 | ScalarType::Float64 => json!({"type": "number"}),
+        ScalarType::Uuid => json!({"type": "string", "format": "uuid"}),
+        // Timestamps are encoded as the number of milliseconds since the Unix
+        // epoch, in a string.
+        ScalarType::Numeric { .. }
+        | ScalarType::Date
+        | ScalarType::Time
+        | ScalarType::Timestamp
+        | ScalarType::TimestampTz
+        | ScalarType::Interval
+        | ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::MzTimestamp => json!({"type": "string"}),
+        ScalarType::Bytes => json!({"type": "array", "items": {"type": "integer"}}),
+        // Any JSON value is a valid `jsonb` value, including null.
+        ScalarType::Jsonb => return json!({}),
+        ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
+            let items = build_json_schema_field(&ColumnType {
+                nullable: true,
+                scalar_type: ty.unwrap_collection_element_type().clone(),
+            });
+            json!({"type": "array", "items": items})
+        }
+        ScalarType::Map { value_type, .. } => {
+            let values = build_json_schema_field(&ColumnType {
+                nullable: true,
+                scalar_type: (**value_type).clone(),
+            });
+            json!({"type": "object", "additionalProperties": values})
+        }
+        ScalarType::Record { fields, .. } => build_record_json_schema(fields),
+    };
+    if typ.nullable {
+        json!({"oneOf": [{"type": "null"}, schema]})
+    } else {
+        schema
+    }
+}
+
 fn build_row_schema_field<F: FnMut() -> String>(
     namer: &mut F,
     names_seen: &mut HashSet<String>,
@@ -400,3 +521,76 @@ pub fn build_row_schema_json(
         "name": name
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_json_schema() {
+        let columns = vec![
+            ("id".into(), ScalarType::Int32.nullable(false)),
+            ("name".into(), ScalarType::String.nullable(true)),
+            (
+                "tags".into(),
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_id: None,
+                }
+                .nullable(false),
+            ),
+            (
+                "attrs".into(),
+                ScalarType::Map {
+                    value_type: Box::new(ScalarType::Float64),
+                    custom_id: None,
+                }
+                .nullable(false),
+            ),
+            (
+                "point".into(),
+                ScalarType::Record {
+                    fields: vec![
+                        ("x".into(), ScalarType::Int64.nullable(false)),
+                        ("y".into(), ScalarType::Int64.nullable(false)),
+                    ],
+                    custom_id: None,
+                }
+                .nullable(true),
+            ),
+            ("doc".into(), ScalarType::Jsonb.nullable(true)),
+        ];
+        let nullable = |schema: serde_json::Value| json!({"oneOf": [{"type": "null"}, schema]});
+        assert_eq!(
+            build_row_json_schema(&columns),
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "name": nullable(json!({"type": "string"})),
+                    "tags": {
+                        "type": "array",
+                        "items": nullable(json!({"type": "string"})),
+                    },
+                    "attrs": {
+                        "type": "object",
+                        "additionalProperties": nullable(json!({"type": "number"})),
+                    },
+                    "point": nullable(json!({
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "integer"},
+                            "y": {"type": "integer"},
+                        },
+                        "required": ["x", "y"],
+                        "additionalProperties": false,
+                    })),
+                    "doc": {},
+                },
+                "required": ["id", "name", "tags", "attrs", "point", "doc"],
+                "additionalProperties": false,
+            })
+        );
+    }
+}
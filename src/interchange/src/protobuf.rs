@@ -19,6 +19,10 @@ use prost_reflect::{
 use mz_ore::str::StrExt;
 use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
 
+mod encode;
+
+pub use self::encode::{ProtobufEncoder, ProtobufSchemaGenerator};
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
@@ -69,6 +73,9 @@ pub struct Decoder {
     descriptors: DecodedDescriptors,
     row: Row,
     confluent_wire_format: bool,
+    /// The ID of the schema with which the most recently decoded message was
+    /// written, if messages are in the Confluent wire format.
+    schema_id: Option<i32>,
 }
 
 impl Decoder {
@@ -81,11 +88,22 @@ impl Decoder {
             descriptors,
             row: Row::default(),
             confluent_wire_format,
+            schema_id: None,
         })
     }
 
+    /// Returns the ID of the schema with which the most recently decoded
+    /// message was written, according to its Confluent wire format header.
+    ///
+    /// Returns `None` if the messages are not in the Confluent wire format, or
+    /// if the header of the most recent message could not be read.
+    pub fn schema_id(&self) -> Option<i32> {
+        self.schema_id
+    }
+
     /// Decodes the encoded Protobuf message into a [`Row`].
     pub fn decode(&mut self, mut bytes: &[u8]) -> Result<Option<Row>, anyhow::Error> {
+        self.schema_id = None;
         if self.confluent_wire_format {
             // We support Protobuf schema evolution by ignoring the schema that
             // the message was written with and attempting to decode into the
@@ -100,13 +118,21 @@ impl Decoder {
             // rules are complex and the Protobuf format is self-describing
             // enough that decoding an Protobuf message with an incompatible
             // schema is handled gracefully (e.g., no accidentally massive
-            // allocations).
+            // allocations). The ID of the schema that the message was written
+            // with is still recorded, so that it can be reported.
             //
             // [0]: https://developers.google.com/protocol-buffers/docs/overview
-            let (_schema_id, adjusted_bytes) = crate::confluent::extract_protobuf_header(bytes)?;
+            let (schema_id, adjusted_bytes) = crate::confluent::extract_protobuf_header(bytes)?;
+            self.schema_id = Some(schema_id);
             bytes = adjusted_bytes;
         }
-        let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
+        let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes);
+        let message = match self.schema_id {
+            Some(id) => message.with_context(|| {
+                format!("unable to decode message (Protobuf schema id = {})", id)
+            })?,
+            None => message?,
+        };
         let mut packer = self.row.packer();
         pack_message(&mut packer, &message)?;
         Ok(Some(self.row.clone()))
@@ -0,0 +1,515 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use byteorder::{NetworkEndian, WriteBytesExt};
+use chrono::Timelike;
+use prost::encoding::{self, WireType};
+
+use mz_repr::adt::char;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
+
+const PROTOBUF_PACKAGE: &str = "com.materialize.sink";
+
+/// Generates the Protobuf schemas for the keys and values of a sink.
+///
+/// Each schema is a `.proto` file whose first message describes the key or
+/// value. Columns are numbered by their position, starting at one. Types
+/// without a Protobuf counterpart are encoded as follows:
+///
+///   * dates as `int32` days since the Unix epoch,
+///   * times as `int64` microseconds since midnight,
+///   * timestamps as `int64` microseconds since the Unix epoch,
+///   * numerics, intervals, `jsonb`, UUIDs, and `mz_timestamp`s as their
+///     text representation,
+///   * records as nested messages, and
+///   * maps as `map<string, ...>` fields.
+///
+/// Nullable scalar columns are `optional` fields. Protobuf cannot represent
+/// null lists or maps, nor null elements of them, so the former are encoded
+/// as empty and the latter as default values. Lists of lists, and maps of
+/// lists or maps, wrap their elements in a message with a single field.
+#[derive(Debug)]
+pub struct ProtobufSchemaGenerator {
+    key: Option<MessageSchema>,
+    value: MessageSchema,
+}
+
+impl ProtobufSchemaGenerator {
+    pub fn new(key_desc: Option<RelationDesc>, value_desc: RelationDesc, debezium: bool) -> Self {
+        let mut value_columns = column_names_and_types(value_desc);
+        if debezium {
+            value_columns = envelopes::dbz_envelope(value_columns);
+        }
+        ProtobufSchemaGenerator {
+            key: key_desc.map(|desc| MessageSchema::new("Key", &column_names_and_types(desc))),
+            value: MessageSchema::new("Value", &value_columns),
+        }
+    }
+
+    /// Returns the `.proto` schema of the keys, if any.
+    pub fn key_schema(&self) -> Option<String> {
+        self.key.as_ref().map(|key| key.to_string())
+    }
+
+    /// Returns the `.proto` schema of the values.
+    pub fn value_schema(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Encodes rows as Protobuf messages, in the Confluent wire format.
+#[derive(Debug)]
+pub struct ProtobufEncoder {
+    schema_generator: ProtobufSchemaGenerator,
+    key_schema_id: Option<i32>,
+    value_schema_id: i32,
+}
+
+impl ProtobufEncoder {
+    pub fn new(
+        schema_generator: ProtobufSchemaGenerator,
+        key_schema_id: Option<i32>,
+        value_schema_id: i32,
+    ) -> Self {
+        ProtobufEncoder {
+            schema_generator,
+            key_schema_id,
+            value_schema_id,
+        }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        let schema = self
+            .schema_generator
+            .key
+            .as_ref()
+            .expect("key schema must exist");
+        schema.encode(self.key_schema_id.expect("key schema id must exist"), &row)
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.schema_generator
+            .value
+            .encode(self.value_schema_id, &row)
+    }
+}
+
+/// A `.proto` file describing one top-level message, which is the first of
+/// `messages`, and the messages nested within it.
+#[derive(Debug)]
+struct MessageSchema {
+    messages: Vec<Message>,
+}
+
+#[derive(Debug)]
+struct Message {
+    name: String,
+    fields: Vec<Field>,
+    /// Whether the message wraps a single collection field, rather than
+    /// describing a record.
+    wrapper: bool,
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    typ: FieldType,
+    nullable: bool,
+}
+
+#[derive(Debug)]
+enum FieldType {
+    Scalar(ScalarType),
+    Message(usize),
+    Repeated {
+        array: bool,
+        element: Box<FieldType>,
+    },
+    Map(Box<FieldType>),
+}
+
+impl MessageSchema {
+    fn new(name: &str, columns: &[(ColumnName, ColumnType)]) -> MessageSchema {
+        let mut schema = MessageSchema {
+            messages: vec![Message {
+                name: name.into(),
+                fields: vec![],
+                wrapper: false,
+            }],
+        };
+        schema.messages[0].fields = schema.fields(columns);
+        schema
+    }
+
+    fn fields(&mut self, columns: &[(ColumnName, ColumnType)]) -> Vec<Field> {
+        let mut seen = HashSet::new();
+        columns
+            .iter()
+            .map(|(name, typ)| Field {
+                name: field_name(name.as_str(), &mut seen),
+                typ: self.field_type(&typ.scalar_type),
+                nullable: typ.nullable,
+            })
+            .collect()
+    }
+
+    fn field_type(&mut self, scalar_type: &ScalarType) -> FieldType {
+        match scalar_type {
+            ScalarType::Record { fields, .. } => {
+                let idx = self.messages.len();
+                self.messages.push(Message {
+                    name: format!("Record{idx}"),
+                    fields: vec![],
+                    wrapper: false,
+                });
+                self.messages[idx].fields = self.fields(fields);
+                FieldType::Message(idx)
+            }
+            ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
+                FieldType::Repeated {
+                    array: !matches!(ty, ScalarType::List { .. }),
+                    element: Box::new(self.element_type(ty.unwrap_collection_element_type())),
+                }
+            }
+            ScalarType::Map { value_type, .. } => {
+                FieldType::Map(Box::new(self.element_type(value_type)))
+            }
+            ty => FieldType::Scalar(ty.clone()),
+        }
+    }
+
+    /// Returns the type of the elements of a repeated or map field, which
+    /// cannot themselves be repeated or map fields.
+    fn element_type(&mut self, scalar_type: &ScalarType) -> FieldType {
+        match self.field_type(scalar_type) {
+            typ @ (FieldType::Repeated { .. } | FieldType::Map(_)) => {
+                let idx = self.messages.len();
+                self.messages.push(Message {
+                    name: format!("Wrapper{idx}"),
+                    fields: vec![Field {
+                        name: "value".into(),
+                        typ,
+                        nullable: false,
+                    }],
+                    wrapper: true,
+                });
+                FieldType::Message(idx)
+            }
+            typ => typ,
+        }
+    }
+
+    fn type_name(&self, typ: &FieldType) -> String {
+        match typ {
+            FieldType::Scalar(scalar_type) => proto_scalar_type(scalar_type).into(),
+            FieldType::Message(idx) => self.messages[*idx].name.clone(),
+            FieldType::Repeated { element, .. } => format!("repeated {}", self.type_name(element)),
+            FieldType::Map(value) => format!("map<string, {}>", self.type_name(value)),
+        }
+    }
+
+    fn encode(&self, schema_id: i32, row: &Row) -> Vec<u8> {
+        // The Confluent wire format is a magic byte (0), the 32-bit schema ID,
+        // and the indexes of the message within the schema, of which the
+        // first message is encoded as a single 0.
+        //
+        // https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+        let mut buf = vec![0];
+        buf.write_i32::<NetworkEndian>(schema_id)
+            .expect("writing to vec cannot fail");
+        buf.push(0);
+        for (i, (field, datum)) in self.messages[0].fields.iter().zip(row.iter()).enumerate() {
+            self.encode_field(field_number(i), field, datum, &mut buf);
+        }
+        buf
+    }
+
+    fn encode_field(&self, tag: u32, field: &Field, datum: Datum, buf: &mut Vec<u8>) {
+        // Null fields are absent, and so decode as unset optional fields,
+        // unset messages, or empty repeated and map fields.
+        if !datum.is_null() {
+            self.encode_value(tag, &field.typ, datum, buf);
+        }
+    }
+
+    fn encode_value(&self, tag: u32, typ: &FieldType, datum: Datum, buf: &mut Vec<u8>) {
+        match typ {
+            FieldType::Scalar(scalar_type) if datum.is_null() => {
+                encode_default(tag, scalar_type, buf)
+            }
+            FieldType::Scalar(scalar_type) => encode_scalar(tag, scalar_type, datum, buf),
+            FieldType::Message(idx) => {
+                let message = &self.messages[*idx];
+                let mut inner = vec![];
+                if message.wrapper {
+                    self.encode_field(1, &message.fields[0], datum, &mut inner);
+                } else if !datum.is_null() {
+                    let fields = message.fields.iter().zip(datum.unwrap_list().iter());
+                    for (i, (field, datum)) in fields.enumerate() {
+                        self.encode_field(field_number(i), field, datum, &mut inner);
+                    }
+                }
+                encode_length_delimited(tag, &inner, buf);
+            }
+            FieldType::Repeated { .. } | FieldType::Map(_) if datum.is_null() => (),
+            FieldType::Repeated { array, element } => {
+                let elements = if *array {
+                    datum.unwrap_array().elements()
+                } else {
+                    datum.unwrap_list()
+                };
+                for datum in elements.iter() {
+                    self.encode_value(tag, element, datum, buf);
+                }
+            }
+            FieldType::Map(value) => {
+                for (key, datum) in datum.unwrap_map().iter() {
+                    let mut entry = vec![];
+                    encode_length_delimited(1, key.as_bytes(), &mut entry);
+                    self.encode_value(2, value, datum, &mut entry);
+                    encode_length_delimited(tag, &entry, buf);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for MessageSchema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "syntax = \"proto3\";")?;
+        writeln!(f)?;
+        writeln!(f, "package {PROTOBUF_PACKAGE};")?;
+        for message in &self.messages {
+            writeln!(f)?;
+            writeln!(f, "message {} {{", message.name)?;
+            for (i, field) in message.fields.iter().enumerate() {
+                let optional = match field.typ {
+                    FieldType::Scalar(_) if field.nullable => "optional ",
+                    _ => "",
+                };
+                writeln!(
+                    f,
+                    "  {optional}{} {} = {};",
+                    self.type_name(&field.typ),
+                    field.name,
+                    field_number(i),
+                )?;
+            }
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+fn field_number(idx: usize) -> u32 {
+    u32::try_from(idx + 1).expect("too many fields")
+}
+
+/// Converts a column name into a unique Protobuf field name.
+fn field_name(name: &str, seen: &mut HashSet<String>) -> String {
+    let mut stem: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !stem.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        stem.insert(0, '_');
+    }
+    let mut name = stem.clone();
+    let mut i = 1;
+    while !seen.insert(name.clone()) {
+        name = format!("{stem}_{i}");
+        i += 1;
+    }
+    name
+}
+
+fn proto_scalar_type(scalar_type: &ScalarType) -> &'static str {
+    match scalar_type {
+        ScalarType::Bool => "bool",
+        ScalarType::Int16 | ScalarType::Int32 | ScalarType::Date => "int32",
+        ScalarType::Int64 | ScalarType::Time | ScalarType::Timestamp | ScalarType::TimestampTz => {
+            "int64"
+        }
+        ScalarType::PgLegacyChar
+        | ScalarType::UInt16
+        | ScalarType::UInt32
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => "uint32",
+        ScalarType::UInt64 => "uint64",
+        ScalarType::Float32 => "float",
+        ScalarType::Float64 => "double",
+        ScalarType::Bytes => "bytes",
+        _ => "string",
+    }
+}
+
+fn encode_scalar(tag: u32, scalar_type: &ScalarType, datum: Datum, buf: &mut Vec<u8>) {
+    match scalar_type {
+        ScalarType::Bool => encoding::bool::encode(tag, &datum.unwrap_bool(), buf),
+        ScalarType::Int16 => encoding::int32::encode(tag, &i32::from(datum.unwrap_int16()), buf),
+        ScalarType::Int32 => encoding::int32::encode(tag, &datum.unwrap_int32(), buf),
+        ScalarType::Int64 => encoding::int64::encode(tag, &datum.unwrap_int64(), buf),
+        ScalarType::PgLegacyChar => {
+            encoding::uint32::encode(tag, &u32::from(datum.unwrap_uint8()), buf)
+        }
+        ScalarType::UInt16 => encoding::uint32::encode(tag, &u32::from(datum.unwrap_uint16()), buf),
+        ScalarType::UInt32
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => encoding::uint32::encode(tag, &datum.unwrap_uint32(), buf),
+        ScalarType::UInt64 => encoding::uint64::encode(tag, &datum.unwrap_uint64(), buf),
+        ScalarType::Float32 => encoding::float::encode(tag, &datum.unwrap_float32(), buf),
+        ScalarType::Float64 => encoding::double::encode(tag, &datum.unwrap_float64(), buf),
+        ScalarType::Date => {
+            encoding::int32::encode(tag, &datum.unwrap_date().unix_epoch_days(), buf)
+        }
+        ScalarType::Time => {
+            let time = datum.unwrap_time();
+            let micros = i64::from(time.num_seconds_from_midnight()) * 1_000_000
+                + i64::from(time.nanosecond()) / 1_000;
+            encoding::int64::encode(tag, &micros, buf)
+        }
+        ScalarType::Timestamp | ScalarType::TimestampTz => {
+            let ts = match scalar_type {
+                ScalarType::Timestamp => datum.unwrap_timestamp().to_naive(),
+                _ => datum.unwrap_timestamptz().to_naive(),
+            };
+            let micros = ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros());
+            encoding::int64::encode(tag, &micros, buf)
+        }
+        ScalarType::Bytes => encode_length_delimited(tag, datum.unwrap_bytes(), buf),
+        ScalarType::String | ScalarType::VarChar { .. } => {
+            encode_length_delimited(tag, datum.unwrap_str().as_bytes(), buf)
+        }
+        ScalarType::Char { length } => {
+            let s = char::format_str_pad(datum.unwrap_str(), *length);
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::Numeric { .. } => {
+            let s = datum.unwrap_numeric().0.to_standard_notation_string();
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::Interval => {
+            let s = datum.unwrap_interval().to_string();
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::Jsonb => {
+            let s = JsonbRef::from_datum(datum).to_serde_json().to_string();
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::Uuid => {
+            let s = datum.unwrap_uuid().to_string();
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::MzTimestamp => {
+            let s = datum.unwrap_mz_timestamp().to_string();
+            encode_length_delimited(tag, s.as_bytes(), buf)
+        }
+        ScalarType::Array(_)
+        | ScalarType::Int2Vector
+        | ScalarType::List { .. }
+        | ScalarType::Map { .. }
+        | ScalarType::Record { .. } => {
+            unreachable!("{scalar_type:?} is not a Protobuf scalar type")
+        }
+    }
+}
+
+/// Encodes the default value of a scalar type, in place of a null element
+/// of a repeated or map field.
+fn encode_default(tag: u32, scalar_type: &ScalarType, buf: &mut Vec<u8>) {
+    match proto_scalar_type(scalar_type) {
+        "float" => encoding::float::encode(tag, &0.0, buf),
+        "double" => encoding::double::encode(tag, &0.0, buf),
+        "string" | "bytes" => encode_length_delimited(tag, &[], buf),
+        _ => encoding::uint64::encode(tag, &0, buf),
+    }
+}
+
+fn encode_length_delimited(tag: u32, bytes: &[u8], buf: &mut Vec<u8>) {
+    encoding::encode_key(tag, WireType::LengthDelimited, buf);
+    encoding::encode_varint(u64::try_from(bytes.len()).expect("usize fits in u64"), buf);
+    buf.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+
+    use super::*;
+
+    #[test]
+    fn generate_schema() {
+        let desc = RelationDesc::empty()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .with_column(
+                "tags",
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_id: None,
+                }
+                .nullable(true),
+            )
+            .with_column("?column?", ScalarType::Float64.nullable(false));
+        let generator = ProtobufSchemaGenerator::new(None, desc, false);
+        assert_eq!(generator.key_schema(), None);
+        assert_eq!(
+            generator.value_schema(),
+            r#"syntax = "proto3";
+
+package com.materialize.sink;
+
+message Value {
+  int64 id = 1;
+  optional string name = 2;
+  repeated string tags = 3;
+  double _column_ = 4;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn encode_row() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int32.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true));
+        let encoder =
+            ProtobufEncoder::new(ProtobufSchemaGenerator::new(None, desc, false), None, 7);
+
+        let row = Row::pack_slice(&[Datum::Int32(150), Datum::String("hi")]);
+        assert_eq!(
+            encoder.encode_value_unchecked(row),
+            vec![0, 0, 0, 0, 7, 0, 0x08, 0x96, 0x01, 0x12, 0x02, b'h', b'i']
+        );
+
+        let row = Row::pack_slice(&[Datum::Int32(1), Datum::Null]);
+        assert_eq!(
+            encoder.encode_value_unchecked(row),
+            vec![0, 0, 0, 0, 7, 0, 0x08, 0x01]
+        );
+    }
+}
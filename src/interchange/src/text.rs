@@ -0,0 +1,311 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of rows as CSV, text, and raw bytes.
+
+use std::fmt;
+
+use bytes::BytesMut;
+
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
+
+/// Encodes rows as CSV records.
+///
+/// Datums are written in their PostgreSQL text representation. NULLs are
+/// written as empty, unquoted fields.
+pub struct CsvEncoder {
+    key_columns: Option<Vec<(ColumnName, ColumnType)>>,
+    value_columns: Vec<(ColumnName, ColumnType)>,
+    delimiter: char,
+    header: Option<Vec<String>>,
+}
+
+impl CsvEncoder {
+    pub fn new(key_desc: Option<RelationDesc>, value_desc: RelationDesc, debezium: bool) -> Self {
+        let mut value_columns = column_names_and_types(value_desc);
+        if debezium {
+            value_columns = envelopes::dbz_envelope(value_columns);
+        }
+        CsvEncoder {
+            key_columns: key_desc.map(column_names_and_types),
+            value_columns,
+            delimiter: ',',
+            header: None,
+        }
+    }
+
+    /// Separates fields with `delimiter` instead of a comma.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Precedes every encoded value with a header record of `names`.
+    pub fn with_header(mut self, names: Vec<String>) -> Self {
+        self.header = Some(names);
+        self
+    }
+
+    /// Returns the names and types of the encoded value columns.
+    pub fn value_columns(&self) -> &[(ColumnName, ColumnType)] {
+        &self.value_columns
+    }
+
+    /// Appends `row` to `buf` as a single CSV record, without a line
+    /// terminator.
+    pub fn encode_record(
+        &self,
+        row: &Row,
+        columns: &[(ColumnName, ColumnType)],
+        buf: &mut Vec<u8>,
+    ) {
+        for (i, (datum, (_name, typ))) in row.iter().zip(columns).enumerate() {
+            if i > 0 {
+                write_char(buf, self.delimiter);
+            }
+            if let Some(text) = encode_text(datum, &typ.scalar_type) {
+                write_csv_field(buf, self.delimiter, &text);
+            }
+        }
+    }
+
+    /// Appends `names` to `buf` as a CSV header record, without a line
+    /// terminator.
+    pub fn encode_header(&self, names: &[String], buf: &mut Vec<u8>) {
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                write_char(buf, self.delimiter);
+            }
+            write_csv_field(buf, self.delimiter, name.as_bytes());
+        }
+    }
+}
+
+impl fmt::Debug for CsvEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsvEncoder")
+            .field("delimiter", &self.delimiter)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl Encode for CsvEncoder {
+    fn get_format_name(&self) -> &str {
+        "csv"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        let mut buf = vec![];
+        self.encode_record(
+            &row,
+            self.key_columns.as_ref().expect("key schema must exist"),
+            &mut buf,
+        );
+        buf
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        let mut buf = vec![];
+        if let Some(header) = &self.header {
+            self.encode_header(header, &mut buf);
+            buf.push(b'\n');
+        }
+        self.encode_record(&row, &self.value_columns, &mut buf);
+        buf
+    }
+}
+
+/// Encodes rows with a single column as the bare contents of that column.
+///
+/// In text mode, the datum is written in its PostgreSQL text representation.
+/// In bytes mode, `bytea` datums are written as is and `text` datums as their
+/// UTF-8 encoding. NULLs are written as empty messages.
+#[derive(Debug)]
+pub struct TextEncoder {
+    key_type: Option<ScalarType>,
+    value_type: ScalarType,
+    raw: bool,
+}
+
+impl TextEncoder {
+    /// Returns an encoder that writes datums in their text representation.
+    ///
+    /// Returns an error if either description has more than one column.
+    pub fn text(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(TextEncoder {
+            key_type: key_desc.map(single_column_type).transpose()?,
+            value_type: single_column_type(value_desc)?,
+            raw: false,
+        })
+    }
+
+    /// Returns an encoder that writes datums as raw bytes.
+    ///
+    /// Returns an error if either description has more than one column or a
+    /// column whose type is neither `bytea` nor `text`.
+    pub fn bytes(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+    ) -> Result<Self, anyhow::Error> {
+        let encoder = TextEncoder {
+            key_type: key_desc.map(single_column_type).transpose()?,
+            value_type: single_column_type(value_desc)?,
+            raw: true,
+        };
+        for typ in encoder.key_type.iter().chain([&encoder.value_type]) {
+            if !matches!(typ, ScalarType::Bytes | ScalarType::String) {
+                anyhow::bail!(
+                    "FORMAT BYTES requires a column of type bytea or text, found {:?}",
+                    typ
+                );
+            }
+        }
+        Ok(encoder)
+    }
+
+    fn encode(&self, row: &Row, typ: &ScalarType) -> Vec<u8> {
+        let datum = match row.iter().next() {
+            Some(datum) => datum,
+            None => return vec![],
+        };
+        match (self.raw, datum) {
+            (_, Datum::Null) => vec![],
+            (true, Datum::Bytes(bytes)) => bytes.to_vec(),
+            (true, Datum::String(s)) => s.as_bytes().to_vec(),
+            _ => encode_text(datum, typ)
+                .map(|text| text.to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Encode for TextEncoder {
+    fn get_format_name(&self) -> &str {
+        if self.raw {
+            "bytes"
+        } else {
+            "text"
+        }
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode(&row, self.key_type.as_ref().expect("key schema must exist"))
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode(&row, &self.value_type)
+    }
+}
+
+fn single_column_type(desc: RelationDesc) -> Result<ScalarType, anyhow::Error> {
+    match desc.typ().column_types.as_slice() {
+        [typ] => Ok(typ.scalar_type.clone()),
+        types => anyhow::bail!(
+            "FORMAT TEXT and FORMAT BYTES require exactly one column, found {}",
+            types.len()
+        ),
+    }
+}
+
+/// Returns the PostgreSQL text representation of `datum`, or `None` if it is
+/// NULL.
+fn encode_text(datum: Datum, typ: &ScalarType) -> Option<BytesMut> {
+    mz_pgrepr::Value::from_datum(datum, typ).map(|value| {
+        let mut text = BytesMut::new();
+        value.encode_text(&mut text);
+        text
+    })
+}
+
+fn write_char(buf: &mut Vec<u8>, c: char) {
+    buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+}
+
+/// Writes `field` to `buf`, quoting it if it is empty or contains the
+/// delimiter, a quote, or a line break.
+fn write_csv_field(buf: &mut Vec<u8>, delimiter: char, field: &[u8]) {
+    let mut delimiter_buf = [0; 4];
+    let delimiter = delimiter.encode_utf8(&mut delimiter_buf).as_bytes();
+    let needs_quotes = field.is_empty()
+        || field
+            .windows(delimiter.len())
+            .any(|window| window == delimiter)
+        || field.iter().any(|b| matches!(b, b'"' | b'\r' | b'\n'));
+    if !needs_quotes {
+        buf.extend_from_slice(field);
+        return;
+    }
+    buf.push(b'"');
+    for b in field {
+        if *b == b'"' {
+            buf.push(b'"');
+        }
+        buf.push(*b);
+    }
+    buf.push(b'"');
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+
+    use super::*;
+
+    #[test]
+    fn encode_csv() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int32.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true));
+        let encoder = CsvEncoder::new(None, desc, false)
+            .with_delimiter('|')
+            .with_header(vec!["a".into(), "b".into()]);
+        let encoded = encoder.encode_value_unchecked(Row::pack_slice(&[
+            Datum::Int32(1),
+            Datum::String("x|\"y\""),
+        ]));
+        assert_eq!(encoded, b"a|b\n1|\"x|\"\"y\"\"\"");
+        let encoded =
+            encoder.encode_value_unchecked(Row::pack_slice(&[Datum::Int32(2), Datum::Null]));
+        assert_eq!(encoded, b"a|b\n2|");
+    }
+
+    #[test]
+    fn encode_text_and_bytes() {
+        let desc = RelationDesc::empty().with_column("a", ScalarType::Bool.nullable(true));
+        let encoder = TextEncoder::text(None, desc.clone()).unwrap();
+        assert_eq!(
+            encoder.encode_value_unchecked(Row::pack_slice(&[Datum::True])),
+            b"t"
+        );
+        assert_eq!(
+            encoder.encode_value_unchecked(Row::pack_slice(&[Datum::Null])),
+            b""
+        );
+        assert!(TextEncoder::bytes(None, desc).is_err());
+
+        let desc = RelationDesc::empty().with_column("a", ScalarType::Bytes.nullable(false));
+        let encoder = TextEncoder::bytes(None, desc).unwrap();
+        assert_eq!(
+            encoder.encode_value_unchecked(Row::pack_slice(&[Datum::Bytes(b"\x00\xff")])),
+            b"\x00\xff"
+        );
+
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Bytes.nullable(false))
+            .with_column("b", ScalarType::Bytes.nullable(false));
+        assert!(TextEncoder::text(None, desc).is_err());
+    }
+}
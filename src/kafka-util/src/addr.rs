@@ -7,22 +7,37 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt::{self, Write};
 use std::num::ParseIntError;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use proptest_derive::Arbitrary;
+use rdkafka::client::BrokerAddr;
 use serde::{Deserialize, Serialize};
+use tokio::runtime::Handle;
+use trust_dns_resolver::TokioAsyncResolver;
 
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
+use crate::client::BrokerAddrRewriter;
+
 include!(concat!(env!("OUT_DIR"), "/mz_kafka_util.addr.rs"));
 
 /// Represents the addresses of several Kafka brokers.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct KafkaAddrs(Vec<(String, u16)>);
 
+impl KafkaAddrs {
+    /// Returns an iterator over the host and port of each broker.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u16)> {
+        self.0.iter().map(|(host, port)| (host.as_str(), *port))
+    }
+}
+
 impl FromStr for KafkaAddrs {
     type Err = KafkaAddrsParseError;
 
@@ -53,6 +68,165 @@ impl fmt::Display for KafkaAddrs {
     }
 }
 
+/// The prefix that marks a broker address as a DNS SRV record.
+const SRV_PREFIX: &str = "srv://";
+
+/// The port with which a DNS SRV record is listed in `bootstrap.servers`.
+///
+/// Clients never connect to this port: [`SrvBrokerAddrRewriter`] replaces
+/// the whole address with one of the record's targets.
+const SRV_BOOTSTRAP_PORT: u16 = 9092;
+
+/// Describes where to find a set of Kafka brokers.
+///
+/// Brokers are either listed explicitly, or discovered by looking up a DNS SRV
+/// record, written as `srv://<name>`, whose targets are the brokers. The
+/// latter allows the set of brokers to change without recreating the objects
+/// that refer to them, e.g. when brokers are rescheduled in Kubernetes.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum KafkaBrokerAddrs {
+    /// An explicit list of brokers.
+    Static(KafkaAddrs),
+    /// The name of a DNS SRV record that lists the brokers.
+    Srv(String),
+}
+
+impl KafkaBrokerAddrs {
+    /// Returns the brokers in the form of `bootstrap.servers`.
+    ///
+    /// An SRV record is listed as its name, which a client configured with an
+    /// [`SrvBrokerAddrRewriter`] resolves whenever it connects to the broker.
+    pub fn bootstrap_servers(&self) -> String {
+        match self {
+            KafkaBrokerAddrs::Static(addrs) => addrs.to_string(),
+            KafkaBrokerAddrs::Srv(name) => format!("{}:{}", name, SRV_BOOTSTRAP_PORT),
+        }
+    }
+
+    /// Resolves the addresses of the brokers.
+    ///
+    /// SRV records are looked up anew on every call, so callers should
+    /// resolve the addresses again whenever they reconnect to the brokers.
+    pub async fn resolve(&self) -> Result<KafkaAddrs, anyhow::Error> {
+        match self {
+            KafkaBrokerAddrs::Static(addrs) => Ok(addrs.clone()),
+            KafkaBrokerAddrs::Srv(name) => {
+                let resolver = TokioAsyncResolver::tokio_from_system_conf()?;
+                let lookup = resolver.srv_lookup(name.as_str()).await?;
+                let mut records: Vec<_> = lookup.iter().collect();
+                // Prefer targets with a lower priority and a higher weight, as
+                // described in RFC 2782.
+                records.sort_by_key(|srv| (srv.priority(), std::cmp::Reverse(srv.weight())));
+                let addrs: Vec<_> = records
+                    .into_iter()
+                    .map(|srv| {
+                        let target = srv.target().to_utf8();
+                        (target.trim_end_matches('.').to_owned(), srv.port())
+                    })
+                    .collect();
+                if addrs.is_empty() {
+                    anyhow::bail!("SRV record {} has no targets", name);
+                }
+                Ok(KafkaAddrs(addrs))
+            }
+        }
+    }
+}
+
+/// Rewrites the addresses of brokers that are listed as DNS SRV records to
+/// the records' targets.
+///
+/// librdkafka consults the rewriter whenever it connects to a broker, so the
+/// record is looked up anew on every connection, and clients follow the
+/// brokers as the record changes. Each connection goes to the next of the
+/// record's targets, so that a client whose connection to one target fails
+/// tries another when it reconnects.
+///
+/// All addresses, including the targets of SRV records, are then passed to
+/// the inner rewriter, if any, e.g. to route the connection through a tunnel.
+pub struct SrvBrokerAddrRewriter {
+    runtime: Handle,
+    /// The names of the SRV records, as listed in `bootstrap.servers`.
+    names: BTreeSet<String>,
+    inner: Option<Arc<dyn BrokerAddrRewriter>>,
+    next_target: AtomicUsize,
+}
+
+impl SrvBrokerAddrRewriter {
+    /// Constructs a rewriter that resolves the SRV records listed in
+    /// `brokers` on `runtime`, and then applies `inner`, if any.
+    ///
+    /// Returns `inner` if no broker is listed as an SRV record.
+    pub fn wrap(
+        runtime: Handle,
+        brokers: &[String],
+        inner: Option<Arc<dyn BrokerAddrRewriter>>,
+    ) -> Option<Arc<dyn BrokerAddrRewriter>> {
+        let names: BTreeSet<_> = brokers
+            .iter()
+            .filter_map(|broker| match broker.parse() {
+                Ok(KafkaBrokerAddrs::Srv(name)) => Some(name),
+                _ => None,
+            })
+            .collect();
+        if names.is_empty() {
+            return inner;
+        }
+        Some(Arc::new(SrvBrokerAddrRewriter {
+            runtime,
+            names,
+            inner,
+            next_target: AtomicUsize::new(0),
+        }))
+    }
+}
+
+impl BrokerAddrRewriter for SrvBrokerAddrRewriter {
+    fn rewrite(&self, addr: BrokerAddr) -> Result<BrokerAddr, anyhow::Error> {
+        let addr = if self.names.contains(&addr.host) {
+            let srv = KafkaBrokerAddrs::Srv(addr.host);
+            let targets = self.runtime.block_on(srv.resolve())?;
+            let i = self.next_target.fetch_add(1, Ordering::Relaxed) % targets.0.len();
+            let (host, port) = &targets.0[i];
+            BrokerAddr {
+                host: host.clone(),
+                port: port.to_string(),
+            }
+        } else {
+            addr
+        };
+        match &self.inner {
+            Some(inner) => inner.rewrite(addr),
+            None => Ok(addr),
+        }
+    }
+}
+
+impl FromStr for KafkaBrokerAddrs {
+    type Err = KafkaAddrsParseError;
+
+    fn from_str(s: &str) -> Result<KafkaBrokerAddrs, Self::Err> {
+        match s.strip_prefix(SRV_PREFIX) {
+            Some(name) => {
+                if name.is_empty() || name.contains(|c: char| c == ',' || c == ':' || c == '/') {
+                    return Err(KafkaAddrsParseError::InvalidSrvName(name.to_owned()));
+                }
+                Ok(KafkaBrokerAddrs::Srv(name.to_owned()))
+            }
+            None => Ok(KafkaBrokerAddrs::Static(s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for KafkaBrokerAddrs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KafkaBrokerAddrs::Static(addrs) => fmt::Display::fmt(addrs, f),
+            KafkaBrokerAddrs::Srv(name) => write!(f, "{}{}", SRV_PREFIX, name),
+        }
+    }
+}
+
 impl RustType<proto_kafka_addrs::ProtoKafkaAddr> for (String, u16) {
     fn into_proto(&self) -> proto_kafka_addrs::ProtoKafkaAddr {
         proto_kafka_addrs::ProtoKafkaAddr {
@@ -83,6 +257,8 @@ impl RustType<ProtoKafkaAddrs> for KafkaAddrs {
 pub enum KafkaAddrsParseError {
     /// The Kafka address contained an invalid port.
     InvalidPort(ParseIntError),
+    /// The Kafka address named an invalid DNS SRV record.
+    InvalidSrvName(String),
 }
 
 impl fmt::Display for KafkaAddrsParseError {
@@ -93,6 +269,11 @@ impl fmt::Display for KafkaAddrsParseError {
                 "unable to parse Kafka broker address: invalid port: {}",
                 e
             ),
+            KafkaAddrsParseError::InvalidSrvName(name) => write!(
+                f,
+                "unable to parse Kafka broker address: invalid SRV record name: {:?}",
+                name
+            ),
         }
     }
 }
@@ -158,6 +339,9 @@ mod tests {
             let addrs: KafkaAddrs = tc.input.parse()?;
             assert_eq!(addrs.0, tc.addrs);
             assert_eq!(addrs.to_string(), tc.output);
+            assert!(addrs
+                .iter()
+                .eq(tc.addrs.iter().map(|(host, port)| (host.as_str(), *port))));
         }
 
         Ok(())
@@ -173,4 +357,42 @@ mod tests {
             "unable to parse Kafka broker address: invalid port: invalid digit found in string",
         )
     }
+
+    #[test]
+    fn test_parse_broker_addrs() -> Result<(), Box<dyn Error>> {
+        let addrs: KafkaBrokerAddrs = "host1,host2:42".parse()?;
+        assert_eq!(
+            addrs,
+            KafkaBrokerAddrs::Static(KafkaAddrs(vec![
+                ("host1".into(), 9092),
+                ("host2".into(), 42)
+            ]))
+        );
+        assert_eq!(addrs.to_string(), "host1:9092,host2:42");
+        assert_eq!(addrs.bootstrap_servers(), "host1:9092,host2:42");
+
+        let addrs: KafkaBrokerAddrs = "srv://_kafka._tcp.kafka.svc.cluster.local".parse()?;
+        assert_eq!(
+            addrs,
+            KafkaBrokerAddrs::Srv("_kafka._tcp.kafka.svc.cluster.local".into())
+        );
+        assert_eq!(
+            addrs.to_string(),
+            "srv://_kafka._tcp.kafka.svc.cluster.local"
+        );
+        assert_eq!(
+            addrs.bootstrap_servers(),
+            "_kafka._tcp.kafka.svc.cluster.local:9092"
+        );
+
+        for input in ["srv://", "srv://host:9092", "srv://host1,host2"] {
+            assert!(
+                input.parse::<KafkaBrokerAddrs>().is_err(),
+                "{} parsed unexpectedly",
+                input
+            );
+        }
+
+        Ok(())
+    }
 }
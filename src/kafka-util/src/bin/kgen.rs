@@ -695,7 +695,7 @@ async fn main() -> anyhow::Result<()> {
             let producer: ThreadedProducer<mz_kafka_util::client::MzClientContext> =
                 mz_kafka_util::client::create_new_client_config_simple()
                     .set("bootstrap.servers", args.bootstrap_server.to_string())
-                    .create_with_context(mz_kafka_util::client::MzClientContext)
+                    .create_with_context(mz_kafka_util::client::MzClientContext::default())
                     .unwrap();
             let mut key_buf = vec![];
             let mut value_buf = vec![];
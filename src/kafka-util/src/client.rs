@@ -9,24 +9,94 @@
 
 //! Helpers for working with Kafka's client API.
 
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::bail;
 use mz_ore::collections::CollectionExt;
-use rdkafka::client::Client;
+use rdkafka::client::{BrokerAddr, Client, OAuthToken};
 use rdkafka::config::{ClientConfig, RDKafkaLogLevel};
 use rdkafka::consumer::ConsumerContext;
+use rdkafka::error::KafkaResult;
 use rdkafka::producer::{DefaultProducerContext, DeliveryResult, ProducerContext};
 use rdkafka::ClientContext;
 use tracing::{debug, error, info, warn, Level};
 
+/// Generates the tokens with which Kafka clients authenticate using the
+/// `OAUTHBEARER` SASL mechanism.
+///
+/// librdkafka requests a new token from the provider shortly before the
+/// previous one expires, from one of its own threads.
+pub trait OAuthTokenProvider: Send + Sync {
+    /// Generates a new token.
+    fn generate_token(&self) -> Result<OAuthToken, anyhow::Error>;
+}
+
+/// Rewrites the addresses of the brokers that Kafka clients connect to, e.g.
+/// to route the connections through an SSH tunnel.
+///
+/// librdkafka consults the rewriter whenever it connects to a broker, from
+/// one of its own threads.
+pub trait BrokerAddrRewriter: Send + Sync {
+    /// Returns the address to connect to in place of `addr`.
+    fn rewrite(&self, addr: BrokerAddr) -> Result<BrokerAddr, anyhow::Error>;
+}
+
 /// A `ClientContext` implementation that uses `tracing` instead of `log` macros.
 ///
 /// All code in Materialize that constructs Kafka clients should use this context or
-/// a custom context that delegates the `log` and `error` methods to this implementation.
-pub struct MzClientContext;
+/// a custom context that delegates the `log`, `error`, `generate_oauth_token`, and
+/// `rewrite_broker_addr` methods to this implementation.
+#[derive(Clone, Default)]
+pub struct MzClientContext {
+    oauth_token_provider: Option<Arc<dyn OAuthTokenProvider>>,
+    broker_addr_rewriter: Option<Arc<dyn BrokerAddrRewriter>>,
+}
+
+impl MzClientContext {
+    /// Constructs a context whose clients authenticate using tokens from
+    /// `oauth_token_provider`, if any.
+    pub fn new(oauth_token_provider: Option<Arc<dyn OAuthTokenProvider>>) -> MzClientContext {
+        MzClientContext {
+            oauth_token_provider,
+            broker_addr_rewriter: None,
+        }
+    }
+
+    /// Makes the context's clients connect to the broker addresses returned by
+    /// `broker_addr_rewriter`.
+    pub fn with_broker_addr_rewriter(
+        mut self,
+        broker_addr_rewriter: Arc<dyn BrokerAddrRewriter>,
+    ) -> MzClientContext {
+        self.broker_addr_rewriter = Some(broker_addr_rewriter);
+        self
+    }
+}
+
+impl fmt::Debug for MzClientContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MzClientContext")
+            .field(
+                "oauth_token_provider",
+                &self.oauth_token_provider.as_ref().map(|_| ".."),
+            )
+            .field(
+                "broker_addr_rewriter",
+                &self.broker_addr_rewriter.as_ref().map(|_| ".."),
+            )
+            .finish()
+    }
+}
 
 impl ClientContext for MzClientContext {
+    // librdkafka only invokes the token refresh callback for clients that use
+    // the `OAUTHBEARER` SASL mechanism.
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
     fn log(&self, level: rdkafka::config::RDKafkaLogLevel, fac: &str, log_message: &str) {
         use rdkafka::config::RDKafkaLogLevel::*;
         // Copied from https://docs.rs/rdkafka/0.28.0/src/rdkafka/client.rs.html#58-79
@@ -45,6 +115,38 @@ impl ClientContext for MzClientContext {
     fn error(&self, error: rdkafka::error::KafkaError, reason: &str) {
         error!("librdkafka: {}: {}", error, reason);
     }
+    fn generate_oauth_token(
+        &self,
+        _oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn Error>> {
+        match &self.oauth_token_provider {
+            Some(provider) => match provider.generate_token() {
+                Ok(token) => Ok(token),
+                Err(e) => {
+                    error!("generating OAUTHBEARER token: {:#}", e);
+                    Err(e.into())
+                }
+            },
+            None => Err("no OAUTHBEARER token provider configured".into()),
+        }
+    }
+    fn rewrite_broker_addr(&self, addr: BrokerAddr) -> BrokerAddr {
+        match &self.broker_addr_rewriter {
+            Some(rewriter) => match rewriter.rewrite(addr.clone()) {
+                Ok(rewritten) => rewritten,
+                Err(e) => {
+                    // librdkafka will fail to connect to the original address
+                    // and retry, which consults the rewriter again.
+                    error!(
+                        "rewriting address of broker {}:{}: {:#}",
+                        addr.host, addr.port, e
+                    );
+                    addr
+                }
+            },
+            None => addr,
+        }
+    }
 }
 
 // Implement `ConsumerContext` and `ProducerContext` for `MzClientContext`, so that it can be used
@@ -92,6 +194,26 @@ pub fn get_partitions<C: ClientContext>(
     Ok(meta_topic.partitions().iter().map(|x| x.id()).collect())
 }
 
+/// The codecs with which Kafka producers may compress batches of messages.
+///
+/// The codec of each batch is chosen by the producer that wrote it rather
+/// than by the broker or the topic, so consumers must be able to decompress
+/// all of them.
+pub const COMPRESSION_CODECS: [&str; 4] = ["gzip", "snappy", "lz4", "zstd"];
+
+/// Returns the codecs in [`COMPRESSION_CODECS`] that the linked librdkafka
+/// was built without, and so cannot decompress.
+pub fn unsupported_compression_codecs() -> KafkaResult<Vec<&'static str>> {
+    #[allow(clippy::disallowed_methods)]
+    let config = ClientConfig::new().create_native_config()?;
+    let features = config.get("builtin.features")?;
+    let features: HashSet<_> = features.split(',').collect();
+    Ok(COMPRESSION_CODECS
+        .into_iter()
+        .filter(|codec| !features.contains(codec))
+        .collect())
+}
+
 /// A simpler version of [`create_new_client_config`] that defaults
 /// the `log_level` to `INFO` and should only be used in tests.
 pub fn create_new_client_config_simple() -> ClientConfig {
@@ -16,4 +16,6 @@ mod addr;
 pub mod admin;
 pub mod client;
 
-pub use addr::{KafkaAddrs, KafkaAddrsParseError, ProtoKafkaAddrs};
+pub use addr::{
+    KafkaAddrs, KafkaAddrsParseError, KafkaBrokerAddrs, ProtoKafkaAddrs, SrvBrokerAddrRewriter,
+};
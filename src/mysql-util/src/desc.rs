@@ -0,0 +1,178 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Descriptions of MySQL objects.
+
+use proptest::prelude::{any, Arbitrary};
+use proptest::strategy::{BoxedStrategy, Strategy};
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{RustType, TryFromProtoError};
+
+include!(concat!(env!("OUT_DIR"), "/mz_mysql_util.desc.rs"));
+
+/// Describes a table in a MySQL database.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlTableDesc {
+    /// The name of the schema (i.e., the MySQL database) that the table
+    /// belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The description of each column, in order.
+    pub columns: Vec<MySqlColumnDesc>,
+}
+
+impl RustType<ProtoMySqlTableDesc> for MySqlTableDesc {
+    fn into_proto(&self) -> ProtoMySqlTableDesc {
+        ProtoMySqlTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlTableDesc {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(MySqlColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Arbitrary for MySqlTableDesc {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            any::<Vec<MySqlColumnDesc>>(),
+        )
+            .prop_map(|(schema_name, name, columns)| MySqlTableDesc {
+                schema_name,
+                name,
+                columns,
+            })
+            .boxed()
+    }
+}
+
+/// Describes a column in a [`MySqlTableDesc`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The name of the column's type, e.g. `int`, as reported by the
+    /// `DATA_TYPE` column of `information_schema.columns`.
+    pub data_type: String,
+    /// The full type of the column, e.g. `int unsigned` or `enum('a','b')`,
+    /// as reported by the `COLUMN_TYPE` column of
+    /// `information_schema.columns`.
+    pub column_type: String,
+    /// True if the column lacks a `NOT NULL` constraint.
+    pub nullable: bool,
+    /// Whether the column is part of the table's primary key.
+    pub primary_key: bool,
+}
+
+impl MySqlColumnDesc {
+    /// Reports whether the column has an unsigned integer type.
+    pub fn is_unsigned(&self) -> bool {
+        self.column_type.ends_with(" unsigned") || self.column_type.contains(" unsigned ")
+    }
+
+    /// Returns the permitted values of an `enum` or `set` column, in order.
+    ///
+    /// Returns `None` if the column has another type.
+    pub fn enum_values(&self) -> Option<Vec<String>> {
+        let list = match self.data_type.as_str() {
+            "enum" => self.column_type.strip_prefix("enum(")?,
+            "set" => self.column_type.strip_prefix("set(")?,
+            _ => return None,
+        };
+        let list = list.strip_suffix(')')?;
+
+        // The values are single-quoted and comma-separated, with embedded
+        // quotes doubled.
+        let mut values = vec![];
+        let mut chars = list.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\'' {
+                continue;
+            }
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                if c == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                value.push(c);
+            }
+            values.push(value);
+        }
+        Some(values)
+    }
+}
+
+impl RustType<ProtoMySqlColumnDesc> for MySqlColumnDesc {
+    fn into_proto(&self) -> ProtoMySqlColumnDesc {
+        ProtoMySqlColumnDesc {
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            column_type: self.column_type.clone(),
+            nullable: self.nullable,
+            primary_key: self.primary_key,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlColumnDesc {
+            name: proto.name,
+            data_type: proto.data_type,
+            column_type: proto.column_type,
+            nullable: proto.nullable,
+            primary_key: proto.primary_key,
+        })
+    }
+}
+
+impl Arbitrary for MySqlColumnDesc {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            any::<String>(),
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(name, data_type, column_type, nullable, primary_key)| MySqlColumnDesc {
+                    name,
+                    data_type,
+                    column_type,
+                    nullable,
+                    primary_key,
+                },
+            )
+            .boxed()
+    }
+}
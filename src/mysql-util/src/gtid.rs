@@ -0,0 +1,155 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! MySQL global transaction identifiers.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use mysql_common::packets::{GnoInterval, Sid};
+use uuid::Uuid;
+
+/// A set of global transaction identifiers (GTIDs), in the format of MySQL's
+/// `gtid_executed` system variable.
+///
+/// A GTID identifies a transaction by the UUID of the server on which it
+/// originated and by a transaction number (GNO) that the server assigns in
+/// commit order, starting at 1.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GtidSet {
+    /// The half-open ranges of transaction numbers in the set, by server.
+    intervals: BTreeMap<Uuid, Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    /// Returns the servers that have transactions in the set.
+    pub fn servers(&self) -> impl Iterator<Item = &Uuid> {
+        self.intervals.keys()
+    }
+
+    /// Returns the largest transaction number of `server` in the set, if any.
+    pub fn max_gno(&self, server: &Uuid) -> Option<u64> {
+        self.intervals
+            .get(server)
+            .and_then(|intervals| intervals.last())
+            .map(|(_start, end)| end - 1)
+    }
+
+    /// Replaces the transactions of `server` in the set with all of its
+    /// transactions numbered below `end`.
+    pub fn set_server_upper(&mut self, server: Uuid, end: u64) {
+        if end > 1 {
+            self.intervals.insert(server, vec![(1, end)]);
+        } else {
+            self.intervals.remove(&server);
+        }
+    }
+
+    /// Returns the set in the form that binlog dump requests expect.
+    pub fn to_sids(&self) -> Vec<Sid<'static>> {
+        self.intervals
+            .iter()
+            .map(|(server, intervals)| {
+                intervals
+                    .iter()
+                    .fold(Sid::new(*server.as_bytes()), |sid, (start, end)| {
+                        sid.with_interval(GnoInterval::new(*start, *end))
+                    })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<GtidSet, anyhow::Error> {
+        let mut set = GtidSet::default();
+        // MySQL separates the servers with newlines as well as commas.
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let mut parts = entry.split(':');
+            let server = parts.next().expect("split returns at least one part");
+            let server = Uuid::parse_str(server)
+                .map_err(|e| anyhow!("invalid server UUID in GTID set {s:?}: {e}"))?;
+            let intervals = set.intervals.entry(server).or_default();
+            for interval in parts {
+                let (start, end) = match interval.split_once('-') {
+                    Some((start, end)) => (start.parse::<u64>()?, end.parse::<u64>()?),
+                    None => {
+                        let gno = interval.parse::<u64>()?;
+                        (gno, gno)
+                    }
+                };
+                if start == 0 || end < start {
+                    bail!("invalid interval {interval:?} in GTID set {s:?}");
+                }
+                intervals.push((start, end + 1));
+            }
+            intervals.sort();
+        }
+        Ok(set)
+    }
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (server, intervals) in &self.intervals {
+            if !first {
+                f.write_str(",")?;
+            }
+            first = false;
+            write!(f, "{server}")?;
+            for (start, end) in intervals {
+                if end - start == 1 {
+                    write!(f, ":{start}")?;
+                } else {
+                    write!(f, ":{start}-{}", end - 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gtid_set() {
+        let a = Uuid::parse_str("3e11fa47-71ca-11e1-9e33-c80aa9429562").unwrap();
+        let b = Uuid::parse_str("8f2c1b9e-1d4a-11ee-8c90-0242ac120002").unwrap();
+
+        let mut set: GtidSet = "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,\n\
+                                8f2c1b9e-1d4a-11ee-8c90-0242ac120002:1-3"
+            .parse()
+            .unwrap();
+        assert_eq!(set.max_gno(&a), Some(7));
+        assert_eq!(set.max_gno(&b), Some(3));
+        assert_eq!(set.servers().collect::<Vec<_>>(), vec![&a, &b]);
+        assert_eq!(
+            set.to_string(),
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,8f2c1b9e-1d4a-11ee-8c90-0242ac120002:1-3"
+        );
+
+        set.set_server_upper(a, 10);
+        assert_eq!(set.max_gno(&a), Some(9));
+        set.set_server_upper(a, 1);
+        assert_eq!(set.max_gno(&a), None);
+
+        assert_eq!("".parse::<GtidSet>().unwrap(), GtidSet::default());
+        assert!("not-a-uuid:1-2".parse::<GtidSet>().is_err());
+        assert!("3e11fa47-71ca-11e1-9e33-c80aa9429562:5-2"
+            .parse::<GtidSet>()
+            .is_err());
+    }
+}
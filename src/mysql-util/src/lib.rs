@@ -0,0 +1,207 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! MySQL utility library.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::{anyhow, bail};
+use mysql_async::prelude::Queryable;
+use mysql_async::{Conn, OptsBuilder, SslOpts};
+use uuid::Uuid;
+
+use crate::desc::{MySqlColumnDesc, MySqlTableDesc};
+
+pub mod desc;
+mod gtid;
+
+pub use gtid::GtidSet;
+
+/// The schemas in which MySQL stores its own metadata, whose tables sources
+/// never ingest.
+pub const SYSTEM_SCHEMAS: &[&str] = &["information_schema", "mysql", "performance_schema", "sys"];
+
+/// Whether to use TLS for encryption, authentication, or both.
+///
+/// The modes correspond to the values of the `--ssl-mode` option of the
+/// `mysql` client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsMode {
+    /// Do not use TLS.
+    Disabled,
+    /// Encrypt the connection, without verifying the server's certificate.
+    Required,
+    /// Encrypt the connection and verify the server's certificate against
+    /// the certificate authority.
+    VerifyCa,
+    /// Like `VerifyCa`, but also verify that the certificate was issued for
+    /// the server's hostname.
+    VerifyIdentity,
+}
+
+/// Configuration for MySQL connections.
+#[derive(Clone)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: Option<String>,
+    pub tls_mode: TlsMode,
+    /// An optional root TLS certificate in PEM format, to verify the server's
+    /// identity.
+    pub tls_root_cert: Option<String>,
+}
+
+// Omit the password from debug output.
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .field("tls_mode", &self.tls_mode)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Connects to the MySQL server.
+    pub async fn connect(&self) -> Result<Conn, anyhow::Error> {
+        let mut opts = OptsBuilder::default()
+            .ip_or_hostname(self.host.clone())
+            .tcp_port(self.port)
+            .user(Some(self.user.clone()))
+            .pass(self.password.clone())
+            .prefer_socket(false);
+
+        // The client only reads the root certificate from a file, and only
+        // while connecting, so the file does not outlive this function.
+        let mut root_cert_file = None;
+        if self.tls_mode != TlsMode::Disabled {
+            let mut ssl_opts = SslOpts::default();
+            match self.tls_mode {
+                TlsMode::Disabled => unreachable!(),
+                TlsMode::Required => {
+                    ssl_opts = ssl_opts
+                        .with_danger_accept_invalid_certs(true)
+                        .with_danger_skip_domain_validation(true);
+                }
+                TlsMode::VerifyCa => {
+                    ssl_opts = ssl_opts.with_danger_skip_domain_validation(true);
+                }
+                TlsMode::VerifyIdentity => {}
+            }
+            if let Some(tls_root_cert) = &self.tls_root_cert {
+                let mut file = tempfile::NamedTempFile::new()?;
+                file.write_all(tls_root_cert.as_bytes())?;
+                ssl_opts = ssl_opts.with_root_cert_path(Some(file.path().to_owned()));
+                root_cert_file = Some(file);
+            }
+            opts = opts.ssl_opts(ssl_opts);
+        }
+
+        let conn = Conn::new(opts).await?;
+        drop(root_cert_file);
+        Ok(conn)
+    }
+}
+
+/// Verifies that the server records the changes to its tables in a form that
+/// sources can replicate: as full row images in the binary log, with global
+/// transaction identifiers.
+pub async fn ensure_replication_settings(conn: &mut Conn) -> Result<(), anyhow::Error> {
+    for (variable, expected) in [
+        ("gtid_mode", "ON"),
+        ("enforce_gtid_consistency", "ON"),
+        ("binlog_format", "ROW"),
+        ("binlog_row_image", "FULL"),
+    ] {
+        let value: Option<String> = conn
+            .query_first(format!("SELECT @@{variable}"))
+            .await?
+            .ok_or_else(|| anyhow!("missing value of system variable {variable}"))?;
+        let value = value.unwrap_or_default();
+        if !value.eq_ignore_ascii_case(expected) {
+            bail!("MySQL system variable {variable} must be set to {expected}, but is {value}");
+        }
+    }
+    Ok(())
+}
+
+/// Returns the UUID of the server, which identifies the transactions that
+/// originate on it.
+pub async fn server_uuid(conn: &mut Conn) -> Result<Uuid, anyhow::Error> {
+    let uuid: String = conn
+        .query_first("SELECT @@server_uuid")
+        .await?
+        .ok_or_else(|| anyhow!("missing value of system variable server_uuid"))?;
+    Ok(Uuid::parse_str(&uuid)?)
+}
+
+/// Returns the set of transactions that the server has executed.
+pub async fn gtid_executed(conn: &mut Conn) -> Result<GtidSet, anyhow::Error> {
+    let gtid_executed: String = conn
+        .query_first("SELECT @@gtid_executed")
+        .await?
+        .ok_or_else(|| anyhow!("missing value of system variable gtid_executed"))?;
+    gtid_executed.parse()
+}
+
+/// Fetches the schema of every base table on the server, except for those in
+/// the [`SYSTEM_SCHEMAS`].
+///
+/// # Errors
+///
+/// - Invalid connection details, user information, or user permissions.
+pub async fn schema_info(conn: &mut Conn) -> Result<Vec<MySqlTableDesc>, anyhow::Error> {
+    let system_schemas = SYSTEM_SCHEMAS
+        .iter()
+        .map(|schema| format!("'{schema}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let rows: Vec<(String, String, String, String, String, String, String)> = conn
+        .query(format!(
+            "SELECT
+                c.table_schema, c.table_name, c.column_name, c.data_type,
+                c.column_type, c.is_nullable, c.column_key
+            FROM
+                information_schema.columns AS c
+                JOIN information_schema.tables AS t ON
+                    c.table_schema = t.table_schema AND c.table_name = t.table_name
+            WHERE
+                t.table_type = 'BASE TABLE'
+                AND c.table_schema NOT IN ({system_schemas})
+            ORDER BY c.table_schema, c.table_name, c.ordinal_position"
+        ))
+        .await?;
+
+    let mut tables: BTreeMap<(String, String), Vec<MySqlColumnDesc>> = BTreeMap::new();
+    for (schema_name, table_name, name, data_type, column_type, is_nullable, column_key) in rows {
+        tables
+            .entry((schema_name, table_name))
+            .or_default()
+            .push(MySqlColumnDesc {
+                name,
+                data_type: data_type.to_lowercase(),
+                column_type,
+                nullable: is_nullable == "YES",
+                primary_key: column_key == "PRI",
+            });
+    }
+
+    Ok(tables
+        .into_iter()
+        .map(|((schema_name, name), columns)| MySqlTableDesc {
+            schema_name,
+            name,
+            columns,
+        })
+        .collect())
+}
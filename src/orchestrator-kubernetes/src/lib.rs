@@ -37,6 +37,7 @@ use mz_orchestrator::{
     LabelSelectionLogic, NamespacedOrchestrator, Orchestrator, Service, ServiceAssignments,
     ServiceConfig, ServiceEvent, ServiceStatus,
 };
+use mz_secrets::keyring::SecretsEncryptionConfig;
 
 pub mod secrets;
 pub mod util;
@@ -57,6 +58,13 @@ pub struct KubernetesOrchestratorConfig {
     pub service_account: Option<String>,
     /// The image pull policy to set for services created by the orchestrator.
     pub image_pull_policy: KubernetesImagePullPolicy,
+    /// How secrets are encrypted at rest, if at all.
+    ///
+    /// Must not use a [`KeyringConfig::LocalFile`] keyring, as the key file is
+    /// not mounted into the pods of services.
+    ///
+    /// [`KeyringConfig::LocalFile`]: mz_secrets::keyring::KeyringConfig::LocalFile
+    pub secrets_encryption: Option<SecretsEncryptionConfig>,
 }
 
 /// Specifies whether Kubernetes should pull Docker images when creating pods.
@@ -310,6 +318,9 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
             "--secrets-reader-kubernetes-context={}",
             self.config.context
         ));
+        if let Some(encryption) = &self.config.secrets_encryption {
+            args.extend(encryption.service_args());
+        }
 
         let anti_affinity = anti_affinity
             .map(|label_selectors| -> Result<_, anyhow::Error> {
@@ -36,6 +36,7 @@ use mz_orchestrator::{
 };
 use mz_ore::id_gen::PortAllocator;
 use mz_pid_file::PidFile;
+use mz_secrets::keyring::SecretsEncryptionConfig;
 
 use crate::port_metadata_file::PortMetadataFile;
 
@@ -57,6 +58,8 @@ pub struct ProcessOrchestratorConfig {
     pub data_dir: PathBuf,
     /// A command to wrap the child command invocation
     pub command_wrapper: Vec<String>,
+    /// How secrets are encrypted at rest, if at all.
+    pub secrets_encryption: Option<SecretsEncryptionConfig>,
 }
 
 /// An orchestrator backed by processes on the local machine.
@@ -77,6 +80,7 @@ pub struct ProcessOrchestrator {
     data_dir: PathBuf,
     secrets_dir: PathBuf,
     command_wrapper: Vec<String>,
+    secrets_encryption: Option<SecretsEncryptionConfig>,
 }
 
 impl ProcessOrchestrator {
@@ -88,6 +92,7 @@ impl ProcessOrchestrator {
             suppress_output,
             data_dir,
             command_wrapper,
+            secrets_encryption,
         }: ProcessOrchestratorConfig,
     ) -> Result<ProcessOrchestrator, anyhow::Error> {
         let secrets_dir = data_dir.join("secrets");
@@ -105,6 +110,7 @@ impl ProcessOrchestrator {
             data_dir: fs::canonicalize(data_dir).await?,
             secrets_dir: fs::canonicalize(secrets_dir).await?,
             command_wrapper,
+            secrets_encryption,
         })
     }
 }
@@ -122,6 +128,7 @@ impl Orchestrator for ProcessOrchestrator {
                 data_dir: self.data_dir.clone(),
                 secrets_dir: self.secrets_dir.clone(),
                 command_wrapper: self.command_wrapper.clone(),
+                secrets_encryption: self.secrets_encryption.clone(),
             })
         }))
     }
@@ -137,6 +144,7 @@ struct NamespacedProcessOrchestrator {
     data_dir: PathBuf,
     secrets_dir: PathBuf,
     command_wrapper: Vec<String>,
+    secrets_encryption: Option<SecretsEncryptionConfig>,
 }
 
 #[async_trait]
@@ -278,6 +286,9 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
                     "--secrets-reader-process-dir={}",
                     self.secrets_dir.display()
                 ));
+                if let Some(encryption) = &self.secrets_encryption {
+                    args.extend(encryption.service_args());
+                }
 
                 let command_wrapper = self.command_wrapper.clone();
                 handles.push(AbortOnDrop(Box::new(mz_ore::task::spawn(
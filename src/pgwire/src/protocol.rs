@@ -1305,9 +1305,11 @@ where
             | ExecuteResponse::DroppedTable
             | ExecuteResponse::DroppedType
             | ExecuteResponse::DroppedView
+            | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::Inserted(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
+            | ExecuteResponse::RevokedPrivilege
             | ExecuteResponse::StartedTransaction { .. }
             | ExecuteResponse::TransactionCommitted
             | ExecuteResponse::TransactionRolledBack
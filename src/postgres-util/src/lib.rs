@@ -210,42 +210,160 @@ pub async fn drop_replication_slots(config: Config, slots: &[&str]) -> Result<()
     Ok(())
 }
 
-/// Configuration on how to connect a given Postgres database.
-#[derive(PartialEq, Clone)]
-pub enum SshTunnelConfig {
-    /// Establish a direct TCP connection to the database host.
+/// Configuration on how to connect to a given host, e.g. a Postgres database
+/// or a Kafka broker.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TunnelConfig {
+    /// Establish a direct TCP connection to the host.
     Direct,
-    /// Establish a TCP connection to the database via an SSH tunnel.
+    /// Establish a TCP connection to the host via an SSH tunnel.
     /// This means first establishing an SSH connection to a bastion host,
-    /// and then opening a separate connection from that host to the database.
+    /// and then opening a separate connection from that host to the target host.
     /// This is commonly referred by vendors as a "direct SSH tunnel", in
     /// opposition to "reverse SSH tunnel", which is currently unsupported.
-    Tunnel {
-        /// Hostname of the SSH bastion host
-        host: String,
-        /// Port where `sshd` is running in the bastion host
-        port: u16,
-        /// Username to be used in the SSH connection
-        user: String,
-        /// SSH keypair used for authentication.
-        keypair: SshKeypair,
+    Ssh(SshTunnelConfig),
+    /// Establish a TCP connection to the host via an AWS PrivateLink VPC
+    /// endpoint. This means connecting to the endpoint, on the port of the
+    /// host, in place of the host, while still verifying the identity of the
+    /// host if TLS is used.
+    AwsPrivatelink {
+        /// The DNS name of the VPC endpoint.
+        endpoint: String,
     },
 }
 
+/// Configuration of an SSH tunnel through a bastion host.
+#[derive(PartialEq, Clone)]
+pub struct SshTunnelConfig {
+    /// Hostname of the SSH bastion host
+    pub host: String,
+    /// Port where `sshd` is running in the bastion host
+    pub port: u16,
+    /// Username to be used in the SSH connection
+    pub user: String,
+    /// SSH keypair used for authentication.
+    pub keypair: SshKeypair,
+}
+
 // Omit keys from debug output
 impl std::fmt::Debug for SshTunnelConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Direct => write!(f, "Direct"),
-            Self::Tunnel {
-                host, port, user, ..
-            } => f
-                .debug_struct("Tunnel")
-                .field("host", host)
-                .field("port", port)
-                .field("user", user)
-                .finish(),
-        }
+        f.debug_struct("SshTunnelConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .finish()
+    }
+}
+
+impl SshTunnelConfig {
+    /// Sets up an SSH tunnel to `remote_host` and `remote_port`.
+    ///
+    /// Returns a handle that you must keep alive to keep the tunnel open, and
+    /// which reports the local port the tunnel is listening on.
+    pub async fn connect(
+        &self,
+        remote_host: &str,
+        remote_port: u16,
+    ) -> Result<SshTunnelHandle, anyhow::Error> {
+        let SshTunnelConfig {
+            host,
+            port,
+            user,
+            keypair,
+        } = self;
+        let tempdir = tempfile::Builder::new()
+            .prefix("ssh-tunnel-key")
+            .tempdir()?;
+        let path = tempdir.path().join("key");
+        let mut tempfile = File::create(&path)?;
+        // Give read+write permissions on the file
+        tempfile.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        // let private_key = keypair.ssh_private_key();
+        tempfile.write_all(keypair.ssh_private_key().as_bytes())?;
+        // Remove write permissions as soon as the key is written
+        // Mostly helpful to ensure the file is not accidentally overwritten.
+        tempfile.set_permissions(std::fs::Permissions::from_mode(0o400))?;
+
+        let mut builder = openssh::SessionBuilder::default();
+        builder.user(user.clone()).port(*port).keyfile(&path);
+        let session = builder.connect(host).await?;
+
+        // Delete the private key for safety: since `ssh` still has an open handle to it,
+        // it still has access to the key.
+        drop(tempfile);
+        std::fs::remove_file(&path)?;
+        drop(tempdir);
+
+        // Ensure session is healthy
+        session.check().await?;
+
+        // Loop trying to find an open port
+        let mut attempts = 0;
+        let local_port = loop {
+            if attempts > 50 {
+                // If we failed to find an open port after 50 attempts, something is seriously wrong
+                bail!("failed to find an open port to open the SSH tunnel")
+            } else {
+                attempts += 1;
+            }
+
+            let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_entropy();
+            // Choosing a dynamic port according to RFC 6335
+            let local_port: u16 = rng.gen_range(49152..65535);
+
+            let local = openssh::Socket::new(&("localhost", local_port))?;
+            let remote = (remote_host, remote_port);
+            let remote = openssh::Socket::new(&remote)?;
+
+            match session
+                .request_port_forward(openssh::ForwardType::Local, local, remote)
+                .await
+            {
+                Err(err) => match err {
+                    openssh::Error::Ssh(err)
+                        if err.to_string().contains("forwarding request failed") =>
+                    {
+                        tracing::warn!("Port {local_port} already in use, testing another port");
+                    }
+                    _ => {
+                        tracing::error!("SSH connection failed: {err}");
+                        bail!("failed to open SSH tunnel")
+                    }
+                },
+                Ok(_) => break local_port,
+            };
+        };
+
+        Ok(SshTunnelHandle {
+            session,
+            local_port,
+        })
+    }
+}
+
+/// An open SSH tunnel, established by [`SshTunnelConfig::connect`].
+///
+/// The tunnel is closed when the handle is closed or dropped.
+pub struct SshTunnelHandle {
+    session: openssh::Session,
+    local_port: u16,
+}
+
+impl SshTunnelHandle {
+    /// Returns the local port the tunnel is listening on.
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    /// Checks whether the SSH connection to the bastion host is still alive.
+    pub async fn check(&self) -> Result<(), anyhow::Error> {
+        Ok(self.session.check().await?)
+    }
+
+    /// Closes the tunnel.
+    pub async fn close(self) -> Result<(), anyhow::Error> {
+        Ok(self.session.close().await?)
     }
 }
 
@@ -262,7 +380,7 @@ pub struct Config {
     postgres_config: PostgresConfig,
     host: String,
     port: u16,
-    ssh_tunnel: SshTunnelConfig,
+    tunnel: TunnelConfig,
 }
 
 impl Config {
@@ -270,29 +388,30 @@ impl Config {
         postgres_config: PostgresConfig,
         host: &str,
         port: u16,
-        ssh_tunnel: SshTunnelConfig,
+        tunnel: TunnelConfig,
     ) -> Self {
         Self {
             postgres_config,
             host: host.to_string(),
             port,
-            ssh_tunnel,
+            tunnel,
         }
     }
 
     /// Connect to a Postgres database, automatically managing SSL and SSH details as needed.
     pub async fn connect(&self, connection_task_name: &str) -> Result<Client, anyhow::Error> {
         let mut tls = make_tls(&self.postgres_config)?;
-        match self.ssh_tunnel {
-            SshTunnelConfig::Direct => {
+        match &self.tunnel {
+            TunnelConfig::Direct => {
                 let (client, connection) = self.postgres_config.connect(tls).await?;
                 task::spawn(|| connection_task_name, connection);
                 Ok(client)
             }
-            SshTunnelConfig::Tunnel { .. } => {
-                let (session, local_port) = self.establish_openssh_connection().await?;
+            TunnelConfig::Ssh(ssh_tunnel) => {
+                let tunnel = ssh_tunnel.connect(&self.host, self.port).await?;
                 let tls = MakeTlsConnect::<TokioTcpStream>::make_tls_connect(&mut tls, &self.host)?;
-                let tcp_stream = TokioTcpStream::connect(("localhost", local_port)).await?;
+                let tcp_stream =
+                    TokioTcpStream::connect(("localhost", tunnel.local_port())).await?;
                 let (client, connection) =
                     self.postgres_config.connect_raw(tcp_stream, tls).await?;
                 task::spawn(|| connection_task_name, async {
@@ -300,7 +419,7 @@ impl Config {
                         .await
                         .err()
                         .map(|e| tracing::error!("Postgres connection failed: {e}"));
-                    _ = session
+                    _ = tunnel
                         .close()
                         .await
                         .err()
@@ -308,6 +427,14 @@ impl Config {
                 });
                 Ok(client)
             }
+            TunnelConfig::AwsPrivatelink { endpoint } => {
+                let tls = MakeTlsConnect::<TokioTcpStream>::make_tls_connect(&mut tls, &self.host)?;
+                let tcp_stream = TokioTcpStream::connect((endpoint.as_str(), self.port)).await?;
+                let (client, connection) =
+                    self.postgres_config.connect_raw(tcp_stream, tls).await?;
+                task::spawn(|| connection_task_name, connection);
+                Ok(client)
+            }
         }
     }
 
@@ -315,8 +442,8 @@ impl Config {
     // TODO(guswynn): explore how to merge this function with `connect`
     pub async fn connect_replication(mut self) -> Result<Client, anyhow::Error> {
         let mut tls = make_tls(&self.postgres_config)?;
-        match self.ssh_tunnel {
-            SshTunnelConfig::Direct => {
+        match &self.tunnel {
+            TunnelConfig::Direct => {
                 let (client, connection) = self
                     .postgres_config
                     .replication_mode(ReplicationMode::Logical)
@@ -327,10 +454,11 @@ impl Config {
                 task::spawn(|| "postgres_connect_replication", connection);
                 Ok(client)
             }
-            SshTunnelConfig::Tunnel { .. } => {
-                let (session, local_port) = self.establish_openssh_connection().await?;
+            TunnelConfig::Ssh(ssh_tunnel) => {
+                let tunnel = ssh_tunnel.connect(&self.host, self.port).await?;
                 let tls = MakeTlsConnect::<TokioTcpStream>::make_tls_connect(&mut tls, &self.host)?;
-                let tcp_stream = TokioTcpStream::connect(("localhost", local_port)).await?;
+                let tcp_stream =
+                    TokioTcpStream::connect(("localhost", tunnel.local_port())).await?;
                 let (client, connection) = self
                     .postgres_config
                     .replication_mode(ReplicationMode::Logical)
@@ -343,7 +471,7 @@ impl Config {
                         .await
                         .err()
                         .map(|e| tracing::error!("Postgres connection failed: {e}"));
-                    _ = session
+                    _ = tunnel
                         .close()
                         .await
                         .err()
@@ -351,89 +479,19 @@ impl Config {
                 });
                 Ok(client)
             }
-        }
-    }
-
-    /// Sets up an ssh tunnel
-    ///
-    /// Returns the `Session` value you must keep alive to keep the tunnel
-    /// open and the local port the tunnel is listening on.
-    async fn establish_openssh_connection(&self) -> Result<(openssh::Session, u16), anyhow::Error> {
-        match &self.ssh_tunnel {
-            SshTunnelConfig::Tunnel {
-                host,
-                port,
-                user,
-                keypair,
-            } => {
-                let tempdir = tempfile::Builder::new()
-                    .prefix("ssh-tunnel-key")
-                    .tempdir()?;
-                let path = tempdir.path().join("key");
-                let mut tempfile = File::create(&path)?;
-                // Give read+write permissions on the file
-                tempfile.set_permissions(std::fs::Permissions::from_mode(0o600))?;
-                // let private_key = keypair.ssh_private_key();
-                tempfile.write_all(keypair.ssh_private_key().as_bytes())?;
-                // Remove write permissions as soon as the key is written
-                // Mostly helpful to ensure the file is not accidentally overwritten.
-                tempfile.set_permissions(std::fs::Permissions::from_mode(0o400))?;
-
-                let mut builder = openssh::SessionBuilder::default();
-                builder.user(user.clone()).port(*port).keyfile(&path);
-                let session = builder.connect(host).await?;
-
-                // Delete the private key for safety: since `ssh` still has an open handle to it,
-                // it still has access to the key.
-                drop(tempfile);
-                std::fs::remove_file(&path)?;
-                drop(tempdir);
-
-                // Ensure session is healthy
-                session.check().await?;
-
-                // Loop trying to find an open port
-                let mut attempts = 0;
-                let local_port = loop {
-                    if attempts > 50 {
-                        // If we failed to find an open port after 50 attempts, something is seriously wrong
-                        bail!("failed to find an open port to open the SSH tunnel")
-                    } else {
-                        attempts += 1;
-                    }
-
-                    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_entropy();
-                    // Choosing a dynamic port according to RFC 6335
-                    let local_port: u16 = rng.gen_range(49152..65535);
-
-                    let local = openssh::Socket::new(&("localhost", local_port))?;
-                    let remote = (self.host.as_str(), self.port);
-                    let remote = openssh::Socket::new(&remote)?;
-
-                    match session
-                        .request_port_forward(openssh::ForwardType::Local, local, remote)
-                        .await
-                    {
-                        Err(err) => match err {
-                            openssh::Error::Ssh(err)
-                                if err.to_string().contains("forwarding request failed") =>
-                            {
-                                tracing::warn!(
-                                    "Port {local_port} already in use, testing another port"
-                                );
-                            }
-                            _ => {
-                                tracing::error!("SSH connection failed: {err}");
-                                bail!("failed to open SSH tunnel")
-                            }
-                        },
-                        Ok(_) => break local_port,
-                    };
-                };
-
-                Ok((session, local_port))
+            TunnelConfig::AwsPrivatelink { endpoint } => {
+                let tls = MakeTlsConnect::<TokioTcpStream>::make_tls_connect(&mut tls, &self.host)?;
+                let tcp_stream = TokioTcpStream::connect((endpoint.as_str(), self.port)).await?;
+                let (client, connection) = self
+                    .postgres_config
+                    .replication_mode(ReplicationMode::Logical)
+                    .connect_timeout(Duration::from_secs(30))
+                    .keepalives_idle(Duration::from_secs(10 * 60))
+                    .connect_raw(tcp_stream, tls)
+                    .await?;
+                task::spawn(|| "postgres_connect_replication", connection);
+                Ok(client)
             }
-            SshTunnelConfig::Direct => bail!("connection not setup to use SSH"),
         }
     }
 }
@@ -521,6 +521,20 @@ impl From<CheckedTimestamp<DateTime<Utc>>> for DateTime<Utc> {
 }
 
 impl CheckedTimestamp<NaiveDateTime> {
+    /// Returns the earliest supported timestamp.
+    pub fn min() -> Self {
+        Self {
+            t: LOW_DATE.and_hms(0, 0, 0),
+        }
+    }
+
+    /// Returns the latest supported timestamp.
+    pub fn max() -> Self {
+        Self {
+            t: HIGH_DATE.and_hms_micro(23, 59, 59, 999_999),
+        }
+    }
+
     pub fn to_naive(&self) -> NaiveDateTime {
         self.t
     }
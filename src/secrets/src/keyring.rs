@@ -0,0 +1,528 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encryption of secrets at rest.
+//!
+//! An [`EncryptedSecretsController`] wraps another [`SecretsController`] and
+//! encrypts the contents of each secret before handing them to the wrapped
+//! controller. Secrets are protected with envelope encryption: the contents
+//! of every secret are encrypted with a fresh AES-256-GCM data key, and that
+//! data key is in turn wrapped by a [`Keyring`]. The wrapped data key is
+//! stored alongside the ciphertext, so only a reader with access to the same
+//! keyring can recover the secret.
+//!
+//! Secrets that were written before encryption was enabled are stored in
+//! plain text, and are rejected unless reading them is explicitly allowed.
+//! Allowing them is meant only for migrating an existing deployment: such
+//! secrets are returned unchanged, and are encrypted the next time they are
+//! altered.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use aws_sdk_kms::model::DataKeySpec;
+use aws_sdk_kms::types::Blob;
+use openssl::rand::rand_bytes;
+use openssl::symm::{self, Cipher};
+
+use mz_repr::GlobalId;
+
+use crate::{SecretsController, SecretsReader};
+
+/// The prefix that identifies the contents of an encrypted secret.
+const MAGIC: &[u8] = b"MZSECv1\0";
+
+/// The length of an AES-256 key, in bytes.
+const KEY_LEN: usize = 32;
+
+/// The length of an AES-GCM nonce, in bytes.
+const NONCE_LEN: usize = 12;
+
+/// The length of an AES-GCM authentication tag, in bytes.
+const TAG_LEN: usize = 16;
+
+/// The maximum number of unwrapped data keys that an [`AwsKmsKeyring`] caches.
+const AWS_KMS_CACHE_CAPACITY: usize = 1024;
+
+/// A data key with which to encrypt the contents of a single secret.
+pub struct DataKey {
+    /// The key itself.
+    pub plaintext: Vec<u8>,
+    /// The key, wrapped such that only the keyring that generated it can
+    /// recover it.
+    pub wrapped: Vec<u8>,
+}
+
+/// Generates and unwraps the data keys that protect secrets.
+#[async_trait]
+pub trait Keyring: Debug + Send + Sync {
+    /// Generates a new 256-bit data key.
+    async fn generate_data_key(&self) -> Result<DataKey, anyhow::Error>;
+
+    /// Recovers a data key from the wrapped form returned by
+    /// [`Keyring::generate_data_key`].
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// A keyring that wraps data keys with a 256-bit master key held in memory.
+pub struct LocalKeyring {
+    key: [u8; KEY_LEN],
+}
+
+impl LocalKeyring {
+    /// Constructs a new [`LocalKeyring`] that uses the specified master key.
+    pub fn new(key: [u8; KEY_LEN]) -> LocalKeyring {
+        LocalKeyring { key }
+    }
+
+    /// Constructs a new [`LocalKeyring`] whose master key is read from the
+    /// file at `path`.
+    ///
+    /// The file must contain the key encoded as 64 hexadecimal digits,
+    /// optionally surrounded by whitespace, as produced by
+    /// `openssl rand -hex 32`.
+    pub fn from_file(path: &Path) -> Result<LocalKeyring, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading keyring key file {}", path.display()))?;
+        let key = hex::decode(contents.trim()).context("decoding keyring key file")?;
+        let key = key
+            .try_into()
+            .map_err(|_| anyhow!("keyring key file must contain a {}-bit key", KEY_LEN * 8))?;
+        Ok(LocalKeyring::new(key))
+    }
+}
+
+impl Debug for LocalKeyring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print the master key.
+        f.debug_struct("LocalKeyring").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Keyring for LocalKeyring {
+    async fn generate_data_key(&self) -> Result<DataKey, anyhow::Error> {
+        let mut plaintext = vec![0; KEY_LEN];
+        rand_bytes(&mut plaintext)?;
+        let wrapped = seal(&self.key, &[], &plaintext)?;
+        Ok(DataKey { plaintext, wrapped })
+    }
+
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        open(&self.key, &[], wrapped).context("unwrapping data key")
+    }
+}
+
+/// A keyring that wraps data keys with a key managed by AWS KMS.
+///
+/// Credentials and the region are loaded from the environment, as by the AWS
+/// CLI.
+///
+/// Unwrapped data keys are cached in memory, keyed by their wrapped form, so
+/// that reading a secret repeatedly calls KMS only once.
+pub struct AwsKmsKeyring {
+    client: aws_sdk_kms::Client,
+    key_id: String,
+    cache: Mutex<BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl AwsKmsKeyring {
+    /// Constructs a new [`AwsKmsKeyring`] that wraps data keys with the KMS key
+    /// identified by `key_id`, which may be a key ID, a key ARN, or an alias.
+    pub async fn new(key_id: String) -> AwsKmsKeyring {
+        let config = aws_config::load_from_env().await;
+        AwsKmsKeyring {
+            client: aws_sdk_kms::Client::new(&config),
+            key_id,
+            cache: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn cache_get(&self, wrapped: &[u8]) -> Option<Vec<u8>> {
+        self.cache
+            .lock()
+            .expect("lock poisoned")
+            .get(wrapped)
+            .cloned()
+    }
+
+    fn cache_insert(&self, wrapped: Vec<u8>, plaintext: Vec<u8>) {
+        let mut cache = self.cache.lock().expect("lock poisoned");
+        if cache.len() >= AWS_KMS_CACHE_CAPACITY && !cache.contains_key(&wrapped) {
+            // Evicting an arbitrary entry is good enough, as a deployment
+            // rarely has more secrets than fit in the cache.
+            if let Some(evicted) = cache.keys().next().cloned() {
+                cache.remove(&evicted);
+            }
+        }
+        cache.insert(wrapped, plaintext);
+    }
+}
+
+impl Debug for AwsKmsKeyring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print the cached data keys.
+        f.debug_struct("AwsKmsKeyring")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Keyring for AwsKmsKeyring {
+    async fn generate_data_key(&self) -> Result<DataKey, anyhow::Error> {
+        let output = self
+            .client
+            .generate_data_key()
+            .key_id(&self.key_id)
+            .key_spec(DataKeySpec::Aes256)
+            .send()
+            .await
+            .context("generating data key")?;
+        let data_key = match (output.plaintext(), output.ciphertext_blob()) {
+            (Some(plaintext), Some(wrapped)) => DataKey {
+                plaintext: plaintext.as_ref().to_vec(),
+                wrapped: wrapped.as_ref().to_vec(),
+            },
+            _ => bail!("AWS KMS did not return a data key"),
+        };
+        self.cache_insert(data_key.wrapped.clone(), data_key.plaintext.clone());
+        Ok(data_key)
+    }
+
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        if let Some(plaintext) = self.cache_get(wrapped) {
+            return Ok(plaintext);
+        }
+        let output = self
+            .client
+            .decrypt()
+            .key_id(&self.key_id)
+            .ciphertext_blob(Blob::new(wrapped))
+            .send()
+            .await
+            .context("unwrapping data key")?;
+        let plaintext = match output.plaintext() {
+            Some(plaintext) => plaintext.as_ref().to_vec(),
+            None => bail!("AWS KMS did not return the unwrapped data key"),
+        };
+        self.cache_insert(wrapped.to_vec(), plaintext.clone());
+        Ok(plaintext)
+    }
+}
+
+/// Specifies the keyring with which to encrypt secrets.
+#[derive(Debug, Clone)]
+pub enum KeyringConfig {
+    /// A [`LocalKeyring`] whose master key is read from the specified file.
+    LocalFile(PathBuf),
+    /// An [`AwsKmsKeyring`] that uses the specified KMS key.
+    AwsKms {
+        /// The ID, ARN, or alias of the KMS key.
+        key_id: String,
+    },
+}
+
+impl KeyringConfig {
+    /// Loads the keyring described by this configuration.
+    pub async fn load(&self) -> Result<Arc<dyn Keyring>, anyhow::Error> {
+        match self {
+            KeyringConfig::LocalFile(path) => Ok(Arc::new(LocalKeyring::from_file(path)?)),
+            KeyringConfig::AwsKms { key_id } => {
+                Ok(Arc::new(AwsKmsKeyring::new(key_id.clone()).await))
+            }
+        }
+    }
+}
+
+/// Specifies how secrets are encrypted at rest.
+#[derive(Debug, Clone)]
+pub struct SecretsEncryptionConfig {
+    /// The keyring with which secrets are encrypted.
+    pub keyring: KeyringConfig,
+    /// Whether to read secrets that were stored in plain text, before
+    /// encryption was enabled.
+    ///
+    /// This is only meant to be enabled while migrating an existing
+    /// deployment, until every secret has been altered and thereby encrypted.
+    pub allow_plaintext: bool,
+}
+
+impl SecretsEncryptionConfig {
+    /// Returns the command-line arguments that instruct the secrets reader of
+    /// an orchestrated service to decrypt secrets accordingly.
+    pub fn service_args(&self) -> Vec<String> {
+        let mut args = match &self.keyring {
+            KeyringConfig::LocalFile(path) => vec![
+                "--secrets-reader-keyring=local-file".into(),
+                format!("--secrets-reader-keyring-local-file={}", path.display()),
+            ],
+            KeyringConfig::AwsKms { key_id } => vec![
+                "--secrets-reader-keyring=aws-kms".into(),
+                format!("--secrets-reader-keyring-aws-kms-key-id={key_id}"),
+            ],
+        };
+        if self.allow_plaintext {
+            args.push("--secrets-reader-keyring-allow-plaintext".into());
+        }
+        args
+    }
+}
+
+/// A [`SecretsController`] that encrypts secrets before storing them in
+/// another controller.
+#[derive(Debug)]
+pub struct EncryptedSecretsController {
+    inner: Arc<dyn SecretsController>,
+    keyring: Arc<dyn Keyring>,
+    allow_plaintext: bool,
+}
+
+impl EncryptedSecretsController {
+    /// Constructs a new [`EncryptedSecretsController`] that stores secrets in
+    /// `inner`, encrypted with data keys from `keyring`.
+    ///
+    /// If `allow_plaintext` is set, the readers of the controller return
+    /// secrets that were stored in plain text unchanged, rather than failing.
+    pub fn new(
+        inner: Arc<dyn SecretsController>,
+        keyring: Arc<dyn Keyring>,
+        allow_plaintext: bool,
+    ) -> EncryptedSecretsController {
+        EncryptedSecretsController {
+            inner,
+            keyring,
+            allow_plaintext,
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsController for EncryptedSecretsController {
+    async fn ensure(&self, id: GlobalId, contents: &[u8]) -> Result<(), anyhow::Error> {
+        let contents = encrypt(&*self.keyring, id, contents).await?;
+        self.inner.ensure(id, &contents).await
+    }
+
+    async fn delete(&self, id: GlobalId) -> Result<(), anyhow::Error> {
+        self.inner.delete(id).await
+    }
+
+    fn reader(&self) -> Arc<dyn SecretsReader> {
+        Arc::new(EncryptedSecretsReader::new(
+            self.inner.reader(),
+            Arc::clone(&self.keyring),
+            self.allow_plaintext,
+        ))
+    }
+}
+
+/// A [`SecretsReader`] that decrypts secrets written by an
+/// [`EncryptedSecretsController`].
+#[derive(Debug)]
+pub struct EncryptedSecretsReader {
+    inner: Arc<dyn SecretsReader>,
+    keyring: Arc<dyn Keyring>,
+    allow_plaintext: bool,
+}
+
+impl EncryptedSecretsReader {
+    /// Constructs a new [`EncryptedSecretsReader`] that reads secrets from
+    /// `inner` and decrypts them with data keys from `keyring`.
+    ///
+    /// If `allow_plaintext` is set, secrets that were stored in plain text are
+    /// returned unchanged, rather than failing.
+    pub fn new(
+        inner: Arc<dyn SecretsReader>,
+        keyring: Arc<dyn Keyring>,
+        allow_plaintext: bool,
+    ) -> EncryptedSecretsReader {
+        EncryptedSecretsReader {
+            inner,
+            keyring,
+            allow_plaintext,
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsReader for EncryptedSecretsReader {
+    async fn read(&self, id: GlobalId) -> Result<Vec<u8>, anyhow::Error> {
+        let contents = self.inner.read(id).await?;
+        decrypt(&*self.keyring, id, contents, self.allow_plaintext).await
+    }
+}
+
+/// Encrypts the contents of the secret with the specified ID.
+///
+/// The encrypted form consists of [`MAGIC`], the length of the wrapped data
+/// key as a big-endian `u16`, the wrapped data key, and the sealed contents.
+/// The ID of the secret is authenticated along with the contents, so that the
+/// encrypted contents of one secret cannot be substituted for another's.
+async fn encrypt(
+    keyring: &dyn Keyring,
+    id: GlobalId,
+    contents: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let data_key = keyring.generate_data_key().await?;
+    let wrapped_len =
+        u16::try_from(data_key.wrapped.len()).context("wrapped data key is too large")?;
+    let sealed = seal(&data_key.plaintext, id.to_string().as_bytes(), contents)?;
+    let mut out = Vec::with_capacity(MAGIC.len() + 2 + data_key.wrapped.len() + sealed.len());
+    out.extend(MAGIC);
+    out.extend(wrapped_len.to_be_bytes());
+    out.extend(&data_key.wrapped);
+    out.extend(sealed);
+    Ok(out)
+}
+
+/// Decrypts the contents of the secret with the specified ID, as encrypted by
+/// [`encrypt`].
+///
+/// Contents that do not begin with [`MAGIC`] were written before encryption
+/// was enabled. They are returned unchanged if `allow_plaintext` is set, and
+/// rejected otherwise.
+async fn decrypt(
+    keyring: &dyn Keyring,
+    id: GlobalId,
+    contents: Vec<u8>,
+    allow_plaintext: bool,
+) -> Result<Vec<u8>, anyhow::Error> {
+    if !contents.starts_with(MAGIC) {
+        if allow_plaintext {
+            return Ok(contents);
+        }
+        bail!("secret {id} is not encrypted, and reading unencrypted secrets is not allowed");
+    }
+    let rest = &contents[MAGIC.len()..];
+    if rest.len() < 2 {
+        bail!("encrypted secret is truncated");
+    }
+    let (wrapped_len, rest) = rest.split_at(2);
+    let wrapped_len = usize::from(u16::from_be_bytes([wrapped_len[0], wrapped_len[1]]));
+    if rest.len() < wrapped_len {
+        bail!("encrypted secret is truncated");
+    }
+    let (wrapped, sealed) = rest.split_at(wrapped_len);
+    let data_key = keyring.unwrap_data_key(wrapped).await?;
+    open(&data_key, id.to_string().as_bytes(), sealed).context("decrypting secret")
+}
+
+/// Encrypts `plaintext` with AES-256-GCM under `key`, authenticating `aad`
+/// along with it.
+///
+/// Returns the random nonce, the ciphertext, and the authentication tag,
+/// concatenated.
+fn seal(key: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut nonce = [0; NONCE_LEN];
+    rand_bytes(&mut nonce)?;
+    let mut tag = [0; TAG_LEN];
+    let ciphertext = symm::encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        aad,
+        plaintext,
+        &mut tag,
+    )?;
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len() + TAG_LEN);
+    out.extend(nonce);
+    out.extend(ciphertext);
+    out.extend(tag);
+    Ok(out)
+}
+
+/// Decrypts the output of [`seal`].
+fn open(key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        bail!("ciphertext is truncated");
+    }
+    let (nonce, rest) = sealed.split_at(NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+    let plaintext = symm::decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        aad,
+        ciphertext,
+        tag,
+    )?;
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mz_repr::GlobalId;
+
+    use crate::{InMemorySecretsController, SecretsController};
+
+    use super::{EncryptedSecretsController, Keyring, LocalKeyring, MAGIC};
+
+    #[tokio::test]
+    async fn test_encrypted_secrets_controller() -> Result<(), anyhow::Error> {
+        let inner = Arc::new(InMemorySecretsController::new());
+        let keyring = Arc::new(LocalKeyring::new([7; 32]));
+        let controller = EncryptedSecretsController::new(
+            Arc::clone(&inner) as Arc<dyn SecretsController>,
+            Arc::clone(&keyring) as Arc<dyn Keyring>,
+            false,
+        );
+        let reader = controller.reader();
+
+        // Secrets round trip, and are not stored in plain text.
+        controller.ensure(GlobalId::User(1), b"hunter2").await?;
+        assert_eq!(reader.read(GlobalId::User(1)).await?, b"hunter2");
+        let stored = inner.reader().read(GlobalId::User(1)).await?;
+        assert!(stored.starts_with(MAGIC));
+        assert!(!stored.windows(7).any(|w| w == b"hunter2"));
+
+        // Secrets written before encryption was enabled are rejected, unless
+        // reading them is explicitly allowed.
+        inner.ensure(GlobalId::User(2), b"legacy").await?;
+        assert!(reader.read(GlobalId::User(2)).await.is_err());
+        let migrating = EncryptedSecretsController::new(
+            Arc::clone(&inner) as Arc<dyn SecretsController>,
+            keyring,
+            true,
+        );
+        assert_eq!(migrating.reader().read(GlobalId::User(2)).await?, b"legacy");
+        assert_eq!(
+            migrating.reader().read(GlobalId::User(1)).await?,
+            b"hunter2"
+        );
+
+        // The encrypted contents of one secret cannot be substituted for
+        // another's.
+        inner.ensure(GlobalId::User(3), &stored).await?;
+        assert!(reader.read(GlobalId::User(3)).await.is_err());
+
+        // Secrets cannot be read with a different master key.
+        let other = EncryptedSecretsController::new(
+            Arc::clone(&inner) as Arc<dyn SecretsController>,
+            Arc::new(LocalKeyring::new([8; 32])),
+            false,
+        );
+        assert!(other.reader().read(GlobalId::User(1)).await.is_err());
+
+        // Tampering with the stored contents is detected.
+        let mut tampered = stored.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        inner.ensure(GlobalId::User(1), &tampered).await?;
+        assert!(reader.read(GlobalId::User(1)).await.is_err());
+
+        Ok(())
+    }
+}
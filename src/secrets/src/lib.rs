@@ -18,6 +18,8 @@ use async_trait::async_trait;
 
 use mz_repr::GlobalId;
 
+pub mod keyring;
+
 /// Securely manages user secrets.
 #[async_trait]
 pub trait SecretsController: Debug + Send + Sync {
@@ -14,6 +14,7 @@ use clap::ArgEnum;
 
 use mz_orchestrator_kubernetes::secrets::KubernetesSecretsReader;
 use mz_orchestrator_process::secrets::ProcessSecretsReader;
+use mz_secrets::keyring::{EncryptedSecretsReader, KeyringConfig};
 use mz_secrets::SecretsReader;
 
 #[derive(clap::Parser)]
@@ -29,6 +30,20 @@ pub struct SecretsReaderCliArgs {
     /// load.
     #[structopt(long, required_if_eq("secrets-reader", "kubernetes"))]
     secrets_reader_kubernetes_context: Option<String>,
+    /// The keyring with which secrets are encrypted, if any.
+    #[structopt(long, arg_enum)]
+    secrets_reader_keyring: Option<SecretsKeyringKind>,
+    /// When using the local file keyring, the file that contains the master
+    /// key.
+    #[structopt(long, required_if_eq("secrets-reader-keyring", "local-file"))]
+    secrets_reader_keyring_local_file: Option<PathBuf>,
+    /// When using the AWS KMS keyring, the ID, ARN, or alias of the KMS key.
+    #[structopt(long, required_if_eq("secrets-reader-keyring", "aws-kms"))]
+    secrets_reader_keyring_aws_kms_key_id: Option<String>,
+    /// Whether to read secrets that were stored unencrypted, before a keyring
+    /// was specified.
+    #[structopt(long, requires = "secrets-reader-keyring")]
+    secrets_reader_keyring_allow_plaintext: bool,
 }
 
 #[derive(ArgEnum, Debug, Clone)]
@@ -37,20 +52,43 @@ enum SecretsReaderKind {
     Kubernetes,
 }
 
+#[derive(ArgEnum, Debug, Clone)]
+enum SecretsKeyringKind {
+    LocalFile,
+    AwsKms,
+}
+
 impl SecretsReaderCliArgs {
     /// Loads the secrets reader specified by the command-line arguments.
     pub async fn load(self) -> Result<Arc<dyn SecretsReader>, anyhow::Error> {
-        match self.secrets_reader {
+        let reader: Arc<dyn SecretsReader> = match self.secrets_reader {
             SecretsReaderKind::Process => {
                 let dir = self.secrets_reader_process_dir.expect("clap enforced");
-                Ok(Arc::new(ProcessSecretsReader::new(dir)))
+                Arc::new(ProcessSecretsReader::new(dir))
             }
             SecretsReaderKind::Kubernetes => {
                 let context = self
                     .secrets_reader_kubernetes_context
                     .expect("clap enforced");
-                Ok(Arc::new(KubernetesSecretsReader::new(context).await?))
+                Arc::new(KubernetesSecretsReader::new(context).await?)
             }
-        }
+        };
+        let keyring = match self.secrets_reader_keyring {
+            None => return Ok(reader),
+            Some(SecretsKeyringKind::LocalFile) => KeyringConfig::LocalFile(
+                self.secrets_reader_keyring_local_file
+                    .expect("clap enforced"),
+            ),
+            Some(SecretsKeyringKind::AwsKms) => KeyringConfig::AwsKms {
+                key_id: self
+                    .secrets_reader_keyring_aws_kms_key_id
+                    .expect("clap enforced"),
+            },
+        };
+        Ok(Arc::new(EncryptedSecretsReader::new(
+            reader,
+            keyring.load().await?,
+            self.secrets_reader_keyring_allow_plaintext,
+        )))
     }
 }
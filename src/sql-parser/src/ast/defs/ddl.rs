@@ -139,6 +139,10 @@ impl_display_t!(ProtobufSchema);
 pub enum CsrConfigOptionName {
     AvroKeyFullname,
     AvroValueFullname,
+    AvroValueSchema,
+    CompatibilityLevel,
+    OnDropDeleteSubjects,
+    SubjectNameStrategy,
 }
 
 impl AstDisplay for CsrConfigOptionName {
@@ -146,6 +150,10 @@ impl AstDisplay for CsrConfigOptionName {
         f.write_str(match self {
             CsrConfigOptionName::AvroKeyFullname => "AVRO KEY FULLNAME",
             CsrConfigOptionName::AvroValueFullname => "AVRO VALUE FULLNAME",
+            CsrConfigOptionName::AvroValueSchema => "AVRO VALUE SCHEMA",
+            CsrConfigOptionName::CompatibilityLevel => "COMPATIBILITY LEVEL",
+            CsrConfigOptionName::OnDropDeleteSubjects => "ON DROP DELETE SUBJECTS",
+            CsrConfigOptionName::SubjectNameStrategy => "SUBJECT NAME STRATEGY",
         })
     }
 }
@@ -337,9 +345,36 @@ pub enum Format<T: AstInfo> {
     Csv {
         columns: CsvColumns,
         delimiter: char,
+        /// The character that quotes fields.
+        quote: char,
+        /// The character that escapes quotes inside of quoted fields, if
+        /// any.
+        escape: Option<char>,
+        /// The columns to decode, selected by name from `columns`, with the
+        /// types to decode them as. If empty, sources decode every column as
+        /// text.
+        typed_columns: Vec<ColumnDef<T>>,
+    },
+    Json {
+        /// The schema registry to publish the JSON Schemas of the records to,
+        /// if any.
+        csr_connection: Option<CsrConnection<T>>,
+        /// The columns to decode the fields of each JSON object into. If
+        /// empty, sources decode each message into a single `jsonb` column.
+        columns: Vec<ColumnDef<T>>,
+        /// Whether sources fail to decode objects with fields that are not
+        /// among `columns`, rather than ignoring those fields.
+        reject_unknown_fields: bool,
     },
-    Json,
     Text,
+    Parquet {
+        /// The columns to read from each object, if known. Purification
+        /// fills these in for S3 sources from the schema of a Parquet object
+        /// in the bucket.
+        columns: Vec<ColumnDef<T>>,
+    },
+    /// Avro object container files, as written by file sinks.
+    AvroOcf,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -370,16 +405,23 @@ impl AstDisplay for CsvColumns {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum SourceIncludeMetadataType {
+pub enum SourceIncludeMetadataType<T: AstInfo> {
     Key,
     Timestamp,
     Partition,
     Topic,
     Offset,
     Headers,
+    /// The name of the file that a line was read from.
+    Filename,
+    /// A single header, decoded into its own column.
+    Header {
+        key: String,
+        format: Option<Format<T>>,
+    },
 }
 
-impl AstDisplay for SourceIncludeMetadataType {
+impl<T: AstInfo> AstDisplay for SourceIncludeMetadataType<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             SourceIncludeMetadataType::Key => f.write_str("KEY"),
@@ -388,18 +430,28 @@ impl AstDisplay for SourceIncludeMetadataType {
             SourceIncludeMetadataType::Topic => f.write_str("TOPIC"),
             SourceIncludeMetadataType::Offset => f.write_str("OFFSET"),
             SourceIncludeMetadataType::Headers => f.write_str("HEADERS"),
+            SourceIncludeMetadataType::Filename => f.write_str("FILENAME"),
+            SourceIncludeMetadataType::Header { key, format } => {
+                f.write_str("HEADER '");
+                f.write_node(&display::escape_single_quote_string(key));
+                f.write_str("'");
+                if let Some(format) = format {
+                    f.write_str(" FORMAT ");
+                    f.write_node(format);
+                }
+            }
         }
     }
 }
-impl_display!(SourceIncludeMetadataType);
+impl_display_t!(SourceIncludeMetadataType);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SourceIncludeMetadata {
-    pub ty: SourceIncludeMetadataType,
+pub struct SourceIncludeMetadata<T: AstInfo> {
+    pub ty: SourceIncludeMetadataType<T>,
     pub alias: Option<Ident>,
 }
 
-impl AstDisplay for SourceIncludeMetadata {
+impl<T: AstInfo> AstDisplay for SourceIncludeMetadata<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_node(&self.ty);
         if let Some(alias) = &self.alias {
@@ -408,17 +460,17 @@ impl AstDisplay for SourceIncludeMetadata {
         }
     }
 }
-impl_display!(SourceIncludeMetadata);
+impl_display_t!(SourceIncludeMetadata);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Envelope {
+pub enum Envelope<T: AstInfo> {
     None,
-    Debezium(DbzMode),
+    Debezium(DbzMode<T>),
     Upsert,
     CdcV2,
 }
 
-impl Envelope {
+impl<T: AstInfo> Envelope<T> {
     /// `true` iff Materialize is expected to crash or exhibit UB
     /// when attempting to ingest data starting at an offset other than zero.
     pub fn requires_all_input(&self) -> bool {
@@ -426,14 +478,16 @@ impl Envelope {
             Envelope::None => false,
             // TODO[btv] - Adjust this if we change Dbz semantics
             // (why is this a parser-level concept, anyway? Should it be moved?)
-            Envelope::Debezium(DbzMode::Plain) => false,
+            // Deduplicating by transaction replays each transaction in full,
+            // so it cannot start partway through the topic.
+            Envelope::Debezium(DbzMode::Plain { tx_metadata }) => !tx_metadata.is_empty(),
             Envelope::Upsert => false,
             Envelope::CdcV2 => true,
         }
     }
 }
 
-impl AstDisplay for Envelope {
+impl<T: AstInfo> AstDisplay for Envelope<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::None => {
@@ -453,7 +507,7 @@ impl AstDisplay for Envelope {
         }
     }
 }
-impl_display!(Envelope);
+impl_display_t!(Envelope);
 
 impl<T: AstInfo> AstDisplay for Format<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
@@ -472,7 +526,13 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_node(&display::escape_single_quote_string(regex));
                 f.write_str("'");
             }
-            Self::Csv { columns, delimiter } => {
+            Self::Csv {
+                columns,
+                delimiter,
+                quote,
+                escape,
+                typed_columns,
+            } => {
                 f.write_str("CSV WITH ");
                 f.write_node(columns);
 
@@ -481,9 +541,51 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_node(&display::escape_single_quote_string(&delimiter.to_string()));
                     f.write_str("'");
                 }
+                if *quote != '"' {
+                    f.write_str(" QUOTE '");
+                    f.write_node(&display::escape_single_quote_string(&quote.to_string()));
+                    f.write_str("'");
+                }
+                if let Some(escape) = escape {
+                    f.write_str(" ESCAPE '");
+                    f.write_node(&display::escape_single_quote_string(&escape.to_string()));
+                    f.write_str("'");
+                }
+                if !typed_columns.is_empty() {
+                    f.write_str(" COLUMNS (");
+                    f.write_node(&display::comma_separated(typed_columns));
+                    f.write_str(")");
+                }
+            }
+            Self::Json {
+                csr_connection,
+                columns,
+                reject_unknown_fields,
+            } => {
+                f.write_str("JSON");
+                if let Some(csr_connection) = csr_connection {
+                    f.write_str(" USING CONFLUENT SCHEMA REGISTRY ");
+                    f.write_node(csr_connection);
+                }
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+                if *reject_unknown_fields {
+                    f.write_str(" REJECT UNKNOWN FIELDS");
+                }
             }
-            Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet { columns } => {
+                f.write_str("PARQUET");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
+            Self::AvroOcf => f.write_str("AVRO OCF"),
         }
     }
 }
@@ -491,14 +593,22 @@ impl_display_t!(Format);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Compression {
+    /// Detect the compression of each object or file from its contents and
+    /// name.
+    Auto,
+    Bzip2,
     Gzip,
+    Zstd,
     None,
 }
 
 impl AstDisplay for Compression {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
+            Self::Auto => f.write_str("AUTO"),
+            Self::Bzip2 => f.write_str("BZIP2"),
             Self::Gzip => f.write_str("GZIP"),
+            Self::Zstd => f.write_str("ZSTD"),
             Self::None => f.write_str("NONE"),
         }
     }
@@ -506,23 +616,35 @@ impl AstDisplay for Compression {
 impl_display!(Compression);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum DbzMode {
+pub enum DbzMode<T: AstInfo> {
     /// There is now only one `DEBEZIUM` envelope,
     /// which has upsert semantics in sources and classic
     /// semantics in sinks.
-    Plain,
+    ///
+    /// If `tx_metadata` is not empty, sources instead deduplicate the
+    /// Debezium stream and group its updates by the upstream transaction
+    /// that produced them.
+    Plain {
+        tx_metadata: Vec<DbzTxMetadataOption<T>>,
+    },
 }
 
-impl AstDisplay for DbzMode {
-    fn fmt<W: fmt::Write>(&self, _f: &mut AstFormatter<W>) {
+impl<T: AstInfo> AstDisplay for DbzMode<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             // We interpret the bare keyword `DEBEZIUM` as debezium upsert, so don't
             // display anything here.
-            Self::Plain => {}
+            Self::Plain { tx_metadata } => {
+                if !tx_metadata.is_empty() {
+                    f.write_str(" (TRANSACTION METADATA (");
+                    f.write_node(&display::comma_separated(tx_metadata));
+                    f.write_str("))");
+                }
+            }
         }
     }
 }
-impl_display!(DbzMode);
+impl_display_t!(DbzMode);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DbzTxMetadataOption<T: AstInfo> {
@@ -549,6 +671,7 @@ impl_display_t!(DbzTxMetadataOption);
 pub enum KafkaConnectionOptionName {
     Broker,
     Brokers,
+    EventHubsConnectionString,
     ProgressTopic,
     SslKey,
     SslCertificate,
@@ -556,6 +679,13 @@ pub enum KafkaConnectionOptionName {
     SaslMechanisms,
     SaslUsername,
     SaslPassword,
+    SaslOauthTokenEndpoint,
+    SaslOauthClientId,
+    SaslOauthClientSecret,
+    SaslOauthScope,
+    AwsConnection,
+    AwsPrivatelink,
+    SshTunnel,
 }
 
 impl AstDisplay for KafkaConnectionOptionName {
@@ -563,6 +693,7 @@ impl AstDisplay for KafkaConnectionOptionName {
         f.write_str(match self {
             KafkaConnectionOptionName::Broker => "BROKER",
             KafkaConnectionOptionName::Brokers => "BROKERS",
+            KafkaConnectionOptionName::EventHubsConnectionString => "EVENT HUBS CONNECTION STRING",
             KafkaConnectionOptionName::ProgressTopic => "PROGRESS TOPIC",
             KafkaConnectionOptionName::SslKey => "SSL KEY",
             KafkaConnectionOptionName::SslCertificate => "SSL CERTIFICATE",
@@ -570,6 +701,13 @@ impl AstDisplay for KafkaConnectionOptionName {
             KafkaConnectionOptionName::SaslMechanisms => "SASL MECHANISMS",
             KafkaConnectionOptionName::SaslUsername => "SASL USERNAME",
             KafkaConnectionOptionName::SaslPassword => "SASL PASSWORD",
+            KafkaConnectionOptionName::SaslOauthTokenEndpoint => "SASL OAUTH TOKEN ENDPOINT",
+            KafkaConnectionOptionName::SaslOauthClientId => "SASL OAUTH CLIENT ID",
+            KafkaConnectionOptionName::SaslOauthClientSecret => "SASL OAUTH CLIENT SECRET",
+            KafkaConnectionOptionName::SaslOauthScope => "SASL OAUTH SCOPE",
+            KafkaConnectionOptionName::AwsConnection => "AWS CONNECTION",
+            KafkaConnectionOptionName::AwsPrivatelink => "AWS PRIVATELINK",
+            KafkaConnectionOptionName::SshTunnel => "SSH TUNNEL",
         })
     }
 }
@@ -637,6 +775,7 @@ impl_display_t!(CsrConnectionOption);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PostgresConnectionOptionName {
+    AwsPrivatelink,
     Database,
     Host,
     Password,
@@ -652,6 +791,7 @@ pub enum PostgresConnectionOptionName {
 impl AstDisplay for PostgresConnectionOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
+            PostgresConnectionOptionName::AwsPrivatelink => "AWS PRIVATELINK",
             PostgresConnectionOptionName::Database => "DATABASE",
             PostgresConnectionOptionName::Host => "HOST",
             PostgresConnectionOptionName::Password => "PASSWORD",
@@ -685,6 +825,92 @@ impl<T: AstInfo> AstDisplay for PostgresConnectionOption<T> {
 }
 impl_display_t!(PostgresConnectionOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MySqlConnectionOptionName {
+    Host,
+    Password,
+    Port,
+    SslCertificateAuthority,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for MySqlConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MySqlConnectionOptionName::Host => "HOST",
+            MySqlConnectionOptionName::Password => "PASSWORD",
+            MySqlConnectionOptionName::Port => "PORT",
+            MySqlConnectionOptionName::SslCertificateAuthority => "SSL CERTIFICATE AUTHORITY",
+            MySqlConnectionOptionName::SslMode => "SSL MODE",
+            MySqlConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(MySqlConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... MYSQL`.
+pub struct MySqlConnectionOption<T: AstInfo> {
+    pub name: MySqlConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MySqlConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MySqlConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SqlServerConnectionOptionName {
+    Database,
+    Host,
+    Password,
+    Port,
+    SslCertificateAuthority,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for SqlServerConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConnectionOptionName::Database => "DATABASE",
+            SqlServerConnectionOptionName::Host => "HOST",
+            SqlServerConnectionOptionName::Password => "PASSWORD",
+            SqlServerConnectionOptionName::Port => "PORT",
+            SqlServerConnectionOptionName::SslCertificateAuthority => "SSL CERTIFICATE AUTHORITY",
+            SqlServerConnectionOptionName::SslMode => "SSL MODE",
+            SqlServerConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(SqlServerConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... SQL SERVER`.
+pub struct SqlServerConnectionOption<T: AstInfo> {
+    pub name: SqlServerConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqlServerConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqlServerConnectionOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AwsConnectionOptionName {
     AccessKeyId,
@@ -763,11 +989,46 @@ impl<T: AstInfo> AstDisplay for SshConnectionOption<T> {
 }
 impl_display_t!(SshConnectionOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AwsPrivatelinkConnectionOptionName {
+    Endpoint,
+}
+
+impl AstDisplay for AwsPrivatelinkConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            AwsPrivatelinkConnectionOptionName::Endpoint => "ENDPOINT",
+        })
+    }
+}
+impl_display!(AwsPrivatelinkConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION...AWS PRIVATELINK`.
+pub struct AwsPrivatelinkConnectionOption<T: AstInfo> {
+    pub name: AwsPrivatelinkConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AwsPrivatelinkConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(AwsPrivatelinkConnectionOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateConnection<T: AstInfo> {
     Aws {
         with_options: Vec<AwsConnectionOption<T>>,
     },
+    AwsPrivatelink {
+        with_options: Vec<AwsPrivatelinkConnectionOption<T>>,
+    },
     Kafka {
         with_options: Vec<KafkaConnectionOption<T>>,
     },
@@ -777,6 +1038,12 @@ pub enum CreateConnection<T: AstInfo> {
     Postgres {
         with_options: Vec<PostgresConnectionOption<T>>,
     },
+    MySql {
+        with_options: Vec<MySqlConnectionOption<T>>,
+    },
+    SqlServer {
+        with_options: Vec<SqlServerConnectionOption<T>>,
+    },
     Ssh {
         with_options: Vec<SshConnectionOption<T>>,
     },
@@ -800,11 +1067,26 @@ impl<T: AstInfo> AstDisplay for CreateConnection<T> {
                 f.write_node(&display::comma_separated(with_options));
                 f.write_str(")");
             }
+            Self::MySql { with_options } => {
+                f.write_str("MYSQL (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::SqlServer { with_options } => {
+                f.write_str("SQL SERVER (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
             Self::Aws { with_options } => {
                 f.write_str("AWS (");
                 f.write_node(&display::comma_separated(with_options));
                 f.write_str(")");
             }
+            Self::AwsPrivatelink { with_options } => {
+                f.write_str("AWS PRIVATELINK (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
             Self::Ssh { with_options } => {
                 f.write_str("SSH TUNNEL (");
                 f.write_node(&display::comma_separated(with_options));
@@ -818,44 +1100,76 @@ impl_display_t!(CreateConnection);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum KafkaConfigOptionName {
     Acks,
+    BatchNumMessages,
     ClientId,
+    CommitInterval,
+    CompressionType,
+    DeadLetterTopic,
     EnableIdempotence,
     FetchMessageMaxBytes,
     GroupIdPrefix,
+    Headers,
     IsolationLevel,
+    LingerMs,
+    MessageTimestamp,
     StatisticsIntervalMs,
     Topic,
+    TopicColumn,
+    TopicConfig,
     TopicMetadataRefreshIntervalMs,
+    TopicPattern,
+    TopicTemplate,
     TransactionTimeoutMs,
     StartTimestamp,
     StartOffset,
+    PartitionBy,
     PartitionCount,
     ReplicationFactor,
     RetentionMs,
     RetentionBytes,
+    RetryMaxAttempts,
+    RetryBackoff,
+    RetryMaxBackoff,
+    RetryExhaustedAction,
 }
 
 impl AstDisplay for KafkaConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             KafkaConfigOptionName::Acks => "ACKS",
+            KafkaConfigOptionName::BatchNumMessages => "BATCH NUM MESSAGES",
             KafkaConfigOptionName::ClientId => "CLIENT ID",
+            KafkaConfigOptionName::CommitInterval => "COMMIT INTERVAL",
+            KafkaConfigOptionName::CompressionType => "COMPRESSION TYPE",
+            KafkaConfigOptionName::DeadLetterTopic => "DEAD LETTER TOPIC",
             KafkaConfigOptionName::EnableIdempotence => "ENABLE IDEMPOTENCE",
             KafkaConfigOptionName::FetchMessageMaxBytes => "FETCH MESSAGE MAX BYTES",
             KafkaConfigOptionName::GroupIdPrefix => "GROUP ID PREFIX",
+            KafkaConfigOptionName::Headers => "HEADERS",
             KafkaConfigOptionName::IsolationLevel => "ISOLATION LEVEL",
+            KafkaConfigOptionName::LingerMs => "LINGER MS",
+            KafkaConfigOptionName::MessageTimestamp => "MESSAGE TIMESTAMP",
             KafkaConfigOptionName::StatisticsIntervalMs => "STATISTICS INTERVAL MS",
             KafkaConfigOptionName::Topic => "TOPIC",
+            KafkaConfigOptionName::TopicColumn => "TOPIC COLUMN",
+            KafkaConfigOptionName::TopicConfig => "TOPIC CONFIG",
             KafkaConfigOptionName::TopicMetadataRefreshIntervalMs => {
                 "TOPIC METADATA REFRESH INTERVAL MS"
             }
+            KafkaConfigOptionName::TopicPattern => "TOPIC PATTERN",
+            KafkaConfigOptionName::TopicTemplate => "TOPIC TEMPLATE",
             KafkaConfigOptionName::TransactionTimeoutMs => "TRANSACTION TIMEOUT MS",
             KafkaConfigOptionName::StartOffset => "START OFFSET",
             KafkaConfigOptionName::StartTimestamp => "START TIMESTAMP",
+            KafkaConfigOptionName::PartitionBy => "PARTITION BY",
             KafkaConfigOptionName::PartitionCount => "PARTITION COUNT",
             KafkaConfigOptionName::ReplicationFactor => "REPLICATION FACTOR",
             KafkaConfigOptionName::RetentionBytes => "RETENTION BYTES",
             KafkaConfigOptionName::RetentionMs => "RETENTION MS",
+            KafkaConfigOptionName::RetryMaxAttempts => "RETRY MAX ATTEMPTS",
+            KafkaConfigOptionName::RetryBackoff => "RETRY BACKOFF",
+            KafkaConfigOptionName::RetryMaxBackoff => "RETRY MAX BACKOFF",
+            KafkaConfigOptionName::RetryExhaustedAction => "RETRY EXHAUSTED ACTION",
         })
     }
 }
@@ -940,6 +1254,72 @@ impl<T: AstInfo> AstDisplay for PgConfigOption<T> {
 }
 impl_display_t!(PgConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MySqlConfigOptionName {
+    /// Hex encoded string of binary serialization of `dataflow_types::MySqlSourceDetails`
+    Details,
+}
+
+impl AstDisplay for MySqlConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MySqlConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(MySqlConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `MYSQL CONNECTION ...` clause.
+pub struct MySqlConfigOption<T: AstInfo> {
+    pub name: MySqlConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MySqlConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MySqlConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SqlServerConfigOptionName {
+    /// Hex encoded string of binary serialization of `dataflow_types::SqlServerSourceDetails`
+    Details,
+}
+
+impl AstDisplay for SqlServerConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(SqlServerConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `SQL SERVER CONNECTION ...` clause.
+pub struct SqlServerConfigOption<T: AstInfo> {
+    pub name: SqlServerConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqlServerConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqlServerConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceConnection<T: AstInfo> {
     Kafka(KafkaSourceConnection<T>),
@@ -947,6 +1327,8 @@ pub enum CreateSourceConnection<T: AstInfo> {
         /// The AWS connection.
         connection: T::ObjectName,
         arn: String,
+        /// The name of the enhanced fan-out consumer to read the stream with, if any.
+        consumer: Option<String>,
     },
     S3 {
         /// The AWS connection.
@@ -962,13 +1344,56 @@ pub enum CreateSourceConnection<T: AstInfo> {
         connection: T::ObjectName,
         options: Vec<PgConfigOption<T>>,
     },
+    MySql {
+        /// The MySQL connection.
+        connection: T::ObjectName,
+        options: Vec<MySqlConfigOption<T>>,
+    },
+    SqlServer {
+        /// The SQL Server connection.
+        connection: T::ObjectName,
+        options: Vec<SqlServerConfigOption<T>>,
+    },
     LoadGenerator {
         generator: LoadGenerator,
         options: Vec<LoadGeneratorOption<T>>,
     },
+    File {
+        /// The files to tail, like `/var/log/app/*.log`.
+        path: String,
+        compression: Compression,
+        options: Vec<FileConfigOption<T>>,
+    },
+    Mqtt {
+        /// The URL of the broker to connect to, like `mqtt://host:1883`.
+        url: String,
+        options: Vec<MqttConfigOption<T>>,
+    },
+    Nats {
+        /// The URL of the server to connect to, like `nats://host:4222`.
+        url: String,
+        options: Vec<NatsConfigOption<T>>,
+    },
+    PubSub {
+        /// The subscription to pull from, like
+        /// `projects/my-project/subscriptions/my-subscription`.
+        subscription: String,
+        options: Vec<PubSubConfigOption<T>>,
+    },
+    Sftp {
+        /// The URL of the directory to watch, like `sftp://host:22/inbox`.
+        url: String,
+        options: Vec<SftpConfigOption<T>>,
+    },
     TestScript {
         desc_json: String,
     },
+    Webhook {
+        /// The secret with which each request must be signed, if any.
+        secret: Option<T::ObjectName>,
+        /// The predicate that the body of each request must satisfy, if any.
+        check: Option<Expr<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
@@ -983,12 +1408,21 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
-            CreateSourceConnection::Kinesis { connection, arn } => {
+            CreateSourceConnection::Kinesis {
+                connection,
+                arn,
+                consumer,
+            } => {
                 f.write_str("KINESIS CONNECTION ");
                 f.write_node(connection);
                 f.write_str(" ARN '");
                 f.write_node(&display::escape_single_quote_string(arn));
                 f.write_str("'");
+                if let Some(consumer) = consumer {
+                    f.write_str(" CONSUMER '");
+                    f.write_node(&display::escape_single_quote_string(consumer));
+                    f.write_str("'");
+                }
             }
             CreateSourceConnection::S3 {
                 connection,
@@ -1021,6 +1455,30 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::MySql {
+                connection,
+                options,
+            } => {
+                f.write_str("MYSQL CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::SqlServer {
+                connection,
+                options,
+            } => {
+                f.write_str("SQL SERVER CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::LoadGenerator { generator, options } => {
                 f.write_str("LOAD GENERATOR ");
                 f.write_node(generator);
@@ -1030,12 +1488,82 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::File {
+                path,
+                compression,
+                options,
+            } => {
+                f.write_str("FILE '");
+                f.write_node(&display::escape_single_quote_string(path));
+                f.write_str("' COMPRESSION ");
+                f.write_node(compression);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Mqtt { url, options } => {
+                f.write_str("MQTT '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Nats { url, options } => {
+                f.write_str("NATS '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::PubSub {
+                subscription,
+                options,
+            } => {
+                f.write_str("PUBSUB SUBSCRIPTION '");
+                f.write_node(&display::escape_single_quote_string(subscription));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Sftp { url, options } => {
+                f.write_str("SFTP '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::TestScript { desc_json } => {
                 f.write_str("TEST SCRIPT ");
                 f.write_str("'");
                 f.write_str(&display::escape_single_quote_string(desc_json));
                 f.write_str("'");
             }
+            CreateSourceConnection::Webhook { secret, check } => {
+                f.write_str("WEBHOOK");
+                if let Some(secret) = secret {
+                    f.write_str(" VALIDATE USING SECRET ");
+                    f.write_node(secret);
+                }
+                if let Some(check) = check {
+                    f.write_str(" CHECK (");
+                    f.write_node(check);
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
@@ -1094,35 +1622,740 @@ impl<T: AstInfo> AstDisplay for LoadGeneratorOption<T> {
 impl_display_t!(LoadGeneratorOption);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum CreateSinkConnection<T: AstInfo> {
-    Kafka {
-        connection: KafkaConnection<T>,
-        key: Option<KafkaSinkKey>,
-    },
+pub enum FileConfigOptionName {
+    /// How often to check the files for new lines.
+    PollInterval,
 }
 
-impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
+impl AstDisplay for FileConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        match self {
-            CreateSinkConnection::Kafka { connection, key } => {
+        f.write_str(match self {
+            FileConfigOptionName::PollInterval => "POLL INTERVAL",
+        })
+    }
+}
+impl_display!(FileConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SOURCE ... FROM FILE` statement.
+pub struct FileConfigOption<T: AstInfo> {
+    pub name: FileConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for FileConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(FileConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MqttConfigOptionName {
+    /// The password to authenticate with.
+    Password,
+    /// The topic filter to subscribe to, which may contain wildcards.
+    Topic,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for MqttConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MqttConfigOptionName::Password => "PASSWORD",
+            MqttConfigOptionName::Topic => "TOPIC",
+            MqttConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(MqttConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SOURCE ... FROM MQTT` statement.
+pub struct MqttConfigOption<T: AstInfo> {
+    pub name: MqttConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MqttConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MqttConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NatsConfigOptionName {
+    /// The name of the durable consumer to read through.
+    Consumer,
+    /// The password to authenticate with.
+    Password,
+    /// The JetStream stream to read from.
+    Stream,
+    /// The subject that ingested messages must match, if any.
+    Subject,
+    /// The token to authenticate with.
+    Token,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for NatsConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            NatsConfigOptionName::Consumer => "CONSUMER",
+            NatsConfigOptionName::Password => "PASSWORD",
+            NatsConfigOptionName::Stream => "STREAM",
+            NatsConfigOptionName::Subject => "SUBJECT",
+            NatsConfigOptionName::Token => "TOKEN",
+            NatsConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(NatsConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SOURCE ... FROM NATS` statement.
+pub struct NatsConfigOption<T: AstInfo> {
+    pub name: NatsConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for NatsConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(NatsConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PubSubConfigOptionName {
+    /// How long Pub/Sub waits for a message to be acknowledged before
+    /// redelivering it.
+    AckDeadline,
+    /// The service account key to authenticate with.
+    Credentials,
+    /// The URL of the Pub/Sub API, which defaults to Google's.
+    Endpoint,
+}
+
+impl AstDisplay for PubSubConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PubSubConfigOptionName::AckDeadline => "ACK DEADLINE",
+            PubSubConfigOptionName::Credentials => "CREDENTIALS",
+            PubSubConfigOptionName::Endpoint => "ENDPOINT",
+        })
+    }
+}
+impl_display!(PubSubConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SOURCE ... FROM PUBSUB` statement.
+pub struct PubSubConfigOption<T: AstInfo> {
+    pub name: PubSubConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for PubSubConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(PubSubConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SftpConfigOptionName {
+    /// The password to authenticate with.
+    Password,
+    /// The glob that the names of ingested files must match.
+    Pattern,
+    /// How often to check the directory for new files.
+    PollInterval,
+    /// The private key to authenticate with.
+    PrivateKey,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for SftpConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SftpConfigOptionName::Password => "PASSWORD",
+            SftpConfigOptionName::Pattern => "PATTERN",
+            SftpConfigOptionName::PollInterval => "POLL INTERVAL",
+            SftpConfigOptionName::PrivateKey => "PRIVATE KEY",
+            SftpConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(SftpConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SOURCE ... FROM SFTP` statement.
+pub struct SftpConfigOption<T: AstInfo> {
+    pub name: SftpConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SftpConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SftpConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CreateSinkConnection<T: AstInfo> {
+    Kafka {
+        connection: KafkaConnection<T>,
+        key: Option<SinkKey>,
+    },
+    S3 {
+        /// The AWS connection.
+        connection: T::ObjectName,
+        options: Vec<S3SinkConfigOption<T>>,
+    },
+    Postgres {
+        /// The Postgres connection.
+        connection: T::ObjectName,
+        /// The name of the upstream table to write into.
+        table: UnresolvedObjectName,
+        key: Option<SinkKey>,
+    },
+    File {
+        /// The path of the file to write to.
+        path: String,
+        options: Vec<FileSinkConfigOption<T>>,
+    },
+    Elasticsearch {
+        /// The URL of the Elasticsearch or OpenSearch cluster.
+        url: String,
+        options: Vec<ElasticsearchSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    Redis {
+        /// The URL of the Redis server.
+        url: String,
+        options: Vec<RedisSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    Nats {
+        /// The URL of the NATS server.
+        url: String,
+        options: Vec<NatsSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    Mqtt {
+        /// The URL of the MQTT broker.
+        url: String,
+        options: Vec<MqttSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    Grpc {
+        /// The URL of the gRPC server.
+        url: String,
+        options: Vec<GrpcSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    Sqs {
+        /// The AWS connection.
+        connection: T::ObjectName,
+        options: Vec<SqsSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            CreateSinkConnection::Kafka { connection, key } => {
                 f.write_str("KAFKA ");
                 f.write_node(connection);
                 if let Some(key) = key.as_ref() {
                     f.write_node(key);
                 }
             }
+            CreateSinkConnection::S3 {
+                connection,
+                options,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSinkConnection::Postgres {
+                connection,
+                table,
+                key,
+            } => {
+                f.write_str("POSTGRES CONNECTION ");
+                f.write_node(connection);
+                f.write_str(" TABLE ");
+                f.write_node(table);
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::File { path, options } => {
+                f.write_str("FILE '");
+                f.write_node(&display::escape_single_quote_string(path));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSinkConnection::Elasticsearch { url, options, key } => {
+                f.write_str("ELASTICSEARCH '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Redis { url, options, key } => {
+                f.write_str("REDIS '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Nats { url, options, key } => {
+                f.write_str("NATS '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Mqtt { url, options, key } => {
+                f.write_str("MQTT '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Grpc { url, options, key } => {
+                f.write_str("GRPC '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Sqs {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("SQS CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
         }
     }
 }
 impl_display_t!(CreateSinkConnection);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct KafkaSinkKey {
+pub enum S3SinkConfigOptionName {
+    /// The bucket to write objects into.
+    Bucket,
+    /// The template from which the keys of written objects are rendered.
+    KeyTemplate,
+    /// The maximum size of a single object before it is rotated.
+    MaxFileSize,
+    /// What to do when a key already names an object written by someone else.
+    OnCollision,
+    /// The key prefix for all written objects.
+    Prefix,
+    /// The maximum amount of time an object stays open before it is rotated.
+    RotationInterval,
+    /// The open table format, if any, whose table the objects are committed
+    /// to.
+    TableFormat,
+}
+
+impl AstDisplay for S3SinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            S3SinkConfigOptionName::Bucket => "BUCKET",
+            S3SinkConfigOptionName::KeyTemplate => "KEY TEMPLATE",
+            S3SinkConfigOptionName::MaxFileSize => "MAX FILE SIZE",
+            S3SinkConfigOptionName::OnCollision => "ON COLLISION",
+            S3SinkConfigOptionName::Prefix => "PREFIX",
+            S3SinkConfigOptionName::RotationInterval => "ROTATION INTERVAL",
+            S3SinkConfigOptionName::TableFormat => "TABLE FORMAT",
+        })
+    }
+}
+impl_display!(S3SinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO S3 CONNECTION ...` statement.
+pub struct S3SinkConfigOption<T: AstInfo> {
+    pub name: S3SinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for S3SinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(S3SinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FileSinkConfigOptionName {
+    /// Whether to fsync the file after writing each batch of updates.
+    Fsync,
+    /// The maximum size of a single file before it is rotated.
+    MaxFileSize,
+    /// The maximum amount of time a file stays open before it is rotated.
+    RotationInterval,
+}
+
+impl AstDisplay for FileSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            FileSinkConfigOptionName::Fsync => "FSYNC",
+            FileSinkConfigOptionName::MaxFileSize => "MAX FILE SIZE",
+            FileSinkConfigOptionName::RotationInterval => "ROTATION INTERVAL",
+        })
+    }
+}
+impl_display!(FileSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO FILE ...` statement.
+pub struct FileSinkConfigOption<T: AstInfo> {
+    pub name: FileSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for FileSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(FileSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ElasticsearchSinkConfigOptionName {
+    /// The index to write documents into.
+    Index,
+    /// The password to authenticate with.
+    Password,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for ElasticsearchSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            ElasticsearchSinkConfigOptionName::Index => "INDEX",
+            ElasticsearchSinkConfigOptionName::Password => "PASSWORD",
+            ElasticsearchSinkConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(ElasticsearchSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO ELASTICSEARCH ...` statement.
+pub struct ElasticsearchSinkConfigOption<T: AstInfo> {
+    pub name: ElasticsearchSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ElasticsearchSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(ElasticsearchSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RedisSinkConfigOptionName {
+    /// The prefix of the keys that rows are written to.
+    KeyPrefix,
+    /// The password to authenticate with.
+    Password,
+    /// The time after which written keys expire.
+    Ttl,
+    /// The Redis data type that rows are written as.
+    DataType,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for RedisSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            RedisSinkConfigOptionName::KeyPrefix => "KEY PREFIX",
+            RedisSinkConfigOptionName::Password => "PASSWORD",
+            RedisSinkConfigOptionName::Ttl => "TTL",
+            RedisSinkConfigOptionName::DataType => "TYPE",
+            RedisSinkConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(RedisSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO REDIS ...` statement.
+pub struct RedisSinkConfigOption<T: AstInfo> {
+    pub name: RedisSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for RedisSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(RedisSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NatsSinkConfigOptionName {
+    /// The password to authenticate with.
+    Password,
+    /// The template of the subjects that rows are published to.
+    Subject,
+    /// The token to authenticate with.
+    Token,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for NatsSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            NatsSinkConfigOptionName::Password => "PASSWORD",
+            NatsSinkConfigOptionName::Subject => "SUBJECT",
+            NatsSinkConfigOptionName::Token => "TOKEN",
+            NatsSinkConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(NatsSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO NATS ...` statement.
+pub struct NatsSinkConfigOption<T: AstInfo> {
+    pub name: NatsSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for NatsSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(NatsSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MqttSinkConfigOptionName {
+    /// The password to authenticate with.
+    Password,
+    /// The quality of service level that messages are published with.
+    Qos,
+    /// Whether the broker retains the last message published to each topic.
+    Retain,
+    /// The template of the topics that rows are published to.
+    Topic,
+    /// The user to authenticate as.
+    User,
+}
+
+impl AstDisplay for MqttSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MqttSinkConfigOptionName::Password => "PASSWORD",
+            MqttSinkConfigOptionName::Qos => "QOS",
+            MqttSinkConfigOptionName::Retain => "RETAIN",
+            MqttSinkConfigOptionName::Topic => "TOPIC",
+            MqttSinkConfigOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(MqttSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO MQTT ...` statement.
+pub struct MqttSinkConfigOption<T: AstInfo> {
+    pub name: MqttSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MqttSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MqttSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GrpcSinkConfigOptionName {
+    /// The client-streaming method that updates are streamed to.
+    Method,
+    /// The bearer token to authenticate with.
+    Token,
+}
+
+impl AstDisplay for GrpcSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            GrpcSinkConfigOptionName::Method => "METHOD",
+            GrpcSinkConfigOptionName::Token => "TOKEN",
+        })
+    }
+}
+impl_display!(GrpcSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO GRPC ...` statement.
+pub struct GrpcSinkConfigOption<T: AstInfo> {
+    pub name: GrpcSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for GrpcSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(GrpcSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SqsSinkConfigOptionName {
+    /// The URL of the queue to send messages to.
+    QueueUrl,
+}
+
+impl AstDisplay for SqsSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqsSinkConfigOptionName::QueueUrl => "QUEUE URL",
+        })
+    }
+}
+impl_display!(SqsSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE SINK ... INTO SQS ...` statement.
+pub struct SqsSinkConfigOption<T: AstInfo> {
+    pub name: SqsSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqsSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqsSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SinkKey {
     pub key_columns: Vec<Ident>,
     pub not_enforced: bool,
 }
 
-impl AstDisplay for KafkaSinkKey {
+impl AstDisplay for SinkKey {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(" KEY (");
         f.write_node(&display::comma_separated(&self.key_columns));
@@ -1133,6 +2366,25 @@ impl AstDisplay for KafkaSinkKey {
     }
 }
 
+/// A column of the relation a sink exports, optionally renamed in the sink's
+/// output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SinkColumn {
+    pub name: Ident,
+    pub alias: Option<Ident>,
+}
+
+impl AstDisplay for SinkColumn {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+    }
+}
+impl_display!(SinkColumn);
+
 /// Information about upstream Postgres tables used for replication sources
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PgTable<T: AstInfo> {
@@ -1279,21 +2531,37 @@ impl_display!(KeyConstraint);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceOptionName {
+    DecodeErrors,
     IgnoreKeys,
+    MaxBytesPerSecond,
+    MaxMessagesPerSecond,
+    NumericOverflow,
     Remote,
     Size,
     Timeline,
+    TimestampColumn,
     TimestampInterval,
+    TimestampLateness,
+    TimestampStrategy,
+    UpsertState,
 }
 
 impl AstDisplay for CreateSourceOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
+            CreateSourceOptionName::DecodeErrors => "DECODE ERRORS",
             CreateSourceOptionName::IgnoreKeys => "IGNORE KEYS",
+            CreateSourceOptionName::MaxBytesPerSecond => "MAX BYTES PER SECOND",
+            CreateSourceOptionName::MaxMessagesPerSecond => "MAX MESSAGES PER SECOND",
+            CreateSourceOptionName::NumericOverflow => "NUMERIC OVERFLOW",
             CreateSourceOptionName::Remote => "REMOTE",
             CreateSourceOptionName::Size => "SIZE",
             CreateSourceOptionName::Timeline => "TIMELINE",
+            CreateSourceOptionName::TimestampColumn => "TIMESTAMP COLUMN",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
+            CreateSourceOptionName::TimestampLateness => "TIMESTAMP LATENESS",
+            CreateSourceOptionName::TimestampStrategy => "TIMESTAMP STRATEGY",
+            CreateSourceOptionName::UpsertState => "UPSERT STATE",
         })
     }
 }
@@ -30,8 +30,9 @@ use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
     AstInfo, ColumnDef, CreateConnection, CreateSinkConnection, CreateSourceConnection,
     CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, Envelope, Expr, Format, Ident,
-    KeyConstraint, Query, SelectItem, SourceIncludeMetadata, TableAlias, TableConstraint,
-    TableWithJoins, UnresolvedDatabaseName, UnresolvedObjectName, UnresolvedSchemaName, Value,
+    KafkaConfigOption, KafkaConfigOptionName, KeyConstraint, Query, SelectItem, SinkColumn,
+    SourceIncludeMetadata, TableAlias, TableConstraint, TableWithJoins, UnresolvedDatabaseName,
+    UnresolvedObjectName, UnresolvedSchemaName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -60,6 +61,7 @@ pub enum Statement<T: AstInfo> {
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
+    AlterOwner(AlterOwnerStatement),
     AlterIndex(AlterIndexStatement<T>),
     AlterSecret(AlterSecretStatement<T>),
     AlterSink(AlterSinkStatement<T>),
@@ -75,6 +77,8 @@ pub enum Statement<T: AstInfo> {
     DropRoles(DropRolesStatement),
     DropClusters(DropClustersStatement),
     DropClusterReplicas(DropClusterReplicasStatement),
+    GrantPrivileges(GrantPrivilegesStatement),
+    RevokePrivileges(RevokePrivilegesStatement),
     SetVariable(SetVariableStatement),
     ResetVariable(ResetVariableStatement),
     Show(ShowStatement<T>),
@@ -117,6 +121,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
+            Statement::AlterOwner(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
             Statement::AlterSecret(stmt) => f.write_node(stmt),
             Statement::AlterSink(stmt) => f.write_node(stmt),
@@ -132,6 +137,8 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::DropRoles(stmt) => f.write_node(stmt),
             Statement::DropClusters(stmt) => f.write_node(stmt),
             Statement::DropClusterReplicas(stmt) => f.write_node(stmt),
+            Statement::GrantPrivileges(stmt) => f.write_node(stmt),
+            Statement::RevokePrivileges(stmt) => f.write_node(stmt),
             Statement::SetVariable(stmt) => f.write_node(stmt),
             Statement::ResetVariable(stmt) => f.write_node(stmt),
             Statement::Show(stmt) => f.write_node(stmt),
@@ -456,9 +463,9 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub col_names: Vec<Ident>,
     pub connection: CreateSourceConnection<T>,
-    pub include_metadata: Vec<SourceIncludeMetadata>,
+    pub include_metadata: Vec<SourceIncludeMetadata<T>>,
     pub format: CreateSourceFormat<T>,
-    pub envelope: Option<Envelope>,
+    pub envelope: Option<Envelope<T>>,
     pub if_not_exists: bool,
     pub key_constraint: Option<KeyConstraint>,
     pub with_options: Vec<CreateSourceOption<T>>,
@@ -515,26 +522,38 @@ impl_display_t!(CreateSourceStatement);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// A selected subsource in a FOR TABLES (..) statement
+///
+/// Each variant carries the columns of the upstream table to ingest; an empty
+/// list ingests all of them.
 pub enum CreateSourceSubsource<T: AstInfo> {
     /// An unaliased subbsource
-    Bare(UnresolvedObjectName),
+    Bare(UnresolvedObjectName, Vec<Ident>),
     /// An subbsource aliased to a different name
-    Aliased(UnresolvedObjectName, UnresolvedObjectName),
+    Aliased(UnresolvedObjectName, Vec<Ident>, UnresolvedObjectName),
     /// An subbsource fully resolved to the target catalog object it will be written to
-    Resolved(UnresolvedObjectName, T::ObjectName),
+    Resolved(UnresolvedObjectName, Vec<Ident>, T::ObjectName),
 }
 
 impl<T: AstInfo> AstDisplay for CreateSourceSubsource<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        let (name, columns) = match self {
+            Self::Bare(name, columns)
+            | Self::Aliased(name, columns, _)
+            | Self::Resolved(name, columns, _) => (name, columns),
+        };
+        f.write_node(name);
+        if !columns.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(columns));
+            f.write_str(")");
+        }
         match self {
-            Self::Bare(name) => f.write_node(name),
-            Self::Aliased(name, alias) => {
-                f.write_node(name);
+            Self::Bare(..) => {}
+            Self::Aliased(_, _, alias) => {
                 f.write_str(" AS ");
                 f.write_node(alias);
             }
-            Self::Resolved(name, target) => {
-                f.write_node(name);
+            Self::Resolved(_, _, target) => {
                 f.write_str(" INTO ");
                 f.write_node(target);
             }
@@ -595,23 +614,79 @@ impl_display_t!(CreateSubsourceStatement);
 /// An option in a `CREATE SINK` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSinkOptionName {
+    CatchUpBytesPerSecond,
+    CatchUpRecordsPerSecond,
+    DeleteMarker,
+    Deletes,
+    IncludeDiff,
+    IncludeTimestamp,
+    MaskHash,
+    MaskRedact,
+    MaskTruncate,
+    MaskTruncateLength,
+    MaxBytesPerSecond,
+    MaxMessagesPerSecond,
     Remote,
+    Retractions,
     Size,
     Snapshot,
+    SpillBudget,
 }
 
 impl AstDisplay for CreateSinkOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
+            CreateSinkOptionName::CatchUpBytesPerSecond => {
+                f.write_str("CATCH UP BYTES PER SECOND");
+            }
+            CreateSinkOptionName::CatchUpRecordsPerSecond => {
+                f.write_str("CATCH UP RECORDS PER SECOND");
+            }
+            CreateSinkOptionName::DeleteMarker => {
+                f.write_str("DELETE MARKER");
+            }
+            CreateSinkOptionName::Deletes => {
+                f.write_str("DELETES");
+            }
+            CreateSinkOptionName::IncludeDiff => {
+                f.write_str("INCLUDE DIFF");
+            }
+            CreateSinkOptionName::IncludeTimestamp => {
+                f.write_str("INCLUDE TIMESTAMP");
+            }
+            CreateSinkOptionName::MaskHash => {
+                f.write_str("MASK HASH");
+            }
+            CreateSinkOptionName::MaskRedact => {
+                f.write_str("MASK REDACT");
+            }
+            CreateSinkOptionName::MaskTruncate => {
+                f.write_str("MASK TRUNCATE");
+            }
+            CreateSinkOptionName::MaskTruncateLength => {
+                f.write_str("MASK TRUNCATE LENGTH");
+            }
+            CreateSinkOptionName::MaxBytesPerSecond => {
+                f.write_str("MAX BYTES PER SECOND");
+            }
+            CreateSinkOptionName::MaxMessagesPerSecond => {
+                f.write_str("MAX MESSAGES PER SECOND");
+            }
             CreateSinkOptionName::Remote => {
                 f.write_str("REMOTE");
             }
+            CreateSinkOptionName::Retractions => {
+                f.write_str("RETRACTIONS");
+            }
             CreateSinkOptionName::Size => {
                 f.write_str("SIZE");
             }
             CreateSinkOptionName::Snapshot => {
                 f.write_str("SNAPSHOT");
             }
+            CreateSinkOptionName::SpillBudget => {
+                f.write_str("SPILL BUDGET");
+            }
         }
     }
 }
@@ -638,9 +713,14 @@ pub struct CreateSinkStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub if_not_exists: bool,
     pub from: T::ObjectName,
+    /// The columns of `from` to export, if not all of them.
+    pub columns: Vec<SinkColumn>,
+    /// The predicate that the rows of `from` must satisfy to be exported, if
+    /// any.
+    pub filter: Option<Expr<T>>,
     pub connection: CreateSinkConnection<T>,
     pub format: Option<Format<T>>,
-    pub envelope: Option<Envelope>,
+    pub envelope: Option<Envelope<T>>,
     pub with_options: Vec<CreateSinkOption<T>>,
 }
 
@@ -653,6 +733,15 @@ impl<T: AstInfo> AstDisplay for CreateSinkStatement<T> {
         f.write_node(&self.name);
         f.write_str(" FROM ");
         f.write_node(&self.from);
+        if !self.columns.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(")");
+        }
+        if let Some(filter) = &self.filter {
+            f.write_str(" WHERE ");
+            f.write_node(filter);
+        }
         f.write_str(" INTO ");
         f.write_node(&self.connection);
         if let Some(format) = &self.format {
@@ -734,6 +823,7 @@ pub struct CreateMaterializedViewStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub columns: Vec<Ident>,
     pub in_cluster: Option<T::ClusterName>,
+    pub with_options: Vec<MaterializedViewOption<T>>,
     pub query: Query<T>,
 }
 
@@ -764,12 +854,57 @@ impl<T: AstInfo> AstDisplay for CreateMaterializedViewStatement<T> {
             f.write_node(cluster);
         }
 
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
+
         f.write_str(" AS ");
         f.write_node(&self.query);
     }
 }
 impl_display_t!(CreateMaterializedViewStatement);
 
+/// An option in a `CREATE MATERIALIZED VIEW` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MaterializedViewOptionName {
+    /// The `TARGET BATCH SIZE` option.
+    TargetBatchSize,
+    /// The `MAX BATCH STALENESS` option.
+    MaxBatchStaleness,
+    /// The `LOGICAL COMPACTION WINDOW` option.
+    LogicalCompactionWindow,
+}
+
+impl AstDisplay for MaterializedViewOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MaterializedViewOptionName::TargetBatchSize => f.write_str("TARGET BATCH SIZE"),
+            MaterializedViewOptionName::MaxBatchStaleness => f.write_str("MAX BATCH STALENESS"),
+            MaterializedViewOptionName::LogicalCompactionWindow => {
+                f.write_str("LOGICAL COMPACTION WINDOW")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaterializedViewOption<T: AstInfo> {
+    pub name: MaterializedViewOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MaterializedViewOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+
 /// `CREATE TABLE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateTableStatement<T: AstInfo> {
@@ -940,6 +1075,128 @@ impl AstDisplay for CreateRoleOption {
 }
 impl_display!(CreateRoleOption);
 
+/// A privilege that can be granted to a role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Privilege {
+    /// The `SELECT` privilege on a relation.
+    Select,
+    /// The `INSERT` privilege on a table.
+    Insert,
+    /// The `UPDATE` privilege on a table.
+    Update,
+    /// The `DELETE` privilege on a table.
+    Delete,
+    /// The `CREATE` privilege on a schema.
+    Create,
+    /// The `USAGE` privilege on a secret or connection.
+    Usage,
+}
+
+impl AstDisplay for Privilege {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Privilege::Select => f.write_str("SELECT"),
+            Privilege::Insert => f.write_str("INSERT"),
+            Privilege::Update => f.write_str("UPDATE"),
+            Privilege::Delete => f.write_str("DELETE"),
+            Privilege::Create => f.write_str("CREATE"),
+            Privilege::Usage => f.write_str("USAGE"),
+        }
+    }
+}
+impl_display!(Privilege);
+
+/// The privileges named by a [`GrantPrivilegesStatement`] or
+/// [`RevokePrivilegesStatement`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrivilegeSpecification {
+    /// `ALL [PRIVILEGES]`, i.e., every privilege that applies to the target.
+    All,
+    /// A list of privileges.
+    Privileges(Vec<Privilege>),
+}
+
+impl AstDisplay for PrivilegeSpecification {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            PrivilegeSpecification::All => f.write_str("ALL"),
+            PrivilegeSpecification::Privileges(privileges) => {
+                f.write_node(&display::comma_separated(privileges))
+            }
+        }
+    }
+}
+impl_display!(PrivilegeSpecification);
+
+/// The object whose privileges a [`GrantPrivilegesStatement`],
+/// [`RevokePrivilegesStatement`] or [`ShowGrantsStatement`] refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GrantTargetSpecification {
+    /// `[TABLE] <name>`: a table, source, view or materialized view.
+    Object(UnresolvedObjectName),
+    /// `SCHEMA <name>`
+    Schema(UnresolvedSchemaName),
+}
+
+impl AstDisplay for GrantTargetSpecification {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            GrantTargetSpecification::Object(name) => f.write_node(name),
+            GrantTargetSpecification::Schema(name) => {
+                f.write_str("SCHEMA ");
+                f.write_node(name);
+            }
+        }
+    }
+}
+impl_display!(GrantTargetSpecification);
+
+/// A `GRANT ... ON ... TO ...` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrantPrivilegesStatement {
+    /// The privileges being granted.
+    pub privileges: PrivilegeSpecification,
+    /// The object the privileges are granted on.
+    pub target: GrantTargetSpecification,
+    /// The roles the privileges are granted to.
+    pub roles: Vec<Ident>,
+}
+
+impl AstDisplay for GrantPrivilegesStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("GRANT ");
+        f.write_node(&self.privileges);
+        f.write_str(" ON ");
+        f.write_node(&self.target);
+        f.write_str(" TO ");
+        f.write_node(&display::comma_separated(&self.roles));
+    }
+}
+impl_display!(GrantPrivilegesStatement);
+
+/// A `REVOKE ... ON ... FROM ...` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RevokePrivilegesStatement {
+    /// The privileges being revoked.
+    pub privileges: PrivilegeSpecification,
+    /// The object the privileges are revoked on.
+    pub target: GrantTargetSpecification,
+    /// The roles the privileges are revoked from.
+    pub roles: Vec<Ident>,
+}
+
+impl AstDisplay for RevokePrivilegesStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("REVOKE ");
+        f.write_node(&self.privileges);
+        f.write_str(" ON ");
+        f.write_node(&self.target);
+        f.write_str(" FROM ");
+        f.write_node(&display::comma_separated(&self.roles));
+    }
+}
+impl_display!(RevokePrivilegesStatement);
+
 /// A `CREATE SECRET` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateSecretStatement<T: AstInfo> {
@@ -1255,6 +1512,32 @@ impl AstDisplay for AlterObjectRenameStatement {
 }
 impl_display!(AlterObjectRenameStatement);
 
+/// `ALTER <object_type> ... OWNER TO`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterOwnerStatement {
+    pub object_type: ObjectType,
+    pub if_exists: bool,
+    /// The name of the object. Databases have exactly one name component and
+    /// schemas at most two.
+    pub name: UnresolvedObjectName,
+    pub new_owner: Ident,
+}
+
+impl AstDisplay for AlterOwnerStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" OWNER TO ");
+        f.write_node(&self.new_owner);
+    }
+}
+impl_display!(AlterOwnerStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterIndexAction<T: AstInfo> {
     SetOptions(Vec<IndexOption<T>>),
@@ -1299,6 +1582,8 @@ impl_display_t!(AlterIndexStatement);
 pub enum AlterSinkAction<T: AstInfo> {
     SetOptions(Vec<CreateSinkOption<T>>),
     ResetOptions(Vec<CreateSinkOptionName>),
+    SetConnectionOptions(Vec<KafkaConfigOption<T>>),
+    ResetConnectionOptions(Vec<KafkaConfigOptionName>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1328,6 +1613,16 @@ impl<T: AstInfo> AstDisplay for AlterSinkStatement<T> {
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
             }
+            AlterSinkAction::SetConnectionOptions(options) => {
+                f.write_str("SET CONNECTION (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
+            AlterSinkAction::ResetConnectionOptions(options) => {
+                f.write_str("RESET CONNECTION (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
         }
     }
 }
@@ -1336,6 +1631,8 @@ impl<T: AstInfo> AstDisplay for AlterSinkStatement<T> {
 pub enum AlterSourceAction<T: AstInfo> {
     SetOptions(Vec<CreateSourceOption<T>>),
     ResetOptions(Vec<CreateSourceOptionName>),
+    /// Re-reads the schemas of the source from its schema registry.
+    RefreshSchema,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1365,6 +1662,7 @@ impl<T: AstInfo> AstDisplay for AlterSourceStatement<T> {
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
             }
+            AlterSourceAction::RefreshSchema => f.write_str("REFRESH SCHEMA"),
         }
     }
 }
@@ -1725,7 +2023,7 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
             ObjectType::Object => "OBJECTS",
             ObjectType::Secret => "SECRETS",
             ObjectType::Connection => "CONNECTIONS",
-            ObjectType::Index => unreachable!(),
+            ObjectType::Index | ObjectType::Database | ObjectType::Schema => unreachable!(),
         });
         if let Some(from) = &self.from {
             f.write_str(" FROM ");
@@ -1798,6 +2096,35 @@ impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
 }
 impl_display_t!(ShowColumnsStatement);
 
+/// `SHOW GRANTS [ON <object>] [FOR <role>]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowGrantsStatement<T: AstInfo> {
+    /// Restricts the grants shown to those on this object.
+    pub target: Option<GrantTargetSpecification>,
+    /// Restricts the grants shown to those held by this role.
+    pub role: Option<Ident>,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowGrantsStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW GRANTS");
+        if let Some(target) = &self.target {
+            f.write_str(" ON ");
+            f.write_node(target);
+        }
+        if let Some(role) = &self.role {
+            f.write_str(" FOR ");
+            f.write_node(role);
+        }
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowGrantsStatement);
+
 /// `SHOW CREATE VIEW <view>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShowCreateViewStatement<T: AstInfo> {
@@ -1964,6 +2291,10 @@ impl_display!(RollbackStatement);
 pub enum SubscribeOptionName {
     Snapshot,
     Progress,
+    /// The `PROGRESS INTERVAL` option.
+    ProgressInterval,
+    /// The `IDLE TIMEOUT` option.
+    IdleTimeout,
 }
 
 impl AstDisplay for SubscribeOptionName {
@@ -1971,6 +2302,8 @@ impl AstDisplay for SubscribeOptionName {
         match self {
             SubscribeOptionName::Snapshot => f.write_str("SNAPSHOT"),
             SubscribeOptionName::Progress => f.write_str("PROGRESS"),
+            SubscribeOptionName::ProgressInterval => f.write_str("PROGRESS INTERVAL"),
+            SubscribeOptionName::IdleTimeout => f.write_str("IDLE TIMEOUT"),
         }
     }
 }
@@ -2094,6 +2427,8 @@ pub enum ObjectType {
     Object,
     Secret,
     Connection,
+    Database,
+    Schema,
 }
 
 impl AstDisplay for ObjectType {
@@ -2112,6 +2447,8 @@ impl AstDisplay for ObjectType {
             ObjectType::Object => "OBJECT",
             ObjectType::Secret => "SECRET",
             ObjectType::Connection => "CONNECTION",
+            ObjectType::Database => "DATABASE",
+            ObjectType::Schema => "SCHEMA",
         })
     }
 }
@@ -2151,6 +2488,7 @@ pub enum WithOptionValue<T: AstInfo> {
     Sequence(Vec<WithOptionValue<T>>),
     // Special cases.
     ClusterReplicas(Vec<ReplicaDefinition<T>>),
+    Expr(Expr<T>),
 }
 
 impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
@@ -2174,6 +2512,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_node(&display::comma_separated(replicas));
                 f.write_str(")");
             }
+            WithOptionValue::Expr(expr) => f.write_node(expr),
         }
     }
 }
@@ -2655,6 +2994,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateIndex(ShowCreateIndexStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
     ShowVariable(ShowVariableStatement),
+    ShowGrants(ShowGrantsStatement<T>),
 }
 
 impl<T: AstInfo> AstDisplay for ShowStatement<T> {
@@ -2673,6 +3013,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
+            ShowStatement::ShowGrants(stmt) => f.write_node(stmt),
         }
     }
 }
@@ -279,6 +279,8 @@ impl<'a> Parser<'a> {
                 Token::Keyword(EXECUTE) => Ok(self.parse_execute()?),
                 Token::Keyword(DEALLOCATE) => Ok(self.parse_deallocate()?),
                 Token::Keyword(RAISE) => Ok(self.parse_raise()?),
+                Token::Keyword(GRANT) => Ok(self.parse_grant()?),
+                Token::Keyword(REVOKE) => Ok(self.parse_revoke()?),
                 Token::Keyword(kw) => parser_err!(
                     self,
                     self.peek_prev_pos(),
@@ -1668,8 +1670,12 @@ impl<'a> Parser<'a> {
 
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
         let format = if self.parse_keyword(AVRO) {
-            self.expect_keyword(USING)?;
-            Format::Avro(self.parse_avro_schema()?)
+            if self.parse_keyword(OCF) {
+                Format::AvroOcf
+            } else {
+                self.expect_keyword(USING)?;
+                Format::Avro(self.parse_avro_schema()?)
+            }
         } else if self.parse_keyword(PROTOBUF) {
             Format::Protobuf(self.parse_protobuf_schema()?)
         } else if self.parse_keyword(REGEX) {
@@ -1687,31 +1693,95 @@ impl<'a> Parser<'a> {
                 CsvColumns::Count(n_cols)
             };
             let delimiter = if self.parse_keywords(&[DELIMITED, BY]) {
-                let s = self.parse_literal_string()?;
-                match s.len() {
-                    1 => Ok(s.chars().next().unwrap()),
-                    _ => self.expected(self.peek_pos(), "one-character string", self.peek_token()),
-                }?
+                self.parse_one_character_string()?
             } else {
                 ','
             };
-            Format::Csv { columns, delimiter }
+            let quote = if self.parse_keyword(QUOTE) {
+                self.parse_one_character_string()?
+            } else {
+                '"'
+            };
+            let escape = if self.parse_keyword(ESCAPE) {
+                Some(self.parse_one_character_string()?)
+            } else {
+                None
+            };
+            let typed_columns = if self.parse_keyword(COLUMNS) {
+                self.parse_format_columns("CSV", Mandatory)?
+            } else {
+                vec![]
+            };
+            Format::Csv {
+                columns,
+                delimiter,
+                quote,
+                escape,
+                typed_columns,
+            }
         } else if self.parse_keyword(JSON) {
-            Format::Json
+            let csr_connection = if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
+                Some(self.parse_csr_connection_reference()?)
+            } else {
+                None
+            };
+            // A parenthesized list after a schema registry connection holds
+            // the connection's options, so columns cannot follow one.
+            let columns = if csr_connection.is_none() {
+                self.parse_format_columns("JSON", Optional)?
+            } else {
+                vec![]
+            };
+            let reject_unknown_fields = self.parse_keywords(&[REJECT, UNKNOWN, FIELDS]);
+            Format::Json {
+                csr_connection,
+                columns,
+                reject_unknown_fields,
+            }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(PARQUET) {
+            let columns = self.parse_format_columns("PARQUET", Optional)?;
+            Format::Parquet { columns }
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
         Ok(format)
     }
 
+    /// Parses the optional list of columns that follows `FORMAT JSON` or
+    /// `FORMAT PARQUET`.
+    fn parse_one_character_string(&mut self) -> Result<char, ParserError> {
+        let s = self.parse_literal_string()?;
+        match s.len() {
+            1 => Ok(s.chars().next().unwrap()),
+            _ => self.expected(self.peek_pos(), "one-character string", self.peek_token()),
+        }
+    }
+
+    fn parse_format_columns(
+        &mut self,
+        format: &str,
+        optional: IsOptional,
+    ) -> Result<Vec<ColumnDef<Raw>>, ParserError> {
+        let (columns, constraints) = self.parse_columns(optional)?;
+        if !constraints.is_empty() {
+            return parser_err!(
+                self,
+                self.peek_prev_pos(),
+                "FORMAT {} does not support constraints",
+                format
+            );
+        }
+        Ok(columns)
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema<Raw>, ParserError> {
         let avro_schema = if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_avro()?;
@@ -1794,15 +1864,30 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_csr_config_option(&mut self) -> Result<CsrConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[AVRO])? {
-            AVRO => {
-                let name = match self.expect_one_of_keywords(&[KEY, VALUE])? {
-                    KEY => CsrConfigOptionName::AvroKeyFullname,
-                    VALUE => CsrConfigOptionName::AvroValueFullname,
+        let name = match self.expect_one_of_keywords(&[AVRO, COMPATIBILITY, ON, SUBJECT])? {
+            AVRO => match self.expect_one_of_keywords(&[KEY, VALUE])? {
+                KEY => {
+                    self.expect_keyword(FULLNAME)?;
+                    CsrConfigOptionName::AvroKeyFullname
+                }
+                VALUE => match self.expect_one_of_keywords(&[FULLNAME, SCHEMA])? {
+                    FULLNAME => CsrConfigOptionName::AvroValueFullname,
+                    SCHEMA => CsrConfigOptionName::AvroValueSchema,
                     _ => unreachable!(),
-                };
-                self.expect_keyword(FULLNAME)?;
-                name
+                },
+                _ => unreachable!(),
+            },
+            COMPATIBILITY => {
+                self.expect_keyword(LEVEL)?;
+                CsrConfigOptionName::CompatibilityLevel
+            }
+            ON => {
+                self.expect_keywords(&[DROP, DELETE, SUBJECTS])?;
+                CsrConfigOptionName::OnDropDeleteSubjects
+            }
+            SUBJECT => {
+                self.expect_keywords(&[NAME, STRATEGY])?;
+                CsrConfigOptionName::SubjectNameStrategy
             }
             _ => unreachable!(),
         };
@@ -1901,13 +1986,24 @@ impl<'a> Parser<'a> {
         Ok(CsrConnectionProtobuf { connection, seed })
     }
 
-    fn parse_envelope(&mut self) -> Result<Envelope, ParserError> {
+    fn parse_envelope(&mut self) -> Result<Envelope<Raw>, ParserError> {
         let envelope = if self.parse_keyword(NONE) {
             Envelope::None
         } else if self.parse_keyword(DEBEZIUM) {
             // In Platform, `DEBEZIUM UPSERT` is the only available option.
             // Revisit this if we ever change that.
-            let debezium_mode = DbzMode::Plain;
+            let tx_metadata = if self.consume_token(&Token::LParen) {
+                self.expect_keywords(&[TRANSACTION, METADATA])?;
+                self.expect_token(&Token::LParen)?;
+                let tx_metadata =
+                    self.parse_comma_separated(Parser::parse_dbz_tx_metadata_option)?;
+                self.expect_token(&Token::RParen)?;
+                self.expect_token(&Token::RParen)?;
+                tx_metadata
+            } else {
+                vec![]
+            };
+            let debezium_mode = DbzMode::Plain { tx_metadata };
             Envelope::Debezium(debezium_mode)
         } else if self.parse_keyword(UPSERT) {
             Envelope::Upsert
@@ -1923,13 +2019,30 @@ impl<'a> Parser<'a> {
         Ok(envelope)
     }
 
-    fn parse_compression(&mut self) -> Result<Compression, ParserError> {
-        let compression = if self.parse_keyword(NONE) {
-            Compression::None
-        } else if self.parse_keyword(GZIP) {
-            Compression::Gzip
+    fn parse_dbz_tx_metadata_option(&mut self) -> Result<DbzTxMetadataOption<Raw>, ParserError> {
+        if self.parse_keyword(SOURCE) {
+            Ok(DbzTxMetadataOption::Source(self.parse_raw_name()?))
+        } else if self.parse_keyword(COLLECTION) {
+            Ok(DbzTxMetadataOption::Collection(self.parse_option_value()?))
         } else {
-            return self.expected(self.peek_pos(), "NONE or GZIP", self.peek_token());
+            self.expected(self.peek_pos(), "SOURCE or COLLECTION", self.peek_token())
+        }
+    }
+
+    fn parse_compression(&mut self) -> Result<Compression, ParserError> {
+        let compression = match self.parse_one_of_keywords(&[NONE, AUTO, BZIP2, GZIP, ZSTD]) {
+            Some(NONE) => Compression::None,
+            Some(AUTO) => Compression::Auto,
+            Some(BZIP2) => Compression::Bzip2,
+            Some(GZIP) => Compression::Gzip,
+            Some(ZSTD) => Compression::Zstd,
+            _ => {
+                return self.expected(
+                    self.peek_pos(),
+                    "NONE, AUTO, BZIP2, GZIP, or ZSTD",
+                    self.peek_token(),
+                )
+            }
         };
         Ok(compression)
     }
@@ -1943,52 +2056,79 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection =
-            match self.expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH])? {
-                AWS => {
+        let connection = match self
+            .expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, MYSQL, SQL, SSH])?
+        {
+            AWS => {
+                if self.parse_keyword(PRIVATELINK) {
                     if expect_paren {
                         self.expect_token(&Token::LParen)?;
                     }
-                    let with_options =
-                        self.parse_comma_separated(Parser::parse_aws_connection_option)?;
-                    CreateConnection::Aws { with_options }
-                }
-                KAFKA => {
+                    let with_options = self
+                        .parse_comma_separated(Parser::parse_aws_privatelink_connection_option)?;
+                    CreateConnection::AwsPrivatelink { with_options }
+                } else {
                     if expect_paren {
                         self.expect_token(&Token::LParen)?;
                     }
                     let with_options =
-                        self.parse_comma_separated(Parser::parse_kafka_connection_option)?;
-                    CreateConnection::Kafka { with_options }
+                        self.parse_comma_separated(Parser::parse_aws_connection_option)?;
+                    CreateConnection::Aws { with_options }
                 }
-                CONFLUENT => {
-                    self.expect_keywords(&[SCHEMA, REGISTRY])?;
-                    if expect_paren {
-                        self.expect_token(&Token::LParen)?;
-                    }
-                    let with_options =
-                        self.parse_comma_separated(Parser::parse_csr_connection_option)?;
-                    CreateConnection::Csr { with_options }
+            }
+            KAFKA => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
                 }
-                POSTGRES => {
-                    if expect_paren {
-                        self.expect_token(&Token::LParen)?;
-                    }
-                    let with_options =
-                        self.parse_comma_separated(Parser::parse_postgres_connection_option)?;
-                    CreateConnection::Postgres { with_options }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_kafka_connection_option)?;
+                CreateConnection::Kafka { with_options }
+            }
+            CONFLUENT => {
+                self.expect_keywords(&[SCHEMA, REGISTRY])?;
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
                 }
-                SSH => {
-                    self.expect_keyword(TUNNEL)?;
-                    if expect_paren {
-                        self.expect_token(&Token::LParen)?;
-                    }
-                    let with_options =
-                        self.parse_comma_separated(Parser::parse_ssh_connection_option)?;
-                    CreateConnection::Ssh { with_options }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_csr_connection_option)?;
+                CreateConnection::Csr { with_options }
+            }
+            POSTGRES => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
                 }
-                _ => unreachable!(),
-            };
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_postgres_connection_option)?;
+                CreateConnection::Postgres { with_options }
+            }
+            MYSQL => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_mysql_connection_option)?;
+                CreateConnection::MySql { with_options }
+            }
+            SQL => {
+                self.expect_keyword(SERVER)?;
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_sql_server_connection_option)?;
+                CreateConnection::SqlServer { with_options }
+            }
+            SSH => {
+                self.expect_keyword(TUNNEL)?;
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_ssh_connection_option)?;
+                CreateConnection::Ssh { with_options }
+            }
+            _ => unreachable!(),
+        };
         if expect_paren {
             self.expect_token(&Token::RParen)?;
         }
@@ -2000,19 +2140,56 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_kafka_connection_option(&mut self) -> Result<KafkaConnectionOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[BROKER, BROKERS, PROGRESS, SASL, SSL])? {
+        let name = match self
+            .expect_one_of_keywords(&[AWS, BROKER, BROKERS, EVENT, PROGRESS, SASL, SSH, SSL])?
+        {
+            AWS => {
+                let name = match self.expect_one_of_keywords(&[CONNECTION, PRIVATELINK])? {
+                    CONNECTION => KafkaConnectionOptionName::AwsConnection,
+                    PRIVATELINK => KafkaConnectionOptionName::AwsPrivatelink,
+                    _ => unreachable!(),
+                };
+                return Ok(KafkaConnectionOption {
+                    name,
+                    value: Some(self.parse_object_option_value()?),
+                });
+            }
             BROKER => KafkaConnectionOptionName::Broker,
             BROKERS => KafkaConnectionOptionName::Brokers,
+            EVENT => {
+                self.expect_keywords(&[HUBS, CONNECTION, STRING])?;
+                KafkaConnectionOptionName::EventHubsConnectionString
+            }
             PROGRESS => {
                 self.expect_keyword(TOPIC)?;
                 KafkaConnectionOptionName::ProgressTopic
             }
-            SASL => match self.expect_one_of_keywords(&[MECHANISMS, PASSWORD, USERNAME])? {
+            SASL => match self.expect_one_of_keywords(&[MECHANISMS, OAUTH, PASSWORD, USERNAME])? {
                 MECHANISMS => KafkaConnectionOptionName::SaslMechanisms,
+                OAUTH => match self.expect_one_of_keywords(&[CLIENT, SCOPE, TOKEN])? {
+                    CLIENT => match self.expect_one_of_keywords(&[ID, SECRET])? {
+                        ID => KafkaConnectionOptionName::SaslOauthClientId,
+                        SECRET => KafkaConnectionOptionName::SaslOauthClientSecret,
+                        _ => unreachable!(),
+                    },
+                    SCOPE => KafkaConnectionOptionName::SaslOauthScope,
+                    TOKEN => {
+                        self.expect_keyword(ENDPOINT)?;
+                        KafkaConnectionOptionName::SaslOauthTokenEndpoint
+                    }
+                    _ => unreachable!(),
+                },
                 PASSWORD => KafkaConnectionOptionName::SaslPassword,
                 USERNAME => KafkaConnectionOptionName::SaslUsername,
                 _ => unreachable!(),
             },
+            SSH => {
+                self.expect_keyword(TUNNEL)?;
+                return Ok(KafkaConnectionOption {
+                    name: KafkaConnectionOptionName::SshTunnel,
+                    value: Some(self.parse_object_option_value()?),
+                });
+            }
             SSL => match self.expect_one_of_keywords(&[KEY, CERTIFICATE])? {
                 KEY => KafkaConnectionOptionName::SslKey,
                 CERTIFICATE => {
@@ -2049,16 +2226,39 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_kafka_config_option(&mut self) -> Result<KafkaConfigOption<Raw>, ParserError> {
+        let name = self.parse_kafka_config_option_name()?;
+        if name == KafkaConfigOptionName::PartitionBy {
+            let _ = self.consume_token(&Token::Eq);
+            return Ok(KafkaConfigOption {
+                name,
+                value: Some(WithOptionValue::Expr(self.parse_expr()?)),
+            });
+        }
+        Ok(KafkaConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_kafka_config_option_name(&mut self) -> Result<KafkaConfigOptionName, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             ACKS,
+            BATCH,
             CLIENT,
+            COMMIT,
+            COMPRESSION,
+            DEAD,
             ENABLE,
             FETCH,
             GROUP,
+            HEADERS,
             ISOLATION,
+            LINGER,
+            MESSAGE,
             PARTITION,
             REPLICATION,
             RETENTION,
+            RETRY,
             SNAPSHOT,
             START,
             STATISTICS,
@@ -2066,10 +2266,26 @@ impl<'a> Parser<'a> {
             TRANSACTION,
         ])? {
             ACKS => KafkaConfigOptionName::Acks,
+            BATCH => {
+                self.expect_keywords(&[NUM, MESSAGES])?;
+                KafkaConfigOptionName::BatchNumMessages
+            }
             CLIENT => {
                 self.expect_keyword(ID)?;
                 KafkaConfigOptionName::ClientId
             }
+            COMMIT => {
+                self.expect_keyword(INTERVAL)?;
+                KafkaConfigOptionName::CommitInterval
+            }
+            COMPRESSION => {
+                self.expect_keyword(TYPE)?;
+                KafkaConfigOptionName::CompressionType
+            }
+            DEAD => {
+                self.expect_keywords(&[LETTER, TOPIC])?;
+                KafkaConfigOptionName::DeadLetterTopic
+            }
             ENABLE => {
                 self.expect_keyword(IDEMPOTENCE)?;
                 KafkaConfigOptionName::EnableIdempotence
@@ -2082,14 +2298,24 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[ID, PREFIX])?;
                 KafkaConfigOptionName::GroupIdPrefix
             }
+            HEADERS => KafkaConfigOptionName::Headers,
             ISOLATION => {
                 self.expect_keyword(LEVEL)?;
                 KafkaConfigOptionName::IsolationLevel
             }
-            PARTITION => {
-                self.expect_keyword(COUNT)?;
-                KafkaConfigOptionName::PartitionCount
+            LINGER => {
+                self.expect_keyword(MS)?;
+                KafkaConfigOptionName::LingerMs
+            }
+            MESSAGE => {
+                self.expect_keyword(TIMESTAMP)?;
+                KafkaConfigOptionName::MessageTimestamp
             }
+            PARTITION => match self.expect_one_of_keywords(&[BY, COUNT])? {
+                BY => KafkaConfigOptionName::PartitionBy,
+                COUNT => KafkaConfigOptionName::PartitionCount,
+                _ => unreachable!(),
+            },
             REPLICATION => {
                 self.expect_keyword(FACTOR)?;
                 KafkaConfigOptionName::ReplicationFactor
@@ -2099,16 +2325,39 @@ impl<'a> Parser<'a> {
                 MS => KafkaConfigOptionName::RetentionMs,
                 _ => unreachable!(),
             },
+            RETRY => {
+                if self.parse_keyword(crate::keywords::MAX) {
+                    match self.expect_one_of_keywords(&[ATTEMPTS, BACKOFF])? {
+                        ATTEMPTS => KafkaConfigOptionName::RetryMaxAttempts,
+                        BACKOFF => KafkaConfigOptionName::RetryMaxBackoff,
+                        _ => unreachable!(),
+                    }
+                } else {
+                    match self.expect_one_of_keywords(&[BACKOFF, EXHAUSTED])? {
+                        BACKOFF => KafkaConfigOptionName::RetryBackoff,
+                        EXHAUSTED => {
+                            self.expect_keyword(ACTION)?;
+                            KafkaConfigOptionName::RetryExhaustedAction
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
             STATISTICS => {
                 self.expect_keywords(&[INTERVAL, MS])?;
                 KafkaConfigOptionName::StatisticsIntervalMs
             }
             TOPIC => {
-                if self.parse_keyword(METADATA) {
-                    self.expect_keywords(&[REFRESH, INTERVAL, MS])?;
-                    KafkaConfigOptionName::TopicMetadataRefreshIntervalMs
-                } else {
-                    KafkaConfigOptionName::Topic
+                match self.parse_one_of_keywords(&[COLUMN, CONFIG, METADATA, PATTERN, TEMPLATE]) {
+                    Some(COLUMN) => KafkaConfigOptionName::TopicColumn,
+                    Some(CONFIG) => KafkaConfigOptionName::TopicConfig,
+                    Some(METADATA) => {
+                        self.expect_keywords(&[REFRESH, INTERVAL, MS])?;
+                        KafkaConfigOptionName::TopicMetadataRefreshIntervalMs
+                    }
+                    Some(PATTERN) => KafkaConfigOptionName::TopicPattern,
+                    Some(TEMPLATE) => KafkaConfigOptionName::TopicTemplate,
+                    _ => KafkaConfigOptionName::Topic,
                 }
             }
             TRANSACTION => {
@@ -2122,10 +2371,7 @@ impl<'a> Parser<'a> {
             },
             _ => unreachable!(),
         };
-        Ok(KafkaConfigOption {
-            name,
-            value: self.parse_optional_option_value()?,
-        })
+        Ok(name)
     }
 
     fn parse_csr_connection_option(&mut self) -> Result<CsrConnectionOption<Raw>, ParserError> {
@@ -2155,9 +2401,16 @@ impl<'a> Parser<'a> {
     fn parse_postgres_connection_option(
         &mut self,
     ) -> Result<PostgresConnectionOption<Raw>, ParserError> {
-        let name = match self
-            .expect_one_of_keywords(&[DATABASE, HOST, PASSWORD, PORT, SSH, SSL, USER, USERNAME])?
-        {
+        let name = match self.expect_one_of_keywords(&[
+            AWS, DATABASE, HOST, PASSWORD, PORT, SSH, SSL, USER, USERNAME,
+        ])? {
+            AWS => {
+                self.expect_keyword(PRIVATELINK)?;
+                return Ok(PostgresConnectionOption {
+                    name: PostgresConnectionOptionName::AwsPrivatelink,
+                    value: Some(self.parse_object_option_value()?),
+                });
+            }
             DATABASE => PostgresConnectionOptionName::Database,
             HOST => PostgresConnectionOptionName::Host,
             PASSWORD => PostgresConnectionOptionName::Password,
@@ -2190,6 +2443,56 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_mysql_connection_option(&mut self) -> Result<MySqlConnectionOption<Raw>, ParserError> {
+        let name =
+            match self.expect_one_of_keywords(&[HOST, PASSWORD, PORT, SSL, USER, USERNAME])? {
+                HOST => MySqlConnectionOptionName::Host,
+                PASSWORD => MySqlConnectionOptionName::Password,
+                PORT => MySqlConnectionOptionName::Port,
+                SSL => match self.expect_one_of_keywords(&[CERTIFICATE, MODE])? {
+                    CERTIFICATE => {
+                        self.expect_keyword(AUTHORITY)?;
+                        MySqlConnectionOptionName::SslCertificateAuthority
+                    }
+                    MODE => MySqlConnectionOptionName::SslMode,
+                    _ => unreachable!(),
+                },
+                USER | USERNAME => MySqlConnectionOptionName::User,
+                _ => unreachable!(),
+            };
+        Ok(MySqlConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sql_server_connection_option(
+        &mut self,
+    ) -> Result<SqlServerConnectionOption<Raw>, ParserError> {
+        let name = match self
+            .expect_one_of_keywords(&[DATABASE, HOST, PASSWORD, PORT, SSL, USER, USERNAME])?
+        {
+            DATABASE => SqlServerConnectionOptionName::Database,
+            HOST => SqlServerConnectionOptionName::Host,
+            PASSWORD => SqlServerConnectionOptionName::Password,
+            PORT => SqlServerConnectionOptionName::Port,
+            SSL => match self.expect_one_of_keywords(&[CERTIFICATE, MODE])? {
+                CERTIFICATE => {
+                    self.expect_keyword(AUTHORITY)?;
+                    SqlServerConnectionOptionName::SslCertificateAuthority
+                }
+                MODE => SqlServerConnectionOptionName::SslMode,
+                _ => unreachable!(),
+            },
+            USER | USERNAME => SqlServerConnectionOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(SqlServerConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_aws_connection_option(&mut self) -> Result<AwsConnectionOption<Raw>, ParserError> {
         let name =
             match self.expect_one_of_keywords(&[ACCESS, ENDPOINT, REGION, ROLE, SECRET, TOKEN])? {
@@ -2216,6 +2519,16 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_aws_privatelink_connection_option(
+        &mut self,
+    ) -> Result<AwsPrivatelinkConnectionOption<Raw>, ParserError> {
+        self.expect_keyword(ENDPOINT)?;
+        Ok(AwsPrivatelinkConnectionOption {
+            name: AwsPrivatelinkConnectionOptionName::Endpoint,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_ssh_connection_option(&mut self) -> Result<SshConnectionOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[HOST, PORT, USER])? {
             HOST => SshConnectionOptionName::Host,
@@ -2275,12 +2588,13 @@ impl<'a> Parser<'a> {
             self.expect_token(&Token::LParen)?;
             let subsources = self.parse_comma_separated(|parser| {
                 let name = parser.parse_object_name()?;
+                let columns = parser.parse_parenthesized_column_list(Optional)?;
                 let subsource = if parser.parse_keyword(INTO) {
-                    CreateSourceSubsource::Resolved(name, parser.parse_raw_name()?)
+                    CreateSourceSubsource::Resolved(name, columns, parser.parse_raw_name()?)
                 } else if parser.parse_keyword(AS) {
-                    CreateSourceSubsource::Aliased(name, parser.parse_object_name()?)
+                    CreateSourceSubsource::Aliased(name, columns, parser.parse_object_name()?)
                 } else {
-                    CreateSourceSubsource::Bare(name)
+                    CreateSourceSubsource::Bare(name, columns)
                 };
                 Ok(subsource)
             })?;
@@ -2359,21 +2673,48 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name =
-            match self.expect_one_of_keywords(&[IGNORE, REMOTE, SIZE, TIMELINE, TIMESTAMP])? {
-                IGNORE => {
-                    self.expect_keyword(KEYS)?;
-                    CreateSourceOptionName::IgnoreKeys
-                }
-                REMOTE => CreateSourceOptionName::Remote,
-                SIZE => CreateSourceOptionName::Size,
-                TIMELINE => CreateSourceOptionName::Timeline,
-                TIMESTAMP => {
-                    self.expect_keyword(INTERVAL)?;
-                    CreateSourceOptionName::TimestampInterval
+        let name = match self.expect_one_of_keywords(&[
+            DECODE, IGNORE, MAX, NUMERIC, REMOTE, SIZE, TIMELINE, TIMESTAMP, UPSERT,
+        ])? {
+            DECODE => {
+                self.expect_keyword(ERRORS)?;
+                CreateSourceOptionName::DecodeErrors
+            }
+            IGNORE => {
+                self.expect_keyword(KEYS)?;
+                CreateSourceOptionName::IgnoreKeys
+            }
+            MAX => {
+                let name = match self.expect_one_of_keywords(&[BYTES, MESSAGES])? {
+                    BYTES => CreateSourceOptionName::MaxBytesPerSecond,
+                    MESSAGES => CreateSourceOptionName::MaxMessagesPerSecond,
+                    _ => unreachable!(),
+                };
+                self.expect_keywords(&[PER, SECOND])?;
+                name
+            }
+            NUMERIC => {
+                self.expect_keyword(OVERFLOW)?;
+                CreateSourceOptionName::NumericOverflow
+            }
+            REMOTE => CreateSourceOptionName::Remote,
+            SIZE => CreateSourceOptionName::Size,
+            TIMELINE => CreateSourceOptionName::Timeline,
+            TIMESTAMP => {
+                match self.expect_one_of_keywords(&[COLUMN, INTERVAL, LATENESS, STRATEGY])? {
+                    COLUMN => CreateSourceOptionName::TimestampColumn,
+                    INTERVAL => CreateSourceOptionName::TimestampInterval,
+                    LATENESS => CreateSourceOptionName::TimestampLateness,
+                    STRATEGY => CreateSourceOptionName::TimestampStrategy,
+                    _ => unreachable!(),
                 }
-                _ => unreachable!(),
-            };
+            }
+            UPSERT => {
+                self.expect_keyword(STATE)?;
+                CreateSourceOptionName::UpsertState
+            }
+            _ => unreachable!(),
+        };
         Ok(name)
     }
 
@@ -2392,6 +2733,18 @@ impl<'a> Parser<'a> {
         let name = self.parse_object_name()?;
         self.expect_keyword(FROM)?;
         let from = self.parse_raw_name()?;
+        let columns = if self.consume_token(&Token::LParen) {
+            let columns = self.parse_comma_separated(Parser::parse_sink_column)?;
+            self.expect_token(&Token::RParen)?;
+            columns
+        } else {
+            vec![]
+        };
+        let filter = if self.parse_keyword(WHERE) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
         self.expect_keyword(INTO)?;
         let connection = self.parse_create_sink_connection()?;
         let format = if self.parse_keyword(FORMAT) {
@@ -2417,6 +2770,8 @@ impl<'a> Parser<'a> {
         Ok(Statement::CreateSink(CreateSinkStatement {
             name,
             from,
+            columns,
+            filter,
             connection,
             format,
             envelope,
@@ -2425,12 +2780,80 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_sink_column(&mut self) -> Result<SinkColumn, ParserError> {
+        let name = self.parse_identifier()?;
+        let alias = if self.parse_keyword(AS) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(SinkColumn { name, alias })
+    }
+
     /// Parse the name of a CREATE SINK optional parameter
     fn parse_create_sink_option_name(&mut self) -> Result<CreateSinkOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[REMOTE, SIZE, SNAPSHOT])? {
+        let name = match self.expect_one_of_keywords(&[
+            CATCH,
+            DELETE,
+            DELETES,
+            INCLUDE,
+            MASK,
+            MAX,
+            REMOTE,
+            RETRACTIONS,
+            SIZE,
+            SNAPSHOT,
+            SPILL,
+        ])? {
+            CATCH => {
+                self.expect_keyword(UP)?;
+                let name = match self.expect_one_of_keywords(&[BYTES, RECORDS])? {
+                    BYTES => CreateSinkOptionName::CatchUpBytesPerSecond,
+                    RECORDS => CreateSinkOptionName::CatchUpRecordsPerSecond,
+                    _ => unreachable!(),
+                };
+                self.expect_keywords(&[PER, SECOND])?;
+                name
+            }
+            MAX => {
+                let name = match self.expect_one_of_keywords(&[BYTES, MESSAGES])? {
+                    BYTES => CreateSinkOptionName::MaxBytesPerSecond,
+                    MESSAGES => CreateSinkOptionName::MaxMessagesPerSecond,
+                    _ => unreachable!(),
+                };
+                self.expect_keywords(&[PER, SECOND])?;
+                name
+            }
+            DELETE => {
+                self.expect_keyword(MARKER)?;
+                CreateSinkOptionName::DeleteMarker
+            }
+            DELETES => CreateSinkOptionName::Deletes,
+            INCLUDE => match self.expect_one_of_keywords(&[DIFF, TIMESTAMP])? {
+                DIFF => CreateSinkOptionName::IncludeDiff,
+                TIMESTAMP => CreateSinkOptionName::IncludeTimestamp,
+                _ => unreachable!(),
+            },
+            MASK => match self.expect_one_of_keywords(&[HASH, REDACT, TRUNCATE])? {
+                HASH => CreateSinkOptionName::MaskHash,
+                REDACT => CreateSinkOptionName::MaskRedact,
+                TRUNCATE => {
+                    if self.parse_keyword(LENGTH) {
+                        CreateSinkOptionName::MaskTruncateLength
+                    } else {
+                        CreateSinkOptionName::MaskTruncate
+                    }
+                }
+                _ => unreachable!(),
+            },
             SIZE => CreateSinkOptionName::Size,
             SNAPSHOT => CreateSinkOptionName::Snapshot,
             REMOTE => CreateSinkOptionName::Remote,
+            RETRACTIONS => CreateSinkOptionName::Retractions,
+            SPILL => {
+                self.expect_keyword(BUDGET)?;
+                CreateSinkOptionName::SpillBudget
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -2447,7 +2870,10 @@ impl<'a> Parser<'a> {
     fn parse_create_source_connection(
         &mut self,
     ) -> Result<CreateSourceConnection<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, KINESIS, S3, POSTGRES, LOAD, TEST])? {
+        match self.expect_one_of_keywords(&[
+            KAFKA, KINESIS, S3, POSTGRES, MYSQL, SQL, LOAD, FILE, MQTT, NATS, PUBSUB, SFTP, TEST,
+            WEBHOOK,
+        ])? {
             POSTGRES => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -2465,19 +2891,54 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
-            KAFKA => {
+            MYSQL => {
                 self.expect_keyword(CONNECTION)?;
-                let connection = self.parse_kafka_connection_reference()?;
-                // one token of lookahead:
-                // * `KEY (` means we're parsing a list of columns for the key
-                // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
-                let key = if self.peek_keyword(KEY)
-                    && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT))
-                {
-                    let _ = self.expect_keyword(KEY);
-                    Some(self.parse_parenthesized_column_list(Mandatory)?)
-                } else {
-                    None
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_mysql_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::MySql {
+                    connection,
+                    options,
+                })
+            }
+            SQL => {
+                self.expect_keywords(&[SERVER, CONNECTION])?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_sql_server_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::SqlServer {
+                    connection,
+                    options,
+                })
+            }
+            KAFKA => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_kafka_connection_reference()?;
+                // one token of lookahead:
+                // * `KEY (` means we're parsing a list of columns for the key
+                // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
+                let key = if self.peek_keyword(KEY)
+                    && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT))
+                {
+                    let _ = self.expect_keyword(KEY);
+                    Some(self.parse_parenthesized_column_list(Mandatory)?)
+                } else {
+                    None
                 };
                 Ok(CreateSourceConnection::Kafka(KafkaSourceConnection {
                     connection,
@@ -2490,7 +2951,16 @@ impl<'a> Parser<'a> {
 
                 self.expect_keyword(ARN)?;
                 let arn = self.parse_literal_string()?;
-                Ok(CreateSourceConnection::Kinesis { connection, arn })
+                let consumer = if self.parse_keyword(CONSUMER) {
+                    Some(self.parse_literal_string()?)
+                } else {
+                    None
+                };
+                Ok(CreateSourceConnection::Kinesis {
+                    connection,
+                    arn,
+                    consumer,
+                })
             }
             S3 => {
                 // FROM S3 CONNECTION <aws CONNECTION> DISCOVER OBJECTS
@@ -2559,12 +3029,102 @@ impl<'a> Parser<'a> {
                 };
                 Ok(CreateSourceConnection::LoadGenerator { generator, options })
             }
+            FILE => {
+                // FROM FILE '<path>' [COMPRESSION <compression>] (<options>)?
+                let path = self.parse_literal_string()?;
+                let compression = if self.parse_keyword(COMPRESSION) {
+                    self.parse_compression()?
+                } else {
+                    Compression::None
+                };
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_file_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::File {
+                    path,
+                    compression,
+                    options,
+                })
+            }
+            MQTT => {
+                // FROM MQTT '<url>' (<options>)?
+                let url = self.parse_literal_string()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_mqtt_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::Mqtt { url, options })
+            }
+            NATS => {
+                // FROM NATS '<url>' (<options>)?
+                let url = self.parse_literal_string()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_nats_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::Nats { url, options })
+            }
+            PUBSUB => {
+                // FROM PUBSUB SUBSCRIPTION '<subscription>' (<options>)?
+                self.expect_keyword(SUBSCRIPTION)?;
+                let subscription = self.parse_literal_string()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_pubsub_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::PubSub {
+                    subscription,
+                    options,
+                })
+            }
+            SFTP => {
+                // FROM SFTP '<url>' (<options>)?
+                let url = self.parse_literal_string()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_sftp_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::Sftp { url, options })
+            }
             TEST => {
                 self.expect_keyword(SCRIPT)?;
                 Ok(CreateSourceConnection::TestScript {
                     desc_json: self.parse_literal_string()?,
                 })
             }
+            WEBHOOK => {
+                let secret = if self.parse_keyword(VALIDATE) {
+                    self.expect_keywords(&[USING, SECRET])?;
+                    Some(self.parse_raw_name()?)
+                } else {
+                    None
+                };
+                let check = if self.parse_keyword(CHECK) {
+                    self.expect_token(&Token::LParen)?;
+                    let check = self.parse_expr()?;
+                    self.expect_token(&Token::RParen)?;
+                    Some(check)
+                } else {
+                    None
+                };
+                Ok(CreateSourceConnection::Webhook { secret, check })
+            }
             _ => unreachable!(),
         }
     }
@@ -2581,6 +3141,106 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_mysql_config_option(&mut self) -> Result<MySqlConfigOption<Raw>, ParserError> {
+        self.expect_keyword(DETAILS)?;
+        Ok(MySqlConfigOption {
+            name: MySqlConfigOptionName::Details,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sql_server_config_option(
+        &mut self,
+    ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
+        self.expect_keyword(DETAILS)?;
+        Ok(SqlServerConfigOption {
+            name: SqlServerConfigOptionName::Details,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_file_config_option(&mut self) -> Result<FileConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[POLL])? {
+            POLL => {
+                self.expect_keyword(INTERVAL)?;
+                FileConfigOptionName::PollInterval
+            }
+            _ => unreachable!(),
+        };
+        Ok(FileConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_mqtt_config_option(&mut self) -> Result<MqttConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PASSWORD, TOPIC, USER])? {
+            PASSWORD => MqttConfigOptionName::Password,
+            TOPIC => MqttConfigOptionName::Topic,
+            USER => MqttConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(MqttConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_nats_config_option(&mut self) -> Result<NatsConfigOption<Raw>, ParserError> {
+        let name = match self
+            .expect_one_of_keywords(&[CONSUMER, PASSWORD, STREAM, SUBJECT, TOKEN, USER])?
+        {
+            CONSUMER => NatsConfigOptionName::Consumer,
+            PASSWORD => NatsConfigOptionName::Password,
+            STREAM => NatsConfigOptionName::Stream,
+            SUBJECT => NatsConfigOptionName::Subject,
+            TOKEN => NatsConfigOptionName::Token,
+            USER => NatsConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(NatsConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_pubsub_config_option(&mut self) -> Result<PubSubConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[ACK, CREDENTIALS, ENDPOINT])? {
+            ACK => {
+                self.expect_keyword(DEADLINE)?;
+                PubSubConfigOptionName::AckDeadline
+            }
+            CREDENTIALS => PubSubConfigOptionName::Credentials,
+            ENDPOINT => PubSubConfigOptionName::Endpoint,
+            _ => unreachable!(),
+        };
+        Ok(PubSubConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sftp_config_option(&mut self) -> Result<SftpConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PASSWORD, PATTERN, POLL, PRIVATE, USER])? {
+            PASSWORD => SftpConfigOptionName::Password,
+            PATTERN => SftpConfigOptionName::Pattern,
+            POLL => {
+                self.expect_keyword(INTERVAL)?;
+                SftpConfigOptionName::PollInterval
+            }
+            PRIVATE => {
+                self.expect_keyword(KEY)?;
+                SftpConfigOptionName::PrivateKey
+            }
+            USER => SftpConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(SftpConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[SCALE, TICK])? {
             SCALE => {
@@ -2594,41 +3254,332 @@ impl<'a> Parser<'a> {
             _ => unreachable!(),
         };
 
-        let _ = self.consume_token(&Token::Eq);
-        Ok(LoadGeneratorOption {
+        let _ = self.consume_token(&Token::Eq);
+        Ok(LoadGeneratorOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        match self.expect_one_of_keywords(&[
+            ELASTICSEARCH,
+            FILE,
+            GRPC,
+            KAFKA,
+            MQTT,
+            NATS,
+            POSTGRES,
+            REDIS,
+            S3,
+            SQS,
+        ])? {
+            ELASTICSEARCH => self.parse_elasticsearch_sink_connection(),
+            FILE => self.parse_file_sink_connection(),
+            GRPC => self.parse_grpc_sink_connection(),
+            KAFKA => self.parse_kafka_sink_connection(),
+            MQTT => self.parse_mqtt_sink_connection(),
+            NATS => self.parse_nats_sink_connection(),
+            POSTGRES => self.parse_postgres_sink_connection(),
+            REDIS => self.parse_redis_sink_connection(),
+            S3 => self.parse_s3_sink_connection(),
+            SQS => self.parse_sqs_sink_connection(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_elasticsearch_sink_connection(
+        &mut self,
+    ) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options =
+                self.parse_comma_separated(Parser::parse_elasticsearch_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Elasticsearch { url, options, key })
+    }
+
+    fn parse_elasticsearch_sink_config_option(
+        &mut self,
+    ) -> Result<ElasticsearchSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[INDEX, PASSWORD, USER])? {
+            INDEX => ElasticsearchSinkConfigOptionName::Index,
+            PASSWORD => ElasticsearchSinkConfigOptionName::Password,
+            USER => ElasticsearchSinkConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(ElasticsearchSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_file_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let path = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_file_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        Ok(CreateSinkConnection::File { path, options })
+    }
+
+    fn parse_file_sink_config_option(&mut self) -> Result<FileSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[FSYNC, MAX, ROTATION])? {
+            FSYNC => FileSinkConfigOptionName::Fsync,
+            MAX => {
+                self.expect_keywords(&[FILE, SIZE])?;
+                FileSinkConfigOptionName::MaxFileSize
+            }
+            ROTATION => {
+                self.expect_keyword(INTERVAL)?;
+                FileSinkConfigOptionName::RotationInterval
+            }
+            _ => unreachable!(),
+        };
+        Ok(FileSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_s3_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        self.expect_keyword(CONNECTION)?;
+        let connection = self.parse_raw_name()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_s3_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        Ok(CreateSinkConnection::S3 {
+            connection,
+            options,
+        })
+    }
+
+    fn parse_s3_sink_config_option(&mut self) -> Result<S3SinkConfigOption<Raw>, ParserError> {
+        let name =
+            match self.expect_one_of_keywords(&[BUCKET, KEY, MAX, ON, PREFIX, ROTATION, TABLE])? {
+                BUCKET => S3SinkConfigOptionName::Bucket,
+                KEY => {
+                    self.expect_keyword(TEMPLATE)?;
+                    S3SinkConfigOptionName::KeyTemplate
+                }
+                MAX => {
+                    self.expect_keywords(&[FILE, SIZE])?;
+                    S3SinkConfigOptionName::MaxFileSize
+                }
+                ON => {
+                    self.expect_keyword(COLLISION)?;
+                    S3SinkConfigOptionName::OnCollision
+                }
+                PREFIX => S3SinkConfigOptionName::Prefix,
+                ROTATION => {
+                    self.expect_keyword(INTERVAL)?;
+                    S3SinkConfigOptionName::RotationInterval
+                }
+                TABLE => {
+                    self.expect_keyword(FORMAT)?;
+                    S3SinkConfigOptionName::TableFormat
+                }
+                _ => unreachable!(),
+            };
+        Ok(S3SinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_kafka_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        self.expect_keyword(CONNECTION)?;
+
+        let connection = self.parse_kafka_connection_reference()?;
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Kafka { connection, key })
+    }
+
+    fn parse_postgres_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        self.expect_keyword(CONNECTION)?;
+        let connection = self.parse_raw_name()?;
+        self.expect_keyword(TABLE)?;
+        let table = self.parse_object_name()?;
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Postgres {
+            connection,
+            table,
+            key,
+        })
+    }
+
+    fn parse_redis_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_redis_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Redis { url, options, key })
+    }
+
+    fn parse_redis_sink_config_option(
+        &mut self,
+    ) -> Result<RedisSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[KEY, PASSWORD, TTL, TYPE, USER])? {
+            KEY => {
+                self.expect_keyword(PREFIX)?;
+                RedisSinkConfigOptionName::KeyPrefix
+            }
+            PASSWORD => RedisSinkConfigOptionName::Password,
+            TTL => RedisSinkConfigOptionName::Ttl,
+            TYPE => RedisSinkConfigOptionName::DataType,
+            USER => RedisSinkConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(RedisSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_nats_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_nats_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Nats { url, options, key })
+    }
+
+    fn parse_nats_sink_config_option(&mut self) -> Result<NatsSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PASSWORD, SUBJECT, TOKEN, USER])? {
+            PASSWORD => NatsSinkConfigOptionName::Password,
+            SUBJECT => NatsSinkConfigOptionName::Subject,
+            TOKEN => NatsSinkConfigOptionName::Token,
+            USER => NatsSinkConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(NatsSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_mqtt_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_mqtt_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Mqtt { url, options, key })
+    }
+
+    fn parse_mqtt_sink_config_option(&mut self) -> Result<MqttSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PASSWORD, QOS, RETAIN, TOPIC, USER])? {
+            PASSWORD => MqttSinkConfigOptionName::Password,
+            QOS => MqttSinkConfigOptionName::Qos,
+            RETAIN => MqttSinkConfigOptionName::Retain,
+            TOPIC => MqttSinkConfigOptionName::Topic,
+            USER => MqttSinkConfigOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(MqttSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_grpc_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_grpc_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Grpc { url, options, key })
+    }
+
+    fn parse_grpc_sink_config_option(&mut self) -> Result<GrpcSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[METHOD, TOKEN])? {
+            METHOD => GrpcSinkConfigOptionName::Method,
+            TOKEN => GrpcSinkConfigOptionName::Token,
+            _ => unreachable!(),
+        };
+        Ok(GrpcSinkConfigOption {
             name,
             value: self.parse_optional_option_value()?,
         })
     }
 
-    fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
-        self.expect_keyword(KAFKA)?;
+    fn parse_sqs_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
         self.expect_keyword(CONNECTION)?;
+        let connection = self.parse_raw_name()?;
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_sqs_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Sqs {
+            connection,
+            options,
+            key,
+        })
+    }
 
-        let connection = self.parse_kafka_connection_reference()?;
+    fn parse_sqs_sink_config_option(&mut self) -> Result<SqsSinkConfigOption<Raw>, ParserError> {
+        self.expect_keywords(&[QUEUE, URL])?;
+        Ok(SqsSinkConfigOption {
+            name: SqsSinkConfigOptionName::QueueUrl,
+            value: self.parse_optional_option_value()?,
+        })
+    }
 
+    fn parse_sink_key(&mut self) -> Result<Option<SinkKey>, ParserError> {
         // one token of lookahead:
         // * `KEY (` means we're parsing a list of columns for the key
         // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
-        let key =
-            if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
-                let _ = self.expect_keyword(KEY);
-                let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
-
-                let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
-                    let _ = self.expect_keywords(&[NOT, ENFORCED])?;
-                    true
-                } else {
-                    false
-                };
-                Some(KafkaSinkKey {
-                    key_columns,
-                    not_enforced,
-                })
+        if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
+            let _ = self.expect_keyword(KEY);
+            let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+
+            let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
+                let _ = self.expect_keywords(&[NOT, ENFORCED])?;
+                true
             } else {
-                None
+                false
             };
-        Ok(CreateSinkConnection::Kafka { connection, key })
+            Ok(Some(SinkKey {
+                key_columns,
+                not_enforced,
+            }))
+        } else {
+            Ok(None)
+        }
     }
 
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -2683,6 +3634,15 @@ impl<'a> Parser<'a> {
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let in_cluster = self.parse_optional_in_cluster()?;
 
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_materialized_view_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
         self.expect_keyword(AS)?;
         let query = self.parse_query()?;
 
@@ -2692,11 +3652,34 @@ impl<'a> Parser<'a> {
                 name,
                 columns,
                 in_cluster,
+                with_options,
                 query,
             },
         ))
     }
 
+    fn parse_materialized_view_option(
+        &mut self,
+    ) -> Result<MaterializedViewOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[LOGICAL, MAX, TARGET])? {
+            LOGICAL => {
+                self.expect_keywords(&[COMPACTION, WINDOW])?;
+                MaterializedViewOptionName::LogicalCompactionWindow
+            }
+            MAX => {
+                self.expect_keywords(&[BATCH, STALENESS])?;
+                MaterializedViewOptionName::MaxBatchStaleness
+            }
+            TARGET => {
+                self.expect_keywords(&[BATCH, SIZE])?;
+                MaterializedViewOptionName::TargetBatchSize
+            }
+            _ => unreachable!(),
+        };
+        let value = self.parse_optional_option_value()?;
+        Ok(MaterializedViewOption { name, value })
+    }
+
     fn parse_create_index(&mut self) -> Result<Statement<Raw>, ParserError> {
         let default_index = self.parse_keyword(DEFAULT);
         self.expect_keyword(INDEX)?;
@@ -2987,18 +3970,30 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_source_include_metadata(&mut self) -> Result<Vec<SourceIncludeMetadata>, ParserError> {
+    fn parse_source_include_metadata(
+        &mut self,
+    ) -> Result<Vec<SourceIncludeMetadata<Raw>>, ParserError> {
         if self.parse_keyword(INCLUDE) {
             self.parse_comma_separated(|parser| {
-                let ty = match parser
-                    .expect_one_of_keywords(&[KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET, HEADERS])?
-                {
+                let ty = match parser.expect_one_of_keywords(&[
+                    KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET, HEADERS, HEADER, FILENAME,
+                ])? {
                     KEY => SourceIncludeMetadataType::Key,
                     TIMESTAMP => SourceIncludeMetadataType::Timestamp,
                     PARTITION => SourceIncludeMetadataType::Partition,
                     TOPIC => SourceIncludeMetadataType::Topic,
                     OFFSET => SourceIncludeMetadataType::Offset,
                     HEADERS => SourceIncludeMetadataType::Headers,
+                    FILENAME => SourceIncludeMetadataType::Filename,
+                    HEADER => {
+                        let key = parser.parse_literal_string()?;
+                        let format = if parser.parse_keyword(FORMAT) {
+                            Some(parser.parse_format()?)
+                        } else {
+                            None
+                        };
+                        SourceIncludeMetadataType::Header { key, format }
+                    }
                     _ => unreachable!("only explicitly allowed items can be parsed"),
                 };
                 let alias = parser
@@ -3381,6 +4376,8 @@ impl<'a> Parser<'a> {
             SECRET,
             SYSTEM,
             CONNECTION,
+            DATABASE,
+            SCHEMA,
         ])? {
             SINK => return self.parse_alter_sink(),
             SOURCE => return self.parse_alter_source(),
@@ -3394,20 +4391,58 @@ impl<'a> Parser<'a> {
             SECRET => return self.parse_alter_secret(),
             SYSTEM => return self.parse_alter_system(),
             CONNECTION => return self.parse_alter_connection(),
+            DATABASE => {
+                let if_exists = self.parse_if_exists()?;
+                let name = self.parse_database_name()?;
+                self.expect_keyword(OWNER)?;
+                let name = UnresolvedObjectName(vec![name.0]);
+                return self.parse_alter_owner(ObjectType::Database, if_exists, name);
+            }
+            SCHEMA => {
+                let if_exists = self.parse_if_exists()?;
+                let name = self.parse_schema_name()?;
+                self.expect_keyword(OWNER)?;
+                let name = UnresolvedObjectName(name.0);
+                return self.parse_alter_owner(ObjectType::Schema, if_exists, name);
+            }
             _ => unreachable!(),
         };
 
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        self.expect_keywords(&[RENAME, TO])?;
-        let to_item_name = self.parse_identifier()?;
+        match self.expect_one_of_keywords(&[RENAME, OWNER])? {
+            RENAME => {
+                self.expect_keyword(TO)?;
+                let to_item_name = self.parse_identifier()?;
+
+                Ok(Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type,
+                    if_exists,
+                    name,
+                    to_item_name,
+                }))
+            }
+            OWNER => self.parse_alter_owner(object_type, if_exists, name),
+            _ => unreachable!(),
+        }
+    }
 
-        Ok(Statement::AlterObjectRename(AlterObjectRenameStatement {
+    /// Parses the remainder of an `ALTER ... OWNER TO` statement, assuming that
+    /// the `OWNER` token has already been consumed.
+    fn parse_alter_owner(
+        &mut self,
+        object_type: ObjectType,
+        if_exists: bool,
+        name: UnresolvedObjectName,
+    ) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(TO)?;
+        let new_owner = self.parse_identifier()?;
+        Ok(Statement::AlterOwner(AlterOwnerStatement {
             object_type,
             if_exists,
             name,
-            to_item_name,
+            new_owner,
         }))
     }
 
@@ -3415,89 +4450,105 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[RESET, SET, RENAME])? {
-            RESET => {
-                self.expect_token(&Token::LParen)?;
-                let reset_options = self.parse_comma_separated(Parser::parse_source_option_name)?;
-                self.expect_token(&Token::RParen)?;
+        Ok(
+            match self.expect_one_of_keywords(&[RESET, SET, RENAME, REFRESH, OWNER])? {
+                RESET => {
+                    self.expect_token(&Token::LParen)?;
+                    let reset_options =
+                        self.parse_comma_separated(Parser::parse_source_option_name)?;
+                    self.expect_token(&Token::RParen)?;
 
-                Statement::AlterSource(AlterSourceStatement {
-                    source_name: name,
-                    if_exists,
-                    action: AlterSourceAction::ResetOptions(reset_options),
-                })
-            }
-            SET => {
-                self.expect_token(&Token::LParen)?;
-                let set_options = self.parse_comma_separated(Parser::parse_source_option)?;
-                self.expect_token(&Token::RParen)?;
-                Statement::AlterSource(AlterSourceStatement {
-                    source_name: name,
-                    if_exists,
-                    action: AlterSourceAction::SetOptions(set_options),
-                })
-            }
-            RENAME => {
-                self.expect_keyword(TO)?;
-                let to_item_name = self.parse_identifier()?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::ResetOptions(reset_options),
+                    })
+                }
+                SET => {
+                    self.expect_token(&Token::LParen)?;
+                    let set_options = self.parse_comma_separated(Parser::parse_source_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::SetOptions(set_options),
+                    })
+                }
+                REFRESH => {
+                    self.expect_keyword(SCHEMA)?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::RefreshSchema,
+                    })
+                }
+                RENAME => {
+                    self.expect_keyword(TO)?;
+                    let to_item_name = self.parse_identifier()?;
 
-                Statement::AlterObjectRename(AlterObjectRenameStatement {
-                    object_type: ObjectType::Source,
-                    if_exists,
-                    name,
-                    to_item_name,
-                })
-            }
-            _ => unreachable!(),
-        })
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Source,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                OWNER => self.parse_alter_owner(ObjectType::Source, if_exists, name)?,
+                _ => unreachable!(),
+            },
+        )
     }
 
     fn parse_alter_index(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[RESET, SET, RENAME])? {
-            RESET => {
-                self.expect_token(&Token::LParen)?;
-                let reset_options = self.parse_comma_separated(Parser::parse_index_option_name)?;
-                self.expect_token(&Token::RParen)?;
+        Ok(
+            match self.expect_one_of_keywords(&[RESET, SET, RENAME, OWNER])? {
+                RESET => {
+                    self.expect_token(&Token::LParen)?;
+                    let reset_options =
+                        self.parse_comma_separated(Parser::parse_index_option_name)?;
+                    self.expect_token(&Token::RParen)?;
 
-                Statement::AlterIndex(AlterIndexStatement {
-                    index_name: name,
-                    if_exists,
-                    action: AlterIndexAction::ResetOptions(reset_options),
-                })
-            }
-            SET => {
-                self.expect_token(&Token::LParen)?;
-                let set_options = self.parse_comma_separated(Parser::parse_index_option)?;
-                self.expect_token(&Token::RParen)?;
-                Statement::AlterIndex(AlterIndexStatement {
-                    index_name: name,
-                    if_exists,
-                    action: AlterIndexAction::SetOptions(set_options),
-                })
-            }
-            RENAME => {
-                self.expect_keyword(TO)?;
-                let to_item_name = self.parse_identifier()?;
+                    Statement::AlterIndex(AlterIndexStatement {
+                        index_name: name,
+                        if_exists,
+                        action: AlterIndexAction::ResetOptions(reset_options),
+                    })
+                }
+                SET => {
+                    self.expect_token(&Token::LParen)?;
+                    let set_options = self.parse_comma_separated(Parser::parse_index_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    Statement::AlterIndex(AlterIndexStatement {
+                        index_name: name,
+                        if_exists,
+                        action: AlterIndexAction::SetOptions(set_options),
+                    })
+                }
+                RENAME => {
+                    self.expect_keyword(TO)?;
+                    let to_item_name = self.parse_identifier()?;
 
-                Statement::AlterObjectRename(AlterObjectRenameStatement {
-                    object_type: ObjectType::Index,
-                    if_exists,
-                    name,
-                    to_item_name,
-                })
-            }
-            _ => unreachable!(),
-        })
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Index,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                OWNER => self.parse_alter_owner(ObjectType::Index, if_exists, name)?,
+                _ => unreachable!(),
+            },
+        )
     }
 
     fn parse_alter_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[AS, RENAME])? {
+        Ok(match self.expect_one_of_keywords(&[AS, RENAME, OWNER])? {
             AS => {
                 let value = self.parse_expr()?;
                 Statement::AlterSecret(AlterSecretStatement {
@@ -3517,6 +4568,7 @@ impl<'a> Parser<'a> {
                     to_item_name,
                 })
             }
+            OWNER => self.parse_alter_owner(ObjectType::Secret, if_exists, name)?,
             _ => unreachable!(),
         })
     }
@@ -3526,42 +4578,62 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[RESET, SET, RENAME])? {
-            RESET => {
-                self.expect_token(&Token::LParen)?;
-                let reset_options =
-                    self.parse_comma_separated(Parser::parse_create_sink_option_name)?;
-                self.expect_token(&Token::RParen)?;
+        Ok(
+            match self.expect_one_of_keywords(&[RESET, SET, RENAME, OWNER])? {
+                RESET => {
+                    let connection = self.parse_keyword(CONNECTION);
+                    self.expect_token(&Token::LParen)?;
+                    let action = if connection {
+                        AlterSinkAction::ResetConnectionOptions(
+                            self.parse_comma_separated(Parser::parse_kafka_config_option_name)?,
+                        )
+                    } else {
+                        AlterSinkAction::ResetOptions(
+                            self.parse_comma_separated(Parser::parse_create_sink_option_name)?,
+                        )
+                    };
+                    self.expect_token(&Token::RParen)?;
 
-                Statement::AlterSink(AlterSinkStatement {
-                    sink_name: name,
-                    if_exists,
-                    action: AlterSinkAction::ResetOptions(reset_options),
-                })
-            }
-            SET => {
-                self.expect_token(&Token::LParen)?;
-                let set_options = self.parse_comma_separated(Parser::parse_create_sink_option)?;
-                self.expect_token(&Token::RParen)?;
-                Statement::AlterSink(AlterSinkStatement {
-                    sink_name: name,
-                    if_exists,
-                    action: AlterSinkAction::SetOptions(set_options),
-                })
-            }
-            RENAME => {
-                self.expect_keyword(TO)?;
-                let to_item_name = self.parse_identifier()?;
+                    Statement::AlterSink(AlterSinkStatement {
+                        sink_name: name,
+                        if_exists,
+                        action,
+                    })
+                }
+                SET => {
+                    let connection = self.parse_keyword(CONNECTION);
+                    self.expect_token(&Token::LParen)?;
+                    let action = if connection {
+                        AlterSinkAction::SetConnectionOptions(
+                            self.parse_comma_separated(Parser::parse_kafka_config_option)?,
+                        )
+                    } else {
+                        AlterSinkAction::SetOptions(
+                            self.parse_comma_separated(Parser::parse_create_sink_option)?,
+                        )
+                    };
+                    self.expect_token(&Token::RParen)?;
+                    Statement::AlterSink(AlterSinkStatement {
+                        sink_name: name,
+                        if_exists,
+                        action,
+                    })
+                }
+                RENAME => {
+                    self.expect_keyword(TO)?;
+                    let to_item_name = self.parse_identifier()?;
 
-                Statement::AlterObjectRename(AlterObjectRenameStatement {
-                    object_type: ObjectType::Sink,
-                    if_exists,
-                    name,
-                    to_item_name,
-                })
-            }
-            _ => unreachable!(),
-        })
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Sink,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                OWNER => self.parse_alter_owner(ObjectType::Sink, if_exists, name)?,
+                _ => unreachable!(),
+            },
+        )
     }
 
     /// Parse an ALTER SYSTEM statement.
@@ -3596,24 +4668,27 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[RENAME, ROTATE])? {
-            RENAME => {
-                self.expect_keyword(TO)?;
-                let to_item_name = self.parse_identifier()?;
+        Ok(
+            match self.expect_one_of_keywords(&[RENAME, ROTATE, OWNER])? {
+                RENAME => {
+                    self.expect_keyword(TO)?;
+                    let to_item_name = self.parse_identifier()?;
 
-                Statement::AlterObjectRename(AlterObjectRenameStatement {
-                    object_type: ObjectType::Connection,
-                    if_exists,
-                    name,
-                    to_item_name,
-                })
-            }
-            ROTATE => {
-                self.expect_keyword(KEYS)?;
-                Statement::AlterConnection(AlterConnectionStatement { name, if_exists })
-            }
-            _ => unreachable!(),
-        })
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Connection,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                OWNER => self.parse_alter_owner(ObjectType::Connection, if_exists, name)?,
+                ROTATE => {
+                    self.expect_keyword(KEYS)?;
+                    Statement::AlterConnection(AlterConnectionStatement { name, if_exists })
+                }
+                _ => unreachable!(),
+            },
+        )
     }
 
     /// Parse a copy statement
@@ -4609,6 +5684,86 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_show_grants(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
+        let target = if self.parse_keyword(ON) {
+            Some(self.parse_grant_target()?)
+        } else {
+            None
+        };
+        let role = if self.parse_keyword(FOR) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(ShowStatement::ShowGrants(ShowGrantsStatement {
+            target,
+            role,
+            filter: self.parse_show_statement_filter()?,
+        }))
+    }
+
+    /// Parses a `GRANT` statement, assuming that the `GRANT` token has already
+    /// been consumed.
+    fn parse_grant(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let privileges = self.parse_privilege_specification()?;
+        self.expect_keyword(ON)?;
+        let target = self.parse_grant_target()?;
+        self.expect_keyword(TO)?;
+        let roles = self.parse_comma_separated(Parser::parse_identifier)?;
+        Ok(Statement::GrantPrivileges(GrantPrivilegesStatement {
+            privileges,
+            target,
+            roles,
+        }))
+    }
+
+    /// Parses a `REVOKE` statement, assuming that the `REVOKE` token has
+    /// already been consumed.
+    fn parse_revoke(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let privileges = self.parse_privilege_specification()?;
+        self.expect_keyword(ON)?;
+        let target = self.parse_grant_target()?;
+        self.expect_keyword(FROM)?;
+        let roles = self.parse_comma_separated(Parser::parse_identifier)?;
+        Ok(Statement::RevokePrivileges(RevokePrivilegesStatement {
+            privileges,
+            target,
+            roles,
+        }))
+    }
+
+    fn parse_privilege_specification(&mut self) -> Result<PrivilegeSpecification, ParserError> {
+        if self.parse_keyword(ALL) {
+            let _ = self.parse_keyword(PRIVILEGES);
+            return Ok(PrivilegeSpecification::All);
+        }
+        let privileges = self.parse_comma_separated(|parser| {
+            Ok(
+                match parser
+                    .expect_one_of_keywords(&[SELECT, INSERT, UPDATE, DELETE, CREATE, USAGE])?
+                {
+                    SELECT => Privilege::Select,
+                    INSERT => Privilege::Insert,
+                    UPDATE => Privilege::Update,
+                    DELETE => Privilege::Delete,
+                    CREATE => Privilege::Create,
+                    USAGE => Privilege::Usage,
+                    _ => unreachable!(),
+                },
+            )
+        })?;
+        Ok(PrivilegeSpecification::Privileges(privileges))
+    }
+
+    fn parse_grant_target(&mut self) -> Result<GrantTargetSpecification, ParserError> {
+        if self.parse_keyword(SCHEMA) {
+            Ok(GrantTargetSpecification::Schema(self.parse_schema_name()?))
+        } else {
+            let _ = self.parse_keyword(TABLE);
+            Ok(GrantTargetSpecification::Object(self.parse_object_name()?))
+        }
+    }
+
     fn parse_show(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
         if self.parse_keyword(DATABASES) {
             return Ok(ShowStatement::ShowDatabases(ShowDatabasesStatement {
@@ -4628,6 +5783,8 @@ impl<'a> Parser<'a> {
                 from,
                 filter: self.parse_show_statement_filter()?,
             }))
+        } else if self.parse_keyword(GRANTS) {
+            self.parse_show_grants()
         } else if let Some(object_type) = self.parse_one_of_keywords(&[
             OBJECTS,
             ROLES,
@@ -5265,8 +6422,18 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_subscribe_option(&mut self) -> Result<SubscribeOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[PROGRESS, SNAPSHOT])? {
-            PROGRESS => SubscribeOptionName::Progress,
+        let name = match self.expect_one_of_keywords(&[IDLE, PROGRESS, SNAPSHOT])? {
+            IDLE => {
+                self.expect_keyword(TIMEOUT)?;
+                SubscribeOptionName::IdleTimeout
+            }
+            PROGRESS => {
+                if self.parse_keyword(INTERVAL) {
+                    SubscribeOptionName::ProgressInterval
+                } else {
+                    SubscribeOptionName::Progress
+                }
+            }
             SNAPSHOT => SubscribeOptionName::Snapshot,
             _ => unreachable!(),
         };
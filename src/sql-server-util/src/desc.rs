@@ -0,0 +1,125 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Descriptions of SQL Server objects.
+
+use proptest::prelude::{any, Arbitrary};
+use proptest::strategy::{BoxedStrategy, Strategy};
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{RustType, TryFromProtoError};
+
+include!(concat!(env!("OUT_DIR"), "/mz_sql_server_util.desc.rs"));
+
+/// Describes a table in a SQL Server database whose changes are captured by
+/// change data capture (CDC).
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerTableDesc {
+    /// The name of the schema that the table belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The name of the CDC capture instance that records the changes to the
+    /// table.
+    pub capture_instance: String,
+    /// The description of each captured column, in order.
+    pub columns: Vec<SqlServerColumnDesc>,
+}
+
+impl RustType<ProtoSqlServerTableDesc> for SqlServerTableDesc {
+    fn into_proto(&self) -> ProtoSqlServerTableDesc {
+        ProtoSqlServerTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            capture_instance: self.capture_instance.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerTableDesc {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            capture_instance: proto.capture_instance,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(SqlServerColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Arbitrary for SqlServerTableDesc {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            any::<String>(),
+            any::<Vec<SqlServerColumnDesc>>(),
+        )
+            .prop_map(
+                |(schema_name, name, capture_instance, columns)| SqlServerTableDesc {
+                    schema_name,
+                    name,
+                    capture_instance,
+                    columns,
+                },
+            )
+            .boxed()
+    }
+}
+
+/// Describes a column in a [`SqlServerTableDesc`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The name of the column's system type, e.g. `nvarchar`. Columns of
+    /// user-defined alias types report the system type they are based on.
+    pub data_type: String,
+    /// True if the column lacks a `NOT NULL` constraint.
+    pub nullable: bool,
+}
+
+impl RustType<ProtoSqlServerColumnDesc> for SqlServerColumnDesc {
+    fn into_proto(&self) -> ProtoSqlServerColumnDesc {
+        ProtoSqlServerColumnDesc {
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            nullable: self.nullable,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerColumnDesc {
+            name: proto.name,
+            data_type: proto.data_type,
+            nullable: proto.nullable,
+        })
+    }
+}
+
+impl Arbitrary for SqlServerColumnDesc {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<String>(), any::<bool>())
+            .prop_map(|(name, data_type, nullable)| SqlServerColumnDesc {
+                name,
+                data_type,
+                nullable,
+            })
+            .boxed()
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server utility library.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::{anyhow, bail};
+use tiberius::{AuthMethod, EncryptionLevel};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use crate::desc::{SqlServerColumnDesc, SqlServerTableDesc};
+
+pub mod desc;
+mod lsn;
+
+pub use lsn::Lsn;
+
+/// A client connected to a SQL Server database.
+pub type Client = tiberius::Client<Compat<TcpStream>>;
+
+/// Whether to use TLS for encryption, authentication, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsMode {
+    /// Do not use TLS.
+    Disabled,
+    /// Encrypt the connection, without verifying the server's certificate.
+    Required,
+    /// Encrypt the connection and verify the server's certificate, including
+    /// that it was issued for the server's hostname.
+    Verify,
+}
+
+/// Configuration for SQL Server connections.
+#[derive(Clone)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub user: String,
+    pub password: Option<String>,
+    pub tls_mode: TlsMode,
+    /// An optional root TLS certificate in PEM format, to verify the server's
+    /// identity.
+    pub tls_root_cert: Option<String>,
+}
+
+// Omit the password from debug output.
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("database", &self.database)
+            .field("user", &self.user)
+            .field("tls_mode", &self.tls_mode)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Connects to the database, authenticating as a SQL Server login.
+    pub async fn connect(&self) -> Result<Client, anyhow::Error> {
+        let mut config = tiberius::Config::new();
+        config.host(&self.host);
+        config.port(self.port);
+        config.database(&self.database);
+        config.authentication(AuthMethod::sql_server(
+            &self.user,
+            self.password.as_deref().unwrap_or_default(),
+        ));
+
+        // The client only reads the root certificate from a file, and only
+        // while connecting, so the file does not outlive this function.
+        let mut root_cert_file = None;
+        match self.tls_mode {
+            TlsMode::Disabled => config.encryption(EncryptionLevel::NotSupported),
+            TlsMode::Required => {
+                config.encryption(EncryptionLevel::Required);
+                config.trust_cert();
+            }
+            TlsMode::Verify => {
+                config.encryption(EncryptionLevel::Required);
+                if let Some(tls_root_cert) = &self.tls_root_cert {
+                    let mut file = tempfile::NamedTempFile::new()?;
+                    file.write_all(tls_root_cert.as_bytes())?;
+                    config.trust_cert_ca(file.path().display().to_string());
+                    root_cert_file = Some(file);
+                }
+            }
+        }
+
+        let tcp = TcpStream::connect(config.get_addr()).await?;
+        tcp.set_nodelay(true)?;
+        let client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+        drop(root_cert_file);
+        Ok(client)
+    }
+}
+
+/// Verifies that change data capture (CDC) is enabled for the database that
+/// the client is connected to.
+pub async fn ensure_cdc_enabled(client: &mut Client) -> Result<(), anyhow::Error> {
+    let row = client
+        .simple_query("SELECT is_cdc_enabled FROM sys.databases WHERE database_id = DB_ID()")
+        .await?
+        .into_row()
+        .await?
+        .ok_or_else(|| anyhow!("database not found"))?;
+    if !row.try_get::<bool, _>(0)?.unwrap_or(false) {
+        bail!("change data capture is not enabled for the database");
+    }
+    Ok(())
+}
+
+/// Verifies that the database that the client is connected to allows
+/// transactions to use snapshot isolation.
+pub async fn ensure_snapshot_isolation_allowed(client: &mut Client) -> Result<(), anyhow::Error> {
+    let row = client
+        .simple_query(
+            "SELECT snapshot_isolation_state FROM sys.databases WHERE database_id = DB_ID()",
+        )
+        .await?
+        .into_row()
+        .await?
+        .ok_or_else(|| anyhow!("database not found"))?;
+    // The state is 1 when snapshot isolation is on, and 2 or 3 while it is
+    // being turned off or on.
+    if row.try_get::<u8, _>(0)? != Some(1) {
+        bail!("snapshot isolation is not allowed for the database; set ALLOW_SNAPSHOT_ISOLATION to ON");
+    }
+    Ok(())
+}
+
+/// Fetches the schema of every table in the database whose changes change
+/// data capture (CDC) records.
+///
+/// Only the columns that CDC captures are included. Tables with more than one
+/// capture instance are described by their newest instance.
+///
+/// # Errors
+///
+/// - Invalid connection details, user information, or user permissions.
+pub async fn schema_info(client: &mut Client) -> Result<Vec<SqlServerTableDesc>, anyhow::Error> {
+    let rows = client
+        .simple_query(
+            "SELECT
+                s.name, t.name, ct.capture_instance, c.name,
+                TYPE_NAME(c.system_type_id), c.is_nullable
+            FROM
+                cdc.change_tables AS ct
+                JOIN sys.tables AS t ON ct.source_object_id = t.object_id
+                JOIN sys.schemas AS s ON t.schema_id = s.schema_id
+                JOIN cdc.captured_columns AS cc ON cc.object_id = ct.object_id
+                JOIN sys.columns AS c ON
+                    c.object_id = t.object_id AND c.column_id = cc.column_id
+            ORDER BY s.name, t.name, ct.create_date DESC, ct.capture_instance, cc.column_ordinal",
+        )
+        .await?
+        .into_first_result()
+        .await?;
+
+    let mut tables: BTreeMap<(String, String), SqlServerTableDesc> = BTreeMap::new();
+    for row in rows {
+        let get = |i| -> Result<String, anyhow::Error> {
+            Ok(row
+                .try_get::<&str, _>(i)?
+                .ok_or_else(|| anyhow!("unexpected NULL in schema information"))?
+                .to_owned())
+        };
+        let schema_name = get(0)?;
+        let table_name = get(1)?;
+        let capture_instance = get(2)?;
+        let table = tables
+            .entry((schema_name.clone(), table_name.clone()))
+            .or_insert_with(|| SqlServerTableDesc {
+                schema_name,
+                name: table_name,
+                capture_instance: capture_instance.clone(),
+                columns: vec![],
+            });
+        // The rows of the newest capture instance come first.
+        if table.capture_instance != capture_instance {
+            continue;
+        }
+        table.columns.push(SqlServerColumnDesc {
+            name: get(3)?,
+            data_type: get(4)?,
+            nullable: row.try_get::<bool, _>(5)?.unwrap_or(true),
+        });
+    }
+    Ok(tables.into_values().collect())
+}
+
+/// Returns the LSN of the latest change that change data capture has
+/// recorded, if any.
+pub async fn max_lsn(client: &mut Client) -> Result<Option<Lsn>, anyhow::Error> {
+    let row = client
+        .simple_query("SELECT sys.fn_cdc_get_max_lsn()")
+        .await?
+        .into_row()
+        .await?;
+    match row.as_ref().and_then(|row| row.get::<&[u8], _>(0)) {
+        Some(bytes) => Ok(Some(Lsn::from_bytes(bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the LSN of the earliest change that the capture instance still
+/// records, as its changes are cleaned up after the retention period.
+pub async fn min_lsn(client: &mut Client, capture_instance: &str) -> Result<Lsn, anyhow::Error> {
+    let row = client
+        .query("SELECT sys.fn_cdc_get_min_lsn(@P1)", &[&capture_instance])
+        .await?
+        .into_row()
+        .await?;
+    match row.as_ref().and_then(|row| row.get::<&[u8], _>(0)) {
+        Some(bytes) => Lsn::from_bytes(bytes),
+        None => bail!("capture instance {capture_instance} not found"),
+    }
+}
+
+/// Returns the LSN of the end of the database's transaction log, which
+/// exceeds the LSN of every transaction that has committed.
+pub async fn log_end_lsn(client: &mut Client) -> Result<Lsn, anyhow::Error> {
+    let row = client
+        .simple_query("SELECT log_end_lsn FROM sys.dm_db_log_stats(DB_ID())")
+        .await?
+        .into_row()
+        .await?;
+    match row.as_ref().and_then(|row| row.get::<&str, _>(0)) {
+        Some(lsn) => lsn.parse(),
+        None => bail!("missing end of transaction log"),
+    }
+}
+
+/// Quotes an identifier for use in a query.
+pub fn quote_identifier(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
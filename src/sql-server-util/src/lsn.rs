@@ -0,0 +1,168 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server log sequence numbers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+
+/// The number of bits of an [`Lsn`] offset that hold the virtual log file
+/// sequence number, the block offset, and the slot number, respectively.
+const VLF_BITS: u32 = 24;
+const BLOCK_BITS: u32 = 28;
+const SLOT_BITS: u32 = 12;
+
+/// A log sequence number (LSN), which identifies a record in the transaction
+/// log of a SQL Server database.
+///
+/// An LSN consists of the sequence number of a virtual log file, the offset of
+/// a log block within that file, and the number of a record within that block.
+/// LSNs order the records of the log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lsn {
+    pub vlf: u32,
+    pub block: u32,
+    pub slot: u16,
+}
+
+impl Lsn {
+    /// Decodes an LSN from the `binary(10)` form in which SQL Server stores
+    /// LSNs, e.g. in change tables.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Lsn, anyhow::Error> {
+        let bytes: &[u8; 10] = bytes
+            .try_into()
+            .map_err(|_| anyhow!("LSN must be 10 bytes long, got {}", bytes.len()))?;
+        Ok(Lsn {
+            vlf: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            block: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            slot: u16::from_be_bytes([bytes[8], bytes[9]]),
+        })
+    }
+
+    /// Encodes the LSN in the `binary(10)` form in which SQL Server stores
+    /// LSNs.
+    pub fn to_bytes(&self) -> [u8; 10] {
+        let mut bytes = [0; 10];
+        bytes[..4].copy_from_slice(&self.vlf.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.block.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.slot.to_be_bytes());
+        bytes
+    }
+
+    /// Returns the LSN as a `binary(10)` literal, for use in queries.
+    pub fn to_sql_literal(&self) -> String {
+        let mut literal = String::from("0x");
+        for byte in self.to_bytes() {
+            literal.push_str(&format!("{byte:02X}"));
+        }
+        literal
+    }
+
+    /// Packs the LSN into a `u64` that orders LSNs the same way.
+    ///
+    /// An LSN has 80 bits, so this fails for LSNs whose parts exceed the
+    /// widths that fit into 64 bits: 24 bits of virtual log file sequence
+    /// number, 28 bits of block offset, and 12 bits of slot number.
+    pub fn to_offset(&self) -> Result<u64, anyhow::Error> {
+        if self.vlf >> VLF_BITS != 0
+            || self.block >> BLOCK_BITS != 0
+            || u32::from(self.slot) >> SLOT_BITS != 0
+        {
+            bail!("LSN {self} exceeds the range that sources support");
+        }
+        Ok(u64::from(self.vlf) << (BLOCK_BITS + SLOT_BITS)
+            | u64::from(self.block) << SLOT_BITS
+            | u64::from(self.slot))
+    }
+
+    /// Unpacks an offset that [`Lsn::to_offset`] produced, or an offset that
+    /// lies between two such offsets.
+    pub fn from_offset(offset: u64) -> Lsn {
+        let mask = |bits: u32| (1 << bits) - 1;
+        Lsn {
+            vlf: u32::try_from(offset >> (BLOCK_BITS + SLOT_BITS)).expect("24 bits fit into u32"),
+            block: u32::try_from((offset >> SLOT_BITS) & mask(BLOCK_BITS))
+                .expect("28 bits fit into u32"),
+            slot: u16::try_from(offset & mask(SLOT_BITS)).expect("12 bits fit into u16"),
+        }
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = anyhow::Error;
+
+    /// Parses an LSN in the textual forms that SQL Server's dynamic management
+    /// views use: either hexadecimal parts of 8, 8 and 4 digits, like
+    /// `0000002a:00000130:0003`, or decimal parts, like `42:304:3`.
+    fn from_str(s: &str) -> Result<Lsn, anyhow::Error> {
+        let parts: Vec<_> = s.trim().split(':').collect();
+        let (vlf, block, slot) = match parts.as_slice() {
+            [vlf, block, slot] => (*vlf, *block, *slot),
+            _ => bail!("invalid LSN {s:?}"),
+        };
+        let radix = if (vlf.len(), block.len(), slot.len()) == (8, 8, 4) {
+            16
+        } else {
+            10
+        };
+        let invalid = |_| anyhow!("invalid LSN {s:?}");
+        Ok(Lsn {
+            vlf: u32::from_str_radix(vlf, radix).map_err(invalid)?,
+            block: u32::from_str_radix(block, radix).map_err(invalid)?,
+            slot: u16::from_str_radix(slot, radix).map_err(invalid)?,
+        })
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}:{:08x}:{:04x}", self.vlf, self.block, self.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lsn() {
+        let lsn: Lsn = "0000002a:00000130:0003".parse().unwrap();
+        assert_eq!(
+            lsn,
+            Lsn {
+                vlf: 42,
+                block: 304,
+                slot: 3
+            }
+        );
+        assert_eq!("42:304:3".parse::<Lsn>().unwrap(), lsn);
+        assert_eq!(lsn.to_string(), "0000002a:00000130:0003");
+        assert_eq!(Lsn::from_bytes(&lsn.to_bytes()).unwrap(), lsn);
+        assert_eq!(lsn.to_sql_literal(), "0x0000002A000001300003");
+
+        let offset = lsn.to_offset().unwrap();
+        assert_eq!(Lsn::from_offset(offset), lsn);
+        let next: Lsn = "0000002a:00000131:0001".parse().unwrap();
+        assert!(offset < next.to_offset().unwrap());
+        assert!(Lsn::from_offset(offset + 1) > lsn);
+        assert!(Lsn::from_offset(offset + 1) <= next);
+
+        assert!(Lsn {
+            vlf: 1 << 24,
+            block: 0,
+            slot: 0
+        }
+        .to_offset()
+        .is_err());
+        assert!("0000002a:00000130".parse::<Lsn>().is_err());
+        assert!(Lsn::from_bytes(&[0; 9]).is_err());
+    }
+}
@@ -326,6 +326,10 @@ pub trait CatalogItem {
     /// Returns the type of the catalog item.
     fn item_type(&self) -> CatalogItemType;
 
+    /// Reports whether the catalog item is a source that receives its data
+    /// from requests to its webhook endpoint.
+    fn is_webhook_source(&self) -> bool;
+
     /// A normalized SQL statement that describes how to create the catalog
     /// item.
     fn create_sql(&self) -> &str;
@@ -395,6 +399,70 @@ impl fmt::Display for CatalogItemType {
     }
 }
 
+/// A privilege that can be granted to a role on a catalog object.
+#[derive(Debug, Deserialize, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Privilege {
+    /// Read the contents of a relation.
+    Select,
+    /// Insert rows into a table.
+    Insert,
+    /// Update rows of a table.
+    Update,
+    /// Delete rows from a table.
+    Delete,
+    /// Create objects in a schema.
+    Create,
+    /// Use a secret or connection in other objects.
+    Usage,
+}
+
+impl Privilege {
+    /// Returns the privileges that can be granted on an item of the specified
+    /// type.
+    pub fn for_item_type(item_type: CatalogItemType) -> &'static [Privilege] {
+        match item_type {
+            CatalogItemType::Table => &[
+                Privilege::Select,
+                Privilege::Insert,
+                Privilege::Update,
+                Privilege::Delete,
+            ],
+            CatalogItemType::Source | CatalogItemType::View | CatalogItemType::MaterializedView => {
+                &[Privilege::Select]
+            }
+            CatalogItemType::Secret | CatalogItemType::Connection => &[Privilege::Usage],
+            CatalogItemType::Sink
+            | CatalogItemType::Index
+            | CatalogItemType::Type
+            | CatalogItemType::Func => &[],
+        }
+    }
+
+    /// Returns the privileges that can be granted on a webhook source, which
+    /// requests to its endpoint insert rows into.
+    pub fn for_webhook_source() -> &'static [Privilege] {
+        &[Privilege::Select, Privilege::Insert]
+    }
+
+    /// Returns the privileges that can be granted on a schema.
+    pub fn for_schema() -> &'static [Privilege] {
+        &[Privilege::Create]
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Privilege::Select => f.write_str("SELECT"),
+            Privilege::Insert => f.write_str("INSERT"),
+            Privilege::Update => f.write_str("UPDATE"),
+            Privilege::Delete => f.write_str("DELETE"),
+            Privilege::Create => f.write_str("CREATE"),
+            Privilege::Usage => f.write_str("USAGE"),
+        }
+    }
+}
+
 /// Details about a type in the catalog.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CatalogTypeDetails<T: TypeReference> {
@@ -10,24 +10,40 @@
 //! Provides parsing and convenience functions for working with Kafka from the `sql` package.
 
 use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 
 use anyhow::bail;
-use rdkafka::client::ClientContext;
+use openssl::pkey::PKey;
+use openssl::ssl::{SslConnector, SslMethod};
+use openssl::x509::{X509VerifyResult, X509};
+use rdkafka::client::{BrokerAddr, ClientContext, OAuthToken};
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::{Offset, TopicPartitionList};
-use tokio::time::Duration;
+use tokio::net::{self, TcpStream};
+use tokio::time::{self, Duration};
+use tokio_openssl::SslStream;
 
 use mz_kafka_util::client::{create_new_client_config, MzClientContext};
+use mz_kafka_util::KafkaBrokerAddrs;
 use mz_ore::task;
+use mz_repr::adt::interval::Interval;
 use mz_secrets::SecretsReader;
 use mz_sql_parser::ast::display::AstDisplay;
-use mz_sql_parser::ast::{AstInfo, KafkaConfigOption, KafkaConfigOptionName};
-use mz_storage::types::connections::{KafkaConnection, StringOrSecret};
+use mz_sql_parser::ast::{
+    AstInfo, Expr, Ident, KafkaConfigOption, KafkaConfigOptionName, Value, WithOptionValue,
+};
+use mz_storage::types::connections::{
+    KafkaConnection, KafkaSecurity, KafkaTlsConfig, OAuthBearerConfig, SaslConfig, StringOrSecret,
+    TlsIdentity,
+};
+use mz_storage::types::sinks::{KafkaSinkCompressionType, KafkaSinkRetryExhaustedAction};
 
 use crate::names::Aug;
 use crate::normalize::generate_extracted_config;
-use crate::plan::with_options::TryFromValue;
+use crate::plan::with_options::{ImpliedValue, TryFromValue};
 use crate::plan::PlanError;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -47,21 +63,37 @@ pub fn validate_options_for_context<T: AstInfo>(
     for KafkaConfigOption { name, .. } in options {
         let limited_to_context = match name {
             Acks => None,
+            BatchNumMessages => Some(Sink),
             ClientId => None,
+            CommitInterval => Some(Source),
+            CompressionType => Some(Sink),
+            DeadLetterTopic => Some(Sink),
             EnableIdempotence => None,
             FetchMessageMaxBytes => None,
             GroupIdPrefix => None,
+            Headers => Some(Sink),
             IsolationLevel => None,
+            LingerMs => Some(Sink),
+            MessageTimestamp => Some(Sink),
             StatisticsIntervalMs => None,
             Topic => None,
+            TopicColumn => Some(Sink),
+            TopicConfig => Some(Sink),
             TopicMetadataRefreshIntervalMs => None,
+            TopicPattern => Some(Source),
+            TopicTemplate => Some(Sink),
             TransactionTimeoutMs => None,
             StartTimestamp => Some(Source),
             StartOffset => Some(Source),
+            PartitionBy => Some(Sink),
             PartitionCount => Some(Sink),
             ReplicationFactor => Some(Sink),
             RetentionBytes => Some(Sink),
             RetentionMs => Some(Sink),
+            RetryMaxAttempts => Some(Sink),
+            RetryBackoff => Some(Sink),
+            RetryMaxBackoff => Some(Sink),
+            RetryExhaustedAction => Some(Sink),
         };
         if limited_to_context.is_some() && limited_to_context != Some(context) {
             bail!(
@@ -81,27 +113,206 @@ pub fn validate_options_for_context<T: AstInfo>(
 generate_extracted_config!(
     KafkaConfigOption,
     (Acks, String),
+    (BatchNumMessages, i32),
     (ClientId, String),
+    (CommitInterval, Interval),
+    (
+        CompressionType,
+        KafkaSinkCompressionType,
+        Default(KafkaSinkCompressionType::None)
+    ),
+    (DeadLetterTopic, String),
     (EnableIdempotence, bool),
     (FetchMessageMaxBytes, i32),
     (GroupIdPrefix, String),
+    (Headers, Vec<KafkaHeaderColumn>, Default(vec![])),
     (
         IsolationLevel,
         String,
         Default(String::from("read_committed"))
     ),
+    (LingerMs, i32),
+    (MessageTimestamp, KafkaMessageTimestamp),
     (StatisticsIntervalMs, i32, Default(1_000)),
     (Topic, String),
+    (TopicColumn, Ident),
+    (TopicConfig, Vec<KafkaTopicConfigEntry>, Default(vec![])),
     (TopicMetadataRefreshIntervalMs, i32),
+    (TopicPattern, String),
+    (TopicTemplate, String),
     (TransactionTimeoutMs, i32),
     (StartTimestamp, i64),
     (StartOffset, Vec<i64>),
+    (PartitionBy, Expr<Aug>),
     (PartitionCount, i32, Default(-1)),
     (ReplicationFactor, i32, Default(-1)),
     (RetentionBytes, i64),
-    (RetentionMs, i64)
+    (RetentionMs, i64),
+    (RetryMaxAttempts, u64),
+    (RetryBackoff, Interval),
+    (RetryMaxBackoff, Interval),
+    (RetryExhaustedAction, KafkaSinkRetryExhaustedAction)
 );
 
+/// A column whose values a Kafka sink attaches to its messages as a header,
+/// as specified by the `HEADERS` option.
+///
+/// Each element of `HEADERS` is either a column name, in which case the header
+/// is named after the column, or a `(column, 'header key')` pair.
+#[derive(Debug, Clone)]
+pub struct KafkaHeaderColumn {
+    pub column: Ident,
+    pub key: Option<String>,
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for KafkaHeaderColumn {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Ident(column) => Ok(KafkaHeaderColumn { column, key: None }),
+            WithOptionValue::Sequence(pair) => match <[_; 2]>::try_from(pair) {
+                Ok(
+                    [WithOptionValue::Ident(column), WithOptionValue::Value(Value::String(key))],
+                ) => Ok(KafkaHeaderColumn {
+                    column,
+                    key: Some(key),
+                }),
+                _ => sql_bail!("HEADERS pairs must be of the form (column, 'header key')"),
+            },
+            _ => sql_bail!("HEADERS must be a list of columns or (column, 'header key') pairs"),
+        }
+    }
+    fn name() -> String {
+        "header column".to_string()
+    }
+}
+
+impl ImpliedValue for Vec<KafkaHeaderColumn> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a list of header columns")
+    }
+}
+
+/// A topic-level configuration with which a Kafka sink creates its topic, as
+/// specified by the `TOPIC CONFIG` option.
+///
+/// Each element of `TOPIC CONFIG` is a `('config name', value)` pair, whose
+/// value is either a string or a number.
+#[derive(Debug, Clone)]
+pub struct KafkaTopicConfigEntry {
+    pub name: String,
+    pub value: String,
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for KafkaTopicConfigEntry {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Sequence(pair) => match <[_; 2]>::try_from(pair) {
+                Ok(
+                    [WithOptionValue::Value(Value::String(name)), WithOptionValue::Value(value)],
+                ) => {
+                    let value = match value {
+                        Value::String(value) | Value::Number(value) => value,
+                        _ => sql_bail!(
+                            "TOPIC CONFIG value of {} must be a string or a number",
+                            name
+                        ),
+                    };
+                    Ok(KafkaTopicConfigEntry { name, value })
+                }
+                _ => sql_bail!("TOPIC CONFIG pairs must be of the form ('config name', value)"),
+            },
+            _ => sql_bail!("TOPIC CONFIG must be a list of ('config name', value) pairs"),
+        }
+    }
+    fn name() -> String {
+        "topic config".to_string()
+    }
+}
+
+impl ImpliedValue for Vec<KafkaTopicConfigEntry> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a list of topic configs")
+    }
+}
+
+/// The source of the timestamps of the messages that a Kafka sink produces, as
+/// specified by the `MESSAGE TIMESTAMP` option.
+///
+/// `MESSAGE TIMESTAMP = column` takes the timestamps from a column, while
+/// `MESSAGE TIMESTAMP` without a value uses the timestamps of the updates.
+#[derive(Debug, Clone)]
+pub enum KafkaMessageTimestamp {
+    MzTimestamp,
+    Column(Ident),
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for KafkaMessageTimestamp {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Ident(column) => Ok(KafkaMessageTimestamp::Column(column)),
+            _ => sql_bail!("MESSAGE TIMESTAMP must be a column name"),
+        }
+    }
+    fn name() -> String {
+        "message timestamp column".to_string()
+    }
+}
+
+impl ImpliedValue for KafkaMessageTimestamp {
+    fn implied_value() -> Result<Self, PlanError> {
+        Ok(KafkaMessageTimestamp::MzTimestamp)
+    }
+}
+
+impl TryFromValue<Value> for KafkaSinkCompressionType {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let codec = String::try_from_value(v)?;
+        Ok(match codec.to_lowercase().as_str() {
+            "none" => KafkaSinkCompressionType::None,
+            "gzip" => KafkaSinkCompressionType::Gzip,
+            "snappy" => KafkaSinkCompressionType::Snappy,
+            "lz4" => KafkaSinkCompressionType::Lz4,
+            "zstd" => KafkaSinkCompressionType::Zstd,
+            _ => sql_bail!(
+                "invalid COMPRESSION TYPE '{}': must be one of none, gzip, snappy, lz4, or zstd",
+                codec
+            ),
+        })
+    }
+    fn name() -> String {
+        "compression type".to_string()
+    }
+}
+
+impl ImpliedValue for KafkaSinkCompressionType {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a compression type")
+    }
+}
+
+impl TryFromValue<Value> for KafkaSinkRetryExhaustedAction {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let action = String::try_from_value(v)?;
+        Ok(match action.to_lowercase().as_str() {
+            "fail" => KafkaSinkRetryExhaustedAction::Fail,
+            "dead letter" => KafkaSinkRetryExhaustedAction::DeadLetter,
+            _ => sql_bail!(
+                "invalid RETRY EXHAUSTED ACTION '{}': must be one of fail or dead letter",
+                action
+            ),
+        })
+    }
+    fn name() -> String {
+        "retry exhausted action".to_string()
+    }
+}
+
+impl ImpliedValue for KafkaSinkRetryExhaustedAction {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a retry exhausted action")
+    }
+}
+
 /// The config options we expect to pass along when connecting to librdkafka
 #[derive(Debug)]
 pub struct LibRdKafkaConfig(pub BTreeMap<String, StringOrSecret>);
@@ -218,7 +429,7 @@ impl TryFrom<&KafkaConfigOptionExtracted> for Option<KafkaStartOffsetType> {
 ///
 /// - `librdkafka` cannot create a BaseConsumer using the provided `options`.
 pub async fn create_consumer(
-    topic: &str,
+    topic: Option<&str>,
     kafka_connection: &KafkaConnection,
     options: &BTreeMap<String, StringOrSecret>,
     librdkafka_log_level: tracing::Level,
@@ -240,24 +451,32 @@ pub async fn create_consumer(
         .get("bootstrap.servers")
         .expect("callers must have already set bootstrap.servers");
 
+    let client_context = kafka_connection
+        .client_context(secrets_reader, None)
+        .await
+        .map_err(|e| sql_err!("{}", e))?;
     let consumer: Arc<BaseConsumer<KafkaErrCheckContext>> = Arc::new(
         config
-            .create_with_context(KafkaErrCheckContext::default())
+            .create_with_context(KafkaErrCheckContext::new(client_context))
             .map_err(|e| sql_err!("{}", e))?,
     );
     let context = Arc::clone(consumer.context());
-    let owned_topic = String::from(topic);
+    let owned_topic = topic.map(String::from);
     // Wait for a metadata request for up to one second. This greatly
     // increases the probability that we'll see a connection error if
     // e.g. the hostname was mistyped. librdkafka doesn't expose a
     // better API for asking whether a connection succeeded or failed,
     // unfortunately.
-    task::spawn_blocking(move || format!("kafka_set_metadata:{broker}:{topic}"), {
-        let consumer = Arc::clone(&consumer);
-        move || {
-            let _ = consumer.fetch_metadata(Some(&owned_topic), Duration::from_secs(1));
-        }
-    })
+    let task_topic = topic.unwrap_or("*").to_string();
+    task::spawn_blocking(
+        move || format!("kafka_set_metadata:{broker}:{task_topic}"),
+        {
+            let consumer = Arc::clone(&consumer);
+            move || {
+                let _ = consumer.fetch_metadata(owned_topic.as_deref(), Duration::from_secs(1));
+            }
+        },
+    )
     .await
     .map_err(|e| sql_err!("{}", e))?;
     let error = context.error.lock().expect("lock poisoned");
@@ -267,6 +486,281 @@ pub async fn create_consumer(
     Ok(consumer)
 }
 
+/// How long each stage of [`preflight`] may take.
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A stage of connecting to a Kafka broker, as checked by [`preflight`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PreflightStage {
+    DnsResolution,
+    TcpConnect,
+    TlsHandshake,
+    SaslAuthentication,
+}
+
+impl fmt::Display for PreflightStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PreflightStage::DnsResolution => "DNS resolution",
+            PreflightStage::TcpConnect => "TCP connect",
+            PreflightStage::TlsHandshake => "TLS handshake",
+            PreflightStage::SaslAuthentication => "SASL authentication",
+        })
+    }
+}
+
+/// Describes the stage at which [`preflight`] failed to connect to a broker.
+#[derive(Debug, Clone)]
+pub struct PreflightError {
+    /// The broker, or brokers, that could not be connected to.
+    pub broker: String,
+    pub stage: PreflightStage,
+    pub detail: String,
+}
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "failed to connect to Kafka broker {}: {} failed: {}",
+            self.broker, self.stage, self.detail
+        )
+    }
+}
+
+impl Error for PreflightError {}
+
+/// Checks that every broker of `kafka_connection` can be connected to, stage
+/// by stage, so that failures are reported precisely rather than as
+/// librdkafka's generic transport failures.
+///
+/// The address of each broker is resolved and connected to over TCP and, if
+/// the connection uses TLS, a TLS handshake is performed with the configured
+/// certificates. If the connection uses SASL, the credentials are then
+/// checked by fetching metadata from the cluster.
+///
+/// Brokers that are reached through an SSH tunnel or an AWS PrivateLink VPC
+/// endpoint are not dialed directly, as their addresses are usually only
+/// reachable from the bastion host or the endpoint service. The SASL check
+/// still applies, as its client connects through the tunnel or endpoint.
+pub async fn preflight(
+    kafka_connection: &KafkaConnection,
+    librdkafka_log_level: tracing::Level,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(), PreflightError> {
+    let all_brokers = kafka_connection.brokers.join(",");
+    let tls = match &kafka_connection.security {
+        Some(KafkaSecurity::Tls(KafkaTlsConfig {
+            root_cert,
+            identity,
+        })) => Some(tls_connector(root_cert.as_ref(), identity.as_ref(), secrets_reader).await),
+        Some(KafkaSecurity::Sasl(SaslConfig { tls_root_cert, .. }))
+        | Some(KafkaSecurity::OAuthBearer(OAuthBearerConfig { tls_root_cert, .. })) => {
+            Some(tls_connector(tls_root_cert.as_ref(), None, secrets_reader).await)
+        }
+        None => None,
+    };
+    let tls = tls.transpose().map_err(|e| PreflightError {
+        broker: all_brokers.clone(),
+        stage: PreflightStage::TlsHandshake,
+        detail: format!("invalid TLS configuration: {:#}", e),
+    })?;
+
+    if kafka_connection.ssh_tunnel.is_none() && kafka_connection.aws_privatelink.is_none() {
+        dial_brokers(&kafka_connection.brokers, tls.as_ref()).await?;
+    }
+
+    if let Some(KafkaSecurity::Sasl(_) | KafkaSecurity::OAuthBearer(_)) = &kafka_connection.security
+    {
+        check_sasl_authentication(kafka_connection, librdkafka_log_level, secrets_reader)
+            .await
+            .map_err(|detail| PreflightError {
+                broker: all_brokers,
+                stage: PreflightStage::SaslAuthentication,
+                detail,
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Connects to each of `brokers` over TCP and, if `tls` is specified,
+/// performs a TLS handshake with it.
+async fn dial_brokers(
+    brokers: &[String],
+    tls: Option<&SslConnector>,
+) -> Result<(), PreflightError> {
+    for broker in brokers {
+        let fail = |broker: &str, stage, detail| PreflightError {
+            broker: broker.to_owned(),
+            stage,
+            detail,
+        };
+        let addrs = match broker.parse::<KafkaBrokerAddrs>() {
+            Ok(addrs) => addrs,
+            Err(e) => return Err(fail(broker, PreflightStage::DnsResolution, e.to_string())),
+        };
+        let addrs = match time::timeout(PREFLIGHT_TIMEOUT, addrs.resolve()).await {
+            Ok(Ok(addrs)) => addrs,
+            Ok(Err(e)) => {
+                return Err(fail(
+                    broker,
+                    PreflightStage::DnsResolution,
+                    format!("{:#}", e),
+                ))
+            }
+            Err(_) => {
+                return Err(fail(
+                    broker,
+                    PreflightStage::DnsResolution,
+                    "timed out".into(),
+                ))
+            }
+        };
+        for (host, port) in addrs.iter() {
+            let addr = format!("{}:{}", host, port);
+            let socket_addrs: Vec<_> =
+                match time::timeout(PREFLIGHT_TIMEOUT, net::lookup_host((host, port))).await {
+                    Ok(Ok(socket_addrs)) => socket_addrs.collect(),
+                    Ok(Err(e)) => {
+                        return Err(fail(&addr, PreflightStage::DnsResolution, e.to_string()))
+                    }
+                    Err(_) => {
+                        return Err(fail(
+                            &addr,
+                            PreflightStage::DnsResolution,
+                            "timed out".into(),
+                        ))
+                    }
+                };
+            if socket_addrs.is_empty() {
+                return Err(fail(
+                    &addr,
+                    PreflightStage::DnsResolution,
+                    format!("{} did not resolve to any addresses", host),
+                ));
+            }
+
+            let mut stream = None;
+            let mut errors = vec![];
+            for socket_addr in socket_addrs {
+                match time::timeout(PREFLIGHT_TIMEOUT, TcpStream::connect(socket_addr)).await {
+                    Ok(Ok(s)) => {
+                        stream = Some(s);
+                        break;
+                    }
+                    Ok(Err(e)) => errors.push(format!("{}: {}", socket_addr, e)),
+                    Err(_) => errors.push(format!("{}: timed out", socket_addr)),
+                }
+            }
+            let stream = match stream {
+                Some(stream) => stream,
+                None => return Err(fail(&addr, PreflightStage::TcpConnect, errors.join("; "))),
+            };
+
+            if let Some(tls) = tls {
+                tls_handshake(tls, host, stream)
+                    .await
+                    .map_err(|detail| fail(&addr, PreflightStage::TlsHandshake, detail))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Builds a TLS connector that trusts `root_cert`, if specified, or the
+/// system's certificate authorities otherwise, and presents `identity`, if
+/// specified.
+async fn tls_connector(
+    root_cert: Option<&StringOrSecret>,
+    identity: Option<&TlsIdentity>,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<SslConnector, anyhow::Error> {
+    let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+    if let Some(root_cert) = root_cert {
+        let root_cert = root_cert.get_string(secrets_reader).await?;
+        for cert in X509::stack_from_pem(root_cert.as_bytes())? {
+            builder.cert_store_mut().add_cert(cert)?;
+        }
+    }
+    if let Some(TlsIdentity { cert, key }) = identity {
+        let cert = cert.get_string(secrets_reader).await?;
+        let key = secrets_reader.read_string(*key).await?;
+        builder.set_certificate(&*X509::from_pem(cert.as_bytes())?)?;
+        builder.set_private_key(&*PKey::private_key_from_pem(key.as_bytes())?)?;
+    }
+    Ok(builder.build())
+}
+
+/// Performs a TLS handshake with `host` over `stream`, describing why the
+/// handshake failed, if it does.
+async fn tls_handshake(
+    connector: &SslConnector,
+    host: &str,
+    stream: TcpStream,
+) -> Result<(), String> {
+    let ssl = connector
+        .configure()
+        .and_then(|config| config.into_ssl(host))
+        .map_err(|e| e.to_string())?;
+    let mut stream = SslStream::new(ssl, stream).map_err(|e| e.to_string())?;
+    match time::timeout(PREFLIGHT_TIMEOUT, Pin::new(&mut stream).connect()).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => {
+            let verify_result = stream.ssl().verify_result();
+            if verify_result == X509VerifyResult::OK {
+                Err(e.to_string())
+            } else {
+                Err(format!(
+                    "certificate verification failed: {}",
+                    verify_result.error_string()
+                ))
+            }
+        }
+        Err(_) => Err("timed out".into()),
+    }
+}
+
+/// Fetches metadata from the cluster with the credentials of
+/// `kafka_connection`, describing why authentication failed, if it does.
+async fn check_sasl_authentication(
+    kafka_connection: &KafkaConnection,
+    librdkafka_log_level: tracing::Level,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(), String> {
+    let mut config = create_new_client_config(librdkafka_log_level);
+    mz_storage::types::connections::populate_client_config(
+        kafka_connection.clone(),
+        &BTreeMap::new(),
+        HashSet::new(),
+        &mut config,
+        secrets_reader,
+    )
+    .await;
+    let client_context = kafka_connection
+        .client_context(secrets_reader, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let consumer: Arc<BaseConsumer<KafkaErrCheckContext>> = Arc::new(
+        config
+            .create_with_context(KafkaErrCheckContext::new(client_context))
+            .map_err(|e| e.to_string())?,
+    );
+    let context = Arc::clone(consumer.context());
+    let result = task::spawn_blocking(|| "kafka_preflight_sasl".to_string(), {
+        let consumer = Arc::clone(&consumer);
+        move || consumer.fetch_metadata(None, PREFLIGHT_TIMEOUT).map(|_| ())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let error = context.error.lock().expect("lock poisoned");
+    match (&*error, result) {
+        (Some(error), _) => Err(error.clone()),
+        (None, Err(e)) => Err(e.to_string()),
+        (None, Ok(())) => Ok(()),
+    }
+}
+
 /// Returns start offsets for the partitions of `topic` and the provided
 /// `START TIMESTAMP` option.
 ///
@@ -377,11 +871,25 @@ fn fetch_end_offset(
 #[derive(Default, Debug)]
 pub struct KafkaErrCheckContext {
     pub error: Mutex<Option<String>>,
+    client_context: MzClientContext,
+}
+
+impl KafkaErrCheckContext {
+    /// Constructs a context that forwards to `client_context`, e.g. to
+    /// generate `OAUTHBEARER` tokens.
+    pub fn new(client_context: MzClientContext) -> Self {
+        KafkaErrCheckContext {
+            error: Mutex::new(None),
+            client_context,
+        }
+    }
 }
 
 impl ConsumerContext for KafkaErrCheckContext {}
 
 impl ClientContext for KafkaErrCheckContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
     // `librdkafka` doesn't seem to propagate all errors up the stack, but does
     // log them, so we are currently relying on the `log` callback for error
     // handling in some situations.
@@ -397,13 +905,22 @@ impl ClientContext for KafkaErrCheckContext {
                 *error = Some(log_message.to_string());
             }
         }
-        MzClientContext.log(level, fac, log_message)
+        self.client_context.log(level, fac, log_message)
     }
     // Refer to the comment on the `log` callback.
     fn error(&self, error: rdkafka::error::KafkaError, reason: &str) {
         // Allow error to overwrite value irrespective of other conditions
         // (i.e. logging).
         *self.error.lock().expect("lock poisoned") = Some(reason.to_string());
-        MzClientContext.error(error, reason)
+        self.client_context.error(error, reason)
+    }
+    fn generate_oauth_token(
+        &self,
+        oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn Error>> {
+        self.client_context.generate_oauth_token(oauthbearer_config)
+    }
+    fn rewrite_broker_addr(&self, addr: BrokerAddr) -> BrokerAddr {
+        self.client_context.rewrite_broker_addr(addr)
     }
 }
@@ -383,6 +383,7 @@ pub fn create_statement(
             name,
             columns: _,
             in_cluster: _,
+            with_options: _,
             query,
         }) => {
             *name = allocate_name(name)?;
@@ -39,21 +39,26 @@ use enum_kinds::EnumKind;
 use serde::{Deserialize, Serialize};
 
 use mz_compute_client::controller::ComputeInstanceId;
+use mz_compute_client::sinks::PersistSinkBatching;
 use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
-use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
-use mz_storage::types::sinks::{SinkEnvelope, StorageSinkConnectionBuilder};
+use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_storage::types::sinks::{
+    KafkaSinkCompressionType, SinkColumnMask, SinkEnvelope, SinkRateLimit,
+    StorageSinkConnectionBuilder,
+};
 use mz_storage::types::sources::{SourceDesc, Timeline};
 
 use crate::ast::{
     ExplainStage, Expr, FetchDirection, IndexOptionName, NoticeSeverity, ObjectType, Raw,
     SetVariableValue, Statement, StatementKind, TransactionAccessMode,
 };
-use crate::catalog::{CatalogType, IdReference};
+use crate::catalog::{CatalogType, IdReference, Privilege};
 use crate::names::{
-    Aug, DatabaseId, FullObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier, SchemaId,
+    Aug, DatabaseId, FullObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId,
+    SchemaId,
 };
 
 pub(crate) mod error;
@@ -126,7 +131,9 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterSink(AlterSinkPlan),
     AlterSource(AlterSourcePlan),
+    AlterSourceRefreshSchema(AlterSourceRefreshSchemaPlan),
     AlterItemRename(AlterItemRenamePlan),
+    AlterOwner(AlterOwnerPlan),
     AlterSecret(AlterSecretPlan),
     AlterSystemSet(AlterSystemSetPlan),
     AlterSystemReset(AlterSystemResetPlan),
@@ -134,6 +141,8 @@ pub enum Plan {
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
+    GrantPrivileges(GrantPrivilegesPlan),
+    RevokePrivileges(RevokePrivilegesPlan),
     ReadThenWrite(ReadThenWritePlan),
     Prepare(PreparePlan),
     Execute(ExecutePlan),
@@ -156,9 +165,14 @@ impl Plan {
             StatementKind::AlterObjectRename => {
                 vec![PlanKind::AlterItemRename, PlanKind::AlterNoop]
             }
+            StatementKind::AlterOwner => vec![PlanKind::AlterNoop, PlanKind::AlterOwner],
             StatementKind::AlterSecret => vec![PlanKind::AlterNoop, PlanKind::AlterSecret],
             StatementKind::AlterSink => vec![PlanKind::AlterNoop, PlanKind::AlterSink],
-            StatementKind::AlterSource => vec![PlanKind::AlterNoop, PlanKind::AlterSource],
+            StatementKind::AlterSource => vec![
+                PlanKind::AlterNoop,
+                PlanKind::AlterSource,
+                PlanKind::AlterSourceRefreshSchema,
+            ],
             StatementKind::AlterSystemReset => {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSystemReset]
             }
@@ -203,10 +217,12 @@ impl Plan {
             StatementKind::Execute => vec![PlanKind::Execute],
             StatementKind::Explain => vec![PlanKind::Explain],
             StatementKind::Fetch => vec![PlanKind::Fetch],
+            StatementKind::GrantPrivileges => vec![PlanKind::GrantPrivileges],
             StatementKind::Insert => vec![PlanKind::Insert],
             StatementKind::Prepare => vec![PlanKind::Prepare],
             StatementKind::Raise => vec![PlanKind::Raise],
             StatementKind::ResetVariable => vec![PlanKind::ResetVariable],
+            StatementKind::RevokePrivileges => vec![PlanKind::RevokePrivileges],
             StatementKind::Rollback => vec![PlanKind::AbortTransaction],
             StatementKind::Select => vec![PlanKind::Peek],
             StatementKind::SetTransaction => vec![],
@@ -246,6 +262,7 @@ pub struct CreateSchemaPlan {
 #[derive(Debug)]
 pub struct CreateRolePlan {
     pub name: String,
+    pub superuser: bool,
 }
 
 #[derive(Debug)]
@@ -410,6 +427,27 @@ pub struct DropRolesPlan {
     pub names: Vec<String>,
 }
 
+#[derive(Debug)]
+pub struct GrantPrivilegesPlan {
+    pub privileges: BTreeSet<Privilege>,
+    pub target: PrivilegeTarget,
+    pub role_ids: Vec<RoleId>,
+}
+
+#[derive(Debug)]
+pub struct RevokePrivilegesPlan {
+    pub privileges: BTreeSet<Privilege>,
+    pub target: PrivilegeTarget,
+    pub role_ids: Vec<RoleId>,
+}
+
+/// The object on which privileges are granted or revoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegeTarget {
+    Item(GlobalId),
+    Schema(SchemaId),
+}
+
 #[derive(Debug)]
 pub struct DropComputeInstancesPlan {
     pub names: Vec<String>,
@@ -458,6 +496,12 @@ pub struct SubscribePlan {
     pub when: QueryWhen,
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
+    /// The interval at which to emit progress updates, even if the
+    /// subscribed relation's frontier has not advanced.
+    pub progress_interval: Option<Duration>,
+    /// How long the subscribe may go without emitting any updates before it
+    /// is ended.
+    pub idle_timeout: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -536,17 +580,65 @@ pub struct AlterIndexResetOptionsPlan {
 
 #[derive(Debug, Clone)]
 
-pub enum AlterOptionParameter {
-    Set(String),
+pub enum AlterOptionParameter<T = String> {
+    Set(T),
     Reset,
     Unchanged,
 }
 
+impl<T> Default for AlterOptionParameter<T> {
+    fn default() -> Self {
+        AlterOptionParameter::Unchanged
+    }
+}
+
+impl<T> AlterOptionParameter<T> {
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, AlterOptionParameter::Unchanged)
+    }
+}
+
 #[derive(Debug)]
 pub struct AlterSinkPlan {
     pub id: GlobalId,
     pub size: AlterOptionParameter,
     pub remote: AlterOptionParameter,
+    pub runtime_options: AlterSinkRuntimeOptions,
+}
+
+/// The options of a sink that can be changed without recreating it.
+#[derive(Debug, Clone, Default)]
+pub struct AlterSinkRuntimeOptions {
+    pub max_bytes_per_second: AlterOptionParameter<u64>,
+    pub max_messages_per_second: AlterOptionParameter<u64>,
+    pub compression_type: AlterOptionParameter<KafkaSinkCompressionType>,
+    pub linger_ms: AlterOptionParameter<u64>,
+    pub batch_num_messages: AlterOptionParameter<u64>,
+    pub retention_ms: AlterOptionParameter<i64>,
+    pub retention_bytes: AlterOptionParameter<i64>,
+}
+
+impl AlterSinkRuntimeOptions {
+    /// Reports whether any of the options are changed.
+    pub fn any_changed(&self) -> bool {
+        !(self.max_bytes_per_second.is_unchanged()
+            && self.max_messages_per_second.is_unchanged()
+            && self.compression_type.is_unchanged()
+            && self.linger_ms.is_unchanged()
+            && self.batch_num_messages.is_unchanged()
+            && self.retention_ms.is_unchanged()
+            && self.retention_bytes.is_unchanged())
+    }
+
+    /// Reports whether any of the options of the sink's Kafka connection are
+    /// changed.
+    pub fn kafka_connection_changed(&self) -> bool {
+        !(self.compression_type.is_unchanged()
+            && self.linger_ms.is_unchanged()
+            && self.batch_num_messages.is_unchanged()
+            && self.retention_ms.is_unchanged()
+            && self.retention_bytes.is_unchanged())
+    }
 }
 
 #[derive(Debug)]
@@ -556,6 +648,11 @@ pub struct AlterSourcePlan {
     pub remote: AlterOptionParameter,
 }
 
+#[derive(Debug)]
+pub struct AlterSourceRefreshSchemaPlan {
+    pub id: GlobalId,
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -564,6 +661,21 @@ pub struct AlterItemRenamePlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterOwnerPlan {
+    pub id: ObjectId,
+    pub object_type: ObjectType,
+    pub new_owner: RoleId,
+}
+
+/// The identifier of an object that has an owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectId {
+    Database(DatabaseId),
+    Schema(DatabaseId, SchemaId),
+    Item(GlobalId),
+}
+
 #[derive(Debug)]
 pub struct AlterSecretPlan {
     pub id: GlobalId,
@@ -641,10 +753,25 @@ pub struct Table {
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
-    pub ingestion: Option<Ingestion>,
+    pub data_source: DataSourceDesc,
     pub desc: RelationDesc,
 }
 
+#[derive(Clone, Debug)]
+pub enum DataSourceDesc {
+    /// Receives data from an external system.
+    Ingestion(Ingestion),
+    /// Receives data from some other source.
+    Source,
+    /// Receives data from requests to the source's webhook endpoint.
+    Webhook {
+        /// The secret with which each request must be signed, if any.
+        secret: Option<GlobalId>,
+        /// The predicate that the body of each request must satisfy, if any.
+        check: Option<MirScalarExpr>,
+    },
+}
+
 #[derive(Clone, Debug)]
 pub struct Ingestion {
     pub desc: SourceDesc,
@@ -670,6 +797,18 @@ pub struct Sink {
     pub from: GlobalId,
     pub connection_builder: StorageSinkConnectionBuilder,
     pub envelope: SinkEnvelope,
+    pub catch_up_limit: Option<SinkRateLimit>,
+    pub rate_limit: Option<SinkRateLimit>,
+    /// The columns of `from` that the sink exports, or empty if it exports
+    /// all of them.
+    pub projection: Vec<usize>,
+    pub column_masks: Vec<SinkColumnMask>,
+    /// Whether the sink appends the timestamp of each update to its rows.
+    pub include_timestamp: bool,
+    /// Whether the sink appends the diff of each update to its rows.
+    pub include_diff: bool,
+    /// The predicate on the rows of `from` that the sink exports, if any.
+    pub filter: Option<MirScalarExpr>,
 }
 
 #[derive(Clone, Debug)]
@@ -686,6 +825,10 @@ pub struct MaterializedView {
     pub expr: mz_expr::MirRelationExpr,
     pub column_names: Vec<ColumnName>,
     pub compute_instance: ComputeInstanceId,
+    pub batching: PersistSinkBatching,
+    /// The logical compaction window of the materialized view, if one was
+    /// specified. `Some(None)` disables logical compaction entirely.
+    pub compaction_window: Option<Option<Timestamp>>,
 }
 
 #[derive(Clone, Debug)]
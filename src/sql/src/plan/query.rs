@@ -966,6 +966,104 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the `PARTITION BY` expression of a Kafka sink over the relation
+/// described by `desc`.
+///
+/// The expression must be castable to `uint8` by assignment, and may not
+/// depend on anything other than the sinked row, so that the same row is
+/// always written to the same partition.
+pub fn plan_partition_by_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "PARTITION BY",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?.type_as_any(ecx)?;
+    let ty = ecx.scalar_type(&expr);
+    let expr = typeconv::plan_cast(ecx, CastContext::Assignment, expr, &ScalarType::UInt64)
+        .map_err(|_| {
+            sql_err!(
+                "PARTITION BY expression must have a type castable to uint8, not {}",
+                ecx.humanize_scalar_type(&ty)
+            )
+        })?
+        .lower_uncorrelated()?;
+    if expr.contains_unmaterializable() {
+        sql_bail!("PARTITION BY expression cannot call unmaterializable functions");
+    }
+    Ok(expr)
+}
+
+/// Plans the `WHERE` predicate of a sink on the rows of the relation it
+/// exports, described by `desc`.
+pub fn plan_sink_filter_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "WHERE clause",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    if expr.contains_unmaterializable() {
+        sql_bail!("sink WHERE clause cannot call unmaterializable functions");
+    }
+    Ok(expr)
+}
+
+/// Plans the `CHECK` predicate of a webhook source on the rows that requests
+/// append to the source, described by `desc`.
+pub fn plan_webhook_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK clause",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    if expr.contains_unmaterializable() {
+        sql_bail!("webhook CHECK clause cannot call unmaterializable functions");
+    }
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1324,6 +1422,7 @@ fn plan_set_expr(
                 ShowStatement::ShowDatabases(stmt) => {
                     show::show_databases(qcx.scx, stmt)?.plan_hir(qcx)
                 }
+                ShowStatement::ShowGrants(stmt) => show::show_grants(qcx.scx, stmt)?.plan_hir(qcx),
                 ShowStatement::ShowIndexes(stmt) => {
                     show::show_indexes(qcx.scx, stmt)?.plan_hir(qcx)
                 }
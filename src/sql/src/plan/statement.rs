@@ -111,6 +111,7 @@ pub fn describe(
         Statement::AlterConnection(stmt) => ddl::describe_alter_connection(&scx, stmt)?,
         Statement::AlterIndex(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
+        Statement::AlterOwner(stmt) => ddl::describe_alter_owner(&scx, stmt)?,
         Statement::AlterSecret(stmt) => ddl::describe_alter_secret_options(&scx, stmt)?,
         Statement::AlterSink(stmt) => ddl::describe_alter_sink(&scx, stmt)?,
         Statement::AlterSource(stmt) => ddl::describe_alter_source(&scx, stmt)?,
@@ -140,6 +141,8 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::DropRoles(stmt) => ddl::describe_drop_role(&scx, stmt)?,
         Statement::DropSchema(stmt) => ddl::describe_drop_schema(&scx, stmt)?,
+        Statement::GrantPrivileges(stmt) => ddl::describe_grant_privileges(&scx, stmt)?,
+        Statement::RevokePrivileges(stmt) => ddl::describe_revoke_privileges(&scx, stmt)?,
 
         // `SHOW` statements.
         Statement::Show(ShowStatement::ShowColumns(stmt)) => {
@@ -169,6 +172,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowDatabases(stmt)) => {
             show::show_databases(&scx, stmt)?.describe()?
         }
+        Statement::Show(ShowStatement::ShowGrants(stmt)) => {
+            show::show_grants(&scx, stmt)?.describe()?
+        }
         Statement::Show(ShowStatement::ShowIndexes(stmt)) => {
             show::show_indexes(&scx, stmt)?.describe()?
         }
@@ -252,6 +258,7 @@ pub fn plan(
         Statement::AlterConnection(stmt) => ddl::plan_alter_connection(scx, stmt),
         Statement::AlterIndex(stmt) => ddl::plan_alter_index_options(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
+        Statement::AlterOwner(stmt) => ddl::plan_alter_owner(scx, stmt),
         Statement::AlterSecret(stmt) => ddl::plan_alter_secret(scx, stmt),
         Statement::AlterSink(stmt) => ddl::plan_alter_sink(scx, stmt),
         Statement::AlterSource(stmt) => ddl::plan_alter_source(scx, stmt),
@@ -281,6 +288,8 @@ pub fn plan(
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::DropRoles(stmt) => ddl::plan_drop_role(scx, stmt),
         Statement::DropSchema(stmt) => ddl::plan_drop_schema(scx, stmt),
+        Statement::GrantPrivileges(stmt) => ddl::plan_grant_privileges(scx, stmt),
+        Statement::RevokePrivileges(stmt) => ddl::plan_revoke_privileges(scx, stmt),
 
         // DML statements.
         Statement::Copy(stmt) => dml::plan_copy(scx, stmt),
@@ -317,6 +326,7 @@ pub fn plan(
         Statement::Show(ShowStatement::ShowDatabases(stmt)) => {
             show::show_databases(scx, stmt)?.plan()
         }
+        Statement::Show(ShowStatement::ShowGrants(stmt)) => show::show_grants(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowIndexes(stmt)) => show::show_indexes(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowObjects(stmt)) => show::show_objects(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowSchemas(stmt)) => show::show_schemas(scx, stmt)?.plan(),
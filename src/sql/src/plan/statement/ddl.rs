@@ -12,10 +12,11 @@
 //! This module houses the handlers for statements that modify the catalog, like
 //! `ALTER`, `CREATE`, and `DROP`.
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
+use std::time::Duration;
 
 use aws_arn::ResourceName as AmazonResourceName;
 use globset::GlobBuilder;
@@ -25,50 +26,73 @@ use prost::Message;
 use regex::Regex;
 use tracing::warn;
 
+use mz_compute_client::sinks::PersistSinkBatching;
 use mz_expr::CollectionPlan;
-use mz_interchange::avro::AvroSchemaGenerator;
-use mz_kafka_util::KafkaAddrs;
+use mz_interchange::avro::{AvroSchemaGenerator, Conformance};
+use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::ProtobufSchemaGenerator;
+use mz_interchange::text::{CsvEncoder, TextEncoder};
+use mz_kafka_util::KafkaBrokerAddrs;
+use mz_mysql_util::desc::MySqlColumnDesc;
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::strconv;
-use mz_repr::{ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType, Timestamp,
+};
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
     AlterSinkAction, AlterSinkStatement, AlterSourceAction, AlterSourceStatement,
     AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement,
-    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
-    SetVariableValue, SshConnectionOption,
+    AwsPrivatelinkConnectionOption, CreateTypeListOption, CreateTypeListOptionName,
+    CreateTypeMapOption, CreateTypeMapOptionName, SetVariableValue, SshConnectionOption,
 };
+use mz_sql_server_util::desc::SqlServerColumnDesc;
 use mz_storage::source::generator::as_generator;
 use mz_storage::types::connections::aws::{AwsAssumeRole, AwsConfig, AwsCredentials, SerdeUri};
 use mz_storage::types::connections::{
-    Connection, CsrConnectionHttpAuth, KafkaConnection, KafkaSecurity, KafkaTlsConfig, SaslConfig,
-    StringOrSecret, TlsIdentity,
+    Connection, CsrConnectionHttpAuth, KafkaConnection, KafkaSecurity, KafkaTlsConfig,
+    MySqlSslMode, OAuthBearerConfig, OAuthBearerTokenSource, OAuthClientCredentials, SaslConfig,
+    SqlServerSslMode, StringOrSecret, TlsIdentity,
 };
 use mz_storage::types::sinks::{
-    KafkaConsistencyConfig, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention,
-    KafkaSinkFormat, SinkEnvelope, StorageSinkConnectionBuilder,
+    is_kafka_topic_name_char, ElasticsearchSinkConnection, FileSinkConnection, FileSinkFormat,
+    GrpcSinkConnection, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention, KafkaSinkFormat,
+    KafkaSinkHeader, KafkaSinkMessageTimestamp, KafkaSinkRetryExhaustedAction,
+    KafkaSinkRetryPolicy, KafkaSinkTopicFanout, KeyTemplateSegment, MqttSinkConnection,
+    NatsSinkConnection, PostgresSinkConnection, RedisSinkConnection, RedisSinkDataType,
+    S3CollisionPolicy, S3KeyTemplateSegment, S3SinkConnection, S3SinkFormat, S3TableFormat,
+    SinkColumnMask, SinkColumnMaskKind, SinkDeletePolicy, SinkEnvelope, SinkRateLimit,
+    SinkRetractionPolicy, SqsSinkConnection, StorageSinkConnectionBuilder, SubjectNameStrategy,
+    KAFKA_SINK_TIMESTAMP_HEADER,
 };
 use mz_storage::types::sources::encoding::{
     included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, DataEncodingInner,
-    ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
+    HeaderEncoding, JsonEncoding, NumericOverflowPolicy, ProtobufEncoding, RegexEncoding,
+    SourceDataEncoding, SourceDataEncodingInner,
 };
 use mz_storage::types::sources::{
-    IncludedColumnPos, KafkaSourceConnection, KeyEnvelope, KinesisSourceConnection,
-    LoadGeneratorSourceConnection, PostgresSourceConnection, PostgresSourceDetails,
-    ProtoPostgresSourceDetails, S3SourceConnection, SourceConnection, SourceDesc, SourceEnvelope,
-    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStyle,
+    DebeziumDedupProjection, DebeziumEnvelope, DebeziumSourceProjection,
+    DebeziumTransactionMetadata, DecodeErrorPolicy, EventTimeConfig, FileSourceConnection,
+    IncludedColumnPos, IncludedHeader, IngestRateLimit, KafkaSourceConnection, KeyEnvelope,
+    KinesisSourceConnection, LoadGeneratorSourceConnection, MqttSourceConnection,
+    MySqlSourceConnection, MySqlSourceDetails, NatsSourceConnection, NoneEnvelope,
+    PostgresSourceConnection, PostgresSourceDetails, ProtoMySqlSourceDetails,
+    ProtoPostgresSourceDetails, ProtoSqlServerSourceDetails, PubSubSourceConnection,
+    S3SourceConnection, SftpSourceConnection, SourceConnection, SourceDesc, SourceEnvelope,
+    SqlServerSourceConnection, SqlServerSourceDetails, TestScriptSourceConnection, Timeline,
+    TimestampStrategy, UnplannedSourceEnvelope, UpsertStateBackend, UpsertStyle,
 };
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
-    AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption,
-    AwsConnectionOptionName, ClusterOption, ClusterOptionName, ColumnOption, Compression,
-    CreateClusterReplicaStatement, CreateClusterStatement, CreateConnection,
-    CreateConnectionStatement, CreateDatabaseStatement, CreateIndexStatement,
+    AlterOwnerStatement, AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName,
+    AwsConnectionOption, AwsConnectionOptionName, ClusterOption, ClusterOptionName, ColumnDef,
+    ColumnOption, Compression, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnection, CreateConnectionStatement, CreateDatabaseStatement, CreateIndexStatement,
     CreateMaterializedViewStatement, CreateRoleOption, CreateRoleStatement, CreateSchemaStatement,
     CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
     CreateSinkStatement, CreateSourceConnection, CreateSourceFormat, CreateSourceOption,
@@ -76,21 +100,37 @@ use crate::ast::{
     CreateSubsourceStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
     CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection, CsrConnectionAvro,
     CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf, CsrSeedProtobuf,
-    CsvColumns, DbzMode, DropClusterReplicasStatement, DropClustersStatement,
-    DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement, Envelope,
-    Expr, Format, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaConfigOptionName,
-    KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint, LoadGeneratorOption,
-    LoadGeneratorOptionName, ObjectType, PgConfigOption, PgConfigOptionName,
-    PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
-    ReplicaDefinition, ReplicaOption, ReplicaOptionName, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, Value, ViewDefinition,
+    CsvColumns, DbzMode, DbzTxMetadataOption, DropClusterReplicasStatement, DropClustersStatement,
+    DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement,
+    ElasticsearchSinkConfigOption, ElasticsearchSinkConfigOptionName, Envelope, Expr,
+    FileConfigOption, FileConfigOptionName, FileSinkConfigOption, FileSinkConfigOptionName, Format,
+    GrantPrivilegesStatement, GrantTargetSpecification, GrpcSinkConfigOption,
+    GrpcSinkConfigOptionName, Ident, IfExistsBehavior, IndexOption, IndexOptionName,
+    KafkaConfigOptionName, KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint,
+    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
+    MaterializedViewOptionName, MqttConfigOption, MqttConfigOptionName, MqttSinkConfigOption,
+    MqttSinkConfigOptionName, MySqlConfigOption, MySqlConfigOptionName, MySqlConnectionOption,
+    MySqlConnectionOptionName, NatsConfigOption, NatsConfigOptionName, NatsSinkConfigOption,
+    NatsSinkConfigOptionName, ObjectType, PgConfigOption, PgConfigOptionName,
+    PostgresConnectionOption, PostgresConnectionOptionName, Privilege as AstPrivilege,
+    PrivilegeSpecification, ProtobufSchema, PubSubConfigOption, PubSubConfigOptionName,
+    QualifiedReplica, RedisSinkConfigOption, RedisSinkConfigOptionName, ReplicaDefinition,
+    ReplicaOption, ReplicaOptionName, RevokePrivilegesStatement, S3SinkConfigOption,
+    S3SinkConfigOptionName, SftpConfigOption, SftpConfigOptionName, SinkColumn,
+    SourceIncludeMetadata, SourceIncludeMetadataType, SqlServerConfigOption,
+    SqlServerConfigOptionName, SqlServerConnectionOption, SqlServerConnectionOptionName,
+    SqsSinkConfigOption, SqsSinkConfigOptionName, SshConnectionOptionName, Statement,
+    TableConstraint, UnresolvedDatabaseName, UnresolvedObjectName, UnresolvedSchemaName, Value,
+    ViewDefinition,
+};
+use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails, Privilege};
+use crate::kafka_util::{
+    self, KafkaConfigOptionExtracted, KafkaHeaderColumn, KafkaMessageTimestamp,
+    KafkaStartOffsetType, KafkaTopicConfigEntry,
 };
-use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails};
-use crate::kafka_util::{self, KafkaConfigOptionExtracted, KafkaStartOffsetType};
 use crate::names::{
     Aug, FullSchemaName, QualifiedObjectName, RawDatabaseSpecifier, ResolvedClusterName,
-    ResolvedDataType, ResolvedDatabaseSpecifier, ResolvedObjectName, SchemaSpecifier,
+    ResolvedDataType, ResolvedDatabaseSpecifier, ResolvedObjectName, RoleId, SchemaSpecifier,
 };
 use crate::normalize::{self, ident};
 use crate::plan::error::PlanError;
@@ -102,15 +142,17 @@ use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{self, OptionalInterval, TryFromValue};
 use crate::plan::{
     plan_utils, query, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
-    AlterNoopPlan, AlterOptionParameter, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan,
+    AlterNoopPlan, AlterOptionParameter, AlterOwnerPlan, AlterSecretPlan, AlterSinkPlan,
+    AlterSinkRuntimeOptions, AlterSourcePlan, AlterSourceRefreshSchemaPlan,
     AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, ComputeReplicaConfig,
     ComputeReplicaIntrospectionConfig, CreateComputeInstancePlan, CreateComputeReplicaPlan,
     CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
     CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan,
+    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropComputeInstancesPlan,
     DropComputeReplicasPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
-    FullObjectName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext,
-    RotateKeysPlan, Secret, Sink, Source, StorageHostConfig, Table, Type, View,
+    FullObjectName, GrantPrivilegesPlan, HirScalarExpr, Index, Ingestion, MaterializedView,
+    ObjectId, Params, Plan, PrivilegeTarget, QueryContext, RevokePrivilegesPlan, RotateKeysPlan,
+    Secret, Sink, Source, StorageHostConfig, Table, Type, View,
 };
 
 pub fn describe_create_database(
@@ -316,15 +358,27 @@ pub fn describe_create_subsource(
 
 generate_extracted_config!(
     CreateSourceOption,
+    (DecodeErrors, DecodeErrorPolicy),
     (IgnoreKeys, bool),
+    (MaxBytesPerSecond, u64),
+    (MaxMessagesPerSecond, u64),
+    (NumericOverflow, NumericOverflowPolicy),
     (Remote, String),
     (Size, String),
     (Timeline, String),
-    (TimestampInterval, Interval)
+    (TimestampColumn, String),
+    (TimestampInterval, Interval),
+    (TimestampLateness, Interval),
+    (TimestampStrategy, TimestampStrategy),
+    (UpsertState, UpsertStateBackend)
 );
 
 generate_extracted_config!(PgConfigOption, (Details, String), (Publication, String));
 
+generate_extracted_config!(MySqlConfigOption, (Details, String));
+
+generate_extracted_config!(SqlServerConfigOption, (Details, String));
+
 pub fn plan_create_source(
     scx: &StatementContext,
     stmt: CreateSourceStatement<Aug>,
@@ -342,9 +396,19 @@ pub fn plan_create_source(
         subsources,
     } = &stmt;
 
+    if let CreateSourceConnection::Webhook { .. } = connection {
+        return plan_create_webhook_source(scx, stmt);
+    }
+
     let envelope = envelope.clone().unwrap_or(Envelope::None);
 
-    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[CreateSourceOptionName::Size];
+    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[
+        CreateSourceOptionName::DecodeErrors,
+        CreateSourceOptionName::MaxBytesPerSecond,
+        CreateSourceOptionName::MaxMessagesPerSecond,
+        CreateSourceOptionName::Size,
+        CreateSourceOptionName::UpsertState,
+    ];
 
     if with_options
         .iter()
@@ -357,18 +421,27 @@ pub fn plan_create_source(
     }
 
     if !matches!(connection, CreateSourceConnection::Kafka { .. })
-        && include_metadata
-            .iter()
-            .any(|sic| sic.ty == SourceIncludeMetadataType::Headers)
+        && include_metadata.iter().any(|sic| {
+            matches!(
+                sic.ty,
+                SourceIncludeMetadataType::Headers | SourceIncludeMetadataType::Header { .. }
+            )
+        })
     {
         // TODO(guswynn): should this be `bail_unsupported!`?
         sql_bail!("INCLUDE HEADERS with non-Kafka sources not supported");
     }
-    if !matches!(connection, CreateSourceConnection::Kafka { .. }) && !include_metadata.is_empty() {
+    if !matches!(
+        connection,
+        CreateSourceConnection::Kafka { .. }
+            | CreateSourceConnection::File { .. }
+            | CreateSourceConnection::Mqtt { .. }
+    ) && !include_metadata.is_empty()
+    {
         bail_unsupported!("INCLUDE metadata with non-Kafka sources");
     }
 
-    let (external_connection, encoding, available_subsources) = match connection {
+    let (external_connection, mut encoding, available_subsources) = match connection {
         CreateSourceConnection::Kafka(mz_sql_parser::ast::KafkaSourceConnection {
             connection:
                 mz_sql_parser::ast::KafkaConnection {
@@ -384,11 +457,15 @@ pub fn plan_create_source(
             };
 
             // Starting offsets are allowed out unsafe mode, as they are a simple,
-            // useful way to specify where to start reading a topic.
+            // useful way to specify where to start reading a topic. The commit
+            // interval is too, as it only affects the offsets we report back to
+            // the consumer group for monitoring.
             if let Some(opt) = options.iter().find(|opt| {
                 opt.name != KafkaConfigOptionName::StartOffset
                     && opt.name != KafkaConfigOptionName::StartTimestamp
                     && opt.name != KafkaConfigOptionName::Topic
+                    && opt.name != KafkaConfigOptionName::TopicPattern
+                    && opt.name != KafkaConfigOptionName::CommitInterval
             }) {
                 scx.require_unsafe_mode(&format!("KAFKA CONNECTION option {}", opt.name))?;
             }
@@ -404,10 +481,26 @@ pub fn plan_create_source(
                 Option::<kafka_util::KafkaStartOffsetType>::try_from(&extracted_options)?;
             let options = kafka_util::LibRdKafkaConfig::try_from(&extracted_options)?.0;
 
-            let topic = extracted_options
-                .topic
-                .expect("validated exists during purification");
+            let (topic, topic_pattern) = match extracted_options.topic {
+                Some(topic) => (topic, false),
+                None => {
+                    let pattern = extracted_options
+                        .topic_pattern
+                        .expect("validated exists during purification");
+                    if let Err(e) = Regex::new(&pattern) {
+                        sql_bail!("invalid TOPIC PATTERN: {}", e);
+                    }
+                    (pattern, true)
+                }
+            };
             let group_id_prefix = extracted_options.group_id_prefix;
+            let commit_interval = extracted_options
+                .commit_interval
+                .map(|i| i.duration())
+                .transpose()?;
+            if commit_interval == Some(std::time::Duration::ZERO) {
+                sql_bail!("COMMIT INTERVAL for Kafka sources must be positive");
+            }
 
             let mut start_offsets = HashMap::new();
             match optional_start_offset {
@@ -436,14 +529,17 @@ pub fn plan_create_source(
                 connection_id: connection_item.id(),
                 options,
                 topic,
+                topic_pattern,
                 start_offsets,
                 group_id_prefix,
+                commit_interval,
                 environment_id: scx.catalog.config().environment_id.clone(),
                 include_timestamp: None,
                 include_partition: None,
                 include_topic: None,
                 include_offset: None,
                 include_headers: None,
+                include_header: vec![],
             };
 
             let unwrap_name = |alias: Option<Ident>, default, pos| {
@@ -456,9 +552,13 @@ pub fn plan_create_source(
             };
 
             if !matches!(envelope, Envelope::Upsert | Envelope::None)
-                && include_metadata
-                    .iter()
-                    .any(|sic| sic.ty == SourceIncludeMetadataType::Headers)
+                && include_metadata.iter().any(|sic| {
+                    matches!(
+                        sic.ty,
+                        SourceIncludeMetadataType::Headers
+                            | SourceIncludeMetadataType::Header { .. }
+                    )
+                })
             {
                 // TODO(guswynn): should this be `bail_unsupported!`?
                 sql_bail!("INCLUDE HEADERS requires ENVELOPE UPSERT or no ENVELOPE");
@@ -478,7 +578,13 @@ pub fn plan_create_source(
                         // whether or not anyone ever uses it. Considering we expect the
                         // overwhelming majority of people will *not* want topics in dataflows that
                         // is an unnacceptable cost.
-                        bail_unsupported!("INCLUDE TOPIC");
+                        //
+                        // Sources that read several topics already track the topic of each
+                        // partition, so there is no such cost for them.
+                        if !connection.topic_pattern {
+                            bail_unsupported!("INCLUDE TOPIC without TOPIC PATTERN");
+                        }
+                        connection.include_topic = unwrap_name(item.alias, "topic", pos);
                     }
                     SourceIncludeMetadataType::Offset => {
                         connection.include_offset = unwrap_name(item.alias, "offset", pos);
@@ -486,6 +592,27 @@ pub fn plan_create_source(
                     SourceIncludeMetadataType::Headers => {
                         connection.include_headers = unwrap_name(item.alias, "headers", pos);
                     }
+                    SourceIncludeMetadataType::Header { key, format } => {
+                        let encoding = get_header_encoding(scx, format.as_ref())?;
+                        // Validate the encoding up front, so that computing
+                        // the source's columns later on cannot fail.
+                        encoding.column_type()?;
+                        let column = IncludedColumnPos {
+                            name: item
+                                .alias
+                                .map(|a| a.to_string())
+                                .unwrap_or_else(|| key.clone()),
+                            pos,
+                        };
+                        connection.include_header.push(IncludedHeader {
+                            key,
+                            encoding,
+                            column,
+                        });
+                    }
+                    SourceIncludeMetadataType::Filename => {
+                        bail_unsupported!("INCLUDE FILENAME with Kafka sources")
+                    }
                     SourceIncludeMetadataType::Key => {} // handled below
                 }
             }
@@ -497,6 +624,7 @@ pub fn plan_create_source(
         CreateSourceConnection::Kinesis {
             connection: aws_connection,
             arn,
+            consumer,
         } => {
             scx.require_unsafe_mode("CREATE SOURCE ... FROM KINESIS")?;
             let arn: AmazonResourceName = arn
@@ -510,6 +638,21 @@ pub fn plan_create_source(
                 ),
             };
 
+            // Kinesis restricts the characters in the names of stream consumers.
+            if let Some(consumer) = consumer {
+                if consumer.is_empty()
+                    || consumer.len() > 128
+                    || !consumer
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+                {
+                    sql_bail!(
+                        "invalid CONSUMER {}: must be 1 to 128 letters, digits, underscores, periods or hyphens",
+                        consumer.quoted()
+                    );
+                }
+            }
+
             let connection_item = scx.get_item_by_resolved_name(aws_connection)?;
             let aws = match connection_item.connection()? {
                 Connection::Aws(aws) => aws.clone(),
@@ -520,6 +663,7 @@ pub fn plan_create_source(
             let connection = SourceConnection::Kinesis(KinesisSourceConnection {
                 connection_id: connection_item.id(),
                 stream_name,
+                consumer_name: consumer.clone(),
                 aws,
             });
             (connection, encoding, None)
@@ -572,10 +716,7 @@ pub fn plan_create_source(
                     .transpose()
                     .map_err(|e| sql_err!("parsing glob: {e}"))?,
                 aws,
-                compression: match compression {
-                    Compression::Gzip => mz_storage::types::sources::Compression::Gzip,
-                    Compression::None => mz_storage::types::sources::Compression::None,
-                },
+                compression: plan_compression(compression),
             });
             (connection, encoding, None)
         }
@@ -601,8 +742,23 @@ pub fn plan_create_source(
             let details =
                 ProtoPostgresSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
 
+            // The columns selected from each upstream table, as validated during purification.
+            // Tables without a column list ingest all of their columns.
+            let mut selected_columns = HashMap::new();
+            if let Some(CreateSourceSubsources::Subset(subsources)) = subsources {
+                for subsource in subsources {
+                    if let CreateSourceSubsource::Resolved(name, columns, _) = subsource {
+                        if !columns.is_empty() {
+                            let name = normalize::full_name(name.clone())?;
+                            selected_columns.insert(name, columns);
+                        }
+                    }
+                }
+            }
+
             // Register the available subsources
             let mut available_subsources = HashMap::new();
+            let mut table_columns = vec![];
 
             for (i, table) in details.tables.iter().enumerate() {
                 let name = FullObjectName {
@@ -610,6 +766,23 @@ pub fn plan_create_source(
                     schema: table.namespace.clone(),
                     item: table.name.clone(),
                 };
+                // Map each selected column to its position in the upstream table
+                let columns = match selected_columns.get(&name) {
+                    Some(columns) => columns
+                        .iter()
+                        .map(|column| {
+                            table
+                                .columns
+                                .iter()
+                                .position(|c| c.name == column.as_str())
+                                .ok_or_else(|| {
+                                    sql_err!("column {} not found in table {}", column, name)
+                                })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => (0..table.columns.len()).collect(),
+                };
+                table_columns.push(columns);
                 // The zero-th output is the main output
                 // TODO(petrosagg): these plus ones are an accident waiting to happen. Find a way
                 // to handle the main source and the subsources uniformly
@@ -621,7 +794,7 @@ pub fn plan_create_source(
             // The postgres source reader will then eval each of those on the incoming rows based
             // on the target table
             let mut table_casts = vec![];
-            for table in details.tables.iter() {
+            for (table, columns) in details.tables.iter().zip(table_columns) {
                 // First, construct an expression context where the expression is evaluated on an
                 // imaginary row which has the same number of columns as the upstream table but all
                 // of the types are text
@@ -649,10 +822,11 @@ pub fn plan_create_source(
                     allow_windows: false,
                 };
 
-                // Then, for each column we will generate a MirRelationExpr that extracts the nth
-                // column and casts it to the appropriate target type
+                // Then, for each selected column we will generate a MirRelationExpr that extracts
+                // the nth column and casts it to the appropriate target type
                 let mut column_casts = vec![];
-                for (i, column) in table.columns.iter().enumerate() {
+                for i in columns {
+                    let column = &table.columns[i];
                     let ty = mz_pgrepr::Type::from_oid_and_typmod(column.type_oid, column.type_mod)
                         .map_err(|e| sql_err!("{}", e))?;
                     let data_type = scx.resolve_type(ty)?;
@@ -695,6 +869,211 @@ pub fn plan_create_source(
             ));
             (connection, encoding, Some(available_subsources))
         }
+        CreateSourceConnection::MySql {
+            connection,
+            options,
+        } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM MYSQL")?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::MySql(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a MySQL connection", connection_item.name()),
+            };
+            let MySqlConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: MySQL source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoMySqlSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details = MySqlSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            // Register the available subsources. MySQL has no level of
+            // namespacing above its schemas, so the upstream names of the
+            // tables are qualified by their schema alone.
+            let mut available_subsources = HashMap::new();
+            for (i, table) in details.tables.iter().enumerate() {
+                let name = FullObjectName {
+                    database: RawDatabaseSpecifier::Ambient,
+                    schema: table.schema_name.clone(),
+                    item: table.name.clone(),
+                };
+                // The zero-th output is the main output
+                available_subsources.insert(name, i + 1);
+            }
+
+            // As with Postgres sources, the reader produces every column as
+            // text, which these expressions cast to the column's target type.
+            let mut table_casts = vec![];
+            for table in details.tables.iter() {
+                let mut cast_scx = scx.clone();
+                cast_scx.param_types = Default::default();
+                let cast_qcx = QueryContext::root(&cast_scx, QueryLifetime::Static);
+                let column_types = table
+                    .columns
+                    .iter()
+                    .map(|column| ColumnType {
+                        nullable: column.nullable,
+                        scalar_type: ScalarType::String,
+                    })
+                    .collect();
+                let cast_ecx = ExprContext {
+                    qcx: &cast_qcx,
+                    name: "plan_mysql_source_cast",
+                    scope: &Scope::empty(),
+                    relation_type: &RelationType {
+                        column_types,
+                        keys: vec![],
+                    },
+                    allow_aggregates: false,
+                    allow_subqueries: false,
+                    allow_windows: false,
+                };
+
+                let mut column_casts = vec![];
+                for (i, column) in table.columns.iter().enumerate() {
+                    let ty = mysql_column_type(column)?;
+                    let data_type = scx.resolve_type(ty)?;
+                    let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
+
+                    let col_expr = HirScalarExpr::Column(ColumnRef {
+                        level: 0,
+                        column: i,
+                    });
+                    let cast_expr = plan_cast(
+                        &cast_ecx,
+                        CastContext::Explicit,
+                        col_expr,
+                        &scalar_type,
+                    )?
+                    .lower_uncorrelated()
+                    .expect(
+                        "lower_uncorrelated should not fail given that there is no correlation \
+                            in the input col_expr",
+                    );
+                    column_casts.push(cast_expr);
+                }
+                table_casts.push(column_casts);
+            }
+
+            let connection = SourceConnection::MySql(MySqlSourceConnection {
+                connection,
+                connection_id: connection_item.id(),
+                table_casts,
+                details,
+            });
+
+            // Like the postgres source, the MySQL source only outputs data to
+            // its subsources.
+            let encoding = SourceDataEncoding::Single(DataEncoding::new(
+                DataEncodingInner::RowCodec(RelationDesc::empty()),
+            ));
+            (connection, encoding, Some(available_subsources))
+        }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM SQL SERVER")?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a SQL Server connection", connection_item.name()),
+            };
+            let SqlServerConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: SQL Server source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoSqlServerSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                SqlServerSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            // Register the available subsources
+            let mut available_subsources = HashMap::new();
+            for (i, table) in details.tables.iter().enumerate() {
+                let name = FullObjectName {
+                    database: RawDatabaseSpecifier::Name(connection.database.clone()),
+                    schema: table.schema_name.clone(),
+                    item: table.name.clone(),
+                };
+                // The zero-th output is the main output
+                available_subsources.insert(name, i + 1);
+            }
+
+            // As with Postgres sources, the reader produces every column as
+            // text, which these expressions cast to the column's target type.
+            let mut table_casts = vec![];
+            for table in details.tables.iter() {
+                let mut cast_scx = scx.clone();
+                cast_scx.param_types = Default::default();
+                let cast_qcx = QueryContext::root(&cast_scx, QueryLifetime::Static);
+                let column_types = table
+                    .columns
+                    .iter()
+                    .map(|column| ColumnType {
+                        nullable: column.nullable,
+                        scalar_type: ScalarType::String,
+                    })
+                    .collect();
+                let cast_ecx = ExprContext {
+                    qcx: &cast_qcx,
+                    name: "plan_sql_server_source_cast",
+                    scope: &Scope::empty(),
+                    relation_type: &RelationType {
+                        column_types,
+                        keys: vec![],
+                    },
+                    allow_aggregates: false,
+                    allow_subqueries: false,
+                    allow_windows: false,
+                };
+
+                let mut column_casts = vec![];
+                for (i, column) in table.columns.iter().enumerate() {
+                    let ty = sql_server_column_type(column)?;
+                    let data_type = scx.resolve_type(ty)?;
+                    let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
+
+                    let col_expr = HirScalarExpr::Column(ColumnRef {
+                        level: 0,
+                        column: i,
+                    });
+                    let cast_expr = plan_cast(
+                        &cast_ecx,
+                        CastContext::Explicit,
+                        col_expr,
+                        &scalar_type,
+                    )?
+                    .lower_uncorrelated()
+                    .expect(
+                        "lower_uncorrelated should not fail given that there is no correlation \
+                            in the input col_expr",
+                    );
+                    column_casts.push(cast_expr);
+                }
+                table_casts.push(column_casts);
+            }
+
+            let connection = SourceConnection::SqlServer(SqlServerSourceConnection {
+                connection,
+                connection_id: connection_item.id(),
+                table_casts,
+                details,
+            });
+
+            // Like the postgres source, the SQL Server source only outputs
+            // data to its subsources.
+            let encoding = SourceDataEncoding::Single(DataEncoding::new(
+                DataEncodingInner::RowCodec(RelationDesc::empty()),
+            ));
+            (connection, encoding, Some(available_subsources))
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let (load_generator, available_subsources) =
                 load_generator_ast_to_generator(generator, options)?;
@@ -706,6 +1085,9 @@ pub fn plan_create_source(
             let tick_micros = match tick_interval {
                 Some(interval) => {
                     let micros: u64 = interval.as_microseconds().try_into()?;
+                    if micros == 0 {
+                        sql_bail!("TICK INTERVAL must be positive");
+                    }
                     Some(micros)
                 }
                 None => None,
@@ -716,39 +1098,378 @@ pub fn plan_create_source(
             });
             (connection, generator.data_encoding(), available_subsources)
         }
-        CreateSourceConnection::TestScript { desc_json } => {
-            scx.require_unsafe_mode("CREATE SOURCE ... FROM TEST SCRIPT")?;
-            let connection = SourceConnection::TestScript(TestScriptSourceConnection {
-                desc_json: desc_json.clone(),
+        CreateSourceConnection::File {
+            path,
+            compression,
+            options,
+        } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM FILE")?;
+
+            // Only the file name may contain wildcards, so that there is a
+            // single directory to watch.
+            let (dir, pattern) = match path.rsplit_once('/') {
+                Some((dir, pattern)) if path.starts_with('/') && !pattern.is_empty() => {
+                    (if dir.is_empty() { "/" } else { dir }, pattern)
+                }
+                _ => sql_bail!(
+                    "FILE path {} must be an absolute path to a file or file name pattern",
+                    path.quoted()
+                ),
+            };
+            if dir.contains(['*', '?', '[', '{']) {
+                sql_bail!(
+                    "FILE path {} may only contain wildcards in its last component",
+                    path.quoted()
+                );
+            }
+            let pattern = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()
+                .map_err(|e| sql_err!("parsing glob: {e}"))?;
+
+            let FileConfigOptionExtracted { poll_interval, .. } = options.clone().try_into()?;
+            let poll_interval = match poll_interval {
+                Some(interval) => interval.duration()?,
+                None => DEFAULT_FILE_POLL_INTERVAL,
+            };
+            if poll_interval == std::time::Duration::ZERO {
+                sql_bail!("POLL INTERVAL for FILE sources must be positive");
+            }
+
+            let mut include_filename = None;
+            let mut include_offset = None;
+            for (pos, item) in include_metadata.iter().enumerate() {
+                let column = |default: &str| IncludedColumnPos {
+                    name: item
+                        .alias
+                        .as_ref()
+                        .map(|a| a.to_string())
+                        .unwrap_or_else(|| default.into()),
+                    pos,
+                };
+                match item.ty {
+                    SourceIncludeMetadataType::Filename => {
+                        include_filename = Some(column("filename"))
+                    }
+                    SourceIncludeMetadataType::Offset => include_offset = Some(column("offset")),
+                    ref ty => bail_unsupported!(format!("INCLUDE {} with FILE sources", ty)),
+                }
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
+                sql_bail!("FILE sources do not support key decoding");
+            }
+            let connection = SourceConnection::File(FileSourceConnection {
+                dir: dir.to_string(),
+                pattern,
+                compression: plan_compression(compression),
+                poll_interval,
+                include_filename,
+                include_offset,
             });
-            // we just use the encoding from the format and envelope
-            let encoding = get_encoding(scx, format, &envelope, None)?;
             (connection, encoding, None)
         }
-    };
-    let (key_desc, value_desc) = encoding.desc()?;
+        CreateSourceConnection::Mqtt { url, options } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM MQTT")?;
 
-    let mut key_envelope = get_key_envelope(include_metadata, &envelope, &encoding)?;
+            let parsed_url: reqwest::Url = url
+                .parse()
+                .map_err(|e| sql_err!("invalid MQTT URL {}: {}", url.quoted(), e))?;
+            if !matches!(parsed_url.scheme(), "mqtt" | "mqtts") {
+                sql_bail!(
+                    "MQTT URL must use the mqtt or mqtts scheme, got {}",
+                    parsed_url.scheme().quoted()
+                );
+            }
+            if parsed_url.host_str().is_none() {
+                sql_bail!("MQTT URL {} must specify a host", url.quoted());
+            }
 
-    // Not all source envelopes are compatible with all source connections.
-    // Whoever constructs the source ingestion pipeline is responsible for
-    // choosing compatible envelopes and connections.
-    //
-    // TODO(guswynn): ambiguously assert which connections and envelopes are
-    // compatible in typechecking
-    //
-    // TODO: remove bails as more support for upsert is added.
-    let envelope = match &envelope {
-        // TODO: fixup key envelope
-        mz_sql_parser::ast::Envelope::None => UnplannedSourceEnvelope::None(key_envelope),
-        mz_sql_parser::ast::Envelope::Debezium(mode) => {
-            //TODO check that key envelope is not set
-            let (_before_idx, after_idx) = typecheck_debezium(&value_desc)?;
+            let MqttConfigOptionExtracted {
+                password,
+                topic,
+                user,
+                ..
+            } = options.clone().try_into()?;
 
-            match mode {
-                DbzMode::Plain => {
-                    UnplannedSourceEnvelope::Upsert(UpsertStyle::Debezium { after_idx })
-                }
+            let topic = match topic {
+                Some(topic) => topic,
+                None => sql_bail!("MQTT sources must specify TOPIC"),
+            };
+            validate_mqtt_topic_filter(&topic)?;
+            if user.is_some() != password.is_some() {
+                sql_bail!("MQTT sources must specify USER and PASSWORD together");
+            }
+
+            let mut include_topic = None;
+            for (pos, item) in include_metadata.iter().enumerate() {
+                match item.ty {
+                    SourceIncludeMetadataType::Topic => {
+                        include_topic = Some(IncludedColumnPos {
+                            name: item
+                                .alias
+                                .as_ref()
+                                .map(|a| a.to_string())
+                                .unwrap_or_else(|| "topic".into()),
+                            pos,
+                        });
+                    }
+                    SourceIncludeMetadataType::Key => {} // handled below
+                    ref ty => bail_unsupported!(format!("INCLUDE {} with MQTT sources", ty)),
+                }
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            let connection = SourceConnection::Mqtt(MqttSourceConnection {
+                url: url.clone(),
+                topic,
+                user,
+                password: password.map(|password| password.into()),
+                include_topic,
+            });
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::Nats { url, options } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM NATS")?;
+
+            let parsed_url: reqwest::Url = url
+                .parse()
+                .map_err(|e| sql_err!("invalid NATS URL {}: {}", url.quoted(), e))?;
+            if !matches!(parsed_url.scheme(), "nats" | "tls") {
+                sql_bail!(
+                    "NATS URL must use the nats or tls scheme, got {}",
+                    parsed_url.scheme().quoted()
+                );
+            }
+
+            let NatsConfigOptionExtracted {
+                consumer,
+                password,
+                stream,
+                subject,
+                token,
+                user,
+                ..
+            } = options.clone().try_into()?;
+
+            let stream = match stream {
+                Some(stream) => stream,
+                None => sql_bail!("NATS sources must specify STREAM"),
+            };
+            validate_nats_name("STREAM", &stream)?;
+            if let Some(consumer) = &consumer {
+                validate_nats_name("CONSUMER", consumer)?;
+            }
+            if let Some(subject) = &subject {
+                if subject.is_empty() || subject.contains(char::is_whitespace) {
+                    sql_bail!("invalid NATS SUBJECT {}", subject.quoted());
+                }
+            }
+            if user.is_some() != password.is_some() {
+                sql_bail!("NATS sources must specify USER and PASSWORD together");
+            }
+            if token.is_some() && user.is_some() {
+                sql_bail!("NATS sources cannot specify both TOKEN and USER");
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            let connection = SourceConnection::Nats(NatsSourceConnection {
+                url: url.clone(),
+                stream,
+                consumer,
+                subject,
+                user,
+                password: password.map(|password| password.into()),
+                token: token.map(|token| token.into()),
+            });
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::PubSub {
+            subscription,
+            options,
+        } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM PUBSUB")?;
+
+            match subscription.split('/').collect::<Vec<_>>()[..] {
+                ["projects", project, "subscriptions", name]
+                    if !project.is_empty() && !name.is_empty() => {}
+                _ => sql_bail!(
+                    "invalid Pub/Sub subscription {}: expected \
+                    projects/<project>/subscriptions/<subscription>",
+                    subscription.quoted()
+                ),
+            }
+
+            let PubSubConfigOptionExtracted {
+                ack_deadline,
+                credentials,
+                endpoint,
+                ..
+            } = options.clone().try_into()?;
+
+            let endpoint = endpoint.unwrap_or_else(|| DEFAULT_PUBSUB_ENDPOINT.into());
+            let parsed_endpoint: reqwest::Url = endpoint
+                .parse()
+                .map_err(|e| sql_err!("invalid ENDPOINT {}: {}", endpoint.quoted(), e))?;
+            if !matches!(parsed_endpoint.scheme(), "http" | "https") {
+                sql_bail!(
+                    "ENDPOINT for Pub/Sub sources must use the http or https scheme, got {}",
+                    parsed_endpoint.scheme().quoted()
+                );
+            }
+            let ack_deadline = match ack_deadline {
+                Some(interval) => interval.duration()?,
+                None => DEFAULT_PUBSUB_ACK_DEADLINE,
+            };
+            if !(MIN_PUBSUB_ACK_DEADLINE..=MAX_PUBSUB_ACK_DEADLINE).contains(&ack_deadline) {
+                sql_bail!(
+                    "ACK DEADLINE for Pub/Sub sources must be between {} and {} seconds",
+                    MIN_PUBSUB_ACK_DEADLINE.as_secs(),
+                    MAX_PUBSUB_ACK_DEADLINE.as_secs()
+                );
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            let connection = SourceConnection::PubSub(PubSubSourceConnection {
+                subscription: subscription.clone(),
+                endpoint,
+                credentials: credentials.map(|credentials| credentials.into()),
+                ack_deadline,
+            });
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::Sftp { url, options } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM SFTP")?;
+
+            let parsed_url: reqwest::Url = url
+                .parse()
+                .map_err(|e| sql_err!("invalid SFTP URL {}: {}", url.quoted(), e))?;
+            if parsed_url.scheme() != "sftp" {
+                sql_bail!(
+                    "SFTP URL must use the sftp scheme, got {}",
+                    parsed_url.scheme().quoted()
+                );
+            }
+            if parsed_url.host_str().is_none() {
+                sql_bail!("SFTP URL {} must specify a host", url.quoted());
+            }
+
+            let SftpConfigOptionExtracted {
+                password,
+                pattern,
+                poll_interval,
+                private_key,
+                user,
+                ..
+            } = options.clone().try_into()?;
+
+            let user = user.ok_or_else(|| sql_err!("SFTP sources must specify USER"))?;
+            if password.is_some() == private_key.is_some() {
+                sql_bail!("SFTP sources must specify exactly one of PASSWORD or PRIVATE KEY");
+            }
+            let poll_interval = match poll_interval {
+                Some(interval) => interval.duration()?,
+                None => DEFAULT_SFTP_POLL_INTERVAL,
+            };
+            if poll_interval == std::time::Duration::ZERO {
+                sql_bail!("POLL INTERVAL for SFTP sources must be positive");
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
+                sql_bail!("SFTP sources do not support key decoding");
+            }
+            let connection = SourceConnection::Sftp(SftpSourceConnection {
+                url: url.clone(),
+                user,
+                password: password.map(|password| password.into()),
+                private_key: private_key.map(|private_key| private_key.into()),
+                pattern: pattern
+                    .as_ref()
+                    .map(|p| {
+                        GlobBuilder::new(p)
+                            .literal_separator(true)
+                            .backslash_escape(true)
+                            .build()
+                    })
+                    .transpose()
+                    .map_err(|e| sql_err!("parsing glob: {e}"))?,
+                poll_interval,
+            });
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::TestScript { desc_json } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM TEST SCRIPT")?;
+            let connection = SourceConnection::TestScript(TestScriptSourceConnection {
+                desc_json: desc_json.clone(),
+            });
+            // we just use the encoding from the format and envelope
+            let encoding = get_encoding(scx, format, &envelope, None)?;
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::Webhook { .. } => {
+            unreachable!("webhook sources are planned by plan_create_webhook_source")
+        }
+    };
+    let CreateSourceOptionExtracted {
+        remote,
+        size,
+        timeline,
+        timestamp_column,
+        timestamp_interval,
+        timestamp_lateness,
+        timestamp_strategy,
+        decode_errors,
+        ignore_keys,
+        max_bytes_per_second,
+        max_messages_per_second,
+        numeric_overflow,
+        upsert_state,
+        seen: _,
+    } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
+
+    if let Some(numeric_overflow) = numeric_overflow {
+        if !encoding.set_numeric_overflow(numeric_overflow) {
+            sql_bail!("NUMERIC OVERFLOW is only supported by sources with FORMAT AVRO");
+        }
+    }
+
+    let (key_desc, value_desc) = encoding.desc()?;
+
+    let mut key_envelope = get_key_envelope(include_metadata, &envelope, &encoding)?;
+
+    // Not all source envelopes are compatible with all source connections.
+    // Whoever constructs the source ingestion pipeline is responsible for
+    // choosing compatible envelopes and connections.
+    //
+    // TODO(guswynn): ambiguously assert which connections and envelopes are
+    // compatible in typechecking
+    //
+    // TODO: remove bails as more support for upsert is added.
+    let mut source_imports = HashSet::new();
+    let envelope = match &envelope {
+        // TODO: fixup key envelope
+        mz_sql_parser::ast::Envelope::None => UnplannedSourceEnvelope::None(key_envelope),
+        mz_sql_parser::ast::Envelope::Debezium(mode) => {
+            //TODO check that key envelope is not set
+            let (before_idx, after_idx) = typecheck_debezium(&value_desc)?;
+
+            match mode {
+                DbzMode::Plain { tx_metadata } if tx_metadata.is_empty() => {
+                    UnplannedSourceEnvelope::Upsert(UpsertStyle::Debezium { after_idx })
+                }
+                DbzMode::Plain { tx_metadata } => {
+                    let tx_metadata =
+                        plan_debezium_transaction_metadata(scx, tx_metadata, &value_desc)?;
+                    source_imports.insert(tx_metadata.tx_metadata_global_id);
+                    UnplannedSourceEnvelope::Debezium(DebeziumEnvelope {
+                        before_idx,
+                        after_idx,
+                        dedup: typecheck_debezium_dedup(&value_desc, tx_metadata)?,
+                    })
+                }
             }
         }
         mz_sql_parser::ast::Envelope::Upsert => {
@@ -779,16 +1500,23 @@ pub fn plan_create_source(
     let metadata_columns = external_connection.metadata_columns();
     let metadata_column_types = external_connection.metadata_column_types();
     let metadata_desc = included_column_desc(metadata_columns.clone());
-    let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+    let (mut envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
 
-    let CreateSourceOptionExtracted {
-        remote,
-        size,
-        timeline,
-        timestamp_interval,
-        ignore_keys,
-        seen: _,
-    } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
+    if let Some(upsert_state) = upsert_state {
+        match &mut envelope {
+            SourceEnvelope::Upsert(upsert) => upsert.state_backend = upsert_state,
+            _ => sql_bail!(
+                "UPSERT STATE requires ENVELOPE UPSERT, or ENVELOPE DEBEZIUM without TRANSACTION METADATA"
+            ),
+        }
+    }
+
+    if let Some(decode_errors) = decode_errors {
+        match &mut envelope {
+            SourceEnvelope::None(none) => none.decode_errors = decode_errors,
+            _ => sql_bail!("DECODE ERRORS requires ENVELOPE NONE"),
+        }
+    }
 
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
@@ -843,16 +1571,95 @@ pub fn plan_create_source(
     let host_config = host_config(remote, size)?;
 
     let timestamp_interval = match timestamp_interval {
-        Some(timestamp_interval) => timestamp_interval.duration()?,
+        Some(timestamp_interval) => {
+            let timestamp_interval = timestamp_interval.duration()?;
+            if timestamp_interval < Duration::from_millis(1)
+                || timestamp_interval > Duration::from_secs(60 * 60)
+            {
+                sql_bail!("TIMESTAMP INTERVAL must be between 1ms and 1h");
+            }
+            timestamp_interval
+        }
         None => scx.catalog.config().timestamp_interval,
     };
 
+    let timestamp_strategy = timestamp_strategy.unwrap_or_default();
+    if timestamp_strategy == TimestampStrategy::Offset {
+        if !matches!(external_connection, SourceConnection::Kafka(_)) {
+            sql_bail!("TIMESTAMP STRATEGY 'offset' is only supported by Kafka sources");
+        }
+        // Offset-derived timestamps have nothing to do with wall clock time and must not be
+        // compared with the timestamps of other collections.
+        match &timeline {
+            Some(timeline) if timeline != "mz_epoch_ms" => (),
+            _ => sql_bail!("TIMESTAMP STRATEGY 'offset' requires a user-defined TIMELINE"),
+        }
+        if timestamp_column.is_some() {
+            sql_bail!("TIMESTAMP STRATEGY 'offset' cannot be used with TIMESTAMP COLUMN");
+        }
+    }
+
+    let event_time = match (timestamp_column, timestamp_lateness) {
+        (Some(column), lateness) => {
+            if !matches!(envelope, SourceEnvelope::None(_)) {
+                sql_bail!("TIMESTAMP COLUMN is only supported by sources with ENVELOPE NONE");
+            }
+            let column = ColumnName::from(column);
+            let (column, typ) = desc
+                .get_by_name(&column)
+                .ok_or_else(|| sql_err!("No such column: {}", column))?;
+            if !matches!(
+                typ.scalar_type,
+                ScalarType::Timestamp | ScalarType::TimestampTz
+            ) {
+                sql_bail!(
+                    "TIMESTAMP COLUMN must have type timestamp or timestamp with time zone, not {}",
+                    scx.humanize_scalar_type(&typ.scalar_type)
+                );
+            }
+            let lateness = match lateness {
+                Some(lateness) => lateness.duration()?,
+                None => timestamp_interval,
+            };
+            if lateness < timestamp_interval {
+                sql_bail!("TIMESTAMP LATENESS must be at least the TIMESTAMP INTERVAL");
+            }
+            Some(EventTimeConfig { column, lateness })
+        }
+        (None, Some(_)) => sql_bail!("TIMESTAMP LATENESS requires TIMESTAMP COLUMN"),
+        (None, None) => None,
+    };
+
     let source_desc = SourceDesc {
         connection: external_connection,
         encoding,
         envelope: envelope.clone(),
         metadata_columns: metadata_column_types,
         timestamp_interval,
+        timestamp_strategy,
+        event_time,
+        max_ingest_rate: IngestRateLimit {
+            bytes_per_second: max_bytes_per_second,
+            messages_per_second: max_messages_per_second,
+        },
+    };
+
+    // Sources that write their decode errors to a subsource have an additional output for them,
+    // after the output of the source itself.
+    let available_subsources = match (&envelope, available_subsources) {
+        (
+            SourceEnvelope::None(NoneEnvelope {
+                decode_errors: DecodeErrorPolicy::Subsource,
+                ..
+            }),
+            available_subsources,
+        ) => {
+            if available_subsources.is_some() {
+                sql_bail!("DECODE ERRORS = 'subsource' is not supported by multi-output sources");
+            }
+            Some(HashMap::from([(decode_errors_subsource_name(), 1)]))
+        }
+        (_, available_subsources) => available_subsources,
     };
 
     let (available_subsources, requested_subsources) = match (available_subsources, subsources) {
@@ -860,8 +1667,8 @@ pub fn plan_create_source(
             let mut requested_subsources = vec![];
             for subsource in subsources {
                 let (name, target) = match subsource {
-                    CreateSourceSubsource::Resolved(name, target) => (name, target),
-                    CreateSourceSubsource::Aliased(_, _) | CreateSourceSubsource::Bare(_) => {
+                    CreateSourceSubsource::Resolved(name, _, target) => (name, target),
+                    CreateSourceSubsource::Aliased(..) | CreateSourceSubsource::Bare(..) => {
                         sql_bail!(
                             "[internal error] subsources should be resolved during purification"
                         )
@@ -927,10 +1734,11 @@ pub fn plan_create_source(
 
     let source = Source {
         create_sql,
-        ingestion: Some(Ingestion {
+        data_source: DataSourceDesc::Ingestion(Ingestion {
             desc: source_desc,
-            // Currently no source reads from another source
-            source_imports: HashSet::new(),
+            // Only Debezium sources with transaction metadata read from
+            // another source.
+            source_imports,
             subsource_exports,
         }),
         desc,
@@ -945,6 +1753,90 @@ pub fn plan_create_source(
     }))
 }
 
+/// Plans a source whose data arrives in the bodies of requests to its webhook
+/// endpoint, rather than from an external system.
+fn plan_create_webhook_source(
+    scx: &StatementContext,
+    stmt: CreateSourceStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_unsafe_mode("creating webhook sources")?;
+
+    let CreateSourceStatement {
+        name,
+        col_names,
+        connection,
+        envelope,
+        if_not_exists,
+        format,
+        key_constraint,
+        include_metadata,
+        with_options,
+        subsources,
+    } = &stmt;
+
+    let (secret, check) = match connection {
+        CreateSourceConnection::Webhook { secret, check } => (secret, check.clone()),
+        _ => unreachable!("only called for webhook sources"),
+    };
+    if !col_names.is_empty() {
+        sql_bail!("webhook sources do not support renaming columns");
+    }
+    if format.is_some() {
+        sql_bail!("webhook sources do not support FORMAT; request bodies must be JSON");
+    }
+    if envelope.is_some() {
+        sql_bail!("webhook sources do not support ENVELOPE");
+    }
+    if key_constraint.is_some() {
+        sql_bail!("webhook sources do not support key constraints");
+    }
+    if !include_metadata.is_empty() {
+        bail_unsupported!("INCLUDE metadata with webhook sources");
+    }
+    if !with_options.is_empty() {
+        sql_bail!("webhook sources do not support WITH options");
+    }
+    if subsources.is_some() {
+        sql_bail!("webhook sources do not support subsources");
+    }
+
+    let secret = match secret {
+        Some(secret) => {
+            let item = scx.get_item_by_resolved_name(secret)?;
+            if item.item_type() != CatalogItemType::Secret {
+                return Err(PlanError::InvalidSecret(secret.clone()));
+            }
+            Some(item.id())
+        }
+        None => None,
+    };
+
+    // Each request appends its body to the source as a single row.
+    let desc = RelationDesc::empty().with_column("body", ScalarType::Jsonb.nullable(false));
+    let check = check
+        .map(|expr| query::plan_webhook_check_expr(scx, &desc, expr))
+        .transpose()?;
+
+    let if_not_exists = *if_not_exists;
+    let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name.clone())?)?;
+    let create_sql = normalize::create_statement(scx, Statement::CreateSource(stmt))?;
+
+    let source = Source {
+        create_sql,
+        data_source: DataSourceDesc::Webhook { secret, check },
+        desc,
+    };
+
+    // Webhook sources are written at the same timestamps as tables.
+    Ok(Plan::CreateSource(CreateSourcePlan {
+        name,
+        source,
+        if_not_exists,
+        timeline: Timeline::EpochMilliseconds,
+        host_config: StorageHostConfig::Undefined,
+    }))
+}
+
 pub fn plan_create_subsource(
     scx: &StatementContext,
     stmt: CreateSubsourceStatement<Aug>,
@@ -1037,7 +1929,7 @@ pub fn plan_create_subsource(
 
     let source = Source {
         create_sql,
-        ingestion: None,
+        data_source: DataSourceDesc::Source,
         desc,
     };
 
@@ -1050,41 +1942,228 @@ pub fn plan_create_subsource(
     }))
 }
 
-generate_extracted_config!(
-    LoadGeneratorOption,
-    (TickInterval, Interval),
-    (ScaleFactor, f64)
-);
-
-pub(crate) fn load_generator_ast_to_generator(
-    loadgen: &mz_sql_parser::ast::LoadGenerator,
-    options: &[LoadGeneratorOption<Aug>],
-) -> Result<
-    (
-        mz_storage::types::sources::LoadGenerator,
-        Option<HashMap<FullObjectName, (usize, RelationDesc)>>,
-    ),
-    PlanError,
-> {
-    let load_generator = match loadgen {
-        mz_sql_parser::ast::LoadGenerator::Auction => {
-            mz_storage::types::sources::LoadGenerator::Auction
-        }
-        mz_sql_parser::ast::LoadGenerator::Counter => {
-            mz_storage::types::sources::LoadGenerator::Counter
+/// Returns the type of the column into which a MySQL source ingests the
+/// upstream column `column`.
+///
+/// Unsigned integers map to the unsigned type of the same width, or the next
+/// wider one for the odd-sized `tinyint` and `mediumint`. `time` values can
+/// exceed a day, so they map to `interval` rather than `time`.
+pub(crate) fn mysql_column_type(column: &MySqlColumnDesc) -> Result<mz_pgrepr::Type, PlanError> {
+    use mz_pgrepr::Type;
+
+    let unsigned = column.is_unsigned();
+    Ok(match column.data_type.as_str() {
+        "tinyint" | "smallint" if unsigned => Type::UInt2,
+        "tinyint" | "smallint" | "year" => Type::Int2,
+        "mediumint" | "int" if unsigned => Type::UInt4,
+        "mediumint" | "int" => Type::Int4,
+        "bigint" if unsigned => Type::UInt8,
+        "bigint" => Type::Int8,
+        "decimal" => Type::Numeric { constraints: None },
+        "float" => Type::Float4,
+        "double" => Type::Float8,
+        "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum" | "set" => {
+            Type::Text
         }
-        mz_sql_parser::ast::LoadGenerator::Tpch => {
-            let LoadGeneratorOptionExtracted { scale_factor, .. } = options.to_vec().try_into()?;
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => Type::Bytea,
+        "date" => Type::Date,
+        "datetime" => Type::Timestamp { precision: None },
+        "timestamp" => Type::TimestampTz { precision: None },
+        "time" => Type::Interval { constraints: None },
+        "json" => Type::Jsonb,
+        _ => sql_bail!(
+            "column {} has type {}, which MySQL sources do not support",
+            column.name.quoted(),
+            column.column_type.quoted()
+        ),
+    })
+}
 
-            // Default to 0.01 scale factor (=10MB).
-            let sf: f64 = scale_factor.unwrap_or(0.01);
-            if !sf.is_finite() || sf < 0.0 {
-                sql_bail!("unsupported scale factor {sf}");
-            }
+/// Returns the type of the column into which a SQL Server source ingests the
+/// upstream column `column`.
+///
+/// `money` and `smallmoney` map to `numeric`, which represents their values
+/// exactly.
+pub(crate) fn sql_server_column_type(
+    column: &SqlServerColumnDesc,
+) -> Result<mz_pgrepr::Type, PlanError> {
+    use mz_pgrepr::Type;
+
+    Ok(match column.data_type.as_str() {
+        "bit" => Type::Bool,
+        "tinyint" | "smallint" => Type::Int2,
+        "int" => Type::Int4,
+        "bigint" => Type::Int8,
+        "real" => Type::Float4,
+        "float" => Type::Float8,
+        "decimal" | "numeric" | "money" | "smallmoney" => Type::Numeric { constraints: None },
+        "char" | "varchar" | "text" | "nchar" | "nvarchar" | "ntext" | "xml" => Type::Text,
+        "binary" | "varbinary" | "image" => Type::Bytea,
+        "date" => Type::Date,
+        "time" => Type::Time { precision: None },
+        "smalldatetime" | "datetime" | "datetime2" => Type::Timestamp { precision: None },
+        "datetimeoffset" => Type::TimestampTz { precision: None },
+        "uniqueidentifier" => Type::Uuid,
+        _ => sql_bail!(
+            "column {} has type {}, which SQL Server sources do not support",
+            column.name.quoted(),
+            column.data_type.quoted()
+        ),
+    })
+}
 
-            let f_to_i = |multiplier: f64| -> Result<i64, PlanError> {
-                let total = (sf * multiplier).floor();
-                let mut i =
+fn plan_compression(compression: &Compression) -> mz_storage::types::sources::Compression {
+    match compression {
+        Compression::Auto => mz_storage::types::sources::Compression::Auto,
+        Compression::Bzip2 => mz_storage::types::sources::Compression::Bzip2,
+        Compression::Gzip => mz_storage::types::sources::Compression::Gzip,
+        Compression::Zstd => mz_storage::types::sources::Compression::Zstd,
+        Compression::None => mz_storage::types::sources::Compression::None,
+    }
+}
+
+/// How often FILE sources check for new lines, unless otherwise specified.
+const DEFAULT_FILE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+generate_extracted_config!(FileConfigOption, (PollInterval, Interval));
+
+generate_extracted_config!(
+    MqttConfigOption,
+    (Password, with_options::Secret),
+    (Topic, String),
+    (User, StringOrSecret)
+);
+
+/// Checks that `filter` is a valid MQTT topic filter, in which the `+` and `#`
+/// wildcards occupy entire levels and `#` only appears in the last level.
+fn validate_mqtt_topic_filter(filter: &str) -> Result<(), PlanError> {
+    let levels: Vec<_> = filter.split('/').collect();
+    let valid = !filter.is_empty()
+        && levels.iter().enumerate().all(|(i, level)| match *level {
+            "+" => true,
+            "#" => i == levels.len() - 1,
+            level => !level.contains(['+', '#']),
+        });
+    if !valid {
+        sql_bail!("invalid MQTT TOPIC {}", filter.quoted());
+    }
+    Ok(())
+}
+
+generate_extracted_config!(
+    NatsConfigOption,
+    (Consumer, String),
+    (Password, with_options::Secret),
+    (Stream, String),
+    (Subject, String),
+    (Token, with_options::Secret),
+    (User, StringOrSecret)
+);
+
+/// Checks that `name`, the value of the `option` of a NATS source, is a valid
+/// name for a JetStream stream or consumer.
+fn validate_nats_name(option: &str, name: &str) -> Result<(), PlanError> {
+    if name.is_empty()
+        || name.contains(|c: char| {
+            c.is_whitespace() || c.is_control() || matches!(c, '.' | '*' | '>' | '/' | '\\')
+        })
+    {
+        sql_bail!(
+            "invalid NATS {} {}: names must be non-empty and may not contain whitespace, \
+            '.', '*', '>', '/', or '\\'",
+            option,
+            name.quoted()
+        );
+    }
+    Ok(())
+}
+
+/// The Pub/Sub API that Pub/Sub sources connect to, unless otherwise
+/// specified.
+const DEFAULT_PUBSUB_ENDPOINT: &str = "https://pubsub.googleapis.com";
+
+/// How long Pub/Sub waits for messages to be acknowledged, unless otherwise
+/// specified.
+const DEFAULT_PUBSUB_ACK_DEADLINE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// The shortest acknowledgement deadline that Pub/Sub accepts.
+const MIN_PUBSUB_ACK_DEADLINE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// The longest acknowledgement deadline that Pub/Sub accepts.
+const MAX_PUBSUB_ACK_DEADLINE: std::time::Duration = std::time::Duration::from_secs(600);
+
+generate_extracted_config!(
+    PubSubConfigOption,
+    (AckDeadline, Interval),
+    (Credentials, with_options::Secret),
+    (Endpoint, String)
+);
+
+/// How often SFTP sources check for new files, unless otherwise specified.
+const DEFAULT_SFTP_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+generate_extracted_config!(
+    SftpConfigOption,
+    (Password, with_options::Secret),
+    (Pattern, String),
+    (PollInterval, Interval),
+    (PrivateKey, with_options::Secret),
+    (User, StringOrSecret)
+);
+
+/// The upstream name of the subsource that receives the messages a source
+/// cannot decode, when the source is created with `DECODE ERRORS = 'subsource'`.
+pub(crate) fn decode_errors_subsource_name() -> FullObjectName {
+    FullObjectName {
+        database: RawDatabaseSpecifier::Ambient,
+        schema: "mz_source".into(),
+        item: "decode_errors".into(),
+    }
+}
+
+generate_extracted_config!(
+    LoadGeneratorOption,
+    (TickInterval, Interval),
+    (ScaleFactor, f64)
+);
+
+pub(crate) fn load_generator_ast_to_generator(
+    loadgen: &mz_sql_parser::ast::LoadGenerator,
+    options: &[LoadGeneratorOption<Aug>],
+) -> Result<
+    (
+        mz_storage::types::sources::LoadGenerator,
+        Option<HashMap<FullObjectName, (usize, RelationDesc)>>,
+    ),
+    PlanError,
+> {
+    if !matches!(loadgen, mz_sql_parser::ast::LoadGenerator::Tpch)
+        && options
+            .iter()
+            .any(|o| o.name == LoadGeneratorOptionName::ScaleFactor)
+    {
+        sql_bail!("SCALE FACTOR is only supported by the TPCH load generator");
+    }
+
+    let load_generator = match loadgen {
+        mz_sql_parser::ast::LoadGenerator::Auction => {
+            mz_storage::types::sources::LoadGenerator::Auction
+        }
+        mz_sql_parser::ast::LoadGenerator::Counter => {
+            mz_storage::types::sources::LoadGenerator::Counter
+        }
+        mz_sql_parser::ast::LoadGenerator::Tpch => {
+            let LoadGeneratorOptionExtracted { scale_factor, .. } = options.to_vec().try_into()?;
+
+            // Default to 0.01 scale factor (=10MB).
+            let sf: f64 = scale_factor.unwrap_or(0.01);
+            if !sf.is_finite() || sf < 0.0 {
+                sql_bail!("unsupported scale factor {sf}");
+            }
+
+            let f_to_i = |multiplier: f64| -> Result<i64, PlanError> {
+                let total = (sf * multiplier).floor();
+                let mut i =
                     f64_to_i64(total).ok_or_else(|| sql_err!("unsupported scale factor {sf}"))?;
                 if i < 1 {
                     i = 1;
@@ -1154,10 +2233,201 @@ fn typecheck_debezium(value_desc: &RelationDesc) -> Result<(usize, usize), PlanE
     Ok((before_idx, after_idx))
 }
 
+/// Determines how to deduplicate a Debezium stream by the position of each
+/// update in the upstream database's log, whose fields depend on the kind of
+/// upstream database.
+fn typecheck_debezium_dedup(
+    value_desc: &RelationDesc,
+    tx_metadata: DebeziumTransactionMetadata,
+) -> Result<DebeziumDedupProjection, PlanError> {
+    let (op_idx, op_ty) = value_desc
+        .get_by_name(&"op".into())
+        .ok_or_else(|| sql_err!("'op' column missing from debezium input"))?;
+    if op_ty.scalar_type != ScalarType::String {
+        sql_bail!("'op' column must be of type string");
+    }
+
+    let (source_idx, source_ty) = value_desc
+        .get_by_name(&"source".into())
+        .ok_or_else(|| sql_err!("'source' column missing from debezium input"))?;
+    let source_fields = match &source_ty.scalar_type {
+        ScalarType::Record { fields, .. } => fields,
+        _ => sql_bail!("'source' column must be of type record"),
+    };
+    // Finds a field of the `source` record, which must have one of `types`
+    // if present.
+    let field = |name: &str, types: &[ScalarType]| -> Result<Option<usize>, PlanError> {
+        match source_fields
+            .iter()
+            .position(|(field, _)| field.as_str() == name)
+        {
+            Some(idx) if types.contains(&source_fields[idx].1.scalar_type) => Ok(Some(idx)),
+            Some(idx) => sql_bail!(
+                "'source.{}' field has unexpected type {:?}",
+                name,
+                source_fields[idx].1.scalar_type
+            ),
+            None => Ok(None),
+        }
+    };
+
+    let snapshot_idx = field("snapshot", &[ScalarType::Bool, ScalarType::String])?
+        .ok_or_else(|| sql_err!("'source.snapshot' field missing from debezium input"))?;
+
+    let mysql = (
+        field("file", &[ScalarType::String])?,
+        field("pos", &[ScalarType::Int64])?,
+        field("row", &[ScalarType::Int32])?,
+    );
+    let postgres = (
+        field("sequence", &[ScalarType::String])?,
+        field("lsn", &[ScalarType::Int64])?,
+    );
+    let sql_server = (
+        field("change_lsn", &[ScalarType::String])?,
+        field("event_serial_no", &[ScalarType::Int64])?,
+    );
+    let source_projection = match (mysql, postgres, sql_server) {
+        ((Some(file), Some(pos), Some(row)), _, _) => {
+            DebeziumSourceProjection::MySql { file, pos, row }
+        }
+        (_, (Some(sequence), Some(lsn)), _) => DebeziumSourceProjection::Postgres { sequence, lsn },
+        (_, _, (Some(change_lsn), Some(event_serial_no))) => DebeziumSourceProjection::SqlServer {
+            change_lsn,
+            event_serial_no,
+        },
+        _ => sql_bail!(
+            "'source' column does not describe a MySQL, PostgreSQL or SQL Server log position"
+        ),
+    };
+
+    Ok(DebeziumDedupProjection {
+        op_idx,
+        source_idx,
+        snapshot_idx,
+        source_projection,
+        tx_metadata: Some(tx_metadata),
+    })
+}
+
+/// Plans `ENVELOPE DEBEZIUM (TRANSACTION METADATA (...))`, checking that the
+/// transaction metadata source and the `transaction` column of the Debezium
+/// input have the shape Debezium gives them.
+fn plan_debezium_transaction_metadata(
+    scx: &StatementContext,
+    options: &[DbzTxMetadataOption<Aug>],
+    value_desc: &RelationDesc,
+) -> Result<DebeziumTransactionMetadata, PlanError> {
+    let mut source = None;
+    let mut collection = None;
+    for option in options {
+        match option {
+            DbzTxMetadataOption::Source(name) => {
+                if source.replace(name).is_some() {
+                    sql_bail!("TRANSACTION METADATA SOURCE specified more than once");
+                }
+            }
+            DbzTxMetadataOption::Collection(value) => {
+                let value = String::try_from_value(value.clone())?;
+                if collection.replace(value).is_some() {
+                    sql_bail!("TRANSACTION METADATA COLLECTION specified more than once");
+                }
+            }
+        }
+    }
+    let source = source.ok_or_else(|| sql_err!("TRANSACTION METADATA requires SOURCE"))?;
+    let tx_data_collection_name =
+        collection.ok_or_else(|| sql_err!("TRANSACTION METADATA requires COLLECTION"))?;
+
+    let item = scx.get_item_by_resolved_name(source)?;
+    let full_name = scx.catalog.resolve_full_name(item.name());
+    if item.item_type() != CatalogItemType::Source {
+        sql_bail!(
+            "provided TRANSACTION METADATA SOURCE {} is not a source",
+            full_name
+        );
+    }
+    let tx_desc = item.desc(&full_name)?;
+
+    let (tx_status_idx, tx_status_ty) = tx_desc
+        .get_by_name(&"status".into())
+        .ok_or_else(|| sql_err!("'status' column missing from debezium transaction metadata"))?;
+    let (tx_transaction_id_idx, tx_transaction_id_ty) = tx_desc
+        .get_by_name(&"id".into())
+        .ok_or_else(|| sql_err!("'id' column missing from debezium transaction metadata"))?;
+    let (tx_data_collections_idx, tx_data_collections_ty) = tx_desc
+        .get_by_name(&"data_collections".into())
+        .ok_or_else(|| {
+            sql_err!("'data_collections' column missing from debezium transaction metadata")
+        })?;
+    if *tx_status_ty != ScalarType::String.nullable(false) {
+        sql_bail!("'status' column must be of type non-nullable string");
+    }
+    if *tx_transaction_id_ty != ScalarType::String.nullable(false) {
+        sql_bail!("'id' column must be of type non-nullable string");
+    }
+    let data_collection_fields = match &tx_data_collections_ty.scalar_type {
+        ScalarType::List { element_type, .. } | ScalarType::Array(element_type) => {
+            match &**element_type {
+                ScalarType::Record { fields, .. } => fields,
+                _ => sql_bail!("'data_collections' column must contain records"),
+            }
+        }
+        _ => sql_bail!("'data_collections' column must be of type list or array"),
+    };
+    let tx_data_collections_data_collection_idx = data_collection_fields
+        .iter()
+        .position(|(name, ty)| {
+            name.as_str() == "data_collection" && ty == &ScalarType::String.nullable(false)
+        })
+        .ok_or_else(|| {
+            sql_err!("'data_collections' records must have a non-nullable string 'data_collection' field")
+        })?;
+    let tx_data_collections_event_count_idx = data_collection_fields
+        .iter()
+        .position(|(name, ty)| {
+            name.as_str() == "event_count"
+                && matches!(
+                    ty.scalar_type,
+                    ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64
+                )
+        })
+        .ok_or_else(|| {
+            sql_err!("'data_collections' records must have an integer 'event_count' field")
+        })?;
+
+    let (data_transaction_idx, data_transaction_ty) = value_desc
+        .get_by_name(&"transaction".into())
+        .ok_or_else(|| sql_err!("'transaction' column missing from debezium input"))?;
+    let data_transaction_id_idx = match &data_transaction_ty.scalar_type {
+        ScalarType::Record { fields, .. } => fields
+            .iter()
+            .position(|(name, ty)| {
+                name.as_str() == "id" && ty == &ScalarType::String.nullable(false)
+            })
+            .ok_or_else(|| {
+                sql_err!("'transaction' column must have a non-nullable string 'id' field")
+            })?,
+        _ => sql_bail!("'transaction' column must be of type record"),
+    };
+
+    Ok(DebeziumTransactionMetadata {
+        tx_metadata_global_id: item.id(),
+        tx_status_idx,
+        tx_transaction_id_idx,
+        tx_data_collections_idx,
+        tx_data_collections_data_collection_idx,
+        tx_data_collections_event_count_idx,
+        tx_data_collection_name,
+        data_transaction_idx,
+        data_transaction_id_idx,
+    })
+}
+
 fn get_encoding(
     scx: &StatementContext,
     format: &CreateSourceFormat<Aug>,
-    envelope: &Envelope,
+    envelope: &Envelope<Aug>,
     connection: Option<&CreateSourceConnection<Aug>>,
 ) -> Result<SourceDataEncoding, PlanError> {
     let encoding = match format {
@@ -1180,10 +2450,12 @@ fn get_encoding(
         && matches!(envelope, Envelope::None);
     let encoding = encoding.into_source_data_encoding(force_nullable_keys);
 
-    let requires_keyvalue = matches!(
-        envelope,
-        Envelope::Debezium(DbzMode::Plain) | Envelope::Upsert
-    );
+    // Deduplicating by transaction metadata does not need the key.
+    let requires_keyvalue = match envelope {
+        Envelope::Debezium(DbzMode::Plain { tx_metadata }) => tx_metadata.is_empty(),
+        Envelope::Upsert => true,
+        Envelope::None | Envelope::CdcV2 => false,
+    };
     let is_keyvalue = matches!(encoding, SourceDataEncoding::KeyValue { .. });
     if requires_keyvalue && !is_keyvalue {
         sql_bail!("ENVELOPE [DEBEZIUM] UPSERT requires that KEY FORMAT be specified");
@@ -1282,11 +2554,13 @@ fn get_encoding_inner(
                         schema: key_schema,
                         csr_connection: csr_connection.clone(),
                         confluent_wire_format,
+                        numeric_overflow: NumericOverflowPolicy::Error,
                     }),
                     value: DataEncodingInner::Avro(AvroEncoding {
                         schema: value_schema,
                         csr_connection,
                         confluent_wire_format,
+                        numeric_overflow: NumericOverflowPolicy::Error,
                     }),
                 });
             } else {
@@ -1294,6 +2568,7 @@ fn get_encoding_inner(
                     schema: value_schema,
                     csr_connection,
                     confluent_wire_format,
+                    numeric_overflow: NumericOverflowPolicy::Error,
                 })
             }
         }
@@ -1361,7 +2636,13 @@ fn get_encoding_inner(
                 regex: mz_repr::adt::regex::Regex(regex),
             })
         }
-        Format::Csv { columns, delimiter } => {
+        Format::Csv {
+            columns,
+            delimiter,
+            quote,
+            escape,
+            typed_columns,
+        } => {
             let columns = match columns {
                 CsvColumns::Header { names } => {
                     if names.is_empty() {
@@ -1373,23 +2654,146 @@ fn get_encoding_inner(
                 }
                 CsvColumns::Count(n) => ColumnSpec::Count(*n),
             };
+            if quote == delimiter {
+                sql_bail!("CSV QUOTE must differ from the delimiter");
+            }
+            let typed_columns = if typed_columns.is_empty() {
+                None
+            } else {
+                let desc = plan_format_columns(scx, "CSV", typed_columns)?;
+                let names = columns.names();
+                for (name, _) in desc.iter() {
+                    if !names.iter().any(|n| n == name.as_str()) {
+                        sql_bail!(
+                            "CSV COLUMNS refers to unknown column {}; the CSV columns are: {}",
+                            name.as_str().quoted(),
+                            names.join(", ")
+                        );
+                    }
+                }
+                Some(desc)
+            };
             DataEncodingInner::Csv(CsvEncoding {
                 columns,
-                delimiter: match *delimiter as u32 {
-                    0..=127 => *delimiter as u8,
-                    _ => sql_bail!("CSV delimiter must be an ASCII character"),
-                },
+                delimiter: plan_csv_character("delimiter", *delimiter)?,
+                quote: plan_csv_character("QUOTE", *quote)?,
+                escape: escape
+                    .map(|escape| plan_csv_character("ESCAPE", escape))
+                    .transpose()?,
+                typed_columns,
+            })
+        }
+        Format::Json {
+            csr_connection: Some(_),
+            ..
+        } => bail_unsupported!("FORMAT JSON USING CONFLUENT SCHEMA REGISTRY for sources"),
+        Format::Json {
+            csr_connection: None,
+            columns,
+            reject_unknown_fields,
+        } => {
+            let columns = if columns.is_empty() {
+                if *reject_unknown_fields {
+                    sql_bail!("REJECT UNKNOWN FIELDS requires FORMAT JSON to declare columns");
+                }
+                None
+            } else {
+                Some(plan_format_columns(scx, "JSON", columns)?)
+            };
+            DataEncodingInner::Json(JsonEncoding {
+                columns,
+                reject_unknown_fields: *reject_unknown_fields,
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
         Format::Text => DataEncodingInner::Text,
+        Format::Parquet { columns } => {
+            if columns.is_empty() {
+                sql_bail!("[internal error] Parquet columns should be discovered in purify")
+            }
+            DataEncodingInner::Parquet(plan_format_columns(scx, "PARQUET", columns)?)
+        }
+        Format::AvroOcf => bail_unsupported!("AVRO OCF sources"),
     }))
 }
 
+/// Plans the columns declared by `FORMAT JSON` or `FORMAT PARQUET`, which may
+/// only be constrained to be (not) null.
+fn plan_csv_character(name: &str, c: char) -> Result<u8, PlanError> {
+    match c as u32 {
+        0..=127 => Ok(c as u8),
+        _ => sql_bail!("CSV {} must be an ASCII character", name),
+    }
+}
+
+fn plan_format_columns(
+    scx: &StatementContext,
+    format: &str,
+    columns: &[ColumnDef<Aug>],
+) -> Result<RelationDesc, PlanError> {
+    let mut desc = RelationDesc::empty();
+    for c in columns {
+        let ty = query::scalar_type_from_sql(scx, &c.data_type)?;
+        let mut nullable = true;
+        for option in &c.options {
+            match &option.option {
+                ColumnOption::NotNull => nullable = false,
+                ColumnOption::Null => nullable = true,
+                other => bail_unsupported!(format!(
+                    "FORMAT {} with column constraint: {}",
+                    format, other
+                )),
+            }
+        }
+        let name = normalize::column_name(c.name.clone());
+        if desc.get_by_name(&name).is_some() {
+            sql_bail!("column {} specified more than once", name.as_str().quoted());
+        }
+        desc = desc.with_column(name, ty.nullable(nullable));
+    }
+    Ok(desc)
+}
+
+/// Determines how the value of a header requested via `INCLUDE HEADER` is
+/// decoded. Headers without an explicit format are included as raw bytes.
+fn get_header_encoding(
+    scx: &StatementContext,
+    format: Option<&Format<Aug>>,
+) -> Result<HeaderEncoding, PlanError> {
+    Ok(match format {
+        None | Some(Format::Bytes) => HeaderEncoding::Bytes,
+        Some(Format::Text) => HeaderEncoding::Text,
+        Some(Format::Json {
+            csr_connection: None,
+            columns,
+            reject_unknown_fields: false,
+        }) if columns.is_empty() => HeaderEncoding::Json,
+        Some(Format::Json {
+            csr_connection: None,
+            ..
+        }) => bail_unsupported!("INCLUDE HEADER with FORMAT JSON columns"),
+        Some(Format::Json {
+            csr_connection: Some(_),
+            ..
+        }) => bail_unsupported!("INCLUDE HEADER with FORMAT JSON USING CONFLUENT SCHEMA REGISTRY"),
+        Some(format @ Format::Avro(AvroSchema::InlineSchema { .. })) => {
+            match get_encoding_inner(scx, format)? {
+                SourceDataEncodingInner::Single(DataEncodingInner::Avro(avro)) => {
+                    HeaderEncoding::Avro(avro)
+                }
+                _ => unreachable!("inline Avro schemas always produce a single Avro encoding"),
+            }
+        }
+        Some(Format::Avro(AvroSchema::Csr { .. })) => {
+            bail_unsupported!("INCLUDE HEADER with FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY")
+        }
+        Some(_) => sql_bail!("INCLUDE HEADER only supports FORMAT BYTES, TEXT, JSON, or AVRO"),
+    })
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
-    included_items: &[SourceIncludeMetadata],
-    envelope: &Envelope,
+    included_items: &[SourceIncludeMetadata<Aug>],
+    envelope: &Envelope<Aug>,
     encoding: &SourceDataEncoding,
 ) -> Result<KeyEnvelope, PlanError> {
     let key_definition = included_items
@@ -1428,10 +2832,11 @@ fn get_unnamed_key_envelope(key: &DataEncoding) -> Result<KeyEnvelope, PlanError
     //
     // Otherwise it gets the names of the columns in the type
     let is_composite = match key.inner {
-        DataEncodingInner::RowCodec(_) => {
+        DataEncodingInner::RowCodec(_) | DataEncodingInner::Parquet(_) => {
             sql_bail!("{} sources cannot use INCLUDE KEY", key.op_name())
         }
         DataEncodingInner::Bytes | DataEncodingInner::Text => false,
+        DataEncodingInner::Json(JsonEncoding { columns, .. }) => columns.is_some(),
         DataEncodingInner::Avro(_)
         | DataEncodingInner::Csv(_)
         | DataEncodingInner::Protobuf(_)
@@ -1550,6 +2955,13 @@ pub fn describe_create_materialized_view(
     Ok(StatementDesc::new(None))
 }
 
+generate_extracted_config!(
+    MaterializedViewOption,
+    (TargetBatchSize, u64),
+    (MaxBatchStaleness, Interval),
+    (LogicalCompactionWindow, OptionalInterval)
+);
+
 pub fn plan_create_materialized_view(
     scx: &StatementContext,
     mut stmt: CreateMaterializedViewStatement<Aug>,
@@ -1570,6 +2982,41 @@ pub fn plan_create_materialized_view(
     let partial_name = normalize::unresolved_object_name(stmt.name)?;
     let name = scx.allocate_qualified_name(partial_name.clone())?;
 
+    let MaterializedViewOptionExtracted {
+        target_batch_size,
+        max_batch_staleness,
+        logical_compaction_window,
+        ..
+    } = stmt.with_options.try_into()?;
+
+    let max_staleness = match max_batch_staleness {
+        Some(interval) => interval.duration()?,
+        None if target_batch_size.is_some() => {
+            sql_bail!("TARGET BATCH SIZE requires MAX BATCH STALENESS")
+        }
+        None => Duration::ZERO,
+    };
+    if target_batch_size == Some(0) {
+        sql_bail!("TARGET BATCH SIZE must be greater than zero");
+    }
+    let batching = PersistSinkBatching {
+        target_bytes: target_batch_size,
+        max_staleness,
+    };
+
+    let compaction_window = match logical_compaction_window {
+        Some(OptionalInterval(window)) => Some(
+            window
+                .map(|interval| {
+                    let duration = interval.duration()?;
+                    Timestamp::try_from(duration)
+                        .map_err(|_| sql_err!("LOGICAL COMPACTION WINDOW is too large"))
+                })
+                .transpose()?,
+        ),
+        None => None,
+    };
+
     let query::PlannedQuery {
         mut expr,
         mut desc,
@@ -1617,6 +3064,8 @@ pub fn plan_create_materialized_view(
             expr,
             column_names,
             compute_instance,
+            batching,
+            compaction_window,
         },
         replace,
         if_not_exists,
@@ -1634,9 +3083,27 @@ generate_extracted_config!(
     CreateSinkOption,
     (Remote, String),
     (Size, String),
-    (Snapshot, bool)
+    (Snapshot, bool),
+    (SpillBudget, u64),
+    (CatchUpBytesPerSecond, u64),
+    (CatchUpRecordsPerSecond, u64),
+    (MaxBytesPerSecond, u64),
+    (MaxMessagesPerSecond, u64),
+    (MaskHash, Vec<Ident>, Default(vec![])),
+    (MaskRedact, Vec<Ident>, Default(vec![])),
+    (MaskTruncate, Vec<Ident>, Default(vec![])),
+    (MaskTruncateLength, u64),
+    (Retractions, String),
+    (Deletes, String),
+    (DeleteMarker, String),
+    (IncludeTimestamp, bool, Default(false)),
+    (IncludeDiff, bool, Default(false))
 );
 
+/// The number of characters that `MASK TRUNCATE` keeps if `MASK TRUNCATE
+/// LENGTH` is not specified.
+const DEFAULT_MASK_TRUNCATE_LENGTH: u64 = 4;
+
 pub fn plan_create_sink(
     scx: &StatementContext,
     stmt: CreateSinkStatement<Aug>,
@@ -1645,6 +3112,8 @@ pub fn plan_create_sink(
     let CreateSinkStatement {
         name,
         from,
+        columns,
+        filter,
         connection,
         format,
         envelope,
@@ -1665,19 +3134,105 @@ pub fn plan_create_sink(
         ))?;
     }
 
+    let CreateSinkOptionExtracted {
+        remote,
+        size,
+        snapshot,
+        spill_budget,
+        catch_up_bytes_per_second,
+        catch_up_records_per_second,
+        max_bytes_per_second,
+        max_messages_per_second,
+        mask_hash,
+        mask_redact,
+        mask_truncate,
+        mask_truncate_length,
+        retractions,
+        deletes,
+        delete_marker,
+        include_timestamp,
+        include_diff,
+        seen: _,
+    } = with_options.try_into()?;
+
     let envelope = match envelope {
         None => sql_bail!("ENVELOPE clause is required"),
-        Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
-        Some(Envelope::Upsert) => SinkEnvelope::Upsert,
-        Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+        Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain { tx_metadata })) => {
+            if !tx_metadata.is_empty() {
+                sql_bail!(
+                    "ENVELOPE DEBEZIUM (TRANSACTION METADATA ...) is only supported by sources"
+                );
+            }
+            SinkEnvelope::Debezium
+        }
+        Some(Envelope::Upsert) => {
+            SinkEnvelope::Upsert(plan_sink_delete_policy(deletes.as_deref())?)
+        }
+        Some(Envelope::CdcV2) => SinkEnvelope::CdcV2,
+        Some(Envelope::None) => {
+            SinkEnvelope::AppendOnly(plan_sink_retraction_policy(retractions.as_deref())?)
+        }
     };
+    if retractions.is_some() && !matches!(envelope, SinkEnvelope::AppendOnly(_)) {
+        sql_bail!("RETRACTIONS is only supported by sinks with ENVELOPE NONE");
+    }
+    if deletes.is_some() {
+        if !matches!(envelope, SinkEnvelope::Upsert(_)) {
+            sql_bail!("DELETES is only supported by sinks with ENVELOPE UPSERT");
+        }
+        if !matches!(connection, CreateSinkConnection::Kafka { .. }) {
+            sql_bail!("DELETES is only supported by Kafka sinks");
+        }
+    }
+    if delete_marker.is_some() && envelope != SinkEnvelope::Upsert(SinkDeletePolicy::DeleteRecord) {
+        sql_bail!("DELETE MARKER is only supported by sinks with DELETES = 'delete record'");
+    }
+    if matches!(envelope, SinkEnvelope::AppendOnly(_))
+        && !matches!(connection, CreateSinkConnection::Kafka { .. })
+    {
+        sql_bail!("ENVELOPE NONE is only supported by Kafka sinks");
+    }
+    if envelope == SinkEnvelope::CdcV2 && !matches!(connection, CreateSinkConnection::Kafka { .. })
+    {
+        sql_bail!("ENVELOPE MATERIALIZE is only supported by Kafka sinks");
+    }
+    if include_timestamp || include_diff {
+        if !matches!(connection, CreateSinkConnection::Kafka { .. }) {
+            sql_bail!("INCLUDE TIMESTAMP and INCLUDE DIFF are only supported by Kafka sinks");
+        }
+        if envelope == SinkEnvelope::CdcV2 {
+            sql_bail!(
+                "ENVELOPE MATERIALIZE sinks already include the timestamp and diff of each update"
+            );
+        }
+    }
+    if include_diff {
+        if !matches!(envelope, SinkEnvelope::AppendOnly(_)) {
+            sql_bail!("INCLUDE DIFF is only supported by sinks with ENVELOPE NONE");
+        }
+        // Retractions are emitted as rows with a negative diff, so there are
+        // none left for a retraction policy to handle.
+        if retractions.is_some() {
+            sql_bail!("RETRACTIONS cannot be combined with INCLUDE DIFF");
+        }
+    }
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let from = scx.get_item_by_resolved_name(&from)?;
 
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
+    let filter = filter
+        .map(|expr| query::plan_sink_filter_expr(scx, &desc, expr))
+        .transpose()?;
+    let (projection, desc) = plan_sink_projection(desc.into_owned(), columns)?;
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. }
+        | CreateSinkConnection::Postgres { key, .. }
+        | CreateSinkConnection::Elasticsearch { key, .. }
+        | CreateSinkConnection::Redis { key, .. }
+        | CreateSinkConnection::Nats { key, .. }
+        | CreateSinkConnection::Mqtt { key, .. }
+        | CreateSinkConnection::Grpc { key, .. }
+        | CreateSinkConnection::Sqs { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -1707,14 +3262,14 @@ pub fn plan_create_sink(
                     desc.typ().keys.iter().any(|key_columns| {
                         key_columns.iter().all(|column| indices.contains(column))
                     });
-                if key.not_enforced && envelope == SinkEnvelope::Upsert {
+                if key.not_enforced && matches!(envelope, SinkEnvelope::Upsert(_)) {
                     // TODO: We should report a warning notice back to the user via the pgwire
                     // protocol. See https://github.com/MaterializeInc/materialize/issues/9333.
                     warn!(
                         "Verification of upsert key disabled for sink '{}' via 'NOT ENFORCED'. This is potentially dangerous and can lead to crashing materialize when the specified key is not in fact a unique key of the sinked view.",
                         name
                     );
-                } else if !is_valid_key && envelope == SinkEnvelope::Upsert {
+                } else if !is_valid_key && matches!(envelope, SinkEnvelope::Upsert(_)) {
                     return Err(invalid_upsert_key_err(&desc, &key_columns));
                 }
                 Some(indices)
@@ -1722,6 +3277,70 @@ pub fn plan_create_sink(
                 None
             }
         }
+        CreateSinkConnection::S3 { .. } | CreateSinkConnection::File { .. } => None,
+    };
+
+    if spill_budget.is_some() {
+        if !matches!(
+            connection,
+            CreateSinkConnection::Postgres { .. }
+                | CreateSinkConnection::Elasticsearch { .. }
+                | CreateSinkConnection::Redis { .. }
+                | CreateSinkConnection::Nats { .. }
+                | CreateSinkConnection::Mqtt { .. }
+                | CreateSinkConnection::Grpc { .. }
+                | CreateSinkConnection::Sqs { .. }
+        ) {
+            sql_bail!(
+                "SPILL BUDGET is only supported by Postgres, Elasticsearch, Redis, NATS, MQTT, gRPC, and SQS sinks"
+            );
+        }
+        if spill_budget == Some(0) {
+            sql_bail!("SPILL BUDGET must be a positive integer");
+        }
+    }
+
+    plan_sink_catch_up_rate(catch_up_bytes_per_second, catch_up_records_per_second)?;
+    let catch_up_limit = (catch_up_bytes_per_second.is_some()
+        || catch_up_records_per_second.is_some())
+    .then(|| SinkRateLimit {
+        records_per_second: catch_up_records_per_second,
+        bytes_per_second: catch_up_bytes_per_second,
+    });
+
+    plan_sink_max_rate(max_bytes_per_second, max_messages_per_second)?;
+    let rate_limit =
+        (max_bytes_per_second.is_some() || max_messages_per_second.is_some()).then(|| {
+            SinkRateLimit {
+                records_per_second: max_messages_per_second,
+                bytes_per_second: max_bytes_per_second,
+            }
+        });
+
+    let column_masks = plan_sink_column_masks(
+        scx,
+        &desc,
+        mask_hash,
+        mask_redact,
+        mask_truncate,
+        mask_truncate_length,
+    )?;
+    if let Some(key_indices) = &key_indices {
+        for mask in &column_masks {
+            if key_indices.contains(&mask.column) && mask.kind != SinkColumnMaskKind::Hash {
+                sql_bail!(
+                    "column {} is part of the sink key and can only be masked with MASK HASH",
+                    desc.get_name(mask.column).as_str().quoted()
+                );
+            }
+        }
+    }
+    let desc = mask_sink_desc(&desc, &column_masks);
+    let desc = plan_sink_update_columns(desc, include_timestamp, include_diff)?;
+    let desc = if envelope == SinkEnvelope::Upsert(SinkDeletePolicy::DeleteRecord) {
+        plan_sink_delete_marker(desc, delete_marker)?
+    } else {
+        desc
     };
 
     // pick the first valid natural relation key, if any
@@ -1738,9 +3357,17 @@ pub fn plan_create_sink(
         (RelationDesc::new(typ, names), key_indices)
     });
 
-    if key_desc_and_indices.is_none() && envelope == SinkEnvelope::Upsert {
+    if key_desc_and_indices.is_none() && matches!(envelope, SinkEnvelope::Upsert(_)) {
         return Err(PlanError::UpsertSinkWithoutKey);
     }
+    if key_desc_and_indices.is_none()
+        && envelope == SinkEnvelope::AppendOnly(SinkRetractionPolicy::DeleteMarker)
+    {
+        sql_bail!("RETRACTIONS = 'delete marker' requires a KEY");
+    }
+    if key_desc_and_indices.is_some() && envelope == SinkEnvelope::CdcV2 {
+        sql_bail!("ENVELOPE MATERIALIZE sinks do not support KEY");
+    }
 
     let connection_builder = match connection {
         CreateSinkConnection::Kafka { connection, .. } => kafka_sink_builder(
@@ -1749,23 +3376,95 @@ pub fn plan_create_sink(
             format,
             relation_key_indices,
             key_desc_and_indices,
-            desc.into_owned(),
+            desc,
             envelope,
         )?,
-    };
-
-    let CreateSinkOptionExtracted {
-        remote,
-        size,
-        snapshot,
-        seen: _,
-    } = with_options.try_into()?;
-
-    let host_config = host_config(remote, size)?;
-
-    // WITH SNAPSHOT defaults to true
-    let with_snapshot = snapshot.unwrap_or(true);
-
+        CreateSinkConnection::S3 {
+            connection,
+            options,
+        } => s3_sink_builder(scx, connection, options, format, desc, envelope)?,
+        CreateSinkConnection::File { path, options } => {
+            file_sink_builder(scx, path, options, format, desc, envelope)?
+        }
+        CreateSinkConnection::Postgres {
+            connection, table, ..
+        } => postgres_sink_builder(
+            scx,
+            connection,
+            table,
+            format,
+            key_desc_and_indices,
+            desc,
+            envelope,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Elasticsearch { url, options, .. } => elasticsearch_sink_builder(
+            scx,
+            url,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            envelope,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Redis { url, options, .. } => redis_sink_builder(
+            scx,
+            url,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            envelope,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Nats { url, options, .. } => nats_sink_builder(
+            scx,
+            url,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Mqtt { url, options, .. } => mqtt_sink_builder(
+            scx,
+            url,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Grpc { url, options, .. } => grpc_sink_builder(
+            scx,
+            url,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            spill_budget,
+        )?,
+        CreateSinkConnection::Sqs {
+            connection,
+            options,
+            ..
+        } => sqs_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            key_desc_and_indices,
+            desc,
+            spill_budget,
+        )?,
+    };
+
+    let host_config = host_config(remote, size)?;
+
+    // WITH SNAPSHOT defaults to true
+    let with_snapshot = snapshot.unwrap_or(true);
+
     Ok(Plan::CreateSink(CreateSinkPlan {
         name,
         sink: Sink {
@@ -1773,6 +3472,13 @@ pub fn plan_create_sink(
             from: from.id(),
             connection_builder,
             envelope,
+            catch_up_limit,
+            rate_limit,
+            projection,
+            column_masks,
+            include_timestamp,
+            include_diff,
+            filter,
         },
         with_snapshot,
         if_not_exists,
@@ -1780,6 +3486,174 @@ pub fn plan_create_sink(
     }))
 }
 
+/// Resolves the columns that a sink exports into their indices in `desc`, and
+/// returns those indices along with the description of the exported rows.
+///
+/// If `columns` is empty, the sink exports every column of `desc` and the
+/// returned projection is empty.
+fn plan_sink_projection(
+    desc: RelationDesc,
+    columns: Vec<SinkColumn>,
+) -> Result<(Vec<usize>, RelationDesc), PlanError> {
+    if columns.is_empty() {
+        return Ok((vec![], desc));
+    }
+    let mut projection = vec![];
+    let mut names = vec![];
+    for SinkColumn { name, alias } in columns {
+        let name = normalize::column_name(name);
+        let (column, _typ) = desc
+            .get_by_name(&name)
+            .ok_or_else(|| sql_err!("No such column: {}", name))?;
+        if desc.get_unambiguous_name(column).is_none() {
+            sql_bail!("Ambiguous column: {}", name);
+        }
+        let output_name = match alias {
+            Some(alias) => normalize::column_name(alias),
+            None => name,
+        };
+        if names.contains(&output_name) {
+            sql_bail!(
+                "column {} is exported by the sink more than once",
+                output_name.as_str().quoted()
+            );
+        }
+        projection.push(column);
+        names.push(output_name);
+    }
+
+    // Keys of `desc` remain keys of the exported rows only if the sink
+    // exports all of their columns.
+    let typ = desc.typ();
+    let keys = typ
+        .keys
+        .iter()
+        .filter_map(|key| {
+            key.iter()
+                .map(|column| projection.iter().position(|c| c == column))
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect();
+    let typ = RelationType::new(
+        projection
+            .iter()
+            .map(|column| typ.column_types[*column].clone())
+            .collect(),
+    )
+    .with_keys(keys);
+    Ok((projection, RelationDesc::new(typ, names)))
+}
+
+/// Resolves the `MASK` options of a sink on `desc` into the masks to apply to
+/// its columns.
+fn plan_sink_column_masks(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mask_hash: Vec<Ident>,
+    mask_redact: Vec<Ident>,
+    mask_truncate: Vec<Ident>,
+    mask_truncate_length: Option<u64>,
+) -> Result<Vec<SinkColumnMask>, PlanError> {
+    let truncate_length = match mask_truncate_length {
+        Some(_) if mask_truncate.is_empty() => {
+            sql_bail!("MASK TRUNCATE LENGTH requires MASK TRUNCATE")
+        }
+        Some(0) => sql_bail!("MASK TRUNCATE LENGTH must be a positive integer"),
+        Some(length) => length,
+        None => DEFAULT_MASK_TRUNCATE_LENGTH,
+    };
+    let truncate_length = usize::try_from(truncate_length)
+        .map_err(|_| sql_err!("MASK TRUNCATE LENGTH is too large"))?;
+
+    let mut masks: Vec<SinkColumnMask> = vec![];
+    for (columns, kind) in [
+        (mask_hash, SinkColumnMaskKind::Hash),
+        (mask_redact, SinkColumnMaskKind::Redact),
+        (
+            mask_truncate,
+            SinkColumnMaskKind::Truncate {
+                length: truncate_length,
+            },
+        ),
+    ] {
+        for column in columns {
+            let name = normalize::column_name(column);
+            let (column, typ) = desc
+                .get_by_name(&name)
+                .ok_or_else(|| sql_err!("No such column: {}", name))?;
+            if desc.get_unambiguous_name(column).is_none() {
+                sql_bail!("Ambiguous column: {}", name);
+            }
+            if masks.iter().any(|mask| mask.column == column) {
+                sql_bail!("column {} is masked more than once", name.as_str().quoted());
+            }
+            if matches!(kind, SinkColumnMaskKind::Truncate { .. })
+                && !matches!(
+                    typ.scalar_type,
+                    ScalarType::String | ScalarType::VarChar { .. }
+                )
+            {
+                sql_bail!(
+                    "MASK TRUNCATE requires a column of type text or varchar, but column {} has type {}",
+                    name.as_str().quoted(),
+                    scx.humanize_scalar_type(&typ.scalar_type)
+                );
+            }
+            masks.push(SinkColumnMask { column, kind });
+        }
+    }
+    masks.sort_by_key(|mask| mask.column);
+    Ok(masks)
+}
+
+/// Returns the description of the rows that a sink emits after it applies
+/// `masks` to rows described by `desc`.
+///
+/// Keys of `desc` that include a redacted or truncated column are dropped, as
+/// the masked values of such a key need not be unique.
+fn mask_sink_desc(desc: &RelationDesc, masks: &[SinkColumnMask]) -> RelationDesc {
+    let mut typ = desc.typ().clone();
+    for mask in masks {
+        let column_type = &mut typ.column_types[mask.column];
+        match mask.kind {
+            SinkColumnMaskKind::Hash => column_type.scalar_type = ScalarType::String,
+            SinkColumnMaskKind::Redact => column_type.nullable = true,
+            SinkColumnMaskKind::Truncate { .. } => (),
+        }
+    }
+    typ.keys.retain(|key| {
+        masks
+            .iter()
+            .all(|mask| mask.kind == SinkColumnMaskKind::Hash || !key.contains(&mask.column))
+    });
+    RelationDesc::new(typ, desc.iter_names().cloned())
+}
+
+/// Appends the columns that `INCLUDE TIMESTAMP` and `INCLUDE DIFF` add to
+/// the rows a sink emits to their description.
+fn plan_sink_update_columns(
+    mut desc: RelationDesc,
+    include_timestamp: bool,
+    include_diff: bool,
+) -> Result<RelationDesc, PlanError> {
+    for (include, name, typ) in [
+        (include_timestamp, "mz_timestamp", ScalarType::MzTimestamp),
+        (include_diff, "mz_diff", ScalarType::Int64),
+    ] {
+        if !include {
+            continue;
+        }
+        if desc.get_by_name(&ColumnName::from(name)).is_some() {
+            sql_bail!(
+                "cannot include column {}: the sink already emits a column with that name",
+                name.quoted()
+            );
+        }
+        desc = desc.with_column(name, typ.nullable(false));
+    }
+    Ok(desc)
+}
+
 fn invalid_upsert_key_err(desc: &RelationDesc, requested_user_key: &[ColumnName]) -> PlanError {
     let requested_user_key = requested_user_key
         .iter()
@@ -1831,9 +3705,861 @@ fn key_constraint_err(desc: &RelationDesc, user_keys: &[ColumnName]) -> PlanErro
 generate_extracted_config!(
     CsrConfigOption,
     (AvroKeyFullname, String),
-    (AvroValueFullname, String)
+    (AvroValueFullname, String),
+    (AvroValueSchema, String),
+    (CompatibilityLevel, String),
+    (OnDropDeleteSubjects, bool, Default(false)),
+    (SubjectNameStrategy, String)
+);
+
+/// The size at which objects written by an S3 sink are rotated, unless
+/// otherwise specified.
+const DEFAULT_S3_SINK_MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+generate_extracted_config!(
+    S3SinkConfigOption,
+    (Bucket, String),
+    (KeyTemplate, String),
+    (MaxFileSize, u64, Default(DEFAULT_S3_SINK_MAX_FILE_SIZE)),
+    (OnCollision, String),
+    (Prefix, String, Default(String::new())),
+    (RotationInterval, Interval),
+    (TableFormat, String)
+);
+
+generate_extracted_config!(
+    FileSinkConfigOption,
+    (Fsync, bool, Default(false)),
+    (MaxFileSize, u64),
+    (RotationInterval, Interval)
+);
+
+fn file_sink_builder(
+    scx: &StatementContext,
+    path: String,
+    options: Vec<FileSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO FILE")?;
+
+    if envelope != SinkEnvelope::Debezium {
+        sql_bail!("file sinks only support ENVELOPE DEBEZIUM");
+    }
+    if path.is_empty() {
+        sql_bail!("file sink path must not be empty");
+    }
+
+    let FileSinkConfigOptionExtracted {
+        fsync,
+        max_file_size,
+        rotation_interval,
+        ..
+    } = options.try_into()?;
+
+    if max_file_size == Some(0) {
+        sql_bail!("MAX FILE SIZE for file sinks must be a positive integer");
+    }
+    let rotation_interval = rotation_interval.map(|i| i.duration()).transpose()?;
+    if rotation_interval == Some(std::time::Duration::ZERO) {
+        sql_bail!("ROTATION INTERVAL for file sinks must be positive");
+    }
+
+    let format = match format {
+        Some(Format::AvroOcf) => FileSinkFormat::AvroOcf,
+        Some(Format::Json {
+            csr_connection: None,
+            columns,
+            reject_unknown_fields: false,
+        }) if columns.is_empty() => FileSinkFormat::Json,
+        Some(Format::Json {
+            csr_connection: None,
+            ..
+        }) => sql_bail!("FORMAT JSON for sinks does not support columns or REJECT UNKNOWN FIELDS"),
+        Some(Format::Json {
+            csr_connection: Some(_),
+            ..
+        }) => {
+            sql_bail!(
+                "FORMAT JSON USING CONFLUENT SCHEMA REGISTRY is only supported by Kafka sinks"
+            )
+        }
+        Some(Format::Csv {
+            columns,
+            delimiter,
+            quote: '"',
+            escape: None,
+            typed_columns,
+        }) if typed_columns.is_empty() => {
+            // The sinked columns are those of the Debezium envelope.
+            let column_names: Vec<_> =
+                AvroSchemaGenerator::new(None, None, None, value_desc.clone(), true)
+                    .value_columns()
+                    .iter()
+                    .map(|(name, _typ)| name.as_str().to_string())
+                    .collect();
+            let header = match columns {
+                CsvColumns::Count(n) if n == column_names.len() => None,
+                CsvColumns::Count(n) => sql_bail!(
+                    "file sink writes {} CSV columns, but WITH {} COLUMNS was specified",
+                    column_names.len(),
+                    n
+                ),
+                CsvColumns::Header { names } if names.is_empty() => Some(column_names),
+                CsvColumns::Header { names } if names.len() == column_names.len() => {
+                    Some(names.into_iter().map(|n| n.into_string()).collect())
+                }
+                CsvColumns::Header { names } => sql_bail!(
+                    "file sink writes {} CSV columns, but {} header names were specified",
+                    column_names.len(),
+                    names.len()
+                ),
+            };
+            FileSinkFormat::Csv { delimiter, header }
+        }
+        Some(Format::Csv { .. }) => {
+            sql_bail!("FORMAT CSV for sinks does not support QUOTE, ESCAPE or COLUMNS")
+        }
+        Some(format) => bail_unsupported!(format!("file sink format {:?}", format)),
+        None => bail_unsupported!("sink without format"),
+    };
+
+    Ok(StorageSinkConnectionBuilder::File(FileSinkConnection {
+        path,
+        format,
+        value_desc,
+        fsync,
+        max_file_size,
+        rotation_interval,
+    }))
+}
+
+fn postgres_sink_builder(
+    scx: &StatementContext,
+    pg_connection: ResolvedObjectName,
+    table: UnresolvedObjectName,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO POSTGRES")?;
+
+    let item = scx.get_item_by_resolved_name(&pg_connection)?;
+    let connection = match item.connection()? {
+        Connection::Postgres(connection) => connection.clone(),
+        _ => sql_bail!("{} is not a postgres connection", item.name()),
+    };
+
+    if !matches!(envelope, SinkEnvelope::Upsert(_)) {
+        sql_bail!("Postgres sinks only support ENVELOPE UPSERT");
+    }
+    if format.is_some() {
+        sql_bail!("Postgres sinks do not support FORMAT");
+    }
+
+    let (_key_desc, key_indices) =
+        key_desc_and_indices.expect("upsert sinks are known to have a key");
+
+    let table = table
+        .0
+        .into_iter()
+        .map(|ident| Ident::new(normalize::ident(ident)).to_ast_string_stable())
+        .join(".");
+
+    Ok(StorageSinkConnectionBuilder::Postgres(
+        PostgresSinkConnection {
+            connection_id: item.id(),
+            connection,
+            table,
+            key_indices,
+            value_desc,
+            spill_budget,
+        },
+    ))
+}
+
+generate_extracted_config!(
+    ElasticsearchSinkConfigOption,
+    (Index, String),
+    (Password, with_options::Secret),
+    (User, StringOrSecret)
+);
+
+fn elasticsearch_sink_builder(
+    scx: &StatementContext,
+    url: String,
+    options: Vec<ElasticsearchSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO ELASTICSEARCH")?;
+
+    if !matches!(envelope, SinkEnvelope::Upsert(_)) {
+        sql_bail!("Elasticsearch sinks only support ENVELOPE UPSERT");
+    }
+    if format.is_some() {
+        sql_bail!("Elasticsearch sinks do not support FORMAT");
+    }
+
+    let url: reqwest::Url = url
+        .parse()
+        .map_err(|e| sql_err!("invalid Elasticsearch URL {}: {}", url.quoted(), e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        sql_bail!(
+            "Elasticsearch URL must use the http or https scheme, got {}",
+            url.scheme().quoted()
+        );
+    }
+
+    let ElasticsearchSinkConfigOptionExtracted {
+        index,
+        password,
+        user,
+        ..
+    } = options.try_into()?;
+
+    let index = match index {
+        Some(index) if !index.is_empty() => index,
+        _ => sql_bail!("Elasticsearch sinks must specify a non-empty INDEX"),
+    };
+    if password.is_some() && user.is_none() {
+        sql_bail!("Elasticsearch sinks that specify PASSWORD must also specify USER");
+    }
+
+    let (_key_desc, key_indices) =
+        key_desc_and_indices.expect("upsert sinks are known to have a key");
+
+    Ok(StorageSinkConnectionBuilder::Elasticsearch(
+        ElasticsearchSinkConnection {
+            url: url.to_string(),
+            index,
+            user,
+            password: password.map(|password| password.into()),
+            key_indices,
+            value_desc,
+            spill_budget,
+        },
+    ))
+}
+
+generate_extracted_config!(
+    RedisSinkConfigOption,
+    (KeyPrefix, String, Default(String::new())),
+    (Password, with_options::Secret),
+    (Ttl, Interval),
+    (DataType, String, Default("string".to_string())),
+    (User, StringOrSecret)
+);
+
+fn redis_sink_builder(
+    scx: &StatementContext,
+    url: String,
+    options: Vec<RedisSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO REDIS")?;
+
+    if !matches!(envelope, SinkEnvelope::Upsert(_)) {
+        sql_bail!("Redis sinks only support ENVELOPE UPSERT");
+    }
+    if format.is_some() {
+        sql_bail!("Redis sinks do not support FORMAT");
+    }
+
+    let parsed_url: reqwest::Url = url
+        .parse()
+        .map_err(|e| sql_err!("invalid Redis URL {}: {}", url.quoted(), e))?;
+    if !matches!(parsed_url.scheme(), "redis" | "rediss") {
+        sql_bail!(
+            "Redis URL must use the redis or rediss scheme, got {}",
+            parsed_url.scheme().quoted()
+        );
+    }
+
+    let RedisSinkConfigOptionExtracted {
+        key_prefix,
+        password,
+        ttl,
+        data_type,
+        user,
+        ..
+    } = options.try_into()?;
+
+    let data_type = match data_type.to_lowercase().as_str() {
+        "hash" => RedisSinkDataType::Hash,
+        "string" => RedisSinkDataType::String,
+        _ => sql_bail!(
+            "invalid Redis sink TYPE {}: must be HASH or STRING",
+            data_type.quoted()
+        ),
+    };
+    let ttl = ttl.map(|i| i.duration()).transpose()?;
+    if ttl == Some(std::time::Duration::ZERO) {
+        sql_bail!("TTL for Redis sinks must be positive");
+    }
+    if user.is_some() && password.is_none() {
+        sql_bail!("Redis sinks that specify USER must also specify PASSWORD");
+    }
+
+    let (_key_desc, key_indices) =
+        key_desc_and_indices.expect("upsert sinks are known to have a key");
+
+    Ok(StorageSinkConnectionBuilder::Redis(RedisSinkConnection {
+        url,
+        key_prefix,
+        data_type,
+        ttl,
+        user,
+        password: password.map(|password| password.into()),
+        key_indices,
+        value_desc,
+        spill_budget,
+    }))
+}
+
+generate_extracted_config!(
+    NatsSinkConfigOption,
+    (Password, with_options::Secret),
+    (Subject, String),
+    (Token, with_options::Secret),
+    (User, StringOrSecret)
+);
+
+fn nats_sink_builder(
+    scx: &StatementContext,
+    url: String,
+    options: Vec<NatsSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO NATS")?;
+
+    if format.is_some() {
+        sql_bail!("NATS sinks do not support FORMAT");
+    }
+
+    let parsed_url: reqwest::Url = url
+        .parse()
+        .map_err(|e| sql_err!("invalid NATS URL {}: {}", url.quoted(), e))?;
+    if !matches!(parsed_url.scheme(), "nats" | "tls") {
+        sql_bail!(
+            "NATS URL must use the nats or tls scheme, got {}",
+            parsed_url.scheme().quoted()
+        );
+    }
+
+    let NatsSinkConfigOptionExtracted {
+        password,
+        subject,
+        token,
+        user,
+        ..
+    } = options.try_into()?;
+
+    let subject = match subject {
+        Some(subject) => subject,
+        None => sql_bail!("NATS sinks must specify SUBJECT"),
+    };
+    let subject = parse_key_template(
+        "NATS SUBJECT",
+        &subject,
+        key_desc_and_indices.as_ref().map(|(d, _)| d),
+        &['*', '>'],
+    )?;
+
+    if user.is_some() != password.is_some() {
+        sql_bail!("NATS sinks must specify USER and PASSWORD together");
+    }
+    if token.is_some() && user.is_some() {
+        sql_bail!("NATS sinks cannot specify both TOKEN and USER");
+    }
+
+    Ok(StorageSinkConnectionBuilder::Nats(NatsSinkConnection {
+        url,
+        subject,
+        user,
+        password: password.map(|password| password.into()),
+        token: token.map(|token| token.into()),
+        key_indices: key_desc_and_indices.map(|(_, key_indices)| key_indices),
+        value_desc,
+        spill_budget,
+    }))
+}
+
+generate_extracted_config!(
+    MqttSinkConfigOption,
+    (Password, with_options::Secret),
+    (Qos, u16, Default(1)),
+    (Retain, bool, Default(false)),
+    (Topic, String),
+    (User, StringOrSecret)
 );
 
+fn mqtt_sink_builder(
+    scx: &StatementContext,
+    url: String,
+    options: Vec<MqttSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO MQTT")?;
+
+    if format.is_some() {
+        sql_bail!("MQTT sinks do not support FORMAT");
+    }
+
+    let parsed_url: reqwest::Url = url
+        .parse()
+        .map_err(|e| sql_err!("invalid MQTT URL {}: {}", url.quoted(), e))?;
+    if !matches!(parsed_url.scheme(), "mqtt" | "mqtts") {
+        sql_bail!(
+            "MQTT URL must use the mqtt or mqtts scheme, got {}",
+            parsed_url.scheme().quoted()
+        );
+    }
+    if parsed_url.host_str().is_none() {
+        sql_bail!("MQTT URL {} must specify a host", url.quoted());
+    }
+
+    let MqttSinkConfigOptionExtracted {
+        password,
+        qos,
+        retain,
+        topic,
+        user,
+        ..
+    } = options.try_into()?;
+
+    let qos = match u8::try_from(qos) {
+        Ok(qos @ 0..=2) => qos,
+        _ => sql_bail!("MQTT QOS must be 0, 1, or 2, got {}", qos),
+    };
+
+    let topic = match topic {
+        Some(topic) => topic,
+        None => sql_bail!("MQTT sinks must specify TOPIC"),
+    };
+    let topic = parse_key_template(
+        "MQTT TOPIC",
+        &topic,
+        key_desc_and_indices.as_ref().map(|(d, _)| d),
+        &['+', '#'],
+    )?;
+
+    if user.is_some() != password.is_some() {
+        sql_bail!("MQTT sinks must specify USER and PASSWORD together");
+    }
+
+    Ok(StorageSinkConnectionBuilder::Mqtt(MqttSinkConnection {
+        url,
+        topic,
+        qos,
+        retain,
+        user,
+        password: password.map(|password| password.into()),
+        key_indices: key_desc_and_indices.map(|(_, key_indices)| key_indices),
+        value_desc,
+        spill_budget,
+    }))
+}
+
+generate_extracted_config!(
+    GrpcSinkConfigOption,
+    (Method, String),
+    (Token, with_options::Secret)
+);
+
+fn grpc_sink_builder(
+    scx: &StatementContext,
+    url: String,
+    options: Vec<GrpcSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO GRPC")?;
+
+    if format.is_some() {
+        sql_bail!("gRPC sinks do not support FORMAT");
+    }
+
+    let parsed_url: reqwest::Url = url
+        .parse()
+        .map_err(|e| sql_err!("invalid gRPC URL {}: {}", url.quoted(), e))?;
+    if !matches!(parsed_url.scheme(), "http" | "https") {
+        sql_bail!(
+            "gRPC URL must use the http or https scheme, got {}",
+            parsed_url.scheme().quoted()
+        );
+    }
+    if parsed_url.host_str().is_none() {
+        sql_bail!("gRPC URL {} must specify a host", url.quoted());
+    }
+
+    let GrpcSinkConfigOptionExtracted { method, token, .. } = options.try_into()?;
+
+    // Methods are named by their fully qualified service and their name,
+    // as in `package.Service/Method`, and are called at the corresponding
+    // path.
+    let method = match method {
+        Some(method) => method,
+        None => sql_bail!("gRPC sinks must specify METHOD"),
+    };
+    let path = method.strip_prefix('/').unwrap_or(&method);
+    let valid = match path.split_once('/') {
+        Some((service, name)) => {
+            !service.is_empty()
+                && !name.is_empty()
+                && !name.contains('/')
+                && !path.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        sql_bail!(
+            "gRPC METHOD must have the form 'package.Service/Method', got {}",
+            method.quoted()
+        );
+    }
+
+    Ok(StorageSinkConnectionBuilder::Grpc(GrpcSinkConnection {
+        url,
+        method: format!("/{}", path),
+        token: token.map(|token| token.into()),
+        key_indices: key_desc_and_indices.map(|(_, key_indices)| key_indices),
+        value_desc,
+        spill_budget,
+    }))
+}
+
+generate_extracted_config!(SqsSinkConfigOption, (QueueUrl, String));
+
+fn sqs_sink_builder(
+    scx: &StatementContext,
+    aws_connection: ResolvedObjectName,
+    options: Vec<SqsSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    spill_budget: Option<u64>,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO SQS")?;
+
+    let item = scx.get_item_by_resolved_name(&aws_connection)?;
+    let aws = match item.connection()? {
+        Connection::Aws(aws) => aws.clone(),
+        _ => sql_bail!("{} is not an AWS connection", item.name()),
+    };
+
+    if format.is_some() {
+        sql_bail!("SQS sinks do not support FORMAT");
+    }
+
+    let SqsSinkConfigOptionExtracted { queue_url, .. } = options.try_into()?;
+
+    let queue_url = match queue_url {
+        Some(queue_url) => queue_url,
+        None => sql_bail!("SQS sinks must specify QUEUE URL"),
+    };
+    let parsed_url: reqwest::Url = queue_url
+        .parse()
+        .map_err(|e| sql_err!("invalid SQS QUEUE URL {}: {}", queue_url.quoted(), e))?;
+    if !matches!(parsed_url.scheme(), "http" | "https") {
+        sql_bail!(
+            "SQS QUEUE URL must use the http or https scheme, got {}",
+            parsed_url.scheme().quoted()
+        );
+    }
+    // The names of FIFO queues, which are the last segment of their URL,
+    // must end in `.fifo`.
+    let fifo = parsed_url.path().ends_with(".fifo");
+
+    Ok(StorageSinkConnectionBuilder::Sqs(SqsSinkConnection {
+        connection_id: item.id(),
+        aws,
+        queue_url,
+        fifo,
+        key_indices: key_desc_and_indices.map(|(_, key_indices)| key_indices),
+        value_desc,
+        spill_budget,
+    }))
+}
+
+/// Parses the template of the `option` of a sink, in which `{column}`
+/// placeholders are substituted by the values of the named key columns.
+///
+/// Literal text must not contain whitespace or the characters in `reserved`.
+fn parse_key_template(
+    option: &str,
+    template: &str,
+    key_desc: Option<&RelationDesc>,
+    reserved: &[char],
+) -> Result<Vec<KeyTemplateSegment>, PlanError> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while !rest.is_empty() {
+        let (literal, placeholder) = match rest.find('{') {
+            Some(start) => match rest[start..].find('}') {
+                Some(len) => (&rest[..start], Some(&rest[start + 1..start + len])),
+                None => sql_bail!(
+                    "unterminated placeholder in {} {}",
+                    option,
+                    template.quoted()
+                ),
+            },
+            None => (rest, None),
+        };
+        if literal.contains('}') {
+            sql_bail!("unmatched }} in {} {}", option, template.quoted());
+        }
+        if literal
+            .chars()
+            .any(|c| c.is_whitespace() || reserved.contains(&c))
+        {
+            sql_bail!(
+                "{} {} must not contain wildcards or whitespace",
+                option,
+                template.quoted()
+            );
+        }
+        if !literal.is_empty() {
+            segments.push(KeyTemplateSegment::Literal(literal.to_string()));
+        }
+        rest = &rest[literal.len()..];
+        if let Some(placeholder) = placeholder {
+            let key_desc = match key_desc {
+                Some(key_desc) => key_desc,
+                None => sql_bail!(
+                    "{} {} can only refer to columns of the sink KEY",
+                    option,
+                    template.quoted()
+                ),
+            };
+            let column = ColumnName::from(placeholder);
+            let index = match key_desc.get_by_name(&column) {
+                Some((index, _)) => index,
+                None => sql_bail!(
+                    "{} {} refers to {}, which is not a column of the sink KEY",
+                    option,
+                    template.quoted(),
+                    placeholder.quoted()
+                ),
+            };
+            segments.push(KeyTemplateSegment::KeyColumn(index));
+            rest = &rest[placeholder.len() + 2..];
+        }
+    }
+    if segments.is_empty() {
+        sql_bail!("{} must not be empty", option);
+    }
+    Ok(segments)
+}
+
+/// Parses the `KEY TEMPLATE` of an S3 sink, in which the placeholders
+/// `{sink_id}`, `{lower}`, `{upper}`, `{sequence}` and `{extension}` are
+/// substituted when each object is written.
+fn parse_s3_key_template(template: &str) -> Result<Vec<S3KeyTemplateSegment>, PlanError> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while !rest.is_empty() {
+        let (literal, placeholder) = match rest.find('{') {
+            Some(start) => match rest[start..].find('}') {
+                Some(len) => (&rest[..start], Some(&rest[start + 1..start + len])),
+                None => sql_bail!(
+                    "unterminated placeholder in KEY TEMPLATE {}",
+                    template.quoted()
+                ),
+            },
+            None => (rest, None),
+        };
+        if literal.contains('}') {
+            sql_bail!("unmatched }} in KEY TEMPLATE {}", template.quoted());
+        }
+        if !literal.is_empty() {
+            segments.push(S3KeyTemplateSegment::Literal(literal.to_string()));
+        }
+        rest = &rest[literal.len()..];
+        if let Some(placeholder) = placeholder {
+            segments.push(match placeholder {
+                "sink_id" => S3KeyTemplateSegment::SinkId,
+                "lower" => S3KeyTemplateSegment::Lower,
+                "upper" => S3KeyTemplateSegment::Upper,
+                "sequence" => S3KeyTemplateSegment::Sequence,
+                "extension" => S3KeyTemplateSegment::Extension,
+                _ => sql_bail!(
+                    "unknown placeholder {} in KEY TEMPLATE {}: expected sink_id, lower, upper, \
+                     sequence or extension",
+                    placeholder.quoted(),
+                    template.quoted()
+                ),
+            });
+            rest = &rest[placeholder.len() + 2..];
+        }
+    }
+    if segments.is_empty() {
+        sql_bail!("KEY TEMPLATE must not be empty");
+    }
+    if template.starts_with('/') {
+        sql_bail!("KEY TEMPLATE {} must not start with /", template.quoted());
+    }
+    // Without a placeholder that varies from object to object, every object
+    // would be written to the same key.
+    if !segments.iter().any(|segment| {
+        matches!(
+            segment,
+            S3KeyTemplateSegment::Lower
+                | S3KeyTemplateSegment::Upper
+                | S3KeyTemplateSegment::Sequence
+        )
+    }) {
+        sql_bail!(
+            "KEY TEMPLATE {} must contain at least one of {{lower}}, {{upper}} or {{sequence}}",
+            template.quoted()
+        );
+    }
+    Ok(segments)
+}
+
+fn s3_sink_builder(
+    scx: &StatementContext,
+    aws_connection: ResolvedObjectName,
+    options: Vec<S3SinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    scx.require_unsafe_mode("CREATE SINK ... INTO S3")?;
+
+    let item = scx.get_item_by_resolved_name(&aws_connection)?;
+    let aws = match item.connection()? {
+        Connection::Aws(aws) => aws.clone(),
+        _ => sql_bail!("{} is not an AWS connection", item.name()),
+    };
+
+    if envelope != SinkEnvelope::Debezium {
+        sql_bail!("S3 sinks only support ENVELOPE DEBEZIUM");
+    }
+
+    let S3SinkConfigOptionExtracted {
+        bucket,
+        key_template,
+        max_file_size,
+        on_collision,
+        prefix,
+        rotation_interval,
+        table_format,
+        ..
+    } = options.try_into()?;
+
+    let bucket = bucket.ok_or_else(|| sql_err!("S3 sinks must specify BUCKET"))?;
+    if max_file_size == 0 {
+        sql_bail!("MAX FILE SIZE for S3 sinks must be a positive integer");
+    }
+    let rotation_interval = match rotation_interval {
+        Some(interval) => interval.duration()?,
+        None => std::time::Duration::from_secs(60),
+    };
+    if rotation_interval.is_zero() {
+        sql_bail!("ROTATION INTERVAL for S3 sinks must be positive");
+    }
+
+    let format = match format {
+        Some(Format::Json {
+            csr_connection: None,
+            columns,
+            reject_unknown_fields: false,
+        }) if columns.is_empty() => S3SinkFormat::Json,
+        Some(Format::Json {
+            csr_connection: None,
+            ..
+        }) => sql_bail!("FORMAT JSON for sinks does not support columns or REJECT UNKNOWN FIELDS"),
+        Some(Format::Json {
+            csr_connection: Some(_),
+            ..
+        }) => {
+            sql_bail!(
+                "FORMAT JSON USING CONFLUENT SCHEMA REGISTRY is only supported by Kafka sinks"
+            )
+        }
+        Some(Format::Parquet { columns }) => {
+            if !columns.is_empty() {
+                sql_bail!("FORMAT PARQUET for S3 sinks does not accept a list of columns");
+            }
+            S3SinkFormat::Parquet
+        }
+        Some(format) => bail_unsupported!(format!("S3 sink format {:?}", format)),
+        None => bail_unsupported!("sink without format"),
+    };
+
+    let table_format = match table_format.as_deref().map(str::to_lowercase).as_deref() {
+        None => None,
+        Some("delta") => Some(S3TableFormat::Delta),
+        Some("iceberg") => Some(S3TableFormat::Iceberg),
+        Some(other) => sql_bail!(
+            "unknown TABLE FORMAT {} for S3 sink: expected DELTA or ICEBERG",
+            other.quoted()
+        ),
+    };
+    if table_format.is_some() && format != S3SinkFormat::Parquet {
+        sql_bail!("S3 sinks with a TABLE FORMAT must use FORMAT PARQUET");
+    }
+
+    // The keys of the data files of a table are chosen by the table format.
+    if table_format.is_some() && (key_template.is_some() || on_collision.is_some()) {
+        sql_bail!("KEY TEMPLATE and ON COLLISION cannot be used with TABLE FORMAT");
+    }
+    let key_template = match key_template {
+        Some(template) => parse_s3_key_template(&template)?,
+        None => vec![
+            S3KeyTemplateSegment::SinkId,
+            S3KeyTemplateSegment::Literal("/".into()),
+            S3KeyTemplateSegment::Lower,
+            S3KeyTemplateSegment::Literal("-".into()),
+            S3KeyTemplateSegment::Upper,
+            S3KeyTemplateSegment::Literal(".".into()),
+            S3KeyTemplateSegment::Extension,
+        ],
+    };
+    let on_collision = match on_collision.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("error") => S3CollisionPolicy::Error,
+        Some("overwrite") => S3CollisionPolicy::Overwrite,
+        Some("suffix") => S3CollisionPolicy::Suffix,
+        Some(other) => sql_bail!(
+            "unknown ON COLLISION {} for S3 sink: expected ERROR, OVERWRITE or SUFFIX",
+            other.quoted()
+        ),
+    };
+
+    Ok(StorageSinkConnectionBuilder::S3(S3SinkConnection {
+        connection_id: item.id(),
+        aws,
+        bucket,
+        prefix,
+        format,
+        value_desc,
+        max_file_size,
+        rotation_interval,
+        table_format,
+        key_template,
+        on_collision,
+    }))
+}
+
 fn kafka_sink_builder(
     scx: &StatementContext,
     mz_sql_parser::ast::KafkaConnection {
@@ -1870,41 +4596,291 @@ fn kafka_sink_builder(
     let extracted_options: KafkaConfigOptionExtracted = with_options.try_into()?;
     let config_options = kafka_util::LibRdKafkaConfig::try_from(&extracted_options)?.0;
     let connection_id = item.id();
+
+    // Event Hubs does not let clients create topics, so the sink writes to
+    // event hubs that already exist, whichever way they are configured.
+    if connection.event_hubs {
+        for name in [
+            KafkaConfigOptionName::PartitionCount,
+            KafkaConfigOptionName::ReplicationFactor,
+            KafkaConfigOptionName::RetentionMs,
+            KafkaConfigOptionName::RetentionBytes,
+            KafkaConfigOptionName::TopicColumn,
+            KafkaConfigOptionName::TopicConfig,
+            KafkaConfigOptionName::TopicTemplate,
+        ] {
+            if extracted_options.seen.contains(&name) {
+                sql_bail!(
+                    "{} is not supported by sinks into Event Hubs, which do not create topics",
+                    name.to_ast_string()
+                );
+            }
+        }
+    }
     let KafkaConfigOptionExtracted {
         topic,
         partition_count,
         replication_factor,
         retention_ms,
         retention_bytes,
+        headers,
+        partition_by,
+        message_timestamp,
+        acks,
+        enable_idempotence,
+        compression_type,
+        linger_ms,
+        batch_num_messages,
+        dead_letter_topic,
+        retry_max_attempts,
+        retry_backoff,
+        retry_max_backoff,
+        retry_exhausted_action,
+        topic_column,
+        topic_config,
+        topic_template,
         ..
     } = extracted_options;
 
-    let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
+    let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
+
+    let headers = plan_kafka_sink_headers(&value_desc, headers)?;
+    let partition_by = partition_by
+        .map(|expr| query::plan_partition_by_expr(scx, &value_desc, expr))
+        .transpose()?;
+    let message_timestamp = message_timestamp
+        .map(|ts| plan_kafka_sink_message_timestamp(scx, &value_desc, ts))
+        .transpose()?;
+
+    // Progress statements are not derived from any row, so there is nothing
+    // to take their metadata from.
+    if envelope == SinkEnvelope::CdcV2
+        && (!headers.is_empty() || partition_by.is_some() || message_timestamp.is_some())
+    {
+        sql_bail!(
+            "HEADERS, PARTITION BY and MESSAGE TIMESTAMP are not supported with ENVELOPE MATERIALIZE"
+        );
+    }
+
+    let format = match format {
+        Some(Format::Avro(AvroSchema::Csr {
+            csr_connection:
+                CsrConnectionAvro {
+                    connection:
+                        CsrConnection {
+                            connection,
+                            options,
+                        },
+                    seed,
+                    key_strategy,
+                    value_strategy,
+                },
+        })) => {
+            if seed.is_some() {
+                sql_bail!("SEED option does not make sense with sinks");
+            }
+            if key_strategy.is_some() {
+                sql_bail!("KEY STRATEGY option does not make sense with sinks");
+            }
+            if value_strategy.is_some() {
+                sql_bail!("VALUE STRATEGY option does not make sense with sinks");
+            }
+
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let csr_connection = match item.connection()? {
+                Connection::Csr(connection) => connection.clone(),
+                _ => {
+                    sql_bail!("{} is not a schema registry connection", item.name())
+                }
+            };
+            let CsrConfigOptionExtracted {
+                avro_key_fullname,
+                avro_value_fullname,
+                avro_value_schema,
+                compatibility_level,
+                on_drop_delete_subjects,
+                subject_name_strategy,
+                ..
+            } = options.try_into()?;
+
+            let compatibility_level = compatibility_level
+                .map(|level| {
+                    level
+                        .parse::<mz_ccsr::CompatibilityLevel>()
+                        .map_err(|e| sql_err!("invalid COMPATIBILITY LEVEL: {e}"))
+                })
+                .transpose()?;
+            let subject_name_strategy = match subject_name_strategy.as_deref() {
+                None | Some("topic-name") => SubjectNameStrategy::TopicName,
+                Some("record-name") => SubjectNameStrategy::RecordName,
+                Some("topic-record-name") => SubjectNameStrategy::TopicRecordName,
+                Some(s) => sql_bail!(
+                    "invalid SUBJECT NAME STRATEGY {}: must be one of 'topic-name', 'record-name' or 'topic-record-name'",
+                    s.quoted()
+                ),
+            };
+
+            if key_desc_and_indices.is_none() && avro_key_fullname.is_some() {
+                sql_bail!("Cannot specify AVRO KEY FULLNAME without a corresponding KEY field");
+            }
+
+            let (key_schema, value_schema) = if envelope == SinkEnvelope::CdcV2 {
+                // Update batches and progress statements share a single value
+                // schema, and messages have no key.
+                if avro_value_fullname.is_some() {
+                    sql_bail!("AVRO VALUE FULLNAME does not apply to ENVELOPE MATERIALIZE");
+                }
+                if avro_value_schema.is_some() {
+                    sql_bail!("AVRO VALUE SCHEMA does not apply to ENVELOPE MATERIALIZE");
+                }
+                let encoder = mz_interchange::avro::cdc_v2::Encoder::new(value_desc.clone());
+                (None, encoder.schema().to_string())
+            } else {
+                let schema_generator = AvroSchemaGenerator::try_new(
+                    avro_key_fullname.as_deref(),
+                    avro_value_fullname.as_deref(),
+                    key_desc_and_indices
+                        .as_ref()
+                        .map(|(desc, _indices)| desc.clone()),
+                    value_desc.clone(),
+                    matches!(envelope, SinkEnvelope::Debezium),
+                )
+                .map_err(|e| sql_err!("invalid AVRO KEY FULLNAME or AVRO VALUE FULLNAME: {}", e))?;
+                let key_schema = schema_generator
+                    .key_writer_schema()
+                    .map(|key_schema| key_schema.to_string());
+                let value_schema = match &avro_value_schema {
+                    Some(value_schema) => {
+                        if avro_value_fullname.is_some() {
+                            sql_bail!(
+                                "cannot specify both AVRO VALUE FULLNAME and AVRO VALUE SCHEMA"
+                            );
+                        }
+                        let value_schema = mz_interchange::avro::parse_schema(value_schema)
+                            .map_err(|e| sql_err!("invalid AVRO VALUE SCHEMA: {:#}", e))?;
+                        Conformance::new(schema_generator.value_writer_schema(), &value_schema)
+                            .map_err(|e| {
+                                sql_err!("AVRO VALUE SCHEMA cannot encode the sinked relation: {e}")
+                            })?;
+                        value_schema.to_string()
+                    }
+                    None => schema_generator.value_writer_schema().to_string(),
+                };
+                (key_schema, value_schema)
+            };
+
+            // Under the record name strategies, records with the same fullname
+            // share a subject, and the key and value schemas differ.
+            if subject_name_strategy != SubjectNameStrategy::TopicName {
+                if let Some(key_schema) = &key_schema {
+                    let fullname = |schema: &str| {
+                        mz_interchange::avro::parse_schema(schema)
+                            .ok()
+                            .and_then(|schema| schema.top_node().name.map(|n| n.human_name()))
+                    };
+                    if fullname(key_schema) == fullname(&value_schema) {
+                        sql_bail!(
+                            "the key and value records must have different fullnames with SUBJECT NAME STRATEGY 'record-name' or 'topic-record-name'"
+                        );
+                    }
+                }
+            }
 
-    let format = match format {
-        Some(Format::Avro(AvroSchema::Csr {
+            KafkaSinkFormat::Avro {
+                key_schema,
+                value_schema,
+                key_fullname: avro_key_fullname,
+                value_fullname: avro_value_fullname,
+                user_value_schema: avro_value_schema.is_some(),
+                subject_name_strategy,
+                compatibility_level,
+                csr_connection,
+                delete_subjects_on_drop: on_drop_delete_subjects,
+            }
+        }
+        Some(Format::Protobuf(ProtobufSchema::Csr {
             csr_connection:
-                CsrConnectionAvro {
+                CsrConnectionProtobuf {
                     connection:
                         CsrConnection {
                             connection,
                             options,
                         },
                     seed,
-                    key_strategy,
-                    value_strategy,
                 },
         })) => {
             if seed.is_some() {
                 sql_bail!("SEED option does not make sense with sinks");
             }
-            if key_strategy.is_some() {
-                sql_bail!("KEY STRATEGY option does not make sense with sinks");
+
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let csr_connection = match item.connection()? {
+                Connection::Csr(connection) => connection.clone(),
+                _ => {
+                    sql_bail!("{} is not a schema registry connection", item.name())
+                }
+            };
+            let CsrConfigOptionExtracted {
+                avro_key_fullname,
+                avro_value_fullname,
+                avro_value_schema,
+                compatibility_level,
+                on_drop_delete_subjects,
+                subject_name_strategy,
+                ..
+            } = options.try_into()?;
+
+            if avro_key_fullname.is_some() || avro_value_fullname.is_some() {
+                sql_bail!(
+                    "AVRO KEY FULLNAME and AVRO VALUE FULLNAME do not apply to FORMAT PROTOBUF"
+                );
             }
-            if value_strategy.is_some() {
-                sql_bail!("VALUE STRATEGY option does not make sense with sinks");
+            if avro_value_schema.is_some() {
+                sql_bail!("AVRO VALUE SCHEMA does not apply to FORMAT PROTOBUF");
+            }
+            if compatibility_level.is_some() || subject_name_strategy.is_some() {
+                sql_bail!(
+                    "COMPATIBILITY LEVEL and SUBJECT NAME STRATEGY do not apply to FORMAT PROTOBUF"
+                );
             }
 
+            let schema_generator = ProtobufSchemaGenerator::new(
+                key_desc_and_indices
+                    .as_ref()
+                    .map(|(desc, _indices)| desc.clone()),
+                value_desc.clone(),
+                matches!(envelope, SinkEnvelope::Debezium),
+            );
+
+            KafkaSinkFormat::Protobuf {
+                key_schema: schema_generator.key_schema(),
+                value_schema: schema_generator.value_schema(),
+                csr_connection,
+                delete_subjects_on_drop: on_drop_delete_subjects,
+            }
+        }
+        Some(Format::Protobuf(ProtobufSchema::InlineSchema { .. })) => {
+            sql_bail!("FORMAT PROTOBUF for sinks requires a schema registry connection")
+        }
+        Some(Format::Json {
+            columns,
+            reject_unknown_fields,
+            ..
+        }) if !columns.is_empty() || reject_unknown_fields => {
+            sql_bail!("FORMAT JSON for sinks does not support columns or REJECT UNKNOWN FIELDS")
+        }
+        Some(Format::Json {
+            csr_connection: None,
+            ..
+        }) => KafkaSinkFormat::Json,
+        Some(Format::Json {
+            csr_connection:
+                Some(CsrConnection {
+                    connection,
+                    options,
+                }),
+            ..
+        }) => {
             let item = scx.get_item_by_resolved_name(&connection)?;
             let csr_connection = match item.connection()? {
                 Connection::Csr(connection) => connection.clone(),
@@ -1915,51 +4891,114 @@ fn kafka_sink_builder(
             let CsrConfigOptionExtracted {
                 avro_key_fullname,
                 avro_value_fullname,
+                avro_value_schema,
+                compatibility_level,
+                on_drop_delete_subjects,
+                subject_name_strategy,
                 ..
             } = options.try_into()?;
 
-            if key_desc_and_indices.is_none() && avro_key_fullname.is_some() {
-                sql_bail!("Cannot specify AVRO KEY FULLNAME without a corresponding KEY field");
+            if avro_key_fullname.is_some() || avro_value_fullname.is_some() {
+                sql_bail!("AVRO KEY FULLNAME and AVRO VALUE FULLNAME do not apply to FORMAT JSON");
             }
-
-            if key_desc_and_indices.is_some()
-                && (avro_key_fullname.is_some() ^ avro_value_fullname.is_some())
-            {
-                sql_bail!("Must specify both AVRO KEY FULLNAME and AVRO VALUE FULLNAME when specifying generated schema names");
+            if avro_value_schema.is_some() {
+                sql_bail!("AVRO VALUE SCHEMA does not apply to FORMAT JSON");
+            }
+            if compatibility_level.is_some() || subject_name_strategy.is_some() {
+                sql_bail!(
+                    "COMPATIBILITY LEVEL and SUBJECT NAME STRATEGY do not apply to FORMAT JSON"
+                );
             }
 
-            let schema_generator = AvroSchemaGenerator::new(
-                avro_key_fullname.as_deref(),
-                avro_value_fullname.as_deref(),
+            let encoder = JsonEncoder::new(
                 key_desc_and_indices
                     .as_ref()
                     .map(|(desc, _indices)| desc.clone()),
                 value_desc.clone(),
                 matches!(envelope, SinkEnvelope::Debezium),
             );
-            let value_schema = schema_generator.value_writer_schema().to_string();
-            let key_schema = schema_generator
-                .key_writer_schema()
-                .map(|key_schema| key_schema.to_string());
 
-            KafkaSinkFormat::Avro {
-                key_schema,
-                value_schema,
+            KafkaSinkFormat::JsonSchema {
+                key_schema: encoder.key_json_schema(),
+                value_schema: encoder.value_json_schema(),
                 csr_connection,
+                delete_subjects_on_drop: on_drop_delete_subjects,
+            }
+        }
+        Some(Format::Csv {
+            columns,
+            delimiter,
+            quote: '"',
+            escape: None,
+            typed_columns,
+        }) if typed_columns.is_empty() => {
+            let encoder = CsvEncoder::new(
+                key_desc_and_indices
+                    .as_ref()
+                    .map(|(desc, _indices)| desc.clone()),
+                value_desc.clone(),
+                matches!(envelope, SinkEnvelope::Debezium),
+            );
+            let column_names: Vec<_> = encoder
+                .value_columns()
+                .iter()
+                .map(|(name, _typ)| name.as_str().to_string())
+                .collect();
+            let header = match columns {
+                CsvColumns::Count(n) if n == column_names.len() => None,
+                CsvColumns::Count(n) => sql_bail!(
+                    "Kafka sink writes {} CSV columns, but WITH {} COLUMNS was specified",
+                    column_names.len(),
+                    n
+                ),
+                CsvColumns::Header { names } if names.is_empty() => Some(column_names),
+                CsvColumns::Header { names } if names.len() == column_names.len() => {
+                    Some(names.into_iter().map(|n| n.into_string()).collect())
+                }
+                CsvColumns::Header { names } => sql_bail!(
+                    "Kafka sink writes {} CSV columns, but {} header names were specified",
+                    column_names.len(),
+                    names.len()
+                ),
+            };
+            KafkaSinkFormat::Csv { delimiter, header }
+        }
+        Some(Format::Csv { .. }) => {
+            sql_bail!("FORMAT CSV for sinks does not support QUOTE, ESCAPE or COLUMNS")
+        }
+        Some(format @ (Format::Text | Format::Bytes)) => {
+            if envelope == SinkEnvelope::Debezium {
+                sql_bail!("FORMAT TEXT and FORMAT BYTES sinks require ENVELOPE UPSERT");
+            }
+            let key_desc = key_desc_and_indices
+                .as_ref()
+                .map(|(desc, _indices)| desc.clone());
+            if matches!(format, Format::Text) {
+                TextEncoder::text(key_desc, value_desc.clone())?;
+                KafkaSinkFormat::Text
+            } else {
+                TextEncoder::bytes(key_desc, value_desc.clone())?;
+                KafkaSinkFormat::Bytes
             }
         }
-        Some(Format::Json) => KafkaSinkFormat::Json,
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
+    if envelope == SinkEnvelope::CdcV2 && !matches!(format, KafkaSinkFormat::Avro { .. }) {
+        sql_bail!("ENVELOPE MATERIALIZE requires FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY");
+    }
 
-    let consistency_config = KafkaConsistencyConfig::Progress {
-        topic: connection.progress_topic.clone().unwrap_or_else(|| {
-            scx.catalog
-                .config()
-                .default_kafka_sink_progress_topic(connection_id)
-        }),
-    };
+    let progress_topic = connection.progress_topic.clone().unwrap_or_else(|| {
+        scx.catalog
+            .config()
+            .default_kafka_sink_progress_topic(connection_id)
+    });
+
+    if let Some(dead_letter_topic) = &dead_letter_topic {
+        if dead_letter_topic == &topic_name || dead_letter_topic == &progress_topic {
+            sql_bail!("DEAD LETTER TOPIC must differ from the sink's data and progress topics");
+        }
+    }
 
     if partition_count == 0 || partition_count < -1 {
         sql_bail!(
@@ -1973,19 +5012,83 @@ fn kafka_sink_builder(
         );
     }
 
-    if retention_ms.unwrap_or(0) < -1 {
-        sql_bail!("RETENTION MS for sink topics must be greater than or equal to -1");
+    if let Some(retention_ms) = retention_ms {
+        plan_kafka_sink_retention_ms(retention_ms)?;
+    }
+    if let Some(retention_bytes) = retention_bytes {
+        plan_kafka_sink_retention_bytes(retention_bytes)?;
     }
 
-    if retention_bytes.unwrap_or(0) < -1 {
-        sql_bail!("RETENTION BYTES for sink topics must be greater than or equal to -1");
+    // Sinks produce with idempotent, transactional producers, which librdkafka
+    // only permits with `acks=all`.
+    if let Some(acks) = &acks {
+        if acks != "all" && acks != "-1" {
+            sql_bail!("ACKS for sinks must be 'all' or '-1', not '{}'", acks);
+        }
+    }
+
+    if enable_idempotence == Some(false) {
+        sql_bail!("ENABLE IDEMPOTENCE cannot be disabled for sinks");
+    }
+
+    let topic_config = plan_kafka_sink_topic_config(topic_config)?;
+
+    let topic_fanout = match (topic_column, topic_template) {
+        (None, None) => None,
+        (Some(column), Some(template)) => Some(plan_kafka_sink_topic_fanout(
+            scx,
+            &value_desc,
+            column,
+            template,
+            partition_count,
+            replication_factor,
+            topic_config.clone(),
+        )?),
+        (Some(_), None) => sql_bail!("TOPIC COLUMN requires TOPIC TEMPLATE"),
+        (None, Some(_)) => sql_bail!("TOPIC TEMPLATE requires TOPIC COLUMN"),
+    };
+    if topic_fanout.is_some() {
+        // The partitions of each topic, and the topic of messages that carry
+        // no row, are unknown.
+        if partition_by.is_some() {
+            sql_bail!("TOPIC COLUMN cannot be combined with PARTITION BY");
+        }
+        if dead_letter_topic.is_some() {
+            sql_bail!("TOPIC COLUMN cannot be combined with DEAD LETTER TOPIC");
+        }
+        match envelope {
+            SinkEnvelope::Debezium
+            | SinkEnvelope::Upsert(SinkDeletePolicy::DeleteRecord | SinkDeletePolicy::Suppress)
+            | SinkEnvelope::AppendOnly(SinkRetractionPolicy::Error | SinkRetractionPolicy::Drop) => {
+            }
+            SinkEnvelope::Upsert(SinkDeletePolicy::Tombstone)
+            | SinkEnvelope::AppendOnly(SinkRetractionPolicy::DeleteMarker) => {
+                sql_bail!("TOPIC COLUMN is not supported by sinks that emit delete markers")
+            }
+            SinkEnvelope::CdcV2 => {
+                sql_bail!("TOPIC COLUMN is not supported with ENVELOPE MATERIALIZE")
+            }
+        }
     }
 
+    let linger_ms = linger_ms.map(plan_kafka_sink_linger_ms).transpose()?;
+    let batch_num_messages = batch_num_messages
+        .map(plan_kafka_sink_batch_num_messages)
+        .transpose()?;
+
     let retention = KafkaSinkConnectionRetention {
         duration: retention_ms,
         bytes: retention_bytes,
     };
 
+    let retry = plan_kafka_sink_retry_policy(
+        retry_max_attempts,
+        retry_backoff,
+        retry_max_backoff,
+        retry_exhausted_action,
+        dead_letter_topic.is_some(),
+    )?;
+
     Ok(StorageSinkConnectionBuilder::Kafka(
         KafkaSinkConnectionBuilder {
             connection_id,
@@ -1993,18 +5096,340 @@ fn kafka_sink_builder(
             options: config_options,
             format,
             topic_name,
-            consistency_config,
+            headers,
+            partition_by,
+            message_timestamp,
+            compression_type,
+            linger_ms,
+            batch_num_messages,
+            dead_letter_topic,
+            retry,
+            topic_fanout,
+            progress_topic,
             partition_count,
             replication_factor,
-            fuel: 10000,
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
             retention,
+            topic_config,
         },
     ))
 }
 
+/// Validates the `MAX` rate limits of a sink.
+fn plan_sink_max_rate(
+    bytes_per_second: Option<u64>,
+    messages_per_second: Option<u64>,
+) -> Result<(), PlanError> {
+    if bytes_per_second == Some(0) {
+        sql_bail!("MAX BYTES PER SECOND must be a positive integer");
+    }
+    if messages_per_second == Some(0) {
+        sql_bail!("MAX MESSAGES PER SECOND must be a positive integer");
+    }
+    Ok(())
+}
+
+/// Resolves the `RETRACTIONS` option of an append-only sink.
+fn plan_sink_retraction_policy(
+    retractions: Option<&str>,
+) -> Result<SinkRetractionPolicy, PlanError> {
+    match retractions.map(|r| r.to_lowercase()).as_deref() {
+        None | Some("error") => Ok(SinkRetractionPolicy::Error),
+        Some("drop") => Ok(SinkRetractionPolicy::Drop),
+        Some("delete marker") => Ok(SinkRetractionPolicy::DeleteMarker),
+        Some(other) => sql_bail!(
+            "invalid RETRACTIONS {}: must be one of 'error', 'drop', or 'delete marker'",
+            other.quoted()
+        ),
+    }
+}
+
+fn plan_sink_delete_policy(deletes: Option<&str>) -> Result<SinkDeletePolicy, PlanError> {
+    match deletes.map(|d| d.to_lowercase()).as_deref() {
+        None | Some("tombstone") => Ok(SinkDeletePolicy::Tombstone),
+        Some("delete record") => Ok(SinkDeletePolicy::DeleteRecord),
+        Some("suppress") => Ok(SinkDeletePolicy::Suppress),
+        Some(other) => sql_bail!(
+            "invalid DELETES {}: must be one of 'tombstone', 'delete record', or 'suppress'",
+            other.quoted()
+        ),
+    }
+}
+
+/// The name of the column that marks deleted rows if `DELETE MARKER` is not
+/// specified.
+const DEFAULT_SINK_DELETE_MARKER: &str = "__deleted";
+
+/// Appends the column that marks the rows of an upsert sink that emits deletes
+/// as delete records.
+fn plan_sink_delete_marker(
+    desc: RelationDesc,
+    delete_marker: Option<String>,
+) -> Result<RelationDesc, PlanError> {
+    let name = delete_marker.unwrap_or_else(|| DEFAULT_SINK_DELETE_MARKER.into());
+    if name.is_empty() {
+        sql_bail!("DELETE MARKER must not be empty");
+    }
+    if desc.get_by_name(&ColumnName::from(name.as_str())).is_some() {
+        sql_bail!(
+            "invalid DELETE MARKER {}: the sink already emits a column with that name",
+            name.quoted()
+        );
+    }
+    Ok(desc.with_column(name, ScalarType::Bool.nullable(false)))
+}
+
+/// Validates the `CATCH UP` rate limits of a sink.
+fn plan_sink_catch_up_rate(
+    bytes_per_second: Option<u64>,
+    records_per_second: Option<u64>,
+) -> Result<(), PlanError> {
+    if bytes_per_second == Some(0) {
+        sql_bail!("CATCH UP BYTES PER SECOND must be a positive integer");
+    }
+    if records_per_second == Some(0) {
+        sql_bail!("CATCH UP RECORDS PER SECOND must be a positive integer");
+    }
+    Ok(())
+}
+
+// The ranges of values come from `linger.ms` and `batch.num.messages` in
+// https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md
+
+/// Validates the `LINGER MS` of a Kafka sink.
+fn plan_kafka_sink_linger_ms(linger_ms: i32) -> Result<u64, PlanError> {
+    match linger_ms {
+        ms @ 0..=900_000 => Ok(u64::from(ms.unsigned_abs())),
+        _ => sql_bail!("LINGER MS must be within [0, 900,000]"),
+    }
+}
+
+/// Plans the `RETRY` options of a Kafka sink, which override the defaults of
+/// [`KafkaSinkRetryPolicy`].
+fn plan_kafka_sink_retry_policy(
+    max_attempts: Option<u64>,
+    backoff: Option<Interval>,
+    max_backoff: Option<Interval>,
+    exhausted_action: Option<KafkaSinkRetryExhaustedAction>,
+    has_dead_letter_topic: bool,
+) -> Result<KafkaSinkRetryPolicy, PlanError> {
+    let default = KafkaSinkRetryPolicy::default();
+    let initial_backoff = match backoff {
+        Some(backoff) => backoff.duration()?,
+        None => default.initial_backoff,
+    };
+    let max_backoff = match max_backoff {
+        Some(max_backoff) => max_backoff.duration()?,
+        None => default.max_backoff,
+    };
+    if initial_backoff > max_backoff {
+        sql_bail!("RETRY BACKOFF must not exceed RETRY MAX BACKOFF");
+    }
+    let exhausted_action = match exhausted_action {
+        Some(_) if max_attempts.is_none() => {
+            sql_bail!("RETRY EXHAUSTED ACTION requires RETRY MAX ATTEMPTS")
+        }
+        Some(KafkaSinkRetryExhaustedAction::DeadLetter) if !has_dead_letter_topic => {
+            sql_bail!("RETRY EXHAUSTED ACTION 'dead letter' requires DEAD LETTER TOPIC")
+        }
+        Some(exhausted_action) => exhausted_action,
+        None => default.exhausted_action,
+    };
+    Ok(KafkaSinkRetryPolicy {
+        max_attempts,
+        initial_backoff,
+        max_backoff,
+        exhausted_action,
+    })
+}
+
+/// Validates the `BATCH NUM MESSAGES` of a Kafka sink.
+fn plan_kafka_sink_batch_num_messages(batch_num_messages: i32) -> Result<u64, PlanError> {
+    match batch_num_messages {
+        n @ 1..=1_000_000 => Ok(u64::from(n.unsigned_abs())),
+        _ => sql_bail!("BATCH NUM MESSAGES must be within [1, 1,000,000]"),
+    }
+}
+
+/// Validates the `RETENTION MS` of a Kafka sink topic.
+fn plan_kafka_sink_retention_ms(retention_ms: i64) -> Result<(), PlanError> {
+    if retention_ms < -1 {
+        sql_bail!("RETENTION MS for sink topics must be greater than or equal to -1");
+    }
+    Ok(())
+}
+
+/// Validates the `RETENTION BYTES` of a Kafka sink topic.
+fn plan_kafka_sink_retention_bytes(retention_bytes: i64) -> Result<(), PlanError> {
+    if retention_bytes < -1 {
+        sql_bail!("RETENTION BYTES for sink topics must be greater than or equal to -1");
+    }
+    Ok(())
+}
+
+/// Validates the `TOPIC CONFIG` option of a Kafka sink.
+///
+/// Only topic-level configs whose values can be checked up front are
+/// accepted, so that the sink does not fail to create its topic later on.
+fn plan_kafka_sink_topic_config(
+    entries: Vec<KafkaTopicConfigEntry>,
+) -> Result<BTreeMap<String, String>, PlanError> {
+    let mut topic_config = BTreeMap::new();
+    for KafkaTopicConfigEntry { name, value } in entries {
+        let valid = match name.as_str() {
+            "cleanup.policy" => matches!(
+                value.as_str(),
+                "delete" | "compact" | "compact,delete" | "delete,compact"
+            ),
+            "message.timestamp.type" => matches!(value.as_str(), "CreateTime" | "LogAppendTime"),
+            "max.message.bytes" | "min.insync.replicas" | "segment.bytes" => {
+                matches!(value.parse::<i32>(), Ok(v) if v > 0)
+            }
+            "delete.retention.ms"
+            | "max.compaction.lag.ms"
+            | "min.compaction.lag.ms"
+            | "segment.ms" => matches!(value.parse::<i64>(), Ok(v) if v >= 0),
+            "retention.bytes" | "retention.ms" => sql_bail!(
+                "TOPIC CONFIG cannot set {}: use the RETENTION options instead",
+                name.quoted()
+            ),
+            _ => sql_bail!(
+                "TOPIC CONFIG does not support {}: must be one of cleanup.policy, \
+                 delete.retention.ms, max.compaction.lag.ms, max.message.bytes, \
+                 message.timestamp.type, min.compaction.lag.ms, min.insync.replicas, \
+                 segment.bytes, or segment.ms",
+                name.quoted()
+            ),
+        };
+        if !valid {
+            sql_bail!(
+                "invalid TOPIC CONFIG value {} for {}",
+                value.quoted(),
+                name.quoted()
+            );
+        }
+        if topic_config.insert(name.clone(), value).is_some() {
+            sql_bail!("TOPIC CONFIG specifies {} more than once", name.quoted());
+        }
+    }
+    Ok(topic_config)
+}
+
+/// Resolves the columns of the `HEADERS` option of a Kafka sink on
+/// `value_desc`.
+fn plan_kafka_sink_headers(
+    value_desc: &RelationDesc,
+    headers: Vec<KafkaHeaderColumn>,
+) -> Result<Vec<KafkaSinkHeader>, PlanError> {
+    let mut keys = HashSet::new();
+    let mut planned = Vec::with_capacity(headers.len());
+    for KafkaHeaderColumn { column, key } in headers {
+        let name = normalize::column_name(column);
+        let (column, _typ) = value_desc
+            .get_by_name(&name)
+            .ok_or_else(|| sql_err!("No such column: {}", name))?;
+        if value_desc.get_unambiguous_name(column).is_none() {
+            sql_bail!("Ambiguous column: {}", name);
+        }
+        let key = key.unwrap_or_else(|| name.as_str().to_string());
+        if key.is_empty() {
+            sql_bail!("HEADERS keys must not be empty");
+        }
+        if key == KAFKA_SINK_TIMESTAMP_HEADER {
+            sql_bail!("HEADERS key {} is reserved", key.quoted());
+        }
+        if !keys.insert(key.clone()) {
+            sql_bail!("HEADERS key {} specified more than once", key.quoted());
+        }
+        planned.push(KafkaSinkHeader { key, column });
+    }
+    Ok(planned)
+}
+
+/// Resolves the `MESSAGE TIMESTAMP` option of a Kafka sink on `value_desc`.
+fn plan_kafka_sink_message_timestamp(
+    scx: &StatementContext,
+    value_desc: &RelationDesc,
+    message_timestamp: KafkaMessageTimestamp,
+) -> Result<KafkaSinkMessageTimestamp, PlanError> {
+    let column = match message_timestamp {
+        KafkaMessageTimestamp::MzTimestamp => return Ok(KafkaSinkMessageTimestamp::MzTimestamp),
+        KafkaMessageTimestamp::Column(column) => column,
+    };
+    let name = normalize::column_name(column);
+    let (column, typ) = value_desc
+        .get_by_name(&name)
+        .ok_or_else(|| sql_err!("No such column: {}", name))?;
+    if value_desc.get_unambiguous_name(column).is_none() {
+        sql_bail!("Ambiguous column: {}", name);
+    }
+    match typ.scalar_type {
+        ScalarType::Timestamp | ScalarType::TimestampTz => {}
+        ref other => sql_bail!(
+            "MESSAGE TIMESTAMP column {} must have type timestamp or timestamp with time zone, not {}",
+            name.as_str().quoted(),
+            scx.humanize_scalar_type(other)
+        ),
+    }
+    Ok(KafkaSinkMessageTimestamp::Column(column))
+}
+
+/// Resolves the `TOPIC COLUMN` and `TOPIC TEMPLATE` options of a Kafka sink
+/// on `value_desc`.
+fn plan_kafka_sink_topic_fanout(
+    scx: &StatementContext,
+    value_desc: &RelationDesc,
+    column: Ident,
+    template: String,
+    partition_count: i32,
+    replication_factor: i32,
+    topic_config: BTreeMap<String, String>,
+) -> Result<KafkaSinkTopicFanout, PlanError> {
+    let name = normalize::column_name(column);
+    let (column, typ) = value_desc
+        .get_by_name(&name)
+        .ok_or_else(|| sql_err!("No such column: {}", name))?;
+    if value_desc.get_unambiguous_name(column).is_none() {
+        sql_bail!("Ambiguous column: {}", name);
+    }
+    match typ.scalar_type {
+        ScalarType::String | ScalarType::VarChar { .. } => {}
+        ref other => sql_bail!(
+            "TOPIC COLUMN {} must have type text or varchar, not {}",
+            name.as_str().quoted(),
+            scx.humanize_scalar_type(other)
+        ),
+    }
+    let (prefix, suffix) = match template.split_once("{}") {
+        Some((prefix, suffix)) if !suffix.contains("{}") => (prefix, suffix),
+        _ => sql_bail!("TOPIC TEMPLATE must contain {{}} exactly once"),
+    };
+    // A prefix keeps the sink from writing to arbitrary topics, like those
+    // of other sinks.
+    if prefix.is_empty() {
+        sql_bail!("TOPIC TEMPLATE must not begin with {{}}");
+    }
+    if !prefix
+        .chars()
+        .chain(suffix.chars())
+        .all(is_kafka_topic_name_char)
+    {
+        sql_bail!(
+            "TOPIC TEMPLATE {} may only contain ASCII alphanumerics, '.', '_' and '-' besides {{}}",
+            template.quoted()
+        );
+    }
+    Ok(KafkaSinkTopicFanout {
+        column,
+        template,
+        partition_count,
+        replication_factor,
+        topic_config,
+    })
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -2284,11 +5709,9 @@ pub fn plan_create_role(
     if login != Some(true) {
         bail_unsupported!("non-login users");
     }
-    if super_user != Some(true) {
-        bail_unsupported!("non-superusers");
-    }
     Ok(Plan::CreateRole(CreateRolePlan {
         name: normalize::ident(name),
+        superuser: super_user.unwrap_or(false),
     }))
 }
 
@@ -2505,13 +5928,21 @@ generate_extracted_config!(
     KafkaConnectionOption,
     (Broker, String),
     (Brokers, Vec<String>),
+    (EventHubsConnectionString, with_options::Secret),
     (ProgressTopic, String),
     (SslKey, with_options::Secret),
     (SslCertificate, StringOrSecret),
     (SslCertificateAuthority, StringOrSecret),
     (SaslMechanisms, String),
     (SaslUsername, StringOrSecret),
-    (SaslPassword, with_options::Secret)
+    (SaslPassword, with_options::Secret),
+    (SaslOauthTokenEndpoint, String),
+    (SaslOauthClientId, StringOrSecret),
+    (SaslOauthClientSecret, with_options::Secret),
+    (SaslOauthScope, String),
+    (AwsConnection, with_options::Object),
+    (AwsPrivatelink, with_options::Object),
+    (SshTunnel, with_options::Object)
 );
 
 impl KafkaConnectionOptionExtracted {
@@ -2525,13 +5956,15 @@ impl KafkaConnectionOptionExtracted {
 
         for broker in &mut brokers {
             // Normalize Kafka addresses
-            *broker = KafkaAddrs::from_str(broker)
-                .map_err(|e| sql_err!("parsing kafka broker: {e}"))?
-                .to_string();
-            if broker.contains(',') {
-                sql_bail!("invalid CONNECTION: cannot specify multiple Kafka broker addresses in one string.\n\n
+            let addrs = KafkaBrokerAddrs::from_str(broker)
+                .map_err(|e| sql_err!("parsing kafka broker: {e}"))?;
+            if let KafkaBrokerAddrs::Static(addrs) = &addrs {
+                if addrs.to_string().contains(',') {
+                    sql_bail!("invalid CONNECTION: cannot specify multiple Kafka broker addresses in one string.\n\n
 Instead, specify BROKERS using multiple strings, e.g. BROKERS ('kafka:9092', 'kafka:9093')");
+                }
             }
+            *broker = addrs.to_string();
         }
 
         Ok(brokers)
@@ -2544,6 +5977,178 @@ Instead, specify BROKERS using multiple strings, e.g. BROKERS ('kafka:9092', 'ka
         use KafkaConnectionOptionName::*;
         HashSet::from([SaslMechanisms, SaslUsername, SaslPassword])
     }
+    pub fn oauth_bearer_config(&self) -> HashSet<KafkaConnectionOptionName> {
+        use KafkaConnectionOptionName::*;
+        HashSet::from([
+            SaslOauthTokenEndpoint,
+            SaslOauthClientId,
+            SaslOauthClientSecret,
+            SaslOauthScope,
+            AwsConnection,
+        ])
+    }
+
+    /// Plans the `OAUTHBEARER` configuration, if `SASL MECHANISMS` selects it.
+    fn oauth_bearer(&self, scx: &StatementContext) -> Result<Option<OAuthBearerConfig>, PlanError> {
+        let is_oauth_bearer = matches!(
+            &self.sasl_mechanisms,
+            Some(mechanisms) if mechanisms.eq_ignore_ascii_case("OAUTHBEARER")
+        );
+        if !is_oauth_bearer {
+            if self
+                .oauth_bearer_config()
+                .iter()
+                .any(|c| self.seen.contains(c))
+            {
+                sql_bail!(
+                    "invalid CONNECTION: SASL OAUTH options and AWS CONNECTION require SASL MECHANISMS = 'OAUTHBEARER'"
+                );
+            }
+            return Ok(None);
+        }
+
+        if self.sasl_username.is_some() || self.sasl_password.is_some() {
+            sql_bail!(
+                "invalid CONNECTION: cannot specify SASL USERNAME or SASL PASSWORD with SASL MECHANISMS = 'OAUTHBEARER'"
+            );
+        }
+
+        let token_source = match (&self.sasl_oauth_token_endpoint, self.aws_connection) {
+            (Some(_), Some(_)) => sql_bail!(
+                "invalid CONNECTION: cannot specify both SASL OAUTH TOKEN ENDPOINT and AWS CONNECTION"
+            ),
+            (None, None) => sql_bail!(
+                "invalid CONNECTION: SASL MECHANISMS = 'OAUTHBEARER' requires either SASL OAUTH TOKEN ENDPOINT or AWS CONNECTION"
+            ),
+            (Some(token_endpoint), None) => {
+                let token_endpoint = token_endpoint
+                    .parse()
+                    .map_err(|e| sql_err!("parsing SASL OAUTH TOKEN ENDPOINT: {e}"))?;
+                match (&self.sasl_oauth_client_id, self.sasl_oauth_client_secret) {
+                    (Some(client_id), Some(client_secret)) => {
+                        OAuthBearerTokenSource::ClientCredentials(OAuthClientCredentials {
+                            token_endpoint,
+                            client_id: client_id.clone(),
+                            client_secret: client_secret.into(),
+                            scope: self.sasl_oauth_scope.clone(),
+                        })
+                    }
+                    _ => sql_bail!(
+                        "invalid CONNECTION: SASL OAUTH TOKEN ENDPOINT requires both SASL OAUTH CLIENT ID and SASL OAUTH CLIENT SECRET"
+                    ),
+                }
+            }
+            (None, Some(aws_connection)) => {
+                if self.sasl_oauth_client_id.is_some()
+                    || self.sasl_oauth_client_secret.is_some()
+                    || self.sasl_oauth_scope.is_some()
+                {
+                    sql_bail!(
+                        "invalid CONNECTION: cannot specify SASL OAUTH options with AWS CONNECTION"
+                    );
+                }
+                let connection_id = aws_connection.into();
+                let item = scx.catalog.get_item(&connection_id);
+                match item.connection()? {
+                    Connection::Aws(aws) => OAuthBearerTokenSource::AwsIam {
+                        connection_id,
+                        connection: aws.clone(),
+                    },
+                    _ => sql_bail!("{} is not an AWS connection", item.name().item),
+                }
+            }
+        };
+
+        Ok(Some(OAuthBearerConfig {
+            token_source,
+            tls_root_cert: self.ssl_certificate_authority.clone(),
+        }))
+    }
+
+    /// Plans the `SASL PLAIN` configuration with which clients authenticate
+    /// to Event Hubs, if `EVENT HUBS CONNECTION STRING` is specified.
+    ///
+    /// Event Hubs accepts the connection string of a shared access policy as
+    /// the password of the fixed user `$ConnectionString`.
+    fn event_hubs(&self) -> Result<Option<SaslConfig>, PlanError> {
+        let connection_string = match self.event_hubs_connection_string {
+            Some(connection_string) => connection_string,
+            None => return Ok(None),
+        };
+        if [
+            self.ssl_config(),
+            self.sasl_config(),
+            self.oauth_bearer_config(),
+        ]
+        .iter()
+        .flatten()
+        .chain(&[KafkaConnectionOptionName::SslCertificateAuthority])
+        .any(|c| self.seen.contains(c))
+        {
+            sql_bail!(
+                "invalid CONNECTION: cannot specify SSL or SASL options with EVENT HUBS CONNECTION STRING"
+            );
+        }
+        Ok(Some(SaslConfig {
+            mechanisms: "PLAIN".into(),
+            username: StringOrSecret::String("$ConnectionString".into()),
+            password: connection_string.into(),
+            tls_root_cert: None,
+        }))
+    }
+
+    fn to_security(&self, scx: &StatementContext) -> Result<Option<KafkaSecurity>, PlanError> {
+        if let Some(event_hubs_config) = self.event_hubs()? {
+            return Ok(Some(KafkaSecurity::from(event_hubs_config)));
+        }
+        let ssl_config = Option::<KafkaTlsConfig>::from(self).map(KafkaSecurity::from);
+        let sasl_config = Option::<SaslConfig>::from(self).map(KafkaSecurity::from);
+        let oauth_bearer_config = self.oauth_bearer(scx)?.map(KafkaSecurity::from);
+
+        let mut security_iter = vec![ssl_config, sasl_config, oauth_bearer_config].into_iter();
+        let res = match security_iter.find(|v| v.is_some()) {
+            Some(config) => {
+                if security_iter.find(|v| v.is_some()).is_some() {
+                    sql_bail!("invalid CONNECTION: cannot specify multiple security protocols");
+                }
+                config
+            }
+            None => None,
+        };
+
+        if res.is_none()
+            && [self.sasl_config(), self.ssl_config()]
+                .iter()
+                .flatten()
+                .any(|c| self.seen.contains(c))
+        {
+            sql_bail!("invalid CONNECTION: under-specified security configuration");
+        }
+
+        Ok(res)
+    }
+
+    pub fn to_connection(self, scx: &StatementContext) -> Result<KafkaConnection, PlanError> {
+        let ssh_tunnel_id = self.ssh_tunnel.map(|ssh_tunnel| ssh_tunnel.into());
+        let ssh_tunnel = plan_ssh_tunnel(scx, ssh_tunnel_id.as_ref())?;
+        let aws_privatelink = plan_aws_privatelink(scx, self.aws_privatelink)?;
+        if ssh_tunnel.is_some() && aws_privatelink.is_some() {
+            sql_bail!("invalid CONNECTION: cannot specify both SSH TUNNEL and AWS PRIVATELINK");
+        }
+        let aws_privatelink = plan_aws_privatelink(scx, self.aws_privatelink)?;
+        if ssh_tunnel.is_some() && aws_privatelink.is_some() {
+            sql_bail!("invalid CONNECTION: cannot specify both SSH TUNNEL and AWS PRIVATELINK");
+        }
+        Ok(KafkaConnection {
+            brokers: self.get_brokers()?,
+            security: self.to_security(scx)?,
+            progress_topic: self.progress_topic,
+            event_hubs: self.event_hubs_connection_string.is_some(),
+            ssh_tunnel_id,
+            ssh_tunnel,
+            aws_privatelink,
+        })
+    }
 }
 
 impl From<&KafkaConnectionOptionExtracted> for Option<KafkaTlsConfig> {
@@ -2577,44 +6182,42 @@ impl From<&KafkaConnectionOptionExtracted> for Option<SaslConfig> {
     }
 }
 
-impl TryFrom<&KafkaConnectionOptionExtracted> for Option<KafkaSecurity> {
-    type Error = PlanError;
-    fn try_from(value: &KafkaConnectionOptionExtracted) -> Result<Self, Self::Error> {
-        let ssl_config = Option::<KafkaTlsConfig>::from(value).map(KafkaSecurity::from);
-        let sasl_config = Option::<SaslConfig>::from(value).map(KafkaSecurity::from);
-
-        let mut security_iter = vec![ssl_config, sasl_config].into_iter();
-        let res = match security_iter.find(|v| v.is_some()) {
-            Some(config) => {
-                if security_iter.find(|v| v.is_some()).is_some() {
-                    sql_bail!("invalid CONNECTION: cannot specify multiple security protocols");
-                }
-                config
+/// Validates that the `AWS PRIVATELINK` of a connection, if any, is indeed an
+/// AWS PrivateLink connection, and returns its details.
+fn plan_aws_privatelink(
+    scx: &StatementContext,
+    aws_privatelink: Option<with_options::Object>,
+) -> Result<Option<mz_storage::types::connections::AwsPrivatelinkConnection>, PlanError> {
+    match aws_privatelink {
+        Some(aws_privatelink) => {
+            let aws_privatelink = scx.catalog.get_item(&aws_privatelink.into());
+            match aws_privatelink.connection()? {
+                Connection::AwsPrivatelink(aws_privatelink) => Ok(Some(aws_privatelink.clone())),
+                _ => sql_bail!(
+                    "{} is not an AWS PRIVATELINK connection",
+                    aws_privatelink.name().item
+                ),
             }
-            None => None,
-        };
-
-        if res.is_none()
-            && [value.sasl_config(), value.ssl_config()]
-                .iter()
-                .flatten()
-                .any(|c| value.seen.contains(c))
-        {
-            sql_bail!("invalid CONNECTION: under-specified security configuration");
         }
-
-        Ok(res)
+        None => Ok(None),
     }
 }
 
-impl TryFrom<KafkaConnectionOptionExtracted> for KafkaConnection {
-    type Error = PlanError;
-    fn try_from(value: KafkaConnectionOptionExtracted) -> Result<Self, Self::Error> {
-        Ok(KafkaConnection {
-            brokers: value.get_brokers()?,
-            security: (&value).try_into()?,
-            progress_topic: value.progress_topic,
-        })
+/// Validates that the `SSH TUNNEL` of a connection, if any, is indeed an SSH
+/// connection, and returns its details.
+fn plan_ssh_tunnel(
+    scx: &StatementContext,
+    ssh_tunnel_id: Option<&GlobalId>,
+) -> Result<Option<mz_storage::types::connections::SshConnection>, PlanError> {
+    match ssh_tunnel_id {
+        Some(ssh_tunnel) => {
+            let ssh_tunnel = scx.catalog.get_item(ssh_tunnel);
+            match ssh_tunnel.connection()? {
+                Connection::Ssh(ssh) => Ok(Some(ssh.clone())),
+                _ => sql_bail!("{} is not an SSH connection", ssh_tunnel.name().item),
+            }
+        }
+        None => Ok(None),
     }
 }
 
@@ -2665,6 +6268,7 @@ generate_extracted_config!(
     (Host, String),
     (Password, with_options::Secret),
     (Port, u16, Default(5432_u16)),
+    (AwsPrivatelink, with_options::Object),
     (SshTunnel, with_options::Object),
     (SslCertificate, StringOrSecret),
     (SslCertificateAuthority, StringOrSecret),
@@ -2697,17 +6301,8 @@ impl PostgresConnectionOptionExtracted {
             Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
         };
 
-        // Validate that the SSH tunnel ID is indeed an SSH connection
         let ssh_tunnel_id = self.ssh_tunnel.map(|ssh_tunnel| ssh_tunnel.into());
-        let ssh_tunnel = if let Some(ref ssh_tunnel) = ssh_tunnel_id {
-            let ssh_tunnel = scx.catalog.get_item(ssh_tunnel);
-            match ssh_tunnel.connection()? {
-                Connection::Ssh(ssh) => Some(ssh.clone()),
-                _ => sql_bail!("{} is not an SSH connection", ssh_tunnel.name().item),
-            }
-        } else {
-            None
-        };
+        let ssh_tunnel = plan_ssh_tunnel(scx, ssh_tunnel_id.as_ref())?;
 
         Ok(mz_storage::types::connections::PostgresConnection {
             database: self
@@ -2720,6 +6315,7 @@ impl PostgresConnectionOptionExtracted {
             port: self.port,
             ssh_tunnel_id,
             ssh_tunnel,
+            aws_privatelink,
             tls_mode,
             tls_root_cert: self.ssl_certificate_authority,
             tls_identity,
@@ -2730,6 +6326,86 @@ impl PostgresConnectionOptionExtracted {
     }
 }
 
+generate_extracted_config!(
+    MySqlConnectionOption,
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16, Default(3306_u16)),
+    (SslCertificateAuthority, StringOrSecret),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl TryFrom<MySqlConnectionOptionExtracted> for mz_storage::types::connections::MySqlConnection {
+    type Error = PlanError;
+
+    fn try_from(options: MySqlConnectionOptionExtracted) -> Result<Self, Self::Error> {
+        let tls_mode = match options.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => MySqlSslMode::Disabled,
+            Some("required") => MySqlSslMode::Required,
+            Some("verify_ca") | Some("verify-ca") => MySqlSslMode::VerifyCa,
+            Some("verify_identity") | Some("verify-identity") => MySqlSslMode::VerifyIdentity,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+        Ok(mz_storage::types::connections::MySqlConnection {
+            host: options
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            port: options.port,
+            user: options
+                .user
+                .ok_or_else(|| sql_err!("USER option is required"))?,
+            password: options.password.map(|password| password.into()),
+            tls_mode,
+            tls_root_cert: options.ssl_certificate_authority,
+        })
+    }
+}
+
+generate_extracted_config!(
+    SqlServerConnectionOption,
+    (Database, String),
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16, Default(1433_u16)),
+    (SslCertificateAuthority, StringOrSecret),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl TryFrom<SqlServerConnectionOptionExtracted>
+    for mz_storage::types::connections::SqlServerConnection
+{
+    type Error = PlanError;
+
+    fn try_from(options: SqlServerConnectionOptionExtracted) -> Result<Self, Self::Error> {
+        let tls_mode = match options.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => SqlServerSslMode::Disabled,
+            Some("required") => SqlServerSslMode::Required,
+            Some("verify") => SqlServerSslMode::Verify,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+        if options.ssl_certificate_authority.is_some() && tls_mode != SqlServerSslMode::Verify {
+            sql_bail!("invalid CONNECTION: SSL CERTIFICATE AUTHORITY requires SSL MODE 'verify'");
+        }
+        Ok(mz_storage::types::connections::SqlServerConnection {
+            host: options
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            port: options.port,
+            database: options
+                .database
+                .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+            user: options
+                .user
+                .ok_or_else(|| sql_err!("USER option is required"))?,
+            password: options.password.map(|password| password.into()),
+            tls_mode,
+            tls_root_cert: options.ssl_certificate_authority,
+        })
+    }
+}
+
 generate_extracted_config!(
     SshConnectionOption,
     (Host, String),
@@ -2754,6 +6430,27 @@ impl TryFrom<SshConnectionOptionExtracted> for mz_storage::types::connections::S
     }
 }
 
+generate_extracted_config!(AwsPrivatelinkConnectionOption, (Endpoint, String));
+
+impl TryFrom<AwsPrivatelinkConnectionOptionExtracted>
+    for mz_storage::types::connections::AwsPrivatelinkConnection
+{
+    type Error = PlanError;
+
+    fn try_from(options: AwsPrivatelinkConnectionOptionExtracted) -> Result<Self, Self::Error> {
+        let endpoint = options
+            .endpoint
+            .ok_or_else(|| sql_err!("ENDPOINT option is required"))?;
+        if endpoint.is_empty() || endpoint.contains(|c| c == ':' || c == '/') {
+            sql_bail!(
+                "invalid ENDPOINT {}: must be the DNS name of a VPC endpoint, without a scheme or port",
+                endpoint.quoted()
+            );
+        }
+        Ok(mz_storage::types::connections::AwsPrivatelinkConnection { endpoint })
+    }
+}
+
 generate_extracted_config!(
     AwsConnectionOption,
     (AccessKeyId, StringOrSecret),
@@ -2810,7 +6507,7 @@ pub fn plan_create_connection(
     let connection = match connection {
         CreateConnection::Kafka { with_options } => {
             let k = KafkaConnectionOptionExtracted::try_from(with_options)?;
-            Connection::Kafka(KafkaConnection::try_from(k)?)
+            Connection::Kafka(k.to_connection(scx)?)
         }
         CreateConnection::Csr { with_options } => {
             let c = CsrConnectionOptionExtracted::try_from(with_options)?;
@@ -2822,11 +6519,28 @@ pub fn plan_create_connection(
             let connection = c.to_connection(scx)?;
             Connection::Postgres(connection)
         }
+        CreateConnection::MySql { with_options } => {
+            scx.require_unsafe_mode("CREATE CONNECTION ... TO MYSQL")?;
+            let c = MySqlConnectionOptionExtracted::try_from(with_options)?;
+            let connection = mz_storage::types::connections::MySqlConnection::try_from(c)?;
+            Connection::MySql(connection)
+        }
+        CreateConnection::SqlServer { with_options } => {
+            scx.require_unsafe_mode("CREATE CONNECTION ... TO SQL SERVER")?;
+            let c = SqlServerConnectionOptionExtracted::try_from(with_options)?;
+            let connection = mz_storage::types::connections::SqlServerConnection::try_from(c)?;
+            Connection::SqlServer(connection)
+        }
         CreateConnection::Aws { with_options } => {
             let c = AwsConnectionOptionExtracted::try_from(with_options)?;
             let connection = AwsConfig::try_from(c)?;
             Connection::Aws(connection)
         }
+        CreateConnection::AwsPrivatelink { with_options } => {
+            let c = AwsPrivatelinkConnectionOptionExtracted::try_from(with_options)?;
+            let connection = mz_storage::types::connections::AwsPrivatelinkConnection::try_from(c)?;
+            Connection::AwsPrivatelink(connection)
+        }
         CreateConnection::Ssh { with_options } => {
             let c = SshConnectionOptionExtracted::try_from(with_options)?;
             let connection = mz_storage::types::connections::SshConnection::try_from(c)?;
@@ -2915,7 +6629,11 @@ pub fn plan_drop_objects(
         | ObjectType::Type
         | ObjectType::Secret
         | ObjectType::Connection => plan_drop_items(scx, object_type, &items, cascade),
-        ObjectType::Role | ObjectType::Cluster | ObjectType::ClusterReplica => {
+        ObjectType::Role
+        | ObjectType::Cluster
+        | ObjectType::ClusterReplica
+        | ObjectType::Database
+        | ObjectType::Schema => {
             unreachable!("handled through their respective plan_drop functions")
         }
         ObjectType::Object => unreachable!("cannot drop generic OBJECT, must provide object type"),
@@ -3016,6 +6734,136 @@ pub fn plan_drop_role(
     Ok(Plan::DropRoles(DropRolesPlan { names: out }))
 }
 
+pub fn describe_grant_privileges(
+    _: &StatementContext,
+    _: GrantPrivilegesStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_grant_privileges(
+    scx: &StatementContext,
+    GrantPrivilegesStatement {
+        privileges,
+        target,
+        roles,
+    }: GrantPrivilegesStatement,
+) -> Result<Plan, PlanError> {
+    let (privileges, target, role_ids) = plan_privileges(scx, privileges, target, roles)?;
+    Ok(Plan::GrantPrivileges(GrantPrivilegesPlan {
+        privileges,
+        target,
+        role_ids,
+    }))
+}
+
+pub fn describe_revoke_privileges(
+    _: &StatementContext,
+    _: RevokePrivilegesStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_revoke_privileges(
+    scx: &StatementContext,
+    RevokePrivilegesStatement {
+        privileges,
+        target,
+        roles,
+    }: RevokePrivilegesStatement,
+) -> Result<Plan, PlanError> {
+    let (privileges, target, role_ids) = plan_privileges(scx, privileges, target, roles)?;
+    Ok(Plan::RevokePrivileges(RevokePrivilegesPlan {
+        privileges,
+        target,
+        role_ids,
+    }))
+}
+
+/// Resolves the target and grantees of a `GRANT` or `REVOKE` statement and
+/// checks that the requested privileges apply to the target.
+fn plan_privileges(
+    scx: &StatementContext,
+    privileges: PrivilegeSpecification,
+    target: GrantTargetSpecification,
+    roles: Vec<Ident>,
+) -> Result<(BTreeSet<Privilege>, PrivilegeTarget, Vec<RoleId>), PlanError> {
+    let (target, applicable) = match target {
+        GrantTargetSpecification::Object(name) => {
+            let name = normalize::unresolved_object_name(name)?;
+            let item = scx.catalog.resolve_item(&name)?;
+            let full_name = scx.catalog.resolve_full_name(item.name());
+            if item.id().is_system() {
+                sql_bail!(
+                    "cannot grant privileges on {} because it is required by the database system",
+                    full_name.to_string().quoted()
+                );
+            }
+            if item.name().qualifiers.schema_spec == SchemaSpecifier::Temporary {
+                sql_bail!(
+                    "cannot grant privileges on temporary {} {}",
+                    item.item_type(),
+                    full_name.to_string().quoted()
+                );
+            }
+            let applicable = if item.is_webhook_source() {
+                Privilege::for_webhook_source()
+            } else {
+                Privilege::for_item_type(item.item_type())
+            };
+            if applicable.is_empty() {
+                sql_bail!(
+                    "privileges cannot be granted on {} {}",
+                    item.item_type(),
+                    full_name.to_string().quoted()
+                );
+            }
+            (PrivilegeTarget::Item(item.id()), applicable)
+        }
+        GrantTargetSpecification::Schema(name) => {
+            let schema = scx.resolve_schema(name)?;
+            let schema_id = match (schema.database(), schema.id()) {
+                (ResolvedDatabaseSpecifier::Id(_), SchemaSpecifier::Id(id)) => *id,
+                _ => sql_bail!(
+                    "cannot grant privileges on schema {} because it is required by the database system",
+                    schema.name().schema.quoted()
+                ),
+            };
+            (PrivilegeTarget::Schema(schema_id), Privilege::for_schema())
+        }
+    };
+
+    let privileges: BTreeSet<_> = match privileges {
+        PrivilegeSpecification::All => applicable.iter().copied().collect(),
+        PrivilegeSpecification::Privileges(privileges) => privileges
+            .into_iter()
+            .map(|privilege| match privilege {
+                AstPrivilege::Select => Privilege::Select,
+                AstPrivilege::Insert => Privilege::Insert,
+                AstPrivilege::Update => Privilege::Update,
+                AstPrivilege::Delete => Privilege::Delete,
+                AstPrivilege::Create => Privilege::Create,
+                AstPrivilege::Usage => Privilege::Usage,
+            })
+            .collect(),
+    };
+    if let Some(privilege) = privileges.iter().find(|p| !applicable.contains(p)) {
+        let object_type = match target {
+            PrivilegeTarget::Item(id) => scx.catalog.get_item(&id).item_type().to_string(),
+            PrivilegeTarget::Schema(_) => "schema".into(),
+        };
+        sql_bail!("invalid privilege type {} for {}", privilege, object_type);
+    }
+
+    let mut role_ids = vec![];
+    for role in roles {
+        let role = scx.catalog.resolve_role(&normalize::ident(role))?;
+        role_ids.push(role.id());
+    }
+
+    Ok((privileges, target, role_ids))
+}
+
 pub fn describe_drop_cluster(
     _: &StatementContext,
     _: DropClustersStatement,
@@ -3334,6 +7182,94 @@ pub fn plan_alter_object_rename(
     }
 }
 
+pub fn describe_alter_owner(
+    _: &StatementContext,
+    _: AlterOwnerStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_owner(
+    scx: &StatementContext,
+    AlterOwnerStatement {
+        object_type,
+        if_exists,
+        name,
+        new_owner,
+    }: AlterOwnerStatement,
+) -> Result<Plan, PlanError> {
+    let id = match object_type {
+        ObjectType::Database => {
+            let name = match <[Ident; 1]>::try_from(name.0) {
+                Ok([name]) => UnresolvedDatabaseName(name),
+                Err(_) => sql_bail!("database names must have exactly one component"),
+            };
+            match scx.resolve_database(&name) {
+                Ok(database) => Some(ObjectId::Database(database.id())),
+                Err(_) if if_exists => None,
+                Err(e) => return Err(e),
+            }
+        }
+        ObjectType::Schema => match scx.resolve_schema(UnresolvedSchemaName(name.0)) {
+            Ok(schema) => match (schema.database(), schema.id()) {
+                (ResolvedDatabaseSpecifier::Id(database_id), SchemaSpecifier::Id(schema_id)) => {
+                    Some(ObjectId::Schema(*database_id, *schema_id))
+                }
+                _ => sql_bail!(
+                    "cannot alter schema {} because it is required by the database system",
+                    schema.name().schema
+                ),
+            },
+            Err(_) if if_exists => None,
+            Err(e) => return Err(e),
+        },
+        ObjectType::Role
+        | ObjectType::Cluster
+        | ObjectType::ClusterReplica
+        | ObjectType::Object => {
+            bail_unsupported!(format!("ALTER {object_type} OWNER TO"))
+        }
+        _ => {
+            let name = normalize::unresolved_object_name(name)?;
+            match scx.catalog.resolve_item(&name) {
+                Ok(item) => {
+                    let full_name = scx.catalog.resolve_full_name(item.name());
+                    let item_type = item.item_type();
+                    if object_type == ObjectType::View
+                        && item_type == CatalogItemType::MaterializedView
+                    {
+                        return Err(PlanError::AlterViewOnMaterializedView(
+                            full_name.to_string(),
+                        ));
+                    } else if object_type != item_type {
+                        sql_bail!("{} is a {} not a {}", full_name, item_type, object_type)
+                    }
+                    if item.id().is_system() {
+                        sql_bail!(
+                            "cannot alter {} because it is required by the database system",
+                            full_name
+                        );
+                    }
+                    Some(ObjectId::Item(item.id()))
+                }
+                Err(_) if if_exists => None,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    };
+    let new_owner = scx.catalog.resolve_role(&normalize::ident(new_owner))?.id();
+    match id {
+        Some(id) => Ok(Plan::AlterOwner(AlterOwnerPlan {
+            id,
+            object_type,
+            new_owner,
+        })),
+        // TODO(benesch/jkosh44): generate a notice indicating this object does
+        // not exist.
+        None => Ok(Plan::AlterNoop(AlterNoopPlan { object_type })),
+    }
+}
+
 pub fn describe_alter_secret_options(
     _: &StatementContext,
     _: AlterSecretStatement<Aug>,
@@ -3413,12 +7349,27 @@ pub fn plan_alter_sink(
 
     let mut size = AlterOptionParameter::Unchanged;
     let mut remote = AlterOptionParameter::Unchanged;
+    let mut runtime_options = AlterSinkRuntimeOptions::default();
     match action {
         AlterSinkAction::SetOptions(options) => {
             let CreateSinkOptionExtracted {
                 remote: remote_opt,
                 size: size_opt,
                 snapshot,
+                spill_budget,
+                catch_up_bytes_per_second,
+                catch_up_records_per_second,
+                max_bytes_per_second,
+                max_messages_per_second,
+                mask_hash,
+                mask_redact,
+                mask_truncate,
+                mask_truncate_length,
+                retractions,
+                deletes,
+                delete_marker,
+                include_timestamp,
+                include_diff,
                 seen: _,
             } = options.try_into()?;
 
@@ -3431,6 +7382,37 @@ pub fn plan_alter_sink(
             if let Some(_) = snapshot {
                 sql_bail!("Cannot modify the SNAPSHOT of a SINK.");
             }
+            if let Some(_) = spill_budget {
+                sql_bail!("Cannot modify the SPILL BUDGET of a SINK.");
+            }
+            // The CATCH UP rate limits only apply while a sink emits its
+            // initial snapshot, which it is past by the time it is altered.
+            if catch_up_bytes_per_second.is_some() || catch_up_records_per_second.is_some() {
+                sql_bail!("Cannot modify the CATCH UP rate limits of a SINK.");
+            }
+            plan_sink_max_rate(max_bytes_per_second, max_messages_per_second)?;
+            if let Some(value) = max_bytes_per_second {
+                runtime_options.max_bytes_per_second = AlterOptionParameter::Set(value);
+            }
+            if let Some(value) = max_messages_per_second {
+                runtime_options.max_messages_per_second = AlterOptionParameter::Set(value);
+            }
+            if !mask_hash.is_empty()
+                || !mask_redact.is_empty()
+                || !mask_truncate.is_empty()
+                || mask_truncate_length.is_some()
+            {
+                sql_bail!("Cannot modify the MASK options of a SINK.");
+            }
+            if let Some(_) = retractions {
+                sql_bail!("Cannot modify the RETRACTIONS of a SINK.");
+            }
+            if deletes.is_some() || delete_marker.is_some() {
+                sql_bail!("Cannot modify the DELETES options of a SINK.");
+            }
+            if include_timestamp || include_diff {
+                sql_bail!("Cannot modify the INCLUDE options of a SINK.");
+            }
         }
         AlterSinkAction::ResetOptions(reset) => {
             for name in reset {
@@ -3444,12 +7426,126 @@ pub fn plan_alter_sink(
                     CreateSinkOptionName::Snapshot => {
                         sql_bail!("Cannot modify the SNAPSHOT of a SINK.");
                     }
+                    CreateSinkOptionName::SpillBudget => {
+                        sql_bail!("Cannot modify the SPILL BUDGET of a SINK.");
+                    }
+                    CreateSinkOptionName::CatchUpBytesPerSecond
+                    | CreateSinkOptionName::CatchUpRecordsPerSecond => {
+                        sql_bail!("Cannot modify the CATCH UP rate limits of a SINK.");
+                    }
+                    CreateSinkOptionName::MaxBytesPerSecond => {
+                        runtime_options.max_bytes_per_second = AlterOptionParameter::Reset;
+                    }
+                    CreateSinkOptionName::MaxMessagesPerSecond => {
+                        runtime_options.max_messages_per_second = AlterOptionParameter::Reset;
+                    }
+                    CreateSinkOptionName::MaskHash
+                    | CreateSinkOptionName::MaskRedact
+                    | CreateSinkOptionName::MaskTruncate
+                    | CreateSinkOptionName::MaskTruncateLength => {
+                        sql_bail!("Cannot modify the MASK options of a SINK.");
+                    }
+                    CreateSinkOptionName::Retractions => {
+                        sql_bail!("Cannot modify the RETRACTIONS of a SINK.");
+                    }
+                    CreateSinkOptionName::Deletes | CreateSinkOptionName::DeleteMarker => {
+                        sql_bail!("Cannot modify the DELETES options of a SINK.");
+                    }
+                    CreateSinkOptionName::IncludeDiff | CreateSinkOptionName::IncludeTimestamp => {
+                        sql_bail!("Cannot modify the INCLUDE options of a SINK.");
+                    }
+                }
+            }
+        }
+        AlterSinkAction::SetConnectionOptions(options) => {
+            scx.require_unsafe_mode("KAFKA CONNECTION options besides TOPIC")?;
+            kafka_util::validate_options_for_context(
+                &options,
+                kafka_util::KafkaOptionCheckContext::Sink,
+            )?;
+
+            let KafkaConfigOptionExtracted {
+                compression_type,
+                linger_ms,
+                batch_num_messages,
+                retention_ms,
+                retention_bytes,
+                seen,
+                ..
+            } = options.try_into()?;
+
+            for name in &seen {
+                if !is_alterable_kafka_sink_option(name) {
+                    sql_bail!("Cannot modify the {} of a SINK.", name.to_ast_string());
+                }
+            }
+
+            if seen.contains(&KafkaConfigOptionName::CompressionType) {
+                runtime_options.compression_type = AlterOptionParameter::Set(compression_type);
+            }
+            if let Some(value) = linger_ms {
+                runtime_options.linger_ms =
+                    AlterOptionParameter::Set(plan_kafka_sink_linger_ms(value)?);
+            }
+            if let Some(value) = batch_num_messages {
+                runtime_options.batch_num_messages =
+                    AlterOptionParameter::Set(plan_kafka_sink_batch_num_messages(value)?);
+            }
+            if let Some(value) = retention_ms {
+                plan_kafka_sink_retention_ms(value)?;
+                runtime_options.retention_ms = AlterOptionParameter::Set(value);
+            }
+            if let Some(value) = retention_bytes {
+                plan_kafka_sink_retention_bytes(value)?;
+                runtime_options.retention_bytes = AlterOptionParameter::Set(value);
+            }
+        }
+        AlterSinkAction::ResetConnectionOptions(reset) => {
+            scx.require_unsafe_mode("KAFKA CONNECTION options besides TOPIC")?;
+            for name in reset {
+                match name {
+                    KafkaConfigOptionName::CompressionType => {
+                        runtime_options.compression_type = AlterOptionParameter::Reset;
+                    }
+                    KafkaConfigOptionName::LingerMs => {
+                        runtime_options.linger_ms = AlterOptionParameter::Reset;
+                    }
+                    KafkaConfigOptionName::BatchNumMessages => {
+                        runtime_options.batch_num_messages = AlterOptionParameter::Reset;
+                    }
+                    KafkaConfigOptionName::RetentionMs => {
+                        runtime_options.retention_ms = AlterOptionParameter::Reset;
+                    }
+                    KafkaConfigOptionName::RetentionBytes => {
+                        runtime_options.retention_bytes = AlterOptionParameter::Reset;
+                    }
+                    other => {
+                        sql_bail!("Cannot modify the {} of a SINK.", other.to_ast_string());
+                    }
                 }
             }
         }
     };
 
-    Ok(Plan::AlterSink(AlterSinkPlan { id, size, remote }))
+    Ok(Plan::AlterSink(AlterSinkPlan {
+        id,
+        size,
+        remote,
+        runtime_options,
+    }))
+}
+
+/// Reports whether the Kafka connection option `name` of a running sink can
+/// be altered.
+fn is_alterable_kafka_sink_option(name: &KafkaConfigOptionName) -> bool {
+    matches!(
+        name,
+        KafkaConfigOptionName::CompressionType
+            | KafkaConfigOptionName::LingerMs
+            | KafkaConfigOptionName::BatchNumMessages
+            | KafkaConfigOptionName::RetentionMs
+            | KafkaConfigOptionName::RetentionBytes
+    )
 }
 
 pub fn describe_alter_source(
@@ -3504,8 +7600,16 @@ pub fn plan_alter_source(
                 remote: remote_opt,
                 size: size_opt,
                 timeline: timeline_opt,
+                timestamp_column: timestamp_column_opt,
                 timestamp_interval: timestamp_interval_opt,
+                timestamp_lateness: timestamp_lateness_opt,
+                timestamp_strategy: timestamp_strategy_opt,
+                decode_errors: decode_errors_opt,
                 ignore_keys: ignore_keys_opt,
+                max_bytes_per_second: max_bytes_per_second_opt,
+                max_messages_per_second: max_messages_per_second_opt,
+                numeric_overflow: numeric_overflow_opt,
+                upsert_state: upsert_state_opt,
             } = CreateSourceOptionExtracted::try_from(options)?;
 
             if let Some(value) = remote_opt {
@@ -3517,12 +7621,36 @@ pub fn plan_alter_source(
             if let Some(_) = timeline_opt {
                 sql_bail!("Cannot modify the TIMELINE of a SOURCE.");
             }
+            if let Some(_) = timestamp_column_opt {
+                sql_bail!("Cannot modify the TIMESTAMP COLUMN of a SOURCE.");
+            }
             if let Some(_) = timestamp_interval_opt {
                 sql_bail!("Cannot modify the TIMESTAMP INTERVAL of a SOURCE.");
             }
+            if let Some(_) = timestamp_lateness_opt {
+                sql_bail!("Cannot modify the TIMESTAMP LATENESS of a SOURCE.");
+            }
+            if let Some(_) = timestamp_strategy_opt {
+                sql_bail!("Cannot modify the TIMESTAMP STRATEGY of a SOURCE.");
+            }
+            if let Some(_) = decode_errors_opt {
+                sql_bail!("Cannot modify the DECODE ERRORS of a SOURCE.");
+            }
             if let Some(_) = ignore_keys_opt {
                 sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
             }
+            if let Some(_) = max_bytes_per_second_opt {
+                sql_bail!("Cannot modify the MAX BYTES PER SECOND of a SOURCE.");
+            }
+            if let Some(_) = max_messages_per_second_opt {
+                sql_bail!("Cannot modify the MAX MESSAGES PER SECOND of a SOURCE.");
+            }
+            if let Some(_) = numeric_overflow_opt {
+                sql_bail!("Cannot modify the NUMERIC OVERFLOW of a SOURCE.");
+            }
+            if let Some(_) = upsert_state_opt {
+                sql_bail!("Cannot modify the UPSERT STATE of a SOURCE.");
+            }
         }
         AlterSourceAction::ResetOptions(reset) => {
             for name in reset {
@@ -3536,15 +7664,46 @@ pub fn plan_alter_source(
                     CreateSourceOptionName::Timeline => {
                         sql_bail!("Cannot modify the TIMELINE of a SOURCE.");
                     }
+                    CreateSourceOptionName::TimestampColumn => {
+                        sql_bail!("Cannot modify the TIMESTAMP COLUMN of a SOURCE.");
+                    }
                     CreateSourceOptionName::TimestampInterval => {
                         sql_bail!("Cannot modify the TIMESTAMP INTERVAL of a SOURCE.");
                     }
+                    CreateSourceOptionName::TimestampLateness => {
+                        sql_bail!("Cannot modify the TIMESTAMP LATENESS of a SOURCE.");
+                    }
+                    CreateSourceOptionName::TimestampStrategy => {
+                        sql_bail!("Cannot modify the TIMESTAMP STRATEGY of a SOURCE.");
+                    }
+                    CreateSourceOptionName::DecodeErrors => {
+                        sql_bail!("Cannot modify the DECODE ERRORS of a SOURCE.");
+                    }
                     CreateSourceOptionName::IgnoreKeys => {
                         sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
                     }
+                    CreateSourceOptionName::MaxBytesPerSecond => {
+                        sql_bail!("Cannot modify the MAX BYTES PER SECOND of a SOURCE.");
+                    }
+                    CreateSourceOptionName::MaxMessagesPerSecond => {
+                        sql_bail!("Cannot modify the MAX MESSAGES PER SECOND of a SOURCE.");
+                    }
+                    CreateSourceOptionName::NumericOverflow => {
+                        sql_bail!("Cannot modify the NUMERIC OVERFLOW of a SOURCE.");
+                    }
+                    CreateSourceOptionName::UpsertState => {
+                        sql_bail!("Cannot modify the UPSERT STATE of a SOURCE.");
+                    }
                 }
             }
         }
+        AlterSourceAction::RefreshSchema => {
+            // The schema is re-read from the registry during sequencing,
+            // which purifies the source's statement anew.
+            return Ok(Plan::AlterSourceRefreshSchema(
+                AlterSourceRefreshSchemaPlan { id },
+            ));
+        }
     };
 
     Ok(Plan::AlterSource(AlterSourcePlan { id, size, remote }))
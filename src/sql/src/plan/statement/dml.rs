@@ -34,7 +34,7 @@ use crate::catalog::CatalogItemType;
 use crate::names::{self, Aug, ResolvedObjectName};
 use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
-use crate::plan::with_options::TryFromValue;
+use crate::plan::with_options::{OptionalInterval, TryFromValue};
 use crate::plan::{
     query, CopyFormat, CopyFromPlan, ExplainPlan, InsertPlan, MutationKind, Params, PeekPlan, Plan,
     PlanError, QueryContext, ReadThenWritePlan, SubscribeFrom, SubscribePlan,
@@ -376,7 +376,13 @@ pub fn plan_query(
     })
 }
 
-generate_extracted_config!(SubscribeOption, (Snapshot, bool), (Progress, bool));
+generate_extracted_config!(
+    SubscribeOption,
+    (Snapshot, bool),
+    (Progress, bool),
+    (ProgressInterval, OptionalInterval),
+    (IdleTimeout, OptionalInterval)
+);
 
 pub fn describe_subscribe(
     scx: &StatementContext,
@@ -467,14 +473,34 @@ pub fn plan_subscribe(
 
     let when = query::plan_as_of(scx, as_of)?;
     let SubscribeOptionExtracted {
-        progress, snapshot, ..
+        progress,
+        snapshot,
+        progress_interval,
+        idle_timeout,
+        ..
     } = options.try_into()?;
+    let emit_progress = progress.unwrap_or(false);
+    let progress_interval = match progress_interval {
+        Some(OptionalInterval(Some(interval))) => {
+            if !emit_progress {
+                sql_bail!("PROGRESS INTERVAL requires PROGRESS");
+            }
+            Some(interval.duration()?)
+        }
+        _ => None,
+    };
+    let idle_timeout = match idle_timeout {
+        Some(OptionalInterval(Some(interval))) => Some(interval.duration()?),
+        _ => None,
+    };
     Ok(Plan::Subscribe(SubscribePlan {
         from,
         when,
         with_snapshot: snapshot.unwrap_or(true),
         copy_to,
-        emit_progress: progress.unwrap_or(false),
+        emit_progress,
+        progress_interval,
+        idle_timeout,
     }))
 }
 
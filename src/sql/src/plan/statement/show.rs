@@ -23,15 +23,18 @@ use query::QueryContext;
 
 use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
-    ObjectType, SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement,
-    ShowCreateSinkStatement, ShowCreateSourceStatement, ShowCreateTableStatement,
-    ShowCreateViewStatement, ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement,
-    ShowSchemasStatement, ShowStatementFilter, Statement, Value,
+    GrantTargetSpecification, ObjectType, SelectStatement, ShowColumnsStatement,
+    ShowCreateIndexStatement, ShowCreateSinkStatement, ShowCreateSourceStatement,
+    ShowCreateTableStatement, ShowCreateViewStatement, ShowDatabasesStatement, ShowGrantsStatement,
+    ShowIndexesStatement, ShowObjectsStatement, ShowSchemasStatement, ShowStatementFilter,
+    Statement, Value,
 };
 use crate::catalog::{CatalogItemType, SessionCatalog};
 use crate::names::{
     self, Aug, NameSimplifier, ResolvedClusterName, ResolvedDatabaseName, ResolvedSchemaName,
+    SchemaSpecifier,
 };
+use crate::normalize;
 use crate::parse;
 use crate::plan::scope::Scope;
 use crate::plan::statement::{dml, StatementContext, StatementDesc};
@@ -294,6 +297,64 @@ pub fn show_schemas<'a>(
     ShowSelect::new(scx, query, filter, None, None)
 }
 
+pub fn show_grants<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowGrantsStatement {
+        target,
+        role,
+        filter,
+    }: ShowGrantsStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let (object_filter, schema_filter) = match target {
+        Some(GrantTargetSpecification::Object(name)) => {
+            let name = normalize::unresolved_object_name(name)?;
+            let item = scx.catalog.resolve_item(&name)?;
+            (format!("p.object_id = '{}'", item.id()), "false".into())
+        }
+        Some(GrantTargetSpecification::Schema(name)) => {
+            let schema = scx.resolve_schema(name)?;
+            let schema_filter = match schema.id() {
+                SchemaSpecifier::Id(id) => format!("p.schema_id = {}", id.0),
+                SchemaSpecifier::Temporary => "false".into(),
+            };
+            ("false".into(), schema_filter)
+        }
+        None => ("true".into(), "true".into()),
+    };
+    let role_filter = match role {
+        Some(role) => {
+            let role = scx.catalog.resolve_role(&normalize::ident(role))?;
+            format!("r.id = '{}'", role.id())
+        }
+        None => "true".into(),
+    };
+    let query = format!(
+        "SELECT
+            d.name || '.' || s.name || '.' || o.name AS name,
+            o.type AS object_type,
+            r.name AS grantee,
+            p.privilege
+        FROM mz_internal.mz_privileges p
+        JOIN mz_catalog.mz_roles r ON p.grantee_id = r.id
+        JOIN mz_catalog.mz_objects o ON p.object_id = o.id
+        JOIN mz_catalog.mz_schemas s ON o.schema_id = s.id
+        JOIN mz_catalog.mz_databases d ON s.database_id = d.id
+        WHERE {object_filter} AND {role_filter}
+        UNION ALL
+        SELECT
+            d.name || '.' || s.name AS name,
+            'schema' AS object_type,
+            r.name AS grantee,
+            p.privilege
+        FROM mz_internal.mz_privileges p
+        JOIN mz_catalog.mz_roles r ON p.grantee_id = r.id
+        JOIN mz_catalog.mz_schemas s ON p.schema_id = s.id
+        JOIN mz_catalog.mz_databases d ON s.database_id = d.id
+        WHERE {schema_filter} AND {role_filter}"
+    );
+    ShowSelect::new(scx, query, filter, None, None)
+}
+
 pub fn show_objects<'a>(
     scx: &'a StatementContext<'a>,
     ShowObjectsStatement {
@@ -316,6 +377,9 @@ pub fn show_objects<'a>(
         ObjectType::ClusterReplica => show_cluster_replicas(scx, filter),
         ObjectType::Secret => show_secrets(scx, from, filter),
         ObjectType::Index => unreachable!("SHOW INDEX handled separately"),
+        ObjectType::Database | ObjectType::Schema => {
+            unreachable!("SHOW DATABASES and SHOW SCHEMAS handled separately")
+        }
         ObjectType::Connection => show_connections(scx, from, filter),
     }
 }
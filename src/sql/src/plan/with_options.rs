@@ -16,8 +16,10 @@ use mz_repr::adt::interval::Interval;
 use mz_repr::strconv;
 use mz_repr::GlobalId;
 use mz_storage::types::connections::StringOrSecret;
+use mz_storage::types::sources::encoding::NumericOverflowPolicy;
+use mz_storage::types::sources::{DecodeErrorPolicy, TimestampStrategy, UpsertStateBackend};
 
-use crate::ast::{AstInfo, IntervalValue, Value, WithOptionValue};
+use crate::ast::{AstInfo, Expr, Ident, IntervalValue, Value, WithOptionValue};
 use crate::names::{ResolvedDataType, ResolvedObjectName};
 use crate::plan::{Aug, PlanError};
 
@@ -108,6 +110,48 @@ impl ImpliedValue for ResolvedDataType {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for Ident {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        Ok(match v {
+            WithOptionValue::Ident(ident) => ident,
+            _ => sql_bail!("must provide an identifier"),
+        })
+    }
+    fn name() -> String {
+        "identifier".to_string()
+    }
+}
+
+impl ImpliedValue for Ident {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an identifier")
+    }
+}
+
+impl ImpliedValue for Vec<Ident> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a list of identifiers")
+    }
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for Expr<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        Ok(match v {
+            WithOptionValue::Expr(expr) => expr,
+            _ => sql_bail!("must provide an expression"),
+        })
+    }
+    fn name() -> String {
+        "expression".to_string()
+    }
+}
+
+impl ImpliedValue for Expr<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an expression")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for StringOrSecret {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         Ok(match v {
@@ -318,6 +362,99 @@ impl ImpliedValue for u64 {
     }
 }
 
+impl TryFromValue<Value> for NumericOverflowPolicy {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let policy = String::try_from_value(v)?;
+        Ok(match policy.to_lowercase().as_str() {
+            "error" => NumericOverflowPolicy::Error,
+            "null" => NumericOverflowPolicy::Null,
+            "saturate" => NumericOverflowPolicy::Saturate,
+            _ => sql_bail!(
+                "invalid NUMERIC OVERFLOW '{}': must be one of error, null, or saturate",
+                policy
+            ),
+        })
+    }
+    fn name() -> String {
+        "numeric overflow policy".to_string()
+    }
+}
+
+impl ImpliedValue for NumericOverflowPolicy {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a numeric overflow policy")
+    }
+}
+
+impl TryFromValue<Value> for UpsertStateBackend {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let backend = String::try_from_value(v)?;
+        Ok(match backend.to_lowercase().as_str() {
+            "memory" => UpsertStateBackend::Memory,
+            "disk" => UpsertStateBackend::Disk,
+            _ => sql_bail!(
+                "invalid UPSERT STATE '{}': must be one of memory or disk",
+                backend
+            ),
+        })
+    }
+    fn name() -> String {
+        "upsert state backend".to_string()
+    }
+}
+
+impl ImpliedValue for UpsertStateBackend {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an upsert state backend")
+    }
+}
+
+impl TryFromValue<Value> for TimestampStrategy {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let strategy = String::try_from_value(v)?;
+        Ok(match strategy.to_lowercase().as_str() {
+            "wall-clock" => TimestampStrategy::WallClock,
+            "offset" => TimestampStrategy::Offset,
+            _ => sql_bail!(
+                "invalid TIMESTAMP STRATEGY '{}': must be one of wall-clock or offset",
+                strategy
+            ),
+        })
+    }
+    fn name() -> String {
+        "timestamp strategy".to_string()
+    }
+}
+
+impl ImpliedValue for TimestampStrategy {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a timestamp strategy")
+    }
+}
+
+impl TryFromValue<Value> for DecodeErrorPolicy {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        let policy = String::try_from_value(v)?;
+        Ok(match policy.to_lowercase().as_str() {
+            "fail" => DecodeErrorPolicy::Fail,
+            "subsource" => DecodeErrorPolicy::Subsource,
+            _ => sql_bail!(
+                "invalid DECODE ERRORS '{}': must be one of fail or subsource",
+                policy
+            ),
+        })
+    }
+    fn name() -> String {
+        "decode error policy".to_string()
+    }
+}
+
+impl ImpliedValue for DecodeErrorPolicy {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a decode error policy")
+    }
+}
+
 impl<T, V> TryFromValue<WithOptionValue<T>> for Vec<V>
 where
     T: AstInfo,
@@ -376,7 +513,8 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             | WithOptionValue::Object(_)
             | WithOptionValue::Secret(_)
             | WithOptionValue::DataType(_)
-            | WithOptionValue::ClusterReplicas(_) => sql_bail!(
+            | WithOptionValue::ClusterReplicas(_)
+            | WithOptionValue::Expr(_) => sql_bail!(
                 "incompatible value types: cannot convert {} to {}",
                 match v {
                     WithOptionValue::Sequence(_) => "sequences",
@@ -384,6 +522,7 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::Secret(_) => "secrets",
                     WithOptionValue::DataType(_) => "data types",
                     WithOptionValue::ClusterReplicas(_) => "cluster replicas",
+                    WithOptionValue::Expr(_) => "expressions",
                     _ => unreachable!(),
                 },
                 V::name()
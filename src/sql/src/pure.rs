@@ -11,13 +11,14 @@
 //!
 //! See the [crate-level documentation](crate) for details.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error as StdError;
 use std::iter;
 use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context};
+use globset::GlobBuilder;
 use itertools::Itertools;
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
@@ -29,20 +30,25 @@ use mz_ccsr::Schema as CcsrSchema;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError};
 use mz_ore::cast::CastFrom;
 use mz_proto::RustType;
-use mz_repr::{strconv, GlobalId};
+use mz_repr::{strconv, GlobalId, RelationDesc};
 use mz_secrets::SecretsReader;
 use mz_sql_parser::ast::{
     ColumnDef, ColumnOption, ColumnOptionDef, CsrConnection, CsrSeedAvro, CsrSeedProtobuf,
     CsrSeedProtobufSchema, DbzMode, Envelope, Ident, KafkaConfigOption, KafkaConfigOptionName,
-    KafkaConnection, KafkaSourceConnection, PgConfigOption, PgConfigOptionName,
-    ReaderSchemaSelectionStrategy, TableConstraint, UnresolvedObjectName,
+    KafkaConnection, KafkaSourceConnection, MySqlConfigOption, MySqlConfigOptionName,
+    PgConfigOption, PgConfigOptionName, ReaderSchemaSelectionStrategy, S3KeySource,
+    SqlServerConfigOption, SqlServerConfigOptionName, TableConstraint, UnresolvedObjectName,
 };
+use mz_storage::source::fetch_parquet_desc;
 use mz_storage::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
 use mz_storage::types::connections::{Connection, ConnectionContext};
-use mz_storage::types::sources::PostgresSourceDetails;
+use mz_storage::types::sources::{
+    decode_errors_desc, DecodeErrorPolicy, MySqlSourceDetails, PostgresSourceDetails,
+    SqlServerSourceDetails,
+};
 
 use crate::ast::{
-    AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
+    AvroSchema, Compression, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
     CreateSourceSubsource, CreateSourceSubsources, CreateSubsourceStatement, CsrConnectionAvro,
     CsrConnectionProtobuf, CsvColumns, Format, ProtobufSchema, Value, WithOptionValue,
 };
@@ -51,26 +57,37 @@ use crate::kafka_util;
 use crate::kafka_util::KafkaConfigOptionExtracted;
 use crate::names::{Aug, RawDatabaseSpecifier, ResolvedObjectName};
 use crate::normalize;
-use crate::plan::statement::ddl::load_generator_ast_to_generator;
+use crate::plan::statement::ddl::{
+    decode_errors_subsource_name, load_generator_ast_to_generator, mysql_column_type,
+    sql_server_column_type, CreateSourceOptionExtracted,
+};
 use crate::plan::StatementContext;
 
 fn subsource_gen<'a, T>(
     selected_subsources: &mut Vec<CreateSourceSubsource<Aug>>,
     tables_by_name: HashMap<String, HashMap<String, HashMap<String, &'a T>>>,
-) -> Result<Vec<(UnresolvedObjectName, UnresolvedObjectName, &'a T)>, anyhow::Error> {
+) -> Result<
+    Vec<(
+        UnresolvedObjectName,
+        UnresolvedObjectName,
+        Vec<Ident>,
+        &'a T,
+    )>,
+    anyhow::Error,
+> {
     let mut validated_requested_subsources = vec![];
 
     for subsource in selected_subsources {
-        let (upstream_name, subsource_name) = match subsource.clone() {
-            CreateSourceSubsource::Bare(name) => {
+        let (upstream_name, subsource_name, columns) = match subsource.clone() {
+            CreateSourceSubsource::Bare(name, columns) => {
                 let upstream_name = normalize::unresolved_object_name(name)?;
                 let subsource_name = UnresolvedObjectName::unqualified(&upstream_name.item);
-                (upstream_name, subsource_name)
+                (upstream_name, subsource_name, columns)
             }
-            CreateSourceSubsource::Aliased(name, alias) => {
-                (normalize::unresolved_object_name(name)?, alias)
+            CreateSourceSubsource::Aliased(name, columns, alias) => {
+                (normalize::unresolved_object_name(name)?, alias, columns)
             }
-            CreateSourceSubsource::Resolved(_, _) => {
+            CreateSourceSubsource::Resolved(..) => {
                 bail!("Cannot alias subsource using `INTO`, use `AS` instead")
             }
         };
@@ -112,7 +129,12 @@ fn subsource_gen<'a, T>(
 
         let qualified_upstream_name =
             UnresolvedObjectName::qualified(&[database, schema, &upstream_name.item]);
-        validated_requested_subsources.push((qualified_upstream_name, subsource_name, desc));
+        validated_requested_subsources.push((
+            qualified_upstream_name,
+            subsource_name,
+            columns,
+            desc,
+        ));
     }
 
     Ok(validated_requested_subsources)
@@ -135,19 +157,33 @@ pub async fn purify_create_source(
     anyhow::Error,
 > {
     let CreateSourceStatement {
+        name: source_name,
         connection,
         format,
         envelope,
         include_metadata: _,
         subsources: requested_subsources,
+        with_options,
         ..
     } = &mut stmt;
 
     // Disallow manually targetting subsources, this syntax is reserved for purification only
     if let Some(CreateSourceSubsources::Subset(subsources)) = requested_subsources {
         for subsource in subsources {
-            if matches!(subsource, CreateSourceSubsource::Resolved(_, _)) {
-                bail!("Cannot alias subsource using `INTO`, use `AS` instead");
+            match subsource {
+                CreateSourceSubsource::Resolved(..) => {
+                    bail!("Cannot alias subsource using `INTO`, use `AS` instead");
+                }
+                CreateSourceSubsource::Bare(name, columns)
+                | CreateSourceSubsource::Aliased(name, columns, _) => {
+                    if !columns.is_empty()
+                        && !matches!(connection, CreateSourceConnection::Postgres { .. })
+                    {
+                        bail!(
+                            "column list for table {name} is only supported for POSTGRES sources"
+                        );
+                    }
+                }
             }
         }
     }
@@ -180,12 +216,45 @@ pub async fn purify_create_source(
                 Option::<kafka_util::KafkaStartOffsetType>::try_from(&extracted_options)?;
             let config_options = kafka_util::LibRdKafkaConfig::try_from(&extracted_options)?.0;
 
-            let topic = extracted_options
-                .topic
-                .ok_or_else(|| sql_err!("KAFKA CONNECTION without TOPIC"))?;
+            // Sources that read every topic matching a pattern can't look up
+            // per-partition start offsets, as the topics are discovered by
+            // the source itself.
+            let metadata_topic = match (&extracted_options.topic, &extracted_options.topic_pattern)
+            {
+                (Some(topic), None) => Some(topic.as_str()),
+                (None, Some(_)) => {
+                    if offset_type.is_some() {
+                        bail!(
+                            "START OFFSET and START TIMESTAMP are not supported with TOPIC PATTERN"
+                        );
+                    }
+                    None
+                }
+                (Some(_), Some(_)) => bail!("cannot specify both TOPIC and TOPIC PATTERN"),
+                (None, None) => bail!("KAFKA CONNECTION without TOPIC"),
+            };
+
+            // Messages may be compressed with any codec, whatever the broker's
+            // settings, so refuse to create a source that could not read them.
+            let unsupported_codecs = mz_kafka_util::client::unsupported_compression_codecs()
+                .context("determining supported Kafka compression codecs")?;
+            if !unsupported_codecs.is_empty() {
+                bail!(
+                    "Kafka sources require support for decompressing {} messages, \
+                     which this build of librdkafka lacks",
+                    unsupported_codecs.join(", ")
+                );
+            }
+
+            kafka_util::preflight(
+                &connection,
+                connection_context.librdkafka_log_level,
+                &*connection_context.secrets_reader,
+            )
+            .await?;
 
             let consumer = kafka_util::create_consumer(
-                &topic,
+                metadata_topic,
                 &connection,
                 &config_options,
                 connection_context.librdkafka_log_level,
@@ -194,11 +263,11 @@ pub async fn purify_create_source(
             .await
             .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
 
-            if let Some(offset_type) = offset_type {
+            if let (Some(offset_type), Some(topic)) = (offset_type, metadata_topic) {
                 // Translate `START TIMESTAMP` to a start offset
                 match kafka_util::lookup_start_offsets(
                     Arc::clone(&consumer),
-                    &topic,
+                    topic,
                     offset_type,
                     now,
                 )
@@ -233,6 +302,25 @@ pub async fn purify_create_source(
         CreateSourceConnection::TestScript { desc_json: _ } => {
             // TODO: verify valid json and valid schema
         }
+        CreateSourceConnection::File { .. } => {
+            // The files are read by the source itself, on the storage host,
+            // which reports any failures to read them as source errors.
+        }
+        CreateSourceConnection::Mqtt { .. } | CreateSourceConnection::Nats { .. } => {
+            // The server is contacted by the source itself, on the storage
+            // host, which reports any failures to connect as source errors.
+        }
+        CreateSourceConnection::PubSub { .. } => {
+            // Pub/Sub is contacted by the source itself, on the storage host,
+            // which reports any failures to pull as source errors.
+        }
+        CreateSourceConnection::Sftp { .. } => {
+            // The server is contacted by the source itself, on the storage
+            // host, which reports any failures to connect as source errors.
+        }
+        CreateSourceConnection::Webhook { .. } => {
+            // Webhook sources have no upstream system to contact.
+        }
         CreateSourceConnection::S3 { connection, .. } => {
             let scx = StatementContext::new(None, &*catalog);
             let aws = {
@@ -300,7 +388,12 @@ pub async fn purify_create_source(
                             &table.name,
                         ]);
                         let subsource_name = UnresolvedObjectName::unqualified(&table.name);
-                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                        validated_requested_subsources.push((
+                            upstream_name,
+                            subsource_name,
+                            vec![],
+                            table,
+                        ));
                     }
                 }
                 Some(CreateSourceSubsources::Subset(subsources)) => {
@@ -325,13 +418,52 @@ pub async fn purify_create_source(
                 None => {}
             };
 
+            // Only the selected tables are ingested, so that large publications do not cost
+            // anything for the tables that nobody asked for. Each table is paired with the
+            // columns selected from it.
+            let mut ingested_tables = vec![];
+
             // Now that we have an explicit list of validated requested subsources we can create them
-            for (i, (upstream_name, subsource_name, table)) in
+            for (i, (upstream_name, subsource_name, selected, table)) in
                 validated_requested_subsources.into_iter().enumerate()
             {
-                // Figure out the schema of the subsource
+                let mut seen = HashSet::new();
+                for column in &selected {
+                    if !table.columns.iter().any(|c| c.name == column.as_str()) {
+                        bail!("column {column} not found in table {upstream_name}");
+                    }
+                    if !seen.insert(column) {
+                        bail!("column {column} specified more than once for table {upstream_name}");
+                    }
+                }
+                match ingested_tables
+                    .iter()
+                    .find(|(t, _)| std::ptr::eq(*t, table))
+                {
+                    Some((_, columns)) if *columns != selected => bail!(
+                        "table {upstream_name} is selected more than once with different columns"
+                    ),
+                    Some(_) => {}
+                    None => ingested_tables.push((table, selected.clone())),
+                }
+
+                // Figure out the schema of the subsource, in the order the columns were selected
+                let table_columns = if selected.is_empty() {
+                    table.columns.iter().collect::<Vec<_>>()
+                } else {
+                    selected
+                        .iter()
+                        .map(|column| {
+                            table
+                                .columns
+                                .iter()
+                                .find(|c| c.name == column.as_str())
+                                .expect("validated above")
+                        })
+                        .collect()
+                };
                 let mut columns = vec![];
-                for c in table.columns.iter() {
+                for c in table_columns {
                     let name = Ident::new(c.name.clone());
 
                     let ty = mz_pgrepr::Type::from_oid_and_typmod(c.type_oid, c.type_mod)
@@ -355,6 +487,7 @@ pub async fn purify_create_source(
                 let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
                 targeted_subsources.push(CreateSourceSubsource::Resolved(
                     upstream_name,
+                    selected,
                     ResolvedObjectName::Object {
                         id: transient_id,
                         qualifiers: qualified_subsource_name.qualifiers,
@@ -385,7 +518,10 @@ pub async fn purify_create_source(
             // Remove any old detail references
             options.retain(|PgConfigOption { name, .. }| name != &PgConfigOptionName::Details);
             let details = PostgresSourceDetails {
-                tables,
+                tables: ingested_tables
+                    .into_iter()
+                    .map(|(table, _)| table.clone())
+                    .collect(),
                 slot: format!(
                     "materialize_{}",
                     Uuid::new_v4().to_string().replace('-', "")
@@ -398,6 +534,306 @@ pub async fn purify_create_source(
                 )))),
             })
         }
+        CreateSourceConnection::MySql {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &*catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::MySql(connection) => connection.clone(),
+                    _ => bail!("{} is not a MySQL connection", item.name()),
+                }
+            };
+
+            // verify that we can connect upstream, that the server keeps a
+            // replicable binary log, and snapshot the table metadata
+            let config = connection
+                .config(&*connection_context.secrets_reader)
+                .await?;
+            let mut conn = config.connect().await?;
+            mz_mysql_util::ensure_replication_settings(&mut conn).await?;
+            let tables = mz_mysql_util::schema_info(&mut conn).await?;
+            conn.disconnect().await?;
+
+            let mut targeted_subsources = vec![];
+
+            // MySQL has no level of namespacing above its schemas, so upstream
+            // names are of the form `schema.table`.
+            let mut validated_requested_subsources = vec![];
+            match requested_subsources {
+                Some(CreateSourceSubsources::All) => {
+                    for table in &tables {
+                        let upstream_name =
+                            UnresolvedObjectName::qualified(&[&table.schema_name, &table.name]);
+                        let subsource_name = UnresolvedObjectName::unqualified(&table.name);
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                Some(CreateSourceSubsources::Subset(subsources)) => {
+                    // An index from table name -> schema name -> MySqlTableDesc
+                    let mut tables_by_name = HashMap::new();
+                    for table in &tables {
+                        tables_by_name
+                            .entry(table.name.clone())
+                            .or_insert_with(HashMap::new)
+                            .entry(table.schema_name.clone())
+                            .or_insert(table);
+                    }
+
+                    for subsource in subsources {
+                        let (upstream_name, subsource_name) = match subsource.clone() {
+                            CreateSourceSubsource::Bare(name, _) => {
+                                let upstream_name = normalize::unresolved_object_name(name)?;
+                                let subsource_name =
+                                    UnresolvedObjectName::unqualified(&upstream_name.item);
+                                (upstream_name, subsource_name)
+                            }
+                            CreateSourceSubsource::Aliased(name, _, alias) => {
+                                (normalize::unresolved_object_name(name)?, alias)
+                            }
+                            CreateSourceSubsource::Resolved(..) => {
+                                bail!("Cannot alias subsource using `INTO`, use `AS` instead")
+                            }
+                        };
+                        if upstream_name.database.is_some() {
+                            bail!(
+                                "table {upstream_name} has too many qualifiers, MySQL tables \
+                                 are named `schema.table`"
+                            );
+                        }
+
+                        let schemas = match tables_by_name.get(&upstream_name.item) {
+                            Some(schemas) => schemas,
+                            None => bail!("table {upstream_name} not found in source"),
+                        };
+                        let schema = match &upstream_name.schema {
+                            Some(schema) => schema,
+                            None => match schemas.keys().exactly_one() {
+                                Ok(schema) => schema,
+                                Err(_) => bail!(
+                                    "table {upstream_name} is ambiguous, consider specifying \
+                                     the schema"
+                                ),
+                            },
+                        };
+                        let table = match schemas.get(schema) {
+                            Some(table) => *table,
+                            None => bail!("schema {schema} not found in source"),
+                        };
+
+                        let qualified_upstream_name =
+                            UnresolvedObjectName::qualified(&[schema, &upstream_name.item]);
+                        validated_requested_subsources.push((
+                            qualified_upstream_name,
+                            subsource_name,
+                            table,
+                        ));
+                    }
+                }
+                None => {}
+            };
+
+            // Only ingest the tables that the source exports, so that the
+            // source does not fail on tables whose types it does not support
+            // but that nobody asked for.
+            let mut ingested_tables = vec![];
+            for (i, (upstream_name, subsource_name, table)) in
+                validated_requested_subsources.into_iter().enumerate()
+            {
+                // Figure out the schema of the subsource
+                let mut columns = vec![];
+                for c in table.columns.iter() {
+                    let name = Ident::new(c.name.clone());
+                    let ty = mysql_column_type(c)?;
+                    let data_type = scx.resolve_type(ty)?;
+
+                    columns.push(ColumnDef {
+                        name,
+                        data_type,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
+
+                // Create the targeted AST node for the original CREATE SOURCE statement
+                let transient_id = GlobalId::Transient(u64::cast_from(i));
+                let partial_subsource_name =
+                    normalize::unresolved_object_name(subsource_name.clone())?;
+                let qualified_subsource_name =
+                    scx.allocate_qualified_name(partial_subsource_name.clone())?;
+                let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
+                targeted_subsources.push(CreateSourceSubsource::Resolved(
+                    upstream_name,
+                    vec![],
+                    ResolvedObjectName::Object {
+                        id: transient_id,
+                        qualifiers: qualified_subsource_name.qualifiers,
+                        full_name: full_subsource_name,
+                        print_id: false,
+                    },
+                ));
+
+                // Create the subsource statement
+                let subsource = CreateSubsourceStatement {
+                    name: subsource_name,
+                    columns,
+                    constraints: vec![],
+                    if_not_exists: false,
+                };
+                subsources.push((transient_id, subsource));
+                ingested_tables.push(table.clone());
+            }
+            *requested_subsources = Some(CreateSourceSubsources::Subset(targeted_subsources));
+
+            // Remove any old detail references
+            options
+                .retain(|MySqlConfigOption { name, .. }| name != &MySqlConfigOptionName::Details);
+            let details = MySqlSourceDetails {
+                tables: ingested_tables,
+                // Replicas of a MySQL server must have distinct server IDs.
+                // Pick one at random, avoiding the zero that MySQL reserves.
+                server_id: rand::random::<u32>().max(1),
+            };
+            options.push(MySqlConfigOption {
+                name: MySqlConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &*catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::SqlServer(connection) => connection.clone(),
+                    _ => bail!("{} is not a SQL Server connection", item.name()),
+                }
+            };
+
+            // verify that we can connect upstream, that change data capture
+            // and snapshot isolation are enabled, and snapshot the metadata of
+            // the captured tables
+            let config = connection
+                .config(&*connection_context.secrets_reader)
+                .await?;
+            let mut client = config.connect().await?;
+            mz_sql_server_util::ensure_cdc_enabled(&mut client).await?;
+            mz_sql_server_util::ensure_snapshot_isolation_allowed(&mut client).await?;
+            let tables = mz_sql_server_util::schema_info(&mut client).await?;
+            client.close().await?;
+
+            let mut targeted_subsources = vec![];
+
+            let mut validated_requested_subsources = vec![];
+            match requested_subsources {
+                Some(CreateSourceSubsources::All) => {
+                    for table in &tables {
+                        let upstream_name = UnresolvedObjectName::qualified(&[
+                            &connection.database,
+                            &table.schema_name,
+                            &table.name,
+                        ]);
+                        let subsource_name = UnresolvedObjectName::unqualified(&table.name);
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                Some(CreateSourceSubsources::Subset(subsources)) => {
+                    // An index from table name -> schema name -> database name -> SqlServerTableDesc
+                    let mut tables_by_name = HashMap::new();
+                    for table in &tables {
+                        tables_by_name
+                            .entry(table.name.clone())
+                            .or_insert_with(HashMap::new)
+                            .entry(table.schema_name.clone())
+                            .or_insert_with(HashMap::new)
+                            .entry(connection.database.clone())
+                            .or_insert(table);
+                    }
+
+                    // Column lists were rejected above for all but Postgres sources
+                    validated_requested_subsources.extend(
+                        subsource_gen(subsources, tables_by_name)?.into_iter().map(
+                            |(upstream_name, subsource_name, _, desc)| {
+                                (upstream_name, subsource_name, desc)
+                            },
+                        ),
+                    );
+                }
+                None => {}
+            };
+
+            // Only ingest the tables that the source exports, so that the
+            // source does not fail on tables whose types it does not support
+            // but that nobody asked for.
+            let mut ingested_tables = vec![];
+            for (i, (upstream_name, subsource_name, table)) in
+                validated_requested_subsources.into_iter().enumerate()
+            {
+                // Figure out the schema of the subsource
+                let mut columns = vec![];
+                for c in table.columns.iter() {
+                    let name = Ident::new(c.name.clone());
+                    let ty = sql_server_column_type(c)?;
+                    let data_type = scx.resolve_type(ty)?;
+
+                    columns.push(ColumnDef {
+                        name,
+                        data_type,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
+
+                // Create the targeted AST node for the original CREATE SOURCE statement
+                let transient_id = GlobalId::Transient(u64::cast_from(i));
+                let partial_subsource_name =
+                    normalize::unresolved_object_name(subsource_name.clone())?;
+                let qualified_subsource_name =
+                    scx.allocate_qualified_name(partial_subsource_name.clone())?;
+                let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
+                targeted_subsources.push(CreateSourceSubsource::Resolved(
+                    upstream_name,
+                    vec![],
+                    ResolvedObjectName::Object {
+                        id: transient_id,
+                        qualifiers: qualified_subsource_name.qualifiers,
+                        full_name: full_subsource_name,
+                        print_id: false,
+                    },
+                ));
+
+                // Create the subsource statement
+                let subsource = CreateSubsourceStatement {
+                    name: subsource_name,
+                    columns,
+                    constraints: vec![],
+                    if_not_exists: false,
+                };
+                subsources.push((transient_id, subsource));
+                ingested_tables.push(table.clone());
+            }
+            *requested_subsources = Some(CreateSourceSubsources::Subset(targeted_subsources));
+
+            // Remove any old detail references
+            options.retain(|SqlServerConfigOption { name, .. }| {
+                name != &SqlServerConfigOptionName::Details
+            });
+            let details = SqlServerSourceDetails {
+                tables: ingested_tables,
+            };
+            options.push(SqlServerConfigOption {
+                name: SqlServerConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let scx = StatementContext::new(None, &*catalog);
 
@@ -443,8 +879,14 @@ pub async fn purify_create_source(
                             .or_insert(desc);
                     }
 
-                    validated_requested_subsources
-                        .extend(subsource_gen(selected_subsources, tables_by_name)?);
+                    // Column lists were rejected above for all but Postgres sources
+                    validated_requested_subsources.extend(
+                        subsource_gen(selected_subsources, tables_by_name)?
+                            .into_iter()
+                            .map(|(upstream_name, subsource_name, _, desc)| {
+                                (upstream_name, subsource_name, desc)
+                            }),
+                    );
                 }
                 None => {
                     if available_subsources.is_some() {
@@ -458,29 +900,7 @@ pub async fn purify_create_source(
                 validated_requested_subsources.into_iter().enumerate()
             {
                 // Figure out the schema of the subsource
-                let mut columns = vec![];
-                for (column_name, column_type) in desc.iter() {
-                    let name = Ident::new(column_name.as_str().to_owned());
-
-                    let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
-                    let data_type = scx.resolve_type(ty)?;
-
-                    let options = if !column_type.nullable {
-                        vec![ColumnOptionDef {
-                            name: None,
-                            option: ColumnOption::NotNull,
-                        }]
-                    } else {
-                        vec![]
-                    };
-
-                    columns.push(ColumnDef {
-                        name,
-                        data_type,
-                        collation: None,
-                        options,
-                    });
-                }
+                let columns = subsource_columns(&scx, desc)?;
 
                 let mut table_constraints = vec![];
                 for key in desc.typ().keys.iter() {
@@ -504,6 +924,7 @@ pub async fn purify_create_source(
                 let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
                 targeted_subsources.push(CreateSourceSubsource::Resolved(
                     upstream_name,
+                    vec![],
                     ResolvedObjectName::Object {
                         id: transient_id,
                         qualifiers: qualified_subsource_name.qualifiers,
@@ -531,26 +952,135 @@ pub async fn purify_create_source(
         }
     }
 
+    // Sources that write their decode errors to a subsource need that subsource to be created
+    // alongside them, as `<source>_errors`
+    let CreateSourceOptionExtracted { decode_errors, .. } = with_options.clone().try_into()?;
+    if decode_errors == Some(DecodeErrorPolicy::Subsource) {
+        if requested_subsources.is_some() {
+            bail!("DECODE ERRORS = 'subsource' is not supported by multi-output sources");
+        }
+        let scx = StatementContext::new(None, &*catalog);
+        let columns = subsource_columns(&scx, &decode_errors_desc())?;
+
+        let mut subsource_name = source_name.clone();
+        let item = subsource_name
+            .0
+            .last_mut()
+            .expect("object names have at least one part");
+        *item = Ident::new(format!("{}_errors", item.as_str()));
+
+        let transient_id = GlobalId::Transient(0);
+        let partial_subsource_name = normalize::unresolved_object_name(subsource_name.clone())?;
+        let qualified_subsource_name =
+            scx.allocate_qualified_name(partial_subsource_name.clone())?;
+        let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
+        *requested_subsources = Some(CreateSourceSubsources::Subset(vec![
+            CreateSourceSubsource::Resolved(
+                UnresolvedObjectName::from(decode_errors_subsource_name()),
+                vec![],
+                ResolvedObjectName::Object {
+                    id: transient_id,
+                    qualifiers: qualified_subsource_name.qualifiers,
+                    full_name: full_subsource_name,
+                    print_id: false,
+                },
+            ),
+        ]));
+
+        let subsource = CreateSubsourceStatement {
+            name: subsource_name,
+            columns,
+            constraints: vec![],
+            if_not_exists: false,
+        };
+        subsources.push((transient_id, subsource));
+    }
+
     purify_source_format(&*catalog, format, connection, envelope, &connection_context).await?;
 
     Ok((subsources, stmt))
 }
 
+/// Describes the columns of a subsource whose rows have the schema `desc`.
+fn subsource_columns(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+) -> Result<Vec<ColumnDef<Aug>>, anyhow::Error> {
+    let mut columns = vec![];
+    for (column_name, column_type) in desc.iter() {
+        let name = Ident::new(column_name.as_str().to_owned());
+
+        let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
+        let data_type = scx.resolve_type(ty)?;
+
+        let options = if !column_type.nullable {
+            vec![ColumnOptionDef {
+                name: None,
+                option: ColumnOption::NotNull,
+            }]
+        } else {
+            vec![]
+        };
+
+        columns.push(ColumnDef {
+            name,
+            data_type,
+            collation: None,
+            options,
+        });
+    }
+    Ok(columns)
+}
+
+/// Clears the schemas that purification previously fetched from the schema
+/// registry of `stmt`, so that purifying it again fetches the registry's latest
+/// schemas.
+///
+/// Used by `ALTER SOURCE ... REFRESH SCHEMA`, which only supports sources that
+/// decode Avro with a Confluent Schema Registry.
+pub fn clear_csr_seeds(
+    mut stmt: CreateSourceStatement<Aug>,
+) -> Result<CreateSourceStatement<Aug>, anyhow::Error> {
+    let formats = match &mut stmt.format {
+        CreateSourceFormat::None => vec![],
+        CreateSourceFormat::Bare(format) => vec![format],
+        CreateSourceFormat::KeyValue { key, value } => vec![key, value],
+    };
+    let mut cleared = false;
+    for format in formats {
+        if let Format::Avro(AvroSchema::Csr { csr_connection }) = format {
+            csr_connection.seed = None;
+            cleared = true;
+        }
+    }
+    if !cleared {
+        bail!(
+            "REFRESH SCHEMA is only supported for sources that use \
+            FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY"
+        );
+    }
+    Ok(stmt)
+}
+
 async fn purify_source_format(
     catalog: &dyn SessionCatalog,
     format: &mut CreateSourceFormat<Aug>,
     connection: &mut CreateSourceConnection<Aug>,
-    envelope: &Option<Envelope>,
+    envelope: &Option<Envelope<Aug>>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
     if matches!(format, CreateSourceFormat::KeyValue { .. })
         && !matches!(
             connection,
-            CreateSourceConnection::Kafka { .. } | CreateSourceConnection::TestScript { .. }
+            CreateSourceConnection::Kafka { .. }
+                | CreateSourceConnection::Mqtt { .. }
+                | CreateSourceConnection::Nats { .. }
+                | CreateSourceConnection::PubSub { .. }
+                | CreateSourceConnection::TestScript { .. }
         )
     {
         // We don't mention `TestScript` to users here
-        bail!("Kafka sources are the only source type that can provide KEY/VALUE formats")
+        bail!("Kafka, MQTT, NATS, and Pub/Sub sources are the only source types that can provide KEY/VALUE formats")
     }
 
     match format {
@@ -574,7 +1104,7 @@ async fn purify_source_format_single(
     catalog: &dyn SessionCatalog,
     format: &mut Format<Aug>,
     connection: &mut CreateSourceConnection<Aug>,
-    envelope: &Option<Envelope>,
+    envelope: &Option<Envelope<Aug>>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
     match format {
@@ -605,8 +1135,7 @@ async fn purify_source_format_single(
             ProtobufSchema::InlineSchema { .. } => {}
         },
         Format::Csv {
-            delimiter: _,
-            ref mut columns,
+            ref mut columns, ..
         } => {
             if let CsvColumns::Header { names } = columns {
                 match connection {
@@ -615,11 +1144,93 @@ async fn purify_source_format_single(
                             bail!("CSV WITH HEADER for S3 sources requires specifying the header columns");
                         }
                     }
-                    _ => bail!("CSV WITH HEADER is only supported for S3 sources"),
+                    CreateSourceConnection::Sftp { .. } => {
+                        if names.is_empty() {
+                            bail!("CSV WITH HEADER for SFTP sources requires specifying the header columns");
+                        }
+                    }
+                    _ => bail!("CSV WITH HEADER is only supported for S3 and SFTP sources"),
                 }
             }
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Parquet { columns } => match connection {
+            CreateSourceConnection::S3 {
+                connection,
+                key_sources,
+                pattern,
+                compression,
+            } => {
+                if !matches!(compression, Compression::None) {
+                    bail!("FORMAT PARQUET does not support COMPRESSION: Parquet files compress their own contents");
+                }
+                if columns.is_empty() {
+                    let bucket = key_sources.iter().find_map(|key_source| match key_source {
+                        S3KeySource::Scan { bucket } => Some(bucket),
+                        S3KeySource::SqsNotifications { .. } => None,
+                    });
+                    let bucket = match bucket {
+                        Some(bucket) => bucket,
+                        None => bail!(
+                            "FORMAT PARQUET for S3 sources without a BUCKET SCAN requires specifying the columns"
+                        ),
+                    };
+                    let scx = StatementContext::new(None, catalog);
+                    let aws = {
+                        let item = scx.get_item_by_resolved_name(connection)?;
+                        match item.connection()? {
+                            Connection::Aws(aws) => aws.clone(),
+                            _ => bail!("{} is not an AWS connection", item.name()),
+                        }
+                    };
+                    let pattern = pattern
+                        .as_ref()
+                        .map(|p| {
+                            GlobBuilder::new(p)
+                                .literal_separator(true)
+                                .backslash_escape(true)
+                                .build()
+                                .map(|g| g.compile_matcher())
+                        })
+                        .transpose()
+                        .context("parsing glob")?;
+                    let desc = fetch_parquet_desc(
+                        &aws,
+                        connection_context.aws_external_id_prefix.as_ref(),
+                        &*connection_context.secrets_reader,
+                        bucket,
+                        pattern.as_ref(),
+                    )
+                    .await?;
+
+                    for (column_name, column_type) in desc.iter() {
+                        let name = Ident::new(column_name.as_str().to_owned());
+
+                        let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
+                        let data_type = scx.resolve_type(ty)?;
+
+                        let options = if !column_type.nullable {
+                            vec![ColumnOptionDef {
+                                name: None,
+                                option: ColumnOption::NotNull,
+                            }]
+                        } else {
+                            vec![]
+                        };
+
+                        columns.push(ColumnDef {
+                            name,
+                            data_type,
+                            collation: None,
+                            options,
+                        });
+                    }
+                }
+            }
+            _ => bail!("FORMAT PARQUET is only supported for S3 sources"),
+        },
+        Format::Bytes | Format::Regex(_) | Format::Json { .. } | Format::Text | Format::AvroOcf => {
+            ()
+        }
     }
     Ok(())
 }
@@ -628,7 +1239,7 @@ async fn purify_csr_connection_proto(
     catalog: &dyn SessionCatalog,
     connection: &mut CreateSourceConnection<Aug>,
     csr_connection: &mut CsrConnectionProtobuf<Aug>,
-    envelope: &Option<Envelope>,
+    envelope: &Option<Envelope<Aug>>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
     let topic = if let CreateSourceConnection::Kafka(KafkaSourceConnection {
@@ -640,7 +1251,10 @@ async fn purify_csr_connection_proto(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        match topic {
+            Some(topic) => topic,
+            None => bail!("Confluent Schema Registry is not supported with TOPIC PATTERN"),
+        }
     } else {
         bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
@@ -670,7 +1284,9 @@ async fn purify_csr_connection_proto(
                 .await
                 .ok();
 
-            if matches!(envelope, Some(Envelope::Debezium(DbzMode::Plain))) && key.is_none() {
+            if matches!(envelope, Some(Envelope::Debezium(DbzMode::Plain { tx_metadata })) if tx_metadata.is_empty())
+                && key.is_none()
+            {
                 bail!("Key schema is required for ENVELOPE DEBEZIUM");
             }
 
@@ -686,7 +1302,7 @@ async fn purify_csr_connection_avro(
     catalog: &dyn SessionCatalog,
     connection: &mut CreateSourceConnection<Aug>,
     csr_connection: &mut CsrConnectionAvro<Aug>,
-    envelope: &Option<Envelope>,
+    envelope: &Option<Envelope<Aug>>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
     let topic = if let CreateSourceConnection::Kafka(KafkaSourceConnection {
@@ -698,7 +1314,10 @@ async fn purify_csr_connection_avro(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        match topic {
+            Some(topic) => topic,
+            None => bail!("Confluent Schema Registry is not supported with TOPIC PATTERN"),
+        }
     } else {
         bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
@@ -729,7 +1348,9 @@ async fn purify_csr_connection_avro(
             topic,
         )
         .await?;
-        if matches!(envelope, Some(Envelope::Debezium(DbzMode::Plain))) && key_schema.is_none() {
+        if matches!(envelope, Some(Envelope::Debezium(DbzMode::Plain { tx_metadata })) if tx_metadata.is_empty())
+            && key_schema.is_none()
+        {
             bail!("Key schema is required for ENVELOPE DEBEZIUM");
         }
 
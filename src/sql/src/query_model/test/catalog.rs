@@ -117,6 +117,10 @@ impl CatalogItem for TestCatalogItem {
         }
     }
 
+    fn is_webhook_source(&self) -> bool {
+        false
+    }
+
     fn create_sql(&self) -> &str {
         unimplemented!()
     }
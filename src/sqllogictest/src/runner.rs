@@ -647,6 +647,7 @@ impl Runner {
                 suppress_output: false,
                 data_dir: temp_dir.path().to_path_buf(),
                 command_wrapper: vec![],
+                secrets_encryption: None,
             })
             .await?,
         );
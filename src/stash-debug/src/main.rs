@@ -20,11 +20,12 @@ use std::{
 
 use clap::Parser;
 use once_cell::sync::Lazy;
+use timely::progress::Antichain;
 
 use mz_adapter::catalog::storage as catalog;
 use mz_build_info::{build_info, BuildInfo};
 use mz_ore::cli::{self, CliConfig};
-use mz_stash::{Append, Postgres, Stash};
+use mz_stash::{Append, Postgres, Stash, Timestamp};
 use mz_storage::controller as storage;
 
 pub const BUILD_INFO: BuildInfo = build_info!();
@@ -51,6 +52,24 @@ enum Action {
         key: serde_json::Value,
         value: serde_json::Value,
     },
+    /// Advances the since frontier of a collection.
+    ///
+    /// WARNING: This permanently forgets the history of the collection before
+    /// `to`. It is intended only for recovering a wedged stash, and should not
+    /// be run against a stash that is in use by a running environment.
+    Compact {
+        collection: String,
+        to: Timestamp,
+    },
+    /// Advances the upper frontier of a collection.
+    ///
+    /// WARNING: This permanently declares all times before `to` to be definite.
+    /// It is intended only for recovering a wedged stash, and should not be run
+    /// against a stash that is in use by a running environment.
+    Seal {
+        collection: String,
+        to: Timestamp,
+    },
 }
 
 #[tokio::main]
@@ -90,6 +109,16 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             let stash = Postgres::new(args.postgres_url, None, tls).await?;
             edit(stash, usage, collection, key, value).await
         }
+        Action::Compact { collection, to } => {
+            // compact needs a mutable stash, so reconnect.
+            let stash = Postgres::new(args.postgres_url, None, tls).await?;
+            frontier(stash, usage, collection, FrontierKind::Since, to).await
+        }
+        Action::Seal { collection, to } => {
+            // seal needs a mutable stash, so reconnect.
+            let stash = Postgres::new(args.postgres_url, None, tls).await?;
+            frontier(stash, usage, collection, FrontierKind::Upper, to).await
+        }
     }
 }
 
@@ -105,6 +134,25 @@ async fn edit(
     Ok(())
 }
 
+async fn frontier(
+    mut stash: impl Stash,
+    usage: Usage,
+    collection: String,
+    kind: FrontierKind,
+    to: Timestamp,
+) -> Result<(), anyhow::Error> {
+    eprintln!(
+        "WARNING: advancing the {} of collection {} to {}; this cannot be undone",
+        kind, collection, to
+    );
+    let (prev_since, prev_upper) = usage.advance(&mut stash, &collection, kind, to).await?;
+    println!("collection: {}", collection);
+    println!("previous since: {:?}", prev_since.elements());
+    println!("previous upper: {:?}", prev_upper.elements());
+    println!("new {}: {:?}", kind, [to]);
+    Ok(())
+}
+
 async fn dump(
     mut stash: impl Stash,
     usage: Usage,
@@ -116,6 +164,22 @@ async fn dump(
     Ok(())
 }
 
+/// The frontier of a collection to advance.
+#[derive(Debug, Clone, Copy)]
+enum FrontierKind {
+    Since,
+    Upper,
+}
+
+impl std::fmt::Display for FrontierKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrontierKind::Since => f.write_str("since"),
+            FrontierKind::Upper => f.write_str("upper"),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Usage {
     Catalog,
@@ -269,6 +333,80 @@ impl Usage {
         }
         anyhow::bail!("unknown collection {} for stash {:?}", collection, self)
     }
+
+    /// Advances the since or upper of `collection` to `to`, returning the
+    /// since and upper from before the change.
+    async fn advance(
+        &self,
+        stash: &mut impl Stash,
+        collection: &str,
+        kind: FrontierKind,
+        to: Timestamp,
+    ) -> Result<(Antichain<Timestamp>, Antichain<Timestamp>), anyhow::Error> {
+        macro_rules! advance_col {
+            ($col:expr) => {
+                if collection == $col.name() {
+                    let col = $col.get(stash).await?;
+                    let since = stash.since(col).await?;
+                    let upper = stash.upper(col).await?;
+                    let new = Antichain::from_elem(to);
+                    match kind {
+                        FrontierKind::Since => {
+                            if !since.less_equal(&to) {
+                                anyhow::bail!(
+                                    "cannot move since {:?} backward to {}",
+                                    since.elements(),
+                                    to
+                                );
+                            }
+                            if upper.less_than(&to) {
+                                anyhow::bail!(
+                                    "cannot move since past upper {:?}",
+                                    upper.elements()
+                                );
+                            }
+                            stash.compact(col, new.borrow()).await?;
+                        }
+                        FrontierKind::Upper => {
+                            if !upper.less_equal(&to) {
+                                anyhow::bail!(
+                                    "cannot move upper {:?} backward to {}",
+                                    upper.elements(),
+                                    to
+                                );
+                            }
+                            stash.seal(col, new.borrow()).await?;
+                        }
+                    }
+                    return Ok((since, upper));
+                }
+            };
+        }
+
+        match self {
+            Usage::Catalog => {
+                advance_col!(catalog::COLLECTION_CONFIG);
+                advance_col!(catalog::COLLECTION_ID_ALLOC);
+                advance_col!(catalog::COLLECTION_SYSTEM_GID_MAPPING);
+                advance_col!(catalog::COLLECTION_COMPUTE_INSTANCES);
+                advance_col!(catalog::COLLECTION_COMPUTE_INTROSPECTION_SOURCE_INDEX);
+                advance_col!(catalog::COLLECTION_COMPUTE_REPLICAS);
+                advance_col!(catalog::COLLECTION_DATABASE);
+                advance_col!(catalog::COLLECTION_SCHEMA);
+                advance_col!(catalog::COLLECTION_ITEM);
+                advance_col!(catalog::COLLECTION_ROLE);
+                advance_col!(catalog::COLLECTION_TIMESTAMP);
+                advance_col!(catalog::COLLECTION_SYSTEM_CONFIGURATION);
+                advance_col!(catalog::COLLECTION_AUDIT_LOG);
+                advance_col!(catalog::COLLECTION_STORAGE_USAGE);
+            }
+            Usage::Storage => {
+                advance_col!(storage::METADATA_COLLECTION);
+                advance_col!(storage::METADATA_EXPORT);
+            }
+        }
+        anyhow::bail!("unknown collection {} for stash {:?}", collection, self)
+    }
 }
 
 #[cfg(test)]
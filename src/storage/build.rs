@@ -25,7 +25,9 @@ fn main() {
         .extern_path(".mz_expr.relation", "::mz_expr")
         .extern_path(".mz_expr.scalar", "::mz_expr")
         .extern_path(".mz_kafka_util.addr", "::mz_kafka_util")
+        .extern_path(".mz_mysql_util.desc", "::mz_mysql_util::desc")
         .extern_path(".mz_postgres_util.desc", "::mz_postgres_util::desc")
+        .extern_path(".mz_sql_server_util.desc", "::mz_sql_server_util::desc")
         .extern_path(".mz_repr.adt.regex", "::mz_repr::adt::regex")
         .extern_path(".mz_repr.chrono", "::mz_repr::chrono")
         .extern_path(".mz_repr.antichain", "::mz_repr::antichain")
@@ -46,23 +46,26 @@ use tracing::debug;
 
 use mz_build_info::BuildInfo;
 use mz_orchestrator::NamespacedOrchestrator;
-use mz_ore::now::{EpochMillis, NowFn};
+use mz_ore::now::{self, EpochMillis, NowFn};
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistLocation, ShardId};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::adt::jsonb::JsonbPacker;
 use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, TimestampManipulation};
 use mz_stash::{self, StashError, TypedCollection};
 
 use crate::controller::hosts::{StorageHosts, StorageHostsConfig};
 use crate::protocol::client::{
     CreateSinkCommand, CreateSourceCommand, ProtoStorageCommand, ProtoStorageResponse,
-    StorageCommand, StorageResponse, Update,
+    SinkMetricsUpdate, SinkStatus, SinkStatusUpdate, StorageCommand, StorageResponse, Update,
 };
 use crate::types::errors::DataflowError;
 use crate::types::hosts::StorageHostConfig;
-use crate::types::sinks::{ProtoDurableExportMetadata, SinkAsOf, StorageSinkDesc};
-use crate::types::sources::{IngestionDescription, SourceExport};
+use crate::types::sinks::{
+    ProtoDurableExportMetadata, SinkAsOf, SinkRateLimit, StorageSinkConnection, StorageSinkDesc,
+};
+use crate::types::sources::{IngestionDescription, SourceDesc, SourceExport};
 
 mod hosts;
 mod rehydration;
@@ -101,6 +104,21 @@ pub enum IntrospectionType {
     SinkStatusHistory,
     SourceStatusHistory,
     ShardMapping,
+    /// Counts of the messages read by each source, bucketed by size. Rows are
+    /// appended as storaged reports them.
+    SourceMessageSizes,
+    /// Counts of the attempts of sinks to publish progress records, bucketed
+    /// by duration. Rows are appended as storaged reports them.
+    SinkProgressPublishes,
+    /// Counts of the messages that sinks published to their dead letter
+    /// topics, by error. Rows are appended as storaged reports them.
+    SinkDeadLetters,
+    /// Counts of the values to which sources applied their numeric overflow
+    /// policy. Rows are appended as storaged reports them.
+    SourceNumericOverflows,
+    /// The metrics of each sink. Each sink's row is replaced as storaged
+    /// reports its metrics.
+    SinkMetrics,
 }
 
 /// Describes how data is written to the collection.
@@ -198,6 +216,19 @@ pub trait StorageController: Debug + Send {
         collections: Vec<(GlobalId, StorageHostConfig)>,
     ) -> Result<(), StorageError>;
 
+    /// Alters the relation and source description of a running ingestion,
+    /// after the schema of its source was refreshed.
+    ///
+    /// The ingestion's dataflow is rebuilt from the new description, resuming
+    /// where it left off. Rows written before the alteration are padded with
+    /// `NULL`s when read back.
+    async fn alter_ingestion(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        source_desc: SourceDesc,
+    ) -> Result<(), StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
@@ -213,6 +244,20 @@ pub trait StorageController: Debug + Send {
         exports: Vec<(CreateExportToken, ExportDescription<Self::Timestamp>)>,
     ) -> Result<(), StorageError>;
 
+    /// Alters the connection and rate limit of a running export.
+    ///
+    /// The export's dataflow is rebuilt from the new description. Like on
+    /// restart, it reads its input from the sink's as_of, fast-forwarded to
+    /// the input's since, which the export's write frontier holds back. Sinks
+    /// that do not track what they have written, unlike Kafka sinks, may thus
+    /// emit some updates again.
+    async fn alter_export(
+        &mut self,
+        id: GlobalId,
+        connection: StorageSinkConnection,
+        rate_limit: Option<SinkRateLimit>,
+    ) -> Result<(), StorageError>;
+
     /// Notify the storage controller to prepare for an export to be created
     async fn prepare_export(
         &mut self,
@@ -399,6 +444,10 @@ pub struct CollectionMetadata {
     pub data_shard: ShardId,
     /// The persist shard containing the status updates for this storage collection
     pub status_shard: Option<ShardId>,
+    /// The arity of the collection's relation, if rows written by earlier
+    /// versions of the collection may be shorter and need to be padded with
+    /// `NULL`s when read back. Set for ingestions, whose schema can evolve.
+    pub relation_arity: Option<usize>,
 }
 
 impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
@@ -409,6 +458,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             data_shard: self.data_shard.to_string(),
             remap_shard: self.remap_shard.to_string(),
             status_shard: self.status_shard.map(|s| s.to_string()),
+            relation_arity: self.relation_arity.into_proto(),
         }
     }
 
@@ -430,6 +480,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
                 .status_shard
                 .map(|s| s.parse().map_err(TryFromProtoError::InvalidShardId))
                 .transpose()?,
+            relation_arity: value.relation_arity.into_rust()?,
         })
     }
 }
@@ -521,6 +572,9 @@ impl Codec for DurableCollectionMetadata {
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DurableExportMetadata<T> {
     pub initial_as_of: SinkAsOf<T>,
+    /// The persist shard that durably records the progress of the export, if
+    /// it keeps one.
+    pub progress_shard: Option<ShardId>,
 }
 
 impl PartialOrd for DurableExportMetadata<mz_repr::Timestamp> {
@@ -543,6 +597,7 @@ impl RustType<ProtoDurableExportMetadata> for DurableExportMetadata<mz_repr::Tim
     fn into_proto(&self) -> ProtoDurableExportMetadata {
         ProtoDurableExportMetadata {
             initial_as_of: Some(self.initial_as_of.into_proto()),
+            progress_shard: self.progress_shard.map(|s| s.to_string()),
         }
     }
 
@@ -551,6 +606,10 @@ impl RustType<ProtoDurableExportMetadata> for DurableExportMetadata<mz_repr::Tim
             initial_as_of: proto
                 .initial_as_of
                 .into_rust_if_some("ProtoDurableExportMetadata::initial_as_of")?,
+            progress_shard: proto
+                .progress_shard
+                .map(|s| s.parse().map_err(TryFromProtoError::InvalidShardId))
+                .transpose()?,
         })
     }
 }
@@ -577,8 +636,14 @@ impl Arbitrary for DurableExportMetadata<mz_repr::Timestamp> {
     type Parameters = ();
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        (any::<SinkAsOf<mz_repr::Timestamp>>(),)
-            .prop_map(|(initial_as_of,)| Self { initial_as_of })
+        (
+            any::<SinkAsOf<mz_repr::Timestamp>>(),
+            any::<Option<ShardId>>(),
+        )
+            .prop_map(|(initial_as_of, progress_shard)| Self {
+                initial_as_of,
+                progress_shard,
+            })
             .boxed()
     }
 }
@@ -610,6 +675,25 @@ pub struct StorageControllerState<
     pub(super) collection_manager: collection_mgmt::CollectionManager,
     /// Tracks which collection is responsible for which [`IntrospectionType`].
     pub(super) introspection_ids: HashMap<IntrospectionType, GlobalId>,
+    /// The metrics of each sink, as last written to the
+    /// `IntrospectionType::SinkMetrics` collection.
+    pub(super) sink_metrics: HashMap<GlobalId, SinkMetricsState>,
+    /// The status and error of each sink, as last written to the
+    /// `IntrospectionType::SinkStatusHistory` collection.
+    pub(super) sink_statuses: HashMap<GlobalId, (SinkStatus, Option<String>)>,
+    /// The function that returns the current time, at which sinks are
+    /// recorded as dropped.
+    pub(super) now: NowFn,
+}
+
+/// The metrics of a sink, accumulated since the controller started.
+#[derive(Debug)]
+pub(super) struct SinkMetricsState {
+    messages: u64,
+    bytes: u64,
+    /// The row that describes the sink in the `IntrospectionType::SinkMetrics`
+    /// collection.
+    row: Row,
 }
 
 /// A storage controller for a storage instance.
@@ -735,7 +819,7 @@ impl<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + TimestampManipulatio
         let collection_manager_write_handle = persist_write_handles.clone();
 
         let collection_manager =
-            collection_mgmt::CollectionManager::new(collection_manager_write_handle, now);
+            collection_mgmt::CollectionManager::new(collection_manager_write_handle, now.clone());
 
         Self {
             collections: BTreeMap::default(),
@@ -747,6 +831,9 @@ impl<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + TimestampManipulatio
             stashed_response: None,
             collection_manager,
             introspection_ids: HashMap::new(),
+            sink_metrics: HashMap::new(),
+            sink_statuses: HashMap::new(),
+            now,
         }
     }
 }
@@ -850,6 +937,10 @@ where
                 remap_shard: collection_shards.remap_shard,
                 data_shard: collection_shards.data_shard,
                 status_shard,
+                relation_arity: match description.data_source {
+                    DataSource::Ingestion(_) => Some(description.desc.arity()),
+                    _ => None,
+                },
             };
 
             // should be replaced with real introspection (https://github.com/MaterializeInc/materialize/issues/14266)
@@ -892,48 +983,13 @@ where
 
             match description.data_source {
                 DataSource::Ingestion(ingestion) => {
-                    // Each ingestion is augmented with the collection metadata.
-                    let mut source_imports = BTreeMap::new();
-                    for (id, _) in ingestion.source_imports {
-                        let metadata = self.collection(id)?.collection_metadata.clone();
-                        source_imports.insert(id, metadata);
-                    }
-
-                    // The ingestion metadata is simply the collection metadata of the collection with
-                    // the associated ingestion
-                    let ingestion_metadata = self.collection(id)?.collection_metadata.clone();
-
-                    let mut source_exports = BTreeMap::new();
-                    for (id, export) in ingestion.source_exports {
-                        let storage_metadata = self.collection(id)?.collection_metadata.clone();
-                        source_exports.insert(
-                            id,
-                            SourceExport {
-                                storage_metadata,
-                                output_index: export.output_index,
-                            },
-                        );
-                    }
-
-                    let desc = IngestionDescription {
-                        source_imports,
-                        source_exports,
-                        ingestion_metadata,
-                        // The rest of the fields are identical
-                        desc: ingestion.desc,
-                        host_config: ingestion.host_config,
-                    };
-                    let mut persist_clients = self.persist.lock().await;
-                    let mut state = desc.initialize_state(&mut persist_clients).await;
-                    let resume_upper = desc.calculate_resumption_frontier(&mut state).await;
+                    let augmented_ingestion = self.create_source_command(id, ingestion).await?;
 
                     // Provision a storage host for the ingestion.
-                    let client = self.hosts.provision(id, desc.host_config.clone()).await?;
-                    let augmented_ingestion = CreateSourceCommand {
-                        id,
-                        description: desc,
-                        resume_upper,
-                    };
+                    let client = self
+                        .hosts
+                        .provision(id, augmented_ingestion.description.host_config.clone())
+                        .await?;
 
                     client.send(StorageCommand::CreateSources(vec![augmented_ingestion]));
                 }
@@ -951,9 +1007,25 @@ where
                             self.truncate_managed_collection(id).await;
                             self.initialize_shard_mapping().await;
                         }
-                        IntrospectionType::SourceStatusHistory
-                        | IntrospectionType::SinkStatusHistory => {
-                            // nothing to do: only storaged writes rows to these collections
+                        IntrospectionType::SourceMessageSizes
+                        | IntrospectionType::SinkProgressPublishes
+                        | IntrospectionType::SinkDeadLetters
+                        | IntrospectionType::SourceNumericOverflows => {
+                            // The counts are deltas reported by storaged since
+                            // the last restart, so start from scratch.
+                            self.truncate_managed_collection(id).await;
+                        }
+                        IntrospectionType::SinkMetrics => {
+                            // The rows we last wrote are forgotten along with
+                            // the accumulated metrics, so start from scratch.
+                            self.truncate_managed_collection(id).await;
+                        }
+                        IntrospectionType::SourceStatusHistory => {
+                            // nothing to do: only storaged writes rows to this collection
+                        }
+                        IntrospectionType::SinkStatusHistory => {
+                            // nothing to do: the history is kept across
+                            // restarts, and rows are only ever appended
                         }
                     }
                 }
@@ -1053,40 +1125,12 @@ where
             }
         }
 
-        for (CreateExportToken { id, from_id }, description) in exports {
+        for (CreateExportToken { id, from_id: _ }, description) in exports {
             self.state
                 .exports
                 .insert(id, ExportState::new(description.clone()));
 
-            let from_collection = self.collection(from_id)?;
-            let from_storage_metadata = from_collection.collection_metadata.clone();
-            // We've added the dependency above in `exported_collections` so this guaranteed not to change at least
-            // until the sink is started up.
-            let from_since = from_collection.implied_capability.clone();
-
-            let as_of = MetadataExportFetcher::get_stash_collection()
-                .insert_key_without_overwrite(
-                    &mut self.state.stash,
-                    &id,
-                    DurableExportMetadata {
-                        initial_as_of: description.sink.as_of,
-                    },
-                )
-                .await?
-                .initial_as_of
-                .maybe_fast_forward(&from_since);
-
-            let cmd = CreateSinkCommand {
-                id,
-                description: StorageSinkDesc {
-                    from: from_id,
-                    from_desc: description.sink.from_desc,
-                    connection: description.sink.connection,
-                    envelope: description.sink.envelope,
-                    as_of,
-                    from_storage_metadata,
-                },
-            };
+            let cmd = self.create_sink_command(id, &description).await?;
 
             // Provision a storage host for the ingestion.
             let client = self.hosts.provision(id, description.host_config).await?;
@@ -1096,6 +1140,51 @@ where
         Ok(())
     }
 
+    async fn alter_export(
+        &mut self,
+        id: GlobalId,
+        connection: StorageSinkConnection,
+        rate_limit: Option<SinkRateLimit>,
+    ) -> Result<(), StorageError> {
+        let export = self.export_mut(id)?;
+        export.description.sink.connection = connection;
+        export.description.sink.rate_limit = rate_limit;
+        let description = export.description.clone();
+
+        let cmd = self.create_sink_command(id, &description).await?;
+        let client = self
+            .hosts
+            .client(id)
+            .ok_or(StorageError::IdentifierMissing(id))?;
+        client.send(StorageCommand::CreateSinks(vec![cmd]));
+        Ok(())
+    }
+
+    async fn alter_ingestion(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        source_desc: SourceDesc,
+    ) -> Result<(), StorageError> {
+        let collection = self.collection_mut(id)?;
+        let ingestion = match &mut collection.description.data_source {
+            DataSource::Ingestion(ingestion) => ingestion,
+            _ => return Err(StorageError::IdentifierMissing(id)),
+        };
+        ingestion.desc = source_desc;
+        let ingestion = ingestion.clone();
+        collection.collection_metadata.relation_arity = Some(desc.arity());
+        collection.description.desc = desc;
+
+        let cmd = self.create_source_command(id, ingestion).await?;
+        let client = self
+            .hosts
+            .client(id)
+            .ok_or(StorageError::IdentifierMissing(id))?;
+        client.send(StorageCommand::CreateSources(vec![cmd]));
+        Ok(())
+    }
+
     async fn drop_sources(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError> {
         self.validate_collection_ids(identifiers.iter().cloned())?;
         let policies = identifiers
@@ -1146,6 +1235,21 @@ where
             self.update_write_frontiers(&[(id, Antichain::new())])
                 .await?;
             self.hosts.deprovision(id).await?;
+
+            if let Some(metrics) = self.state.sink_metrics.remove(&id) {
+                let metrics_id = self.state.introspection_ids[&IntrospectionType::SinkMetrics];
+                self.append_to_managed_collection(metrics_id, vec![(metrics.row, -1)])
+                    .await;
+            }
+
+            self.state.sink_statuses.remove(&id);
+            let dropped = SinkStatusUpdate {
+                occurred_at: (self.state.now)(),
+                status: SinkStatus::Dropped,
+                error: None,
+                details: None,
+            };
+            self.record_sink_status_updates(vec![(id, dropped)]).await;
         }
         Ok(())
     }
@@ -1336,6 +1440,30 @@ where
                 self.update_write_frontiers(&updates).await?;
                 Ok(())
             }
+            Some(StorageResponse::SourceMessageSizes(updates)) => {
+                self.record_source_message_sizes(updates).await;
+                Ok(())
+            }
+            Some(StorageResponse::SinkProgressPublishes(updates)) => {
+                self.record_sink_progress_publishes(updates).await;
+                Ok(())
+            }
+            Some(StorageResponse::SinkDeadLetters(updates)) => {
+                self.record_sink_dead_letters(updates).await;
+                Ok(())
+            }
+            Some(StorageResponse::SourceNumericOverflows(updates)) => {
+                self.record_source_numeric_overflows(updates).await;
+                Ok(())
+            }
+            Some(StorageResponse::SinkMetrics(updates)) => {
+                self.record_sink_metrics(updates).await;
+                Ok(())
+            }
+            Some(StorageResponse::SinkStatusUpdates(updates)) => {
+                self.record_sink_status_updates(updates).await;
+                Ok(())
+            }
         }
     }
 }
@@ -1406,6 +1534,109 @@ where
         Ok(())
     }
 
+    /// Builds the command that installs the described ingestion on its storage
+    /// host, augmenting it with the metadata of the collections it involves.
+    async fn create_source_command(
+        &mut self,
+        id: GlobalId,
+        ingestion: IngestionDescription<()>,
+    ) -> Result<CreateSourceCommand<T>, StorageError> {
+        let mut source_imports = BTreeMap::new();
+        for (id, _) in ingestion.source_imports {
+            let metadata = self.collection(id)?.collection_metadata.clone();
+            source_imports.insert(id, metadata);
+        }
+
+        // The ingestion metadata is simply the collection metadata of the collection with
+        // the associated ingestion
+        let ingestion_metadata = self.collection(id)?.collection_metadata.clone();
+
+        let mut source_exports = BTreeMap::new();
+        for (id, export) in ingestion.source_exports {
+            let storage_metadata = self.collection(id)?.collection_metadata.clone();
+            source_exports.insert(
+                id,
+                SourceExport {
+                    storage_metadata,
+                    output_index: export.output_index,
+                },
+            );
+        }
+
+        let description = IngestionDescription {
+            source_imports,
+            source_exports,
+            ingestion_metadata,
+            // The rest of the fields are identical
+            desc: ingestion.desc,
+            host_config: ingestion.host_config,
+        };
+        let mut persist_clients = self.persist.lock().await;
+        let mut state = description.initialize_state(&mut persist_clients).await;
+        let resume_upper = description.calculate_resumption_frontier(&mut state).await;
+
+        Ok(CreateSourceCommand {
+            id,
+            description,
+            resume_upper,
+        })
+    }
+
+    /// Builds the command that installs the described export on its storage
+    /// host.
+    async fn create_sink_command(
+        &mut self,
+        id: GlobalId,
+        description: &ExportDescription<T>,
+    ) -> Result<CreateSinkCommand<T>, StorageError> {
+        let from_id = description.sink.from;
+        let from_collection = self.collection(from_id)?;
+        let from_storage_metadata = from_collection.collection_metadata.clone();
+        // We've added the dependency in `exported_collections` so this guaranteed not to change at least
+        // until the sink is started up.
+        let from_since = from_collection.implied_capability.clone();
+
+        // Kafka sinks record their progress in a shard of their own, so that
+        // they can resume exactly-once regardless of the topics they write
+        // to. Sinks created before progress shards existed are assigned one
+        // the next time they are installed.
+        let needs_progress_shard =
+            matches!(description.sink.connection, StorageSinkConnection::Kafka(_));
+        let (_, metadata) = MetadataExportFetcher::get_stash_collection()
+            .upsert_key(&mut self.state.stash, &id, |prev| {
+                let mut metadata = prev.cloned().unwrap_or_else(|| DurableExportMetadata {
+                    initial_as_of: description.sink.as_of.clone(),
+                    progress_shard: None,
+                });
+                if needs_progress_shard && metadata.progress_shard.is_none() {
+                    metadata.progress_shard = Some(ShardId::new());
+                }
+                Ok::<_, StorageError>(metadata)
+            })
+            .await??;
+        let as_of = metadata.initial_as_of.maybe_fast_forward(&from_since);
+
+        Ok(CreateSinkCommand {
+            id,
+            description: StorageSinkDesc {
+                from: from_id,
+                from_desc: description.sink.from_desc.clone(),
+                connection: description.sink.connection.clone(),
+                envelope: description.sink.envelope,
+                as_of,
+                from_storage_metadata,
+                catch_up_limit: description.sink.catch_up_limit,
+                rate_limit: description.sink.rate_limit,
+                projection: description.sink.projection.clone(),
+                column_masks: description.sink.column_masks.clone(),
+                include_timestamp: description.sink.include_timestamp,
+                include_diff: description.sink.include_diff,
+                filter: description.sink.filter.clone(),
+                progress_shard: metadata.progress_shard,
+            },
+        })
+    }
+
     // Should only fail if collection doesn't exist. N.B. We can't just take in the mut ref because then the borrow checker wouldn't let us read state.
     fn generate_new_capability_for_collection<F>(
         &mut self,
@@ -1554,6 +1785,40 @@ pub trait CollectionManagement: Debug + Send + StorageController {
 
     /// Writes a new global ID, shard ID pair to the appropriate collection.
     async fn register_shard_mapping(&mut self, global_id: GlobalId);
+
+    // SourceMessageSizes functions
+
+    /// Records counts of messages read by sources, as reported by storaged.
+    async fn record_source_message_sizes(&mut self, updates: Vec<(GlobalId, u64, i64)>);
+
+    // SinkProgressPublishes functions
+
+    /// Records counts of progress record publishes by sinks, as reported by
+    /// storaged.
+    async fn record_sink_progress_publishes(&mut self, updates: Vec<(GlobalId, u64, bool, i64)>);
+
+    // SinkDeadLetters functions
+
+    /// Records counts of messages published to dead letter topics by sinks,
+    /// as reported by storaged.
+    async fn record_sink_dead_letters(&mut self, updates: Vec<(GlobalId, String, i64)>);
+
+    // SourceNumericOverflows functions
+
+    /// Records counts of values to which sources applied their numeric
+    /// overflow policy, as reported by storaged.
+    async fn record_source_numeric_overflows(&mut self, updates: Vec<(GlobalId, String, i64)>);
+
+    // SinkMetrics functions
+
+    /// Records the metrics of sinks, as reported by storaged.
+    async fn record_sink_metrics(&mut self, updates: Vec<(GlobalId, SinkMetricsUpdate)>);
+
+    // SinkStatusHistory functions
+
+    /// Records the transitions of sinks between statuses, as reported by
+    /// storaged.
+    async fn record_sink_status_updates(&mut self, updates: Vec<(GlobalId, SinkStatusUpdate)>);
 }
 
 #[async_trait(?Send)]
@@ -1673,6 +1938,276 @@ where
 
         self.append_to_managed_collection(id, updates).await;
     }
+
+    /// Appends one row per counted message, with the count as the row's diff,
+    /// to the `IntrospectionType::SourceMessageSizes` collection.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SourceMessageSizes` collection; in other cases,
+    /// data is dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SourceMessageSizes`'s `GlobalId` is not
+    ///   registered as a managed collection.
+    async fn record_source_message_sizes(&mut self, updates: Vec<(GlobalId, u64, i64)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SourceMessageSizes)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let updates = updates
+            .into_iter()
+            .map(|(source_id, size_bytes, count)| {
+                let mut packer = row_buf.packer();
+                packer.push(Datum::from(source_id.to_string().as_str()));
+                packer.push(Datum::UInt64(size_bytes));
+                (row_buf.clone(), count)
+            })
+            .collect();
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
+    /// Appends one row per counted publish attempt, with the count as the
+    /// row's diff, to the `IntrospectionType::SinkProgressPublishes`
+    /// collection.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SinkProgressPublishes` collection; in other cases,
+    /// data is dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SinkProgressPublishes`'s `GlobalId` is not
+    ///   registered as a managed collection.
+    async fn record_sink_progress_publishes(&mut self, updates: Vec<(GlobalId, u64, bool, i64)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SinkProgressPublishes)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let updates = updates
+            .into_iter()
+            .map(|(sink_id, duration_ns, failed, count)| {
+                let mut packer = row_buf.packer();
+                packer.push(Datum::from(sink_id.to_string().as_str()));
+                packer.push(Datum::UInt64(duration_ns));
+                packer.push(Datum::from(failed));
+                (row_buf.clone(), count)
+            })
+            .collect();
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
+    /// Appends one row per message published to a dead letter topic, with the
+    /// count as the row's diff, to the `IntrospectionType::SinkDeadLetters`
+    /// collection.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SinkDeadLetters` collection; in other cases, data
+    /// is dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SinkDeadLetters`'s `GlobalId` is not
+    ///   registered as a managed collection.
+    async fn record_sink_dead_letters(&mut self, updates: Vec<(GlobalId, String, i64)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SinkDeadLetters)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let updates = updates
+            .into_iter()
+            .map(|(sink_id, error, count)| {
+                let mut packer = row_buf.packer();
+                packer.push(Datum::from(sink_id.to_string().as_str()));
+                packer.push(Datum::from(error.as_str()));
+                (row_buf.clone(), count)
+            })
+            .collect();
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
+    /// Appends one row per value to which a source applied its numeric
+    /// overflow policy, with the count as the row's diff, to the
+    /// `IntrospectionType::SourceNumericOverflows` collection.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SourceNumericOverflows` collection; in other cases,
+    /// data is dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SourceNumericOverflows`'s `GlobalId` is not
+    ///   registered as a managed collection.
+    async fn record_source_numeric_overflows(&mut self, updates: Vec<(GlobalId, String, i64)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SourceNumericOverflows)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let updates = updates
+            .into_iter()
+            .map(|(source_id, policy, count)| {
+                let mut packer = row_buf.packer();
+                packer.push(Datum::from(source_id.to_string().as_str()));
+                packer.push(Datum::from(policy.as_str()));
+                (row_buf.clone(), count)
+            })
+            .collect();
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
+    /// Replaces the row of each sink in the `IntrospectionType::SinkMetrics`
+    /// collection with one that accounts for the sink's reported metrics.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SinkMetrics` collection; in other cases, data is
+    /// dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SinkMetrics`'s `GlobalId` is not registered
+    ///   as a managed collection.
+    async fn record_sink_metrics(&mut self, updates: Vec<(GlobalId, SinkMetricsUpdate)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SinkMetrics)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let mut changes = vec![];
+        for (sink_id, update) in updates {
+            // Reports may race with dropping the sink, whose row we must not
+            // resurrect.
+            match self.state.exports.get(&sink_id) {
+                Some(export) if !export.write_frontier.is_empty() => (),
+                _ => continue,
+            }
+
+            let committed = update.committed_frontier.as_option().copied();
+            let input = update.input_frontier.as_option().copied();
+            let lag_ms = match (committed, input) {
+                (Some(committed), Some(input)) => {
+                    Some(u64::from(input).saturating_sub(u64::from(committed)))
+                }
+                _ => None,
+            };
+
+            let (messages, bytes) = match self.state.sink_metrics.remove(&sink_id) {
+                Some(prev) => {
+                    changes.push((prev.row, -1));
+                    (prev.messages + update.messages, prev.bytes + update.bytes)
+                }
+                None => (update.messages, update.bytes),
+            };
+
+            let mut packer = row_buf.packer();
+            packer.push(Datum::from(sink_id.to_string().as_str()));
+            packer.push(Datum::UInt64(messages));
+            packer.push(Datum::UInt64(bytes));
+            packer.push(committed.map_or(Datum::Null, Datum::MzTimestamp));
+            packer.push(input.map_or(Datum::Null, Datum::MzTimestamp));
+            packer.push(lag_ms.map_or(Datum::Null, Datum::UInt64));
+            changes.push((row_buf.clone(), 1));
+
+            self.state.sink_metrics.insert(
+                sink_id,
+                SinkMetricsState {
+                    messages,
+                    bytes,
+                    row: row_buf.clone(),
+                },
+            );
+        }
+
+        self.append_to_managed_collection(id, changes).await;
+    }
+
+    /// Appends one row per transition of a sink to a new status to the
+    /// `IntrospectionType::SinkStatusHistory` collection.
+    ///
+    /// Transitions that repeat the status and error last recorded for a sink
+    /// are skipped, as are transitions of sinks that have since been dropped.
+    ///
+    /// Data is written iff we know of the `GlobalId` of the
+    /// `IntrospectionType::SinkStatusHistory` collection; in other cases, data
+    /// is dropped on the floor.
+    ///
+    /// # Panics
+    /// - If `IntrospectionType::SinkStatusHistory`'s `GlobalId` is not
+    ///   registered as a managed collection.
+    async fn record_sink_status_updates(&mut self, updates: Vec<(GlobalId, SinkStatusUpdate)>) {
+        let id = match self
+            .state
+            .introspection_ids
+            .get(&IntrospectionType::SinkStatusHistory)
+        {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        let mut row_buf = Row::default();
+        let mut rows = vec![];
+        for (sink_id, update) in updates {
+            if update.status != SinkStatus::Dropped {
+                match self.state.exports.get(&sink_id) {
+                    Some(export) if !export.write_frontier.is_empty() => (),
+                    _ => continue,
+                }
+                let last = (update.status, update.error.clone());
+                if self.state.sink_statuses.get(&sink_id) == Some(&last) {
+                    continue;
+                }
+                self.state.sink_statuses.insert(sink_id, last);
+            }
+
+            let occurred_at = now::to_datetime(update.occurred_at);
+            let mut packer = row_buf.packer();
+            packer.push(Datum::TimestampTz(
+                occurred_at.try_into().expect("must fit"),
+            ));
+            packer.push(Datum::from(sink_id.to_string().as_str()));
+            packer.push(Datum::from(update.status.name()));
+            packer.push(Datum::from(update.error.as_deref()));
+            match update
+                .details
+                .and_then(|details| serde_json::from_str(&details).ok())
+            {
+                Some(details) => JsonbPacker::new(&mut packer)
+                    .pack_serde_json(details)
+                    .expect("status details are valid JSON"),
+                None => packer.push(Datum::Null),
+            }
+            rows.push((row_buf.clone(), 1));
+        }
+
+        self.append_to_managed_collection(id, rows).await;
+    }
 }
 
 mod persist_read_handles {
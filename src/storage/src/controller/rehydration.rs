@@ -345,6 +345,78 @@ where
                     None
                 }
             }
+            StorageResponse::SourceMessageSizes(sizes) => {
+                // Drop the counts of sources that have since been dropped.
+                let sizes: Vec<_> = sizes
+                    .into_iter()
+                    .filter(|(id, _, _)| self.sources.contains_key(id))
+                    .collect();
+                if !sizes.is_empty() {
+                    Some(StorageResponse::SourceMessageSizes(sizes))
+                } else {
+                    None
+                }
+            }
+            StorageResponse::SinkProgressPublishes(publishes) => {
+                // Drop the counts of sinks that have since been dropped.
+                let publishes: Vec<_> = publishes
+                    .into_iter()
+                    .filter(|(id, _, _, _)| self.sinks.contains_key(id))
+                    .collect();
+                if !publishes.is_empty() {
+                    Some(StorageResponse::SinkProgressPublishes(publishes))
+                } else {
+                    None
+                }
+            }
+            StorageResponse::SourceNumericOverflows(overflows) => {
+                // Drop the counts of sources that have since been dropped.
+                let overflows: Vec<_> = overflows
+                    .into_iter()
+                    .filter(|(id, _, _)| self.sources.contains_key(id))
+                    .collect();
+                if !overflows.is_empty() {
+                    Some(StorageResponse::SourceNumericOverflows(overflows))
+                } else {
+                    None
+                }
+            }
+            StorageResponse::SinkDeadLetters(dead_letters) => {
+                // Drop the counts of sinks that have since been dropped.
+                let dead_letters: Vec<_> = dead_letters
+                    .into_iter()
+                    .filter(|(id, _, _)| self.sinks.contains_key(id))
+                    .collect();
+                if !dead_letters.is_empty() {
+                    Some(StorageResponse::SinkDeadLetters(dead_letters))
+                } else {
+                    None
+                }
+            }
+            StorageResponse::SinkMetrics(metrics) => {
+                // Drop the metrics of sinks that have since been dropped.
+                let metrics: Vec<_> = metrics
+                    .into_iter()
+                    .filter(|(id, _)| self.sinks.contains_key(id))
+                    .collect();
+                if !metrics.is_empty() {
+                    Some(StorageResponse::SinkMetrics(metrics))
+                } else {
+                    None
+                }
+            }
+            StorageResponse::SinkStatusUpdates(updates) => {
+                // Drop the transitions of sinks that have since been dropped.
+                let updates: Vec<_> = updates
+                    .into_iter()
+                    .filter(|(id, _)| self.sinks.contains_key(id))
+                    .collect();
+                if !updates.is_empty() {
+                    Some(StorageResponse::SinkStatusUpdates(updates))
+                } else {
+                    None
+                }
+            }
         }
     }
 }
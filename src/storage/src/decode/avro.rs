@@ -13,12 +13,14 @@ use mz_interchange::avro::Decoder;
 use mz_repr::Row;
 
 use crate::types::errors::DecodeErrorKind;
+use crate::types::sources::encoding::NumericOverflowPolicy;
 
 #[derive(Debug)]
 pub struct AvroDecoderState {
     tokio_handle: TokioHandle,
     decoder: Decoder,
     events_success: i64,
+    numeric_overflow: NumericOverflowPolicy,
 }
 
 impl AvroDecoderState {
@@ -27,14 +29,28 @@ impl AvroDecoderState {
         ccsr_client: Option<mz_ccsr::Client>,
         debug_name: String,
         confluent_wire_format: bool,
+        numeric_overflow: NumericOverflowPolicy,
     ) -> Result<Self, anyhow::Error> {
         Ok(AvroDecoderState {
             tokio_handle: TokioHandle::current(),
-            decoder: Decoder::new(value_schema, ccsr_client, debug_name, confluent_wire_format)?,
+            decoder: Decoder::new(
+                value_schema,
+                ccsr_client,
+                debug_name,
+                confluent_wire_format,
+                numeric_overflow.into(),
+            )?,
             events_success: 0,
+            numeric_overflow,
         })
     }
 
+    /// Returns the overflow policy of the decoder and the number of values to
+    /// which it has been applied since the last call.
+    pub fn take_overflows(&mut self) -> (NumericOverflowPolicy, u64) {
+        (self.numeric_overflow, self.decoder.take_overflows())
+    }
+
     pub fn decode(&mut self, bytes: &mut &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
         match self.tokio_handle.block_on(self.decoder.decode(bytes)) {
             Ok(row) => {
@@ -7,16 +7,29 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_repr::{Datum, Row};
+use mz_pgrepr::Type;
+use mz_repr::{Datum, Row, RowArena};
 
 use crate::types::errors::DecodeErrorKind;
 use crate::types::sources::encoding::CsvEncoding;
 
+#[derive(Debug)]
+struct CsvColumn {
+    /// The position of the column's field in each record.
+    index: usize,
+    name: String,
+    typ: Type,
+    nullable: bool,
+}
+
 #[derive(Debug)]
 pub struct CsvDecoderState {
     next_row_is_header: bool,
     header_names: Option<Vec<String>>,
     n_cols: usize,
+    /// The columns to decode the fields of each record into, or `None` to
+    /// decode every field as text.
+    typed_columns: Option<Vec<CsvColumn>>,
     output: Vec<u8>,
     output_cursor: usize,
     ends: Vec<usize>,
@@ -33,19 +46,44 @@ impl CsvDecoderState {
     }
 
     pub fn new(format: CsvEncoding) -> Self {
-        let CsvEncoding { columns, delimiter } = format;
+        let CsvEncoding {
+            columns,
+            delimiter,
+            quote,
+            escape,
+            typed_columns,
+        } = format;
         let n_cols = columns.arity();
 
+        let typed_columns = typed_columns.map(|desc| {
+            let names = columns.names();
+            desc.iter()
+                .map(|(name, typ)| CsvColumn {
+                    index: names
+                        .iter()
+                        .position(|n| n == name.as_str())
+                        .expect("typed CSV columns validated during planning"),
+                    name: name.as_str().to_string(),
+                    typ: Type::from(&typ.scalar_type),
+                    nullable: typ.nullable,
+                })
+                .collect()
+        });
         let header_names = columns.into_header_names();
         Self {
             next_row_is_header: header_names.is_some(),
             header_names,
             n_cols,
+            typed_columns,
             output: vec![0],
             output_cursor: 0,
             ends: vec![0],
             ends_cursor: 1,
-            csv_reader: csv_core::ReaderBuilder::new().delimiter(delimiter).build(),
+            csv_reader: csv_core::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .quote(quote)
+                .escape(escape)
+                .build(),
             row_buf: Row::default(),
             events_error: 0,
             events_success: 0,
@@ -97,14 +135,37 @@ impl CsvDecoderState {
                         } else {
                             match std::str::from_utf8(&self.output[0..self.output_cursor]) {
                                 Ok(output) => {
-                                    self.events_success += 1;
-                                    let mut row_packer = self.row_buf.packer();
-                                    row_packer.extend((0..self.n_cols).map(|i| {
-                                        Datum::String(&output[self.ends[i]..self.ends[i + 1]])
-                                    }));
+                                    let field = |i: usize| &output[self.ends[i]..self.ends[i + 1]];
+                                    // Header rows are always packed as text, so that they can
+                                    // be compared against the expected header names.
+                                    let packed = match &self.typed_columns {
+                                        Some(columns) if !self.next_row_is_header => {
+                                            pack_typed_columns(&mut self.row_buf, columns, field)
+                                        }
+                                        _ => {
+                                            let mut row_packer = self.row_buf.packer();
+                                            row_packer.extend(
+                                                (0..self.n_cols).map(|i| Datum::String(field(i))),
+                                            );
+                                            Ok(())
+                                        }
+                                    };
                                     self.output_cursor = 0;
                                     self.ends_cursor = 1;
-                                    Ok(Some(self.row_buf.clone()))
+                                    match packed {
+                                        Ok(()) => {
+                                            self.events_success += 1;
+                                            Ok(Some(self.row_buf.clone()))
+                                        }
+                                        Err(e) => {
+                                            self.events_error += 1;
+                                            Err(DecodeErrorKind::Text(format!(
+                                                "CSV error at record number {}: {}",
+                                                self.total_events(),
+                                                e
+                                            )))
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     self.events_error += 1;
@@ -152,3 +213,32 @@ impl CsvDecoderState {
         }
     }
 }
+
+/// Packs the fields of a record into `row`, decoding each of `columns` from
+/// the text of its field. Empty fields are decoded as `NULL`.
+fn pack_typed_columns<'a>(
+    row: &mut Row,
+    columns: &[CsvColumn],
+    field: impl Fn(usize) -> &'a str,
+) -> Result<(), String> {
+    let arena = RowArena::new();
+    let mut packer = row.packer();
+    for column in columns {
+        let text = field(column.index);
+        if text.is_empty() {
+            if !column.nullable {
+                return Err(format!("column {} is empty, but is NOT NULL", column.name));
+            }
+            packer.push(Datum::Null);
+            continue;
+        }
+        let value = mz_pgrepr::Value::decode_text(&column.typ, text.as_bytes()).map_err(|e| {
+            format!(
+                "failed to decode column {} as {}: {}",
+                column.name, column.typ, e
+            )
+        })?;
+        packer.push(value.into_datum(&arena, &column.typ));
+    }
+    Ok(())
+}
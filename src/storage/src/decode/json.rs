@@ -0,0 +1,139 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use itertools::Itertools;
+use mz_pgrepr::Type;
+use mz_repr::adt::jsonb::{Jsonb, JsonbPacker};
+use mz_repr::{Datum, Row, RowArena};
+use serde_json::Value;
+
+use crate::types::errors::DecodeErrorKind;
+use crate::types::sources::encoding::JsonEncoding;
+
+#[derive(Debug)]
+struct JsonColumn {
+    name: String,
+    typ: Type,
+    nullable: bool,
+}
+
+#[derive(Debug)]
+pub struct JsonDecoderState {
+    /// The columns to decode the fields of each object into, or `None` to
+    /// decode each message into a single `jsonb` datum.
+    columns: Option<Vec<JsonColumn>>,
+    reject_unknown_fields: bool,
+    row_buf: Row,
+}
+
+impl JsonDecoderState {
+    pub fn new(
+        JsonEncoding {
+            columns,
+            reject_unknown_fields,
+        }: JsonEncoding,
+    ) -> Self {
+        let columns = columns.map(|desc| {
+            desc.iter()
+                .map(|(name, typ)| JsonColumn {
+                    name: name.as_str().to_string(),
+                    typ: Type::from(&typ.scalar_type),
+                    nullable: typ.nullable,
+                })
+                .collect()
+        });
+        JsonDecoderState {
+            columns,
+            reject_unknown_fields,
+            row_buf: Row::default(),
+        }
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => {
+                let jsonb = Jsonb::from_slice(bytes)
+                    .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?;
+                self.row_buf.packer().push(jsonb.as_ref().into_datum());
+                return Ok(Some(self.row_buf.clone()));
+            }
+        };
+
+        let mut object = match serde_json::from_slice(bytes) {
+            Ok(Value::Object(object)) => object,
+            Ok(_) => return Err(DecodeErrorKind::Text("Expected a JSON object".to_string())),
+            Err(e) => {
+                return Err(DecodeErrorKind::Text(format!(
+                    "Failed to decode JSON: {}",
+                    e
+                )))
+            }
+        };
+
+        let arena = RowArena::new();
+        let mut packer = self.row_buf.packer();
+        for column in columns {
+            match object.remove(&column.name) {
+                // Missing fields and explicit nulls are both decoded as
+                // `NULL`, which is only an error for `NOT NULL` columns.
+                None | Some(Value::Null) => {
+                    if !column.nullable {
+                        return Err(DecodeErrorKind::Text(format!(
+                            "Field {} is missing or null, but its column is NOT NULL",
+                            column.name
+                        )));
+                    }
+                    packer.push(Datum::Null);
+                }
+                Some(value) if column.typ == Type::Jsonb => {
+                    JsonbPacker::new(&mut packer)
+                        .pack_serde_json(value)
+                        .map_err(|e| {
+                            DecodeErrorKind::Text(format!(
+                                "Failed to decode field {}: {}",
+                                column.name, e
+                            ))
+                        })?;
+                }
+                Some(value) => {
+                    // Other types are decoded from the text of the JSON
+                    // scalar, as if they had been cast from `text`.
+                    let text = match value {
+                        Value::String(s) => s,
+                        Value::Number(n) => n.to_string(),
+                        Value::Bool(b) => b.to_string(),
+                        Value::Array(_) | Value::Object(_) | Value::Null => {
+                            return Err(DecodeErrorKind::Text(format!(
+                                "Field {} must be a JSON scalar to be decoded as {}",
+                                column.name, column.typ
+                            )))
+                        }
+                    };
+                    let value = mz_pgrepr::Value::decode_text(&column.typ, text.as_bytes())
+                        .map_err(|e| {
+                            DecodeErrorKind::Text(format!(
+                                "Failed to decode field {} as {}: {}",
+                                column.name, column.typ, e
+                            ))
+                        })?;
+                    packer.push(value.into_datum(&arena, &column.typ));
+                }
+            }
+        }
+
+        if self.reject_unknown_fields && !object.is_empty() {
+            return Err(DecodeErrorKind::Text(format!(
+                "Unknown fields: {}",
+                object.keys().join(", ")
+            )));
+        }
+        Ok(Some(self.row_buf.clone()))
+    }
+}
@@ -9,9 +9,13 @@
 
 //! Utilities for tracking metrics related to decoding.
 
+use std::sync::Arc;
+
+use mz_ccsr::ClientCache;
 use mz_ore::metric;
 use mz_ore::metrics::raw::IntCounterVec;
 use mz_ore::metrics::MetricsRegistry;
+use prometheus::core::AtomicU64;
 
 use crate::decode::{DataDecoderInner, PreDelimitedFormat};
 
@@ -43,6 +47,8 @@ impl DecodeMetrics {
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
+                PreDelimitedFormat::Json(..) => "json",
+                PreDelimitedFormat::Parquet => "parquet",
             },
         };
         let success_label = if success { "success" } else { "error" };
@@ -59,3 +65,24 @@ impl DecodeMetrics {
         self.counter_inc(decoder, true, n);
     }
 }
+
+/// Registers metrics that report how many schema registry lookups `cache`
+/// has answered.
+pub fn register_csr_cache_metrics(registry: &MetricsRegistry, cache: &Arc<ClientCache>) {
+    let hits_cache = Arc::clone(cache);
+    registry.register_computed_gauge::<_, AtomicU64>(
+        metric!(
+            name: "mz_csr_cache_hits",
+            help: "The number of schema registry lookups answered from the cache.",
+        ),
+        move || hits_cache.stats().hits,
+    );
+    let misses_cache = Arc::clone(cache);
+    registry.register_computed_gauge::<_, AtomicU64>(
+        metric!(
+            name: "mz_csr_cache_misses",
+            help: "The number of schema registry lookups sent to the schema registry.",
+        ),
+        move || misses_cache.stats().misses,
+    );
+}
@@ -16,9 +16,10 @@
 use std::{
     any::Any,
     cell::RefCell,
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     marker::{Send, Sync},
     rc::Rc,
+    sync::Arc,
     time::Duration,
 };
 
@@ -29,6 +30,7 @@ use differential_dataflow::Hashable;
 use differential_dataflow::{AsCollection, Collection};
 use mz_avro::{AvroDeserializer, GeneralDeserializer};
 use mz_expr::PartitionId;
+use mz_persist_types::Codec;
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::Operator;
 use timely::dataflow::{Scope, Stream};
@@ -36,20 +38,23 @@ use timely::scheduling::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
 
 use mz_interchange::avro::ConfluentAvroResolver;
+use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::{adt::timestamp::CheckedTimestamp, Datum};
-use mz_repr::{Diff, Row, Timestamp};
+use mz_repr::{Diff, GlobalId, Row, RowPacker, Timestamp};
 use tracing::error;
 
 use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
+use self::json::JsonDecoderState;
 use self::metrics::DecodeMetrics;
 use self::protobuf::ProtobufDecoderState;
 use crate::types::connections::ConnectionContext;
 use crate::types::errors::DecodeError;
 use crate::types::sources::encoding::{
-    AvroEncoding, DataEncoding, DataEncodingInner, RegexEncoding,
+    AvroEncoding, DataEncoding, DataEncodingInner, HeaderEncoding, NumericOverflowPolicy,
+    RegexEncoding,
 };
-use crate::types::sources::{IncludedColumnSource, MzOffset};
+use crate::types::sources::{FileLineKey, IncludedColumnSource, MzOffset};
 use crate::{
     source::types::{DecodeResult, SourceOutput},
     types::errors::DecodeErrorKind,
@@ -57,6 +62,7 @@ use crate::{
 
 mod avro;
 mod csv;
+mod json;
 pub mod metrics;
 mod protobuf;
 
@@ -144,6 +150,10 @@ pub(crate) enum PreDelimitedFormat {
     Text,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
+    Json(JsonDecoderState),
+    /// Rows that the source already decoded from Parquet objects, encoded
+    /// with [`Row`]'s [`Codec`] implementation.
+    Parquet,
 }
 
 impl PreDelimitedFormat {
@@ -171,6 +181,10 @@ impl PreDelimitedFormat {
                 Ok(Some(row_buf.clone()))
             }
             PreDelimitedFormat::Protobuf(pb) => pb.get_value(bytes).transpose(),
+            PreDelimitedFormat::Json(json) => json.decode(bytes),
+            PreDelimitedFormat::Parquet => Row::decode(bytes)
+                .map(Some)
+                .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode row: {}", e))),
         }
     }
 }
@@ -248,6 +262,25 @@ impl DataDecoder {
     pub fn log_successes(&self, n: usize) {
         self.metrics.count_successes(&self.inner, n);
     }
+
+    /// Adds the number of values to which the overflow policy of the decoder
+    /// has been applied since the last call.
+    pub fn record_overflows(
+        &mut self,
+        source_id: GlobalId,
+        overflows: &RefCell<HashMap<(GlobalId, NumericOverflowPolicy), i64>>,
+    ) {
+        if let DataDecoderInner::Avro(avro) = &mut self.inner {
+            let (policy, count) = avro.take_overflows();
+            if count > 0 {
+                let count = i64::try_from(count).unwrap_or(i64::MAX);
+                *overflows
+                    .borrow_mut()
+                    .entry((source_id, policy))
+                    .or_insert(0) += count;
+            }
+        }
+    }
 }
 
 fn get_decoder(
@@ -265,13 +298,15 @@ fn get_decoder(
             schema,
             csr_connection,
             confluent_wire_format,
+            numeric_overflow,
         }) => {
             let csr_client = match csr_connection {
                 None => None,
                 Some(csr_connection) => Some(
                     TokioHandle::current()
                         .block_on(csr_connection.connect(&*connection_context.secrets_reader))
-                        .expect("CSR connection unexpectedly missing secrets"),
+                        .expect("CSR connection unexpectedly missing secrets")
+                        .with_cache(Arc::clone(&connection_context.csr_cache)),
                 ),
             };
             let state = avro::AvroDecoderState::new(
@@ -279,6 +314,7 @@ fn get_decoder(
                 csr_client,
                 debug_name.to_string(),
                 confluent_wire_format,
+                numeric_overflow,
             )
             .expect("Failed to create avro decoder, even though we validated ccsr client creation in purification.");
             DataDecoder {
@@ -289,16 +325,20 @@ fn get_decoder(
         DataEncodingInner::Text
         | DataEncodingInner::Bytes
         | DataEncodingInner::Protobuf(_)
-        | DataEncodingInner::Regex(_) => {
+        | DataEncodingInner::Regex(_)
+        | DataEncodingInner::Json(_) => {
             let after_delimiting = match encoding.inner {
                 DataEncodingInner::Regex(RegexEncoding { regex }) => {
                     PreDelimitedFormat::Regex(regex.0, Default::default())
                 }
-                DataEncodingInner::Protobuf(encoding) => {
-                    PreDelimitedFormat::Protobuf(ProtobufDecoderState::new(encoding).expect(
+                DataEncodingInner::Protobuf(encoding) => PreDelimitedFormat::Protobuf(
+                    ProtobufDecoderState::new(encoding, debug_name.to_string()).expect(
                         "Failed to create protobuf decoder, even though we validated ccsr \
                                     client creation in purification.",
-                    ))
+                    ),
+                ),
+                DataEncodingInner::Json(encoding) => {
+                    PreDelimitedFormat::Json(JsonDecoderState::new(encoding))
                 }
                 DataEncodingInner::Bytes => PreDelimitedFormat::Bytes,
                 DataEncodingInner::Text => PreDelimitedFormat::Text,
@@ -321,12 +361,85 @@ fn get_decoder(
                 metrics,
             }
         }
+        // The source reads Parquet objects itself, and emits one message
+        // per row whether or not the connection is otherwise delimited.
+        DataEncodingInner::Parquet(_) => DataDecoder {
+            inner: DataDecoderInner::PreDelimited(PreDelimitedFormat::Parquet),
+            metrics,
+        },
         DataEncodingInner::RowCodec(_) => {
             unreachable!("RowCodec sources should not go through the general decoding path.")
         }
     }
 }
 
+/// Decodes the value of a single Kafka header requested via `INCLUDE HEADER`.
+#[derive(Debug)]
+enum HeaderDecoder {
+    Bytes,
+    Text,
+    Json,
+    Avro(DataDecoder),
+}
+
+impl HeaderDecoder {
+    fn new(
+        encoding: HeaderEncoding,
+        debug_name: &str,
+        metrics: DecodeMetrics,
+        connection_context: &ConnectionContext,
+    ) -> HeaderDecoder {
+        match encoding {
+            HeaderEncoding::Bytes => HeaderDecoder::Bytes,
+            HeaderEncoding::Text => HeaderDecoder::Text,
+            HeaderEncoding::Json => HeaderDecoder::Json,
+            HeaderEncoding::Avro(avro) => HeaderDecoder::Avro(get_decoder(
+                DataEncoding::new(DataEncodingInner::Avro(avro)),
+                debug_name,
+                true,
+                metrics,
+                connection_context,
+            )),
+        }
+    }
+
+    /// Decodes `value` into a single datum, packing Avro values that decode
+    /// to more than one column into a record.
+    fn decode(&mut self, value: &[u8], packer: &mut RowPacker) -> Result<(), DecodeErrorKind> {
+        match self {
+            HeaderDecoder::Bytes => packer.push(Datum::Bytes(value)),
+            HeaderDecoder::Text => {
+                let s = std::str::from_utf8(value)
+                    .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
+                packer.push(Datum::String(s));
+            }
+            HeaderDecoder::Json => {
+                let jsonb = Jsonb::from_slice(value)
+                    .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?;
+                packer.push(jsonb.as_ref().into_datum());
+            }
+            HeaderDecoder::Avro(decoder) => {
+                let bytes = &mut &value[..];
+                let row = decoder.next(bytes)?.ok_or_else(|| {
+                    DecodeErrorKind::Text("Header did not contain an Avro value".to_string())
+                })?;
+                if !bytes.is_empty() {
+                    return Err(DecodeErrorKind::Text(format!(
+                        "Unexpected bytes remaining for decoded header: {:?}",
+                        bytes
+                    )));
+                }
+                let mut datums = row.iter();
+                match (datums.next(), datums.next()) {
+                    (Some(datum), None) => packer.push(datum),
+                    _ => packer.push_list_with(|packer| packer.extend(row.iter())),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 fn try_decode_delimited(
     decoder: &mut DataDecoder,
     value: Option<&Vec<u8>>,
@@ -365,6 +478,8 @@ pub fn render_decode_delimited<G>(
     metadata_items: Vec<IncludedColumnSource>,
     metrics: DecodeMetrics,
     connection_context: &ConnectionContext,
+    source_id: GlobalId,
+    overflows: Rc<RefCell<HashMap<(GlobalId, NumericOverflowPolicy), i64>>>,
 ) -> (Stream<G, DecodeResult>, Option<Box<dyn Any + Send + Sync>>)
 where
     G: Scope,
@@ -387,6 +502,19 @@ where
         )
     });
 
+    let mut header_decoders: Vec<_> = metadata_items
+        .iter()
+        .filter_map(|item| match item {
+            IncludedColumnSource::Header { encoding, .. } => Some(HeaderDecoder::new(
+                encoding.clone(),
+                debug_name,
+                metrics.clone(),
+                connection_context,
+            )),
+            _ => None,
+        })
+        .collect();
+
     let mut value_decoder = get_decoder(
         value_encoding,
         debug_name,
@@ -413,6 +541,16 @@ where
                     diff: (),
                 } in data.iter()
                 {
+                    let metadata = to_metadata_row(
+                        &metadata_items,
+                        &mut header_decoders,
+                        partition.clone(),
+                        *position,
+                        *upstream_time_millis,
+                        headers.as_deref(),
+                        key.as_deref(),
+                    );
+
                     let key = key_decoder.as_mut().and_then(|decoder| {
                         try_decode_delimited(decoder, key.as_ref()).map(|result| {
                             result.map_err(|inner| DecodeError {
@@ -430,6 +568,12 @@ where
                             })
                         });
 
+                    // A header that fails to decode makes the whole record an error.
+                    let (metadata, value) = match metadata {
+                        Ok(metadata) => (metadata, value),
+                        Err(err) => (Row::default(), Some(Err(err))),
+                    };
+
                     if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
                         n_errors += 1;
                     } else if matches!(&value, Some(Ok(_))) {
@@ -442,13 +586,7 @@ where
                         position: *position,
                         upstream_time_millis: *upstream_time_millis,
                         partition: partition.clone(),
-                        metadata: to_metadata_row(
-                            &metadata_items,
-                            partition.clone(),
-                            *position,
-                            *upstream_time_millis,
-                            headers.as_deref(),
-                        ),
+                        metadata,
                     });
                 }
             });
@@ -459,6 +597,10 @@ where
             if n_successes > 0 {
                 value_decoder.log_successes(n_successes);
             }
+            if let Some(key_decoder) = &mut key_decoder {
+                key_decoder.record_overflows(source_id, &overflows);
+            }
+            value_decoder.record_overflows(source_id, &overflows);
         }
     });
     (results, None)
@@ -486,6 +628,8 @@ pub fn render_decode<G>(
     metadata_items: Vec<IncludedColumnSource>,
     metrics: DecodeMetrics,
     connection_context: &ConnectionContext,
+    source_id: GlobalId,
+    overflows: Rc<RefCell<HashMap<(GlobalId, NumericOverflowPolicy), i64>>>,
 ) -> (Stream<G, DecodeResult>, Option<Box<dyn Any + Send + Sync>>)
 where
     G: Scope<Timestamp = Timestamp>,
@@ -550,11 +694,14 @@ where
                                     let position = n_seen.next().unwrap();
                                     let metadata = to_metadata_row(
                                         &metadata_items,
+                                        &mut [],
                                         partition.clone(),
                                         position.into(),
                                         *upstream_time_millis,
                                         headers.as_deref(),
-                                    );
+                                        None,
+                                    )
+                                    .expect("only delimited sources decode headers");
 
                                     session.give(DecodeResult {
                                         key: None,
@@ -616,11 +763,14 @@ where
                         let position = n_seen.next().unwrap();
                         let metadata = to_metadata_row(
                             &metadata_items,
+                            &mut [],
                             partition.clone(),
                             position.into(),
                             *upstream_time_millis,
                             headers.as_deref(),
-                        );
+                            None,
+                        )
+                        .expect("only delimited sources decode headers");
 
                         if value_bytes_remaining.is_empty() {
                             session.give(DecodeResult {
@@ -664,6 +814,7 @@ where
             if n_successes > 0 {
                 value_decoder.log_successes(n_errors);
             }
+            value_decoder.record_overflows(source_id, &overflows);
         }
     });
     (results, None)
@@ -671,16 +822,24 @@ where
 
 fn to_metadata_row(
     metadata_items: &[IncludedColumnSource],
+    header_decoders: &mut [HeaderDecoder],
     partition: PartitionId,
     position: MzOffset,
     upstream_time_millis: Option<i64>,
     headers: Option<&[(String, Option<Vec<u8>>)]>,
-) -> Row {
+    key: Option<&[u8]>,
+) -> Result<Row, DecodeError> {
     let position = position.offset;
+    let mut header_decoders = header_decoders.iter_mut();
     let mut row = Row::default();
     let mut packer = row.packer();
     match partition {
-        PartitionId::Kafka(partition) => {
+        PartitionId::Kafka(_) | PartitionId::KafkaTopic(..) => {
+            let (topic, partition) = match &partition {
+                PartitionId::Kafka(partition) => (None, *partition),
+                PartitionId::KafkaTopic(topic, partition) => (Some(topic.as_str()), *partition),
+                _ => unreachable!(),
+            };
             for item in metadata_items.iter() {
                 match item {
                     IncludedColumnSource::Partition => packer.push(Datum::from(partition)),
@@ -702,7 +861,12 @@ fn to_metadata_row(
                             .into();
                         packer.push(d)
                     }
-                    IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
+                    IncludedColumnSource::Topic => packer.push(Datum::String(
+                        topic.expect("only sources that read several topics include topics"),
+                    )),
+                    IncludedColumnSource::Filename => {
+                        unreachable!("Kafka sources do not include file names")
+                    }
                     IncludedColumnSource::Headers => {
                         packer.push_list_with(|r| {
                             // If the source asked for headers, but we didn't get any, we still
@@ -726,14 +890,60 @@ fn to_metadata_row(
                             }
                         });
                     }
+                    IncludedColumnSource::Header { key, .. } => {
+                        let decoder = header_decoders
+                            .next()
+                            .expect("one decoder per included header");
+                        // Kafka permits repeated header keys; the last one wins.
+                        let value = headers
+                            .and_then(|headers| headers.iter().rev().find(|(k, _)| k == key))
+                            .and_then(|(_, v)| v.as_ref());
+                        match value {
+                            Some(value) => {
+                                decoder
+                                    .decode(value, &mut packer)
+                                    .map_err(|kind| DecodeError {
+                                        kind,
+                                        raw: Some(value.clone()),
+                                    })?
+                            }
+                            None => packer.push(Datum::Null),
+                        }
+                    }
                 }
             }
         }
-        PartitionId::None => {
-            if !metadata_items.is_empty() {
-                unreachable!("Only Kafka supports metadata items");
+        PartitionId::Kinesis(_)
+        | PartitionId::Nats(_)
+        | PartitionId::PostgresSnapshot(_)
+        | PartitionId::MySqlServer(_)
+        | PartitionId::None => {
+            for item in metadata_items.iter() {
+                match item {
+                    // Sources that include topics, like MQTT sources, use the
+                    // topic of each message as its key.
+                    IncludedColumnSource::Topic => {
+                        let topic = key
+                            .and_then(|key| std::str::from_utf8(key).ok())
+                            .expect("sources that include topics key messages by topic");
+                        packer.push(Datum::String(topic))
+                    }
+                    // File sources encode the file and offset of each line
+                    // in its key.
+                    IncludedColumnSource::Filename => {
+                        let key = key.expect("file sources key lines by file and offset");
+                        packer.push(Datum::String(FileLineKey::decode(key).filename))
+                    }
+                    IncludedColumnSource::Offset => {
+                        let key = key.expect("file sources key lines by file and offset");
+                        packer.push(Datum::UInt64(FileLineKey::decode(key).offset))
+                    }
+                    _ => unreachable!(
+                        "Only Kafka supports metadata items other than TOPIC, FILENAME, and OFFSET"
+                    ),
+                }
             }
         }
     }
-    row
+    Ok(row)
 }
@@ -7,6 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeSet;
+
+use tracing::info;
+
 use mz_interchange::protobuf::{DecodedDescriptors, Decoder};
 use mz_repr::Row;
 
@@ -16,6 +20,11 @@ use crate::types::sources::encoding::ProtobufEncoding;
 #[derive(Debug)]
 pub struct ProtobufDecoderState {
     decoder: Decoder,
+    debug_name: String,
+    /// The IDs of the schemas with which the decoded messages were written,
+    /// if they are in the Confluent wire format. Each newly seen ID is logged,
+    /// which shows when producers switch to an evolved schema.
+    schema_ids: BTreeSet<i32>,
     events_success: i64,
     events_error: i64,
 }
@@ -27,17 +36,29 @@ impl ProtobufDecoderState {
             message_name,
             confluent_wire_format,
         }: ProtobufEncoding,
+        debug_name: String,
     ) -> Result<Self, anyhow::Error> {
         let descriptors = DecodedDescriptors::from_bytes(&descriptors, message_name)
             .expect("descriptors provided to protobuf source are pre-validated");
         Ok(ProtobufDecoderState {
             decoder: Decoder::new(descriptors, confluent_wire_format)?,
+            debug_name,
+            schema_ids: BTreeSet::new(),
             events_success: 0,
             events_error: 0,
         })
     }
     pub fn get_value(&mut self, bytes: &[u8]) -> Option<Result<Row, DecodeErrorKind>> {
-        match self.decoder.decode(bytes) {
+        let result = self.decoder.decode(bytes);
+        if let Some(schema_id) = self.decoder.schema_id() {
+            if self.schema_ids.insert(schema_id) {
+                info!(
+                    "{}: decoding Protobuf messages written with schema id {}",
+                    self.debug_name, schema_id
+                );
+            }
+        }
+        match result {
             Ok(row) => {
                 if let Some(row) = row {
                     self.events_success += 1;
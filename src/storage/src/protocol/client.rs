@@ -23,6 +23,7 @@ use differential_dataflow::lattice::Lattice;
 use proptest::prelude::{any, Arbitrary};
 use proptest::prop_oneof;
 use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::{Antichain, MutableAntichain};
 use timely::PartialOrder;
@@ -269,6 +270,8 @@ impl Arbitrary for StorageCommand<mz_repr::Timestamp> {
                     .map(|(id, frontier_vec)| { (id, Antichain::from(frontier_vec)) })
                     .collect()
             )),
+            proptest::collection::vec(any::<(GlobalId, SinkStatusUpdate)>(), 1..4)
+                .prop_map(StorageResponse::SinkStatusUpdates),
         ]
         .boxed()
     }
@@ -282,6 +285,97 @@ pub enum StorageResponse<T = mz_repr::Timestamp> {
     /// TODO(teskje): Consider also reporting the previous upper frontier and using that
     /// information to assert the correct implementation of our protocols at various places.
     FrontierUppers(Vec<(GlobalId, Antichain<T>)>),
+    /// Counts of the messages read by sources since the previous report, as
+    /// (source id, size bucket in bytes, count) triples.
+    ///
+    /// Each size bucket is the smallest power of two that is at least the
+    /// size of the messages it counts.
+    SourceMessageSizes(Vec<(GlobalId, u64, i64)>),
+    /// Counts of the attempts of sinks to publish records to their progress
+    /// topics since the previous report, as (sink id, duration bucket in
+    /// nanoseconds, failed, count) tuples.
+    ///
+    /// The duration of an attempt is measured from the time the progress
+    /// record was enqueued, and each duration bucket is the smallest power of
+    /// two that is at least the durations it counts.
+    SinkProgressPublishes(Vec<(GlobalId, u64, bool, i64)>),
+    /// Counts of the messages that sinks published to their dead letter
+    /// topics since the previous report, as (sink id, error, count) triples.
+    SinkDeadLetters(Vec<(GlobalId, String, i64)>),
+    /// Counts of the values to which sources applied their numeric overflow
+    /// policy since the previous report, as (source id, policy, count)
+    /// triples.
+    SourceNumericOverflows(Vec<(GlobalId, String, i64)>),
+    /// The metrics of sinks since the previous report.
+    SinkMetrics(Vec<(GlobalId, SinkMetricsUpdate)>),
+    /// The transitions of sinks between statuses since the previous report,
+    /// in the order in which they occurred.
+    SinkStatusUpdates(Vec<(GlobalId, SinkStatusUpdate)>),
+}
+
+/// The metrics of a sink since the previous report.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SinkMetricsUpdate {
+    /// The number of messages the sink emitted since the previous report.
+    pub messages: u64,
+    /// The number of bytes the sink emitted since the previous report.
+    pub bytes: u64,
+    /// The frontier up to which the sink has committed its output.
+    pub committed_frontier: Antichain<mz_repr::Timestamp>,
+    /// The frontier of the sink's input.
+    pub input_frontier: Antichain<mz_repr::Timestamp>,
+}
+
+/// The status of a sink, as recorded in the sink status history.
+#[derive(Arbitrary, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SinkStatus {
+    /// The sink is writing to its external system.
+    Running,
+    /// The sink is retrying a failure that might resolve on its own.
+    Stalled,
+    /// The sink gave up after a failure, and no longer writes anything.
+    Failed,
+    /// The sink was dropped.
+    Dropped,
+}
+
+impl SinkStatus {
+    /// Returns the name of the status, as recorded in the sink status
+    /// history.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SinkStatus::Running => "running",
+            SinkStatus::Stalled => "stalled",
+            SinkStatus::Failed => "failed",
+            SinkStatus::Dropped => "dropped",
+        }
+    }
+}
+
+impl TryFrom<&str> for SinkStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "running" => Ok(SinkStatus::Running),
+            "stalled" => Ok(SinkStatus::Stalled),
+            "failed" => Ok(SinkStatus::Failed),
+            "dropped" => Ok(SinkStatus::Dropped),
+            _ => Err(format!("{value} is not a valid SinkStatus")),
+        }
+    }
+}
+
+/// A transition of a sink to a new status.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SinkStatusUpdate {
+    /// When the transition occurred, in milliseconds since the Unix epoch.
+    pub occurred_at: u64,
+    pub status: SinkStatus,
+    /// The error that caused the transition, if any.
+    pub error: Option<String>,
+    /// Further details of the transition, as a JSON object, if any.
+    pub details: Option<String>,
 }
 
 impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
@@ -290,6 +384,22 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
         ProtoStorageResponse {
             kind: Some(match self {
                 StorageResponse::FrontierUppers(traces) => FrontierUppers(traces.into_proto()),
+                StorageResponse::SourceMessageSizes(sizes) => {
+                    SourceMessageSizes(sizes.into_proto())
+                }
+                StorageResponse::SinkProgressPublishes(publishes) => {
+                    SinkProgressPublishes(publishes.into_proto())
+                }
+                StorageResponse::SinkDeadLetters(dead_letters) => {
+                    SinkDeadLetters(dead_letters.into_proto())
+                }
+                StorageResponse::SourceNumericOverflows(overflows) => {
+                    SourceNumericOverflows(overflows.into_proto())
+                }
+                StorageResponse::SinkMetrics(metrics) => SinkMetrics(metrics.into_proto()),
+                StorageResponse::SinkStatusUpdates(updates) => {
+                    SinkStatusUpdates(updates.into_proto())
+                }
             }),
         }
     }
@@ -300,6 +410,22 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
             Some(FrontierUppers(traces)) => {
                 Ok(StorageResponse::FrontierUppers(traces.into_rust()?))
             }
+            Some(SourceMessageSizes(sizes)) => {
+                Ok(StorageResponse::SourceMessageSizes(sizes.into_rust()?))
+            }
+            Some(SinkProgressPublishes(publishes)) => Ok(StorageResponse::SinkProgressPublishes(
+                publishes.into_rust()?,
+            )),
+            Some(SinkDeadLetters(dead_letters)) => {
+                Ok(StorageResponse::SinkDeadLetters(dead_letters.into_rust()?))
+            }
+            Some(SourceNumericOverflows(overflows)) => Ok(StorageResponse::SourceNumericOverflows(
+                overflows.into_rust()?,
+            )),
+            Some(SinkMetrics(metrics)) => Ok(StorageResponse::SinkMetrics(metrics.into_rust()?)),
+            Some(SinkStatusUpdates(updates)) => {
+                Ok(StorageResponse::SinkStatusUpdates(updates.into_rust()?))
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStorageResponse::kind",
             )),
@@ -315,6 +441,43 @@ impl Arbitrary for StorageResponse<mz_repr::Timestamp> {
         prop_oneof![
             proptest::collection::vec((any::<GlobalId>(), any_antichain()), 1..4)
                 .prop_map(StorageResponse::FrontierUppers),
+            proptest::collection::vec((any::<GlobalId>(), any::<u64>(), any::<i64>()), 1..4)
+                .prop_map(StorageResponse::SourceMessageSizes),
+            proptest::collection::vec(
+                (any::<GlobalId>(), any::<u64>(), any::<bool>(), any::<i64>()),
+                1..4
+            )
+            .prop_map(StorageResponse::SinkProgressPublishes),
+            proptest::collection::vec((any::<GlobalId>(), any::<String>(), any::<i64>()), 1..4)
+                .prop_map(StorageResponse::SinkDeadLetters),
+            proptest::collection::vec((any::<GlobalId>(), any::<String>(), any::<i64>()), 1..4)
+                .prop_map(StorageResponse::SourceNumericOverflows),
+            proptest::collection::vec(
+                (
+                    any::<GlobalId>(),
+                    any::<u64>(),
+                    any::<u64>(),
+                    any_antichain(),
+                    any_antichain()
+                ),
+                1..4
+            )
+            .prop_map(|metrics| StorageResponse::SinkMetrics(
+                metrics
+                    .into_iter()
+                    .map(
+                        |(id, messages, bytes, committed_frontier, input_frontier)| (
+                            id,
+                            SinkMetricsUpdate {
+                                messages,
+                                bytes,
+                                committed_frontier,
+                                input_frontier,
+                            }
+                        )
+                    )
+                    .collect()
+            )),
         ]
         .boxed()
     }
@@ -422,6 +585,28 @@ where
                     Some(Ok(StorageResponse::FrontierUppers(new_uppers)))
                 }
             }
+            // Counts from each worker are deltas, so they can be passed along
+            // as they are.
+            StorageResponse::SourceMessageSizes(sizes) => {
+                Some(Ok(StorageResponse::SourceMessageSizes(sizes)))
+            }
+            StorageResponse::SinkProgressPublishes(publishes) => {
+                Some(Ok(StorageResponse::SinkProgressPublishes(publishes)))
+            }
+            StorageResponse::SinkDeadLetters(dead_letters) => {
+                Some(Ok(StorageResponse::SinkDeadLetters(dead_letters)))
+            }
+            StorageResponse::SourceNumericOverflows(overflows) => {
+                Some(Ok(StorageResponse::SourceNumericOverflows(overflows)))
+            }
+            // Only the worker that writes to a sink reports its metrics.
+            StorageResponse::SinkMetrics(metrics) => {
+                Some(Ok(StorageResponse::SinkMetrics(metrics)))
+            }
+            // Likewise for its transitions between statuses.
+            StorageResponse::SinkStatusUpdates(updates) => {
+                Some(Ok(StorageResponse::SinkStatusUpdates(updates)))
+            }
         }
     }
 }
@@ -470,6 +655,207 @@ impl RustType<ProtoFrontierUppersKind> for Vec<(GlobalId, Antichain<mz_repr::Tim
     }
 }
 
+impl RustType<ProtoSourceMessageSize> for (GlobalId, u64, i64) {
+    fn into_proto(&self) -> ProtoSourceMessageSize {
+        ProtoSourceMessageSize {
+            id: Some(self.0.into_proto()),
+            size_bytes: self.1,
+            count: self.2,
+        }
+    }
+
+    fn from_proto(proto: ProtoSourceMessageSize) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto.id.into_rust_if_some("ProtoSourceMessageSize::id")?,
+            proto.size_bytes,
+            proto.count,
+        ))
+    }
+}
+
+impl RustType<ProtoSourceMessageSizesKind> for Vec<(GlobalId, u64, i64)> {
+    fn into_proto(&self) -> ProtoSourceMessageSizesKind {
+        ProtoSourceMessageSizesKind {
+            sizes: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSourceMessageSizesKind) -> Result<Self, TryFromProtoError> {
+        proto.sizes.into_rust()
+    }
+}
+
+impl RustType<ProtoSinkProgressPublish> for (GlobalId, u64, bool, i64) {
+    fn into_proto(&self) -> ProtoSinkProgressPublish {
+        ProtoSinkProgressPublish {
+            id: Some(self.0.into_proto()),
+            duration_ns: self.1,
+            failed: self.2,
+            count: self.3,
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkProgressPublish) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto.id.into_rust_if_some("ProtoSinkProgressPublish::id")?,
+            proto.duration_ns,
+            proto.failed,
+            proto.count,
+        ))
+    }
+}
+
+impl RustType<ProtoSinkProgressPublishesKind> for Vec<(GlobalId, u64, bool, i64)> {
+    fn into_proto(&self) -> ProtoSinkProgressPublishesKind {
+        ProtoSinkProgressPublishesKind {
+            publishes: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkProgressPublishesKind) -> Result<Self, TryFromProtoError> {
+        proto.publishes.into_rust()
+    }
+}
+
+impl RustType<ProtoSinkDeadLetter> for (GlobalId, String, i64) {
+    fn into_proto(&self) -> ProtoSinkDeadLetter {
+        ProtoSinkDeadLetter {
+            id: Some(self.0.into_proto()),
+            error: self.1.clone(),
+            count: self.2,
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkDeadLetter) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto.id.into_rust_if_some("ProtoSinkDeadLetter::id")?,
+            proto.error,
+            proto.count,
+        ))
+    }
+}
+
+impl RustType<ProtoSinkDeadLettersKind> for Vec<(GlobalId, String, i64)> {
+    fn into_proto(&self) -> ProtoSinkDeadLettersKind {
+        ProtoSinkDeadLettersKind {
+            dead_letters: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkDeadLettersKind) -> Result<Self, TryFromProtoError> {
+        proto.dead_letters.into_rust()
+    }
+}
+
+impl RustType<ProtoSourceNumericOverflow> for (GlobalId, String, i64) {
+    fn into_proto(&self) -> ProtoSourceNumericOverflow {
+        ProtoSourceNumericOverflow {
+            id: Some(self.0.into_proto()),
+            policy: self.1.clone(),
+            count: self.2,
+        }
+    }
+
+    fn from_proto(proto: ProtoSourceNumericOverflow) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto
+                .id
+                .into_rust_if_some("ProtoSourceNumericOverflow::id")?,
+            proto.policy,
+            proto.count,
+        ))
+    }
+}
+
+impl RustType<ProtoSourceNumericOverflowsKind> for Vec<(GlobalId, String, i64)> {
+    fn into_proto(&self) -> ProtoSourceNumericOverflowsKind {
+        ProtoSourceNumericOverflowsKind {
+            overflows: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSourceNumericOverflowsKind) -> Result<Self, TryFromProtoError> {
+        proto.overflows.into_rust()
+    }
+}
+
+impl RustType<ProtoSinkMetrics> for (GlobalId, SinkMetricsUpdate) {
+    fn into_proto(&self) -> ProtoSinkMetrics {
+        ProtoSinkMetrics {
+            id: Some(self.0.into_proto()),
+            messages: self.1.messages,
+            bytes: self.1.bytes,
+            committed_frontier: Some(self.1.committed_frontier.into_proto()),
+            input_frontier: Some(self.1.input_frontier.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkMetrics) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto.id.into_rust_if_some("ProtoSinkMetrics::id")?,
+            SinkMetricsUpdate {
+                messages: proto.messages,
+                bytes: proto.bytes,
+                committed_frontier: proto
+                    .committed_frontier
+                    .into_rust_if_some("ProtoSinkMetrics::committed_frontier")?,
+                input_frontier: proto
+                    .input_frontier
+                    .into_rust_if_some("ProtoSinkMetrics::input_frontier")?,
+            },
+        ))
+    }
+}
+
+impl RustType<ProtoSinkMetricsKind> for Vec<(GlobalId, SinkMetricsUpdate)> {
+    fn into_proto(&self) -> ProtoSinkMetricsKind {
+        ProtoSinkMetricsKind {
+            metrics: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkMetricsKind) -> Result<Self, TryFromProtoError> {
+        proto.metrics.into_rust()
+    }
+}
+
+impl RustType<ProtoSinkStatusUpdate> for (GlobalId, SinkStatusUpdate) {
+    fn into_proto(&self) -> ProtoSinkStatusUpdate {
+        ProtoSinkStatusUpdate {
+            id: Some(self.0.into_proto()),
+            occurred_at: self.1.occurred_at,
+            status: self.1.status.name().to_string(),
+            error: self.1.error.clone(),
+            details: self.1.details.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkStatusUpdate) -> Result<Self, TryFromProtoError> {
+        Ok((
+            proto.id.into_rust_if_some("ProtoSinkStatusUpdate::id")?,
+            SinkStatusUpdate {
+                occurred_at: proto.occurred_at,
+                status: SinkStatus::try_from(proto.status.as_str())
+                    .map_err(TryFromProtoError::UnknownEnumVariant)?,
+                error: proto.error,
+                details: proto.details,
+            },
+        ))
+    }
+}
+
+impl RustType<ProtoSinkStatusUpdatesKind> for Vec<(GlobalId, SinkStatusUpdate)> {
+    fn into_proto(&self) -> ProtoSinkStatusUpdatesKind {
+        ProtoSinkStatusUpdatesKind {
+            updates: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkStatusUpdatesKind) -> Result<Self, TryFromProtoError> {
+        proto.updates.into_rust()
+    }
+}
+
 impl RustType<ProtoCompaction> for (GlobalId, Antichain<mz_repr::Timestamp>) {
     fn into_proto(&self) -> ProtoCompaction {
         ProtoCompaction {
@@ -11,6 +11,7 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use anyhow::anyhow;
 use mz_build_info::BuildInfo;
@@ -23,10 +24,11 @@ use mz_ore::now::NowFn;
 use mz_persist_client::cache::PersistClientCache;
 use mz_service::local::LocalClient;
 
+use crate::decode::metrics::register_csr_cache_metrics;
 use crate::protocol::client::StorageClient;
 use crate::sink::SinkBaseMetrics;
 use crate::source::metrics::SourceBaseMetrics;
-use crate::storage_state::{StorageState, Worker};
+use crate::storage_state::{StorageState, UpsertStateConfig, Worker};
 use crate::types::connections::ConnectionContext;
 use crate::DecodeMetrics;
 
@@ -44,6 +46,9 @@ pub struct Config {
     pub metrics_registry: MetricsRegistry,
     /// Configuration for source and sink connection.
     pub connection_context: ConnectionContext,
+    /// Configuration for the state of upsert sources with a disk-backed
+    /// `UPSERT STATE`.
+    pub upsert_state_config: UpsertStateConfig,
 }
 
 /// A handle to a running dataflow server.
@@ -63,6 +68,10 @@ pub fn serve(
     let source_metrics = SourceBaseMetrics::register_with(&config.metrics_registry);
     let sink_metrics = SinkBaseMetrics::register_with(&config.metrics_registry);
     let decode_metrics = DecodeMetrics::register_with(&config.metrics_registry);
+    register_csr_cache_metrics(
+        &config.metrics_registry,
+        &config.connection_context.csr_cache,
+    );
     // Bundle metrics to conceal complexity.
     let metrics_bundle = (source_metrics, sink_metrics, decode_metrics);
 
@@ -110,6 +119,14 @@ pub fn serve(
                 persist_clients,
                 sink_tokens: HashMap::new(),
                 sink_write_frontiers: HashMap::new(),
+                source_message_sizes: Default::default(),
+                sink_progress_publishes: Default::default(),
+                sink_dead_letters: Default::default(),
+                source_numeric_overflows: Default::default(),
+                sink_metrics_updates: Default::default(),
+                sink_status_updates: Default::default(),
+                last_introspection_report: Instant::now(),
+                upsert_state_config: config.upsert_state_config.clone(),
             },
         }
         .run()
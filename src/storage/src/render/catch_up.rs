@@ -0,0 +1,251 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Rate limiting of sinks, either while they catch up with their input or
+//! for as long as they run.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use differential_dataflow::{AsCollection, Collection};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::{Capability, Operator};
+use timely::dataflow::Scope;
+use tracing::info;
+
+use mz_ore::cast::CastFrom;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+
+use crate::storage_state::StorageState;
+use crate::types::sinks::SinkRateLimit;
+
+type Update = ((Option<Row>, Option<Row>), Timestamp, Diff);
+
+/// Limits the rate at which the updates of `collection` are emitted until
+/// the sink has caught up with its input.
+///
+/// The sink has caught up once it has emitted all updates at times before
+/// the time at which the operator was rendered. Until then updates are
+/// buffered and released in time order at no more than the rates in
+/// `limit`, which are shared evenly between the workers. Afterwards updates
+/// pass through unthrottled.
+pub(crate) fn render<G>(
+    storage_state: &StorageState,
+    sink_id: GlobalId,
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    limit: SinkRateLimit,
+) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let start = Timestamp::from((storage_state.now)());
+    let worker_id = storage_state.timely_worker_index;
+    let catching_up = storage_state
+        .sink_metrics
+        .catch_up
+        .catching_up
+        .get_delete_on_drop_gauge(vec![sink_id.to_string(), worker_id.to_string()]);
+    catching_up.set(1);
+
+    limit_rate(
+        storage_state,
+        "SinkCatchUp",
+        collection,
+        limit,
+        Some(start),
+        move || {
+            info!(
+                "sink {sink_id} on worker {worker_id} caught up with its input, \
+                 no longer rate limiting"
+            );
+            catching_up.set(0);
+        },
+    )
+}
+
+/// Limits the rate at which the updates of `collection` are emitted for as
+/// long as the sink runs.
+///
+/// Updates are buffered and released in time order at no more than the rates
+/// in `limit`, which are shared evenly between the workers.
+pub(crate) fn render_max_rate<G>(
+    storage_state: &StorageState,
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    limit: SinkRateLimit,
+) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    limit_rate(
+        storage_state,
+        "SinkRateLimit",
+        collection,
+        limit,
+        None,
+        || (),
+    )
+}
+
+/// Releases the updates of `collection` at no more than the rates in `limit`
+/// until the input frontier passes `until`, if given, at which point
+/// `on_caught_up` is called and updates pass through unthrottled.
+fn limit_rate<G, F>(
+    storage_state: &StorageState,
+    name: &str,
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    limit: SinkRateLimit,
+    until: Option<Timestamp>,
+    mut on_caught_up: F,
+) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+    F: FnMut() + 'static,
+{
+    let scope = collection.scope();
+    let peers = u64::cast_from(storage_state.timely_worker_peers);
+
+    let mut bucket = TokenBucket::new(
+        limit.records_per_second.map(|rate| (rate / peers).max(1)),
+        limit.bytes_per_second.map(|rate| (rate / peers).max(1)),
+    );
+
+    collection
+        .inner
+        .unary_frontier(Pipeline, name, move |_cap, info| {
+            let activator = scope.activator_for(&info.address[..]);
+            let mut pending: BTreeMap<Timestamp, (Capability<Timestamp>, VecDeque<Update>)> =
+                BTreeMap::new();
+            let mut caught_up = false;
+            let mut buffer = Vec::new();
+
+            move |input, output| {
+                if caught_up {
+                    input.for_each(|cap, data| {
+                        data.swap(&mut buffer);
+                        output.session(&cap).give_vec(&mut buffer);
+                    });
+                    return;
+                }
+
+                input.for_each(|cap, data| {
+                    data.swap(&mut buffer);
+                    for update in buffer.drain(..) {
+                        pending
+                            .entry(update.1)
+                            .or_insert_with(|| (cap.delayed(&update.1), VecDeque::new()))
+                            .1
+                            .push_back(update);
+                    }
+                });
+
+                bucket.refill();
+                while let Some(time) = pending.keys().next().copied() {
+                    let (cap, updates) = pending.get_mut(&time).expect("key exists");
+                    let mut session = output.session(cap);
+                    while bucket.has_capacity() {
+                        let update = match updates.pop_front() {
+                            Some(update) => update,
+                            None => break,
+                        };
+                        let ((key, value), _time, diff) = &update;
+                        let bytes = key.as_ref().map_or(0, |row| row.byte_len())
+                            + value.as_ref().map_or(0, |row| row.byte_len());
+                        bucket.take(diff.unsigned_abs(), u64::cast_from(bytes));
+                        session.give(update);
+                    }
+                    if !updates.is_empty() {
+                        break;
+                    }
+                    drop(session);
+                    pending.remove(&time);
+                }
+
+                if !pending.is_empty() {
+                    activator.activate_after(bucket.refill_delay());
+                } else if let Some(until) = &until {
+                    if !input.frontier().less_equal(until) {
+                        on_caught_up();
+                        caught_up = true;
+                    }
+                }
+            }
+        })
+        .as_collection()
+}
+
+/// Budgets of records and bytes that refill continuously at fixed rates,
+/// holding up to one second's worth.
+struct TokenBucket {
+    records: Option<(u64, i64)>,
+    bytes: Option<(u64, i64)>,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(records_per_second: Option<u64>, bytes_per_second: Option<u64>) -> TokenBucket {
+        let full = |rate: u64| (rate, i64::try_from(rate).unwrap_or(i64::MAX));
+        TokenBucket {
+            records: records_per_second.map(full),
+            bytes: bytes_per_second.map(full),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_nanos();
+        let mut refilled_any = false;
+        for (rate, available) in self.records.iter_mut().chain(self.bytes.iter_mut()) {
+            let refilled = u128::from(*rate) * elapsed / 1_000_000_000;
+            let refilled = i64::try_from(refilled).unwrap_or(i64::MAX);
+            refilled_any |= refilled > 0;
+            *available = available
+                .saturating_add(refilled)
+                .min(i64::try_from(*rate).unwrap_or(i64::MAX));
+        }
+        // Don't lose the fractional refill of frequent activations.
+        if refilled_any {
+            self.last_refill = now;
+        }
+    }
+
+    /// Reports whether another update may be emitted. The last update
+    /// emitted may overdraw the budget, which delays later updates instead.
+    fn has_capacity(&self) -> bool {
+        self.records
+            .iter()
+            .chain(self.bytes.iter())
+            .all(|(_rate, available)| *available > 0)
+    }
+
+    fn take(&mut self, records: u64, bytes: u64) {
+        for ((_rate, available), amount) in [(&mut self.records, records), (&mut self.bytes, bytes)]
+            .into_iter()
+            .filter_map(|(budget, amount)| budget.as_mut().map(|budget| (budget, amount)))
+        {
+            *available = available.saturating_sub(i64::try_from(amount).unwrap_or(i64::MAX));
+        }
+    }
+
+    /// Returns how long until the budget has capacity again.
+    fn refill_delay(&self) -> Duration {
+        self.records
+            .iter()
+            .chain(self.bytes.iter())
+            .filter(|(_rate, available)| *available <= 0)
+            .map(|(rate, available)| {
+                let deficit = u128::from(available.unsigned_abs()) + 1;
+                let nanos = deficit * 1_000_000_000 / u128::from(*rate);
+                Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+            })
+            .max()
+            .unwrap_or(Duration::ZERO)
+            .max(Duration::from_millis(10))
+    }
+}
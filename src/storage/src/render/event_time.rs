@@ -0,0 +1,99 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Timestamping of source updates by the event time they carry.
+
+use differential_dataflow::{AsCollection, Collection};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::{Capability, Operator};
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+
+use mz_repr::{Datum, Diff, Row, Timestamp};
+
+use crate::types::sources::EventTimeConfig;
+
+/// Retimestamps the updates of `collection`, which are timestamped by the
+/// time at which they were ingested, by the event time in the column
+/// described by `config`.
+///
+/// The frontier of the output advances in steps of `config.lateness`,
+/// aligned to multiples of it, that trail the frontier of the input, and
+/// each update is timestamped by its event time clamped to the step that
+/// contains its ingestion time. Because the updates ingested at times
+/// before each step boundary are exactly those output at times before it,
+/// a source that resumes from the upper of its output shard re-ingests
+/// exactly the updates that were not yet written.
+///
+/// Updates with a null event time retain their ingestion time.
+pub(crate) fn render<G>(
+    collection: &Collection<G, Row, Diff>,
+    config: EventTimeConfig,
+    resume_upper: Antichain<Timestamp>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let lateness = u64::try_from(config.lateness.as_millis())
+        .unwrap_or(u64::MAX)
+        .max(1);
+    // Returns the step boundary at or before `time`.
+    let step_lower = move |time: Timestamp| {
+        let time = u64::from(time);
+        Timestamp::from(time - time % lateness)
+    };
+
+    collection
+        .inner
+        .unary_frontier(Pipeline, "EventTime", move |cap, _info| {
+            let mut cap: Option<Capability<Timestamp>> = match resume_upper.as_option() {
+                Some(upper) => Some(cap.delayed(upper)),
+                None => None,
+            };
+            let mut buffer = Vec::new();
+
+            move |input, output| {
+                input.for_each(|_time, data| {
+                    let cap = cap
+                        .as_ref()
+                        .expect("updates are not received after the input is closed");
+                    data.swap(&mut buffer);
+                    let mut session = output.session(cap);
+                    for (row, ingest_time, diff) in buffer.drain(..) {
+                        let lower = std::cmp::max(step_lower(ingest_time), *cap.time());
+                        let upper = step_lower(ingest_time).saturating_add(lateness);
+                        let event_time = match row.iter().nth(config.column) {
+                            Some(Datum::Timestamp(ts)) => Some(ts.timestamp_millis()),
+                            Some(Datum::TimestampTz(ts)) => Some(ts.timestamp_millis()),
+                            _ => None,
+                        };
+                        let time = match event_time {
+                            Some(millis) => {
+                                let millis = Timestamp::from(u64::try_from(millis).unwrap_or(0));
+                                millis.clamp(lower, upper.step_back().unwrap_or(lower))
+                            }
+                            None => ingest_time,
+                        };
+                        session.give((row, time, diff));
+                    }
+                });
+
+                match input.frontier().frontier().as_option() {
+                    Some(frontier) => {
+                        if let Some(cap) = &mut cap {
+                            let boundary = std::cmp::max(step_lower(*frontier), *cap.time());
+                            cap.downgrade(&boundary);
+                        }
+                    }
+                    None => cap = None,
+                }
+            }
+        })
+        .as_collection()
+}
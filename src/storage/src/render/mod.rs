@@ -114,7 +114,9 @@ use crate::storage_state::StorageState;
 use crate::types::sinks::StorageSinkDesc;
 use crate::types::sources::IngestionDescription;
 
+mod catch_up;
 mod debezium;
+mod event_time;
 mod persist_sink;
 pub mod sinks;
 pub mod sources;
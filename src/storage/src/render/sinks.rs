@@ -15,18 +15,26 @@ use std::collections::BTreeSet;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use bytes::BytesMut;
 use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
 use differential_dataflow::{AsCollection, Collection, Hashable};
+use sha2::{Digest, Sha256};
 use timely::dataflow::Scope;
 
-use mz_interchange::envelopes::{combine_at_timestamp, dbz_format, upsert_format};
-use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_interchange::envelopes::{
+    combine_at_timestamp, dbz_format, upsert_delete_record_format, upsert_format,
+};
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row, RowArena, ScalarType, Timestamp};
+use mz_timely_util::operator::CollectionExt;
 
 use crate::controller::CollectionMetadata;
 use crate::source::persist_source;
 use crate::storage_state::{SinkToken, StorageState};
 use crate::types::errors::DataflowError;
-use crate::types::sinks::{SinkEnvelope, StorageSinkConnection, StorageSinkDesc};
+use crate::types::sinks::{
+    SinkColumnMask, SinkColumnMaskKind, SinkDeletePolicy, SinkEnvelope, SinkRetractionPolicy,
+    StorageSinkConnection, StorageSinkDesc,
+};
 
 /// _Renders_ complete _differential_ [`Collection`]s
 /// that represent the sink and its errors as requested
@@ -62,17 +70,30 @@ pub(crate) fn render_sink<G: Scope<Timestamp = Timestamp>>(
     );
     needed_tokens.push(source_token);
 
+    let (ok_collection, filter_errs) = apply_filter(sink, ok_collection.as_collection());
+    let err_collection = err_collection.as_collection().concat(&filter_errs);
+    let ok_collection = apply_projection(sink, ok_collection);
+    let ok_collection = apply_column_masks(sink, ok_collection);
+    let ok_collection = apply_update_columns(sink, ok_collection);
+
     // TODO(teskje): Remove envelope-wrapping once the Kafka sink has been
     // moved to STORAGE.
-    let ok_collection =
-        apply_sink_envelope(sink_id, sink, &sink_render, ok_collection.as_collection());
+    let ok_collection = apply_sink_envelope(sink_id, sink, &sink_render, ok_collection);
+    let ok_collection = match sink.catch_up_limit {
+        Some(limit) => super::catch_up::render(storage_state, sink_id, ok_collection, limit),
+        None => ok_collection,
+    };
+    let ok_collection = match sink.rate_limit {
+        Some(limit) => super::catch_up::render_max_rate(storage_state, ok_collection, limit),
+        None => ok_collection,
+    };
 
     let sink_token = sink_render.render_continuous_sink(
         storage_state,
         sink,
         sink_id,
         ok_collection,
-        err_collection.as_collection(),
+        err_collection,
     );
 
     if let Some(sink_token) = sink_token {
@@ -84,6 +105,160 @@ pub(crate) fn render_sink<G: Scope<Timestamp = Timestamp>>(
         .insert(sink_id, SinkToken::new(Box::new(needed_tokens)));
 }
 
+/// Drops the rows of `collection` that do not satisfy the sink's filter.
+///
+/// Rows for which evaluating the filter fails are turned into errors, which
+/// are returned separately.
+fn apply_filter<G>(
+    sink: &StorageSinkDesc<CollectionMetadata>,
+    collection: Collection<G, Row, Diff>,
+) -> (Collection<G, Row, Diff>, Collection<G, DataflowError, Diff>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let filter = match &sink.filter {
+        Some(filter) => filter.clone(),
+        None => {
+            let errs = Collection::empty(&collection.scope());
+            return (collection, errs);
+        }
+    };
+    let mut datum_vec = DatumVec::new();
+    collection.flat_map_fallible("SinkFilter", move |row| {
+        let arena = RowArena::new();
+        let datums = datum_vec.borrow_with(&row);
+        let keep = match filter.eval(&datums, &arena) {
+            Ok(datum) => datum == Datum::True,
+            Err(e) => return Some(Err(DataflowError::from(e))),
+        };
+        drop(datums);
+        keep.then(|| Ok(row))
+    })
+}
+
+/// Restricts every row of `collection` to the columns the sink exports.
+fn apply_projection<G>(
+    sink: &StorageSinkDesc<CollectionMetadata>,
+    collection: Collection<G, Row, Diff>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    if sink.projection.is_empty() {
+        return collection;
+    }
+    let projection = sink.projection.clone();
+    let mut row_buf = Row::default();
+    collection.map(move |row| {
+        let datums = row.unpack();
+        row_buf
+            .packer()
+            .extend(projection.iter().map(|column| datums[*column]));
+        row_buf.clone()
+    })
+}
+
+/// Applies the sink's column masks to every row of `collection`, so that the
+/// masked values never reach the sink's key extraction or encoders.
+fn apply_column_masks<G>(
+    sink: &StorageSinkDesc<CollectionMetadata>,
+    collection: Collection<G, Row, Diff>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    if sink.column_masks.is_empty() {
+        return collection;
+    }
+    let masks: Vec<(SinkColumnMask, ScalarType)> = sink
+        .column_masks
+        .iter()
+        .map(|mask| {
+            let typ = sink.from_desc.typ().column_types[sink.from_column(mask.column)]
+                .scalar_type
+                .clone();
+            (*mask, typ)
+        })
+        .collect();
+    let mut row_buf = Row::default();
+    collection.map(move |row| {
+        // Hashed values are owned strings that the masked datums borrow from.
+        let hashes: Vec<Option<String>> = masks
+            .iter()
+            .map(|(mask, typ)| match mask.kind {
+                SinkColumnMaskKind::Hash => hash_datum(row.iter().nth(mask.column)?, typ),
+                _ => None,
+            })
+            .collect();
+        let mut datums: Vec<Datum> = row.iter().collect();
+        for ((mask, _typ), hash) in masks.iter().zip(hashes.iter()) {
+            let datum = &mut datums[mask.column];
+            *datum = match mask.kind {
+                SinkColumnMaskKind::Hash => match hash {
+                    Some(hash) => Datum::String(hash),
+                    None => Datum::Null,
+                },
+                SinkColumnMaskKind::Redact => Datum::Null,
+                SinkColumnMaskKind::Truncate { length } => match *datum {
+                    Datum::String(s) => match s.char_indices().nth(length) {
+                        Some((end, _)) => Datum::String(&s[..end]),
+                        None => Datum::String(s),
+                    },
+                    datum => datum,
+                },
+            };
+        }
+        row_buf.packer().extend(datums);
+        row_buf.clone()
+    })
+}
+
+/// Appends the timestamp and the diff of every update of `collection` to its
+/// row, as requested by the sink.
+///
+/// Updates whose diff is appended become single insertions, so that the
+/// sink's envelope emits retractions as rows with a negative diff.
+fn apply_update_columns<G>(
+    sink: &StorageSinkDesc<CollectionMetadata>,
+    collection: Collection<G, Row, Diff>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    if !sink.include_timestamp && !sink.include_diff {
+        return collection;
+    }
+    let include_timestamp = sink.include_timestamp;
+    let include_diff = sink.include_diff;
+    let mut row_buf = Row::default();
+    collection
+        .inner
+        .map(move |(row, time, diff)| {
+            let mut packer = row_buf.packer();
+            packer.extend(row.iter());
+            if include_timestamp {
+                packer.push(Datum::MzTimestamp(time));
+            }
+            if include_diff {
+                packer.push(Datum::Int64(diff));
+                (row_buf.clone(), time, 1)
+            } else {
+                (row_buf.clone(), time, diff)
+            }
+        })
+        .as_collection()
+}
+
+/// Returns the hex-encoded SHA-256 digest of the PostgreSQL text
+/// representation of `datum`, or `None` if it is NULL.
+fn hash_datum(datum: Datum, typ: &ScalarType) -> Option<String> {
+    mz_pgrepr::Value::from_datum(datum, typ).map(|value| {
+        let mut text = BytesMut::new();
+        value.encode_text(&mut text);
+        hex::encode(Sha256::digest(&text))
+    })
+}
+
 #[allow(clippy::borrowed_box)]
 fn apply_sink_envelope<G>(
     sink_id: GlobalId,
@@ -153,6 +328,11 @@ where
     //   It then renders those as Avro.
     // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
     //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
+    //   Deletes are rendered as tombstones, as marked rows, or not at all, according to its delete policy.
+    // * "AppendOnly" does the same, except that it emits the `after` record of each diff pair, and
+    //   handles the `before` record of updates and deletions according to its retraction policy.
+    // * "CdcV2" passes the updates through unchanged, with their multiplicities, for the sink to
+    //   encode along with progress statements.
     let collection = match sink.envelope {
         Some(SinkEnvelope::Debezium) => {
             let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
@@ -180,16 +360,62 @@ where
             });
             collection
         }
-        Some(SinkEnvelope::Upsert) => {
+        Some(SinkEnvelope::Upsert(policy)) => {
             let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
 
             let from = sink.from;
-            let collection = combined.map(move |(k, v)| {
-                let v = upsert_format(v, sink_id, from);
-                (k, v)
+            let collection = match policy {
+                SinkDeletePolicy::Tombstone => combined.map(move |(k, v)| {
+                    let v = upsert_format(v, sink_id, from);
+                    (k, v)
+                }),
+                SinkDeletePolicy::Suppress => combined
+                    .flat_map(move |(k, v)| upsert_format(v, sink_id, from).map(|v| (k, Some(v)))),
+                SinkDeletePolicy::DeleteRecord => {
+                    let mut row_buf = Row::default();
+                    combined.map(move |(k, v)| {
+                        upsert_delete_record_format(&mut row_buf.packer(), v, sink_id, from);
+                        (k, Some(row_buf.clone()))
+                    })
+                }
+            };
+            collection
+        }
+        Some(SinkEnvelope::AppendOnly(policy)) => {
+            let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
+
+            // if there is no user-specified key, remove the synthetic
+            // distribution key again
+            let user_key_indices = sink_render.get_key_indices();
+            let combined = if user_key_indices.is_some() {
+                combined
+            } else {
+                combined.map(|(_key, value)| (None, value))
+            };
+
+            let from = sink.from;
+            let collection = combined.flat_map(move |(k, v)| {
+                let mut messages = Vec::new();
+                for dp in v {
+                    if dp.before.is_some() {
+                        match policy {
+                            SinkRetractionPolicy::Error => panic!(
+                                "append-only sink {sink_id} created from {from} encountered a \
+                                 retraction, which it is configured to not tolerate"
+                            ),
+                            SinkRetractionPolicy::Drop => {}
+                            SinkRetractionPolicy::DeleteMarker => messages.push((k.clone(), None)),
+                        }
+                    }
+                    if let Some(after) = dp.after {
+                        messages.push((k.clone(), Some(after)));
+                    }
+                }
+                messages
             });
             collection
         }
+        Some(SinkEnvelope::CdcV2) => keyed.map(|(_key, value)| (None, Some(value))),
         None => keyed.map(|(key, value)| (key, Some(value))),
     };
 
@@ -226,5 +452,14 @@ where
 {
     match connection {
         StorageSinkConnection::Kafka(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::S3(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::File(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Postgres(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Elasticsearch(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Redis(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Nats(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Mqtt(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Grpc(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Sqs(connection) => Box::new(connection.clone()),
     }
 }
@@ -29,11 +29,12 @@ use crate::controller::CollectionMetadata;
 use crate::decode::{render_decode, render_decode_cdcv2, render_decode_delimited};
 use crate::source::types::{DecodeResult, SourceOutput};
 use crate::source::{
-    self, persist_source, DelimitedValueSource, KafkaSourceReader, KinesisSourceReader,
-    LoadGeneratorSourceReader, PostgresSourceReader, RawSourceCreationConfig, S3SourceReader,
-    TestScriptSourceReader,
+    self, persist_source, DelimitedValueSource, FileSourceReader, KafkaSourceReader,
+    KinesisSourceReader, LoadGeneratorSourceReader, MqttSourceReader, MySqlSourceReader,
+    NatsSourceReader, PostgresSourceReader, PubSubSourceReader, RawSourceCreationConfig,
+    S3SourceReader, SftpSourceReader, SqlServerSourceReader, TestScriptSourceReader,
 };
-use crate::types::errors::{DataflowError, DecodeError, EnvelopeError};
+use crate::types::errors::{DataflowError, DecodeError, DecodeErrorKind, EnvelopeError};
 use crate::types::sources::{encoding::*, *};
 
 /// A type-level enum that holds one of two types of sources depending on their message type
@@ -93,6 +94,8 @@ where
         id,
         num_outputs: description.desc.num_outputs(),
         timestamp_interval: description.desc.timestamp_interval.clone(),
+        timestamp_strategy: description.desc.timestamp_strategy,
+        max_ingest_rate: description.desc.max_ingest_rate,
         worker_id: scope.index(),
         worker_count: scope.peers(),
         encoding: description.desc.encoding.clone(),
@@ -102,6 +105,7 @@ where
         resume_upper: resume_upper.clone(),
         storage_metadata: description.ingestion_metadata.clone(),
         persist_clients: Arc::clone(&storage_state.persist_clients),
+        message_sizes: Rc::clone(&storage_state.source_message_sizes),
     };
 
     // TODO(petrosagg): put the description as-is in the RawSourceCreationConfig instead of cloning
@@ -145,6 +149,61 @@ where
             let oks = oks.into_iter().map(SourceType::ByteStream).collect();
             ((oks, err), cap)
         }
+        SourceConnection::File(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, FileSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
+        SourceConnection::Mqtt(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, MqttSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
+        SourceConnection::Nats(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, NatsSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
+        SourceConnection::PubSub(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, PubSubSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
+        SourceConnection::Sftp(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, SftpSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::ByteStream).collect();
+            ((oks, err), cap)
+        }
         SourceConnection::Postgres(connection) => {
             let ((oks, err), cap) = source::create_raw_source::<_, PostgresSourceReader, _>(
                 scope,
@@ -156,6 +215,28 @@ where
             let oks = oks.into_iter().map(SourceType::Row).collect();
             ((oks, err), cap)
         }
+        SourceConnection::MySql(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, MySqlSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Row).collect();
+            ((oks, err), cap)
+        }
+        SourceConnection::SqlServer(connection) => {
+            let ((oks, err), cap) = source::create_raw_source::<_, SqlServerSourceReader, _>(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+            );
+            let oks = oks.into_iter().map(SourceType::Row).collect();
+            ((oks, err), cap)
+        }
         SourceConnection::LoadGenerator(connection) => {
             let ((oks, err), cap) = source::create_raw_source::<_, LoadGeneratorSourceReader, _>(
                 scope,
@@ -185,6 +266,7 @@ where
     needed_tokens.push(source_token);
 
     let mut outputs = vec![];
+    let mut decode_error_collections = vec![];
     for ok_source in ok_sources {
         // All sources should push their various error streams into this vector,
         // whose contents will be concatenated and inserted along the collection.
@@ -194,7 +276,7 @@ where
             .pass_through("source-errors", 1)
             .as_collection()];
 
-        let (ok, err, extra_tokens) = render_source_stream(
+        let (ok, err, decode_errors, extra_tokens) = render_source_stream(
             scope,
             dataflow_debug_name,
             id,
@@ -206,7 +288,20 @@ where
         );
         needed_tokens.extend(extra_tokens);
         outputs.push((ok, err));
+        decode_error_collections.extend(decode_errors);
+    }
+
+    // The messages that could not be decoded, if requested, form an additional output that
+    // follows the outputs of the source itself.
+    if let SourceEnvelope::None(NoneEnvelope {
+        decode_errors: DecodeErrorPolicy::Subsource,
+        ..
+    }) = description.desc.envelope
+    {
+        let decode_errors = collection::concatenate(scope, decode_error_collections);
+        outputs.push((decode_errors, Collection::empty(scope)));
     }
+
     (outputs, Rc::new(needed_tokens))
 }
 
@@ -221,6 +316,9 @@ type ConcreteSourceType<G> = SourceType<
 
 /// Completes the rendering of a particular source stream by applying decoding and envelope
 /// processing as necessary
+///
+/// Messages that cannot be decoded are returned separately, as rows of the decode errors
+/// subsource, if the envelope requests it.
 fn render_source_stream<G>(
     scope: &mut G,
     dataflow_debug_name: &String,
@@ -233,17 +331,20 @@ fn render_source_stream<G>(
 ) -> (
     Collection<G, Row, Diff>,
     Collection<G, DataflowError, Diff>,
+    Option<Collection<G, Row, Diff>>,
     Vec<Rc<dyn Any>>,
 )
 where
     G: Scope<Timestamp = Timestamp>,
 {
     let mut needed_tokens: Vec<Rc<dyn Any>> = vec![];
+    let mut decode_errors = None;
 
     let SourceDesc {
         encoding,
         envelope,
         metadata_columns,
+        event_time,
         ..
     } = description.desc;
     let (stream, errors) = {
@@ -261,6 +362,7 @@ where
                 schema,
                 csr_connection,
                 confluent_wire_format,
+                numeric_overflow: _,
             } = match value_encoding.inner {
                 DataEncodingInner::Avro(enc) => enc,
                 _ => unreachable!("Attempted to create non-Avro CDCv2 source"),
@@ -277,7 +379,8 @@ where
                             csr_connection
                                 .connect(&*storage_state.connection_context.secrets_reader),
                         )
-                        .expect("CSR connection unexpectedly missing secrets"),
+                        .expect("CSR connection unexpectedly missing secrets")
+                        .with_cache(Arc::clone(&storage_state.connection_context.csr_cache)),
                 ),
             };
             // TODO(petrosagg): this should move to the envelope section below and
@@ -299,6 +402,8 @@ where
                     metadata_columns,
                     storage_state.decode_metrics.clone(),
                     &storage_state.connection_context,
+                    id,
+                    Rc::clone(&storage_state.source_numeric_overflows),
                 ),
                 SourceType::ByteStream(source) => render_decode(
                     &source,
@@ -307,6 +412,8 @@ where
                     metadata_columns,
                     storage_state.decode_metrics.clone(),
                     &storage_state.connection_context,
+                    id,
+                    Rc::clone(&storage_state.source_numeric_overflows),
                 ),
                 SourceType::Row(source) => (
                     source.map(|r| DecodeResult {
@@ -413,8 +520,10 @@ where
                         };
                     let (upsert_ok, upsert_err) = super::upsert::upsert(
                         &transformed_results,
-                        resume_upper,
+                        id,
+                        resume_upper.clone(),
                         upsert_envelope.clone(),
+                        storage_state.upsert_state_config.clone(),
                         previous_stream,
                         previous_token,
                     );
@@ -422,6 +531,14 @@ where
                     (upsert_ok.as_collection(), Some(upsert_err.as_collection()))
                 }
                 SourceEnvelope::None(none_envelope) => {
+                    let results = match none_envelope.decode_errors {
+                        DecodeErrorPolicy::Fail => results,
+                        DecodeErrorPolicy::Subsource => {
+                            let (results, errors) = split_decode_errors(&results);
+                            decode_errors = Some(errors);
+                            results
+                        }
+                    };
                     let results = append_metadata_to_value(results);
 
                     let flattened_stream = flatten_results_prepend_keys(none_envelope, results);
@@ -464,6 +581,12 @@ where
 
     // Perform various additional transformations on the collection.
 
+    // Retimestamp updates by their event time, if requested.
+    let stream = match event_time {
+        Some(event_time) => super::event_time::render(&stream, event_time, resume_upper),
+        None => stream,
+    };
+
     // Force a shuffling of data in case sources are not uniformly distributed.
     let collection = stream.inner.exchange(|x| x.hashed()).as_collection();
 
@@ -475,7 +598,38 @@ where
     };
 
     // Return the collections and any needed tokens.
-    (collection, err_collection, needed_tokens)
+    (collection, err_collection, decode_errors, needed_tokens)
+}
+
+/// Splits the messages whose key or value could not be decoded out of `results`, as rows
+/// described by [`decode_errors_desc`].
+fn split_decode_errors<G>(
+    results: &Stream<G, DecodeResult>,
+) -> (Stream<G, DecodeResult>, Collection<G, Row, Diff>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let (oks, errors) = results.ok_err(|result| {
+        // Prioritize the value error if both the key and the value have one, as the none
+        // envelope does.
+        let error = match (&result.key, &result.value) {
+            (_, Some(Err(e))) | (Some(Err(e)), _) => e.clone(),
+            _ => return Ok(result),
+        };
+        let DecodeError {
+            kind: DecodeErrorKind::Text(text),
+            raw,
+        } = error;
+        let partition = result.partition.to_string();
+        let offset = i64::try_from(result.position.offset).unwrap_or(i64::MAX);
+        Err(Row::pack_slice(&[
+            Datum::String(&partition),
+            Datum::Int64(offset),
+            raw.as_deref().map(Datum::Bytes).unwrap_or(Datum::Null),
+            Datum::String(&text),
+        ]))
+    });
+    (oks, errors.pass_through("decode-errors", 1).as_collection())
 }
 
 /// After handling metadata insertion, we split streams into key/value parts for convenience
@@ -560,6 +714,7 @@ where
     let NoneEnvelope {
         key_envelope,
         key_arity,
+        decode_errors: _,
     } = none_envelope;
 
     let null_key_columns = Row::pack_slice(&vec![Datum::Null; *key_arity]);
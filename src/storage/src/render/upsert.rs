@@ -8,7 +8,6 @@
 // by the Apache License, Version 2.0.
 
 use std::any::Any;
-use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
@@ -22,19 +21,25 @@ use timely::dataflow::operators::{Capability, CapabilityRef, Concat, OkErr, Oper
 use timely::dataflow::{Scope, Stream};
 use timely::order::PartialOrder;
 use timely::progress::frontier::AntichainRef;
-use timely::progress::{Antichain, ChangeBatch};
+use timely::progress::Antichain;
 use tracing::{error, info};
 
 use mz_expr::{EvalError, MirScalarExpr};
-use mz_repr::{Datum, DatumVec, DatumVecBorrow, Diff, Row, RowArena, Timestamp};
+use mz_repr::{Datum, DatumVec, DatumVecBorrow, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_timely_util::operator::StreamExt;
 
 use crate::source::types::DecodeResult;
+use crate::storage_state::UpsertStateConfig;
 use crate::types::errors::{
-    DataflowError, DecodeError, EnvelopeError, UpsertError, UpsertValueError,
+    DataflowError, DecodeError, EnvelopeError, SourceError, SourceErrorDetails, UpsertError,
+    UpsertValueError,
 };
 use crate::types::sources::{MzOffset, UpsertEnvelope, UpsertStyle};
 
+use self::state::{Rehydration, UpsertState};
+
+mod state;
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 struct UpsertSourceData {
     /// The actual value
@@ -56,8 +61,10 @@ struct UpsertSourceData {
 /// can be null or empty.
 pub(crate) fn upsert<G>(
     stream: &Stream<G, DecodeResult>,
+    source_id: GlobalId,
     as_of_frontier: Antichain<Timestamp>,
     upsert_envelope: UpsertEnvelope,
+    state_config: UpsertStateConfig,
     previous: Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>,
     previous_token: Option<Rc<dyn Any>>,
 ) -> (
@@ -133,10 +140,12 @@ where
 
     let upsert_output = upsert_core(
         stream,
+        source_id,
         predicates,
         position_or,
         as_of_frontier,
         upsert_envelope,
+        state_config,
         previous.as_collection(),
         previous_token,
     );
@@ -274,10 +283,12 @@ fn extract_kv<G: Scope>(
 /// Internal core upsert logic.
 fn upsert_core<G>(
     stream: &Stream<G, DecodeResult>,
+    source_id: GlobalId,
     predicates: Vec<MirScalarExpr>,
     position_or: Vec<Option<usize>>,
     as_of_frontier: Antichain<Timestamp>,
     upsert_envelope: UpsertEnvelope,
+    state_config: UpsertStateConfig,
     previous: Collection<G, Result<Row, UpsertError>, Diff>,
     mut previous_token: Option<Rc<dyn Any>>,
 ) -> Stream<G, (Result<Row, DataflowError>, Timestamp, Diff)>
@@ -347,45 +358,62 @@ where
             let mut kdv = DatumVec::new();
             // this is a map of (decoded key) -> (decoded_value). We store the
             // latest value for a given key that way we know what to retract if
-            // a new value with the same key comes along.
+            // a new value with the same key comes along. Depending on the
+            // envelope's `state_backend`, it is kept in memory or on disk.
             //
             // If `previous_token` is true, we need to rehydrate this from the last good input,
-            // so set it to `None` for now.
-            let mut current_values = if previous_token.is_some() {
-                None
+            // so set it to `None` for now, and accumulate the last good input in `rehydration`.
+            let mut current_values = None;
+            let mut rehydration = None;
+            // An error of the state backend that has yet to be reported. The state can no longer
+            // be trusted once its backend fails, so the error is reported at the next complete
+            // time and all later input is discarded.
+            let mut state_error = None;
+            if previous_token.is_some() {
+                match Rehydration::new(upsert_envelope.state_backend, &state_config) {
+                    Ok(state) => rehydration = Some(state),
+                    Err(err) => state_error = Some(err),
+                }
             } else {
-                Some(HashMap::default())
-            };
+                match UpsertState::new(upsert_envelope.state_backend, &state_config) {
+                    Ok(state) => current_values = Some(state),
+                    Err(err) => state_error = Some(err),
+                }
+            }
+            if state_error.is_some() {
+                previous_token = None;
+            }
 
-            let mut initial_values_multiset = ChangeBatch::default();
             move |data_input, previous_input, output| {
-                if previous_token.is_some() {
+                if let Some(initial_values) = &mut rehydration {
                     assert!(current_values.is_none());
                     // Hydrate the `current_values` map from the previous state of the collection.
                     // We can't just insert things into the `current_values` map directly, since
                     // we might in general have non-one multiplicities due to Persist being behind on compaction.
-                    // Thus, we use `initial_values_multiset` to keep track of how many of each record we've seen.
+                    // Thus, we use `rehydration` to keep track of how many of each record we've seen.
                     //
-                    // At the end of reading the entire previous input, `initial_values_multiset` must have exactly one of each record,
+                    // At the end of reading the entire previous input, `rehydration` must have exactly one of each record,
                     // and furthermore, each key must be unique. We validate this property for sanity's sake, and build `current_values`.
                     //
-                    // TODO[btv] This algorithm has the potential to use unbounded space if we can't make any assumptions
-                    // about Persist's level of compaction or the order in which it returns updates.
-                    // See the detailed discussion [here](https://materializeinc.slack.com/archives/C01CFKM1QRF/p1659063332027139).
-                    // This is thought to be fine for our current purposes, but we will need to rethink it once the Persist team has
-                    // thought harder about what guarantees/APIs they want to offer relating to bounded-memory consolidation.
+                    // The disk backend accumulates the records on disk as they arrive, while the memory backend
+                    // keeps them in memory, which has the potential to use unbounded space if we can't make any
+                    // assumptions about Persist's level of compaction or the order in which it returns updates.
                     // Tracked here: https://github.com/MaterializeInc/materialize/issues/14086
                     previous_input.for_each(|_cap, data| {
                         data.swap(&mut repop_scratch_vector);
-                        initial_values_multiset.extend(
-                            repop_scratch_vector
-                                .drain(..)
-                                // filter out records at or past when we are resuming this operator from
-                                .filter(|(_d, t, _r)| !as_of_frontier.less_equal(t))
-                                .map(|(d, _t, r)| (d, r)),
-                        );
+                        let records = repop_scratch_vector
+                            .drain(..)
+                            // filter out records at or past when we are resuming this operator from
+                            .filter(|(_d, t, _r)| !as_of_frontier.less_equal(t))
+                            .map(|(d, _t, r)| (d, r));
+                        if let Err(err) = initial_values.extend(records) {
+                            state_error.get_or_insert(err);
+                        }
                     });
-                    if PartialOrder::less_equal(
+                    if state_error.is_some() {
+                        previous_token = None;
+                        rehydration = None;
+                    } else if PartialOrder::less_equal(
                         &AntichainRef::new(&as_of_frontier),
                         &previous_input.frontier().frontier(),
                     ) {
@@ -393,23 +421,11 @@ where
                         // Without this, we will re-download everything we upload, wasting tons of bandwidth.
                         previous_token = None;
 
-                        let mut new_current_values =
-                            HashMap::with_capacity(initial_values_multiset.len());
-                        for ((k, v), r) in initial_values_multiset.drain() {
-                            assert!(
-                                r == 1,
-                                "The upsert state should have exactly one value per key"
-                            );
-                            match new_current_values.entry(k) {
-                                Entry::Occupied(_oe) => {
-                                    panic!("The upsert state should have exactly one value per key")
-                                }
-                                Entry::Vacant(ve) => {
-                                    ve.insert(v);
-                                }
-                            }
+                        let initial_values = rehydration.take().expect("rehydrating");
+                        match initial_values.finish(&state_config) {
+                            Ok(state) => current_values = Some(state),
+                            Err(err) => state_error = Some(err),
                         }
-                        current_values = Some(new_current_values);
                     }
                 }
 
@@ -427,10 +443,9 @@ where
                 // Don't try to do anything if we aren't done building the `current_values` map.
                 // Any new data that comes in as we rehydrate `current_values` is just stored in
                 // memory in `pending_values` until we are ready to merge it into `current_values`.
-                let current_values = match &mut current_values {
-                    None => return,
-                    Some(x) => x,
-                };
+                if rehydration.is_some() {
+                    return;
+                }
 
                 let mut removed_times = Vec::new();
                 for (time, (cap, map)) in pending_values.iter_mut() {
@@ -441,22 +456,40 @@ where
                         // it will be less than the times in the rest of the map
                         break;
                     }
-                    process_pending_values_batch(
-                        time,
-                        cap,
-                        map,
-                        current_values,
-                        &mut row_packer,
-                        &mut dv,
-                        &upsert_envelope,
-                        &key_indices_sorted,
-                        &key_indices_map,
-                        &mut kdv,
-                        &predicates,
-                        &position_or,
-                        &mut removed_times,
-                        output,
-                    )
+                    match current_values.as_mut() {
+                        Some(state) => {
+                            let result = process_pending_values_batch(
+                                time,
+                                cap,
+                                map,
+                                state,
+                                &mut row_packer,
+                                &mut dv,
+                                &upsert_envelope,
+                                &key_indices_sorted,
+                                &key_indices_map,
+                                &mut kdv,
+                                &predicates,
+                                &position_or,
+                                &mut removed_times,
+                                output,
+                            );
+                            if let Err(err) = result {
+                                current_values = None;
+                                state_error = Some(err);
+                            }
+                        }
+                        None => removed_times.push(*time),
+                    }
+                    if let Some(err) = state_error.take() {
+                        let err = SourceError::new(
+                            source_id,
+                            SourceErrorDetails::Other(format!("upsert state: {:#}", err)),
+                        );
+                        output
+                            .session(cap)
+                            .give((Err(DataflowError::SourceError(err)), *time, 1));
+                    }
                 }
                 // Discard entries, capabilities for complete times.
                 for time in removed_times {
@@ -549,7 +582,7 @@ fn process_pending_values_batch(
     cap: &mut Capability<Timestamp>,
     map: &mut HashMap<Option<Result<Row, DecodeError>>, UpsertSourceData>,
     // The current map of values we use to perform the upsert comparision
-    current_values: &mut HashMap<Result<Row, DecodeError>, Result<Row, DataflowError>>,
+    current_values: &mut UpsertState,
     // A shared row used to pack new rows for evaluation and output
     row_packer: &mut Row,
     // A shared row used to build a Vec<Datum<'_>> for evaluation
@@ -580,7 +613,7 @@ fn process_pending_values_batch(
             (Result<Row, DataflowError>, Timestamp, Diff),
         >,
     >,
-) {
+) -> Result<(), anyhow::Error> {
     let mut session = output.session(cap);
     removed_times.push(time.clone());
     for (key, data) in map.drain() {
@@ -636,7 +669,7 @@ fn process_pending_values_batch(
                     .map(|full_row| thin(key_indices_sorted, full_row, row_packer))
                     .map_err(|e| e.clone());
                 current_values
-                    .insert(decoded_key.clone(), thinned_value)
+                    .insert(decoded_key.clone(), thinned_value)?
                     .map(|res| {
                         res.map(|v| {
                             rehydrate(
@@ -651,7 +684,7 @@ fn process_pending_values_batch(
                         })
                     })
             } else {
-                current_values.remove(&decoded_key).map(|res| {
+                current_values.remove(&decoded_key)?.map(|res| {
                     res.map(|v| {
                         rehydrate(
                             key_indices_map,
@@ -676,6 +709,7 @@ fn process_pending_values_batch(
             }
         }
     }
+    current_values.flush()
 }
 
 fn build_datum_vec_for_evaluation<'row>(
@@ -0,0 +1,364 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The map from keys to their latest values kept by the upsert operator.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context;
+use lru::LruCache;
+use rocksdb::{IteratorMode, Options, WriteBatch, DB};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tempfile::TempDir;
+use timely::progress::ChangeBatch;
+
+use mz_repr::{Diff, Row};
+
+use crate::storage_state::UpsertStateConfig;
+use crate::types::errors::{DataflowError, DecodeError};
+use crate::types::sources::UpsertStateBackend;
+
+type Key = Result<Row, DecodeError>;
+type Value = Result<Row, DataflowError>;
+
+/// The maximum number of changes the disk backend buffers in memory before
+/// writing them to RocksDB.
+const DISK_PENDING_CAPACITY: usize = 10_000;
+
+/// The latest value of each key seen by the upsert operator.
+pub(super) enum UpsertState {
+    Memory(HashMap<Key, Value>),
+    Disk(DiskState),
+}
+
+impl UpsertState {
+    pub fn new(
+        backend: UpsertStateBackend,
+        config: &UpsertStateConfig,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match backend {
+            UpsertStateBackend::Memory => UpsertState::Memory(HashMap::new()),
+            UpsertStateBackend::Disk => UpsertState::Disk(DiskState::new(config)?),
+        })
+    }
+
+    /// Sets the value of `key`, returning its previous value.
+    pub fn insert(&mut self, key: Key, value: Value) -> Result<Option<Value>, anyhow::Error> {
+        match self {
+            UpsertState::Memory(map) => Ok(map.insert(key, value)),
+            UpsertState::Disk(state) => state.put(key, Some(value)),
+        }
+    }
+
+    /// Removes the value of `key`, returning it.
+    pub fn remove(&mut self, key: &Key) -> Result<Option<Value>, anyhow::Error> {
+        match self {
+            UpsertState::Memory(map) => Ok(map.remove(key)),
+            UpsertState::Disk(state) => state.put(key.clone(), None),
+        }
+    }
+
+    /// Writes any buffered changes to the backing store.
+    ///
+    /// Called once per batch of updates, so that the disk backend writes each
+    /// batch with as few RocksDB writes as its buffer allows.
+    pub fn flush(&mut self) -> Result<(), anyhow::Error> {
+        match self {
+            UpsertState::Memory(_) => Ok(()),
+            UpsertState::Disk(state) => state.flush(),
+        }
+    }
+}
+
+/// A RocksDB instance fronted by an in-memory cache.
+pub(super) struct DiskState {
+    /// Changes made since the last flush; `None` marks a deleted key.
+    pending: HashMap<Key, Option<Value>>,
+    /// The most recently written values, which are known to match the
+    /// contents of `db`.
+    cache: LruCache<Key, Value>,
+    db: ScratchDb,
+}
+
+impl DiskState {
+    fn new(config: &UpsertStateConfig) -> Result<Self, anyhow::Error> {
+        Ok(DiskState {
+            pending: HashMap::new(),
+            cache: LruCache::new(config.disk_cache_capacity),
+            db: ScratchDb::open(config)?,
+        })
+    }
+
+    /// Replaces the value of `key`, returning its previous value, and writes
+    /// the buffered changes once there are `DISK_PENDING_CAPACITY` of them.
+    fn put(&mut self, key: Key, value: Option<Value>) -> Result<Option<Value>, anyhow::Error> {
+        let old = self.take(&key)?;
+        self.pending.insert(key, value);
+        if self.pending.len() >= DISK_PENDING_CAPACITY {
+            self.flush()?;
+        }
+        Ok(old)
+    }
+
+    /// Removes the value of `key` from memory, reading it from disk if it is
+    /// not there.
+    fn take(&mut self, key: &Key) -> Result<Option<Value>, anyhow::Error> {
+        if let Some(value) = self.pending.remove(key) {
+            return Ok(value);
+        }
+        if let Some(value) = self.cache.pop(key) {
+            return Ok(Some(value));
+        }
+        self.db.get(key)
+    }
+
+    fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut batch = WriteBatch::default();
+        for (key, value) in self.pending.drain() {
+            match value {
+                Some(value) => {
+                    batch.put(encode(&key)?, encode(&value)?);
+                    self.cache.put(key, value);
+                }
+                None => batch.delete(encode(&key)?),
+            }
+        }
+        self.db.write(batch)
+    }
+}
+
+/// The previous output of the upsert operator, which its state is rehydrated
+/// from.
+///
+/// The output is not necessarily consolidated, so it is accumulated in full
+/// before the state is built from it. The disk backend accumulates it in a
+/// RocksDB instance of its own, buffering at most `DISK_PENDING_CAPACITY`
+/// records in memory.
+pub(super) enum Rehydration {
+    Memory(ChangeBatch<(Key, Value)>),
+    Disk {
+        pending: BTreeMap<(Key, Value), Diff>,
+        db: ScratchDb,
+    },
+}
+
+impl Rehydration {
+    pub fn new(
+        backend: UpsertStateBackend,
+        config: &UpsertStateConfig,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match backend {
+            UpsertStateBackend::Memory => Rehydration::Memory(ChangeBatch::default()),
+            UpsertStateBackend::Disk => Rehydration::Disk {
+                pending: BTreeMap::new(),
+                db: ScratchDb::open(config)?,
+            },
+        })
+    }
+
+    /// Accumulates records of the previous output.
+    pub fn extend<I>(&mut self, updates: I) -> Result<(), anyhow::Error>
+    where
+        I: IntoIterator<Item = ((Key, Value), Diff)>,
+    {
+        match self {
+            Rehydration::Memory(multiset) => multiset.extend(updates.into_iter()),
+            Rehydration::Disk { pending, db } => {
+                for (record, diff) in updates {
+                    *pending.entry(record).or_insert(0) += diff;
+                    if pending.len() >= DISK_PENDING_CAPACITY {
+                        Self::flush(pending, db)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the upsert state from the accumulated output, which must hold
+    /// exactly one value per key.
+    pub fn finish(self, config: &UpsertStateConfig) -> Result<UpsertState, anyhow::Error> {
+        let mut state;
+        match self {
+            Rehydration::Memory(mut multiset) => {
+                state = UpsertState::new(UpsertStateBackend::Memory, config)?;
+                for ((key, value), diff) in multiset.drain() {
+                    Self::insert(&mut state, key, value, diff)?;
+                }
+            }
+            Rehydration::Disk { mut pending, db } => {
+                Self::flush(&mut pending, &db)?;
+                state = UpsertState::new(UpsertStateBackend::Disk, config)?;
+                for entry in db.db.iterator(IteratorMode::Start) {
+                    let (record, diff) = entry.context("reading upsert state from RocksDB")?;
+                    let (key, value) = decode(&record)?;
+                    Self::insert(&mut state, key, value, decode(&diff)?)?;
+                }
+            }
+        }
+        state.flush()?;
+        Ok(state)
+    }
+
+    fn insert(
+        state: &mut UpsertState,
+        key: Key,
+        value: Value,
+        diff: Diff,
+    ) -> Result<(), anyhow::Error> {
+        assert!(
+            diff == 1,
+            "The upsert state should have exactly one value per key"
+        );
+        if state.insert(key, value)?.is_some() {
+            panic!("The upsert state should have exactly one value per key")
+        }
+        Ok(())
+    }
+
+    /// Adds the buffered records to those accumulated in `db`.
+    fn flush(
+        pending: &mut BTreeMap<(Key, Value), Diff>,
+        db: &ScratchDb,
+    ) -> Result<(), anyhow::Error> {
+        let mut batch = WriteBatch::default();
+        for (record, diff) in std::mem::take(pending) {
+            let diff = diff + db.get::<_, Diff>(&record)?.unwrap_or(0);
+            if diff == 0 {
+                batch.delete(encode(&record)?);
+            } else {
+                batch.put(encode(&record)?, encode(&diff)?);
+            }
+        }
+        db.write(batch)
+    }
+}
+
+/// A RocksDB instance in a temporary directory under the configured scratch
+/// directory.
+///
+/// The state does not need to survive restarts, as the upsert operator
+/// rehydrates it from its own output, so the directory is removed when the
+/// instance is dropped.
+pub(super) struct ScratchDb {
+    // N.B. `db` must be declared before `_dir`, so that it is closed before
+    // the directory is removed.
+    db: DB,
+    _dir: TempDir,
+}
+
+impl ScratchDb {
+    fn open(config: &UpsertStateConfig) -> Result<Self, anyhow::Error> {
+        let dir = tempfile::tempdir_in(&config.scratch_directory).with_context(|| {
+            format!(
+                "creating upsert state directory in {}",
+                config.scratch_directory.display()
+            )
+        })?;
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        let db = DB::open(&options, dir.path()).context("opening upsert state RocksDB instance")?;
+        Ok(ScratchDb { db, _dir: dir })
+    }
+
+    fn get<K: Serialize, V: DeserializeOwned>(&self, key: &K) -> Result<Option<V>, anyhow::Error> {
+        match self
+            .db
+            .get(encode(key)?)
+            .context("reading upsert state from RocksDB")?
+        {
+            Some(bytes) => Ok(Some(decode(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), anyhow::Error> {
+        self.db
+            .write(batch)
+            .context("writing upsert state to RocksDB")
+    }
+}
+
+fn encode<T: Serialize>(t: &T) -> Result<Vec<u8>, anyhow::Error> {
+    bincode::serialize(t).context("encoding upsert state")
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, anyhow::Error> {
+    bincode::deserialize(bytes).context("decoding upsert state")
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    fn row(i: i64) -> Row {
+        Row::pack_slice(&[Datum::Int64(i)])
+    }
+
+    fn config(disk_cache_capacity: usize) -> UpsertStateConfig {
+        UpsertStateConfig {
+            scratch_directory: std::env::temp_dir(),
+            disk_cache_capacity,
+        }
+    }
+
+    #[test]
+    fn test_disk_state() {
+        let config = config(1);
+        let mut state = UpsertState::new(UpsertStateBackend::Disk, &config).unwrap();
+        assert_eq!(state.insert(Ok(row(1)), Ok(row(10))).unwrap(), None);
+        assert_eq!(state.insert(Ok(row(2)), Ok(row(20))).unwrap(), None);
+        state.flush().unwrap();
+
+        // Values are read back from the cache and, once evicted, from disk.
+        assert_eq!(
+            state.insert(Ok(row(1)), Ok(row(11))).unwrap(),
+            Some(Ok(row(10)))
+        );
+        assert_eq!(state.remove(&Ok(row(2))).unwrap(), Some(Ok(row(20))));
+        state.flush().unwrap();
+
+        assert_eq!(state.remove(&Ok(row(1))).unwrap(), Some(Ok(row(11))));
+        assert_eq!(state.remove(&Ok(row(2))).unwrap(), None);
+        state.flush().unwrap();
+        assert_eq!(state.insert(Ok(row(1)), Ok(row(12))).unwrap(), None);
+    }
+
+    #[test]
+    fn test_disk_rehydration() {
+        let config = config(1);
+        let mut rehydration = Rehydration::new(UpsertStateBackend::Disk, &config).unwrap();
+        rehydration
+            .extend(vec![
+                ((Ok(row(1)), Ok(row(10))), 1),
+                ((Ok(row(2)), Ok(row(20))), 1),
+            ])
+            .unwrap();
+        if let Rehydration::Disk { pending, db } = &mut rehydration {
+            Rehydration::flush(pending, db).unwrap();
+        }
+        // Retractions are accumulated with the records already on disk.
+        rehydration
+            .extend(vec![
+                ((Ok(row(1)), Ok(row(10))), -1),
+                ((Ok(row(1)), Ok(row(11))), 1),
+            ])
+            .unwrap();
+
+        let mut state = rehydration.finish(&config).unwrap();
+        assert_eq!(state.remove(&Ok(row(1))).unwrap(), Some(Ok(row(11))));
+        assert_eq!(state.remove(&Ok(row(2))).unwrap(), Some(Ok(row(20))));
+    }
+}
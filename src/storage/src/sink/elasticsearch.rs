@@ -0,0 +1,315 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that indexes upserts into an Elasticsearch or OpenSearch index.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use differential_dataflow::Collection;
+use itertools::Itertools;
+use serde::Deserialize;
+use timely::dataflow::Scope;
+
+use mz_interchange::encode::{column_names_and_types, TypedDatum};
+use mz_interchange::json::{encode_datums_as_json, ToJson};
+use mz_ore::collections::CollectionExt;
+use mz_repr::{ColumnName, ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_secrets::SecretsReader;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::sink_connection::{collapse_upserts, render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{ElasticsearchSinkConnection, StorageSinkDesc};
+
+/// The maximum number of documents indexed or deleted by a single bulk
+/// request.
+const MAX_DOCUMENTS_PER_REQUEST: usize = 1000;
+
+impl<G> SinkRender<G> for ElasticsearchSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_indices)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker issues all bulk requests, so that the upserts at
+        // each batch of timestamps are applied in order.
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "elasticsearch",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            BulkEncoder::new(self),
+            ElasticsearchWriter {
+                connection: self.clone(),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// A client for the HTTP API of an Elasticsearch or OpenSearch cluster.
+pub(super) struct ElasticsearchClient {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    credentials: Option<(String, Option<String>)>,
+}
+
+impl ElasticsearchClient {
+    /// Constructs a client for the cluster targeted by `connection`, reading
+    /// its credentials from `secrets_reader`.
+    pub(super) async fn new(
+        connection: &ElasticsearchSinkConnection,
+        secrets_reader: &dyn SecretsReader,
+    ) -> Result<ElasticsearchClient, anyhow::Error> {
+        let url = connection.url.parse()?;
+        let credentials = match &connection.user {
+            None => None,
+            Some(user) => {
+                let user = user.get_string(secrets_reader).await?;
+                let password = match connection.password {
+                    None => None,
+                    Some(id) => Some(secrets_reader.read_string(id).await?),
+                };
+                Some((user, password))
+            }
+        };
+        Ok(ElasticsearchClient {
+            client: reqwest::Client::new(),
+            url,
+            credentials,
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .expect("validated to be an http(s) URL")
+            .pop_if_empty()
+            .push(path);
+        let request = self.client.request(method, url);
+        match &self.credentials {
+            None => request,
+            Some((user, password)) => request.basic_auth(user, password.as_ref()),
+        }
+    }
+
+    /// Verifies that the cluster is reachable and accepts our credentials.
+    pub(super) async fn ping(&self) -> Result<(), anyhow::Error> {
+        self.request(reqwest::Method::GET, "")
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Submits a bulk request with the given newline-delimited body, returning
+    /// an error if any of its actions failed.
+    async fn bulk(&self, body: &str) -> Result<(), anyhow::Error> {
+        let response: BulkResponse = self
+            .request(reqwest::Method::POST, "_bulk")
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body.to_owned())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if !response.errors {
+            return Ok(());
+        }
+        for item in response.items {
+            for (action, result) in item {
+                // Deleting a document that was never indexed is not an error
+                // for our purposes.
+                if action == "delete" && result.status == 404 {
+                    continue;
+                }
+                if result.status >= 300 {
+                    bail!(
+                        "{} of document {} failed with status {}: {}",
+                        action,
+                        result.id.unwrap_or_default(),
+                        result.status,
+                        result.error.unwrap_or_default()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The response to a bulk request.
+#[derive(Deserialize)]
+struct BulkResponse {
+    errors: bool,
+    items: Vec<HashMap<String, BulkItemResult>>,
+}
+
+/// The result of a single action within a bulk request.
+#[derive(Deserialize)]
+struct BulkItemResult {
+    #[serde(rename = "_id")]
+    id: Option<String>,
+    status: u16,
+    error: Option<serde_json::Value>,
+}
+
+/// Encodes upserts as the actions of bulk requests.
+struct BulkEncoder {
+    index: String,
+    key_columns: Vec<ColumnType>,
+    value_columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl BulkEncoder {
+    fn new(connection: &ElasticsearchSinkConnection) -> Self {
+        let types = &connection.value_desc.typ().column_types;
+        BulkEncoder {
+            index: connection.index.clone(),
+            key_columns: connection
+                .key_indices
+                .iter()
+                .map(|i| types[*i].clone())
+                .collect(),
+            value_columns: column_names_and_types(connection.value_desc.clone()),
+        }
+    }
+
+    /// Returns the document ID for `key`.
+    ///
+    /// A single string column is used verbatim; any other key is identified by
+    /// its JSON encoding, as a scalar for a single column or as an array for
+    /// multiple columns.
+    fn document_id(&self, key: &Row) -> String {
+        let values = key
+            .iter()
+            .zip(&self.key_columns)
+            .map(|(datum, typ)| TypedDatum::new(datum, typ.clone()).json())
+            .collect::<Vec<_>>();
+        if values.len() == 1 {
+            match values.into_element() {
+                serde_json::Value::String(s) => s,
+                value => value.to_string(),
+            }
+        } else {
+            serde_json::Value::Array(values).to_string()
+        }
+    }
+
+    /// Appends the action that applies the upsert of `key` to `value` to
+    /// `body`.
+    fn encode(&self, body: &mut String, key: &Row, value: &Option<Row>) {
+        let metadata = serde_json::json!({
+            "_index": self.index,
+            "_id": self.document_id(key),
+        });
+        match value {
+            Some(value) => {
+                body.push_str(&serde_json::json!({ "index": metadata }).to_string());
+                body.push('\n');
+                body.push_str(
+                    &encode_datums_as_json(value.iter(), &self.value_columns).to_string(),
+                );
+                body.push('\n');
+            }
+            None => {
+                body.push_str(&serde_json::json!({ "delete": metadata }).to_string());
+                body.push('\n');
+            }
+        }
+    }
+
+    /// Returns the bodies of the bulk requests that apply the upserts in
+    /// `batch`, each of which covers at most `MAX_DOCUMENTS_PER_REQUEST`
+    /// documents.
+    ///
+    /// Indexing a document replaces any previous version with the same ID, so
+    /// applying a batch is idempotent, which means that replaying updates
+    /// after a restart or a failed request is safe.
+    fn encode_upserts(&self, batch: &HashMap<Row, Option<Row>>) -> Vec<String> {
+        let mut bodies = vec![];
+        for chunk in &batch.iter().chunks(MAX_DOCUMENTS_PER_REQUEST) {
+            let mut body = String::new();
+            for (key, value) in chunk {
+                self.encode(&mut body, key, value);
+            }
+            bodies.push(body);
+        }
+        bodies
+    }
+}
+
+impl BatchEncoder for BulkEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        self.encode_upserts(&collapse_upserts(updates))
+    }
+}
+
+/// Submits batches of bulk requests to the cluster.
+struct ElasticsearchWriter {
+    connection: ElasticsearchSinkConnection,
+    connection_context: ConnectionContext,
+    client: Option<ElasticsearchClient>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for ElasticsearchWriter {
+    async fn write(&mut self, bodies: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            let client = ElasticsearchClient::new(
+                &self.connection,
+                &*self.connection_context.secrets_reader,
+            )
+            .await
+            .context("error creating elasticsearch client")?;
+            self.client = Some(client);
+        }
+        let client = self.client.as_ref().expect("known to exist");
+        for body in bodies {
+            client.bulk(body).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that appends a collection to files on the local filesystem.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+use timely::PartialOrder;
+use tracing::{debug, info};
+
+use mz_interchange::avro::{encode_datums_as_avro, AvroSchemaGenerator};
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_interchange::text::CsvEncoder;
+use mz_ore::collections::CollectionExt;
+use mz_repr::{ColumnName, ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_timely_util::async_op;
+use mz_timely_util::operators_async_ext::OperatorBuilderExt;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::sink_connection::{single_worker_write_frontier, PendingUpdates, SinkToken};
+use crate::storage_state::StorageState;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{
+    FileSinkConnection, FileSinkFormat, SinkAsOf, SinkEnvelope, StorageSinkDesc,
+};
+
+impl<G> SinkRender<G> for FileSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        false
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker writes all files, so that updates are appended in
+        // timestamp order.
+        let (is_active_worker, write_frontier) =
+            single_worker_write_frontier(storage_state, sink_id, &sinked_collection.inner.scope());
+
+        let token = file(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.envelope,
+            sink.as_of.clone(),
+            is_active_worker,
+            write_frontier,
+        );
+
+        Some(token)
+    }
+}
+
+/// Encodes rows into the format of a file sink.
+struct RowEncoder {
+    format: FileSinkFormat,
+    columns: Vec<(ColumnName, ColumnType)>,
+    avro_schema: mz_avro::Schema,
+    json_encoder: JsonEncoder,
+    csv_encoder: CsvEncoder,
+}
+
+impl RowEncoder {
+    fn new(connection: &FileSinkConnection, debezium: bool) -> Self {
+        let schema_generator =
+            AvroSchemaGenerator::new(None, None, None, connection.value_desc.clone(), debezium);
+        let mut csv_encoder = CsvEncoder::new(None, connection.value_desc.clone(), debezium);
+        if let FileSinkFormat::Csv { delimiter, .. } = &connection.format {
+            csv_encoder = csv_encoder.with_delimiter(*delimiter);
+        }
+        RowEncoder {
+            format: connection.format.clone(),
+            columns: schema_generator.value_columns().to_vec(),
+            avro_schema: schema_generator.value_writer_schema().clone(),
+            json_encoder: JsonEncoder::new(None, connection.value_desc.clone(), debezium),
+            csv_encoder,
+        }
+    }
+}
+
+/// The kind of writer for an open file.
+enum FileWriter {
+    AvroOcf(mz_avro::Writer<File>),
+    Text(File),
+}
+
+/// The file that a file sink is currently appending to.
+///
+/// Only updates at closed timestamps are ever written to a file, so a file
+/// always describes a contiguous range of complete timestamps.
+struct OpenFile {
+    path: String,
+    writer: FileWriter,
+    /// A second handle to the file, used to sync and stat the file while the
+    /// writer owns the first one.
+    handle: File,
+    size_bytes: u64,
+    opened_at: Instant,
+}
+
+impl OpenFile {
+    /// Opens the file for the updates starting at `lower`.
+    ///
+    /// A non-rotating sink appends to the file at its configured path,
+    /// continuing whatever an earlier incarnation of the sink wrote. A rotating
+    /// sink writes a new file for each range of timestamps, named after the
+    /// first timestamp in the range, which makes rewriting a file after a
+    /// restart idempotent.
+    fn open(
+        connection: &FileSinkConnection,
+        encoder: &RowEncoder,
+        lower: Timestamp,
+    ) -> Result<Self, io::Error> {
+        let path = if connection.rotates() {
+            format!("{}.{}", connection.path, lower)
+        } else {
+            connection.path.clone()
+        };
+        let mut options = OpenOptions::new();
+        options.read(true).create(true);
+        if connection.rotates() {
+            options.write(true).truncate(true);
+        } else {
+            options.append(true);
+        }
+        let file = options.open(&path)?;
+        let handle = file.try_clone()?;
+        let size_bytes = file.metadata()?.len();
+
+        let writer = match &connection.format {
+            FileSinkFormat::AvroOcf if size_bytes > 0 => FileWriter::AvroOcf(
+                mz_avro::Writer::append_to(file)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
+            ),
+            FileSinkFormat::AvroOcf => {
+                FileWriter::AvroOcf(mz_avro::Writer::new(encoder.avro_schema.clone(), file))
+            }
+            FileSinkFormat::Csv {
+                header: Some(names),
+                ..
+            } if size_bytes == 0 => {
+                let mut file = file;
+                let mut buf = Vec::new();
+                encoder.csv_encoder.encode_header(names, &mut buf);
+                buf.push(b'\n');
+                file.write_all(&buf)?;
+                FileWriter::Text(file)
+            }
+            FileSinkFormat::Csv { .. } | FileSinkFormat::Json => FileWriter::Text(file),
+        };
+
+        Ok(OpenFile {
+            path,
+            writer,
+            handle,
+            size_bytes,
+            opened_at: Instant::now(),
+        })
+    }
+
+    /// Appends `rows` to the file, and syncs the file to disk if `fsync` is
+    /// set.
+    fn write(
+        &mut self,
+        encoder: &RowEncoder,
+        rows: &[(Row, usize)],
+        fsync: bool,
+    ) -> Result<(), io::Error> {
+        match &mut self.writer {
+            FileWriter::AvroOcf(writer) => {
+                for (row, count) in rows {
+                    let value = encode_datums_as_avro(row.iter(), &encoder.columns);
+                    for _ in 0..*count {
+                        writer
+                            .append_value_ref(&value)
+                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+                    }
+                }
+                writer
+                    .flush()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            }
+            FileWriter::Text(file) => {
+                let mut buf = Vec::new();
+                for (row, count) in rows {
+                    let mut encoded = Vec::new();
+                    match &encoder.format {
+                        FileSinkFormat::Csv { .. } => {
+                            let csv_encoder = &encoder.csv_encoder;
+                            csv_encoder.encode_record(
+                                row,
+                                csv_encoder.value_columns(),
+                                &mut encoded,
+                            );
+                            encoded.push(b'\n');
+                        }
+                        FileSinkFormat::Json => {
+                            encoded = encoder.json_encoder.encode_value_unchecked(row.clone());
+                            encoded.push(b'\n');
+                        }
+                        FileSinkFormat::AvroOcf => unreachable!("avro files use an avro writer"),
+                    }
+                    for _ in 0..*count {
+                        buf.extend_from_slice(&encoded);
+                    }
+                }
+                file.write_all(&buf)?;
+                file.flush()?;
+            }
+        }
+        if fsync {
+            self.handle.sync_data()?;
+        }
+        self.size_bytes = self.handle.metadata()?.len();
+        Ok(())
+    }
+
+    /// Reports whether the sink should move on to a new file, either because
+    /// this one has grown too large or because it has been open for too long.
+    fn should_rotate(&self, connection: &FileSinkConnection) -> bool {
+        let too_large = matches!(connection.max_file_size, Some(max) if self.size_bytes >= max);
+        let too_old = matches!(
+            connection.rotation_interval,
+            Some(interval) if self.opened_at.elapsed() >= interval
+        );
+        too_large || too_old
+    }
+}
+
+fn file<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: FileSinkConnection,
+    envelope: Option<SinkEnvelope>,
+    as_of: SinkAsOf,
+    is_active_worker: bool,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("file-{}", id);
+    let stream = &collection.inner;
+    let scope = stream.scope();
+    let mut builder = OperatorBuilder::new(name.clone(), scope.clone());
+
+    // We want exactly one worker to write all files.
+    let hashed_id = id.hashed();
+    let mut input = builder.new_input(stream, Exchange::new(move |_| hashed_id));
+
+    let debezium = matches!(envelope, Some(SinkEnvelope::Debezium));
+    let encoder = RowEncoder::new(&connection, debezium);
+
+    let (token, shutdown_flag) = SinkToken::new(name.clone());
+    let mut open_file: Option<OpenFile> = None;
+    let mut pending = PendingUpdates::new(as_of);
+    let mut vector = Vec::new();
+
+    builder.build_async(
+        scope,
+        async_op!(|_initial_capabilities, frontiers| {
+            if shutdown_flag.load(Ordering::SeqCst) {
+                debug!("shutting down sink: {}", name);
+                // NOTE: We never downgrade our write frontier to the empty
+                // frontier when shutting down. See the Kafka sink for details.
+                return false;
+            }
+
+            let frontier = frontiers.clone().into_element();
+
+            input.for_each(|_, rows| {
+                assert!(is_active_worker);
+                rows.swap(&mut vector);
+                for ((key, value), time, diff) in vector.drain(..) {
+                    pending.push(key, value, time, diff);
+                }
+            });
+
+            if !is_active_worker {
+                return false;
+            }
+
+            // Append the rows at all newly closed timestamps, rotating files
+            // between timestamps as necessary.
+            for (time, updates) in pending.drain_closed(&frontier) {
+                let rows: Vec<_> = updates
+                    .into_iter()
+                    .filter_map(|(_key, value, count)| Some((value?, count)))
+                    .collect();
+                if rows.is_empty() {
+                    continue;
+                }
+
+                // Files are rotated lazily, when the next update arrives, so
+                // that empty files are never created.
+                if matches!(&open_file, Some(f) if f.should_rotate(&connection)) {
+                    let f = open_file.take().expect("known to exist");
+                    info!("{}: finished writing {}", name, f.path);
+                }
+                let f = match &mut open_file {
+                    Some(f) => f,
+                    None => {
+                        let f = OpenFile::open(&connection, &encoder, time)
+                            .unwrap_or_else(|e| panic!("{}: opening file: {}", name, e));
+                        info!("{}: writing to {}", name, f.path);
+                        open_file.insert(f)
+                    }
+                };
+                f.write(&encoder, &rows, connection.fsync)
+                    .unwrap_or_else(|e| panic!("{}: writing to {}: {}", name, f.path, e));
+            }
+
+            // All updates at closed timestamps have been written, so our write
+            // frontier is the input frontier.
+            if PartialOrder::less_than(&*write_frontier.borrow(), &frontier) {
+                write_frontier.borrow_mut().clone_from(&frontier);
+            }
+
+            !pending.is_empty()
+        }),
+    );
+
+    Rc::new(token)
+}
@@ -0,0 +1,277 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that streams updates to a client-streaming method of a gRPC
+//! service.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use differential_dataflow::Collection;
+use http::uri::PathAndQuery;
+use timely::dataflow::Scope;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_secrets::SecretsReader;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::sink_connection::{render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{GrpcSinkConnection, SinkEnvelope, StorageSinkDesc};
+
+impl<G> SinkRender<G> for GrpcSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_indices.as_deref()
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker streams all updates, so that the updates for each
+        // timestamp are streamed after those of earlier timestamps.
+        let debezium = matches!(sink.envelope, Some(SinkEnvelope::Debezium));
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "grpc",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            MessageEncoder::new(self, debezium),
+            GrpcWriter {
+                connection: self.clone(),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// An update, as streamed to the sink's method.
+///
+/// Services declare a compatible request message to receive updates, e.g.:
+///
+/// ```protobuf
+/// message Update {
+///     optional bytes key = 1;
+///     optional bytes value = 2;
+///     uint64 timestamp = 3;
+/// }
+/// ```
+#[derive(Clone, PartialEq, prost::Message)]
+struct GrpcUpdate {
+    /// The JSON encoding of the update's key, if the sink has a key.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    key: Option<Vec<u8>>,
+    /// The JSON encoding of the update's value, or nothing if the update
+    /// deletes its key.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    value: Option<Vec<u8>>,
+    /// The timestamp of the update.
+    #[prost(uint64, tag = "3")]
+    timestamp: u64,
+}
+
+/// Connects to the server targeted by `connection`.
+async fn connect(connection: &GrpcSinkConnection) -> Result<Grpc<Channel>, anyhow::Error> {
+    let mut endpoint =
+        Endpoint::from_shared(connection.url.clone())?.connect_timeout(Duration::from_secs(10));
+    if connection.url.starts_with("https:") {
+        endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+    }
+    Ok(Grpc::new(endpoint.connect().await?))
+}
+
+/// Returns the `authorization` header sent with each call of the sink's
+/// method, if the sink authenticates with a token.
+async fn authorization(
+    connection: &GrpcSinkConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<Option<MetadataValue<tonic::metadata::Ascii>>, anyhow::Error> {
+    match connection.token {
+        Some(token) => {
+            let token = secrets_reader.read_string(token).await?;
+            let value = format!("Bearer {}", token)
+                .parse()
+                .context("token is not a valid header value")?;
+            Ok(Some(value))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Checks that the server targeted by `connection` accepts connections, and
+/// that its token, if any, can be read.
+pub(super) async fn check_connection(
+    connection: &GrpcSinkConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(), anyhow::Error> {
+    authorization(connection, secrets_reader).await?;
+    let mut client = connect(connection).await?;
+    client.ready().await?;
+    Ok(())
+}
+
+/// Encodes updates as the messages that stream them.
+///
+/// Each message is encoded as the JSON encoding of its key, value, and
+/// timestamp, so that batches can be spilled to disk.
+struct MessageEncoder {
+    json_encoder: JsonEncoder,
+}
+
+impl MessageEncoder {
+    fn new(connection: &GrpcSinkConnection, debezium: bool) -> Self {
+        let value_desc = &connection.value_desc;
+        let key_desc = connection.key_indices.as_ref().map(|key_indices| {
+            RelationDesc::from_names_and_types(key_indices.iter().map(|i| {
+                (
+                    value_desc.get_name(*i).clone(),
+                    value_desc.typ().column_types[*i].clone(),
+                )
+            }))
+        });
+        MessageEncoder {
+            json_encoder: JsonEncoder::new(key_desc, value_desc.clone(), debezium),
+        }
+    }
+
+    /// Appends the `count` messages that stream the update of `key` to
+    /// `value` at `time` to `messages`.
+    fn encode(
+        &self,
+        messages: &mut Vec<String>,
+        time: Timestamp,
+        key: Option<Row>,
+        value: Option<Row>,
+        count: usize,
+    ) {
+        let encode = |bytes| String::from_utf8(bytes).expect("JSON encoding is valid UTF-8");
+        let key = key.map(|key| encode(self.json_encoder.encode_key_unchecked(key)));
+        let value = value.map(|value| encode(self.json_encoder.encode_value_unchecked(value)));
+        let message = serde_json::to_string(&(&key, &value, u64::from(time)))
+            .expect("serializing strings cannot fail");
+        for _ in 0..count {
+            messages.push(message.clone());
+        }
+    }
+}
+
+impl BatchEncoder for MessageEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        let mut messages = vec![];
+        for (time, updates) in updates {
+            for (key, value, count) in updates {
+                self.encode(&mut messages, time, key, value, count);
+            }
+        }
+        messages
+    }
+}
+
+/// Streams batches of messages to the sink's method.
+///
+/// Each batch is streamed in a single call, and is acknowledged by the
+/// method's response. A batch that fails is streamed again in its entirety,
+/// so updates are delivered at least once.
+struct GrpcWriter {
+    connection: GrpcSinkConnection,
+    connection_context: ConnectionContext,
+    client: Option<Grpc<Channel>>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for GrpcWriter {
+    /// Streams each of `messages` in a single call of the sink's method and
+    /// waits for its response, connecting to the server first if necessary.
+    async fn write(&mut self, messages: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            self.client = Some(
+                connect(&self.connection)
+                    .await
+                    .context("error connecting to grpc")?,
+            );
+        }
+        let updates = messages
+            .iter()
+            .map(|message| {
+                let (key, value, timestamp): (Option<String>, Option<String>, u64) =
+                    serde_json::from_str(message)?;
+                Ok(GrpcUpdate {
+                    key: key.map(String::into_bytes),
+                    value: value.map(String::into_bytes),
+                    timestamp,
+                })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        let mut request = tonic::Request::new(futures::stream::iter(updates));
+        if let Some(authorization) =
+            authorization(&self.connection, &*self.connection_context.secrets_reader).await?
+        {
+            request
+                .metadata_mut()
+                .insert("authorization", authorization);
+        }
+        let path = PathAndQuery::try_from(self.connection.method.as_str())?;
+        let client = self.client.as_mut().expect("known to exist");
+        let result = async {
+            client.ready().await.context("error awaiting grpc server")?;
+            // The content of the response is irrelevant; that there is one
+            // acknowledges the batch.
+            client
+                .client_streaming::<_, _, (), _>(request, path, ProstCodec::default())
+                .await
+                .context("error streaming updates")?;
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+        if result.is_err() {
+            // The connection may be unusable after an error, so start over
+            // with a fresh one.
+            self.client = None;
+        }
+        result
+    }
+}
@@ -10,19 +10,20 @@
 use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::cmp;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
 use differential_dataflow::{Collection, Hashable};
 use futures::{StreamExt, TryFutureExt};
 use itertools::Itertools;
 use prometheus::core::AtomicU64;
-use rdkafka::client::ClientContext;
+use rdkafka::admin::AdminClient;
+use rdkafka::client::{BrokerAddr, ClientContext, OAuthToken};
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{BaseConsumer, Consumer};
 use rdkafka::error::{KafkaError, KafkaResult, RDKafkaErrorCode};
@@ -44,27 +45,42 @@ use tokio::runtime::Handle as TokioHandle;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
-use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator};
+use mz_expr::MirScalarExpr;
+use mz_interchange::avro::{cdc_v2, AvroEncoder, AvroSchemaGenerator};
 use mz_interchange::encode::Encode;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{ProtobufEncoder, ProtobufSchemaGenerator};
+use mz_interchange::text::{CsvEncoder, TextEncoder};
 use mz_kafka_util::client::{create_new_client_config, MzClientContext};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
-use mz_ore::metrics::{CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVecExt};
+use mz_ore::metrics::{
+    CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, DeleteOnDropHistogram, GaugeVecExt,
+    HistogramVecExt,
+};
+use mz_ore::now::NowFn;
 use mz_ore::retry::Retry;
 use mz_ore::task;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::write::WriteHandle;
+use mz_persist_client::{PersistLocation, ShardId};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, ScalarType, Timestamp};
 use mz_timely_util::async_op;
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
+use super::sink_connection::ensure_kafka_topic;
 use super::KafkaBaseMetrics;
 use crate::controller::CollectionMetadata;
+use crate::protocol::client::{SinkMetricsUpdate, SinkStatus, SinkStatusUpdate};
 use crate::render::sinks::SinkRender;
 use crate::storage_state::StorageState;
 use crate::types::connections::{ConnectionContext, PopulateClientConfig};
 use crate::types::errors::DataflowError;
 use crate::types::sinks::{
-    KafkaSinkConnection, PublishedSchemaInfo, SinkAsOf, SinkEnvelope, StorageSinkDesc,
+    KafkaSinkConnection, KafkaSinkConnectionRetention, KafkaSinkMessageTimestamp,
+    KafkaSinkRetryExhaustedAction, KafkaSinkRetryPolicy, KafkaSinkTextFormat, KafkaSinkTopicFanout,
+    PublishedSchemaFormat, PublishedSchemaInfo, SinkAsOf, SinkEnvelope, StorageSinkDesc,
+    KAFKA_SINK_ERROR_HEADER, KAFKA_SINK_TIMESTAMP_HEADER, KAFKA_SINK_TOPIC_HEADER,
 };
 
 // 30s is a good maximum backoff for network operations. Long enough to reduce
@@ -72,6 +88,62 @@ use crate::types::sinks::{
 // the upstream system comes back online.
 const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
 
+// The wall time a single invocation of the encode operator aims to spend
+// encoding before yielding the worker to other operators.
+const ENCODE_INVOCATION_TARGET: Duration = Duration::from_millis(10);
+
+// The wall time a single transaction aims to take from its beginning to its
+// commit. Longer transactions amortize the cost of committing over more
+// messages, but delay the publication of the timestamps they contain.
+const TRANSACTION_TARGET: Duration = Duration::from_secs(1);
+
+/// A batch size that adapts to observed throughput so that processing a batch
+/// takes roughly `target` wall time.
+///
+/// After each batch, the size is moved towards the amount that would have
+/// taken exactly `target` at the observed rate. The size changes by at most a
+/// factor of two per observation, so that a single slow or fast batch cannot
+/// swing it wildly, and always stays within `[min, max]`.
+#[derive(Debug)]
+struct AdaptiveBatchSize {
+    target: Duration,
+    size: u64,
+    min: u64,
+    max: u64,
+}
+
+impl AdaptiveBatchSize {
+    fn new(target: Duration, initial: u64, min: u64, max: u64) -> Self {
+        assert!(min <= initial && initial <= max);
+        AdaptiveBatchSize {
+            target,
+            size: initial,
+            min,
+            max,
+        }
+    }
+
+    /// The amount to process in the next batch.
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Records that processing `amount` took `elapsed`.
+    fn observe(&mut self, amount: u64, elapsed: Duration) {
+        // Batches that were cut short by a lack of input say little about
+        // how large a full batch can be.
+        if amount == 0 || (amount < self.size / 2 && elapsed < self.target) {
+            return;
+        }
+        let elapsed = elapsed.as_nanos().max(1);
+        let ideal = u128::from(amount) * self.target.as_nanos() / elapsed;
+        let ideal = u64::try_from(ideal).unwrap_or(u64::MAX);
+        let lower = self.size / 2;
+        let upper = self.size.saturating_mul(2);
+        self.size = ideal.clamp(lower, upper).clamp(self.min, self.max);
+    }
+}
+
 impl<G> SinkRender<G> for KafkaSinkConnection
 where
     G: Scope<Timestamp = Timestamp>,
@@ -121,6 +193,10 @@ where
             Antichain::new()
         }));
 
+        let progress_shard = sink
+            .progress_shard
+            .expect("the storage controller assigns Kafka sinks a progress shard");
+
         let token = kafka(
             sinked_collection,
             sink_id,
@@ -128,8 +204,16 @@ where
             sink.envelope,
             sink.as_of.clone(),
             Rc::clone(&shared_frontier),
+            Arc::clone(&storage_state.persist_clients),
+            sink.from_storage_metadata.persist_location.clone(),
+            progress_shard,
             &storage_state.sink_metrics.kafka,
             &storage_state.connection_context,
+            Rc::clone(&storage_state.sink_progress_publishes),
+            Rc::clone(&storage_state.sink_dead_letters),
+            Rc::clone(&storage_state.sink_metrics_updates),
+            Rc::clone(&storage_state.sink_status_updates),
+            storage_state.now.clone(),
         );
 
         storage_state
@@ -146,6 +230,9 @@ pub struct SinkMetrics {
     message_send_errors_counter: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     message_delivery_errors_counter: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     rows_queued: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    progress_publish_seconds: DeleteOnDropHistogram<'static, Vec<String>>,
+    progress_publish_errors_counter: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    consistency_wait_seconds: DeleteOnDropHistogram<'static, Vec<String>>,
 }
 
 impl SinkMetrics {
@@ -170,7 +257,16 @@ impl SinkMetrics {
             message_delivery_errors_counter: base
                 .message_delivery_errors_counter
                 .get_delete_on_drop_counter(labels.clone()),
-            rows_queued: base.rows_queued.get_delete_on_drop_gauge(labels),
+            rows_queued: base.rows_queued.get_delete_on_drop_gauge(labels.clone()),
+            progress_publish_seconds: base
+                .progress_publish_seconds
+                .get_delete_on_drop_histogram(labels.clone()),
+            progress_publish_errors_counter: base
+                .progress_publish_errors_counter
+                .get_delete_on_drop_counter(labels.clone()),
+            consistency_wait_seconds: base
+                .consistency_wait_seconds
+                .get_delete_on_drop_histogram(labels),
         }
     }
 }
@@ -184,7 +280,10 @@ pub struct KafkaSinkSendRetryManager {
     // rely on that fact if/when we eventually (tm) get around to making our queues bounded.
     //
     // If perf becomes an issue, we can do something slightly more complex with a crossbeam channel
-    q: VecDeque<OwnedMessage>,
+    q: VecDeque<(OwnedMessage, KafkaError)>,
+    /// Messages that the broker rejected with an error that retrying cannot
+    /// fix, to be published to the dead letter topic.
+    dead_letters: VecDeque<(OwnedMessage, KafkaError)>,
     outstanding_send_count: u64,
 }
 
@@ -192,52 +291,82 @@ impl KafkaSinkSendRetryManager {
     fn new() -> Self {
         Self {
             q: VecDeque::new(),
+            dead_letters: VecDeque::new(),
             outstanding_send_count: 0,
         }
     }
     fn record_send(&mut self) {
         self.outstanding_send_count += 1;
     }
-    fn record_error(&mut self, msg: OwnedMessage) {
-        self.q.push_back(msg);
+    fn record_error(&mut self, msg: OwnedMessage, error: KafkaError) {
+        self.q.push_back((msg, error));
+        self.outstanding_send_count -= 1;
+    }
+    fn record_dead_letter(&mut self, msg: OwnedMessage, error: KafkaError) {
+        self.dead_letters.push_back((msg, error));
         self.outstanding_send_count -= 1;
     }
     fn record_success(&mut self) {
         self.outstanding_send_count -= 1;
     }
     fn sends_flushed(&mut self) -> bool {
-        self.outstanding_send_count == 0 && self.q.is_empty()
+        self.outstanding_send_count == 0 && self.q.is_empty() && self.dead_letters.is_empty()
+    }
+    fn take_retries(&mut self) -> Vec<(OwnedMessage, KafkaError)> {
+        self.q.drain(..).collect()
     }
-    fn pop_retry(&mut self) -> Option<OwnedMessage> {
-        self.q.pop_front()
+    fn divert_to_dead_letters(&mut self, msgs: Vec<(OwnedMessage, KafkaError)>) {
+        self.dead_letters.extend(msgs);
+    }
+    fn pop_dead_letter(&mut self) -> Option<(OwnedMessage, KafkaError)> {
+        self.dead_letters.pop_front()
     }
 }
 
 pub struct SinkProducerContext {
     metrics: Arc<SinkMetrics>,
     retry_manager: Arc<Mutex<KafkaSinkSendRetryManager>>,
+    /// The sink topic, if messages that cannot be delivered to it are to be
+    /// published to a dead letter topic.
+    dead_letter_source_topic: Option<String>,
+    client_context: MzClientContext,
 }
 
 impl SinkProducerContext {
     pub fn new(
         metrics: Arc<SinkMetrics>,
         retry_manager: Arc<Mutex<KafkaSinkSendRetryManager>>,
+        dead_letter_source_topic: Option<String>,
+        client_context: MzClientContext,
     ) -> Self {
         SinkProducerContext {
             metrics,
             retry_manager,
+            dead_letter_source_topic,
+            client_context,
         }
     }
 }
 
 impl ClientContext for SinkProducerContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
     // The shape of the rdkafka *Context traits require us to forward to the `MzClientContext`
     // implementation.
     fn log(&self, level: rdkafka::config::RDKafkaLogLevel, fac: &str, log_message: &str) {
-        MzClientContext.log(level, fac, log_message)
+        self.client_context.log(level, fac, log_message)
     }
     fn error(&self, error: rdkafka::error::KafkaError, reason: &str) {
-        MzClientContext.error(error, reason)
+        self.client_context.error(error, reason)
+    }
+    fn generate_oauth_token(
+        &self,
+        oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn std::error::Error>> {
+        self.client_context.generate_oauth_token(oauthbearer_config)
+    }
+    fn rewrite_broker_addr(&self, addr: BrokerAddr) -> BrokerAddr {
+        self.client_context.rewrite_broker_addr(addr)
     }
 }
 impl ProducerContext for SinkProducerContext {
@@ -248,18 +377,45 @@ impl ProducerContext for SinkProducerContext {
             Ok(_) => self.retry_manager.blocking_lock().record_success(),
             Err((e, msg)) => {
                 self.metrics.message_delivery_errors_counter.inc();
+                // Data messages that can never be delivered are diverted to
+                // the dead letter topic, if there is one, rather than retried.
+                if let Some(topic) = &self.dead_letter_source_topic {
+                    if is_dead_letter_error(e) && msg.topic() == topic {
+                        warn!(
+                            "Kafka producer delivery error {:?} for {:?}; publishing to dead letter topic",
+                            e, msg
+                        );
+                        self.retry_manager
+                            .blocking_lock()
+                            .record_dead_letter(msg.detach(), e.clone());
+                        return;
+                    }
+                }
                 // TODO: figure out a good way to back these retries off.  Should be okay without
                 // because we seem to very rarely end up in a constant state where rdkafka::send
                 // works but everything is immediately rejected and hits this branch.
                 warn!("Kafka producer delivery error {:?} for {:?}", e, msg);
                 self.retry_manager
                     .blocking_lock()
-                    .record_error(msg.detach());
+                    .record_error(msg.detach(), e.clone());
             }
         }
     }
 }
 
+/// Reports whether `error` indicates that the broker will never accept the
+/// message that caused it, no matter how often it is retried.
+fn is_dead_letter_error(error: &KafkaError) -> bool {
+    matches!(
+        error,
+        KafkaError::MessageProduction(
+            RDKafkaErrorCode::MessageSizeTooLarge
+                | RDKafkaErrorCode::RecordListTooLarge
+                | RDKafkaErrorCode::InvalidRecord
+        )
+    )
+}
+
 struct KafkaSinkToken {
     shutdown_flag: Arc<AtomicBool>,
 }
@@ -345,6 +501,7 @@ struct ProgressInitState {
     topic: String,
     key: String,
     progress_client_config: rdkafka::ClientConfig,
+    client_context: MzClientContext,
 }
 
 impl ProgressInitState {
@@ -364,6 +521,94 @@ struct ProgressRunningState {
     gate_ts: Rc<Cell<Option<Timestamp>>>,
 }
 
+/// The persist shard that durably records the progress of a sink, keyed by
+/// the sink rather than by the topics it writes to, so that the sink resumes
+/// exactly-once even after its topics are migrated.
+///
+/// The shard holds no data. Its upper is one past the latest timestamp whose
+/// transaction the sink committed.
+struct ProgressShard {
+    write_handle: WriteHandle<(), (), Timestamp, Diff>,
+}
+
+impl ProgressShard {
+    async fn open(
+        persist_clients: &Arc<Mutex<PersistClientCache>>,
+        location: PersistLocation,
+        shard: ShardId,
+    ) -> Result<Self, anyhow::Error> {
+        let persist_client = persist_clients
+            .lock()
+            .await
+            .open(location)
+            .await
+            .context("error creating persist client for progress shard")?;
+        let write_handle = persist_client
+            .open_writer(shard)
+            .await
+            .context("error opening progress shard")?;
+        Ok(ProgressShard { write_handle })
+    }
+
+    /// Returns the latest timestamp the shard records as committed, if any.
+    async fn latest_ts(&mut self) -> Option<Timestamp> {
+        let upper = self.write_handle.fetch_recent_upper().await;
+        upper
+            .as_option()
+            .expect("progress shards are never closed")
+            .step_back()
+    }
+
+    /// Records that the sink committed all timestamps up to and including
+    /// `ts`.
+    async fn record(&mut self, ts: Timestamp) {
+        let new_upper = Antichain::from_elem(ts.step_forward());
+        loop {
+            let expected_upper = self.write_handle.upper().clone();
+            if !PartialOrder::less_than(&expected_upper, &new_upper) {
+                return;
+            }
+            let updates: [(((), ()), Timestamp, Diff); 0] = [];
+            match self
+                .write_handle
+                .compare_and_append(updates, expected_upper, new_upper.clone())
+                .await
+            {
+                Ok(Ok(Ok(()))) => return,
+                // The upper moved, which happens when a previous incarnation
+                // of the sink recorded its progress. Retry from the new upper.
+                Ok(Ok(Err(_))) => {
+                    self.write_handle.fetch_recent_upper().await;
+                }
+                Ok(Err(invalid_use)) => panic!("compare_and_append failed: {invalid_use}"),
+                // Retrying is safe whether or not the append went through, as
+                // recording the same progress twice is a no-op.
+                Err(external_err) => {
+                    warn!("recording progress in progress shard failed: {external_err}");
+                    self.write_handle.fetch_recent_upper().await;
+                }
+            }
+        }
+    }
+}
+
+/// The partition to which sinks with a CDCv2 envelope publish all their
+/// messages.
+///
+/// A CDCv2 consumer must read the progress statements in order with the
+/// updates they cover, which Kafka only guarantees within a partition.
+const CDC_V2_PARTITION: i32 = 0;
+
+/// The state needed to publish the progress statements of a sink with a CDCv2
+/// envelope.
+struct CdcV2Progress {
+    encoder: cdc_v2::Encoder,
+    value_schema_id: i32,
+    /// The upper of the latest progress statement, which is the lower of the
+    /// next one.
+    upper: Cell<Timestamp>,
+}
+
 #[derive(Debug, Clone)]
 enum KafkaSinkStateEnum {
     // Initialize ourselves as a transactional producer with Kafka
@@ -391,27 +636,92 @@ impl KafkaSinkStateEnum {
 
 struct KafkaSinkState {
     name: String,
+    sink_id: GlobalId,
     topic: String,
+    /// The keys of the headers whose values are attached to each row.
+    header_keys: Vec<String>,
+    /// Whether rows are assigned to partitions by a `PARTITION BY`
+    /// expression.
+    has_partition_by: bool,
+    /// The number of partitions of `topic`, once fetched.
+    ///
+    /// Only fetched when the sink has a `PARTITION BY` expression, and only
+    /// once, at startup. Partitions added to the topic later are not written
+    /// to by the expression until the sink restarts.
+    partition_count: Option<u64>,
     metrics: Arc<SinkMetrics>,
     producer: KafkaTxProducer,
     activator: timely::scheduling::Activator,
     pending_rows: HashMap<Timestamp, Vec<EncodedRow>>,
-    ready_rows: VecDeque<(Timestamp, Vec<EncodedRow>)>,
+    /// When the first row at each timestamp in `pending_rows` arrived.
+    pending_since: HashMap<Timestamp, Instant>,
+    /// Rows at closed timestamps, with when the first of them arrived.
+    ready_rows: VecDeque<(Timestamp, Instant, Vec<EncodedRow>)>,
+    /// The number of message bytes to publish per transaction, adapted to
+    /// the observed latency of the producer and of committing.
+    transaction_bytes: AdaptiveBatchSize,
     retry_manager: Arc<Mutex<KafkaSinkSendRetryManager>>,
     sink_state: KafkaSinkStateEnum,
 
     /// Timestamp of the latest progress record that was written out to Kafka.
     latest_progress_ts: Timestamp,
 
+    /// The shard that durably records the progress of this sink, opened when
+    /// the sink initializes.
+    progress_shard: Option<ProgressShard>,
+
     /// Write frontier of this sink.
     ///
     /// The write frontier potentially blocks compaction of timestamp bindings
-    /// in upstream sources. The latest written progress record is used when
-    /// restarting the sink to gate updates with a lower timestamp. We advance
-    /// the write frontier in lockstep with writing out progress records. This
-    /// ensures that we don't write updates more than once, ensuring
-    /// exactly-once guarantees.
+    /// in upstream sources. The latest progress recorded in the progress shard
+    /// is used when restarting the sink to gate updates with a lower
+    /// timestamp. We advance the write frontier in lockstep with recording
+    /// progress. This ensures that we don't write updates more than once,
+    /// ensuring exactly-once guarantees.
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+
+    /// Counts of the attempts to publish progress records, keyed by sink,
+    /// duration bucket, and whether the attempt failed, that have yet to be
+    /// reported to the controller.
+    progress_publishes: Rc<RefCell<HashMap<(GlobalId, u64, bool), i64>>>,
+
+    /// The topic to which messages that cannot be delivered to `topic` are
+    /// published, if any.
+    dead_letter_topic: Option<String>,
+
+    /// Counts of the messages published to `dead_letter_topic`, keyed by sink
+    /// and error, that have yet to be reported to the controller.
+    dead_letters: Rc<RefCell<HashMap<(GlobalId, String), i64>>>,
+
+    /// The metrics of this sink that have yet to be reported to the
+    /// controller.
+    metrics_updates: Rc<RefCell<HashMap<GlobalId, SinkMetricsUpdate>>>,
+
+    /// The progress statement state, if the sink has a CDCv2 envelope.
+    cdc_v2: Option<CdcV2Progress>,
+
+    /// How messages that the broker rejects are retried.
+    retry: KafkaSinkRetryPolicy,
+
+    /// How rows are fanned out to a topic per value of a column, if at all.
+    topic_fanout: Option<KafkaSinkTopicFanout>,
+    /// The client that creates the topics that rows fan out to, if the sink
+    /// fans rows out.
+    admin_client: Option<AdminClient<MzClientContext>>,
+    /// The topics that rows fan out to that are known to exist.
+    fanout_topics: HashSet<String>,
+    /// The retention with which the topics that rows fan out to are created.
+    retention: KafkaSinkConnectionRetention,
+
+    /// Whether the sink hit an error that retrying cannot fix, which stops
+    /// the sink for good.
+    failed: bool,
+
+    /// The changes of this sink's status that have yet to be reported to the
+    /// controller.
+    status_updates: Rc<RefCell<Vec<(GlobalId, SinkStatusUpdate)>>>,
+
+    now: NowFn,
 }
 
 impl KafkaSinkState {
@@ -424,6 +734,12 @@ impl KafkaSinkState {
         write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
         metrics: &KafkaBaseMetrics,
         connection_context: &ConnectionContext,
+        progress_publishes: Rc<RefCell<HashMap<(GlobalId, u64, bool), i64>>>,
+        dead_letters: Rc<RefCell<HashMap<(GlobalId, String), i64>>>,
+        metrics_updates: Rc<RefCell<HashMap<GlobalId, SinkMetricsUpdate>>>,
+        status_updates: Rc<RefCell<Vec<(GlobalId, SinkStatusUpdate)>>>,
+        now: NowFn,
+        cdc_v2: Option<CdcV2Progress>,
     ) -> Self {
         let transactional_id = format!("mz-producer-{sink_id}-{worker_id}");
         let config =
@@ -440,6 +756,13 @@ impl KafkaSinkState {
 
         let retry_manager = Arc::new(Mutex::new(KafkaSinkSendRetryManager::new()));
 
+        let client_context = TokioHandle::current()
+            .block_on(connection.connection.client_context(
+                &*connection_context.secrets_reader,
+                connection_context.aws_external_id_prefix.as_ref(),
+            ))
+            .expect("creating kafka client context for Kafka sink failed");
+
         let producer = KafkaTxProducer {
             name: sink_name.clone(),
             inner: Arc::new(
@@ -447,30 +770,75 @@ impl KafkaSinkState {
                     .create_with_context::<_, ThreadedProducer<_>>(SinkProducerContext::new(
                         Arc::clone(&metrics),
                         Arc::clone(&retry_manager),
+                        connection
+                            .dead_letter_topic
+                            .as_ref()
+                            .map(|_| connection.topic.clone()),
+                        client_context.clone(),
                     ))
                     .expect("creating kafka producer for Kafka sink failed"),
             ),
             timeout: Duration::from_secs(5),
         };
 
+        let admin_client = connection.topic_fanout.as_ref().map(|_| {
+            let mut config = create_new_client_config(connection_context.librdkafka_log_level);
+            TokioHandle::current().block_on(
+                connection.populate_client_config(&mut config, &*connection_context.secrets_reader),
+            );
+            config
+                .create_with_context(client_context.clone())
+                .expect("creating kafka admin client for Kafka sink failed")
+        });
+
         let sink_state = KafkaSinkStateEnum::Init(Some(ProgressInitState {
             topic: connection.progress.topic,
             key: format!("mz-sink-{sink_id}"),
             progress_client_config,
+            client_context,
         }));
 
         KafkaSinkState {
             name: sink_name,
+            sink_id: *sink_id,
             topic: connection.topic,
+            header_keys: connection
+                .headers
+                .into_iter()
+                .map(|header| header.key)
+                .collect(),
+            has_partition_by: connection.partition_by.is_some(),
+            partition_count: None,
             metrics,
             producer,
             activator,
             pending_rows: HashMap::new(),
+            pending_since: HashMap::new(),
             ready_rows: VecDeque::new(),
+            transaction_bytes: AdaptiveBatchSize::new(
+                TRANSACTION_TARGET,
+                1 << 20,
+                64 << 10,
+                64 << 20,
+            ),
             retry_manager,
             sink_state,
             latest_progress_ts: Timestamp::minimum(),
+            progress_shard: None,
             write_frontier,
+            progress_publishes,
+            dead_letter_topic: connection.dead_letter_topic,
+            dead_letters,
+            metrics_updates,
+            cdc_v2,
+            retry: connection.retry,
+            topic_fanout: connection.topic_fanout,
+            admin_client,
+            fanout_topics: HashSet::new(),
+            retention: connection.retention,
+            failed: false,
+            status_updates,
+            now,
         }
     }
 
@@ -508,6 +876,19 @@ impl KafkaSinkState {
         // if it makes a big difference
         config.set("queue.buffering.max.ms", &format!("{}", 10));
 
+        // `linger.ms` is an alias of `queue.buffering.max.ms`, so a
+        // user-specified value overrides the default above.
+        if let Some(linger_ms) = connection.linger_ms {
+            config.set("linger.ms", &format!("{}", linger_ms));
+        }
+        if let Some(batch_num_messages) = connection.batch_num_messages {
+            config.set("batch.num.messages", &format!("{}", batch_num_messages));
+        }
+        config.set(
+            "compression.type",
+            connection.compression_type.as_librdkafka_str(),
+        );
+
         config.set("transactional.id", transactional_id);
 
         config
@@ -560,6 +941,63 @@ impl KafkaSinkState {
             .expect("retries infinitely")
     }
 
+    /// Commits the active transaction, which contains a progress record for
+    /// `ts` enqueued at `enqueued`, recording the duration and outcome of each
+    /// attempt, and then records the progress in the progress shard.
+    async fn commit_progress_txn(&mut self, enqueued: Instant, ts: Timestamp) {
+        self.retry_on_txn_error(|p| async move {
+            let result = p.commit_transaction().await;
+            self.record_progress_publish(enqueued.elapsed(), result.is_err());
+            result
+        })
+        .await;
+        self.progress_shard
+            .as_mut()
+            .expect("progress shard opened before progress is committed")
+            .record(ts)
+            .await;
+    }
+
+    fn record_progress_publish(&self, duration: Duration, failed: bool) {
+        if failed {
+            warn!(
+                "{}: failed to commit progress record after {:?}",
+                self.name, duration
+            );
+            self.metrics.progress_publish_errors_counter.inc();
+        } else {
+            self.metrics
+                .progress_publish_seconds
+                .observe(duration.as_secs_f64());
+        }
+        let duration_ns = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        *self
+            .progress_publishes
+            .borrow_mut()
+            .entry((self.sink_id, duration_ns.next_power_of_two(), failed))
+            .or_default() += 1;
+    }
+
+    /// Records that the sink committed `messages` messages totalling `bytes`
+    /// bytes, to be reported to the controller.
+    fn record_committed(&self, messages: u64, bytes: u64) {
+        let mut updates = self.metrics_updates.borrow_mut();
+        let update = updates.entry(self.sink_id).or_default();
+        update.messages += messages;
+        update.bytes += bytes;
+    }
+
+    /// Records the sink's current write frontier and `input_frontier`, to be
+    /// reported to the controller.
+    fn record_frontiers(&self, input_frontier: &Antichain<Timestamp>) {
+        let mut updates = self.metrics_updates.borrow_mut();
+        let update = updates.entry(self.sink_id).or_default();
+        update
+            .committed_frontier
+            .clone_from(&self.write_frontier.borrow());
+        update.input_frontier.clone_from(input_frontier);
+    }
+
     async fn abort_active_txn(&self) {
         Retry::default()
             .clamp_backoff(BACKOFF_CLAMP)
@@ -579,7 +1017,32 @@ impl KafkaSinkState {
             .expect("retries infinitely");
     }
 
-    async fn send<'a, K, P>(&self, mut record: BaseRecord<'a, K, P>)
+    async fn send<'a, K, P>(&self, record: BaseRecord<'a, K, P>)
+    where
+        K: ToBytes + ?Sized,
+        P: ToBytes + ?Sized,
+    {
+        if let Err((e, record)) = self.try_send(record).await {
+            match &self.dead_letter_topic {
+                Some(dead_letter_topic)
+                    if is_dead_letter_error(&e) && record.topic == self.topic =>
+                {
+                    self.send_dead_letter(dead_letter_topic, record, &e).await
+                }
+                // We've received an error that is not transient
+                _ => panic!("fatal error while producing message in {}: {e}", self.name),
+            }
+        }
+    }
+
+    /// Enqueues `record` with the producer, retrying for as long as the
+    /// producer's queue is full.
+    ///
+    /// Returns any other error, along with the record.
+    async fn try_send<'a, K, P>(
+        &self,
+        mut record: BaseRecord<'a, K, P>,
+    ) -> Result<(), (KafkaError, BaseRecord<'a, K, P>)>
     where
         K: ToBytes + ?Sized,
         P: ToBytes + ?Sized,
@@ -595,10 +1058,9 @@ impl KafkaSinkState {
                 Ok(()) => {
                     self.metrics.messages_sent_counter.inc();
                     self.retry_manager.lock().await.record_send();
-                    return;
+                    return Ok(());
                 }
                 Err((e, rec)) => {
-                    record = rec;
                     self.metrics.message_send_errors_counter.inc();
 
                     if let KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull) = e {
@@ -606,26 +1068,159 @@ impl KafkaSinkState {
                             "unable to produce message in {}: rdkafka queue full; will retry",
                             self.name
                         );
+                        record = rec;
                         continue;
                     } else {
-                        // We've received an error that is not transient
-                        panic!("fatal error while producing message in {}: {e}", self.name,);
+                        return Err((e, rec));
                     }
                 }
             }
         }
     }
 
-    async fn flush(&self) {
+    /// Publishes `record`, which could not be delivered to the sink topic
+    /// because of `error`, to the dead letter topic instead.
+    ///
+    /// The dead letter message carries the key and headers of the original
+    /// message, along with headers that describe the error. Its payload is
+    /// omitted if the original payload is what the broker rejected as too
+    /// large.
+    async fn send_dead_letter<'a, K, P>(
+        &self,
+        dead_letter_topic: &'a str,
+        record: BaseRecord<'a, K, P>,
+        error: &KafkaError,
+    ) where
+        K: ToBytes + ?Sized,
+        P: ToBytes + ?Sized,
+    {
+        let error_str = error.to_string();
+        let headers = record
+            .headers
+            .unwrap_or_else(OwnedHeaders::new)
+            .insert(Header {
+                key: KAFKA_SINK_ERROR_HEADER,
+                value: Some(error_str.as_bytes()),
+            })
+            .insert(Header {
+                key: KAFKA_SINK_TOPIC_HEADER,
+                value: Some(self.topic.as_bytes()),
+            });
+        let mut dead_letter = BaseRecord::to(dead_letter_topic).headers(headers);
+        dead_letter.key = record.key;
+        dead_letter.timestamp = record.timestamp;
+        if !matches!(
+            error,
+            KafkaError::MessageProduction(
+                RDKafkaErrorCode::MessageSizeTooLarge | RDKafkaErrorCode::RecordListTooLarge
+            )
+        ) {
+            dead_letter.payload = record.payload;
+        }
+
+        if let Err((e, _)) = self.try_send(dead_letter).await {
+            panic!(
+                "fatal error while producing message to dead letter topic {} in {}: {e}",
+                dead_letter_topic, self.name,
+            );
+        }
+
+        warn!(
+            "{}: published message to dead letter topic {}: {}",
+            self.name, dead_letter_topic, error_str
+        );
+        *self
+            .dead_letters
+            .borrow_mut()
+            .entry((self.sink_id, error_str))
+            .or_default() += 1;
+    }
+
+    /// Records that the sink's status changed to `status`.
+    fn record_status(&self, status: SinkStatus, error: Option<String>, details: Option<String>) {
+        self.status_updates.borrow_mut().push((
+            self.sink_id,
+            SinkStatusUpdate {
+                occurred_at: (self.now)(),
+                status,
+                error,
+                details,
+            },
+        ));
+    }
+
+    /// Stops the sink for good after an error that retrying cannot fix, like
+    /// its retry policy giving up on delivering a message.
+    ///
+    /// The sink discards its input from then on and never advances its write
+    /// frontier again, so that it resumes from its last committed progress
+    /// record when it is recreated.
+    async fn fail(&mut self, reason: String, abort_txn: bool) {
+        error!("{}: {}", self.name, reason);
+        self.record_status(SinkStatus::Failed, Some(reason), None);
+        if abort_txn {
+            self.abort_active_txn().await;
+        }
+        self.pending_rows.clear();
+        self.pending_since.clear();
+        self.ready_rows.clear();
+        self.failed = true;
+    }
+
+    /// Flushes the messages sent so far, retrying those that the broker
+    /// rejected according to the sink's retry policy.
+    ///
+    /// Returns the reason the sink failed if the retry policy gave up on
+    /// delivering a message.
+    async fn flush(&self) -> Result<(), String> {
         self.flush_inner().await;
+        let mut attempt = 0;
         while !{
             let mut guard = self.retry_manager.lock().await;
             guard.sends_flushed()
         } {
-            while let Some(msg) = {
-                let mut guard = self.retry_manager.lock().await;
-                guard.pop_retry()
-            } {
+            let mut retries = self.retry_manager.lock().await.take_retries();
+            if let Some((_, error)) = retries.last() {
+                attempt += 1;
+                let error = error.to_string();
+                if self.retry.max_attempts.map_or(false, |max| attempt > max) {
+                    // Only data messages can be diverted to the dead letter
+                    // topic. The sink fails if any other message is exhausted.
+                    let divert = self.retry.exhausted_action
+                        == KafkaSinkRetryExhaustedAction::DeadLetter
+                        && retries.iter().all(|(msg, _)| msg.topic() == self.topic);
+                    if !divert {
+                        let reason = format!(
+                            "gave up delivering {} messages after {} retries: {}",
+                            retries.len(),
+                            attempt - 1,
+                            error
+                        );
+                        return Err(reason);
+                    }
+                    warn!(
+                        "{}: publishing {} messages to dead letter topic after {} retries",
+                        self.name,
+                        retries.len(),
+                        attempt - 1
+                    );
+                    self.retry_manager
+                        .lock()
+                        .await
+                        .divert_to_dead_letters(std::mem::take(&mut retries));
+                } else {
+                    let backoff = self.retry.backoff(attempt);
+                    let next_retry_at = (self.now)()
+                        .saturating_add(u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX));
+                    let details = serde_json::json!({
+                        "attempt": attempt,
+                        "next_retry_at": next_retry_at,
+                    });
+                    self.record_status(SinkStatus::Stalled, Some(error), Some(details.to_string()));
+                    tokio::time::sleep(backoff).await;
+                }
+            }
+            for (msg, _error) in retries {
                 let mut transformed_msg = BaseRecord::to(msg.topic());
                 transformed_msg = match msg.key() {
                     Some(k) => transformed_msg.key(k),
@@ -635,10 +1230,115 @@ impl KafkaSinkState {
                     Some(p) => transformed_msg.payload(p),
                     None => transformed_msg,
                 };
+                transformed_msg = match msg.headers() {
+                    Some(h) => transformed_msg.headers(h.clone()),
+                    None => transformed_msg,
+                };
+                if self.has_partition_by && msg.partition() >= 0 {
+                    transformed_msg = transformed_msg.partition(msg.partition());
+                }
                 self.send(transformed_msg).await;
             }
+            while let Some((msg, error)) = {
+                let mut guard = self.retry_manager.lock().await;
+                guard.pop_dead_letter()
+            } {
+                let dead_letter_topic = self
+                    .dead_letter_topic
+                    .as_deref()
+                    .expect("dead letters are only recorded with a dead letter topic");
+                let mut record = BaseRecord::<[u8], [u8]>::to(msg.topic());
+                record.key = msg.key();
+                record.payload = msg.payload();
+                record.headers = msg.headers().cloned();
+                if let Some(timestamp) = msg.timestamp().to_millis() {
+                    record.timestamp = Some(timestamp);
+                }
+                self.send_dead_letter(dead_letter_topic, record, &error)
+                    .await;
+            }
             self.flush_inner().await;
         }
+        if attempt > 0 {
+            self.record_status(SinkStatus::Running, None, None);
+        }
+        Ok(())
+    }
+
+    /// Creates the topics that the rows of `batch` fan out to, if the sink
+    /// fans rows out and the topics are not known to exist.
+    ///
+    /// Returns the reason the sink failed if the column value of a row does
+    /// not form a valid topic name.
+    async fn ensure_fanout_topics(
+        &mut self,
+        batch: &[(Timestamp, Instant, Vec<EncodedRow>)],
+    ) -> Result<(), String> {
+        let fanout = match &self.topic_fanout {
+            Some(fanout) => fanout,
+            None => return Ok(()),
+        };
+        let mut new_topics = BTreeSet::new();
+        for row in batch.iter().flat_map(|(_, _, rows)| rows) {
+            if let Some(value) = &row.message.topic_value {
+                let topic = fanout.topic(value)?;
+                if !self.fanout_topics.contains(&topic) {
+                    new_topics.insert(topic);
+                }
+            }
+        }
+        let admin_client = self
+            .admin_client
+            .as_ref()
+            .expect("created for sinks that fan rows out");
+        for topic in &new_topics {
+            Retry::default()
+                .max_tries(usize::MAX)
+                .clamp_backoff(BACKOFF_CLAMP)
+                .retry_async(|_| {
+                    ensure_kafka_topic(
+                        admin_client,
+                        topic,
+                        fanout.partition_count,
+                        fanout.replication_factor,
+                        self.retention,
+                        false,
+                        &fanout.topic_config,
+                    )
+                })
+                .await
+                .expect("retries infinitely");
+            info!("{}: ensured topic {} exists", self.name, topic);
+        }
+        self.fanout_topics.extend(new_topics);
+        Ok(())
+    }
+
+    /// Fetches the number of partitions of the sink topic, among which the
+    /// sink's `PARTITION BY` expression selects.
+    async fn fetch_partition_count(&self) -> u64 {
+        let partitions = Retry::default()
+            .max_tries(usize::MAX)
+            .clamp_backoff(BACKOFF_CLAMP)
+            .retry_async(|_| async {
+                let producer = Arc::clone(&self.producer.inner);
+                let topic = self.topic.clone();
+                task::spawn_blocking(
+                    || format!("get_partitions:{}", self.name),
+                    move || {
+                        mz_kafka_util::client::get_partitions(
+                            producer.client(),
+                            &topic,
+                            Duration::from_secs(10),
+                        )
+                    },
+                )
+                .await
+                .unwrap_or_else(|e| bail!(e))
+            })
+            .await
+            .expect("retries infinitely");
+        u64::cast_from(partitions.len())
     }
 
     async fn flush_inner(&self) {
@@ -654,7 +1354,7 @@ impl KafkaSinkState {
         // Polls a message from a Kafka Source.  Blocking so should always be called on background
         // thread.
         fn get_next_message(
-            consumer: &mut BaseConsumer,
+            consumer: &mut BaseConsumer<MzClientContext>,
             timeout: Duration,
         ) -> Result<Option<(Vec<u8>, Vec<u8>, i64)>, anyhow::Error> {
             if let Some(result) = consumer.poll(timeout) {
@@ -681,10 +1381,11 @@ impl KafkaSinkState {
             progress_topic: &str,
             progress_key: &str,
             config: &ClientConfig,
+            client_context: MzClientContext,
             timeout: Duration,
         ) -> Result<Option<Timestamp>, anyhow::Error> {
             let mut consumer = config
-                .create::<BaseConsumer>()
+                .create_with_context::<_, BaseConsumer<_>>(client_context)
                 .context("creating consumer client failed")?;
 
             // ensure the progress topic has exactly one partition
@@ -776,6 +1477,7 @@ impl KafkaSinkState {
             topic,
             key,
             progress_client_config,
+            client_context,
         })) = &self.sink_state
         {
             // Only actually used for retriable errors.
@@ -785,6 +1487,7 @@ impl KafkaSinkState {
                     let topic = topic.clone();
                     let key = key.clone();
                     let progress_client_config = progress_client_config.clone();
+                    let client_context = client_context.clone();
                     task::spawn_blocking(
                         || format!("get_latest_ts:{}", self.name),
                         move || {
@@ -792,6 +1495,7 @@ impl KafkaSinkState {
                                 &topic,
                                 &key,
                                 &progress_client_config,
+                                client_context,
                                 Duration::from_secs(10),
                             )
                         },
@@ -819,6 +1523,43 @@ impl KafkaSinkState {
         self.send(record).await
     }
 
+    /// Publishes a CDCv2 progress statement that advances the sink's output to
+    /// `upper`, with the number of updates published at each of the times in
+    /// `counts`, if the sink has a CDCv2 envelope.
+    ///
+    /// This must happen in the same transaction as the progress record for the
+    /// time before `upper`, so that the statements resume seamlessly from the
+    /// gate timestamp after a restart.
+    async fn send_cdc_v2_progress(&self, upper: Timestamp, counts: &[(Timestamp, usize)]) {
+        let cdc_v2 = match &self.cdc_v2 {
+            Some(cdc_v2) => cdc_v2,
+            None => return,
+        };
+        let lower = cdc_v2.upper.get();
+        if upper <= lower {
+            return;
+        }
+        let to_i64 = |ts: Timestamp| i64::try_from(ts).expect("timestamp fits in i64");
+        let counts: Vec<_> = counts
+            .iter()
+            .map(|(time, count)| {
+                let count = i64::try_from(*count).expect("count fits in i64");
+                (to_i64(*time), count)
+            })
+            .collect();
+        let progress = cdc_v2
+            .encoder
+            .encode_progress(&[to_i64(lower)], &[to_i64(upper)], &counts);
+        let encoded = cdc_v2
+            .encoder
+            .encode_unchecked(cdc_v2.value_schema_id, &progress);
+        let record = BaseRecord::<Vec<u8>, _>::to(&self.topic)
+            .payload(&encoded)
+            .partition(CDC_V2_PARTITION);
+        self.send(record).await;
+        cdc_v2.upper.set(upper);
+    }
+
     /// Asserts that the write frontier has not yet advanced beyond `t`.
     fn assert_progress(&self, ts: &Timestamp) {
         assert!(self.write_frontier.borrow().less_equal(ts));
@@ -844,10 +1585,6 @@ impl KafkaSinkState {
     /// This will emit a progress record to the progress topic if the frontier
     /// advanced and advance the maintained write frontier, which will in turn
     /// unblock compaction of timestamp bindings in sources.
-    ///
-    /// *NOTE*: Progress records will only be emitted when
-    /// `KafkaSinkConnection.consistency` points to a progress topic. The
-    /// write frontier will be advanced regardless.
     async fn maybe_emit_progress(
         &mut self,
         mut input_frontier: Antichain<Timestamp>,
@@ -903,10 +1640,13 @@ impl KafkaSinkState {
                         "{}: sending progress for gate ts: {:?}",
                         &self.name, min_frontier
                     );
+                    self.send_cdc_v2_progress(min_frontier.step_forward(), &[])
+                        .await;
+                    let enqueued = Instant::now();
                     self.send_progress_record(min_frontier, progress_state)
                         .await;
 
-                    self.retry_on_txn_error(|p| p.commit_transaction()).await;
+                    self.commit_progress_txn(enqueued, min_frontier).await;
                     progress_emitted = true;
                 }
                 self.latest_progress_ts = min_frontier;
@@ -935,9 +1675,180 @@ impl KafkaSinkState {
 
 #[derive(Debug)]
 struct EncodedRow {
+    message: EncodedMessage,
+    count: usize,
+}
+
+/// A message encoded for the sink topic.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EncodedMessage {
     key: Option<Vec<u8>>,
     value: Option<Vec<u8>>,
-    count: usize,
+    /// The values of the sink's headers, in the order of their keys.
+    headers: Vec<Option<Vec<u8>>>,
+    /// The value of the sink's `PARTITION BY` expression, if any.
+    partition: Option<u64>,
+    /// The timestamp of the message, in milliseconds since the Unix epoch, if
+    /// it is not to be assigned by the producer.
+    timestamp: Option<i64>,
+    /// The value of the sink's `TOPIC COLUMN`, which selects the topic of
+    /// the message, if any. Messages without one go to the sink topic.
+    topic_value: Option<String>,
+}
+
+/// The number of message bytes that publishing `row` produces.
+fn encoded_row_bytes(row: &EncodedRow) -> u64 {
+    let message = &row.message;
+    let len =
+        message.key.as_ref().map_or(0, |k| k.len()) + message.value.as_ref().map_or(0, |v| v.len());
+    u64::cast_from(len.saturating_mul(row.count))
+}
+
+/// Extracts the values of a Kafka sink's headers, of its `PARTITION BY`
+/// expression, and of its message timestamps from the rows it emits.
+#[derive(Clone, Debug)]
+struct MetadataEncoder {
+    /// The index and type of the column of each header.
+    header_columns: Vec<(usize, ScalarType)>,
+    /// The expression that selects the partition of each row, if any.
+    partition_by: Option<MirScalarExpr>,
+    /// The source of the timestamp of each message, if any.
+    message_timestamp: Option<KafkaSinkMessageTimestamp>,
+    /// The index of the column whose value selects the topic of each
+    /// message, if any.
+    topic_column: Option<usize>,
+    /// Whether the rows are wrapped in a Debezium envelope.
+    debezium: bool,
+}
+
+impl MetadataEncoder {
+    fn new(connection: &KafkaSinkConnection, envelope: Option<SinkEnvelope>) -> MetadataEncoder {
+        let column_types = &connection.value_desc.typ().column_types;
+        MetadataEncoder {
+            header_columns: connection
+                .headers
+                .iter()
+                .map(|header| {
+                    let typ = column_types[header.column].scalar_type.clone();
+                    (header.column, typ)
+                })
+                .collect(),
+            partition_by: connection.partition_by.clone(),
+            message_timestamp: connection.message_timestamp.clone(),
+            topic_column: connection.topic_fanout.as_ref().map(|fanout| fanout.column),
+            debezium: matches!(envelope, Some(SinkEnvelope::Debezium)),
+        }
+    }
+
+    /// Encodes the message for an update to `key` and `value` at `time`.
+    ///
+    /// The metadata of the message is taken from the `after` record of
+    /// Debezium-enveloped rows, or from the `before` record of deletions.
+    /// Rows without a value have no header values, no partition, and no
+    /// timestamp unless timestamps are taken from `time`. Rows whose
+    /// `PARTITION BY` expression evaluates to `NULL` or to an error have no
+    /// partition, and are assigned one by the producer's default partitioner.
+    /// Rows whose timestamp column is `NULL` or before the Unix epoch are
+    /// assigned a timestamp by the producer. Rows whose topic column is
+    /// `NULL` are published to the sink topic.
+    fn encode(
+        &self,
+        encoder: &impl Encode,
+        key: Option<Row>,
+        value: Option<Row>,
+        time: Timestamp,
+    ) -> EncodedMessage {
+        let (headers, partition, timestamp, topic_value) =
+            self.encode_metadata(value.as_ref(), time);
+        EncodedMessage {
+            key: key.map(|key| encoder.encode_key_unchecked(key)),
+            value: value.map(|value| encoder.encode_value_unchecked(value)),
+            headers,
+            partition,
+            timestamp,
+            topic_value,
+        }
+    }
+
+    fn encode_metadata(
+        &self,
+        value: Option<&Row>,
+        time: Timestamp,
+    ) -> (
+        Vec<Option<Vec<u8>>>,
+        Option<u64>,
+        Option<i64>,
+        Option<String>,
+    ) {
+        let timestamp_column = match self.message_timestamp {
+            Some(KafkaSinkMessageTimestamp::Column(column)) => Some(column),
+            _ => None,
+        };
+        let mz_timestamp = match self.message_timestamp {
+            Some(KafkaSinkMessageTimestamp::MzTimestamp) => i64::try_from(u64::from(time)).ok(),
+            _ => None,
+        };
+        if self.header_columns.is_empty()
+            && self.partition_by.is_none()
+            && timestamp_column.is_none()
+            && self.topic_column.is_none()
+        {
+            return (vec![], None, mz_timestamp, None);
+        }
+        let value = match value {
+            Some(value) => value,
+            None => return (vec![], None, mz_timestamp, None),
+        };
+        let datums: Vec<Datum> = if self.debezium {
+            let mut iter = value.iter();
+            let before = iter.next().unwrap_or(Datum::Null);
+            let after = iter.next().unwrap_or(Datum::Null);
+            match (before, after) {
+                (_, Datum::List(record)) | (Datum::List(record), Datum::Null) => {
+                    record.iter().collect()
+                }
+                _ => return (vec![], None, mz_timestamp, None),
+            }
+        } else {
+            value.iter().collect()
+        };
+        let headers = self
+            .header_columns
+            .iter()
+            .map(|(column, typ)| match datums[*column] {
+                Datum::Null => None,
+                Datum::Bytes(bytes) => Some(bytes.to_vec()),
+                Datum::String(s) => Some(s.as_bytes().to_vec()),
+                datum => mz_pgrepr::Value::from_datum(datum, typ).map(|value| {
+                    let mut buf = bytes::BytesMut::new();
+                    value.encode_text(&mut buf);
+                    buf.to_vec()
+                }),
+            })
+            .collect();
+        let temp_storage = RowArena::new();
+        let partition =
+            self.partition_by
+                .as_ref()
+                .and_then(|expr| match expr.eval(&datums, &temp_storage) {
+                    Ok(Datum::UInt64(partition)) => Some(partition),
+                    _ => None,
+                });
+        let timestamp = match timestamp_column {
+            Some(column) => match datums[column] {
+                Datum::Timestamp(ts) => Some(ts.timestamp_millis()),
+                Datum::TimestampTz(ts) => Some(ts.timestamp_millis()),
+                _ => None,
+            }
+            .filter(|millis| *millis >= 0),
+            None => mz_timestamp,
+        };
+        let topic_value = self.topic_column.and_then(|column| match datums[column] {
+            Datum::String(s) => Some(s.to_owned()),
+            _ => None,
+        });
+        (headers, partition, timestamp, topic_value)
+    }
 }
 
 // TODO@jldlaughlin: What guarantees does this sink support? #1728
@@ -948,8 +1859,16 @@ fn kafka<G>(
     envelope: Option<SinkEnvelope>,
     as_of: SinkAsOf,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    persist_clients: Arc<Mutex<PersistClientCache>>,
+    persist_location: PersistLocation,
+    progress_shard: ShardId,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
+    progress_publishes: Rc<RefCell<HashMap<(GlobalId, u64, bool), i64>>>,
+    dead_letters: Rc<RefCell<HashMap<(GlobalId, String), i64>>>,
+    metrics_updates: Rc<RefCell<HashMap<GlobalId, SinkMetricsUpdate>>>,
+    status_updates: Rc<RefCell<Vec<(GlobalId, SinkStatusUpdate)>>>,
+    now: NowFn,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -965,44 +1884,174 @@ where
         .as_ref()
         .map(|(desc, _indices)| desc.clone());
     let value_desc = connection.value_desc.clone();
+    let metadata_encoder = MetadataEncoder::new(&connection, envelope);
 
+    let mut cdc_v2 = None;
     let encoded_stream = match connection.published_schema_info {
+        Some(PublishedSchemaInfo {
+            value_schema_id,
+            format: PublishedSchemaFormat::Avro,
+            ..
+        }) if envelope == Some(SinkEnvelope::CdcV2) => {
+            cdc_v2 = Some(CdcV2Progress {
+                encoder: cdc_v2::Encoder::new(value_desc.clone()),
+                value_schema_id,
+                upper: Cell::new(Timestamp::minimum()),
+            });
+            let encoder = cdc_v2::Encoder::new(value_desc);
+            encode_stream_with(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                format!("{}-cdcv2_encode", name),
+                move |(_key, value), time, diff| {
+                    let value = value.expect("CDCv2 updates always have a value");
+                    let time = i64::try_from(time).expect("timestamp fits in i64");
+                    let updates = encoder.encode_updates(&[(value, time, diff)]);
+                    let message = EncodedMessage {
+                        key: None,
+                        value: Some(encoder.encode_unchecked(value_schema_id, &updates)),
+                        headers: vec![],
+                        partition: None,
+                        timestamp: None,
+                        topic_value: None,
+                    };
+                    // The multiplicity is part of the encoded update, which
+                    // must be published exactly once.
+                    (message, 1)
+                },
+            )
+        }
         Some(PublishedSchemaInfo {
             key_schema_id,
             value_schema_id,
+            format: PublishedSchemaFormat::Avro,
+            ref key_fullname,
+            ref value_fullname,
+            ref value_schema,
+            ..
         }) => {
             let schema_generator = AvroSchemaGenerator::new(
-                None,
-                None,
+                key_fullname.as_deref(),
+                value_fullname.as_deref(),
                 key_desc,
                 value_desc,
                 matches!(envelope, Some(SinkEnvelope::Debezium)),
             );
-            let encoder = AvroEncoder::new(schema_generator, key_schema_id, value_schema_id);
+            let mut encoder = AvroEncoder::new(schema_generator, key_schema_id, value_schema_id);
+            if let Some(value_schema) = value_schema {
+                // The schema was validated against the sinked relation when
+                // the sink was planned.
+                let value_schema = mz_interchange::avro::parse_schema(value_schema)
+                    .expect("valid user-supplied value schema");
+                encoder = encoder
+                    .with_value_schema(value_schema)
+                    .expect("user-supplied value schema conforms to relation");
+            }
             encode_stream(
                 stream,
                 as_of.clone(),
                 Rc::clone(&shared_gate_ts),
                 encoder,
-                connection.fuel,
+                metadata_encoder.clone(),
                 name.clone(),
             )
         }
-        None => {
-            let encoder = JsonEncoder::new(
+        Some(PublishedSchemaInfo {
+            key_schema_id,
+            value_schema_id,
+            format: PublishedSchemaFormat::Protobuf,
+            ..
+        }) => {
+            let schema_generator = ProtobufSchemaGenerator::new(
                 key_desc,
                 value_desc,
                 matches!(envelope, Some(SinkEnvelope::Debezium)),
             );
+            let encoder = ProtobufEncoder::new(schema_generator, key_schema_id, value_schema_id);
+            encode_stream(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                encoder,
+                metadata_encoder.clone(),
+                name.clone(),
+            )
+        }
+        Some(PublishedSchemaInfo {
+            key_schema_id,
+            value_schema_id,
+            format: PublishedSchemaFormat::Json,
+            ..
+        }) => {
+            let encoder = JsonEncoder::new(
+                key_desc,
+                value_desc,
+                matches!(envelope, Some(SinkEnvelope::Debezium)),
+            )
+            .with_schema_ids(key_schema_id, value_schema_id);
             encode_stream(
                 stream,
                 as_of.clone(),
                 Rc::clone(&shared_gate_ts),
                 encoder,
-                connection.fuel,
+                metadata_encoder.clone(),
                 name.clone(),
             )
         }
+        None => match &connection.text_format {
+            Some(KafkaSinkTextFormat::Csv { delimiter, header }) => {
+                let mut encoder = CsvEncoder::new(
+                    key_desc,
+                    value_desc,
+                    matches!(envelope, Some(SinkEnvelope::Debezium)),
+                )
+                .with_delimiter(*delimiter);
+                if let Some(header) = header {
+                    encoder = encoder.with_header(header.clone());
+                }
+                encode_stream(
+                    stream,
+                    as_of.clone(),
+                    Rc::clone(&shared_gate_ts),
+                    encoder,
+                    metadata_encoder.clone(),
+                    name.clone(),
+                )
+            }
+            Some(text_format @ (KafkaSinkTextFormat::Text | KafkaSinkTextFormat::Bytes)) => {
+                // The planner ensures that the key and value have a single
+                // column of a suitable type.
+                let encoder = match text_format {
+                    KafkaSinkTextFormat::Text => TextEncoder::text(key_desc, value_desc),
+                    _ => TextEncoder::bytes(key_desc, value_desc),
+                }
+                .expect("validated by the planner");
+                encode_stream(
+                    stream,
+                    as_of.clone(),
+                    Rc::clone(&shared_gate_ts),
+                    encoder,
+                    metadata_encoder.clone(),
+                    name.clone(),
+                )
+            }
+            None => {
+                let encoder = JsonEncoder::new(
+                    key_desc,
+                    value_desc,
+                    matches!(envelope, Some(SinkEnvelope::Debezium)),
+                );
+                encode_stream(
+                    stream,
+                    as_of.clone(),
+                    Rc::clone(&shared_gate_ts),
+                    encoder,
+                    metadata_encoder.clone(),
+                    name.clone(),
+                )
+            }
+        },
     };
 
     produce_to_kafka(
@@ -1013,8 +2062,17 @@ where
         as_of,
         shared_gate_ts,
         write_frontier,
+        persist_clients,
+        persist_location,
+        progress_shard,
         metrics,
         connection_context,
+        progress_publishes,
+        dead_letters,
+        metrics_updates,
+        status_updates,
+        now,
+        cdc_v2,
     )
 }
 
@@ -1029,16 +2087,25 @@ where
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnection`] will be discarded without producing them.
-pub fn produce_to_kafka<G>(
-    stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
+fn produce_to_kafka<G>(
+    stream: Stream<G, (EncodedMessage, Timestamp, Diff)>,
     id: GlobalId,
     name: String,
     connection: KafkaSinkConnection,
     as_of: SinkAsOf,
     shared_gate_ts: Rc<Cell<Option<Timestamp>>>,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    persist_clients: Arc<Mutex<PersistClientCache>>,
+    persist_location: PersistLocation,
+    progress_shard: ShardId,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
+    progress_publishes: Rc<RefCell<HashMap<(GlobalId, u64, bool), i64>>>,
+    dead_letters: Rc<RefCell<HashMap<(GlobalId, String), i64>>>,
+    metrics_updates: Rc<RefCell<HashMap<GlobalId, SinkMetricsUpdate>>>,
+    status_updates: Rc<RefCell<Vec<(GlobalId, SinkStatusUpdate)>>>,
+    now: NowFn,
+    cdc_v2: Option<CdcV2Progress>,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -1059,6 +2126,12 @@ where
         write_frontier,
         metrics,
         connection_context,
+        progress_publishes,
+        dead_letters,
+        metrics_updates,
+        status_updates,
+        now,
+        cdc_v2,
     );
 
     let mut vector = Vec::new();
@@ -1099,6 +2172,12 @@ where
                 // _did_ downgrade to the empty frontier here.
                 return false;
             }
+            if s.failed {
+                // A failed sink discards its input and, like a sink that is
+                // shutting down, never advances its write frontier again.
+                input.for_each(|_, _| {});
+                return false;
+            }
             // Panic if there's not exactly once element in the frontier like we expect.
             let frontier = frontiers.clone().into_element();
 
@@ -1106,14 +2185,34 @@ where
                 if let KafkaSinkStateEnum::Init(ref init) = s.sink_state {
                     s.retry_on_txn_error(|p| p.init_transactions()).await;
 
-                    let latest_ts = s
+                    if s.has_partition_by {
+                        s.partition_count = Some(s.fetch_partition_count().await);
+                    }
+
+                    let mut progress_shard = ProgressShard::open(
+                        &persist_clients,
+                        persist_location.clone(),
+                        progress_shard,
+                    )
+                    .await
+                    .expect("opening progress shard");
+                    let shard_ts = progress_shard.latest_ts().await;
+                    // The shard is written only once a transaction has
+                    // committed, so a crash in between leaves the progress
+                    // record of that transaction as the only trace of it.
+                    let record_ts = s
                         .determine_latest_progress_record()
                         .await
                         .expect("determining latest progress record");
+                    let latest_ts = cmp::max(shard_ts, record_ts);
                     info!(
-                        "{}: initial as_of: {:?}, latest progress record: {:?}",
-                        s.name, as_of.frontier, latest_ts
+                        "{}: initial as_of: {:?}, progress shard: {:?}, latest progress record: {:?}",
+                        s.name, as_of.frontier, shard_ts, record_ts
                     );
+                    if let Some(latest_ts) = latest_ts {
+                        progress_shard.record(latest_ts).await;
+                    }
+                    s.progress_shard = Some(progress_shard);
                     shared_gate_ts.set(latest_ts);
 
                     let progress_state = init
@@ -1131,9 +2230,13 @@ where
                             gate
                         );
                         s.maybe_update_progress(&gate);
+                        if let Some(cdc_v2) = &s.cdc_v2 {
+                            cdc_v2.upper.set(gate.step_forward());
+                        }
                     }
 
                     s.sink_state = KafkaSinkStateEnum::Running(progress_state);
+                    s.record_status(SinkStatus::Running, None, None);
                 }
             }
 
@@ -1141,7 +2244,7 @@ where
             input.for_each(|_, rows| {
                 assert!(is_active_worker);
                 rows.swap(&mut vector);
-                for ((key, value), time, diff) in vector.drain(..) {
+                for (message, time, diff) in vector.drain(..) {
                     let should_emit = if as_of.strict {
                         as_of.frontier.less_than(&time)
                     } else {
@@ -1162,10 +2265,10 @@ where
                     };
                     let diff = diff as usize;
 
+                    s.pending_since.entry(time).or_insert_with(Instant::now);
                     let rows = s.pending_rows.entry(time).or_default();
                     rows.push(EncodedRow {
-                        key,
-                        value,
+                        message,
                         count: diff,
                     });
                     s.metrics.rows_queued.inc();
@@ -1182,67 +2285,161 @@ where
             closed_ts.sort_unstable();
             closed_ts.into_iter().for_each(|ts| {
                 let rows = s.pending_rows.remove(&ts).unwrap();
-                s.ready_rows.push_back((ts, rows));
+                let since = s.pending_since.remove(&ts).unwrap();
+                s.ready_rows.push_back((ts, since, rows));
             });
 
-            while let Some((ts, rows)) = s.ready_rows.front() {
+            while !s.ready_rows.is_empty() {
                 assert!(is_active_worker);
 
+                // Publish as many closed timestamps as fit into the current
+                // byte budget in a single transaction, but always at least
+                // one, so that large timestamps cannot stall the sink.
+                let budget = s.transaction_bytes.size();
+                let mut batch = vec![s.ready_rows.pop_front().expect("known to exist")];
+                let mut batch_bytes = batch[0].2.iter().map(encoded_row_bytes).sum::<u64>();
+                while let Some((_, _, rows)) = s.ready_rows.front() {
+                    let bytes = rows.iter().map(encoded_row_bytes).sum::<u64>();
+                    if batch_bytes.saturating_add(bytes) > budget {
+                        break;
+                    }
+                    batch_bytes += bytes;
+                    batch.push(s.ready_rows.pop_front().expect("known to exist"));
+                }
+                let last_ts = batch.last().expect("known to exist").0;
+
                 info!(
-                    "Beginning transaction for {:?} with {:?} rows",
-                    ts,
-                    rows.len()
+                    "Beginning transaction for {:?} through {:?} with {:?} rows",
+                    batch[0].0,
+                    last_ts,
+                    batch.iter().map(|(_, _, rows)| rows.len()).sum::<usize>()
                 );
-                s.retry_on_txn_error(|p| p.begin_transaction()).await;
+                // The topics that rows fan out to must exist before the
+                // transaction publishes to them.
+                if let Err(reason) = s.ensure_fanout_topics(&batch).await {
+                    s.fail(reason, false).await;
+                    return false;
+                }
 
-                let mut repeat_counter = 0;
-                for encoded_row in rows {
-                    let record = BaseRecord::to(&s.topic);
-                    let record = match encoded_row.value.as_ref() {
-                        Some(r) => record.payload(r),
-                        None => record,
-                    };
-                    let record = match encoded_row.key.as_ref() {
-                        Some(r) => record.key(r),
-                        None => record,
-                    };
+                let txn_started = Instant::now();
+                s.retry_on_txn_error(|p| p.begin_transaction()).await;
 
+                for (ts, _, rows) in &batch {
                     let ts_bytes = ts.to_string().into_bytes();
-                    let record = record.headers(OwnedHeaders::new().insert(Header {
-                        key: "materialize-timestamp",
-                        value: Some(&ts_bytes),
-                    }));
-
-                    s.send(record).await;
-
-                    // advance to the next repetition of this row, or the next row if all
-                    // repetitions are exhausted
-                    repeat_counter += 1;
-                    if repeat_counter == encoded_row.count {
-                        repeat_counter = 0;
-                        s.metrics.rows_queued.dec();
+                    let mut repeat_counter = 0;
+                    for encoded_row in rows {
+                        let message = &encoded_row.message;
+                        let fanout_topic = s.topic_fanout.as_ref().and_then(|fanout| {
+                            let value = message.topic_value.as_ref()?;
+                            Some(
+                                fanout
+                                    .topic(value)
+                                    .expect("validated before the transaction"),
+                            )
+                        });
+                        let record = BaseRecord::to(fanout_topic.as_deref().unwrap_or(&s.topic));
+                        let record = match message.value.as_ref() {
+                            Some(r) => record.payload(r),
+                            None => record,
+                        };
+                        let record = match message.key.as_ref() {
+                            Some(r) => record.key(r),
+                            None => record,
+                        };
+                        let record = match (message.partition, s.partition_count) {
+                            (Some(partition), Some(count)) => record.partition(
+                                i32::try_from(partition % count)
+                                    .expect("partition count fits in i32"),
+                            ),
+                            _ if s.cdc_v2.is_some() => record.partition(CDC_V2_PARTITION),
+                            _ => record,
+                        };
+                        let record = match message.timestamp {
+                            Some(timestamp) => record.timestamp(timestamp),
+                            None => record,
+                        };
+
+                        let mut headers = OwnedHeaders::new().insert(Header {
+                            key: KAFKA_SINK_TIMESTAMP_HEADER,
+                            value: Some(&ts_bytes),
+                        });
+                        for (key, value) in s.header_keys.iter().zip(&message.headers) {
+                            headers = headers.insert(Header {
+                                key,
+                                value: value.as_ref(),
+                            });
+                        }
+                        let record = record.headers(headers);
+
+                        s.send(record).await;
+
+                        // advance to the next repetition of this row, or the next row if all
+                        // repetitions are exhausted
+                        repeat_counter += 1;
+                        if repeat_counter == encoded_row.count {
+                            repeat_counter = 0;
+                            s.metrics.rows_queued.dec();
+                        }
                     }
                 }
 
+                if s.cdc_v2.is_some() {
+                    let counts: Vec<(Timestamp, usize)> = batch
+                        .iter()
+                        .map(|(ts, _, rows)| (*ts, rows.iter().map(|row| row.count).sum()))
+                        .collect();
+                    s.send_cdc_v2_progress(last_ts.step_forward(), &counts)
+                        .await;
+                }
+
                 // Flush to make sure that errored messages have been properly retried before
                 // sending progress records and commit transactions.
-                s.flush().await;
-
-                if let Some(progress_state) = s.sink_state.unwrap_running() {
-                    s.send_progress_record(*ts, progress_state).await;
+                if let Err(reason) = s.flush().await {
+                    s.fail(reason, true).await;
+                    return false;
                 }
 
-                info!("Committing transaction for {:?}", ts,);
-                s.retry_on_txn_error(|p| p.commit_transaction()).await;
+                let progress_enqueued = match s.sink_state.unwrap_running() {
+                    Some(progress_state) => {
+                        let enqueued = Instant::now();
+                        s.send_progress_record(last_ts, progress_state).await;
+                        Some(enqueued)
+                    }
+                    None => None,
+                };
+
+                info!("Committing transaction for {:?}", last_ts);
+                match progress_enqueued {
+                    Some(enqueued) => {
+                        s.commit_progress_txn(enqueued, last_ts).await;
+                        // Only exactly-once sinks hold back timestamps until
+                        // they can be published consistently.
+                        for (_, since, _) in &batch {
+                            s.metrics
+                                .consistency_wait_seconds
+                                .observe(since.elapsed().as_secs_f64());
+                        }
+                    }
+                    None => s.retry_on_txn_error(|p| p.commit_transaction()).await,
+                }
 
-                s.flush().await;
+                if let Err(reason) = s.flush().await {
+                    s.fail(reason, false).await;
+                    return false;
+                }
+                s.transaction_bytes
+                    .observe(batch_bytes, txn_started.elapsed());
+                let batch_messages = batch
+                    .iter()
+                    .flat_map(|(_, _, rows)| rows)
+                    .map(|row| u64::cast_from(row.count))
+                    .sum();
+                s.record_committed(batch_messages, batch_bytes);
 
                 // sanity check for the continuous updating
                 // of the write frontier below
-                s.assert_progress(ts);
-                progress_update.replace(ts.clone());
-
-                s.ready_rows.pop_front();
+                s.assert_progress(&last_ts);
+                progress_update.replace(last_ts);
             }
 
             // Update our state based on any progress we may have sent.  This
@@ -1264,8 +2461,12 @@ where
                 if progress_emitted {
                     // Don't flush if we know there were no records emitted.
                     // It has a noticeable negative performance impact.
-                    s.flush().await;
+                    if let Err(reason) = s.flush().await {
+                        s.fail(reason, false).await;
+                        return false;
+                    }
                 }
+                s.record_frontiers(&frontier);
             }
 
             // We want debug_assert but also to print out if we would have failed the assertion in release mode
@@ -1300,7 +2501,8 @@ where
 
 /// Encodes a stream of `(Option<Row>, Option<Row>)` updates using the specified encoder.
 ///
-/// This operator will only encode `fuel` number of updates per invocation. If necessary, it will
+/// This operator aims to spend at most [`ENCODE_INVOCATION_TARGET`] encoding per invocation,
+/// adapting the number of updates it encodes to the observed encoding rate. If necessary, it will
 /// stash updates and use an [`timely::scheduling::Activator`] to re-schedule future invocations.
 ///
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
@@ -1320,14 +2522,38 @@ fn encode_stream<G>(
     as_of: SinkAsOf,
     shared_gate_ts: Rc<Cell<Option<Timestamp>>>,
     encoder: impl Encode + 'static,
-    fuel: usize,
+    metadata_encoder: MetadataEncoder,
     name_prefix: String,
-) -> Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>
+) -> Stream<G, (EncodedMessage, Timestamp, Diff)>
 where
     G: Scope<Timestamp = Timestamp>,
 {
     let name = format!("{}-{}_encode", name_prefix, encoder.get_format_name());
+    encode_stream_with(
+        input_stream,
+        as_of,
+        shared_gate_ts,
+        name,
+        move |(key, value), time, diff| {
+            let message = metadata_encoder.encode(&encoder, key, value, time);
+            (message, diff)
+        },
+    )
+}
 
+/// Like [`encode_stream`], but encodes each update with `encode`, which also
+/// determines the multiplicity of the resulting message.
+fn encode_stream_with<G, F>(
+    input_stream: &Stream<G, ((Option<Row>, Option<Row>), Timestamp, Diff)>,
+    as_of: SinkAsOf,
+    shared_gate_ts: Rc<Cell<Option<Timestamp>>>,
+    name: String,
+    mut encode: F,
+) -> Stream<G, (EncodedMessage, Timestamp, Diff)>
+where
+    G: Scope<Timestamp = Timestamp>,
+    F: FnMut((Option<Row>, Option<Row>), Timestamp, Diff) -> (EncodedMessage, Diff) + 'static,
+{
     let mut builder = OperatorBuilder::new(name, input_stream.scope());
     let mut input = builder.new_input(input_stream, Pipeline);
     let (mut output, output_stream) = builder.new_output();
@@ -1339,75 +2565,77 @@ where
 
     let mut stash: HashMap<Capability<Timestamp>, Vec<_>> = HashMap::new();
     let mut vector = Vec::new();
-    let mut encode_logic = move |input: &mut InputHandle<
-        Timestamp,
-        ((Option<Row>, Option<Row>), Timestamp, Diff),
-        _,
-    >,
-                                 output: &mut OutputHandle<
-        _,
-        ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff),
-        _,
-    >| {
-        let mut fuel_remaining = fuel;
-        // stash away all the input we get, we want to be a nice citizen
-        input.for_each(|cap, data| {
-            data.swap(&mut vector);
-            let stashed = stash.entry(cap.retain()).or_default();
-            for update in vector.drain(..) {
-                let time = update.1;
-
-                let should_emit = if as_of.strict {
-                    as_of.frontier.less_than(&time)
-                } else {
-                    as_of.frontier.less_equal(&time)
-                };
-                let ts_gated = Some(time) <= shared_gate_ts.get();
+    let mut batch_size = AdaptiveBatchSize::new(ENCODE_INVOCATION_TARGET, 1 << 10, 64, 1 << 20);
+    let mut encode_logic =
+        move |input: &mut InputHandle<
+            Timestamp,
+            ((Option<Row>, Option<Row>), Timestamp, Diff),
+            _,
+        >,
+              output: &mut OutputHandle<_, (EncodedMessage, Timestamp, Diff), _>| {
+            let mut fuel_remaining = usize::cast_from(batch_size.size());
+            // stash away all the input we get, we want to be a nice citizen
+            input.for_each(|cap, data| {
+                data.swap(&mut vector);
+                let stashed = stash.entry(cap.retain()).or_default();
+                for update in vector.drain(..) {
+                    let time = update.1;
+
+                    let should_emit = if as_of.strict {
+                        as_of.frontier.less_than(&time)
+                    } else {
+                        as_of.frontier.less_equal(&time)
+                    };
+                    let ts_gated = Some(time) <= shared_gate_ts.get();
 
-                if !should_emit || ts_gated {
-                    // Skip stale data for already published timestamps
-                    continue;
+                    if !should_emit || ts_gated {
+                        // Skip stale data for already published timestamps
+                        continue;
+                    }
+                    stashed.push(update);
                 }
-                stashed.push(update);
-            }
-        });
+            });
 
-        // work off some of our data and then yield, can't be hogging
-        // the worker for minutes at a time
-
-        while fuel_remaining > 0 && !stash.is_empty() {
-            let lowest_ts = stash
-                .keys()
-                .min_by(|x, y| x.time().cmp(y.time()))
-                .expect("known to exist")
-                .clone();
-            let records = stash.get_mut(&lowest_ts).expect("known to exist");
-
-            let mut session = output.session(&lowest_ts);
-            let num_records_to_drain = cmp::min(records.len(), fuel_remaining);
-            records
-                .drain(..num_records_to_drain)
-                .for_each(|((key, value), time, diff)| {
-                    let key = key.map(|key| encoder.encode_key_unchecked(key));
-                    let value = value.map(|value| encoder.encode_value_unchecked(value));
-                    session.give(((key, value), time, diff));
-                });
+            // work off some of our data and then yield, can't be hogging
+            // the worker for minutes at a time
+
+            let started = Instant::now();
+            let mut encoded = 0;
+            while fuel_remaining > 0 && !stash.is_empty() {
+                let lowest_ts = stash
+                    .keys()
+                    .min_by(|x, y| x.time().cmp(y.time()))
+                    .expect("known to exist")
+                    .clone();
+                let records = stash.get_mut(&lowest_ts).expect("known to exist");
+
+                let mut session = output.session(&lowest_ts);
+                let num_records_to_drain = cmp::min(records.len(), fuel_remaining);
+                records
+                    .drain(..num_records_to_drain)
+                    .for_each(|(update, time, diff)| {
+                        let (message, diff) = encode(update, time, diff);
+                        session.give((message, time, diff));
+                    });
 
-            fuel_remaining -= num_records_to_drain;
+                fuel_remaining -= num_records_to_drain;
+                encoded += num_records_to_drain;
 
-            if records.is_empty() {
-                // drop our capability for this time
-                stash.remove(&lowest_ts);
+                if records.is_empty() {
+                    // drop our capability for this time
+                    stash.remove(&lowest_ts);
+                }
             }
-        }
 
-        if !stash.is_empty() {
-            activator.activate();
-            return true;
-        }
-        // signal that we're complete now
-        false
-    };
+            batch_size.observe(u64::cast_from(encoded), started.elapsed());
+
+            if !stash.is_empty() {
+                activator.activate();
+                return true;
+            }
+            // signal that we're complete now
+            false
+        };
 
     builder.build_reschedule(|_capabilities| {
         move |_frontiers| {
@@ -1426,6 +2654,10 @@ where
 /// future if needed.) It's encoded as JSON to make it easier to introspect while debugging, and
 /// because we expect it to remain small.
 ///
+/// The sink resumes from its [`ProgressShard`], which is only written once a transaction has
+/// committed. The record covers the transaction that committed right before a crash, whose
+/// progress the shard may not have recorded yet.
+///
 /// Unlike the old consistency topic, this is not intended to be a user-facing feature; it's there
 /// purely so the sink can maintain its transactional guarantees. Any future user-facing consistency
 /// information should be added elsewhere instead of overloading this record.
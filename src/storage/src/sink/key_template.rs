@@ -0,0 +1,52 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Rendering of templates, such as NATS subjects and MQTT topics, that
+//! depend on the key of each update.
+
+use mz_repr::{ColumnType, Row};
+
+use crate::sink::redis::encode_text;
+use crate::types::sinks::KeyTemplateSegment;
+
+/// Renders `template` for the update of `key`, whose columns have the types
+/// `key_types`.
+///
+/// Key columns are substituted by their text format, with whitespace and the
+/// characters in `reserved` replaced by underscores. Null key columns are
+/// substituted by `NULL`, and empty ones by a single underscore.
+pub(super) fn render(
+    template: &[KeyTemplateSegment],
+    key: Option<&Row>,
+    key_types: &[ColumnType],
+    reserved: &[char],
+) -> String {
+    let mut rendered = String::new();
+    for segment in template {
+        match segment {
+            KeyTemplateSegment::Literal(literal) => rendered.push_str(literal),
+            KeyTemplateSegment::KeyColumn(i) => {
+                let key = key.expect("templates with key columns require a key");
+                let datum = key.iter().nth(*i).expect("key column known to exist");
+                let text = encode_text(datum, &key_types[*i]).unwrap_or_else(|| "NULL".into());
+                if text.is_empty() {
+                    rendered.push('_');
+                }
+                rendered.extend(text.chars().map(|c| {
+                    if c.is_whitespace() || reserved.contains(&c) {
+                        '_'
+                    } else {
+                        c
+                    }
+                }));
+            }
+        }
+    }
+    rendered
+}
@@ -11,7 +11,7 @@
 
 use mz_ore::{
     metric,
-    metrics::{IntCounterVec, MetricsRegistry, UIntGaugeVec},
+    metrics::{HistogramVec, IntCounterVec, MetricsRegistry, UIntGaugeVec},
 };
 
 /// Metrics reported by each kafka sink.
@@ -21,6 +21,9 @@ pub struct KafkaBaseMetrics {
     pub(crate) message_send_errors_counter: IntCounterVec,
     pub(crate) message_delivery_errors_counter: IntCounterVec,
     pub(crate) rows_queued: UIntGaugeVec,
+    pub(crate) progress_publish_seconds: HistogramVec,
+    pub(crate) progress_publish_errors_counter: IntCounterVec,
+    pub(crate) consistency_wait_seconds: HistogramVec,
 }
 
 impl KafkaBaseMetrics {
@@ -46,6 +49,65 @@ impl KafkaBaseMetrics {
                 help: "The current number of rows queued by the Kafka sink operator (note that one row can generate multiple Kafka messages)",
                 var_labels: ["topic", "sink_id", "worker_id"],
             )),
+            progress_publish_seconds: registry.register(metric!(
+                name: "mz_kafka_progress_publish_seconds",
+                help: "The time from enqueueing a record to the progress topic to committing its transaction",
+                var_labels: ["topic", "sink_id", "worker_id"],
+            )),
+            progress_publish_errors_counter: registry.register(metric!(
+                name: "mz_kafka_progress_publish_errors_total",
+                help: "The number of times committing a transaction with a progress record failed",
+                var_labels: ["topic", "sink_id", "worker_id"],
+            )),
+            consistency_wait_seconds: registry.register(metric!(
+                name: "mz_kafka_sink_consistency_wait_seconds",
+                help: "The time from the first update at a timestamp arriving at an exactly-once sink to the transaction that publishes the timestamp committing",
+                var_labels: ["topic", "sink_id", "worker_id"],
+            )),
+        }
+    }
+}
+
+/// Metrics reported by sinks that spill batches to disk while their
+/// destination is unavailable.
+#[derive(Clone)]
+pub struct SpillBaseMetrics {
+    pub(crate) batches_spilled: UIntGaugeVec,
+    pub(crate) bytes_spilled: UIntGaugeVec,
+}
+
+impl SpillBaseMetrics {
+    pub fn register_with(registry: &MetricsRegistry) -> Self {
+        Self {
+            batches_spilled: registry.register(metric!(
+                name: "mz_sink_spilled_batches",
+                help: "The current number of encoded batches spilled to disk by the sink, awaiting replay",
+                var_labels: ["sink_id", "worker_id"],
+            )),
+            bytes_spilled: registry.register(metric!(
+                name: "mz_sink_spilled_bytes",
+                help: "The current number of bytes of encoded batches spilled to disk by the sink, awaiting replay",
+                var_labels: ["sink_id", "worker_id"],
+            )),
+        }
+    }
+}
+
+/// Metrics reported by sinks that are rate limited while they catch up with
+/// their input.
+#[derive(Clone)]
+pub struct CatchUpBaseMetrics {
+    pub(crate) catching_up: UIntGaugeVec,
+}
+
+impl CatchUpBaseMetrics {
+    pub fn register_with(registry: &MetricsRegistry) -> Self {
+        Self {
+            catching_up: registry.register(metric!(
+                name: "mz_sink_catching_up",
+                help: "Whether the sink is rate limited while catching up with its input",
+                var_labels: ["sink_id", "worker_id"],
+            )),
         }
     }
 }
@@ -54,6 +116,8 @@ impl KafkaBaseMetrics {
 #[derive(Clone)]
 pub struct SinkBaseMetrics {
     pub(crate) kafka: KafkaBaseMetrics,
+    pub(crate) spill: SpillBaseMetrics,
+    pub(crate) catch_up: CatchUpBaseMetrics,
 }
 
 impl SinkBaseMetrics {
@@ -61,6 +125,8 @@ impl SinkBaseMetrics {
     pub fn register_with(registry: &MetricsRegistry) -> Self {
         Self {
             kafka: KafkaBaseMetrics::register_with(registry),
+            spill: SpillBaseMetrics::register_with(registry),
+            catch_up: CatchUpBaseMetrics::register_with(registry),
         }
     }
 }
@@ -9,10 +9,22 @@
 
 //! Moving data to external systems
 
+mod elasticsearch;
+mod file;
+mod grpc;
 mod kafka;
+mod key_template;
 mod metrics;
+mod mqtt;
+mod nats;
+mod postgres;
+mod redis;
+mod s3;
 mod sink_connection;
+mod spill;
+mod sqs;
+mod table_format;
 
 pub(crate) use metrics::KafkaBaseMetrics;
 pub use metrics::SinkBaseMetrics;
-pub use sink_connection::build_sink_connection;
+pub use sink_connection::{alter_kafka_sink_retention, build_sink_connection};
@@ -0,0 +1,300 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that publishes updates to topics of an MQTT broker.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use differential_dataflow::Collection;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Outgoing, Packet, QoS, Transport};
+use timely::dataflow::Scope;
+
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_repr::{ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_secrets::SecretsReader;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::key_template;
+use crate::sink::sink_connection::{render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{KeyTemplateSegment, MqttSinkConnection, SinkEnvelope, StorageSinkDesc};
+
+impl<G> SinkRender<G> for MqttSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_indices.as_deref()
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker publishes all messages, so that the messages for
+        // each timestamp are published after those of earlier timestamps.
+        let debezium = matches!(sink.envelope, Some(SinkEnvelope::Debezium));
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "mqtt",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            MessageEncoder::new(self, debezium),
+            MqttWriter {
+                sink_id,
+                connection: self.clone(),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// Returns the options for connecting to the broker targeted by `connection`
+/// as `client_id`, reading its credentials from `secrets_reader`.
+async fn options(
+    connection: &MqttSinkConnection,
+    client_id: String,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<MqttOptions, anyhow::Error> {
+    let url = url::Url::parse(&connection.url)?;
+    let (tls, default_port) = match url.scheme() {
+        "mqtt" => (false, 1883),
+        "mqtts" => (true, 8883),
+        scheme => bail!("unsupported MQTT URL scheme: {}", scheme),
+    };
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("MQTT URL is missing a host"))?;
+    let mut options = MqttOptions::new(client_id, host, url.port().unwrap_or(default_port));
+    // The event loop is only polled while a batch is being published, so be
+    // generous with the keep alive interval. A broker that has disconnected
+    // an idle sink is reconnected to by the next batch.
+    options.set_keep_alive(Duration::from_secs(60));
+    options.set_clean_session(true);
+    if tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    if let (Some(user), Some(password)) = (&connection.user, connection.password) {
+        options.set_credentials(
+            user.get_string(secrets_reader).await?,
+            secrets_reader.read_string(password).await?,
+        );
+    }
+    Ok(options)
+}
+
+/// Connects to the broker targeted by `connection` as `client_id`, waiting
+/// for the broker to accept the connection.
+async fn connect(
+    connection: &MqttSinkConnection,
+    client_id: String,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(AsyncClient, EventLoop), anyhow::Error> {
+    let options = options(connection, client_id, secrets_reader).await?;
+    let (client, mut event_loop) = AsyncClient::new(options, 64);
+    loop {
+        if let Event::Incoming(Packet::ConnAck(_)) = event_loop.poll().await? {
+            return Ok((client, event_loop));
+        }
+    }
+}
+
+/// Checks that the broker targeted by `connection` accepts connections with
+/// its credentials.
+pub(super) async fn check_connection(
+    connection: &MqttSinkConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(), anyhow::Error> {
+    let client_id = format!("materialize-check-{}", uuid::Uuid::new_v4());
+    let (client, mut event_loop) = connect(connection, client_id, secrets_reader).await?;
+    client.disconnect().await?;
+    // Flush the disconnect request to the broker.
+    while !matches!(
+        event_loop.poll().await?,
+        Event::Outgoing(Outgoing::Disconnect)
+    ) {}
+    Ok(())
+}
+
+/// Encodes updates as the messages that publish them.
+///
+/// Each message is encoded as the JSON encoding of its topic and payload, so
+/// that batches can be spilled to disk.
+struct MessageEncoder {
+    topic: Vec<KeyTemplateSegment>,
+    key_types: Vec<ColumnType>,
+    json_encoder: JsonEncoder,
+}
+
+impl MessageEncoder {
+    fn new(connection: &MqttSinkConnection, debezium: bool) -> Self {
+        let types = &connection.value_desc.typ().column_types;
+        MessageEncoder {
+            topic: connection.topic.clone(),
+            key_types: connection
+                .key_indices
+                .iter()
+                .flatten()
+                .map(|i| types[*i].clone())
+                .collect(),
+            json_encoder: JsonEncoder::new(None, connection.value_desc.clone(), debezium),
+        }
+    }
+
+    /// Returns the topic that the update of `key` is published to.
+    ///
+    /// The characters that separate topic levels or are wildcards are
+    /// replaced by underscores in key columns.
+    fn topic(&self, key: Option<&Row>) -> String {
+        key_template::render(&self.topic, key, &self.key_types, &['/', '+', '#'])
+    }
+
+    /// Appends the `count` messages that publish the update of `key` to
+    /// `value` to `messages`.
+    ///
+    /// The deletion of a key is published as a message with an empty payload,
+    /// which also clears the message retained for its topic, if any.
+    fn encode(
+        &self,
+        messages: &mut Vec<String>,
+        key: Option<&Row>,
+        value: Option<Row>,
+        count: usize,
+    ) {
+        let topic = self.topic(key);
+        let payload = match value {
+            Some(value) => String::from_utf8(self.json_encoder.encode_value_unchecked(value))
+                .expect("JSON encoding is valid UTF-8"),
+            None => String::new(),
+        };
+        for _ in 0..count {
+            messages.push(
+                serde_json::to_string(&(&topic, &payload))
+                    .expect("serializing strings cannot fail"),
+            );
+        }
+    }
+}
+
+impl BatchEncoder for MessageEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        let mut messages = vec![];
+        for (_time, updates) in updates {
+            for (key, value, count) in updates {
+                self.encode(&mut messages, key.as_ref(), value, count);
+            }
+        }
+        messages
+    }
+}
+
+/// Publishes batches of messages to the broker.
+///
+/// A batch that fails is published again in its entirety, so messages are
+/// delivered at least once, even at quality of service level 2.
+struct MqttWriter {
+    sink_id: GlobalId,
+    connection: MqttSinkConnection,
+    connection_context: ConnectionContext,
+    client: Option<(AsyncClient, EventLoop)>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for MqttWriter {
+    /// Publishes each of `messages` and waits for the broker to acknowledge
+    /// all of them, connecting to the broker first if necessary.
+    ///
+    /// At quality of service level 0, which has no acknowledgements, waits
+    /// for the messages to be written to the connection instead.
+    async fn write(&mut self, messages: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            let client_id = format!("materialize-{}", self.sink_id);
+            self.client = Some(
+                connect(
+                    &self.connection,
+                    client_id,
+                    &*self.connection_context.secrets_reader,
+                )
+                .await
+                .context("error connecting to mqtt")?,
+            );
+        }
+        let (client, event_loop) = self.client.as_mut().expect("known to exist");
+        let qos = rumqttc::qos(self.connection.qos).map_err(|e| anyhow!("{}", e))?;
+        let retain = self.connection.retain;
+        let publish = async {
+            for message in messages {
+                let (topic, payload): (String, String) = serde_json::from_str(message)?;
+                client
+                    .publish(topic, qos, retain, payload)
+                    .await
+                    .context("error publishing message")?;
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+        let complete = async {
+            let mut remaining = messages.len();
+            while remaining > 0 {
+                let event = event_loop
+                    .poll()
+                    .await
+                    .context("error awaiting acknowledgement")?;
+                let completed = match (qos, event) {
+                    (QoS::AtMostOnce, Event::Outgoing(Outgoing::Publish(_))) => true,
+                    (QoS::AtLeastOnce, Event::Incoming(Packet::PubAck(_))) => true,
+                    (QoS::ExactlyOnce, Event::Incoming(Packet::PubComp(_))) => true,
+                    _ => false,
+                };
+                if completed {
+                    remaining -= 1;
+                }
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+        let result = futures::try_join!(publish, complete).map(|_| ());
+        if result.is_err() {
+            // The event loop retransmits unacknowledged messages when it
+            // reconnects, which would confuse the accounting of the retried
+            // batch, so start over with a fresh connection.
+            self.client = None;
+        }
+        result
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that publishes updates to subjects of a NATS JetStream stream.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Context};
+use async_nats::jetstream;
+use async_trait::async_trait;
+use differential_dataflow::Collection;
+use timely::dataflow::Scope;
+
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_repr::{ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_secrets::SecretsReader;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::key_template;
+use crate::sink::sink_connection::{render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{KeyTemplateSegment, NatsSinkConnection, SinkEnvelope, StorageSinkDesc};
+
+impl<G> SinkRender<G> for NatsSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_indices.as_deref()
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker publishes all messages, so that the messages for
+        // each timestamp are published after those of earlier timestamps.
+        let debezium = matches!(sink.envelope, Some(SinkEnvelope::Debezium));
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "nats",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            MessageEncoder::new(sink_id, self, debezium),
+            NatsWriter {
+                connection: self.clone(),
+                connection_context,
+                context: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// Connects to the server targeted by `connection`, reading its credentials
+/// from `secrets_reader`.
+pub(super) async fn connect(
+    connection: &NatsSinkConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<async_nats::Client, anyhow::Error> {
+    let mut options = async_nats::ConnectOptions::new();
+    if let (Some(user), Some(password)) = (&connection.user, connection.password) {
+        options = options.user_and_password(
+            user.get_string(secrets_reader).await?,
+            secrets_reader.read_string(password).await?,
+        );
+    }
+    if let Some(token) = connection.token {
+        options = options.token(secrets_reader.read_string(token).await?);
+    }
+    Ok(options.connect(connection.url.as_str()).await?)
+}
+
+/// Encodes updates as the messages that publish them.
+///
+/// Each message is encoded as the JSON encoding of its subject, message ID,
+/// and payload, so that batches can be spilled to disk.
+struct MessageEncoder {
+    sink_id: GlobalId,
+    subject: Vec<KeyTemplateSegment>,
+    key_types: Vec<ColumnType>,
+    json_encoder: JsonEncoder,
+}
+
+impl MessageEncoder {
+    fn new(sink_id: GlobalId, connection: &NatsSinkConnection, debezium: bool) -> Self {
+        let types = &connection.value_desc.typ().column_types;
+        MessageEncoder {
+            sink_id,
+            subject: connection.subject.clone(),
+            key_types: connection
+                .key_indices
+                .iter()
+                .flatten()
+                .map(|i| types[*i].clone())
+                .collect(),
+            json_encoder: JsonEncoder::new(None, connection.value_desc.clone(), debezium),
+        }
+    }
+
+    /// Returns the subject that the update of `key` is published to.
+    ///
+    /// The characters that separate subject tokens or are wildcards are
+    /// replaced by underscores in key columns.
+    fn subject(&self, key: Option<&Row>) -> String {
+        key_template::render(&self.subject, key, &self.key_types, &['.', '*', '>'])
+    }
+
+    /// Appends the `count` messages that publish the update of `key` to
+    /// `value` at `time` to `messages`.
+    ///
+    /// Messages are identified by the sink, the timestamp, and their position
+    /// among the messages at that timestamp, so that JetStream discards the
+    /// duplicates published when a batch is retried. The deletion of a key
+    /// is published as a message with an empty payload.
+    fn encode(
+        &self,
+        messages: &mut Vec<String>,
+        time: Timestamp,
+        key: Option<&Row>,
+        value: Option<Row>,
+        count: usize,
+    ) {
+        let subject = self.subject(key);
+        let payload = match value {
+            Some(value) => String::from_utf8(self.json_encoder.encode_value_unchecked(value))
+                .expect("JSON encoding is valid UTF-8"),
+            None => String::new(),
+        };
+        for _ in 0..count {
+            let msg_id = format!("{}.{}.{}", self.sink_id, time, messages.len());
+            messages.push(
+                serde_json::to_string(&(&subject, msg_id, &payload))
+                    .expect("serializing strings cannot fail"),
+            );
+        }
+    }
+}
+
+impl BatchEncoder for MessageEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        let mut messages = vec![];
+        for (time, updates) in updates {
+            // Messages are identified by their position among the messages
+            // at their timestamp.
+            let mut time_messages = vec![];
+            for (key, value, count) in updates {
+                self.encode(&mut time_messages, time, key.as_ref(), value, count);
+            }
+            messages.extend(time_messages);
+        }
+        messages
+    }
+}
+
+/// Publishes batches of messages to JetStream.
+struct NatsWriter {
+    connection: NatsSinkConnection,
+    connection_context: ConnectionContext,
+    context: Option<jetstream::Context>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for NatsWriter {
+    /// Publishes each of `messages` and waits for the stream to acknowledge
+    /// all of them, connecting to the server first if necessary.
+    async fn write(&mut self, messages: &[String]) -> Result<(), anyhow::Error> {
+        if self.context.is_none() {
+            let client = connect(&self.connection, &*self.connection_context.secrets_reader)
+                .await
+                .context("error connecting to nats")?;
+            self.context = Some(jetstream::new(client));
+        }
+        let context = self.context.as_ref().expect("known to exist");
+        let result = async {
+            let mut acks = Vec::with_capacity(messages.len());
+            for message in messages {
+                let (subject, msg_id, payload): (String, String, String) =
+                    serde_json::from_str(message)?;
+                let mut headers = async_nats::HeaderMap::new();
+                headers.insert(async_nats::header::NATS_MESSAGE_ID, msg_id.as_str());
+                let ack = context
+                    .publish_with_headers(subject, headers, payload.into())
+                    .await
+                    .map_err(|e| anyhow!("error publishing message: {}", e))?;
+                acks.push(ack);
+            }
+            for ack in acks {
+                ack.await
+                    .map_err(|e| anyhow!("error awaiting acknowledgement: {}", e))?;
+            }
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+        if result.is_err() {
+            // The connection may be unusable after an error, so start over
+            // with a fresh one.
+            self.context = None;
+        }
+        result
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that applies upserts to a table in an upstream PostgreSQL database.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use differential_dataflow::Collection;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use timely::dataflow::Scope;
+use tokio_postgres::Client;
+
+use mz_repr::{ColumnType, Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_sql_parser::ast::Ident;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::redis::encode_text;
+use crate::sink::sink_connection::{collapse_upserts, render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{PostgresSinkConnection, StorageSinkDesc};
+
+/// The maximum number of rows affected by a single `DELETE` or `INSERT`
+/// statement.
+const MAX_ROWS_PER_STATEMENT: usize = 1024;
+
+impl<G> SinkRender<G> for PostgresSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_indices)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker applies all updates, so that each batch of
+        // timestamps can be applied within a single upstream transaction.
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "postgres",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            UpsertEncoder::new(self),
+            PostgresWriter {
+                connection: self.clone(),
+                statements: UpsertStatements::new(self),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// Returns the quoted names of the columns in `desc`, separated by commas.
+pub(super) fn column_list(desc: &RelationDesc) -> String {
+    desc.iter_names()
+        .map(|name| Ident::new(name.as_str()).to_ast_string_stable())
+        .join(", ")
+}
+
+/// The statements that apply a batch of upserts to the upstream table.
+///
+/// Both statements take a single parameter: the JSON encoding of an array of
+/// objects, each of which maps the names of some of the table's columns to
+/// the text format of their values, or to null. Postgres parses the values as
+/// the types of the table's columns, so that no value is ever interpolated
+/// into a statement.
+struct UpsertStatements {
+    /// Deletes the rows whose keys are those of the given objects.
+    delete: String,
+    /// Inserts the rows described by the given objects.
+    insert: String,
+}
+
+impl UpsertStatements {
+    fn new(connection: &PostgresSinkConnection) -> Self {
+        let table = &connection.table;
+        let names: Vec<_> = connection.value_desc.iter_names().collect();
+        let rows = format!("json_populate_recordset(NULL::{}, $1::text::json)", table);
+        let key_matches = connection
+            .key_indices
+            .iter()
+            .map(|i| {
+                let name = Ident::new(names[*i].as_str()).to_ast_string_stable();
+                format!("t.{} IS NOT DISTINCT FROM k.{}", name, name)
+            })
+            .join(" AND ");
+        let columns = column_list(&connection.value_desc);
+        UpsertStatements {
+            delete: format!(
+                "DELETE FROM {} AS t USING {} AS k WHERE {}",
+                table, rows, key_matches
+            ),
+            insert: format!(
+                "INSERT INTO {} ({}) SELECT {} FROM {}",
+                table, columns, columns, rows
+            ),
+        }
+    }
+}
+
+/// A request to execute one of the [`UpsertStatements`] with the given
+/// parameter.
+///
+/// Requests are encoded as JSON, so that batches can be spilled to disk.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum UpsertRequest {
+    Delete(String),
+    Insert(String),
+}
+
+/// Encodes upserts as the requests that apply them.
+struct UpsertEncoder {
+    names: Vec<String>,
+    types: Vec<ColumnType>,
+    key_indices: Vec<usize>,
+    value_indices: Vec<usize>,
+}
+
+impl UpsertEncoder {
+    fn new(connection: &PostgresSinkConnection) -> Self {
+        let desc = &connection.value_desc;
+        UpsertEncoder {
+            names: desc.iter_names().map(|name| name.as_str().into()).collect(),
+            types: desc.typ().column_types.clone(),
+            key_indices: connection.key_indices.clone(),
+            value_indices: (0..desc.arity()).collect(),
+        }
+    }
+
+    /// Returns the parameter of the [`UpsertStatements`] that describes
+    /// `rows`, whose datums are those of the columns at `indices`.
+    fn encode_rows<'a>(&self, rows: impl Iterator<Item = &'a Row>, indices: &[usize]) -> String {
+        let rows: Vec<serde_json::Map<_, _>> = rows
+            .map(|row| {
+                indices
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(i, datum)| {
+                        let value = match encode_text(datum, &self.types[*i]) {
+                            Some(text) => serde_json::Value::String(text),
+                            None => serde_json::Value::Null,
+                        };
+                        (self.names[*i].clone(), value)
+                    })
+                    .collect()
+            })
+            .collect();
+        serde_json::to_string(&rows).expect("serializing JSON values cannot fail")
+    }
+
+    /// Returns the requests that apply the upserts in `batch`.
+    ///
+    /// Every key in the batch is first deleted from the table, and the new
+    /// values of all keys that were not retracted are then inserted. This
+    /// makes applying a batch idempotent, which means that replaying updates
+    /// after a restart is safe.
+    fn encode_upserts(&self, batch: &HashMap<Row, Option<Row>>) -> Vec<String> {
+        let mut requests = vec![];
+        for keys in &batch.keys().chunks(MAX_ROWS_PER_STATEMENT) {
+            requests.push(UpsertRequest::Delete(
+                self.encode_rows(keys, &self.key_indices),
+            ));
+        }
+        for rows in &batch.values().flatten().chunks(MAX_ROWS_PER_STATEMENT) {
+            requests.push(UpsertRequest::Insert(
+                self.encode_rows(rows, &self.value_indices),
+            ));
+        }
+        requests
+            .iter()
+            .map(|request| serde_json::to_string(request).expect("serializing strings cannot fail"))
+            .collect()
+    }
+}
+
+impl BatchEncoder for UpsertEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        self.encode_upserts(&collapse_upserts(updates))
+    }
+}
+
+/// Applies batches of statements to the upstream database.
+struct PostgresWriter {
+    connection: PostgresSinkConnection,
+    statements: UpsertStatements,
+    connection_context: ConnectionContext,
+    client: Option<Client>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for PostgresWriter {
+    /// Executes `requests` within a single transaction, connecting to the
+    /// upstream database first if necessary.
+    async fn write(&mut self, requests: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            let config = self
+                .connection
+                .connection
+                .config(&*self.connection_context.secrets_reader)
+                .await?;
+            let client = config
+                .connect("postgres_sink")
+                .await
+                .context("error connecting to postgres")?;
+            self.client = Some(client);
+        }
+        let client = self.client.as_mut().expect("known to exist");
+        let statements = &self.statements;
+        let result = async {
+            let tx = client.transaction().await?;
+            for request in requests {
+                let (statement, rows) = match serde_json::from_str(request)? {
+                    UpsertRequest::Delete(keys) => (&statements.delete, keys),
+                    UpsertRequest::Insert(rows) => (&statements.insert, rows),
+                };
+                tx.execute(statement.as_str(), &[&rows]).await?;
+            }
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+        if result.is_err() {
+            // The connection may be unusable after an error, so start over
+            // with a fresh one.
+            self.client = None;
+        }
+        result
+    }
+}
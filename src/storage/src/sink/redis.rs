@@ -0,0 +1,273 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes upserts to keys on a Redis server.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use bytes::BytesMut;
+use differential_dataflow::Collection;
+use itertools::Itertools;
+use redis::aio::Connection;
+use redis::IntoConnectionInfo;
+use timely::dataflow::Scope;
+
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_ore::collections::CollectionExt;
+use mz_repr::{ColumnName, ColumnType, Datum, Diff, GlobalId, Row, Timestamp};
+use mz_secrets::SecretsReader;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::sink_connection::{collapse_upserts, render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{RedisSinkConnection, RedisSinkDataType, StorageSinkDesc};
+
+/// The maximum number of keys written by a single pipeline.
+const MAX_KEYS_PER_PIPELINE: usize = 1024;
+
+impl<G> SinkRender<G> for RedisSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_indices)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "redis",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            CommandEncoder::new(self),
+            RedisWriter {
+                connection: self.clone(),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// Connects to the server targeted by `connection`, reading its credentials
+/// from `secrets_reader`.
+pub(super) async fn connect(
+    connection: &RedisSinkConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<Connection, anyhow::Error> {
+    let mut info = connection.url.as_str().into_connection_info()?;
+    if let Some(user) = &connection.user {
+        info.redis.username = Some(user.get_string(secrets_reader).await?);
+    }
+    if let Some(password) = connection.password {
+        info.redis.password = Some(secrets_reader.read_string(password).await?);
+    }
+    let client = redis::Client::open(info)?;
+    Ok(client.get_async_connection().await?)
+}
+
+/// Renders `datum` in its text format, or returns `None` if it is null.
+pub(super) fn encode_text(datum: Datum, typ: &ColumnType) -> Option<String> {
+    mz_pgrepr::Value::from_datum(datum, &typ.scalar_type).map(|value| {
+        let mut text = BytesMut::new();
+        value.encode_text(&mut text);
+        String::from_utf8(text.to_vec()).expect("text encoding is valid UTF-8")
+    })
+}
+
+/// Encodes upserts as the Redis commands that apply them.
+///
+/// Each batch is encoded as a sequence of pipelines, each of which is the JSON
+/// encoding of a list of commands, so that batches can be spilled to disk.
+struct CommandEncoder {
+    key_prefix: String,
+    data_type: RedisSinkDataType,
+    ttl_millis: Option<String>,
+    key_types: Vec<ColumnType>,
+    value_columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl CommandEncoder {
+    fn new(connection: &RedisSinkConnection) -> Self {
+        let types = &connection.value_desc.typ().column_types;
+        CommandEncoder {
+            key_prefix: connection.key_prefix.clone(),
+            data_type: connection.data_type,
+            ttl_millis: connection.ttl.map(|ttl| ttl.as_millis().to_string()),
+            key_types: connection
+                .key_indices
+                .iter()
+                .map(|i| types[*i].clone())
+                .collect(),
+            value_columns: column_names_and_types(connection.value_desc.clone()),
+        }
+    }
+
+    /// Returns the Redis key for `key`.
+    ///
+    /// A single column is identified by its text format; multiple columns are
+    /// identified by the JSON array of their text formats.
+    fn key(&self, key: &Row) -> String {
+        let texts = key
+            .iter()
+            .zip(&self.key_types)
+            .map(|(datum, typ)| encode_text(datum, typ))
+            .collect::<Vec<_>>();
+        let id = if texts.len() == 1 {
+            texts.into_element().unwrap_or_else(|| "NULL".into())
+        } else {
+            serde_json::to_string(&texts).expect("serializing strings cannot fail")
+        };
+        format!("{}{}", self.key_prefix, id)
+    }
+
+    /// Appends the commands that apply the upsert of `key` to `value` to
+    /// `commands`.
+    ///
+    /// Every written key is overwritten entirely, which makes applying a
+    /// batch idempotent.
+    fn encode(&self, commands: &mut Vec<Vec<String>>, key: &Row, value: &Option<Row>) {
+        let key = self.key(key);
+        let value = match value {
+            None => {
+                commands.push(vec!["DEL".into(), key]);
+                return;
+            }
+            Some(value) => value,
+        };
+        match self.data_type {
+            RedisSinkDataType::Hash => {
+                // Remove the fields of any null columns.
+                commands.push(vec!["DEL".into(), key.clone()]);
+                let mut hset = vec!["HSET".into(), key.clone()];
+                for (datum, (name, typ)) in value.iter().zip(&self.value_columns) {
+                    if let Some(text) = encode_text(datum, typ) {
+                        hset.push(name.as_str().into());
+                        hset.push(text);
+                    }
+                }
+                // A hash without fields does not exist in Redis.
+                if hset.len() == 2 {
+                    return;
+                }
+                commands.push(hset);
+                if let Some(ttl_millis) = &self.ttl_millis {
+                    commands.push(vec!["PEXPIRE".into(), key, ttl_millis.clone()]);
+                }
+            }
+            RedisSinkDataType::String => {
+                let json = encode_datums_as_json(value.iter(), &self.value_columns);
+                let mut set = vec!["SET".into(), key, json.to_string()];
+                if let Some(ttl_millis) = &self.ttl_millis {
+                    set.push("PX".into());
+                    set.push(ttl_millis.clone());
+                }
+                commands.push(set);
+            }
+        }
+    }
+
+    /// Returns the pipelines that apply the upserts in `batch`, each of which
+    /// covers at most `MAX_KEYS_PER_PIPELINE` keys.
+    fn encode_upserts(&self, batch: &HashMap<Row, Option<Row>>) -> Vec<String> {
+        let mut pipelines = vec![];
+        for chunk in &batch.iter().chunks(MAX_KEYS_PER_PIPELINE) {
+            let mut commands = vec![];
+            for (key, value) in chunk {
+                self.encode(&mut commands, key, value);
+            }
+            pipelines
+                .push(serde_json::to_string(&commands).expect("serializing strings cannot fail"));
+        }
+        pipelines
+    }
+}
+
+impl BatchEncoder for CommandEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        self.encode_upserts(&collapse_upserts(updates))
+    }
+}
+
+/// Executes batches of pipelines on the server.
+struct RedisWriter {
+    connection: RedisSinkConnection,
+    connection_context: ConnectionContext,
+    client: Option<Connection>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for RedisWriter {
+    /// Executes each of `pipelines` as a transaction, connecting to the
+    /// server first if necessary.
+    async fn write(&mut self, pipelines: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            let client = connect(&self.connection, &*self.connection_context.secrets_reader)
+                .await
+                .context("error connecting to redis")?;
+            self.client = Some(client);
+        }
+        let client = self.client.as_mut().expect("known to exist");
+        let mut result = Ok(());
+        for pipeline in pipelines {
+            let commands: Vec<Vec<String>> = serde_json::from_str(pipeline)?;
+            let mut pipe = redis::pipe();
+            pipe.atomic();
+            for command in commands {
+                let mut cmd = redis::cmd(&command[0]);
+                for arg in &command[1..] {
+                    cmd.arg(arg);
+                }
+                pipe.add_command(cmd).ignore();
+            }
+            result = pipe.query_async::<_, ()>(client).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_err() {
+            // The connection may be unusable after an error, so start over
+            // with a fresh one.
+            self.client = None;
+        }
+        Ok(result?)
+    }
+}
@@ -0,0 +1,675 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes a collection as a sequence of objects into S3.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arrow2::array::{Array, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::write::{
+    CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use aws_sdk_s3::types::{ByteStream, SdkError};
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use timely::PartialOrder;
+use tracing::{debug, info, warn};
+
+use mz_interchange::encode::{column_names_and_types, Encode, TypedDatum};
+use mz_interchange::envelopes::dbz_envelope;
+use mz_interchange::json::{JsonEncoder, ToJson};
+use mz_ore::cast::CastFrom;
+use mz_ore::collections::CollectionExt;
+use mz_ore::retry::Retry;
+use mz_repr::{ColumnName, ColumnType, Datum, Diff, GlobalId, Row, ScalarType, Timestamp};
+use mz_timely_util::async_op;
+use mz_timely_util::operators_async_ext::OperatorBuilderExt;
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::table_format::Table;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{
+    S3CollisionPolicy, S3KeyTemplateSegment, S3SinkConnection, S3SinkFormat, SinkAsOf,
+    SinkEnvelope, StorageSinkDesc,
+};
+
+// 30s is a good maximum backoff for network operations. Long enough to reduce
+// load on an upstream system, but short enough that we can respond quickly when
+// the upstream system comes back online.
+pub(super) const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+// The user-defined metadata with which each object is tagged, so that a sink
+// can tell the objects it wrote apart from objects written by anyone else.
+const SINK_ID_METADATA: &str = "mz-sink-id";
+const LOWER_METADATA: &str = "mz-lower";
+const UPPER_METADATA: &str = "mz-upper";
+
+impl<G> SinkRender<G> for S3SinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        false
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // Like the Kafka sink, a single worker writes all objects, so that the
+        // objects partition the timestamps of the sinked collection.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = s3(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.envelope,
+            sink.as_of.clone(),
+            Rc::clone(&shared_frontier),
+            &storage_state.connection_context,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+struct S3SinkToken {
+    shutdown_flag: Arc<AtomicBool>,
+}
+
+impl Drop for S3SinkToken {
+    fn drop(&mut self) {
+        debug!("dropping s3 sink");
+        self.shutdown_flag.store(true, Ordering::SeqCst);
+    }
+}
+
+/// The object that is currently being assembled by an S3 sink.
+///
+/// Only updates at closed timestamps are ever added to an object, so an
+/// object always describes a contiguous range of complete timestamps.
+struct PendingObject {
+    rows: Vec<(Row, usize)>,
+    lower: Option<Timestamp>,
+    upper: Option<Timestamp>,
+    size_bytes: u64,
+    opened_at: Instant,
+}
+
+impl PendingObject {
+    fn new() -> Self {
+        PendingObject {
+            rows: Vec::new(),
+            lower: None,
+            upper: None,
+            size_bytes: 0,
+            opened_at: Instant::now(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn push(&mut self, time: Timestamp, rows: Vec<(Row, usize)>) {
+        if self.is_empty() {
+            self.opened_at = Instant::now();
+            self.lower = Some(time);
+        }
+        self.upper = Some(time);
+        for (row, count) in rows {
+            self.size_bytes += u64::cast_from(row.byte_len() * count);
+            self.rows.push((row, count));
+        }
+    }
+
+    /// Reports whether the object is due to be written out, either because it
+    /// has grown too large or because it has been open for too long.
+    fn should_rotate(&self, max_file_size: u64, rotation_interval: Duration) -> bool {
+        !self.is_empty()
+            && (self.size_bytes >= max_file_size || self.opened_at.elapsed() >= rotation_interval)
+    }
+
+    /// Returns the time remaining until the object is due to be rotated.
+    fn remaining(&self, rotation_interval: Duration) -> Duration {
+        rotation_interval.saturating_sub(self.opened_at.elapsed())
+    }
+
+    /// Returns the key under which this object is stored, by rendering
+    /// `template` beneath `prefix`.
+    ///
+    /// `sequence` is the position of this object among the objects written
+    /// since the sink was started.
+    fn key(
+        &self,
+        prefix: &str,
+        template: &[S3KeyTemplateSegment],
+        id: GlobalId,
+        sequence: u64,
+        format: S3SinkFormat,
+    ) -> String {
+        let prefix = prefix.trim_end_matches('/');
+        let lower = self.lower.expect("object is not empty");
+        let upper = self.upper.expect("object is not empty");
+        let mut name = String::new();
+        for segment in template {
+            match segment {
+                S3KeyTemplateSegment::Literal(literal) => name.push_str(literal),
+                S3KeyTemplateSegment::SinkId => name.push_str(&id.to_string()),
+                S3KeyTemplateSegment::Lower => name.push_str(&lower.to_string()),
+                S3KeyTemplateSegment::Upper => name.push_str(&upper.to_string()),
+                S3KeyTemplateSegment::Sequence => name.push_str(&sequence.to_string()),
+                S3KeyTemplateSegment::Extension => name.push_str(format.extension()),
+            }
+        }
+        if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        }
+    }
+
+    /// Returns the metadata with which this object is tagged when written by
+    /// the sink `id`.
+    fn metadata(&self, id: GlobalId) -> BTreeMap<String, String> {
+        let lower = self.lower.expect("object is not empty");
+        let upper = self.upper.expect("object is not empty");
+        BTreeMap::from([
+            (SINK_ID_METADATA.to_string(), id.to_string()),
+            (LOWER_METADATA.to_string(), lower.to_string()),
+            (UPPER_METADATA.to_string(), upper.to_string()),
+        ])
+    }
+}
+
+/// Returns the key that an object tagged with `metadata` should be written to,
+/// given the key rendered for it from the sink's key template.
+///
+/// An existing object that carries the same metadata was written by this sink
+/// before a restart, and rewriting it is idempotent. Any other existing object
+/// is a collision, which is handled according to `policy`.
+async fn resolve_collision(
+    name: &str,
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: String,
+    metadata: &BTreeMap<String, String>,
+    policy: S3CollisionPolicy,
+) -> String {
+    let mut candidate = key.clone();
+    for suffix in 1.. {
+        let existing = Retry::default()
+            .clamp_backoff(BACKOFF_CLAMP)
+            .retry_async(|_| async {
+                let result = match client
+                    .head_object()
+                    .bucket(bucket)
+                    .key(&candidate)
+                    .send()
+                    .await
+                {
+                    Ok(object) => Ok(Some(
+                        object
+                            .metadata()
+                            .map(|m| {
+                                m.iter()
+                                    .map(|(k, v)| (k.clone(), v.clone()))
+                                    .collect::<BTreeMap<_, _>>()
+                            })
+                            .unwrap_or_default(),
+                    )),
+                    Err(SdkError::ServiceError { err, .. }) if err.is_not_found() => Ok(None),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = &result {
+                    warn!("{}: error checking for object {}: {}", name, candidate, e);
+                }
+                result
+            })
+            .await
+            .expect("Infinite retry cannot fail");
+        match existing {
+            None => return candidate,
+            Some(existing) if existing == *metadata => return candidate,
+            Some(_) => match policy {
+                S3CollisionPolicy::Error => panic!(
+                    "{}: object s3://{}/{} already exists and was not written by this sink",
+                    name, bucket, candidate
+                ),
+                S3CollisionPolicy::Overwrite => {
+                    warn!(
+                        "{}: overwriting object s3://{}/{} not written by this sink",
+                        name, bucket, candidate
+                    );
+                    return candidate;
+                }
+                S3CollisionPolicy::Suffix => candidate = suffixed_key(&key, suffix),
+            },
+        }
+    }
+    unreachable!("suffixes are unbounded")
+}
+
+/// Appends `-{suffix}` to the object name in `key`, before its extension.
+fn suffixed_key(key: &str, suffix: u64) -> String {
+    let name_start = key.rfind('/').map_or(0, |i| i + 1);
+    match key[name_start..].rfind('.') {
+        Some(i) if i > 0 => {
+            let (stem, extension) = key.split_at(name_start + i);
+            format!("{}-{}{}", stem, suffix, extension)
+        }
+        _ => format!("{}-{}", key, suffix),
+    }
+}
+
+fn s3<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: S3SinkConnection,
+    envelope: Option<SinkEnvelope>,
+    as_of: SinkAsOf,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    connection_context: &ConnectionContext,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("s3-{}", id);
+    let stream = &collection.inner;
+    let scope = stream.scope();
+    let mut builder = OperatorBuilder::new(name.clone(), scope.clone());
+    let activator = scope.activator_for(&builder.operator_info().address[..]);
+
+    // We want exactly one worker to write all objects.
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let mut input = builder.new_input(stream, Exchange::new(move |_| hashed_id));
+
+    let debezium = matches!(envelope, Some(SinkEnvelope::Debezium));
+    let mut columns = column_names_and_types(connection.value_desc.clone());
+    if debezium {
+        columns = dbz_envelope(columns);
+    }
+    let json_encoder = JsonEncoder::new(None, connection.value_desc.clone(), debezium);
+    let mut table = connection
+        .table_format
+        .map(|format| Table::new(format, id, &connection.bucket, &connection.prefix, &columns));
+    let mut table_loaded = false;
+    let mut committed_upper = None;
+
+    let shutdown_flag = Arc::new(AtomicBool::new(false));
+    let shutdown_flag_clone = Arc::clone(&shutdown_flag);
+    let connection_context = connection_context.clone();
+
+    let mut client = None;
+    let mut pending_rows: BTreeMap<Timestamp, Vec<(Row, usize)>> = BTreeMap::new();
+    let mut object = PendingObject::new();
+    let mut sequence = 0;
+    let mut vector = Vec::new();
+
+    builder.build_async(
+        scope,
+        async_op!(|_initial_capabilities, frontiers| {
+            if shutdown_flag_clone.load(Ordering::SeqCst) {
+                debug!("shutting down sink: {}", name);
+                // NOTE: We never downgrade our write frontier to the empty
+                // frontier when shutting down. See the Kafka sink for details.
+                return false;
+            }
+
+            if is_active_worker {
+                if client.is_none() {
+                    let config = connection
+                        .aws
+                        .load(
+                            connection_context.aws_external_id_prefix.as_ref(),
+                            Some(&connection.connection_id),
+                            &*connection_context.secrets_reader,
+                        )
+                        .await;
+                    client = Some(aws_sdk_s3::Client::new(&config));
+                }
+                // Skip the updates that were committed to the table before a
+                // restart.
+                if let (Some(table), false) = (&mut table, table_loaded) {
+                    let client = client.as_ref().expect("known to exist");
+                    committed_upper = table.load(client).await;
+                    table_loaded = true;
+                }
+            }
+
+            let frontier = frontiers.clone().into_element();
+
+            input.for_each(|_, rows| {
+                assert!(is_active_worker);
+                rows.swap(&mut vector);
+                for ((_key, value), time, diff) in vector.drain(..) {
+                    let should_emit = if as_of.strict {
+                        as_of.frontier.less_than(&time)
+                    } else {
+                        as_of.frontier.less_equal(&time)
+                    };
+                    if !should_emit || committed_upper.map_or(false, |upper| time < upper) {
+                        continue;
+                    }
+
+                    assert!(diff >= 0, "can't sink negative multiplicities");
+                    let value = match value {
+                        Some(value) if diff > 0 => value,
+                        _ => continue,
+                    };
+                    pending_rows
+                        .entry(time)
+                        .or_default()
+                        .push((value, usize::try_from(diff).expect("diff is positive")));
+                }
+            });
+
+            if !is_active_worker {
+                return false;
+            }
+
+            // Move the rows at all newly closed timestamps into the open object.
+            loop {
+                let time = match pending_rows.keys().next() {
+                    Some(time) if !frontier.less_equal(time) => *time,
+                    _ => break,
+                };
+                let rows = pending_rows.remove(&time).expect("known to exist");
+                object.push(time, rows);
+            }
+
+            // Once the input is complete, there is no point in waiting for
+            // more data to arrive.
+            let input_done = frontier.is_empty() && !object.is_empty();
+            if input_done
+                || object.should_rotate(connection.max_file_size, connection.rotation_interval)
+            {
+                let client = client.as_ref().expect("known to exist");
+
+                let lower = object.lower.expect("object is not empty");
+                let upper = object.upper.expect("object is not empty");
+                let (key, path) = match &table {
+                    Some(table) => {
+                        let path = table.data_file_path(lower, upper);
+                        (table.key(&path), Some(path))
+                    }
+                    None => {
+                        let key = object.key(
+                            &connection.prefix,
+                            &connection.key_template,
+                            id,
+                            sequence,
+                            connection.format,
+                        );
+                        let key = resolve_collision(
+                            &name,
+                            client,
+                            &connection.bucket,
+                            key,
+                            &object.metadata(id),
+                            connection.on_collision,
+                        )
+                        .await;
+                        (key, None)
+                    }
+                };
+                let body = match connection.format {
+                    S3SinkFormat::Json => encode_json(&json_encoder, &object.rows),
+                    S3SinkFormat::Parquet => encode_parquet(&columns, &object.rows)
+                        .expect("encoding parquet object for s3 sink"),
+                };
+
+                info!(
+                    "{}: writing {} rows ({} bytes) to s3://{}/{}",
+                    name,
+                    object.rows.len(),
+                    body.len(),
+                    connection.bucket,
+                    key
+                );
+                Retry::default()
+                    .clamp_backoff(BACKOFF_CLAMP)
+                    .retry_async(|_| async {
+                        let result = client
+                            .put_object()
+                            .bucket(&connection.bucket)
+                            .key(&key)
+                            .set_metadata(Some(object.metadata(id).into_iter().collect()))
+                            .body(ByteStream::from(body.clone()))
+                            .send()
+                            .await;
+                        if let Err(e) = &result {
+                            warn!("{}: error writing object {}: {}", name, key, e);
+                        }
+                        result
+                    })
+                    .await
+                    .expect("Infinite retry cannot fail");
+
+                if let (Some(table), Some(path)) = (&mut table, path) {
+                    let record_count = object
+                        .rows
+                        .iter()
+                        .map(|(_row, count)| u64::cast_from(*count))
+                        .sum();
+                    let size = u64::cast_from(body.len());
+                    table
+                        .commit(client, &path, size, record_count, upper.step_forward())
+                        .await;
+                }
+
+                object = PendingObject::new();
+                sequence += 1;
+            }
+
+            // Our write frontier is the input frontier, held back by any rows
+            // that we have not yet written out.
+            let new_frontier = match object.lower {
+                Some(lower) => Antichain::from_elem(lower),
+                None => frontier.clone(),
+            };
+            if PartialOrder::less_than(&*write_frontier.borrow(), &new_frontier) {
+                write_frontier.borrow_mut().clone_from(&new_frontier);
+            }
+
+            if !object.is_empty() {
+                // Make sure we come back to rotate the object even if no more
+                // data arrives.
+                activator.activate_after(object.remaining(connection.rotation_interval));
+                return true;
+            }
+
+            !pending_rows.is_empty()
+        }),
+    );
+
+    Rc::new(S3SinkToken { shutdown_flag })
+}
+
+/// Encodes rows as newline-delimited JSON.
+fn encode_json(encoder: &JsonEncoder, rows: &[(Row, usize)]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (row, count) in rows {
+        let encoded = encoder.encode_value_unchecked(row.clone());
+        for _ in 0..*count {
+            buf.extend_from_slice(&encoded);
+            buf.push(b'\n');
+        }
+    }
+    buf
+}
+
+/// Encodes rows as a Parquet file with a single row group.
+///
+/// Booleans, integers and floats are mapped to their native Parquet types. All
+/// other types are written as strings, using their JSON representation for
+/// non-string types.
+fn encode_parquet(
+    columns: &[(ColumnName, ColumnType)],
+    rows: &[(Row, usize)],
+) -> Result<Vec<u8>, arrow2::error::Error> {
+    let fields: Vec<_> = columns
+        .iter()
+        .map(|(name, typ)| Field::new(name.as_str(), parquet_type(typ), typ.nullable))
+        .collect();
+    let schema = Schema::from(fields);
+
+    // Transpose the rows into columns, expanding repeated rows.
+    let mut datums: Vec<Vec<Datum>> = vec![Vec::new(); columns.len()];
+    for (row, count) in rows {
+        for _ in 0..*count {
+            for (i, datum) in row.iter().enumerate() {
+                datums[i].push(datum);
+            }
+        }
+    }
+
+    let arrays: Vec<Box<dyn Array>> = columns
+        .iter()
+        .zip(datums)
+        .map(|((_name, typ), datums)| parquet_array(typ, datums))
+        .collect();
+    let chunk = Chunk::new(arrays);
+
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|_| vec![Encoding::Plain])
+        .collect();
+    let row_groups =
+        RowGroupIterator::try_new(std::iter::once(Ok(chunk)), &schema, options, encodings)?;
+
+    let mut buf = Vec::new();
+    let mut writer = FileWriter::try_new(&mut buf, schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(buf)
+}
+
+pub(super) fn parquet_type(typ: &ColumnType) -> DataType {
+    match typ.scalar_type {
+        ScalarType::Bool => DataType::Boolean,
+        ScalarType::Int16 => DataType::Int16,
+        ScalarType::Int32 => DataType::Int32,
+        ScalarType::Int64 => DataType::Int64,
+        ScalarType::Float32 => DataType::Float32,
+        ScalarType::Float64 => DataType::Float64,
+        _ => DataType::Utf8,
+    }
+}
+
+fn parquet_array(typ: &ColumnType, datums: Vec<Datum>) -> Box<dyn Array> {
+    fn non_null<T>(datum: Datum, f: impl FnOnce(Datum) -> T) -> Option<T> {
+        if datum.is_null() {
+            None
+        } else {
+            Some(f(datum))
+        }
+    }
+
+    match typ.scalar_type {
+        ScalarType::Bool => Box::new(BooleanArray::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_bool()))
+                .collect::<Vec<_>>(),
+        )),
+        ScalarType::Int16 => Box::new(PrimitiveArray::<i16>::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_int16()))
+                .collect::<Vec<_>>(),
+        )),
+        ScalarType::Int32 => Box::new(PrimitiveArray::<i32>::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_int32()))
+                .collect::<Vec<_>>(),
+        )),
+        ScalarType::Int64 => Box::new(PrimitiveArray::<i64>::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_int64()))
+                .collect::<Vec<_>>(),
+        )),
+        ScalarType::Float32 => Box::new(PrimitiveArray::<f32>::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_float32()))
+                .collect::<Vec<_>>(),
+        )),
+        ScalarType::Float64 => Box::new(PrimitiveArray::<f64>::from(
+            datums
+                .into_iter()
+                .map(|d| non_null(d, |d| d.unwrap_float64()))
+                .collect::<Vec<_>>(),
+        )),
+        _ => Box::new(Utf8Array::<i32>::from(
+            datums
+                .into_iter()
+                .map(|d| {
+                    non_null(d, |d| match TypedDatum::new(d, typ.clone()).json() {
+                        serde_json::Value::String(s) => s,
+                        v => v.to_string(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )),
+    }
+}
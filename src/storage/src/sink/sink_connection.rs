@@ -7,19 +7,46 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{anyhow, Context};
-use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
+use anyhow::{anyhow, bail, Context};
+use differential_dataflow::{Collection, Hashable};
+use rdkafka::admin::{
+    AdminClient, AdminOptions, AlterConfig, NewTopic, ResourceSpecifier, TopicReplication,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::debug;
 
 use mz_kafka_util::client::{create_new_client_config, MzClientContext};
+use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_timely_util::async_op;
+use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
-use crate::types::connections::{ConnectionContext, PopulateClientConfig};
+use crate::controller::CollectionMetadata;
+use crate::sink::elasticsearch::ElasticsearchClient;
+use crate::sink::spill::{BatchWriter, SpillBuffer, SpillingWriter};
+use crate::storage_state::StorageState;
+use crate::types::connections::{
+    ConnectionContext, CsrConnection, KafkaConnection, PopulateClientConfig,
+};
 use crate::types::sinks::{
-    KafkaConsistencyConfig, KafkaSinkConnection, KafkaSinkConnectionBuilder,
-    KafkaSinkConnectionRetention, KafkaSinkFormat, KafkaSinkProgressConnection,
-    PublishedSchemaInfo, StorageSinkConnection, StorageSinkConnectionBuilder,
+    ElasticsearchSinkConnection, GrpcSinkConnection, KafkaSinkConnection,
+    KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention, KafkaSinkFormat,
+    KafkaSinkProgressConnection, KafkaSinkTextFormat, MqttSinkConnection, NatsSinkConnection,
+    PostgresSinkConnection, PublishedSchemaFormat, PublishedSchemaInfo, RedisSinkConnection,
+    S3SinkConnection, SinkAsOf, SqsSinkConnection, StorageSinkConnection,
+    StorageSinkConnectionBuilder, StorageSinkDesc, SubjectNameStrategy,
 };
 
 /// Build a sink connection.
@@ -31,15 +58,31 @@ pub async fn build_sink_connection(
 ) -> Result<StorageSinkConnection, anyhow::Error> {
     match builder {
         StorageSinkConnectionBuilder::Kafka(k) => build_kafka(k, connection_context).await,
+        StorageSinkConnectionBuilder::S3(s3) => build_s3(s3, connection_context).await,
+        StorageSinkConnectionBuilder::Postgres(postgres) => {
+            build_postgres(postgres, connection_context).await
+        }
+        // The file is created by the sink itself, on the storage replica.
+        StorageSinkConnectionBuilder::File(file) => Ok(StorageSinkConnection::File(file)),
+        StorageSinkConnectionBuilder::Elasticsearch(elasticsearch) => {
+            build_elasticsearch(elasticsearch, connection_context).await
+        }
+        StorageSinkConnectionBuilder::Redis(redis) => build_redis(redis, connection_context).await,
+        StorageSinkConnectionBuilder::Nats(nats) => build_nats(nats, connection_context).await,
+        StorageSinkConnectionBuilder::Mqtt(mqtt) => build_mqtt(mqtt, connection_context).await,
+        StorageSinkConnectionBuilder::Grpc(grpc) => build_grpc(grpc, connection_context).await,
+        StorageSinkConnectionBuilder::Sqs(sqs) => build_sqs(sqs, connection_context).await,
     }
 }
 
-async fn ensure_kafka_topic(
+pub(crate) async fn ensure_kafka_topic(
     client: &AdminClient<MzClientContext>,
     topic: &str,
     mut partition_count: i32,
     mut replication_factor: i32,
     retention: KafkaSinkConnectionRetention,
+    compacted: bool,
+    config: &BTreeMap<String, String>,
 ) -> Result<(), anyhow::Error> {
     // if either partition count or replication factor should be defaulted to the broker's config
     // (signaled by a value of -1), explicitly poll the broker to discover the defaults.
@@ -137,6 +180,12 @@ async fn ensure_kafka_topic(
     if let Some(ref retention_bytes) = retention_bytes_str {
         kafka_topic = kafka_topic.set("retention.bytes", retention_bytes);
     }
+    if compacted {
+        kafka_topic = kafka_topic.set("cleanup.policy", "compact");
+    }
+    for (name, value) in config {
+        kafka_topic = kafka_topic.set(name, value);
+    }
 
     mz_kafka_util::admin::ensure_topic(
         client,
@@ -149,38 +198,137 @@ async fn ensure_kafka_topic(
     Ok(())
 }
 
-/// Publish value and optional key schemas for a given topic.
+/// Ensures that a topic of a Kafka sink exists, creating it with
+/// [`ensure_kafka_topic`] unless the sink writes to Event Hubs, which does
+/// not let clients create topics.
+async fn ensure_sink_topic(
+    client: &AdminClient<MzClientContext>,
+    connection: &KafkaConnection,
+    topic: &str,
+    partition_count: i32,
+    replication_factor: i32,
+    retention: KafkaSinkConnectionRetention,
+    compacted: bool,
+    config: &BTreeMap<String, String>,
+) -> Result<(), anyhow::Error> {
+    if !connection.event_hubs {
+        return ensure_kafka_topic(
+            client,
+            topic,
+            partition_count,
+            replication_factor,
+            retention,
+            compacted,
+            config,
+        )
+        .await;
+    }
+
+    let metadata = client
+        .inner()
+        .fetch_metadata(Some(topic), Duration::from_secs(5))
+        .with_context(|| format!("error fetching metadata of topic {} for sink", topic))?;
+    let exists = metadata
+        .topics()
+        .iter()
+        .any(|t| t.name() == topic && t.error().is_none());
+    if !exists {
+        bail!(
+            "topic {} does not exist: sinks into Event Hubs do not create topics, so the event hub {} must be created before the sink",
+            topic,
+            topic
+        );
+    }
+    Ok(())
+}
+
+/// Changes the retention of the topic to which a Kafka sink writes.
+///
+/// Kafka's `AlterConfigs` API replaces the entire configuration of the topic,
+/// so the topic ends up configured as if it had been created with `retention`.
+pub async fn alter_kafka_sink_retention(
+    connection: &KafkaSinkConnection,
+    retention: KafkaSinkConnectionRetention,
+    connection_context: &ConnectionContext,
+) -> Result<(), anyhow::Error> {
+    if connection.connection.event_hubs {
+        bail!("Event Hubs does not support altering the retention of topics");
+    }
+    let mut config = create_new_client_config(connection_context.librdkafka_log_level);
+    connection
+        .populate_client_config(&mut config, &*connection_context.secrets_reader)
+        .await;
+    let client_context = connection
+        .connection
+        .client_context(
+            &*connection_context.secrets_reader,
+            connection_context.aws_external_id_prefix.as_ref(),
+        )
+        .await?;
+
+    let client: AdminClient<_> = config
+        .create_with_context(client_context)
+        .context("creating admin client failed")?;
+
+    let mut alter_config = AlterConfig::new(ResourceSpecifier::Topic(&connection.topic));
+    let retention_ms_str = retention.duration.map(|d| d.to_string());
+    let retention_bytes_str = retention.bytes.map(|s| s.to_string());
+    if let Some(ref retention_ms) = retention_ms_str {
+        alter_config = alter_config.set("retention.ms", retention_ms);
+    }
+    if let Some(ref retention_bytes) = retention_bytes_str {
+        alter_config = alter_config.set("retention.bytes", retention_bytes);
+    }
+
+    let results = client
+        .alter_configs(
+            &[alter_config],
+            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+        )
+        .await
+        .with_context(|| format!("error altering retention of topic {}", connection.topic))?;
+    for result in results {
+        if let Err((_, e)) = result {
+            Err(anyhow!(
+                "error altering retention of topic {}: {}",
+                connection.topic,
+                e
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Publish value and optional key schemas under the given subjects.
 ///
 /// TODO(benesch): do we need to delete the Kafka topic if publishing the
 /// schema fails?
 async fn publish_kafka_schemas(
     ccsr: &mz_ccsr::Client,
-    topic: &str,
+    key_subject: Option<&str>,
     key_schema: Option<&str>,
     key_schema_type: Option<mz_ccsr::SchemaType>,
+    value_subject: &str,
     value_schema: &str,
     value_schema_type: mz_ccsr::SchemaType,
 ) -> Result<(Option<i32>, i32), anyhow::Error> {
     let value_schema_id = ccsr
-        .publish_schema(
-            &format!("{}-value", topic),
-            value_schema,
-            value_schema_type,
-            &[],
-        )
+        .publish_schema(value_subject, value_schema, value_schema_type, &[])
         .await
         .context("unable to publish value schema to registry in kafka sink")?;
 
-    let key_schema_id = if let Some(key_schema) = key_schema {
-        let key_schema_type =
-            key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
-        Some(
-            ccsr.publish_schema(&format!("{}-key", topic), key_schema, key_schema_type, &[])
-                .await
-                .context("unable to publish key schema to registry in kafka sink")?,
-        )
-    } else {
-        None
+    let key_schema_id = match (key_subject, key_schema) {
+        (Some(key_subject), Some(key_schema)) => {
+            let key_schema_type =
+                key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
+            Some(
+                ccsr.publish_schema(key_subject, key_schema, key_schema_type, &[])
+                    .await
+                    .context("unable to publish key schema to registry in kafka sink")?,
+            )
+        }
+        _ => None,
     };
 
     Ok((key_schema_id, value_schema_id))
@@ -195,65 +343,149 @@ async fn build_kafka(
     builder
         .populate_client_config(&mut config, &*connection_context.secrets_reader)
         .await;
+    let client_context = builder
+        .connection
+        .client_context(
+            &*connection_context.secrets_reader,
+            connection_context.aws_external_id_prefix.as_ref(),
+        )
+        .await?;
 
     let client: AdminClient<_> = config
-        .create_with_context(MzClientContext)
+        .create_with_context(client_context)
         .context("creating admin client failed")?;
 
-    ensure_kafka_topic(
+    ensure_sink_topic(
         &client,
+        &builder.connection,
         &builder.topic_name,
         builder.partition_count,
         builder.replication_factor,
         builder.retention,
+        false,
+        &builder.topic_config,
     )
     .await
     .context("error registering kafka topic for sink")?;
 
+    let text_format = match &builder.format {
+        KafkaSinkFormat::Csv { delimiter, header } => Some(KafkaSinkTextFormat::Csv {
+            delimiter: *delimiter,
+            header: header.clone(),
+        }),
+        KafkaSinkFormat::Text => Some(KafkaSinkTextFormat::Text),
+        KafkaSinkFormat::Bytes => Some(KafkaSinkTextFormat::Bytes),
+        _ => None,
+    };
+
     let published_schema_info = match builder.format {
         KafkaSinkFormat::Avro {
             key_schema,
             value_schema,
+            key_fullname,
+            value_fullname,
+            user_value_schema,
+            subject_name_strategy,
+            compatibility_level,
             csr_connection,
-            ..
+            delete_subjects_on_drop,
         } => {
-            let ccsr = csr_connection
-                .connect(&*connection_context.secrets_reader)
-                .await?;
-            let (key_schema_id, value_schema_id) = publish_kafka_schemas(
-                &ccsr,
+            let info = publish_sink_schemas(
                 &builder.topic_name,
                 key_schema.as_deref(),
-                Some(mz_ccsr::SchemaType::Avro),
                 &value_schema,
-                mz_ccsr::SchemaType::Avro,
+                subject_name_strategy,
+                compatibility_level,
+                csr_connection,
+                delete_subjects_on_drop,
+                PublishedSchemaFormat::Avro,
+                &connection_context,
             )
-            .await
-            .context("error publishing kafka schemas for sink")?;
+            .await?;
             Some(PublishedSchemaInfo {
-                key_schema_id,
-                value_schema_id,
+                key_fullname,
+                value_fullname,
+                value_schema: user_value_schema.then_some(value_schema),
+                ..info
             })
         }
-        KafkaSinkFormat::Json => None,
-    };
-
-    let progress = match builder.consistency_config {
-        KafkaConsistencyConfig::Progress { topic } => {
-            ensure_kafka_topic(
-                &client,
-                &topic,
-                1,
-                builder.replication_factor,
-                KafkaSinkConnectionRetention::default(),
+        KafkaSinkFormat::Protobuf {
+            key_schema,
+            value_schema,
+            csr_connection,
+            delete_subjects_on_drop,
+        } => Some(
+            publish_sink_schemas(
+                &builder.topic_name,
+                key_schema.as_deref(),
+                &value_schema,
+                SubjectNameStrategy::TopicName,
+                None,
+                csr_connection,
+                delete_subjects_on_drop,
+                PublishedSchemaFormat::Protobuf,
+                &connection_context,
             )
-            .await
-            .context("error registering kafka consistency topic for sink")?;
+            .await?,
+        ),
+        KafkaSinkFormat::JsonSchema {
+            key_schema,
+            value_schema,
+            csr_connection,
+            delete_subjects_on_drop,
+        } => Some(
+            publish_sink_schemas(
+                &builder.topic_name,
+                key_schema.as_deref(),
+                &value_schema,
+                SubjectNameStrategy::TopicName,
+                None,
+                csr_connection,
+                delete_subjects_on_drop,
+                PublishedSchemaFormat::Json,
+                &connection_context,
+            )
+            .await?,
+        ),
+        KafkaSinkFormat::Json
+        | KafkaSinkFormat::Csv { .. }
+        | KafkaSinkFormat::Text
+        | KafkaSinkFormat::Bytes => None,
+    };
 
-            KafkaSinkProgressConnection { topic }
-        }
+    // The progress topic only ever needs the latest record for each sink, so
+    // it is compacted rather than subject to time or size based retention.
+    ensure_sink_topic(
+        &client,
+        &builder.connection,
+        &builder.progress_topic,
+        1,
+        builder.replication_factor,
+        KafkaSinkConnectionRetention::default(),
+        true,
+        &BTreeMap::new(),
+    )
+    .await
+    .context("error registering kafka progress topic for sink")?;
+    let progress = KafkaSinkProgressConnection {
+        topic: builder.progress_topic,
     };
 
+    if let Some(dead_letter_topic) = &builder.dead_letter_topic {
+        ensure_sink_topic(
+            &client,
+            &builder.connection,
+            dead_letter_topic,
+            -1,
+            builder.replication_factor,
+            KafkaSinkConnectionRetention::default(),
+            false,
+            &BTreeMap::new(),
+        )
+        .await
+        .context("error registering kafka dead letter topic for sink")?;
+    }
+
     Ok(StorageSinkConnection::Kafka(KafkaSinkConnection {
         connection: builder.connection,
         connection_id: builder.connection_id,
@@ -263,7 +495,482 @@ async fn build_kafka(
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
         published_schema_info,
+        text_format,
+        headers: builder.headers,
+        partition_by: builder.partition_by,
+        message_timestamp: builder.message_timestamp,
+        compression_type: builder.compression_type,
+        linger_ms: builder.linger_ms,
+        batch_num_messages: builder.batch_num_messages,
+        dead_letter_topic: builder.dead_letter_topic,
+        retention: builder.retention,
+        retry: builder.retry,
+        topic_fanout: builder.topic_fanout,
         progress,
-        fuel: builder.fuel,
     }))
 }
+
+/// Publishes the key and value schemas of a Kafka sink under the subjects
+/// named by `subject_name_strategy`, after setting their compatibility level
+/// to `compatibility_level`, if specified.
+async fn publish_sink_schemas(
+    topic_name: &str,
+    key_schema: Option<&str>,
+    value_schema: &str,
+    subject_name_strategy: SubjectNameStrategy,
+    compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+    csr_connection: CsrConnection,
+    delete_subjects_on_drop: bool,
+    format: PublishedSchemaFormat,
+    connection_context: &ConnectionContext,
+) -> Result<PublishedSchemaInfo, anyhow::Error> {
+    let schema_type = match format {
+        PublishedSchemaFormat::Avro => mz_ccsr::SchemaType::Avro,
+        PublishedSchemaFormat::Protobuf => mz_ccsr::SchemaType::Protobuf,
+        PublishedSchemaFormat::Json => mz_ccsr::SchemaType::Json,
+    };
+    let ccsr = csr_connection
+        .connect(&*connection_context.secrets_reader)
+        .await?;
+    let subject = |schema: &str, is_key| -> Result<String, anyhow::Error> {
+        let record_fullname = match subject_name_strategy {
+            SubjectNameStrategy::TopicName => String::new(),
+            SubjectNameStrategy::RecordName | SubjectNameStrategy::TopicRecordName => {
+                avro_record_fullname(schema)?
+            }
+        };
+        Ok(subject_name_strategy.subject(topic_name, &record_fullname, is_key))
+    };
+    let key_subject = key_schema
+        .map(|key_schema| subject(key_schema, true))
+        .transpose()?;
+    let value_subject = subject(value_schema, false)?;
+
+    if let Some(compatibility_level) = compatibility_level {
+        for subject in key_subject.iter().chain(std::iter::once(&value_subject)) {
+            ccsr.set_subject_compatibility_level(subject, compatibility_level)
+                .await
+                .with_context(|| {
+                    format!("unable to set compatibility level of subject {subject} in kafka sink")
+                })?;
+        }
+    }
+    let (key_schema_id, value_schema_id) = publish_kafka_schemas(
+        &ccsr,
+        key_subject.as_deref(),
+        key_schema,
+        Some(schema_type),
+        &value_subject,
+        value_schema,
+        schema_type,
+    )
+    .await
+    .context("error publishing kafka schemas for sink")?;
+    Ok(PublishedSchemaInfo {
+        key_schema_id,
+        value_schema_id,
+        key_subject,
+        value_subject,
+        delete_subjects_on_drop: delete_subjects_on_drop.then_some(csr_connection),
+        format,
+        key_fullname: None,
+        value_fullname: None,
+        value_schema: None,
+    })
+}
+
+/// Returns the fullname of the record described by the Avro `schema`.
+fn avro_record_fullname(schema: &str) -> Result<String, anyhow::Error> {
+    let schema = mz_interchange::avro::parse_schema(schema)?;
+    match schema.top_node().name {
+        Some(name) => Ok(name.human_name()),
+        None => bail!("Avro schema does not describe a named record"),
+    }
+}
+
+async fn build_s3(
+    connection: S3SinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let config = connection
+        .aws
+        .load(
+            connection_context.aws_external_id_prefix.as_ref(),
+            Some(&connection.connection_id),
+            &*connection_context.secrets_reader,
+        )
+        .await;
+    let client = aws_sdk_s3::Client::new(&config);
+
+    // Fail early if the bucket does not exist or we lack permissions to
+    // access it, rather than discovering this when the first object is
+    // written.
+    client
+        .head_bucket()
+        .bucket(&connection.bucket)
+        .send()
+        .await
+        .with_context(|| format!("error accessing bucket {} for sink", connection.bucket))?;
+
+    Ok(StorageSinkConnection::S3(connection))
+}
+
+async fn build_postgres(
+    connection: PostgresSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let config = connection
+        .connection
+        .config(&*connection_context.secrets_reader)
+        .await?;
+    let client = config.connect("postgres_sink_preflight").await?;
+
+    // Fail early if the table does not exist or is missing any of the sinked
+    // columns, rather than discovering this when the first batch is applied.
+    let query = format!(
+        "SELECT {} FROM {} LIMIT 0",
+        crate::sink::postgres::column_list(&connection.value_desc),
+        connection.table
+    );
+    client
+        .prepare(&query)
+        .await
+        .with_context(|| format!("error validating table {} for sink", connection.table))?;
+
+    Ok(StorageSinkConnection::Postgres(connection))
+}
+
+async fn build_elasticsearch(
+    connection: ElasticsearchSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    // Fail early if the cluster is unreachable or rejects our credentials. The
+    // index itself is created by the cluster when the first document is
+    // indexed, if it does not already exist.
+    let client = ElasticsearchClient::new(&connection, &*connection_context.secrets_reader).await?;
+    client
+        .ping()
+        .await
+        .with_context(|| format!("error connecting to Elasticsearch at {}", connection.url))?;
+
+    Ok(StorageSinkConnection::Elasticsearch(connection))
+}
+
+async fn build_redis(
+    connection: RedisSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    // Fail early if the server is unreachable or rejects our credentials.
+    let ping = async {
+        let mut client =
+            crate::sink::redis::connect(&connection, &*connection_context.secrets_reader).await?;
+        redis::cmd("PING")
+            .query_async::<_, String>(&mut client)
+            .await?;
+        Ok::<_, anyhow::Error>(())
+    };
+    ping.await
+        .with_context(|| format!("error connecting to Redis at {}", connection.url))?;
+
+    Ok(StorageSinkConnection::Redis(connection))
+}
+
+async fn build_nats(
+    connection: NatsSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    // Fail early if the server is unreachable, rejects our credentials, or
+    // does not have JetStream enabled. The streams that capture the sink's
+    // subjects are managed outside of Materialize.
+    let check = async {
+        let client =
+            crate::sink::nats::connect(&connection, &*connection_context.secrets_reader).await?;
+        async_nats::jetstream::new(client)
+            .query_account()
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok::<_, anyhow::Error>(())
+    };
+    check
+        .await
+        .with_context(|| format!("error connecting to NATS JetStream at {}", connection.url))?;
+
+    Ok(StorageSinkConnection::Nats(connection))
+}
+
+async fn build_mqtt(
+    connection: MqttSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    // Fail early if the broker is unreachable or rejects our credentials.
+    crate::sink::mqtt::check_connection(&connection, &*connection_context.secrets_reader)
+        .await
+        .with_context(|| format!("error connecting to MQTT broker at {}", connection.url))?;
+
+    Ok(StorageSinkConnection::Mqtt(connection))
+}
+
+async fn build_grpc(
+    connection: GrpcSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    // Fail early if the server is unreachable. The method itself is only
+    // called once there are updates to stream.
+    crate::sink::grpc::check_connection(&connection, &*connection_context.secrets_reader)
+        .await
+        .with_context(|| format!("error connecting to gRPC server at {}", connection.url))?;
+
+    Ok(StorageSinkConnection::Grpc(connection))
+}
+
+async fn build_sqs(
+    connection: SqsSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let config = connection
+        .aws
+        .load(
+            connection_context.aws_external_id_prefix.as_ref(),
+            Some(&connection.connection_id),
+            &*connection_context.secrets_reader,
+        )
+        .await;
+    let client = aws_sdk_sqs::Client::new(&config);
+
+    // Fail early if the queue does not exist or we lack permissions to
+    // access it, rather than discovering this when the first message is
+    // sent.
+    client
+        .get_queue_attributes()
+        .queue_url(&connection.queue_url)
+        .send()
+        .await
+        .with_context(|| format!("error accessing queue {} for sink", connection.queue_url))?;
+
+    Ok(StorageSinkConnection::Sqs(connection))
+}
+
+/// An update to a sinked collection: its key, its value, and the number of
+/// times it occurs.
+pub(super) type SinkUpdate = (Option<Row>, Option<Row>, usize);
+
+/// Shuts down a sink's operator when dropped.
+pub(super) struct SinkToken {
+    name: String,
+    shutdown_flag: Arc<AtomicBool>,
+}
+
+impl SinkToken {
+    /// Returns a token for the sink named `name`, along with the flag that
+    /// its operator must check to learn that it has been shut down.
+    pub(super) fn new(name: String) -> (SinkToken, Arc<AtomicBool>) {
+        let shutdown_flag = Arc::new(AtomicBool::new(false));
+        let token = SinkToken {
+            name,
+            shutdown_flag: Arc::clone(&shutdown_flag),
+        };
+        (token, shutdown_flag)
+    }
+}
+
+impl Drop for SinkToken {
+    fn drop(&mut self) {
+        debug!("dropping sink: {}", self.name);
+        self.shutdown_flag.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Returns whether this worker writes all updates of the sink `sink_id`, and
+/// the sink's write frontier, which is registered with `storage_state`.
+///
+/// The write frontier of every other worker is empty, so that only the
+/// active worker holds back the sink's progress.
+pub(super) fn single_worker_write_frontier<G>(
+    storage_state: &mut StorageState,
+    sink_id: GlobalId,
+    scope: &G,
+) -> (bool, Rc<RefCell<Antichain<Timestamp>>>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let is_active_worker = usize::cast_from(sink_id.hashed()) % scope.peers() == scope.index();
+    let write_frontier = Rc::new(RefCell::new(if is_active_worker {
+        Antichain::from_elem(Timestamp::minimum())
+    } else {
+        Antichain::new()
+    }));
+    storage_state
+        .sink_write_frontiers
+        .insert(sink_id, Rc::clone(&write_frontier));
+    (is_active_worker, write_frontier)
+}
+
+/// The updates that a sink has received at timestamps that are not yet
+/// closed.
+pub(super) struct PendingUpdates {
+    as_of: SinkAsOf,
+    updates: BTreeMap<Timestamp, Vec<SinkUpdate>>,
+}
+
+impl PendingUpdates {
+    pub(super) fn new(as_of: SinkAsOf) -> Self {
+        PendingUpdates {
+            as_of,
+            updates: BTreeMap::new(),
+        }
+    }
+
+    /// Buffers an update, unless it is not beyond the sink's as-of.
+    pub(super) fn push(
+        &mut self,
+        key: Option<Row>,
+        value: Option<Row>,
+        time: Timestamp,
+        diff: Diff,
+    ) {
+        let should_emit = if self.as_of.strict {
+            self.as_of.frontier.less_than(&time)
+        } else {
+            self.as_of.frontier.less_equal(&time)
+        };
+        if !should_emit {
+            return;
+        }
+
+        assert!(diff >= 0, "can't sink negative multiplicities");
+        if diff == 0 {
+            return;
+        }
+        let count = usize::try_from(diff).expect("known to be positive");
+        self.updates
+            .entry(time)
+            .or_default()
+            .push((key, value, count));
+    }
+
+    /// Removes and returns the updates at all timestamps that `frontier` has
+    /// closed, in timestamp order.
+    pub(super) fn drain_closed(
+        &mut self,
+        frontier: &Antichain<Timestamp>,
+    ) -> BTreeMap<Timestamp, Vec<SinkUpdate>> {
+        let open = match frontier.as_option() {
+            Some(time) => self.updates.split_off(time),
+            None => BTreeMap::new(),
+        };
+        std::mem::replace(&mut self.updates, open)
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+}
+
+/// Encodes the updates of a sink rendered by [`render_batch_sink`] as the
+/// requests that its [`BatchWriter`] applies.
+pub(super) trait BatchEncoder {
+    /// Returns the requests that apply `updates`, the updates at a run of
+    /// newly closed timestamps.
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String>;
+}
+
+/// Collapses `updates` into the latest value of each key, for sinks that
+/// upsert keys in the destination.
+pub(super) fn collapse_upserts(
+    updates: BTreeMap<Timestamp, Vec<SinkUpdate>>,
+) -> HashMap<Row, Option<Row>> {
+    updates
+        .into_values()
+        .flatten()
+        .map(|(key, value, _count)| (key.expect("upsert sinks are always keyed"), value))
+        .collect()
+}
+
+/// Renders a sink that applies the updates at each run of closed timestamps
+/// with `writer`, spilling the encoded batches to disk, up to `spill_budget`
+/// bytes, while the destination is unavailable.
+///
+/// A single worker applies all updates, so that batches are applied in
+/// timestamp order. The sink's write frontier advances only as far as updates
+/// have actually been applied.
+pub(super) fn render_batch_sink<G, E, W>(
+    kind: &str,
+    storage_state: &mut StorageState,
+    sink: &StorageSinkDesc<CollectionMetadata>,
+    sink_id: GlobalId,
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    spill_budget: Option<u64>,
+    encoder: E,
+    writer: W,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+    E: BatchEncoder + 'static,
+    W: BatchWriter + 'static,
+{
+    let name = format!("{}-{}", kind, sink_id);
+    let stream = &collection.inner;
+    let scope = stream.scope();
+    let (is_active_worker, write_frontier) =
+        single_worker_write_frontier(storage_state, sink_id, &scope);
+
+    let mut builder = OperatorBuilder::new(name.clone(), scope.clone());
+    let activator = scope.activator_for(&builder.operator_info().address[..]);
+    let hashed_id = sink_id.hashed();
+    let mut input = builder.new_input(stream, Exchange::new(move |_| hashed_id));
+
+    let buffer = spill_budget.map(|budget| {
+        SpillBuffer::new(
+            budget,
+            &storage_state.sink_metrics.spill,
+            &sink_id.to_string(),
+            &scope.index().to_string(),
+        )
+    });
+    let mut writer = SpillingWriter::new(name.clone(), writer, buffer, activator);
+    let (token, shutdown_flag) = SinkToken::new(name.clone());
+    let mut pending = PendingUpdates::new(sink.as_of.clone());
+    let mut vector = Vec::new();
+
+    builder.build_async(
+        scope,
+        async_op!(|_initial_capabilities, frontiers| {
+            if shutdown_flag.load(Ordering::SeqCst) {
+                debug!("shutting down sink: {}", name);
+                // NOTE: We never downgrade our write frontier to the empty
+                // frontier when shutting down. See the Kafka sink for details.
+                return false;
+            }
+
+            let frontier = frontiers.clone().into_element();
+
+            input.for_each(|_, rows| {
+                assert!(is_active_worker);
+                rows.swap(&mut vector);
+                for ((key, value), time, diff) in vector.drain(..) {
+                    pending.push(key, value, time, diff);
+                }
+            });
+
+            if !is_active_worker {
+                return false;
+            }
+
+            let updates = pending.drain_closed(&frontier);
+            let count: usize = updates.values().map(|updates| updates.len()).sum();
+            if count > 0 {
+                debug!("{}: writing {} updates", name, count);
+            }
+
+            // Apply the updates at all closed timestamps, or spill them to
+            // disk if the destination is unavailable.
+            writer
+                .write(encoder.encode_batch(updates), frontier, &write_frontier)
+                .await;
+
+            !pending.is_empty()
+        }),
+    );
+
+    Rc::new(token)
+}
@@ -0,0 +1,415 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Spilling of encoded batches to disk while a sink's destination is slow or
+//! unavailable.
+//!
+//! Sinks that apply batches of updates to an external system one request at a
+//! time (e.g., the Postgres and Elasticsearch sinks) would otherwise have to
+//! stop consuming their input until the destination recovers, which
+//! backpressures the entire dataflow. Instead, a [`SpillingWriter`] appends
+//! batches that cannot be applied to a local file, up to a configurable
+//! budget, and replays them in order once the destination accepts writes
+//! again.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use timely::progress::Antichain;
+use timely::scheduling::Activator;
+use timely::PartialOrder;
+use tracing::{info, warn};
+
+use mz_ore::cast::CastFrom;
+use mz_ore::metrics::{DeleteOnDropGauge, GaugeVecExt};
+use mz_repr::Timestamp;
+
+use crate::sink::metrics::SpillBaseMetrics;
+
+// 30s is a good maximum backoff for network operations. Long enough to reduce
+// load on an upstream system, but short enough that we can respond quickly when
+// the upstream system comes back online.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(125);
+
+/// A destination that applies encoded batches of updates.
+#[async_trait(?Send)]
+pub(super) trait BatchWriter {
+    /// Attempts to apply the requests that make up a single batch, in order.
+    ///
+    /// Applying a batch must be idempotent, as a batch may be applied again
+    /// after a partial failure.
+    async fn write(&mut self, requests: &[String]) -> Result<(), anyhow::Error>;
+}
+
+/// Per-sink spill metrics.
+struct SpillMetrics {
+    batches_spilled: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    bytes_spilled: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+}
+
+impl SpillMetrics {
+    fn new(base: &SpillBaseMetrics, sink_id: &str, worker_id: &str) -> SpillMetrics {
+        let labels = vec![sink_id.to_string(), worker_id.to_string()];
+        SpillMetrics {
+            batches_spilled: base
+                .batches_spilled
+                .get_delete_on_drop_gauge(labels.clone()),
+            bytes_spilled: base.bytes_spilled.get_delete_on_drop_gauge(labels),
+        }
+    }
+}
+
+/// A batch whose encoding has been spilled to disk.
+struct SpilledBatch {
+    /// The offset of the encoded batch in the spill file.
+    offset: u64,
+    /// The length of the encoded batch.
+    len: u64,
+    /// The frontier that the sink's write frontier may advance to once the
+    /// batch has been applied.
+    upper: Antichain<Timestamp>,
+}
+
+/// A FIFO queue of encoded batches, stored in an anonymous temporary file.
+///
+/// Each batch is encoded as a sequence of requests, each of which is prefixed
+/// with its length as a little-endian `u64`. The file is truncated whenever
+/// the queue drains.
+pub(super) struct SpillBuffer {
+    budget: u64,
+    file: Option<File>,
+    batches: VecDeque<SpilledBatch>,
+    /// The offset at which the next batch will be written.
+    end: u64,
+    metrics: SpillMetrics,
+}
+
+impl SpillBuffer {
+    /// Constructs a buffer that holds at most `budget` bytes of encoded
+    /// batches.
+    pub(super) fn new(
+        budget: u64,
+        base_metrics: &SpillBaseMetrics,
+        sink_id: &str,
+        worker_id: &str,
+    ) -> SpillBuffer {
+        SpillBuffer {
+            budget,
+            file: None,
+            batches: VecDeque::new(),
+            end: 0,
+            metrics: SpillMetrics::new(base_metrics, sink_id, worker_id),
+        }
+    }
+
+    /// Reports whether there are no spilled batches.
+    pub(super) fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// Returns the number of bytes of spilled batches.
+    pub(super) fn bytes(&self) -> u64 {
+        match self.batches.front() {
+            Some(batch) => self.end - batch.offset,
+            None => 0,
+        }
+    }
+
+    /// Appends a batch to the buffer.
+    ///
+    /// Returns `false`, without spilling the batch, if doing so would exceed
+    /// the budget.
+    pub(super) fn push(
+        &mut self,
+        requests: &[String],
+        upper: Antichain<Timestamp>,
+    ) -> Result<bool, io::Error> {
+        let len: u64 = requests
+            .iter()
+            .map(|request| 8 + u64::cast_from(request.len()))
+            .sum();
+        if self.bytes() + len > self.budget {
+            return Ok(false);
+        }
+
+        if self.file.is_none() {
+            self.file = Some(tempfile::tempfile()?);
+        }
+        let file = self.file.as_mut().expect("known to exist");
+        file.seek(SeekFrom::Start(self.end))?;
+        let mut buf = Vec::with_capacity(usize::cast_from(len));
+        for request in requests {
+            buf.extend(u64::cast_from(request.len()).to_le_bytes());
+            buf.extend(request.as_bytes());
+        }
+        file.write_all(&buf)?;
+
+        self.batches.push_back(SpilledBatch {
+            offset: self.end,
+            len,
+            upper,
+        });
+        self.end += len;
+        self.update_metrics();
+        Ok(true)
+    }
+
+    /// Reads the oldest spilled batch, if any.
+    pub(super) fn front(&mut self) -> Result<Option<Vec<String>>, io::Error> {
+        let batch = match self.batches.front() {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+        let file = self.file.as_mut().expect("spilled batches exist");
+        file.seek(SeekFrom::Start(batch.offset))?;
+        let mut buf = vec![0; usize::cast_from(batch.len)];
+        file.read_exact(&mut buf)?;
+
+        let mut requests = vec![];
+        let mut buf = &buf[..];
+        while !buf.is_empty() {
+            let (len, rest) = buf.split_at(8);
+            let len = u64::from_le_bytes(len.try_into().expect("known to be 8 bytes"));
+            let (request, rest) = rest.split_at(usize::cast_from(len));
+            let request = String::from_utf8(request.to_vec())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            requests.push(request);
+            buf = rest;
+        }
+        Ok(Some(requests))
+    }
+
+    /// Removes the oldest spilled batch, returning its upper.
+    pub(super) fn pop_front(&mut self) -> Option<Antichain<Timestamp>> {
+        let batch = self.batches.pop_front()?;
+        if self.batches.is_empty() {
+            self.end = 0;
+            if let Some(file) = &self.file {
+                if let Err(e) = file.set_len(0) {
+                    warn!("error truncating sink spill file: {}", e);
+                }
+            }
+        }
+        self.update_metrics();
+        Some(batch.upper)
+    }
+
+    fn update_metrics(&self) {
+        self.metrics
+            .batches_spilled
+            .set(u64::cast_from(self.batches.len()));
+        self.metrics.bytes_spilled.set(self.bytes());
+    }
+}
+
+/// Applies batches to a [`BatchWriter`], spilling them to disk while the
+/// destination is unavailable.
+///
+/// Batches are always applied in the order in which they are written, and the
+/// sink's write frontier only advances past a batch once it has been applied.
+pub(super) struct SpillingWriter<W> {
+    name: String,
+    writer: W,
+    /// The spill buffer, if spilling is enabled.
+    buffer: Option<SpillBuffer>,
+    /// Activates the sink operator to retry spilled batches.
+    activator: Activator,
+    backoff: Duration,
+}
+
+impl<W: BatchWriter> SpillingWriter<W> {
+    pub(super) fn new(
+        name: String,
+        writer: W,
+        buffer: Option<SpillBuffer>,
+        activator: Activator,
+    ) -> Self {
+        SpillingWriter {
+            name,
+            writer,
+            buffer,
+            activator,
+            backoff: INITIAL_BACKOFF,
+        }
+    }
+
+    fn has_spilled(&self) -> bool {
+        matches!(&self.buffer, Some(buffer) if !buffer.is_empty())
+    }
+
+    /// Applies `requests`, which encode all updates not beyond `upper` that
+    /// have not been passed to a previous call, and advances `write_frontier`
+    /// as far as all updates have been applied.
+    ///
+    /// Any previously spilled batches are replayed first. If the destination
+    /// does not accept `requests`, they are spilled to disk if the budget
+    /// allows, in which case the operator is scheduled to retry them later.
+    /// Otherwise this method blocks until all batches have been applied.
+    pub(super) async fn write(
+        &mut self,
+        requests: Vec<String>,
+        upper: Antichain<Timestamp>,
+        write_frontier: &RefCell<Antichain<Timestamp>>,
+    ) {
+        self.replay(write_frontier).await;
+
+        if !requests.is_empty() {
+            if !self.has_spilled() {
+                match self.writer.write(&requests).await {
+                    Ok(()) => {
+                        advance(write_frontier, &upper);
+                        return;
+                    }
+                    Err(e) => warn!("{}: error applying upserts: {:#}", self.name, e),
+                }
+            }
+            let spilled = match &mut self.buffer {
+                Some(buffer) => match buffer.push(&requests, upper.clone()) {
+                    Ok(spilled) => spilled,
+                    Err(e) => {
+                        warn!("{}: error spilling batch to disk: {}", self.name, e);
+                        false
+                    }
+                },
+                None => false,
+            };
+            if spilled {
+                let bytes = self.buffer.as_ref().map(|b| b.bytes()).unwrap_or(0);
+                info!(
+                    "{}: spilled batch to disk ({} bytes spilled)",
+                    self.name, bytes
+                );
+            } else {
+                self.flush(requests, upper, write_frontier).await;
+                return;
+            }
+        } else if !self.has_spilled() {
+            advance(write_frontier, &upper);
+        }
+
+        if self.has_spilled() {
+            self.activator.activate_after(self.backoff);
+            self.backoff = std::cmp::min(self.backoff * 2, BACKOFF_CLAMP);
+        }
+    }
+
+    /// Attempts to replay spilled batches, oldest first, stopping at the first
+    /// failure.
+    async fn replay(&mut self, write_frontier: &RefCell<Antichain<Timestamp>>) {
+        let buffer = match &mut self.buffer {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        while let Some(requests) = buffer.front().expect("error reading sink spill file") {
+            match self.writer.write(&requests).await {
+                Ok(()) => {
+                    let upper = buffer.pop_front().expect("known to exist");
+                    advance(write_frontier, &upper);
+                    self.backoff = INITIAL_BACKOFF;
+                }
+                Err(e) => {
+                    warn!("{}: error replaying spilled batch: {:#}", self.name, e);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Applies all spilled batches and then `requests`, retrying until the
+    /// destination accepts them.
+    async fn flush(
+        &mut self,
+        requests: Vec<String>,
+        upper: Antichain<Timestamp>,
+        write_frontier: &RefCell<Antichain<Timestamp>>,
+    ) {
+        let mut backoff = INITIAL_BACKOFF;
+        if let Some(buffer) = &mut self.buffer {
+            while let Some(spilled) = buffer.front().expect("error reading sink spill file") {
+                loop {
+                    match self.writer.write(&spilled).await {
+                        Ok(()) => break,
+                        Err(e) => {
+                            warn!("{}: error replaying spilled batch: {:#}", self.name, e);
+                            tokio::time::sleep(backoff).await;
+                            backoff = std::cmp::min(backoff * 2, BACKOFF_CLAMP);
+                        }
+                    }
+                }
+                let spilled_upper = buffer.pop_front().expect("known to exist");
+                advance(write_frontier, &spilled_upper);
+            }
+        }
+        loop {
+            match self.writer.write(&requests).await {
+                Ok(()) => break,
+                Err(e) => {
+                    warn!("{}: error applying upserts: {:#}", self.name, e);
+                    tokio::time::sleep(backoff).await;
+                    backoff = std::cmp::min(backoff * 2, BACKOFF_CLAMP);
+                }
+            }
+        }
+        advance(write_frontier, &upper);
+        self.backoff = INITIAL_BACKOFF;
+    }
+}
+
+fn advance(write_frontier: &RefCell<Antichain<Timestamp>>, upper: &Antichain<Timestamp>) {
+    if PartialOrder::less_than(&*write_frontier.borrow(), upper) {
+        write_frontier.borrow_mut().clone_from(upper);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metrics::MetricsRegistry;
+
+    use super::*;
+
+    fn upper(t: u64) -> Antichain<Timestamp> {
+        Antichain::from_elem(Timestamp::new(t))
+    }
+
+    #[test]
+    fn spill_buffer_roundtrip() {
+        let metrics = SpillBaseMetrics::register_with(&MetricsRegistry::new());
+        let mut buffer = SpillBuffer::new(64, &metrics, "u1", "0");
+        assert!(buffer.is_empty());
+
+        let first = vec!["a".to_string(), "".to_string(), "bcd".to_string()];
+        let second = vec!["ef".to_string()];
+        assert!(buffer.push(&first, upper(1)).unwrap());
+        assert!(buffer.push(&second, upper(2)).unwrap());
+        assert_eq!(buffer.bytes(), 3 * 8 + 4 + 8 + 2);
+
+        // Exceeding the budget does not spill the batch.
+        assert!(!buffer.push(&["x".repeat(32)], upper(3)).unwrap());
+
+        assert_eq!(buffer.front().unwrap(), Some(first));
+        assert_eq!(buffer.pop_front(), Some(upper(1)));
+        assert_eq!(buffer.front().unwrap(), Some(second.clone()));
+
+        // Space is reclaimed as batches are replayed.
+        assert!(buffer.push(&second, upper(4)).unwrap());
+        assert_eq!(buffer.pop_front(), Some(upper(2)));
+        assert_eq!(buffer.pop_front(), Some(upper(4)));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.bytes(), 0);
+        assert_eq!(buffer.front().unwrap(), None);
+        assert_eq!(buffer.pop_front(), None);
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that sends updates as messages to an AWS SQS queue.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use aws_sdk_sqs::model::SendMessageBatchRequestEntry;
+use aws_sdk_sqs::Client as SqsClient;
+use differential_dataflow::Collection;
+use sha2::{Digest, Sha256};
+use timely::dataflow::Scope;
+
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
+
+use crate::controller::CollectionMetadata;
+use crate::render::sinks::SinkRender;
+use crate::sink::sink_connection::{render_batch_sink, BatchEncoder, SinkUpdate};
+use crate::sink::spill::BatchWriter;
+use crate::storage_state::StorageState;
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::DataflowError;
+use crate::types::sinks::{SinkEnvelope, SqsSinkConnection, StorageSinkDesc};
+
+/// The maximum number of messages that SQS accepts in a single batch.
+const MAX_BATCH_MESSAGES: usize = 10;
+
+/// The maximum total size of the bodies of the messages that SQS accepts in a
+/// single batch.
+const MAX_BATCH_BYTES: usize = 256 * 1024;
+
+impl<G> SinkRender<G> for SqsSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_indices.as_deref()
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A single worker sends all messages, so that the messages for each
+        // timestamp are sent after those of earlier timestamps.
+        let debezium = matches!(sink.envelope, Some(SinkEnvelope::Debezium));
+        let connection_context = storage_state.connection_context.clone();
+        let token = render_batch_sink(
+            "sqs",
+            storage_state,
+            sink,
+            sink_id,
+            sinked_collection,
+            self.spill_budget,
+            MessageEncoder::new(sink_id, self, debezium),
+            SqsWriter {
+                connection: self.clone(),
+                connection_context,
+                client: None,
+            },
+        );
+
+        Some(token)
+    }
+}
+
+/// Encodes updates as the messages that send them.
+///
+/// Each message is encoded as the JSON encoding of its message group ID,
+/// deduplication ID, and body, so that batches can be spilled to disk.
+struct MessageEncoder {
+    sink_id: GlobalId,
+    fifo: bool,
+    json_encoder: JsonEncoder,
+}
+
+impl MessageEncoder {
+    fn new(sink_id: GlobalId, connection: &SqsSinkConnection, debezium: bool) -> Self {
+        let value_desc = &connection.value_desc;
+        let key_desc = connection.key_indices.as_ref().map(|key_indices| {
+            RelationDesc::from_names_and_types(key_indices.iter().map(|i| {
+                (
+                    value_desc.get_name(*i).clone(),
+                    value_desc.typ().column_types[*i].clone(),
+                )
+            }))
+        });
+        MessageEncoder {
+            sink_id,
+            fifo: connection.fifo,
+            json_encoder: JsonEncoder::new(key_desc, value_desc.clone(), debezium),
+        }
+    }
+
+    /// Appends the `count` messages that send the update of `key` to `value`
+    /// at `time` to `messages`.
+    ///
+    /// The body of each message is a JSON object with the update's `key` and
+    /// `value`, either of which is null if absent. Messages for a FIFO queue
+    /// are grouped by the SHA-256 hash of the update's key, or into a single
+    /// group if the sink has no key, and are deduplicated by the sink, the
+    /// timestamp, and their position among the messages at that timestamp.
+    fn encode(
+        &self,
+        messages: &mut Vec<String>,
+        time: Timestamp,
+        key: Option<Row>,
+        value: Option<Row>,
+        count: usize,
+    ) {
+        let encode = |bytes| String::from_utf8(bytes).expect("JSON encoding is valid UTF-8");
+        let key = key.map(|key| encode(self.json_encoder.encode_key_unchecked(key)));
+        let value = value.map(|value| encode(self.json_encoder.encode_value_unchecked(value)));
+        let group_id = match &key {
+            _ if !self.fifo => None,
+            Some(key) => Some(hex::encode(Sha256::digest(key.as_bytes()))),
+            None => Some(self.sink_id.to_string()),
+        };
+        let body = format!(
+            r#"{{"key":{},"value":{}}}"#,
+            key.as_deref().unwrap_or("null"),
+            value.as_deref().unwrap_or("null"),
+        );
+        for _ in 0..count {
+            let dedup_id = self
+                .fifo
+                .then(|| format!("{}-{}-{}", self.sink_id, time, messages.len()));
+            messages.push(
+                serde_json::to_string(&(&group_id, dedup_id, &body))
+                    .expect("serializing strings cannot fail"),
+            );
+        }
+    }
+}
+
+impl BatchEncoder for MessageEncoder {
+    fn encode_batch(&self, updates: BTreeMap<Timestamp, Vec<SinkUpdate>>) -> Vec<String> {
+        let mut messages = vec![];
+        for (time, updates) in updates {
+            // Messages are identified by their position among the messages
+            // at their timestamp.
+            let mut time_messages = vec![];
+            for (key, value, count) in updates {
+                self.encode(&mut time_messages, time, key, value, count);
+            }
+            messages.extend(time_messages);
+        }
+        messages
+    }
+}
+
+/// Sends batches of messages to the queue.
+///
+/// A batch that fails is sent again in its entirety, so messages are
+/// delivered at least once. FIFO queues discard the duplicates sent within
+/// their deduplication interval.
+struct SqsWriter {
+    connection: SqsSinkConnection,
+    connection_context: ConnectionContext,
+    client: Option<SqsClient>,
+}
+
+#[async_trait(?Send)]
+impl BatchWriter for SqsWriter {
+    /// Sends each of `messages`, in as few requests as SQS allows, and waits
+    /// for the queue to accept all of them.
+    async fn write(&mut self, messages: &[String]) -> Result<(), anyhow::Error> {
+        if self.client.is_none() {
+            let config = self
+                .connection
+                .aws
+                .load(
+                    self.connection_context.aws_external_id_prefix.as_ref(),
+                    Some(&self.connection.connection_id),
+                    &*self.connection_context.secrets_reader,
+                )
+                .await;
+            self.client = Some(SqsClient::new(&config));
+        }
+        let client = self.client.as_ref().expect("known to exist");
+
+        let mut entries = Vec::with_capacity(messages.len());
+        for (i, message) in messages.iter().enumerate() {
+            let (group_id, dedup_id, body): (Option<String>, Option<String>, String) =
+                serde_json::from_str(message)?;
+            let entry = SendMessageBatchRequestEntry::builder()
+                .id(i.to_string())
+                .set_message_group_id(group_id)
+                .set_message_deduplication_id(dedup_id)
+                .message_body(body)
+                .build();
+            entries.push(entry);
+        }
+
+        let mut entries = entries.into_iter().peekable();
+        while entries.peek().is_some() {
+            let mut batch = vec![];
+            let mut batch_bytes = 0;
+            while let Some(entry) = entries.peek() {
+                let bytes = entry.message_body().map_or(0, str::len);
+                if batch.len() == MAX_BATCH_MESSAGES
+                    || (!batch.is_empty() && batch_bytes + bytes > MAX_BATCH_BYTES)
+                {
+                    break;
+                }
+                batch_bytes += bytes;
+                batch.push(entries.next().expect("known to exist"));
+            }
+            let output = client
+                .send_message_batch()
+                .queue_url(&self.connection.queue_url)
+                .set_entries(Some(batch))
+                .send()
+                .await
+                .context("error sending messages")?;
+            if let Some(failed) = output.failed().and_then(|failed| failed.first()) {
+                return Err(anyhow!(
+                    "error sending message: {}: {}",
+                    failed.code().unwrap_or("unknown"),
+                    failed.message().unwrap_or("no message")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1197 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Commits of the data files written by an S3 sink to Delta Lake and Apache
+//! Iceberg tables.
+
+use std::io::Cursor;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail};
+use arrow2::array::{Array, PrimitiveArray, StructArray, Utf8Array};
+use arrow2::datatypes::DataType;
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use aws_sdk_s3::types::{ByteStream, SdkError};
+use http::header::{HeaderValue, IF_NONE_MATCH};
+use itertools::Itertools;
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use mz_avro::types::Value;
+use mz_avro::Codec;
+use mz_ore::retry::Retry;
+use mz_repr::{ColumnName, ColumnType, GlobalId, Timestamp};
+
+use crate::sink::s3::{parquet_type, BACKOFF_CLAMP};
+use crate::types::sinks::S3TableFormat;
+
+/// The Avro schema of the entries of an Iceberg manifest, as of version 1 of
+/// the table format.
+const ICEBERG_MANIFEST_ENTRY_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "manifest_entry",
+    "fields": [
+        {"name": "status", "type": "int", "field-id": 0},
+        {"name": "snapshot_id", "type": "long", "field-id": 1},
+        {"name": "data_file", "field-id": 2, "type": {
+            "type": "record",
+            "name": "r2",
+            "fields": [
+                {"name": "file_path", "type": "string", "field-id": 100},
+                {"name": "file_format", "type": "string", "field-id": 101},
+                {"name": "partition", "field-id": 102, "type": {
+                    "type": "record",
+                    "name": "r102",
+                    "fields": []
+                }},
+                {"name": "record_count", "type": "long", "field-id": 103},
+                {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+                {"name": "block_size_in_bytes", "type": "long", "field-id": 105}
+            ]
+        }}
+    ]
+}"#;
+
+/// The Avro schema of the entries of an Iceberg manifest list, as of version 1
+/// of the table format.
+const ICEBERG_MANIFEST_FILE_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "manifest_file",
+    "fields": [
+        {"name": "manifest_path", "type": "string", "field-id": 500},
+        {"name": "manifest_length", "type": "long", "field-id": 501},
+        {"name": "partition_spec_id", "type": "int", "field-id": 502},
+        {"name": "added_snapshot_id", "type": "long", "field-id": 503}
+    ]
+}"#;
+
+/// The block size that Iceberg manifests report for Parquet data files.
+const ICEBERG_BLOCK_SIZE: i64 = 64 << 20;
+
+/// A table rooted at a prefix of an S3 bucket that an S3 sink commits the
+/// data files it writes to.
+///
+/// The sink must be the only writer of the table. Each commit records the
+/// upper of the timestamps that the table contains, which lets the sink skip
+/// the updates that it already committed when it restarts.
+pub(super) struct Table {
+    format: S3TableFormat,
+    sink_id: GlobalId,
+    bucket: String,
+    root: String,
+    columns: Vec<(ColumnName, ColumnType)>,
+    state: Option<TableState>,
+}
+
+/// The state of a table that the next commit builds upon.
+enum TableState {
+    Delta {
+        /// The version of the next entry of the transaction log.
+        next_version: u64,
+    },
+    Iceberg {
+        /// The version of the current metadata file, or 0 if the table does
+        /// not exist yet.
+        version: u64,
+        /// The contents of the current metadata file, if any.
+        metadata: Option<serde_json::Value>,
+        /// The manifests of the current snapshot.
+        manifests: Vec<IcebergManifest>,
+    },
+}
+
+/// An entry of an Iceberg manifest list.
+#[derive(Debug, Clone, PartialEq)]
+struct IcebergManifest {
+    path: String,
+    length: i64,
+    added_snapshot_id: i64,
+}
+
+impl Table {
+    pub(super) fn new(
+        format: S3TableFormat,
+        sink_id: GlobalId,
+        bucket: &str,
+        prefix: &str,
+        columns: &[(ColumnName, ColumnType)],
+    ) -> Self {
+        Table {
+            format,
+            sink_id,
+            bucket: bucket.to_string(),
+            root: prefix.trim_end_matches('/').to_string(),
+            columns: columns.to_vec(),
+            state: None,
+        }
+    }
+
+    /// Returns the path, relative to the root of the table, of the data file
+    /// that contains the timestamps from `lower` through `upper`.
+    pub(super) fn data_file_path(&self, lower: Timestamp, upper: Timestamp) -> String {
+        format!("data/{}-{}-{}.parquet", self.sink_id, lower, upper)
+    }
+
+    /// Returns the key of the object at `path` relative to the root of the
+    /// table.
+    pub(super) fn key(&self, path: &str) -> String {
+        if self.root.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.root, path)
+        }
+    }
+
+    /// Returns the URI of the object at `key`.
+    fn uri(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.bucket, key)
+    }
+
+    /// Reads the state of the table, retrying until it succeeds, and returns
+    /// the upper of the timestamps that the sink has committed to it, if any.
+    pub(super) async fn load(&mut self, client: &aws_sdk_s3::Client) -> Option<Timestamp> {
+        let (state, upper) = Retry::default()
+            .clamp_backoff(BACKOFF_CLAMP)
+            .retry_async(|_| async {
+                let result = match self.format {
+                    S3TableFormat::Delta => self.load_delta(client).await,
+                    S3TableFormat::Iceberg => self.load_iceberg(client).await,
+                };
+                if let Err(e) = &result {
+                    warn!("s3-{}: error loading table: {:#}", self.sink_id, e);
+                }
+                result
+            })
+            .await
+            .expect("Infinite retry cannot fail");
+        self.state = Some(state);
+        upper
+    }
+
+    /// Commits the data file at `path`, which contains `record_count` records
+    /// in `size` bytes, and records that the table contains all timestamps
+    /// before `upper`, retrying until it succeeds.
+    ///
+    /// Each attempt builds upon the state of the table as of the last
+    /// successful commit, so a failed attempt leaves behind at most
+    /// unreferenced files, or a log entry that the next attempt recognizes as
+    /// its own.
+    pub(super) async fn commit(
+        &mut self,
+        client: &aws_sdk_s3::Client,
+        path: &str,
+        size: u64,
+        record_count: u64,
+        upper: Timestamp,
+    ) {
+        let state = Retry::default()
+            .clamp_backoff(BACKOFF_CLAMP)
+            .retry_async(|_| async {
+                let result = match self.state.as_ref().expect("table must be loaded") {
+                    TableState::Delta { next_version } => self
+                        .commit_delta(client, *next_version, path, size, upper)
+                        .await
+                        .map(|version| TableState::Delta {
+                            next_version: version + 1,
+                        }),
+                    TableState::Iceberg { .. } => {
+                        self.commit_iceberg(client, path, size, record_count, upper)
+                            .await
+                    }
+                };
+                if let Err(e) = &result {
+                    warn!("s3-{}: error committing {}: {:#}", self.sink_id, path, e);
+                }
+                result
+            })
+            .await
+            .expect("Infinite retry cannot fail");
+        self.state = Some(state);
+    }
+
+    async fn get(
+        &self,
+        client: &aws_sdk_s3::Client,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        match client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(object) => Ok(Some(object.body.collect().await?.into_bytes().to_vec())),
+            Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => Ok(None),
+            Err(e) => Err(anyhow!("error reading s3://{}/{}: {}", self.bucket, key, e)),
+        }
+    }
+
+    async fn put(
+        &self,
+        client: &aws_sdk_s3::Client,
+        key: &str,
+        body: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| anyhow!("error writing s3://{}/{}: {}", self.bucket, key, e))?;
+        Ok(())
+    }
+
+    /// Like [`Table::put`], but only writes the object if no object exists at
+    /// `key`, and returns whether it did.
+    async fn put_if_absent(
+        &self,
+        client: &aws_sdk_s3::Client,
+        key: &str,
+        body: Vec<u8>,
+    ) -> Result<bool, anyhow::Error> {
+        let mut operation = client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .customize()
+            .await
+            .map_err(|e| anyhow!("error writing s3://{}/{}: {}", self.bucket, key, e))?;
+        // This version of the SDK predates conditional writes in S3.
+        operation
+            .request_mut()
+            .headers_mut()
+            .insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        match operation.send().await {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError { err, .. }) if err.code() == Some("PreconditionFailed") => {
+                Ok(false)
+            }
+            Err(e) => Err(anyhow!("error writing s3://{}/{}: {}", self.bucket, key, e)),
+        }
+    }
+
+    /// Reads the state of a Delta table from its transaction log.
+    ///
+    /// Only the entries from the last checkpoint on are listed and read, as
+    /// the checkpoint summarizes the entries before it. The sink does not
+    /// write checkpoints itself, but other Delta writers, like Spark, do so
+    /// periodically.
+    async fn load_delta(
+        &self,
+        client: &aws_sdk_s3::Client,
+    ) -> Result<(TableState, Option<Timestamp>), anyhow::Error> {
+        let checkpoint_key = self.key("_delta_log/_last_checkpoint");
+        let checkpoint = match self.get(client, &checkpoint_key).await? {
+            Some(last_checkpoint) => Some(
+                DeltaCheckpoint::parse(&last_checkpoint)
+                    .map_err(|e| anyhow!("invalid Delta checkpoint {}: {:#}", checkpoint_key, e))?,
+            ),
+            None => None,
+        };
+
+        let log_prefix = self.key("_delta_log/");
+        // The keys of the entries from the checkpoint on sort after the
+        // zero-padded version of the checkpoint.
+        let start_after = checkpoint
+            .as_ref()
+            .map(|checkpoint| format!("{}{:020}", log_prefix, checkpoint.version));
+        let mut versions = vec![];
+        let mut continuation_token = None;
+        loop {
+            let resp = client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&log_prefix)
+                .set_start_after(start_after.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| anyhow!("error listing s3://{}/{}: {}", self.bucket, log_prefix, e))?;
+            for object in resp.contents.unwrap_or_default() {
+                let version = object
+                    .key
+                    .as_deref()
+                    .and_then(|key| key.strip_prefix(&log_prefix))
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .and_then(|version| version.parse::<u64>().ok());
+                versions.extend(version);
+            }
+            match resp.next_continuation_token {
+                Some(token) => continuation_token = Some(token),
+                None => break,
+            }
+        }
+        versions.sort_unstable();
+
+        let next_version = match (versions.last(), &checkpoint) {
+            (Some(version), _) => version + 1,
+            (None, Some(checkpoint)) => checkpoint.version + 1,
+            (None, None) => 0,
+        };
+        // The upper is recorded by the transaction identifier of the most
+        // recent entry that the sink committed. As the sink is the only writer
+        // of the table, that is usually the last entry.
+        let app_id = self.sink_id.to_string();
+        for version in versions.into_iter().rev() {
+            let key = self.key(&format!("_delta_log/{:020}.json", version));
+            let entry = match self.get(client, &key).await? {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let upper = delta_txn_version(&entry, &app_id)
+                .map_err(|e| anyhow!("invalid Delta log entry {}: {:#}", key, e))?;
+            if let Some(upper) = upper {
+                let state = TableState::Delta { next_version };
+                return Ok((state, Some(Timestamp::from(upper))));
+            }
+        }
+        // Otherwise, the checkpoint records the latest transaction identifier
+        // of every writer as of its version.
+        if let Some(checkpoint) = checkpoint {
+            for path in checkpoint.paths() {
+                let key = self.key(&path);
+                let part = self
+                    .get(client, &key)
+                    .await?
+                    .ok_or_else(|| anyhow!("missing Delta checkpoint {}", key))?;
+                let upper = checkpoint_txn_version(part, &app_id)
+                    .map_err(|e| anyhow!("invalid Delta checkpoint {}: {:#}", key, e))?;
+                if let Some(upper) = upper {
+                    let state = TableState::Delta { next_version };
+                    return Ok((state, Some(Timestamp::from(upper))));
+                }
+            }
+        }
+        Ok((TableState::Delta { next_version }, None))
+    }
+
+    /// Writes the log entry that adds the data file at `path` at the first
+    /// version from `version` on that no other entry occupies, and returns
+    /// that version.
+    ///
+    /// The entry is written with a conditional put, so that it never replaces
+    /// the entry of another writer, or of an earlier attempt whose response
+    /// was lost. In the latter case, the existing entry records `upper`, and
+    /// the commit is already complete.
+    async fn commit_delta(
+        &self,
+        client: &aws_sdk_s3::Client,
+        mut version: u64,
+        path: &str,
+        size: u64,
+        upper: Timestamp,
+    ) -> Result<u64, anyhow::Error> {
+        let app_id = self.sink_id.to_string();
+        loop {
+            let entry = self.delta_log_entry(version, path, size, upper);
+            let key = self.key(&format!("_delta_log/{:020}.json", version));
+            if self.put_if_absent(client, &key, entry.into_bytes()).await? {
+                return Ok(version);
+            }
+            let existing = self
+                .get(client, &key)
+                .await?
+                .ok_or_else(|| anyhow!("Delta log entry {} vanished", key))?;
+            let existing_upper = delta_txn_version(&existing, &app_id)
+                .map_err(|e| anyhow!("invalid Delta log entry {}: {:#}", key, e))?;
+            if existing_upper == Some(u64::from(upper)) {
+                return Ok(version);
+            }
+            version += 1;
+        }
+    }
+
+    /// Returns the Delta log entry at `version` that adds the data file at
+    /// `path`, which contains `size` bytes, and records `upper` as the version
+    /// of the sink's transaction.
+    ///
+    /// The first entry of the log also creates the table.
+    fn delta_log_entry(&self, version: u64, path: &str, size: u64, upper: Timestamp) -> String {
+        let now = now_millis();
+        let mut actions = vec![];
+        if version == 0 {
+            let fields = self
+                .columns
+                .iter()
+                .map(|(name, typ)| {
+                    json!({
+                        "name": name.as_str(),
+                        "type": delta_type(&parquet_type(typ)),
+                        "nullable": typ.nullable,
+                        "metadata": {},
+                    })
+                })
+                .collect::<Vec<_>>();
+            let schema = json!({"type": "struct", "fields": fields});
+            actions.push(json!({
+                "protocol": {"minReaderVersion": 1, "minWriterVersion": 2},
+            }));
+            actions.push(json!({
+                "metaData": {
+                    "id": Uuid::new_v4().to_string(),
+                    "format": {"provider": "parquet", "options": {}},
+                    "schemaString": schema.to_string(),
+                    "partitionColumns": [],
+                    "configuration": {},
+                    "createdTime": now,
+                },
+            }));
+        }
+        actions.push(json!({
+            "add": {
+                "path": path,
+                "partitionValues": {},
+                "size": size,
+                "modificationTime": now,
+                "dataChange": true,
+            },
+        }));
+        actions.push(json!({
+            "txn": {
+                "appId": self.sink_id.to_string(),
+                "version": u64::from(upper),
+                "lastUpdated": now,
+            },
+        }));
+        actions.push(json!({
+            "commitInfo": {
+                "timestamp": now,
+                "operation": "WRITE",
+                "operationParameters": {"mode": "Append"},
+                "engineInfo": "Materialize",
+            },
+        }));
+        actions.iter().map(|action| action.to_string()).join("\n")
+    }
+
+    async fn load_iceberg(
+        &self,
+        client: &aws_sdk_s3::Client,
+    ) -> Result<(TableState, Option<Timestamp>), anyhow::Error> {
+        let hint_key = self.key("metadata/version-hint.text");
+        let version = match self.get(client, &hint_key).await? {
+            Some(hint) => String::from_utf8(hint)?.trim().parse::<u64>()?,
+            None => {
+                let state = TableState::Iceberg {
+                    version: 0,
+                    metadata: None,
+                    manifests: vec![],
+                };
+                return Ok((state, None));
+            }
+        };
+        let metadata_key = self.key(&format!("metadata/v{}.metadata.json", version));
+        let metadata = self
+            .get(client, &metadata_key)
+            .await?
+            .ok_or_else(|| anyhow!("missing Iceberg table metadata {}", metadata_key))?;
+        let metadata: serde_json::Value = serde_json::from_slice(&metadata)?;
+        if metadata["format-version"].as_u64() != Some(1) {
+            bail!(
+                "unsupported Iceberg format version {} in {}",
+                metadata["format-version"],
+                metadata_key
+            );
+        }
+
+        let snapshots = metadata["snapshots"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let sink_id = self.sink_id.to_string();
+        let upper = snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot["summary"]["materialize.sink-id"].as_str() == Some(&sink_id))
+            .and_then(|snapshot| snapshot["summary"]["materialize.sink-upper"].as_str())
+            .map(|upper| upper.parse::<u64>().map(Timestamp::from))
+            .transpose()?;
+
+        let current_snapshot = snapshots
+            .iter()
+            .find(|snapshot| snapshot["snapshot-id"] == metadata["current-snapshot-id"]);
+        let manifests = match current_snapshot {
+            Some(snapshot) => {
+                let uri = snapshot["manifest-list"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("missing manifest list in {}", metadata_key))?;
+                self.read_iceberg_manifest_list(client, uri).await?
+            }
+            None => vec![],
+        };
+
+        let state = TableState::Iceberg {
+            version,
+            metadata: Some(metadata),
+            manifests,
+        };
+        Ok((state, upper))
+    }
+
+    async fn read_iceberg_manifest_list(
+        &self,
+        client: &aws_sdk_s3::Client,
+        uri: &str,
+    ) -> Result<Vec<IcebergManifest>, anyhow::Error> {
+        let key = uri
+            .strip_prefix(&format!("s3://{}/", self.bucket))
+            .ok_or_else(|| anyhow!("manifest list {} is outside of the sink's bucket", uri))?;
+        let list = self
+            .get(client, key)
+            .await?
+            .ok_or_else(|| anyhow!("missing Iceberg manifest list {}", uri))?;
+        decode_iceberg_manifest_list(&list, uri)
+    }
+
+    async fn commit_iceberg(
+        &self,
+        client: &aws_sdk_s3::Client,
+        path: &str,
+        size: u64,
+        record_count: u64,
+        upper: Timestamp,
+    ) -> Result<TableState, anyhow::Error> {
+        let (version, metadata, manifests) = match &self.state {
+            Some(TableState::Iceberg {
+                version,
+                metadata,
+                manifests,
+            }) => (*version, metadata, manifests),
+            _ => unreachable!("known to be an Iceberg table"),
+        };
+        let now = now_millis();
+        let commit_id = Uuid::new_v4();
+        let snapshot_id = i64::from_le_bytes(
+            commit_id.as_bytes()[..8]
+                .try_into()
+                .expect("known to be 8 bytes"),
+        ) & i64::MAX;
+        let record_count = i64::try_from(record_count)?;
+        let size = i64::try_from(size)?;
+
+        // Write a manifest that adds the data file.
+        let manifest = self.iceberg_manifest(snapshot_id, path, size, record_count)?;
+        let manifest_key = self.key(&format!("metadata/{}-m0.avro", commit_id));
+        let manifest_length = i64::try_from(manifest.len())?;
+        self.put(client, &manifest_key, manifest).await?;
+
+        // Write a manifest list with the manifests of the current snapshot
+        // and the new manifest.
+        let mut manifests = manifests.to_vec();
+        manifests.push(IcebergManifest {
+            path: self.uri(&manifest_key),
+            length: manifest_length,
+            added_snapshot_id: snapshot_id,
+        });
+        let parent_snapshot_id = metadata
+            .as_ref()
+            .and_then(|metadata| metadata["current-snapshot-id"].as_i64())
+            .filter(|id| *id != -1);
+        let list = iceberg_manifest_list(&manifests, snapshot_id, parent_snapshot_id)?;
+        let list_key = self.key(&format!(
+            "metadata/snap-{}-1-{}.avro",
+            snapshot_id, commit_id
+        ));
+        self.put(client, &list_key, list).await?;
+
+        // Write the metadata file of the new snapshot, and only then point
+        // the version hint at it.
+        let mut metadata = match metadata {
+            Some(metadata) => {
+                let mut metadata = metadata.clone();
+                let previous_key = self.key(&format!("metadata/v{}.metadata.json", version));
+                let previous = json!({
+                    "timestamp-ms": metadata["last-updated-ms"].clone(),
+                    "metadata-file": self.uri(&previous_key),
+                });
+                push(&mut metadata, "metadata-log", previous);
+                metadata
+            }
+            None => self.new_iceberg_metadata(self.iceberg_schema()),
+        };
+        let mut snapshot = json!({
+            "snapshot-id": snapshot_id,
+            "timestamp-ms": now,
+            "summary": {
+                "operation": "append",
+                "added-data-files": "1",
+                "added-records": record_count.to_string(),
+                "materialize.sink-id": self.sink_id.to_string(),
+                "materialize.sink-upper": upper.to_string(),
+            },
+            "manifest-list": self.uri(&list_key),
+            "schema-id": 0,
+        });
+        if let Some(parent_snapshot_id) = parent_snapshot_id {
+            snapshot["parent-snapshot-id"] = json!(parent_snapshot_id);
+        }
+        push(&mut metadata, "snapshots", snapshot);
+        push(
+            &mut metadata,
+            "snapshot-log",
+            json!({"timestamp-ms": now, "snapshot-id": snapshot_id}),
+        );
+        metadata["current-snapshot-id"] = json!(snapshot_id);
+        metadata["last-updated-ms"] = json!(now);
+
+        let version = version + 1;
+        let metadata_key = self.key(&format!("metadata/v{}.metadata.json", version));
+        self.put(client, &metadata_key, serde_json::to_vec(&metadata)?)
+            .await?;
+        let hint_key = self.key("metadata/version-hint.text");
+        self.put(client, &hint_key, version.to_string().into_bytes())
+            .await?;
+
+        Ok(TableState::Iceberg {
+            version,
+            metadata: Some(metadata),
+            manifests,
+        })
+    }
+
+    /// Returns an Iceberg manifest that adds the data file at `path`, which
+    /// contains `record_count` records in `size` bytes, in the snapshot
+    /// `snapshot_id`.
+    fn iceberg_manifest(
+        &self,
+        snapshot_id: i64,
+        path: &str,
+        size: i64,
+        record_count: i64,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let entry = Value::Record(vec![
+            ("status".into(), Value::Int(1)),
+            ("snapshot_id".into(), Value::Long(snapshot_id)),
+            (
+                "data_file".into(),
+                Value::Record(vec![
+                    ("file_path".into(), Value::String(self.uri(&self.key(path)))),
+                    ("file_format".into(), Value::String("PARQUET".into())),
+                    ("partition".into(), Value::Record(vec![])),
+                    ("record_count".into(), Value::Long(record_count)),
+                    ("file_size_in_bytes".into(), Value::Long(size)),
+                    (
+                        "block_size_in_bytes".into(),
+                        Value::Long(ICEBERG_BLOCK_SIZE),
+                    ),
+                ]),
+            ),
+        ]);
+        encode_avro_file(
+            ICEBERG_MANIFEST_ENTRY_SCHEMA,
+            &[
+                ("schema", self.iceberg_schema().to_string()),
+                ("partition-spec", "[]".into()),
+                ("partition-spec-id", "0".into()),
+                ("format-version", "1".into()),
+            ],
+            &[entry],
+        )
+    }
+
+    /// Returns the Iceberg schema of the table.
+    fn iceberg_schema(&self) -> serde_json::Value {
+        let fields = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (name, typ))| {
+                json!({
+                    "id": i + 1,
+                    "name": name.as_str(),
+                    "required": !typ.nullable,
+                    "type": iceberg_type(&parquet_type(typ)),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({"type": "struct", "schema-id": 0, "fields": fields})
+    }
+
+    /// Returns the metadata of a new Iceberg table with `schema` and no
+    /// snapshots.
+    ///
+    /// The data files do not carry Iceberg field IDs, so the table maps
+    /// columns to fields by name.
+    fn new_iceberg_metadata(&self, schema: serde_json::Value) -> serde_json::Value {
+        let name_mapping = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _typ))| json!({"field-id": i + 1, "names": [name.as_str()]}))
+            .collect::<Vec<_>>();
+        json!({
+            "format-version": 1,
+            "table-uuid": Uuid::new_v4().to_string(),
+            "location": self.uri(&self.root),
+            "last-updated-ms": now_millis(),
+            "last-column-id": self.columns.len(),
+            "schema": schema.clone(),
+            "schemas": [schema],
+            "current-schema-id": 0,
+            "partition-spec": [],
+            "partition-specs": [{"spec-id": 0, "fields": []}],
+            "default-spec-id": 0,
+            "last-partition-id": 999,
+            "sort-orders": [{"order-id": 0, "fields": []}],
+            "default-sort-order-id": 0,
+            "properties": {
+                "schema.name-mapping.default": json!(name_mapping).to_string(),
+            },
+            "current-snapshot-id": -1,
+            "snapshots": [],
+            "snapshot-log": [],
+            "metadata-log": [],
+        })
+    }
+}
+
+/// Appends `value` to the array at `field` of `object`, creating the array if
+/// necessary.
+fn push(object: &mut serde_json::Value, field: &str, value: serde_json::Value) {
+    match object.get_mut(field).and_then(|array| array.as_array_mut()) {
+        Some(array) => array.push(value),
+        None => object[field] = json!([value]),
+    }
+}
+
+fn now_millis() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before Unix epoch");
+    u64::try_from(now.as_millis()).expect("known to fit")
+}
+
+fn delta_type(typ: &DataType) -> &'static str {
+    match typ {
+        DataType::Boolean => "boolean",
+        DataType::Int16 => "short",
+        DataType::Int32 => "integer",
+        DataType::Int64 => "long",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        _ => "string",
+    }
+}
+
+fn iceberg_type(typ: &DataType) -> &'static str {
+    match typ {
+        DataType::Boolean => "boolean",
+        DataType::Int16 | DataType::Int32 => "int",
+        DataType::Int64 => "long",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        _ => "string",
+    }
+}
+
+/// Returns an Iceberg manifest list with `manifests`, for the snapshot
+/// `snapshot_id`.
+fn iceberg_manifest_list(
+    manifests: &[IcebergManifest],
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let entries = manifests
+        .iter()
+        .map(|manifest| {
+            Value::Record(vec![
+                ("manifest_path".into(), Value::String(manifest.path.clone())),
+                ("manifest_length".into(), Value::Long(manifest.length)),
+                ("partition_spec_id".into(), Value::Int(0)),
+                (
+                    "added_snapshot_id".into(),
+                    Value::Long(manifest.added_snapshot_id),
+                ),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let mut list_metadata = vec![
+        ("snapshot-id", snapshot_id.to_string()),
+        ("format-version", "1".to_string()),
+    ];
+    if let Some(parent_snapshot_id) = parent_snapshot_id {
+        list_metadata.push(("parent-snapshot-id", parent_snapshot_id.to_string()));
+    }
+    encode_avro_file(ICEBERG_MANIFEST_FILE_SCHEMA, &list_metadata, &entries)
+}
+
+/// Decodes the entries of the Iceberg manifest `list` at `uri`.
+fn decode_iceberg_manifest_list(
+    list: &[u8],
+    uri: &str,
+) -> Result<Vec<IcebergManifest>, anyhow::Error> {
+    let mut manifests = vec![];
+    for entry in mz_avro::Reader::new(list)? {
+        let fields = match entry? {
+            Value::Record(fields) => fields,
+            _ => bail!("invalid entry in Iceberg manifest list {}", uri),
+        };
+        let mut path = None;
+        let mut length = None;
+        let mut added_snapshot_id = None;
+        for (name, value) in fields {
+            let value = match value {
+                Value::Union { inner, .. } => *inner,
+                value => value,
+            };
+            match (name.as_str(), value) {
+                ("manifest_path", Value::String(value)) => path = Some(value),
+                ("manifest_length", Value::Long(value)) => length = Some(value),
+                ("added_snapshot_id", Value::Long(value)) => added_snapshot_id = Some(value),
+                _ => (),
+            }
+        }
+        match (path, length) {
+            (Some(path), Some(length)) => manifests.push(IcebergManifest {
+                path,
+                length,
+                added_snapshot_id: added_snapshot_id.unwrap_or(-1),
+            }),
+            _ => bail!("invalid entry in Iceberg manifest list {}", uri),
+        }
+    }
+    Ok(manifests)
+}
+
+/// The last checkpoint of a Delta table, as recorded by its
+/// `_delta_log/_last_checkpoint` file.
+#[derive(Debug, PartialEq)]
+struct DeltaCheckpoint {
+    version: u64,
+    /// The number of parts of a multi-part checkpoint.
+    parts: Option<u64>,
+}
+
+impl DeltaCheckpoint {
+    fn parse(last_checkpoint: &[u8]) -> Result<Self, anyhow::Error> {
+        let last_checkpoint: serde_json::Value = serde_json::from_slice(last_checkpoint)?;
+        let version = last_checkpoint["version"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("invalid checkpoint version"))?;
+        let parts = last_checkpoint["parts"].as_u64();
+        Ok(DeltaCheckpoint { version, parts })
+    }
+
+    /// Returns the paths of the parts of the checkpoint, relative to the root
+    /// of the table.
+    fn paths(&self) -> Vec<String> {
+        match self.parts {
+            None => vec![format!(
+                "_delta_log/{:020}.checkpoint.parquet",
+                self.version
+            )],
+            Some(parts) => (1..=parts)
+                .map(|part| {
+                    format!(
+                        "_delta_log/{:020}.checkpoint.{:010}.{:010}.parquet",
+                        self.version, part, parts
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the version of the transaction of `app_id` that the Delta log
+/// `entry` records, if any.
+fn delta_txn_version(entry: &[u8], app_id: &str) -> Result<Option<u64>, anyhow::Error> {
+    for line in std::str::from_utf8(entry)?.lines() {
+        let action: serde_json::Value = serde_json::from_str(line)?;
+        let txn = &action["txn"];
+        if txn["appId"].as_str() == Some(app_id) {
+            let version = txn["version"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("invalid transaction version"))?;
+            return Ok(Some(version));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the version of the transaction of `app_id` that the Parquet
+/// `checkpoint` of a Delta table records, if any.
+fn checkpoint_txn_version(checkpoint: Vec<u8>, app_id: &str) -> Result<Option<u64>, anyhow::Error> {
+    let mut reader = Cursor::new(checkpoint);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?.filter(|_, field| field.name == "txn");
+    if schema.fields.is_empty() {
+        return Ok(None);
+    }
+    for chunk in FileReader::new(reader, metadata.row_groups, schema, None, None, None) {
+        let chunk = chunk?;
+        let txn = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| anyhow!("invalid txn column"))?;
+        let app_ids = struct_field(txn, "appId")?
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .ok_or_else(|| anyhow!("invalid txn.appId column"))?;
+        let versions = struct_field(txn, "version")?
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .ok_or_else(|| anyhow!("invalid txn.version column"))?;
+        for i in 0..txn.len() {
+            if txn.is_valid(i)
+                && app_ids.is_valid(i)
+                && app_ids.value(i) == app_id
+                && versions.is_valid(i)
+            {
+                return Ok(Some(u64::try_from(versions.value(i))?));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn struct_field<'a>(array: &'a StructArray, name: &str) -> Result<&'a dyn Array, anyhow::Error> {
+    array
+        .fields()
+        .iter()
+        .position(|field| field.name == name)
+        .map(|i| array.values()[i].as_ref())
+        .ok_or_else(|| anyhow!("missing field {}", name))
+}
+
+/// Encodes `values` as an Avro object container file with the user
+/// `metadata`.
+///
+/// The header embeds `schema` verbatim, which preserves attributes like the
+/// field IDs that Iceberg relies on.
+fn encode_avro_file(
+    schema: &str,
+    metadata: &[(&str, String)],
+    values: &[Value],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut writer = mz_avro::Writer::with_json_schema(schema, Vec::new(), Codec::Null)?;
+    for (key, value) in metadata {
+        writer.add_user_metadata(key, value)?;
+    }
+    writer.extend_from_slice(values)?;
+    Ok(writer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow2::chunk::Chunk;
+    use arrow2::datatypes::{Field, Schema};
+    use arrow2::io::parquet::write::{
+        CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+    };
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    fn table(format: S3TableFormat) -> Table {
+        Table::new(
+            format,
+            GlobalId::User(1),
+            "bucket",
+            "tables/orders/",
+            &[
+                ("id".into(), ScalarType::Int64.nullable(false)),
+                ("item".into(), ScalarType::String.nullable(true)),
+            ],
+        )
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn delta_log_entries() {
+        let table = table(S3TableFormat::Delta);
+        let actions = |entry: &str| -> Vec<serde_json::Value> {
+            entry
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        };
+        let kinds = |actions: &[serde_json::Value]| -> Vec<String> {
+            actions
+                .iter()
+                .map(|action| action.as_object().unwrap().keys().join(","))
+                .collect()
+        };
+
+        // The first entry creates the table.
+        let entry = table.delta_log_entry(0, "data/u1-0-5.parquet", 1024, Timestamp::from(5));
+        let first = actions(&entry);
+        assert_eq!(
+            kinds(&first),
+            ["protocol", "metaData", "add", "txn", "commitInfo"]
+        );
+        let schema: serde_json::Value =
+            serde_json::from_str(first[1]["metaData"]["schemaString"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            schema,
+            json!({"type": "struct", "fields": [
+                {"name": "id", "type": "long", "nullable": false, "metadata": {}},
+                {"name": "item", "type": "string", "nullable": true, "metadata": {}},
+            ]})
+        );
+        assert_eq!(first[2]["add"]["path"], "data/u1-0-5.parquet");
+        assert_eq!(first[2]["add"]["size"], 1024);
+        assert_eq!(first[3]["txn"]["appId"], "u1");
+        assert_eq!(delta_txn_version(entry.as_bytes(), "u1").unwrap(), Some(5));
+        assert_eq!(delta_txn_version(entry.as_bytes(), "u2").unwrap(), None);
+
+        // Later entries only add data files.
+        let entry = table.delta_log_entry(1, "data/u1-5-9.parquet", 512, Timestamp::from(9));
+        let later = actions(&entry);
+        assert_eq!(kinds(&later), ["add", "txn", "commitInfo"]);
+        assert_eq!(later[0]["add"]["path"], "data/u1-5-9.parquet");
+        assert_eq!(delta_txn_version(entry.as_bytes(), "u1").unwrap(), Some(9));
+    }
+
+    #[test]
+    fn delta_checkpoints() {
+        let checkpoint = DeltaCheckpoint::parse(br#"{"version":10,"size":13}"#).unwrap();
+        assert_eq!(
+            checkpoint.paths(),
+            ["_delta_log/00000000000000000010.checkpoint.parquet"]
+        );
+        let checkpoint = DeltaCheckpoint::parse(br#"{"version":10,"size":13,"parts":2}"#).unwrap();
+        assert_eq!(
+            checkpoint.paths(),
+            [
+                "_delta_log/00000000000000000010.checkpoint.0000000001.0000000002.parquet",
+                "_delta_log/00000000000000000010.checkpoint.0000000002.0000000002.parquet",
+            ]
+        );
+        assert!(DeltaCheckpoint::parse(br#"{"size":13}"#).is_err());
+
+        // A checkpoint has a row per action, of which only the transactions
+        // set the `txn` column.
+        let txn_type = DataType::Struct(vec![
+            Field::new("appId", DataType::Utf8, true),
+            Field::new("version", DataType::Int64, true),
+        ]);
+        let txn = StructArray::new(
+            txn_type.clone(),
+            vec![
+                Box::new(Utf8Array::<i32>::from(vec![None, Some("u2"), Some("u1")]))
+                    as Box<dyn Array>,
+                Box::new(PrimitiveArray::<i64>::from(vec![None, Some(3), Some(7)])),
+            ],
+            None,
+        );
+        let schema = Schema::from(vec![Field::new("txn", txn_type, true)]);
+        let options = WriteOptions {
+            write_statistics: false,
+            compression: CompressionOptions::Uncompressed,
+            version: Version::V2,
+        };
+        let chunk = Chunk::new(vec![Box::new(txn) as Box<dyn Array>]);
+        let row_groups = RowGroupIterator::try_new(
+            std::iter::once(Ok(chunk)),
+            &schema,
+            options,
+            vec![vec![Encoding::Plain, Encoding::Plain]],
+        )
+        .unwrap();
+        let mut checkpoint = Vec::new();
+        let mut writer = FileWriter::try_new(&mut checkpoint, schema, options).unwrap();
+        for group in row_groups {
+            writer.write(group.unwrap()).unwrap();
+        }
+        writer.end(None).unwrap();
+
+        assert_eq!(
+            checkpoint_txn_version(checkpoint.clone(), "u1").unwrap(),
+            Some(7)
+        );
+        assert_eq!(checkpoint_txn_version(checkpoint, "u3").unwrap(), None);
+    }
+
+    #[test]
+    fn iceberg_manifest() {
+        let table = table(S3TableFormat::Iceberg);
+        let manifest = table
+            .iceberg_manifest(42, "data/u1-0-5.parquet", 1024, 3)
+            .unwrap();
+
+        // The header embeds the schema verbatim, field IDs included.
+        assert!(contains(
+            &manifest,
+            ICEBERG_MANIFEST_ENTRY_SCHEMA.as_bytes()
+        ));
+        assert!(contains(
+            &manifest,
+            table.iceberg_schema().to_string().as_bytes()
+        ));
+
+        let entries = mz_avro::Reader::new(&manifest[..])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            [Value::Record(vec![
+                ("status".into(), Value::Int(1)),
+                ("snapshot_id".into(), Value::Long(42)),
+                (
+                    "data_file".into(),
+                    Value::Record(vec![
+                        (
+                            "file_path".into(),
+                            Value::String("s3://bucket/tables/orders/data/u1-0-5.parquet".into())
+                        ),
+                        ("file_format".into(), Value::String("PARQUET".into())),
+                        ("partition".into(), Value::Record(vec![])),
+                        ("record_count".into(), Value::Long(3)),
+                        ("file_size_in_bytes".into(), Value::Long(1024)),
+                        (
+                            "block_size_in_bytes".into(),
+                            Value::Long(ICEBERG_BLOCK_SIZE)
+                        ),
+                    ])
+                ),
+            ])]
+        );
+    }
+
+    #[test]
+    fn iceberg_manifest_lists() {
+        let manifests = vec![
+            IcebergManifest {
+                path: "s3://bucket/tables/orders/metadata/a-m0.avro".into(),
+                length: 100,
+                added_snapshot_id: 1,
+            },
+            IcebergManifest {
+                path: "s3://bucket/tables/orders/metadata/b-m0.avro".into(),
+                length: 200,
+                added_snapshot_id: 2,
+            },
+        ];
+        let list = iceberg_manifest_list(&manifests, 2, Some(1)).unwrap();
+        assert!(contains(&list, ICEBERG_MANIFEST_FILE_SCHEMA.as_bytes()));
+        assert!(contains(&list, b"parent-snapshot-id"));
+        assert_eq!(
+            decode_iceberg_manifest_list(&list, "s3://bucket/snap.avro").unwrap(),
+            manifests
+        );
+
+        let list = iceberg_manifest_list(&manifests[..1], 1, None).unwrap();
+        assert!(!contains(&list, b"parent-snapshot-id"));
+        assert_eq!(
+            decode_iceberg_manifest_list(&list, "s3://bucket/snap.avro").unwrap(),
+            manifests[..1]
+        );
+    }
+}
@@ -112,6 +112,9 @@ where
             NextMessage::Ready(SourceMessageType::DropPartitionCapabilities(pids)) => Ok(
                 NextMessage::Ready(SourceMessageType::DropPartitionCapabilities(pids)),
             ),
+            NextMessage::Ready(SourceMessageType::Progress(pid, upper)) => {
+                Ok(NextMessage::Ready(SourceMessageType::Progress(pid, upper)))
+            }
             NextMessage::Ready(SourceMessageType::SourceStatus(update)) => {
                 Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
             }
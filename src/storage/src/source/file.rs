@@ -0,0 +1,622 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functionality for creating file sources.
+//!
+//! A single worker runs a task that polls a local directory and tails each
+//! file whose name matches the source's pattern, sending every complete line
+//! to dataflow as a message. Reading files is blocking, so each poll runs on a
+//! blocking thread.
+//!
+//! Files are identified by their device and inode numbers rather than by their
+//! names, which makes log rotation work as expected:
+//!
+//!   * A file that is renamed keeps being tailed from where it left off. If
+//!     its new name no longer matches the pattern, it is tailed until a poll
+//!     finds nothing new in it, so that the lines written to it just before it
+//!     was rotated away are not lost.
+//!   * A file that is created in place of a rotated file is a new file, and is
+//!     tailed from its beginning.
+//!   * A file that shrinks was truncated in place, like `copytruncate` does,
+//!     and is tailed again from its beginning.
+//!
+//! Compressed files, whose compression is given or detected from their
+//! contents and names, can't be tailed. They are read in their entirety once
+//! their size stopped changing between two consecutive polls.
+//!
+//! The name of each line's file and the offset of the line in it are encoded
+//! in the line's key, from which they are decoded into the source's metadata
+//! columns. Like SFTP sources, file sources do not remember how far they
+//! tailed each file across restarts, and tail every file from its beginning
+//! when they restart.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use futures::FutureExt;
+use globset::GlobMatcher;
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{debug, info, warn};
+
+use mz_expr::PartitionId;
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_repr::GlobalId;
+
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{SourceErrorClass, SourceRetry};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::{Compression, FileLineKey, FileSourceConnection, MzOffset};
+
+/// Size of the chunks files are read in
+const CHUNK_SIZE: usize = 8192;
+
+/// The number of bytes at the start of a file that suffice to detect its
+/// compression.
+const MAGIC_BYTES_LEN: usize = 4;
+
+enum InternalMessage {
+    /// A line of a file, keyed by its [`FileLineKey`].
+    Line {
+        key: Vec<u8>,
+        line: Vec<u8>,
+    },
+    Status(SourceStatusUpdate),
+}
+
+/// Information required to tail the files in a local directory
+pub struct FileSourceReader {
+    /// Global source ID
+    id: GlobalId,
+    /// Receiver channel that ingests lines
+    receiver_stream: Receiver<Result<InternalMessage, anyhow::Error>>,
+    /// Total number of lines that this source has read
+    offset: u64,
+    // File sources support single-threaded ingestion only, so only one of the
+    // `FileSourceReader`s will actually produce data.
+    active_read_worker: bool,
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+impl SourceReader for FileSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Diff = ();
+    type OffsetCommitter = LogCommitter;
+    type Connection = FileSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        _connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        // a single arbitrary worker is responsible for tailing the files
+        let receiver = if active_read_worker {
+            let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(10_000);
+            let tailer = DirectoryTailer {
+                source_id,
+                dir: PathBuf::from(&connection.dir),
+                pattern: connection.pattern.compile_matcher(),
+                compression: connection.compression,
+                files: HashMap::new(),
+                tx: dataflow_tx.clone(),
+                activator: consumer_activator.clone(),
+            };
+            debug!(
+                "source_id={} tailing files in directory={} worker={}",
+                source_id, connection.dir, worker_id
+            );
+            task::spawn(
+                || format!("file_tail:{}", source_id),
+                tail_directory_task(tailer, connection, dataflow_tx, consumer_activator),
+            );
+            dataflow_rx
+        } else {
+            let (_dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(1);
+            dataflow_rx
+        };
+
+        Ok((
+            FileSourceReader {
+                id: source_id,
+                receiver_stream: receiver,
+                offset: 0,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+
+    fn get_next_message(
+        &mut self,
+    ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Ok(NextMessage::Ready(
+                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
+                ));
+            }
+            return Ok(NextMessage::Finished);
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(Ok(InternalMessage::Line { key, line }))) => {
+                self.offset += 1;
+                Ok(NextMessage::Ready(SourceMessageType::Finalized(
+                    SourceMessage {
+                        output: 0,
+                        partition: PartitionId::None,
+                        offset: MzOffset {
+                            offset: self.offset,
+                        },
+                        upstream_time_millis: None,
+                        key: Some(key),
+                        value: Some(line),
+                        headers: None,
+                        specific_diff: (),
+                    },
+                )))
+            }
+            Some(Some(Ok(InternalMessage::Status(update)))) => {
+                Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
+            }
+            Some(Some(Err(e))) => Err(SourceReaderError {
+                inner: SourceErrorDetails::Other(format!("{:#}", e)),
+            }),
+            None => Ok(NextMessage::Pending),
+            Some(None) => Ok(NextMessage::Finished),
+        }
+    }
+}
+
+impl Drop for FileSourceReader {
+    fn drop(&mut self) {
+        debug!("source_id={} Dropping FileSourceReader", self.id);
+    }
+}
+
+/// The device and inode numbers of a file, which identify it regardless of
+/// its name.
+type FileId = (u64, u64);
+
+/// A file that is being tailed.
+struct TailedFile {
+    /// The name of the file when it was last seen in the directory.
+    name: String,
+    file: File,
+    /// How the file is compressed, once that is known.
+    compression: Option<Compression>,
+    /// The number of bytes of the file that have been read.
+    position: u64,
+    /// The number of complete lines that have been read.
+    lines: u64,
+    /// The beginning of a line whose end has not been read yet.
+    partial: Vec<u8>,
+    /// Whether the file was missing from the directory, or no longer matched
+    /// the pattern, during the latest poll.
+    rotated: bool,
+    /// The size of a compressed file as of the previous poll.
+    compressed_size: Option<u64>,
+    /// Whether a compressed file was read in its entirety.
+    done: bool,
+}
+
+impl TailedFile {
+    fn new(name: String, file: File) -> TailedFile {
+        TailedFile {
+            name,
+            file,
+            compression: None,
+            position: 0,
+            lines: 0,
+            partial: vec![],
+            rotated: false,
+            compressed_size: None,
+            done: false,
+        }
+    }
+
+    /// Reads the lines of the file that were not read yet, passing each
+    /// complete line to `emit` together with its offset in the file. `emit`
+    /// returns `false` if the source has been dropped.
+    ///
+    /// Returns whether the file changed since it was last read, or `None` if
+    /// the source has been dropped.
+    fn read(
+        &mut self,
+        compression: Compression,
+        emit: &mut impl FnMut(&str, u64, Vec<u8>) -> bool,
+    ) -> Result<Option<bool>, anyhow::Error> {
+        if self.compression.is_none() {
+            self.compression = self.detect_compression(compression)?;
+        }
+        match self.compression {
+            // Too little of the file was written to tell yet.
+            None => Ok(Some(false)),
+            Some(Compression::None) => self.tail(emit),
+            Some(compression) => self.read_compressed(compression, emit),
+        }
+    }
+
+    /// Determines how the file is compressed, or returns `None` if that can't
+    /// be told from what was written to it so far.
+    fn detect_compression(
+        &mut self,
+        compression: Compression,
+    ) -> Result<Option<Compression>, anyhow::Error> {
+        if compression != Compression::Auto {
+            return Ok(Some(compression));
+        }
+        let mut prefix = Vec::with_capacity(MAGIC_BYTES_LEN);
+        self.file.seek(SeekFrom::Start(0))?;
+        (&mut self.file)
+            .take(u64::cast_from(MAGIC_BYTES_LEN))
+            .read_to_end(&mut prefix)?;
+        let detected = Compression::detect(&self.name, &prefix);
+        if detected == Compression::None && prefix.len() < MAGIC_BYTES_LEN && !self.rotated {
+            return Ok(None);
+        }
+        debug!("file {} has compression {}", self.name, detected.name());
+        Ok(Some(detected))
+    }
+
+    /// Reads what was appended to an uncompressed file since it was last read.
+    fn tail(
+        &mut self,
+        emit: &mut impl FnMut(&str, u64, Vec<u8>) -> bool,
+    ) -> Result<Option<bool>, anyhow::Error> {
+        let len = self.file.metadata()?.len();
+        if len < self.position {
+            info!(
+                "file {} was truncated from {} to {} bytes, tailing it from its beginning",
+                self.name, self.position, len
+            );
+            self.position = 0;
+            self.lines = 0;
+            self.partial.clear();
+        }
+        self.file.seek(SeekFrom::Start(self.position))?;
+
+        let mut read_any = false;
+        let mut buf = vec![0; CHUNK_SIZE];
+        loop {
+            let n = self.file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            read_any = true;
+            self.position += u64::cast_from(n);
+            if !self.push_chunk(&buf[..n], 0, emit) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(read_any))
+    }
+
+    /// Reads a compressed file in its entirety, once its size stopped changing
+    /// between two consecutive polls. Compressed files can't be tailed, as
+    /// they are only complete once they are no longer written to.
+    fn read_compressed(
+        &mut self,
+        compression: Compression,
+        emit: &mut impl FnMut(&str, u64, Vec<u8>) -> bool,
+    ) -> Result<Option<bool>, anyhow::Error> {
+        if self.done {
+            return Ok(Some(false));
+        }
+        let size = self.file.metadata()?.len();
+        if self.compressed_size != Some(size) {
+            self.compressed_size = Some(size);
+            return Ok(Some(true));
+        }
+
+        // Decompress the file from its beginning, skipping the lines that a
+        // previous, failed attempt already emitted.
+        let resume_at = self.lines;
+        self.lines = 0;
+        self.partial.clear();
+        let mut file = self.file.try_clone()?;
+        file.seek(SeekFrom::Start(0))?;
+        let mut decoder: Box<dyn Read> = match compression {
+            Compression::Bzip2 => Box::new(MultiBzDecoder::new(file)),
+            Compression::Gzip => Box::new(MultiGzDecoder::new(file)),
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(file)?),
+            Compression::Auto | Compression::None => {
+                unreachable!("compressed files have a known compression")
+            }
+        };
+        let mut buf = vec![0; CHUNK_SIZE];
+        loop {
+            let n = decoder.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            if !self.push_chunk(&buf[..n], resume_at, emit) {
+                return Ok(None);
+            }
+        }
+        // The file is complete, so its last line is too, even without a
+        // trailing newline.
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            if self.lines >= resume_at && !emit(&self.name, self.lines, line) {
+                return Ok(None);
+            }
+            self.lines += 1;
+        }
+        self.done = true;
+        Ok(Some(true))
+    }
+
+    /// Passes the complete lines in `chunk`, which continues the partial line
+    /// that was read before it, to `emit`, except for the lines before offset
+    /// `resume_at`.
+    ///
+    /// Returns `false` if the source has been dropped.
+    fn push_chunk(
+        &mut self,
+        mut chunk: &[u8],
+        resume_at: u64,
+        emit: &mut impl FnMut(&str, u64, Vec<u8>) -> bool,
+    ) -> bool {
+        while let Some(end) = chunk.iter().position(|b| *b == b'\n') {
+            self.partial.extend_from_slice(&chunk[..end]);
+            chunk = &chunk[end + 1..];
+            let mut line = std::mem::take(&mut self.partial);
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if self.lines >= resume_at && !emit(&self.name, self.lines, line) {
+                return false;
+            }
+            self.lines += 1;
+        }
+        self.partial.extend_from_slice(chunk);
+        true
+    }
+}
+
+/// Tails the files in a local directory whose names match a pattern.
+struct DirectoryTailer {
+    source_id: GlobalId,
+    dir: PathBuf,
+    pattern: GlobMatcher,
+    compression: Compression,
+    /// The files that are being tailed.
+    files: HashMap<FileId, TailedFile>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    activator: SyncActivator,
+}
+
+impl DirectoryTailer {
+    /// Picks up the files that appeared in the directory and sends the lines
+    /// that were appended to all tailed files to dataflow.
+    ///
+    /// Returns `false` if the source has been dropped.
+    fn poll(&mut self) -> Result<bool, anyhow::Error> {
+        let mut present = HashMap::new();
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("error listing directory {}", self.dir.display()))?;
+        for entry in entries {
+            let entry =
+                entry.with_context(|| format!("error listing directory {}", self.dir.display()))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !self.pattern.is_match(&name) {
+                continue;
+            }
+            // Symbolic links are followed, so that a link to the current log
+            // file is tailed like the file itself.
+            let metadata = match std::fs::metadata(entry.path()) {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(e).with_context(|| format!("error inspecting file {}", name));
+                }
+            };
+            if metadata.is_file() {
+                present.insert((metadata.dev(), metadata.ino()), (name, entry.path()));
+            }
+        }
+
+        for (id, file) in self.files.iter_mut() {
+            file.rotated = match present.remove(id) {
+                Some((name, _)) => {
+                    file.name = name;
+                    false
+                }
+                None => true,
+            };
+        }
+        for (name, path) in present.into_values() {
+            // The file may have been replaced since it was inspected, so the
+            // file that was opened is identified anew.
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e).with_context(|| format!("error opening file {}", name)),
+            };
+            let metadata = file.metadata()?;
+            debug!("source_id={} tailing file {}", self.source_id, name);
+            self.files
+                .entry((metadata.dev(), metadata.ino()))
+                .or_insert_with(|| TailedFile::new(name, file));
+        }
+
+        let mut ids: Vec<_> = self
+            .files
+            .iter()
+            .map(|(id, file)| (file.name.clone(), *id))
+            .collect();
+        ids.sort();
+        let mut emit = |filename: &str, offset, line| {
+            let key = FileLineKey { filename, offset }.encode();
+            send(
+                &self.tx,
+                &self.activator,
+                InternalMessage::Line { key, line },
+            )
+        };
+        for (name, id) in ids {
+            let file = self.files.get_mut(&id).expect("known to exist");
+            let changed = match file
+                .read(self.compression, &mut emit)
+                .with_context(|| format!("error reading file {}", name))?
+            {
+                Some(changed) => changed,
+                None => return Ok(false),
+            };
+            // A rotated file that did not change is no longer written to, so
+            // its last line is complete even without a trailing newline.
+            if file.rotated && !changed {
+                let file = self.files.remove(&id).expect("known to exist");
+                debug!(
+                    "source_id={} stopped tailing rotated file {}",
+                    self.source_id, file.name
+                );
+                if !file.partial.is_empty() && !emit(&file.name, file.lines, file.partial) {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Sends `message` to dataflow.
+///
+/// Returns `false` if the source has been dropped.
+fn send(
+    tx: &Sender<Result<InternalMessage, anyhow::Error>>,
+    activator: &SyncActivator,
+    message: InternalMessage,
+) -> bool {
+    if tx.blocking_send(Ok(message)).is_err() {
+        return false;
+    }
+    activator.activate().expect("file reader activation failed");
+    true
+}
+
+/// Polls the directory every `poll_interval`, retrying failed polls with a
+/// backoff, until the source is dropped.
+async fn tail_directory_task(
+    mut tailer: DirectoryTailer,
+    connection: FileSourceConnection,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    activator: SyncActivator,
+) {
+    let source_id = tailer.source_id;
+    let mut retry = SourceRetry::default();
+    loop {
+        let (returned, result) = task::spawn_blocking(
+            || format!("file_tail:{}", source_id),
+            move || {
+                let result = tailer.poll();
+                (tailer, result)
+            },
+        )
+        .await
+        .expect("file tailer panicked");
+        tailer = returned;
+
+        let delay = match result {
+            Ok(false) => return,
+            Ok(true) => {
+                retry.reset();
+                connection.poll_interval
+            }
+            Err(e) => {
+                let class = error_class(&e);
+                let backoff = match retry.on_error(class) {
+                    Some(backoff) => backoff,
+                    None => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                warn!(
+                    "tailing source {} interrupted by {} error, retrying in {:?}: {:#}",
+                    source_id,
+                    class.name(),
+                    backoff,
+                    e
+                );
+                let next_retry_at = (SystemTime::now() + backoff)
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time after Unix epoch")
+                    .as_millis();
+                let update = SourceStatusUpdate::retrying(
+                    &format!("{:#}", e),
+                    class,
+                    u64::try_from(next_retry_at).expect("retry time fits into u64"),
+                );
+                if tx.send(Ok(InternalMessage::Status(update))).await.is_err() {
+                    return;
+                }
+                activator.activate().expect("file reader activation failed");
+                backoff
+            }
+        };
+
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = tokio::time::sleep(delay) => {}
+        }
+    }
+    info!("source_id={} stopped tailing files", source_id);
+}
+
+/// Classifies an error encountered while tailing the files.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    let permission_denied = err
+        .chain()
+        .any(|e| match e.downcast_ref::<std::io::Error>() {
+            Some(e) => e.kind() == ErrorKind::PermissionDenied,
+            None => false,
+        });
+    if permission_denied {
+        SourceErrorClass::Auth
+    } else {
+        SourceErrorClass::Transient
+    }
+}
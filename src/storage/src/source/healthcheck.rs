@@ -20,8 +20,10 @@ use tracing::trace;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::read::{Listen, ListenEvent, ReadHandle};
 use mz_persist_client::write::WriteHandle;
+use mz_repr::adt::jsonb::JsonbPacker;
 use mz_repr::{Datum, GlobalId, Row, Timestamp};
 
+use crate::source::retry::SourceErrorClass;
 use crate::source::{CollectionMetadata, NowFn};
 use crate::types::sources::SourceData;
 
@@ -106,7 +108,7 @@ impl Healthchecker {
             current_status = &self.current_status
         );
         // Only update status if it is a valid transition
-        if self.active && self.current_status.can_transition(&status_update.status) {
+        if self.active && self.current_status.accepts(&status_update) {
             loop {
                 let next_ts = (self.now)();
                 let new_upper = Antichain::from_elem(Timestamp::from(next_ts).step_forward());
@@ -132,7 +134,7 @@ impl Healthchecker {
                         // Sync to the new upper, go to the loop again
                         self.sync(&actual_upper.0).await;
                         // If we can't transition to the new status after the sync, no need to do anything else
-                        if !self.current_status.can_transition(&status_update.status) {
+                        if !self.current_status.accepts(&status_update) {
                             break;
                         }
                     }
@@ -245,8 +247,15 @@ impl Healthchecker {
         let source_id = Datum::String(&source_id);
         let status = Datum::String(status_update.status.name());
         let error = status_update.error.as_deref().into();
-        let metadata = Datum::Null;
-        let row = Row::pack_slice(&[timestamp, source_id, status, error, metadata]);
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.extend([timestamp, source_id, status, error]);
+        match &status_update.details {
+            Some(details) => JsonbPacker::new(&mut packer)
+                .pack_serde_json(details.clone())
+                .expect("status details are valid JSON"),
+            None => packer.push(Datum::Null),
+        }
 
         vec![(
             (SourceData(Ok(row)), ()),
@@ -305,6 +314,20 @@ impl SourceStatus {
             | SourceStatus::Stalled => self != new_status,
         }
     }
+
+    /// Reports whether `update` should be recorded for a source with this
+    /// status.
+    ///
+    /// Besides valid transitions, updates with details, like retries and
+    /// upstream schema changes, are recorded even though a Stalled or Running
+    /// source stays in its status, so that the status relation reflects the
+    /// latest attempt or change.
+    fn accepts(&self, update: &SourceStatusUpdate) -> bool {
+        let is_detail_update = self == &update.status
+            && matches!(self, SourceStatus::Stalled | SourceStatus::Running)
+            && update.details.is_some();
+        self.can_transition(&update.status) || is_detail_update
+    }
 }
 
 impl Display for SourceStatus {
@@ -333,7 +356,7 @@ impl TryFrom<&str> for SourceStatus {
 pub struct SourceStatusUpdate {
     status: SourceStatus,
     error: Option<String>,
-    // TODO(andrioni): figure out later how to accept a JSON as metadata
+    details: Option<serde_json::Value>,
 }
 
 impl SourceStatusUpdate {
@@ -341,6 +364,7 @@ impl SourceStatusUpdate {
         Self {
             status,
             error: None,
+            details: None,
         }
     }
 
@@ -348,6 +372,43 @@ impl SourceStatusUpdate {
         Self {
             status: SourceStatus::Failed,
             error: Some(error_message.to_string()),
+            details: None,
+        }
+    }
+
+    /// Reports that the source stalled on an error of class `class`, and will
+    /// retry at `next_retry_at` (in milliseconds since the Unix epoch).
+    pub fn retrying(error_message: &str, class: SourceErrorClass, next_retry_at: u64) -> Self {
+        Self {
+            status: SourceStatus::Stalled,
+            error: Some(error_message.to_string()),
+            details: Some(serde_json::json!({
+                "error_class": class.name(),
+                "next_retry_at": next_retry_at,
+            })),
+        }
+    }
+
+    /// Reports that the source failed on an error of class `class`, which is
+    /// not retried.
+    pub fn fatal(error_message: &str, class: SourceErrorClass) -> Self {
+        Self {
+            status: SourceStatus::Failed,
+            error: Some(error_message.to_string()),
+            details: Some(serde_json::json!({ "error_class": class.name() })),
+        }
+    }
+
+    /// Reports that the upstream table `table` gained `columns`, which the
+    /// source keeps running without.
+    pub fn upstream_columns_added(table: &str, columns: &[String]) -> Self {
+        Self {
+            status: SourceStatus::Running,
+            error: None,
+            details: Some(serde_json::json!({
+                "upstream_table": table,
+                "added_columns": columns,
+            })),
         }
     }
 }
@@ -524,6 +585,23 @@ mod tests {
             .await;
         assert_eq!(healthchecker.current_status, SourceStatus::Failed);
 
+        // Nor to Stalled, even with details
+        tokio::time::advance(Duration::from_millis(1)).await;
+        healthchecker
+            .update_status(SourceStatusUpdate::retrying(
+                "connection refused",
+                SourceErrorClass::Transient,
+                1000,
+            ))
+            .await;
+        assert_eq!(healthchecker.current_status, SourceStatus::Failed);
+        assert_eq!(
+            dump_storage_collection(shard_id, &persist_cache)
+                .await
+                .len(),
+            2
+        );
+
         // Check that the error message is persisted
         let error_message = dump_storage_collection(shard_id, &persist_cache)
             .await
@@ -540,6 +618,35 @@ mod tests {
         assert_eq!(error_message, "some error here")
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn test_repeated_retry() {
+        let shard_id = ShardId::new();
+        let persist_cache = persist_cache();
+        let mut healthchecker = simple_healthchecker(shard_id, 1, &persist_cache).await;
+
+        // Every retry is recorded, even though the status stays Stalled
+        for next_retry_at in [1000, 2000] {
+            tokio::time::advance(Duration::from_millis(1)).await;
+            healthchecker
+                .update_status(SourceStatusUpdate::retrying(
+                    "connection refused",
+                    SourceErrorClass::Transient,
+                    next_retry_at,
+                ))
+                .await;
+            assert_eq!(healthchecker.current_status, SourceStatus::Stalled);
+        }
+
+        let rows = dump_storage_collection(shard_id, &persist_cache).await;
+        assert_eq!(rows.len(), 2);
+        for row in rows {
+            let datums = row.unpack();
+            assert_eq!(datums[2].unwrap_str(), "stalled");
+            assert_eq!(datums[3].unwrap_str(), "connection refused");
+            assert!(!datums[4].is_null());
+        }
+    }
+
     #[test]
     fn test_can_transition() {
         let test_cases = [
@@ -658,6 +765,7 @@ mod tests {
             remap_shard: ShardId::new(),
             data_shard: ShardId::new(),
             status_shard: Some(status_shard_id),
+            relation_arity: None,
         };
 
         Healthchecker::new(
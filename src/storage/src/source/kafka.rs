@@ -12,6 +12,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use rdkafka::client::{BrokerAddr, OAuthToken};
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::error::KafkaError;
@@ -19,6 +20,7 @@ use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::statistics::Statistics;
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientConfig, ClientContext, Message, TopicPartitionList};
+use regex::Regex;
 use timely::scheduling::activate::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
 use tracing::{error, info, warn};
@@ -43,8 +45,12 @@ mod metrics;
 
 /// Contains all information necessary to ingest data from Kafka
 pub struct KafkaSourceReader {
-    /// Name of the topic on which this source is backed on
+    /// Name of the topic on which this source is backed on, or the pattern
+    /// that the names of its topics match
     topic_name: String,
+    /// If set, the source reads all topics whose names match this pattern,
+    /// and identifies partitions by topic as well as partition number
+    topic_pattern: Option<Regex>,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
     /// Source global ID
@@ -60,13 +66,13 @@ pub struct KafkaSourceReader {
     /// The most recently read offset for each partition known to this source
     /// reader. An offset of -1 indicates that no prior message has been read
     /// for the given partition.
-    last_offsets: HashMap<i32, i64>,
+    last_offsets: HashMap<PartitionId, i64>,
     /// The offset to start reading from for each partition.
-    start_offsets: HashMap<i32, i64>,
+    start_offsets: HashMap<PartitionId, i64>,
     /// Channel to receive Kafka statistics JSON blobs from the stats callback.
     stats_rx: crossbeam_channel::Receiver<Jsonb>,
     /// The last partition we received
-    partition_info: Arc<Mutex<Option<Vec<i32>>>>,
+    partition_info: Arc<Mutex<Option<Vec<PartitionId>>>>,
     /// A handle to the spawned metadata thread
     // Drop order is important here, we want the thread to be unparked after the `partition_info`
     // Arc has been dropped, so that the unpacked thread notices it and exits immediately
@@ -82,6 +88,8 @@ pub struct KafkaOffsetCommiter {
     topic_name: String,
     logger: LogCommitter,
     consumer: Arc<BaseConsumer<GlueConsumerContext>>,
+    /// The minimum time between commits, if any.
+    commit_interval: Option<Duration>,
 }
 
 impl SourceReader for KafkaSourceReader {
@@ -109,7 +117,9 @@ impl SourceReader for KafkaSourceReader {
             connection_id,
             options,
             topic,
+            topic_pattern,
             group_id_prefix,
+            commit_interval,
             environment_id,
             ..
         } = kc;
@@ -122,32 +132,38 @@ impl SourceReader for KafkaSourceReader {
             &options,
             &connection_context,
         ));
+        let client_context = TokioHandle::current().block_on(connection.client_context(
+            &*connection_context.secrets_reader,
+            connection_context.aws_external_id_prefix.as_ref(),
+        ))?;
         let (stats_tx, stats_rx) = crossbeam_channel::unbounded();
         let consumer: BaseConsumer<GlueConsumerContext> = kafka_config
             .create_with_context(GlueConsumerContext {
                 activator: consumer_activator,
                 stats_tx,
+                client_context,
             })
             .expect("Failed to create Kafka Consumer");
         let consumer = Arc::new(consumer);
+        let topic_pattern = if topic_pattern {
+            // Match the entire topic name, like Kafka Connect's `topics.regex`.
+            Some(Regex::new(&format!("^(?:{})$", topic)).expect("validated during planning"))
+        } else {
+            None
+        };
 
         // Start offsets is a map from partition to the next offset to read
         // from.
         let mut start_offsets: HashMap<_, i64> = kc
             .start_offsets
             .into_iter()
+            .map(|(pid, offset)| (PartitionId::Kafka(pid), offset))
             .filter(|(pid, _offset)| {
-                let pid = PartitionId::Kafka(*pid);
-                crate::source::responsible_for(&source_id, worker_id, worker_count, &pid)
+                crate::source::responsible_for(&source_id, worker_id, worker_count, pid)
             })
-            .map(|(k, v)| (k, v))
             .collect();
 
         for (pid, restored_offset) in restored_offsets {
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
             if let Some(restored_offset) = restored_offset {
                 let restored_offset = i64::try_from(restored_offset.offset)
                     .expect("restored kafka offsets must fit into i64");
@@ -165,6 +181,7 @@ impl SourceReader for KafkaSourceReader {
         let metadata_thread_handle = {
             let partition_info = Arc::downgrade(&partition_info);
             let topic = topic.clone();
+            let topic_pattern = topic_pattern.clone();
             let consumer = Arc::clone(&consumer);
             let metadata_refresh_frequency = kafka_config
                 .get("topic.metadata.refresh.interval.ms")
@@ -178,7 +195,18 @@ impl SourceReader for KafkaSourceReader {
                 .name("kafka-metadata".to_string())
                 .spawn(move || {
                     while let Some(partition_info) = partition_info.upgrade() {
-                        match get_kafka_partitions(&consumer, &topic, Duration::from_secs(30)) {
+                        let partitions = match &topic_pattern {
+                            Some(pattern) => get_kafka_topic_partitions(
+                                &consumer,
+                                pattern,
+                                Duration::from_secs(30),
+                            ),
+                            None => {
+                                get_kafka_partitions(&consumer, &topic, Duration::from_secs(30))
+                                    .map(|pids| pids.into_iter().map(PartitionId::Kafka).collect())
+                            }
+                        };
+                        match partitions {
                             Ok(info) => {
                                 *partition_info.lock().unwrap() = Some(info);
                                 thread::park_timeout(metadata_refresh_frequency);
@@ -190,10 +218,17 @@ impl SourceReader for KafkaSourceReader {
                 .unwrap()
                 .unpark_on_drop()
         };
-        let partition_ids = start_offsets.keys().copied().collect();
+        let partition_ids = start_offsets
+            .keys()
+            .map(|pid| {
+                let (topic, pid) = topic_partition(&topic, pid);
+                (topic.to_string(), pid)
+            })
+            .collect();
         Ok((
             KafkaSourceReader {
                 topic_name: topic.clone(),
+                topic_pattern,
                 source_name,
                 id: source_id,
                 partition_consumers: VecDeque::new(),
@@ -204,13 +239,14 @@ impl SourceReader for KafkaSourceReader {
                 start_offsets,
                 stats_rx,
                 partition_info,
-                include_headers: kc.include_headers.is_some(),
+                include_headers: kc.include_headers.is_some() || !kc.include_header.is_empty(),
                 _metadata_thread_handle: metadata_thread_handle,
                 partition_metrics: KafkaPartitionMetrics::new(
                     metrics,
                     partition_ids,
                     topic.clone(),
                     source_id,
+                    worker_id,
                 ),
             },
             KafkaOffsetCommiter {
@@ -222,6 +258,7 @@ impl SourceReader for KafkaSourceReader {
                     worker_count,
                 },
                 consumer,
+                commit_interval,
             },
         ))
     }
@@ -242,7 +279,6 @@ impl SourceReader for KafkaSourceReader {
             // new partitions.
             let mut unconsumed_partitions = Vec::new();
             for pid in partitions {
-                let pid = PartitionId::Kafka(pid);
                 if crate::source::responsible_for(&self.id, self.worker_id, self.worker_count, &pid)
                 {
                     self.ensure_partition(pid);
@@ -272,7 +308,11 @@ impl SourceReader for KafkaSourceReader {
                     self.source_name, self.topic_name, e
                 ),
                 Ok(message) => {
-                    let source_message = construct_source_message(&message, self.include_headers)?;
+                    let source_message = construct_source_message(
+                        &message,
+                        self.include_headers,
+                        self.topic_pattern.is_some(),
+                    )?;
                     next_message = self.handle_message(source_message);
                 }
             }
@@ -312,26 +352,23 @@ impl OffsetCommitter for KafkaOffsetCommiter {
         use rdkafka::topic_partition_list::Offset;
 
         let mut tpl = TopicPartitionList::new();
-        for (pid, offset) in offsets.clone() {
+        for (pid, offset) in offsets.iter() {
             // Note that we expect the above layers to pre-filter
             // by partition for us. This is part of the
             // `OffsetCommitter` contract.
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
+            let (topic, pid) = topic_partition(&self.topic_name, pid);
 
             // This matches the behavior of auto-commit, where we commit a
             // pseudo-_frontier_. Additionally, overflow will be caught
             // in the converstion to i64;
-            let offset_to_commit = offset + MzOffset::from(1);
+            let offset_to_commit = *offset + MzOffset::from(1);
             let offset_to_commit = Offset::Offset(
                 offset_to_commit
                     .offset
                     .try_into()
                     .expect("offset to be vald i64"),
             );
-            tpl.add_partition_offset(&self.topic_name, pid, offset_to_commit)
+            tpl.add_partition_offset(topic, pid, offset_to_commit)
                 .expect("offset known to be valid");
         }
 
@@ -343,6 +380,14 @@ impl OffsetCommitter for KafkaOffsetCommiter {
         .await??;
 
         self.logger.commit_offsets(offsets).await?;
+
+        // Offsets are only offered to us once the previous commit returns, and
+        // only the most recent ones are kept, so waiting here spaces commits
+        // out to at most one per interval without ever dropping the latest
+        // offsets.
+        if let Some(interval) = self.commit_interval {
+            tokio::time::sleep(interval).await;
+        }
         Ok(())
     }
 }
@@ -350,16 +395,12 @@ impl OffsetCommitter for KafkaOffsetCommiter {
 impl KafkaSourceReader {
     /// Ensures that a partition queue for `pid` exists.
     fn ensure_partition(&mut self, pid: PartitionId) {
-        let pid = match pid {
-            PartitionId::Kafka(p) => p,
-            _ => unreachable!(),
-        };
         if self.last_offsets.contains_key(&pid) {
             return;
         }
 
         let start_offset = self.start_offsets.get(&pid).copied().unwrap_or(0);
-        self.create_partition_queue(pid, Offset::Offset(start_offset));
+        self.create_partition_queue(pid.clone(), Offset::Offset(start_offset));
 
         let prev = self.last_offsets.insert(pid, start_offset - 1);
 
@@ -373,25 +414,26 @@ impl KafkaSourceReader {
         self.partition_consumers.len().try_into().unwrap()
     }
 
-    /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: i32, initial_offset: Offset) {
+    /// Creates a new partition queue for `pid`.
+    fn create_partition_queue(&mut self, pid: PartitionId, initial_offset: Offset) {
+        let (topic, partition_id) = topic_partition(&self.topic_name, &pid);
         info!(
             "Activating Kafka queue for {} [{}] (source {}) on worker {}",
-            self.topic_name, partition_id, self.id, self.worker_id
+            topic, partition_id, self.id, self.worker_id
         );
 
         // Collect old partition assignments
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, initial_offset)
+            .add_partition_offset(topic, partition_id, initial_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -401,9 +443,10 @@ impl KafkaSourceReader {
         // after every call to `self.consumer.assign`.
         let context = Arc::clone(self.consumer.context());
         for pc in &mut self.partition_consumers {
+            let (topic, partition_id) = topic_partition(&self.topic_name, &pc.pid);
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(topic, partition_id)
                 .expect("partition known to be valid");
             pc.partition_queue.set_nonempty_callback({
                 let context = Arc::clone(&context);
@@ -413,20 +456,16 @@ impl KafkaSourceReader {
 
         let mut partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(topic, partition_id)
             .expect("partition known to be valid");
         partition_queue.set_nonempty_callback(move || context.activate());
         self.partition_consumers.push_front(PartitionConsumer::new(
-            partition_id,
+            pid,
             partition_queue,
             self.include_headers,
         ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().count(),
             self.partition_consumers.len()
         );
     }
@@ -434,9 +473,10 @@ impl KafkaSourceReader {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: i32, next_offset: i64) {
+    fn fast_forward_consumer(&self, pid: &PartitionId, next_offset: i64) {
+        let (topic, pid) = topic_partition(&self.topic_name, pid);
         let res = self.consumer.seek(
-            &self.topic_name,
+            topic,
             pid,
             Offset::Offset(next_offset),
             Duration::from_secs(1),
@@ -444,12 +484,10 @@ impl KafkaSourceReader {
         match res {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
-                let position = res
-                    .get(&(self.topic_name.clone(), pid))
-                    .and_then(|p| match p {
-                        Offset::Offset(o) => Some(o),
-                        _ => None,
-                    });
+                let position = res.get(&(topic.to_string(), pid)).and_then(|p| match p {
+                    Offset::Offset(o) => Some(o),
+                    _ => None,
+                });
                 if let Some(position) = position {
                     if *position != next_offset {
                         warn!("Did not fast-forward consumer on partition PID: {} to the correct Kafka offset. Currently at offset: {} Expected offset: {}",
@@ -474,15 +512,33 @@ impl KafkaSourceReader {
         while let Ok(stats) = self.stats_rx.try_recv() {
             match serde_json::from_str::<Statistics>(&stats.to_string()) {
                 Ok(statistics) => {
-                    let topic = statistics.topics.get(&self.topic_name);
-                    match topic {
-                        Some(topic) => {
-                            for (id, partition) in &topic.partitions {
-                                self.partition_metrics
-                                    .set_offset_max(*id, partition.hi_offset);
+                    // Compressed message sets are counted at their compressed
+                    // size by the brokers, but at their decompressed size by
+                    // the partitions.
+                    let wire_bytes = statistics.brokers.values().map(|b| b.rxbytes).sum();
+                    self.partition_metrics.set_wire_bytes(wire_bytes);
+
+                    let topics: Vec<_> = match &self.topic_pattern {
+                        Some(pattern) => statistics
+                            .topics
+                            .iter()
+                            .filter(|(name, _)| pattern.is_match(name))
+                            .collect(),
+                        None => match statistics.topics.get_key_value(&self.topic_name) {
+                            Some(topic) => vec![topic],
+                            None => {
+                                error!("No stats found for topic: {}", &self.topic_name);
+                                vec![]
                             }
+                        },
+                    };
+                    for (name, topic) in topics {
+                        for (id, partition) in &topic.partitions {
+                            self.partition_metrics
+                                .set_offset_max(name, *id, partition.hi_offset);
+                            self.partition_metrics
+                                .set_decoded_bytes(name, *id, partition.rxbytes);
                         }
-                        None => error!("No stats found for topic: {}", &self.topic_name),
                     }
                 }
                 Err(e) => {
@@ -507,7 +563,7 @@ impl KafkaSourceReader {
                 let pid = partition_queue.pid();
                 let last_offset = self
                     .last_offsets
-                    .get(&pid)
+                    .get(pid)
                     .expect("partition known to be installed");
 
                 error!(
@@ -534,10 +590,7 @@ impl KafkaSourceReader {
         &mut self,
         message: SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()>,
     ) -> NextMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
-        let partition = match message.partition {
-            PartitionId::Kafka(pid) => pid,
-            _ => unreachable!(),
-        };
+        let partition = message.partition.clone();
 
         // Offsets are guaranteed to be 1) monotonically increasing *unless* there is
         // a network issue or a new partition added, at which point the consumer may
@@ -575,7 +628,7 @@ impl KafkaSourceReader {
                 last_offset + 1,
             );
             // Seek to the *next* offset that we have not yet processed
-            self.fast_forward_consumer(partition, last_offset + 1);
+            self.fast_forward_consumer(&partition, last_offset + 1);
             // We explicitly should not consume the message as we have already processed it
             // However, we make sure to activate the source to make sure that we get a chance
             // to read from this consumer again (even if no new data arrives)
@@ -663,6 +716,7 @@ async fn create_kafka_config(
 fn construct_source_message(
     msg: &BorrowedMessage<'_>,
     include_headers: bool,
+    include_topic: bool,
 ) -> Result<SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()>, anyhow::Error> {
     let headers = match msg.headers() {
         Some(headers) if include_headers => Some(
@@ -673,9 +727,14 @@ fn construct_source_message(
         ),
         _ => None,
     };
+    let partition = if include_topic {
+        PartitionId::KafkaTopic(msg.topic().to_string(), msg.partition())
+    } else {
+        PartitionId::Kafka(msg.partition())
+    };
     Ok(SourceMessage {
         output: 0,
-        partition: PartitionId::Kafka(msg.partition()),
+        partition,
         offset: u64::try_from(msg.offset())
             .map_err(|_| {
                 anyhow::anyhow!(
@@ -695,7 +754,7 @@ fn construct_source_message(
 /// Wrapper around a partition containing the underlying consumer
 struct PartitionConsumer {
     /// the partition id with which this consumer is associated
-    pid: i32,
+    pid: PartitionId,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<GlueConsumerContext>,
     /// Whether or not to unpack and allocate headers and pass them through in the `SourceMessage`
@@ -705,7 +764,7 @@ struct PartitionConsumer {
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
     fn new(
-        pid: i32,
+        pid: PartitionId,
         partition_queue: PartitionQueue<GlueConsumerContext>,
         include_headers: bool,
     ) -> Self {
@@ -730,8 +789,9 @@ impl PartitionConsumer {
     > {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let result = construct_source_message(&msg, self.include_headers)?;
-                assert_eq!(result.partition, PartitionId::Kafka(self.pid));
+                let include_topic = matches!(self.pid, PartitionId::KafkaTopic(..));
+                let result = construct_source_message(&msg, self.include_headers, include_topic)?;
+                assert_eq!(result.partition, self.pid);
                 Ok(Ok(Some(result)))
             }
             Some(Err(err)) => Ok(Err(err)),
@@ -740,8 +800,8 @@ impl PartitionConsumer {
     }
 
     /// Return the partition id for this PartitionConsumer
-    fn pid(&self) -> i32 {
-        self.pid
+    fn pid(&self) -> &PartitionId {
+        &self.pid
     }
 }
 
@@ -750,9 +810,12 @@ impl PartitionConsumer {
 struct GlueConsumerContext {
     activator: SyncActivator,
     stats_tx: crossbeam_channel::Sender<Jsonb>,
+    client_context: MzClientContext,
 }
 
 impl ClientContext for GlueConsumerContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
     fn stats_raw(&self, statistics: &[u8]) {
         match Jsonb::from_slice(statistics) {
             Ok(statistics) => {
@@ -768,10 +831,19 @@ impl ClientContext for GlueConsumerContext {
     // The shape of the rdkafka *Context traits require us to forward to the `MzClientContext`
     // implementation.
     fn log(&self, level: rdkafka::config::RDKafkaLogLevel, fac: &str, log_message: &str) {
-        MzClientContext.log(level, fac, log_message)
+        self.client_context.log(level, fac, log_message)
     }
     fn error(&self, error: rdkafka::error::KafkaError, reason: &str) {
-        MzClientContext.error(error, reason)
+        self.client_context.error(error, reason)
+    }
+    fn generate_oauth_token(
+        &self,
+        oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn std::error::Error>> {
+        self.client_context.generate_oauth_token(oauthbearer_config)
+    }
+    fn rewrite_broker_addr(&self, addr: BrokerAddr) -> BrokerAddr {
+        self.client_context.rewrite_broker_addr(addr)
     }
 }
 
@@ -785,6 +857,36 @@ impl GlueConsumerContext {
 
 impl ConsumerContext for GlueConsumerContext {}
 
+/// Returns the topic and partition number of `pid`, which is a partition of
+/// `topic` unless it names its own topic.
+fn topic_partition<'a>(topic: &'a str, pid: &'a PartitionId) -> (&'a str, i32) {
+    match pid {
+        PartitionId::Kafka(pid) => (topic, *pid),
+        PartitionId::KafkaTopic(topic, pid) => (topic, *pid),
+        _ => panic!("unexpected partition id type"),
+    }
+}
+
+/// Return the list of partitions of all topics whose names match `pattern`
+fn get_kafka_topic_partitions(
+    consumer: &BaseConsumer<GlueConsumerContext>,
+    pattern: &Regex,
+    timeout: Duration,
+) -> Result<Vec<PartitionId>, anyhow::Error> {
+    let metadata = consumer.fetch_metadata(None, timeout)?;
+    Ok(metadata
+        .topics()
+        .iter()
+        .filter(|topic| pattern.is_match(topic.name()))
+        .flat_map(|topic| {
+            topic
+                .partitions()
+                .iter()
+                .map(|p| PartitionId::KafkaTopic(topic.name().to_string(), p.id()))
+        })
+        .collect())
+}
+
 /// Return the list of partition ids associated with a specific topic
 fn get_kafka_partitions(
     consumer: &BaseConsumer<GlueConsumerContext>,
@@ -12,41 +12,51 @@ use std::collections::HashMap;
 use prometheus::core::AtomicI64;
 use tracing::debug;
 
-use mz_ore::iter::IteratorExt;
 use mz_ore::metrics::{DeleteOnDropGauge, GaugeVecExt};
 use mz_repr::GlobalId;
 
 use crate::source::metrics::SourceBaseMetrics;
 pub(super) struct KafkaPartitionMetrics {
-    labels: Vec<String>,
+    source_id: String,
     base_metrics: SourceBaseMetrics,
-    partition_offset_map: HashMap<i32, DeleteOnDropGauge<'static, AtomicI64, Vec<String>>>,
+    partition_offset_map:
+        HashMap<(String, i32), DeleteOnDropGauge<'static, AtomicI64, Vec<String>>>,
+    partition_decoded_bytes_map:
+        HashMap<(String, i32), DeleteOnDropGauge<'static, AtomicI64, Vec<String>>>,
+    wire_bytes: DeleteOnDropGauge<'static, AtomicI64, Vec<String>>,
 }
 
 impl KafkaPartitionMetrics {
     pub fn new(
         base_metrics: SourceBaseMetrics,
-        ids: Vec<i32>,
+        ids: Vec<(String, i32)>,
         topic: String,
         source_id: GlobalId,
+        worker_id: usize,
     ) -> Self {
         let metrics = &base_metrics.partition_specific;
         Self {
-            partition_offset_map: HashMap::from_iter(ids.iter().map(|id| {
+            partition_offset_map: HashMap::from_iter(ids.into_iter().map(|(topic, id)| {
                 let labels = &[topic.clone(), source_id.to_string(), format!("{}", id)];
                 (
-                    *id,
+                    (topic, id),
                     metrics
                         .partition_offset_max
                         .get_delete_on_drop_gauge(labels.to_vec()),
                 )
             })),
-            labels: vec![topic.clone(), source_id.to_string()],
+            partition_decoded_bytes_map: HashMap::new(),
+            wire_bytes: metrics.kafka_wire_bytes.get_delete_on_drop_gauge(vec![
+                topic,
+                source_id.to_string(),
+                worker_id.to_string(),
+            ]),
+            source_id: source_id.to_string(),
             base_metrics,
         }
     }
 
-    pub fn set_offset_max(&mut self, id: i32, offset: i64) {
+    pub fn set_offset_max(&mut self, topic: &str, id: i32, offset: i64) {
         // Valid partition ids start at 0, librdkafka uses -1 as a sentinel for unassigned partitions
         if id < 0 {
             return;
@@ -58,19 +68,44 @@ impl KafkaPartitionMetrics {
             return;
         }
         self.partition_offset_map
-            .entry(id)
-            .or_insert_with_key(|id| {
+            .entry((topic.to_string(), id))
+            .or_insert_with_key(|(topic, id)| {
                 self.base_metrics
                     .partition_specific
                     .partition_offset_max
-                    .get_delete_on_drop_gauge(
-                        self.labels
-                            .iter()
-                            .cloned()
-                            .chain_one(format!("{}", id))
-                            .collect(),
-                    )
+                    .get_delete_on_drop_gauge(vec![
+                        topic.clone(),
+                        self.source_id.clone(),
+                        format!("{}", id),
+                    ])
             })
             .set(offset);
     }
+
+    /// Records the number of bytes of messages consumed from partition `id` of
+    /// `topic`, after they were decompressed.
+    pub fn set_decoded_bytes(&mut self, topic: &str, id: i32, bytes: i64) {
+        if id < 0 {
+            return;
+        }
+        self.partition_decoded_bytes_map
+            .entry((topic.to_string(), id))
+            .or_insert_with_key(|(topic, id)| {
+                self.base_metrics
+                    .partition_specific
+                    .partition_decoded_bytes
+                    .get_delete_on_drop_gauge(vec![
+                        topic.clone(),
+                        self.source_id.clone(),
+                        format!("{}", id),
+                    ])
+            })
+            .set(bytes);
+    }
+
+    /// Records the number of bytes received from the brokers, before they
+    /// were decompressed.
+    pub fn set_wire_bytes(&mut self, bytes: i64) {
+        self.wire_bytes.set(bytes);
+    }
 }
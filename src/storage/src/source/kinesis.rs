@@ -7,31 +7,46 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::time::Duration;
-use std::time::Instant;
-
-use anyhow::anyhow;
-use aws_sdk_kinesis::error::GetRecordsError;
-use aws_sdk_kinesis::output::GetRecordsOutput;
-use aws_sdk_kinesis::types::SdkError;
+//! Reading Kinesis data streams.
+//!
+//! Shards are read either by polling them with `GetRecords`, or, if the source
+//! names an enhanced fan-out consumer, by subscribing to them with
+//! `SubscribeToShard`. When a stream is resharded, the shards that are split or
+//! merged are closed, and their children are only read once all of their
+//! parents have been read to their end, so that the records of each key are
+//! read in order.
+//!
+//! Each shard is a separate partition of the source. Kinesis sequence numbers
+//! do not fit into an [`MzOffset`], so the offset of each record is instead
+//! derived from its approximate arrival timestamp and its position among the
+//! records of the shard that arrived in the same millisecond. On restart, each
+//! shard is resumed at the arrival timestamp of the first record that was not
+//! yet ingested, rather than at the start of the shard.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+use aws_sdk_kinesis::model::{
+    ConsumerStatus, Record, ShardIteratorType, StartingPosition, SubscribeToShardEventStream,
+};
+use aws_sdk_kinesis::types::{DateTime, SdkError};
 use aws_sdk_kinesis::Client as KinesisClient;
 use prometheus::core::AtomicI64;
 use timely::scheduling::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
+use tokio::sync::mpsc::{self, Receiver, Sender};
 use tracing::error;
 
 use mz_expr::PartitionId;
 use mz_ore::metrics::{DeleteOnDropGauge, GaugeVecExt};
+use mz_ore::retry::Retry;
+use mz_ore::task;
 use mz_repr::GlobalId;
-use mz_secrets::SecretsReader;
 
 use crate::source::commit::LogCommitter;
 use crate::source::metrics::KinesisMetrics;
-use crate::source::{
-    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
-};
-use crate::types::connections::aws::AwsExternalIdPrefix;
+use crate::source::{SourceMessage, SourceMessageType, SourceReader, SourceReaderError};
 use crate::types::connections::ConnectionContext;
 use crate::types::errors::SourceErrorDetails;
 use crate::types::sources::encoding::SourceDataEncoding;
@@ -44,37 +59,120 @@ use crate::types::sources::{KinesisSourceConnection, MzOffset};
 /// (100x/sec per stream) and to improve source performance overall.
 const KINESIS_SHARD_REFRESH_RATE: Duration = Duration::from_secs(60);
 
+/// The number of offsets reserved for the records of a shard that arrived in
+/// the same millisecond.
+///
+/// Shards accept at most 1,000 records per second, so this is never exhausted
+/// in practice.
+const OFFSETS_PER_MILLISECOND: u64 = 1_000_000;
+
 /// Contains all information necessary to ingest data from Kinesis
 pub struct KinesisSourceReader {
-    tokio_handle: TokioHandle,
     /// Kinesis client used to obtain records
     kinesis_client: KinesisClient,
     /// The name of the stream
     stream_name: String,
-    /// The set of active shards
-    shard_set: HashMap<String, ShardMetrics>,
-    /// A queue representing the next shard to read from. This is necessary
-    /// to ensure that all shards are read from uniformly
-    shard_queue: VecDeque<(String, Option<String>)>,
-    /// The time at which we last refreshed metadata
+    /// The name of the enhanced fan-out consumer to read the stream with, if any
+    consumer_name: Option<String>,
+    /// The ARN of the enhanced fan-out consumer, once it has been registered
+    consumer_arn: Option<String>,
+    /// All known shards of the stream, including closed ones
+    shards: HashMap<String, ShardState>,
+    /// A queue representing the next shard to poll, with its shard iterator.
+    /// This is necessary to ensure that all shards are read from uniformly.
+    /// Unused when reading with an enhanced fan-out consumer.
+    shard_queue: VecDeque<(String, String)>,
+    /// Events from the enhanced fan-out subscriptions to the shards
+    events_tx: Sender<ShardEvent>,
+    events_rx: Receiver<ShardEvent>,
+    /// The offsets at which to resume reading each shard, as restored from the
+    /// source's bindings
+    restored_offsets: HashMap<String, MzOffset>,
+    /// The time at which we last refreshed metadata, or `None` if we never did
     /// TODO(natacha): this should be moved to timestamper
-    last_checked_shards: Instant,
+    last_checked_shards: Option<Instant>,
     /// Storage for messages that have not yet been timestamped
     buffered_messages: VecDeque<SourceMessage<(), Option<Vec<u8>>, ()>>,
-    /// Count of processed message
-    processed_message_count: u64,
     /// Metrics from which per-shard metrics get created.
     base_metrics: KinesisMetrics,
     // Kinesis sources support single-threaded ingestion only, so only one of
     // the `KinesisSourceReader`s will actually produce data.
     active_read_worker: bool,
     // The non-active reader (see above `active_read_worker`) has to report back
-    // that is is not consuming from the one [`PartitionId:None`] partition.
-    // Before it can return a [`NextMessage::Finished`]. This is keeping track
-    // of that.
+    // that is is not consuming from the one [`PartitionId:None`] partition
+    // before it can finish. This is keeping track of that.
     reported_unconsumed_partitions: bool,
 }
 
+struct ShardState {
+    /// The shards this shard was split or merged from.
+    parents: Vec<String>,
+    status: ShardStatus,
+    position: ShardPosition,
+    /// Records at lower offsets were ingested before the shard was (re)opened.
+    resume_offset: MzOffset,
+    metrics: ShardMetrics,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShardStatus {
+    /// The shard is waiting for its parents to be read to their end.
+    Waiting,
+    Reading,
+    /// The shard was closed and has been read to its end.
+    Finished,
+}
+
+/// The position of a reader within a shard, from which the offsets of the
+/// shard's records are derived.
+#[derive(Debug, Default)]
+struct ShardPosition {
+    /// The arrival timestamp of the last record read.
+    millis: u64,
+    /// The number of records read that arrived in `millis`, minus one.
+    index: u64,
+}
+
+impl ShardPosition {
+    /// Returns the offset of the next record of the shard, which arrived at
+    /// `arrival_millis`.
+    fn next_offset(&mut self, arrival_millis: u64) -> MzOffset {
+        // Arrival timestamps are approximate, so they are clamped to keep
+        // offsets ascending.
+        if arrival_millis > self.millis {
+            self.millis = arrival_millis;
+            self.index = 0;
+        } else {
+            self.index += 1;
+        }
+        let index = self.index.min(OFFSETS_PER_MILLISECOND - 1);
+        MzOffset::from(self.millis * OFFSETS_PER_MILLISECOND + index)
+    }
+}
+
+/// Where to start reading a shard.
+#[derive(Debug, Clone, Copy)]
+enum StartingPoint {
+    TrimHorizon,
+    /// The first record that arrived at or after the given Unix timestamp in
+    /// milliseconds.
+    AtTimestamp(u64),
+}
+
+/// An event from the enhanced fan-out subscription to a shard.
+enum ShardEvent {
+    Records {
+        shard_id: String,
+        records: Vec<Record>,
+        millis_behind_latest: Option<i64>,
+    },
+    /// The shard was closed and has been read to its end.
+    Finished {
+        shard_id: String,
+    },
+    Error(String),
+}
+
 struct ShardMetrics {
     millis_behind_latest: DeleteOnDropGauge<'static, AtomicI64, Vec<String>>,
 }
@@ -91,45 +189,263 @@ impl ShardMetrics {
 
 impl KinesisSourceReader {
     async fn update_shard_information(&mut self) -> Result<(), anyhow::Error> {
-        let current_shards: HashSet<_> =
-            mz_kinesis_util::get_shard_ids(&self.kinesis_client, &self.stream_name)
-                .await?
+        if self.consumer_arn.is_none() {
+            if let Some(consumer_name) = &self.consumer_name {
+                let consumer_arn =
+                    register_consumer(&self.kinesis_client, &self.stream_name, consumer_name)
+                        .await?;
+                self.consumer_arn = Some(consumer_arn);
+            }
+        }
+
+        let shards = mz_kinesis_util::list_shards(&self.kinesis_client, &self.stream_name).await?;
+        for shard in shards {
+            let shard_id = shard.shard_id.unwrap_or_default();
+            if self.shards.contains_key(&shard_id) {
+                continue;
+            }
+            let parents = shard
+                .parent_shard_id
+                .into_iter()
+                .chain(shard.adjacent_parent_shard_id)
                 .collect();
-        let known_shards: HashSet<_> = self.shard_set.keys().cloned().collect();
-        let new_shards = current_shards
-            .difference(&known_shards)
-            .map(|shard_id| shard_id.to_owned());
-        for shard_id in new_shards {
-            self.shard_set.insert(
-                shard_id.to_string(),
-                ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id),
+            let metrics = ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id);
+            self.shards.insert(
+                shard_id,
+                ShardState {
+                    parents,
+                    status: ShardStatus::Waiting,
+                    position: ShardPosition::default(),
+                    resume_offset: MzOffset::from(0),
+                    metrics,
+                },
             );
-            self.shard_queue.push_back((
-                shard_id.to_string(),
-                mz_kinesis_util::get_shard_iterator(
-                    &self.kinesis_client,
-                    &self.stream_name,
-                    &shard_id,
-                )
-                .await?,
-            ));
+        }
+        self.start_ready_shards().await
+    }
+
+    /// Starts reading the shards whose parents have all been read to their
+    /// end, or have expired from the stream.
+    async fn start_ready_shards(&mut self) -> Result<(), anyhow::Error> {
+        let ready: Vec<_> = self
+            .shards
+            .iter()
+            .filter(|(_, shard)| {
+                shard.status == ShardStatus::Waiting
+                    && shard.parents.iter().all(|parent| {
+                        self.shards
+                            .get(parent)
+                            .map_or(true, |parent| parent.status == ShardStatus::Finished)
+                    })
+            })
+            .map(|(shard_id, _)| shard_id.clone())
+            .collect();
+        for shard_id in ready {
+            let starting_point = match self.restored_offsets.remove(&shard_id) {
+                Some(offset) => self.reopen_shard(&shard_id, offset),
+                None => StartingPoint::TrimHorizon,
+            };
+            self.start_shard(shard_id, starting_point).await?;
+        }
+        Ok(())
+    }
+
+    /// Prepares to read `shard_id` again from `offset`, returning where to
+    /// start reading it.
+    fn reopen_shard(&mut self, shard_id: &str, offset: MzOffset) -> StartingPoint {
+        let shard = self.shards.get_mut(shard_id).expect("known shard");
+        shard.position = ShardPosition::default();
+        shard.resume_offset = offset;
+        StartingPoint::AtTimestamp(offset.offset / OFFSETS_PER_MILLISECOND)
+    }
+
+    async fn start_shard(
+        &mut self,
+        shard_id: String,
+        starting_point: StartingPoint,
+    ) -> Result<(), anyhow::Error> {
+        self.shards.get_mut(&shard_id).expect("known shard").status = ShardStatus::Reading;
+        match self.consumer_arn.clone() {
+            None => {
+                let iterator = self.get_shard_iterator(&shard_id, starting_point).await?;
+                self.shard_queue.push_back((shard_id, iterator));
+            }
+            Some(consumer_arn) => {
+                task::spawn(|| format!("kinesis_subscribe:{}", shard_id), {
+                    let client = self.kinesis_client.clone();
+                    let events_tx = self.events_tx.clone();
+                    subscribe_to_shard(client, consumer_arn, shard_id, starting_point, events_tx)
+                });
+            }
         }
         Ok(())
     }
 
-    /// Obtains the next record for this shard given a shard iterator
-    async fn get_records(
+    async fn finish_shard(&mut self, shard_id: &str) -> Result<(), anyhow::Error> {
+        self.shards.get_mut(shard_id).expect("known shard").status = ShardStatus::Finished;
+        self.start_ready_shards().await
+    }
+
+    async fn get_shard_iterator(
         &self,
-        shard_iterator: &str,
-    ) -> Result<GetRecordsOutput, SdkError<GetRecordsError>> {
-        self.kinesis_client
+        shard_id: &str,
+        starting_point: StartingPoint,
+    ) -> Result<String, anyhow::Error> {
+        let request = self
+            .kinesis_client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(shard_id);
+        let request = match starting_point {
+            StartingPoint::TrimHorizon => {
+                request.shard_iterator_type(ShardIteratorType::TrimHorizon)
+            }
+            StartingPoint::AtTimestamp(millis) => request
+                .shard_iterator_type(ShardIteratorType::AtTimestamp)
+                .timestamp(date_time(millis)),
+        };
+        request
+            .send()
+            .await?
+            .shard_iterator
+            .ok_or_else(|| anyhow!("Kinesis returned no iterator for shard {}", shard_id))
+    }
+
+    /// Reads the next batch of records from the shard at the front of the
+    /// queue.
+    async fn poll_next_shard(&mut self) -> Result<(), SourceReaderError> {
+        let (shard_id, shard_iterator) = match self.shard_queue.pop_front() {
+            Some(shard) => shard,
+            None => return Ok(()),
+        };
+        match self
+            .kinesis_client
             .get_records()
-            .shard_iterator(shard_iterator)
+            .shard_iterator(&shard_iterator)
             .send()
             .await
+        {
+            Ok(output) => {
+                self.buffer_records(
+                    &shard_id,
+                    output.records.unwrap_or_default(),
+                    output.millis_behind_latest,
+                );
+                match output.next_shard_iterator {
+                    Some(shard_iterator) => self.shard_queue.push_back((shard_id, shard_iterator)),
+                    None => self.finish_shard(&shard_id).await?,
+                }
+            }
+            Err(SdkError::DispatchFailure(e)) => {
+                // todo@jldlaughlin: Parse this to determine fatal/retriable?
+                error!("{}", e);
+                // Do not send error message as this would cause source to terminate
+                self.shard_queue.push_back((shard_id, shard_iterator));
+            }
+            Err(SdkError::ServiceError { err, .. }) if err.is_expired_iterator_exception() => {
+                // Resume the shard after the last record that was read from it.
+                let shard = &self.shards[&shard_id];
+                let offset = MzOffset::from(
+                    shard.position.millis * OFFSETS_PER_MILLISECOND + shard.position.index + 1,
+                )
+                .max(shard.resume_offset);
+                let starting_point = self.reopen_shard(&shard_id, offset);
+                let shard_iterator = self.get_shard_iterator(&shard_id, starting_point).await?;
+                self.shard_queue.push_back((shard_id, shard_iterator));
+            }
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_provisioned_throughput_exceeded_exception() =>
+            {
+                // Do not send error message as this would cause source to terminate
+                self.shard_queue.push_back((shard_id, shard_iterator));
+            }
+            Err(e) => {
+                // Fatal service errors:
+                //  - InvalidArgument
+                //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
+                //    KMSOptInRequired, KMSThrottling
+                //  - ResourceNotFound
+                //
+                // Other fatal Rusoto errors:
+                // - Credentials
+                // - Validation
+                // - ParseError
+                // - Unknown (raw HTTP provided)
+                // - Blocking
+                error!("{}", e);
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles the next event from the enhanced fan-out subscriptions, if one
+    /// arrives within `timeout`.
+    async fn receive_shard_event(&mut self, timeout: Duration) -> Result<(), SourceReaderError> {
+        let event = match tokio::time::timeout(timeout, self.events_rx.recv()).await {
+            Ok(event) => event.expect("the reader holds a sender"),
+            Err(_) => return Ok(()),
+        };
+        match event {
+            ShardEvent::Records {
+                shard_id,
+                records,
+                millis_behind_latest,
+            } => self.buffer_records(&shard_id, records, millis_behind_latest),
+            ShardEvent::Finished { shard_id } => self.finish_shard(&shard_id).await?,
+            ShardEvent::Error(e) => {
+                error!("{}", e);
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn buffer_records(
+        &mut self,
+        shard_id: &str,
+        records: Vec<Record>,
+        millis_behind_latest: Option<i64>,
+    ) {
+        let shard = self.shards.get_mut(shard_id).expect("known shard");
+        if let Some(millis) = millis_behind_latest {
+            shard.metrics.millis_behind_latest.set(millis);
+        }
+        for record in records {
+            let arrival_millis = record
+                .approximate_arrival_timestamp
+                .and_then(|ts| ts.to_millis().ok())
+                .and_then(|millis| u64::try_from(millis).ok())
+                .unwrap_or(0);
+            let offset = shard.position.next_offset(arrival_millis);
+            // Skip the records that were ingested before the shard was
+            // reopened.
+            if offset < shard.resume_offset {
+                continue;
+            }
+            let data = record
+                .data
+                .map(|blob| blob.into_inner())
+                .unwrap_or_else(Vec::new);
+            self.buffered_messages.push_back(SourceMessage {
+                output: 0,
+                partition: PartitionId::Kinesis(shard_id.to_string()),
+                offset,
+                upstream_time_millis: None,
+                key: (),
+                value: Some(data),
+                headers: None,
+                specific_diff: (),
+            });
+        }
     }
 }
 
+#[async_trait::async_trait(?Send)]
 impl SourceReader for KinesisSourceReader {
     type Key = ();
     type Value = Option<Vec<u8>>;
@@ -144,7 +460,7 @@ impl SourceReader for KinesisSourceReader {
         worker_count: usize,
         _consumer_activator: SyncActivator,
         kc: Self::Connection,
-        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
         _encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
@@ -152,204 +468,241 @@ impl SourceReader for KinesisSourceReader {
         let active_read_worker =
             crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
 
-        // TODO: This creates all the machinery, even for the non-active workers.
-        // We could change that to only spin up Kinesis when needed.
-        let state = TokioHandle::current().block_on(create_state(
-            &metrics.kinesis,
-            kc,
+        let config = TokioHandle::current().block_on(kc.aws.load(
             connection_context.aws_external_id_prefix.as_ref(),
-            source_id,
+            Some(&source_id),
             &*connection_context.secrets_reader,
         ));
-        match state {
-            Ok((kinesis_client, stream_name, shard_set, shard_queue)) => Ok((
-                KinesisSourceReader {
-                    tokio_handle: TokioHandle::current(),
-                    kinesis_client,
-                    shard_queue,
-                    last_checked_shards: Instant::now(),
-                    buffered_messages: VecDeque::new(),
-                    shard_set,
-                    stream_name,
-                    processed_message_count: 0,
-                    base_metrics: metrics.kinesis,
-                    active_read_worker,
-                    reported_unconsumed_partitions: false,
-                },
-                LogCommitter {
-                    source_id,
-                    worker_id,
-                    worker_count,
-                },
-            )),
-            Err(e) => Err(anyhow!("{}", e)),
-        }
+        let restored_offsets = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match (pid, offset) {
+                (PartitionId::Kinesis(shard_id), Some(offset)) => Some((shard_id, offset)),
+                _ => None,
+            })
+            .collect();
+        let (events_tx, events_rx) = mpsc::channel(1024);
+
+        Ok((
+            KinesisSourceReader {
+                kinesis_client: KinesisClient::new(&config),
+                stream_name: kc.stream_name,
+                consumer_name: kc.consumer_name,
+                consumer_arn: None,
+                shards: HashMap::new(),
+                shard_queue: VecDeque::new(),
+                events_tx,
+                events_rx,
+                restored_offsets,
+                last_checked_shards: None,
+                buffered_messages: VecDeque::new(),
+                base_metrics: metrics.kinesis,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
     }
 
-    fn get_next_message(
+    async fn next(
         &mut self,
-    ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
+        timestamp_granularity: Duration,
+    ) -> Option<Result<SourceMessageType<Self::Key, Self::Value, Self::Diff>, SourceReaderError>>
+    {
         if !self.active_read_worker {
             if !self.reported_unconsumed_partitions {
                 self.reported_unconsumed_partitions = true;
-                return Ok(NextMessage::Ready(
-                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
-                ));
+                return Some(Ok(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ])));
             }
-            return Ok(NextMessage::Finished);
+            return None;
         }
 
-        assert_eq!(self.shard_queue.len(), self.shard_set.len());
+        loop {
+            if let Some(message) = self.buffered_messages.pop_front() {
+                return Some(Ok(SourceMessageType::Finalized(message)));
+            }
 
-        //TODO move to timestamper
-        if self.last_checked_shards.elapsed() >= KINESIS_SHARD_REFRESH_RATE {
-            if let Err(e) = self
-                .tokio_handle
-                .clone()
-                .block_on(self.update_shard_information())
-            {
-                error!("{:#?}", e);
-                return Err(e.into());
+            //TODO move to timestamper
+            if self.last_checked_shards.map_or(true, |checked| {
+                checked.elapsed() >= KINESIS_SHARD_REFRESH_RATE
+            }) {
+                if let Err(e) = self.update_shard_information().await {
+                    error!("{:#?}", e);
+                    return Some(Err(e.into()));
+                }
+                self.last_checked_shards = Some(Instant::now());
+            }
+
+            // Rotate through all of a stream's shards, or wait for the
+            // subscriptions to them to produce records.
+            let result = match self.consumer_arn {
+                None => match self.poll_next_shard().await {
+                    Ok(()) if self.buffered_messages.is_empty() => {
+                        // There were no new messages, check again after a delay
+                        tokio::time::sleep(timestamp_granularity).await;
+                        Ok(())
+                    }
+                    result => result,
+                },
+                Some(_) => self.receive_shard_event(timestamp_granularity).await,
+            };
+            if let Err(e) = result {
+                return Some(Err(e));
             }
-            self.last_checked_shards = std::time::Instant::now();
         }
+    }
+}
 
-        if let Some(message) = self.buffered_messages.pop_front() {
-            Ok(NextMessage::Ready(SourceMessageType::Finalized(message)))
-        } else {
-            // Rotate through all of a stream's shards, start with a new shard on each activation.
-            if let Some((shard_id, mut shard_iterator)) = self.shard_queue.pop_front() {
-                if let Some(iterator) = &shard_iterator {
-                    let get_records_output =
-                        match self.tokio_handle.block_on(self.get_records(iterator)) {
-                            Ok(output) => {
-                                shard_iterator = output.next_shard_iterator.clone();
-                                if let Some(millis) = output.millis_behind_latest {
-                                    self.shard_set
-                                        .get(&shard_id)
-                                        .unwrap()
-                                        .millis_behind_latest
-                                        .set(millis);
-                                }
-                                output
-                            }
-                            Err(SdkError::DispatchFailure(e)) => {
-                                // todo@jldlaughlin: Parse this to determine fatal/retriable?
-                                error!("{}", e);
-                                self.shard_queue.push_back((shard_id, shard_iterator));
-                                // Do not send error message as this would cause source to terminate
-                                return Ok(NextMessage::TransientDelay);
-                            }
-                            Err(SdkError::ServiceError { err, .. })
-                                if err.is_expired_iterator_exception() =>
-                            {
-                                // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
-                                error!("{}", err);
-                                return Err(SourceReaderError {
-                                    inner: SourceErrorDetails::Other(err.to_string()),
-                                });
-                            }
-                            Err(SdkError::ServiceError { err, .. })
-                                if err.is_provisioned_throughput_exceeded_exception() =>
-                            {
-                                self.shard_queue.push_back((shard_id, shard_iterator));
-                                // Do not send error message as this would cause source to terminate
-                                return Ok(NextMessage::Pending);
-                            }
-                            Err(e) => {
-                                // Fatal service errors:
-                                //  - InvalidArgument
-                                //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
-                                //    KMSOptInRequired, KMSThrottling
-                                //  - ResourceNotFound
-                                //
-                                // Other fatal Rusoto errors:
-                                // - Credentials
-                                // - Validation
-                                // - ParseError
-                                // - Unknown (raw HTTP provided)
-                                // - Blocking
-                                error!("{}", e);
-                                return Err(SourceReaderError {
-                                    inner: SourceErrorDetails::Other(e.to_string()),
-                                });
-                            }
-                        };
-
-                    for record in get_records_output.records.unwrap_or_default() {
-                        let data = record
-                            .data
-                            .map(|blob| blob.into_inner())
-                            .unwrap_or_else(Vec::new);
-                        self.processed_message_count += 1;
-                        let source_message = SourceMessage {
-                            output: 0,
-                            partition: PartitionId::None,
-                            offset: MzOffset {
-                                //TODO: should MzOffset be modified to be a string?
-                                offset: self.processed_message_count,
-                            },
-                            upstream_time_millis: None,
-                            key: (),
-                            value: Some(data),
-                            headers: None,
-                            specific_diff: (),
-                        };
-                        self.buffered_messages.push_back(source_message);
+/// Registers the enhanced fan-out consumer `consumer_name` with the stream, if
+/// it is not registered yet, and waits for it to become active, returning its
+/// ARN.
+async fn register_consumer(
+    client: &KinesisClient,
+    stream_name: &str,
+    consumer_name: &str,
+) -> Result<String, anyhow::Error> {
+    let stream_arn = client
+        .describe_stream_summary()
+        .stream_name(stream_name)
+        .send()
+        .await?
+        .stream_description_summary
+        .and_then(|summary| summary.stream_arn)
+        .ok_or_else(|| anyhow!("Kinesis stream {} has no ARN", stream_name))?;
+
+    match client
+        .register_stream_consumer()
+        .stream_arn(&stream_arn)
+        .consumer_name(consumer_name)
+        .send()
+        .await
+    {
+        Ok(_) => {}
+        // The consumer was registered by an earlier instance of the source.
+        Err(SdkError::ServiceError { err, .. }) if err.is_resource_in_use_exception() => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    Retry::default()
+        .max_duration(Duration::from_secs(60))
+        .retry_async(|_| async {
+            let consumer = client
+                .describe_stream_consumer()
+                .stream_arn(&stream_arn)
+                .consumer_name(consumer_name)
+                .send()
+                .await?
+                .consumer_description
+                .ok_or_else(|| anyhow!("Kinesis consumer {} does not exist", consumer_name))?;
+            match (consumer.consumer_status, consumer.consumer_arn) {
+                (Some(ConsumerStatus::Active), Some(consumer_arn)) => Ok(consumer_arn),
+                _ => bail!("Kinesis consumer {} is not active", consumer_name),
+            }
+        })
+        .await
+}
+
+/// Forwards the records of `shard_id` to `events_tx`, through an enhanced
+/// fan-out subscription that is renewed whenever it expires.
+async fn subscribe_to_shard(
+    client: KinesisClient,
+    consumer_arn: String,
+    shard_id: String,
+    starting_point: StartingPoint,
+    events_tx: Sender<ShardEvent>,
+) {
+    let mut starting_position = match starting_point {
+        StartingPoint::TrimHorizon => StartingPosition::builder()
+            .r#type(ShardIteratorType::TrimHorizon)
+            .build(),
+        StartingPoint::AtTimestamp(millis) => StartingPosition::builder()
+            .r#type(ShardIteratorType::AtTimestamp)
+            .timestamp(date_time(millis))
+            .build(),
+    };
+    // Stop once the source is dropped.
+    while !events_tx.is_closed() {
+        let output = match client
+            .subscribe_to_shard()
+            .consumer_arn(&consumer_arn)
+            .shard_id(&shard_id)
+            .starting_position(starting_position.clone())
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            // The previous subscription to the shard, if any, may not have
+            // been released yet.
+            Err(SdkError::ServiceError { err, .. }) if err.is_resource_in_use_exception() => {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+            Err(e) => {
+                let _ = events_tx.send(ShardEvent::Error(e.to_string())).await;
+                return;
+            }
+        };
+
+        let mut event_stream = output.event_stream;
+        loop {
+            match event_stream.recv().await {
+                Ok(Some(SubscribeToShardEventStream::SubscribeToShardEvent(event))) => {
+                    let continuation_sequence_number = event.continuation_sequence_number;
+                    let records = ShardEvent::Records {
+                        shard_id: shard_id.clone(),
+                        records: event.records.unwrap_or_default(),
+                        millis_behind_latest: event.millis_behind_latest,
+                    };
+                    if events_tx.send(records).await.is_err() {
+                        // The source was dropped.
+                        return;
+                    }
+                    match continuation_sequence_number {
+                        Some(sequence_number) => {
+                            starting_position = StartingPosition::builder()
+                                .r#type(ShardIteratorType::AfterSequenceNumber)
+                                .sequence_number(sequence_number)
+                                .build();
+                        }
+                        None => {
+                            // The shard was closed by resharding.
+                            let _ = events_tx.send(ShardEvent::Finished { shard_id }).await;
+                            return;
+                        }
                     }
-                    self.shard_queue.push_back((shard_id, shard_iterator));
+                }
+                Ok(Some(_)) => {}
+                // Subscriptions expire after five minutes.
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = events_tx.send(ShardEvent::Error(e.to_string())).await;
+                    return;
                 }
             }
-            Ok(match self.buffered_messages.pop_front() {
-                Some(message) => NextMessage::Ready(SourceMessageType::Finalized(message)),
-                None => NextMessage::Pending,
-            })
         }
     }
 }
 
-/// Creates the necessary data-structures for shard management
-// todo: Better error handling here! Not all errors mean we're done/can't progress.
-async fn create_state(
-    base_metrics: &KinesisMetrics,
-    c: KinesisSourceConnection,
-    aws_external_id_prefix: Option<&AwsExternalIdPrefix>,
-    source_id: GlobalId,
-    secrets_reader: &dyn SecretsReader,
-) -> Result<
-    (
-        KinesisClient,
-        String,
-        HashMap<String, ShardMetrics>,
-        VecDeque<(String, Option<String>)>,
-    ),
-    anyhow::Error,
-> {
-    let config = c
-        .aws
-        .load(aws_external_id_prefix, Some(&source_id), secrets_reader)
-        .await;
-
-    let kinesis_client = aws_sdk_kinesis::Client::new(&config);
-
-    let shard_set = mz_kinesis_util::get_shard_ids(&kinesis_client, &c.stream_name).await?;
-    let mut shard_queue: VecDeque<(String, Option<String>)> = VecDeque::new();
-    let mut shard_map = HashMap::new();
-    for shard_id in shard_set {
-        shard_queue.push_back((
-            shard_id.clone(),
-            mz_kinesis_util::get_shard_iterator(&kinesis_client, &c.stream_name, &shard_id).await?,
-        ));
-        shard_map.insert(
-            shard_id.clone(),
-            ShardMetrics::new(base_metrics, &c.stream_name, &shard_id),
-        );
-    }
+fn date_time(millis: u64) -> DateTime {
+    DateTime::from_millis(i64::try_from(millis).expect("timestamp fits into i64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok((
-        kinesis_client,
-        c.stream_name.clone(),
-        shard_map,
-        shard_queue,
-    ))
+    #[test]
+    fn test_shard_position() {
+        let mut position = ShardPosition::default();
+        assert_eq!(position.next_offset(5), MzOffset::from(5_000_000));
+        assert_eq!(position.next_offset(5), MzOffset::from(5_000_001));
+        assert_eq!(position.next_offset(7), MzOffset::from(7_000_000));
+        // Arrival timestamps that go backwards are clamped.
+        assert_eq!(position.next_offset(6), MzOffset::from(7_000_001));
+    }
 }
@@ -111,6 +111,8 @@ pub(super) struct PartitionSpecificMetrics {
     pub(super) closed_ts: UIntGaugeVec,
     pub(super) messages_ingested: GenericCounterVec<AtomicI64>,
     pub(super) partition_offset_max: IntGaugeVec,
+    pub(super) partition_decoded_bytes: IntGaugeVec,
+    pub(super) kafka_wire_bytes: IntGaugeVec,
     pub(super) source_resume_upper: UIntGaugeVec,
 }
 
@@ -143,6 +145,16 @@ impl PartitionSpecificMetrics {
                 help: "High watermark offset on broker for partition",
                 var_labels: ["topic", "source_id", "partition_id"],
             )),
+            partition_decoded_bytes: registry.register(metric!(
+                name: "mz_kafka_partition_decoded_bytes",
+                help: "The number of bytes of messages consumed from the partition, after decompression",
+                var_labels: ["topic", "source_id", "partition_id"],
+            )),
+            kafka_wire_bytes: registry.register(metric!(
+                name: "mz_kafka_source_wire_bytes",
+                help: "The number of bytes received from the brokers by the source's consumer, before decompression",
+                var_labels: ["topic", "source_id", "worker_id"],
+            )),
             source_resume_upper: registry.register(metric!(
                 name: "mz_source_resume_upper",
                 help: "The offset-domain upper that is used for initializing this partition",
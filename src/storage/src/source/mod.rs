@@ -40,17 +40,26 @@ use crate::source::types::{NextMessage, SourceMessage, SourceReader};
 mod antichain;
 mod commit;
 mod delimited_value_reader;
+mod file;
 pub mod generator;
 mod healthcheck;
 mod kafka;
 mod kinesis;
 pub mod metrics;
+mod mqtt;
+mod mysql;
+mod nats;
 pub mod persist_source;
 mod postgres;
+mod pubsub;
+mod rate_limit;
 mod reclock;
 mod resumption;
+mod retry;
 mod s3;
+mod sftp;
 mod source_reader_pipeline;
+mod sql_server;
 // Public for integration testing.
 #[doc(hidden)]
 pub mod testscript;
@@ -58,13 +67,20 @@ pub mod types;
 pub mod util;
 
 pub use delimited_value_reader::DelimitedValueSource;
+pub use file::FileSourceReader;
 pub use generator::LoadGeneratorSourceReader;
 pub use kafka::KafkaSourceReader;
 pub use kinesis::KinesisSourceReader;
+pub use mqtt::MqttSourceReader;
+pub use mysql::MySqlSourceReader;
+pub use nats::NatsSourceReader;
 pub use postgres::PostgresSourceReader;
-pub use s3::S3SourceReader;
+pub use pubsub::PubSubSourceReader;
+pub use s3::{fetch_parquet_desc, S3SourceReader};
+pub use sftp::SftpSourceReader;
 pub use source_reader_pipeline::create_raw_source;
 pub use source_reader_pipeline::RawSourceCreationConfig;
+pub use sql_server::SqlServerSourceReader;
 pub use testscript::TestScriptSourceReader;
 
 /// Returns true if the given source id/worker id is responsible for handling the given
@@ -75,6 +91,12 @@ pub fn responsible_for(
     worker_count: usize,
     pid: &PartitionId,
 ) -> bool {
+    // MySQL sources read the transactions of all servers from a single
+    // binary log, on the worker responsible for `PartitionId::None`.
+    let pid = match pid {
+        PartitionId::MySqlServer(_) => &PartitionId::None,
+        pid => pid,
+    };
     // Distribute partitions equally amongst workers.
     (usize::cast_from(pid.hashed()) % worker_count) == worker_id
 }
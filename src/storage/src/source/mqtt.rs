@@ -0,0 +1,434 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functionality for creating MQTT sources.
+//!
+//! A single worker runs a task that subscribes to the topic filter at quality
+//! of service level 1, in a persistent session that the broker keeps for the
+//! source across restarts. A message is only acknowledged once the offset it
+//! was emitted at is durably recorded, so messages that were not yet ingested
+//! when the source restarts or reconnects are redelivered by the broker. MQTT
+//! sources therefore deliver messages at least once.
+//!
+//! The topic of each message is used as its key, so that `ENVELOPE UPSERT`
+//! keeps the latest message of each topic and `INCLUDE TOPIC` can report the
+//! topic that matched a wildcard.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use bytes::Bytes;
+use rumqttc::{
+    AsyncClient, ConnectReturnCode, Event, MqttOptions, Packet, Publish, QoS, SubscribeReasonCode,
+    Transport,
+};
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
+use tracing::{info, warn};
+
+use mz_expr::PartitionId;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{SourceErrorClass, SourceRetry};
+use crate::source::types::OffsetCommitter;
+use crate::source::{SourceMessage, SourceMessageType, SourceReader, SourceReaderError};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::{MqttSourceConnection, MzOffset};
+
+/// The capacity of the channel of requests to the event loop.
+///
+/// Packet identifiers are 16 bits wide, so the broker can't have more
+/// unacknowledged messages outstanding than this, and acknowledgements never
+/// find the channel full.
+const REQUEST_CAPACITY: usize = 1 << 16;
+
+/// A message received from the broker, together with the connection it was
+/// received on.
+///
+/// A message can only be acknowledged on the connection it was received on,
+/// since its packet identifier is reused once the connection is closed.
+struct MqttMessage {
+    connection: u64,
+    publish: Publish,
+}
+
+enum InternalMessage {
+    Message(MqttMessage),
+    Status(SourceStatusUpdate),
+}
+
+/// Information required to subscribe to topics of an MQTT broker
+pub struct MqttSourceReader {
+    /// Receiver channel that ingests messages
+    receiver_stream: Receiver<Result<InternalMessage, anyhow::Error>>,
+    /// The offset to emit the next message at
+    next_offset: MzOffset,
+    /// The message that acknowledges each emitted message that was not yet
+    /// acknowledged, by the offset it was emitted at
+    pending: Arc<Mutex<BTreeMap<MzOffset, MqttMessage>>>,
+    // MQTT sources support single-threaded ingestion only, so only one of
+    // the `MqttSourceReader`s will actually produce data.
+    active_read_worker: bool,
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+/// Acknowledges the messages whose offsets were durably recorded.
+pub struct MqttOffsetCommitter {
+    pending: Arc<Mutex<BTreeMap<MzOffset, MqttMessage>>>,
+    /// Sends the messages to acknowledge to the subscribing task
+    acks_tx: UnboundedSender<Vec<MqttMessage>>,
+    logger: LogCommitter,
+}
+
+#[async_trait]
+impl OffsetCommitter for MqttOffsetCommitter {
+    async fn commit_offsets(
+        &self,
+        offsets: HashMap<PartitionId, MzOffset>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(offset) = offsets.get(&PartitionId::None) {
+            let committed = {
+                let mut pending = self.pending.lock().expect("lock poisoned");
+                let uncommitted = pending.split_off(&(*offset + MzOffset::from(1)));
+                std::mem::replace(&mut *pending, uncommitted)
+            };
+            if !committed.is_empty() {
+                // The subscribing task stops once the source is dropped, at
+                // which point there is nothing left to acknowledge.
+                let _ = self.acks_tx.send(committed.into_values().collect());
+            }
+        }
+        self.logger.commit_offsets(offsets).await
+    }
+}
+
+#[async_trait(?Send)]
+impl SourceReader for MqttSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Diff = ();
+    type OffsetCommitter = MqttOffsetCommitter;
+    type Connection = MqttSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        // Messages have no offsets of their own, so they are emitted at
+        // consecutive offsets that continue from where the previous
+        // incarnation of the source left off.
+        let next_offset = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match pid {
+                PartitionId::None => offset,
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        // a single arbitrary worker is responsible for subscribing
+        let (acks_tx, acks_rx) = mpsc::unbounded_channel();
+        let receiver = if active_read_worker {
+            let (dataflow_tx, dataflow_rx) = mpsc::channel(10_000);
+            info!(
+                "source_id={} subscribing to mqtt topic={} worker={}",
+                source_id, connection.topic, worker_id
+            );
+            task::spawn(
+                || format!("mqtt_subscribe:{}", source_id),
+                subscribe_task(
+                    source_id,
+                    connection,
+                    Arc::clone(&connection_context.secrets_reader),
+                    dataflow_tx,
+                    acks_rx,
+                ),
+            );
+            dataflow_rx
+        } else {
+            let (_dataflow_tx, dataflow_rx) = mpsc::channel(1);
+            dataflow_rx
+        };
+
+        let pending = Arc::new(Mutex::new(BTreeMap::new()));
+        Ok((
+            MqttSourceReader {
+                receiver_stream: receiver,
+                next_offset,
+                pending: Arc::clone(&pending),
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            MqttOffsetCommitter {
+                pending,
+                acks_tx,
+                logger: LogCommitter {
+                    source_id,
+                    worker_id,
+                    worker_count,
+                },
+            },
+        ))
+    }
+
+    async fn next(
+        &mut self,
+        _timestamp_granularity: Duration,
+    ) -> Option<Result<SourceMessageType<Self::Key, Self::Value, Self::Diff>, SourceReaderError>>
+    {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(Ok(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ])));
+            }
+            return None;
+        }
+
+        match self.receiver_stream.recv().await? {
+            Ok(InternalMessage::Message(message)) => {
+                let offset = self.next_offset;
+                self.next_offset += 1;
+                let Publish { topic, payload, .. } = message.publish;
+                // Only the packet identifier is needed for the
+                // acknowledgement, so the payload isn't kept around.
+                let ack = Publish {
+                    topic: String::new(),
+                    payload: Bytes::new(),
+                    ..message.publish
+                };
+                self.pending.lock().expect("lock poisoned").insert(
+                    offset,
+                    MqttMessage {
+                        connection: message.connection,
+                        publish: ack,
+                    },
+                );
+                Some(Ok(SourceMessageType::Finalized(SourceMessage {
+                    output: 0,
+                    partition: PartitionId::None,
+                    offset,
+                    upstream_time_millis: None,
+                    key: Some(topic.into_bytes()),
+                    value: Some(payload.to_vec()),
+                    headers: None,
+                    specific_diff: (),
+                })))
+            }
+            Ok(InternalMessage::Status(update)) => {
+                Some(Ok(SourceMessageType::SourceStatus(update)))
+            }
+            Err(e) => Some(Err(SourceReaderError {
+                inner: SourceErrorDetails::Other(format!("{:#}", e)),
+            })),
+        }
+    }
+}
+
+/// Subscribes to the topic filter, reconnecting with a backoff whenever the
+/// connection fails, until the source is dropped.
+async fn subscribe_task(
+    source_id: GlobalId,
+    connection: MqttSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    mut acks_rx: UnboundedReceiver<Vec<MqttMessage>>,
+) {
+    // The broker keeps the session of the client ID, including its
+    // subscription and unacknowledged messages, across connections.
+    let client_id = format!("materialize-{}", source_id);
+    let mut retry = SourceRetry::default();
+    let mut connection_id = 0;
+    loop {
+        connection_id += 1;
+        let result = subscribe(
+            &connection,
+            &client_id,
+            connection_id,
+            &*secrets_reader,
+            &tx,
+            &mut acks_rx,
+            &mut retry,
+        )
+        .await;
+        let e = match result {
+            // The source was dropped.
+            Ok(()) => break,
+            Err(e) => e,
+        };
+
+        let class = error_class(&e);
+        let backoff = match retry.on_error(class) {
+            Some(backoff) => backoff,
+            None => {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+        };
+        warn!(
+            "subscription of source {} interrupted by {} error, retrying in {:?}: {:#}",
+            source_id,
+            class.name(),
+            backoff,
+            e
+        );
+        let next_retry_at = (SystemTime::now() + backoff)
+            .duration_since(UNIX_EPOCH)
+            .expect("system time after Unix epoch")
+            .as_millis();
+        let update = SourceStatusUpdate::retrying(
+            &format!("{:#}", e),
+            class,
+            u64::try_from(next_retry_at).expect("retry time fits into u64"),
+        );
+        if tx.send(Ok(InternalMessage::Status(update))).await.is_err() {
+            return;
+        }
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = tokio::time::sleep(backoff) => {}
+        }
+    }
+    info!("source_id={} stopped subscribing to mqtt topic", source_id);
+}
+
+/// Connects to the broker, subscribing to the topic filter if the broker has
+/// no session for the source, and forwards the messages it delivers to `tx`
+/// until the source is dropped.
+async fn subscribe(
+    connection: &MqttSourceConnection,
+    client_id: &str,
+    connection_id: u64,
+    secrets_reader: &dyn SecretsReader,
+    tx: &Sender<Result<InternalMessage, anyhow::Error>>,
+    acks_rx: &mut UnboundedReceiver<Vec<MqttMessage>>,
+    retry: &mut SourceRetry,
+) -> Result<(), anyhow::Error> {
+    // Credentials are read anew for every connection, so that rotated secrets
+    // take effect the next time we connect.
+    let options = options(connection, client_id.into(), secrets_reader).await?;
+    let (client, mut event_loop) = AsyncClient::new(options, REQUEST_CAPACITY);
+    loop {
+        tokio::select! {
+            event = event_loop.poll() => {
+                match event.context("error connecting to mqtt")? {
+                    Event::Incoming(Packet::ConnAck(ack)) => {
+                        if ack.code != ConnectReturnCode::Success {
+                            bail!("broker refused connection: {:?}", ack.code);
+                        }
+                        // Subscribing again would make the broker send the
+                        // retained messages of the topics again.
+                        if !ack.session_present {
+                            client
+                                .try_subscribe(&connection.topic, QoS::AtLeastOnce)
+                                .map_err(|e| anyhow!("error subscribing: {}", e))?;
+                        }
+                    }
+                    Event::Incoming(Packet::SubAck(ack)) => {
+                        if ack.return_codes.contains(&SubscribeReasonCode::Failure) {
+                            bail!("broker refused subscription to topic {}", connection.topic);
+                        }
+                    }
+                    Event::Incoming(Packet::Publish(publish)) => {
+                        retry.reset();
+                        let message = MqttMessage {
+                            connection: connection_id,
+                            publish,
+                        };
+                        if tx.send(Ok(InternalMessage::Message(message))).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(messages) = acks_rx.recv() => {
+                // Messages received on earlier connections are redelivered
+                // by the broker, and acknowledged once they are received
+                // again.
+                for message in messages.iter().filter(|m| m.connection == connection_id) {
+                    client
+                        .try_ack(&message.publish)
+                        .map_err(|e| anyhow!("error acknowledging message: {}", e))?;
+                }
+            }
+            _ = tx.closed() => return Ok(()),
+        }
+    }
+}
+
+/// Returns the options for connecting to the broker targeted by `connection`
+/// as `client_id`, reading its credentials from `secrets_reader`.
+async fn options(
+    connection: &MqttSourceConnection,
+    client_id: String,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<MqttOptions, anyhow::Error> {
+    let url = url::Url::parse(&connection.url)?;
+    let (tls, default_port) = match url.scheme() {
+        "mqtt" => (false, 1883),
+        "mqtts" => (true, 8883),
+        scheme => bail!("unsupported MQTT URL scheme: {}", scheme),
+    };
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("MQTT URL is missing a host"))?;
+    let mut options = MqttOptions::new(client_id, host, url.port().unwrap_or(default_port));
+    options.set_keep_alive(Duration::from_secs(30));
+    // Keep the session, and with it the messages that were not acknowledged
+    // yet, across connections.
+    options.set_clean_session(false);
+    // Messages are acknowledged once they are durably ingested.
+    options.set_manual_acks(true);
+    if tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    if let (Some(user), Some(password)) = (&connection.user, connection.password) {
+        options.set_credentials(
+            user.get_string(secrets_reader).await?,
+            secrets_reader.read_string(password).await?,
+        );
+    }
+    Ok(options)
+}
+
+/// Classifies an error encountered while subscribing.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    let message = format!("{:#}", err);
+    if message.contains("BadUserNamePassword") || message.contains("NotAuthorized") {
+        SourceErrorClass::Auth
+    } else {
+        SourceErrorClass::Transient
+    }
+}
@@ -0,0 +1,1069 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that replicates tables from MySQL by following its binary log.
+//!
+//! The source identifies its position in the binary log by the global
+//! transaction identifiers (GTIDs) of the transactions it has read. A GTID
+//! consists of the UUID of the server on which the transaction originated and
+//! a transaction number (GNO) that increases in commit order on that server.
+//! GNOs only order the transactions of a single server, so the source tracks
+//! each server as a partition of its own, whose offsets are the GNOs of the
+//! server's transactions. The initial snapshot of the tables is emitted in a
+//! partition of its own.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, bail};
+use futures::{FutureExt, StreamExt};
+use mysql_async::prelude::Queryable;
+use mysql_async::{BinlogRequest, Conn, Value};
+use mysql_common::binlog::events::{EventData, RowsEventData};
+use mysql_common::binlog::row::BinlogRow;
+use mysql_common::binlog::value::BinlogValue;
+use timely::scheduling::SyncActivator;
+use tokio::runtime::Handle as TokioHandle;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use mz_expr::{MirScalarExpr, PartitionId};
+use mz_mysql_util::desc::{MySqlColumnDesc, MySqlTableDesc};
+use mz_ore::task;
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
+
+use super::metrics::SourceBaseMetrics;
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{
+    retry_replication, ReplicationFailure, ReplicationTask, SourceErrorClass,
+    REPLICATION_PROGRESS_THRESHOLD,
+};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::{encoding::SourceDataEncoding, MySqlSourceConnection, MzOffset};
+
+/// MySQL error codes that mean the source can never make progress again.
+///
+/// See <https://dev.mysql.com/doc/mysql-errors/8.0/en/server-error-reference.html>.
+const DEFINITE_ERROR_CODES: &[u16] = &[
+    1049, // ER_BAD_DB_ERROR: the schema of a table no longer exists
+    1146, // ER_NO_SUCH_TABLE: a table no longer exists
+    1236, // ER_MASTER_FATAL_ERROR_READING_BINLOG: the binary log has been purged
+];
+
+/// MySQL error codes that indicate the source failed to authenticate.
+const AUTH_ERROR_CODES: &[u16] = &[
+    1044, // ER_DBACCESS_DENIED_ERROR
+    1045, // ER_ACCESS_DENIED_ERROR
+];
+
+enum ReplicationError {
+    /// This error is definite: this source is permanently wedged.
+    /// Returning a definite error will cause the collection to become un-queryable.
+    Definite(anyhow::Error),
+    /// This error may or may not resolve itself in the future, and
+    /// should be retried instead of being added to the output.
+    Indefinite(anyhow::Error),
+}
+
+impl From<mysql_async::Error> for ReplicationError {
+    fn from(err: mysql_async::Error) -> Self {
+        match &err {
+            mysql_async::Error::Server(e) if DEFINITE_ERROR_CODES.contains(&e.code) => {
+                Self::Definite(err.into())
+            }
+            _ => Self::Indefinite(err.into()),
+        }
+    }
+}
+
+macro_rules! try_definite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Definite(err.into())),
+        }
+    };
+}
+macro_rules! try_indefinite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Indefinite(err.into())),
+        }
+    };
+}
+
+// Message used to communicate between `get_next_message` and the tokio task
+enum InternalMessage {
+    Err(SourceReaderError),
+    Status(SourceStatusUpdate),
+    Progress(PartitionId, MzOffset),
+    Value {
+        output: usize,
+        value: Row,
+        partition: PartitionId,
+        offset: MzOffset,
+        diff: Diff,
+        end: bool,
+    },
+}
+
+/// Information required to sync data from MySQL
+pub struct MySqlSourceReader {
+    receiver_stream: Receiver<InternalMessage>,
+
+    // MySQL sources support single-threaded ingestion only, so only one of
+    // the `MySqlSourceReader`s will actually produce data.
+    active_read_worker: bool,
+
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+/// Information about an ingested upstream table
+struct SourceTable {
+    /// The source output index of this table
+    output_index: usize,
+    /// The description of this table at the time the source was created
+    desc: MySqlTableDesc,
+    /// The scalar expressions required to cast the text encoded columns received from MySQL
+    /// into the target relational types
+    casts: Vec<MirScalarExpr>,
+}
+
+/// An internal struct held by the spawned tokio task
+struct MySqlTaskInfo {
+    source_id: GlobalId,
+    connection_config: mz_mysql_util::Config,
+    server_id: u32,
+    /// Whether the source has emitted the initial snapshot of its tables.
+    snapshotted: bool,
+    /// The transaction number of the next transaction to read from the
+    /// binary log, by the server on which the transactions originate.
+    offsets: BTreeMap<Uuid, u64>,
+    /// The ingested tables, by schema and table name.
+    source_tables: BTreeMap<(String, String), SourceTable>,
+    row_sender: RowSender,
+    sender: Sender<InternalMessage>,
+}
+
+impl SourceReader for MySqlSourceReader {
+    type Key = ();
+    type Value = Row;
+    // MySQL can produce deletes that cause retractions
+    type Diff = Diff;
+    type OffsetCommitter = LogCommitter;
+    type Connection = MySqlSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(50_000);
+
+        let mut snapshotted = false;
+        let mut offsets = BTreeMap::new();
+        for (pid, offset) in start_offsets {
+            match (pid, offset) {
+                (PartitionId::None, Some(offset)) => snapshotted = offset.offset > 0,
+                (PartitionId::MySqlServer(server), Some(offset)) => {
+                    let server = Uuid::parse_str(&server).expect("MySQL server IDs are UUIDs");
+                    offsets.insert(server, offset.offset);
+                }
+                _ => {}
+            }
+        }
+
+        let connection_config = TokioHandle::current()
+            .block_on(
+                connection
+                    .connection
+                    .config(&*connection_context.secrets_reader),
+            )
+            .expect("MySQL connection unexpectedly missing secrets");
+
+        if active_read_worker {
+            let mut source_tables = BTreeMap::new();
+            let tables_iter = connection.details.tables.into_iter();
+            for (i, (desc, casts)) in tables_iter.zip(connection.table_casts).enumerate() {
+                let key = (desc.schema_name.clone(), desc.name.clone());
+                let source_table = SourceTable {
+                    output_index: i + 1,
+                    desc,
+                    casts,
+                };
+                source_tables.insert(key, source_table);
+            }
+
+            let task_info = MySqlTaskInfo {
+                source_id,
+                connection_config,
+                server_id: connection.details.server_id,
+                snapshotted,
+                offsets,
+                source_tables,
+                row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
+                sender: dataflow_tx,
+            };
+
+            task::spawn(
+                || format!("mysql_source:{}", source_id),
+                mysql_replication_loop(task_info),
+            );
+        }
+
+        Ok((
+            Self {
+                receiver_stream: dataflow_rx,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+
+    fn get_next_message(
+        &mut self,
+    ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Ok(NextMessage::Ready(
+                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
+                ));
+            }
+            return Ok(NextMessage::Finished);
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(InternalMessage::Value {
+                output,
+                value,
+                partition,
+                offset,
+                diff,
+                end,
+            })) => {
+                let message = SourceMessage {
+                    output,
+                    partition,
+                    offset,
+                    upstream_time_millis: None,
+                    key: (),
+                    value,
+                    headers: None,
+                    specific_diff: diff,
+                };
+                if end {
+                    Ok(NextMessage::Ready(SourceMessageType::Finalized(message)))
+                } else {
+                    Ok(NextMessage::Ready(SourceMessageType::InProgress(message)))
+                }
+            }
+            Some(Some(InternalMessage::Progress(partition, upper))) => Ok(NextMessage::Ready(
+                SourceMessageType::Progress(partition, upper),
+            )),
+            Some(Some(InternalMessage::Err(e))) => Err(e),
+            Some(Some(InternalMessage::Status(update))) => {
+                Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
+            }
+            None => Ok(NextMessage::Pending),
+            Some(None) => Ok(NextMessage::Finished),
+        }
+    }
+}
+
+/// Defers to `mysql_replication_loop_inner` and sends errors through the channel if they occur
+async fn mysql_replication_loop(mut task_info: MySqlTaskInfo) {
+    match mysql_replication_loop_inner(&mut task_info).await {
+        Ok(()) => {}
+        Err(e) => {
+            // Drop the send error, as we have no way of communicating back to the
+            // source operator if the channel is gone.
+            let _ = task_info
+                .row_sender
+                .sender
+                .send(InternalMessage::Err(e))
+                .await;
+            task_info
+                .row_sender
+                .activator
+                .activate()
+                .expect("mysql reader activation failed");
+        }
+    }
+}
+
+/// Core logic
+async fn mysql_replication_loop_inner(
+    task_info: &mut MySqlTaskInfo,
+) -> Result<(), SourceReaderError> {
+    if !task_info.snapshotted {
+        match task_info.produce_snapshot().await {
+            Ok(_) => {
+                info!(
+                    "replication snapshot for source {} succeeded",
+                    &task_info.source_id
+                );
+            }
+            Err(ReplicationError::Indefinite(e)) => {
+                // Like the postgres source, panic to discard the partially
+                // emitted snapshot. The restarted storaged instance will
+                // produce the snapshot afresh.
+                panic!(
+                    "replication snapshot for source {} failed: {}",
+                    &task_info.source_id, e
+                );
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Initialization(e.to_string()),
+                })
+            }
+        }
+    }
+
+    retry_replication(REPLICATION_PROGRESS_THRESHOLD, task_info).await
+}
+
+#[async_trait::async_trait]
+impl ReplicationTask for MySqlTaskInfo {
+    fn source_id(&self) -> GlobalId {
+        self.source_id
+    }
+
+    async fn replicate(&mut self) -> Result<(), ReplicationFailure> {
+        match self.produce_replication().await {
+            Ok(()) => Ok(()),
+            Err(ReplicationError::Indefinite(e)) => {
+                let class = error_class(&e);
+                Err(ReplicationFailure::Indefinite(e, class))
+            }
+            Err(ReplicationError::Definite(e)) => Err(ReplicationFailure::Definite(e)),
+        }
+    }
+
+    async fn send_status(&mut self, update: SourceStatusUpdate) {
+        // Drop the send error, as we have no way of communicating back to the
+        // source operator if the channel is gone.
+        let _ = self
+            .row_sender
+            .sender
+            .send(InternalMessage::Status(update))
+            .await;
+        self.row_sender
+            .activator
+            .activate()
+            .expect("mysql reader activation failed");
+    }
+}
+
+/// Classifies an indefinite error encountered during replication.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    match err.downcast_ref::<mysql_async::Error>() {
+        Some(mysql_async::Error::Server(e)) if AUTH_ERROR_CODES.contains(&e.code) => {
+            SourceErrorClass::Auth
+        }
+        _ => SourceErrorClass::Transient,
+    }
+}
+
+struct RowMessage {
+    output_index: usize,
+    row: Row,
+    partition: PartitionId,
+    offset: MzOffset,
+    diff: Diff,
+}
+
+/// A type that makes it easy to correctly send inserts and deletes.
+///
+/// Note: `RowSender::delete/insert` should be called with the same partition
+/// and offset until `close_offset` is called, which should be called and
+/// awaited before dropping the `RowSender` or moving onto a new offset.
+struct RowSender {
+    sender: Sender<InternalMessage>,
+    activator: SyncActivator,
+    buffered_message: Option<RowMessage>,
+}
+
+impl RowSender {
+    /// Create a new `RowSender`.
+    fn new(sender: Sender<InternalMessage>, activator: SyncActivator) -> Self {
+        Self {
+            sender,
+            activator,
+            buffered_message: None,
+        }
+    }
+
+    /// Insert a row at an offset of a partition.
+    async fn insert(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        partition: &PartitionId,
+        offset: MzOffset,
+    ) {
+        self.buffer(output_index, row, partition, offset, 1).await;
+    }
+
+    /// Delete a row at an offset of a partition.
+    async fn delete(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        partition: &PartitionId,
+        offset: MzOffset,
+    ) {
+        self.buffer(output_index, row, partition, offset, -1).await;
+    }
+
+    async fn buffer(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        partition: &PartitionId,
+        offset: MzOffset,
+        diff: Diff,
+    ) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(&buffered.partition, partition);
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, false).await;
+        }
+        self.buffered_message = Some(RowMessage {
+            output_index,
+            row,
+            partition: partition.clone(),
+            offset,
+            diff,
+        });
+    }
+
+    /// Finalize an offset of a partition, making sure all messages that my be
+    /// buffered are flushed, and that the last message sent is marked as
+    /// closing the offset.
+    async fn close_offset(&mut self, partition: &PartitionId, offset: MzOffset) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(&buffered.partition, partition);
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, true).await;
+        }
+    }
+
+    /// Records that the source will not emit messages below `upper` in
+    /// `partition`, whether or not it has emitted any messages in it.
+    async fn send_progress(&self, partition: PartitionId, upper: MzOffset) {
+        // a closed receiver means the source has been shutdown
+        // (dropped or the process is dying), so just continue on
+        // without activation
+        if let Ok(_) = self
+            .sender
+            .send(InternalMessage::Progress(partition, upper))
+            .await
+        {
+            self.activator
+                .activate()
+                .expect("mysql reader activation failed");
+        }
+    }
+
+    async fn send_row(&self, message: RowMessage, end: bool) {
+        // a closed receiver means the source has been shutdown
+        // (dropped or the process is dying), so just continue on
+        // without activation
+        if let Ok(_) = self
+            .sender
+            .send(InternalMessage::Value {
+                output: message.output_index,
+                value: message.row,
+                partition: message.partition,
+                offset: message.offset,
+                diff: message.diff,
+                end,
+            })
+            .await
+        {
+            self.activator
+                .activate()
+                .expect("mysql reader activation failed");
+        }
+    }
+}
+
+impl MySqlTaskInfo {
+    /// Connects to the server, with timestamps rendered in UTC.
+    async fn connect(&self) -> Result<Conn, ReplicationError> {
+        let mut conn = try_indefinite!(self.connection_config.connect().await);
+        conn.query_drop("SET time_zone = '+00:00'").await?;
+        Ok(conn)
+    }
+
+    /// Validates that all expected tables exist upstream and have the same schema
+    fn validate_tables(&self, tables: Vec<MySqlTableDesc>) -> Result<(), anyhow::Error> {
+        let upstream_tables: BTreeMap<_, _> = tables
+            .into_iter()
+            .map(|t| ((t.schema_name.clone(), t.name.clone()), t))
+            .collect();
+        for (name, info) in &self.source_tables {
+            match upstream_tables.get(name) {
+                Some(desc) if desc == &info.desc => {}
+                Some(desc) => {
+                    error!(
+                        "Error validating MySQL table. Expected: {:?} Actual: {:?}",
+                        &info.desc, desc
+                    );
+                    bail!(
+                        "Schema for table {}.{} differs, recreate Materialize source to use new schema",
+                        info.desc.schema_name,
+                        info.desc.name
+                    )
+                }
+                None => bail!(
+                    "MySQL server missing expected table {}.{}",
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Produces the initial snapshot of the tables and determines the
+    /// transaction at which replication starts.
+    async fn produce_snapshot(&mut self) -> Result<(), ReplicationError> {
+        let mut lock_conn = self.connect().await?;
+        let mut snapshot_conn = self.connect().await?;
+
+        let tables = try_indefinite!(mz_mysql_util::schema_info(&mut lock_conn).await);
+        try_definite!(self.validate_tables(tables));
+
+        // Holding read locks on the tables prevents any transaction that
+        // writes to them from committing, so the consistent snapshot that the
+        // second connection starts reflects exactly the transactions in the
+        // `gtid_executed` that it reads.
+        let lock_query = format!(
+            "LOCK TABLES {}",
+            self.source_tables
+                .keys()
+                .map(|(schema, table)| format!("{} READ", quote_identifier(schema, table)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        lock_conn.query_drop(lock_query).await?;
+        snapshot_conn
+            .query_drop("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .await?;
+        snapshot_conn
+            .query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+            .await?;
+        let gtid_executed = try_indefinite!(mz_mysql_util::gtid_executed(&mut snapshot_conn).await);
+        lock_conn.query_drop("UNLOCK TABLES").await?;
+        try_indefinite!(lock_conn.disconnect().await);
+
+        // The snapshot includes every transaction up to and including the
+        // last one of each server, so replication starts with the next one.
+        // The positions are recorded before the snapshot completes, so a
+        // source that restarts with a complete snapshot always has them.
+        for server in gtid_executed.servers() {
+            let last_gno = gtid_executed
+                .max_gno(server)
+                .expect("servers in a GTID set have transactions");
+            self.offsets.insert(*server, last_gno + 1);
+            let partition = PartitionId::MySqlServer(server.to_string());
+            self.row_sender
+                .send_progress(partition, MzOffset::from(last_gno + 1))
+                .await;
+        }
+        let partition = PartitionId::None;
+        let offset = MzOffset::from(0);
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        for info in self.source_tables.values() {
+            let query = format!(
+                "SELECT * FROM {}",
+                quote_identifier(&info.desc.schema_name, &info.desc.name)
+            );
+            let mut result = snapshot_conn.query_iter(query).await?;
+            let mut stream = try_indefinite!(result
+                .stream::<mysql_async::Row>()
+                .await
+                .transpose()
+                .ok_or_else(|| anyhow!("snapshot query returned no result set")))?;
+            while let Some(row) = stream.next().await {
+                let row = row?;
+                let mut texts = Vec::with_capacity(info.desc.columns.len());
+                for (column, value) in info.desc.columns.iter().zip(row.unwrap()) {
+                    texts.push(try_definite!(text_value(column, value)));
+                }
+                let mut datums = datum_vec.borrow();
+                datums.extend(texts.iter().map(|t| match t {
+                    Some(t) => Datum::String(t),
+                    None => Datum::Null,
+                }));
+                let row = try_definite!(cast_row(&info.casts, &datums));
+                self.row_sender
+                    .insert(info.output_index, row, &partition, offset)
+                    .await;
+            }
+        }
+        snapshot_conn.query_drop("COMMIT").await?;
+        try_indefinite!(snapshot_conn.disconnect().await);
+
+        // close the current `row_sender` context after we are sure we have not errored
+        // out (in the commit).
+        self.row_sender.close_offset(&partition, offset).await;
+        // Tables without any rows leave nothing to close.
+        self.row_sender.send_progress(partition, offset + 1).await;
+        self.snapshotted = true;
+        Ok(())
+    }
+
+    async fn produce_replication(&mut self) -> Result<(), ReplicationError> {
+        use ReplicationError::*;
+
+        let mut conn = self.connect().await?;
+
+        // Ask for every transaction that the source has yet to read. The
+        // server sends the transactions that are *not* in the requested set,
+        // so the set consists of everything the server has executed, with
+        // the transactions of each server truncated to those the source has
+        // read. The snapshot records the position of every server whose
+        // transactions it reflects, so the source reads all transactions of
+        // servers without one.
+        let mut gtid_set = try_indefinite!(mz_mysql_util::gtid_executed(&mut conn).await);
+        let servers: Vec<_> = gtid_set.servers().copied().collect();
+        for server in servers {
+            let upper = self.offsets.get(&server).copied().unwrap_or(1);
+            gtid_set.set_server_upper(server, upper);
+        }
+        let request = BinlogRequest::new(self.server_id)
+            .with_gtid()
+            .with_sids(gtid_set.to_sids());
+        let stream = conn.get_binlog_stream(request).await?;
+        let stream = stream.take_until(self.sender.closed());
+        tokio::pin!(stream);
+
+        // The transaction that is currently being read, if any, and the
+        // changes it has made to the ingested tables.
+        let mut gtid = None;
+        let mut inserts = vec![];
+        let mut deletes = vec![];
+        // The ingested tables by the IDs the binary log assigns them.
+        let mut tables_by_id: HashMap<u64, &SourceTable> = HashMap::new();
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            let data = match try_definite!(event.read_data()) {
+                Some(data) => data,
+                None => continue,
+            };
+            match data {
+                EventData::GtidEvent(event) => {
+                    let uuid = Uuid::from_bytes(event.sid());
+                    if !inserts.is_empty() || !deletes.is_empty() {
+                        return Err(Definite(anyhow!(
+                            "got transaction {uuid}:{} before the end of the previous one",
+                            event.gno()
+                        )));
+                    }
+                    gtid = Some((uuid, event.gno()));
+                }
+                EventData::TableMapEvent(event) => {
+                    let name = (
+                        event.database_name().into_owned(),
+                        event.table_name().into_owned(),
+                    );
+                    if let Some(info) = self.source_tables.get(&name) {
+                        let columns = usize::try_from(event.columns_count())
+                            .expect("column count fits into usize");
+                        if columns != info.desc.columns.len() {
+                            return Err(Definite(anyhow!(
+                                "source table {}.{} has been altered",
+                                info.desc.schema_name,
+                                info.desc.name
+                            )));
+                        }
+                        tables_by_id.insert(event.table_id(), info);
+                    } else {
+                        tables_by_id.remove(&event.table_id());
+                    }
+                }
+                EventData::RowsEvent(event) => {
+                    let info = match tables_by_id.get(&event.table_id()) {
+                        Some(info) => *info,
+                        None => continue,
+                    };
+                    let tme = stream
+                        .as_mut()
+                        .get_mut()
+                        .get_tme(event.table_id())
+                        .ok_or_else(|| {
+                            Definite(anyhow!("rows event for unknown table {}", event.table_id()))
+                        })?;
+                    let (before_image, after_image) =
+                        match &event {
+                            RowsEventData::WriteRowsEvent(_)
+                            | RowsEventData::WriteRowsEventV1(_) => (false, true),
+                            RowsEventData::UpdateRowsEvent(_)
+                            | RowsEventData::UpdateRowsEventV1(_) => (true, true),
+                            RowsEventData::DeleteRowsEvent(_)
+                            | RowsEventData::DeleteRowsEventV1(_) => (true, false),
+                            _ => {
+                                return Err(Definite(anyhow!(
+                                    "unsupported rows event for table {}.{}",
+                                    info.desc.schema_name,
+                                    info.desc.name
+                                )))
+                            }
+                        };
+                    for rows in event.rows(tme) {
+                        let (before, after) = try_definite!(rows);
+                        if before_image {
+                            let before = try_definite!(before.ok_or_else(|| anyhow!(
+                                "missing row image; is binlog_row_image set to FULL?"
+                            )));
+                            let row = try_definite!(binlog_row(info, before, &mut datum_vec));
+                            deletes.push((info.output_index, row));
+                        }
+                        if after_image {
+                            let after = try_definite!(after.ok_or_else(|| anyhow!(
+                                "missing row image; is binlog_row_image set to FULL?"
+                            )));
+                            let row = try_definite!(binlog_row(info, after, &mut datum_vec));
+                            inserts.push((info.output_index, row));
+                        }
+                    }
+                }
+                EventData::QueryEvent(event) => {
+                    let query = event.query();
+                    if query.eq_ignore_ascii_case("BEGIN") {
+                        continue;
+                    }
+                    // Transactions on tables that do not support transactions
+                    // end with a `COMMIT` query rather than an XID event.
+                    if query.eq_ignore_ascii_case("COMMIT") {
+                        self.commit(gtid.take(), &mut inserts, &mut deletes).await?;
+                        continue;
+                    }
+                    if let Some(info) = altered_table(&self.source_tables, &event.schema(), &query)
+                    {
+                        return Err(Definite(anyhow!(
+                            "source table {}.{} has been altered",
+                            info.desc.schema_name,
+                            info.desc.name
+                        )));
+                    }
+                }
+                EventData::XidEvent(_) => {
+                    self.commit(gtid.take(), &mut inserts, &mut deletes).await?;
+                }
+                _ => {}
+            }
+        }
+
+        if !stream.is_stopped() {
+            return Err(Indefinite(anyhow!("replication stream ended")));
+        }
+        Ok(())
+    }
+
+    /// Emits the changes of the transaction whose GTID, the UUID of the server
+    /// on which it originated and its transaction number, is `gtid`.
+    async fn commit(
+        &mut self,
+        gtid: Option<(Uuid, u64)>,
+        inserts: &mut Vec<(usize, Row)>,
+        deletes: &mut Vec<(usize, Row)>,
+    ) -> Result<(), ReplicationError> {
+        let (server, gno) = match gtid {
+            Some(gtid) => gtid,
+            None if inserts.is_empty() && deletes.is_empty() => return Ok(()),
+            None => {
+                return Err(ReplicationError::Definite(anyhow!(
+                    "got changes outside of a transaction"
+                )))
+            }
+        };
+        let partition = PartitionId::MySqlServer(server.to_string());
+        let offset = MzOffset::from(gno);
+        for (output, row) in deletes.drain(..) {
+            self.row_sender
+                .delete(output, row, &partition, offset)
+                .await;
+        }
+        for (output, row) in inserts.drain(..) {
+            self.row_sender
+                .insert(output, row, &partition, offset)
+                .await;
+        }
+        self.row_sender.close_offset(&partition, offset).await;
+        self.offsets.insert(server, gno + 1);
+        Ok(())
+    }
+}
+
+/// Returns the ingested table that the DDL statement `query`, executed with
+/// `schema` as the default schema, changes, if any.
+///
+/// This recognizes the statements that change a table in ways that the source
+/// cannot follow by the name of the table alone, which errs on the side of
+/// reporting statements that mention a table of the same name in another
+/// schema.
+fn altered_table<'a>(
+    source_tables: &'a BTreeMap<(String, String), SourceTable>,
+    schema: &str,
+    query: &str,
+) -> Option<&'a SourceTable> {
+    let query = query.trim_start().to_lowercase();
+    if !["alter ", "drop ", "rename ", "truncate "]
+        .iter()
+        .any(|prefix| query.starts_with(prefix))
+    {
+        return None;
+    }
+    let words: Vec<_> = query
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';' || c == '(')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.replace('`', ""))
+        .collect();
+    source_tables.values().find(|info| {
+        let name = info.desc.name.to_lowercase();
+        let qualified = format!("{}.{}", info.desc.schema_name.to_lowercase(), name);
+        words.iter().any(|w| {
+            *w == qualified || (*w == name && schema.eq_ignore_ascii_case(&info.desc.schema_name))
+        })
+    })
+}
+
+/// Quotes the name of a table for use in a query.
+fn quote_identifier(schema: &str, table: &str) -> String {
+    format!(
+        "`{}`.`{}`",
+        schema.replace('`', "``"),
+        table.replace('`', "``")
+    )
+}
+
+/// Casts a text row into the target types
+fn cast_row(table_cast: &[MirScalarExpr], datums: &[Datum<'_>]) -> Result<Row, anyhow::Error> {
+    let arena = mz_repr::RowArena::new();
+    let mut row = Row::default();
+    let mut packer = row.packer();
+    for column_cast in table_cast {
+        let datum = column_cast.eval(datums, &arena)?;
+        packer.push(datum);
+    }
+    Ok(row)
+}
+
+/// Converts a row from the binary log into the target types.
+fn binlog_row(
+    info: &SourceTable,
+    row: BinlogRow,
+    datum_vec: &mut DatumVec,
+) -> Result<Row, anyhow::Error> {
+    let mut texts = Vec::with_capacity(info.desc.columns.len());
+    for (column, value) in info.desc.columns.iter().zip(row.unwrap()) {
+        texts.push(binlog_value(column, value)?);
+    }
+    let mut datums = datum_vec.borrow();
+    datums.extend(texts.iter().map(|t| match t {
+        Some(t) => Datum::String(t),
+        None => Datum::Null,
+    }));
+    cast_row(&info.casts, &datums)
+}
+
+/// Renders a value that a text protocol query returned as the text that the
+/// column's cast expects, or `None` if the value is `NULL`.
+fn text_value(column: &MySqlColumnDesc, value: Value) -> Result<Option<String>, anyhow::Error> {
+    let bytes = match value {
+        Value::NULL => return Ok(None),
+        Value::Bytes(bytes) => bytes,
+        // Queries over the text protocol only return strings.
+        value => bail!("unexpected value {value:?} in column {}", column.name),
+    };
+    if is_binary(column) {
+        return Ok(Some(format!("\\x{}", hex::encode(bytes))));
+    }
+    let text = String::from_utf8(bytes)?;
+    match column.data_type.as_str() {
+        "date" | "datetime" | "timestamp" => {
+            if text.starts_with("0000-00-00") || text.contains("-00") {
+                bail!("column {} contains invalid date {text}", column.name);
+            }
+            if column.data_type == "timestamp" {
+                // The session time zone is UTC.
+                Ok(Some(format!("{text}+00")))
+            } else {
+                Ok(Some(text))
+            }
+        }
+        _ => Ok(Some(text)),
+    }
+}
+
+/// Renders a value from the binary log as the text that the column's cast
+/// expects, or `None` if the value is `NULL`.
+fn binlog_value(
+    column: &MySqlColumnDesc,
+    value: BinlogValue,
+) -> Result<Option<String>, anyhow::Error> {
+    let value = match value {
+        BinlogValue::Value(value) => value,
+        BinlogValue::Jsonb(value) => {
+            let value = serde_json::Value::try_from(value)
+                .map_err(|e| anyhow!("invalid JSON in column {}: {e:?}", column.name))?;
+            return Ok(Some(value.to_string()));
+        }
+        BinlogValue::JsonDiff(_) => bail!(
+            "partial JSON update to column {}; set binlog_row_value_options to ''",
+            column.name
+        ),
+    };
+    let text = match value {
+        Value::NULL => return Ok(None),
+        Value::Bytes(bytes) if is_binary(column) => format!("\\x{}", hex::encode(bytes)),
+        Value::Bytes(bytes) if column.data_type == "timestamp" => {
+            // The binary log records timestamps as seconds since the Unix
+            // epoch, with an optional fractional part.
+            let text = String::from_utf8(bytes)?;
+            let (secs, frac) = text.split_once('.').unwrap_or((&text, "0"));
+            unix_timestamp(column, secs.parse()?, format!("{frac:0<6}")[..6].parse()?)?
+        }
+        Value::Bytes(bytes) => String::from_utf8(bytes)?,
+        Value::Int(i) => integer_value(column, i as u64, i.to_string())?,
+        Value::UInt(u) => integer_value(column, u, u.to_string())?,
+        Value::Float(f) => f.to_string(),
+        Value::Double(d) => d.to_string(),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            if year == 0 || month == 0 || day == 0 {
+                bail!("column {} contains invalid date", column.name);
+            }
+            let date = format!("{year:04}-{month:02}-{day:02}");
+            match column.data_type.as_str() {
+                "date" => date,
+                "datetime" => format!("{date} {hour:02}:{minute:02}:{second:02}.{micros:06}"),
+                _ => format!("{date} {hour:02}:{minute:02}:{second:02}.{micros:06}+00"),
+            }
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            let sign = if negative { "-" } else { "" };
+            let hours = u64::from(days) * 24 + u64::from(hours);
+            format!("{sign}{hours}:{minutes:02}:{seconds:02}.{micros:06}")
+        }
+    };
+    Ok(Some(text))
+}
+
+/// Renders an integer from the binary log, which records enums, sets,
+/// timestamps and unsigned integers as integers too.
+fn integer_value(
+    column: &MySqlColumnDesc,
+    bits: u64,
+    signed: String,
+) -> Result<String, anyhow::Error> {
+    match column.data_type.as_str() {
+        "enum" => {
+            let values = column.enum_values().unwrap_or_default();
+            // The binary log records the 1-based index of the value, and 0
+            // for the empty string that MySQL stores for invalid values.
+            match usize::try_from(bits).ok().and_then(|i| i.checked_sub(1)) {
+                None => Ok(String::new()),
+                Some(i) => values
+                    .get(i)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("invalid enum index {bits} in column {}", column.name)),
+            }
+        }
+        "set" => {
+            // The binary log records the set as a bitmask of its values.
+            let values = column.enum_values().unwrap_or_default();
+            Ok(values
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i < 64 && bits & (1 << i) != 0)
+                .map(|(_, v)| v.as_str())
+                .collect::<Vec<_>>()
+                .join(","))
+        }
+        "timestamp" => unix_timestamp(column, i64::try_from(bits)?, 0),
+        "tinyint" | "smallint" | "mediumint" | "int" | "bigint" if column.is_unsigned() => {
+            // The binary log does not record the signedness of columns, so
+            // unsigned values above the signed range appear negative.
+            let width = match column.data_type.as_str() {
+                "tinyint" => 8,
+                "smallint" => 16,
+                "mediumint" => 24,
+                "int" => 32,
+                _ => 64,
+            };
+            let mask = if width == 64 {
+                u64::MAX
+            } else {
+                (1 << width) - 1
+            };
+            Ok((bits & mask).to_string())
+        }
+        _ => Ok(signed),
+    }
+}
+
+/// Renders a number of seconds since the Unix epoch as a timestamp in UTC.
+fn unix_timestamp(
+    column: &MySqlColumnDesc,
+    secs: i64,
+    micros: u32,
+) -> Result<String, anyhow::Error> {
+    if secs == 0 && micros == 0 {
+        // MySQL records the zero timestamp as zero.
+        bail!("column {} contains invalid date", column.name);
+    }
+    let ts = chrono::NaiveDateTime::from_timestamp_opt(secs, micros * 1000)
+        .ok_or_else(|| anyhow!("column {} contains invalid timestamp", column.name))?;
+    Ok(format!("{}+00", ts.format("%Y-%m-%d %H:%M:%S%.6f")))
+}
+
+/// Reports whether the column holds binary strings.
+fn is_binary(column: &MySqlColumnDesc) -> bool {
+    matches!(
+        column.data_type.as_str(),
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob"
+    )
+}
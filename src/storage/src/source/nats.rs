@@ -0,0 +1,514 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functionality for creating NATS JetStream sources.
+//!
+//! A single worker runs a task that reads the stream through a durable pull
+//! consumer, which JetStream keeps for the source across restarts. Each
+//! subject of the stream is a partition of the source, and each message is
+//! emitted at its sequence number in the stream, which increases within every
+//! subject. Messages are acknowledged once the offsets they were emitted at are
+//! durably recorded, so that messages that were not yet ingested when the
+//! source restarts are redelivered to it.
+//!
+//! Redelivered messages, and messages that the consumer delivers again after
+//! it is recreated, are recognized by their sequence numbers and are not
+//! emitted again, so NATS sources ingest each message exactly once. The
+//! subject of each message is used as its key, so that `ENVELOPE UPSERT` keeps
+//! the latest message of each subject.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context};
+use async_nats::jetstream;
+use async_nats::jetstream::consumer::{pull, AckPolicy, DeliverPolicy};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
+use tracing::{info, warn};
+
+use mz_expr::PartitionId;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{SourceErrorClass, SourceRetry};
+use crate::source::types::OffsetCommitter;
+use crate::source::{SourceMessage, SourceMessageType, SourceReader, SourceReaderError};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::{MzOffset, NatsSourceConnection};
+
+/// How long JetStream waits for a message to be acknowledged before
+/// redelivering it.
+const ACK_WAIT: Duration = Duration::from_secs(120);
+
+/// The maximum number of messages JetStream delivers before waiting for some of
+/// them to be acknowledged.
+const MAX_ACK_PENDING: i64 = 10_000;
+
+/// The payload that acknowledges a message when published to its reply
+/// subject.
+const ACK_PAYLOAD: &[u8] = b"+ACK";
+
+/// A message read from the stream.
+struct NatsMessage {
+    subject: String,
+    /// The sequence number of the message in the stream.
+    stream_sequence: u64,
+    upstream_time_millis: Option<i64>,
+    payload: Vec<u8>,
+    /// The subject that acknowledges the message when published to.
+    reply: String,
+}
+
+enum InternalMessage {
+    Message(NatsMessage),
+    Status(SourceStatusUpdate),
+}
+
+/// Information required to read messages from a NATS JetStream stream
+pub struct NatsSourceReader {
+    /// Receiver channel that ingests messages
+    receiver_stream: Receiver<Result<InternalMessage, anyhow::Error>>,
+    /// The offset of the next message to emit, by subject
+    next_offsets: HashMap<String, MzOffset>,
+    /// The messages that were read but not yet acknowledged
+    pending: Arc<Mutex<PendingAcks>>,
+    // NATS sources support single-threaded ingestion only, so only one of
+    // the `NatsSourceReader`s will actually produce data.
+    active_read_worker: bool,
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+/// Acknowledges the messages whose offsets were durably recorded.
+pub struct NatsOffsetCommitter {
+    pending: Arc<Mutex<PendingAcks>>,
+    /// Sends the reply subjects of the messages to acknowledge to the reading
+    /// task
+    acks_tx: UnboundedSender<String>,
+    logger: LogCommitter,
+}
+
+#[async_trait]
+impl OffsetCommitter for NatsOffsetCommitter {
+    async fn commit_offsets(
+        &self,
+        offsets: HashMap<PartitionId, MzOffset>,
+    ) -> Result<(), anyhow::Error> {
+        let reply = self.pending.lock().expect("lock poisoned").commit(&offsets);
+        if let Some(reply) = reply {
+            // The reading task stops once the source is dropped, at which
+            // point there is nothing left to acknowledge.
+            let _ = self.acks_tx.send(reply);
+        }
+        self.logger.commit_offsets(offsets).await
+    }
+}
+
+#[async_trait(?Send)]
+impl SourceReader for NatsSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Diff = ();
+    type OffsetCommitter = NatsOffsetCommitter;
+    type Connection = NatsSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        let next_offsets: HashMap<_, _> = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match (pid, offset) {
+                (PartitionId::Nats(subject), Some(offset)) => Some((subject, offset)),
+                _ => None,
+            })
+            .collect();
+
+        // a single arbitrary worker is responsible for reading the stream
+        let (acks_tx, acks_rx) = mpsc::unbounded_channel();
+        let receiver = if active_read_worker {
+            let (dataflow_tx, dataflow_rx) = mpsc::channel(10_000);
+            info!(
+                "source_id={} reading nats stream={} worker={}",
+                source_id, connection.stream, worker_id
+            );
+            task::spawn(
+                || format!("nats_read:{}", source_id),
+                read_task(
+                    source_id,
+                    connection,
+                    Arc::clone(&connection_context.secrets_reader),
+                    dataflow_tx,
+                    acks_rx,
+                ),
+            );
+            dataflow_rx
+        } else {
+            let (_dataflow_tx, dataflow_rx) = mpsc::channel(1);
+            dataflow_rx
+        };
+
+        let pending = Arc::new(Mutex::new(PendingAcks::default()));
+        Ok((
+            NatsSourceReader {
+                receiver_stream: receiver,
+                next_offsets,
+                pending: Arc::clone(&pending),
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            NatsOffsetCommitter {
+                pending,
+                acks_tx,
+                logger: LogCommitter {
+                    source_id,
+                    worker_id,
+                    worker_count,
+                },
+            },
+        ))
+    }
+
+    async fn next(
+        &mut self,
+        _timestamp_granularity: Duration,
+    ) -> Option<Result<SourceMessageType<Self::Key, Self::Value, Self::Diff>, SourceReaderError>>
+    {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(Ok(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ])));
+            }
+            return None;
+        }
+
+        loop {
+            match self.receiver_stream.recv().await? {
+                Ok(InternalMessage::Message(message)) => {
+                    let offset = MzOffset::from(message.stream_sequence);
+                    self.pending.lock().expect("lock poisoned").push(
+                        message.subject.clone(),
+                        offset,
+                        message.reply,
+                    );
+                    let next_offset = self
+                        .next_offsets
+                        .entry(message.subject.clone())
+                        .or_default();
+                    if offset < *next_offset {
+                        // The message was already emitted, and is acknowledged
+                        // together with the messages that follow it.
+                        continue;
+                    }
+                    *next_offset = offset + 1;
+                    return Some(Ok(SourceMessageType::Finalized(SourceMessage {
+                        output: 0,
+                        key: Some(message.subject.clone().into_bytes()),
+                        partition: PartitionId::Nats(message.subject),
+                        offset,
+                        upstream_time_millis: message.upstream_time_millis,
+                        value: Some(message.payload),
+                        headers: None,
+                        specific_diff: (),
+                    })));
+                }
+                Ok(InternalMessage::Status(update)) => {
+                    return Some(Ok(SourceMessageType::SourceStatus(update)))
+                }
+                Err(e) => {
+                    return Some(Err(SourceReaderError {
+                        inner: SourceErrorDetails::Other(format!("{:#}", e)),
+                    }))
+                }
+            }
+        }
+    }
+}
+
+/// The messages that were read but not yet acknowledged, in the order they
+/// were delivered.
+///
+/// The consumer acknowledges all messages delivered before an acknowledged
+/// message, so only the last message of each run of durably ingested messages
+/// needs to be acknowledged.
+#[derive(Debug, Default)]
+struct PendingAcks {
+    /// The subject, offset, and reply subject of each message.
+    messages: VecDeque<(String, MzOffset, String)>,
+}
+
+impl PendingAcks {
+    fn push(&mut self, subject: String, offset: MzOffset, reply: String) {
+        self.messages.push_back((subject, offset, reply));
+    }
+
+    /// Removes the messages that were delivered before the first message that
+    /// is not durably ingested according to `offsets`, returning the reply
+    /// subject that acknowledges all of them, if any.
+    fn commit(&mut self, offsets: &HashMap<PartitionId, MzOffset>) -> Option<String> {
+        let mut reply = None;
+        while let Some((subject, offset, _)) = self.messages.front() {
+            match offsets.get(&PartitionId::Nats(subject.clone())) {
+                Some(committed) if offset <= committed => {}
+                _ => break,
+            }
+            let (_, _, next_reply) = self.messages.pop_front().expect("known to exist");
+            reply = Some(next_reply);
+        }
+        reply
+    }
+}
+
+/// Reads messages from the stream, retrying with a backoff whenever reading
+/// fails, until the source is dropped.
+async fn read_task(
+    source_id: GlobalId,
+    connection: NatsSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    mut acks_rx: UnboundedReceiver<String>,
+) {
+    let consumer_name = connection
+        .consumer
+        .clone()
+        .unwrap_or_else(|| format!("materialize-{}", source_id));
+    let mut retry = SourceRetry::default();
+    loop {
+        let result = read(
+            &connection,
+            &consumer_name,
+            &*secrets_reader,
+            &tx,
+            &mut acks_rx,
+            &mut retry,
+        )
+        .await;
+        let e = match result {
+            // The source was dropped.
+            Ok(()) => break,
+            Err(e) => e,
+        };
+
+        let class = error_class(&e);
+        let backoff = match retry.on_error(class) {
+            Some(backoff) => backoff,
+            None => {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+        };
+        warn!(
+            "reading source {} interrupted by {} error, retrying in {:?}: {:#}",
+            source_id,
+            class.name(),
+            backoff,
+            e
+        );
+        let next_retry_at = (SystemTime::now() + backoff)
+            .duration_since(UNIX_EPOCH)
+            .expect("system time after Unix epoch")
+            .as_millis();
+        let update = SourceStatusUpdate::retrying(
+            &format!("{:#}", e),
+            class,
+            u64::try_from(next_retry_at).expect("retry time fits into u64"),
+        );
+        if tx.send(Ok(InternalMessage::Status(update))).await.is_err() {
+            return;
+        }
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = tokio::time::sleep(backoff) => {}
+        }
+    }
+    info!("source_id={} stopped reading nats stream", source_id);
+}
+
+/// Reads messages from the stream through the durable consumer
+/// `consumer_name`, creating it if it does not exist, and forwards them to
+/// `tx` until the source is dropped.
+async fn read(
+    connection: &NatsSourceConnection,
+    consumer_name: &str,
+    secrets_reader: &dyn SecretsReader,
+    tx: &Sender<Result<InternalMessage, anyhow::Error>>,
+    acks_rx: &mut UnboundedReceiver<String>,
+    retry: &mut SourceRetry,
+) -> Result<(), anyhow::Error> {
+    // Credentials are read anew for every connection, so that rotated secrets
+    // take effect the next time we connect.
+    let client = connect(connection, secrets_reader)
+        .await
+        .context("error connecting to nats")?;
+    let context = jetstream::new(client.clone());
+    let stream = context
+        .get_stream(&connection.stream)
+        .await
+        .map_err(|e| anyhow!("error looking up stream {}: {}", connection.stream, e))?;
+    // A consumer that has to be created, because it is new or was deleted,
+    // starts at the beginning of the stream, since the messages that were
+    // not yet ingested can be interleaved with ingested ones of other
+    // subjects. The messages that were already ingested are skipped.
+    let consumer = stream
+        .get_or_create_consumer(
+            consumer_name,
+            pull::Config {
+                durable_name: Some(consumer_name.into()),
+                deliver_policy: DeliverPolicy::All,
+                ack_policy: AckPolicy::All,
+                ack_wait: ACK_WAIT,
+                max_ack_pending: MAX_ACK_PENDING,
+                filter_subject: connection.subject.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| anyhow!("error creating consumer {}: {}", consumer_name, e))?;
+    if consumer.cached_info().config.ack_policy != AckPolicy::All {
+        bail!(
+            "consumer {} must use the \"all\" acknowledgement policy",
+            consumer_name
+        );
+    }
+    let mut messages = consumer
+        .messages()
+        .await
+        .map_err(|e| anyhow!("error reading from consumer {}: {}", consumer_name, e))?;
+
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let message = match message {
+                    Some(message) => message
+                        .map_err(|e| anyhow!("error reading from consumer {}: {}", consumer_name, e))?,
+                    None => bail!("consumer {} stopped delivering messages", consumer_name),
+                };
+                retry.reset();
+                let info = message
+                    .info()
+                    .map_err(|e| anyhow!("invalid message metadata: {}", e))?;
+                let stream_sequence = info.stream_sequence;
+                let upstream_time_millis =
+                    i64::try_from(info.published.unix_timestamp_nanos() / 1_000_000).ok();
+                let message = async_nats::Message::from(message);
+                let reply = match message.reply {
+                    Some(reply) => reply,
+                    None => bail!("JetStream delivered a message without a reply subject"),
+                };
+                let message = NatsMessage {
+                    subject: message.subject,
+                    stream_sequence,
+                    upstream_time_millis,
+                    payload: message.payload.to_vec(),
+                    reply,
+                };
+                if tx.send(Ok(InternalMessage::Message(message))).await.is_err() {
+                    return Ok(());
+                }
+            }
+            Some(reply) = acks_rx.recv() => {
+                client
+                    .publish(reply, Bytes::from_static(ACK_PAYLOAD))
+                    .await
+                    .map_err(|e| anyhow!("error acknowledging messages: {}", e))?;
+                client
+                    .flush()
+                    .await
+                    .map_err(|e| anyhow!("error acknowledging messages: {}", e))?;
+            }
+            _ = tx.closed() => return Ok(()),
+        }
+    }
+}
+
+/// Connects to the server targeted by `connection`, reading its credentials
+/// from `secrets_reader`.
+async fn connect(
+    connection: &NatsSourceConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<async_nats::Client, anyhow::Error> {
+    let mut options = async_nats::ConnectOptions::new();
+    if let (Some(user), Some(password)) = (&connection.user, connection.password) {
+        options = options.user_and_password(
+            user.get_string(secrets_reader).await?,
+            secrets_reader.read_string(password).await?,
+        );
+    }
+    if let Some(token) = connection.token {
+        options = options.token(secrets_reader.read_string(token).await?);
+    }
+    Ok(options.connect(connection.url.as_str()).await?)
+}
+
+/// Classifies an error encountered while reading the stream.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    if format!("{:#}", err)
+        .to_lowercase()
+        .contains("authorization violation")
+    {
+        SourceErrorClass::Auth
+    } else {
+        SourceErrorClass::Transient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_acks() {
+        let mut pending = PendingAcks::default();
+        pending.push("a".into(), MzOffset::from(1), "r1".into());
+        pending.push("b".into(), MzOffset::from(2), "r2".into());
+        pending.push("a".into(), MzOffset::from(3), "r3".into());
+
+        let offsets = |a: u64, b: u64| {
+            HashMap::from([
+                (PartitionId::Nats("a".into()), MzOffset::from(a)),
+                (PartitionId::Nats("b".into()), MzOffset::from(b)),
+            ])
+        };
+
+        // Nothing is acknowledged before the first message is durable.
+        assert_eq!(pending.commit(&offsets(0, 2)), None);
+
+        // Only the last message of the durable prefix is acknowledged, which
+        // acknowledges the messages before it.
+        assert_eq!(pending.commit(&offsets(3, 1)), Some("r1".into()));
+        assert_eq!(pending.commit(&offsets(3, 2)), Some("r3".into()));
+        assert!(pending.messages.is_empty());
+    }
+}
@@ -31,7 +31,7 @@ use mz_ore::cast::CastFrom;
 use mz_persist::location::ExternalError;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::fetch::{LeasedBatchPart, SerdeLeasedBatchPart};
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
 use crate::controller::CollectionMetadata;
@@ -215,6 +215,7 @@ where
     let persist_clients_stream = Arc::<Mutex<PersistClientCache>>::clone(&persist_clients);
     let persist_location_stream = metadata.persist_location.clone();
     let data_shard = metadata.data_shard.clone();
+    let relation_arity = metadata.relation_arity;
     let as_of_stream = as_of;
 
     // Connects the consumed part collector operator with the part-issuing
@@ -423,6 +424,7 @@ where
                             if !until.less_equal(&time) {
                                 match (key, val) {
                                     (Ok(SourceData(Ok(row))), Ok(())) => {
+                                        let row = pad_row(row, relation_arity);
                                         if let Some(mfp) = &mut map_filter_project {
                                             let arena = mz_repr::RowArena::new();
                                             let mut datums_local = datum_vec.borrow_with(&row);
@@ -559,3 +561,23 @@ async fn force_yield() {
     })
     .await;
 }
+
+/// Pads `row` with trailing `NULL`s up to `arity`.
+///
+/// Rows written before the relation of a source was extended with new
+/// nullable columns (see `ALTER SOURCE ... REFRESH SCHEMA`) are shorter than
+/// the relation's current arity.
+fn pad_row(row: Row, arity: Option<usize>) -> Row {
+    let missing = match arity {
+        Some(arity) => arity.saturating_sub(row.iter().count()),
+        None => 0,
+    };
+    if missing == 0 {
+        return row;
+    }
+    let mut padded = Row::default();
+    let mut packer = padded.packer();
+    packer.extend_by_row(&row);
+    packer.extend(std::iter::repeat(Datum::Null).take(missing));
+    padded
+}
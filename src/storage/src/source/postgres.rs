@@ -7,12 +7,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, bail};
-use futures::{FutureExt, StreamExt};
+use bytes::Bytes;
+use futures::{FutureExt, StreamExt, TryStreamExt};
 use once_cell::sync::Lazy;
 use postgres_protocol::message::backend::{
     LogicalReplicationMessage, ReplicationMessage, TupleData,
@@ -22,8 +23,8 @@ use tokio::runtime::Handle as TokioHandle;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio_postgres::error::DbError;
 use tokio_postgres::replication::LogicalReplicationStream;
-use tokio_postgres::types::PgLsn;
-use tokio_postgres::SimpleQueryMessage;
+use tokio_postgres::types::{PgLsn, ToSql, Type};
+use tokio_postgres::{Client, SimpleQueryMessage};
 use tracing::{error, info, warn};
 
 use mz_expr::{MirScalarExpr, PartitionId};
@@ -34,6 +35,11 @@ use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
 use self::metrics::PgSourceMetrics;
 use super::metrics::SourceBaseMetrics;
 use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{
+    retry_replication, ReplicationFailure, ReplicationTask, SourceErrorClass,
+    REPLICATION_PROGRESS_THRESHOLD,
+};
 use crate::source::{
     types::OffsetCommitter, NextMessage, SourceMessage, SourceMessageType, SourceReader,
     SourceReaderError,
@@ -56,6 +62,12 @@ static WAL_LAG_GRACE_PERIOD: Duration = Duration::from_secs(5 * 60); // 5 minute
 /// The maximum amount of WAL lag allowed before restarting the replication process
 static MAX_WAL_LAG: u64 = 100 * 1024 * 1024;
 
+/// The maximum number of rows read from an upstream table per chunk of the initial snapshot
+static SNAPSHOT_CHUNK_ROWS: i64 = 100_000;
+
+/// The offset of the last chunk of the initial snapshot of a table, which completes it
+static SNAPSHOT_COMPLETE: u64 = u64::MAX - 1;
+
 trait ErrorExt {
     fn is_definite(&self) -> bool;
 }
@@ -138,10 +150,12 @@ macro_rules! try_indefinite {
 // Message used to communicate between `get_next_message` and the tokio task
 enum InternalMessage {
     Err(SourceReaderError),
+    Status(SourceStatusUpdate),
     Value {
         output: usize,
         value: Row,
-        lsn: PgLsn,
+        partition: PartitionId,
+        offset: MzOffset,
         diff: Diff,
         end: bool,
     },
@@ -176,9 +190,23 @@ struct SourceTable {
     output_index: usize,
     /// The relational description of this table
     desc: PostgresTableDesc,
+    /// The number of columns the upstream table was last known to have, which
+    /// exceeds the number of columns in `desc` if columns have been added
+    /// upstream since the source was created
+    upstream_columns: usize,
     /// The scalar expressions required to cast the text encoded columns received from postgres
     /// into the target relational types
     casts: Vec<MirScalarExpr>,
+    /// The index of the column the initial snapshot of this table is paginated by, if any
+    snapshot_key: Option<usize>,
+}
+
+impl SourceTable {
+    /// Returns the values of the columns of this table in `tuple`, dropping those of any columns
+    /// added upstream since the source was created.
+    fn known_columns<'a>(&self, tuple: &'a [TupleData]) -> &'a [TupleData] {
+        &tuple[..self.desc.columns.len().min(tuple.len())]
+    }
 }
 
 /// An internal struct held by the spawned tokio task
@@ -192,6 +220,9 @@ struct PostgresTaskInfo {
     metrics: PgSourceMetrics,
     /// A map of the table oid to its information
     source_tables: HashMap<u32, SourceTable>,
+    /// A map of the table oid to the upper of the chunks of its initial snapshot that have
+    /// already been ingested
+    snapshot_uppers: HashMap<u32, MzOffset>,
     row_sender: RowSender,
     sender: Sender<InternalMessage>,
     /// Channel to receive lsn's from the PgOffsetCommitter
@@ -228,19 +259,20 @@ impl SourceReader for PostgresSourceReader {
 
         let (offset_tx, offset_rx) = tokio::sync::mpsc::channel(10);
 
-        // Pick out the partition we care about
+        // Pick out the partitions we care about
         // TODO(petrosagg): add an associated type to SourceReader so that each source can define
         // its own gauge type
-        let start_offset = start_offsets
-            .into_iter()
-            .find_map(|(pid, offset)| {
-                if pid == PartitionId::None {
-                    offset
-                } else {
-                    None
+        let mut start_offset = MzOffset::default();
+        let mut snapshot_uppers = HashMap::new();
+        for (pid, offset) in start_offsets {
+            match (pid, offset) {
+                (PartitionId::None, Some(offset)) => start_offset = offset,
+                (PartitionId::PostgresSnapshot(oid), Some(offset)) => {
+                    snapshot_uppers.insert(oid, offset);
                 }
-            })
-            .unwrap_or_default();
+                _ => {}
+            }
+        }
 
         let connection_config = TokioHandle::current()
             .block_on(
@@ -257,7 +289,9 @@ impl SourceReader for PostgresSourceReader {
                 let source_table = SourceTable {
                     output_index: i + 1,
                     desc: desc.clone(),
+                    upstream_columns: desc.columns.len(),
                     casts,
+                    snapshot_key: None,
                 };
                 source_tables.insert(desc.oid, source_table);
             }
@@ -271,6 +305,7 @@ impl SourceReader for PostgresSourceReader {
                 lsn: start_offset.offset.into(),
                 metrics: PgSourceMetrics::new(&metrics, source_id),
                 source_tables,
+                snapshot_uppers,
                 row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
                 sender: dataflow_tx,
                 offset_rx,
@@ -318,16 +353,17 @@ impl SourceReader for PostgresSourceReader {
             Some(Some(InternalMessage::Value {
                 output,
                 value,
+                partition,
+                offset,
                 diff,
-                lsn,
                 end,
             })) => {
                 if end {
                     Ok(NextMessage::Ready(SourceMessageType::Finalized(
                         SourceMessage {
                             output,
-                            partition: PartitionId::None,
-                            offset: lsn.into(),
+                            partition,
+                            offset,
                             upstream_time_millis: None,
                             key: (),
                             value,
@@ -339,8 +375,8 @@ impl SourceReader for PostgresSourceReader {
                     Ok(NextMessage::Ready(SourceMessageType::InProgress(
                         SourceMessage {
                             output,
-                            partition: PartitionId::None,
-                            offset: lsn.into(),
+                            partition,
+                            offset,
                             upstream_time_millis: None,
                             key: (),
                             value,
@@ -351,6 +387,9 @@ impl SourceReader for PostgresSourceReader {
                 }
             }
             Some(Some(InternalMessage::Err(e))) => Err(e),
+            Some(Some(InternalMessage::Status(update))) => {
+                Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
+            }
             None => Ok(NextMessage::Pending),
             Some(None) => Ok(NextMessage::Finished),
         };
@@ -411,10 +450,9 @@ async fn postgres_replication_loop_inner(
                 // TODO: In the future we probably want to handle this more gracefully,
                 // to avoid stressing out any monitoring tools,
                 // but for now panicking is the easiest way to dump the data in the pipe.
-                // The restarted storaged instance will restart the snapshot fresh, which will
-                // avoid any inconsistencies. Note that if the same lsn is chosen in the
-                // next snapshotting, the remapped timestamp chosen will be the same for
-                // both instances of storaged.
+                // The restarted storaged instance will resume the snapshot after the last
+                // chunk that was ingested. Chunks that were only partially emitted are read
+                // again from scratch, which avoids any inconsistencies.
                 panic!(
                     "replication snapshot for source {} failed: {}",
                     &task_info.source_id, e
@@ -428,35 +466,121 @@ async fn postgres_replication_loop_inner(
         }
     }
 
-    loop {
-        match task_info.produce_replication().await {
+    retry_replication(REPLICATION_PROGRESS_THRESHOLD, task_info).await
+}
+
+#[async_trait::async_trait]
+impl ReplicationTask for PostgresTaskInfo {
+    fn source_id(&self) -> GlobalId {
+        self.source_id
+    }
+
+    async fn replicate(&mut self) -> Result<(), ReplicationFailure> {
+        match self.produce_replication().await {
+            Ok(()) => Ok(()),
             Err(ReplicationError::Indefinite(e)) => {
-                warn!(
-                    "replication for source {} interrupted, retrying: {}",
-                    task_info.source_id, e
-                )
-            }
-            Err(ReplicationError::Definite(e)) => {
-                return Err(SourceReaderError {
-                    inner: SourceErrorDetails::Other(e.to_string()),
-                })
+                let class = error_class(&e);
+                Err(ReplicationFailure::Indefinite(e, class))
             }
-            Ok(_) => {
-                // shutdown initiated elsewhere
-                return Ok(());
+            // Definite errors are never retried. Upstream schema changes are
+            // passed on with their class regardless, so that they are reported
+            // as such, which is never retried either.
+            Err(ReplicationError::Definite(e)) if error_class(&e) == SourceErrorClass::Schema => {
+                Err(ReplicationFailure::Indefinite(e, SourceErrorClass::Schema))
             }
+            Err(ReplicationError::Definite(e)) => Err(ReplicationFailure::Definite(e)),
         }
+    }
 
-        // TODO(petrosagg): implement exponential back-off
-        tokio::time::sleep(Duration::from_secs(3)).await;
-        info!("resuming replication for source {}", task_info.source_id);
+    async fn send_status(&mut self, update: SourceStatusUpdate) {
+        self.row_sender.send_status(update).await;
+    }
+}
+
+/// Runs a `CREATE_REPLICATION_SLOT` command and returns the consistent point of the created slot
+async fn create_replication_slot(client: &Client, query: &str) -> Result<PgLsn, ReplicationError> {
+    let slot_row = client
+        .simple_query(query)
+        .await?
+        .into_iter()
+        .next()
+        .and_then(|msg| match msg {
+            SimpleQueryMessage::Row(row) => Some(row),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            ReplicationError::Indefinite(anyhow!("empty result after creating replication slot"))
+        })?;
+
+    let consistent_point = try_indefinite!(slot_row
+        .get("consistent_point")
+        .ok_or_else(|| anyhow!("missing expected column: `consistent_point`")));
+    Ok(try_definite!(consistent_point
+        .parse()
+        .or_else(|_| Err(anyhow!("invalid lsn")))))
+}
+
+/// Returns the index of the column of `desc` that the initial snapshot of a table can be
+/// paginated by, which is the sole column of the primary key of the `upstream` table if it is
+/// ingested and has an integer type.
+fn snapshot_key(upstream: &PostgresTableDesc, desc: &PostgresTableDesc) -> Option<usize> {
+    let mut key_columns = upstream.columns.iter().filter(|c| c.primary_key);
+    let key = match (key_columns.next(), key_columns.next()) {
+        (Some(key), None) => key,
+        _ => return None,
+    };
+    let integer_types = [Type::INT2.oid(), Type::INT4.oid(), Type::INT8.oid()];
+    if !integer_types.contains(&key.type_oid) {
+        return None;
+    }
+    desc.columns.iter().position(|c| c.name == key.name)
+}
+
+/// Returns the offset of a chunk of the initial snapshot of a table that ends at `key`, which
+/// orders chunks by their keys.
+fn snapshot_offset(key: i64) -> MzOffset {
+    MzOffset::from((key as u64) ^ (1 << 63))
+}
+
+/// Returns the key that the chunk of the initial snapshot of a table at `offset` ends at
+fn snapshot_offset_key(offset: MzOffset) -> i64 {
+    (offset.offset ^ (1 << 63)) as i64
+}
+
+/// Returns the value of an integer key column in a tuple received in the replication stream
+fn tuple_key(value: &TupleData) -> Result<i64, anyhow::Error> {
+    match value {
+        TupleData::Text(b) => Ok(std::str::from_utf8(b)?.parse()?),
+        _ => bail!("primary key value missing from replication stream"),
+    }
+}
+
+/// An upstream table no longer matches the table the source was created with.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct SchemaMismatch(String);
+
+/// Classifies an error encountered during replication.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    if err.is::<SchemaMismatch>() {
+        return SourceErrorClass::Schema;
+    }
+    let db_err = err
+        .downcast_ref::<tokio_postgres::Error>()
+        .and_then(|e| e.source())
+        .and_then(|e| e.downcast_ref::<DbError>());
+    match db_err.and_then(|e| e.code().code().get(0..2)) {
+        // invalid authorization specification
+        Some("28") => SourceErrorClass::Auth,
+        _ => SourceErrorClass::Transient,
     }
 }
 
 struct RowMessage {
     output_index: usize,
     row: Row,
-    lsn: PgLsn,
+    partition: PartitionId,
+    offset: MzOffset,
     diff: i64,
 }
 
@@ -464,7 +588,9 @@ struct RowMessage {
 ///
 /// Note: `RowSender::delete/insert` should be called with the same
 /// lsn until `close_lsn` is called, which should be called and awaited
-/// before dropping the `RowSender` or moving onto a new lsn.
+/// before dropping the `RowSender` or moving onto a new lsn. The same holds for
+/// `RowSender::insert_snapshot` and `close_snapshot_chunk` with the offset of a chunk of
+/// the initial snapshot.
 /// Internally, this type uses asserts to uphold the first requirement.
 struct RowSender {
     sender: Sender<InternalMessage>,
@@ -484,44 +610,49 @@ impl RowSender {
 
     /// Insert a row at an lsn.
     pub async fn insert(&mut self, output_index: usize, row: Row, lsn: PgLsn) {
-        if let Some(buffered) = self.buffered_message.take() {
-            assert_eq!(buffered.lsn, lsn);
-            self.send_row(
-                buffered.output_index,
-                buffered.row,
-                buffered.lsn,
-                buffered.diff,
-                false,
-            )
+        self.push(output_index, row, PartitionId::None, lsn.into(), 1)
             .await;
-        }
-
-        self.buffered_message = Some(RowMessage {
-            output_index,
-            row,
-            lsn,
-            diff: 1,
-        });
     }
+
     /// Delete a row at an lsn.
     pub async fn delete(&mut self, output_index: usize, row: Row, lsn: PgLsn) {
-        if let Some(buffered) = self.buffered_message.take() {
-            assert_eq!(buffered.lsn, lsn);
-            self.send_row(
-                buffered.output_index,
-                buffered.row,
-                buffered.lsn,
-                buffered.diff,
-                false,
-            )
+        self.push(output_index, row, PartitionId::None, lsn.into(), -1)
             .await;
+    }
+
+    /// Insert a row of the initial snapshot of the table with OID `oid`, as part of the chunk
+    /// of the snapshot at `offset`.
+    pub async fn insert_snapshot(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        oid: u32,
+        offset: MzOffset,
+    ) {
+        let partition = PartitionId::PostgresSnapshot(oid);
+        self.push(output_index, row, partition, offset, 1).await;
+    }
+
+    async fn push(
+        &mut self,
+        output_index: usize,
+        row: Row,
+        partition: PartitionId,
+        offset: MzOffset,
+        diff: i64,
+    ) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.partition, partition);
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, false).await;
         }
 
         self.buffered_message = Some(RowMessage {
             output_index,
             row,
-            lsn,
-            diff: -1,
+            partition,
+            offset,
+            diff,
         });
     }
 
@@ -529,30 +660,45 @@ impl RowSender {
     /// last message sent is marked as closing the `lsn` (which is the messages `offset` in the
     /// rest of the source pipeline.
     pub async fn close_lsn(&mut self, lsn: PgLsn) {
+        self.close(PartitionId::None, lsn.into()).await;
+    }
+
+    /// Finalize the chunk of the initial snapshot of the table with OID `oid` at `offset`, like
+    /// `close_lsn` does for an lsn.
+    pub async fn close_snapshot_chunk(&mut self, oid: u32, offset: MzOffset) {
+        self.close(PartitionId::PostgresSnapshot(oid), offset).await;
+    }
+
+    async fn close(&mut self, partition: PartitionId, offset: MzOffset) {
         if let Some(buffered) = self.buffered_message.take() {
-            assert_eq!(buffered.lsn, lsn);
-            self.send_row(
-                buffered.output_index,
-                buffered.row,
-                buffered.lsn,
-                buffered.diff,
-                true,
-            )
-            .await;
+            assert_eq!(buffered.partition, partition);
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, true).await;
         }
     }
 
-    async fn send_row(&self, output: usize, row: Row, lsn: PgLsn, diff: i64, end: bool) {
+    /// Reports a status update of the source.
+    pub async fn send_status(&self, update: SourceStatusUpdate) {
+        // Drop the send error, as we have no way of communicating back to the
+        // source operator if the channel is gone.
+        let _ = self.sender.send(InternalMessage::Status(update)).await;
+        self.activator
+            .activate()
+            .expect("postgres reader activation failed");
+    }
+
+    async fn send_row(&self, message: RowMessage, end: bool) {
         // a closed receiver means the source has been shutdown
         // (dropped or the process is dying), so just continue on
         // without activation
         if let Ok(_) = self
             .sender
             .send(InternalMessage::Value {
-                output,
-                value: row,
-                lsn,
-                diff,
+                output: message.output_index,
+                value: message.row,
+                partition: message.partition,
+                offset: message.offset,
+                diff: message.diff,
                 end,
             })
             .await
@@ -564,21 +710,132 @@ impl RowSender {
     }
 }
 
+/// The destination of the rows copied from a table for a chunk of its initial snapshot.
+enum SnapshotRows<'a, 'b> {
+    /// The rows are buffered, along with their keys, until the offset of the chunk is known.
+    Buffered(&'a mut Vec<(Option<i64>, Row)>),
+    /// The rows are emitted as they are copied.
+    Streamed(&'a mut RewoundChunk<'b>),
+}
+
+/// A chunk of the initial snapshot of a table that is being emitted, rewound to the lsn the
+/// replication slot was created at.
+struct RewoundChunk<'a> {
+    row_sender: &'a mut RowSender,
+    info: &'a SourceTable,
+    /// The offset of the chunk in the snapshot partition of its table
+    offset: MzOffset,
+    /// The changes that rewind the rows of the chunk, by key and row, less the retractions that
+    /// were already applied to emitted rows
+    undo: BTreeMap<(Option<i64>, Row), Diff>,
+}
+
+impl RewoundChunk<'_> {
+    /// Emits a row copied from the table, unless the rewind retracts it.
+    async fn emit(&mut self, key: Option<i64>, row: Row) -> Result<(), ReplicationError> {
+        let entry = (key, row);
+        if let Some(diff) = self.undo.get_mut(&entry) {
+            if *diff < 0 {
+                *diff += 1;
+                if *diff == 0 {
+                    self.undo.remove(&entry);
+                }
+                return Ok(());
+            }
+        }
+        let (_, row) = entry;
+        self.row_sender
+            .insert_snapshot(self.info.output_index, row, self.info.desc.oid, self.offset)
+            .await;
+        // Failure scenario after we have produced at least one row, but before the chunk is
+        // closed
+        fail::fail_point!("pg_snapshot_failure", |_| {
+            Err(ReplicationError::Indefinite(anyhow::anyhow!(
+                "recoverable errors should crash the process"
+            )))
+        });
+        Ok(())
+    }
+
+    /// Emits the rows the rewind restores with keys up to `upper`, the last key of the chunk,
+    /// and closes the chunk.
+    async fn close(self, upper: Option<i64>) -> Result<(), ReplicationError> {
+        let oid = self.info.desc.oid;
+        let in_chunk = |key: &Option<i64>| match (key, upper) {
+            (Some(key), Some(upper)) => *key <= upper,
+            _ => true,
+        };
+        let undo: Vec<_> = self
+            .undo
+            .into_iter()
+            .filter(|((key, _), _)| in_chunk(key))
+            .collect();
+        // Every row the rewind retracts must have been copied from the table.
+        if undo.iter().any(|(_, diff)| *diff < 0) {
+            return Err(ReplicationError::Definite(anyhow!(
+                "initial snapshot of table {} with oid {} does not match its replication slot",
+                self.info.desc.name,
+                oid
+            )));
+        }
+        for ((_, row), diff) in undo {
+            for _ in 0..diff {
+                self.row_sender
+                    .insert_snapshot(self.info.output_index, row.clone(), oid, self.offset)
+                    .await;
+            }
+        }
+        self.row_sender.close_snapshot_chunk(oid, self.offset).await;
+        Ok(())
+    }
+}
+
 // implement the core pg logic in this impl block
 impl PostgresTaskInfo {
     /// Validates that all expected tables exist in the publication tables and they have the same schema
-    fn validate_tables(&self, tables: Vec<PostgresTableDesc>) -> Result<(), anyhow::Error> {
+    ///
+    /// Tables may have gained columns after the columns the source ingests, which are reported
+    /// in a status update and otherwise ignored.
+    async fn validate_tables(
+        &mut self,
+        tables: Vec<PostgresTableDesc>,
+    ) -> Result<(), anyhow::Error> {
         let pub_tables: HashMap<u32, PostgresTableDesc> =
             tables.into_iter().map(|t| (t.oid, t)).collect();
-        for (id, info) in self.source_tables.iter() {
+        for (id, info) in self.source_tables.iter_mut() {
             match pub_tables.get(id) {
                 Some(pub_schema) => {
-                    if pub_schema != &info.desc {
+                    let known = info.desc.columns.len();
+                    let compatible = pub_schema.namespace == info.desc.namespace
+                        && pub_schema.name == info.desc.name
+                        && pub_schema.columns.len() >= known
+                        && pub_schema.columns[..known] == info.desc.columns[..];
+                    if !compatible {
                         error!(
                             "Error validating table in publication. Expected: {:?} Actual: {:?}",
                             &info.desc, pub_schema
                         );
-                        bail!("Schema for table {} differs, recreate Materialize source to use new schema", info.desc.name)
+                        bail!(SchemaMismatch(format!(
+                            "Schema for table {} differs, recreate Materialize source to use new schema",
+                            info.desc.name
+                        )))
+                    }
+                    info.snapshot_key = snapshot_key(pub_schema, &info.desc);
+                    if pub_schema.columns.len() > info.upstream_columns {
+                        info.upstream_columns = pub_schema.columns.len();
+                        let added: Vec<_> = pub_schema.columns[known..]
+                            .iter()
+                            .map(|c| c.name.clone())
+                            .collect();
+                        warn!(
+                            "table {} with oid {} has new columns that are not ingested: {:?}",
+                            info.desc.name, id, added
+                        );
+                        let update = SourceStatusUpdate::upstream_columns_added(
+                            &format!("{}.{}", info.desc.namespace, info.desc.name),
+                            &added,
+                        );
+                        self.row_sender.send_status(update).await;
                     }
                 }
                 None => {
@@ -586,11 +843,10 @@ impl PostgresTaskInfo {
                         "publication missing table: {} with id {}",
                         info.desc.name, id
                     );
-                    bail!(
+                    bail!(SchemaMismatch(format!(
                         "Publication missing expected table {} with oid {}",
-                        info.desc.name,
-                        id
-                    )
+                        info.desc.name, id
+                    )))
                 }
             }
         }
@@ -599,8 +855,11 @@ impl PostgresTaskInfo {
 
     /// Creates the replication slot and produces the initial snapshot of the data
     ///
-    /// After the initial snapshot has been produced it returns the name of the created slot and
-    /// the LSN at which we should start the replication stream at.
+    /// Tables are read in chunks of at most `SNAPSHOT_CHUNK_ROWS` rows, paginated by their
+    /// primary key, each in a short transaction of its own. Every chunk is rewound to the LSN the
+    /// replication slot was created at, which the replication stream then starts at, and is
+    /// emitted at its own offset of the snapshot partition of its table. An interrupted snapshot
+    /// resumes after the last chunk that was ingested.
     async fn produce_snapshot(&mut self) -> Result<(), ReplicationError> {
         // Get all the relevant tables for this publication
         let publication_tables = try_indefinite!(
@@ -609,112 +868,389 @@ impl PostgresTaskInfo {
 
         let client = try_indefinite!(self.connection_config.clone().connect_replication().await);
 
-        // We're initializing this source so any previously existing slot must be removed and
-        // re-created. Once we have data persistence we will be able to reuse slots across restarts
-        let _ = client
-            .simple_query(&format!("DROP_REPLICATION_SLOT {:?}", &self.slot))
-            .await;
-
         // Validate publication tables against the state snapshot
-        try_definite!(self.validate_tables(publication_tables));
+        try_definite!(self.validate_tables(publication_tables).await);
+
+        if self.snapshot_uppers.is_empty() {
+            // We're initializing this source so any previously existing slot must be removed and
+            // re-created.
+            let _ = client
+                .simple_query(&format!("DROP_REPLICATION_SLOT {:?}", &self.slot))
+                .await;
 
-        // Start a transaction and immediately create a replication slot with the USE SNAPSHOT
-        // directive. This makes the starting point of the slot and the snapshot of the transaction
-        // identical.
+            // Store the lsn at which we will need to start the replication stream from
+            let slot_query = format!(
+                r#"CREATE_REPLICATION_SLOT {:?} LOGICAL "pgoutput" NOEXPORT_SNAPSHOT"#,
+                &self.slot
+            );
+            self.lsn = create_replication_slot(&client, &slot_query).await?;
+        } else {
+            // We're resuming an interrupted snapshot. The slot is only advanced once replication
+            // starts, so it still points at the lsn the ingested chunks were rewound to.
+            let query = format!(
+                "SELECT confirmed_flush_lsn FROM pg_replication_slots WHERE slot_name = '{}'",
+                &self.slot
+            );
+            let slot_lsn =
+                client
+                    .simple_query(&query)
+                    .await?
+                    .into_iter()
+                    .find_map(|msg| match msg {
+                        SimpleQueryMessage::Row(row) => row.get(0).map(|lsn| lsn.to_string()),
+                        _ => None,
+                    });
+            let slot_lsn = try_definite!(slot_lsn.ok_or_else(|| anyhow!(
+                "replication slot {} was dropped during the initial snapshot",
+                &self.slot
+            )));
+            self.lsn = try_definite!(slot_lsn.parse().or_else(|_| Err(anyhow!("invalid lsn"))));
+        }
+
+        // Decoding the changes to rewind chunks requires the extended query protocol, which
+        // replication connections do not support.
+        let rewind_client = try_indefinite!(
+            self.connection_config
+                .connect("postgres_source_snapshot")
+                .await
+        );
+
+        let mut oids: Vec<_> = self.source_tables.keys().copied().collect();
+        oids.sort();
+        for oid in oids {
+            self.produce_table_snapshot(oid, &rewind_client).await?;
+            self.metrics.tables.inc();
+        }
+        self.metrics.lsn.set(self.lsn.into());
+        Ok(())
+    }
+
+    /// Produces the initial snapshot of the table with OID `oid`, after the chunks of it that
+    /// were already ingested.
+    async fn produce_table_snapshot(
+        &mut self,
+        oid: u32,
+        rewind_client: &Client,
+    ) -> Result<(), ReplicationError> {
+        let mut lower = match self.snapshot_uppers.get(&oid) {
+            None => None,
+            Some(upper) if upper.offset > SNAPSHOT_COMPLETE => return Ok(()),
+            Some(upper) => Some(snapshot_offset_key(MzOffset::from(upper.offset - 1))),
+        };
+
+        loop {
+            // Offsets are committed as chunks are ingested, but they are only of interest to the
+            // replication stream.
+            while let Ok(_) = self.offset_rx.try_recv() {}
+            if self.sender.is_closed() {
+                return Ok(());
+            }
+            // Failure scenario in between two chunks of a table
+            fail::fail_point!("pg_snapshot_chunk_failure", |_| {
+                Err(ReplicationError::Indefinite(anyhow::anyhow!(
+                    "recoverable errors should crash the process"
+                )))
+            });
+
+            match self
+                .produce_snapshot_chunk(oid, lower, rewind_client)
+                .await?
+            {
+                Some(upper) => lower = Some(upper),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Produces the chunk of the initial snapshot of the table with OID `oid` that starts after
+    /// the key `lower` in a transaction of its own, returning the key the chunk ends at, or
+    /// `None` if it completes the snapshot of the table.
+    ///
+    /// Tables that cannot be paginated are read in a single chunk whose rows are emitted as they
+    /// are copied. The rows of any other chunk are buffered until its last key, and thus its
+    /// offset, is known, which bounds them by `SNAPSHOT_CHUNK_ROWS`.
+    async fn produce_snapshot_chunk(
+        &mut self,
+        oid: u32,
+        lower: Option<i64>,
+        rewind_client: &Client,
+    ) -> Result<Option<i64>, ReplicationError> {
+        let info = &self.source_tables[&oid];
+        let client = try_indefinite!(self.connection_config.clone().connect_replication().await);
+
+        // Start a transaction and immediately create a temporary replication slot with the USE
+        // SNAPSHOT directive. The starting point of the slot is the lsn the snapshot of the
+        // transaction is consistent with.
         client
             .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ;")
             .await?;
-
+        let snapshot_slot = format!("{}_snapshot", self.slot);
         let slot_query = format!(
-            r#"CREATE_REPLICATION_SLOT {:?} LOGICAL "pgoutput" USE_SNAPSHOT"#,
-            &self.slot
+            r#"CREATE_REPLICATION_SLOT {:?} TEMPORARY LOGICAL "pgoutput" USE_SNAPSHOT"#,
+            &snapshot_slot
         );
-        let slot_row = client
-            .simple_query(&slot_query)
-            .await?
-            .into_iter()
-            .next()
-            .and_then(|msg| match msg {
-                SimpleQueryMessage::Row(row) => Some(row),
-                _ => None,
-            })
-            .ok_or_else(|| {
-                ReplicationError::Indefinite(anyhow!(
-                    "empty result after creating replication slot"
-                ))
-            })?;
-
-        // Store the lsn at which we will need to start the replication stream from
-        let consistent_point = try_indefinite!(slot_row
-            .get("consistent_point")
-            .ok_or_else(|| anyhow!("missing expected column: `consistent_point`")));
-        self.lsn = try_definite!(consistent_point
-            .parse()
-            .or_else(|_| Err(anyhow!("invalid lsn"))));
+        let snapshot_lsn = create_replication_slot(&client, &slot_query).await?;
+
+        let undo = PostgresTaskInfo::snapshot_rewind(
+            rewind_client,
+            &self.slot,
+            &self.publication,
+            info,
+            lower,
+            snapshot_lsn,
+        )
+        .await?;
+        let mut chunk = RewoundChunk {
+            row_sender: &mut self.row_sender,
+            info,
+            offset: MzOffset::from(SNAPSHOT_COMPLETE),
+            undo,
+        };
 
-        // Scratch space to use while evaluating casts
-        let mut datum_vec = DatumVec::new();
+        let mut buffered = vec![];
+        match info.snapshot_key {
+            None => {
+                let mut rows = SnapshotRows::Streamed(&mut chunk);
+                PostgresTaskInfo::copy_snapshot_rows(&client, info, lower, &mut rows).await?;
+            }
+            Some(_) => {
+                let mut rows = SnapshotRows::Buffered(&mut buffered);
+                PostgresTaskInfo::copy_snapshot_rows(&client, info, lower, &mut rows).await?;
+                // A chunk that reaches the penultimate key also includes the last one, so that
+                // the offset of a full chunk is never that of a completed snapshot.
+                if let Some((Some(key), _)) = buffered.last() {
+                    if *key == i64::MAX - 1 {
+                        let lower = Some(*key);
+                        let mut rows = SnapshotRows::Buffered(&mut buffered);
+                        PostgresTaskInfo::copy_snapshot_rows(&client, info, lower, &mut rows)
+                            .await?;
+                    }
+                }
+            }
+        }
 
-        for info in self.source_tables.values() {
-            let reader = client
-                .copy_out_simple(
-                    format!(
-                        "COPY {:?}.{:?} TO STDOUT (FORMAT TEXT, DELIMITER '\t')",
-                        info.desc.namespace, info.desc.name
-                    )
-                    .as_str(),
-                )
-                .await?;
+        client.simple_query("COMMIT;").await?;
+        client
+            .simple_query(&format!("DROP_REPLICATION_SLOT {:?}", &snapshot_slot))
+            .await?;
 
-            tokio::pin!(reader);
-            let mut text_row = Row::default();
-            // TODO: once tokio-stream is released with https://github.com/tokio-rs/tokio/pull/4502
-            //    we can convert this into a single `timeout(...)` call on the reader CopyOutStream
-            while let Some(b) = tokio::time::timeout(Duration::from_secs(30), reader.next())
-                .await?
-                .transpose()?
+        // A full chunk ends at its last key, and the next chunk starts right after it. Any
+        // other chunk is the last one of its table and covers all keys after its start,
+        // including those of rows deleted since the replication slot was created.
+        let upper = match buffered.last() {
+            Some((Some(key), _))
+                if buffered.len() as i64 >= SNAPSHOT_CHUNK_ROWS && *key < i64::MAX - 1 =>
             {
-                let mut packer = text_row.packer();
-                // Convert raw rows from COPY into repr:Row. Each Row is a relation_id
-                // and list of string-encoded values, e.g. Row{ 16391 , ["1", "2"] }
-                let parser = mz_pgcopy::CopyTextFormatParser::new(b.as_ref(), "\t", "\\N");
-
-                let mut raw_values = parser.iter_raw(info.desc.columns.len() as i32);
-                while let Some(raw_value) = raw_values.next() {
-                    match try_definite!(raw_value) {
-                        Some(value) => {
-                            packer.push(Datum::String(try_definite!(std::str::from_utf8(value))))
+                Some(*key)
+            }
+            _ => None,
+        };
+        if let Some(key) = upper {
+            chunk.offset = snapshot_offset(key);
+        }
+        for (key, row) in buffered {
+            chunk.emit(key, row).await?;
+        }
+        chunk.close(upper).await?;
+        Ok(upper)
+    }
+
+    /// Copies the rows of a table with keys after `lower` into `rows`, or only the first
+    /// `SNAPSHOT_CHUNK_ROWS` of them if the table is paginated.
+    async fn copy_snapshot_rows(
+        client: &Client,
+        info: &SourceTable,
+        lower: Option<i64>,
+        rows: &mut SnapshotRows<'_, '_>,
+    ) -> Result<(), ReplicationError> {
+        // Only copy the columns the source knows about, as the table may have gained columns
+        // since the source was created.
+        let columns = info
+            .desc
+            .columns
+            .iter()
+            .map(|c| format!("{:?}", c.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let table = format!("{:?}.{:?}", info.desc.namespace, info.desc.name);
+        let query = match info.snapshot_key {
+            Some(key) => {
+                let key = format!("{:?}", info.desc.columns[key].name);
+                let filter = match lower {
+                    Some(lower) => format!("WHERE {} > {} ", key, lower),
+                    None => String::new(),
+                };
+                format!(
+                    "SELECT {} FROM {} {}ORDER BY {} LIMIT {}",
+                    columns, table, filter, key, SNAPSHOT_CHUNK_ROWS
+                )
+            }
+            None => format!("SELECT {} FROM {}", columns, table),
+        };
+        let reader = client
+            .copy_out_simple(
+                format!("COPY ({}) TO STDOUT (FORMAT TEXT, DELIMITER '\t')", query).as_str(),
+            )
+            .await?;
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        tokio::pin!(reader);
+        let mut text_row = Row::default();
+        // TODO: once tokio-stream is released with https://github.com/tokio-rs/tokio/pull/4502
+        //    we can convert this into a single `timeout(...)` call on the reader CopyOutStream
+        while let Some(b) = tokio::time::timeout(Duration::from_secs(30), reader.next())
+            .await?
+            .transpose()?
+        {
+            let mut packer = text_row.packer();
+            // Convert raw rows from COPY into repr:Row. Each Row is a relation_id
+            // and list of string-encoded values, e.g. Row{ 16391 , ["1", "2"] }
+            let parser = mz_pgcopy::CopyTextFormatParser::new(b.as_ref(), "\t", "\\N");
+
+            let mut key = None;
+            let mut column = 0;
+            let mut raw_values = parser.iter_raw(info.desc.columns.len() as i32);
+            while let Some(raw_value) = raw_values.next() {
+                match try_definite!(raw_value) {
+                    Some(value) => {
+                        let value = try_definite!(std::str::from_utf8(value));
+                        if info.snapshot_key == Some(column) {
+                            key = Some(try_definite!(value.parse::<i64>()));
                         }
-                        None => packer.push(Datum::Null),
+                        packer.push(Datum::String(value))
                     }
+                    None => packer.push(Datum::Null),
                 }
+                column += 1;
+            }
 
+            let row = {
                 let mut datums = datum_vec.borrow();
                 datums.extend(text_row.iter());
+                try_definite!(PostgresTaskInfo::cast_row(&info.casts, &datums))
+            };
+            match rows {
+                SnapshotRows::Buffered(rows) => rows.push((key, row)),
+                SnapshotRows::Streamed(chunk) => chunk.emit(key, row).await?,
+            }
+        }
+        Ok(())
+    }
 
-                let row = try_definite!(PostgresTaskInfo::cast_row(&info.casts, &datums));
+    /// Returns the changes that rewind the rows of a table with keys after `lower`, which are
+    /// consistent with `snapshot_lsn`, to the lsn the replication slot was created at, by key and
+    /// row. The changes retract the tuples inserted and restore the tuples deleted by the
+    /// transactions committed in between.
+    ///
+    /// The slot is not advanced during the initial snapshot, so peeking it up to `snapshot_lsn`
+    /// decodes exactly those transactions. Peeking does not consume the changes, so the slot
+    /// keeps them for the next chunks and the replication stream. The changes are streamed, and
+    /// only those of this table with keys after `lower` are kept.
+    async fn snapshot_rewind(
+        client: &Client,
+        slot: &str,
+        publication: &str,
+        info: &SourceTable,
+        lower: Option<i64>,
+        snapshot_lsn: PgLsn,
+    ) -> Result<BTreeMap<(Option<i64>, Row), Diff>, ReplicationError> {
+        let query = format!(
+            "SELECT data FROM pg_logical_slot_peek_binary_changes(
+                 '{name}', '{lsn}', NULL,
+                 'proto_version', '1',
+                 'publication_names', '{publication}'
+            )",
+            name = slot,
+            lsn = snapshot_lsn,
+            publication = publication
+        );
+        let changes = try_indefinite!(
+            client
+                .query_raw(query.as_str(), std::iter::empty::<&dyn ToSql>())
+                .await
+        );
+        tokio::pin!(changes);
+
+        let oid = info.desc.oid;
+        let err = || {
+            anyhow!(
+                "Old row missing from replication stream for table with OID = {}.
+                 Did you forget to set REPLICA IDENTITY to FULL for your table?",
+                oid
+            )
+        };
 
-                self.row_sender
-                    .insert(info.output_index, row, self.lsn)
-                    .await;
-                // Failure scenario after we have produced at least one row, but before a
-                // successful `COMMIT`
-                fail::fail_point!("pg_snapshot_failure", |_| {
-                    Err(ReplicationError::Indefinite(anyhow::anyhow!(
-                        "recoverable errors should crash the process"
-                    )))
-                });
-            }
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
 
-            self.metrics.tables.inc();
+        let mut rewind = BTreeMap::new();
+        while let Some(change) = try_indefinite!(changes.try_next().await) {
+            let data: Vec<u8> = change.get(0);
+            let message = try_definite!(LogicalReplicationMessage::parse(&Bytes::from(data)));
+            use LogicalReplicationMessage::*;
+            let undo: Vec<(Vec<&TupleData>, Diff)> = match &message {
+                Insert(insert) if insert.rel_id() == oid => {
+                    let new_tuple = info.known_columns(insert.tuple().tuple_data());
+                    vec![(new_tuple.iter().collect(), -1)]
+                }
+                Update(update) if update.rel_id() == oid => {
+                    let old_tuple = info.known_columns(
+                        try_definite!(update.old_tuple().ok_or_else(err)).tuple_data(),
+                    );
+                    // If the new tuple contains unchanged toast values, reuse the ones
+                    // from the old tuple
+                    let new_tuple = info
+                        .known_columns(update.new_tuple().tuple_data())
+                        .iter()
+                        .zip(old_tuple.iter())
+                        .map(|(new, old)| match new {
+                            TupleData::UnchangedToast => old,
+                            _ => new,
+                        })
+                        .collect();
+                    vec![(old_tuple.iter().collect(), 1), (new_tuple, -1)]
+                }
+                Delete(delete) if delete.rel_id() == oid => {
+                    let old_tuple = info.known_columns(
+                        try_definite!(delete.old_tuple().ok_or_else(err)).tuple_data(),
+                    );
+                    vec![(old_tuple.iter().collect(), 1)]
+                }
+                Truncate(truncate) if truncate.rel_ids().contains(&oid) => {
+                    return Err(ReplicationError::Definite(anyhow!(
+                        "source table(s) name: {} id: {} got truncated",
+                        info.desc.name,
+                        oid
+                    )));
+                }
+                _ => continue,
+            };
+
+            for (tuple, diff) in undo {
+                let key = match info.snapshot_key {
+                    Some(key) => Some(try_definite!(tuple_key(tuple[key]))),
+                    None => None,
+                };
+                if let (Some(key), Some(lower)) = (key, lower) {
+                    if key <= lower {
+                        continue;
+                    }
+                }
+                let mut datums = datum_vec.borrow();
+                try_definite!(PostgresTaskInfo::datums_from_tuple(
+                    oid,
+                    tuple,
+                    &mut *datums
+                ));
+                let row = try_definite!(PostgresTaskInfo::cast_row(&info.casts, &datums));
+                *rewind.entry((key, row)).or_insert(0) += diff;
+            }
         }
-        self.metrics.lsn.set(self.lsn.into());
-        client.simple_query("COMMIT;").await?;
 
-        // close the current `row_sender` context after we are sure we have not errored
-        // out (in the commit).
-        self.row_sender.close_lsn(self.lsn).await;
-        Ok(())
+        rewind.retain(|_, diff| *diff != 0);
+        Ok(rewind)
     }
 
     /// Packs a Tuple received in the replication stream into a Row packer.
@@ -850,12 +1386,14 @@ impl PostgresTaskInfo {
             use futures::future::Either;
             let item = match futures::future::select(offset_recv, data_next).await {
                 Either::Left((Some(to_commit), _)) => {
-                    // We assume there is only a single partition here.
-                    let lsn: PgLsn = to_commit[&PartitionId::None].offset.into();
-                    // Set the committed lsn so we can send a correct status update
-                    // next time we are required. We assume this is always
-                    // increasing, and >= the initial lsn.
-                    committed_lsn = lsn;
+                    // Only the replication stream is tracked by the `None` partition,
+                    // the others track the chunks of the initial snapshot.
+                    if let Some(offset) = to_commit.get(&PartitionId::None) {
+                        // Set the committed lsn so we can send a correct status update
+                        // next time we are required. We assume this is always
+                        // increasing, and >= the initial lsn.
+                        committed_lsn = offset.offset.into();
+                    }
                     continue;
                 }
                 Either::Right((Some(item), _)) => item,
@@ -896,7 +1434,7 @@ impl PostgresTaskInfo {
                         self.metrics.inserts.inc();
                         let rel_id = insert.rel_id();
                         let info = self.source_tables.get(&rel_id).unwrap();
-                        let new_tuple = insert.tuple().tuple_data();
+                        let new_tuple = info.known_columns(insert.tuple().tuple_data());
                         let mut datums = datum_vec.borrow();
                         try_definite!(PostgresTaskInfo::datums_from_tuple(
                             rel_id,
@@ -918,8 +1456,9 @@ impl PostgresTaskInfo {
                                 rel_id
                             )
                         };
-                        let old_tuple =
-                            try_definite!(update.old_tuple().ok_or_else(err)).tuple_data();
+                        let old_tuple = info.known_columns(
+                            try_definite!(update.old_tuple().ok_or_else(err)).tuple_data(),
+                        );
                         let mut old_datums = datum_vec.borrow();
                         try_definite!(PostgresTaskInfo::datums_from_tuple(
                             rel_id,
@@ -933,9 +1472,8 @@ impl PostgresTaskInfo {
 
                         // If the new tuple contains unchanged toast values, reuse the ones
                         // from the old tuple
-                        let new_tuple = update
-                            .new_tuple()
-                            .tuple_data()
+                        let new_tuple = info
+                            .known_columns(update.new_tuple().tuple_data())
                             .iter()
                             .zip(old_tuple.iter())
                             .map(|(new, old)| match new {
@@ -964,8 +1502,9 @@ impl PostgresTaskInfo {
                                 rel_id
                             )
                         };
-                        let old_tuple =
-                            try_definite!(delete.old_tuple().ok_or_else(err)).tuple_data();
+                        let old_tuple = info.known_columns(
+                            try_definite!(delete.old_tuple().ok_or_else(err)).tuple_data(),
+                        );
                         let mut datums = datum_vec.borrow();
                         try_definite!(PostgresTaskInfo::datums_from_tuple(
                             rel_id,
@@ -993,18 +1532,18 @@ impl PostgresTaskInfo {
                     Relation(relation) => {
                         last_data_message = Instant::now();
                         let rel_id = relation.rel_id();
-                        if let Some(info) = self.source_tables.get(&rel_id) {
-                            // Start with the cheapest check first, this will catch the majority of alters
-                            if info.desc.columns.len() != relation.columns().len() {
+                        if let Some(info) = self.source_tables.get_mut(&rel_id) {
+                            // Start with the cheapest check first, this will catch dropped columns.
+                            // Columns added after the ones we know about are tolerated below.
+                            if info.desc.columns.len() > relation.columns().len() {
                                 error!(
                                     "alter table detected on {} with id {}",
                                     info.desc.name, info.desc.oid
                                 );
-                                return Err(Definite(anyhow!(
+                                return Err(Definite(anyhow!(SchemaMismatch(format!(
                                     "source table {} with oid {} has been altered",
-                                    info.desc.name,
-                                    info.desc.oid
-                                )));
+                                    info.desc.name, info.desc.oid
+                                )))));
                             }
                             let same_name = info.desc.name == relation.name().unwrap();
                             let same_namespace =
@@ -1018,11 +1557,10 @@ impl PostgresTaskInfo {
                                     relation.namespace().unwrap(),
                                     relation.name().unwrap()
                                 );
-                                return Err(Definite(anyhow!(
+                                return Err(Definite(anyhow!(SchemaMismatch(format!(
                                     "source table {} with oid {} has been altered",
-                                    info.desc.name,
-                                    info.desc.oid
-                                )));
+                                    info.desc.name, info.desc.oid
+                                )))));
                             }
                             // Relation messages do not include nullability/primary_key data so we
                             // check the name, type_oid, and type_mod explicitly and error if any
@@ -1041,13 +1579,32 @@ impl PostgresTaskInfo {
                                         info.desc.columns,
                                         relation.columns()
                                     );
-                                    return Err(Definite(anyhow!(
+                                    return Err(Definite(anyhow!(SchemaMismatch(format!(
                                         "source table {} with oid {} has been altered",
-                                        info.desc.name,
-                                        info.desc.oid
-                                    )));
+                                        info.desc.name, info.desc.oid
+                                    )))));
                                 }
                             }
+                            // The remaining columns were added upstream. We keep decoding the
+                            // columns we know about, and report the others whenever more of them
+                            // appear.
+                            if relation.columns().len() > info.upstream_columns {
+                                let added = relation.columns()[info.desc.columns.len()..]
+                                    .iter()
+                                    .map(|c| c.name().map(|n| n.to_string()))
+                                    .collect::<Result<Vec<_>, _>>();
+                                let added = try_definite!(added);
+                                info.upstream_columns = relation.columns().len();
+                                warn!(
+                                    "table {} with oid {} has new columns that are not ingested: {:?}",
+                                    info.desc.name, info.desc.oid, added
+                                );
+                                let update = SourceStatusUpdate::upstream_columns_added(
+                                    &format!("{}.{}", info.desc.namespace, info.desc.name),
+                                    &added,
+                                );
+                                self.row_sender.send_status(update).await;
+                            }
                         }
                     }
                     Insert(_) | Update(_) | Delete(_) | Origin(_) | Type(_) => {
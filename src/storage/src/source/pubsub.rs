@@ -0,0 +1,635 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functionality for creating Google Cloud Pub/Sub sources.
+//!
+//! A single worker runs a task that pulls messages from the subscription over a
+//! streaming pull, through which Pub/Sub delivers messages as they are
+//! published. A message is only acknowledged once the offset it was emitted at
+//! is durably recorded, and until then the task keeps extending its
+//! acknowledgement deadline, so messages that were not yet ingested when the
+//! source restarts are redelivered to it. Pub/Sub sources therefore deliver
+//! messages at least once.
+//!
+//! Messages are emitted in the order they are received. When the subscription
+//! has message ordering enabled, Pub/Sub delivers the messages of each ordering
+//! key in order, and redelivers a message together with the messages of its
+//! ordering key that follow it. Redelivered messages that were already emitted
+//! but not yet acknowledged are recognized by their message IDs and are not
+//! emitted again. The ordering key of each message is used as its key, so that
+//! `ENVELOPE UPSERT` keeps the latest message of each ordering key.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use http::uri::PathAndQuery;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::Code;
+use tracing::{info, warn};
+
+use mz_expr::PartitionId;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{SourceErrorClass, SourceRetry};
+use crate::source::types::OffsetCommitter;
+use crate::source::{SourceMessage, SourceMessageType, SourceReader, SourceReaderError};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::{MzOffset, PubSubSourceConnection};
+
+/// The audience of the tokens that authenticate with Pub/Sub.
+const PUBSUB_AUDIENCE: &str = "https://pubsub.googleapis.com/";
+
+/// How long the tokens that authenticate with Pub/Sub are valid for.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// The maximum number of messages Pub/Sub delivers before waiting for some of
+/// them to be acknowledged.
+const MAX_OUTSTANDING_MESSAGES: i64 = 10_000;
+
+/// The maximum number of bytes of messages Pub/Sub delivers before waiting for
+/// some of them to be acknowledged.
+const MAX_OUTSTANDING_BYTES: i64 = 100 * 1024 * 1024;
+
+/// The maximum number of acknowledgement IDs sent in a single request, which
+/// keeps requests well below Pub/Sub's size limit.
+const ACK_IDS_PER_REQUEST: usize = 2_500;
+
+/// A request of the `StreamingPull` method of the Pub/Sub API.
+///
+/// The first request of each stream opens it, and later requests acknowledge
+/// messages or modify their acknowledgement deadlines.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StreamingPullRequest {
+    #[prost(string, tag = "1")]
+    subscription: String,
+    #[prost(string, repeated, tag = "2")]
+    ack_ids: Vec<String>,
+    #[prost(int32, repeated, tag = "3")]
+    modify_deadline_seconds: Vec<i32>,
+    #[prost(string, repeated, tag = "4")]
+    modify_deadline_ack_ids: Vec<String>,
+    #[prost(int32, tag = "5")]
+    stream_ack_deadline_seconds: i32,
+    #[prost(string, tag = "6")]
+    client_id: String,
+    #[prost(int64, tag = "7")]
+    max_outstanding_messages: i64,
+    #[prost(int64, tag = "8")]
+    max_outstanding_bytes: i64,
+}
+
+/// A response of the `StreamingPull` method of the Pub/Sub API.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StreamingPullResponse {
+    #[prost(message, repeated, tag = "1")]
+    received_messages: Vec<ReceivedMessage>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReceivedMessage {
+    #[prost(string, tag = "1")]
+    ack_id: String,
+    #[prost(message, optional, tag = "2")]
+    message: Option<PubsubMessage>,
+}
+
+/// A Pub/Sub message, without its attributes, which the source does not
+/// ingest.
+#[derive(Clone, PartialEq, prost::Message)]
+struct PubsubMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    data: Vec<u8>,
+    #[prost(string, tag = "3")]
+    message_id: String,
+    #[prost(message, optional, tag = "4")]
+    publish_time: Option<ProtoTimestamp>,
+    #[prost(string, tag = "5")]
+    ordering_key: String,
+}
+
+/// A `google.protobuf.Timestamp`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ProtoTimestamp {
+    #[prost(int64, tag = "1")]
+    seconds: i64,
+    #[prost(int32, tag = "2")]
+    nanos: i32,
+}
+
+enum InternalMessage {
+    Message(PubsubMessage),
+    Status(SourceStatusUpdate),
+}
+
+/// Information required to pull messages from a Pub/Sub subscription
+pub struct PubSubSourceReader {
+    /// Receiver channel that ingests messages
+    receiver_stream: Receiver<Result<InternalMessage, anyhow::Error>>,
+    /// The offset to emit the next message at
+    next_offset: MzOffset,
+    /// The message ID of each emitted message that was not yet acknowledged,
+    /// by the offset it was emitted at
+    pending: Arc<Mutex<BTreeMap<MzOffset, String>>>,
+    // Pub/Sub sources support single-threaded ingestion only, so only one of
+    // the `PubSubSourceReader`s will actually produce data.
+    active_read_worker: bool,
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+/// Acknowledges the messages whose offsets were durably recorded.
+pub struct PubSubOffsetCommitter {
+    pending: Arc<Mutex<BTreeMap<MzOffset, String>>>,
+    /// Sends the message IDs of the messages to acknowledge to the pulling
+    /// task
+    acks_tx: UnboundedSender<Vec<String>>,
+    logger: LogCommitter,
+}
+
+#[async_trait]
+impl OffsetCommitter for PubSubOffsetCommitter {
+    async fn commit_offsets(
+        &self,
+        offsets: HashMap<PartitionId, MzOffset>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(offset) = offsets.get(&PartitionId::None) {
+            let committed = {
+                let mut pending = self.pending.lock().expect("lock poisoned");
+                let uncommitted = pending.split_off(&(*offset + MzOffset::from(1)));
+                std::mem::replace(&mut *pending, uncommitted)
+            };
+            if !committed.is_empty() {
+                // The pulling task stops once the source is dropped, at which
+                // point there is nothing left to acknowledge.
+                let _ = self.acks_tx.send(committed.into_values().collect());
+            }
+        }
+        self.logger.commit_offsets(offsets).await
+    }
+}
+
+#[async_trait(?Send)]
+impl SourceReader for PubSubSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Diff = ();
+    type OffsetCommitter = PubSubOffsetCommitter;
+    type Connection = PubSubSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        // Messages have no offsets of their own, so they are emitted at
+        // consecutive offsets that continue from where the previous
+        // incarnation of the source left off.
+        let next_offset = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match pid {
+                PartitionId::None => offset,
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        // a single arbitrary worker is responsible for pulling the subscription
+        let (acks_tx, acks_rx) = mpsc::unbounded_channel();
+        let receiver = if active_read_worker {
+            let (dataflow_tx, dataflow_rx) = mpsc::channel(10_000);
+            info!(
+                "source_id={} pulling pubsub subscription={} worker={}",
+                source_id, connection.subscription, worker_id
+            );
+            task::spawn(
+                || format!("pubsub_pull:{}", source_id),
+                pull_task(
+                    source_id,
+                    connection,
+                    Arc::clone(&connection_context.secrets_reader),
+                    dataflow_tx,
+                    acks_rx,
+                ),
+            );
+            dataflow_rx
+        } else {
+            let (_dataflow_tx, dataflow_rx) = mpsc::channel(1);
+            dataflow_rx
+        };
+
+        let pending = Arc::new(Mutex::new(BTreeMap::new()));
+        Ok((
+            PubSubSourceReader {
+                receiver_stream: receiver,
+                next_offset,
+                pending: Arc::clone(&pending),
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            PubSubOffsetCommitter {
+                pending,
+                acks_tx,
+                logger: LogCommitter {
+                    source_id,
+                    worker_id,
+                    worker_count,
+                },
+            },
+        ))
+    }
+
+    async fn next(
+        &mut self,
+        _timestamp_granularity: Duration,
+    ) -> Option<Result<SourceMessageType<Self::Key, Self::Value, Self::Diff>, SourceReaderError>>
+    {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(Ok(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ])));
+            }
+            return None;
+        }
+
+        match self.receiver_stream.recv().await? {
+            Ok(InternalMessage::Message(message)) => {
+                let offset = self.next_offset;
+                self.next_offset += 1;
+                self.pending
+                    .lock()
+                    .expect("lock poisoned")
+                    .insert(offset, message.message_id);
+                let upstream_time_millis = message
+                    .publish_time
+                    .map(|t| t.seconds * 1_000 + i64::from(t.nanos) / 1_000_000);
+                let key = match message.ordering_key {
+                    ordering_key if ordering_key.is_empty() => None,
+                    ordering_key => Some(ordering_key.into_bytes()),
+                };
+                Some(Ok(SourceMessageType::Finalized(SourceMessage {
+                    output: 0,
+                    partition: PartitionId::None,
+                    offset,
+                    upstream_time_millis,
+                    key,
+                    value: Some(message.data),
+                    headers: None,
+                    specific_diff: (),
+                })))
+            }
+            Ok(InternalMessage::Status(update)) => {
+                Some(Ok(SourceMessageType::SourceStatus(update)))
+            }
+            Err(e) => Some(Err(SourceReaderError {
+                inner: SourceErrorDetails::Other(format!("{:#}", e)),
+            })),
+        }
+    }
+}
+
+/// The messages that were received but not yet acknowledged, whose
+/// acknowledgement deadlines the pulling task extends.
+#[derive(Debug, Default)]
+struct OutstandingMessages {
+    /// The latest acknowledgement ID of each message, by message ID.
+    ack_ids: HashMap<String, String>,
+}
+
+impl OutstandingMessages {
+    /// Records that the message `message_id` was delivered with `ack_id`,
+    /// returning whether it is new, rather than redelivered while outstanding.
+    fn receive(&mut self, message_id: String, ack_id: String) -> bool {
+        // A redelivered message can only be acknowledged with the ID it was
+        // redelivered with.
+        self.ack_ids.insert(message_id, ack_id).is_none()
+    }
+
+    /// Removes the messages `message_ids`, returning the acknowledgement IDs
+    /// to acknowledge them with.
+    fn acknowledge(&mut self, message_ids: Vec<String>) -> Vec<String> {
+        message_ids
+            .into_iter()
+            .filter_map(|message_id| self.ack_ids.remove(&message_id))
+            .collect()
+    }
+}
+
+/// Pulls messages from the subscription, retrying with a backoff whenever the
+/// stream fails, until the source is dropped.
+async fn pull_task(
+    source_id: GlobalId,
+    connection: PubSubSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    mut acks_rx: UnboundedReceiver<Vec<String>>,
+) {
+    // Identifies the streams of this task to Pub/Sub, which keeps delivering
+    // the messages that were outstanding on a failed stream to the next one.
+    let client_id = uuid::Uuid::new_v4().to_string();
+    let mut outstanding = OutstandingMessages::default();
+    let mut retry = SourceRetry::default();
+    loop {
+        let result = stream_pull(
+            &connection,
+            &*secrets_reader,
+            &client_id,
+            &tx,
+            &mut acks_rx,
+            &mut outstanding,
+            &mut retry,
+        )
+        .await;
+        let e = match result {
+            // The source was dropped.
+            Ok(false) => break,
+            // Pub/Sub closed the stream, as it does periodically.
+            Ok(true) => continue,
+            Err(e) => e,
+        };
+
+        let class = error_class(&e);
+        let backoff = match retry.on_error(class) {
+            Some(backoff) => backoff,
+            None => {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+        };
+        warn!(
+            "pulling source {} interrupted by {} error, retrying in {:?}: {:#}",
+            source_id,
+            class.name(),
+            backoff,
+            e
+        );
+        let next_retry_at = (SystemTime::now() + backoff)
+            .duration_since(UNIX_EPOCH)
+            .expect("system time after Unix epoch")
+            .as_millis();
+        let update = SourceStatusUpdate::retrying(
+            &format!("{:#}", e),
+            class,
+            u64::try_from(next_retry_at).expect("retry time fits into u64"),
+        );
+        if tx.send(Ok(InternalMessage::Status(update))).await.is_err() {
+            return;
+        }
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = tokio::time::sleep(backoff) => {}
+        }
+    }
+    info!(
+        "source_id={} stopped pulling pubsub subscription",
+        source_id
+    );
+}
+
+/// Opens a stream that pulls messages from the subscription, and forwards its
+/// messages to `tx` until the stream is closed, returning whether the source
+/// is still interested in messages.
+async fn stream_pull(
+    connection: &PubSubSourceConnection,
+    secrets_reader: &dyn SecretsReader,
+    client_id: &str,
+    tx: &Sender<Result<InternalMessage, anyhow::Error>>,
+    acks_rx: &mut UnboundedReceiver<Vec<String>>,
+    outstanding: &mut OutstandingMessages,
+    retry: &mut SourceRetry,
+) -> Result<bool, anyhow::Error> {
+    let ack_deadline_seconds =
+        i32::try_from(connection.ack_deadline.as_secs()).expect("ack deadline fits into i32");
+    let (requests_tx, requests_rx) = mpsc::unbounded_channel();
+    requests_tx
+        .send(StreamingPullRequest {
+            subscription: connection.subscription.clone(),
+            stream_ack_deadline_seconds: ack_deadline_seconds,
+            client_id: client_id.into(),
+            max_outstanding_messages: MAX_OUTSTANDING_MESSAGES,
+            max_outstanding_bytes: MAX_OUTSTANDING_BYTES,
+            ..Default::default()
+        })
+        .expect("receiver known to be open");
+
+    let mut request = tonic::Request::new(UnboundedReceiverStream::new(requests_rx));
+    // Credentials are read anew for every stream, so that rotated secrets take
+    // effect the next time we connect.
+    if let Some(authorization) = authorization(connection, secrets_reader).await? {
+        request
+            .metadata_mut()
+            .insert("authorization", authorization);
+    }
+    let routing = format!("subscription={}", connection.subscription)
+        .parse()
+        .context("subscription is not a valid header value")?;
+    request
+        .metadata_mut()
+        .insert("x-goog-request-params", routing);
+
+    let mut client = connect(&connection.endpoint)
+        .await
+        .context("error connecting to pubsub")?;
+    client.ready().await.context("error awaiting pubsub")?;
+    let path = PathAndQuery::from_static("/google.pubsub.v1.Subscriber/StreamingPull");
+    let mut responses = client
+        .streaming::<_, _, StreamingPullResponse, _>(request, path, ProstCodec::default())
+        .await?
+        .into_inner();
+
+    // Deadlines are extended well before they pass, so that messages are not
+    // redelivered while they wait to be acknowledged.
+    let mut extend_deadlines = tokio::time::interval(connection.ack_deadline / 2);
+    loop {
+        tokio::select! {
+            response = responses.message() => {
+                let response = match response? {
+                    Some(response) => response,
+                    None => return Ok(true),
+                };
+                retry.reset();
+                for received in response.received_messages {
+                    let message = match received.message {
+                        Some(message) => message,
+                        None => bail!("Pub/Sub delivered a message without contents"),
+                    };
+                    if outstanding.receive(message.message_id.clone(), received.ack_id) {
+                        if tx.send(Ok(InternalMessage::Message(message))).await.is_err() {
+                            return Ok(false);
+                        }
+                    }
+                }
+            }
+            Some(message_ids) = acks_rx.recv() => {
+                let ack_ids = outstanding.acknowledge(message_ids);
+                for ack_ids in ack_ids.chunks(ACK_IDS_PER_REQUEST) {
+                    let request = StreamingPullRequest {
+                        ack_ids: ack_ids.to_vec(),
+                        ..Default::default()
+                    };
+                    if requests_tx.send(request).is_err() {
+                        return Ok(true);
+                    }
+                }
+            }
+            _ = extend_deadlines.tick() => {
+                let ack_ids: Vec<_> = outstanding.ack_ids.values().cloned().collect();
+                for ack_ids in ack_ids.chunks(ACK_IDS_PER_REQUEST) {
+                    let request = StreamingPullRequest {
+                        modify_deadline_seconds: vec![ack_deadline_seconds; ack_ids.len()],
+                        modify_deadline_ack_ids: ack_ids.to_vec(),
+                        ..Default::default()
+                    };
+                    if requests_tx.send(request).is_err() {
+                        return Ok(true);
+                    }
+                }
+            }
+            _ = tx.closed() => return Ok(false),
+        }
+    }
+}
+
+/// Connects to the Pub/Sub API at `endpoint`.
+async fn connect(endpoint: &str) -> Result<Grpc<Channel>, anyhow::Error> {
+    let mut channel =
+        Endpoint::from_shared(endpoint.to_string())?.connect_timeout(Duration::from_secs(10));
+    if endpoint.starts_with("https:") {
+        channel = channel.tls_config(ClientTlsConfig::new())?;
+    }
+    Ok(Grpc::new(channel.connect().await?))
+}
+
+/// The fields of a service account key that are needed to authenticate as the
+/// service account.
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key_id: String,
+    private_key: String,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    sub: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+/// Returns the `authorization` header sent with each stream, if the source
+/// authenticates with a service account key.
+///
+/// Google APIs accept tokens signed by the service account's own key, so no
+/// token needs to be obtained from Google's OAuth server.
+async fn authorization(
+    connection: &PubSubSourceConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<Option<MetadataValue<tonic::metadata::Ascii>>, anyhow::Error> {
+    let credentials = match connection.credentials {
+        Some(credentials) => credentials,
+        None => return Ok(None),
+    };
+    let key = secrets_reader.read_string(credentials).await?;
+    let key: ServiceAccountKey = serde_json::from_str(&key)
+        .context("CREDENTIALS must contain a service account key in JSON format")?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time after Unix epoch")
+        .as_secs();
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(key.private_key_id.clone());
+    let claims = Claims {
+        iss: &key.client_email,
+        sub: &key.client_email,
+        aud: PUBSUB_AUDIENCE,
+        iat: now,
+        exp: now + TOKEN_LIFETIME.as_secs(),
+    };
+    let encoding_key = EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+        .context("invalid private key in CREDENTIALS")?;
+    let token = jsonwebtoken::encode(&header, &claims, &encoding_key)?;
+    let value = format!("Bearer {}", token)
+        .parse()
+        .context("token is not a valid header value")?;
+    Ok(Some(value))
+}
+
+/// Classifies an error encountered while pulling the subscription.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    if let Some(status) = err.downcast_ref::<tonic::Status>() {
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => SourceErrorClass::Auth,
+            _ => SourceErrorClass::Transient,
+        }
+    } else if err.downcast_ref::<serde_json::Error>().is_some()
+        || err.downcast_ref::<jsonwebtoken::errors::Error>().is_some()
+    {
+        SourceErrorClass::Auth
+    } else {
+        SourceErrorClass::Transient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outstanding_messages() {
+        let mut outstanding = OutstandingMessages::default();
+        assert!(outstanding.receive("m1".into(), "a1".into()));
+        assert!(outstanding.receive("m2".into(), "a2".into()));
+
+        // Redelivered messages are not new, and are acknowledged with the ID
+        // they were redelivered with.
+        assert!(!outstanding.receive("m1".into(), "a3".into()));
+        assert_eq!(
+            outstanding.acknowledge(vec!["m1".into(), "m3".into()]),
+            vec!["a3".to_string()]
+        );
+
+        // Messages that were acknowledged are new if they are redelivered.
+        assert!(outstanding.receive("m1".into(), "a4".into()));
+        assert_eq!(outstanding.ack_ids.len(), 2);
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Enforcement of [`IngestRateLimit`]s in the source reader operator.
+
+use std::time::{Duration, Instant};
+
+use mz_ore::cast::CastFrom;
+
+use crate::types::sources::IngestRateLimit;
+
+/// Limits the rate at which a source reader consumes messages, with a token
+/// bucket each for bytes and messages.
+#[derive(Debug)]
+pub(crate) struct IngestRateLimiter {
+    bytes: Option<TokenBucket>,
+    messages: Option<TokenBucket>,
+}
+
+impl IngestRateLimiter {
+    pub fn new(limit: IngestRateLimit, now: Instant) -> Self {
+        IngestRateLimiter {
+            bytes: limit
+                .bytes_per_second
+                .map(|rate| TokenBucket::new(rate, now)),
+            messages: limit
+                .messages_per_second
+                .map(|rate| TokenBucket::new(rate, now)),
+        }
+    }
+
+    /// Accounts for a message of `len` bytes read at `now`, returning how long
+    /// the reader must wait before reading the next message.
+    pub fn consume(&mut self, len: usize, now: Instant) -> Duration {
+        let bytes = match &mut self.bytes {
+            Some(bucket) => bucket.consume(u64::cast_from(len), now),
+            None => Duration::ZERO,
+        };
+        let messages = match &mut self.messages {
+            Some(bucket) => bucket.consume(1, now),
+            None => Duration::ZERO,
+        };
+        bytes.max(messages)
+    }
+}
+
+/// A token bucket that holds up to one second's worth of tokens.
+///
+/// Consuming more tokens than are available puts the bucket into debt, which
+/// is repaid by waiting, so that messages larger than the bucket are still
+/// admitted.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per second, which is also the capacity of the bucket.
+    rate: f64,
+    /// The current number of tokens, which is negative while in debt.
+    tokens: f64,
+    /// The last time `tokens` was refilled.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        // A rate of zero would never admit another message.
+        let rate = rate.max(1) as f64;
+        TokenBucket {
+            rate,
+            tokens: rate,
+            refilled_at: now,
+        }
+    }
+
+    fn consume(&mut self, tokens: u64, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+        self.refilled_at = now;
+
+        self.tokens -= tokens as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingest_rate_limiter() {
+        let start = Instant::now();
+        let mut limiter = IngestRateLimiter::new(
+            IngestRateLimit {
+                bytes_per_second: Some(100),
+                messages_per_second: Some(10),
+            },
+            start,
+        );
+
+        // The first second's worth of messages is admitted immediately.
+        for _ in 0..10 {
+            assert_eq!(limiter.consume(1, start), Duration::ZERO);
+        }
+        assert_eq!(limiter.consume(1, start), Duration::from_millis(100));
+
+        // Large messages put the byte bucket into debt.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.consume(150, later), Duration::from_millis(500));
+
+        // Unlimited readers never wait.
+        let mut limiter = IngestRateLimiter::new(IngestRateLimit::default(), start);
+        assert_eq!(limiter.consume(usize::MAX, start), Duration::ZERO);
+    }
+}
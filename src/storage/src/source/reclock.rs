@@ -23,6 +23,7 @@ use timely::progress::frontier::{Antichain, AntichainRef};
 use timely::progress::Timestamp as _;
 use timely::PartialOrder;
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 use mz_expr::PartitionId;
 use mz_ore::now::NowFn;
@@ -35,7 +36,7 @@ use tracing::trace;
 
 use crate::controller::CollectionMetadata;
 use crate::source::antichain::OffsetAntichain;
-use crate::types::sources::{MzOffset, SourceData};
+use crate::types::sources::{MzOffset, SourceData, TimestampStrategy};
 
 /// A "follower" for the ReclockOperator, that maintains
 /// a trace based on the results of reclocking and data from
@@ -52,12 +53,37 @@ pub struct ReclockFollower {
 /// A binding of None partition is encoded as a single datum containing the offset.
 ///
 /// A binding of a Kafka partition is encoded as the partition datum followed by the offset datum.
+///
+/// A binding of a Kinesis shard is encoded as the shard ID datum followed by the offset datum.
+///
+/// A binding of a NATS subject is encoded as the subject, as bytes to tell it apart from a
+/// Kinesis shard ID, followed by the offset datum.
+///
+/// A binding of a partition of one of several Kafka topics is encoded as the topic datum followed
+/// by the partition datum and the offset datum.
+///
+/// A binding of the initial snapshot of a Postgres table is encoded as the table OID datum
+/// followed by the offset datum.
+///
+/// A binding of the transactions of a MySQL server is encoded as the server UUID datum followed
+/// by the offset datum.
 fn pack_binding(pid: PartitionId, offset: MzOffset) -> SourceData {
     let mut row = Row::with_capacity(2);
     let mut packer = row.packer();
     match pid {
         PartitionId::None => {}
         PartitionId::Kafka(pid) => packer.push(Datum::Int32(pid)),
+        PartitionId::KafkaTopic(topic, pid) => {
+            packer.push(Datum::String(&topic));
+            packer.push(Datum::Int32(pid));
+        }
+        PartitionId::Kinesis(shard_id) => packer.push(Datum::String(&shard_id)),
+        PartitionId::Nats(subject) => packer.push(Datum::Bytes(subject.as_bytes())),
+        PartitionId::PostgresSnapshot(oid) => packer.push(Datum::UInt32(oid)),
+        PartitionId::MySqlServer(server) => {
+            let server = Uuid::parse_str(&server).expect("MySQL server IDs are UUIDs");
+            packer.push(Datum::Uuid(server))
+        }
     }
     packer.push(Datum::UInt64(offset.offset));
     SourceData(Ok(row))
@@ -68,9 +94,27 @@ fn pack_binding(pid: PartitionId, offset: MzOffset) -> SourceData {
 fn unpack_binding(data: SourceData) -> (PartitionId, MzOffset) {
     let row = data.0.expect("invalid binding");
     let mut datums = row.iter();
-    let (pid, offset) = match (datums.next(), datums.next()) {
-        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => (PartitionId::Kafka(p), offset),
-        (Some(Datum::UInt64(offset)), None) => (PartitionId::None, offset),
+    let (pid, offset) = match (datums.next(), datums.next(), datums.next()) {
+        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::Kafka(p), offset)
+        }
+        (Some(Datum::String(topic)), Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => {
+            (PartitionId::KafkaTopic(topic.to_string(), p), offset)
+        }
+        (Some(Datum::String(shard_id)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::Kinesis(shard_id.to_string()), offset)
+        }
+        (Some(Datum::Bytes(subject)), Some(Datum::UInt64(offset)), None) => {
+            let subject = std::str::from_utf8(subject).expect("invalid binding");
+            (PartitionId::Nats(subject.to_string()), offset)
+        }
+        (Some(Datum::UInt32(oid)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::PostgresSnapshot(oid), offset)
+        }
+        (Some(Datum::Uuid(server)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::MySqlServer(server.to_string()), offset)
+        }
+        (Some(Datum::UInt64(offset)), None, None) => (PartitionId::None, offset),
         _ => panic!("invalid binding"),
     };
 
@@ -433,6 +477,8 @@ pub struct ReclockOperator {
     now: NowFn,
     /// Values of current time will be rounded to be multiples of this duration in milliseconds
     update_interval_ms: u64,
+    /// How the timestamps of new bindings are chosen
+    strategy: TimestampStrategy,
 }
 
 impl ReclockOperator {
@@ -442,6 +488,7 @@ impl ReclockOperator {
         metadata: CollectionMetadata,
         now: NowFn,
         update_interval: Duration,
+        strategy: TimestampStrategy,
         as_of: Antichain<Timestamp>,
         // additional information to improve logging
         id: GlobalId,
@@ -506,6 +553,7 @@ impl ReclockOperator {
                 .as_millis()
                 .try_into()
                 .expect("huge duration"),
+            strategy,
         };
 
         // Load the initial state that might exist in the shard
@@ -592,8 +640,11 @@ impl ReclockOperator {
     }
 
     /// Advances the upper of the reclock operator if appropriate
+    ///
+    /// With the offset strategy the upper only advances when new bindings are minted, since time
+    /// does not pass while no offsets are being ingested.
     pub async fn advance(&mut self) {
-        if self.next_mint_timestamp().is_ok() {
+        if self.strategy == TimestampStrategy::WallClock && self.next_mint_timestamp().is_ok() {
             let empty: Vec<(PartitionId, MzOffset)> = Vec::new();
             while let Err(Upper(actual_upper)) = self.append(&empty).await {
                 self.sync(&actual_upper).await;
@@ -752,11 +803,14 @@ impl ReclockOperator {
         &mut self,
         updates: &[(PartitionId, MzOffset)],
     ) -> Result<Vec<(PartitionId, Vec<(Timestamp, MzOffset)>)>, Upper<Timestamp>> {
-        let next_ts = loop {
-            match self.next_mint_timestamp() {
-                Ok(ts) => break ts,
-                Err(sleep_duration) => tokio::time::sleep(sleep_duration).await,
-            }
+        let next_ts = match self.strategy {
+            TimestampStrategy::WallClock => loop {
+                match self.next_mint_timestamp() {
+                    Ok(ts) => break ts,
+                    Err(sleep_duration) => tokio::time::sleep(sleep_duration).await,
+                }
+            },
+            TimestampStrategy::Offset => self.next_offset_timestamp(updates),
         };
         let new_upper = Antichain::from_elem(next_ts.step_forward());
         loop {
@@ -811,6 +865,20 @@ impl ReclockOperator {
         }
     }
 
+    /// Produces the timestamp at which the provided updates will be minted when using the offset
+    /// strategy, which is the total number of offsets the source will have ingested across all of
+    /// its partitions. The returned timestamp is guaranteed to be beyond the current `upper`
+    /// frontier.
+    fn next_offset_timestamp(&self, updates: &[(PartitionId, MzOffset)]) -> Timestamp {
+        let ingested = self
+            .source_upper
+            .values()
+            .chain(updates.iter().map(|(_, diff)| diff))
+            .fold(0u64, |total, offset| total.saturating_add(offset.offset));
+        let upper_ts = self.upper.as_option().expect("no more timestamps to mint");
+        std::cmp::max(Timestamp::from(ingested), *upper_ts)
+    }
+
     /// Returns an iterator of timestamp bindings for a given partition
     fn partition_bindings(&self, pid: &PartitionId) -> PartitionBindings {
         let bindings = match self.remap_trace.get(pid) {
@@ -1039,6 +1107,7 @@ mod tests {
             remap_shard: shard,
             data_shard: ShardId::new(),
             status_shard: None,
+            relation_arity: None,
         };
 
         let operator = ReclockOperator::new(
@@ -1046,6 +1115,7 @@ mod tests {
             metadata,
             now_fn.now_fn(),
             Duration::from_secs(1),
+            TimestampStrategy::WallClock,
             as_of.clone(),
             GlobalId::Explain,
             "unittest",
@@ -1187,6 +1257,51 @@ mod tests {
         assert_eq!(operator.next_mint_timestamp(), Ok(3000.into()));
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn test_offset_strategy() {
+        const PART1: PartitionId = PartitionId::Kafka(1);
+        const PART2: PartitionId = PartitionId::Kafka(2);
+
+        let now_fn = TestNowFn::new();
+        let persist_cache = persist_cache(now_fn.now_fn());
+
+        let (mut operator, _follower) = make_test_operator(
+            ShardId::new(),
+            Antichain::from_elem(0.into()),
+            &persist_cache,
+            &now_fn,
+        )
+        .await;
+        operator.strategy = TimestampStrategy::Offset;
+
+        // Bindings are minted at the total number of ingested offsets, regardless of wall clock
+        operator
+            .mint(&OffsetAntichain::from_iter([(PART1, MzOffset::from(10))]))
+            .await;
+        operator
+            .mint(&OffsetAntichain::from_iter([(PART2, MzOffset::from(5))]))
+            .await;
+        operator
+            .mint(&OffsetAntichain::from_iter([(PART1, MzOffset::from(12))]))
+            .await;
+        assert_eq!(
+            operator.remap_trace[&PART1],
+            &[
+                (10.into(), MzOffset::from(10)),
+                (17.into(), MzOffset::from(12))
+            ]
+        );
+        assert_eq!(
+            operator.remap_trace[&PART2],
+            &[(15.into(), MzOffset::from(5))]
+        );
+
+        // Time passing without new offsets does not advance the upper
+        now_fn.advance(Duration::from_secs(10));
+        operator.advance().await;
+        assert_eq!(operator.upper, Antichain::from_elem(18.into()));
+    }
+
     #[tokio::test(start_paused = true)]
     async fn test_reclock_frontier() {
         let now_fn = TestNowFn::new();
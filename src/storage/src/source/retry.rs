@@ -0,0 +1,254 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Retry policies for errors encountered by source readers.
+//!
+//! Errors are classified by how likely they are to resolve on their own, and
+//! each class of error is retried with its own backoff.
+//!
+//! Source readers opt into this by classifying their own errors; readers that
+//! do not, like the Kafka reader, keep their own retry behavior. Only the
+//! PostgreSQL reader currently produces [`SourceErrorClass::Schema`].
+//!
+//! Readers that replicate a change log, like the PostgreSQL, MySQL and SQL
+//! Server readers, implement [`ReplicationTask`] and are driven by
+//! [`retry_replication`].
+
+use std::cmp;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use mz_repr::GlobalId;
+
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::SourceReaderError;
+use crate::types::errors::SourceErrorDetails;
+
+/// How long replication must run before failing for the failure to be
+/// considered unrelated to the previous one
+pub static REPLICATION_PROGRESS_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// The class of an error encountered while reading from an upstream system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceErrorClass {
+    /// A network or availability problem that is likely to resolve on its own.
+    Transient,
+    /// The upstream system rejected our credentials. This is unlikely to
+    /// resolve until someone fixes the credentials or the upstream
+    /// permissions, so it is retried infrequently.
+    Auth,
+    /// The upstream schema changed in a way the source cannot handle. This
+    /// never resolves on its own and is not retried.
+    Schema,
+}
+
+impl SourceErrorClass {
+    /// Returns the name of the class, as reported in the source status
+    /// relation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceErrorClass::Transient => "transient",
+            SourceErrorClass::Auth => "auth",
+            SourceErrorClass::Schema => "schema",
+        }
+    }
+
+    /// Returns the policy for retrying errors of this class, or `None` if
+    /// errors of this class are fatal.
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        match self {
+            SourceErrorClass::Transient => Some(RetryPolicy {
+                initial_backoff: Duration::from_secs(1),
+                max_backoff: Duration::from_secs(30),
+            }),
+            SourceErrorClass::Auth => Some(RetryPolicy {
+                initial_backoff: Duration::from_secs(30),
+                max_backoff: Duration::from_secs(10 * 60),
+            }),
+            SourceErrorClass::Schema => None,
+        }
+    }
+}
+
+/// An exponential backoff policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum backoff between retries. Each backoff is double the
+    /// previous one, up to this maximum.
+    pub max_backoff: Duration,
+}
+
+/// Tracks the backoff between consecutive failed attempts of a source to make
+/// progress.
+#[derive(Debug, Default)]
+pub struct SourceRetry {
+    /// The class of the most recent error and the backoff to apply after it.
+    current: Option<(SourceErrorClass, Duration)>,
+}
+
+impl SourceRetry {
+    /// Records an error of class `class`, and returns how long to wait before
+    /// retrying, or `None` if the error should not be retried.
+    ///
+    /// The backoff starts over whenever the class of error changes.
+    pub fn on_error(&mut self, class: SourceErrorClass) -> Option<Duration> {
+        let policy = class.retry_policy()?;
+        let backoff = match self.current {
+            Some((current_class, backoff)) if current_class == class => {
+                cmp::min(backoff * 2, policy.max_backoff)
+            }
+            _ => policy.initial_backoff,
+        };
+        self.current = Some((class, backoff));
+        Some(backoff)
+    }
+
+    /// Records that the source made progress, which resets the backoff.
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+/// An error that interrupted an attempt to replicate.
+#[derive(Debug)]
+pub enum ReplicationFailure {
+    /// An error from which the source can never recover, which is not
+    /// retried.
+    Definite(anyhow::Error),
+    /// An error of the specified class, which is retried according to the
+    /// policy of the class.
+    Indefinite(anyhow::Error, SourceErrorClass),
+}
+
+/// The replication of a source reader, which [`retry_replication`] restarts
+/// whenever it fails.
+#[async_trait]
+pub trait ReplicationTask: Send {
+    /// Returns the ID of the source.
+    fn source_id(&self) -> GlobalId;
+
+    /// Replicates until the source is dropped, in which case `Ok` is returned,
+    /// or until an error interrupts replication.
+    async fn replicate(&mut self) -> Result<(), ReplicationFailure>;
+
+    /// Reports a status update of the source.
+    async fn send_status(&mut self, update: SourceStatusUpdate);
+}
+
+/// Runs the replication of `task`, retrying it with backoff whenever it fails
+/// with an indefinite error.
+///
+/// Replication that ran for longer than `threshold` before failing is assumed
+/// to have made progress, so the backoff starts over. Every retry is reported
+/// through [`ReplicationTask::send_status`], as is an error that is not
+/// retried because of its class.
+pub async fn retry_replication<T>(
+    threshold: Duration,
+    task: &mut T,
+) -> Result<(), SourceReaderError>
+where
+    T: ReplicationTask,
+{
+    let mut retry = SourceRetry::default();
+    loop {
+        let started = Instant::now();
+        let (e, class) = match task.replicate().await {
+            // shutdown initiated elsewhere
+            Ok(()) => return Ok(()),
+            Err(ReplicationFailure::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                })
+            }
+            Err(ReplicationFailure::Indefinite(e, class)) => (e, class),
+        };
+
+        if started.elapsed() > threshold {
+            retry.reset();
+        }
+        let backoff = match retry.on_error(class) {
+            Some(backoff) => backoff,
+            None => {
+                let update = SourceStatusUpdate::fatal(&e.to_string(), class);
+                task.send_status(update).await;
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                });
+            }
+        };
+        warn!(
+            "replication for source {} interrupted by {} error, retrying in {:?}: {}",
+            task.source_id(),
+            class.name(),
+            backoff,
+            e
+        );
+
+        let next_retry_at = (SystemTime::now() + backoff)
+            .duration_since(UNIX_EPOCH)
+            .expect("system time after Unix epoch")
+            .as_millis();
+        let update = SourceStatusUpdate::retrying(
+            &e.to_string(),
+            class,
+            u64::try_from(next_retry_at).expect("retry time fits into u64"),
+        );
+        task.send_status(update).await;
+
+        tokio::time::sleep(backoff).await;
+        info!("resuming replication for source {}", task.source_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_per_class() {
+        let mut retry = SourceRetry::default();
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Transient),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Transient),
+            Some(Duration::from_secs(2))
+        );
+        for _ in 0..10 {
+            retry.on_error(SourceErrorClass::Transient);
+        }
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Transient),
+            Some(Duration::from_secs(30))
+        );
+
+        // A different class of error starts over with its own policy.
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Auth),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Auth),
+            Some(Duration::from_secs(60))
+        );
+
+        retry.reset();
+        assert_eq!(
+            retry.on_error(SourceErrorClass::Auth),
+            Some(Duration::from_secs(30))
+        );
+
+        assert_eq!(retry.on_error(SourceErrorClass::Schema), None);
+    }
+}
@@ -33,7 +33,7 @@ use std::default::Default;
 use std::ops::AddAssign;
 use std::sync::Arc;
 
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
 use aws_sdk_s3::error::{GetObjectError, ListObjectsV2Error};
 use aws_sdk_s3::types::SdkError;
 use aws_sdk_s3::Client as S3Client;
@@ -43,7 +43,7 @@ use futures::{FutureExt, StreamExt, TryStreamExt};
 use globset::GlobMatcher;
 use mz_secrets::SecretsReader;
 use timely::scheduling::SyncActivator;
-use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::{self, Duration};
 use tokio_util::io::{ReaderStream, StreamReader};
@@ -52,7 +52,8 @@ use tracing::{debug, error, trace, warn};
 use mz_expr::PartitionId;
 use mz_ore::retry::{Retry, RetryReader};
 use mz_ore::task;
-use mz_repr::GlobalId;
+use mz_persist_types::Codec;
+use mz_repr::{GlobalId, RelationDesc};
 
 use self::metrics::{BucketMetrics, ScanBucketMetrics};
 use self::notifications::{Event, EventType, TestEvent};
@@ -62,13 +63,16 @@ use crate::source::{
 };
 use crate::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
 use crate::types::connections::ConnectionContext;
-use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::encoding::{DataEncoding, DataEncodingInner, SourceDataEncoding};
 use crate::types::sources::{Compression, MzOffset, S3KeySource, S3SourceConnection};
 
 use super::metrics::SourceBaseMetrics;
 
 mod metrics;
 mod notifications;
+mod parquet;
+
+pub use parquet::fetch_parquet_desc;
 
 struct InternalMessage {
     record: Option<Vec<u8>>,
@@ -143,6 +147,7 @@ async fn download_objects_task(
     aws_external_id_prefix: Option<AwsExternalIdPrefix>,
     activator: SyncActivator,
     compression: Compression,
+    parquet_desc: Option<RelationDesc>,
     metrics: SourceBaseMetrics,
     secrets_reader: Arc<dyn SecretsReader>,
 ) {
@@ -212,6 +217,7 @@ async fn download_objects_task(
                     &msg_ref.bucket,
                     &msg_ref.key,
                     compression,
+                    parquet_desc.as_ref(),
                     sid,
                 )
                 .await;
@@ -680,6 +686,7 @@ async fn download_object(
     bucket: &str,
     key: &str,
     compression: Compression,
+    parquet_desc: Option<&RelationDesc>,
     source_id: &str,
 ) -> Result<DownloadMetricUpdate, DownloadError> {
     let retry_reader: RetryReader<_, _, _> = RetryReader::new(|state, offset| async move {
@@ -713,8 +720,11 @@ async fn download_object(
         if let Some(s) = obj.content_encoding.as_deref() {
             match (s, compression) {
                 ("gzip", Compression::Gzip) => (),
+                ("zstd", Compression::Zstd) => (),
                 ("identity", Compression::None) => (),
-                ("identity" | "gzip", _) => {
+                // The compression is detected from the object's contents below.
+                (_, Compression::Auto) => (),
+                ("identity" | "gzip" | "zstd", _) => {
                     debug!("object {} has mismatched Content-Encoding: {}", key, s)
                 }
                 _ => debug!("object {} has unrecognized Content-Encoding: {}", key, s),
@@ -729,22 +739,35 @@ async fn download_object(
     let mut reader = Box::pin(BufReader::new(retry_reader));
 
     // Check for empty files by filling up the buffer of bufreader and checking if it got any bytes
-    match reader.fill_buf().await {
+    let compression = match reader.fill_buf().await {
         Ok(buf) => {
             if buf.is_empty() {
                 trace!("source_id={} empty object {}/{}", source_id, bucket, key);
                 return Ok(Default::default());
             }
+            match compression {
+                Compression::Auto => Compression::detect(key, buf),
+                compression => compression,
+            }
         }
         Err(err) => return Err(DownloadError::Failed { err }),
     };
 
     let mut download_result = match compression {
-        Compression::None => read_object_chunked(source_id, reader, tx).await,
+        Compression::None => read_object(source_id, reader, tx, parquet_desc).await,
         Compression::Gzip => {
             let decoder = GzipDecoder::new(reader);
-            read_object_chunked(source_id, decoder, tx).await
+            read_object(source_id, decoder, tx, parquet_desc).await
+        }
+        Compression::Zstd => {
+            let decoder = ZstdDecoder::new(reader);
+            read_object(source_id, decoder, tx, parquet_desc).await
         }
+        Compression::Bzip2 => {
+            let decoder = BzDecoder::new(reader);
+            read_object(source_id, decoder, tx, parquet_desc).await
+        }
+        Compression::Auto => unreachable!("compression was detected above"),
     };
 
     debug!(
@@ -762,6 +785,81 @@ async fn download_object(
     download_result
 }
 
+async fn read_object<R>(
+    source_id: &str,
+    reader: R,
+    tx: &Sender<Result<InternalMessage, S3Error>>,
+    parquet_desc: Option<&RelationDesc>,
+) -> Result<DownloadMetricUpdate, DownloadError>
+where
+    R: Unpin + AsyncRead,
+{
+    match parquet_desc {
+        Some(desc) => read_object_parquet(source_id, reader, tx, desc).await,
+        None => read_object_chunked(source_id, reader, tx).await,
+    }
+}
+
+/// Reads a whole Parquet object, and sends each of its rows to dataflow as
+/// its own message.
+///
+/// Parquet files can only be decoded once their footer has been read, so
+/// unlike other formats the object is buffered in memory in its entirety.
+async fn read_object_parquet<R>(
+    source_id: &str,
+    mut reader: R,
+    tx: &Sender<Result<InternalMessage, S3Error>>,
+    desc: &RelationDesc,
+) -> Result<DownloadMetricUpdate, DownloadError>
+where
+    R: Unpin + AsyncRead,
+{
+    let mut bytes = vec![];
+    if let Err(err) = reader.read_to_end(&mut bytes).await {
+        return Err(DownloadError::Failed { err });
+    }
+    let bytes_read = bytes.len();
+
+    let rows = match parquet::decode_parquet(bytes, desc) {
+        Ok(rows) => rows,
+        Err(e) => {
+            return Err(DownloadError::Failed {
+                err: std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("decoding Parquet object: {:#}", e),
+                ),
+            })
+        }
+    };
+
+    let mut messages = 0;
+    for row in rows {
+        let mut record = vec![];
+        row.encode(&mut record);
+        if tx
+            .send(Ok(InternalMessage {
+                record: Some(record),
+            }))
+            .await
+            .is_err()
+        {
+            return Err(DownloadError::SendFailed);
+        }
+        messages += 1;
+    }
+
+    trace!(
+        "source_id={} finished sending parquet object to dataflow rows={} bytes={}",
+        source_id,
+        messages,
+        bytes_read
+    );
+    Ok(DownloadMetricUpdate {
+        bytes: bytes_read.try_into().expect("usize <= u64"),
+        messages,
+    })
+}
+
 async fn read_object_chunked<R>(
     source_id: &str,
     reader: R,
@@ -823,7 +921,7 @@ impl SourceReader for S3SourceReader {
         consumer_activator: SyncActivator,
         s3_conn: Self::Connection,
         _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
-        _encoding: SourceDataEncoding,
+        encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
     ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
@@ -836,6 +934,13 @@ impl SourceReader for S3SourceReader {
             let (keys_tx, keys_rx) = tokio::sync::mpsc::channel(10_000);
             let (shutdowner, shutdown_rx) = tokio::sync::watch::channel(DataflowStatus::Running);
             let glob = s3_conn.pattern.map(|g| g.compile_matcher());
+            let parquet_desc = match encoding {
+                SourceDataEncoding::Single(DataEncoding {
+                    inner: DataEncodingInner::Parquet(desc),
+                    ..
+                }) => Some(desc),
+                _ => None,
+            };
 
             task::spawn(|| format!("s3_download:{}", source_id), {
                 let secrets_reader = Arc::clone(&connection_context.secrets_reader);
@@ -848,6 +953,7 @@ impl SourceReader for S3SourceReader {
                     connection_context.aws_external_id_prefix.clone(),
                     consumer_activator,
                     s3_conn.compression,
+                    parquet_desc,
                     metrics.clone(),
                     secrets_reader,
                 )
@@ -0,0 +1,239 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reading Apache Parquet objects from S3.
+//!
+//! The schema of a Parquet source is fixed during purification, by mapping the
+//! schema of an object in the bucket to a [`RelationDesc`]. Objects are then
+//! decoded by the source itself, reading only the columns in that
+//! [`RelationDesc`] and matching them to the columns of each object by name.
+
+use std::io::Cursor;
+
+use anyhow::{anyhow, bail, Context};
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use arrow2::temporal_conversions::timestamp_to_naive_datetime;
+use aws_sdk_s3::Client as S3Client;
+use chrono::{DateTime, Utc};
+use globset::GlobMatcher;
+
+use mz_repr::adt::date::Date;
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+use mz_secrets::SecretsReader;
+
+use crate::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
+
+/// The size of the footer of a Parquet file: the length of the file metadata,
+/// followed by the magic bytes.
+const FOOTER_SIZE: u64 = 8;
+
+/// The magic bytes that end every Parquet file.
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Describes the columns of the first object in `bucket` whose key matches
+/// `pattern`, which must be a Parquet file.
+///
+/// Only the footer of the object is downloaded.
+pub async fn fetch_parquet_desc(
+    aws: &AwsConfig,
+    external_id_prefix: Option<&AwsExternalIdPrefix>,
+    secrets_reader: &dyn SecretsReader,
+    bucket: &str,
+    pattern: Option<&GlobMatcher>,
+) -> Result<RelationDesc, anyhow::Error> {
+    let config = aws.load(external_id_prefix, None, secrets_reader).await;
+    let client = S3Client::new(&config);
+
+    let prefix = pattern.map(|g| super::find_prefix(g.glob().glob()));
+    let mut continuation_token = None;
+    let key = loop {
+        let response = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .set_prefix(prefix.clone())
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .with_context(|| format!("Unable to list S3 bucket {}", bucket))?;
+        let key = response
+            .contents
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|obj| obj.key)
+            .find(|k| pattern.map(|g| g.is_match(k)).unwrap_or(true));
+        if let Some(key) = key {
+            break key;
+        }
+        match response.next_continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => bail!(
+                "S3 bucket {} has no matching objects to read a Parquet schema from",
+                bucket
+            ),
+        }
+    };
+
+    let footer = get_object_suffix(&client, bucket, &key, FOOTER_SIZE).await?;
+    if footer.len() != 8 || &footer[4..] != PARQUET_MAGIC {
+        bail!("S3 object {}/{} is not a Parquet file", bucket, key);
+    }
+    let metadata_len = u32::from_le_bytes(footer[..4].try_into().expect("slice has 4 bytes"));
+    let tail =
+        get_object_suffix(&client, bucket, &key, u64::from(metadata_len) + FOOTER_SIZE).await?;
+    let metadata = read_metadata(&mut Cursor::new(tail))
+        .with_context(|| format!("reading Parquet metadata of S3 object {}/{}", bucket, key))?;
+    let schema = infer_schema(&metadata)?;
+    parquet_desc(&schema).with_context(|| format!("S3 object {}/{}", bucket, key))
+}
+
+/// Downloads the last `len` bytes of an object.
+async fn get_object_suffix(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    len: u64,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let object = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .range(format!("bytes=-{}", len))
+        .send()
+        .await
+        .with_context(|| format!("Unable to get S3 object {}/{}", bucket, key))?;
+    let bytes = object.body.collect().await?.into_bytes();
+    Ok(bytes.to_vec())
+}
+
+/// Maps the schema of a Parquet file to a [`RelationDesc`].
+pub fn parquet_desc(schema: &Schema) -> Result<RelationDesc, anyhow::Error> {
+    let mut desc = RelationDesc::empty();
+    for field in &schema.fields {
+        let scalar_type = scalar_type(&field.data_type).ok_or_else(|| {
+            anyhow!(
+                "column {} has unsupported Parquet type {:?}",
+                field.name,
+                field.data_type
+            )
+        })?;
+        desc = desc.with_column(field.name.as_str(), scalar_type.nullable(field.is_nullable));
+    }
+    Ok(desc)
+}
+
+fn scalar_type(data_type: &DataType) -> Option<ScalarType> {
+    Some(match data_type {
+        DataType::Boolean => ScalarType::Bool,
+        DataType::Int8 | DataType::Int16 => ScalarType::Int16,
+        DataType::Int32 => ScalarType::Int32,
+        DataType::Int64 => ScalarType::Int64,
+        DataType::Float32 => ScalarType::Float32,
+        DataType::Float64 => ScalarType::Float64,
+        DataType::Utf8 | DataType::LargeUtf8 => ScalarType::String,
+        DataType::Binary | DataType::LargeBinary => ScalarType::Bytes,
+        DataType::Date32 => ScalarType::Date,
+        DataType::Timestamp(_, None) => ScalarType::Timestamp,
+        DataType::Timestamp(_, Some(_)) => ScalarType::TimestampTz,
+        _ => return None,
+    })
+}
+
+/// Decodes the rows of a Parquet file, reading only the columns in `desc`.
+pub fn decode_parquet(bytes: Vec<u8>, desc: &RelationDesc) -> Result<Vec<Row>, anyhow::Error> {
+    let mut reader = Cursor::new(bytes);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+
+    let mut fields: Vec<Field> = Vec::with_capacity(desc.arity());
+    for (name, typ) in desc.iter() {
+        let field = schema
+            .fields
+            .iter()
+            .find(|f| f.name == name.as_str())
+            .ok_or_else(|| anyhow!("object has no column {}", name))?;
+        if scalar_type(&field.data_type).as_ref() != Some(&typ.scalar_type) {
+            bail!(
+                "column {} has Parquet type {:?}, which does not match the source's type {:?}",
+                name,
+                field.data_type,
+                typ.scalar_type
+            );
+        }
+        fields.push(field.clone());
+    }
+    let reader = FileReader::new(
+        reader,
+        metadata.row_groups,
+        Schema::from(fields),
+        None,
+        None,
+        None,
+    );
+
+    let mut rows = vec![];
+    for chunk in reader {
+        let chunk = chunk?;
+        for i in 0..chunk.len() {
+            let mut row = Row::default();
+            let mut packer = row.packer();
+            for (array, (name, typ)) in chunk.columns().iter().zip(desc.iter()) {
+                let datum = datum(array.as_ref(), i)?;
+                if datum.is_null() && !typ.nullable {
+                    bail!("column {} is unexpectedly null", name);
+                }
+                packer.push(datum);
+            }
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+fn datum(array: &dyn Array, i: usize) -> Result<Datum, anyhow::Error> {
+    if array.is_null(i) {
+        return Ok(Datum::Null);
+    }
+    let any = array.as_any();
+    Ok(match array.data_type() {
+        DataType::Boolean => Datum::from(downcast::<BooleanArray>(any).value(i)),
+        DataType::Int8 => Datum::Int16(downcast::<PrimitiveArray<i8>>(any).value(i).into()),
+        DataType::Int16 => Datum::Int16(downcast::<PrimitiveArray<i16>>(any).value(i)),
+        DataType::Int32 => Datum::Int32(downcast::<PrimitiveArray<i32>>(any).value(i)),
+        DataType::Int64 => Datum::Int64(downcast::<PrimitiveArray<i64>>(any).value(i)),
+        DataType::Float32 => Datum::Float32(downcast::<PrimitiveArray<f32>>(any).value(i).into()),
+        DataType::Float64 => Datum::Float64(downcast::<PrimitiveArray<f64>>(any).value(i).into()),
+        DataType::Utf8 => Datum::String(downcast::<Utf8Array<i32>>(any).value(i)),
+        DataType::LargeUtf8 => Datum::String(downcast::<Utf8Array<i64>>(any).value(i)),
+        DataType::Binary => Datum::Bytes(downcast::<BinaryArray<i32>>(any).value(i)),
+        DataType::LargeBinary => Datum::Bytes(downcast::<BinaryArray<i64>>(any).value(i)),
+        DataType::Date32 => Datum::Date(Date::from_unix_epoch(
+            downcast::<PrimitiveArray<i32>>(any).value(i),
+        )?),
+        DataType::Timestamp(unit, tz) => {
+            let ts =
+                timestamp_to_naive_datetime(downcast::<PrimitiveArray<i64>>(any).value(i), *unit);
+            match tz {
+                None => Datum::Timestamp(CheckedTimestamp::from_timestamplike(ts)?),
+                Some(_) => Datum::TimestampTz(CheckedTimestamp::from_timestamplike(
+                    DateTime::<Utc>::from_utc(ts, Utc),
+                )?),
+            }
+        }
+        data_type => bail!("unsupported Parquet type {:?}", data_type),
+    })
+}
+
+fn downcast<A: 'static>(array: &dyn std::any::Any) -> &A {
+    array
+        .downcast_ref()
+        .expect("array type matches its data type")
+}
@@ -0,0 +1,515 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functionality for creating SFTP sources.
+//!
+//! A single worker runs a task that polls a directory on the SFTP server and
+//! downloads each new file whose name matches the source's pattern, shuffling
+//! its contents into dataflow in chunks, just like the S3 source does for
+//! objects. `libssh2` is a blocking library, so each poll runs on a blocking
+//! thread.
+//!
+//! A file is only ingested once its size has stopped changing between two
+//! consecutive polls, as a file that is still growing is likely still being
+//! uploaded. Each file is ingested at most once per incarnation of the source:
+//! like S3 sources, SFTP sources do not remember which files they ingested
+//! across restarts.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek, SeekFrom};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use futures::FutureExt;
+use globset::GlobMatcher;
+use ssh2::{ErrorCode, Session, Sftp};
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{debug, info, warn};
+
+use mz_expr::PartitionId;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{SourceErrorClass, SourceRetry};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::encoding::SourceDataEncoding;
+use crate::types::sources::{MzOffset, SftpSourceConnection};
+
+/// Size of data chunks we send to dataflow
+const CHUNK_SIZE: usize = 4096;
+
+/// The default SSH port, for URLs that do not specify one.
+const DEFAULT_SFTP_PORT: u16 = 22;
+
+/// libssh2's error code for rejected credentials.
+const LIBSSH2_ERROR_AUTHENTICATION_FAILED: i32 = -18;
+/// libssh2's error code for a public key that the server did not accept.
+const LIBSSH2_ERROR_PUBLICKEY_UNVERIFIED: i32 = -19;
+
+enum InternalMessage {
+    /// A chunk of a file, or `None` to mark the end of a file.
+    Record(Option<Vec<u8>>),
+    Status(SourceStatusUpdate),
+}
+
+/// Information required to load data from an SFTP server
+pub struct SftpSourceReader {
+    /// Global source ID
+    id: GlobalId,
+    /// Receiver channel that ingests records
+    receiver_stream: Receiver<Result<InternalMessage, anyhow::Error>>,
+    /// Total number of records that this source has read
+    offset: u64,
+    // SFTP sources support single-threaded ingestion only, so only one of the
+    // `SftpSourceReader`s will actually produce data.
+    active_read_worker: bool,
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+impl SourceReader for SftpSourceReader {
+    type Key = ();
+    type Value = Option<Vec<u8>>;
+    type Diff = ();
+    type OffsetCommitter = LogCommitter;
+    type Connection = SftpSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        // a single arbitrary worker is responsible for polling the directory
+        let receiver = if active_read_worker {
+            let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(10_000);
+            let poller = DirectoryPoller::new(
+                source_id,
+                &connection,
+                dataflow_tx.clone(),
+                consumer_activator.clone(),
+            )?;
+            debug!(
+                "source_id={} polling sftp directory={} worker={}",
+                source_id,
+                poller.dir.display(),
+                worker_id
+            );
+            task::spawn(
+                || format!("sftp_poll:{}", source_id),
+                poll_directory_task(
+                    poller,
+                    connection,
+                    Arc::clone(&connection_context.secrets_reader),
+                    dataflow_tx,
+                    consumer_activator,
+                ),
+            );
+            dataflow_rx
+        } else {
+            let (_dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(1);
+            dataflow_rx
+        };
+
+        Ok((
+            SftpSourceReader {
+                id: source_id,
+                receiver_stream: receiver,
+                offset: 0,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+
+    fn get_next_message(
+        &mut self,
+    ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Ok(NextMessage::Ready(
+                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
+                ));
+            }
+            return Ok(NextMessage::Finished);
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(Ok(InternalMessage::Record(record)))) => {
+                self.offset += 1;
+                Ok(NextMessage::Ready(SourceMessageType::Finalized(
+                    SourceMessage {
+                        output: 0,
+                        partition: PartitionId::None,
+                        offset: MzOffset {
+                            offset: self.offset,
+                        },
+                        upstream_time_millis: None,
+                        key: (),
+                        value: record,
+                        headers: None,
+                        specific_diff: (),
+                    },
+                )))
+            }
+            Some(Some(Ok(InternalMessage::Status(update)))) => {
+                Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
+            }
+            Some(Some(Err(e))) => Err(SourceReaderError {
+                inner: SourceErrorDetails::Other(format!("{:#}", e)),
+            }),
+            None => Ok(NextMessage::Pending),
+            Some(None) => Ok(NextMessage::Finished),
+        }
+    }
+}
+
+impl Drop for SftpSourceReader {
+    fn drop(&mut self) {
+        debug!("source_id={} Dropping SftpSourceReader", self.id);
+    }
+}
+
+/// The credentials to authenticate to the server with.
+enum Credentials {
+    Password(String),
+    PrivateKey(String),
+}
+
+/// Polls a directory on an SFTP server for new files.
+struct DirectoryPoller {
+    source_id: GlobalId,
+    host: String,
+    port: u16,
+    /// The directory to poll.
+    dir: PathBuf,
+    pattern: Option<GlobMatcher>,
+    /// The current session, if connected.
+    session: Option<Session>,
+    /// The files that have been ingested.
+    ingested: HashSet<PathBuf>,
+    /// The sizes of the files that were seen by the previous poll but have not
+    /// yet been ingested.
+    sizes: HashMap<PathBuf, u64>,
+    /// The file that was being ingested when the previous poll failed, and the
+    /// offset up to which its contents were sent to dataflow.
+    partial: Option<(PathBuf, u64)>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    activator: SyncActivator,
+}
+
+impl DirectoryPoller {
+    fn new(
+        source_id: GlobalId,
+        connection: &SftpSourceConnection,
+        tx: Sender<Result<InternalMessage, anyhow::Error>>,
+        activator: SyncActivator,
+    ) -> Result<Self, anyhow::Error> {
+        let url = url::Url::parse(&connection.url)
+            .with_context(|| format!("invalid SFTP URL {}", connection.url))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("SFTP URL {} does not specify a host", connection.url))?;
+        let dir = match url.path() {
+            "" => "/",
+            path => path,
+        };
+        Ok(DirectoryPoller {
+            source_id,
+            host: host.to_string(),
+            port: url.port().unwrap_or(DEFAULT_SFTP_PORT),
+            dir: PathBuf::from(dir),
+            pattern: connection.pattern.as_ref().map(|g| g.compile_matcher()),
+            session: None,
+            ingested: HashSet::new(),
+            sizes: HashMap::new(),
+            partial: None,
+            tx,
+            activator,
+        })
+    }
+
+    fn connect(&self, user: &str, credentials: &Credentials) -> Result<Session, anyhow::Error> {
+        let tcp = TcpStream::connect((self.host.as_str(), self.port))?;
+        let mut session = Session::new()?;
+        session.set_tcp_stream(tcp);
+        session.handshake()?;
+        match credentials {
+            Credentials::Password(password) => session.userauth_password(user, password)?,
+            Credentials::PrivateKey(key) => {
+                session.userauth_pubkey_memory(user, None, key, None)?
+            }
+        }
+        Ok(session)
+    }
+
+    /// Ingests the new files in the directory, connecting to the server first
+    /// if necessary.
+    ///
+    /// Returns `false` if the source has been dropped.
+    fn poll(&mut self, user: &str, credentials: &Credentials) -> Result<bool, anyhow::Error> {
+        let result = self.poll_inner(user, credentials);
+        if result.is_err() {
+            // The session may be unusable after an error, so start over with a
+            // fresh one.
+            self.session = None;
+        }
+        result
+    }
+
+    fn poll_inner(&mut self, user: &str, credentials: &Credentials) -> Result<bool, anyhow::Error> {
+        if self.session.is_none() {
+            let session = self.connect(user, credentials).with_context(|| {
+                format!(
+                    "error connecting to SFTP server {}:{}",
+                    self.host, self.port
+                )
+            })?;
+            self.session = Some(session);
+        }
+        let sftp = self.session.as_ref().expect("known to exist").sftp()?;
+
+        // Finish ingesting the file that the previous poll was interrupted in
+        // the middle of, before moving on to any other file.
+        if let Some((path, _)) = self.partial.clone() {
+            if !self.ingest(&sftp, &path)? {
+                return Ok(false);
+            }
+        }
+
+        let mut entries = sftp
+            .readdir(&self.dir)
+            .with_context(|| format!("error listing SFTP directory {}", self.dir.display()))?;
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut sizes = HashMap::new();
+        for (path, stat) in entries {
+            if !stat.is_file() || self.ingested.contains(&path) {
+                continue;
+            }
+            let name = match path.file_name() {
+                Some(name) => name.to_string_lossy(),
+                None => continue,
+            };
+            if let Some(pattern) = &self.pattern {
+                if !pattern.is_match(&*name) {
+                    continue;
+                }
+            }
+            let size = stat.size.unwrap_or(0);
+            if self.sizes.get(&path) != Some(&size) {
+                sizes.insert(path, size);
+                continue;
+            }
+            if !self.ingest(&sftp, &path)? {
+                return Ok(false);
+            }
+        }
+        self.sizes = sizes;
+        Ok(true)
+    }
+
+    /// Sends the contents of the file at `path` to dataflow, resuming from
+    /// where the previous attempt left off, if any.
+    ///
+    /// Returns `false` if the source has been dropped.
+    fn ingest(&mut self, sftp: &Sftp, path: &Path) -> Result<bool, anyhow::Error> {
+        let mut offset = match &self.partial {
+            Some((partial, offset)) if partial == path => *offset,
+            _ => 0,
+        };
+        self.partial = Some((path.to_owned(), offset));
+        debug!(
+            "source_id={} ingesting sftp file {} from offset {}",
+            self.source_id,
+            path.display(),
+            offset
+        );
+
+        let mut file = sftp
+            .open(path)
+            .with_context(|| format!("error opening SFTP file {}", path.display()))?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buf = vec![0; CHUNK_SIZE];
+        loop {
+            let n = file
+                .read(&mut buf)
+                .with_context(|| format!("error reading SFTP file {}", path.display()))?;
+            if n == 0 {
+                break;
+            }
+            if !self.send(InternalMessage::Record(Some(buf[..n].to_vec()))) {
+                return Ok(false);
+            }
+            offset += u64::try_from(n).expect("chunk size fits into u64");
+            self.partial = Some((path.to_owned(), offset));
+        }
+
+        self.partial = None;
+        self.ingested.insert(path.to_owned());
+        // Mark the end of the file, so that decoders that track per-file state,
+        // like CSV headers, start over.
+        Ok(self.send(InternalMessage::Record(None)))
+    }
+
+    /// Sends `message` to dataflow.
+    ///
+    /// Returns `false` if the source has been dropped.
+    fn send(&self, message: InternalMessage) -> bool {
+        if self.tx.blocking_send(Ok(message)).is_err() {
+            return false;
+        }
+        self.activator
+            .activate()
+            .expect("sftp reader activation failed");
+        true
+    }
+}
+
+async fn read_credentials(
+    connection: &SftpSourceConnection,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<(String, Credentials), anyhow::Error> {
+    let user = connection.user.get_string(secrets_reader).await?;
+    let credentials = match (connection.private_key, connection.password) {
+        (Some(private_key), _) => {
+            Credentials::PrivateKey(secrets_reader.read_string(private_key).await?)
+        }
+        (None, Some(password)) => {
+            Credentials::Password(secrets_reader.read_string(password).await?)
+        }
+        (None, None) => unreachable!("SFTP sources always specify a password or private key"),
+    };
+    Ok((user, credentials))
+}
+
+/// Polls the directory every `poll_interval`, retrying failed polls with a
+/// backoff, until the source is dropped.
+async fn poll_directory_task(
+    mut poller: DirectoryPoller,
+    connection: SftpSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    tx: Sender<Result<InternalMessage, anyhow::Error>>,
+    activator: SyncActivator,
+) {
+    let source_id = poller.source_id;
+    let mut retry = SourceRetry::default();
+    loop {
+        // Credentials are read anew for every poll, so that rotated secrets
+        // take effect the next time we connect.
+        let result = match read_credentials(&connection, &*secrets_reader).await {
+            Ok((user, credentials)) => {
+                let (returned, result) = task::spawn_blocking(
+                    || format!("sftp_poll:{}", source_id),
+                    move || {
+                        let result = poller.poll(&user, &credentials);
+                        (poller, result)
+                    },
+                )
+                .await
+                .expect("sftp poller panicked");
+                poller = returned;
+                result
+            }
+            Err(e) => Err(e),
+        };
+
+        let delay = match result {
+            Ok(false) => return,
+            Ok(true) => {
+                retry.reset();
+                connection.poll_interval
+            }
+            Err(e) => {
+                let class = error_class(&e);
+                let backoff = match retry.on_error(class) {
+                    Some(backoff) => backoff,
+                    None => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                warn!(
+                    "polling source {} interrupted by {} error, retrying in {:?}: {:#}",
+                    source_id,
+                    class.name(),
+                    backoff,
+                    e
+                );
+                let next_retry_at = (SystemTime::now() + backoff)
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time after Unix epoch")
+                    .as_millis();
+                let update = SourceStatusUpdate::retrying(
+                    &format!("{:#}", e),
+                    class,
+                    u64::try_from(next_retry_at).expect("retry time fits into u64"),
+                );
+                if tx.send(Ok(InternalMessage::Status(update))).await.is_err() {
+                    return;
+                }
+                activator.activate().expect("sftp reader activation failed");
+                backoff
+            }
+        };
+
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = tokio::time::sleep(delay) => {}
+        }
+    }
+    info!("source_id={} stopped polling sftp directory", source_id);
+}
+
+/// Classifies an error encountered while polling the directory.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    let auth_failed = err.chain().any(|e| match e.downcast_ref::<ssh2::Error>() {
+        Some(e) => matches!(
+            e.code(),
+            ErrorCode::Session(
+                LIBSSH2_ERROR_AUTHENTICATION_FAILED | LIBSSH2_ERROR_PUBLICKEY_UNVERIFIED
+            )
+        ),
+        None => false,
+    });
+    if auth_failed {
+        SourceErrorClass::Auth
+    } else {
+        SourceErrorClass::Transient
+    }
+}
@@ -63,6 +63,7 @@ use crate::source::antichain::MutableOffsetAntichain;
 use crate::source::antichain::OffsetAntichain;
 use crate::source::healthcheck::Healthchecker;
 use crate::source::metrics::SourceBaseMetrics;
+use crate::source::rate_limit::IngestRateLimiter;
 use crate::source::reclock::ReclockFollower;
 use crate::source::reclock::ReclockOperator;
 use crate::source::types::SourceOutput;
@@ -75,7 +76,7 @@ use crate::source::util::async_source;
 use crate::types::connections::ConnectionContext;
 use crate::types::errors::SourceError;
 use crate::types::sources::encoding::SourceDataEncoding;
-use crate::types::sources::MzOffset;
+use crate::types::sources::{IngestRateLimit, MzOffset, TimestampStrategy};
 
 // Interval after which the source operator will yield control.
 const YIELD_INTERVAL: Duration = Duration::from_millis(10);
@@ -97,6 +98,10 @@ pub struct RawSourceCreationConfig {
     /// Granularity with which timestamps should be closed (and capabilities
     /// downgraded).
     pub timestamp_interval: Duration,
+    /// How the timestamps that offsets are bound to are chosen.
+    pub timestamp_strategy: TimestampStrategy,
+    /// The maximum rate at which this worker reads from the source.
+    pub max_ingest_rate: IngestRateLimit,
     /// Data encoding
     pub encoding: SourceDataEncoding,
     /// The function to return a now time.
@@ -109,6 +114,9 @@ pub struct RawSourceCreationConfig {
     pub resume_upper: Antichain<Timestamp>,
     /// A handle to the persist client cache
     pub persist_clients: Arc<Mutex<PersistClientCache>>,
+    /// Counts of the messages read by sources on this worker, keyed by source
+    /// and size bucket, to be reported to the controller.
+    pub message_sizes: Rc<RefCell<HashMap<(GlobalId, u64), i64>>>,
 }
 
 /// A batch of messages from a source reader, along with the batch upper, the
@@ -266,12 +274,15 @@ where
         worker_id,
         worker_count,
         timestamp_interval,
+        timestamp_strategy: _,
+        max_ingest_rate,
         encoding: _,
         storage_metadata,
         resume_upper: _,
         base_metrics: _,
         now,
         persist_clients,
+        message_sizes: _,
     } = config;
     Box::pin(async_stream::stream!({
         let mut healthchecker = if storage_metadata.status_shard.is_some() {
@@ -317,6 +328,8 @@ where
         let mut emission_interval = tokio::time::interval(timestamp_interval / 5);
         emission_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
+        let mut rate_limiter = IngestRateLimiter::new(max_ingest_rate, Instant::now());
+
         let mut untimestamped_messages = HashMap::<_, Vec<_>>::new();
         let mut unconsumed_partitions = Vec::new();
         let mut source_errors = vec![];
@@ -372,7 +385,22 @@ where
                                     if let Some(prev_offset) = prev_offset {
                                         assert!(offset_frontier >= prev_offset, "offset regressed");
                                     }
+                                    let len = message.key.len().unwrap_or(0) + message.value.len().unwrap_or(0);
                                     untimestamped_messages.entry(pid).or_default().push((message, offset));
+
+                                    // Hold off on reading the next message
+                                    // if the source is over its rate limit.
+                                    let delay = rate_limiter.consume(len, Instant::now());
+                                    if delay > Duration::ZERO {
+                                        tokio::time::sleep(delay).await;
+                                    }
+                                }
+                                SourceMessageType::Progress(pid, upper) => {
+                                    source_upper.insert(pid.clone(), upper);
+                                    let prev_offset = batch_upper.insert(pid, upper);
+                                    if let Some(prev_offset) = prev_offset {
+                                        assert!(upper >= prev_offset, "offset regressed");
+                                    }
                                 }
                                 SourceMessageType::SourceStatus(update) => {
                                     if let Some(healthchecker) = &mut healthchecker {
@@ -488,12 +516,15 @@ where
         worker_id,
         worker_count,
         timestamp_interval: _,
+        timestamp_strategy: _,
+        max_ingest_rate: _,
         encoding,
         storage_metadata: _,
         resume_upper,
         base_metrics,
         now: now_fn,
         persist_clients: _,
+        message_sizes: _,
     } = config;
 
     let (stream, capability) = async_source(
@@ -920,12 +951,15 @@ where
         worker_id,
         worker_count,
         timestamp_interval,
+        timestamp_strategy,
+        max_ingest_rate: _,
         encoding: _,
         storage_metadata,
         resume_upper,
         base_metrics: _,
         now,
         persist_clients,
+        message_sizes: _,
     } = config;
 
     let chosen_worker = (id.hashed() % worker_count as u64) as usize;
@@ -978,6 +1012,7 @@ where
                 storage_metadata.clone(),
                 now.clone(),
                 timestamp_interval.clone(),
+                timestamp_strategy,
                 as_of,
                 id,
                 "remap",
@@ -1174,12 +1209,15 @@ where
         worker_id,
         worker_count,
         timestamp_interval: _,
+        timestamp_strategy: _,
+        max_ingest_rate: _,
         encoding: _,
         storage_metadata: _,
         resume_upper,
         base_metrics,
         now: _,
         persist_clients: _,
+        message_sizes,
     } = config;
 
     let bytes_read_counter = base_metrics.bytes_read.clone();
@@ -1297,6 +1335,9 @@ where
             let mut bytes_read = 0;
             // Accumulate updates to offsets for system table metrics collection
             let mut metric_updates = HashMap::new();
+            // Accumulate message counts per size bucket for the message size
+            // histogram
+            let mut size_updates = HashMap::new();
 
             trace!(
                 "reclock({id}) {worker_id}/{worker_count}: \
@@ -1347,6 +1388,7 @@ where
                         handle_message::<S>(
                             message,
                             &mut bytes_read,
+                            &mut size_updates,
                             &cap_set,
                             &mut output,
                             &mut metric_updates,
@@ -1379,6 +1421,12 @@ where
             }
 
             bytes_read_counter.inc_by(bytes_read as u64);
+            if !size_updates.is_empty() {
+                let mut message_sizes = message_sizes.borrow_mut();
+                for (size, count) in size_updates {
+                    *message_sizes.entry((id, size)).or_insert(0) += count;
+                }
+            }
             source_metrics.record_partition_offsets(metric_updates);
 
             // This is correct for totally ordered times because there can be at
@@ -1488,6 +1536,12 @@ where
     ((ok_streams, err_stream), None)
 }
 
+/// Returns the size bucket of a message of `len` bytes, i.e., the smallest power
+/// of two that is at least `len`.
+fn message_size_bucket(len: usize) -> u64 {
+    u64::cast_from(len).next_power_of_two()
+}
+
 /// Take `message` and assign it the appropriate timestamps and push it into the
 /// dataflow layer, if possible.
 ///
@@ -1496,6 +1550,7 @@ where
 fn handle_message<S: SourceReader>(
     message: SourceMessage<S::Key, S::Value, S::Diff>,
     bytes_read: &mut usize,
+    size_updates: &mut HashMap<u64, i64>,
     cap_set: &CapabilitySet<Timestamp>,
     output: &mut OutputHandle<
         Timestamp,
@@ -1524,12 +1579,18 @@ fn handle_message<S: SourceReader>(
     // Entry for partition_metadata is guaranteed to exist as messages are only
     // processed after we have updated the partition_metadata for a partition
     // and created a partition queue for it.
-    if let Some(len) = key.len() {
+    let key_len = key.len();
+    let out_len = out.len();
+    if let Some(len) = key_len {
         *bytes_read += len;
     }
-    if let Some(len) = out.len() {
+    if let Some(len) = out_len {
         *bytes_read += len;
     }
+    if key_len.is_some() || out_len.is_some() {
+        let len = key_len.unwrap_or(0) + out_len.unwrap_or(0);
+        *size_updates.entry(message_size_bucket(len)).or_insert(0) += 1;
+    }
     let ts_cap = cap_set.delayed(&ts);
     output.session(&ts_cap).give((
         message.output,
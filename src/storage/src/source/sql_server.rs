@@ -0,0 +1,791 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that replicates tables from SQL Server using change data capture
+//! (CDC).
+//!
+//! SQL Server's capture job copies the changes to each captured table from
+//! the transaction log into a change table, tagged with the log sequence
+//! number (LSN) of the commit of the transaction that made them. The source
+//! snapshots the tables, then polls the change tables for changes with larger
+//! LSNs, and uses the commit LSN, packed into a `u64`, as its offset.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use futures::{FutureExt, TryStreamExt};
+use timely::scheduling::SyncActivator;
+use tokio::runtime::Handle as TokioHandle;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{error, info};
+
+use mz_expr::{MirScalarExpr, PartitionId};
+use mz_ore::task;
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
+use mz_sql_server_util::desc::{SqlServerColumnDesc, SqlServerTableDesc};
+use mz_sql_server_util::{quote_identifier, Client, Lsn};
+
+use super::metrics::SourceBaseMetrics;
+use crate::source::commit::LogCommitter;
+use crate::source::healthcheck::SourceStatusUpdate;
+use crate::source::retry::{
+    retry_replication, ReplicationFailure, ReplicationTask, SourceErrorClass,
+    REPLICATION_PROGRESS_THRESHOLD,
+};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+use crate::types::connections::ConnectionContext;
+use crate::types::errors::SourceErrorDetails;
+use crate::types::sources::{encoding::SourceDataEncoding, MzOffset, SqlServerSourceConnection};
+
+/// How often to poll the change tables for new changes
+static POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// SQL Server error numbers that mean the source can never make progress
+/// again.
+///
+/// See <https://learn.microsoft.com/en-us/sql/relational-databases/errors-events/database-engine-events-and-errors>.
+const DEFINITE_ERROR_CODES: &[u32] = &[
+    208,  // Invalid object name: a table or its capture instance no longer exists
+    3952, // Snapshot isolation is not allowed in the database
+];
+
+/// SQL Server error numbers that indicate the source failed to authenticate.
+const AUTH_ERROR_CODES: &[u32] = &[
+    18456, // Login failed
+];
+
+enum ReplicationError {
+    /// This error is definite: this source is permanently wedged.
+    /// Returning a definite error will cause the collection to become un-queryable.
+    Definite(anyhow::Error),
+    /// This error may or may not resolve itself in the future, and
+    /// should be retried instead of being added to the output.
+    Indefinite(anyhow::Error),
+}
+
+impl From<tiberius::error::Error> for ReplicationError {
+    fn from(err: tiberius::error::Error) -> Self {
+        match &err {
+            tiberius::error::Error::Server(e) if DEFINITE_ERROR_CODES.contains(&e.code()) => {
+                Self::Definite(err.into())
+            }
+            _ => Self::Indefinite(err.into()),
+        }
+    }
+}
+
+macro_rules! try_definite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Definite(err.into())),
+        }
+    };
+}
+macro_rules! try_indefinite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Indefinite(err.into())),
+        }
+    };
+}
+
+// Message used to communicate between `get_next_message` and the tokio task
+enum InternalMessage {
+    Err(SourceReaderError),
+    Status(SourceStatusUpdate),
+    Value {
+        output: usize,
+        value: Row,
+        offset: MzOffset,
+        diff: Diff,
+        end: bool,
+    },
+}
+
+/// Information required to sync data from SQL Server
+pub struct SqlServerSourceReader {
+    receiver_stream: Receiver<InternalMessage>,
+
+    // SQL Server sources support single-threaded ingestion only, so only one
+    // of the `SqlServerSourceReader`s will actually produce data.
+    active_read_worker: bool,
+
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+}
+
+/// Information about an ingested upstream table
+struct SourceTable {
+    /// The source output index of this table
+    output_index: usize,
+    /// The description of this table at the time the source was created
+    desc: SqlServerTableDesc,
+    /// The scalar expressions required to cast the text encoded columns received from SQL Server
+    /// into the target relational types
+    casts: Vec<MirScalarExpr>,
+}
+
+/// An internal struct held by the spawned tokio task
+struct SqlServerTaskInfo {
+    source_id: GlobalId,
+    connection_config: mz_sql_server_util::Config,
+    /// The offset from which to read changes, or zero if the source has yet
+    /// to snapshot its tables.
+    offset: u64,
+    /// The ingested tables, by schema and table name.
+    source_tables: BTreeMap<(String, String), SourceTable>,
+    row_sender: RowSender,
+    sender: Sender<InternalMessage>,
+}
+
+impl SourceReader for SqlServerSourceReader {
+    type Key = ();
+    type Value = Row;
+    // SQL Server can produce deletes that cause retractions
+    type Diff = Diff;
+    type OffsetCommitter = LogCommitter;
+    type Connection = SqlServerSourceConnection;
+
+    fn new(
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        connection: Self::Connection,
+        start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(50_000);
+
+        let start_offset = start_offsets
+            .into_iter()
+            .find_map(|(pid, offset)| {
+                if pid == PartitionId::None {
+                    offset
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        let connection_config = TokioHandle::current()
+            .block_on(
+                connection
+                    .connection
+                    .config(&*connection_context.secrets_reader),
+            )
+            .expect("SQL Server connection unexpectedly missing secrets");
+
+        if active_read_worker {
+            let mut source_tables = BTreeMap::new();
+            let tables_iter = connection.details.tables.into_iter();
+            for (i, (desc, casts)) in tables_iter.zip(connection.table_casts).enumerate() {
+                let key = (desc.schema_name.clone(), desc.name.clone());
+                let source_table = SourceTable {
+                    output_index: i + 1,
+                    desc,
+                    casts,
+                };
+                source_tables.insert(key, source_table);
+            }
+
+            let task_info = SqlServerTaskInfo {
+                source_id,
+                connection_config,
+                offset: start_offset.offset,
+                source_tables,
+                row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
+                sender: dataflow_tx,
+            };
+
+            task::spawn(
+                || format!("sql_server_source:{}", source_id),
+                sql_server_replication_loop(task_info),
+            );
+        }
+
+        Ok((
+            Self {
+                receiver_stream: dataflow_rx,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+
+    fn get_next_message(
+        &mut self,
+    ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Ok(NextMessage::Ready(
+                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
+                ));
+            }
+            return Ok(NextMessage::Finished);
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(InternalMessage::Value {
+                output,
+                value,
+                offset,
+                diff,
+                end,
+            })) => {
+                let message = SourceMessage {
+                    output,
+                    partition: PartitionId::None,
+                    offset,
+                    upstream_time_millis: None,
+                    key: (),
+                    value,
+                    headers: None,
+                    specific_diff: diff,
+                };
+                if end {
+                    Ok(NextMessage::Ready(SourceMessageType::Finalized(message)))
+                } else {
+                    Ok(NextMessage::Ready(SourceMessageType::InProgress(message)))
+                }
+            }
+            Some(Some(InternalMessage::Err(e))) => Err(e),
+            Some(Some(InternalMessage::Status(update))) => {
+                Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
+            }
+            None => Ok(NextMessage::Pending),
+            Some(None) => Ok(NextMessage::Finished),
+        }
+    }
+}
+
+/// Defers to `sql_server_replication_loop_inner` and sends errors through the channel if they occur
+async fn sql_server_replication_loop(mut task_info: SqlServerTaskInfo) {
+    match sql_server_replication_loop_inner(&mut task_info).await {
+        Ok(()) => {}
+        Err(e) => {
+            // Drop the send error, as we have no way of communicating back to the
+            // source operator if the channel is gone.
+            let _ = task_info
+                .row_sender
+                .sender
+                .send(InternalMessage::Err(e))
+                .await;
+            task_info
+                .row_sender
+                .activator
+                .activate()
+                .expect("sql server reader activation failed");
+        }
+    }
+}
+
+/// Core logic
+async fn sql_server_replication_loop_inner(
+    task_info: &mut SqlServerTaskInfo,
+) -> Result<(), SourceReaderError> {
+    if task_info.offset == 0 {
+        match task_info.produce_snapshot().await {
+            Ok(_) => {
+                info!(
+                    "replication snapshot for source {} succeeded",
+                    &task_info.source_id
+                );
+            }
+            Err(ReplicationError::Indefinite(e)) => {
+                // Like the postgres source, panic to discard the partially
+                // emitted snapshot. The restarted storaged instance will
+                // produce the snapshot afresh.
+                panic!(
+                    "replication snapshot for source {} failed: {}",
+                    &task_info.source_id, e
+                );
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Initialization(e.to_string()),
+                })
+            }
+        }
+    }
+
+    retry_replication(REPLICATION_PROGRESS_THRESHOLD, task_info).await
+}
+
+#[async_trait::async_trait]
+impl ReplicationTask for SqlServerTaskInfo {
+    fn source_id(&self) -> GlobalId {
+        self.source_id
+    }
+
+    async fn replicate(&mut self) -> Result<(), ReplicationFailure> {
+        match self.produce_replication().await {
+            Ok(()) => Ok(()),
+            Err(ReplicationError::Indefinite(e)) => {
+                let class = error_class(&e);
+                Err(ReplicationFailure::Indefinite(e, class))
+            }
+            Err(ReplicationError::Definite(e)) => Err(ReplicationFailure::Definite(e)),
+        }
+    }
+
+    async fn send_status(&mut self, update: SourceStatusUpdate) {
+        // Drop the send error, as we have no way of communicating back to the
+        // source operator if the channel is gone.
+        let _ = self
+            .row_sender
+            .sender
+            .send(InternalMessage::Status(update))
+            .await;
+        self.row_sender
+            .activator
+            .activate()
+            .expect("sql server reader activation failed");
+    }
+}
+
+/// Classifies an indefinite error encountered during replication.
+fn error_class(err: &anyhow::Error) -> SourceErrorClass {
+    match err.downcast_ref::<tiberius::error::Error>() {
+        Some(tiberius::error::Error::Server(e)) if AUTH_ERROR_CODES.contains(&e.code()) => {
+            SourceErrorClass::Auth
+        }
+        _ => SourceErrorClass::Transient,
+    }
+}
+
+struct RowMessage {
+    output_index: usize,
+    row: Row,
+    offset: MzOffset,
+    diff: Diff,
+}
+
+/// A type that makes it easy to correctly send inserts and deletes.
+///
+/// Note: `RowSender::delete/insert` should be called with the same offset
+/// until `close_offset` is called, which should be called and awaited before
+/// dropping the `RowSender` or moving onto a new offset.
+struct RowSender {
+    sender: Sender<InternalMessage>,
+    activator: SyncActivator,
+    buffered_message: Option<RowMessage>,
+}
+
+impl RowSender {
+    /// Create a new `RowSender`.
+    fn new(sender: Sender<InternalMessage>, activator: SyncActivator) -> Self {
+        Self {
+            sender,
+            activator,
+            buffered_message: None,
+        }
+    }
+
+    /// Insert a row at an offset.
+    async fn insert(&mut self, output_index: usize, row: Row, offset: MzOffset) {
+        self.buffer(output_index, row, offset, 1).await;
+    }
+
+    /// Delete a row at an offset.
+    async fn delete(&mut self, output_index: usize, row: Row, offset: MzOffset) {
+        self.buffer(output_index, row, offset, -1).await;
+    }
+
+    async fn buffer(&mut self, output_index: usize, row: Row, offset: MzOffset, diff: Diff) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, false).await;
+        }
+        self.buffered_message = Some(RowMessage {
+            output_index,
+            row,
+            offset,
+            diff,
+        });
+    }
+
+    /// Finalize an offset, making sure all messages that my be buffered are
+    /// flushed, and that the last message sent is marked as closing the
+    /// offset.
+    async fn close_offset(&mut self, offset: MzOffset) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.offset, offset);
+            self.send_row(buffered, true).await;
+        }
+    }
+
+    async fn send_row(&self, message: RowMessage, end: bool) {
+        // a closed receiver means the source has been shutdown
+        // (dropped or the process is dying), so just continue on
+        // without activation
+        if let Ok(_) = self
+            .sender
+            .send(InternalMessage::Value {
+                output: message.output_index,
+                value: message.row,
+                offset: message.offset,
+                diff: message.diff,
+                end,
+            })
+            .await
+        {
+            self.activator
+                .activate()
+                .expect("sql server reader activation failed");
+        }
+    }
+}
+
+/// A change to an ingested table, as recorded in its change table.
+struct Change {
+    /// The LSN of the commit of the transaction that made the change.
+    lsn: Lsn,
+    /// The order of the change within its transaction.
+    seqval: Vec<u8>,
+    /// The kind of change: 1 for deletes, 2 for inserts, and 3 and 4 for the
+    /// old and new values of updated rows, respectively.
+    operation: i32,
+    output_index: usize,
+    row: Row,
+}
+
+impl SqlServerTaskInfo {
+    /// Validates that all expected tables are still captured with the same
+    /// capture instance and columns
+    fn validate_tables(&self, tables: Vec<SqlServerTableDesc>) -> Result<(), anyhow::Error> {
+        let upstream_tables: BTreeMap<_, _> = tables
+            .into_iter()
+            .map(|t| ((t.schema_name.clone(), t.name.clone()), t))
+            .collect();
+        for (name, info) in &self.source_tables {
+            match upstream_tables.get(name) {
+                Some(desc) if desc == &info.desc => {}
+                Some(desc) => {
+                    error!(
+                        "Error validating SQL Server table. Expected: {:?} Actual: {:?}",
+                        &info.desc, desc
+                    );
+                    bail!(
+                        "Schema for table {}.{} differs, recreate Materialize source to use new schema",
+                        info.desc.schema_name,
+                        info.desc.name
+                    )
+                }
+                None => bail!(
+                    "SQL Server database missing expected captured table {}.{}",
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Produces the initial snapshot of the tables and determines the LSN
+    /// from which to read changes.
+    async fn produce_snapshot(&mut self) -> Result<(), ReplicationError> {
+        let mut lock_client = try_indefinite!(self.connection_config.connect().await);
+        let mut client = try_indefinite!(self.connection_config.connect().await);
+
+        let tables = try_indefinite!(mz_sql_server_util::schema_info(&mut lock_client).await);
+        try_definite!(self.validate_tables(tables));
+
+        // Holding shared locks on the tables prevents any transaction that
+        // writes to them from committing, so the snapshot transaction that
+        // the second client starts in the meantime reflects exactly the
+        // transactions that committed before the end of the transaction log.
+        // Reads under snapshot isolation take no locks, so the locks are
+        // released as soon as the snapshot transaction has started.
+        query_drop(&mut lock_client, "BEGIN TRANSACTION").await?;
+        for info in self.source_tables.values() {
+            let query = format!(
+                "SELECT TOP 0 1 FROM {} WITH (TABLOCK, HOLDLOCK)",
+                table_name(&info.desc)
+            );
+            query_drop(&mut lock_client, &query).await?;
+        }
+        query_drop(&mut client, "SET TRANSACTION ISOLATION LEVEL SNAPSHOT").await?;
+        query_drop(&mut client, "BEGIN TRANSACTION").await?;
+        // A snapshot transaction reads the data as of its first read.
+        if let Some(info) = self.source_tables.values().next() {
+            let query = format!("SELECT TOP 1 1 FROM {}", table_name(&info.desc));
+            query_drop(&mut client, &query).await?;
+        }
+        let log_end = try_indefinite!(mz_sql_server_util::log_end_lsn(&mut lock_client).await);
+        query_drop(&mut lock_client, "COMMIT").await?;
+        try_indefinite!(lock_client.close().await);
+        let next_offset = try_definite!(log_end.to_offset());
+
+        // Every transaction in the snapshot has an LSN below the end of the
+        // log, so the snapshot is emitted at the preceding offset and
+        // replication starts with the changes at or after the end of the log.
+        let offset = MzOffset::from(try_definite!(next_offset.checked_sub(1).ok_or_else(
+            || anyhow!("the end of the transaction log, {log_end}, precedes every LSN")
+        )));
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        for info in self.source_tables.values() {
+            let query = format!(
+                "SELECT {} FROM {}",
+                text_columns(&info.desc.columns),
+                table_name(&info.desc)
+            );
+            let mut stream = client.simple_query(query).await?.into_row_stream();
+            while let Some(row) = stream.try_next().await? {
+                let mut datums = datum_vec.borrow();
+                for i in 0..info.desc.columns.len() {
+                    datums.push(match row.get::<&str, _>(i) {
+                        Some(text) => Datum::String(text),
+                        None => Datum::Null,
+                    });
+                }
+                let row = try_definite!(cast_row(&info.casts, &datums));
+                drop(datums);
+                self.row_sender.insert(info.output_index, row, offset).await;
+            }
+        }
+        query_drop(&mut client, "COMMIT").await?;
+        try_indefinite!(client.close().await);
+
+        // close the current `row_sender` context after we are sure we have not errored
+        // out (in the commit).
+        self.row_sender.close_offset(offset).await;
+        self.offset = next_offset;
+        Ok(())
+    }
+
+    async fn produce_replication(&mut self) -> Result<(), ReplicationError> {
+        use ReplicationError::*;
+
+        let mut client = try_indefinite!(self.connection_config.connect().await);
+        let tables = try_indefinite!(mz_sql_server_util::schema_info(&mut client).await);
+        try_definite!(self.validate_tables(tables));
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        loop {
+            if self.sender.is_closed() {
+                // shutdown initiated elsewhere
+                return Ok(());
+            }
+
+            let from = Lsn::from_offset(self.offset);
+            let to = match try_indefinite!(mz_sql_server_util::max_lsn(&mut client).await) {
+                Some(to) if to >= from => to,
+                _ => {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            // The capture job removes changes once they are older than the
+            // retention period, after which the source cannot tell which
+            // changes it has missed.
+            for info in self.source_tables.values() {
+                let min = try_indefinite!(
+                    mz_sql_server_util::min_lsn(&mut client, &info.desc.capture_instance).await
+                );
+                if min > from {
+                    return Err(Definite(anyhow!(
+                        "the changes to table {}.{} from LSN {from} onwards have been cleaned up",
+                        info.desc.schema_name,
+                        info.desc.name
+                    )));
+                }
+            }
+
+            // Schema changes do not alter the capture instance, whose change
+            // table keeps the columns it was created with, but they are
+            // recorded in the DDL history.
+            let capture_instances = self
+                .source_tables
+                .values()
+                .map(|info| format!("N'{}'", info.desc.capture_instance.replace('\'', "''")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let query = format!(
+                "SELECT TOP 1 ct.capture_instance
+                FROM cdc.ddl_history AS h JOIN cdc.change_tables AS ct ON h.object_id = ct.object_id
+                WHERE h.ddl_lsn >= {} AND h.ddl_lsn <= {} AND ct.capture_instance IN ({})",
+                from.to_sql_literal(),
+                to.to_sql_literal(),
+                capture_instances,
+            );
+            let altered = client.simple_query(query).await?.into_row().await?;
+            if let Some(capture_instance) = altered.as_ref().and_then(|r| r.get::<&str, _>(0)) {
+                let info = self
+                    .source_tables
+                    .values()
+                    .find(|info| info.desc.capture_instance == capture_instance)
+                    .expect("query only returns ingested capture instances");
+                return Err(Definite(anyhow!(
+                    "source table {}.{} has been altered",
+                    info.desc.schema_name,
+                    info.desc.name
+                )));
+            }
+
+            let mut changes = vec![];
+            for info in self.source_tables.values() {
+                let query = format!(
+                    "SELECT __$start_lsn, __$seqval, __$operation, {}
+                    FROM cdc.{}
+                    WHERE __$start_lsn >= {} AND __$start_lsn <= {}",
+                    text_columns(&info.desc.columns),
+                    quote_identifier(&format!("{}_CT", info.desc.capture_instance)),
+                    from.to_sql_literal(),
+                    to.to_sql_literal(),
+                );
+                let mut stream = client.simple_query(query).await?.into_row_stream();
+                while let Some(row) = stream.try_next().await? {
+                    let lsn = row
+                        .get::<&[u8], _>(0)
+                        .ok_or_else(|| Definite(anyhow!("change without LSN")))?;
+                    let lsn = try_definite!(Lsn::from_bytes(lsn));
+                    let seqval = row.get::<&[u8], _>(1).unwrap_or_default().to_vec();
+                    let operation = row.get::<i32, _>(2).unwrap_or_default();
+                    let mut datums = datum_vec.borrow();
+                    for i in 0..info.desc.columns.len() {
+                        datums.push(match row.get::<&str, _>(i + 3) {
+                            Some(text) => Datum::String(text),
+                            None => Datum::Null,
+                        });
+                    }
+                    let row = try_definite!(cast_row(&info.casts, &datums));
+                    drop(datums);
+                    changes.push(Change {
+                        lsn,
+                        seqval,
+                        operation,
+                        output_index: info.output_index,
+                        row,
+                    });
+                }
+            }
+
+            // Emit the changes of each transaction in commit order, across
+            // all tables.
+            changes.sort_by(|a, b| {
+                (a.lsn, &a.seqval, a.operation).cmp(&(b.lsn, &b.seqval, b.operation))
+            });
+            let mut current = None;
+            for change in changes {
+                let offset = MzOffset::from(try_definite!(change.lsn.to_offset()));
+                if let Some(prev) = current {
+                    if prev != offset {
+                        self.row_sender.close_offset(prev).await;
+                    }
+                }
+                current = Some(offset);
+                match change.operation {
+                    1 | 3 => {
+                        self.row_sender
+                            .delete(change.output_index, change.row, offset)
+                            .await
+                    }
+                    2 | 4 => {
+                        self.row_sender
+                            .insert(change.output_index, change.row, offset)
+                            .await
+                    }
+                    op => return Err(Definite(anyhow!("unknown change operation {op}"))),
+                }
+            }
+            if let Some(offset) = current {
+                self.row_sender.close_offset(offset).await;
+            }
+            self.offset = try_definite!(to.to_offset()) + 1;
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Runs a query and discards its results.
+async fn query_drop(client: &mut Client, query: &str) -> Result<(), ReplicationError> {
+    client.simple_query(query).await?.into_results().await?;
+    Ok(())
+}
+
+/// Returns the quoted, schema-qualified name of a table.
+fn table_name(desc: &SqlServerTableDesc) -> String {
+    format!(
+        "{}.{}",
+        quote_identifier(&desc.schema_name),
+        quote_identifier(&desc.name)
+    )
+}
+
+/// Returns a select list that renders each column as the text that the
+/// column's cast expects.
+fn text_columns(columns: &[SqlServerColumnDesc]) -> String {
+    columns
+        .iter()
+        .map(|column| {
+            let c = quote_identifier(&column.name);
+            match column.data_type.as_str() {
+                "binary" | "varbinary" => format!("'\\x' + CONVERT(varchar(max), {c}, 2)"),
+                "image" => {
+                    format!("'\\x' + CONVERT(varchar(max), CONVERT(varbinary(max), {c}), 2)")
+                }
+                "date" => format!("CONVERT(varchar(max), {c}, 23)"),
+                "smalldatetime" | "datetime" | "datetime2" => {
+                    format!("CONVERT(varchar(max), CONVERT(datetime2, {c}), 121)")
+                }
+                "datetimeoffset" => format!(
+                    "CONVERT(varchar(max), CONVERT(datetime2, SWITCHOFFSET({c}, 0)), 121) + '+00'"
+                ),
+                // Style 3 renders floating-point numbers with enough digits
+                // to round-trip.
+                "real" | "float" => format!("CONVERT(varchar(max), {c}, 3)"),
+                "money" | "smallmoney" => {
+                    format!("CONVERT(varchar(max), CONVERT(decimal(19, 4), {c}))")
+                }
+                _ => format!("CONVERT(nvarchar(max), {c})"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Casts a text row into the target types
+fn cast_row(table_cast: &[MirScalarExpr], datums: &[Datum<'_>]) -> Result<Row, anyhow::Error> {
+    let arena = mz_repr::RowArena::new();
+    let mut row = Row::default();
+    let mut packer = row.packer();
+    for column_cast in table_cast {
+        let datum = column_cast.eval(datums, &arena)?;
+        packer.push(datum);
+    }
+    Ok(row)
+}
@@ -12,7 +12,7 @@
 // https://github.com/tokio-rs/prost/issues/237
 // #![allow(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::marker::{Send, Sync};
 use std::rc::Rc;
@@ -158,6 +158,10 @@ pub trait SourceReader {
 
 pub trait SourceConnection: Clone {
     fn name(&self) -> &'static str;
+
+    /// Returns the options the source was configured with, keyed by option
+    /// name. Secrets are rendered as references rather than their contents.
+    fn options(&self) -> BTreeMap<String, String>;
 }
 
 /// A sibling trait to `SourceReader` that represents a source's
@@ -218,6 +222,11 @@ pub enum SourceMessageType<Key, Value, Diff> {
     /// We need these to compute a "global" source upper, when determining
     /// completeness of a timestamp.
     DropPartitionCapabilities(Vec<PartitionId>),
+    /// Signals that this [`SourceReader`] instance will never emit
+    /// messages/updates for a given partition below the given offset, which
+    /// lets sources record a position in a partition at which they are yet to
+    /// emit a message.
+    Progress(PartitionId, MzOffset),
 }
 
 /// Source-agnostic wrapper for messages. Each source must implement a
@@ -8,8 +8,10 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::TryRecvError;
 use mz_persist_client::cache::PersistClientCache;
@@ -24,10 +26,13 @@ use mz_ore::now::NowFn;
 use mz_repr::{GlobalId, Timestamp};
 
 use crate::controller::CollectionMetadata;
-use crate::protocol::client::{StorageCommand, StorageResponse};
+use crate::protocol::client::{
+    SinkMetricsUpdate, SinkStatusUpdate, StorageCommand, StorageResponse,
+};
 use crate::sink::SinkBaseMetrics;
 use crate::types::connections::ConnectionContext;
 use crate::types::sinks::StorageSinkDesc;
+use crate::types::sources::encoding::NumericOverflowPolicy;
 use crate::types::sources::IngestionDescription;
 
 use crate::decode::metrics::DecodeMetrics;
@@ -36,6 +41,10 @@ use crate::source::metrics::SourceBaseMetrics;
 type CommandReceiver = crossbeam_channel::Receiver<StorageCommand>;
 type ResponseSender = mpsc::UnboundedSender<StorageResponse>;
 
+/// How often the sizes of the messages read by sources and the progress
+/// publishes of sinks are reported to the controller.
+const INTROSPECTION_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// State maintained for each worker thread.
 ///
 /// Much of this state can be viewed as local variables for the worker thread,
@@ -90,6 +99,55 @@ pub struct StorageState {
     /// Frontier of sink writes (all subsequent writes will be at times at or
     /// equal to this frontier)
     pub sink_write_frontiers: HashMap<GlobalId, Rc<RefCell<Antichain<Timestamp>>>>,
+    /// Counts of the messages read by each source since the last report,
+    /// keyed by source and size bucket.
+    ///
+    /// This is shared among all source instances on the worker.
+    pub source_message_sizes: Rc<RefCell<HashMap<(GlobalId, u64), i64>>>,
+    /// Counts of the attempts of sinks to publish progress records since the
+    /// last report, keyed by sink, duration bucket, and whether the attempt
+    /// failed.
+    ///
+    /// This is shared among all sink instances on the worker.
+    pub sink_progress_publishes: Rc<RefCell<HashMap<(GlobalId, u64, bool), i64>>>,
+    /// Counts of the messages that sinks published to their dead letter
+    /// topics since the last report, keyed by sink and error.
+    ///
+    /// This is shared among all sink instances on the worker.
+    pub sink_dead_letters: Rc<RefCell<HashMap<(GlobalId, String), i64>>>,
+    /// Counts of the values to which sources applied their numeric overflow
+    /// policy since the last report, keyed by source and policy.
+    ///
+    /// This is shared among all source instances on the worker.
+    pub source_numeric_overflows: Rc<RefCell<HashMap<(GlobalId, NumericOverflowPolicy), i64>>>,
+    /// The metrics of each sink since the last report, keyed by sink.
+    ///
+    /// This is shared among all sink instances on the worker.
+    pub sink_metrics_updates: Rc<RefCell<HashMap<GlobalId, SinkMetricsUpdate>>>,
+    /// The transitions of sinks between statuses since the last report, in
+    /// the order in which they occurred.
+    ///
+    /// This is shared among all sink instances on the worker.
+    pub sink_status_updates: Rc<RefCell<Vec<(GlobalId, SinkStatusUpdate)>>>,
+    /// The last time source message sizes, sink progress publishes, sink dead
+    /// letters, source numeric overflows, sink metrics, and sink status
+    /// transitions were reported.
+    pub last_introspection_report: Instant,
+    /// Configuration for the state of upsert sources with a disk-backed
+    /// `UPSERT STATE`.
+    pub upsert_state_config: UpsertStateConfig,
+}
+
+/// Configuration for the state of upsert sources with a disk-backed
+/// `UPSERT STATE`.
+#[derive(Debug, Clone)]
+pub struct UpsertStateConfig {
+    /// The directory in which each upsert operator creates the RocksDB
+    /// instances that hold its state.
+    pub scratch_directory: PathBuf,
+    /// The maximum number of values each upsert operator caches in memory in
+    /// front of its RocksDB instance.
+    pub disk_cache_capacity: usize,
 }
 
 /// A token that keeps a sink alive.
@@ -137,6 +195,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
             }
 
             self.report_frontier_progress(&response_tx);
+            self.report_introspection(&response_tx);
 
             // Handle any received commands.
             let mut cmds = vec![];
@@ -163,6 +222,13 @@ impl<'w, A: Allocate> Worker<'w, A> {
             StorageCommand::InitializationComplete => (),
             StorageCommand::CreateSources(ingestions) => {
                 for ingestion in ingestions {
+                    // A source that is already running has had its schema
+                    // refreshed. Shut down its dataflow, which is rebuilt
+                    // below from the new description.
+                    if self.storage_state.ingestions.contains_key(&ingestion.id) {
+                        self.storage_state.source_tokens.remove(&ingestion.id);
+                    }
+
                     // Remember the ingestion description to facilitate possible
                     // reconciliation later.
                     self.storage_state
@@ -194,6 +260,13 @@ impl<'w, A: Allocate> Worker<'w, A> {
             }
             StorageCommand::CreateSinks(exports) => {
                 for export in exports {
+                    // A sink that is already running has been altered. Shut
+                    // down its dataflow, which is rebuilt below from the new
+                    // description.
+                    if self.storage_state.exports.contains_key(&export.id) {
+                        self.storage_state.sink_tokens.remove(&export.id);
+                    }
+
                     self.storage_state
                         .exports
                         .insert(export.id, export.description.clone());
@@ -274,6 +347,99 @@ impl<'w, A: Allocate> Worker<'w, A> {
         }
     }
 
+    /// Reports the sizes of the messages read by sources, and the progress
+    /// publishes, metrics, and status transitions of sinks since the last
+    /// report, at most once per `INTROSPECTION_REPORT_INTERVAL`.
+    pub fn report_introspection(&mut self, response_tx: &ResponseSender) {
+        if self.storage_state.last_introspection_report.elapsed() < INTROSPECTION_REPORT_INTERVAL {
+            return;
+        }
+        self.storage_state.last_introspection_report = Instant::now();
+
+        let sizes: Vec<_> = self
+            .storage_state
+            .source_message_sizes
+            .borrow_mut()
+            .drain()
+            .filter(|(_, count)| *count != 0)
+            .map(|((id, size), count)| (id, size, count))
+            .collect();
+
+        if !sizes.is_empty() {
+            self.send_storage_response(response_tx, StorageResponse::SourceMessageSizes(sizes));
+        }
+
+        let publishes: Vec<_> = self
+            .storage_state
+            .sink_progress_publishes
+            .borrow_mut()
+            .drain()
+            .filter(|(_, count)| *count != 0)
+            .map(|((id, duration, failed), count)| (id, duration, failed, count))
+            .collect();
+
+        if !publishes.is_empty() {
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::SinkProgressPublishes(publishes),
+            );
+        }
+
+        let dead_letters: Vec<_> = self
+            .storage_state
+            .sink_dead_letters
+            .borrow_mut()
+            .drain()
+            .filter(|(_, count)| *count != 0)
+            .map(|((id, error), count)| (id, error, count))
+            .collect();
+
+        if !dead_letters.is_empty() {
+            self.send_storage_response(response_tx, StorageResponse::SinkDeadLetters(dead_letters));
+        }
+
+        let overflows: Vec<_> = self
+            .storage_state
+            .source_numeric_overflows
+            .borrow_mut()
+            .drain()
+            .filter(|(_, count)| *count != 0)
+            .map(|((id, policy), count)| (id, policy.as_str().to_string(), count))
+            .collect();
+
+        if !overflows.is_empty() {
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::SourceNumericOverflows(overflows),
+            );
+        }
+
+        let sink_metrics: Vec<_> = self
+            .storage_state
+            .sink_metrics_updates
+            .borrow_mut()
+            .drain()
+            .collect();
+
+        if !sink_metrics.is_empty() {
+            self.send_storage_response(response_tx, StorageResponse::SinkMetrics(sink_metrics));
+        }
+
+        let sink_statuses: Vec<_> = self
+            .storage_state
+            .sink_status_updates
+            .borrow_mut()
+            .drain(..)
+            .collect();
+
+        if !sink_statuses.is_empty() {
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::SinkStatusUpdates(sink_statuses),
+            );
+        }
+    }
+
     /// Send a response to the coordinator.
     fn send_storage_response(&self, response_tx: &ResponseSender, response: StorageResponse) {
         // Ignore send errors because the coordinator is free to ignore our
@@ -329,14 +495,10 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         if let Some(existing) = self.storage_state.ingestions.get(&ingestion.id) {
                             stale_ingestions.remove(&ingestion.id);
                             // If we've been asked to create an ingestion that is
-                            // already installed, the descriptions must match
-                            // exactly.
-                            assert_eq!(
-                                *existing, ingestion.description,
-                                "New ingestion with same ID {:?}",
-                                ingestion.id,
-                            );
-                            false
+                            // already installed with a different description,
+                            // the source's schema was refreshed while we were
+                            // disconnected, and it must be rebuilt.
+                            *existing != ingestion.description
                         } else {
                             true
                         }
@@ -347,14 +509,10 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         if let Some(existing) = self.storage_state.exports.get(&export.id) {
                             stale_exports.remove(&export.id);
                             // If we've been asked to create an export that is
-                            // already installed, the descriptions must match
-                            // exactly.
-                            assert_eq!(
-                                *existing, export.description,
-                                "New export with same ID {:?}",
-                                export.id,
-                            );
-                            false
+                            // already installed with a different description,
+                            // the sink was altered while we were disconnected,
+                            // and must be rebuilt.
+                            *existing != export.description
                         } else {
                             true
                         }
@@ -11,7 +11,7 @@
 
 use std::collections::{BTreeMap, HashSet};
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
@@ -21,16 +21,22 @@ use tokio_postgres::config::SslMode;
 use url::Url;
 
 use mz_ccsr::tls::{Certificate, Identity};
-
+use mz_kafka_util::client::{BrokerAddrRewriter, MzClientContext, OAuthTokenProvider};
+use mz_kafka_util::{KafkaBrokerAddrs, SrvBrokerAddrRewriter};
+use mz_postgres_util::{SshTunnelConfig, TunnelConfig};
 use mz_proto::tokio_postgres::any_ssl_mode;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::url::any_url;
 use mz_repr::GlobalId;
 use mz_secrets::SecretsReader;
 
-use crate::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
+use crate::types::connections::aws::{
+    AwsAssumeRole, AwsConfig, AwsCredentials, AwsExternalIdPrefix, SerdeUri,
+};
 
 pub mod aws;
+mod oauth;
+mod tunnel;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage.types.connections.rs"));
 
@@ -49,6 +55,15 @@ impl StringOrSecret {
         }
     }
 
+    /// Renders the value for display, referring to a secret by its ID rather
+    /// than reading its contents.
+    pub fn to_redacted_string(&self) -> String {
+        match self {
+            StringOrSecret::String(s) => s.clone(),
+            StringOrSecret::Secret(id) => format!("SECRET {id}"),
+        }
+    }
+
     /// Asserts that this string or secret is a string and returns its contents.
     pub fn unwrap_string(&self) -> &str {
         match self {
@@ -108,6 +123,9 @@ pub struct ConnectionContext {
     pub aws_external_id_prefix: Option<AwsExternalIdPrefix>,
     /// A secrets reader.
     pub secrets_reader: Arc<dyn SecretsReader>,
+    /// A cache of schema registry responses shared by all sources in the
+    /// process.
+    pub csr_cache: Arc<mz_ccsr::ClientCache>,
 }
 
 impl ConnectionContext {
@@ -127,15 +145,24 @@ impl ConnectionContext {
             librdkafka_log_level: mz_ore::tracing::target_level(filter, "librdkafka"),
             aws_external_id_prefix: aws_external_id_prefix.map(AwsExternalIdPrefix),
             secrets_reader,
+            csr_cache: Arc::new(mz_ccsr::ClientCache::new(mz_ccsr::CacheTtls::default())),
         }
     }
 
+    /// Replaces the schema registry cache with an empty cache that retains
+    /// responses for `ttls`.
+    pub fn with_csr_cache_ttls(mut self, ttls: mz_ccsr::CacheTtls) -> ConnectionContext {
+        self.csr_cache = Arc::new(mz_ccsr::ClientCache::new(ttls));
+        self
+    }
+
     /// Constructs a new connection context for usage in tests.
     pub fn for_tests(secrets_reader: Arc<dyn SecretsReader>) -> ConnectionContext {
         ConnectionContext {
             librdkafka_log_level: tracing::Level::INFO,
             aws_external_id_prefix: None,
             secrets_reader,
+            csr_cache: Arc::new(mz_ccsr::ClientCache::new(mz_ccsr::CacheTtls::default())),
         }
     }
 }
@@ -145,8 +172,11 @@ pub enum Connection {
     Kafka(KafkaConnection),
     Csr(CsrConnection),
     Postgres(PostgresConnection),
+    MySql(MySqlConnection),
+    SqlServer(SqlServerConnection),
     Ssh(SshConnection),
     Aws(AwsConfig),
+    AwsPrivatelink(AwsPrivatelinkConnection),
 }
 
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -186,10 +216,71 @@ impl Arbitrary for SaslConfig {
     }
 }
 
+/// The configuration of a Kafka connection that authenticates using the
+/// `OAUTHBEARER` SASL mechanism.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct OAuthBearerConfig {
+    /// Where clients obtain the tokens with which they authenticate.
+    pub token_source: OAuthBearerTokenSource,
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+/// Where clients of a Kafka connection obtain the tokens with which they
+/// authenticate using the `OAUTHBEARER` SASL mechanism.
+///
+/// Tokens are refreshed by the clients themselves shortly before they expire.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum OAuthBearerTokenSource {
+    /// Tokens are requested from an OAuth 2.0 token endpoint using the client
+    /// credentials grant.
+    ClientCredentials(OAuthClientCredentials),
+    /// Tokens are AWS MSK IAM authentication tokens, signed with the
+    /// credentials of an AWS connection.
+    AwsIam {
+        /// The ID of the AWS connection.
+        connection_id: GlobalId,
+        connection: AwsConfig,
+    },
+}
+
+/// The client credentials with which tokens are requested from an OAuth 2.0
+/// token endpoint.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct OAuthClientCredentials {
+    pub token_endpoint: Url,
+    pub client_id: StringOrSecret,
+    pub client_secret: GlobalId,
+    pub scope: Option<String>,
+}
+
+impl Arbitrary for OAuthClientCredentials {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any_url(),
+            any::<StringOrSecret>(),
+            any::<GlobalId>(),
+            any::<Option<String>>(),
+        )
+            .prop_map(
+                |(token_endpoint, client_id, client_secret, scope)| OAuthClientCredentials {
+                    token_endpoint,
+                    client_id,
+                    client_secret,
+                    scope,
+                },
+            )
+            .boxed()
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum KafkaSecurity {
     Tls(KafkaTlsConfig),
     Sasl(SaslConfig),
+    OAuthBearer(OAuthBearerConfig),
 }
 
 impl From<KafkaTlsConfig> for KafkaSecurity {
@@ -204,19 +295,40 @@ impl From<SaslConfig> for KafkaSecurity {
     }
 }
 
+impl From<OAuthBearerConfig> for KafkaSecurity {
+    fn from(c: OAuthBearerConfig) -> Self {
+        KafkaSecurity::OAuthBearer(c)
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct KafkaConnection {
     pub brokers: Vec<String>,
     pub progress_topic: Option<String>,
     pub security: Option<KafkaSecurity>,
+    /// Whether the brokers are the Kafka endpoint of an Azure Event Hubs
+    /// namespace, which closes idle connections early and does not let
+    /// clients create topics.
+    pub event_hubs: bool,
+    /// An optional named SSH tunnel connection ID. Used to manage the public key secret.
+    pub ssh_tunnel_id: Option<GlobalId>,
+    /// An optional SSH tunnel connection details, through which clients
+    /// connect to every broker.
+    pub ssh_tunnel: Option<SshConnection>,
+    /// An optional AWS PrivateLink connection details, through whose VPC
+    /// endpoint clients connect to every broker.
+    pub aws_privatelink: Option<AwsPrivatelinkConnection>,
 }
 
 mod kafka_config_keys {
     pub const BOOTSTRAP_SERVERS: &str = "bootstrap.servers";
+    pub const CONNECTIONS_MAX_IDLE_MS: &str = "connections.max.idle.ms";
+    pub const METADATA_MAX_AGE_MS: &str = "metadata.max.age.ms";
     pub const SASL_MECHANISMS: &str = "sasl.mechanisms";
     pub const SASL_PASSWORD: &str = "sasl.password";
     pub const SASL_USERNAME: &str = "sasl.username";
     pub const SECURITY_PROTOCOL: &str = "security.protocol";
+    pub const SOCKET_KEEPALIVE_ENABLE: &str = "socket.keepalive.enable";
     pub const SSL_CERTIFICATE: &str = "ssl.certificate.pem";
     pub const SSL_CERTIFICATE_AUTHORITY: &str = "ssl.ca.pem";
     pub const SSL_KEY: &str = "ssl.key.pem";
@@ -258,8 +370,28 @@ impl From<KafkaConnection> for BTreeMap<String, StringOrSecret> {
                     r.insert(SSL_CERTIFICATE_AUTHORITY.to_owned(), certificate_authority);
                 }
             }
+            Some(KafkaSecurity::OAuthBearer(OAuthBearerConfig {
+                token_source: _,
+                tls_root_cert: certificate_authority,
+            })) => {
+                // Tokens are generated by the clients' contexts; see
+                // `KafkaConnection::client_context`.
+                r.insert(SECURITY_PROTOCOL.to_owned(), "SASL_SSL".into());
+                r.insert(SASL_MECHANISMS.to_owned(), "OAUTHBEARER".into());
+                if let Some(certificate_authority) = certificate_authority {
+                    r.insert(SSL_CERTIFICATE_AUTHORITY.to_owned(), certificate_authority);
+                }
+            }
             None => {}
         }
+        if v.event_hubs {
+            // Event Hubs closes connections that are idle for four minutes
+            // without notice, so clients must close them first, and must
+            // refresh their metadata before their connections go stale.
+            r.insert(CONNECTIONS_MAX_IDLE_MS.to_owned(), "180000".into());
+            r.insert(METADATA_MAX_AGE_MS.to_owned(), "180000".into());
+            r.insert(SOCKET_KEEPALIVE_ENABLE.to_owned(), "true".into());
+        }
 
         r
     }
@@ -305,6 +437,101 @@ impl RustType<ProtoKafkaConnectionSaslConfig> for SaslConfig {
     }
 }
 
+impl RustType<ProtoOAuthClientCredentials> for OAuthClientCredentials {
+    fn into_proto(&self) -> ProtoOAuthClientCredentials {
+        ProtoOAuthClientCredentials {
+            token_endpoint: Some(self.token_endpoint.into_proto()),
+            client_id: Some(self.client_id.into_proto()),
+            client_secret: Some(self.client_secret.into_proto()),
+            scope: self.scope.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoOAuthClientCredentials) -> Result<Self, TryFromProtoError> {
+        Ok(OAuthClientCredentials {
+            token_endpoint: proto
+                .token_endpoint
+                .into_rust_if_some("ProtoOAuthClientCredentials::token_endpoint")?,
+            client_id: proto
+                .client_id
+                .into_rust_if_some("ProtoOAuthClientCredentials::client_id")?,
+            client_secret: proto
+                .client_secret
+                .into_rust_if_some("ProtoOAuthClientCredentials::client_secret")?,
+            scope: proto.scope,
+        })
+    }
+}
+
+impl RustType<ProtoKafkaConnectionOAuthBearerConfig> for OAuthBearerConfig {
+    fn into_proto(&self) -> ProtoKafkaConnectionOAuthBearerConfig {
+        use proto_kafka_connection_o_auth_bearer_config::TokenSource;
+        ProtoKafkaConnectionOAuthBearerConfig {
+            token_source: Some(match &self.token_source {
+                OAuthBearerTokenSource::ClientCredentials(credentials) => {
+                    TokenSource::ClientCredentials(credentials.into_proto())
+                }
+                OAuthBearerTokenSource::AwsIam {
+                    connection_id,
+                    connection,
+                } => TokenSource::AwsIam(ProtoKafkaAwsIam {
+                    connection_id: Some(connection_id.into_proto()),
+                    access_key_id: Some(connection.credentials.access_key_id.into_proto()),
+                    secret_access_key: Some(connection.credentials.secret_access_key.into_proto()),
+                    session_token: connection.credentials.session_token.into_proto(),
+                    region: connection.region.clone(),
+                    role_arn: connection.role.as_ref().map(|role| role.arn.clone()),
+                    endpoint: connection
+                        .endpoint
+                        .as_ref()
+                        .map(|endpoint| endpoint.0.to_string()),
+                }),
+            }),
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaConnectionOAuthBearerConfig) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_connection_o_auth_bearer_config::TokenSource;
+        let token_source = match proto.token_source {
+            Some(TokenSource::ClientCredentials(credentials)) => {
+                OAuthBearerTokenSource::ClientCredentials(credentials.into_rust()?)
+            }
+            Some(TokenSource::AwsIam(aws)) => OAuthBearerTokenSource::AwsIam {
+                connection_id: aws
+                    .connection_id
+                    .into_rust_if_some("ProtoKafkaAwsIam::connection_id")?,
+                connection: AwsConfig {
+                    credentials: AwsCredentials {
+                        access_key_id: aws
+                            .access_key_id
+                            .into_rust_if_some("ProtoKafkaAwsIam::access_key_id")?,
+                        secret_access_key: aws
+                            .secret_access_key
+                            .into_rust_if_some("ProtoKafkaAwsIam::secret_access_key")?,
+                        session_token: aws.session_token.into_rust()?,
+                    },
+                    region: aws.region,
+                    role: aws.role_arn.map(|arn| AwsAssumeRole { arn }),
+                    endpoint: match aws.endpoint {
+                        Some(endpoint) => Some(SerdeUri(endpoint.parse()?)),
+                        None => None,
+                    },
+                },
+            },
+            None => {
+                return Err(TryFromProtoError::missing_field(
+                    "ProtoKafkaConnectionOAuthBearerConfig::token_source",
+                ))
+            }
+        };
+        Ok(OAuthBearerConfig {
+            token_source,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
 impl RustType<ProtoKafkaConnectionSecurity> for KafkaSecurity {
     fn into_proto(&self) -> ProtoKafkaConnectionSecurity {
         use proto_kafka_connection_security::Kind;
@@ -312,6 +539,7 @@ impl RustType<ProtoKafkaConnectionSecurity> for KafkaSecurity {
             kind: Some(match self {
                 KafkaSecurity::Tls(config) => Kind::Tls(config.into_proto()),
                 KafkaSecurity::Sasl(config) => Kind::Sasl(config.into_proto()),
+                KafkaSecurity::OAuthBearer(config) => Kind::OauthBearer(config.into_proto()),
             }),
         }
     }
@@ -324,16 +552,94 @@ impl RustType<ProtoKafkaConnectionSecurity> for KafkaSecurity {
         Ok(match kind {
             Kind::Tls(s) => KafkaSecurity::Tls(KafkaTlsConfig::from_proto(s)?),
             Kind::Sasl(s) => KafkaSecurity::Sasl(SaslConfig::from_proto(s)?),
+            Kind::OauthBearer(s) => KafkaSecurity::OAuthBearer(OAuthBearerConfig::from_proto(s)?),
         })
     }
 }
 
+impl KafkaConnection {
+    /// Constructs the context for Kafka clients of this connection, which
+    /// generates the tokens with which the clients authenticate if the
+    /// connection uses the `OAUTHBEARER` SASL mechanism, resolves brokers
+    /// listed as DNS SRV records whenever the clients connect to them, and
+    /// routes the clients' connections to the brokers through the
+    /// connection's SSH tunnel or AWS PrivateLink VPC endpoint, if any.
+    pub async fn client_context(
+        &self,
+        secrets_reader: &dyn SecretsReader,
+        aws_external_id_prefix: Option<&AwsExternalIdPrefix>,
+    ) -> Result<MzClientContext, anyhow::Error> {
+        let runtime = tokio::runtime::Handle::current();
+        let provider: Option<Arc<dyn OAuthTokenProvider>> = match &self.security {
+            Some(KafkaSecurity::OAuthBearer(config)) => match &config.token_source {
+                OAuthBearerTokenSource::ClientCredentials(credentials) => {
+                    Some(Arc::new(oauth::ClientCredentialsTokenProvider {
+                        runtime: runtime.clone(),
+                        http: reqwest::Client::new(),
+                        token_endpoint: credentials.token_endpoint.clone(),
+                        client_id: credentials.client_id.get_string(secrets_reader).await?,
+                        client_secret: secrets_reader
+                            .read_string(credentials.client_secret)
+                            .await?,
+                        scope: credentials.scope.clone(),
+                    }))
+                }
+                OAuthBearerTokenSource::AwsIam {
+                    connection_id,
+                    connection,
+                } => {
+                    let sdk_config = connection
+                        .load(aws_external_id_prefix, Some(connection_id), secrets_reader)
+                        .await;
+                    Some(Arc::new(oauth::AwsIamTokenProvider {
+                        runtime: runtime.clone(),
+                        sdk_config,
+                    }))
+                }
+            },
+            _ => None,
+        };
+        let tunnel = tunnel_config(
+            self.ssh_tunnel_id,
+            self.ssh_tunnel.as_ref(),
+            self.aws_privatelink.as_ref(),
+            secrets_reader,
+        )
+        .await?;
+        let tunnel_rewriter: Option<Arc<dyn BrokerAddrRewriter>> = match tunnel {
+            TunnelConfig::Direct => None,
+            TunnelConfig::Ssh(config) => Some(Arc::new(tunnel::SshTunnelBrokerAddrRewriter {
+                runtime: runtime.clone(),
+                config,
+                tunnels: Mutex::new(BTreeMap::new()),
+            })),
+            TunnelConfig::AwsPrivatelink { endpoint } => {
+                Some(Arc::new(tunnel::AwsPrivatelinkBrokerAddrRewriter {
+                    endpoint,
+                }))
+            }
+        };
+        // Brokers listed as DNS SRV records are resolved on every connection,
+        // and the targets are then routed through the tunnel, if any.
+        let mut context = MzClientContext::new(provider);
+        if let Some(rewriter) = SrvBrokerAddrRewriter::wrap(runtime, &self.brokers, tunnel_rewriter)
+        {
+            context = context.with_broker_addr_rewriter(rewriter);
+        }
+        Ok(context)
+    }
+}
+
 impl RustType<ProtoKafkaConnection> for KafkaConnection {
     fn into_proto(&self) -> ProtoKafkaConnection {
         ProtoKafkaConnection {
             brokers: self.brokers.into_proto(),
             progress_topic: self.progress_topic.into_proto(),
             security: self.security.into_proto(),
+            event_hubs: self.event_hubs,
+            ssh_tunnel_id: self.ssh_tunnel_id.into_proto(),
+            ssh_tunnel: self.ssh_tunnel.into_proto(),
+            aws_privatelink: self.aws_privatelink.into_proto(),
         }
     }
 
@@ -342,6 +648,10 @@ impl RustType<ProtoKafkaConnection> for KafkaConnection {
             brokers: proto.brokers,
             progress_topic: proto.progress_topic,
             security: proto.security.into_rust()?,
+            event_hubs: proto.event_hubs,
+            ssh_tunnel_id: proto.ssh_tunnel_id.into_rust()?,
+            ssh_tunnel: proto.ssh_tunnel.into_rust()?,
+            aws_privatelink: proto.aws_privatelink.into_rust()?,
         })
     }
 }
@@ -501,6 +811,9 @@ impl RustType<ProtoCsrConnectionHttpAuth> for CsrConnectionHttpAuth {
 ///
 /// Note that this:
 /// - Performs blocking reads when extracting SECRETS.
+/// - Lists any brokers specified as DNS SRV records by name, which only the
+///   clients that use the connection's [`KafkaConnection::client_context`]
+///   can resolve.
 /// - Does not ensure that the keys from the Kafka connection and
 ///   additional options are disjoint.
 pub async fn populate_client_config<'a>(
@@ -510,7 +823,12 @@ pub async fn populate_client_config<'a>(
     config: &'a mut rdkafka::ClientConfig,
     secrets_reader: &'a dyn SecretsReader,
 ) {
-    let config_options: BTreeMap<String, StringOrSecret> = kafka_connection.into();
+    let brokers = kafka_bootstrap_servers(&kafka_connection.brokers);
+    let mut config_options: BTreeMap<String, StringOrSecret> = kafka_connection.into();
+    config_options.insert(
+        kafka_config_keys::BOOTSTRAP_SERVERS.to_owned(),
+        brokers.into(),
+    );
     for (k, v) in options.iter().chain(config_options.iter()) {
         if !drop_option_keys.contains(k.as_str()) {
             config.set(
@@ -523,6 +841,18 @@ pub async fn populate_client_config<'a>(
     }
 }
 
+/// Returns `brokers` in the form of `bootstrap.servers`.
+fn kafka_bootstrap_servers(brokers: &[String]) -> String {
+    brokers
+        .iter()
+        .map(|broker| match broker.parse::<KafkaBrokerAddrs>() {
+            Ok(addrs) => addrs.bootstrap_servers(),
+            Err(_) => broker.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Provides cleaner access to the `populate_client_config` implementation for
 /// structs.
 #[async_trait]
@@ -565,6 +895,9 @@ pub struct PostgresConnection {
     pub ssh_tunnel_id: Option<GlobalId>,
     /// An optional SSH tunnel connection details.
     pub ssh_tunnel: Option<SshConnection>,
+    /// An optional AWS PrivateLink connection details, through whose VPC
+    /// endpoint to connect to the server.
+    pub aws_privatelink: Option<AwsPrivatelinkConnection>,
     /// Whether to use TLS for encryption, authentication, or both.
     pub tls_mode: SslMode,
     /// An optional root TLS certificate in PEM format, to verify the server's
@@ -607,27 +940,19 @@ impl PostgresConnection {
         &self,
         secrets_reader: &dyn mz_secrets::SecretsReader,
     ) -> Result<mz_postgres_util::Config, anyhow::Error> {
-        let ssh_tunnel = if let (Some(ssh_secret_id), Some(ssh_tunnel)) =
-            (self.ssh_tunnel_id, self.ssh_tunnel.as_ref())
-        {
-            let secret = secrets_reader.read(ssh_secret_id).await?;
-            let keyset = mz_ore::ssh_key::SshKeyset::from_bytes(&secret)?;
-            let keypair = keyset.primary().clone();
-            mz_postgres_util::SshTunnelConfig::Tunnel {
-                host: ssh_tunnel.host.clone(),
-                port: ssh_tunnel.port,
-                user: ssh_tunnel.user.clone(),
-                keypair,
-            }
-        } else {
-            mz_postgres_util::SshTunnelConfig::Direct
-        };
+        let tunnel = tunnel_config(
+            self.ssh_tunnel_id,
+            self.ssh_tunnel.as_ref(),
+            self.aws_privatelink.as_ref(),
+            secrets_reader,
+        )
+        .await?;
 
         Ok(mz_postgres_util::Config::new(
             self.postgres_config(secrets_reader).await?,
             &self.host,
             self.port,
-            ssh_tunnel,
+            tunnel,
         ))
     }
 }
@@ -642,6 +967,7 @@ impl RustType<ProtoPostgresConnection> for PostgresConnection {
             password: self.password.into_proto(),
             ssh_tunnel_id: self.ssh_tunnel_id.into_proto(),
             ssh_tunnel: self.ssh_tunnel.into_proto(),
+            aws_privatelink: self.aws_privatelink.into_proto(),
             tls_mode: Some(self.tls_mode.into_proto()),
             tls_root_cert: self.tls_root_cert.into_proto(),
             tls_identity: self.tls_identity.into_proto(),
@@ -659,6 +985,7 @@ impl RustType<ProtoPostgresConnection> for PostgresConnection {
             password: proto.password.into_rust()?,
             ssh_tunnel_id: proto.ssh_tunnel_id.into_rust()?,
             ssh_tunnel: proto.ssh_tunnel.into_rust()?,
+            aws_privatelink: proto.aws_privatelink.into_rust()?,
             tls_mode: proto
                 .tls_mode
                 .into_rust_if_some("ProtoPostgresConnection::tls_mode")?,
@@ -681,6 +1008,7 @@ impl Arbitrary for PostgresConnection {
             any::<Option<GlobalId>>(),
             any::<Option<GlobalId>>(),
             any::<Option<SshConnection>>(),
+            any::<Option<AwsPrivatelinkConnection>>(),
             any_ssl_mode(),
             any::<Option<StringOrSecret>>(),
             any::<Option<TlsIdentity>>(),
@@ -694,6 +1022,7 @@ impl Arbitrary for PostgresConnection {
                     password,
                     ssh_tunnel_id,
                     ssh_tunnel,
+                    aws_privatelink,
                     tls_mode,
                     tls_root_cert,
                     tls_identity,
@@ -706,6 +1035,7 @@ impl Arbitrary for PostgresConnection {
                         password,
                         ssh_tunnel_id,
                         ssh_tunnel,
+                        aws_privatelink,
                         tls_mode,
                         tls_root_cert,
                         tls_identity,
@@ -716,6 +1046,258 @@ impl Arbitrary for PostgresConnection {
     }
 }
 
+/// A connection to a MySQL server.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MySqlConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to use TLS for encryption, authentication, or both.
+    pub tls_mode: MySqlSslMode,
+    /// An optional root TLS certificate in PEM format, to verify the server's
+    /// identity.
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+impl MySqlConnection {
+    pub async fn config(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<mz_mysql_util::Config, anyhow::Error> {
+        let password = match self.password {
+            Some(password) => Some(secrets_reader.read_string(password).await?),
+            None => None,
+        };
+        let tls_root_cert = match &self.tls_root_cert {
+            Some(tls_root_cert) => Some(tls_root_cert.get_string(secrets_reader).await?),
+            None => None,
+        };
+        Ok(mz_mysql_util::Config {
+            host: self.host.clone(),
+            port: self.port,
+            user: self.user.get_string(secrets_reader).await?,
+            password,
+            tls_mode: match self.tls_mode {
+                MySqlSslMode::Disabled => mz_mysql_util::TlsMode::Disabled,
+                MySqlSslMode::Required => mz_mysql_util::TlsMode::Required,
+                MySqlSslMode::VerifyCa => mz_mysql_util::TlsMode::VerifyCa,
+                MySqlSslMode::VerifyIdentity => mz_mysql_util::TlsMode::VerifyIdentity,
+            },
+            tls_root_cert,
+        })
+    }
+}
+
+impl RustType<ProtoMySqlConnection> for MySqlConnection {
+    fn into_proto(&self) -> ProtoMySqlConnection {
+        ProtoMySqlConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            tls_mode: Some(self.tls_mode.into_proto()),
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            user: proto.user.into_rust_if_some("ProtoMySqlConnection::user")?,
+            password: proto.password.into_rust()?,
+            tls_mode: proto
+                .tls_mode
+                .into_rust_if_some("ProtoMySqlConnection::tls_mode")?,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
+/// Whether a [`MySqlConnection`] uses TLS for encryption, authentication, or
+/// both.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MySqlSslMode {
+    Disabled,
+    Required,
+    VerifyCa,
+    VerifyIdentity,
+}
+
+impl RustType<ProtoMySqlSslMode> for MySqlSslMode {
+    fn into_proto(&self) -> ProtoMySqlSslMode {
+        use proto_my_sql_ssl_mode::Kind;
+        ProtoMySqlSslMode {
+            kind: Some(match self {
+                MySqlSslMode::Disabled => Kind::Disabled(()),
+                MySqlSslMode::Required => Kind::Required(()),
+                MySqlSslMode::VerifyCa => Kind::VerifyCa(()),
+                MySqlSslMode::VerifyIdentity => Kind::VerifyIdentity(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlSslMode) -> Result<Self, TryFromProtoError> {
+        use proto_my_sql_ssl_mode::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoMySqlSslMode::kind"))?;
+        Ok(match kind {
+            Kind::Disabled(()) => MySqlSslMode::Disabled,
+            Kind::Required(()) => MySqlSslMode::Required,
+            Kind::VerifyCa(()) => MySqlSslMode::VerifyCa,
+            Kind::VerifyIdentity(()) => MySqlSslMode::VerifyIdentity,
+        })
+    }
+}
+
+/// A connection to a SQL Server database.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SqlServerConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The name of the database to connect to.
+    pub database: String,
+    /// The SQL Server login to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to use TLS for encryption, authentication, or both.
+    pub tls_mode: SqlServerSslMode,
+    /// An optional root TLS certificate in PEM format, to verify the server's
+    /// identity.
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+impl SqlServerConnection {
+    pub async fn config(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<mz_sql_server_util::Config, anyhow::Error> {
+        let password = match self.password {
+            Some(password) => Some(secrets_reader.read_string(password).await?),
+            None => None,
+        };
+        let tls_root_cert = match &self.tls_root_cert {
+            Some(tls_root_cert) => Some(tls_root_cert.get_string(secrets_reader).await?),
+            None => None,
+        };
+        Ok(mz_sql_server_util::Config {
+            host: self.host.clone(),
+            port: self.port,
+            database: self.database.clone(),
+            user: self.user.get_string(secrets_reader).await?,
+            password,
+            tls_mode: match self.tls_mode {
+                SqlServerSslMode::Disabled => mz_sql_server_util::TlsMode::Disabled,
+                SqlServerSslMode::Required => mz_sql_server_util::TlsMode::Required,
+                SqlServerSslMode::Verify => mz_sql_server_util::TlsMode::Verify,
+            },
+            tls_root_cert,
+        })
+    }
+}
+
+impl RustType<ProtoSqlServerConnection> for SqlServerConnection {
+    fn into_proto(&self) -> ProtoSqlServerConnection {
+        ProtoSqlServerConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            tls_mode: Some(self.tls_mode.into_proto()),
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSqlServerConnection::user")?,
+            password: proto.password.into_rust()?,
+            tls_mode: proto
+                .tls_mode
+                .into_rust_if_some("ProtoSqlServerConnection::tls_mode")?,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
+/// Whether a [`SqlServerConnection`] uses TLS for encryption, authentication,
+/// or both.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SqlServerSslMode {
+    Disabled,
+    Required,
+    Verify,
+}
+
+impl RustType<ProtoSqlServerSslMode> for SqlServerSslMode {
+    fn into_proto(&self) -> ProtoSqlServerSslMode {
+        use proto_sql_server_ssl_mode::Kind;
+        ProtoSqlServerSslMode {
+            kind: Some(match self {
+                SqlServerSslMode::Disabled => Kind::Disabled(()),
+                SqlServerSslMode::Required => Kind::Required(()),
+                SqlServerSslMode::Verify => Kind::Verify(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSslMode) -> Result<Self, TryFromProtoError> {
+        use proto_sql_server_ssl_mode::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoSqlServerSslMode::kind"))?;
+        Ok(match kind {
+            Kind::Disabled(()) => SqlServerSslMode::Disabled,
+            Kind::Required(()) => SqlServerSslMode::Required,
+            Kind::Verify(()) => SqlServerSslMode::Verify,
+        })
+    }
+}
+
+/// Returns the configuration of the tunnel through which to connect: the SSH
+/// tunnel, if both `ssh_tunnel_id` and `ssh_tunnel` are specified, reading the
+/// tunnel's keypair from the secret that shares the connection's ID, or else
+/// the AWS PrivateLink VPC endpoint, if `aws_privatelink` is specified.
+async fn tunnel_config(
+    ssh_tunnel_id: Option<GlobalId>,
+    ssh_tunnel: Option<&SshConnection>,
+    aws_privatelink: Option<&AwsPrivatelinkConnection>,
+    secrets_reader: &dyn SecretsReader,
+) -> Result<TunnelConfig, anyhow::Error> {
+    match (ssh_tunnel_id, ssh_tunnel, aws_privatelink) {
+        (Some(ssh_secret_id), Some(ssh_tunnel), _) => {
+            let secret = secrets_reader.read(ssh_secret_id).await?;
+            let keyset = mz_ore::ssh_key::SshKeyset::from_bytes(&secret)?;
+            let keypair = keyset.primary().clone();
+            Ok(TunnelConfig::Ssh(SshTunnelConfig {
+                host: ssh_tunnel.host.clone(),
+                port: ssh_tunnel.port,
+                user: ssh_tunnel.user.clone(),
+                keypair,
+            }))
+        }
+        (_, _, Some(aws_privatelink)) => Ok(TunnelConfig::AwsPrivatelink {
+            endpoint: aws_privatelink.endpoint.clone(),
+        }),
+        _ => Ok(TunnelConfig::Direct),
+    }
+}
+
 /// A connection to a SSH tunnel.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SshConnection {
@@ -759,3 +1341,26 @@ impl RustType<ProtoSshConnection> for SshConnection {
         })
     }
 }
+
+/// A connection to an AWS PrivateLink service, through a VPC endpoint that
+/// the user has created for the service.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct AwsPrivatelinkConnection {
+    /// The DNS name of the VPC endpoint, e.g.
+    /// `vpce-0123456789abcdef0-abcdefgh.vpce-svc-0123456789abcdef0.us-east-1.vpce.amazonaws.com`.
+    pub endpoint: String,
+}
+
+impl RustType<ProtoAwsPrivatelinkConnection> for AwsPrivatelinkConnection {
+    fn into_proto(&self) -> ProtoAwsPrivatelinkConnection {
+        ProtoAwsPrivatelinkConnection {
+            endpoint: self.endpoint.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoAwsPrivatelinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(AwsPrivatelinkConnection {
+            endpoint: proto.endpoint,
+        })
+    }
+}
@@ -9,6 +9,8 @@
 
 //! AWS configuration for sources and sinks.
 
+use std::collections::BTreeMap;
+
 use http::Uri;
 use mz_secrets::SecretsReader;
 use proptest::prelude::{Arbitrary, BoxedStrategy, Strategy};
@@ -165,6 +167,22 @@ impl RustType<ProtoAwsAssumeRole> for AwsAssumeRole {
 }
 
 impl AwsConfig {
+    /// Returns the overrides that are not credentials, keyed by option name,
+    /// for display in the catalog.
+    pub fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        if let Some(region) = &self.region {
+            options.insert("region".into(), region.clone());
+        }
+        if let Some(role) = &self.role {
+            options.insert("role_arn".into(), role.arn.clone());
+        }
+        if let Some(endpoint) = &self.endpoint {
+            options.insert("endpoint".into(), endpoint.0.to_string());
+        }
+        options
+    }
+
     /// Loads the AWS SDK configuration object from the environment, then
     /// applies the overrides from this object.
     pub async fn load(
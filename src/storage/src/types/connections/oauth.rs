@@ -0,0 +1,157 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Token providers for Kafka connections that authenticate using the
+//! `OAUTHBEARER` SASL mechanism.
+//!
+//! librdkafka invokes the providers from the threads that poll its clients,
+//! which are never Tokio worker threads, so the providers block on the
+//! runtime that constructed them to perform their requests.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use aws_sigv4::http_request::{sign, SignableRequest, SignatureLocation, SigningSettings};
+use aws_sigv4::SigningParams;
+use aws_types::credentials::ProvideCredentials;
+use rdkafka::client::OAuthToken;
+use serde::Deserialize;
+use tokio::runtime::Handle;
+use url::Url;
+
+use mz_kafka_util::client::OAuthTokenProvider;
+
+/// The lifetime of the tokens requested from token endpoints that do not
+/// report one.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// The lifetime of AWS MSK IAM authentication tokens.
+const AWS_IAM_TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// Returns the time at which a token that lives for `lifetime` from now
+/// expires, in milliseconds since the Unix epoch.
+fn expiry_ms(lifetime: Duration) -> i64 {
+    let expiry = SystemTime::now() + lifetime;
+    let expiry = expiry
+        .duration_since(UNIX_EPOCH)
+        .expect("expiry is after the epoch");
+    i64::try_from(expiry.as_millis()).unwrap_or(i64::MAX)
+}
+
+/// Requests tokens from an OAuth 2.0 token endpoint using the client
+/// credentials grant.
+pub(super) struct ClientCredentialsTokenProvider {
+    pub(super) runtime: Handle,
+    pub(super) http: reqwest::Client,
+    pub(super) token_endpoint: Url,
+    pub(super) client_id: String,
+    pub(super) client_secret: String,
+    pub(super) scope: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuthTokenProvider for ClientCredentialsTokenProvider {
+    fn generate_token(&self) -> Result<OAuthToken, anyhow::Error> {
+        self.runtime.block_on(async {
+            let mut form = vec![("grant_type", "client_credentials")];
+            if let Some(scope) = &self.scope {
+                form.push(("scope", scope));
+            }
+            let response: TokenResponse = self
+                .http
+                .post(self.token_endpoint.clone())
+                .basic_auth(&self.client_id, Some(&self.client_secret))
+                .form(&form)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .with_context(|| format!("requesting token from {}", self.token_endpoint))?
+                .json()
+                .await
+                .with_context(|| format!("decoding token from {}", self.token_endpoint))?;
+            let lifetime = response
+                .expires_in
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+            Ok(OAuthToken {
+                token: response.access_token,
+                principal_name: self.client_id.clone(),
+                lifetime_ms: expiry_ms(lifetime),
+            })
+        })
+    }
+}
+
+/// Signs AWS MSK IAM authentication tokens with the credentials of an AWS
+/// connection.
+///
+/// A token is a presigned `kafka-cluster:Connect` request, encoded as
+/// unpadded URL-safe base64.
+pub(super) struct AwsIamTokenProvider {
+    pub(super) runtime: Handle,
+    pub(super) sdk_config: aws_types::SdkConfig,
+}
+
+impl OAuthTokenProvider for AwsIamTokenProvider {
+    fn generate_token(&self) -> Result<OAuthToken, anyhow::Error> {
+        self.runtime.block_on(async {
+            let region = self
+                .sdk_config
+                .region()
+                .ok_or_else(|| anyhow!("AWS connection does not specify a region"))?
+                .to_string();
+            let credentials = self
+                .sdk_config
+                .credentials_provider()
+                .ok_or_else(|| anyhow!("AWS connection does not provide credentials"))?
+                .provide_credentials()
+                .await
+                .context("loading AWS credentials")?;
+
+            let mut request = http::Request::builder()
+                .method("GET")
+                .uri(format!(
+                    "https://kafka.{region}.amazonaws.com/?Action=kafka-cluster%3AConnect"
+                ))
+                .body("")?;
+
+            let mut settings = SigningSettings::default();
+            settings.signature_location = SignatureLocation::QueryParams;
+            settings.expires_in = Some(AWS_IAM_TOKEN_LIFETIME);
+            let mut params = SigningParams::builder()
+                .access_key(credentials.access_key_id())
+                .secret_key(credentials.secret_access_key())
+                .region(&region)
+                .service_name("kafka-cluster")
+                .time(SystemTime::now())
+                .settings(settings);
+            params.set_security_token(credentials.session_token());
+            let params = params
+                .build()
+                .map_err(|e| anyhow!("building AWS signing parameters: {e}"))?;
+
+            let (instructions, _signature) = sign(SignableRequest::from(&request), &params)
+                .map_err(|e| anyhow!("signing AWS MSK IAM token: {e}"))?
+                .into_parts();
+            instructions.apply_to_request(&mut request);
+
+            let url = format!("{}&User-Agent=materialize", request.uri());
+            Ok(OAuthToken {
+                token: base64::encode_config(url, base64::URL_SAFE_NO_PAD),
+                principal_name: credentials.access_key_id().to_string(),
+                lifetime_ms: expiry_ms(AWS_IAM_TOKEN_LIFETIME),
+            })
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Broker address rewriters for Kafka connections that reach their brokers
+//! through an SSH tunnel or an AWS PrivateLink VPC endpoint.
+//!
+//! librdkafka invokes the rewriters from the threads that poll its clients,
+//! which are never Tokio worker threads, so the rewriters block on the
+//! runtime that constructed them to open their tunnels.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use rdkafka::client::BrokerAddr;
+use tokio::runtime::Handle;
+use tracing::{info, warn};
+
+use mz_kafka_util::client::BrokerAddrRewriter;
+use mz_postgres_util::{SshTunnelConfig, SshTunnelHandle};
+
+/// Routes the connections to each broker through its own SSH tunnel.
+///
+/// The tunnel to a broker is opened the first time a client connects to the
+/// broker. librdkafka reconnects to a broker whenever its connection fails,
+/// so a tunnel whose SSH connection has died is replaced when the client next
+/// reconnects through it.
+pub(super) struct SshTunnelBrokerAddrRewriter {
+    pub(super) runtime: Handle,
+    pub(super) config: SshTunnelConfig,
+    /// The open tunnels, by the host and port of the broker they lead to.
+    pub(super) tunnels: Mutex<BTreeMap<(String, u16), SshTunnelHandle>>,
+}
+
+impl BrokerAddrRewriter for SshTunnelBrokerAddrRewriter {
+    fn rewrite(&self, addr: BrokerAddr) -> Result<BrokerAddr, anyhow::Error> {
+        let port: u16 = addr
+            .port
+            .parse()
+            .with_context(|| format!("parsing port of broker {}:{}", addr.host, addr.port))?;
+        let key = (addr.host, port);
+        let mut tunnels = self.tunnels.lock().expect("lock poisoned");
+        self.runtime.block_on(async {
+            if let Some(tunnel) = tunnels.get(&key) {
+                match tunnel.check().await {
+                    Ok(()) => return Ok(local_addr(tunnel)),
+                    Err(e) => {
+                        warn!(
+                            "SSH tunnel to broker {}:{} failed, reopening: {:#}",
+                            key.0, key.1, e
+                        );
+                        tunnels.remove(&key);
+                    }
+                }
+            }
+            let tunnel = self
+                .config
+                .connect(&key.0, key.1)
+                .await
+                .with_context(|| format!("opening SSH tunnel to broker {}:{}", key.0, key.1))?;
+            info!(
+                "opened SSH tunnel to broker {}:{} on local port {}",
+                key.0,
+                key.1,
+                tunnel.local_port()
+            );
+            let addr = local_addr(&tunnel);
+            tunnels.insert(key, tunnel);
+            Ok(addr)
+        })
+    }
+}
+
+/// Returns the local address at which `tunnel` listens.
+fn local_addr(tunnel: &SshTunnelHandle) -> BrokerAddr {
+    BrokerAddr {
+        host: "localhost".into(),
+        port: tunnel.local_port().to_string(),
+    }
+}
+
+/// Routes the connections to each broker through an AWS PrivateLink VPC
+/// endpoint, on the port the broker advertises.
+///
+/// This overrides the DNS name of every broker with that of the endpoint, so
+/// the load balancer of the endpoint service must forward each port to the
+/// broker that advertises it.
+pub(super) struct AwsPrivatelinkBrokerAddrRewriter {
+    pub(super) endpoint: String,
+}
+
+impl BrokerAddrRewriter for AwsPrivatelinkBrokerAddrRewriter {
+    fn rewrite(&self, addr: BrokerAddr) -> Result<BrokerAddr, anyhow::Error> {
+        Ok(BrokerAddr {
+            host: self.endpoint.clone(),
+            port: addr.port,
+        })
+    }
+}
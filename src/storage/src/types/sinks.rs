@@ -10,6 +10,7 @@
 //! Types and traits related to reporting changing collections out of `dataflow`.
 
 use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
 
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
@@ -17,12 +18,15 @@ use serde::{Deserialize, Serialize};
 use timely::progress::frontier::Antichain;
 use timely::PartialOrder;
 
+use mz_expr::MirScalarExpr;
+use mz_persist_client::ShardId;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{GlobalId, RelationDesc};
 
 use crate::controller::CollectionMetadata;
+use crate::types::connections::aws::AwsConfig;
 use crate::types::connections::{
-    CsrConnection, KafkaConnection, PopulateClientConfig, StringOrSecret,
+    CsrConnection, KafkaConnection, PopulateClientConfig, PostgresConnection, StringOrSecret,
 };
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage.types.sinks.rs"));
@@ -36,6 +40,41 @@ pub struct StorageSinkDesc<S = (), T = mz_repr::Timestamp> {
     pub envelope: Option<SinkEnvelope>,
     pub as_of: SinkAsOf<T>,
     pub from_storage_metadata: S,
+    /// The rate at which the sink emits updates while it catches up with its
+    /// input, if limited.
+    pub catch_up_limit: Option<SinkRateLimit>,
+    /// The rate at which the sink emits updates once it has caught up with
+    /// its input, if limited.
+    pub rate_limit: Option<SinkRateLimit>,
+    /// The columns of `from` that the sink emits, in order, or empty if the
+    /// sink emits all of them.
+    pub projection: Vec<usize>,
+    /// The masks to apply to the projected columns before they are emitted.
+    pub column_masks: Vec<SinkColumnMask>,
+    /// Whether the sink appends the timestamp of each update to the row it
+    /// emits, as the `mz_timestamp` column.
+    pub include_timestamp: bool,
+    /// Whether the sink appends the diff of each update to the row it emits,
+    /// as the `mz_diff` column, emitting retractions as rows rather than
+    /// applying them.
+    pub include_diff: bool,
+    /// The predicate on the rows of `from` that the rows the sink emits must
+    /// satisfy, if any.
+    pub filter: Option<MirScalarExpr>,
+    /// The persist shard that durably records the progress of the sink, if
+    /// it keeps one. Assigned by the storage controller.
+    pub progress_shard: Option<ShardId>,
+}
+
+impl<S, T> StorageSinkDesc<S, T> {
+    /// Returns the index in `from` of the `column`th column the sink emits.
+    pub fn from_column(&self, column: usize) -> usize {
+        if self.projection.is_empty() {
+            column
+        } else {
+            self.projection[column]
+        }
+    }
 }
 
 impl Arbitrary for StorageSinkDesc<CollectionMetadata, mz_repr::Timestamp> {
@@ -50,9 +89,28 @@ impl Arbitrary for StorageSinkDesc<CollectionMetadata, mz_repr::Timestamp> {
             any::<Option<SinkEnvelope>>(),
             any::<SinkAsOf<mz_repr::Timestamp>>(),
             any::<CollectionMetadata>(),
+            any::<Option<SinkRateLimit>>(),
+            any::<Option<SinkRateLimit>>(),
+            any::<Vec<usize>>(),
+            any::<Vec<SinkColumnMask>>(),
+            any::<(bool, bool)>(),
+            any::<(Option<MirScalarExpr>, Option<ShardId>)>(),
         )
             .prop_map(
-                |(from, from_desc, connection, envelope, as_of, from_storage_metadata)| {
+                |(
+                    from,
+                    from_desc,
+                    connection,
+                    envelope,
+                    as_of,
+                    from_storage_metadata,
+                    catch_up_limit,
+                    rate_limit,
+                    projection,
+                    column_masks,
+                    (include_timestamp, include_diff),
+                    (filter, progress_shard),
+                )| {
                     StorageSinkDesc {
                         from,
                         from_desc,
@@ -60,6 +118,14 @@ impl Arbitrary for StorageSinkDesc<CollectionMetadata, mz_repr::Timestamp> {
                         envelope,
                         as_of,
                         from_storage_metadata,
+                        catch_up_limit,
+                        rate_limit,
+                        projection,
+                        column_masks,
+                        include_timestamp,
+                        include_diff,
+                        filter,
+                        progress_shard,
                     }
                 },
             )
@@ -76,6 +142,14 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<CollectionMetadata, mz_r
             envelope: self.envelope.into_proto(),
             as_of: Some(self.as_of.into_proto()),
             from_storage_metadata: Some(self.from_storage_metadata.into_proto()),
+            catch_up_limit: self.catch_up_limit.into_proto(),
+            rate_limit: self.rate_limit.into_proto(),
+            projection: self.projection.into_proto(),
+            column_masks: self.column_masks.into_proto(),
+            include_timestamp: self.include_timestamp,
+            include_diff: self.include_diff,
+            filter: self.filter.into_proto(),
+            progress_shard: self.progress_shard.map(|s| s.to_string()),
         }
     }
 
@@ -95,6 +169,94 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<CollectionMetadata, mz_r
             from_storage_metadata: proto
                 .from_storage_metadata
                 .into_rust_if_some("ProtoStorageSinkDesc::from_storage_metadata")?,
+            catch_up_limit: proto.catch_up_limit.into_rust()?,
+            rate_limit: proto.rate_limit.into_rust()?,
+            projection: proto.projection.into_rust()?,
+            column_masks: proto.column_masks.into_rust()?,
+            include_timestamp: proto.include_timestamp,
+            include_diff: proto.include_diff,
+            filter: proto.filter.into_rust()?,
+            progress_shard: proto
+                .progress_shard
+                .map(|s| s.parse().map_err(TryFromProtoError::InvalidShardId))
+                .transpose()?,
+        })
+    }
+}
+
+/// A mask that a sink applies to the values of a column before emitting them,
+/// to keep sensitive data out of its destination.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SinkColumnMask {
+    /// The index of the masked column.
+    pub column: usize,
+    pub kind: SinkColumnMaskKind,
+}
+
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SinkColumnMaskKind {
+    /// Replaces values with the hex-encoded SHA-256 digest of their text
+    /// representation.
+    Hash,
+    /// Replaces values with NULL.
+    Redact,
+    /// Keeps only the first `length` characters of string values.
+    Truncate { length: usize },
+}
+
+impl RustType<ProtoSinkColumnMask> for SinkColumnMask {
+    fn into_proto(&self) -> ProtoSinkColumnMask {
+        use proto_sink_column_mask::Kind;
+        ProtoSinkColumnMask {
+            column: self.column.into_proto(),
+            kind: Some(match self.kind {
+                SinkColumnMaskKind::Hash => Kind::Hash(()),
+                SinkColumnMaskKind::Redact => Kind::Redact(()),
+                SinkColumnMaskKind::Truncate { length } => Kind::Truncate(length.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkColumnMask) -> Result<Self, TryFromProtoError> {
+        use proto_sink_column_mask::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkColumnMask::kind"))?;
+        Ok(SinkColumnMask {
+            column: proto.column.into_rust()?,
+            kind: match kind {
+                Kind::Hash(()) => SinkColumnMaskKind::Hash,
+                Kind::Redact(()) => SinkColumnMaskKind::Redact,
+                Kind::Truncate(length) => SinkColumnMaskKind::Truncate {
+                    length: length.into_rust()?,
+                },
+            },
+        })
+    }
+}
+
+/// A limit on the rate at which a sink emits updates.
+///
+/// Updates are counted by their multiplicity, and bytes by the encoded size
+/// of their key and value rows.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SinkRateLimit {
+    pub records_per_second: Option<u64>,
+    pub bytes_per_second: Option<u64>,
+}
+
+impl RustType<ProtoSinkRateLimit> for SinkRateLimit {
+    fn into_proto(&self) -> ProtoSinkRateLimit {
+        ProtoSinkRateLimit {
+            records_per_second: self.records_per_second,
+            bytes_per_second: self.bytes_per_second,
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkRateLimit) -> Result<Self, TryFromProtoError> {
+        Ok(SinkRateLimit {
+            records_per_second: proto.records_per_second,
+            bytes_per_second: proto.bytes_per_second,
         })
     }
 }
@@ -102,7 +264,14 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<CollectionMetadata, mz_r
 #[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SinkEnvelope {
     Debezium,
-    Upsert,
+    /// Emits the latest value of each key, handling deletes according to the
+    /// policy.
+    Upsert(SinkDeletePolicy),
+    /// Emits only insertions, handling retractions according to the policy.
+    AppendOnly(SinkRetractionPolicy),
+    /// Emits batches of updates and progress statements in the differential
+    /// CDCv2 format, preserving exact timestamps and multiplicities.
+    CdcV2,
 }
 
 impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
@@ -111,7 +280,9 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
         ProtoSinkEnvelope {
             kind: Some(match self {
                 SinkEnvelope::Debezium => Kind::Debezium(()),
-                SinkEnvelope::Upsert => Kind::Upsert(()),
+                SinkEnvelope::Upsert(policy) => Kind::Upsert(policy.into_proto()),
+                SinkEnvelope::AppendOnly(policy) => Kind::AppendOnly(policy.into_proto()),
+                SinkEnvelope::CdcV2 => Kind::CdcV2(()),
             }),
         }
     }
@@ -123,7 +294,84 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkEnvelope::kind"))?;
         Ok(match kind {
             Kind::Debezium(()) => SinkEnvelope::Debezium,
-            Kind::Upsert(()) => SinkEnvelope::Upsert,
+            Kind::Upsert(policy) => SinkEnvelope::Upsert(policy.into_rust()?),
+            Kind::AppendOnly(policy) => SinkEnvelope::AppendOnly(policy.into_rust()?),
+            Kind::CdcV2(()) => SinkEnvelope::CdcV2,
+        })
+    }
+}
+
+/// How an upsert sink handles the deletion of a key.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SinkDeletePolicy {
+    /// Emit a message with the key and a null value.
+    Tombstone,
+    /// Emit the deleted row with its trailing marker column set to `true`.
+    ///
+    /// The marker column is the last column of the sink's value
+    /// description, and is `false` for all other messages.
+    DeleteRecord,
+    /// Emit nothing.
+    Suppress,
+}
+
+impl RustType<ProtoSinkDeletePolicy> for SinkDeletePolicy {
+    fn into_proto(&self) -> ProtoSinkDeletePolicy {
+        use proto_sink_delete_policy::Kind;
+        ProtoSinkDeletePolicy {
+            kind: Some(match self {
+                SinkDeletePolicy::Tombstone => Kind::Tombstone(()),
+                SinkDeletePolicy::DeleteRecord => Kind::DeleteRecord(()),
+                SinkDeletePolicy::Suppress => Kind::Suppress(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkDeletePolicy) -> Result<Self, TryFromProtoError> {
+        use proto_sink_delete_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkDeletePolicy::kind"))?;
+        Ok(match kind {
+            Kind::Tombstone(()) => SinkDeletePolicy::Tombstone,
+            Kind::DeleteRecord(()) => SinkDeletePolicy::DeleteRecord,
+            Kind::Suppress(()) => SinkDeletePolicy::Suppress,
+        })
+    }
+}
+
+/// How an append-only sink handles the retraction of a row.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SinkRetractionPolicy {
+    /// Fail the sink.
+    Error,
+    /// Silently skip the retraction.
+    Drop,
+    /// Emit a message with the row's key and no value.
+    DeleteMarker,
+}
+
+impl RustType<ProtoSinkRetractionPolicy> for SinkRetractionPolicy {
+    fn into_proto(&self) -> ProtoSinkRetractionPolicy {
+        use proto_sink_retraction_policy::Kind;
+        ProtoSinkRetractionPolicy {
+            kind: Some(match self {
+                SinkRetractionPolicy::Error => Kind::Error(()),
+                SinkRetractionPolicy::Drop => Kind::Drop(()),
+                SinkRetractionPolicy::DeleteMarker => Kind::DeleteMarker(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkRetractionPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_sink_retraction_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkRetractionPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Error(()) => SinkRetractionPolicy::Error,
+            Kind::Drop(()) => SinkRetractionPolicy::Drop,
+            Kind::DeleteMarker(()) => SinkRetractionPolicy::DeleteMarker,
         })
     }
 }
@@ -186,6 +434,15 @@ impl RustType<ProtoSinkAsOf> for SinkAsOf<mz_repr::Timestamp> {
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum StorageSinkConnection {
     Kafka(KafkaSinkConnection),
+    S3(S3SinkConnection),
+    Postgres(PostgresSinkConnection),
+    File(FileSinkConnection),
+    Elasticsearch(ElasticsearchSinkConnection),
+    Redis(RedisSinkConnection),
+    Nats(NatsSinkConnection),
+    Mqtt(MqttSinkConnection),
+    Grpc(GrpcSinkConnection),
+    Sqs(SqsSinkConnection),
 }
 
 impl StorageSinkConnection {
@@ -194,6 +451,10 @@ impl StorageSinkConnection {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            S3(S3SinkConnection { connection_id, .. }) => Some(*connection_id),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Sqs(SqsSinkConnection { connection_id, .. }) => Some(*connection_id),
+            File(_) | Elasticsearch(_) | Redis(_) | Nats(_) | Mqtt(_) | Grpc(_) => None,
         }
     }
 }
@@ -204,6 +465,17 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 StorageSinkConnection::Kafka(kafka) => Kind::Kafka(kafka.into_proto()),
+                StorageSinkConnection::S3(s3) => Kind::S3(s3.into_proto()),
+                StorageSinkConnection::Postgres(postgres) => Kind::Postgres(postgres.into_proto()),
+                StorageSinkConnection::File(file) => Kind::File(file.into_proto()),
+                StorageSinkConnection::Elasticsearch(elasticsearch) => {
+                    Kind::Elasticsearch(elasticsearch.into_proto())
+                }
+                StorageSinkConnection::Redis(redis) => Kind::Redis(redis.into_proto()),
+                StorageSinkConnection::Nats(nats) => Kind::Nats(nats.into_proto()),
+                StorageSinkConnection::Mqtt(mqtt) => Kind::Mqtt(mqtt.into_proto()),
+                StorageSinkConnection::Grpc(grpc) => Kind::Grpc(grpc.into_proto()),
+                StorageSinkConnection::Sqs(sqs) => Kind::Sqs(sqs.into_proto()),
             }),
         }
     }
@@ -215,6 +487,17 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoStorageSinkConnection::kind"))?;
         Ok(match kind {
             Kind::Kafka(kafka) => StorageSinkConnection::Kafka(kafka.into_rust()?),
+            Kind::S3(s3) => StorageSinkConnection::S3(s3.into_rust()?),
+            Kind::Postgres(postgres) => StorageSinkConnection::Postgres(postgres.into_rust()?),
+            Kind::File(file) => StorageSinkConnection::File(file.into_rust()?),
+            Kind::Elasticsearch(elasticsearch) => {
+                StorageSinkConnection::Elasticsearch(elasticsearch.into_rust()?)
+            }
+            Kind::Redis(redis) => StorageSinkConnection::Redis(redis.into_rust()?),
+            Kind::Nats(nats) => StorageSinkConnection::Nats(nats.into_rust()?),
+            Kind::Mqtt(mqtt) => StorageSinkConnection::Mqtt(mqtt.into_rust()?),
+            Kind::Grpc(grpc) => StorageSinkConnection::Grpc(grpc.into_rust()?),
+            Kind::Sqs(sqs) => StorageSinkConnection::Sqs(sqs.into_rust()?),
         })
     }
 }
@@ -246,10 +529,37 @@ pub struct KafkaSinkConnection {
     pub relation_key_indices: Option<Vec<usize>>,
     pub value_desc: RelationDesc,
     pub published_schema_info: Option<PublishedSchemaInfo>,
+    /// The encoding of the records, if they are neither encoded with
+    /// published schemas nor as JSON.
+    pub text_format: Option<KafkaSinkTextFormat>,
+    /// The columns of `value_desc` whose values are attached to each message
+    /// as headers.
+    pub headers: Vec<KafkaSinkHeader>,
+    /// An expression over the columns of `value_desc` that selects the
+    /// partition each message is written to, in place of the hash of its key.
+    pub partition_by: Option<MirScalarExpr>,
+    /// The source of the timestamp of each message, if not the time at which
+    /// the message is produced.
+    pub message_timestamp: Option<KafkaSinkMessageTimestamp>,
+    /// The codec with which the producer compresses batches of messages.
+    pub compression_type: KafkaSinkCompressionType,
+    /// How long the producer waits to accumulate messages into a batch, if
+    /// not the default.
+    pub linger_ms: Option<u64>,
+    /// The maximum number of messages the producer puts into a batch, if not
+    /// the default.
+    pub batch_num_messages: Option<u64>,
+    /// The topic to which messages that the broker permanently rejects are
+    /// published instead, if any.
+    pub dead_letter_topic: Option<String>,
+    /// The retention configured on `topic`.
+    pub retention: KafkaSinkConnectionRetention,
+    /// How messages that the broker fails to accept are retried.
+    pub retry: KafkaSinkRetryPolicy,
+    /// How rows are fanned out to a topic per value of a column, if they
+    /// are not all published to `topic`.
+    pub topic_fanout: Option<KafkaSinkTopicFanout>,
     pub progress: KafkaSinkProgressConnection,
-    // Maximum number of records the sink will attempt to send each time it is
-    // invoked
-    pub fuel: usize,
 }
 
 impl PopulateClientConfig for KafkaSinkConnection {
@@ -274,8 +584,18 @@ proptest::prop_compose! {
         relation_key_indices in any::<Option<Vec<usize>>>(),
         value_desc in any::<RelationDesc>(),
         published_schema_info in any::<Option<PublishedSchemaInfo>>(),
+        text_format in any::<Option<KafkaSinkTextFormat>>(),
+        headers in any::<Vec<KafkaSinkHeader>>(),
+        partition_by in any::<Option<MirScalarExpr>>(),
+        message_timestamp in any::<Option<KafkaSinkMessageTimestamp>>(),
+        compression_type in any::<KafkaSinkCompressionType>(),
+        linger_ms in any::<Option<u64>>(),
+        batch_num_messages in any::<Option<u64>>(),
+        dead_letter_topic in any::<Option<String>>(),
+        retention in any::<KafkaSinkConnectionRetention>(),
+        retry in any::<KafkaSinkRetryPolicy>(),
+        topic_fanout in any::<Option<KafkaSinkTopicFanout>>(),
         progress in any::<KafkaSinkProgressConnection>(),
-        fuel in any::<usize>(),
     ) -> KafkaSinkConnection {
         KafkaSinkConnection {
             connection,
@@ -286,8 +606,18 @@ proptest::prop_compose! {
             relation_key_indices,
             value_desc,
             published_schema_info,
+            text_format,
+            headers,
+            partition_by,
+            message_timestamp,
+            compression_type,
+            linger_ms,
+            batch_num_messages,
+            dead_letter_topic,
+            retention,
+            retry,
+            topic_fanout,
             progress,
-            fuel,
         }
     }
 }
@@ -350,8 +680,19 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
             relation_key_indices: self.relation_key_indices.into_proto(),
             value_desc: Some(self.value_desc.into_proto()),
             published_schema_info: self.published_schema_info.into_proto(),
+            text_format: self.text_format.into_proto(),
+            headers: self.headers.into_proto(),
+            partition_by: self.partition_by.into_proto(),
+            message_timestamp: self.message_timestamp.into_proto(),
+            compression_type: Some(self.compression_type.into_proto()),
+            linger_ms: self.linger_ms,
+            batch_num_messages: self.batch_num_messages,
+            dead_letter_topic: self.dead_letter_topic.clone(),
+            retention_ms: self.retention.duration,
+            retention_bytes: self.retention.bytes,
+            retry: Some(self.retry.into_proto()),
+            topic_fanout: self.topic_fanout.into_proto(),
             progress: Some(self.progress.into_proto()),
-            fuel: self.fuel.into_proto(),
         }
     }
 
@@ -377,10 +718,811 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
                 .value_desc
                 .into_rust_if_some("ProtoKafkaSinkConnection::addrs")?,
             published_schema_info: proto.published_schema_info.into_rust()?,
+            text_format: proto.text_format.into_rust()?,
+            headers: proto.headers.into_rust()?,
+            partition_by: proto.partition_by.into_rust()?,
+            message_timestamp: proto.message_timestamp.into_rust()?,
+            compression_type: proto
+                .compression_type
+                .into_rust_if_some("ProtoKafkaSinkConnection::compression_type")?,
+            linger_ms: proto.linger_ms,
+            batch_num_messages: proto.batch_num_messages,
+            dead_letter_topic: proto.dead_letter_topic,
+            retention: KafkaSinkConnectionRetention {
+                duration: proto.retention_ms,
+                bytes: proto.retention_bytes,
+            },
+            retry: proto
+                .retry
+                .into_rust_if_some("ProtoKafkaSinkConnection::retry")?,
+            topic_fanout: proto.topic_fanout.into_rust()?,
             progress: proto
                 .progress
                 .into_rust_if_some("ProtoKafkaSinkConnection::progress")?,
-            fuel: proto.fuel.into_rust()?,
+        })
+    }
+}
+
+/// The encoding of the objects written by an S3 sink.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3SinkFormat {
+    /// Newline-delimited JSON, one record per line.
+    Json,
+    /// Apache Parquet, one row group per object.
+    Parquet,
+}
+
+impl S3SinkFormat {
+    /// Returns the file extension used for objects in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            S3SinkFormat::Json => "json",
+            S3SinkFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
+    fn into_proto(&self) -> ProtoS3SinkFormat {
+        use proto_s3_sink_format::Kind;
+        ProtoS3SinkFormat {
+            kind: Some(match self {
+                S3SinkFormat::Json => Kind::Json(()),
+                S3SinkFormat::Parquet => Kind::Parquet(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkFormat) -> Result<Self, TryFromProtoError> {
+        use proto_s3_sink_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3SinkFormat::kind"))?;
+        Ok(match kind {
+            Kind::Json(()) => S3SinkFormat::Json,
+            Kind::Parquet(()) => S3SinkFormat::Parquet,
+        })
+    }
+}
+
+/// An open table format whose tables an S3 sink can commit its objects to.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3TableFormat {
+    /// A Delta Lake table, whose transaction log is stored under
+    /// `_delta_log/`.
+    Delta,
+    /// An Apache Iceberg table, whose metadata is stored under `metadata/`
+    /// and tracked by a version hint, as in Iceberg's Hadoop catalog.
+    Iceberg,
+}
+
+impl RustType<ProtoS3TableFormat> for S3TableFormat {
+    fn into_proto(&self) -> ProtoS3TableFormat {
+        use proto_s3_table_format::Kind;
+        ProtoS3TableFormat {
+            kind: Some(match self {
+                S3TableFormat::Delta => Kind::Delta(()),
+                S3TableFormat::Iceberg => Kind::Iceberg(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3TableFormat) -> Result<Self, TryFromProtoError> {
+        use proto_s3_table_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3TableFormat::kind"))?;
+        Ok(match kind {
+            Kind::Delta(()) => S3TableFormat::Delta,
+            Kind::Iceberg(()) => S3TableFormat::Iceberg,
+        })
+    }
+}
+
+/// A segment of the template from which an S3 sink names its objects.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3KeyTemplateSegment {
+    /// Literal text.
+    Literal(String),
+    /// The ID of the sink.
+    SinkId,
+    /// The least timestamp in the object.
+    Lower,
+    /// The greatest timestamp in the object.
+    Upper,
+    /// The position of the object among the objects written by this sink
+    /// since it was last started.
+    Sequence,
+    /// The file extension of the sink's format.
+    Extension,
+}
+
+impl RustType<ProtoS3KeyTemplateSegment> for S3KeyTemplateSegment {
+    fn into_proto(&self) -> ProtoS3KeyTemplateSegment {
+        use proto_s3_key_template_segment::Kind;
+        ProtoS3KeyTemplateSegment {
+            kind: Some(match self {
+                S3KeyTemplateSegment::Literal(literal) => Kind::Literal(literal.clone()),
+                S3KeyTemplateSegment::SinkId => Kind::SinkId(()),
+                S3KeyTemplateSegment::Lower => Kind::Lower(()),
+                S3KeyTemplateSegment::Upper => Kind::Upper(()),
+                S3KeyTemplateSegment::Sequence => Kind::Sequence(()),
+                S3KeyTemplateSegment::Extension => Kind::Extension(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3KeyTemplateSegment) -> Result<Self, TryFromProtoError> {
+        use proto_s3_key_template_segment::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3KeyTemplateSegment::kind"))?;
+        Ok(match kind {
+            Kind::Literal(literal) => S3KeyTemplateSegment::Literal(literal),
+            Kind::SinkId(()) => S3KeyTemplateSegment::SinkId,
+            Kind::Lower(()) => S3KeyTemplateSegment::Lower,
+            Kind::Upper(()) => S3KeyTemplateSegment::Upper,
+            Kind::Sequence(()) => S3KeyTemplateSegment::Sequence,
+            Kind::Extension(()) => S3KeyTemplateSegment::Extension,
+        })
+    }
+}
+
+/// What an S3 sink does when the key it is about to write already names an
+/// object that it did not write itself.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3CollisionPolicy {
+    /// Fail the sink.
+    Error,
+    /// Replace the existing object.
+    Overwrite,
+    /// Append the smallest numeric suffix to the object name that yields an
+    /// unused key.
+    Suffix,
+}
+
+impl RustType<ProtoS3CollisionPolicy> for S3CollisionPolicy {
+    fn into_proto(&self) -> ProtoS3CollisionPolicy {
+        use proto_s3_collision_policy::Kind;
+        ProtoS3CollisionPolicy {
+            kind: Some(match self {
+                S3CollisionPolicy::Error => Kind::Error(()),
+                S3CollisionPolicy::Overwrite => Kind::Overwrite(()),
+                S3CollisionPolicy::Suffix => Kind::Suffix(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3CollisionPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_s3_collision_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3CollisionPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Error(()) => S3CollisionPolicy::Error,
+            Kind::Overwrite(()) => S3CollisionPolicy::Overwrite,
+            Kind::Suffix(()) => S3CollisionPolicy::Suffix,
+        })
+    }
+}
+
+/// A sink that writes the sinked collection as a sequence of objects into an
+/// S3 bucket.
+///
+/// Objects are named by rendering `key_template` beneath `prefix`, and are
+/// rotated once they exceed `max_file_size` bytes or have been open for longer
+/// than `rotation_interval`. Before each object is written, its key is checked
+/// for an existing object not written by this sink, which is handled
+/// according to `on_collision`.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnection {
+    pub connection_id: GlobalId,
+    pub aws: AwsConfig,
+    pub bucket: String,
+    pub prefix: String,
+    pub format: S3SinkFormat,
+    pub value_desc: RelationDesc,
+    pub max_file_size: u64,
+    pub rotation_interval: Duration,
+    /// The table format, if any, of the table rooted at `prefix` that each
+    /// object is committed to once it is written.
+    pub table_format: Option<S3TableFormat>,
+    pub key_template: Vec<S3KeyTemplateSegment>,
+    pub on_collision: S3CollisionPolicy,
+}
+
+impl RustType<ProtoS3SinkConnection> for S3SinkConnection {
+    fn into_proto(&self) -> ProtoS3SinkConnection {
+        ProtoS3SinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            aws: Some(self.aws.into_proto()),
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            format: Some(self.format.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+            max_file_size: self.max_file_size,
+            rotation_interval: Some(self.rotation_interval.into_proto()),
+            table_format: self.table_format.into_proto(),
+            key_template: self.key_template.into_proto(),
+            on_collision: Some(self.on_collision.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(S3SinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoS3SinkConnection::connection_id")?,
+            aws: proto.aws.into_rust_if_some("ProtoS3SinkConnection::aws")?,
+            bucket: proto.bucket,
+            prefix: proto.prefix,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoS3SinkConnection::format")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoS3SinkConnection::value_desc")?,
+            max_file_size: proto.max_file_size,
+            rotation_interval: proto
+                .rotation_interval
+                .into_rust_if_some("ProtoS3SinkConnection::rotation_interval")?,
+            table_format: proto.table_format.into_rust()?,
+            key_template: proto.key_template.into_rust()?,
+            on_collision: proto
+                .on_collision
+                .into_rust_if_some("ProtoS3SinkConnection::on_collision")?,
+        })
+    }
+}
+
+/// A sink that applies the upserts of the sinked collection to a table in an
+/// upstream PostgreSQL database.
+///
+/// The table is keyed on the columns at `key_indices`, and all updates at a
+/// batch of closed timestamps are applied within a single transaction.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSinkConnection {
+    pub connection_id: GlobalId,
+    pub connection: PostgresConnection,
+    /// The name of the upstream table, as a (possibly qualified) SQL
+    /// identifier that is already quoted as necessary.
+    pub table: String,
+    pub key_indices: Vec<usize>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the upstream database is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoPostgresSinkConnection> for PostgresSinkConnection {
+    fn into_proto(&self) -> ProtoPostgresSinkConnection {
+        ProtoPostgresSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            table: self.table.clone(),
+            key_indices: self.key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoPostgresSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PostgresSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection")?,
+            table: proto.table,
+            key_indices: proto.key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoPostgresSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// The encoding of the files written by a file sink.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileSinkFormat {
+    /// An Avro object container file.
+    AvroOcf,
+    /// Comma-separated values, optionally preceded by a header row.
+    Csv {
+        delimiter: char,
+        header: Option<Vec<String>>,
+    },
+    /// Newline-delimited JSON, one record per line.
+    Json,
+}
+
+impl RustType<ProtoFileSinkFormat> for FileSinkFormat {
+    fn into_proto(&self) -> ProtoFileSinkFormat {
+        use proto_file_sink_format::{Kind, ProtoCsv};
+        ProtoFileSinkFormat {
+            kind: Some(match self {
+                FileSinkFormat::AvroOcf => Kind::AvroOcf(()),
+                FileSinkFormat::Csv { delimiter, header } => Kind::Csv(ProtoCsv {
+                    delimiter: delimiter.into_proto(),
+                    header: header.clone().unwrap_or_default(),
+                }),
+                FileSinkFormat::Json => Kind::Json(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSinkFormat) -> Result<Self, TryFromProtoError> {
+        use proto_file_sink_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoFileSinkFormat::kind"))?;
+        Ok(match kind {
+            Kind::AvroOcf(()) => FileSinkFormat::AvroOcf,
+            Kind::Csv(csv) => FileSinkFormat::Csv {
+                delimiter: csv.delimiter.into_rust()?,
+                header: if csv.header.is_empty() {
+                    None
+                } else {
+                    Some(csv.header)
+                },
+            },
+            Kind::Json(()) => FileSinkFormat::Json,
+        })
+    }
+}
+
+/// A sink that appends the sinked collection to a file on the local
+/// filesystem of the storage replica.
+///
+/// If `max_file_size` or `rotation_interval` is set, the sink instead writes a
+/// sequence of files named after `path` and the first timestamp they contain.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSinkConnection {
+    pub path: String,
+    pub format: FileSinkFormat,
+    pub value_desc: RelationDesc,
+    /// Whether to fsync the file after writing each batch of updates.
+    pub fsync: bool,
+    pub max_file_size: Option<u64>,
+    pub rotation_interval: Option<Duration>,
+}
+
+impl FileSinkConnection {
+    /// Reports whether the sink writes a sequence of files rather than a single
+    /// one.
+    pub fn rotates(&self) -> bool {
+        self.max_file_size.is_some() || self.rotation_interval.is_some()
+    }
+}
+
+impl RustType<ProtoFileSinkConnection> for FileSinkConnection {
+    fn into_proto(&self) -> ProtoFileSinkConnection {
+        ProtoFileSinkConnection {
+            path: self.path.clone(),
+            format: Some(self.format.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+            fsync: self.fsync,
+            max_file_size: self.max_file_size,
+            rotation_interval: self.rotation_interval.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(FileSinkConnection {
+            path: proto.path,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoFileSinkConnection::format")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoFileSinkConnection::value_desc")?,
+            fsync: proto.fsync,
+            max_file_size: proto.max_file_size,
+            rotation_interval: proto.rotation_interval.into_rust()?,
+        })
+    }
+}
+
+/// A sink that indexes the upserts of the sinked collection into an
+/// Elasticsearch or OpenSearch index via the bulk API.
+///
+/// Each row is indexed as a document whose `_id` is derived from the columns
+/// at `key_indices`; retractions of a key delete the corresponding document.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ElasticsearchSinkConnection {
+    /// The base URL of the cluster.
+    pub url: String,
+    pub index: String,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    pub key_indices: Vec<usize>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the cluster is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoElasticsearchSinkConnection> for ElasticsearchSinkConnection {
+    fn into_proto(&self) -> ProtoElasticsearchSinkConnection {
+        ProtoElasticsearchSinkConnection {
+            url: self.url.clone(),
+            index: self.index.clone(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            key_indices: self.key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoElasticsearchSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(ElasticsearchSinkConnection {
+            url: proto.url,
+            index: proto.index,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            key_indices: proto.key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoElasticsearchSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// The Redis data type that a Redis sink writes rows as.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RedisSinkDataType {
+    /// A hash with a field for each non-null column, in its text format.
+    Hash,
+    /// A string containing the JSON encoding of the row.
+    String,
+}
+
+impl RustType<ProtoRedisSinkDataType> for RedisSinkDataType {
+    fn into_proto(&self) -> ProtoRedisSinkDataType {
+        use proto_redis_sink_data_type::Kind;
+        ProtoRedisSinkDataType {
+            kind: Some(match self {
+                RedisSinkDataType::Hash => Kind::Hash(()),
+                RedisSinkDataType::String => Kind::String(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoRedisSinkDataType) -> Result<Self, TryFromProtoError> {
+        use proto_redis_sink_data_type::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoRedisSinkDataType::kind"))?;
+        Ok(match kind {
+            Kind::Hash(()) => RedisSinkDataType::Hash,
+            Kind::String(()) => RedisSinkDataType::String,
+        })
+    }
+}
+
+/// A sink that writes the upserts of the sinked collection to a Redis server.
+///
+/// Each row is written to the key formed by `key_prefix` and the columns at
+/// `key_indices`; retractions of a key delete it.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RedisSinkConnection {
+    pub url: String,
+    pub key_prefix: String,
+    pub data_type: RedisSinkDataType,
+    /// The time after which written keys expire, if any.
+    pub ttl: Option<Duration>,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    pub key_indices: Vec<usize>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the server is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoRedisSinkConnection> for RedisSinkConnection {
+    fn into_proto(&self) -> ProtoRedisSinkConnection {
+        ProtoRedisSinkConnection {
+            url: self.url.clone(),
+            key_prefix: self.key_prefix.clone(),
+            data_type: Some(self.data_type.into_proto()),
+            ttl: self.ttl.into_proto(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            key_indices: self.key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoRedisSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(RedisSinkConnection {
+            url: proto.url,
+            key_prefix: proto.key_prefix,
+            data_type: proto
+                .data_type
+                .into_rust_if_some("ProtoRedisSinkConnection::data_type")?,
+            ttl: proto.ttl.into_rust()?,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            key_indices: proto.key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoRedisSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// A segment of a template, such as the subject template of a NATS sink,
+/// whose rendering depends on the key of each update.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KeyTemplateSegment {
+    /// Literal text.
+    Literal(String),
+    /// The text format of the key column at the given position in the key.
+    KeyColumn(usize),
+}
+
+impl RustType<ProtoKeyTemplateSegment> for KeyTemplateSegment {
+    fn into_proto(&self) -> ProtoKeyTemplateSegment {
+        use proto_key_template_segment::Kind;
+        ProtoKeyTemplateSegment {
+            kind: Some(match self {
+                KeyTemplateSegment::Literal(literal) => Kind::Literal(literal.clone()),
+                KeyTemplateSegment::KeyColumn(i) => Kind::KeyColumn(i.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKeyTemplateSegment) -> Result<Self, TryFromProtoError> {
+        use proto_key_template_segment::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoKeyTemplateSegment::kind"))?;
+        Ok(match kind {
+            Kind::Literal(literal) => KeyTemplateSegment::Literal(literal),
+            Kind::KeyColumn(i) => KeyTemplateSegment::KeyColumn(i.into_rust()?),
+        })
+    }
+}
+
+/// A sink that publishes the updates of the sinked collection, encoded as
+/// JSON, to subjects of a NATS JetStream stream.
+///
+/// Each update is published to the subject formed by concatenating the
+/// segments of `subject`, and is acknowledged by the stream before the sink's
+/// write frontier advances past it.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NatsSinkConnection {
+    pub url: String,
+    pub subject: Vec<KeyTemplateSegment>,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    pub token: Option<GlobalId>,
+    /// The user-specified key, if any.
+    pub key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the server is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoNatsSinkConnection> for NatsSinkConnection {
+    fn into_proto(&self) -> ProtoNatsSinkConnection {
+        ProtoNatsSinkConnection {
+            url: self.url.clone(),
+            subject: self.subject.into_proto(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            token: self.token.into_proto(),
+            key_indices: self.key_indices.as_ref().map(|key_indices| {
+                proto_nats_sink_connection::ProtoKeyIndices {
+                    key_indices: key_indices.into_proto(),
+                }
+            }),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoNatsSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(NatsSinkConnection {
+            url: proto.url,
+            subject: proto.subject.into_rust()?,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            token: proto.token.into_rust()?,
+            key_indices: proto
+                .key_indices
+                .map(|key_indices| key_indices.key_indices.into_rust())
+                .transpose()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoNatsSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// A sink that publishes the updates of the sinked collection, encoded as
+/// JSON, to topics of an MQTT broker.
+///
+/// Each update is published to the topic formed by concatenating the segments
+/// of `topic` with quality of service `qos`, and is acknowledged by the broker
+/// (for a `qos` above 0) before the sink's write frontier advances past it.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MqttSinkConnection {
+    pub url: String,
+    pub topic: Vec<KeyTemplateSegment>,
+    /// The MQTT quality of service level, one of 0, 1, or 2.
+    pub qos: u8,
+    /// Whether the broker should retain the last message published to each
+    /// topic.
+    pub retain: bool,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    /// The user-specified key, if any.
+    pub key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the broker is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoMqttSinkConnection> for MqttSinkConnection {
+    fn into_proto(&self) -> ProtoMqttSinkConnection {
+        ProtoMqttSinkConnection {
+            url: self.url.clone(),
+            topic: self.topic.into_proto(),
+            qos: self.qos.into_proto(),
+            retain: self.retain,
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            key_indices: self.key_indices.as_ref().map(|key_indices| {
+                proto_mqtt_sink_connection::ProtoKeyIndices {
+                    key_indices: key_indices.into_proto(),
+                }
+            }),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoMqttSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MqttSinkConnection {
+            url: proto.url,
+            topic: proto.topic.into_rust()?,
+            qos: proto.qos.into_rust()?,
+            retain: proto.retain,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            key_indices: proto
+                .key_indices
+                .map(|key_indices| key_indices.key_indices.into_rust())
+                .transpose()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoMqttSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// A sink that streams the updates of the sinked collection, encoded as JSON,
+/// to a client-streaming method of a gRPC service.
+///
+/// Each batch of updates is streamed in a single call of `method`, as
+/// messages with the update's JSON-encoded key in field 1, its JSON-encoded
+/// value in field 2 (absent for deletions), and its timestamp in field 3. The
+/// service's response to the call acknowledges the batch, and the sink's
+/// write frontier advances past a batch only once it is acknowledged.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GrpcSinkConnection {
+    pub url: String,
+    /// The path of the method, in the form `/package.Service/Method`.
+    pub method: String,
+    /// The bearer token sent with each call, if any.
+    pub token: Option<GlobalId>,
+    /// The user-specified key, if any.
+    pub key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the service is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoGrpcSinkConnection> for GrpcSinkConnection {
+    fn into_proto(&self) -> ProtoGrpcSinkConnection {
+        ProtoGrpcSinkConnection {
+            url: self.url.clone(),
+            method: self.method.clone(),
+            token: self.token.into_proto(),
+            key_indices: self.key_indices.as_ref().map(|key_indices| {
+                proto_grpc_sink_connection::ProtoKeyIndices {
+                    key_indices: key_indices.into_proto(),
+                }
+            }),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoGrpcSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(GrpcSinkConnection {
+            url: proto.url,
+            method: proto.method,
+            token: proto.token.into_rust()?,
+            key_indices: proto
+                .key_indices
+                .map(|key_indices| key_indices.key_indices.into_rust())
+                .transpose()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoGrpcSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
+        })
+    }
+}
+
+/// A sink that sends the updates of the sinked collection, encoded as JSON,
+/// as messages to an AWS SQS queue.
+///
+/// Messages sent to a FIFO queue are grouped by the key of their update, so
+/// that the updates of each key are received in order, and are deduplicated
+/// by their position in the sink's output.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqsSinkConnection {
+    pub connection_id: GlobalId,
+    pub aws: AwsConfig,
+    pub queue_url: String,
+    /// Whether the queue is a FIFO queue.
+    pub fifo: bool,
+    /// The user-specified key, if any.
+    pub key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    /// The number of bytes of encoded batches that may be spilled to disk
+    /// while the queue is unavailable, if any.
+    pub spill_budget: Option<u64>,
+}
+
+impl RustType<ProtoSqsSinkConnection> for SqsSinkConnection {
+    fn into_proto(&self) -> ProtoSqsSinkConnection {
+        ProtoSqsSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            aws: Some(self.aws.into_proto()),
+            queue_url: self.queue_url.clone(),
+            fifo: self.fifo,
+            key_indices: self.key_indices.as_ref().map(|key_indices| {
+                proto_sqs_sink_connection::ProtoKeyIndices {
+                    key_indices: key_indices.into_proto(),
+                }
+            }),
+            value_desc: Some(self.value_desc.into_proto()),
+            spill_budget: self.spill_budget,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqsSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqsSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoSqsSinkConnection::connection_id")?,
+            aws: proto.aws.into_rust_if_some("ProtoSqsSinkConnection::aws")?,
+            queue_url: proto.queue_url,
+            fifo: proto.fifo,
+            key_indices: proto
+                .key_indices
+                .map(|key_indices| key_indices.key_indices.into_rust())
+                .transpose()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoSqsSinkConnection::value_desc")?,
+            spill_budget: proto.spill_budget,
         })
     }
 }
@@ -390,6 +1532,32 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
 pub struct PublishedSchemaInfo {
     pub key_schema_id: Option<i32>,
     pub value_schema_id: i32,
+    /// The subject under which the key schema was published, if any.
+    pub key_subject: Option<String>,
+    /// The subject under which the value schema was published.
+    pub value_subject: String,
+    /// The schema registry from which to delete the subjects when the sink is
+    /// dropped, if the sink was created with `ON DROP DELETE SUBJECTS`.
+    pub delete_subjects_on_drop: Option<CsrConnection>,
+    /// The format of the published schemas, and so of the encoded records.
+    pub format: PublishedSchemaFormat,
+    /// The fullname of the Avro key record, if overridden.
+    pub key_fullname: Option<String>,
+    /// The fullname of the Avro value record, if overridden.
+    pub value_fullname: Option<String>,
+    /// The user-supplied Avro value schema, to which encoded values are
+    /// conformed, if any.
+    pub value_schema: Option<String>,
+}
+
+impl PublishedSchemaInfo {
+    /// Returns the subjects under which the sink's schemas were published.
+    pub fn subjects(&self) -> impl Iterator<Item = &str> {
+        self.key_subject
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(self.value_subject.as_str()))
+    }
 }
 
 impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
@@ -397,6 +1565,13 @@ impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
         ProtoPublishedSchemaInfo {
             key_schema_id: self.key_schema_id.clone(),
             value_schema_id: self.value_schema_id,
+            key_subject: self.key_subject.clone(),
+            value_subject: self.value_subject.clone(),
+            delete_subjects_on_drop: self.delete_subjects_on_drop.into_proto(),
+            format: Some(self.format.into_proto()),
+            key_fullname: self.key_fullname.clone(),
+            value_fullname: self.value_fullname.clone(),
+            value_schema: self.value_schema.clone(),
         }
     }
 
@@ -404,6 +1579,396 @@ impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
         Ok(PublishedSchemaInfo {
             key_schema_id: proto.key_schema_id,
             value_schema_id: proto.value_schema_id,
+            key_subject: proto.key_subject,
+            value_subject: proto.value_subject,
+            delete_subjects_on_drop: proto.delete_subjects_on_drop.into_rust()?,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoPublishedSchemaInfo::format")?,
+            key_fullname: proto.key_fullname,
+            value_fullname: proto.value_fullname,
+            value_schema: proto.value_schema,
+        })
+    }
+}
+
+/// The encoding of the records written by a Kafka sink for consumers that
+/// speak neither Avro nor JSON.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkTextFormat {
+    /// One CSV record per message, optionally preceded by a header record.
+    Csv {
+        delimiter: char,
+        header: Option<Vec<String>>,
+    },
+    /// The text representation of the single column.
+    Text,
+    /// The raw contents of the single `bytea` or `text` column.
+    Bytes,
+}
+
+impl RustType<ProtoKafkaSinkTextFormat> for KafkaSinkTextFormat {
+    fn into_proto(&self) -> ProtoKafkaSinkTextFormat {
+        use proto_kafka_sink_text_format::{Kind, ProtoCsv};
+        ProtoKafkaSinkTextFormat {
+            kind: Some(match self {
+                KafkaSinkTextFormat::Csv { delimiter, header } => Kind::Csv(ProtoCsv {
+                    delimiter: delimiter.into_proto(),
+                    header: header.clone().unwrap_or_default(),
+                }),
+                KafkaSinkTextFormat::Text => Kind::Text(()),
+                KafkaSinkTextFormat::Bytes => Kind::Bytes(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkTextFormat) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_text_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoKafkaSinkTextFormat::kind"))?;
+        Ok(match kind {
+            Kind::Csv(csv) => KafkaSinkTextFormat::Csv {
+                delimiter: csv.delimiter.into_rust()?,
+                header: if csv.header.is_empty() {
+                    None
+                } else {
+                    Some(csv.header)
+                },
+            },
+            Kind::Text(()) => KafkaSinkTextFormat::Text,
+            Kind::Bytes(()) => KafkaSinkTextFormat::Bytes,
+        })
+    }
+}
+
+/// The key of the header in which a Kafka sink records the timestamp of each
+/// message.
+pub const KAFKA_SINK_TIMESTAMP_HEADER: &str = "materialize-timestamp";
+
+/// The key of the header in which a Kafka sink records, on each message it
+/// publishes to its dead letter topic, why the message could not be delivered.
+pub const KAFKA_SINK_ERROR_HEADER: &str = "materialize-error";
+
+/// The key of the header in which a Kafka sink records, on each message it
+/// publishes to its dead letter topic, the topic the message was meant for.
+pub const KAFKA_SINK_TOPIC_HEADER: &str = "materialize-topic";
+
+/// The source of the timestamp that a Kafka sink assigns to each message it
+/// produces.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkMessageTimestamp {
+    /// The timestamp of the update that the message describes.
+    MzTimestamp,
+    /// The value of the `timestamp` or `timestamptz` column with the given
+    /// index.
+    Column(usize),
+}
+
+impl RustType<ProtoKafkaSinkMessageTimestamp> for KafkaSinkMessageTimestamp {
+    fn into_proto(&self) -> ProtoKafkaSinkMessageTimestamp {
+        use proto_kafka_sink_message_timestamp::Kind;
+        ProtoKafkaSinkMessageTimestamp {
+            kind: Some(match self {
+                KafkaSinkMessageTimestamp::MzTimestamp => Kind::MzTimestamp(()),
+                KafkaSinkMessageTimestamp::Column(column) => Kind::Column(column.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkMessageTimestamp) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_message_timestamp::Kind;
+        let kind = proto.kind.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoKafkaSinkMessageTimestamp::kind")
+        })?;
+        Ok(match kind {
+            Kind::MzTimestamp(()) => KafkaSinkMessageTimestamp::MzTimestamp,
+            Kind::Column(column) => KafkaSinkMessageTimestamp::Column(column.into_rust()?),
+        })
+    }
+}
+
+/// The codec with which a Kafka sink compresses the batches of messages it
+/// produces.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkCompressionType {
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl KafkaSinkCompressionType {
+    /// Returns the value of librdkafka's `compression.type` setting that
+    /// selects this codec.
+    pub fn as_librdkafka_str(&self) -> &'static str {
+        match self {
+            KafkaSinkCompressionType::None => "none",
+            KafkaSinkCompressionType::Gzip => "gzip",
+            KafkaSinkCompressionType::Snappy => "snappy",
+            KafkaSinkCompressionType::Lz4 => "lz4",
+            KafkaSinkCompressionType::Zstd => "zstd",
+        }
+    }
+}
+
+impl RustType<ProtoKafkaSinkCompressionType> for KafkaSinkCompressionType {
+    fn into_proto(&self) -> ProtoKafkaSinkCompressionType {
+        use proto_kafka_sink_compression_type::Kind;
+        ProtoKafkaSinkCompressionType {
+            kind: Some(match self {
+                KafkaSinkCompressionType::None => Kind::None(()),
+                KafkaSinkCompressionType::Gzip => Kind::Gzip(()),
+                KafkaSinkCompressionType::Snappy => Kind::Snappy(()),
+                KafkaSinkCompressionType::Lz4 => Kind::Lz4(()),
+                KafkaSinkCompressionType::Zstd => Kind::Zstd(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkCompressionType) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_compression_type::Kind;
+        let kind = proto.kind.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoKafkaSinkCompressionType::kind")
+        })?;
+        Ok(match kind {
+            Kind::None(()) => KafkaSinkCompressionType::None,
+            Kind::Gzip(()) => KafkaSinkCompressionType::Gzip,
+            Kind::Snappy(()) => KafkaSinkCompressionType::Snappy,
+            Kind::Lz4(()) => KafkaSinkCompressionType::Lz4,
+            Kind::Zstd(()) => KafkaSinkCompressionType::Zstd,
+        })
+    }
+}
+
+/// How a Kafka sink retries the messages that the broker fails to accept.
+///
+/// Each consecutive failed attempt to deliver the outstanding messages is
+/// followed by a backoff that starts at `initial_backoff` and doubles up to
+/// `max_backoff`. The sink gives up after `max_attempts` retries, if bounded,
+/// and then takes `exhausted_action`.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkRetryPolicy {
+    pub max_attempts: Option<u64>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub exhausted_action: KafkaSinkRetryExhaustedAction,
+}
+
+impl Default for KafkaSinkRetryPolicy {
+    fn default() -> Self {
+        KafkaSinkRetryPolicy {
+            max_attempts: None,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            exhausted_action: KafkaSinkRetryExhaustedAction::Fail,
+        }
+    }
+}
+
+impl KafkaSinkRetryPolicy {
+    /// Returns the backoff before retry number `attempt`, counting from one.
+    pub fn backoff(&self, attempt: u64) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(exponent))
+            .map_or(self.max_backoff, |backoff| {
+                std::cmp::min(backoff, self.max_backoff)
+            })
+    }
+}
+
+impl RustType<ProtoKafkaSinkRetryPolicy> for KafkaSinkRetryPolicy {
+    fn into_proto(&self) -> ProtoKafkaSinkRetryPolicy {
+        ProtoKafkaSinkRetryPolicy {
+            max_attempts: self.max_attempts,
+            initial_backoff: Some(self.initial_backoff.into_proto()),
+            max_backoff: Some(self.max_backoff.into_proto()),
+            exhausted_action: Some(self.exhausted_action.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkRetryPolicy) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaSinkRetryPolicy {
+            max_attempts: proto.max_attempts,
+            initial_backoff: proto
+                .initial_backoff
+                .into_rust_if_some("ProtoKafkaSinkRetryPolicy::initial_backoff")?,
+            max_backoff: proto
+                .max_backoff
+                .into_rust_if_some("ProtoKafkaSinkRetryPolicy::max_backoff")?,
+            exhausted_action: proto
+                .exhausted_action
+                .into_rust_if_some("ProtoKafkaSinkRetryPolicy::exhausted_action")?,
+        })
+    }
+}
+
+/// What a Kafka sink does once it has exhausted its retries of a message.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkRetryExhaustedAction {
+    /// Stop the sink, and report it as failed.
+    Fail,
+    /// Publish the message to the sink's dead letter topic instead.
+    DeadLetter,
+}
+
+impl RustType<ProtoKafkaSinkRetryExhaustedAction> for KafkaSinkRetryExhaustedAction {
+    fn into_proto(&self) -> ProtoKafkaSinkRetryExhaustedAction {
+        use proto_kafka_sink_retry_exhausted_action::Kind;
+        ProtoKafkaSinkRetryExhaustedAction {
+            kind: Some(match self {
+                KafkaSinkRetryExhaustedAction::Fail => Kind::Fail(()),
+                KafkaSinkRetryExhaustedAction::DeadLetter => Kind::DeadLetter(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkRetryExhaustedAction) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_retry_exhausted_action::Kind;
+        let kind = proto.kind.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoKafkaSinkRetryExhaustedAction::kind")
+        })?;
+        Ok(match kind {
+            Kind::Fail(()) => KafkaSinkRetryExhaustedAction::Fail,
+            Kind::DeadLetter(()) => KafkaSinkRetryExhaustedAction::DeadLetter,
+        })
+    }
+}
+
+/// A header that a Kafka sink attaches to each message it produces.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkHeader {
+    /// The key of the header.
+    pub key: String,
+    /// The index of the column whose value becomes the value of the header.
+    pub column: usize,
+}
+
+impl RustType<ProtoKafkaSinkHeader> for KafkaSinkHeader {
+    fn into_proto(&self) -> ProtoKafkaSinkHeader {
+        ProtoKafkaSinkHeader {
+            key: self.key.clone(),
+            column: self.column.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkHeader) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaSinkHeader {
+            key: proto.key,
+            column: proto.column.into_rust()?,
+        })
+    }
+}
+
+/// The maximum length of a Kafka topic name.
+const MAX_KAFKA_TOPIC_NAME_LEN: usize = 249;
+
+/// Reports whether `c` may appear in a Kafka topic name.
+pub fn is_kafka_topic_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// How a Kafka sink publishes each row to a topic chosen by the value of one
+/// of its columns.
+///
+/// Rows whose column is `NULL` are published to the sink's topic. The topics
+/// are created as rows for them are first published.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkTopicFanout {
+    /// The index of the column whose value selects the topic of each row.
+    pub column: usize,
+    /// The name of the topic of each row, in which `{}` stands for the value
+    /// of `column`.
+    pub template: String,
+    /// The number of partitions with which topics are created, or -1 for
+    /// the broker's default.
+    pub partition_count: i32,
+    /// The replication factor with which topics are created, or -1 for the
+    /// broker's default.
+    pub replication_factor: i32,
+    /// The topic-level configs with which topics are created.
+    pub topic_config: BTreeMap<String, String>,
+}
+
+impl KafkaSinkTopicFanout {
+    /// Returns the name of the topic of the rows whose column holds `value`,
+    /// or an error if it is not a valid topic name.
+    pub fn topic(&self, value: &str) -> Result<String, String> {
+        if value.is_empty() || !value.chars().all(is_kafka_topic_name_char) {
+            return Err(format!(
+                "TOPIC COLUMN value {:?} is not a valid part of a topic name: \
+                 only ASCII alphanumerics, '.', '_' and '-' are allowed",
+                value
+            ));
+        }
+        let topic = self.template.replacen("{}", value, 1);
+        if topic.len() > MAX_KAFKA_TOPIC_NAME_LEN {
+            return Err(format!(
+                "topic name {:?} for TOPIC COLUMN value {:?} is longer than {} characters",
+                topic, value, MAX_KAFKA_TOPIC_NAME_LEN
+            ));
+        }
+        Ok(topic)
+    }
+}
+
+impl RustType<ProtoKafkaSinkTopicFanout> for KafkaSinkTopicFanout {
+    fn into_proto(&self) -> ProtoKafkaSinkTopicFanout {
+        ProtoKafkaSinkTopicFanout {
+            column: self.column.into_proto(),
+            template: self.template.clone(),
+            partition_count: self.partition_count,
+            replication_factor: self.replication_factor,
+            topic_config: self
+                .topic_config
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkTopicFanout) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaSinkTopicFanout {
+            column: proto.column.into_rust()?,
+            template: proto.template,
+            partition_count: proto.partition_count,
+            replication_factor: proto.replication_factor,
+            topic_config: proto.topic_config.into_iter().collect(),
+        })
+    }
+}
+
+/// The format of the schemas that a Kafka sink published to a schema registry.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PublishedSchemaFormat {
+    Avro,
+    Protobuf,
+    Json,
+}
+
+impl RustType<ProtoPublishedSchemaFormat> for PublishedSchemaFormat {
+    fn into_proto(&self) -> ProtoPublishedSchemaFormat {
+        use proto_published_schema_format::Kind;
+        ProtoPublishedSchemaFormat {
+            kind: Some(match self {
+                PublishedSchemaFormat::Avro => Kind::Avro(()),
+                PublishedSchemaFormat::Protobuf => Kind::Protobuf(()),
+                PublishedSchemaFormat::Json => Kind::Json(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoPublishedSchemaFormat) -> Result<Self, TryFromProtoError> {
+        use proto_published_schema_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoPublishedSchemaFormat::kind"))?;
+        Ok(match kind {
+            Kind::Avro(()) => PublishedSchemaFormat::Avro,
+            Kind::Protobuf(()) => PublishedSchemaFormat::Protobuf,
+            Kind::Json(()) => PublishedSchemaFormat::Json,
         })
     }
 }
@@ -413,6 +1978,15 @@ impl StorageSinkConnection {
     pub fn name(&self) -> &'static str {
         match self {
             StorageSinkConnection::Kafka(_) => "kafka",
+            StorageSinkConnection::S3(_) => "s3",
+            StorageSinkConnection::Postgres(_) => "postgres",
+            StorageSinkConnection::File(_) => "file",
+            StorageSinkConnection::Elasticsearch(_) => "elasticsearch",
+            StorageSinkConnection::Redis(_) => "redis",
+            StorageSinkConnection::Nats(_) => "nats",
+            StorageSinkConnection::Mqtt(_) => "mqtt",
+            StorageSinkConnection::Grpc(_) => "grpc",
+            StorageSinkConnection::Sqs(_) => "sqs",
         }
     }
 }
@@ -420,6 +1994,27 @@ impl StorageSinkConnection {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnectionBuilder {
     Kafka(KafkaSinkConnectionBuilder),
+    // S3 sinks have no external state to set up ahead of time, so the builder
+    // is the connection itself.
+    S3(S3SinkConnection),
+    // Likewise for Postgres sinks, which write into a table that must already
+    // exist upstream.
+    Postgres(PostgresSinkConnection),
+    // File sinks write to the local filesystem of the storage replica, which
+    // is not accessible ahead of time.
+    File(FileSinkConnection),
+    // Elasticsearch sinks create the index, if necessary, on the first write.
+    Elasticsearch(ElasticsearchSinkConnection),
+    // Redis sinks write keys that need not exist ahead of time.
+    Redis(RedisSinkConnection),
+    // NATS sinks publish to subjects of streams that must already exist.
+    Nats(NatsSinkConnection),
+    // MQTT sinks publish to topics that need not exist ahead of time.
+    Mqtt(MqttSinkConnection),
+    // gRPC sinks stream to a service that must already be running.
+    Grpc(GrpcSinkConnection),
+    // SQS sinks send to a queue that must already exist.
+    Sqs(SqsSinkConnection),
 }
 
 impl StorageSinkConnectionBuilder {
@@ -428,15 +2023,14 @@ impl StorageSinkConnectionBuilder {
         use StorageSinkConnectionBuilder::*;
         match self {
             Kafka(KafkaSinkConnectionBuilder { connection_id, .. }) => Some(*connection_id),
+            S3(S3SinkConnection { connection_id, .. }) => Some(*connection_id),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Sqs(SqsSinkConnection { connection_id, .. }) => Some(*connection_id),
+            File(_) | Elasticsearch(_) | Redis(_) | Nats(_) | Mqtt(_) | Grpc(_) => None,
         }
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub enum KafkaConsistencyConfig {
-    Progress { topic: String },
-}
-
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConnectionBuilder {
     pub connection_id: GlobalId,
@@ -449,11 +2043,32 @@ pub struct KafkaSinkConnectionBuilder {
     pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     pub value_desc: RelationDesc,
     pub topic_name: String,
-    pub consistency_config: KafkaConsistencyConfig,
+    /// The headers to attach to each message.
+    pub headers: Vec<KafkaSinkHeader>,
+    /// The expression that selects the partition of each message, if any.
+    pub partition_by: Option<MirScalarExpr>,
+    /// The source of the timestamp of each message, if any.
+    pub message_timestamp: Option<KafkaSinkMessageTimestamp>,
+    /// The codec with which to compress batches of messages.
+    pub compression_type: KafkaSinkCompressionType,
+    /// The `linger.ms` setting of the producer, if any.
+    pub linger_ms: Option<u64>,
+    /// The `batch.num.messages` setting of the producer, if any.
+    pub batch_num_messages: Option<u64>,
+    /// The topic to which undeliverable messages are published, if any.
+    pub dead_letter_topic: Option<String>,
+    /// How messages that the broker fails to accept are retried.
+    pub retry: KafkaSinkRetryPolicy,
+    /// How rows are fanned out to a topic per value of a column, if at all.
+    pub topic_fanout: Option<KafkaSinkTopicFanout>,
+    /// The compacted topic in which the sink records its progress.
+    pub progress_topic: String,
     pub partition_count: i32,
     pub replication_factor: i32,
-    pub fuel: usize,
     pub retention: KafkaSinkConnectionRetention,
+    /// The topic-level configs with which the topic is created, if the sink
+    /// creates it.
+    pub topic_config: BTreeMap<String, String>,
 }
 
 impl PopulateClientConfig for KafkaSinkConnectionBuilder {
@@ -468,18 +2083,81 @@ impl PopulateClientConfig for KafkaSinkConnectionBuilder {
     }
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Arbitrary, Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConnectionRetention {
     pub duration: Option<i64>,
     pub bytes: Option<i64>,
 }
 
+/// How the subjects under which a Kafka sink publishes its Avro schemas are
+/// named, following the strategies of Confluent's serializers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SubjectNameStrategy {
+    /// `<topic>-key` and `<topic>-value`.
+    TopicName,
+    /// The fullname of the key or value record.
+    RecordName,
+    /// `<topic>-<fullname>`, where `<fullname>` is the fullname of the key or
+    /// value record.
+    TopicRecordName,
+}
+
+impl SubjectNameStrategy {
+    /// Returns the subject for the key or value schema, whose record has
+    /// fullname `record_fullname`, of records written to `topic`.
+    pub fn subject(&self, topic: &str, record_fullname: &str, is_key: bool) -> String {
+        match self {
+            SubjectNameStrategy::TopicName => {
+                format!("{}-{}", topic, if is_key { "key" } else { "value" })
+            }
+            SubjectNameStrategy::RecordName => record_fullname.to_string(),
+            SubjectNameStrategy::TopicRecordName => format!("{}-{}", topic, record_fullname),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaSinkFormat {
     Avro {
+        key_schema: Option<String>,
+        value_schema: String,
+        /// The fullname of the key record, if not the default.
+        key_fullname: Option<String>,
+        /// The fullname of the value record, if not the default.
+        value_fullname: Option<String>,
+        /// Whether `value_schema` was supplied by the user rather than
+        /// generated from the sinked relation.
+        user_value_schema: bool,
+        /// How the subjects under which the schemas are published are named.
+        subject_name_strategy: SubjectNameStrategy,
+        /// The compatibility level to set on the subjects before publishing
+        /// the schemas, if any.
+        compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+        csr_connection: CsrConnection,
+        /// Whether to delete the published subjects when the sink is dropped.
+        delete_subjects_on_drop: bool,
+    },
+    Protobuf {
         key_schema: Option<String>,
         value_schema: String,
         csr_connection: CsrConnection,
+        /// Whether to delete the published subjects when the sink is dropped.
+        delete_subjects_on_drop: bool,
     },
     Json,
+    Csv {
+        delimiter: char,
+        header: Option<Vec<String>>,
+    },
+    Text,
+    Bytes,
+    /// JSON whose key and value schemas are published to a schema registry
+    /// as JSON Schemas.
+    JsonSchema {
+        key_schema: Option<String>,
+        value_schema: String,
+        csr_connection: CsrConnection,
+        /// Whether to delete the published subjects when the sink is dropped.
+        delete_subjects_on_drop: bool,
+    },
 }
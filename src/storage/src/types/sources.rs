@@ -19,6 +19,7 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use differential_dataflow::lattice::Lattice;
 use globset::{Glob, GlobBuilder};
+use itertools::Itertools;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
 use prost::Message;
@@ -37,11 +38,13 @@ use mz_repr::{ColumnType, Diff, GlobalId, RelationDesc, RelationType, Row, Scala
 use crate::controller::{CollectionMetadata, ResumptionFrontierCalculator};
 use crate::source::generator::as_generator;
 use crate::types::connections::aws::AwsConfig;
-use crate::types::connections::{KafkaConnection, PostgresConnection, StringOrSecret};
+use crate::types::connections::{
+    KafkaConnection, MySqlConnection, PostgresConnection, SqlServerConnection, StringOrSecret,
+};
 use crate::types::errors::DataflowError;
 use crate::types::hosts::StorageHostConfig;
 
-use self::encoding::{DataEncoding, DataEncodingInner, SourceDataEncoding};
+use self::encoding::{DataEncoding, DataEncodingInner, HeaderEncoding, SourceDataEncoding};
 use proto_ingestion_description::{ProtoSourceExport, ProtoSourceImport};
 use proto_load_generator_source_connection::Generator as ProtoGenerator;
 
@@ -90,6 +93,7 @@ impl<T: timely::progress::Timestamp + Lattice + Codec64> ResumptionFrontierCalcu
                 data_shard,
                 // The status shard only contains non-definite status updates
                 status_shard: _,
+                relation_arity: _,
             } = &export.storage_metadata;
             let handle = client_cache
                 .open(persist_location.clone())
@@ -107,6 +111,7 @@ impl<T: timely::progress::Timestamp + Lattice + Codec64> ResumptionFrontierCalcu
             data_shard: _,
             // The status shard only contains non-definite status updates
             status_shard: _,
+            relation_arity: _,
         } = &self.ingestion_metadata;
         let remap_handle = client_cache
             .open(persist_location.clone())
@@ -383,13 +388,20 @@ impl From<tokio_postgres::types::PgLsn> for MzOffset {
 }
 
 /// Which piece of metadata a column corresponds to
-#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum IncludedColumnSource {
     Partition,
     Offset,
     Timestamp,
     Topic,
     Headers,
+    /// The name of the file that a line was read from.
+    Filename,
+    /// The value of the header with the given key, decoded with the given encoding.
+    Header {
+        key: String,
+        encoding: HeaderEncoding,
+    },
 }
 
 impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
@@ -402,6 +414,13 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
                 IncludedColumnSource::Timestamp => Kind::Timestamp(()),
                 IncludedColumnSource::Topic => Kind::Topic(()),
                 IncludedColumnSource::Headers => Kind::Headers(()),
+                IncludedColumnSource::Filename => Kind::Filename(()),
+                IncludedColumnSource::Header { key, encoding } => {
+                    Kind::Header(ProtoIncludedHeaderSource {
+                        key: key.clone(),
+                        encoding: Some(encoding.into_proto()),
+                    })
+                }
             }),
         }
     }
@@ -417,6 +436,13 @@ impl RustType<ProtoIncludedColumnSource> for IncludedColumnSource {
             Kind::Timestamp(()) => IncludedColumnSource::Timestamp,
             Kind::Topic(()) => IncludedColumnSource::Topic,
             Kind::Headers(()) => IncludedColumnSource::Headers,
+            Kind::Filename(()) => IncludedColumnSource::Filename,
+            Kind::Header(header) => IncludedColumnSource::Header {
+                key: header.key,
+                encoding: header
+                    .encoding
+                    .into_rust_if_some("ProtoIncludedHeaderSource::encoding")?,
+            },
         })
     }
 }
@@ -484,6 +510,38 @@ impl RustType<ProtoIncludedColumnPos> for IncludedColumnPos {
     }
 }
 
+/// A single Kafka header that was included as a column via `INCLUDE HEADER`
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IncludedHeader {
+    /// The key of the header to look up in each message.
+    pub key: String,
+    /// How the value of the header is decoded.
+    pub encoding: HeaderEncoding,
+    pub column: IncludedColumnPos,
+}
+
+impl RustType<ProtoIncludedHeader> for IncludedHeader {
+    fn into_proto(&self) -> ProtoIncludedHeader {
+        ProtoIncludedHeader {
+            key: self.key.clone(),
+            encoding: Some(self.encoding.into_proto()),
+            column: Some(self.column.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoIncludedHeader) -> Result<Self, TryFromProtoError> {
+        Ok(IncludedHeader {
+            key: proto.key,
+            encoding: proto
+                .encoding
+                .into_rust_if_some("ProtoIncludedHeader::encoding")?,
+            column: proto
+                .column
+                .into_rust_if_some("ProtoIncludedHeader::column")?,
+        })
+    }
+}
+
 /// The meaning of the timestamp number produced by data sources. This type
 /// is not concerned with the source of the timestamp (like if the data came
 /// from a Debezium consistency topic or a CDCv2 stream), instead only what the
@@ -637,6 +695,25 @@ impl RustType<ProtoSourceEnvelope> for SourceEnvelope {
     }
 }
 
+impl SourceEnvelope {
+    /// Returns the name of the envelope as spelled in `ENVELOPE` clauses.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceEnvelope::None(_) => "none",
+            SourceEnvelope::Debezium(_) => "debezium",
+            SourceEnvelope::Upsert(UpsertEnvelope {
+                style: UpsertStyle::Default(_),
+                ..
+            }) => "upsert",
+            SourceEnvelope::Upsert(UpsertEnvelope {
+                style: UpsertStyle::Debezium { .. },
+                ..
+            }) => "debezium upsert",
+            SourceEnvelope::CdcV2 => "materialize",
+        }
+    }
+}
+
 /// `UnplannedSourceEnvelope` is a `SourceEnvelope` missing some information. This information
 /// is obtained in `UnplannedSourceEnvelope::desc`, where
 /// `UnplannedSourceEnvelope::into_source_envelope`
@@ -653,6 +730,8 @@ pub enum UnplannedSourceEnvelope {
 pub struct NoneEnvelope {
     pub key_envelope: KeyEnvelope,
     pub key_arity: usize,
+    /// What to do with messages whose key or value cannot be decoded
+    pub decode_errors: DecodeErrorPolicy,
 }
 
 impl RustType<ProtoNoneEnvelope> for NoneEnvelope {
@@ -660,6 +739,7 @@ impl RustType<ProtoNoneEnvelope> for NoneEnvelope {
         ProtoNoneEnvelope {
             key_envelope: Some(self.key_envelope.into_proto()),
             key_arity: self.key_arity.into_proto(),
+            decode_errors: Some(self.decode_errors.into_proto()),
         }
     }
 
@@ -669,10 +749,63 @@ impl RustType<ProtoNoneEnvelope> for NoneEnvelope {
                 .key_envelope
                 .into_rust_if_some("ProtoNoneEnvelope::key_envelope")?,
             key_arity: proto.key_arity.into_rust()?,
+            decode_errors: proto
+                .decode_errors
+                .into_rust_if_some("ProtoNoneEnvelope::decode_errors")?,
+        })
+    }
+}
+
+/// What a source does with messages that it cannot decode.
+#[derive(Arbitrary, Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DecodeErrorPolicy {
+    /// The error is written to the error collection of the source, which
+    /// makes the source unqueryable until the message is retracted.
+    Fail,
+    /// The message is written to the decode errors subsource of the source, as
+    /// described by [`decode_errors_desc`], and otherwise skipped.
+    Subsource,
+}
+
+impl Default for DecodeErrorPolicy {
+    fn default() -> Self {
+        DecodeErrorPolicy::Fail
+    }
+}
+
+impl RustType<ProtoDecodeErrorPolicy> for DecodeErrorPolicy {
+    fn into_proto(&self) -> ProtoDecodeErrorPolicy {
+        use proto_decode_error_policy::Kind;
+        ProtoDecodeErrorPolicy {
+            kind: Some(match self {
+                DecodeErrorPolicy::Fail => Kind::Fail(()),
+                DecodeErrorPolicy::Subsource => Kind::Subsource(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoDecodeErrorPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_decode_error_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoDecodeErrorPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Fail(()) => DecodeErrorPolicy::Fail,
+            Kind::Subsource(()) => DecodeErrorPolicy::Subsource,
         })
     }
 }
 
+/// The schema of the subsource that receives the messages a source cannot
+/// decode, when its [`DecodeErrorPolicy`] is [`DecodeErrorPolicy::Subsource`].
+pub fn decode_errors_desc() -> RelationDesc {
+    RelationDesc::empty()
+        .with_column("partition", ScalarType::String.nullable(false))
+        .with_column("offset", ScalarType::Int64.nullable(false))
+        .with_column("raw", ScalarType::Bytes.nullable(true))
+        .with_column("error", ScalarType::String.nullable(false))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct UpsertEnvelope {
     /// Full arity, including the key columns
@@ -682,6 +815,8 @@ pub struct UpsertEnvelope {
     /// The indices of the keys in the full value row, used
     /// to deduplicate data in `upsert_core`
     pub key_indices: Vec<usize>,
+    /// Where `upsert_core` keeps the latest value of each key
+    pub state_backend: UpsertStateBackend,
 }
 
 impl Arbitrary for UpsertEnvelope {
@@ -693,11 +828,13 @@ impl Arbitrary for UpsertEnvelope {
             any::<usize>(),
             any::<UpsertStyle>(),
             proptest::collection::vec(any::<usize>(), 1..4),
+            any::<UpsertStateBackend>(),
         )
-            .prop_map(|(source_arity, style, key_indices)| Self {
+            .prop_map(|(source_arity, style, key_indices, state_backend)| Self {
                 source_arity,
                 style,
                 key_indices,
+                state_backend,
             })
             .boxed()
     }
@@ -709,6 +846,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
             source_arity: self.source_arity.into_proto(),
             style: Some(self.style.into_proto()),
             key_indices: self.key_indices.into_proto(),
+            state_backend: Some(self.state_backend.into_proto()),
         }
     }
 
@@ -719,6 +857,49 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
                 .style
                 .into_rust_if_some("ProtoUpsertEnvelope::style")?,
             key_indices: proto.key_indices.into_rust()?,
+            state_backend: proto
+                .state_backend
+                .into_rust_if_some("ProtoUpsertEnvelope::state_backend")?,
+        })
+    }
+}
+
+/// Where the upsert operator keeps the latest value of each key.
+#[derive(Arbitrary, Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum UpsertStateBackend {
+    /// All keys and values are kept in memory.
+    Memory,
+    /// Keys and values are kept in a RocksDB instance on the local disk of the
+    /// worker, with a bounded in-memory cache of recently used keys in front
+    /// of it.
+    Disk,
+}
+
+impl Default for UpsertStateBackend {
+    fn default() -> Self {
+        UpsertStateBackend::Memory
+    }
+}
+
+impl RustType<ProtoUpsertStateBackend> for UpsertStateBackend {
+    fn into_proto(&self) -> ProtoUpsertStateBackend {
+        use proto_upsert_state_backend::Kind;
+        ProtoUpsertStateBackend {
+            kind: Some(match self {
+                UpsertStateBackend::Memory => Kind::Memory(()),
+                UpsertStateBackend::Disk => Kind::Disk(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoUpsertStateBackend) -> Result<Self, TryFromProtoError> {
+        use proto_upsert_state_backend::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoUpsertStateBackend::kind"))?;
+        Ok(match kind {
+            Kind::Memory(()) => UpsertStateBackend::Memory,
+            Kind::Disk(()) => UpsertStateBackend::Disk,
         })
     }
 }
@@ -1001,6 +1182,7 @@ impl UnplannedSourceEnvelope {
                     style: upsert_style,
                     key_indices: key.expect("into_source_envelope to be passed correct parameters for UnplannedSourceEnvelope::Upsert"),
                     source_arity: source_arity.expect("into_source_envelope to be passed correct parameters for UnplannedSourceEnvelope::Upsert"),
+                    state_backend: UpsertStateBackend::default(),
                 })
             },
             UnplannedSourceEnvelope::Debezium(inner) => {
@@ -1009,6 +1191,7 @@ impl UnplannedSourceEnvelope {
             UnplannedSourceEnvelope::None(key_envelope) => SourceEnvelope::None(NoneEnvelope {
                 key_envelope,
                 key_arity: key_arity.unwrap_or(0),
+                decode_errors: DecodeErrorPolicy::default(),
             }),
             UnplannedSourceEnvelope::CdcV2 => SourceEnvelope::CdcV2,
         }
@@ -1134,10 +1317,16 @@ pub struct KafkaSourceConnection {
     pub connection: KafkaConnection,
     pub connection_id: GlobalId,
     pub options: BTreeMap<String, StringOrSecret>,
+    /// The topic to read or, if `topic_pattern` is set, a regular expression
+    /// that the names of the topics to read must match in their entirety.
     pub topic: String,
+    pub topic_pattern: bool,
     // Map from partition -> starting offset
     pub start_offsets: HashMap<i32, i64>,
     pub group_id_prefix: Option<String>,
+    /// If present, commit the source's offsets back to its consumer group at
+    /// most this often, rather than whenever they advance.
+    pub commit_interval: Option<Duration>,
     pub environment_id: String,
     /// If present, include the timestamp as an output column of the source with the given name
     pub include_timestamp: Option<IncludedColumnPos>,
@@ -1148,12 +1337,64 @@ pub struct KafkaSourceConnection {
     /// If present, include the offset as an output column of the source with the given name.
     pub include_offset: Option<IncludedColumnPos>,
     pub include_headers: Option<IncludedColumnPos>,
+    /// Individual headers to decode into their own output columns.
+    pub include_header: Vec<IncludedHeader>,
 }
 
 impl crate::source::types::SourceConnection for KafkaSourceConnection {
     fn name(&self) -> &'static str {
         "kafka"
     }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options: BTreeMap<_, _> = self
+            .options
+            .iter()
+            .map(|(k, v)| (k.clone(), v.to_redacted_string()))
+            .collect();
+        if self.topic_pattern {
+            options.insert("topic_pattern".into(), self.topic.clone());
+        } else {
+            options.insert("topic".into(), self.topic.clone());
+        }
+        if let Some(prefix) = &self.group_id_prefix {
+            options.insert("group_id_prefix".into(), prefix.clone());
+        }
+        if let Some(interval) = &self.commit_interval {
+            options.insert(
+                "commit_interval".into(),
+                format!("{}ms", interval.as_millis()),
+            );
+        }
+        if !self.start_offsets.is_empty() {
+            let offsets: BTreeMap<_, _> = self.start_offsets.iter().collect();
+            options.insert(
+                "start_offset".into(),
+                offsets
+                    .into_iter()
+                    .map(|(partition, offset)| format!("{partition}:{offset}"))
+                    .join(","),
+            );
+        }
+        for (option, column) in [
+            ("include_timestamp", &self.include_timestamp),
+            ("include_partition", &self.include_partition),
+            ("include_topic", &self.include_topic),
+            ("include_offset", &self.include_offset),
+            ("include_headers", &self.include_headers),
+        ] {
+            if let Some(column) = column {
+                options.insert(option.into(), column.name.clone());
+            }
+        }
+        for header in &self.include_header {
+            options.insert(
+                format!("include_header.{}", header.key),
+                header.column.name.clone(),
+            );
+        }
+        options
+    }
 }
 
 impl Arbitrary for KafkaSourceConnection {
@@ -1165,43 +1406,50 @@ impl Arbitrary for KafkaSourceConnection {
             any::<KafkaConnection>(),
             any::<GlobalId>(),
             proptest::collection::btree_map(any::<String>(), any::<StringOrSecret>(), 1..4),
-            any::<String>(),
+            (any::<String>(), any::<bool>()),
             proptest::collection::hash_map(any::<i32>(), any::<i64>(), 1..4),
-            any::<Option<String>>(),
-            any::<String>(),
+            (
+                any::<Option<String>>(),
+                any::<Option<Duration>>(),
+                any::<String>(),
+            ),
             any::<Option<IncludedColumnPos>>(),
             any::<Option<IncludedColumnPos>>(),
             any::<Option<IncludedColumnPos>>(),
             any::<Option<IncludedColumnPos>>(),
             any::<Option<IncludedColumnPos>>(),
+            proptest::collection::vec(any::<IncludedHeader>(), 0..2),
         )
             .prop_map(
                 |(
                     connection,
                     connection_id,
                     options,
-                    topic,
+                    (topic, topic_pattern),
                     start_offsets,
-                    group_id_prefix,
-                    environment_id,
+                    (group_id_prefix, commit_interval, environment_id),
                     include_timestamp,
                     include_partition,
                     include_topic,
                     include_offset,
                     include_headers,
+                    include_header,
                 )| KafkaSourceConnection {
                     connection,
                     connection_id,
                     options,
                     topic,
+                    topic_pattern,
                     start_offsets,
                     group_id_prefix,
+                    commit_interval,
                     environment_id,
                     include_timestamp,
                     include_partition,
                     include_topic,
                     include_offset,
                     include_headers,
+                    include_header,
                 },
             )
             .boxed()
@@ -1219,8 +1467,10 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
                 .map(|(k, v)| (k.clone(), v.into_proto()))
                 .collect(),
             topic: self.topic.clone(),
+            topic_pattern: self.topic_pattern,
             start_offsets: self.start_offsets.clone(),
             group_id_prefix: self.group_id_prefix.clone(),
+            commit_interval: self.commit_interval.into_proto(),
             environment_id: None,
             environment_name: Some(self.environment_id.into_proto()),
             include_timestamp: self.include_timestamp.into_proto(),
@@ -1228,6 +1478,7 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             include_topic: self.include_topic.into_proto(),
             include_offset: self.include_offset.into_proto(),
             include_headers: self.include_headers.into_proto(),
+            include_header: self.include_header.into_proto(),
         }
     }
 
@@ -1246,8 +1497,10 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
                 .into_rust_if_some("ProtoKafkaSourceConnection::connection_id")?,
             options: options?,
             topic: proto.topic,
+            topic_pattern: proto.topic_pattern,
             start_offsets: proto.start_offsets,
             group_id_prefix: proto.group_id_prefix,
+            commit_interval: proto.commit_interval.into_rust()?,
             environment_id: match (proto.environment_id, proto.environment_name) {
                 (_, Some(name)) => name,
                 (u128, _) => {
@@ -1261,22 +1514,64 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             include_topic: proto.include_topic.into_rust()?,
             include_offset: proto.include_offset.into_rust()?,
             include_headers: proto.include_headers.into_rust()?,
+            include_header: proto.include_header.into_rust()?,
         })
     }
 }
 
 #[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Compression {
+    /// Detect the compression of each object or file with
+    /// [`Compression::detect`].
+    Auto,
+    Bzip2,
     Gzip,
+    Zstd,
     None,
 }
 
+impl Compression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::Auto => "auto",
+            Compression::Bzip2 => "bzip2",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::None => "none",
+        }
+    }
+
+    /// Determines how an object or file is compressed from the magic bytes at
+    /// the start of its contents, or failing that from the extension of its
+    /// name. Never returns [`Compression::Auto`].
+    pub fn detect(name: &str, prefix: &[u8]) -> Compression {
+        if prefix.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if prefix.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if prefix.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else if name.ends_with(".gz") {
+            Compression::Gzip
+        } else if name.ends_with(".zst") {
+            Compression::Zstd
+        } else if name.ends_with(".bz2") {
+            Compression::Bzip2
+        } else {
+            Compression::None
+        }
+    }
+}
+
 impl RustType<ProtoCompression> for Compression {
     fn into_proto(&self) -> ProtoCompression {
         use proto_compression::Kind;
         ProtoCompression {
             kind: Some(match self {
+                Compression::Auto => Kind::Auto(()),
+                Compression::Bzip2 => Kind::Bzip2(()),
                 Compression::Gzip => Kind::Gzip(()),
+                Compression::Zstd => Kind::Zstd(()),
                 Compression::None => Kind::None(()),
             }),
         }
@@ -1285,7 +1580,10 @@ impl RustType<ProtoCompression> for Compression {
     fn from_proto(proto: ProtoCompression) -> Result<Self, TryFromProtoError> {
         use proto_compression::Kind;
         Ok(match proto.kind {
+            Some(Kind::Auto(())) => Compression::Auto,
+            Some(Kind::Bzip2(())) => Compression::Bzip2,
             Some(Kind::Gzip(())) => Compression::Gzip,
+            Some(Kind::Zstd(())) => Compression::Zstd,
             Some(Kind::None(())) => Compression::None,
             None => {
                 return Err(TryFromProtoError::MissingField(
@@ -1304,6 +1602,12 @@ pub struct SourceDesc {
     pub envelope: SourceEnvelope,
     pub metadata_columns: Vec<IncludedColumnSource>,
     pub timestamp_interval: Duration,
+    /// How the timestamps that ingested offsets are bound to are chosen.
+    pub timestamp_strategy: TimestampStrategy,
+    /// The column from which updates are timestamped, if any.
+    pub event_time: Option<EventTimeConfig>,
+    /// The maximum rate at which data is read from the upstream system.
+    pub max_ingest_rate: IngestRateLimit,
 }
 
 impl Arbitrary for SourceDesc {
@@ -1317,14 +1621,29 @@ impl Arbitrary for SourceDesc {
             any::<SourceEnvelope>(),
             any::<Vec<IncludedColumnSource>>(),
             any::<Duration>(),
+            any::<TimestampStrategy>(),
+            any::<Option<EventTimeConfig>>(),
+            any::<IngestRateLimit>(),
         )
             .prop_map(
-                |(connection, encoding, envelope, metadata_columns, timestamp_interval)| Self {
+                |(
+                    connection,
+                    encoding,
+                    envelope,
+                    metadata_columns,
+                    timestamp_interval,
+                    timestamp_strategy,
+                    event_time,
+                    max_ingest_rate,
+                )| Self {
                     connection,
                     encoding,
                     envelope,
                     metadata_columns,
                     timestamp_interval,
+                    timestamp_strategy,
+                    event_time,
+                    max_ingest_rate,
                 },
             )
             .boxed()
@@ -1339,6 +1658,9 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             envelope: Some(self.envelope.into_proto()),
             metadata_columns: self.metadata_columns.into_proto(),
             timestamp_interval: Some(self.timestamp_interval.into_proto()),
+            timestamp_strategy: Some(self.timestamp_strategy.into_proto()),
+            event_time: self.event_time.into_proto(),
+            max_ingest_rate: Some(self.max_ingest_rate.into_proto()),
         }
     }
 
@@ -1357,6 +1679,120 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             timestamp_interval: proto
                 .timestamp_interval
                 .into_rust_if_some("ProtoSourceDesc::timestamp_interval")?,
+            timestamp_strategy: proto
+                .timestamp_strategy
+                .into_rust_if_some("ProtoSourceDesc::timestamp_strategy")?,
+            event_time: proto.event_time.into_rust()?,
+            max_ingest_rate: proto
+                .max_ingest_rate
+                .into_rust_if_some("ProtoSourceDesc::max_ingest_rate")?,
+        })
+    }
+}
+
+/// Configures the timestamping of updates by the time at which the events
+/// they describe occurred, rather than the time at which they were ingested.
+///
+/// The frontier of the source advances in steps of `lateness`, aligned to
+/// multiples of it, that trail the ingestion frontier. Each update is
+/// timestamped by its event time, clamped to the step in which it was
+/// ingested. Updates whose event time is within `lateness` of their
+/// ingestion time are thus usually timestamped exactly, while the
+/// correspondence between the frontiers of the source and the offsets of
+/// the upstream system is preserved across restarts.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EventTimeConfig {
+    /// The index of the `timestamp` or `timestamp with time zone` column that
+    /// holds the event time of each update.
+    pub column: usize,
+    /// The bound on the out-of-orderness of event times.
+    pub lateness: Duration,
+}
+
+impl RustType<ProtoEventTimeConfig> for EventTimeConfig {
+    fn into_proto(&self) -> ProtoEventTimeConfig {
+        ProtoEventTimeConfig {
+            column: self.column.into_proto(),
+            lateness: Some(self.lateness.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoEventTimeConfig) -> Result<Self, TryFromProtoError> {
+        Ok(EventTimeConfig {
+            column: proto.column.into_rust()?,
+            lateness: proto
+                .lateness
+                .into_rust_if_some("ProtoEventTimeConfig::lateness")?,
+        })
+    }
+}
+
+/// How a source chooses the timestamps that the offsets it ingests are bound to.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TimestampStrategy {
+    /// Offsets are bound to the current wall-clock time, rounded up to a
+    /// multiple of the timestamp interval.
+    WallClock,
+    /// Offsets are bound to the total number of offsets the source has
+    /// ingested across its partitions, so that timestamps advance in
+    /// proportion to the amount of data ingested rather than with time.
+    Offset,
+}
+
+impl Default for TimestampStrategy {
+    fn default() -> Self {
+        TimestampStrategy::WallClock
+    }
+}
+
+impl RustType<ProtoTimestampStrategy> for TimestampStrategy {
+    fn into_proto(&self) -> ProtoTimestampStrategy {
+        use proto_timestamp_strategy::Kind;
+        ProtoTimestampStrategy {
+            kind: Some(match self {
+                TimestampStrategy::WallClock => Kind::WallClock(()),
+                TimestampStrategy::Offset => Kind::Offset(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoTimestampStrategy) -> Result<Self, TryFromProtoError> {
+        use proto_timestamp_strategy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoTimestampStrategy::kind"))?;
+        Ok(match kind {
+            Kind::WallClock(()) => TimestampStrategy::WallClock,
+            Kind::Offset(()) => TimestampStrategy::Offset,
+        })
+    }
+}
+
+/// Limits on the rate at which a source reads data from its upstream system.
+///
+/// The limits are enforced by each worker that reads from the source, so that
+/// a source that is catching up on a large backlog cannot monopolize the
+/// workers it shares with other dataflows.
+#[derive(Arbitrary, Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IngestRateLimit {
+    /// The maximum number of bytes of keys and values read per second.
+    pub bytes_per_second: Option<u64>,
+    /// The maximum number of messages read per second.
+    pub messages_per_second: Option<u64>,
+}
+
+impl RustType<ProtoIngestRateLimit> for IngestRateLimit {
+    fn into_proto(&self) -> ProtoIngestRateLimit {
+        ProtoIngestRateLimit {
+            bytes_per_second: self.bytes_per_second,
+            messages_per_second: self.messages_per_second,
+        }
+    }
+
+    fn from_proto(proto: ProtoIngestRateLimit) -> Result<Self, TryFromProtoError> {
+        Ok(IngestRateLimit {
+            bytes_per_second: proto.bytes_per_second,
+            messages_per_second: proto.messages_per_second,
         })
     }
 }
@@ -1375,6 +1811,16 @@ impl SourceDesc {
                 connection: SourceConnection::Postgres(_),
                 ..
             } => false,
+            // MySQL can produce retractions (deletes)
+            SourceDesc {
+                connection: SourceConnection::MySql(_),
+                ..
+            } => false,
+            // SQL Server can produce retractions (deletes)
+            SourceDesc {
+                connection: SourceConnection::SqlServer(_),
+                ..
+            } => false,
             // Loadgen can produce retractions (deletes)
             SourceDesc {
                 connection: SourceConnection::LoadGenerator(_),
@@ -1393,6 +1839,11 @@ impl SourceDesc {
                     SourceConnection::S3(_)
                     | SourceConnection::Kafka(_)
                     | SourceConnection::Kinesis(_)
+                    | SourceConnection::File(_)
+                    | SourceConnection::Mqtt(_)
+                    | SourceConnection::Nats(_)
+                    | SourceConnection::PubSub(_)
+                    | SourceConnection::Sftp(_)
                     | SourceConnection::TestScript(_),
                 ..
             } => false,
@@ -1405,11 +1856,18 @@ impl SourceDesc {
             SourceConnection::Kafka(_)
             | SourceConnection::Kinesis(_)
             | SourceConnection::S3(_)
+            | SourceConnection::File(_)
+            | SourceConnection::Mqtt(_)
+            | SourceConnection::Nats(_)
+            | SourceConnection::PubSub(_)
+            | SourceConnection::Sftp(_)
             | SourceConnection::TestScript(_) => 0,
             SourceConnection::LoadGenerator(connection) => {
                 as_generator(&connection.load_generator).views().len()
             }
             SourceConnection::Postgres(connection) => connection.details.tables.len(),
+            SourceConnection::MySql(connection) => connection.details.tables.len(),
+            SourceConnection::SqlServer(connection) => connection.details.tables.len(),
         };
         // Every ingestion produces a main stream plus subsource streams
         subsources + 1
@@ -1426,7 +1884,14 @@ pub enum SourceConnection {
     Kinesis(KinesisSourceConnection),
     S3(S3SourceConnection),
     Postgres(PostgresSourceConnection),
+    MySql(MySqlSourceConnection),
+    SqlServer(SqlServerSourceConnection),
     LoadGenerator(LoadGeneratorSourceConnection),
+    File(FileSourceConnection),
+    Mqtt(MqttSourceConnection),
+    Nats(NatsSourceConnection),
+    PubSub(PubSubSourceConnection),
+    Sftp(SftpSourceConnection),
     TestScript(TestScriptSourceConnection),
 }
 
@@ -1437,8 +1902,11 @@ impl SourceConnection {
             Kafka(KafkaSourceConnection { connection_id, .. })
             | Kinesis(KinesisSourceConnection { connection_id, .. })
             | S3(S3SourceConnection { connection_id, .. })
-            | Postgres(PostgresSourceConnection { connection_id, .. }) => Some(*connection_id),
-            LoadGenerator(_) | TestScript(_) => None,
+            | Postgres(PostgresSourceConnection { connection_id, .. })
+            | MySql(MySqlSourceConnection { connection_id, .. })
+            | SqlServer(SqlServerSourceConnection { connection_id, .. }) => Some(*connection_id),
+            LoadGenerator(_) | File(_) | Mqtt(_) | Nats(_) | PubSub(_) | Sftp(_)
+            | TestScript(_) => None,
         }
     }
 }
@@ -1452,7 +1920,14 @@ impl RustType<ProtoSourceConnection> for SourceConnection {
                 SourceConnection::Kinesis(kinesis) => Kind::Kinesis(kinesis.into_proto()),
                 SourceConnection::S3(s3) => Kind::S3(s3.into_proto()),
                 SourceConnection::Postgres(postgres) => Kind::Postgres(postgres.into_proto()),
+                SourceConnection::MySql(mysql) => Kind::Mysql(mysql.into_proto()),
+                SourceConnection::SqlServer(sql_server) => Kind::SqlServer(sql_server.into_proto()),
                 SourceConnection::LoadGenerator(loadgen) => Kind::Loadgen(loadgen.into_proto()),
+                SourceConnection::File(file) => Kind::File(file.into_proto()),
+                SourceConnection::Mqtt(mqtt) => Kind::Mqtt(mqtt.into_proto()),
+                SourceConnection::Nats(nats) => Kind::Nats(nats.into_proto()),
+                SourceConnection::PubSub(pubsub) => Kind::Pubsub(pubsub.into_proto()),
+                SourceConnection::Sftp(sftp) => Kind::Sftp(sftp.into_proto()),
                 SourceConnection::TestScript(testscript) => {
                     Kind::Testscript(testscript.into_proto())
                 }
@@ -1470,7 +1945,14 @@ impl RustType<ProtoSourceConnection> for SourceConnection {
             Kind::Kinesis(kinesis) => SourceConnection::Kinesis(kinesis.into_rust()?),
             Kind::S3(s3) => SourceConnection::S3(s3.into_rust()?),
             Kind::Postgres(postgres) => SourceConnection::Postgres(postgres.into_rust()?),
+            Kind::Mysql(mysql) => SourceConnection::MySql(mysql.into_rust()?),
+            Kind::SqlServer(sql_server) => SourceConnection::SqlServer(sql_server.into_rust()?),
             Kind::Loadgen(loadgen) => SourceConnection::LoadGenerator(loadgen.into_rust()?),
+            Kind::File(file) => SourceConnection::File(file.into_rust()?),
+            Kind::Mqtt(mqtt) => SourceConnection::Mqtt(mqtt.into_rust()?),
+            Kind::Nats(nats) => SourceConnection::Nats(nats.into_rust()?),
+            Kind::Pubsub(pubsub) => SourceConnection::PubSub(pubsub.into_rust()?),
+            Kind::Sftp(sftp) => SourceConnection::Sftp(sftp.into_rust()?),
             Kind::Testscript(testscript) => SourceConnection::TestScript(testscript.into_rust()?),
         })
     }
@@ -1494,6 +1976,7 @@ impl SourceConnection {
                 include_topic: topic,
                 include_offset: offset,
                 include_headers: headers,
+                include_header,
                 ..
             }) => {
                 let mut items = BTreeMap::new();
@@ -1532,13 +2015,45 @@ impl SourceConnection {
                         items.insert(include.pos + 1, (&*include.name, ty.nullable(false)));
                     }
                 }
+                for header in include_header {
+                    let ty = header
+                        .encoding
+                        .column_type()
+                        .expect("header encodings are validated during planning");
+                    items.insert(header.column.pos + 1, (&*header.column.name, ty));
+                }
 
                 items.into_values().collect()
             }
             Self::Kinesis(_) => vec![],
             Self::S3(_) => vec![],
             Self::Postgres(_) => vec![],
+            Self::MySql(_) => vec![],
+            Self::SqlServer(_) => vec![],
             Self::LoadGenerator(_) => vec![],
+            Self::File(FileSourceConnection {
+                include_filename,
+                include_offset,
+                ..
+            }) => {
+                let mut items = BTreeMap::new();
+                for (include, ty) in [
+                    (include_filename, ScalarType::String),
+                    (include_offset, ScalarType::UInt64),
+                ] {
+                    if let Some(include) = include {
+                        items.insert(include.pos, (&*include.name, ty.nullable(false)));
+                    }
+                }
+                items.into_values().collect()
+            }
+            Self::Mqtt(MqttSourceConnection { include_topic, .. }) => include_topic
+                .iter()
+                .map(|include| (&*include.name, ScalarType::String.nullable(false)))
+                .collect(),
+            Self::Nats(_) => vec![],
+            Self::PubSub(_) => vec![],
+            Self::Sftp(_) => vec![],
             Self::TestScript(_) => vec![],
         }
     }
@@ -1551,6 +2066,7 @@ impl SourceConnection {
                 include_topic: topic,
                 include_offset: offset,
                 include_headers: headers,
+                include_header,
                 ..
             }) => {
                 // create a sorted list of column types based on the order they were declared in sql
@@ -1568,14 +2084,50 @@ impl SourceConnection {
                         items.insert(include.pos, ty);
                     }
                 }
+                for header in include_header {
+                    items.insert(
+                        header.column.pos,
+                        IncludedColumnSource::Header {
+                            key: header.key.clone(),
+                            encoding: header.encoding.clone(),
+                        },
+                    );
+                }
+
+                items.into_values().collect()
+            }
 
+            SourceConnection::File(FileSourceConnection {
+                include_filename,
+                include_offset,
+                ..
+            }) => {
+                let mut items = BTreeMap::new();
+                for (include, ty) in [
+                    (include_filename, IncludedColumnSource::Filename),
+                    (include_offset, IncludedColumnSource::Offset),
+                ] {
+                    if let Some(include) = include {
+                        items.insert(include.pos, ty);
+                    }
+                }
                 items.into_values().collect()
             }
 
+            SourceConnection::Mqtt(MqttSourceConnection { include_topic, .. }) => include_topic
+                .iter()
+                .map(|_| IncludedColumnSource::Topic)
+                .collect(),
+
             SourceConnection::Kinesis(_)
             | SourceConnection::S3(_)
             | SourceConnection::Postgres(_)
+            | SourceConnection::MySql(_)
+            | SourceConnection::SqlServer(_)
             | SourceConnection::LoadGenerator(_)
+            | SourceConnection::Nats(_)
+            | SourceConnection::PubSub(_)
+            | SourceConnection::Sftp(_)
             | SourceConnection::TestScript(_) => Vec::new(),
         }
     }
@@ -1588,11 +2140,39 @@ impl SourceConnection {
             SourceConnection::Kinesis(c) => c.name(),
             SourceConnection::S3(c) => c.name(),
             SourceConnection::Postgres(c) => c.name(),
+            SourceConnection::MySql(c) => c.name(),
+            SourceConnection::SqlServer(c) => c.name(),
             SourceConnection::LoadGenerator(c) => c.name(),
+            SourceConnection::File(c) => c.name(),
+            SourceConnection::Mqtt(c) => c.name(),
+            SourceConnection::Nats(c) => c.name(),
+            SourceConnection::PubSub(c) => c.name(),
+            SourceConnection::Sftp(c) => c.name(),
             SourceConnection::TestScript(c) => c.name(),
         }
     }
 
+    /// Returns the options of the external source connection, with secrets
+    /// redacted.
+    pub fn options(&self) -> BTreeMap<String, String> {
+        use crate::source::types::SourceConnection as _;
+        match self {
+            SourceConnection::Kafka(c) => c.options(),
+            SourceConnection::Kinesis(c) => c.options(),
+            SourceConnection::S3(c) => c.options(),
+            SourceConnection::Postgres(c) => c.options(),
+            SourceConnection::MySql(c) => c.options(),
+            SourceConnection::SqlServer(c) => c.options(),
+            SourceConnection::LoadGenerator(c) => c.options(),
+            SourceConnection::File(c) => c.options(),
+            SourceConnection::Mqtt(c) => c.options(),
+            SourceConnection::Nats(c) => c.options(),
+            SourceConnection::PubSub(c) => c.options(),
+            SourceConnection::Sftp(c) => c.options(),
+            SourceConnection::TestScript(c) => c.options(),
+        }
+    }
+
     /// Optionally returns the name of the upstream resource this source corresponds to.
     /// (Currently only implemented for Kafka and Kinesis, to match old-style behavior
     ///  TODO: decide whether we want file paths and other upstream names to show up in metrics too.
@@ -1604,7 +2184,14 @@ impl SourceConnection {
             }
             SourceConnection::S3(_) => None,
             SourceConnection::Postgres(_) => None,
+            SourceConnection::MySql(_) => None,
+            SourceConnection::SqlServer(_) => None,
             SourceConnection::LoadGenerator(_) => None,
+            SourceConnection::File(_) => None,
+            SourceConnection::Mqtt(_) => None,
+            SourceConnection::Nats(_) => None,
+            SourceConnection::PubSub(_) => None,
+            SourceConnection::Sftp(_) => None,
             SourceConnection::TestScript(_) => None,
         }
     }
@@ -1614,6 +2201,9 @@ impl SourceConnection {
 pub struct KinesisSourceConnection {
     pub connection_id: GlobalId,
     pub stream_name: String,
+    /// The name of the enhanced fan-out consumer to read the stream with, or
+    /// `None` to poll the stream's shards with `GetRecords`.
+    pub consumer_name: Option<String>,
     pub aws: AwsConfig,
 }
 
@@ -1621,12 +2211,22 @@ impl crate::source::types::SourceConnection for KinesisSourceConnection {
     fn name(&self) -> &'static str {
         "kinesis"
     }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = self.aws.options();
+        options.insert("stream_name".into(), self.stream_name.clone());
+        if let Some(consumer_name) = &self.consumer_name {
+            options.insert("consumer_name".into(), consumer_name.clone());
+        }
+        options
+    }
 }
 
 impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
     fn into_proto(&self) -> ProtoKinesisSourceConnection {
         ProtoKinesisSourceConnection {
             stream_name: self.stream_name.clone(),
+            consumer_name: self.consumer_name.clone(),
             aws: Some(self.aws.into_proto()),
             connection_id: Some(self.connection_id.into_proto()),
         }
@@ -1635,6 +2235,7 @@ impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
     fn from_proto(proto: ProtoKinesisSourceConnection) -> Result<Self, TryFromProtoError> {
         Ok(KinesisSourceConnection {
             stream_name: proto.stream_name,
+            consumer_name: proto.consumer_name,
             aws: proto
                 .aws
                 .into_rust_if_some("ProtoKinesisSourceConnection::aws")?,
@@ -1687,6 +2288,21 @@ impl crate::source::types::SourceConnection for PostgresSourceConnection {
     fn name(&self) -> &'static str {
         "postgres"
     }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("publication".into(), self.publication.clone()),
+            ("slot".into(), self.details.slot.clone()),
+            (
+                "tables".into(),
+                self.details
+                    .tables
+                    .iter()
+                    .map(|t| format!("{}.{}", t.namespace, t.name))
+                    .join(","),
+            ),
+        ])
+    }
 }
 
 impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
@@ -1762,55 +2378,326 @@ impl RustType<ProtoPostgresSourceDetails> for PostgresSourceDetails {
     }
 }
 
-#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub struct LoadGeneratorSourceConnection {
-    pub load_generator: LoadGenerator,
-    pub tick_micros: Option<u64>,
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: MySqlConnection,
+    /// The cast expressions to convert the incoming string encoded rows to their target types
+    pub table_casts: Vec<Vec<MirScalarExpr>>,
+    pub details: MySqlSourceDetails,
 }
 
-impl crate::source::types::SourceConnection for LoadGeneratorSourceConnection {
-    fn name(&self) -> &'static str {
-        "load-generator"
+impl Arbitrary for MySqlSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<MySqlConnection>(),
+            any::<GlobalId>(),
+            proptest::collection::vec(
+                proptest::collection::vec(any::<MirScalarExpr>(), 1..4),
+                1..4,
+            ),
+            any::<MySqlSourceDetails>(),
+        )
+            .prop_map(|(connection, connection_id, table_casts, details)| Self {
+                connection,
+                connection_id,
+                table_casts,
+                details,
+            })
+            .boxed()
     }
 }
-#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub enum LoadGenerator {
-    Auction,
-    Counter,
-    Tpch {
-        count_supplier: i64,
-        count_part: i64,
-        count_customer: i64,
-        count_orders: i64,
-        count_clerk: i64,
-    },
-}
 
-pub trait Generator {
-    fn data_encoding_inner(&self) -> DataEncodingInner;
-    fn data_encoding(&self) -> SourceDataEncoding {
-        SourceDataEncoding::Single(DataEncoding::new(self.data_encoding_inner()))
+impl crate::source::types::SourceConnection for MySqlSourceConnection {
+    fn name(&self) -> &'static str {
+        "mysql"
     }
-    /// Returns the list of table names and their column types that this generator generates
-    fn views(&self) -> Vec<(&str, RelationDesc)>;
 
-    /// Returns a function that produces rows and batch information.
-    fn by_seed(
-        &self,
-        now: NowFn,
-        seed: Option<u64>,
-    ) -> Box<dyn Iterator<Item = (usize, GeneratorMessageType, Row)>>;
+    fn options(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("server id".into(), self.details.server_id.to_string()),
+            (
+                "tables".into(),
+                self.details
+                    .tables
+                    .iter()
+                    .map(|t| format!("{}.{}", t.schema_name, t.name))
+                    .join(","),
+            ),
+        ])
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub enum GeneratorMessageType {
-    InProgress,
-    Finalized,
-}
+impl RustType<ProtoMySqlSourceConnection> for MySqlSourceConnection {
+    fn into_proto(&self) -> ProtoMySqlSourceConnection {
+        use proto_my_sql_source_connection::ProtoMySqlTableCast;
+        let mut table_casts = vec![];
+        for table_cast in self.table_casts.iter() {
+            table_casts.push(ProtoMySqlTableCast {
+                column_casts: table_cast
+                    .iter()
+                    .cloned()
+                    .map(|cast| cast.into_proto())
+                    .collect(),
+            });
+        }
+        ProtoMySqlSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+            table_casts,
+        }
+    }
 
-impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnection {
-    fn into_proto(&self) -> ProtoLoadGeneratorSourceConnection {
-        ProtoLoadGeneratorSourceConnection {
+    fn from_proto(proto: ProtoMySqlSourceConnection) -> Result<Self, TryFromProtoError> {
+        let mut table_casts = vec![];
+        for table_cast in proto.table_casts {
+            let mut column_casts = vec![];
+            for cast in table_cast.column_casts {
+                column_casts.push(cast.into_rust()?);
+            }
+            table_casts.push(column_casts);
+        }
+        Ok(MySqlSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoMySqlSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoMySqlSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoMySqlSourceConnection::details")?,
+            table_casts,
+        })
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlSourceDetails {
+    pub tables: Vec<mz_mysql_util::desc::MySqlTableDesc>,
+    /// The server ID with which the source registers as a replica of the
+    /// MySQL server, which must be unique among its replicas.
+    pub server_id: u32,
+}
+
+impl RustType<ProtoMySqlSourceDetails> for MySqlSourceDetails {
+    fn into_proto(&self) -> ProtoMySqlSourceDetails {
+        ProtoMySqlSourceDetails {
+            tables: self.tables.iter().map(|t| t.into_proto()).collect(),
+            server_id: self.server_id,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlSourceDetails {
+            tables: proto
+                .tables
+                .into_iter()
+                .map(mz_mysql_util::desc::MySqlTableDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+            server_id: proto.server_id,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: SqlServerConnection,
+    /// The cast expressions to convert the incoming string encoded rows to their target types
+    pub table_casts: Vec<Vec<MirScalarExpr>>,
+    pub details: SqlServerSourceDetails,
+}
+
+impl Arbitrary for SqlServerSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<SqlServerConnection>(),
+            any::<GlobalId>(),
+            proptest::collection::vec(
+                proptest::collection::vec(any::<MirScalarExpr>(), 1..4),
+                1..4,
+            ),
+            any::<SqlServerSourceDetails>(),
+        )
+            .prop_map(|(connection, connection_id, table_casts, details)| Self {
+                connection,
+                connection_id,
+                table_casts,
+                details,
+            })
+            .boxed()
+    }
+}
+
+impl crate::source::types::SourceConnection for SqlServerSourceConnection {
+    fn name(&self) -> &'static str {
+        "sql-server"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([(
+            "tables".into(),
+            self.details
+                .tables
+                .iter()
+                .map(|t| format!("{}.{}", t.schema_name, t.name))
+                .join(","),
+        )])
+    }
+}
+
+impl RustType<ProtoSqlServerSourceConnection> for SqlServerSourceConnection {
+    fn into_proto(&self) -> ProtoSqlServerSourceConnection {
+        use proto_sql_server_source_connection::ProtoSqlServerTableCast;
+        let mut table_casts = vec![];
+        for table_cast in self.table_casts.iter() {
+            table_casts.push(ProtoSqlServerTableCast {
+                column_casts: table_cast
+                    .iter()
+                    .cloned()
+                    .map(|cast| cast.into_proto())
+                    .collect(),
+            });
+        }
+        ProtoSqlServerSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+            table_casts,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceConnection) -> Result<Self, TryFromProtoError> {
+        let mut table_casts = vec![];
+        for table_cast in proto.table_casts {
+            let mut column_casts = vec![];
+            for cast in table_cast.column_casts {
+                column_casts.push(cast.into_rust()?);
+            }
+            table_casts.push(column_casts);
+        }
+        Ok(SqlServerSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoSqlServerSourceConnection::details")?,
+            table_casts,
+        })
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerSourceDetails {
+    pub tables: Vec<mz_sql_server_util::desc::SqlServerTableDesc>,
+}
+
+impl RustType<ProtoSqlServerSourceDetails> for SqlServerSourceDetails {
+    fn into_proto(&self) -> ProtoSqlServerSourceDetails {
+        ProtoSqlServerSourceDetails {
+            tables: self.tables.iter().map(|t| t.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerSourceDetails {
+            tables: proto
+                .tables
+                .into_iter()
+                .map(mz_sql_server_util::desc::SqlServerTableDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LoadGeneratorSourceConnection {
+    pub load_generator: LoadGenerator,
+    pub tick_micros: Option<u64>,
+}
+
+impl crate::source::types::SourceConnection for LoadGeneratorSourceConnection {
+    fn name(&self) -> &'static str {
+        "load-generator"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        let generator = match &self.load_generator {
+            LoadGenerator::Auction => "auction",
+            LoadGenerator::Counter => "counter",
+            LoadGenerator::Tpch {
+                count_supplier,
+                count_part,
+                count_customer,
+                count_orders,
+                count_clerk,
+            } => {
+                options.insert("count_supplier".into(), count_supplier.to_string());
+                options.insert("count_part".into(), count_part.to_string());
+                options.insert("count_customer".into(), count_customer.to_string());
+                options.insert("count_orders".into(), count_orders.to_string());
+                options.insert("count_clerk".into(), count_clerk.to_string());
+                "tpch"
+            }
+        };
+        options.insert("generator".into(), generator.into());
+        if let Some(tick_micros) = self.tick_micros {
+            options.insert("tick_interval".into(), format!("{tick_micros}us"));
+        }
+        options
+    }
+}
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LoadGenerator {
+    Auction,
+    Counter,
+    Tpch {
+        count_supplier: i64,
+        count_part: i64,
+        count_customer: i64,
+        count_orders: i64,
+        count_clerk: i64,
+    },
+}
+
+pub trait Generator {
+    fn data_encoding_inner(&self) -> DataEncodingInner;
+    fn data_encoding(&self) -> SourceDataEncoding {
+        SourceDataEncoding::Single(DataEncoding::new(self.data_encoding_inner()))
+    }
+    /// Returns the list of table names and their column types that this generator generates
+    fn views(&self) -> Vec<(&str, RelationDesc)>;
+
+    /// Returns a function that produces rows and batch information.
+    fn by_seed(
+        &self,
+        now: NowFn,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (usize, GeneratorMessageType, Row)>>;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum GeneratorMessageType {
+    InProgress,
+    Finalized,
+}
+
+impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnection {
+    fn into_proto(&self) -> ProtoLoadGeneratorSourceConnection {
+        ProtoLoadGeneratorSourceConnection {
             generator: Some(match &self.load_generator {
                 LoadGenerator::Auction => ProtoGenerator::Auction(()),
                 LoadGenerator::Counter => ProtoGenerator::Counter(()),
@@ -1868,6 +2755,10 @@ impl crate::source::types::SourceConnection for TestScriptSourceConnection {
     fn name(&self) -> &'static str {
         "testscript"
     }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
 }
 
 impl RustType<ProtoTestScriptSourceConnection> for TestScriptSourceConnection {
@@ -1897,6 +2788,32 @@ impl crate::source::types::SourceConnection for S3SourceConnection {
     fn name(&self) -> &'static str {
         "s3"
     }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = self.aws.options();
+        let mut buckets = vec![];
+        let mut queues = vec![];
+        for source in &self.key_sources {
+            match source {
+                S3KeySource::Scan { bucket } => buckets.push(bucket.as_str()),
+                S3KeySource::SqsNotifications { queue } => queues.push(queue.as_str()),
+            }
+        }
+        if !buckets.is_empty() {
+            options.insert("discover_objects.scan".into(), buckets.join(","));
+        }
+        if !queues.is_empty() {
+            options.insert(
+                "discover_objects.sqs_notifications".into(),
+                queues.join(","),
+            );
+        }
+        if let Some(pattern) = &self.pattern {
+            options.insert("pattern".into(), pattern.glob().to_string());
+        }
+        options.insert("compression".into(), self.compression.name().into());
+        options
+    }
 }
 
 fn any_glob() -> impl Strategy<Value = Glob> {
@@ -1970,6 +2887,432 @@ impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
     }
 }
 
+/// A source that tails the files in a local directory whose names match a
+/// pattern.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSourceConnection {
+    /// The directory that contains the files to tail.
+    pub dir: String,
+    /// The glob that the names of tailed files must match.
+    pub pattern: Glob,
+    pub compression: Compression,
+    /// How often to check the files for new lines.
+    pub poll_interval: Duration,
+    /// The column that the name of each line's file is included in, if any.
+    pub include_filename: Option<IncludedColumnPos>,
+    /// The column that the offset of each line in its file is included in, if
+    /// any.
+    pub include_offset: Option<IncludedColumnPos>,
+}
+
+impl crate::source::types::SourceConnection for FileSourceConnection {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::from([
+            ("dir".into(), self.dir.clone()),
+            ("pattern".into(), self.pattern.glob().to_string()),
+            ("compression".into(), self.compression.name().into()),
+            (
+                "poll_interval".into(),
+                format!("{}ms", self.poll_interval.as_millis()),
+            ),
+        ]);
+        if let Some(column) = &self.include_filename {
+            options.insert("include_filename".into(), column.name.clone());
+        }
+        if let Some(column) = &self.include_offset {
+            options.insert("include_offset".into(), column.name.clone());
+        }
+        options
+    }
+}
+
+impl Arbitrary for FileSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any_glob(),
+            any::<Compression>(),
+            any::<Duration>(),
+            any::<Option<IncludedColumnPos>>(),
+            any::<Option<IncludedColumnPos>>(),
+        )
+            .prop_map(
+                |(dir, pattern, compression, poll_interval, include_filename, include_offset)| {
+                    FileSourceConnection {
+                        dir,
+                        pattern,
+                        compression,
+                        poll_interval,
+                        include_filename,
+                        include_offset,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl RustType<ProtoFileSourceConnection> for FileSourceConnection {
+    fn into_proto(&self) -> ProtoFileSourceConnection {
+        ProtoFileSourceConnection {
+            dir: self.dir.clone(),
+            pattern: self.pattern.glob().into(),
+            compression: Some(self.compression.into_proto()),
+            poll_interval: Some(self.poll_interval.into_proto()),
+            include_filename: self.include_filename.into_proto(),
+            include_offset: self.include_offset.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(FileSourceConnection {
+            dir: proto.dir,
+            pattern: GlobBuilder::new(&proto.pattern)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()?,
+            compression: proto
+                .compression
+                .into_rust_if_some("ProtoFileSourceConnection::compression")?,
+            poll_interval: proto
+                .poll_interval
+                .into_rust_if_some("ProtoFileSourceConnection::poll_interval")?,
+            include_filename: proto.include_filename.into_rust()?,
+            include_offset: proto.include_offset.into_rust()?,
+        })
+    }
+}
+
+/// The key of a line read by a file source, which carries the name of the
+/// line's file and the offset of the line in it to the decoding of the
+/// source's metadata columns. File sources have no other use for keys.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileLineKey<'a> {
+    pub filename: &'a str,
+    /// The zero-based number of the line in its file.
+    pub offset: u64,
+}
+
+impl<'a> FileLineKey<'a> {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut key = Vec::with_capacity(8 + self.filename.len());
+        key.extend_from_slice(&self.offset.to_be_bytes());
+        key.extend_from_slice(self.filename.as_bytes());
+        key
+    }
+
+    pub fn decode(key: &'a [u8]) -> FileLineKey<'a> {
+        let (offset, filename) = key.split_at(8);
+        FileLineKey {
+            filename: std::str::from_utf8(filename).expect("file names are encoded as UTF-8"),
+            offset: u64::from_be_bytes(offset.try_into().expect("known to be 8 bytes")),
+        }
+    }
+}
+
+/// A source that subscribes to topics of an MQTT broker.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MqttSourceConnection {
+    /// The URL of the broker to connect to, like `mqtt://host:1883`.
+    pub url: String,
+    /// The topic filter to subscribe to, which may contain wildcards.
+    pub topic: String,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    /// The column that the topic of each message is included in, if any.
+    pub include_topic: Option<IncludedColumnPos>,
+}
+
+impl crate::source::types::SourceConnection for MqttSourceConnection {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::from([
+            ("url".into(), self.url.clone()),
+            ("topic".into(), self.topic.clone()),
+        ]);
+        if let Some(user) = &self.user {
+            options.insert("user".into(), user.to_redacted_string());
+        }
+        if let Some(id) = self.password {
+            options.insert("password".into(), format!("SECRET {id}"));
+        }
+        if let Some(column) = &self.include_topic {
+            options.insert("include_topic".into(), column.name.clone());
+        }
+        options
+    }
+}
+
+impl RustType<ProtoMqttSourceConnection> for MqttSourceConnection {
+    fn into_proto(&self) -> ProtoMqttSourceConnection {
+        ProtoMqttSourceConnection {
+            url: self.url.clone(),
+            topic: self.topic.clone(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            include_topic: self.include_topic.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMqttSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MqttSourceConnection {
+            url: proto.url,
+            topic: proto.topic,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            include_topic: proto.include_topic.into_rust()?,
+        })
+    }
+}
+
+/// A source that reads the messages of a NATS JetStream stream through a
+/// durable consumer.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NatsSourceConnection {
+    /// The URL of the server to connect to, like `nats://host:4222`.
+    pub url: String,
+    /// The stream to read from.
+    pub stream: String,
+    /// The name of the durable consumer to read through, or `None` to derive
+    /// it from the ID of the source.
+    pub consumer: Option<String>,
+    /// The subject that ingested messages must match, if any.
+    pub subject: Option<String>,
+    pub user: Option<StringOrSecret>,
+    pub password: Option<GlobalId>,
+    pub token: Option<GlobalId>,
+}
+
+impl crate::source::types::SourceConnection for NatsSourceConnection {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::from([
+            ("url".into(), self.url.clone()),
+            ("stream".into(), self.stream.clone()),
+        ]);
+        if let Some(consumer) = &self.consumer {
+            options.insert("consumer".into(), consumer.clone());
+        }
+        if let Some(subject) = &self.subject {
+            options.insert("subject".into(), subject.clone());
+        }
+        if let Some(user) = &self.user {
+            options.insert("user".into(), user.to_redacted_string());
+        }
+        if let Some(id) = self.password {
+            options.insert("password".into(), format!("SECRET {id}"));
+        }
+        if let Some(id) = self.token {
+            options.insert("token".into(), format!("SECRET {id}"));
+        }
+        options
+    }
+}
+
+impl RustType<ProtoNatsSourceConnection> for NatsSourceConnection {
+    fn into_proto(&self) -> ProtoNatsSourceConnection {
+        ProtoNatsSourceConnection {
+            url: self.url.clone(),
+            stream: self.stream.clone(),
+            consumer: self.consumer.clone(),
+            subject: self.subject.clone(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            token: self.token.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoNatsSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(NatsSourceConnection {
+            url: proto.url,
+            stream: proto.stream,
+            consumer: proto.consumer,
+            subject: proto.subject,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            token: proto.token.into_rust()?,
+        })
+    }
+}
+
+/// A source that pulls messages from a Google Cloud Pub/Sub subscription.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PubSubSourceConnection {
+    /// The subscription to pull from, like
+    /// `projects/my-project/subscriptions/my-subscription`.
+    pub subscription: String,
+    /// The URL of the Pub/Sub API.
+    pub endpoint: String,
+    /// The secret containing the JSON key of the service account to
+    /// authenticate as, or `None` to connect without authenticating, as to an
+    /// emulator.
+    pub credentials: Option<GlobalId>,
+    /// How long Pub/Sub waits for each message to be acknowledged before
+    /// redelivering it, unless the source extends the deadline.
+    pub ack_deadline: Duration,
+}
+
+impl crate::source::types::SourceConnection for PubSubSourceConnection {
+    fn name(&self) -> &'static str {
+        "pubsub"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::from([
+            ("subscription".into(), self.subscription.clone()),
+            ("endpoint".into(), self.endpoint.clone()),
+            (
+                "ack_deadline".into(),
+                format!("{}s", self.ack_deadline.as_secs()),
+            ),
+        ]);
+        if let Some(id) = self.credentials {
+            options.insert("credentials".into(), format!("SECRET {id}"));
+        }
+        options
+    }
+}
+
+impl RustType<ProtoPubSubSourceConnection> for PubSubSourceConnection {
+    fn into_proto(&self) -> ProtoPubSubSourceConnection {
+        ProtoPubSubSourceConnection {
+            subscription: self.subscription.clone(),
+            endpoint: self.endpoint.clone(),
+            credentials: self.credentials.into_proto(),
+            ack_deadline: Some(self.ack_deadline.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPubSubSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PubSubSourceConnection {
+            subscription: proto.subscription,
+            endpoint: proto.endpoint,
+            credentials: proto.credentials.into_rust()?,
+            ack_deadline: proto
+                .ack_deadline
+                .into_rust_if_some("ProtoPubSubSourceConnection::ack_deadline")?,
+        })
+    }
+}
+
+/// A source that ingests the files that appear in a directory on an SFTP
+/// server.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SftpSourceConnection {
+    /// The URL of the directory to watch, like `sftp://host:22/inbox`.
+    pub url: String,
+    pub user: StringOrSecret,
+    pub password: Option<GlobalId>,
+    pub private_key: Option<GlobalId>,
+    /// The glob that the names of ingested files must match, if any.
+    pub pattern: Option<Glob>,
+    /// How often to check the directory for new files.
+    pub poll_interval: Duration,
+}
+
+impl crate::source::types::SourceConnection for SftpSourceConnection {
+    fn name(&self) -> &'static str {
+        "sftp"
+    }
+
+    fn options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::from([
+            ("url".into(), self.url.clone()),
+            ("user".into(), self.user.to_redacted_string()),
+            (
+                "poll_interval".into(),
+                format!("{}ms", self.poll_interval.as_millis()),
+            ),
+        ]);
+        if let Some(id) = self.password {
+            options.insert("password".into(), format!("SECRET {id}"));
+        }
+        if let Some(id) = self.private_key {
+            options.insert("private_key".into(), format!("SECRET {id}"));
+        }
+        if let Some(pattern) = &self.pattern {
+            options.insert("pattern".into(), pattern.glob().to_string());
+        }
+        options
+    }
+}
+
+impl Arbitrary for SftpSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<StringOrSecret>(),
+            any::<Option<GlobalId>>(),
+            any::<Option<GlobalId>>(),
+            proptest::option::of(any_glob()),
+            any::<Duration>(),
+        )
+            .prop_map(
+                |(url, user, password, private_key, pattern, poll_interval)| SftpSourceConnection {
+                    url,
+                    user,
+                    password,
+                    private_key,
+                    pattern,
+                    poll_interval,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl RustType<ProtoSftpSourceConnection> for SftpSourceConnection {
+    fn into_proto(&self) -> ProtoSftpSourceConnection {
+        ProtoSftpSourceConnection {
+            url: self.url.clone(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            private_key: self.private_key.into_proto(),
+            pattern: self.pattern.as_ref().map(|g| g.glob().into()),
+            poll_interval: Some(self.poll_interval.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSftpSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SftpSourceConnection {
+            url: proto.url,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSftpSourceConnection::user")?,
+            password: proto.password.into_rust()?,
+            private_key: proto.private_key.into_rust()?,
+            pattern: proto
+                .pattern
+                .map(|p| {
+                    GlobBuilder::new(&p)
+                        .literal_separator(true)
+                        .backslash_escape(true)
+                        .build()
+                })
+                .transpose()?,
+            poll_interval: proto
+                .poll_interval
+                .into_rust_if_some("ProtoSftpSourceConnection::poll_interval")?,
+        })
+    }
+}
+
 /// A Source of Object Key names, the argument of the `DISCOVER OBJECTS` clause
 #[derive(Arbitrary, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum S3KeySource {
@@ -126,6 +126,23 @@ impl SourceDataEncoding {
             SourceDataEncoding::KeyValue { key, value } => (Some(key.desc()?), value.desc()?),
         })
     }
+
+    /// Sets the numeric overflow policy of the Avro encodings among the key
+    /// and value encodings, and returns whether there were any.
+    pub fn set_numeric_overflow(&mut self, policy: NumericOverflowPolicy) -> bool {
+        let encodings = match self {
+            SourceDataEncoding::Single(value) => vec![value],
+            SourceDataEncoding::KeyValue { key, value } => vec![key, value],
+        };
+        let mut found = false;
+        for encoding in encodings {
+            if let DataEncodingInner::Avro(avro) = &mut encoding.inner {
+                avro.numeric_overflow = policy;
+                found = true;
+            }
+        }
+        found
+    }
 }
 
 /// A description of how each row should be decoded, from a string of bytes to a sequence of
@@ -139,6 +156,10 @@ pub enum DataEncodingInner {
     Bytes,
     Text,
     RowCodec(RelationDesc),
+    /// Rows read from Parquet objects, with the columns described by the
+    /// given [`RelationDesc`].
+    Parquet(RelationDesc),
+    Json(JsonEncoding),
 }
 
 impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
@@ -153,6 +174,8 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
                 DataEncodingInner::Bytes => Kind::Bytes(()),
                 DataEncodingInner::Text => Kind::Text(()),
                 DataEncodingInner::RowCodec(e) => Kind::RowCodec(e.into_proto()),
+                DataEncodingInner::Parquet(e) => Kind::Parquet(e.into_proto()),
+                DataEncodingInner::Json(e) => Kind::Json(e.into_proto()),
             }),
         }
     }
@@ -170,6 +193,8 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
             Kind::Bytes(()) => DataEncodingInner::Bytes,
             Kind::Text(()) => DataEncodingInner::Text,
             Kind::RowCodec(e) => DataEncodingInner::RowCodec(e.into_rust()?),
+            Kind::Parquet(e) => DataEncodingInner::Parquet(e.into_rust()?),
+            Kind::Json(e) => DataEncodingInner::Json(e.into_rust()?),
         })
     }
 }
@@ -224,9 +249,22 @@ impl DataEncoding {
             DataEncodingInner::Bytes => {
                 RelationDesc::empty().with_column("data", ScalarType::Bytes.nullable(false))
             }
-            DataEncodingInner::Avro(AvroEncoding { schema, .. }) => {
+            DataEncodingInner::Avro(AvroEncoding {
+                schema,
+                numeric_overflow,
+                ..
+            }) => {
                 let parsed_schema = avro::parse_schema(schema).context("validating avro schema")?;
-                avro::schema_to_relationdesc(parsed_schema).context("validating avro schema")?
+                let desc = avro::schema_to_relationdesc(parsed_schema)
+                    .context("validating avro schema")?;
+                match numeric_overflow {
+                    // Values that overflow their column are decoded as `NULL`.
+                    NumericOverflowPolicy::Null => RelationDesc::from_names_and_types(
+                        desc.into_iter()
+                            .map(|(name, typ)| (name, nullable_if_overflowable(typ))),
+                    ),
+                    NumericOverflowPolicy::Error | NumericOverflowPolicy::Saturate => desc,
+                }
             }
             DataEncodingInner::Protobuf(ProtobufEncoding {
                 descriptors,
@@ -254,6 +292,10 @@ impl DataEncoding {
                     let ty = ScalarType::String.nullable(true);
                     desc.with_column(name, ty)
                 }),
+            DataEncodingInner::Csv(CsvEncoding {
+                typed_columns: Some(desc),
+                ..
+            }) => desc.clone(),
             DataEncodingInner::Csv(CsvEncoding { columns, .. }) => match columns {
                 ColumnSpec::Count(n) => {
                     (1..=*n).into_iter().fold(RelationDesc::empty(), |desc, i| {
@@ -270,7 +312,13 @@ impl DataEncoding {
             DataEncodingInner::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
-            DataEncodingInner::RowCodec(desc) => desc.clone(),
+            DataEncodingInner::RowCodec(desc) | DataEncodingInner::Parquet(desc) => desc.clone(),
+            DataEncodingInner::Json(JsonEncoding { columns, .. }) => match columns {
+                Some(desc) => desc.clone(),
+                None => {
+                    RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+                }
+            },
         };
 
         if self.force_nullable_columns {
@@ -292,16 +340,39 @@ impl DataEncoding {
             DataEncodingInner::Csv(_) => "Csv",
             DataEncodingInner::Text => "Text",
             DataEncodingInner::RowCodec(_) => "RowCodec",
+            DataEncodingInner::Parquet(_) => "Parquet",
+            DataEncodingInner::Json(_) => "Json",
         }
     }
 }
 
+/// Makes `typ`, and the types of the fields of any records it contains,
+/// nullable if values of the type can overflow during decoding.
+fn nullable_if_overflowable(typ: ColumnType) -> ColumnType {
+    match typ.scalar_type {
+        ScalarType::Numeric { .. } | ScalarType::Date | ScalarType::Timestamp => typ.nullable(true),
+        ScalarType::Record { fields, custom_id } => ColumnType {
+            scalar_type: ScalarType::Record {
+                fields: fields
+                    .into_iter()
+                    .map(|(name, typ)| (name, nullable_if_overflowable(typ)))
+                    .collect(),
+                custom_id,
+            },
+            nullable: typ.nullable,
+        },
+        _ => typ,
+    }
+}
+
 /// Encoding in Avro format.
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct AvroEncoding {
     pub schema: String,
     pub csr_connection: Option<CsrConnection>,
     pub confluent_wire_format: bool,
+    /// How to decode values that do not fit their column.
+    pub numeric_overflow: NumericOverflowPolicy,
 }
 
 impl RustType<ProtoAvroEncoding> for AvroEncoding {
@@ -310,6 +381,7 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: self.schema.clone(),
             csr_connection: self.csr_connection.into_proto(),
             confluent_wire_format: self.confluent_wire_format,
+            numeric_overflow: Some(self.numeric_overflow.into_proto()),
         }
     }
 
@@ -318,6 +390,133 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: proto.schema,
             csr_connection: proto.csr_connection.into_rust()?,
             confluent_wire_format: proto.confluent_wire_format,
+            numeric_overflow: proto
+                .numeric_overflow
+                .into_rust_if_some("ProtoAvroEncoding::numeric_overflow")?,
+        })
+    }
+}
+
+/// How the value of a single Kafka header is decoded into a column.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum HeaderEncoding {
+    Bytes,
+    Text,
+    Json,
+    Avro(AvroEncoding),
+}
+
+impl RustType<ProtoHeaderEncoding> for HeaderEncoding {
+    fn into_proto(&self) -> ProtoHeaderEncoding {
+        use proto_header_encoding::Kind;
+        ProtoHeaderEncoding {
+            kind: Some(match self {
+                HeaderEncoding::Bytes => Kind::Bytes(()),
+                HeaderEncoding::Text => Kind::Text(()),
+                HeaderEncoding::Json => Kind::Json(()),
+                HeaderEncoding::Avro(avro) => Kind::Avro(avro.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoHeaderEncoding) -> Result<Self, TryFromProtoError> {
+        use proto_header_encoding::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoHeaderEncoding::kind"))?;
+        Ok(match kind {
+            Kind::Bytes(()) => HeaderEncoding::Bytes,
+            Kind::Text(()) => HeaderEncoding::Text,
+            Kind::Json(()) => HeaderEncoding::Json,
+            Kind::Avro(avro) => HeaderEncoding::Avro(avro.into_rust()?),
+        })
+    }
+}
+
+impl HeaderEncoding {
+    /// Computes the type of the column the decoded header is written to.
+    ///
+    /// The column is always nullable, as any given message may lack the
+    /// header. Avro values that decode to more than one column are packed
+    /// into a single record.
+    pub fn column_type(&self) -> Result<ColumnType, anyhow::Error> {
+        let scalar_type = match self {
+            HeaderEncoding::Bytes => ScalarType::Bytes,
+            HeaderEncoding::Text => ScalarType::String,
+            HeaderEncoding::Json => ScalarType::Jsonb,
+            HeaderEncoding::Avro(avro) => {
+                let desc = DataEncoding::new(DataEncodingInner::Avro(avro.clone())).desc()?;
+                match desc.typ().column_types.as_slice() {
+                    [column] => column.scalar_type.clone(),
+                    _ => ScalarType::Record {
+                        fields: desc
+                            .iter()
+                            .map(|(name, typ)| (name.clone(), typ.clone()))
+                            .collect(),
+                        custom_id: None,
+                    },
+                }
+            }
+        };
+        Ok(scalar_type.nullable(true))
+    }
+}
+
+/// How a source decodes values that do not fit the type of their column,
+/// e.g. Avro decimals that exceed the maximum precision of `numeric`, or
+/// dates and timestamps outside of the supported range.
+#[derive(Arbitrary, Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum NumericOverflowPolicy {
+    /// Fail to decode the record.
+    Error,
+    /// Decode the value as `NULL`. Columns that can overflow are nullable.
+    Null,
+    /// Decode the value as the closest value that fits the column.
+    Saturate,
+}
+
+impl NumericOverflowPolicy {
+    /// Returns the name of the policy, as it appears in SQL.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NumericOverflowPolicy::Error => "error",
+            NumericOverflowPolicy::Null => "null",
+            NumericOverflowPolicy::Saturate => "saturate",
+        }
+    }
+}
+
+impl From<NumericOverflowPolicy> for avro::OverflowPolicy {
+    fn from(policy: NumericOverflowPolicy) -> Self {
+        match policy {
+            NumericOverflowPolicy::Error => avro::OverflowPolicy::Error,
+            NumericOverflowPolicy::Null => avro::OverflowPolicy::Null,
+            NumericOverflowPolicy::Saturate => avro::OverflowPolicy::Saturate,
+        }
+    }
+}
+
+impl RustType<ProtoNumericOverflowPolicy> for NumericOverflowPolicy {
+    fn into_proto(&self) -> ProtoNumericOverflowPolicy {
+        use proto_numeric_overflow_policy::Kind;
+        ProtoNumericOverflowPolicy {
+            kind: Some(match self {
+                NumericOverflowPolicy::Error => Kind::Error(()),
+                NumericOverflowPolicy::Null => Kind::Null(()),
+                NumericOverflowPolicy::Saturate => Kind::Saturate(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoNumericOverflowPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_numeric_overflow_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoNumericOverflowPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Error(()) => NumericOverflowPolicy::Error,
+            Kind::Null(()) => NumericOverflowPolicy::Null,
+            Kind::Saturate(()) => NumericOverflowPolicy::Saturate,
         })
     }
 }
@@ -353,6 +552,14 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
 pub struct CsvEncoding {
     pub columns: ColumnSpec,
     pub delimiter: u8,
+    /// The character that quotes fields.
+    pub quote: u8,
+    /// The character that escapes quotes inside of quoted fields, if any.
+    /// Quotes can always be escaped by doubling them.
+    pub escape: Option<u8>,
+    /// The columns to decode, selected by name from `columns` and decoded
+    /// into their types, or `None` to decode every column as text.
+    pub typed_columns: Option<RelationDesc>,
 }
 
 impl RustType<ProtoCsvEncoding> for CsvEncoding {
@@ -360,6 +567,9 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
         ProtoCsvEncoding {
             columns: Some(self.columns.into_proto()),
             delimiter: self.delimiter.into_proto(),
+            quote: self.quote.into_proto(),
+            escape: self.escape.into_proto(),
+            typed_columns: self.typed_columns.into_proto(),
         }
     }
 
@@ -369,6 +579,36 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
                 .columns
                 .into_rust_if_some("ProtoCsvEncoding::columns")?,
             delimiter: proto.delimiter.into_rust()?,
+            quote: proto.quote.into_rust()?,
+            escape: proto.escape.into_rust()?,
+            typed_columns: proto.typed_columns.into_rust()?,
+        })
+    }
+}
+
+/// Arguments necessary to define how to decode from JSON format
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonEncoding {
+    /// The columns to decode the fields of each JSON object into, or `None`
+    /// to decode each message into a single `jsonb` column named `data`.
+    pub columns: Option<RelationDesc>,
+    /// Whether objects with fields that are not among `columns` fail to
+    /// decode, rather than having those fields ignored.
+    pub reject_unknown_fields: bool,
+}
+
+impl RustType<ProtoJsonEncoding> for JsonEncoding {
+    fn into_proto(&self) -> ProtoJsonEncoding {
+        ProtoJsonEncoding {
+            columns: self.columns.into_proto(),
+            reject_unknown_fields: self.reject_unknown_fields,
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonEncoding {
+            columns: proto.columns.into_rust()?,
+            reject_unknown_fields: proto.reject_unknown_fields,
         })
     }
 }
@@ -418,6 +658,15 @@ impl ColumnSpec {
         }
     }
 
+    /// The names of the columns described by the column spec, which are
+    /// `columnN` if the column spec has no header.
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            ColumnSpec::Count(n) => (1..=*n).map(|i| format!("column{}", i)).collect(),
+            ColumnSpec::Header { names } => names.clone(),
+        }
+    }
+
     pub fn into_header_names(self) -> Option<Vec<String>> {
         match self {
             ColumnSpec::Count(_) => None,
@@ -12,7 +12,7 @@
 use std::collections::{BTreeMap, HashMap};
 use std::marker::{Send, Sync};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use timely::progress::{Antichain, Timestamp as _};
 
@@ -48,6 +48,9 @@ pub fn run_script_source(
         envelope,
         metadata_columns: vec![],
         timestamp_interval,
+        timestamp_strategy: Default::default(),
+        event_time: None,
+        max_ingest_rate: Default::default(),
     };
 
     build_and_run_source(desc, timestamp_interval, move |upper, mut read| {
@@ -154,14 +157,23 @@ where
                 sink_metrics,
                 timely_worker_index: 0,
                 timely_worker_peers: 0,
-                connection_context: mz_storage::types::connections::ConnectionContext {
-                    librdkafka_log_level: tracing::Level::INFO,
-                    aws_external_id_prefix: None,
-                    secrets_reader: Arc::new(mz_secrets::InMemorySecretsController::new()),
-                },
+                connection_context: mz_storage::types::connections::ConnectionContext::for_tests(
+                    Arc::new(mz_secrets::InMemorySecretsController::new()),
+                ),
                 persist_clients,
                 sink_tokens: HashMap::new(),
                 sink_write_frontiers: HashMap::new(),
+                source_message_sizes: Default::default(),
+                sink_progress_publishes: Default::default(),
+                sink_dead_letters: Default::default(),
+                source_numeric_overflows: Default::default(),
+                sink_metrics_updates: Default::default(),
+                sink_status_updates: Default::default(),
+                last_introspection_report: Instant::now(),
+                upsert_state_config: mz_storage::storage_state::UpsertStateConfig {
+                    scratch_directory: std::env::temp_dir(),
+                    disk_cache_capacity: 100_000,
+                },
             };
 
             let (_fake_tx, fake_rx) = crossbeam_channel::bounded(1);
@@ -175,6 +187,7 @@ where
                 remap_shard: mz_persist_client::ShardId::new(),
                 data_shard: mz_persist_client::ShardId::new(),
                 status_shard: None,
+                relation_arity: None,
             };
             let data_shard = collection_metadata.data_shard.clone();
             let id = GlobalId::User(1);
@@ -11,6 +11,7 @@ use std::env;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
 use anyhow::{bail, Context};
 use axum::routing;
@@ -26,6 +27,7 @@ use mz_ore::now::SYSTEM_TIME;
 use mz_pid_file::PidFile;
 use mz_service::grpc::GrpcServer;
 use mz_storage::protocol::client::proto_storage_server::ProtoStorageServer;
+use mz_storage::storage_state::UpsertStateConfig;
 use mz_storage::types::connections::ConnectionContext;
 
 // Disable jemalloc on macOS, as it is not well supported [0][1][2].
@@ -81,6 +83,48 @@ struct Args {
     #[clap(long, env = "AWS_EXTERNAL_ID", value_name = "ID")]
     aws_external_id: Option<String>,
 
+    // === Schema registry options. ===
+    /// How long to cache schemas fetched from schema registries by ID.
+    #[clap(
+        long,
+        env = "CSR_CACHE_SCHEMA_TTL",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "1h"
+    )]
+    csr_cache_schema_ttl: Duration,
+    /// How long to cache the latest versions of schema registry subjects.
+    #[clap(
+        long,
+        env = "CSR_CACHE_SUBJECT_TTL",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "60s"
+    )]
+    csr_cache_subject_ttl: Duration,
+    /// How long to cache the absence of schemas and subjects from schema
+    /// registries.
+    #[clap(
+        long,
+        env = "CSR_CACHE_NEGATIVE_TTL",
+        parse(try_from_str = humantime::parse_duration),
+        default_value = "10s"
+    )]
+    csr_cache_negative_ttl: Duration,
+
+    // === Upsert state options. ===
+    /// The directory in which upsert sources with a disk-backed `UPSERT STATE`
+    /// keep their state. Defaults to the system's temporary directory.
+    #[clap(long, env = "UPSERT_SCRATCH_DIRECTORY", value_name = "PATH")]
+    upsert_scratch_directory: Option<PathBuf>,
+    /// The maximum number of values each upsert source with a disk-backed
+    /// `UPSERT STATE` caches in memory.
+    #[clap(
+        long,
+        env = "UPSERT_DISK_CACHE_CAPACITY",
+        value_name = "N",
+        default_value = "100000"
+    )]
+    upsert_disk_cache_capacity: usize,
+
     // === Process orchestrator options. ===
     /// Where to write a PID lock file.
     ///
@@ -207,7 +251,16 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             &args.tracing.log_filter.inner,
             args.aws_external_id,
             secrets_reader,
-        ),
+        )
+        .with_csr_cache_ttls(mz_ccsr::CacheTtls {
+            schema: args.csr_cache_schema_ttl,
+            subject: args.csr_cache_subject_ttl,
+            negative: args.csr_cache_negative_ttl,
+        }),
+        upsert_state_config: UpsertStateConfig {
+            scratch_directory: args.upsert_scratch_directory.unwrap_or_else(env::temp_dir),
+            disk_cache_capacity: args.upsert_disk_cache_capacity,
+        },
     };
 
     // Initialize fail crate for failpoint support
@@ -22,6 +22,8 @@ pub async fn run_request(
     let url = cmd.args.string("url")?;
     let method: Method = cmd.args.parse("method")?;
     let content_type = cmd.args.opt_string("content-type");
+    let header = cmd.args.opt_string("header");
+    let expected_status: Option<u16> = cmd.args.opt_parse("expected-status")?;
     let body = cmd.input.join("\n");
 
     println!("$ http-request {} {}\n{}", method, url, body);
@@ -34,14 +36,27 @@ pub async fn run_request(
         request = request.header(CONTENT_TYPE, value);
     }
 
+    if let Some(header) = &header {
+        let (name, value) = match header.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => bail!("header must be of the form name:value, got {}", header),
+        };
+        request = request.header(name, value);
+    }
+
     let response = request.send().await?;
     let status = response.status();
 
     println!("{}\n{}", status, response.text().await?);
 
-    if status.is_success() {
-        Ok(ControlFlow::Continue)
-    } else {
-        bail!("http request returned failing status: {}", status)
+    match expected_status {
+        Some(expected) if status.as_u16() == expected => Ok(ControlFlow::Continue),
+        Some(expected) => bail!(
+            "http request returned status {}, but expected {}",
+            status,
+            expected
+        ),
+        None if status.is_success() => Ok(ControlFlow::Continue),
+        None => bail!("http request returned failing status: {}", status),
     }
 }
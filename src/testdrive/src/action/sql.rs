@@ -68,6 +68,7 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &mut State) -> Result<ControlFl
         | CreateType(_)
         | CreateRole(_)
         | AlterObjectRename(_)
+        | AlterOwner(_)
         | AlterIndex(_)
         | Discard(_)
         | DropDatabase(_)
@@ -28,10 +28,10 @@ def test_secrets(mz: MaterializeApplication) -> None:
               );
 
             # Our Redpanda instance is not configured for SASL, so we can not
-            # really establish a successful connection. Hence the expectation for an SSL error
+            # really establish a successful connection. Hence the expectation for a TLS error
             ! CREATE SOURCE secrets_source
               FROM KAFKA CONNECTION secrets_conn (TOPIC 'foo_bar');
-            contains: SSL handshake failed
+            contains: TLS handshake failed
             """
         )
     )
@@ -63,6 +63,7 @@ def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
         # See: https://materializeinc.slack.com/archives/C02FWJ94HME/p1661288774456699?thread_ts=1661288684.301649&cid=C02FWJ94HME
         # "test-builtin-migration",
         "pg-snapshot-resumption",
+        "pg-chunked-snapshot-resumption",
     ]:
         with c.test_case(name):
             c.workflow(name)
@@ -396,6 +397,48 @@ def workflow_pg_snapshot_resumption(c: Composition) -> None:
             c.run("testdrive", "pg-snapshot-resumption/04-verify-data.td")
 
 
+def workflow_pg_chunked_snapshot_resumption(c: Composition) -> None:
+    """Test resuming the chunked initial snapshot of a Postgres source after a crash."""
+
+    c.down(destroy_volumes=True)
+
+    with c.override(
+        Postgres(),
+        Testdrive(no_reset=True),
+        # Give the first chunks time to be ingested before crashing
+        Storaged(
+            environment=[
+                "FAILPOINTS=pg_snapshot_chunk_failure=3*off->1*sleep(10000)->return"
+            ]
+        ),
+    ):
+        dependencies = [
+            "materialized",
+            "postgres",
+            "storaged",
+        ]
+        c.start_and_wait_for_tcp(
+            services=dependencies,
+        )
+
+        c.run("testdrive", "pg-chunked-snapshot-resumption/01-configure-postgres.td")
+        c.run("testdrive", "pg-chunked-snapshot-resumption/02-create-sources.td")
+
+        print("Sleeping to ensure that storaged crashes")
+        time.sleep(20)
+
+        c.run("testdrive", "pg-chunked-snapshot-resumption/03-while-storaged-down.td")
+
+        with c.override(
+            # turn off the failpoint
+            Storaged()
+        ):
+            c.start_and_wait_for_tcp(
+                services=["storaged"],
+            )
+            c.run("testdrive", "pg-chunked-snapshot-resumption/04-verify-data.td")
+
+
 def workflow_test_bootstrap_vars(c: Composition) -> None:
     """Test default system vars values passed with a CLI option."""
 
@@ -0,0 +1,89 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""A gRPC server whose client-streaming method records the updates that gRPC
+sinks stream to it, as one JSON object per line."""
+
+import json
+from concurrent import futures
+from typing import Dict, Iterator, Optional, Tuple, Union
+
+import grpc
+
+TOKEN = "Bearer grpc-token"
+UPDATES_PATH = "/tmp/updates.jsonl"
+
+Update = Dict[str, Optional[Union[str, int]]]
+
+
+def read_varint(data: bytes, pos: int) -> Tuple[int, int]:
+    result = 0
+    shift = 0
+    while True:
+        byte = data[pos]
+        pos += 1
+        result |= (byte & 0x7F) << shift
+        if not byte & 0x80:
+            return result, pos
+        shift += 7
+
+
+def decode_update(data: bytes) -> Update:
+    """Decodes the `key`, `value` and `timestamp` fields of an update."""
+    update: Update = {"key": None, "value": None, "timestamp": 0}
+    names = {1: "key", 2: "value", 3: "timestamp"}
+    pos = 0
+    while pos < len(data):
+        tag, pos = read_varint(data, pos)
+        field, wire_type = tag >> 3, tag & 7
+        value: Union[str, int]
+        if wire_type == 0:
+            value, pos = read_varint(data, pos)
+        elif wire_type == 2:
+            length, pos = read_varint(data, pos)
+            value = data[pos : pos + length].decode()
+            pos += length
+        else:
+            raise ValueError(f"unexpected wire type {wire_type}")
+        update[names[field]] = value
+    return update
+
+
+def stream(updates: Iterator[Update], context: grpc.ServicerContext) -> bytes:
+    metadata = dict(context.invocation_metadata())
+    if metadata.get("authorization") != TOKEN:
+        context.abort(grpc.StatusCode.UNAUTHENTICATED, "invalid token")
+    # Only record complete calls, as the sink streams a failed call again.
+    received = list(updates)
+    with open(UPDATES_PATH, "a") as f:
+        for update in received:
+            f.write(json.dumps(update) + "\n")
+    return b""
+
+
+def main() -> None:
+    handler = grpc.method_handlers_generic_handler(
+        "orders.v1.OrderIngest",
+        {
+            "Stream": grpc.stream_unary_rpc_method_handler(
+                stream,
+                request_deserializer=decode_update,
+                response_serializer=lambda response: response,
+            )
+        },
+    )
+    server = grpc.server(futures.ThreadPoolExecutor(max_workers=4))
+    server.add_generic_rpc_handlers((handler,))
+    server.add_insecure_port("[::]:50051")
+    server.start()
+    server.wait_for_termination()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,301 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Tests for the sink and source connectors to external systems, which check
+what the sinks write to those systems, and what the sources read from them."""
+
+import json
+import time
+from typing import Any, Callable, Dict, List, Optional
+
+from materialize.mzcompose import Composition, Service
+from materialize.mzcompose.services import (
+    Elasticsearch,
+    Localstack,
+    Materialized,
+    Mosquitto,
+    Nats,
+    NatsBox,
+    Redis,
+    Sftp,
+    Testdrive,
+)
+
+SERVICES = [
+    Materialized(),
+    Testdrive(),
+    Elasticsearch(),
+    Redis(),
+    Sftp(),
+    Nats(),
+    NatsBox(),
+    Mosquitto(),
+    Localstack(),
+    Service(
+        "grpc-server",
+        {
+            "image": "python:3.10-slim",
+            "command": "sh -c 'pip install grpcio==1.50.0 && python /grpc_server.py'",
+            "ports": [50051],
+            "volumes": ["./grpc_server.py:/grpc_server.py"],
+        },
+    ),
+]
+
+
+def wait_for(description: str, check: Callable[[], bool], timeout: int = 120) -> None:
+    """Polls `check` until it passes, as sinks write to external systems
+    asynchronously."""
+    deadline = time.monotonic() + timeout
+    while not check():
+        if time.monotonic() > deadline:
+            raise Exception(f"timed out waiting for {description}")
+        time.sleep(1)
+
+
+def workflow_elasticsearch(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "elasticsearch"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "elasticsearch.td")
+
+    def document(id: int) -> Optional[Dict[str, Any]]:
+        response = c.exec(
+            "elasticsearch",
+            "curl",
+            "--silent",
+            f"http://localhost:9200/orders/_doc/{id}",
+            capture=True,
+        ).stdout
+        body = json.loads(response)
+        return body["_source"] if body.get("found") else None
+
+    wait_for(
+        "the initial documents",
+        lambda: document(1) == {"id": 1, "item": "widget"}
+        and document(2) == {"id": 2, "item": "gadget"},
+    )
+
+    c.sql("UPDATE orders SET item = 'gizmo' WHERE id = 2")
+    c.sql("DELETE FROM orders WHERE id = 1")
+
+    wait_for(
+        "the updated documents",
+        lambda: document(1) is None and document(2) == {"id": 2, "item": "gizmo"},
+    )
+
+
+def workflow_redis(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "redis"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "redis.td")
+
+    def redis(*args: str) -> str:
+        return c.exec("redis", "redis-cli", *args, capture=True).stdout.strip()
+
+    wait_for(
+        "the initial keys",
+        lambda: redis("GET", "orders:1") == '{"id":1,"item":"widget"}'
+        and redis("GET", "orders:2") == '{"id":2,"item":"gadget"}'
+        and redis("HGET", "orders-hash:1", "item") == "widget"
+        and redis("HGET", "orders-hash:2", "item") == "gadget",
+    )
+
+    # The hash sink sets a TTL on the keys it writes, the string sink does not.
+    assert redis("PTTL", "orders:1") == "-1"
+    assert 0 < int(redis("PTTL", "orders-hash:1")) <= 3600 * 1000
+
+    c.sql("UPDATE orders SET item = 'gizmo' WHERE id = 2")
+    c.sql("DELETE FROM orders WHERE id = 1")
+
+    wait_for(
+        "the updated keys",
+        lambda: redis("EXISTS", "orders:1") == "0"
+        and redis("GET", "orders:2") == '{"id":2,"item":"gizmo"}'
+        and redis("EXISTS", "orders-hash:1") == "0"
+        and redis("HGET", "orders-hash:2", "item") == "gizmo",
+    )
+
+
+def workflow_sftp(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "sftp"])
+    c.wait_for_materialized()
+
+    def upload(name: str, contents: str) -> None:
+        c.exec(
+            "sftp", "sh", "-c", f"cat > /home/feeds/inbox/{name}", stdin=contents
+        )
+
+    upload("orders-1.csv", "id,item\n1,widget\n2,gadget\n")
+    upload("notes.txt", "not,an\norder,file\n")
+
+    c.run("testdrive", "sftp.td")
+
+    upload("orders-2.csv", "id,item\n3,gizmo\n")
+
+    c.run("testdrive", "--no-reset", "sftp-new-file.td")
+
+
+def workflow_nats(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "nats"])
+    c.wait_for_materialized()
+
+    # JetStream only accepts messages published to the subjects of a stream.
+    c.run(
+        "nats-box",
+        "nats",
+        "stream",
+        "add",
+        "orders",
+        "--subjects=orders.>",
+        "--defaults",
+        rm=True,
+    )
+
+    c.run("testdrive", "nats.td")
+
+
+def workflow_mqtt(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "mosquitto"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "mqtt.td")
+
+    def retained() -> Dict[str, str]:
+        # Waits two seconds for the retained messages, and then times out
+        # with a non-zero exit code.
+        output = c.exec(
+            "mosquitto",
+            "sh",
+            "-c",
+            "mosquitto_sub -t 'orders/#' -v --retained-only -W 2 || true",
+            capture=True,
+        ).stdout
+        return dict(line.split(" ", 1) for line in output.splitlines())
+
+    wait_for(
+        "the initial retained messages",
+        lambda: retained()
+        == {
+            "orders/1": '{"id":1,"item":"widget"}',
+            "orders/2": '{"id":2,"item":"gadget"}',
+        },
+    )
+
+    c.sql("UPDATE orders SET item = 'gizmo' WHERE id = 2")
+    c.sql("DELETE FROM orders WHERE id = 1")
+
+    # Publishing an empty message clears the retained message of a topic.
+    wait_for(
+        "the updated retained messages",
+        lambda: retained() == {"orders/2": '{"id":2,"item":"gizmo"}'},
+    )
+
+
+def workflow_grpc(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "grpc-server"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "grpc.td")
+
+    def items() -> Dict[int, str]:
+        """Applies the updates that the server received, in timestamp order."""
+        output = c.exec(
+            "grpc-server",
+            "sh",
+            "-c",
+            "cat /tmp/updates.jsonl 2>/dev/null || true",
+            capture=True,
+        ).stdout
+        updates = sorted(
+            (json.loads(line) for line in output.splitlines()),
+            key=lambda update: update["timestamp"],
+        )
+        items = {}
+        for update in updates:
+            id = json.loads(update["key"])["id"]
+            if update["value"] is None:
+                items.pop(id, None)
+            else:
+                items[id] = json.loads(update["value"])["item"]
+        return items
+
+    wait_for("the initial updates", lambda: items() == {1: "widget", 2: "gadget"})
+
+    c.sql("UPDATE orders SET item = 'gizmo' WHERE id = 2")
+    c.sql("DELETE FROM orders WHERE id = 1")
+
+    wait_for("the later updates", lambda: items() == {2: "gizmo"})
+
+
+def workflow_sqs(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "localstack"])
+    c.wait_for_materialized()
+
+    def awslocal(*args: str) -> str:
+        return c.exec("localstack", "awslocal", *args, capture=True).stdout
+
+    queue_url = json.loads(
+        awslocal(
+            "sqs",
+            "create-queue",
+            "--queue-name=orders.fifo",
+            "--attributes=FifoQueue=true",
+        )
+    )["QueueUrl"]
+
+    c.run("testdrive", f"--var=queue-url={queue_url}", "sqs.td")
+
+    received: List[Dict[str, Any]] = []
+
+    def items() -> Dict[int, str]:
+        """Receives the pending messages, and applies all received messages
+        in the order of the queue."""
+        output = awslocal(
+            "sqs",
+            "receive-message",
+            f"--queue-url={queue_url}",
+            "--max-number-of-messages=10",
+        )
+        # A FIFO queue only delivers the later messages of a group once the
+        # earlier ones are deleted.
+        for message in json.loads(output)["Messages"] if output.strip() else []:
+            received.append(json.loads(message["Body"]))
+            awslocal(
+                "sqs",
+                "delete-message",
+                f"--queue-url={queue_url}",
+                f"--receipt-handle={message['ReceiptHandle']}",
+            )
+        items = {}
+        for body in received:
+            id = body["key"]["id"]
+            if body["value"] is None:
+                items.pop(id, None)
+            else:
+                items[id] = body["value"]["item"]
+        return items
+
+    wait_for("the initial messages", lambda: items() == {1: "widget", 2: "gadget"})
+
+    c.sql("UPDATE orders SET item = 'gizmo' WHERE id = 2")
+    c.sql("DELETE FROM orders WHERE id = 1")
+
+    wait_for("the later messages", lambda: items() == {2: "gizmo"})
+
+
+def workflow_default(c: Composition) -> None:
+    workflow_elasticsearch(c)
+    workflow_redis(c)
+    workflow_sftp(c)
+    workflow_nats(c)
+    workflow_mqtt(c)
+    workflow_grpc(c)
+    workflow_sqs(c)
@@ -0,0 +1,106 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Tests for the options of Kafka sinks whose effects are only visible to the
+Kafka broker, like how the messages are compressed."""
+
+import time
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import (
+    Kafka,
+    Materialized,
+    SchemaRegistry,
+    Testdrive,
+    Zookeeper,
+)
+
+SERVICES = [
+    Zookeeper(),
+    Kafka(),
+    SchemaRegistry(),
+    Materialized(),
+    Testdrive(),
+]
+
+# A fixed seed, so that the workflows know the names of the topics that the
+# sinks write to.
+SEED = 1
+
+
+def start(c: Composition) -> None:
+    c.start_and_wait_for_tcp(
+        services=["zookeeper", "kafka", "schema-registry", "materialized"]
+    )
+    c.wait_for_materialized()
+
+
+def dump_log(c: Composition, topic: str, partition: int = 0) -> str:
+    """Returns the description of the batches in the first log segment of a
+    partition of `topic`."""
+    return c.exec(
+        "kafka",
+        "kafka-dump-log",
+        f"--files=/var/lib/kafka/data/{topic}-{partition}/00000000000000000000.log",
+        capture=True,
+    ).stdout
+
+
+def workflow_compression(c: Composition) -> None:
+    start(c)
+
+    c.run("testdrive", f"--seed={SEED}", "compression.td")
+
+    for codec in ["gzip", "snappy", "lz4", "zstd"]:
+        log = dump_log(c, f"testdrive-compression-{codec}-{SEED}").lower()
+        assert (
+            f"compresscodec: {codec}" in log
+        ), f"expected {codec}-compressed batches, got:\n{log}"
+
+    log = dump_log(c, f"testdrive-compression-none-{SEED}").lower()
+    for codec in ["gzip", "snappy", "lz4", "zstd"]:
+        assert f"compresscodec: {codec}" not in log, f"unexpected {codec} batches"
+
+
+def workflow_rate_limit(c: Composition) -> None:
+    start(c)
+
+    # The sink may send a second's worth of messages at once, and then has
+    # to wait at least five seconds for the other 25.
+    started = time.monotonic()
+    c.run("testdrive", f"--seed={SEED}", "rate-limit.td")
+    elapsed = time.monotonic() - started
+    assert elapsed >= 5, f"sink produced 30 messages in {elapsed:.1f}s"
+
+
+def workflow_alter(c: Composition) -> None:
+    start(c)
+
+    c.run("testdrive", f"--seed={SEED}", "alter.td")
+
+    topic = f"testdrive-alter-{SEED}"
+    log = dump_log(c, topic).lower()
+    assert "compresscodec: gzip" in log, f"expected gzip-compressed batches, got:\n{log}"
+
+    config = c.exec(
+        "kafka",
+        "kafka-configs",
+        "--bootstrap-server=kafka:9092",
+        "--entity-type=topics",
+        f"--entity-name={topic}",
+        "--describe",
+        capture=True,
+    ).stdout
+    assert "retention.ms=3600000" in config, f"unexpected topic config:\n{config}"
+
+
+def workflow_default(c: Composition) -> None:
+    workflow_compression(c)
+    workflow_rate_limit(c)
+    workflow_alter(c)
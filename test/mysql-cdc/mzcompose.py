@@ -0,0 +1,82 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Tests for MySQL sources, which snapshot the tables, replicate the
+transactions of several servers, and resume after a restart."""
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import Materialized, MySql, Testdrive
+
+MYSQL_ROOT_PASSWORD = "p@ssw0rd"
+
+# Transactions that originated on other servers are applied with an explicit
+# GTID, like a replica applies them.
+SERVER_A = "3e11fa47-71ca-11e1-9e33-c80aa9429562"
+SERVER_C = "c0ffee00-1d4a-11ee-8c90-0242ac120002"
+
+SERVICES = [
+    Materialized(),
+    Testdrive(no_reset=True),
+    MySql(
+        mysql_root_password=MYSQL_ROOT_PASSWORD,
+        command="\n".join(
+            [
+                "--default-authentication-plugin=mysql_native_password",
+                "--secure-file-priv=/var/lib/mysql-files",
+                "--gtid-mode=ON",
+                "--enforce-gtid-consistency=ON",
+            ]
+        ),
+    ),
+]
+
+
+def mysql(c: Composition, sql: str) -> None:
+    c.exec(
+        "mysql",
+        "mysql",
+        "--user=root",
+        f"--password={MYSQL_ROOT_PASSWORD}",
+        "test",
+        f"--execute={sql}",
+    )
+
+
+def workflow_default(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "mysql"])
+    c.wait_for_materialized()
+
+    c.run(
+        "testdrive",
+        f"--var=mysql-root-password={MYSQL_ROOT_PASSWORD}",
+        "mysql-cdc.td",
+    )
+
+    # The source resumes from the transactions it has read of each server,
+    # including servers whose transactions it is yet to see.
+    c.kill("materialized")
+    mysql(
+        c,
+        f"""
+        INSERT INTO orders VALUES (4, 'thingamajig');
+        SET gtid_next = '{SERVER_A}:3';
+        UPDATE orders SET item = 'whatsit' WHERE id = 3;
+        SET gtid_next = '{SERVER_C}:1';
+        INSERT INTO orders VALUES (5, 'widget');
+        SET gtid_next = 'AUTOMATIC';
+        """,
+    )
+    c.up("materialized")
+    c.wait_for_materialized()
+
+    c.run(
+        "testdrive",
+        f"--var=mysql-root-password={MYSQL_ROOT_PASSWORD}",
+        "restart.td",
+    )
@@ -0,0 +1,36 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Tests for SQL Server sources, which snapshot the tables, replicate their
+changes, and resume after a restart."""
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import Materialized, SqlServer, Testdrive
+
+SA_PASSWORD = "AAbb!@" + "sqlserver1"
+
+SERVICES = [
+    Materialized(),
+    Testdrive(no_reset=True, default_timeout="300s"),
+    SqlServer(sa_password=SA_PASSWORD),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "sql-server"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", f"--var=sa-password={SA_PASSWORD}", "sql-server-cdc.td")
+
+    # Restarts while the source may still be reading the latest changes.
+    c.run("testdrive", f"--var=sa-password={SA_PASSWORD}", "before-restart.td")
+    c.kill("materialized")
+    c.up("materialized")
+    c.wait_for_materialized()
+    c.run("testdrive", f"--var=sa-password={SA_PASSWORD}", "after-restart.td")